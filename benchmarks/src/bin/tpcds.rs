@@ -0,0 +1,543 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmark derived from TPC-DS. This is not an official TPC-DS benchmark.
+
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Instant, SystemTime},
+};
+
+use datafusion::arrow::{record_batch::RecordBatch, util::pretty};
+use datafusion::common::fingerprint::Fingerprint;
+use datafusion::datasource::file_format::csv::{CsvFormat, DEFAULT_CSV_EXTENSION};
+use datafusion::datasource::file_format::parquet::{
+    ParquetFormat, DEFAULT_PARQUET_EXTENSION,
+};
+use datafusion::datasource::file_format::FileFormat;
+use datafusion::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::parquet::basic::Compression;
+use datafusion::physical_plan::{collect, displayable};
+use datafusion::prelude::*;
+use datafusion::DATAFUSION_VERSION;
+use datafusion_benchmarks::tpcds::*;
+use serde::Serialize;
+use structopt::StructOpt;
+
+#[cfg(feature = "snmalloc")]
+#[global_allocator]
+static ALLOC: snmalloc_rs::SnMalloc = snmalloc_rs::SnMalloc;
+
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+#[derive(Debug, StructOpt, Clone)]
+struct DataFusionBenchmarkOpt {
+    /// Query number. If not specified, runs all queries
+    #[structopt(short, long)]
+    query: Option<usize>,
+
+    /// Activate debug mode to see query results
+    #[structopt(short, long)]
+    debug: bool,
+
+    /// Number of iterations of each test run
+    #[structopt(short = "i", long = "iterations", default_value = "3")]
+    iterations: usize,
+
+    /// Number of partitions to process in parallel
+    #[structopt(short = "n", long = "partitions", default_value = "2")]
+    partitions: usize,
+
+    /// Batch size when reading CSV or Parquet files
+    #[structopt(short = "s", long = "batch-size", default_value = "8192")]
+    batch_size: usize,
+
+    /// Path to data files
+    #[structopt(parse(from_os_str), required = true, short = "p", long = "path")]
+    path: PathBuf,
+
+    /// Path to the queries generated by `dsqgen`
+    #[structopt(
+        parse(from_os_str),
+        long = "query-path",
+        default_value = "./data/tpcds/queries"
+    )]
+    query_path: PathBuf,
+
+    /// File format: `dat`, `csv` or `parquet`
+    #[structopt(short = "f", long = "format", default_value = "dat")]
+    file_format: String,
+
+    /// Path to output directory where JSON summary file should be written to
+    #[structopt(parse(from_os_str), short = "o", long = "output")]
+    output_path: Option<PathBuf>,
+
+    /// Whether to disable collection of statistics (and cost based optimizations) or not.
+    #[structopt(short = "S", long = "disable-statistics")]
+    disable_statistics: bool,
+}
+
+#[derive(Debug, StructOpt)]
+struct ConvertOpt {
+    /// Path to dat files
+    #[structopt(parse(from_os_str), required = true, short = "i", long = "input")]
+    input_path: PathBuf,
+
+    /// Output path
+    #[structopt(parse(from_os_str), required = true, short = "o", long = "output")]
+    output_path: PathBuf,
+
+    /// Output file format: `csv` or `parquet`
+    #[structopt(short = "f", long = "format")]
+    file_format: String,
+
+    /// Compression to use when writing Parquet files
+    #[structopt(short = "c", long = "compression", default_value = "zstd")]
+    compression: String,
+
+    /// Number of partitions to produce
+    #[structopt(short = "n", long = "partitions", default_value = "1")]
+    partitions: usize,
+
+    /// Batch size when reading CSV or Parquet files
+    #[structopt(short = "s", long = "batch-size", default_value = "8192")]
+    batch_size: usize,
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "benchmark command")]
+enum BenchmarkSubCommandOpt {
+    #[structopt(name = "datafusion")]
+    DataFusionBenchmark(DataFusionBenchmarkOpt),
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "TPC-DS", about = "TPC-DS Benchmarks.")]
+enum TpcdsOpt {
+    Benchmark(BenchmarkSubCommandOpt),
+    Convert(ConvertOpt),
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    use BenchmarkSubCommandOpt::*;
+
+    env_logger::init();
+    match TpcdsOpt::from_args() {
+        TpcdsOpt::Benchmark(DataFusionBenchmark(opt)) => benchmark_datafusion(opt).await,
+        TpcdsOpt::Convert(opt) => {
+            let compression = match opt.compression.as_str() {
+                "none" => Compression::UNCOMPRESSED,
+                "snappy" => Compression::SNAPPY,
+                "brotli" => Compression::BROTLI,
+                "gzip" => Compression::GZIP,
+                "lz4" => Compression::LZ4,
+                "lz0" => Compression::LZO,
+                "zstd" => Compression::ZSTD,
+                other => {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "Invalid compression format: {other}"
+                    )));
+                }
+            };
+            convert_dat(
+                opt.input_path.to_str().unwrap(),
+                opt.output_path.to_str().unwrap(),
+                &opt.file_format,
+                opt.partitions,
+                opt.batch_size,
+                compression,
+            )
+            .await
+        }
+    }
+}
+
+async fn benchmark_datafusion(opt: DataFusionBenchmarkOpt) -> Result<()> {
+    println!("Running benchmarks with the following options: {opt:?}");
+    let query_range = match opt.query {
+        Some(query_id) => query_id..=query_id,
+        None => TPCDS_QUERY_START_ID..=TPCDS_QUERY_END_ID,
+    };
+
+    let config = SessionConfig::new()
+        .with_target_partitions(opt.partitions)
+        .with_batch_size(opt.batch_size)
+        .with_collect_statistics(!opt.disable_statistics);
+    let ctx = SessionContext::with_config(config);
+    register_tables(&opt, &ctx).await?;
+
+    let mut benchmark_run = BenchmarkRun::new();
+    for query_id in query_range {
+        let query_run = benchmark_query(&opt, &ctx, query_id).await;
+        if let Some(error) = &query_run.error {
+            println!("Query {query_id} failed: {error}");
+        }
+        benchmark_run.add_query(query_run);
+    }
+
+    let failed = benchmark_run
+        .queries
+        .iter()
+        .filter(|q| q.error.is_some())
+        .count();
+    println!(
+        "{} queries succeeded, {failed} failed",
+        benchmark_run.queries.len() - failed
+    );
+
+    if let Some(path) = &opt.output_path {
+        write_summary_json(&mut benchmark_run, path)?;
+    }
+    Ok(())
+}
+
+/// Runs a query, recording rather than returning any error so that the
+/// remaining queries still run
+async fn benchmark_query(
+    opt: &DataFusionBenchmarkOpt,
+    ctx: &SessionContext,
+    query_id: usize,
+) -> QueryRun {
+    let mut query_run = QueryRun::new(query_id);
+    if let Err(e) = run_query(opt, ctx, query_id, &mut query_run).await {
+        query_run.error = Some(e.to_string());
+    }
+    query_run
+}
+
+async fn run_query(
+    opt: &DataFusionBenchmarkOpt,
+    ctx: &SessionContext,
+    query_id: usize,
+    query_run: &mut QueryRun,
+) -> Result<()> {
+    let sql = get_tpcds_query_sql(opt.query_path.to_str().unwrap(), query_id)?;
+
+    let mut millis = vec![];
+    for i in 0..opt.iterations {
+        let start = Instant::now();
+
+        // queries with several statements return the result of the last one
+        let mut result = vec![];
+        let mut plan_hashes = vec![];
+        for query in &sql {
+            let (batches, hashes) = execute_query(ctx, query, opt.debug).await?;
+            result = batches;
+            plan_hashes.push(hashes);
+        }
+
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+        millis.push(elapsed);
+        let row_count = result.iter().map(|b| b.num_rows()).sum();
+        println!(
+            "Query {query_id} iteration {i} took {elapsed:.1} ms and returned {row_count} rows"
+        );
+        query_run.add_result(elapsed, row_count);
+        query_run.plans = plan_hashes;
+    }
+
+    let avg = millis.iter().sum::<f64>() / millis.len() as f64;
+    println!("Query {query_id} avg time: {avg:.2} ms");
+    Ok(())
+}
+
+async fn register_tables(
+    opt: &DataFusionBenchmarkOpt,
+    ctx: &SessionContext,
+) -> Result<()> {
+    for table in TPCDS_TABLES {
+        let table_provider = get_table(
+            ctx,
+            opt.path.to_str().unwrap(),
+            table,
+            opt.file_format.as_str(),
+            opt.partitions,
+        )
+        .await?;
+        ctx.register_table(*table, table_provider)?;
+    }
+    Ok(())
+}
+
+fn write_summary_json(benchmark_run: &mut BenchmarkRun, path: &Path) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(&benchmark_run).expect("summary is serializable");
+    let filename = format!("tpcds-summary--{}.json", benchmark_run.context.start_time);
+    let path = path.join(filename);
+    println!(
+        "Writing summary file to {}",
+        path.as_os_str().to_str().unwrap()
+    );
+    let mut file = File::create(path)?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+/// Executes a single statement, returning its results and the hashes of its
+/// optimized plans
+async fn execute_query(
+    ctx: &SessionContext,
+    sql: &str,
+    debug: bool,
+) -> Result<(Vec<RecordBatch>, PlanHashes)> {
+    let plan = ctx.sql(sql).await?;
+    let (state, plan) = plan.into_parts();
+
+    let plan = state.optimize(&plan)?;
+    if debug {
+        println!("=== Optimized logical plan ===\n{plan:?}\n");
+    }
+    let physical_plan = state.create_physical_plan(&plan).await?;
+    let physical_plan_display = displayable(physical_plan.as_ref()).indent().to_string();
+    if debug {
+        println!("=== Physical plan ===\n{physical_plan_display}\n");
+    }
+    let hashes = PlanHashes {
+        logical_plan: plan_hash(&format!("{}", plan.display_indent())),
+        physical_plan: plan_hash(&physical_plan_display),
+    };
+
+    let result = collect(physical_plan, state.task_ctx()).await?;
+    if debug && !result.is_empty() {
+        // do not call print_batches if there are no batches as the result is confusing
+        // and makes it look like there is a batch with no columns
+        pretty::print_batches(&result)?;
+    }
+    Ok((result, hashes))
+}
+
+/// Hashes the display of a plan with a [`Fingerprint`], which unlike the std
+/// hashers is stable across Rust releases so summaries can be compared over
+/// time
+fn plan_hash(plan: &str) -> String {
+    Fingerprint::of(plan.as_bytes()).to_string()
+}
+
+async fn get_table(
+    ctx: &SessionContext,
+    path: &str,
+    table: &str,
+    table_format: &str,
+    target_partitions: usize,
+) -> Result<Arc<dyn TableProvider>> {
+    // Obtain a snapshot of the SessionState
+    let state = ctx.state();
+    let (format, path, extension): (Arc<dyn FileFormat>, String, &'static str) =
+        match table_format {
+            // dsdgen creates .dat ('|' delimited) files without header
+            "dat" => {
+                let path = format!("{path}/{table}.dat");
+
+                let format = CsvFormat::default()
+                    .with_delimiter(b'|')
+                    .with_has_header(false);
+
+                (Arc::new(format), path, ".dat")
+            }
+            "csv" => {
+                let path = format!("{path}/{table}");
+                let format = CsvFormat::default()
+                    .with_delimiter(b',')
+                    .with_has_header(true);
+
+                (Arc::new(format), path, DEFAULT_CSV_EXTENSION)
+            }
+            "parquet" => {
+                let path = format!("{path}/{table}");
+                let format = ParquetFormat::default().with_enable_pruning(Some(true));
+
+                (Arc::new(format), path, DEFAULT_PARQUET_EXTENSION)
+            }
+            other => {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "Invalid file format: {other}"
+                )));
+            }
+        };
+
+    let options = ListingOptions::new(format)
+        .with_file_extension(extension)
+        .with_target_partitions(target_partitions)
+        .with_collect_stat(state.config().collect_statistics());
+
+    let table_path = ListingTableUrl::parse(path)?;
+    let config = ListingTableConfig::new(table_path).with_listing_options(options);
+
+    let config = match table_format {
+        "parquet" => config.infer_schema(&state).await?,
+        "dat" => config.with_schema(Arc::new(get_dat_tpcds_table_schema(table))),
+        _ => config.with_schema(Arc::new(get_tpcds_table_schema(table))),
+    };
+
+    Ok(Arc::new(ListingTable::try_new(config)?))
+}
+
+#[derive(Debug, Serialize)]
+struct RunContext {
+    /// Benchmark crate version
+    benchmark_version: String,
+    /// DataFusion crate version
+    datafusion_version: String,
+    /// Number of CPU cores
+    num_cpus: usize,
+    /// Start time
+    start_time: u64,
+    /// CLI arguments
+    arguments: Vec<String>,
+}
+
+impl RunContext {
+    fn new() -> Self {
+        Self {
+            benchmark_version: env!("CARGO_PKG_VERSION").to_owned(),
+            datafusion_version: DATAFUSION_VERSION.to_owned(),
+            num_cpus: num_cpus::get(),
+            start_time: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("current time is later than UNIX_EPOCH")
+                .as_secs(),
+            arguments: std::env::args().skip(1).collect::<Vec<String>>(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct BenchmarkRun {
+    /// Information regarding the environment in which the benchmark was run
+    context: RunContext,
+    /// Per-query summaries
+    queries: Vec<QueryRun>,
+}
+
+impl BenchmarkRun {
+    fn new() -> Self {
+        Self {
+            context: RunContext::new(),
+            queries: vec![],
+        }
+    }
+
+    fn add_query(&mut self, query: QueryRun) {
+        self.queries.push(query)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct QueryRun {
+    /// query number
+    query: usize,
+    /// list of individual run times and row counts
+    iterations: Vec<QueryResult>,
+    /// plan hashes of each statement of the query
+    plans: Vec<PlanHashes>,
+    /// error message if the query failed
+    error: Option<String>,
+    /// Start time
+    start_time: u64,
+}
+
+impl QueryRun {
+    fn new(query: usize) -> Self {
+        Self {
+            query,
+            iterations: vec![],
+            plans: vec![],
+            error: None,
+            start_time: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("current time is later than UNIX_EPOCH")
+                .as_secs(),
+        }
+    }
+
+    fn add_result(&mut self, elapsed: f64, row_count: usize) {
+        self.iterations.push(QueryResult { elapsed, row_count })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct QueryResult {
+    elapsed: f64,
+    row_count: usize,
+}
+
+/// Hashes of the optimized plans of a statement. The physical plan includes
+/// the paths of the data files, so its hash is only comparable between runs
+/// over the same data.
+#[derive(Debug, Serialize)]
+struct PlanHashes {
+    logical_plan: String,
+    physical_plan: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::datasource::MemTable;
+
+    #[test]
+    fn query_statements() -> Result<()> {
+        let dir = std::env::temp_dir().join("datafusion_tpcds_queries");
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(
+            dir.join("q14.sql"),
+            "-- start query 14 in stream 0 using template query14.tpl\n\
+             select 1;\n\
+             select 2;\n\
+             -- end query 14 in stream 0 using template query14.tpl\n",
+        )?;
+
+        let sql = get_tpcds_query_sql(dir.to_str().unwrap(), 14)?;
+        assert_eq!(sql, vec!["select 1", "select 2"]);
+        assert!(get_tpcds_query_sql(dir.to_str().unwrap(), 13).is_err());
+        assert!(get_tpcds_query_sql(dir.to_str().unwrap(), 100).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn plan_hashes() -> Result<()> {
+        let ctx = SessionContext::new();
+        for table in TPCDS_TABLES {
+            let schema = Arc::new(get_tpcds_table_schema(table));
+            let provider = MemTable::try_new(schema, vec![vec![]])?;
+            ctx.register_table(*table, Arc::new(provider))?;
+        }
+
+        let sql = "select d_year, sum(ss_net_paid) from store_sales \
+                   join date_dim on ss_sold_date_sk = d_date_sk group by d_year";
+        let (batches, hashes) = execute_query(&ctx, sql, false).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+        let (_, same) = execute_query(&ctx, sql, false).await?;
+        assert_eq!(hashes.logical_plan, same.logical_plan);
+        assert_eq!(hashes.physical_plan, same.physical_plan);
+
+        let (_, other) =
+            execute_query(&ctx, "select count(*) from store_sales", false).await?;
+        assert_ne!(hashes.logical_plan, other.logical_plan);
+        Ok(())
+    }
+}
@@ -462,7 +462,7 @@ fn col_to_scalar(column: &ArrayRef, row_index: usize) -> ScalarValue {
         }
         DataType::Utf8 => {
             let array = as_string_array(column).unwrap();
-            ScalarValue::Utf8(Some(array.value(row_index).to_string()))
+            ScalarValue::Utf8(Some(array.value(row_index).into()))
         }
         other => panic!("unexpected data type in benchmark: {other}"),
     }
@@ -0,0 +1,66 @@
+#!/bin/bash
+# Licensed to the Apache Software Foundation (ASF) under one
+# or more contributor license agreements.  See the NOTICE file
+# distributed with this work for additional information
+# regarding copyright ownership.  The ASF licenses this file
+# to you under the Apache License, Version 2.0 (the
+# "License"); you may not use this file except in compliance
+# with the License.  You may obtain a copy of the License at
+#
+#   http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing,
+# software distributed under the License is distributed on an
+# "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+# KIND, either express or implied.  See the License for the
+# specific language governing permissions and limitations
+# under the License.
+
+# Generates TPC-DS data and queries into ./data/tpcds using the TPC-DS kit,
+# which is built from source and requires gcc, make, flex and bison
+#
+# Usage: ./tpcds-gen.sh <scale_factor>
+
+set -e
+
+SCALE_FACTOR=${1:-1}
+KIT=./data/tpcds-kit
+OUTPUT=`pwd`/data/tpcds
+
+mkdir -p $OUTPUT/queries
+
+if test -d "$KIT"; then
+    echo "$KIT exists."
+else
+    git clone --depth 1 https://github.com/gregrahn/tpcds-kit.git $KIT
+    make -C $KIT/tools OS=LINUX
+fi
+
+# Generate data into the ./data/tpcds directory if it does not already exist
+FILE=$OUTPUT/store_sales.dat
+if test -f "$FILE"; then
+    echo "$FILE exists."
+else
+    pushd $KIT/tools
+    ./dsdgen -scale $SCALE_FACTOR -dir $OUTPUT -force Y -quiet Y
+    popd
+    # dsdgen writes latin-1, which is a superset of ascii
+    for f in $OUTPUT/*.dat; do
+        iconv -f ISO-8859-1 -t UTF-8 $f > $f.utf8 && mv $f.utf8 $f
+    done
+    ls -l $OUTPUT
+fi
+
+# Generate the 99 queries, substituting the parameters for the scale factor
+FILE=$OUTPUT/queries/q99.sql
+if test -f "$FILE"; then
+    echo "$FILE exists."
+else
+    pushd $KIT/tools
+    for i in `seq 1 99`; do
+        ./dsqgen -directory ../query_templates -template query$i.tpl \
+            -dialect netezza -scale $SCALE_FACTOR -filter Y -quiet Y \
+            > $OUTPUT/queries/q$i.sql
+    done
+    popd
+fi
@@ -45,6 +45,10 @@ pub enum Command {
     SearchFunctions(String),
     QuietMode(Option<bool>),
     OutputFormat(Option<String>),
+    Output(Option<String>),
+    Timing(Option<bool>),
+    Watch(f64),
+    History(Option<String>),
 }
 
 pub enum OutputFormat {
@@ -59,16 +63,21 @@ impl Command {
     ) -> Result<()> {
         let now = Instant::now();
         match self {
-            Self::Help => print_options.print_batches(&[all_commands_info()], now),
+            Self::Help => {
+                let batch = all_commands_info();
+                print_options.print_batches(&batch.schema(), &[batch], now)
+            }
             Self::ListTables => {
                 let df = ctx.sql("SHOW TABLES").await?;
+                let schema = Schema::from(df.schema());
                 let batches = df.collect().await?;
-                print_options.print_batches(&batches, now)
+                print_options.print_batches(&schema, &batches, now)
             }
             Self::DescribeTable(name) => {
                 let df = ctx.sql(&format!("SHOW COLUMNS FROM {}", name)).await?;
+                let schema = Schema::from(df.schema());
                 let batches = df.collect().await?;
-                print_options.print_batches(&batches, now)
+                print_options.print_batches(&schema, &batches, now)
             }
             Self::Include(filename) => {
                 if let Some(filename) = filename {
@@ -101,6 +110,24 @@ impl Command {
                 }
                 Ok(())
             }
+            Self::Timing(timing) => {
+                // like psql, a bare \timing toggles the timing
+                print_options.timing = timing.unwrap_or(!print_options.timing);
+                println!(
+                    "Timing is {}",
+                    if print_options.timing { "on" } else { "off" }
+                );
+                Ok(())
+            }
+            Self::Output(filename) => {
+                print_options.set_output(filename.clone())?;
+                if let Some(filename) = filename {
+                    println!("Output is written to {:?}.", filename);
+                } else {
+                    println!("Output is written to stdout.");
+                }
+                Ok(())
+            }
             Self::Quit => Err(DataFusionError::Execution(
                 "Unexpected quit, this should be handled outside".into(),
             )),
@@ -118,6 +145,12 @@ impl Command {
             Self::OutputFormat(_) => Err(DataFusionError::Execution(
                 "Unexpected change output format, this should be handled outside".into(),
             )),
+            Self::Watch(_) => Err(DataFusionError::Execution(
+                "Unexpected watch, this should be handled outside".into(),
+            )),
+            Self::History(_) => Err(DataFusionError::Execution(
+                "Unexpected history, this should be handled outside".into(),
+            )),
         }
     }
 
@@ -136,11 +169,27 @@ impl Command {
             Self::OutputFormat(_) => {
                 ("\\pset [NAME [VALUE]]", "set table output option\n(format)")
             }
+            Self::Output(_) => (
+                "\\o [filename]",
+                "send query results to filename, or stdout if omitted",
+            ),
+            Self::Timing(_) => (
+                "\\timing [on|off]",
+                "toggle query timing, or turn it on or off",
+            ),
+            Self::Watch(_) => (
+                "\\watch [seconds]",
+                "re-run the last query every seconds (default 2) until ctrl-c",
+            ),
+            Self::History(_) => (
+                "\\history [pattern]",
+                "list the history, or search it for pattern",
+            ),
         }
     }
 }
 
-const ALL_COMMANDS: [Command; 9] = [
+const ALL_COMMANDS: [Command; 13] = [
     Command::ListTables,
     Command::DescribeTable(String::new()),
     Command::Quit,
@@ -150,8 +199,15 @@ const ALL_COMMANDS: [Command; 9] = [
     Command::SearchFunctions(String::new()),
     Command::QuietMode(None),
     Command::OutputFormat(None),
+    Command::Output(None),
+    Command::Timing(None),
+    Command::Watch(DEFAULT_WATCH_INTERVAL),
+    Command::History(None),
 ];
 
+/// Interval in seconds used by `\watch` when none is given
+const DEFAULT_WATCH_INTERVAL: f64 = 2.0;
+
 fn all_commands_info() -> RecordBatch {
     let schema = Arc::new(Schema::new(vec![
         Field::new("Command", DataType::Utf8, false),
@@ -200,6 +256,24 @@ impl FromStr for Command {
                 Self::OutputFormat(Some(subcommand.to_string()))
             }
             ("pset", None) => Self::OutputFormat(None),
+            ("timing", Some("true" | "t" | "yes" | "y" | "on")) => {
+                Self::Timing(Some(true))
+            }
+            ("timing", Some("false" | "f" | "no" | "n" | "off")) => {
+                Self::Timing(Some(false))
+            }
+            ("timing", None) => Self::Timing(None),
+            ("watch", None) => Self::Watch(DEFAULT_WATCH_INTERVAL),
+            ("watch", Some(interval)) => match interval.parse::<f64>() {
+                Ok(interval) if interval > 0.0 && interval.is_finite() => {
+                    Self::Watch(interval)
+                }
+                _ => return Err(()),
+            },
+            ("history", None) => Self::History(None),
+            ("history", Some(pattern)) => Self::History(Some(pattern.to_owned())),
+            ("o", None) => Self::Output(None),
+            ("o", Some(filename)) => Self::Output(Some(filename.to_owned())),
             _ => return Err(()),
         })
     }
@@ -240,3 +314,25 @@ impl OutputFormat {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(command: &str) -> Option<Option<bool>> {
+        match command.parse() {
+            Ok(Command::Timing(timing)) => Some(timing),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parse_timing() {
+        assert_eq!(timing("timing"), Some(None));
+        assert_eq!(timing("timing on"), Some(Some(true)));
+        assert_eq!(timing("timing true"), Some(Some(true)));
+        assert_eq!(timing("timing off"), Some(Some(false)));
+        assert_eq!(timing("timing f"), Some(Some(false)));
+        assert_eq!(timing("timing maybe"), None);
+    }
+}
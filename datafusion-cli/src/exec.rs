@@ -20,16 +20,22 @@
 use crate::{
     command::{Command, OutputFormat},
     helper::CliHelper,
+    object_storage::build_object_store,
     print_options::PrintOptions,
 };
-use datafusion::error::Result;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::{CreateExternalTable, LogicalPlan};
 use datafusion::prelude::SessionContext;
 use rustyline::error::ReadlineError;
+use rustyline::history::History;
 use rustyline::Editor;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufReader;
-use std::time::Instant;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use url::Url;
 
 /// run and execute SQL statements and commands from a file, against a context with the given print options
 pub async fn exec_from_lines(
@@ -94,9 +100,11 @@ pub async fn exec_from_repl(
 ) -> rustyline::Result<()> {
     let mut rl = Editor::<CliHelper>::new()?;
     rl.set_helper(Some(CliHelper::default()));
-    rl.load_history(".history").ok();
+    let history_file = history_file();
+    rl.load_history(&history_file).ok();
 
     let mut print_options = print_options.clone();
+    let mut last_query: Option<String> = None;
 
     loop {
         match rl.readline("❯ ") {
@@ -124,6 +132,21 @@ pub async fn exec_from_repl(
                                 println!("Output format is {:?}.", print_options.format);
                             }
                         }
+                        Command::Watch(interval) => {
+                            if let Some(query) = &last_query {
+                                if let Err(e) =
+                                    exec_watch(ctx, &print_options, query, *interval)
+                                        .await
+                                {
+                                    eprintln!("{:?}", e)
+                                }
+                            } else {
+                                eprintln!("There is no query to watch");
+                            }
+                        }
+                        Command::History(pattern) => {
+                            print_history(rl.history(), pattern.as_deref())
+                        }
                         _ => {
                             if let Err(e) = cmd.execute(ctx, &mut print_options).await {
                                 eprintln!("{}", e)
@@ -136,10 +159,11 @@ pub async fn exec_from_repl(
             }
             Ok(line) => {
                 rl.add_history_entry(line.trim_end());
-                match exec_and_print(ctx, &print_options, line).await {
+                match exec_and_print(ctx, &print_options, line.clone()).await {
                     Ok(_) => {}
                     Err(err) => eprintln!("{:?}", err),
                 }
+                last_query = Some(line);
             }
             Err(ReadlineError::Interrupted) => {
                 println!("^C");
@@ -156,7 +180,48 @@ pub async fn exec_from_repl(
         }
     }
 
-    rl.save_history(".history")
+    // complete the Arrow file written to the output file, if any
+    if let Err(e) = print_options.finish_output() {
+        eprintln!("{}", e)
+    }
+    rl.save_history(&history_file)
+}
+
+/// history is kept in the home directory so that it is shared between sessions
+fn history_file() -> PathBuf {
+    dirs::home_dir()
+        .map(|home| home.join(".datafusion_history"))
+        .unwrap_or_else(|| PathBuf::from(".history"))
+}
+
+/// print the history entries containing `pattern`, or all entries if `None`
+fn print_history(history: &History, pattern: Option<&str>) {
+    for (i, entry) in history.iter().enumerate() {
+        if pattern.map_or(true, |pattern| entry.contains(pattern)) {
+            println!("{:>5}  {}", i + 1, entry);
+        }
+    }
+}
+
+/// re-run `sql` every `interval` seconds until interrupted with ctrl-c
+async fn exec_watch(
+    ctx: &mut SessionContext,
+    print_options: &PrintOptions,
+    sql: &str,
+    interval: f64,
+) -> Result<()> {
+    let interval = Duration::from_secs_f64(interval);
+    loop {
+        let iteration = async {
+            exec_and_print(ctx, print_options, sql.to_owned()).await?;
+            tokio::time::sleep(interval).await;
+            Ok::<_, DataFusionError>(())
+        };
+        tokio::select! {
+            result = iteration => result?,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
 }
 
 async fn exec_and_print(
@@ -165,9 +230,36 @@ async fn exec_and_print(
     sql: String,
 ) -> Result<()> {
     let now = Instant::now();
-    let df = ctx.sql(&sql).await?;
+    let plan = ctx.state().create_logical_plan(&sql).await?;
+    // the object store of an external table is registered before the table
+    // is created, which infers its schema from the files
+    if let LogicalPlan::CreateExternalTable(cmd) = &plan {
+        register_object_store(ctx, cmd)?;
+    }
+    let df = ctx.execute_logical_plan(plan).await?;
+    let schema = Schema::from(df.schema());
     let results = df.collect().await?;
-    print_options.print_batches(&results, now)?;
+    print_options.print_batches(&schema, &results, now)?;
+
+    Ok(())
+}
 
+/// Registers an object store for the location of `cmd` configured with its
+/// `OPTIONS`, replacing any store previously registered for the same bucket
+fn register_object_store(ctx: &SessionContext, cmd: &CreateExternalTable) -> Result<()> {
+    let url = match Url::parse(&cmd.location) {
+        Ok(url) => url,
+        // local paths are not urls
+        Err(_) => return Ok(()),
+    };
+    let store = match url.scheme() {
+        "s3" | "gcs" | "az" => build_object_store(&url, &cmd.options)?,
+        _ => return Ok(()),
+    };
+    ctx.runtime_env().register_object_store(
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        store,
+    );
     Ok(())
 }
@@ -24,5 +24,6 @@ pub mod exec;
 pub mod functions;
 pub mod helper;
 pub mod object_storage;
+pub mod parquet_metadata;
 pub mod print_format;
 pub mod print_options;
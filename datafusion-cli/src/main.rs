@@ -23,6 +23,7 @@ use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
 use datafusion::prelude::SessionContext;
 use datafusion_cli::catalog::DynamicFileCatalog;
 use datafusion_cli::object_storage::DatafusionCliObjectStoreProvider;
+use datafusion_cli::parquet_metadata::register_parquet_functions;
 use datafusion_cli::{
     exec, print_format::PrintFormat, print_options::PrintOptions, DATAFUSION_CLI_VERSION,
 };
@@ -112,11 +113,10 @@ pub async fn main() -> Result<()> {
         ctx.state().catalog_list(),
         ctx.state_weak_ref(),
     )));
+    // install the table functions describing the footer of parquet files
+    register_parquet_functions(&ctx);
 
-    let mut print_options = PrintOptions {
-        format: args.format,
-        quiet: args.quiet,
-    };
+    let mut print_options = PrintOptions::new(args.format, args.quiet);
 
     let files = args.file;
     let rc = match args.rc {
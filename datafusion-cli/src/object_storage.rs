@@ -16,16 +16,20 @@
 // under the License.
 
 use datafusion::error::Result;
-use std::{env, str::FromStr, sync::Arc};
+use std::{collections::HashMap, env, str::FromStr, sync::Arc};
 
 use datafusion::{datasource::object_store::ObjectStoreProvider, error::DataFusionError};
-use object_store::{aws::AmazonS3Builder, gcp::GoogleCloudStorageBuilder};
+use object_store::{
+    aws::AmazonS3Builder, azure::MicrosoftAzureBuilder, gcp::GoogleCloudStorageBuilder,
+    ObjectStore,
+};
 use url::Url;
 
 #[derive(Debug, PartialEq, Eq, clap::ArgEnum, Clone)]
 pub enum ObjectStoreScheme {
     S3,
     GCS,
+    Azure,
 }
 
 impl FromStr for ObjectStoreScheme {
@@ -35,6 +39,7 @@ impl FromStr for ObjectStoreScheme {
         match input {
             "s3" => Ok(ObjectStoreScheme::S3),
             "gcs" => Ok(ObjectStoreScheme::GCS),
+            "az" => Ok(ObjectStoreScheme::Azure),
             _ => Err(DataFusionError::Execution(format!(
                 "Unsupported object store scheme {}",
                 input
@@ -46,37 +51,160 @@ impl FromStr for ObjectStoreScheme {
 #[derive(Debug)]
 pub struct DatafusionCliObjectStoreProvider {}
 
-/// ObjectStoreProvider for S3 and GCS
+/// ObjectStoreProvider for S3, GCS and Azure, configured from the environment
 impl ObjectStoreProvider for DatafusionCliObjectStoreProvider {
-    fn get_by_url(&self, url: &Url) -> Result<Arc<dyn object_store::ObjectStore>> {
-        ObjectStoreScheme::from_str(url.scheme()).map(|scheme| match scheme {
-            ObjectStoreScheme::S3 => build_s3_object_store(url),
-            ObjectStoreScheme::GCS => build_gcs_object_store(url),
-        })?
+    fn get_by_url(&self, url: &Url) -> Result<Arc<dyn ObjectStore>> {
+        build_object_store(url, &HashMap::new())
     }
 }
 
-fn build_s3_object_store(url: &Url) -> Result<Arc<dyn object_store::ObjectStore>> {
+/// Builds the object store for `url`, configured from the environment and
+/// `options`, which are the `OPTIONS` of a `CREATE EXTERNAL TABLE` statement.
+///
+/// Options are prefixed with the provider they apply to, e.g. `aws.region`,
+/// and take precedence over the environment. Options for other providers
+/// are ignored.
+pub fn build_object_store(
+    url: &Url,
+    options: &HashMap<String, String>,
+) -> Result<Arc<dyn ObjectStore>> {
+    match ObjectStoreScheme::from_str(url.scheme())? {
+        ObjectStoreScheme::S3 => build_s3_object_store(url, options),
+        ObjectStoreScheme::GCS => build_gcs_object_store(url, options),
+        ObjectStoreScheme::Azure => build_azure_object_store(url, options),
+    }
+}
+
+/// Returns the options starting with `prefix`, with the prefix removed
+fn provider_options<'a>(
+    options: &'a HashMap<String, String>,
+    prefix: &str,
+) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+    let prefix = format!("{}.", prefix);
+    options.iter().filter_map(move |(key, value)| {
+        key.strip_prefix(prefix.as_str())
+            .map(|key| (key, value.as_str()))
+    })
+}
+
+fn unknown_option(prefix: &str, key: &str) -> DataFusionError {
+    DataFusionError::Execution(format!(
+        "Unsupported object store option '{}.{}'",
+        prefix, key
+    ))
+}
+
+fn parse_bool_option(prefix: &str, key: &str, value: &str) -> Result<bool> {
+    value.parse().map_err(|_| {
+        DataFusionError::Execution(format!(
+            "Invalid value '{}' for object store option '{}.{}', expected true or false",
+            value, prefix, key
+        ))
+    })
+}
+
+/// Credentials, region and endpoint are read from the standard `AWS_*`
+/// environment variables, including `AWS_ROLE_ARN` and
+/// `AWS_WEB_IDENTITY_TOKEN_FILE` to assume a role, unless overridden by
+/// `aws.*` options.
+fn build_s3_object_store(
+    url: &Url,
+    options: &HashMap<String, String>,
+) -> Result<Arc<dyn ObjectStore>> {
     let host = get_host_name(url)?;
-    match AmazonS3Builder::from_env().with_bucket_name(host).build() {
+    let mut builder = AmazonS3Builder::from_env().with_bucket_name(host);
+
+    let mut access_key_id = None;
+    let mut secret_access_key = None;
+    for (key, value) in provider_options(options, "aws") {
+        builder = match key {
+            "access_key_id" => {
+                access_key_id = Some(value);
+                builder.with_access_key_id(value)
+            }
+            "secret_access_key" => {
+                secret_access_key = Some(value);
+                builder.with_secret_access_key(value)
+            }
+            "session_token" => builder.with_token(value),
+            "region" => builder.with_region(value),
+            "endpoint" => builder.with_endpoint(value),
+            "allow_http" => {
+                builder.with_allow_http(parse_bool_option("aws", key, value)?)
+            }
+            _ => return Err(unknown_option("aws", key)),
+        };
+    }
+    if access_key_id.is_some() != secret_access_key.is_some() {
+        return Err(DataFusionError::Execution(
+            "Object store options 'aws.access_key_id' and 'aws.secret_access_key' must be specified together".to_string(),
+        ));
+    }
+
+    match builder.build() {
         Ok(s3) => Ok(Arc::new(s3)),
         Err(err) => Err(DataFusionError::External(Box::new(err))),
     }
 }
 
-fn build_gcs_object_store(url: &Url) -> Result<Arc<dyn object_store::ObjectStore>> {
+/// The service account is read from `GCP_SERVICE_ACCOUNT_PATH` unless
+/// overridden by the `gcp.service_account_path` option.
+fn build_gcs_object_store(
+    url: &Url,
+    options: &HashMap<String, String>,
+) -> Result<Arc<dyn ObjectStore>> {
     let host = get_host_name(url)?;
     let mut builder = GoogleCloudStorageBuilder::new().with_bucket_name(host);
 
     if let Ok(path) = env::var("GCP_SERVICE_ACCOUNT_PATH") {
         builder = builder.with_service_account_path(path);
     }
+    for (key, value) in provider_options(options, "gcp") {
+        builder = match key {
+            "service_account_path" => builder.with_service_account_path(value),
+            _ => return Err(unknown_option("gcp", key)),
+        };
+    }
+
     match builder.build() {
         Ok(gcs) => Ok(Arc::new(gcs)),
         Err(err) => Err(DataFusionError::External(Box::new(err))),
     }
 }
 
+/// Urls are of the form `az://container/path`, the account and credentials
+/// are read from the standard `AZURE_*` environment variables unless
+/// overridden by `azure.*` options.
+fn build_azure_object_store(
+    url: &Url,
+    options: &HashMap<String, String>,
+) -> Result<Arc<dyn ObjectStore>> {
+    let host = get_host_name(url)?;
+    let mut builder = MicrosoftAzureBuilder::from_env().with_container_name(host);
+
+    for (key, value) in provider_options(options, "azure") {
+        builder = match key {
+            "account_name" => builder.with_account(value),
+            "access_key" => builder.with_access_key(value),
+            "client_id" => builder.with_client_id(value),
+            "client_secret" => builder.with_client_secret(value),
+            "tenant_id" => builder.with_tenant_id(value),
+            "use_emulator" => {
+                builder.with_use_emulator(parse_bool_option("azure", key, value)?)
+            }
+            "allow_http" => {
+                builder.with_allow_http(parse_bool_option("azure", key, value)?)
+            }
+            _ => return Err(unknown_option("azure", key)),
+        };
+    }
+
+    match builder.build() {
+        Ok(azure) => Ok(Arc::new(azure)),
+        Err(err) => Err(DataFusionError::External(Box::new(err))),
+    }
+}
+
 fn get_host_name(url: &Url) -> Result<&str> {
     url.host_str().ok_or_else(|| {
         DataFusionError::Execution(format!(
@@ -88,12 +216,12 @@ fn get_host_name(url: &Url) -> Result<&str> {
 
 #[cfg(test)]
 mod tests {
-    use std::{env, str::FromStr};
+    use std::{collections::HashMap, env, str::FromStr};
 
     use datafusion::datasource::object_store::ObjectStoreProvider;
     use url::Url;
 
-    use super::DatafusionCliObjectStoreProvider;
+    use super::{build_object_store, DatafusionCliObjectStoreProvider};
 
     #[test]
     fn s3_provider_no_host() {
@@ -150,4 +278,59 @@ mod tests {
         assert_eq!("".to_string(), msg); // Fail with error message
         env::remove_var("AWS_REGION");
     }
+
+    #[test]
+    fn s3_options() {
+        let url = Url::from_str("s3://bucket_name/path").unwrap();
+        let options = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        build_object_store(
+            &url,
+            &options(&[
+                ("aws.region", "us-east-1"),
+                ("aws.access_key_id", "key"),
+                ("aws.secret_access_key", "secret"),
+                ("aws.endpoint", "http://localhost:9000"),
+                ("aws.allow_http", "true"),
+                ("gcp.service_account_path", "ignored"),
+            ]),
+        )
+        .unwrap();
+
+        let err = build_object_store(
+            &url,
+            &options(&[("aws.region", "us-east-1"), ("aws.access_key_id", "key")]),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("must be specified together"),
+            "{}",
+            err
+        );
+
+        let err =
+            build_object_store(&url, &options(&[("aws.unknown", "value")])).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unsupported object store option 'aws.unknown'"),
+            "{}",
+            err
+        );
+
+        let err = build_object_store(
+            &url,
+            &options(&[("aws.region", "us-east-1"), ("aws.allow_http", "maybe")]),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("expected true or false"),
+            "{}",
+            err
+        );
+    }
 }
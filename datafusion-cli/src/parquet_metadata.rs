@@ -0,0 +1,430 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Table functions describing the footer of a parquet file, used to debug
+//! row group pruning, such as `SELECT * FROM parquet_metadata('file.parquet')`
+
+use async_trait::async_trait;
+use datafusion::arrow::array::{ArrayRef, Int64Array, StringArray, UInt64Array};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::file_format::parquet::fetch_parquet_metadata;
+use datafusion::datasource::function::TableFunctionImpl;
+use datafusion::datasource::listing::ListingTableUrl;
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::{SessionContext, SessionState};
+use datafusion::logical_expr::{Expr, TableType};
+use datafusion::parquet::file::metadata::ParquetMetaData;
+use datafusion::parquet::file::statistics::Statistics;
+use datafusion::physical_plan::memory::MemoryExec;
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::scalar::ScalarValue;
+use object_store::path::Path;
+use std::any::Any;
+use std::sync::Arc;
+
+/// Registers the `parquet_metadata`, `parquet_schema` and
+/// `parquet_row_groups` table functions, reading the footer of a parquet
+/// file from the object stores of the session
+pub fn register_parquet_functions(ctx: &SessionContext) {
+    for footer in [Footer::Metadata, Footer::Schema, Footer::RowGroups] {
+        ctx.register_udtf(footer.name(), Arc::new(ParquetFooterFunction(footer)));
+    }
+}
+
+/// The part of the footer of a parquet file returned by a table function
+#[derive(Debug, Clone, Copy)]
+enum Footer {
+    Metadata,
+    Schema,
+    RowGroups,
+}
+
+impl Footer {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Metadata => "parquet_metadata",
+            Self::Schema => "parquet_schema",
+            Self::RowGroups => "parquet_row_groups",
+        }
+    }
+
+    fn schema(self) -> SchemaRef {
+        match self {
+            Self::Metadata => metadata_schema(),
+            Self::Schema => schema_schema(),
+            Self::RowGroups => row_groups_schema(),
+        }
+    }
+
+    fn batch(self, filename: &str, metadata: &ParquetMetaData) -> Result<RecordBatch> {
+        match self {
+            Self::Metadata => parquet_metadata(filename, metadata),
+            Self::Schema => parquet_schema(metadata),
+            Self::RowGroups => parquet_row_groups(metadata),
+        }
+    }
+}
+
+/// A table function called with the location of a parquet file
+struct ParquetFooterFunction(Footer);
+
+impl TableFunctionImpl for ParquetFooterFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        match args {
+            [Expr::Literal(ScalarValue::Utf8(Some(location)))] => {
+                Ok(Arc::new(ParquetFooterTable {
+                    footer: self.0,
+                    location: location.to_string(),
+                }))
+            }
+            _ => Err(DataFusionError::Plan(format!(
+                "{} expects the location of a parquet file as its only argument",
+                self.0.name()
+            ))),
+        }
+    }
+}
+
+/// The rows describing the footer of the parquet file at `location`, which
+/// is read when the table is scanned
+struct ParquetFooterTable {
+    footer: Footer,
+    location: String,
+}
+
+#[async_trait]
+impl TableProvider for ParquetFooterTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.footer.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Temporary
+    }
+
+    async fn scan(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let metadata = read_metadata(state, &self.location).await?;
+        let batch = self.footer.batch(&self.location, &metadata)?;
+        Ok(Arc::new(MemoryExec::try_new(
+            &[vec![batch]],
+            self.schema(),
+            projection.cloned(),
+        )?))
+    }
+}
+
+/// Reads the footer of the parquet file at `location`, a path or the url of
+/// a registered object store
+async fn read_metadata(state: &SessionState, location: &str) -> Result<ParquetMetaData> {
+    let url = ListingTableUrl::parse(location)?;
+    let store = state.runtime_env().object_store(&url)?;
+    let url: &url::Url = url.as_ref();
+    let path = Path::from_url_path(url.path())
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    let meta = store.head(&path).await?;
+    fetch_parquet_metadata(store.as_ref(), &meta, None).await
+}
+
+fn string_field(name: &str, nullable: bool) -> Field {
+    Field::new(name, DataType::Utf8, nullable)
+}
+
+fn metadata_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        string_field("filename", false),
+        Field::new("version", DataType::Int64, false),
+        Field::new("num_rows", DataType::Int64, false),
+        Field::new("num_row_groups", DataType::UInt64, false),
+        Field::new("num_columns", DataType::UInt64, false),
+        string_field("created_by", true),
+        string_field("key_value_metadata", true),
+    ]))
+}
+
+/// Returns a single row describing the file level metadata of a parquet file
+fn parquet_metadata(filename: &str, metadata: &ParquetMetaData) -> Result<RecordBatch> {
+    let file_metadata = metadata.file_metadata();
+    let key_value_metadata = file_metadata.key_value_metadata().map(|kvs| {
+        kvs.iter()
+            .map(|kv| format!("{}={}", kv.key, kv.value.as_deref().unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec![filename])),
+        Arc::new(Int64Array::from(vec![file_metadata.version() as i64])),
+        Arc::new(Int64Array::from(vec![file_metadata.num_rows()])),
+        Arc::new(UInt64Array::from(vec![metadata.num_row_groups() as u64])),
+        Arc::new(UInt64Array::from(vec![
+            file_metadata.schema_descr().num_columns() as u64,
+        ])),
+        Arc::new(StringArray::from(vec![file_metadata.created_by()])),
+        Arc::new(StringArray::from(vec![key_value_metadata.as_deref()])),
+    ];
+    Ok(RecordBatch::try_new(metadata_schema(), columns)?)
+}
+
+fn schema_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        string_field("column", false),
+        string_field("physical_type", false),
+        string_field("logical_type", true),
+        string_field("converted_type", false),
+        string_field("repetition", false),
+        Field::new("max_definition_level", DataType::Int64, false),
+        Field::new("max_repetition_level", DataType::Int64, false),
+    ]))
+}
+
+/// Returns a row for each leaf column of the schema of a parquet file
+fn parquet_schema(metadata: &ParquetMetaData) -> Result<RecordBatch> {
+    let columns = metadata.file_metadata().schema_descr().columns();
+    let arrays: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            columns.iter().map(|c| c.path().string()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            columns.iter().map(|c| c.physical_type().to_string()),
+        )),
+        Arc::new(
+            columns
+                .iter()
+                .map(|c| c.logical_type().map(|t| format!("{:?}", t)))
+                .collect::<StringArray>(),
+        ),
+        Arc::new(StringArray::from_iter_values(
+            columns.iter().map(|c| c.converted_type().to_string()),
+        )),
+        Arc::new(StringArray::from_iter_values(columns.iter().map(|c| {
+            c.self_type().get_basic_info().repetition().to_string()
+        }))),
+        Arc::new(Int64Array::from_iter_values(
+            columns.iter().map(|c| c.max_def_level() as i64),
+        )),
+        Arc::new(Int64Array::from_iter_values(
+            columns.iter().map(|c| c.max_rep_level() as i64),
+        )),
+    ];
+    Ok(RecordBatch::try_new(schema_schema(), arrays)?)
+}
+
+fn row_groups_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("row_group", DataType::UInt64, false),
+        Field::new("row_group_num_rows", DataType::Int64, false),
+        string_field("column", false),
+        string_field("compression", false),
+        string_field("encodings", false),
+        Field::new("compressed_size", DataType::Int64, false),
+        Field::new("uncompressed_size", DataType::Int64, false),
+        Field::new("null_count", DataType::UInt64, true),
+        Field::new("distinct_count", DataType::UInt64, true),
+        string_field("min", true),
+        string_field("max", true),
+    ]))
+}
+
+/// Returns a row for each column chunk of each row group of a parquet file,
+/// including the statistics used to prune row groups
+fn parquet_row_groups(metadata: &ParquetMetaData) -> Result<RecordBatch> {
+    let mut row_group = vec![];
+    let mut row_group_num_rows = vec![];
+    let mut column = vec![];
+    let mut compression = vec![];
+    let mut encodings = vec![];
+    let mut compressed_size = vec![];
+    let mut uncompressed_size = vec![];
+    let mut null_count = vec![];
+    let mut distinct_count = vec![];
+    let mut min = vec![];
+    let mut max = vec![];
+
+    for (i, rg) in metadata.row_groups().iter().enumerate() {
+        for chunk in rg.columns() {
+            row_group.push(i as u64);
+            row_group_num_rows.push(rg.num_rows());
+            column.push(chunk.column_path().string());
+            compression.push(chunk.compression().to_string());
+            encodings.push(
+                chunk
+                    .encodings()
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+            compressed_size.push(chunk.compressed_size());
+            uncompressed_size.push(chunk.uncompressed_size());
+
+            let stats = chunk.statistics();
+            null_count.push(stats.map(|s| s.null_count()));
+            distinct_count.push(stats.and_then(|s| s.distinct_count()));
+            let (chunk_min, chunk_max) = stats.map(min_max).unwrap_or_default();
+            min.push(chunk_min);
+            max.push(chunk_max);
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from(row_group)),
+        Arc::new(Int64Array::from(row_group_num_rows)),
+        Arc::new(StringArray::from(column)),
+        Arc::new(StringArray::from(compression)),
+        Arc::new(StringArray::from(encodings)),
+        Arc::new(Int64Array::from(compressed_size)),
+        Arc::new(Int64Array::from(uncompressed_size)),
+        Arc::new(UInt64Array::from(null_count)),
+        Arc::new(UInt64Array::from(distinct_count)),
+        Arc::new(min.into_iter().collect::<StringArray>()),
+        Arc::new(max.into_iter().collect::<StringArray>()),
+    ];
+    Ok(RecordBatch::try_new(row_groups_schema(), columns)?)
+}
+
+/// Formats the min and max values of `stats`, byte arrays are shown as
+/// strings if they are valid utf8
+fn min_max(stats: &Statistics) -> (Option<String>, Option<String>) {
+    if !stats.has_min_max_set() {
+        return (None, None);
+    }
+    let (min, max) = match stats {
+        Statistics::Boolean(s) => (s.min().to_string(), s.max().to_string()),
+        Statistics::Int32(s) => (s.min().to_string(), s.max().to_string()),
+        Statistics::Int64(s) => (s.min().to_string(), s.max().to_string()),
+        Statistics::Int96(s) => (s.min().to_string(), s.max().to_string()),
+        Statistics::Float(s) => (s.min().to_string(), s.max().to_string()),
+        Statistics::Double(s) => (s.min().to_string(), s.max().to_string()),
+        Statistics::ByteArray(s) => (
+            s.min()
+                .as_utf8()
+                .map(str::to_string)
+                .unwrap_or_else(|_| s.min().to_string()),
+            s.max()
+                .as_utf8()
+                .map(str::to_string)
+                .unwrap_or_else(|_| s.max().to_string()),
+        ),
+        Statistics::FixedLenByteArray(s) => (s.min().to_string(), s.max().to_string()),
+    };
+    (Some(min), Some(max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::Int32Array;
+    use datafusion::arrow::util::pretty::pretty_format_batches;
+    use datafusion::parquet::arrow::ArrowWriter;
+    use std::fs::File;
+
+    fn write_test_file(name: &str) -> Result<String> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(3), None, Some(1)])),
+                Arc::new(StringArray::from(vec!["x", "y", "z"])),
+            ],
+        )?;
+
+        let path = std::env::temp_dir().join(name);
+        let mut writer = ArrowWriter::try_new(File::create(&path)?, schema, None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    async fn query(ctx: &SessionContext, sql: &str) -> Result<String> {
+        let batches = ctx.sql(sql).await?.collect().await?;
+        Ok(pretty_format_batches(&batches)?.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_parquet_footer_functions() -> Result<()> {
+        let filename = write_test_file("datafusion_cli_parquet_metadata.parquet")?;
+        let ctx = SessionContext::new();
+        register_parquet_functions(&ctx);
+
+        let sql = format!(
+            "SELECT num_rows, num_row_groups FROM parquet_metadata('{filename}')"
+        );
+        let expected = vec![
+            "+----------+----------------+",
+            "| num_rows | num_row_groups |",
+            "+----------+----------------+",
+            "| 3        | 1              |",
+            "+----------+----------------+",
+        ];
+        assert_eq!(expected.join("\n"), query(&ctx, &sql).await?);
+
+        let sql = format!("SELECT * FROM parquet_schema('{filename}')");
+        let expected = vec![
+            "+--------+---------------+--------------+----------------+------------+----------------------+----------------------+",
+            "| column | physical_type | logical_type | converted_type | repetition | max_definition_level | max_repetition_level |",
+            "+--------+---------------+--------------+----------------+------------+----------------------+----------------------+",
+            "| a      | INT32         |              | NONE           | OPTIONAL   | 1                    | 0                    |",
+            "| b      | BYTE_ARRAY    | String       | UTF8           | REQUIRED   | 0                    | 0                    |",
+            "+--------+---------------+--------------+----------------+------------+----------------------+----------------------+",
+        ];
+        assert_eq!(expected.join("\n"), query(&ctx, &sql).await?);
+
+        let sql = format!(
+            "SELECT row_group, \"column\", null_count, min, max \
+            FROM parquet_row_groups('{filename}') WHERE null_count = 1"
+        );
+        let expected = vec![
+            "+-----------+--------+------------+-----+-----+",
+            "| row_group | column | null_count | min | max |",
+            "+-----------+--------+------------+-----+-----+",
+            "| 0         | a      | 1          | 1   | 3   |",
+            "+-----------+--------+------------+-----+-----+",
+        ];
+        assert_eq!(expected.join("\n"), query(&ctx, &sql).await?);
+
+        let err = ctx
+            .sql("SELECT * FROM parquet_metadata(1)")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: parquet_metadata expects the location of a \
+            parquet file as its only argument"
+        );
+        assert!(
+            query(&ctx, "SELECT * FROM parquet_metadata('missing.parquet')")
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+}
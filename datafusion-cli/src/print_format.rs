@@ -17,10 +17,14 @@
 
 //! Print format variants
 use arrow::csv::writer::WriterBuilder;
+use arrow::ipc::writer::FileWriter;
 use arrow::json::{ArrayWriter, LineDelimitedWriter};
+use arrow::util::display::array_value_to_string;
+use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::arrow::util::pretty;
 use datafusion::error::{DataFusionError, Result};
+use std::io::Write;
 use std::str::FromStr;
 
 /// Allow records to be printed in different formats
@@ -31,6 +35,9 @@ pub enum PrintFormat {
     Table,
     Json,
     NdJson,
+    Markdown,
+    /// Arrow IPC file format, which can only be written to an output file
+    Arrow,
 }
 
 impl FromStr for PrintFormat {
@@ -69,17 +76,86 @@ fn print_batches_with_sep(batches: &[RecordBatch], delimiter: u8) -> Result<Stri
     Ok(formatted)
 }
 
+fn batches_to_markdown(batches: &[RecordBatch]) -> Result<String> {
+    let schema = match batches.first() {
+        Some(batch) => batch.schema(),
+        None => return Ok(String::new()),
+    };
+
+    let escape = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+    let mut lines = Vec::new();
+    let header = schema
+        .fields()
+        .iter()
+        .map(|f| escape(f.name()))
+        .collect::<Vec<_>>();
+    lines.push(format!("| {} |", header.join(" | ")));
+    lines.push(format!("|{}", "---|".repeat(header.len())));
+
+    for batch in batches {
+        for row in 0..batch.num_rows() {
+            let cells = batch
+                .columns()
+                .iter()
+                .map(|column| Ok(escape(&array_value_to_string(column, row)?)))
+                .collect::<Result<Vec<_>>>()?;
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Writes an Arrow IPC file, which holds the schema and a footer even when
+/// there are no batches
+fn write_batches_as_arrow<W: Write>(
+    writer: W,
+    schema: &Schema,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    let mut writer = FileWriter::try_new(writer, schema)?;
+    for batch in batches {
+        writer.write(batch)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
 impl PrintFormat {
+    /// Returns true if this format produces binary rather than text output
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::Arrow)
+    }
+
     /// print the batches to stdout using the specified format
-    pub fn print_batches(&self, batches: &[RecordBatch]) -> Result<()> {
+    pub fn print_batches(&self, schema: &Schema, batches: &[RecordBatch]) -> Result<()> {
+        if self.is_binary() {
+            return Err(DataFusionError::Execution(format!(
+                "The {:?} format can only be written to a file, use \\o FILENAME to set one",
+                self
+            )));
+        }
+        self.write_batches(&mut std::io::stdout(), schema, batches)
+    }
+
+    /// write the batches to `writer` using the specified format
+    pub fn write_batches<W: Write>(
+        &self,
+        writer: &mut W,
+        schema: &Schema,
+        batches: &[RecordBatch],
+    ) -> Result<()> {
         match self {
-            Self::Csv => println!("{}", print_batches_with_sep(batches, b',')?),
-            Self::Tsv => println!("{}", print_batches_with_sep(batches, b'\t')?),
-            Self::Table => pretty::print_batches(batches)?,
-            Self::Json => println!("{}", batches_to_json!(ArrayWriter, batches)),
+            Self::Csv => writeln!(writer, "{}", print_batches_with_sep(batches, b',')?)?,
+            Self::Tsv => writeln!(writer, "{}", print_batches_with_sep(batches, b'\t')?)?,
+            Self::Table => {
+                writeln!(writer, "{}", pretty::pretty_format_batches(batches)?)?
+            }
+            Self::Json => writeln!(writer, "{}", batches_to_json!(ArrayWriter, batches))?,
             Self::NdJson => {
-                println!("{}", batches_to_json!(LineDelimitedWriter, batches))
+                writeln!(writer, "{}", batches_to_json!(LineDelimitedWriter, batches))?
             }
+            Self::Markdown => writeln!(writer, "{}", batches_to_markdown(batches)?)?,
+            Self::Arrow => write_batches_as_arrow(writer, schema, batches)?,
         }
         Ok(())
     }
@@ -88,8 +164,9 @@ impl PrintFormat {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow::array::Int32Array;
+    use arrow::array::{Int32Array, StringArray};
     use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::ipc::reader::FileReader;
     use datafusion::from_slice::FromSlice;
     use std::sync::Arc;
 
@@ -152,4 +229,60 @@ mod tests {
         assert_eq!("{\"a\":1,\"b\":4,\"c\":7}\n{\"a\":2,\"b\":5,\"c\":8}\n{\"a\":3,\"b\":6,\"c\":9}\n", r);
         Ok(())
     }
+
+    #[test]
+    fn test_print_batches_to_markdown() -> Result<()> {
+        assert_eq!("", batches_to_markdown(&[])?);
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None])),
+                Arc::new(StringArray::from(vec![Some("x|y"), Some("z")])),
+            ],
+        )
+        .unwrap();
+
+        let r = batches_to_markdown(&[batch])?;
+        assert_eq!("| a | b |\n|---|---|\n| 1 | x\\|y |\n|  | z |", r);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_batches_as_arrow() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int32Array::from_slice([1, 2, 3]))],
+        )
+        .unwrap();
+
+        let mut bytes = vec![];
+        PrintFormat::Arrow.write_batches(
+            &mut bytes,
+            &batch.schema(),
+            std::slice::from_ref(&batch),
+        )?;
+
+        let reader = FileReader::try_new(std::io::Cursor::new(bytes), None)?;
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        assert_eq!(vec![batch.clone()], batches);
+
+        // without any batch, the file still holds the schema
+        let mut bytes = vec![];
+        PrintFormat::Arrow.write_batches(&mut bytes, &batch.schema(), &[])?;
+        let reader = FileReader::try_new(std::io::Cursor::new(bytes), None)?;
+        assert_eq!(reader.schema(), batch.schema());
+        assert_eq!(reader.count(), 0);
+
+        assert!(PrintFormat::Arrow
+            .print_batches(&batch.schema(), &[])
+            .is_err());
+        Ok(())
+    }
 }
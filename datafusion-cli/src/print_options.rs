@@ -16,39 +16,214 @@
 // under the License.
 
 use crate::print_format::PrintFormat;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::ipc::writer::FileWriter;
 use datafusion::arrow::record_batch::RecordBatch;
-use datafusion::error::Result;
+use datafusion::error::{DataFusionError, Result};
+use parking_lot::Mutex;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::sync::Arc;
 use std::time::Instant;
 
 #[derive(Debug, Clone)]
 pub struct PrintOptions {
     pub format: PrintFormat,
     pub quiet: bool,
+    /// Whether to print how long each query took
+    pub timing: bool,
+    /// File that query results are written to instead of stdout, see
+    /// [`Self::set_output`]
+    output: Option<String>,
+    /// The Arrow IPC file being written to `output`, if any
+    arrow_file: Arc<Mutex<Option<ArrowFile>>>,
 }
 
-fn print_timing_info(row_count: usize, now: Instant) {
-    println!(
-        "{} {} in set. Query took {:.3} seconds.",
-        row_count,
-        if row_count == 1 { "row" } else { "rows" },
-        now.elapsed().as_secs_f64()
-    );
+/// An Arrow IPC file that is kept open while it is the output file, so that
+/// the results of each query are appended to it, and finished once the
+/// output file is changed or the CLI exits
+struct ArrowFile {
+    schema: Schema,
+    writer: FileWriter<File>,
+}
+
+impl fmt::Debug for ArrowFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArrowFile")
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+fn print_timing_info(row_count: usize, now: Instant, timing: bool) {
+    let rows = if row_count == 1 { "row" } else { "rows" };
+    if timing {
+        println!(
+            "{} {} in set. Query took {:.3} seconds.",
+            row_count,
+            rows,
+            now.elapsed().as_secs_f64()
+        );
+    } else {
+        println!("{} {} in set.", row_count, rows);
+    }
 }
 
 impl PrintOptions {
-    /// print the batches to stdout using the specified format
-    pub fn print_batches(&self, batches: &[RecordBatch], now: Instant) -> Result<()> {
-        if batches.is_empty() {
-            if !self.quiet {
-                print_timing_info(0, now);
+    /// Creates options that print the results to stdout
+    pub fn new(format: PrintFormat, quiet: bool) -> Self {
+        Self {
+            format,
+            quiet,
+            timing: true,
+            output: None,
+            arrow_file: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Writes the query results to the file `output`, which is truncated, or
+    /// to stdout if `None`, after finishing the Arrow file written so far
+    pub fn set_output(&mut self, output: Option<String>) -> Result<()> {
+        self.finish_output()?;
+        if let Some(path) = &output {
+            // start from an empty file, later results are appended
+            File::create(path).map_err(|e| {
+                DataFusionError::Execution(format!("Error opening {:?} {}", path, e))
+            })?;
+        }
+        self.output = output;
+        Ok(())
+    }
+
+    /// Finishes the Arrow file written to the output file, if any, by writing
+    /// its footer. Later Arrow results can't be appended to it.
+    pub fn finish_output(&self) -> Result<()> {
+        if let Some(mut arrow_file) = self.arrow_file.lock().take() {
+            arrow_file.writer.finish()?;
+        }
+        Ok(())
+    }
+
+    /// print the batches of a result of `schema` to stdout using the
+    /// specified format
+    pub fn print_batches(
+        &self,
+        schema: &Schema,
+        batches: &[RecordBatch],
+        now: Instant,
+    ) -> Result<()> {
+        // an Arrow file is written even for an empty result, as it holds
+        // the schema of the result
+        if !batches.is_empty() || self.format.is_binary() {
+            self.print_results(schema, batches)?;
+        }
+        if !self.quiet {
+            let row_count: usize = batches.iter().map(|b| b.num_rows()).sum();
+            print_timing_info(row_count, now, self.timing);
+        }
+        Ok(())
+    }
+
+    /// write the batches to the output file, if any, or stdout otherwise.
+    ///
+    /// The results are appended to the output file, in all formats. An
+    /// Arrow IPC file holds the results of a single schema, and is only
+    /// complete once it is finished by [`Self::finish_output`].
+    fn print_results(&self, schema: &Schema, batches: &[RecordBatch]) -> Result<()> {
+        let path = match &self.output {
+            Some(path) => path,
+            None => return self.format.print_batches(schema, batches),
+        };
+
+        let mut arrow_file = self.arrow_file.lock();
+        if self.format.is_binary() {
+            match &*arrow_file {
+                Some(open) if open.schema.fields() != schema.fields() => {
+                    return Err(DataFusionError::Execution(format!(
+                        "Can not append the results to {:?}, which holds results with \
+                        other columns, use \\o FILENAME to start a new file",
+                        path
+                    )))
+                }
+                Some(_) => {}
+                None => *arrow_file = Some(create_arrow_file(path, schema)?),
             }
-        } else {
-            self.format.print_batches(batches)?;
-            if !self.quiet {
-                let row_count: usize = batches.iter().map(|b| b.num_rows()).sum();
-                print_timing_info(row_count, now);
+            if let Some(open) = &mut *arrow_file {
+                for batch in batches {
+                    open.writer.write(batch)?;
+                }
             }
+            Ok(())
+        } else if arrow_file.is_some() {
+            Err(DataFusionError::Execution(format!(
+                "Can not append {:?} results to {:?}, which holds Arrow results, \
+                use \\o FILENAME to start a new file",
+                self.format, path
+            )))
+        } else {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            self.format.write_batches(&mut file, schema, batches)
         }
+    }
+}
+
+/// Starts writing an Arrow IPC file of the results of `schema` to the output
+/// file `path`, which must not hold other results yet
+fn create_arrow_file(path: &str, schema: &Schema) -> Result<ArrowFile> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() > 0 {
+        return Err(DataFusionError::Execution(format!(
+            "Can not append Arrow results to {:?}, which holds other results, \
+            use \\o FILENAME to start a new file",
+            path
+        )));
+    }
+    Ok(ArrowFile {
+        schema: schema.clone(),
+        writer: FileWriter::try_new(file, schema)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::Int32Array;
+    use datafusion::arrow::datatypes::{DataType, Field};
+    use datafusion::arrow::ipc::reader::FileReader;
+    use datafusion::from_slice::FromSlice;
+
+    #[test]
+    fn append_arrow_results() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice([1, 2, 3]))],
+        )?;
+        let path = std::env::temp_dir()
+            .join(format!("append_arrow_results_{}.arrow", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+
+        let mut print_options = PrintOptions::new(PrintFormat::Arrow, true);
+        print_options.set_output(Some(path.clone()))?;
+        print_options.print_results(&schema, std::slice::from_ref(&batch))?;
+        print_options.print_results(&schema, &[])?;
+        print_options.print_results(&schema, std::slice::from_ref(&batch))?;
+
+        // the results of other columns, or of a text format, can't be appended
+        let other = Schema::new(vec![Field::new("b", DataType::Int32, false)]);
+        assert!(print_options.print_results(&other, &[]).is_err());
+        print_options.format = PrintFormat::Csv;
+        assert!(print_options
+            .print_results(&schema, std::slice::from_ref(&batch))
+            .is_err());
+
+        print_options.set_output(None)?;
+        let reader = FileReader::try_new(File::open(&path)?, None)?;
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        assert_eq!(vec![batch.clone(), batch], batches);
+
+        std::fs::remove_file(&path)?;
         Ok(())
     }
 }
@@ -264,6 +264,6 @@ impl ExecutionPlan for CustomExec {
     }
 
     fn statistics(&self) -> Statistics {
-        Statistics::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
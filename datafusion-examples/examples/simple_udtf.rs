@@ -0,0 +1,84 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use datafusion::arrow::array::Int64Array;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::ScalarValue;
+use datafusion::datasource::function::TableFunctionImpl;
+use datafusion::datasource::{MemTable, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::Expr;
+use datafusion::prelude::*;
+use std::sync::Arc;
+
+/// This example shows how to define a User Defined Table Function (UDTF),
+/// called in the `FROM` clause of a query like a table
+#[tokio::main]
+async fn main() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    // register the function under the name used by the queries
+    ctx.register_udtf("sequence", Arc::new(Sequence));
+
+    // the function is called with the arguments of the query, and the
+    // table it returns is scanned like any other table
+    let df = ctx
+        .sql("SELECT value * 10 AS tens FROM sequence(1, 5) WHERE value % 2 = 1")
+        .await?;
+    df.show().await?;
+
+    Ok(())
+}
+
+/// A table function returning a table of the integers from its first argument
+/// to its second one, both included
+struct Sequence;
+
+impl TableFunctionImpl for Sequence {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        // the arguments are the logical expressions of the query, so that
+        // the function decides which ones it supports
+        let (start, end) = match args {
+            [Expr::Literal(start), Expr::Literal(end)] => (start, end),
+            _ => {
+                return Err(DataFusionError::Plan(
+                    "sequence expects two literal arguments".to_string(),
+                ))
+            }
+        };
+        let (start, end) = match (start, end) {
+            (ScalarValue::Int64(Some(start)), ScalarValue::Int64(Some(end))) => {
+                (*start, *end)
+            }
+            _ => {
+                return Err(DataFusionError::Plan(
+                    "sequence expects two integers".to_string(),
+                ))
+            }
+        };
+
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "value",
+            DataType::Int64,
+            false,
+        )]));
+        let values = Int64Array::from_iter_values(start..=end);
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(values)])?;
+        Ok(Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))
+    }
+}
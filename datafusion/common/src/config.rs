@@ -193,9 +193,58 @@ config_namespace! {
         /// target batch size is determined by the configuration setting
         pub coalesce_batches: bool, default = true
 
+        /// Target size in bytes of the batches coalesced when `coalesce_batches` is
+        /// enabled. The number of rows of the coalesced batches is reduced below
+        /// `batch_size` for operators whose rows are wide enough that `batch_size` rows
+        /// would exceed this size, based on the estimated width of their rows. Set to 0
+        /// to always coalesce batches to `batch_size` rows
+        pub coalesce_target_bytes: usize, default = 8 * 1024 * 1024
+
         /// Should DataFusion collect statistics after listing files
         pub collect_statistics: bool, default = false
 
+        /// When set to true, round robin repartitions send each batch to the output
+        /// partition with the fewest buffered batches instead of the next one in turn,
+        /// so that fewer batches are queued for the output partitions that are consumed
+        /// slowly, and the faster ones process more of the input
+        pub dynamic_round_robin_repartition: bool, default = false
+
+        /// When set to true, hash aggregations whose aggregate functions all
+        /// support it keep the states of their groups in columnar form, updated
+        /// with a vectorized kernel per input batch, instead of in a row or an
+        /// accumulator per group
+        pub groups_accumulator: bool, default = true
+
+        /// Number of files to read in parallel when inferring the schema of a listing
+        /// table of Parquet, Arrow or Avro files, or collecting the statistics of the
+        /// files of a listing table. The schema of CSV and JSON files is inferred
+        /// from their first records, reading one file after the other
+        pub meta_fetch_concurrency: usize, default = 32
+
+        /// When set to true, round robin repartitions neither spawn a task per input
+        /// partition nor buffer batches. Instead, whenever an output partition is polled,
+        /// it reads the next batch of any input partition that is not being read by
+        /// another output partition, so that the inputs are only read as fast as the
+        /// output is consumed, on the tasks of the consumers
+        pub morsel_driven_repartition: bool, default = false
+
+        /// Number of rows of the batches of the query results. When set, the output
+        /// of the query is re-chunked into batches of exactly this many rows, except
+        /// the last one of each partition, whatever the sizes of the batches produced
+        /// by the plan. When not set, the batches of the plan are returned as is
+        pub output_batch_size: Option<usize>, default = None
+
+        /// The maximum number of files read to infer the schema of a listing table, the
+        /// first ones by path. If not specified, the schema is inferred from all the files,
+        /// which can take long for tables of many files
+        pub schema_infer_max_files: Option<usize>, default = None
+
+        /// The maximum number of sorted runs that an external sort merges at once. When
+        /// a sort spills more files than that, it first merges groups of files into
+        /// bigger files, until few enough remain. The number of runs merged at once is
+        /// further reduced when the memory pool cannot buffer a batch of each of them
+        pub sort_spill_merge_fan_in: usize, default = 64
+
         /// Number of partitions for query execution. Increasing partitions can increase
         /// concurrency. Defaults to the number of cpu cores on the system
         pub target_partitions: usize, default = num_cpus::get()
@@ -242,6 +291,15 @@ config_namespace! {
         /// will be reordered heuristically to minimize the cost of evaluation. If false,
         /// the filters are applied in the same order as written in the query
         pub reorder_filters: bool, default = false
+
+        /// If true, the parquet reader coalesces the byte ranges of a file that it reads
+        /// together, such as the column chunks of a row group, into fewer requests to the
+        /// object store
+        pub coalesce_ranges: bool, default = true
+
+        /// The byte ranges read by the parquet reader that are less than this number of
+        /// bytes apart are coalesced into a single request, if `coalesce_ranges` is true
+        pub coalesce_range_gap: usize, default = 1024 * 1024
     }
 }
 
@@ -252,6 +310,17 @@ config_namespace! {
         /// repartition to increase parallelism to leverage more CPU cores
         pub enable_round_robin_repartition: bool, default = true
 
+        /// When set to true, the physical plan optimizer adapts the number of partitions
+        /// that the plan is repartitioned to, `target_partitions`, to the size of the input
+        /// of the query, as estimated from the statistics or the size of the scanned files:
+        /// small inputs use fewer partitions, down to 1, and huge inputs use more, up to 4
+        /// times `target_partitions`
+        pub enable_adaptive_target_partitions: bool, default = false
+
+        /// The number of bytes of input that each partition should process when
+        /// `enable_adaptive_target_partitions` is set to true
+        pub adaptive_partition_bytes: usize, default = 128 * 1024 * 1024
+
         /// When set to true, the optimizer will insert filters before a join between
         /// a nullable and non-nullable column to filter out nulls on the nullable side. This
         /// filter can add additional overhead when the file format does not fully support
@@ -278,6 +347,25 @@ config_namespace! {
         /// Number of times that the optimizer will attempt to optimize the plan
         pub max_passes: usize, default = 3
 
+        /// When set, an optimizer rule whose call takes longer than this
+        /// number of milliseconds is skipped in the next passes of the
+        /// optimizer, with a warning shown by `EXPLAIN VERBOSE`. A call of a
+        /// rule can't be interrupted, so it still completes.
+        pub max_rule_duration_ms: Option<usize>, default = None
+
+        /// When set, the optimizer rules are skipped for the plans with more
+        /// expressions than this, with a warning shown by `EXPLAIN VERBOSE`.
+        /// This avoids rules that are slow on machine generated queries with
+        /// thousands of expressions. The rules required to execute the plan,
+        /// such as type coercion, are never skipped.
+        pub max_rule_plan_size: Option<usize>, default = None
+
+        /// When set to true, the optimizer checks the invariants of the plan
+        /// produced by each rule, such as the columns of each node referring
+        /// to fields of its inputs, and fails with the name of the rule that
+        /// broke them. Useful to debug optimizer rules, custom ones included.
+        pub check_plan_invariants: bool, default = false
+
         /// When set to true, the physical plan optimizer will run a top down
         /// process to reorder the join keys
         pub top_down_join_key_reordering: bool, default = true
@@ -289,6 +377,28 @@ config_namespace! {
         /// The maximum estimated size in bytes for one input side of a HashJoin
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold: usize, default = 1024 * 1024
+
+        /// When set to true, the physical planner will plan inner joins without equal
+        /// join keys, whose filter bounds a column of one side between columns of the
+        /// other side, as a band join that sorts both sides instead of a nested loop join
+        pub enable_band_join: bool, default = true
+
+        /// When set to true, the physical plan optimizer will replace a sort whose input
+        /// is already sorted on a prefix of the sort expressions with a partial sort, that
+        /// only sorts the runs of rows with equal prefix values and emits them incrementally
+        pub enable_partial_sort: bool, default = true
+
+        /// When set to true, a common table expression that is referenced several times
+        /// in a query is computed once, and its results are shared by the references,
+        /// unless it only scans, filters and projects tables. When set to false, the plan
+        /// of the common table expression is executed for each reference
+        pub enable_cte_materialization: bool, default = true
+
+        /// The maximum estimated size in bytes of the results of a common table
+        /// expression for them to be shared by its references. The plan of larger common
+        /// table expressions is executed for each reference instead of buffering their
+        /// results in memory
+        pub cte_materialization_threshold: usize, default = 100 * 1024 * 1024
     }
 }
 
@@ -303,6 +413,43 @@ config_namespace! {
     }
 }
 
+config_namespace! {
+    /// Options related to the planning of SQL statements
+    pub struct SqlParserOptions {
+        /// When set to true, the expressions of the SELECT list and of the WHERE clause
+        /// can reference the aliases of the previous expressions of the SELECT list, like
+        /// in `SELECT a + b AS s, s * 2 FROM t WHERE s > 10`. The columns of the input
+        /// take precedence over the aliases with the same name
+        pub enable_lateral_column_alias: bool, default = false
+
+        /// When set to true, unquoted identifiers are converted to lowercase, and quoted
+        /// identifiers keep their case. When set to false, the case of all identifiers
+        /// is preserved
+        pub enable_ident_normalization: bool, default = true
+
+        /// The SQL dialect used to parse statements, which determines the quoting of
+        /// identifiers and the escapes of string literals. Possible values: generic,
+        /// mysql, postgresql, mssql, hive, snowflake, bigquery, ansi, sqlite,
+        /// clickhouse and redshift
+        pub dialect: String, default = "generic".to_string()
+
+        /// When set to true, the diagnostics of planning errors about unknown columns,
+        /// tables and functions suggest the closest valid name, if any
+        pub enable_suggestions: bool, default = true
+
+        /// The VALUES lists of literals with more rows than this threshold are planned
+        /// as scans of in-memory tables, whose columns are built directly from the
+        /// literals, rather than as lists of expressions
+        pub values_table_threshold: usize, default = 1000
+
+        /// The placement of nulls in the ORDER BY clauses of queries, windows and
+        /// aggregates whose sort keys have no NULLS FIRST or NULLS LAST. Possible values:
+        /// nulls_max (nulls sort as if larger than any value, like in postgres),
+        /// nulls_min, nulls_first and nulls_last
+        pub default_null_ordering: String, default = "nulls_max".to_string()
+    }
+}
+
 /// A key value pair, with a corresponding description
 #[derive(Debug)]
 pub struct ConfigEntry {
@@ -328,6 +475,8 @@ pub struct ConfigOptions {
     pub optimizer: OptimizerOptions,
     /// Explain options
     pub explain: ExplainOptions,
+    /// SQL parser options
+    pub sql_parser: SqlParserOptions,
     /// Optional extensions registered using [`Extensions::insert`]
     pub extensions: Extensions,
 }
@@ -341,6 +490,7 @@ impl ConfigField for ConfigOptions {
             "execution" => self.execution.set(rem, value),
             "optimizer" => self.optimizer.set(rem, value),
             "explain" => self.explain.set(rem, value),
+            "sql_parser" => self.sql_parser.set(rem, value),
             _ => Err(DataFusionError::Internal(format!(
                 "Config value \"{}\" not found on ConfigOptions",
                 key
@@ -353,6 +503,7 @@ impl ConfigField for ConfigOptions {
         self.execution.visit(v, "datafusion.execution", "");
         self.optimizer.visit(v, "datafusion.optimizer", "");
         self.explain.visit(v, "datafusion.explain", "");
+        self.sql_parser.visit(v, "datafusion.sql_parser", "");
     }
 }
 
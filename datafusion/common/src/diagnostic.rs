@@ -0,0 +1,231 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Structured information about errors, for tools like IDEs and notebooks
+
+use std::fmt::{Display, Formatter};
+
+/// A range of the SQL text, as the byte offsets `start..end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The offset of the first byte of the range
+    pub start: usize,
+    /// The offset right after the last byte of the range
+    pub end: usize,
+}
+
+impl Span {
+    /// Create the span `start..end`
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A stable code for the kind of an error, that tools can rely on instead of
+/// the message of the error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// A column that isn't in the schema of the input
+    UnknownColumn,
+    /// A column name that matches several columns of the input
+    AmbiguousColumn,
+    /// A table that isn't registered
+    UnknownTable,
+    /// A function that is neither built-in nor registered
+    UnknownFunction,
+}
+
+impl ErrorCode {
+    /// Returns the code as a string, like `unknown_column`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnknownColumn => "unknown_column",
+            Self::AmbiguousColumn => "ambiguous_column",
+            Self::UnknownTable => "unknown_table",
+            Self::UnknownFunction => "unknown_function",
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Describes what caused an error, so that it can be shown next to the
+/// part of the SQL query it is about, instead of parsing the message of
+/// the error.
+///
+/// The SQL planner works on an AST that doesn't know where its nodes come
+/// from, so it records the `snippet` of SQL the error is about, like the
+/// name of an unknown table, and the [`Span`] of the snippet is found
+/// afterwards in the SQL text of the query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// The kind of the error, if it has a code
+    pub code: Option<ErrorCode>,
+    /// A short description of the error
+    pub message: String,
+    /// The SQL text the error is about, like an identifier or a function name
+    pub snippet: Option<String>,
+    /// The location of the error in the SQL text of the query, if known
+    pub span: Option<Span>,
+    /// A valid name close to the unknown name the error is about, to ask
+    /// "did you mean ...?"
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic with `message`, that isn't about a part of the query
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            code: None,
+            message: message.into(),
+            snippet: None,
+            span: None,
+            suggestion: None,
+        }
+    }
+
+    /// Sets the code of the kind of the error
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the SQL text the error is about
+    pub fn with_snippet(mut self, snippet: impl Into<String>) -> Self {
+        self.snippet = Some(snippet.into());
+        self
+    }
+
+    /// Sets the location of the error in the SQL text
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Sets the name suggested instead of the unknown name
+    pub fn with_suggestion(mut self, suggestion: Option<String>) -> Self {
+        self.suggestion = suggestion;
+        self
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(code) = &self.code {
+            write!(f, "[{code}] ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(span) = &self.span {
+            write!(f, " at {span}")?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ". Did you mean '{suggestion}'?")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the candidate closest to `name`, ignoring case, if it is close
+/// enough to be a misspelling of `name`: at most a third of its characters,
+/// and at least one, differ.
+pub fn find_closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(&name, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// The number of characters to insert, delete or substitute to change `a`
+/// into `b`
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // the distances between the prefix of `a` and the prefixes of `b`
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a_char != *b_char);
+            previous = distances[j + 1];
+            distances[j + 1] =
+                substitution.min(distances[j] + 1).min(distances[j + 1] + 1);
+        }
+    }
+    distances[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("flaw", "lawn"), 2);
+        assert_eq!(levenshtein("héllo", "hello"), 1);
+    }
+
+    #[test]
+    fn test_find_closest_match() {
+        let candidates = ["count", "coalesce", "cos", "concat"];
+        assert_eq!(
+            find_closest_match("cont", candidates),
+            Some("count".to_string())
+        );
+        assert_eq!(
+            find_closest_match("COS", candidates),
+            Some("cos".to_string())
+        );
+        assert_eq!(
+            find_closest_match("coalese", candidates),
+            Some("coalesce".to_string())
+        );
+        assert_eq!(find_closest_match("sum", candidates), None);
+        assert_eq!(find_closest_match("x", Vec::<&str>::new()), None);
+    }
+
+    #[test]
+    fn diagnostic_display() {
+        let diagnostic = Diagnostic::new("Invalid function 'cont'")
+            .with_code(ErrorCode::UnknownFunction)
+            .with_span(Span::new(7, 11))
+            .with_suggestion(Some("count".to_string()));
+        assert_eq!(
+            diagnostic.to_string(),
+            "[unknown_function] Invalid function 'cont' at 7..11. Did you mean 'count'?"
+        );
+    }
+}
@@ -23,7 +23,7 @@ use std::io;
 use std::result;
 use std::sync::Arc;
 
-use crate::{Column, DFSchema};
+use crate::{Column, DFSchema, Diagnostic};
 #[cfg(feature = "avro")]
 use apache_avro::Error as AvroError;
 use arrow::error::ArrowError;
@@ -86,6 +86,9 @@ pub enum DataFusionError {
     JITError(ModuleError),
     /// Error with additional context
     Context(String, Box<DataFusionError>),
+    /// Error with a [`Diagnostic`] about its cause, like the part of the
+    /// SQL query that caused it
+    Diagnostic(Box<Diagnostic>, Box<DataFusionError>),
 }
 
 #[macro_export]
@@ -320,6 +323,7 @@ impl Display for DataFusionError {
             DataFusionError::Context(ref desc, ref err) => {
                 write!(f, "{}\ncaused by\n{}", desc, *err)
             }
+            DataFusionError::Diagnostic(_, ref err) => write!(f, "{}", *err),
         }
     }
 }
@@ -408,7 +412,7 @@ impl DataFusionError {
             }
 
             // traverse context chain
-            if let Self::Context(_msg, inner) = checkpoint {
+            if let Self::Context(_, inner) | Self::Diagnostic(_, inner) = checkpoint {
                 checkpoint = inner;
                 continue;
             }
@@ -433,6 +437,37 @@ impl DataFusionError {
         // return last checkpoint (which may be the original error)
         checkpoint
     }
+
+    /// Wraps this error in a [`DataFusionError::Context`] with `description`,
+    /// like the expression or the file that was processed when it happened
+    pub fn context(self, description: impl Into<String>) -> Self {
+        DataFusionError::Context(description.into(), Box::new(self))
+    }
+
+    /// Attaches `diagnostic` to this error
+    pub fn with_diagnostic(self, diagnostic: Diagnostic) -> Self {
+        DataFusionError::Diagnostic(Box::new(diagnostic), Box::new(self))
+    }
+
+    /// Returns the [`Diagnostic`] attached to this error or to the errors
+    /// it adds context to, if any
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Self::Diagnostic(diagnostic, _) => Some(diagnostic),
+            Self::Context(_, inner) => inner.diagnostic(),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the [`Diagnostic`] of this error, see
+    /// [`Self::diagnostic`]
+    pub fn diagnostic_mut(&mut self) -> Option<&mut Diagnostic> {
+        match self {
+            Self::Diagnostic(diagnostic, _) => Some(diagnostic),
+            Self::Context(_, inner) => inner.diagnostic_mut(),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -440,6 +475,7 @@ mod test {
     use std::sync::Arc;
 
     use crate::error::DataFusionError;
+    use crate::Diagnostic;
     use arrow::error::ArrowError;
 
     #[test]
@@ -451,6 +487,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn error_context() {
+        let err = DataFusionError::Execution("foo".to_string()).context("bar");
+        assert_eq!(err.to_string(), "bar\ncaused by\nExecution error: foo");
+        assert!(matches!(err.find_root(), DataFusionError::Execution(_)));
+    }
+
+    #[test]
+    fn error_diagnostic() {
+        let diagnostic = Diagnostic::new("table 't' not found").with_snippet("t");
+        let err = DataFusionError::Plan("table 't' not found".to_string())
+            .with_diagnostic(diagnostic.clone())
+            .context("bar");
+        assert_eq!(
+            err.to_string(),
+            "bar\ncaused by\nError during planning: table 't' not found"
+        );
+        assert_eq!(err.diagnostic(), Some(&diagnostic));
+        assert!(matches!(err.find_root(), DataFusionError::Plan(_)));
+        assert_eq!(DataFusionError::Plan("foo".to_string()).diagnostic(), None);
+    }
+
     #[test]
     fn datafusion_error_to_arrow() {
         let res = return_datafusion_error().unwrap_err();
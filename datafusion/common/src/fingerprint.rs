@@ -0,0 +1,141 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Deterministic fingerprints of the canonical forms of plans, for the plan
+//! and result caches of downstream systems
+
+use std::collections::HashMap;
+use std::fmt;
+
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// A 128-bit fingerprint, computed with the FNV-1a hash so that it is the
+/// same across processes, platforms and releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(u128);
+
+impl Fingerprint {
+    /// The fingerprint of `bytes`
+    pub fn of(bytes: &[u8]) -> Self {
+        let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ *byte as u128).wrapping_mul(FNV_PRIME)
+        });
+        Self(hash)
+    }
+
+    /// The 128 bits of the fingerprint
+    pub fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// The fingerprint folded to 64 bits
+    pub fn as_u64(&self) -> u64 {
+        (self.0 ^ (self.0 >> 64)) as u64
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+/// Renumbers the aliases generated by the optimizer, such as
+/// `__correlated_sq_3`, in their order of appearance in `text`, for each
+/// prefix. The same plan optimized twice then has the same canonical form,
+/// whatever aliases were generated before.
+pub fn normalize_generated_aliases(text: &str) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut counters: HashMap<&str, usize> = HashMap::new();
+    let mut renamed: HashMap<&str, String> = HashMap::new();
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("__") {
+        let preceded_by_ident = rest[..start].chars().next_back().map_or(false, is_ident);
+        let len = rest[start..]
+            .find(|c: char| !is_ident(c))
+            .unwrap_or(rest.len() - start);
+        let ident = &rest[start..start + len];
+        normalized.push_str(&rest[..start]);
+        rest = &rest[start + len..];
+
+        let prefix = match ident.rsplit_once('_') {
+            Some((prefix, id))
+                if !preceded_by_ident
+                    && prefix.len() > 2
+                    && !id.is_empty()
+                    && id.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                prefix
+            }
+            _ => {
+                normalized.push_str(ident);
+                continue;
+            }
+        };
+        let alias = renamed.entry(ident).or_insert_with(|| {
+            let counter = counters.entry(prefix).or_insert(0);
+            *counter += 1;
+            format!("{prefix}_{counter}")
+        });
+        normalized.push_str(alias);
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint() {
+        // the FNV-1a test vectors
+        assert_eq!(
+            Fingerprint::of(b"").to_string(),
+            "6c62272e07bb014262b821756295c58d"
+        );
+        assert_eq!(
+            Fingerprint::of(b"a").as_u128(),
+            0xd228cb696f1a8caf78912b704e4a8964
+        );
+        assert_ne!(Fingerprint::of(b"ab"), Fingerprint::of(b"ba"));
+        let fingerprint = Fingerprint::of(b"a");
+        assert_eq!(
+            fingerprint.as_u64(),
+            (fingerprint.as_u128() as u64) ^ ((fingerprint.as_u128() >> 64) as u64)
+        );
+    }
+
+    #[test]
+    fn normalize_aliases() {
+        let text = "Join: t.a = __correlated_sq_7.a, t.b = __scalar_sq_3.b\
+                    \n  SubqueryAlias: __correlated_sq_7\
+                    \n  SubqueryAlias: __scalar_sq_3\
+                    \n  SubqueryAlias: __correlated_sq_4\
+                    \n  TableScan: my__table_1, __private, x__y_2";
+        assert_eq!(
+            normalize_generated_aliases(text),
+            "Join: t.a = __correlated_sq_1.a, t.b = __scalar_sq_1.b\
+             \n  SubqueryAlias: __correlated_sq_1\
+             \n  SubqueryAlias: __scalar_sq_1\
+             \n  SubqueryAlias: __correlated_sq_2\
+             \n  TableScan: my__table_1, __private, x__y_2"
+        );
+    }
+}
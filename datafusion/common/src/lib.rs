@@ -21,7 +21,9 @@ mod column;
 pub mod config;
 pub mod delta;
 mod dfschema;
+mod diagnostic;
 mod error;
+pub mod fingerprint;
 pub mod from_slice;
 pub mod parsers;
 #[cfg(feature = "pyarrow")]
@@ -30,14 +32,16 @@ pub mod scalar;
 pub mod stats;
 mod table_reference;
 pub mod test_util;
+pub mod tree_node;
 
 use arrow::compute::SortOptions;
 pub use column::Column;
 pub use dfschema::{DFField, DFSchema, DFSchemaRef, ExprSchema, ToDFSchema};
+pub use diagnostic::{find_closest_match, Diagnostic, ErrorCode, Span};
 pub use error::{field_not_found, DataFusionError, Result, SchemaError};
 pub use parsers::parse_interval;
 pub use scalar::{ScalarType, ScalarValue};
-pub use stats::{ColumnStatistics, Statistics};
+pub use stats::{ColumnStatistics, Precision, Statistics};
 pub use table_reference::{OwnedTableReference, ResolvedTableReference, TableReference};
 
 /// Downcast an Arrow Array to a concrete type, return an `DataFusionError::Internal` if the cast is
@@ -34,6 +34,10 @@ pub enum CompressionTypeVariant {
     BZIP2,
     /// Xz-ed file (liblzma)
     XZ,
+    /// Zstd-ed file
+    ZSTD,
+    /// Blocked gzip-ed file (BGZF), that can be read in byte ranges
+    BGZIP,
     /// Uncompressed file
     UNCOMPRESSED,
 }
@@ -47,6 +51,8 @@ impl FromStr for CompressionTypeVariant {
             "GZIP" | "GZ" => Ok(Self::GZIP),
             "BZIP2" | "BZ2" => Ok(Self::BZIP2),
             "XZ" => Ok(Self::XZ),
+            "ZSTD" | "ZST" => Ok(Self::ZSTD),
+            "BGZIP" | "BGZF" => Ok(Self::BGZIP),
             "" => Ok(Self::UNCOMPRESSED),
             _ => Err(ParserError::ParserError(format!(
                 "Unsupported file compression type {s}"
@@ -61,6 +67,8 @@ impl ToString for CompressionTypeVariant {
             Self::GZIP => "GZIP",
             Self::BZIP2 => "BZIP2",
             Self::XZ => "XZ",
+            Self::ZSTD => "ZSTD",
+            Self::BGZIP => "BGZIP",
             Self::UNCOMPRESSED => "",
         }
         .to_string()
@@ -114,7 +114,7 @@ mod tests {
             ScalarValue::Boolean(Some(true)),
             ScalarValue::Int32(Some(23)),
             ScalarValue::Float64(Some(12.34)),
-            ScalarValue::Utf8(Some("Hello!".to_string())),
+            ScalarValue::Utf8(Some("Hello!".into())),
             ScalarValue::Date32(Some(1234)),
         ];
 
@@ -137,7 +137,7 @@ mod tests {
             let py_float = scalar_float.into_py(py).call_method0(py, "as_py").unwrap();
             py_run!(py, py_float, "assert py_float == 12.34");
 
-            let scalar_string = ScalarValue::Utf8(Some("Hello!".to_string()));
+            let scalar_string = ScalarValue::Utf8(Some("Hello!".into()));
             let py_string = scalar_string.into_py(py).call_method0(py, "as_py").unwrap();
             py_run!(py, py_string, "assert py_string == 'Hello!'");
         });
@@ -33,6 +33,7 @@ use crate::delta::shift_months;
 use crate::error::{DataFusionError, Result};
 use arrow::{
     array::*,
+    buffer::Buffer,
     compute::kernels::cast::{cast, cast_with_options, CastOptions},
     datatypes::{
         ArrowDictionaryKeyType, ArrowNativeType, DataType, Field, Float32Type,
@@ -79,18 +80,18 @@ pub enum ScalarValue {
     UInt32(Option<u32>),
     /// unsigned 64bit int
     UInt64(Option<u64>),
-    /// utf-8 encoded string.
-    Utf8(Option<String>),
+    /// utf-8 encoded string, shared so that cloning doesn't copy it.
+    Utf8(Option<Arc<str>>),
     /// utf-8 encoded string representing a LargeString's arrow type.
-    LargeUtf8(Option<String>),
+    LargeUtf8(Option<Arc<str>>),
     /// binary
-    Binary(Option<Vec<u8>>),
+    Binary(Option<Arc<[u8]>>),
     /// fixed size binary
-    FixedSizeBinary(i32, Option<Vec<u8>>),
+    FixedSizeBinary(i32, Option<Arc<[u8]>>),
     /// large binary
-    LargeBinary(Option<Vec<u8>>),
-    /// list of nested ScalarValue
-    List(Option<Vec<ScalarValue>>, Box<Field>),
+    LargeBinary(Option<Arc<[u8]>>),
+    /// list of the values of an array of the type of the field
+    List(Option<ArrayRef>, Box<Field>),
     /// Date stored as a signed 32bit int days since UNIX epoch 1970-01-01
     Date32(Option<i32>),
     /// Date stored as a signed 64bit int milliseconds since UNIX epoch 1970-01-01
@@ -120,6 +121,14 @@ pub enum ScalarValue {
     /// Months and days are encoded as 32-bit signed integers.
     /// Nanoseconds is encoded as a 64-bit signed integer (no leap seconds).
     IntervalMonthDayNano(Option<i128>),
+    /// Duration in seconds
+    DurationSecond(Option<i64>),
+    /// Duration in milliseconds
+    DurationMillisecond(Option<i64>),
+    /// Duration in microseconds
+    DurationMicrosecond(Option<i64>),
+    /// Duration in nanoseconds
+    DurationNanosecond(Option<i64>),
     /// struct of nested ScalarValue
     Struct(Option<Vec<ScalarValue>>, Box<Vec<Field>>),
     /// Dictionary type: index type and value
@@ -204,6 +213,14 @@ impl PartialEq for ScalarValue {
             (IntervalDayTime(_), _) => false,
             (IntervalMonthDayNano(v1), IntervalMonthDayNano(v2)) => v1.eq(v2),
             (IntervalMonthDayNano(_), _) => false,
+            (DurationSecond(v1), DurationSecond(v2)) => v1.eq(v2),
+            (DurationSecond(_), _) => false,
+            (DurationMillisecond(v1), DurationMillisecond(v2)) => v1.eq(v2),
+            (DurationMillisecond(_), _) => false,
+            (DurationMicrosecond(v1), DurationMicrosecond(v2)) => v1.eq(v2),
+            (DurationMicrosecond(_), _) => false,
+            (DurationNanosecond(v1), DurationNanosecond(v2)) => v1.eq(v2),
+            (DurationNanosecond(_), _) => false,
             (Struct(v1, t1), Struct(v2, t2)) => v1.eq(v2) && t1.eq(t2),
             (Struct(_, _), _) => false,
             (Dictionary(k1, v1), Dictionary(k2, v2)) => k1.eq(k2) && v1.eq(v2),
@@ -271,7 +288,7 @@ impl PartialOrd for ScalarValue {
             (LargeBinary(_), _) => None,
             (List(v1, t1), List(v2, t2)) => {
                 if t1.eq(t2) {
-                    v1.partial_cmp(v2)
+                    partial_cmp_list(v1.as_ref(), v2.as_ref())
                 } else {
                     None
                 }
@@ -309,6 +326,14 @@ impl PartialOrd for ScalarValue {
             (IntervalDayTime(_), _) => None,
             (IntervalMonthDayNano(v1), IntervalMonthDayNano(v2)) => v1.partial_cmp(v2),
             (IntervalMonthDayNano(_), _) => None,
+            (DurationSecond(v1), DurationSecond(v2)) => v1.partial_cmp(v2),
+            (DurationSecond(_), _) => None,
+            (DurationMillisecond(v1), DurationMillisecond(v2)) => v1.partial_cmp(v2),
+            (DurationMillisecond(_), _) => None,
+            (DurationMicrosecond(v1), DurationMicrosecond(v2)) => v1.partial_cmp(v2),
+            (DurationMicrosecond(_), _) => None,
+            (DurationNanosecond(v1), DurationNanosecond(v2)) => v1.partial_cmp(v2),
+            (DurationNanosecond(_), _) => None,
             (Struct(v1, t1), Struct(v2, t2)) => {
                 if t1.eq(t2) {
                     v1.partial_cmp(v2)
@@ -334,6 +359,19 @@ impl PartialOrd for ScalarValue {
 
 impl Eq for ScalarValue {}
 
+/// Compares the values of two lists like vectors of scalars, element by element
+fn partial_cmp_list(v1: Option<&ArrayRef>, v2: Option<&ArrayRef>) -> Option<Ordering> {
+    match (v1, v2) {
+        (Some(v1), Some(v2)) => {
+            let v1 = ScalarValue::convert_array_to_scalar_vec(v1).ok()?;
+            let v2 = ScalarValue::convert_array_to_scalar_vec(v2).ok()?;
+            v1.partial_cmp(&v2)
+        }
+        // a null list is lower than any other
+        (v1, v2) => v1.is_some().partial_cmp(&v2.is_some()),
+    }
+}
+
 // TODO implement this in arrow-rs with simd
 // https://github.com/apache/arrow-rs/issues/1010
 macro_rules! decimal_op {
@@ -503,6 +541,27 @@ macro_rules! impl_op {
             (ScalarValue::Int8(lhs), ScalarValue::Int8(rhs)) => {
                 primitive_op!(lhs, rhs, Int8, $OPERATION)
             }
+            (ScalarValue::DurationSecond(lhs), ScalarValue::DurationSecond(rhs)) => {
+                primitive_op!(lhs, rhs, DurationSecond, $OPERATION)
+            }
+            (
+                ScalarValue::DurationMillisecond(lhs),
+                ScalarValue::DurationMillisecond(rhs),
+            ) => {
+                primitive_op!(lhs, rhs, DurationMillisecond, $OPERATION)
+            }
+            (
+                ScalarValue::DurationMicrosecond(lhs),
+                ScalarValue::DurationMicrosecond(rhs),
+            ) => {
+                primitive_op!(lhs, rhs, DurationMicrosecond, $OPERATION)
+            }
+            (
+                ScalarValue::DurationNanosecond(lhs),
+                ScalarValue::DurationNanosecond(rhs),
+            ) => {
+                primitive_op!(lhs, rhs, DurationNanosecond, $OPERATION)
+            }
             // Binary operations on arguments with different types:
             (ScalarValue::Date32(Some(days)), _) => {
                 let value = date32_add(*days, $RHS, get_sign!($OPERATION))?;
@@ -528,6 +587,23 @@ macro_rules! impl_op {
                 let value = nanoseconds_add(*ts_ns, $RHS, get_sign!($OPERATION))?;
                 Ok(ScalarValue::TimestampNanosecond(Some(value), zone.clone()))
             }
+            (ScalarValue::Time32Second(Some(t)), _) => {
+                let value =
+                    time_add(*t as i64, 1_000_000_000, $RHS, get_sign!($OPERATION))?;
+                Ok(ScalarValue::Time32Second(Some(value as i32)))
+            }
+            (ScalarValue::Time32Millisecond(Some(t)), _) => {
+                let value = time_add(*t as i64, 1_000_000, $RHS, get_sign!($OPERATION))?;
+                Ok(ScalarValue::Time32Millisecond(Some(value as i32)))
+            }
+            (ScalarValue::Time64Microsecond(Some(t)), _) => {
+                let value = time_add(*t, 1000, $RHS, get_sign!($OPERATION))?;
+                Ok(ScalarValue::Time64Microsecond(Some(value)))
+            }
+            (ScalarValue::Time64Nanosecond(Some(t)), _) => {
+                let value = time_add(*t, 1, $RHS, get_sign!($OPERATION))?;
+                Ok(ScalarValue::Time64Nanosecond(Some(value)))
+            }
             _ => Err(DataFusionError::Internal(format!(
                 "Operator {} is not implemented for types {:?} and {:?}",
                 stringify!($OPERATION),
@@ -568,27 +644,67 @@ pub fn seconds_add(ts_s: i64, scalar: &ScalarValue, sign: i32) -> Result<i64> {
     Ok(do_date_time_math(ts_s, 0, scalar, sign)?.timestamp())
 }
 
+// The fractional part of the timestamps is split with `rem_euclid`, as it
+// must be positive for the timestamps before the epoch.
 #[inline]
 pub fn milliseconds_add(ts_ms: i64, scalar: &ScalarValue, sign: i32) -> Result<i64> {
-    let secs = ts_ms / 1000;
-    let nsecs = ((ts_ms % 1000) * 1_000_000) as u32;
+    let secs = ts_ms.div_euclid(1000);
+    let nsecs = (ts_ms.rem_euclid(1000) * 1_000_000) as u32;
     Ok(do_date_time_math(secs, nsecs, scalar, sign)?.timestamp_millis())
 }
 
 #[inline]
 pub fn microseconds_add(ts_us: i64, scalar: &ScalarValue, sign: i32) -> Result<i64> {
-    let secs = ts_us / 1_000_000;
-    let nsecs = ((ts_us % 1_000_000) * 1000) as u32;
+    let secs = ts_us.div_euclid(1_000_000);
+    let nsecs = (ts_us.rem_euclid(1_000_000) * 1000) as u32;
     Ok(do_date_time_math(secs, nsecs, scalar, sign)?.timestamp_nanos() / 1000)
 }
 
 #[inline]
 pub fn nanoseconds_add(ts_ns: i64, scalar: &ScalarValue, sign: i32) -> Result<i64> {
-    let secs = ts_ns / 1_000_000_000;
-    let nsecs = (ts_ns % 1_000_000_000) as u32;
+    let secs = ts_ns.div_euclid(1_000_000_000);
+    let nsecs = ts_ns.rem_euclid(1_000_000_000) as u32;
     Ok(do_date_time_math(secs, nsecs, scalar, sign)?.timestamp_nanos())
 }
 
+/// Adds `sign` times the interval `scalar` to `time`, a time of day in units
+/// of `unit_nanos` nanoseconds. Only the time part of the interval is added,
+/// and the result is not wrapped around midnight, so that the bounds of RANGE
+/// window frames keep the order of the times they are computed from.
+#[inline]
+pub fn time_add(
+    time: i64,
+    unit_nanos: i64,
+    scalar: &ScalarValue,
+    sign: i32,
+) -> Result<i64> {
+    let (months, days, nanos) = match scalar {
+        ScalarValue::IntervalDayTime(Some(i)) => {
+            let (days, ms) = IntervalDayTimeType::to_parts(*i);
+            (0, days, ms as i64 * 1_000_000)
+        }
+        ScalarValue::IntervalYearMonth(Some(i)) => (*i, 0, 0),
+        ScalarValue::IntervalMonthDayNano(Some(i)) => {
+            IntervalMonthDayNanoType::to_parts(*i)
+        }
+        ScalarValue::DurationSecond(Some(v)) => (0, 0, v * 1_000_000_000),
+        ScalarValue::DurationMillisecond(Some(v)) => (0, 0, v * 1_000_000),
+        ScalarValue::DurationMicrosecond(Some(v)) => (0, 0, v * 1000),
+        ScalarValue::DurationNanosecond(Some(v)) => (0, 0, *v),
+        other => {
+            return Err(DataFusionError::Execution(format!(
+                "Time arithmetic does not support non-interval type {other:?}"
+            )))
+        }
+    };
+    if months != 0 || days != 0 {
+        return Err(DataFusionError::Execution(format!(
+            "Cannot add an interval of months or days to a time: {scalar:?}"
+        )));
+    }
+    Ok(time + sign as i64 * nanos / unit_nanos)
+}
+
 #[inline]
 fn do_date_time_math(
     secs: i64,
@@ -612,6 +728,18 @@ where
         ScalarValue::IntervalDayTime(Some(i)) => add_day_time(prior, *i, sign),
         ScalarValue::IntervalYearMonth(Some(i)) => shift_months(prior, *i * sign),
         ScalarValue::IntervalMonthDayNano(Some(i)) => add_m_d_nano(prior, *i, sign),
+        ScalarValue::DurationSecond(Some(v)) => {
+            prior.add(Duration::seconds(v * sign as i64))
+        }
+        ScalarValue::DurationMillisecond(Some(v)) => {
+            prior.add(Duration::milliseconds(v * sign as i64))
+        }
+        ScalarValue::DurationMicrosecond(Some(v)) => {
+            prior.add(Duration::microseconds(v * sign as i64))
+        }
+        ScalarValue::DurationNanosecond(Some(v)) => {
+            prior.add(Duration::nanoseconds(v * sign as i64))
+        }
         other => Err(DataFusionError::Execution(format!(
             "DateIntervalExpr does not support non-interval type {other:?}"
         )))?,
@@ -660,6 +788,60 @@ macro_rules! hash_float_value {
 
 hash_float_value!((f64, u64), (f32, u32));
 
+macro_rules! hash_typed_values {
+    ($array:expr, $state:expr, $ARRAYTYPE:ident) => {{
+        let array = $array.as_any().downcast_ref::<$ARRAYTYPE>().unwrap();
+        for index in 0..array.len() {
+            array.is_valid(index).hash($state);
+            if array.is_valid(index) {
+                array.value(index).hash($state);
+            }
+        }
+    }};
+    ($array:expr, $state:expr, $ARRAYTYPE:ident, $WRAPPER:ident) => {{
+        let array = $array.as_any().downcast_ref::<$ARRAYTYPE>().unwrap();
+        for index in 0..array.len() {
+            array.is_valid(index).hash($state);
+            if array.is_valid(index) {
+                $WRAPPER(array.value(index)).hash($state);
+            }
+        }
+    }};
+}
+
+/// Hashes the values of the `array` of a [`ScalarValue::List`] in place,
+/// consistently with the equality of the arrays
+fn hash_list_values<H: std::hash::Hasher>(array: &dyn Array, state: &mut H) {
+    use std::hash::Hash;
+    array.len().hash(state);
+    match array.data_type() {
+        DataType::Boolean => hash_typed_values!(array, state, BooleanArray),
+        DataType::Int8 => hash_typed_values!(array, state, Int8Array),
+        DataType::Int16 => hash_typed_values!(array, state, Int16Array),
+        DataType::Int32 => hash_typed_values!(array, state, Int32Array),
+        DataType::Int64 => hash_typed_values!(array, state, Int64Array),
+        DataType::UInt8 => hash_typed_values!(array, state, UInt8Array),
+        DataType::UInt16 => hash_typed_values!(array, state, UInt16Array),
+        DataType::UInt32 => hash_typed_values!(array, state, UInt32Array),
+        DataType::UInt64 => hash_typed_values!(array, state, UInt64Array),
+        DataType::Float32 => hash_typed_values!(array, state, Float32Array, Fl),
+        DataType::Float64 => hash_typed_values!(array, state, Float64Array, Fl),
+        DataType::Date32 => hash_typed_values!(array, state, Date32Array),
+        DataType::Date64 => hash_typed_values!(array, state, Date64Array),
+        DataType::Utf8 => hash_typed_values!(array, state, StringArray),
+        DataType::LargeUtf8 => hash_typed_values!(array, state, LargeStringArray),
+        DataType::Binary => hash_typed_values!(array, state, BinaryArray),
+        DataType::LargeBinary => hash_typed_values!(array, state, LargeBinaryArray),
+        // nested and less common types are hashed value by value, without
+        // collecting them
+        _ => {
+            for index in 0..array.len() {
+                ScalarValue::try_from_array(array, index).ok().hash(state);
+            }
+        }
+    }
+}
+
 // manual implementation of `Hash`
 impl std::hash::Hash for ScalarValue {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -687,7 +869,10 @@ impl std::hash::Hash for ScalarValue {
             FixedSizeBinary(_, v) => v.hash(state),
             LargeBinary(v) => v.hash(state),
             List(v, t) => {
-                v.hash(state);
+                v.is_some().hash(state);
+                if let Some(v) = v {
+                    hash_list_values(v.as_ref(), state);
+                }
                 t.hash(state);
             }
             Date32(v) => v.hash(state),
@@ -703,6 +888,10 @@ impl std::hash::Hash for ScalarValue {
             IntervalYearMonth(v) => v.hash(state),
             IntervalDayTime(v) => v.hash(state),
             IntervalMonthDayNano(v) => v.hash(state),
+            DurationSecond(v)
+            | DurationMillisecond(v)
+            | DurationMicrosecond(v)
+            | DurationNanosecond(v) => v.hash(state),
             Struct(v, t) => {
                 v.hash(state);
                 t.hash(state);
@@ -811,134 +1000,6 @@ macro_rules! typed_cast {
     }};
 }
 
-// keep until https://github.com/apache/arrow-rs/issues/2054 is finished
-macro_rules! build_list {
-    ($VALUE_BUILDER_TY:ident, $SCALAR_TY:ident, $VALUES:expr, $SIZE:expr) => {{
-        match $VALUES {
-            // the return on the macro is necessary, to short-circuit and return ArrayRef
-            None => {
-                return new_null_array(
-                    &DataType::List(Box::new(Field::new(
-                        "item",
-                        DataType::$SCALAR_TY,
-                        true,
-                    ))),
-                    $SIZE,
-                )
-            }
-            Some(values) => {
-                build_values_list!($VALUE_BUILDER_TY, $SCALAR_TY, values, $SIZE)
-            }
-        }
-    }};
-}
-
-macro_rules! build_timestamp_list {
-    ($TIME_UNIT:expr, $TIME_ZONE:expr, $VALUES:expr, $SIZE:expr) => {{
-        match $VALUES {
-            // the return on the macro is necessary, to short-circuit and return ArrayRef
-            None => {
-                return new_null_array(
-                    &DataType::List(Box::new(Field::new(
-                        "item",
-                        DataType::Timestamp($TIME_UNIT, $TIME_ZONE),
-                        true,
-                    ))),
-                    $SIZE,
-                )
-            }
-            Some(values) => match $TIME_UNIT {
-                TimeUnit::Second => {
-                    build_values_list_tz!(
-                        TimestampSecondBuilder,
-                        TimestampSecond,
-                        values,
-                        $SIZE
-                    )
-                }
-                TimeUnit::Microsecond => build_values_list_tz!(
-                    TimestampMillisecondBuilder,
-                    TimestampMillisecond,
-                    values,
-                    $SIZE
-                ),
-                TimeUnit::Millisecond => build_values_list_tz!(
-                    TimestampMicrosecondBuilder,
-                    TimestampMicrosecond,
-                    values,
-                    $SIZE
-                ),
-                TimeUnit::Nanosecond => build_values_list_tz!(
-                    TimestampNanosecondBuilder,
-                    TimestampNanosecond,
-                    values,
-                    $SIZE
-                ),
-            },
-        }
-    }};
-}
-
-macro_rules! new_builder {
-    (StringBuilder, $len:expr) => {
-        StringBuilder::new()
-    };
-    (LargeStringBuilder, $len:expr) => {
-        LargeStringBuilder::new()
-    };
-    ($el:ident, $len:expr) => {{
-        <$el>::with_capacity($len)
-    }};
-}
-
-macro_rules! build_values_list {
-    ($VALUE_BUILDER_TY:ident, $SCALAR_TY:ident, $VALUES:expr, $SIZE:expr) => {{
-        let builder = new_builder!($VALUE_BUILDER_TY, $VALUES.len());
-        let mut builder = ListBuilder::new(builder);
-
-        for _ in 0..$SIZE {
-            for scalar_value in $VALUES {
-                match scalar_value {
-                    ScalarValue::$SCALAR_TY(Some(v)) => {
-                        builder.values().append_value(v.clone());
-                    }
-                    ScalarValue::$SCALAR_TY(None) => {
-                        builder.values().append_null();
-                    }
-                    _ => panic!("Incompatible ScalarValue for list"),
-                };
-            }
-            builder.append(true);
-        }
-
-        builder.finish()
-    }};
-}
-
-macro_rules! build_values_list_tz {
-    ($VALUE_BUILDER_TY:ident, $SCALAR_TY:ident, $VALUES:expr, $SIZE:expr) => {{
-        let mut builder =
-            ListBuilder::new($VALUE_BUILDER_TY::with_capacity($VALUES.len()));
-
-        for _ in 0..$SIZE {
-            for scalar_value in $VALUES {
-                match scalar_value {
-                    ScalarValue::$SCALAR_TY(Some(v), _) => {
-                        builder.values().append_value(v.clone());
-                    }
-                    ScalarValue::$SCALAR_TY(None, _) => {
-                        builder.values().append_null();
-                    }
-                    _ => panic!("Incompatible ScalarValue for list"),
-                };
-            }
-            builder.append(true);
-        }
-
-        builder.finish()
-    }};
-}
-
 macro_rules! build_array_from_option {
     ($DATA_TYPE:ident, $ARRAY_TYPE:ident, $EXPR:expr, $SIZE:expr) => {{
         match $EXPR {
@@ -989,7 +1050,7 @@ impl ScalarValue {
     }
 
     /// Returns a [`ScalarValue::Utf8`] representing `val`
-    pub fn new_utf8(val: impl Into<String>) -> Self {
+    pub fn new_utf8(val: impl Into<Arc<str>>) -> Self {
         ScalarValue::Utf8(Some(val.into()))
     }
 
@@ -1015,8 +1076,29 @@ impl ScalarValue {
     }
 
     /// Create a new nullable ScalarValue::List with the specified child_type
-    pub fn new_list(scalars: Option<Vec<Self>>, child_type: DataType) -> Self {
-        Self::List(scalars, Box::new(Field::new("item", child_type, true)))
+    ///
+    /// Returns an error if the scalars are not all of `child_type`
+    pub fn new_list(scalars: Option<Vec<Self>>, child_type: DataType) -> Result<Self> {
+        let values = match scalars {
+            Some(scalars) if scalars.is_empty() => Some(new_empty_array(&child_type)),
+            // the values of timestamps with a time zone are built without it
+            Some(scalars) => Some(cast(&Self::iter_to_array(scalars)?, &child_type)?),
+            None => None,
+        };
+        Ok(Self::new_list_from_array(values, child_type))
+    }
+
+    /// Create a new nullable ScalarValue::List of the values of an array of
+    /// `child_type`, without copying them
+    pub fn new_list_from_array(values: Option<ArrayRef>, child_type: DataType) -> Self {
+        Self::List(values, Box::new(Field::new("item", child_type, true)))
+    }
+
+    /// Converts all the values of `array` into ScalarValues
+    pub fn convert_array_to_scalar_vec(array: &dyn Array) -> Result<Vec<Self>> {
+        (0..array.len())
+            .map(|index| Self::try_from_array(array, index))
+            .collect()
     }
 
     /// Getter for the `DataType` of the value
@@ -1071,6 +1153,16 @@ impl ScalarValue {
             ScalarValue::IntervalMonthDayNano(_) => {
                 DataType::Interval(IntervalUnit::MonthDayNano)
             }
+            ScalarValue::DurationSecond(_) => DataType::Duration(TimeUnit::Second),
+            ScalarValue::DurationMillisecond(_) => {
+                DataType::Duration(TimeUnit::Millisecond)
+            }
+            ScalarValue::DurationMicrosecond(_) => {
+                DataType::Duration(TimeUnit::Microsecond)
+            }
+            ScalarValue::DurationNanosecond(_) => {
+                DataType::Duration(TimeUnit::Nanosecond)
+            }
             ScalarValue::Struct(_, fields) => DataType::Struct(fields.as_ref().clone()),
             ScalarValue::Dictionary(k, v) => {
                 DataType::Dictionary(k.clone(), Box::new(v.get_datatype()))
@@ -1157,15 +1249,20 @@ impl ScalarValue {
             ScalarValue::IntervalYearMonth(v) => v.is_none(),
             ScalarValue::IntervalDayTime(v) => v.is_none(),
             ScalarValue::IntervalMonthDayNano(v) => v.is_none(),
+            ScalarValue::DurationSecond(v)
+            | ScalarValue::DurationMillisecond(v)
+            | ScalarValue::DurationMicrosecond(v)
+            | ScalarValue::DurationNanosecond(v) => v.is_none(),
             ScalarValue::Struct(v, _) => v.is_none(),
             ScalarValue::Dictionary(_, v) => v.is_null(),
         }
     }
 
-    /// Absolute distance between two numeric values (of the same type). This method will return
-    /// None if either one of the arguments are null. It might also return None if the resulting
-    /// distance is greater than [`usize::MAX`]. If the type is a float, then the distance will be
-    /// rounded to the nearest integer.
+    /// Absolute distance between two numeric or temporal values (of the same type). This method
+    /// will return None if either one of the arguments are null. It might also return None if the
+    /// resulting distance is greater than [`usize::MAX`]. If the type is a float, then the distance
+    /// will be rounded to the nearest integer. The distance between temporal values is in their
+    /// unit (e.g. days for `Date32`).
     ///
     ///
     /// Note: the datatype itself must support subtraction.
@@ -1177,6 +1274,49 @@ impl ScalarValue {
             return None;
         }
 
+        let temporal = match (self, other) {
+            (ScalarValue::Date32(Some(l)), ScalarValue::Date32(Some(r))) => {
+                Some((*l as i128, *r as i128))
+            }
+            (ScalarValue::Date64(Some(l)), ScalarValue::Date64(Some(r)))
+            | (
+                ScalarValue::TimestampSecond(Some(l), _),
+                ScalarValue::TimestampSecond(Some(r), _),
+            )
+            | (
+                ScalarValue::TimestampMillisecond(Some(l), _),
+                ScalarValue::TimestampMillisecond(Some(r), _),
+            )
+            | (
+                ScalarValue::TimestampMicrosecond(Some(l), _),
+                ScalarValue::TimestampMicrosecond(Some(r), _),
+            )
+            | (
+                ScalarValue::TimestampNanosecond(Some(l), _),
+                ScalarValue::TimestampNanosecond(Some(r), _),
+            )
+            | (
+                ScalarValue::DurationSecond(Some(l)),
+                ScalarValue::DurationSecond(Some(r)),
+            )
+            | (
+                ScalarValue::DurationMillisecond(Some(l)),
+                ScalarValue::DurationMillisecond(Some(r)),
+            )
+            | (
+                ScalarValue::DurationMicrosecond(Some(l)),
+                ScalarValue::DurationMicrosecond(Some(r)),
+            )
+            | (
+                ScalarValue::DurationNanosecond(Some(l)),
+                ScalarValue::DurationNanosecond(Some(r)),
+            ) => Some((*l as i128, *r as i128)),
+            _ => None,
+        };
+        if let Some((l, r)) = temporal {
+            return usize::try_from((l - r).abs()).ok();
+        }
+
         let distance = if self > other {
             self.sub(other).ok()?
         } else {
@@ -1314,72 +1454,6 @@ impl ScalarValue {
             }};
         }
 
-        macro_rules! build_array_list_primitive {
-            ($ARRAY_TY:ident, $SCALAR_TY:ident, $NATIVE_TYPE:ident) => {{
-                Arc::new(ListArray::from_iter_primitive::<$ARRAY_TY, _, _>(
-                    scalars.into_iter().map(|x| match x {
-                        ScalarValue::List(xs, _) => xs.map(|x| {
-                            x.iter().map(|x| match x {
-                                ScalarValue::$SCALAR_TY(i) => *i,
-                                sv => panic!(
-                                    "Inconsistent types in ScalarValue::iter_to_array. \
-                                        Expected {:?}, got {:?}",
-                                    data_type, sv
-                                ),
-                            })
-                            .collect::<Vec<Option<$NATIVE_TYPE>>>()
-                        }),
-                        sv => panic!(
-                            "Inconsistent types in ScalarValue::iter_to_array. \
-                                Expected {:?}, got {:?}",
-                            data_type, sv
-                        ),
-                    }),
-                ))
-            }};
-        }
-
-        macro_rules! build_array_list_string {
-            ($BUILDER:ident, $SCALAR_TY:ident) => {{
-                let mut builder = ListBuilder::new($BUILDER::new());
-                for scalar in scalars.into_iter() {
-                    match scalar {
-                        ScalarValue::List(Some(xs), _) => {
-                            for s in xs {
-                                match s {
-                                    ScalarValue::$SCALAR_TY(Some(val)) => {
-                                        builder.values().append_value(val);
-                                    }
-                                    ScalarValue::$SCALAR_TY(None) => {
-                                        builder.values().append_null();
-                                    }
-                                    sv => {
-                                        return Err(DataFusionError::Internal(format!(
-                                            "Inconsistent types in ScalarValue::iter_to_array. \
-                                                Expected Utf8, got {:?}",
-                                            sv
-                                        )))
-                                    }
-                                }
-                            }
-                            builder.append(true);
-                        }
-                        ScalarValue::List(None, _) => {
-                            builder.append(false);
-                        }
-                        sv => {
-                            return Err(DataFusionError::Internal(format!(
-                                "Inconsistent types in ScalarValue::iter_to_array. \
-                                    Expected List, got {:?}",
-                                sv
-                            )))
-                        }
-                    }
-                }
-                Arc::new(builder.finish())
-            }};
-        }
-
         let array: ArrayRef = match &data_type {
             DataType::Decimal128(precision, scale) => {
                 let decimal_array =
@@ -1439,44 +1513,19 @@ impl ScalarValue {
             DataType::Interval(IntervalUnit::YearMonth) => {
                 build_array_primitive!(IntervalYearMonthArray, IntervalYearMonth)
             }
-            DataType::List(fields) if fields.data_type() == &DataType::Int8 => {
-                build_array_list_primitive!(Int8Type, Int8, i8)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::Int16 => {
-                build_array_list_primitive!(Int16Type, Int16, i16)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::Int32 => {
-                build_array_list_primitive!(Int32Type, Int32, i32)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::Int64 => {
-                build_array_list_primitive!(Int64Type, Int64, i64)
+            DataType::Duration(TimeUnit::Second) => {
+                build_array_primitive!(DurationSecondArray, DurationSecond)
             }
-            DataType::List(fields) if fields.data_type() == &DataType::UInt8 => {
-                build_array_list_primitive!(UInt8Type, UInt8, u8)
+            DataType::Duration(TimeUnit::Millisecond) => {
+                build_array_primitive!(DurationMillisecondArray, DurationMillisecond)
             }
-            DataType::List(fields) if fields.data_type() == &DataType::UInt16 => {
-                build_array_list_primitive!(UInt16Type, UInt16, u16)
+            DataType::Duration(TimeUnit::Microsecond) => {
+                build_array_primitive!(DurationMicrosecondArray, DurationMicrosecond)
             }
-            DataType::List(fields) if fields.data_type() == &DataType::UInt32 => {
-                build_array_list_primitive!(UInt32Type, UInt32, u32)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::UInt64 => {
-                build_array_list_primitive!(UInt64Type, UInt64, u64)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::Float32 => {
-                build_array_list_primitive!(Float32Type, Float32, f32)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::Float64 => {
-                build_array_list_primitive!(Float64Type, Float64, f64)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::Utf8 => {
-                build_array_list_string!(StringBuilder, Utf8)
-            }
-            DataType::List(fields) if fields.data_type() == &DataType::LargeUtf8 => {
-                build_array_list_string!(LargeStringBuilder, LargeUtf8)
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                build_array_primitive!(DurationNanosecondArray, DurationNanosecond)
             }
             DataType::List(_) => {
-                // Fallback case handling homogeneous lists with any ScalarValue element type
                 let list_array = ScalarValue::iter_to_array_list(scalars, &data_type)?;
                 Arc::new(list_array)
             }
@@ -1484,10 +1533,15 @@ impl ScalarValue {
                 // Initialize a Vector to store the ScalarValues for each column
                 let mut columns: Vec<Vec<ScalarValue>> =
                     (0..fields.len()).map(|_| Vec::new()).collect();
+                // The validity of each row, as NULL structs are NULL rows
+                let mut validity = BooleanBufferBuilder::new(0);
+                let mut has_nulls = false;
 
                 // Iterate over scalars to populate the column scalars for each row
                 for scalar in scalars {
                     if let ScalarValue::Struct(values, fields) = scalar {
+                        validity.append(values.is_some());
+                        has_nulls |= values.is_none();
                         match values {
                             Some(values) => {
                                 // Push value for each field
@@ -1521,7 +1575,11 @@ impl ScalarValue {
                     })
                     .collect::<Result<Vec<_>>>()?;
 
-                Arc::new(StructArray::from(field_values))
+                if has_nulls {
+                    Arc::new(StructArray::from((field_values, validity.finish())))
+                } else {
+                    Arc::new(StructArray::from(field_values))
+                }
             }
             DataType::Dictionary(key_type, value_type) => {
                 // create the values array
@@ -1587,7 +1645,6 @@ impl ScalarValue {
             | DataType::Time32(TimeUnit::Nanosecond)
             | DataType::Time64(TimeUnit::Second)
             | DataType::Time64(TimeUnit::Millisecond)
-            | DataType::Duration(_)
             | DataType::FixedSizeList(_, _)
             | DataType::Interval(_)
             | DataType::LargeList(_)
@@ -1642,20 +1699,14 @@ impl ScalarValue {
         let mut valid = BooleanBufferBuilder::new(0);
         let mut flat_len = 0i32;
         for scalar in scalars {
-            if let ScalarValue::List(values, field) = scalar {
+            if let ScalarValue::List(values, _) = scalar {
                 match values {
                     Some(values) => {
-                        let element_array = if !values.is_empty() {
-                            ScalarValue::iter_to_array(values)?
-                        } else {
-                            arrow::array::new_empty_array(field.data_type())
-                        };
-
                         // Add new offset index
-                        flat_len += element_array.len() as i32;
+                        flat_len += values.len() as i32;
                         offsets.append_value(flat_len);
 
-                        elements.push(element_array);
+                        elements.push(values);
 
                         // Element is valid
                         valid.append(true);
@@ -1676,23 +1727,42 @@ impl ScalarValue {
         }
 
         // Concatenate element arrays to create single flat array
-        let element_arrays: Vec<&dyn Array> =
-            elements.iter().map(|a| a.as_ref()).collect();
-        let flat_array = match arrow::compute::concat(&element_arrays) {
-            Ok(flat_array) => flat_array,
-            Err(err) => return Err(DataFusionError::ArrowError(err)),
+        let flat_array = match elements.as_slice() {
+            // the values of a single list are not copied
+            [values] => values.clone(),
+            [] => match data_type {
+                DataType::List(field) => new_empty_array(field.data_type()),
+                _ => unreachable!(),
+            },
+            _ => {
+                let element_arrays: Vec<&dyn Array> =
+                    elements.iter().map(|a| a.as_ref()).collect();
+                arrow::compute::concat(&element_arrays)?
+            }
         };
 
-        // Build ListArray using ArrayData so we can specify a flat inner array, and offset indices
-        let offsets_array = offsets.finish();
-        let array_data = ArrayDataBuilder::new(data_type.clone())
-            .len(offsets_array.len() - 1)
-            .null_bit_buffer(Some(valid.finish()))
-            .add_buffer(offsets_array.data().buffers()[0].clone())
-            .add_child_data(flat_array.data().clone());
+        Self::build_list_array(
+            data_type,
+            offsets.finish(),
+            Some(valid.finish()),
+            flat_array,
+        )
+    }
 
-        let list_array = ListArray::from(array_data.build()?);
-        Ok(list_array)
+    /// Builds a ListArray of `values` using ArrayData, so that the values and
+    /// the offset indices are not copied
+    fn build_list_array(
+        data_type: &DataType,
+        offsets: Int32Array,
+        null_bit_buffer: Option<Buffer>,
+        values: ArrayRef,
+    ) -> Result<GenericListArray<i32>> {
+        let array_data = ArrayDataBuilder::new(data_type.clone())
+            .len(offsets.len() - 1)
+            .null_bit_buffer(null_bit_buffer)
+            .add_buffer(offsets.data().buffers()[0].clone())
+            .add_child_data(values.data().clone());
+        Ok(ListArray::from(array_data.build()?))
     }
 
     fn build_decimal_array(
@@ -1784,11 +1854,9 @@ impl ScalarValue {
                 None => new_null_array(&DataType::LargeUtf8, size),
             },
             ScalarValue::Binary(e) => match e {
-                Some(value) => Arc::new(
-                    repeat(Some(value.as_slice()))
-                        .take(size)
-                        .collect::<BinaryArray>(),
-                ),
+                Some(value) => {
+                    Arc::new(repeat(Some(&value[..])).take(size).collect::<BinaryArray>())
+                }
                 None => {
                     Arc::new(repeat(None::<&str>).take(size).collect::<BinaryArray>())
                 }
@@ -1796,7 +1864,7 @@ impl ScalarValue {
             ScalarValue::FixedSizeBinary(s, e) => match e {
                 Some(value) => Arc::new(
                     FixedSizeBinaryArray::try_from_sparse_iter_with_size(
-                        repeat(Some(value.as_slice())).take(size),
+                        repeat(Some(&value[..])).take(size),
                         *s,
                     )
                     .unwrap(),
@@ -1811,7 +1879,7 @@ impl ScalarValue {
             },
             ScalarValue::LargeBinary(e) => match e {
                 Some(value) => Arc::new(
-                    repeat(Some(value.as_slice()))
+                    repeat(Some(&value[..]))
                         .take(size)
                         .collect::<LargeBinaryArray>(),
                 ),
@@ -1821,35 +1889,32 @@ impl ScalarValue {
                         .collect::<LargeBinaryArray>(),
                 ),
             },
-            ScalarValue::List(values, field) => Arc::new(match field.data_type() {
-                DataType::Boolean => build_list!(BooleanBuilder, Boolean, values, size),
-                DataType::Int8 => build_list!(Int8Builder, Int8, values, size),
-                DataType::Int16 => build_list!(Int16Builder, Int16, values, size),
-                DataType::Int32 => build_list!(Int32Builder, Int32, values, size),
-                DataType::Int64 => build_list!(Int64Builder, Int64, values, size),
-                DataType::UInt8 => build_list!(UInt8Builder, UInt8, values, size),
-                DataType::UInt16 => build_list!(UInt16Builder, UInt16, values, size),
-                DataType::UInt32 => build_list!(UInt32Builder, UInt32, values, size),
-                DataType::UInt64 => build_list!(UInt64Builder, UInt64, values, size),
-                DataType::Utf8 => build_list!(StringBuilder, Utf8, values, size),
-                DataType::Float32 => build_list!(Float32Builder, Float32, values, size),
-                DataType::Float64 => build_list!(Float64Builder, Float64, values, size),
-                DataType::Timestamp(unit, tz) => {
-                    build_timestamp_list!(unit.clone(), tz.clone(), values, size)
-                }
-                &DataType::LargeUtf8 => {
-                    build_list!(LargeStringBuilder, LargeUtf8, values, size)
+            ScalarValue::List(values, field) => {
+                let data_type = DataType::List(Box::new(Field::new(
+                    "item",
+                    field.data_type().clone(),
+                    true,
+                )));
+                match values {
+                    Some(values) => {
+                        let offsets = Int32Array::from_iter_values(
+                            (0..=size).map(|i| (i * values.len()) as i32),
+                        );
+                        // the values of a single row are not copied
+                        let values = match size {
+                            0 => new_empty_array(field.data_type()),
+                            1 => values.clone(),
+                            _ => arrow::compute::concat(&vec![values.as_ref(); size])
+                                .unwrap(),
+                        };
+                        Arc::new(
+                            Self::build_list_array(&data_type, offsets, None, values)
+                                .unwrap(),
+                        )
+                    }
+                    None => new_null_array(&data_type, size),
                 }
-                _ => ScalarValue::iter_to_array_list(
-                    repeat(self.clone()).take(size),
-                    &DataType::List(Box::new(Field::new(
-                        "item",
-                        field.data_type().clone(),
-                        true,
-                    ))),
-                )
-                .unwrap(),
-            }),
+            }
             ScalarValue::Date32(e) => {
                 build_array_from_option!(Date32, Date32Array, e, size)
             }
@@ -1913,6 +1978,34 @@ impl ScalarValue {
                 e,
                 size
             ),
+            ScalarValue::DurationSecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Second,
+                DurationSecondArray,
+                e,
+                size
+            ),
+            ScalarValue::DurationMillisecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Millisecond,
+                DurationMillisecondArray,
+                e,
+                size
+            ),
+            ScalarValue::DurationMicrosecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Microsecond,
+                DurationMicrosecondArray,
+                e,
+                size
+            ),
+            ScalarValue::DurationNanosecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Nanosecond,
+                DurationNanosecondArray,
+                e,
+                size
+            ),
             ScalarValue::Struct(values, fields) => match values {
                 Some(values) => {
                     let field_values: Vec<_> = fields
@@ -1934,8 +2027,10 @@ impl ScalarValue {
                             (field.clone(), none_field.to_array_of_size(size))
                         })
                         .collect();
+                    let mut validity = BooleanBufferBuilder::new(size);
+                    validity.append_n(size, false);
 
-                    Arc::new(StructArray::from(field_values))
+                    Arc::new(StructArray::from((field_values, validity.finish())))
                 }
             },
             ScalarValue::Dictionary(key_type, v) => {
@@ -2004,17 +2099,12 @@ impl ScalarValue {
             DataType::LargeUtf8 => typed_cast!(array, index, LargeStringArray, LargeUtf8),
             DataType::List(nested_type) => {
                 let list_array = as_list_array(array)?;
+                // the values are a slice of the values of the array, not a copy
                 let value = match list_array.is_null(index) {
                     true => None,
-                    false => {
-                        let nested_array = list_array.value(index);
-                        let scalar_vec = (0..nested_array.len())
-                            .map(|i| ScalarValue::try_from_array(&nested_array, i))
-                            .collect::<Result<Vec<_>>>()?;
-                        Some(scalar_vec)
-                    }
+                    false => Some(list_array.value(index)),
                 };
-                ScalarValue::new_list(value, nested_type.data_type().clone())
+                ScalarValue::new_list_from_array(value, nested_type.data_type().clone())
             }
             DataType::Date32 => {
                 typed_cast!(array, index, Date32Array, Date32)
@@ -2070,6 +2160,18 @@ impl ScalarValue {
                     tz_opt
                 )
             }
+            DataType::Duration(TimeUnit::Second) => {
+                typed_cast!(array, index, DurationSecondArray, DurationSecond)
+            }
+            DataType::Duration(TimeUnit::Millisecond) => {
+                typed_cast!(array, index, DurationMillisecondArray, DurationMillisecond)
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                typed_cast!(array, index, DurationMicrosecondArray, DurationMicrosecond)
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                typed_cast!(array, index, DurationNanosecondArray, DurationNanosecond)
+            }
             DataType::Dictionary(key_type, _) => {
                 let (values_array, values_index) = match key_type.as_ref() {
                     DataType::Int8 => get_dict_value::<Int8Type>(array, index),
@@ -2095,6 +2197,9 @@ impl ScalarValue {
             }
             DataType::Struct(fields) => {
                 let array = as_struct_array(array)?;
+                if array.is_null(index) {
+                    return Ok(Self::Struct(None, Box::new(fields.clone())));
+                }
                 let mut field_values: Vec<ScalarValue> = Vec::new();
                 for col_index in 0..array.num_columns() {
                     let col_array = array.column(col_index);
@@ -2107,15 +2212,9 @@ impl ScalarValue {
                 let list_array = as_fixed_size_list_array(array)?;
                 let value = match list_array.is_null(index) {
                     true => None,
-                    false => {
-                        let nested_array = list_array.value(index);
-                        let scalar_vec = (0..nested_array.len())
-                            .map(|i| ScalarValue::try_from_array(&nested_array, i))
-                            .collect::<Result<Vec<_>>>()?;
-                        Some(scalar_vec)
-                    }
+                    false => Some(list_array.value(index)),
                 };
-                ScalarValue::new_list(value, nested_type.data_type().clone())
+                ScalarValue::new_list_from_array(value, nested_type.data_type().clone())
             }
             DataType::FixedSizeBinary(_) => {
                 let array = as_fixed_size_binary_array(array)?;
@@ -2141,7 +2240,7 @@ impl ScalarValue {
 
     /// Try to parse `value` into a ScalarValue of type `target_type`
     pub fn try_from_string(value: String, target_type: &DataType) -> Result<Self> {
-        let value = ScalarValue::Utf8(Some(value));
+        let value = ScalarValue::Utf8(Some(value.into()));
         let cast_options = CastOptions { safe: false };
         let cast_arr = cast_with_options(&value.to_array(), target_type, &cast_options)?;
         ScalarValue::try_from_array(&cast_arr, 0)
@@ -2218,20 +2317,28 @@ impl ScalarValue {
             ScalarValue::UInt64(val) => {
                 eq_array_primitive!(array, index, UInt64Array, val)
             }
-            ScalarValue::Utf8(val) => eq_array_primitive!(array, index, StringArray, val),
+            ScalarValue::Utf8(val) => {
+                eq_array_primitive!(array, index, StringArray, &val.as_deref())
+            }
             ScalarValue::LargeUtf8(val) => {
-                eq_array_primitive!(array, index, LargeStringArray, val)
+                eq_array_primitive!(array, index, LargeStringArray, &val.as_deref())
             }
             ScalarValue::Binary(val) => {
-                eq_array_primitive!(array, index, BinaryArray, val)
+                eq_array_primitive!(array, index, BinaryArray, &val.as_deref())
             }
             ScalarValue::FixedSizeBinary(_, val) => {
-                eq_array_primitive!(array, index, FixedSizeBinaryArray, val)
+                eq_array_primitive!(array, index, FixedSizeBinaryArray, &val.as_deref())
             }
             ScalarValue::LargeBinary(val) => {
-                eq_array_primitive!(array, index, LargeBinaryArray, val)
+                eq_array_primitive!(array, index, LargeBinaryArray, &val.as_deref())
+            }
+            ScalarValue::List(val, _) => {
+                let array = as_list_array(array).unwrap();
+                match val {
+                    Some(val) => array.is_valid(index) && &array.value(index) == val,
+                    None => !array.is_valid(index),
+                }
             }
-            ScalarValue::List(_, _) => unimplemented!(),
             ScalarValue::Date32(val) => {
                 eq_array_primitive!(array, index, Date32Array, val)
             }
@@ -2271,7 +2378,31 @@ impl ScalarValue {
             ScalarValue::IntervalMonthDayNano(val) => {
                 eq_array_primitive!(array, index, IntervalMonthDayNanoArray, val)
             }
-            ScalarValue::Struct(_, _) => unimplemented!(),
+            ScalarValue::DurationSecond(val) => {
+                eq_array_primitive!(array, index, DurationSecondArray, val)
+            }
+            ScalarValue::DurationMillisecond(val) => {
+                eq_array_primitive!(array, index, DurationMillisecondArray, val)
+            }
+            ScalarValue::DurationMicrosecond(val) => {
+                eq_array_primitive!(array, index, DurationMicrosecondArray, val)
+            }
+            ScalarValue::DurationNanosecond(val) => {
+                eq_array_primitive!(array, index, DurationNanosecondArray, val)
+            }
+            ScalarValue::Struct(val, _) => {
+                let array = as_struct_array(array).unwrap();
+                match val {
+                    Some(val) => {
+                        array.is_valid(index)
+                            && val
+                                .iter()
+                                .enumerate()
+                                .all(|(i, v)| v.eq_array(array.column(i), index))
+                    }
+                    None => !array.is_valid(index),
+                }
+            }
             ScalarValue::Dictionary(key_type, v) => {
                 let (values_array, values_index) = match key_type.as_ref() {
                     DataType::Int8 => get_dict_value::<Int8Type>(array, index),
@@ -2320,23 +2451,29 @@ impl ScalarValue {
                 | ScalarValue::Time64Nanosecond(_)
                 | ScalarValue::IntervalYearMonth(_)
                 | ScalarValue::IntervalDayTime(_)
-                | ScalarValue::IntervalMonthDayNano(_) => 0,
-                ScalarValue::Utf8(s)
-                | ScalarValue::LargeUtf8(s)
-                | ScalarValue::TimestampSecond(_, s)
+                | ScalarValue::IntervalMonthDayNano(_)
+                | ScalarValue::DurationSecond(_)
+                | ScalarValue::DurationMillisecond(_)
+                | ScalarValue::DurationMicrosecond(_)
+                | ScalarValue::DurationNanosecond(_) => 0,
+                ScalarValue::TimestampSecond(_, s)
                 | ScalarValue::TimestampMillisecond(_, s)
                 | ScalarValue::TimestampMicrosecond(_, s)
                 | ScalarValue::TimestampNanosecond(_, s) => {
                     s.as_ref().map(|s| s.capacity()).unwrap_or_default()
                 }
+                // the shared strings and bytes are counted by each of their owners
+                ScalarValue::Utf8(s) | ScalarValue::LargeUtf8(s) => {
+                    s.as_ref().map(|s| s.len()).unwrap_or_default()
+                }
                 ScalarValue::Binary(b)
                 | ScalarValue::FixedSizeBinary(_, b)
                 | ScalarValue::LargeBinary(b) => {
-                    b.as_ref().map(|b| b.capacity()).unwrap_or_default()
+                    b.as_ref().map(|b| b.len()).unwrap_or_default()
                 }
                 ScalarValue::List(vals, field) => {
                     vals.as_ref()
-                        .map(|vals| Self::size_of_vec(vals) - std::mem::size_of_val(vals))
+                        .map(|vals| vals.get_array_memory_size())
                         .unwrap_or_default()
                         // `field` is boxed, so it is NOT already included in `self`
                         + field.size()
@@ -2423,8 +2560,13 @@ impl From<&str> for ScalarValue {
 
 impl From<Option<&str>> for ScalarValue {
     fn from(value: Option<&str>) -> Self {
-        let value = value.map(|s| s.to_string());
-        ScalarValue::Utf8(value)
+        ScalarValue::Utf8(value.map(Arc::from))
+    }
+}
+
+impl From<String> for ScalarValue {
+    fn from(value: String) -> Self {
+        ScalarValue::Utf8(Some(value.into()))
     }
 }
 
@@ -2490,7 +2632,7 @@ impl TryFrom<ScalarValue> for i32 {
     }
 }
 
-// special implementation for i64 because of Date64, Time64 and Timestamp
+// special implementation for i64 because of Date64, Time64, Timestamp and Duration
 impl TryFrom<ScalarValue> for i64 {
     type Error = DataFusionError;
 
@@ -2503,7 +2645,11 @@ impl TryFrom<ScalarValue> for i64 {
             | ScalarValue::TimestampNanosecond(Some(inner_value), _)
             | ScalarValue::TimestampMicrosecond(Some(inner_value), _)
             | ScalarValue::TimestampMillisecond(Some(inner_value), _)
-            | ScalarValue::TimestampSecond(Some(inner_value), _) => Ok(inner_value),
+            | ScalarValue::TimestampSecond(Some(inner_value), _)
+            | ScalarValue::DurationSecond(Some(inner_value))
+            | ScalarValue::DurationMillisecond(Some(inner_value))
+            | ScalarValue::DurationMicrosecond(Some(inner_value))
+            | ScalarValue::DurationNanosecond(Some(inner_value)) => Ok(inner_value),
             _ => Err(DataFusionError::Internal(format!(
                 "Cannot convert {:?} to {}",
                 value,
@@ -2602,12 +2748,22 @@ impl TryFrom<&DataType> for ScalarValue {
             DataType::Interval(IntervalUnit::MonthDayNano) => {
                 ScalarValue::IntervalMonthDayNano(None)
             }
+            DataType::Duration(TimeUnit::Second) => ScalarValue::DurationSecond(None),
+            DataType::Duration(TimeUnit::Millisecond) => {
+                ScalarValue::DurationMillisecond(None)
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                ScalarValue::DurationMicrosecond(None)
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                ScalarValue::DurationNanosecond(None)
+            }
             DataType::Dictionary(index_type, value_type) => ScalarValue::Dictionary(
                 index_type.clone(),
                 Box::new(value_type.as_ref().try_into()?),
             ),
             DataType::List(ref nested_type) => {
-                ScalarValue::new_list(None, nested_type.data_type().clone())
+                ScalarValue::new_list_from_array(None, nested_type.data_type().clone())
             }
             DataType::Struct(fields) => {
                 ScalarValue::Struct(None, Box::new(fields.clone()))
@@ -2631,6 +2787,30 @@ macro_rules! format_option {
     }};
 }
 
+/// Formats a duration of `value` `unit`s the way arrow pretty prints
+/// durations, e.g. `PT1.5S`
+fn format_duration(
+    f: &mut fmt::Formatter,
+    value: &Option<i64>,
+    unit: TimeUnit,
+) -> fmt::Result {
+    let value = match value {
+        Some(value) => *value,
+        None => return write!(f, "NULL"),
+    };
+    let duration = match unit {
+        // `Duration::seconds` panics on overflow
+        TimeUnit::Second => value.checked_mul(1000).map(Duration::milliseconds),
+        TimeUnit::Millisecond => Some(Duration::milliseconds(value)),
+        TimeUnit::Microsecond => Some(Duration::microseconds(value)),
+        TimeUnit::Nanosecond => Some(Duration::nanoseconds(value)),
+    };
+    match duration {
+        Some(duration) => write!(f, "{duration}"),
+        None => write!(f, "{value}"),
+    }
+}
+
 impl fmt::Display for ScalarValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -2691,7 +2871,9 @@ impl fmt::Display for ScalarValue {
                 Some(l) => write!(
                     f,
                     "{}",
-                    l.iter()
+                    ScalarValue::convert_array_to_scalar_vec(l)
+                        .map_err(|_| fmt::Error)?
+                        .iter()
                         .map(|v| format!("{v}"))
                         .collect::<Vec<_>>()
                         .join(",")
@@ -2707,6 +2889,16 @@ impl fmt::Display for ScalarValue {
             ScalarValue::IntervalDayTime(e) => format_option!(f, e)?,
             ScalarValue::IntervalYearMonth(e) => format_option!(f, e)?,
             ScalarValue::IntervalMonthDayNano(e) => format_option!(f, e)?,
+            ScalarValue::DurationSecond(e) => format_duration(f, e, TimeUnit::Second)?,
+            ScalarValue::DurationMillisecond(e) => {
+                format_duration(f, e, TimeUnit::Millisecond)?
+            }
+            ScalarValue::DurationMicrosecond(e) => {
+                format_duration(f, e, TimeUnit::Microsecond)?
+            }
+            ScalarValue::DurationNanosecond(e) => {
+                format_duration(f, e, TimeUnit::Nanosecond)?
+            }
             ScalarValue::Struct(e, fields) => match e {
                 Some(l) => write!(
                     f,
@@ -2789,6 +2981,16 @@ impl fmt::Debug for ScalarValue {
             ScalarValue::IntervalMonthDayNano(_) => {
                 write!(f, "IntervalMonthDayNano(\"{self}\")")
             }
+            ScalarValue::DurationSecond(_) => write!(f, "DurationSecond(\"{self}\")"),
+            ScalarValue::DurationMillisecond(_) => {
+                write!(f, "DurationMillisecond(\"{self}\")")
+            }
+            ScalarValue::DurationMicrosecond(_) => {
+                write!(f, "DurationMicrosecond(\"{self}\")")
+            }
+            ScalarValue::DurationNanosecond(_) => {
+                write!(f, "DurationNanosecond(\"{self}\")")
+            }
             ScalarValue::Struct(e, fields) => {
                 // Use Debug representation of field values
                 match e {
@@ -2889,6 +3091,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn scalar_temporal_interval_test() -> Result<()> {
+        let hour = ScalarValue::new_interval_mdn(0, 0, 3_600_000_000_000);
+        let zone = Some("+05:00".to_string());
+
+        // timestamps before the epoch keep their fractional part
+        let ts = ScalarValue::TimestampMillisecond(Some(-1), zone.clone());
+        assert_eq!(
+            ts.sub(&hour)?,
+            ScalarValue::TimestampMillisecond(Some(-3_600_001), zone)
+        );
+        let ts = ScalarValue::TimestampNanosecond(Some(-1_500_000_000), None);
+        assert_eq!(
+            ts.add(ScalarValue::new_interval_dt(1, 0))?,
+            ScalarValue::TimestampNanosecond(Some(86_398_500_000_000), None)
+        );
+
+        // times are not wrapped around midnight
+        let time = ScalarValue::Time32Second(Some(1800));
+        assert_eq!(time.sub(&hour)?, ScalarValue::Time32Second(Some(-1800)));
+        let time = ScalarValue::Time64Nanosecond(Some(0));
+        assert_eq!(
+            time.add(ScalarValue::new_interval_dt(0, 1500))?,
+            ScalarValue::Time64Nanosecond(Some(1_500_000_000))
+        );
+        let time = ScalarValue::Time32Millisecond(Some(0));
+        assert!(time.add(ScalarValue::new_interval_ym(0, 1)).is_err());
+        Ok(())
+    }
+
     #[test]
     fn scalar_decimal_test() -> Result<()> {
         let decimal_value = ScalarValue::Decimal128(Some(123), 10, 1);
@@ -3038,14 +3270,15 @@ mod tests {
 
     #[test]
     fn scalar_list_to_array() -> Result<()> {
-        let list_array_ref = ScalarValue::List(
+        let list_array_ref = ScalarValue::new_list(
             Some(vec![
                 ScalarValue::UInt64(Some(100)),
                 ScalarValue::UInt64(None),
                 ScalarValue::UInt64(Some(101)),
             ]),
-            Box::new(Field::new("item", DataType::UInt64, false)),
+            DataType::UInt64,
         )
+        .unwrap()
         .to_array();
 
         let list_array = as_list_array(&list_array_ref)?;
@@ -3061,6 +3294,96 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn scalar_list_of_array() -> Result<()> {
+        let list_array: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                Some(vec![Some(1), Some(2)]),
+                None,
+                Some(vec![Some(3)]),
+            ]));
+
+        // the values of the scalar are a slice of the values of the array
+        let list = ScalarValue::try_from_array(&list_array, 2)?;
+        let values = match &list {
+            ScalarValue::List(Some(values), _) => values.clone(),
+            other => panic!("Expected a list, got {other:?}"),
+        };
+        assert_eq!(values.data().offset(), 2);
+        assert_eq!(
+            ScalarValue::convert_array_to_scalar_vec(&values)?,
+            vec![ScalarValue::Int32(Some(3))]
+        );
+        assert_eq!(
+            list,
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Int32(Some(3))]),
+                DataType::Int32
+            )
+            .unwrap()
+        );
+        assert_eq!(list.to_string(), "3");
+
+        // the single row array shares the values of the scalar
+        let array = list.to_array();
+        let array = as_list_array(&array)?;
+        assert_eq!(array.len(), 1);
+        assert_eq!(
+            array.values().data().buffers()[0].as_ptr(),
+            values.data().buffers()[0].as_ptr()
+        );
+
+        let array = list.to_array_of_size(3);
+        for index in 0..3 {
+            assert!(list.eq_array(&array, index));
+        }
+        assert!(!ScalarValue::try_from_array(&list_array, 0)?.eq_array(&array, 0));
+        assert!(ScalarValue::try_from_array(&list_array, 1)?.eq_array(&list_array, 1));
+        assert!(!list.eq_array(&list_array, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_list_hash() -> Result<()> {
+        let list_array: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                Some(vec![Some(1), None]),
+                Some(vec![Some(1), None]),
+                Some(vec![Some(1), Some(2)]),
+            ]));
+
+        // equal lists sliced at different offsets hash the same
+        let mut set = HashSet::new();
+        for index in 0..3 {
+            set.insert(ScalarValue::try_from_array(&list_array, index)?);
+        }
+        set.insert(ScalarValue::new_list(
+            Some(vec![ScalarValue::Int32(Some(1)), ScalarValue::Int32(None)]),
+            DataType::Int32,
+        )?);
+        set.insert(ScalarValue::new_list_from_array(None, DataType::Int32));
+        assert_eq!(set.len(), 3);
+
+        // mismatched values are an error rather than a panic
+        assert!(ScalarValue::new_list(
+            Some(vec![ScalarValue::Int32(Some(1)), ScalarValue::from("a")]),
+            DataType::Int32,
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_string_clone() {
+        let value = ScalarValue::new_utf8("a string that is not copied");
+        match (&value, &value.clone()) {
+            (ScalarValue::Utf8(Some(v1)), ScalarValue::Utf8(Some(v2))) => {
+                assert!(Arc::ptr_eq(v1, v2))
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Creates array directly and via ScalarValue and ensures they are the same
     macro_rules! check_scalar_iter {
         ($SCALAR_T:ident, $ARRAYTYPE:ident, $INPUT:expr) => {{
@@ -3098,7 +3421,7 @@ mod tests {
         ($SCALAR_T:ident, $ARRAYTYPE:ident, $INPUT:expr) => {{
             let scalars: Vec<_> = $INPUT
                 .iter()
-                .map(|v| ScalarValue::$SCALAR_T(v.map(|v| v.to_string())))
+                .map(|v| ScalarValue::$SCALAR_T(v.map(Arc::from)))
                 .collect();
 
             let array = ScalarValue::iter_to_array(scalars.into_iter()).unwrap();
@@ -3115,7 +3438,7 @@ mod tests {
         ($SCALAR_T:ident, $ARRAYTYPE:ident, $INPUT:expr) => {{
             let scalars: Vec<_> = $INPUT
                 .iter()
-                .map(|v| ScalarValue::$SCALAR_T(v.map(|v| v.to_vec())))
+                .map(|v| ScalarValue::$SCALAR_T(v.map(|v| Arc::from(&v[..]))))
                 .collect();
 
             let array = ScalarValue::iter_to_array(scalars.into_iter()).unwrap();
@@ -3204,9 +3527,9 @@ mod tests {
 
     #[test]
     fn scalar_iter_to_dictionary() {
-        fn make_val(v: Option<String>) -> ScalarValue {
+        fn make_val(v: Option<&str>) -> ScalarValue {
             let key_type = DataType::Int32;
-            let value = ScalarValue::Utf8(v);
+            let value = ScalarValue::from(v);
             ScalarValue::Dictionary(Box::new(key_type), Box::new(value))
         }
 
@@ -3286,7 +3609,7 @@ mod tests {
 
     #[test]
     fn memory_size() {
-        let sv = ScalarValue::Binary(Some(Vec::with_capacity(10)));
+        let sv = ScalarValue::Binary(Some(vec![0; 10].into()));
         assert_eq!(sv.size(), std::mem::size_of::<ScalarValue>() + 10,);
         let sv_size = sv.size();
 
@@ -3378,7 +3701,7 @@ mod tests {
                     array: Arc::new($INPUT.iter().cloned().collect::<$ARRAY_TY>()),
                     scalars: $INPUT
                         .iter()
-                        .map(|v| ScalarValue::$SCALAR_TY(v.map(|v| v.to_string())))
+                        .map(|v| ScalarValue::$SCALAR_TY(v.map(Arc::from)))
                         .collect(),
                 }
             }};
@@ -3391,7 +3714,7 @@ mod tests {
                     scalars: $INPUT
                         .iter()
                         .map(|v| {
-                            ScalarValue::$SCALAR_TY(v.map(|v| v.as_bytes().to_vec()))
+                            ScalarValue::$SCALAR_TY(v.map(|v| Arc::from(v.as_bytes())))
                         })
                         .collect(),
                 }
@@ -3413,7 +3736,7 @@ mod tests {
                         .map(|v| {
                             ScalarValue::Dictionary(
                                 Box::new($INDEX_TY::DATA_TYPE),
-                                Box::new(ScalarValue::Utf8(v.map(|v| v.to_string()))),
+                                Box::new(ScalarValue::Utf8(v.map(Arc::from))),
                             )
                         })
                         .collect(),
@@ -3545,51 +3868,67 @@ mod tests {
         assert_eq!(Int32(Some(33)).partial_cmp(&Int64(Some(33))), None);
 
         assert_eq!(
-            List(
+            ScalarValue::new_list(
                 Some(vec![Int32(Some(1)), Int32(Some(5))]),
-                Box::new(Field::new("item", DataType::Int32, false)),
+                DataType::Int32
             )
-            .partial_cmp(&List(
-                Some(vec![Int32(Some(1)), Int32(Some(5))]),
-                Box::new(Field::new("item", DataType::Int32, false)),
-            )),
+            .unwrap()
+            .partial_cmp(
+                &ScalarValue::new_list(
+                    Some(vec![Int32(Some(1)), Int32(Some(5))]),
+                    DataType::Int32
+                )
+                .unwrap()
+            ),
             Some(Ordering::Equal)
         );
 
         assert_eq!(
-            List(
+            ScalarValue::new_list(
                 Some(vec![Int32(Some(10)), Int32(Some(5))]),
-                Box::new(Field::new("item", DataType::Int32, false)),
+                DataType::Int32
             )
-            .partial_cmp(&List(
-                Some(vec![Int32(Some(1)), Int32(Some(5))]),
-                Box::new(Field::new("item", DataType::Int32, false)),
-            )),
+            .unwrap()
+            .partial_cmp(
+                &ScalarValue::new_list(
+                    Some(vec![Int32(Some(1)), Int32(Some(5))]),
+                    DataType::Int32
+                )
+                .unwrap()
+            ),
             Some(Ordering::Greater)
         );
 
         assert_eq!(
-            List(
+            ScalarValue::new_list(
                 Some(vec![Int32(Some(1)), Int32(Some(5))]),
-                Box::new(Field::new("item", DataType::Int32, false)),
+                DataType::Int32
             )
-            .partial_cmp(&List(
-                Some(vec![Int32(Some(10)), Int32(Some(5))]),
-                Box::new(Field::new("item", DataType::Int32, false)),
-            )),
+            .unwrap()
+            .partial_cmp(
+                &ScalarValue::new_list(
+                    Some(vec![Int32(Some(10)), Int32(Some(5))]),
+                    DataType::Int32
+                )
+                .unwrap()
+            ),
             Some(Ordering::Less)
         );
 
         // For different data type, `partial_cmp` returns None.
         assert_eq!(
-            List(
+            ScalarValue::new_list(
                 Some(vec![Int64(Some(1)), Int64(Some(5))]),
-                Box::new(Field::new("item", DataType::Int64, false)),
+                DataType::Int64
             )
-            .partial_cmp(&List(
-                Some(vec![Int32(Some(1)), Int32(Some(5))]),
-                Box::new(Field::new("item", DataType::Int32, false)),
-            )),
+            .unwrap()
+            .partial_cmp(
+                &ScalarValue::new_list(
+                    Some(vec![Int32(Some(1)), Int32(Some(5))]),
+                    DataType::Int32
+                )
+                .unwrap()
+            ),
             None
         );
 
@@ -3637,7 +3976,7 @@ mod tests {
             Some(vec![
                 ScalarValue::Int32(Some(23)),
                 ScalarValue::Boolean(Some(false)),
-                ScalarValue::Utf8(Some("Hello".to_string())),
+                ScalarValue::from("Hello"),
                 ScalarValue::from(vec![
                     ("e", ScalarValue::from(2i16)),
                     ("f", ScalarValue::from(3i64)),
@@ -3795,6 +4134,46 @@ mod tests {
         assert_eq!(&array, &expected);
     }
 
+    #[test]
+    fn test_scalar_struct_eq_array() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ];
+        let row = |a: Option<i32>, b: &str| {
+            ScalarValue::Struct(
+                Some(vec![ScalarValue::Int32(a), ScalarValue::from(b)]),
+                Box::new(fields.clone()),
+            )
+        };
+        let scalars = vec![
+            row(Some(1), "x"),
+            ScalarValue::Struct(None, Box::new(fields.clone())),
+            row(None, "x"),
+            row(Some(1), "y"),
+        ];
+        let array = ScalarValue::iter_to_array(scalars.clone()).unwrap();
+        assert_eq!(array.null_count(), 1);
+        assert!(array.is_null(1));
+
+        for (index, scalar) in scalars.iter().enumerate() {
+            // NULL structs are NULL rows, not structs of NULL fields
+            assert_eq!(&ScalarValue::try_from_array(&array, index).unwrap(), scalar);
+            for other_index in 0..array.len() {
+                assert_eq!(
+                    scalar.eq_array(&array, other_index),
+                    index == other_index,
+                    "{scalar} at {other_index}"
+                );
+            }
+        }
+
+        let null_array = ScalarValue::Struct(None, Box::new(fields)).to_array_of_size(2);
+        assert_eq!(null_array.null_count(), 2);
+        assert!(scalars[1].eq_array(&null_array, 0));
+        assert!(!scalars[0].eq_array(&null_array, 1));
+    }
+
     #[test]
     fn test_lists_in_struct() {
         let field_a = Field::new("A", DataType::Utf8, false);
@@ -3805,38 +4184,39 @@ mod tests {
         );
 
         // Define primitive list scalars
-        let l0 = ScalarValue::List(
+        let l0 = ScalarValue::new_list(
             Some(vec![
                 ScalarValue::from(1i32),
                 ScalarValue::from(2i32),
                 ScalarValue::from(3i32),
             ]),
-            Box::new(Field::new("item", DataType::Int32, false)),
-        );
+            DataType::Int32,
+        )
+        .unwrap();
 
-        let l1 = ScalarValue::List(
+        let l1 = ScalarValue::new_list(
             Some(vec![ScalarValue::from(4i32), ScalarValue::from(5i32)]),
-            Box::new(Field::new("item", DataType::Int32, false)),
-        );
+            DataType::Int32,
+        )
+        .unwrap();
 
-        let l2 = ScalarValue::List(
-            Some(vec![ScalarValue::from(6i32)]),
-            Box::new(Field::new("item", DataType::Int32, false)),
-        );
+        let l2 =
+            ScalarValue::new_list(Some(vec![ScalarValue::from(6i32)]), DataType::Int32)
+                .unwrap();
 
         // Define struct scalars
         let s0 = ScalarValue::from(vec![
-            ("A", ScalarValue::Utf8(Some(String::from("First")))),
+            ("A", ScalarValue::from("First")),
             ("primitive_list", l0),
         ]);
 
         let s1 = ScalarValue::from(vec![
-            ("A", ScalarValue::Utf8(Some(String::from("Second")))),
+            ("A", ScalarValue::from("Second")),
             ("primitive_list", l1),
         ]);
 
         let s2 = ScalarValue::from(vec![
-            ("A", ScalarValue::Utf8(Some(String::from("Third")))),
+            ("A", ScalarValue::from("Third")),
             ("primitive_list", l2),
         ]);
 
@@ -3864,11 +4244,12 @@ mod tests {
 
         // Define list-of-structs scalars
         let nl0 =
-            ScalarValue::new_list(Some(vec![s0.clone(), s1.clone()]), s0.get_datatype());
+            ScalarValue::new_list(Some(vec![s0.clone(), s1.clone()]), s0.get_datatype())
+                .unwrap();
 
-        let nl1 = ScalarValue::new_list(Some(vec![s2]), s0.get_datatype());
+        let nl1 = ScalarValue::new_list(Some(vec![s2]), s0.get_datatype()).unwrap();
 
-        let nl2 = ScalarValue::new_list(Some(vec![s1]), s0.get_datatype());
+        let nl2 = ScalarValue::new_list(Some(vec![s1]), s0.get_datatype()).unwrap();
         // iter_to_array for list-of-struct
         let array = ScalarValue::iter_to_array(vec![nl0, nl1, nl2]).unwrap();
         let array = as_list_array(&array).unwrap();
@@ -4003,36 +4384,44 @@ mod tests {
                         ScalarValue::from(3i32),
                     ]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(4i32), ScalarValue::from(5i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
             ]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let l2 = ScalarValue::new_list(
             Some(vec![
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(6i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(7i32), ScalarValue::from(8i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
             ]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let l3 = ScalarValue::new_list(
             Some(vec![ScalarValue::new_list(
                 Some(vec![ScalarValue::from(9i32)]),
                 DataType::Int32,
-            )]),
+            )
+            .unwrap()]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let array = ScalarValue::iter_to_array(vec![l1, l2, l3]).unwrap();
         let array = as_list_array(&array).unwrap();
@@ -4105,7 +4494,7 @@ mod tests {
         check_scalar_cast(ScalarValue::Float64(None), DataType::Int16);
 
         check_scalar_cast(
-            ScalarValue::Utf8(Some("foo".to_string())),
+            ScalarValue::from("foo"),
             DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
         );
 
@@ -4345,6 +4734,26 @@ mod tests {
                 ScalarValue::Float64(Some(-9.9)),
                 5,
             ),
+            (
+                ScalarValue::Date32(Some(0)),
+                ScalarValue::Date32(Some(1)),
+                1,
+            ),
+            (
+                ScalarValue::Date64(Some(0)),
+                ScalarValue::Date64(Some(1)),
+                1,
+            ),
+            (
+                ScalarValue::TimestampNanosecond(Some(i64::MIN), None),
+                ScalarValue::TimestampNanosecond(Some(i64::MAX), None),
+                u64::MAX as usize,
+            ),
+            (
+                ScalarValue::TimestampSecond(Some(100), None),
+                ScalarValue::TimestampSecond(Some(-20), None),
+                120,
+            ),
         ];
         for (lhs, rhs, expected) in cases.iter() {
             let distance = lhs.distance(rhs).unwrap();
@@ -4376,16 +4785,16 @@ mod tests {
             (ScalarValue::Int8(None), ScalarValue::Int16(Some(1))),
             (ScalarValue::Int8(Some(1)), ScalarValue::Int16(None)),
             // Unsupported types
-            (
-                ScalarValue::Utf8(Some("foo".to_string())),
-                ScalarValue::Utf8(Some("bar".to_string())),
-            ),
+            (ScalarValue::from("foo"), ScalarValue::from("bar")),
             (
                 ScalarValue::Boolean(Some(true)),
                 ScalarValue::Boolean(Some(false)),
             ),
-            (ScalarValue::Date32(Some(0)), ScalarValue::Date32(Some(1))),
-            (ScalarValue::Date64(Some(0)), ScalarValue::Date64(Some(1))),
+            // Timestamps of different units
+            (
+                ScalarValue::TimestampSecond(Some(0), None),
+                ScalarValue::TimestampMillisecond(Some(1), None),
+            ),
             (
                 ScalarValue::Decimal128(Some(123), 5, 5),
                 ScalarValue::Decimal128(Some(120), 5, 5),
@@ -17,35 +17,409 @@
 
 //! This module provides data structures to represent statistics
 
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+
 use crate::ScalarValue;
+use arrow::datatypes::Schema;
+
+/// Represents a value with a degree of certainty. `Precision` is used to
+/// propagate information about the precision of statistical values.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Precision<T: Debug + Clone + PartialEq + Eq + PartialOrd> {
+    /// The exact value is known
+    Exact(T),
+    /// The value is not known exactly, but is likely close to this value
+    Inexact(T),
+    /// Nothing is known about the value
+    #[default]
+    Absent,
+}
+
+impl<T: Debug + Clone + PartialEq + Eq + PartialOrd> Precision<T> {
+    /// If we have some value (exact or inexact), it returns that value.
+    /// Otherwise, it returns `None`.
+    pub fn get_value(&self) -> Option<&T> {
+        match self {
+            Precision::Exact(value) | Precision::Inexact(value) => Some(value),
+            Precision::Absent => None,
+        }
+    }
+
+    /// Transform the value in this [`Precision`] object, if one exists, using
+    /// the given function. Preserves the exactness state.
+    pub fn map<U, F>(self, f: F) -> Precision<U>
+    where
+        F: Fn(T) -> U,
+        U: Debug + Clone + PartialEq + Eq + PartialOrd,
+    {
+        match self {
+            Precision::Exact(value) => Precision::Exact(f(value)),
+            Precision::Inexact(value) => Precision::Inexact(f(value)),
+            Precision::Absent => Precision::Absent,
+        }
+    }
+
+    /// Returns `Some(true)` if we have an exact value, `Some(false)` if we
+    /// have an inexact value, and `None` if there is no value.
+    pub fn is_exact(&self) -> Option<bool> {
+        match self {
+            Precision::Exact(_) => Some(true),
+            Precision::Inexact(_) => Some(false),
+            Precision::Absent => None,
+        }
+    }
+
+    /// Returns the maximum of two (possibly inexact) values, conservatively
+    /// propagating exactness information. If one of the input values is
+    /// [`Precision::Absent`], the result is `Absent` too.
+    pub fn max(&self, other: &Precision<T>) -> Precision<T> {
+        self.combine(other, |a, b| if a >= b { a } else { b })
+    }
+
+    /// Returns the minimum of two (possibly inexact) values, conservatively
+    /// propagating exactness information. If one of the input values is
+    /// [`Precision::Absent`], the result is `Absent` too.
+    pub fn min(&self, other: &Precision<T>) -> Precision<T> {
+        self.combine(other, |a, b| if a <= b { a } else { b })
+    }
+
+    /// Demotes the precision state from exact to inexact (if present)
+    pub fn to_inexact(self) -> Self {
+        match self {
+            Precision::Exact(value) => Precision::Inexact(value),
+            _ => self,
+        }
+    }
+
+    fn combine(
+        &self,
+        other: &Precision<T>,
+        f: impl for<'a> Fn(&'a T, &'a T) -> &'a T,
+    ) -> Precision<T> {
+        match (self, other) {
+            (Precision::Exact(a), Precision::Exact(b)) => {
+                Precision::Exact(f(a, b).clone())
+            }
+            (Precision::Exact(a), Precision::Inexact(b))
+            | (Precision::Inexact(a), Precision::Exact(b))
+            | (Precision::Inexact(a), Precision::Inexact(b)) => {
+                Precision::Inexact(f(a, b).clone())
+            }
+            (_, _) => Precision::Absent,
+        }
+    }
+}
 
-/// Statistics for a relation
-/// Fields are optional and can be inexact because the sources
-/// sometimes provide approximate estimates for performance reasons
-/// and the transformations output are not always predictable.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+impl Precision<usize> {
+    /// Calculates the sum of two (possibly inexact) [`usize`] values,
+    /// conservatively propagating exactness information. If one of the input
+    /// values is [`Precision::Absent`], the result is `Absent` too.
+    pub fn add(&self, other: &Precision<usize>) -> Precision<usize> {
+        self.arithmetic(other, |a, b| a.saturating_add(b))
+    }
+
+    /// Calculates the difference of two (possibly inexact) [`usize`] values,
+    /// saturating at zero, see [`Self::add`]
+    pub fn sub(&self, other: &Precision<usize>) -> Precision<usize> {
+        self.arithmetic(other, |a, b| a.saturating_sub(b))
+    }
+
+    /// Calculates the product of two (possibly inexact) [`usize`] values,
+    /// see [`Self::add`]
+    pub fn multiply(&self, other: &Precision<usize>) -> Precision<usize> {
+        self.arithmetic(other, |a, b| a.saturating_mul(b))
+    }
+
+    /// Returns the estimate of this value after applying a filter that keeps
+    /// the fraction `selectivity` of the rows. The result is always inexact,
+    /// except for an exact zero.
+    pub fn with_estimated_selectivity(self, selectivity: f64) -> Self {
+        match self {
+            Precision::Exact(0) => Precision::Exact(0),
+            Precision::Exact(value) | Precision::Inexact(value) => {
+                Precision::Inexact((value as f64 * selectivity).ceil() as usize)
+            }
+            Precision::Absent => Precision::Absent,
+        }
+    }
+
+    fn arithmetic(
+        &self,
+        other: &Precision<usize>,
+        f: impl Fn(usize, usize) -> usize,
+    ) -> Precision<usize> {
+        match (self, other) {
+            (Precision::Exact(a), Precision::Exact(b)) => Precision::Exact(f(*a, *b)),
+            (Precision::Exact(a), Precision::Inexact(b))
+            | (Precision::Inexact(a), Precision::Exact(b))
+            | (Precision::Inexact(a), Precision::Inexact(b)) => {
+                Precision::Inexact(f(*a, *b))
+            }
+            (_, _) => Precision::Absent,
+        }
+    }
+}
+
+impl<T: Debug + Clone + PartialEq + Eq + PartialOrd> Debug for Precision<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Precision::Exact(value) => write!(f, "Exact({value:?})"),
+            Precision::Inexact(value) => write!(f, "Inexact({value:?})"),
+            Precision::Absent => write!(f, "Absent"),
+        }
+    }
+}
+
+impl<T: Debug + Clone + PartialEq + Eq + PartialOrd> Display for Precision<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Precision::Exact(value) => write!(f, "{value:?}"),
+            Precision::Inexact(value) => write!(f, "~{value:?}"),
+            Precision::Absent => write!(f, "?"),
+        }
+    }
+}
+
+/// Statistics for a relation, or a single partition of a relation.
+///
+/// Each value records whether it is exact or an estimate, as sources
+/// sometimes provide approximate values for performance reasons and the
+/// output of transformations is not always predictable.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Statistics {
     /// The number of table rows
-    pub num_rows: Option<usize>,
-    /// total bytes of the table rows
-    pub total_byte_size: Option<usize>,
-    /// Statistics on a column level
-    pub column_statistics: Option<Vec<ColumnStatistics>>,
-    /// If true, any field that is `Some(..)` is the actual value in the data provided by the operator (it is not
-    /// an estimate). Any or all other fields might still be None, in which case no information is known.
-    /// if false, any field that is `Some(..)` may contain an inexact estimate and may not be the actual value.
-    pub is_exact: bool,
+    pub num_rows: Precision<usize>,
+    /// Total bytes of the table rows
+    pub total_byte_size: Precision<usize>,
+    /// Statistics on a column level, one entry per column of the schema
+    pub column_statistics: Vec<ColumnStatistics>,
+}
+
+impl Statistics {
+    /// Returns a [`Statistics`] instance for the given schema by assigning
+    /// unknown statistics to each column in the schema
+    pub fn new_unknown(schema: &Schema) -> Self {
+        Self {
+            num_rows: Precision::Absent,
+            total_byte_size: Precision::Absent,
+            column_statistics: Statistics::unknown_column(schema),
+        }
+    }
+
+    /// Returns an unbounded [`ColumnStatistics`] for each field in the schema
+    pub fn unknown_column(schema: &Schema) -> Vec<ColumnStatistics> {
+        schema
+            .fields()
+            .iter()
+            .map(|_| ColumnStatistics::new_unknown())
+            .collect()
+    }
+
+    /// If the exactness of a [`Statistics`] instance is lost, this function
+    /// relaxes the exactness of all information by converting them to
+    /// [`Precision::Inexact`]
+    pub fn into_inexact(self) -> Self {
+        Self {
+            num_rows: self.num_rows.to_inexact(),
+            total_byte_size: self.total_byte_size.to_inexact(),
+            column_statistics: self
+                .column_statistics
+                .into_iter()
+                .map(ColumnStatistics::to_inexact)
+                .collect(),
+        }
+    }
+
+    /// Combines the statistics of two disjoint sets of rows with the same
+    /// schema, such as two partitions of a relation, into the statistics of
+    /// their union
+    pub fn merge(&self, other: &Statistics) -> Statistics {
+        Statistics {
+            num_rows: self.num_rows.add(&other.num_rows),
+            total_byte_size: self.total_byte_size.add(&other.total_byte_size),
+            column_statistics: self
+                .column_statistics
+                .iter()
+                .zip(&other.column_statistics)
+                .map(|(a, b)| ColumnStatistics {
+                    null_count: a.null_count.add(&b.null_count),
+                    max_value: merge_bound(&a.max_value, &b.max_value, Ordering::Greater),
+                    min_value: merge_bound(&a.min_value, &b.min_value, Ordering::Less),
+                    distinct_count: Precision::Absent,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Combines two bounds of a column into the bound of their union, picking the
+/// value that compares as `wanted` to the other one. Null bounds are ignored,
+/// and the result is absent if the values are not comparable.
+fn merge_bound(
+    a: &Precision<ScalarValue>,
+    b: &Precision<ScalarValue>,
+    wanted: Ordering,
+) -> Precision<ScalarValue> {
+    let (x, y) = match (a.get_value(), b.get_value()) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Precision::Absent,
+    };
+    let value = if x.is_null() {
+        y
+    } else if y.is_null() {
+        x
+    } else {
+        match x.partial_cmp(y) {
+            Some(ordering) if ordering == wanted => x,
+            Some(_) => y,
+            None => return Precision::Absent,
+        }
+    };
+    match (a, b) {
+        (Precision::Exact(_), Precision::Exact(_)) => Precision::Exact(value.clone()),
+        _ => Precision::Inexact(value.clone()),
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rows={}, Bytes={}", self.num_rows, self.total_byte_size)?;
+        for (i, c) in self.column_statistics.iter().enumerate() {
+            write!(
+                f,
+                ", ({i}): Min={} Max={} Null={} Distinct={}",
+                c.min_value, c.max_value, c.null_count, c.distinct_count
+            )?;
+        }
+        Ok(())
+    }
 }
 
 /// Statistics for a column within a relation
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ColumnStatistics {
     /// Number of null values on column
-    pub null_count: Option<usize>,
+    pub null_count: Precision<usize>,
     /// Maximum value of column
-    pub max_value: Option<ScalarValue>,
+    pub max_value: Precision<ScalarValue>,
     /// Minimum value of column
-    pub min_value: Option<ScalarValue>,
+    pub min_value: Precision<ScalarValue>,
     /// Number of distinct values
-    pub distinct_count: Option<usize>,
+    pub distinct_count: Precision<usize>,
+}
+
+impl ColumnStatistics {
+    /// Returns an unbounded [`ColumnStatistics`]
+    pub fn new_unknown() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the column is known to contain a single value
+    pub fn is_singleton(&self) -> bool {
+        match (&self.min_value, &self.max_value) {
+            (Precision::Exact(min), Precision::Exact(max)) => {
+                !min.is_null() && !max.is_null() && min == max
+            }
+            _ => false,
+        }
+    }
+
+    /// If the exactness of a [`ColumnStatistics`] instance is lost, this
+    /// function relaxes the exactness of all information by converting them
+    /// to [`Precision::Inexact`]
+    pub fn to_inexact(self) -> Self {
+        Self {
+            null_count: self.null_count.to_inexact(),
+            max_value: self.max_value.to_inexact(),
+            min_value: self.min_value.to_inexact(),
+            distinct_count: self.distinct_count.to_inexact(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field};
+
+    #[test]
+    fn test_get_value() {
+        assert_eq!(Precision::Exact(42).get_value(), Some(&42));
+        assert_eq!(Precision::Inexact(23).get_value(), Some(&23));
+        assert_eq!(Precision::<i32>::Absent.get_value(), None);
+    }
+
+    #[test]
+    fn test_max_min() {
+        let exact = Precision::Exact(42);
+        let inexact = Precision::Inexact(23);
+        let absent = Precision::<i32>::Absent;
+
+        assert_eq!(exact.max(&Precision::Exact(10)), Precision::Exact(42));
+        assert_eq!(exact.max(&inexact), Precision::Inexact(42));
+        assert_eq!(exact.min(&inexact), Precision::Inexact(23));
+        assert_eq!(exact.max(&absent), Precision::Absent);
+        assert_eq!(absent.min(&inexact), Precision::Absent);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let exact = Precision::Exact(42usize);
+        let inexact = Precision::Inexact(23usize);
+
+        assert_eq!(exact.add(&Precision::Exact(1)), Precision::Exact(43));
+        assert_eq!(exact.add(&inexact), Precision::Inexact(65));
+        assert_eq!(inexact.sub(&exact), Precision::Inexact(0));
+        assert_eq!(exact.multiply(&Precision::Exact(2)), Precision::Exact(84));
+        assert_eq!(exact.add(&Precision::Absent), Precision::Absent);
+        assert_eq!(
+            exact.with_estimated_selectivity(0.5),
+            Precision::Inexact(21)
+        );
+        assert_eq!(
+            Precision::Exact(0).with_estimated_selectivity(0.5),
+            Precision::Exact(0)
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let stats = |rows, min, max| Statistics {
+            num_rows: Precision::Exact(rows),
+            total_byte_size: Precision::Absent,
+            column_statistics: vec![ColumnStatistics {
+                null_count: Precision::Exact(1),
+                max_value: Precision::Exact(ScalarValue::Int32(Some(max))),
+                min_value: Precision::Exact(ScalarValue::Int32(Some(min))),
+                distinct_count: Precision::Exact(rows),
+            }],
+        };
+
+        let merged = stats(10, 1, 5).merge(&stats(20, 3, 8).into_inexact());
+        assert_eq!(merged.num_rows, Precision::Inexact(30));
+        assert_eq!(merged.total_byte_size, Precision::Absent);
+        let column = &merged.column_statistics[0];
+        assert_eq!(column.null_count, Precision::Inexact(2));
+        assert_eq!(
+            column.min_value,
+            Precision::Inexact(ScalarValue::Int32(Some(1)))
+        );
+        assert_eq!(
+            column.max_value,
+            Precision::Inexact(ScalarValue::Int32(Some(8)))
+        );
+        assert_eq!(column.distinct_count, Precision::Absent);
+
+        let unknown = Statistics::new_unknown(&schema);
+        assert_eq!(
+            unknown.column_statistics,
+            vec![ColumnStatistics::new_unknown()]
+        );
+        assert_eq!(
+            unknown.to_string(),
+            "Rows=?, Bytes=?, (0): Min=? Max=? Null=? Distinct=?"
+        );
+    }
 }
@@ -0,0 +1,681 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`TreeNode`] trait, implemented by the trees of DataFusion (logical
+//! expressions, logical plans, physical expressions and execution plans), and
+//! the visitors and rewriters that walk them.
+
+use std::sync::Arc;
+
+use crate::{DataFusionError, Result};
+
+/// A node of a tree, such as an `Expr`, a `LogicalPlan`, an
+/// `Arc<dyn PhysicalExpr>` or an `Arc<dyn ExecutionPlan>`.
+///
+/// The implementors only define how to walk and rebuild the children of a
+/// node, with [`Self::apply_children`] and [`Self::map_children`]. All the
+/// traversals are then provided, so that optimizer rules don't need to
+/// implement the recursion themselves:
+///
+/// * [`Self::apply`] and [`Self::visit`] walk the tree without changing it,
+/// * [`Self::transform_down`] and [`Self::transform_up`] rewrite each node with
+///   a function, and their `_mut` variants with a stateful one,
+/// * [`Self::transform_using`] rewrites the tree with a [`TreeNodeRewriter`],
+/// * [`Self::transform_down_with_payload`] and
+///   [`Self::transform_up_with_payload`] rewrite the tree while passing values
+///   from each node to its children, or from the children to their parent.
+///
+/// For example, counting the columns of an expression, without looking into
+/// the arguments of the functions:
+///
+/// ```text
+/// let mut columns = 0;
+/// expr.apply(&mut |expr| {
+///     Ok(match expr {
+///         Expr::Column(_) => {
+///             columns += 1;
+///             VisitRecursion::Continue
+///         }
+///         Expr::ScalarFunction { .. } => VisitRecursion::Skip,
+///         _ => VisitRecursion::Continue,
+///     })
+/// })?;
+/// ```
+pub trait TreeNode: Sized {
+    /// Applies `op` to the node and then to its descendants, top down
+    /// (preorder), until `op` returns [`VisitRecursion::Stop`]. When `op`
+    /// returns [`VisitRecursion::Skip`], the descendants of that node are
+    /// not visited.
+    fn apply<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>,
+    {
+        match op(self)? {
+            VisitRecursion::Continue => {}
+            VisitRecursion::Skip => return Ok(VisitRecursion::Continue),
+            VisitRecursion::Stop => return Ok(VisitRecursion::Stop),
+        };
+
+        self.apply_children(&mut |node| node.apply(op))
+    }
+
+    /// Visits the tree with the given [`TreeNodeVisitor`], performing a depth
+    /// first walk of the node and its children.
+    ///
+    /// For a node tree such as
+    /// ```text
+    /// ParentNode
+    ///    left: ChildNode1
+    ///    right: ChildNode2
+    /// ```
+    ///
+    /// The nodes are visited using the following order
+    /// ```text
+    /// pre_visit(ParentNode)
+    /// pre_visit(ChildNode1)
+    /// post_visit(ChildNode1)
+    /// pre_visit(ChildNode2)
+    /// post_visit(ChildNode2)
+    /// post_visit(ParentNode)
+    /// ```
+    ///
+    /// If an Err result is returned, recursion is stopped immediately.
+    ///
+    /// If [`VisitRecursion::Skip`] is returned by `pre_visit`, the children
+    /// of that node are not visited, but `post_visit` is still called on it.
+    /// If [`VisitRecursion::Stop`] is returned by either method, the walk
+    /// ends.
+    fn visit<V: TreeNodeVisitor<N = Self>>(
+        &self,
+        visitor: &mut V,
+    ) -> Result<VisitRecursion> {
+        match visitor.pre_visit(self)? {
+            VisitRecursion::Continue => {}
+            VisitRecursion::Skip => return visitor.post_visit(self),
+            VisitRecursion::Stop => return Ok(VisitRecursion::Stop),
+        };
+
+        match self.apply_children(&mut |node| node.visit(visitor))? {
+            VisitRecursion::Stop => Ok(VisitRecursion::Stop),
+            _ => visitor.post_visit(self),
+        }
+    }
+
+    /// Transform the tree node using the given [TreeNodeRewriter]
+    /// It performs a depth first walk of an node and its children.
+    ///
+    /// For an node tree such as
+    /// ```text
+    /// ParentNode
+    ///    left: ChildNode1
+    ///    right: ChildNode2
+    /// ```
+    ///
+    /// The nodes are visited using the following order
+    /// ```text
+    /// pre_visit(ParentNode)
+    /// pre_visit(ChildNode1)
+    /// mutate(ChildNode1)
+    /// pre_visit(ChildNode2)
+    /// mutate(ChildNode2)
+    /// mutate(ParentNode)
+    /// ```
+    ///
+    /// If an Err result is returned, recursion is stopped immediately
+    ///
+    /// If [`RewriteRecursion::Stop`] is returned on a call to pre_visit, no
+    /// children of that node are visited, nor is mutate
+    /// called on that node
+    ///
+    fn transform_using<R: TreeNodeRewriter<Self>>(
+        self,
+        rewriter: &mut R,
+    ) -> Result<Self> {
+        let need_mutate = match rewriter.pre_visit(&self)? {
+            RewriteRecursion::Mutate => return rewriter.mutate(self),
+            RewriteRecursion::Stop => return Ok(self),
+            RewriteRecursion::Continue => true,
+            RewriteRecursion::Skip => false,
+        };
+
+        let after_op_children =
+            self.map_children(|node| node.transform_using(rewriter))?;
+
+        // now rewrite this node itself
+        if need_mutate {
+            rewriter.mutate(after_op_children)
+        } else {
+            Ok(after_op_children)
+        }
+    }
+
+    /// Convenience utils for writing optimizers rule: recursively apply the given `op` to the node tree.
+    /// When `op` does not apply to a given node, it is left unchanged.
+    /// The default tree traversal direction is transform_up(Postorder Traversal).
+    fn transform<F>(self, op: &F) -> Result<Self>
+    where
+        F: Fn(Self) -> Result<Option<Self>>,
+        Self: Clone,
+    {
+        self.transform_up(op)
+    }
+
+    /// Convenience utils for writing optimizers rule: recursively apply the given 'op' to the node and all of its
+    /// children(Preorder Traversal).
+    /// When the `op` does not apply to a given node, it is left unchanged.
+    fn transform_down<F>(self, op: &F) -> Result<Self>
+    where
+        F: Fn(Self) -> Result<Option<Self>>,
+        Self: Clone,
+    {
+        let node_cloned = self.clone();
+        let after_op = match op(node_cloned)? {
+            Some(value) => value,
+            None => self,
+        };
+        after_op.map_children(|node| node.transform_down(op))
+    }
+
+    /// Like [`Self::transform_down`], with a stateful `op`
+    fn transform_down_mut<F>(self, op: &mut F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Option<Self>>,
+        Self: Clone,
+    {
+        let node_cloned = self.clone();
+        let after_op = match op(node_cloned)? {
+            Some(value) => value,
+            None => self,
+        };
+        after_op.map_children(|node| node.transform_down_mut(op))
+    }
+
+    /// Convenience utils for writing optimizers rule: recursively apply the given 'op' first to all of its
+    /// children and then itself(Postorder Traversal).
+    /// When the `op` does not apply to a given node, it is left unchanged.
+    fn transform_up<F>(self, op: &F) -> Result<Self>
+    where
+        F: Fn(Self) -> Result<Option<Self>>,
+        Self: Clone,
+    {
+        let after_op_children = self.map_children(|node| node.transform_up(op))?;
+
+        let after_op_children_clone = after_op_children.clone();
+        let new_node = match op(after_op_children)? {
+            Some(value) => value,
+            None => after_op_children_clone,
+        };
+        Ok(new_node)
+    }
+
+    /// Like [`Self::transform_up`], with a stateful `op`
+    fn transform_up_mut<F>(self, op: &mut F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Option<Self>>,
+        Self: Clone,
+    {
+        let after_op_children = self.map_children(|node| node.transform_up_mut(op))?;
+
+        let after_op_children_clone = after_op_children.clone();
+        let new_node = match op(after_op_children)? {
+            Some(value) => value,
+            None => after_op_children_clone,
+        };
+        Ok(new_node)
+    }
+
+    /// Rewrites the tree top down (preorder), passing a payload from each
+    /// node to each of its children.
+    ///
+    /// `op` is called with a node and the payload from its parent, `payload`
+    /// for the root. It returns the rewritten node, and the payloads of its
+    /// children, one for each child of the rewritten node, which are then
+    /// rewritten in turn. For example, the ordering a node requires from
+    /// each of its inputs.
+    fn transform_down_with_payload<P, F>(self, op: &mut F, payload: P) -> Result<Self>
+    where
+        F: FnMut(Self, P) -> Result<(Self, Vec<P>)>,
+    {
+        let (new_node, children_payloads) = op(self, payload)?;
+        let mut children_payloads = children_payloads.into_iter();
+        let new_node = new_node.map_children(|node| {
+            let payload = children_payloads.next().ok_or_else(|| {
+                DataFusionError::Internal(
+                    "Fewer payloads than children in transform_down_with_payload"
+                        .to_string(),
+                )
+            })?;
+            node.transform_down_with_payload(op, payload)
+        })?;
+        if children_payloads.next().is_some() {
+            return Err(DataFusionError::Internal(
+                "More payloads than children in transform_down_with_payload".to_string(),
+            ));
+        }
+        Ok(new_node)
+    }
+
+    /// Rewrites the tree bottom up (postorder), passing a payload from the
+    /// children of each node to their parent.
+    ///
+    /// `op` is called with a node, whose children are already rewritten, and
+    /// the payloads returned for its children, in order. It returns the
+    /// rewritten node and its own payload, for example whether its output is
+    /// unbounded. Returns the rewritten root and its payload.
+    fn transform_up_with_payload<P, F>(self, op: &mut F) -> Result<(Self, P)>
+    where
+        F: FnMut(Self, Vec<P>) -> Result<(Self, P)>,
+    {
+        let mut children_payloads = vec![];
+        let new_node = self.map_children(|node| {
+            let (new_node, payload) = node.transform_up_with_payload(op)?;
+            children_payloads.push(payload);
+            Ok(new_node)
+        })?;
+        op(new_node, children_payloads)
+    }
+
+    /// Applies `op` to each child of the node, in order, until it returns
+    /// [`VisitRecursion::Stop`], which is then returned.
+    fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>;
+
+    /// Apply transform `F` to the node's children, the transform `F` might have a direction(Preorder or Postorder)
+    fn map_children<F>(self, transform: F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Self>;
+}
+
+/// Trait for visiting a [`TreeNode`] tree with [`TreeNode::visit`], keeping
+/// some state across the nodes
+pub trait TreeNodeVisitor: Sized {
+    /// The type of the nodes of the tree
+    type N: TreeNode;
+
+    /// Invoked before (Preorder) any children of `node` are visited.
+    fn pre_visit(&mut self, node: &Self::N) -> Result<VisitRecursion>;
+
+    /// Invoked after (Postorder) all children of `node` are visited. Default
+    /// implementation does nothing.
+    fn post_visit(&mut self, _node: &Self::N) -> Result<VisitRecursion> {
+        Ok(VisitRecursion::Continue)
+    }
+}
+
+/// Controls how the visit of a [`TreeNode`] tree should proceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitRecursion {
+    /// Continue the visit of this node tree.
+    Continue,
+    /// Do not visit the children of this node, but continue with the rest
+    /// of the tree.
+    Skip,
+    /// Stop the whole visit.
+    Stop,
+}
+
+/// Trait for potentially recursively transform a [`TreeNode`] node
+/// tree. When passed to [`TreeNode::transform_using`], [`TreeNodeRewriter::mutate`] is
+/// invoked recursively on all nodes of a tree.
+pub trait TreeNodeRewriter<N: TreeNode>: Sized {
+    /// Invoked before (Preorder) any children of `node` are rewritten /
+    /// visited. Default implementation returns `Ok(RewriteRecursion::Continue)`
+    fn pre_visit(&mut self, _node: &N) -> Result<RewriteRecursion> {
+        Ok(RewriteRecursion::Continue)
+    }
+
+    /// Invoked after (Postorder) all children of `node` have been mutated and
+    /// returns a potentially modified node.
+    fn mutate(&mut self, node: N) -> Result<N>;
+}
+
+/// Controls how the [TreeNodeRewriter] recursion should proceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteRecursion {
+    /// Continue rewrite / visit this node tree.
+    Continue,
+    /// Call 'op' immediately and return.
+    Mutate,
+    /// Do not rewrite / visit the children of this node.
+    Stop,
+    /// Keep recursive but skip apply op on this node
+    Skip,
+}
+
+/// Helper for the trees whose nodes are shared trait objects, such as
+/// `Arc<dyn ExecutionPlan>`: implementing it for the trait object implements
+/// [`TreeNode`] for the `Arc` of it.
+pub trait DynTreeNode {
+    /// Returns the children of the node
+    fn arc_children(&self) -> Vec<Arc<Self>>;
+
+    /// Returns a node with the given children, `arc_self` if they are the
+    /// same as its current children
+    fn with_new_arc_children(
+        &self,
+        arc_self: Arc<Self>,
+        new_children: Vec<Arc<Self>>,
+    ) -> Result<Arc<Self>>;
+}
+
+impl<T: DynTreeNode + ?Sized> TreeNode for Arc<T> {
+    fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>,
+    {
+        for child in self.arc_children() {
+            if op(&child)? == VisitRecursion::Stop {
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    }
+
+    fn map_children<F>(self, transform: F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Self>,
+    {
+        let children = self.arc_children();
+        if !children.is_empty() {
+            let new_children: Result<Vec<_>> =
+                children.into_iter().map(transform).collect();
+            let arc_self = Arc::clone(&self);
+            self.with_new_arc_children(arc_self, new_children?)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// The former trait of the rewritable execution plans and physical
+/// expressions, kept for backwards compatibility: its traversals are now
+/// provided by [`TreeNode`], which is implemented by all the trees.
+#[deprecated(since = "17.0.0", note = "please use `TreeNode` instead")]
+pub trait TreeNodeRewritable: Clone {
+    /// Transform the tree node using the given [TreeNodeRewriter], see
+    /// [`TreeNode::transform_using`]
+    fn transform_using<R: TreeNodeRewriter<Self>>(self, rewriter: &mut R) -> Result<Self>
+    where
+        Self: TreeNode,
+    {
+        TreeNode::transform_using(self, rewriter)
+    }
+
+    /// Recursively apply the given `op` to the node tree, see
+    /// [`TreeNode::transform`]
+    fn transform<F>(self, op: &F) -> Result<Self>
+    where
+        F: Fn(Self) -> Result<Option<Self>>,
+    {
+        self.transform_up(op)
+    }
+
+    /// Recursively apply the given `op` to the node and then to its children,
+    /// see [`TreeNode::transform_down`]
+    fn transform_down<F>(self, op: &F) -> Result<Self>
+    where
+        F: Fn(Self) -> Result<Option<Self>>,
+    {
+        let node_cloned = self.clone();
+        let after_op = match op(node_cloned)? {
+            Some(value) => value,
+            None => self,
+        };
+        TreeNodeRewritable::map_children(after_op, |node| node.transform_down(op))
+    }
+
+    /// Recursively apply the given `op` to the children of the node and then
+    /// to the node, see [`TreeNode::transform_up`]
+    fn transform_up<F>(self, op: &F) -> Result<Self>
+    where
+        F: Fn(Self) -> Result<Option<Self>>,
+    {
+        let after_op_children =
+            TreeNodeRewritable::map_children(self, |node| node.transform_up(op))?;
+
+        let after_op_children_clone = after_op_children.clone();
+        let new_node = match op(after_op_children)? {
+            Some(value) => value,
+            None => after_op_children_clone,
+        };
+        Ok(new_node)
+    }
+
+    /// Apply transform `F` to the node's children
+    fn map_children<F>(self, transform: F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Self>;
+}
+
+#[allow(deprecated)]
+impl<T: DynTreeNode + ?Sized> TreeNodeRewritable for Arc<T> {
+    fn map_children<F>(self, transform: F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Self>,
+    {
+        TreeNode::map_children(self, transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tree of numbers
+    #[derive(Debug, Clone, PartialEq)]
+    struct Node {
+        value: i32,
+        children: Vec<Node>,
+    }
+
+    fn node(value: i32, children: Vec<Node>) -> Node {
+        Node { value, children }
+    }
+
+    impl TreeNode for Node {
+        fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+        where
+            F: FnMut(&Self) -> Result<VisitRecursion>,
+        {
+            for child in &self.children {
+                if op(child)? == VisitRecursion::Stop {
+                    return Ok(VisitRecursion::Stop);
+                }
+            }
+            Ok(VisitRecursion::Continue)
+        }
+
+        fn map_children<F>(self, transform: F) -> Result<Self>
+        where
+            F: FnMut(Self) -> Result<Self>,
+        {
+            Ok(Node {
+                value: self.value,
+                children: self
+                    .children
+                    .into_iter()
+                    .map(transform)
+                    .collect::<Result<_>>()?,
+            })
+        }
+    }
+
+    ///       1
+    ///     /   \
+    ///    2     5
+    ///   / \
+    ///  3   4
+    fn tree() -> Node {
+        node(
+            1,
+            vec![
+                node(2, vec![node(3, vec![]), node(4, vec![])]),
+                node(5, vec![]),
+            ],
+        )
+    }
+
+    #[test]
+    fn apply() -> Result<()> {
+        let mut visited = vec![];
+        tree().apply(&mut |node| {
+            visited.push(node.value);
+            Ok(VisitRecursion::Continue)
+        })?;
+        assert_eq!(visited, vec![1, 2, 3, 4, 5]);
+
+        // skip the children of 2
+        let mut visited = vec![];
+        tree().apply(&mut |node| {
+            visited.push(node.value);
+            Ok(if node.value == 2 {
+                VisitRecursion::Skip
+            } else {
+                VisitRecursion::Continue
+            })
+        })?;
+        assert_eq!(visited, vec![1, 2, 5]);
+
+        // stop at 3
+        let mut visited = vec![];
+        let recursion = tree().apply(&mut |node| {
+            visited.push(node.value);
+            Ok(if node.value == 3 {
+                VisitRecursion::Stop
+            } else {
+                VisitRecursion::Continue
+            })
+        })?;
+        assert_eq!(visited, vec![1, 2, 3]);
+        assert_eq!(recursion, VisitRecursion::Stop);
+        Ok(())
+    }
+
+    #[test]
+    fn visit() -> Result<()> {
+        struct Recorder(Vec<String>);
+
+        impl TreeNodeVisitor for Recorder {
+            type N = Node;
+
+            fn pre_visit(&mut self, node: &Node) -> Result<VisitRecursion> {
+                self.0.push(format!("pre {}", node.value));
+                Ok(if node.value == 2 {
+                    VisitRecursion::Skip
+                } else {
+                    VisitRecursion::Continue
+                })
+            }
+
+            fn post_visit(&mut self, node: &Node) -> Result<VisitRecursion> {
+                self.0.push(format!("post {}", node.value));
+                Ok(VisitRecursion::Continue)
+            }
+        }
+
+        let mut recorder = Recorder(vec![]);
+        tree().visit(&mut recorder)?;
+        assert_eq!(
+            recorder.0,
+            vec!["pre 1", "pre 2", "post 2", "pre 5", "post 5", "post 1"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn transform_mut() -> Result<()> {
+        // number the nodes in preorder
+        let mut next = 0;
+        let numbered = tree().transform_down_mut(&mut |node| {
+            next += 1;
+            Ok(Some(Node {
+                value: next,
+                ..node
+            }))
+        })?;
+        assert_eq!(numbered, tree());
+
+        // number the nodes in postorder
+        let mut next = 0;
+        let numbered = tree().transform_up_mut(&mut |node| {
+            next += 1;
+            Ok(Some(Node {
+                value: next,
+                ..node
+            }))
+        })?;
+        let expected = node(
+            5,
+            vec![
+                node(3, vec![node(1, vec![]), node(2, vec![])]),
+                node(4, vec![]),
+            ],
+        );
+        assert_eq!(numbered, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn transform_with_payload() -> Result<()> {
+        // replace each value by its depth
+        let depths = tree().transform_down_with_payload(
+            &mut |node, depth| {
+                let children_depths = vec![depth + 1; node.children.len()];
+                Ok((
+                    Node {
+                        value: depth,
+                        ..node
+                    },
+                    children_depths,
+                ))
+            },
+            0,
+        )?;
+        let expected = node(
+            0,
+            vec![
+                node(1, vec![node(2, vec![]), node(2, vec![])]),
+                node(1, vec![]),
+            ],
+        );
+        assert_eq!(depths, expected);
+
+        // replace each value by the sum of the values of its subtree
+        let (sums, total) =
+            tree().transform_up_with_payload(&mut |node, children_sums: Vec<i32>| {
+                let sum = node.value + children_sums.iter().sum::<i32>();
+                Ok((Node { value: sum, ..node }, sum))
+            })?;
+        let expected = node(
+            15,
+            vec![
+                node(9, vec![node(3, vec![]), node(4, vec![])]),
+                node(5, vec![]),
+            ],
+        );
+        assert_eq!(sums, expected);
+        assert_eq!(total, 15);
+
+        // a payload is required for each child
+        let err = tree()
+            .transform_down_with_payload(&mut |node, _| Ok((node, vec![])), ())
+            .unwrap_err();
+        assert!(err.to_string().contains("Fewer payloads than children"));
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks for sorting multiple columns
+//!
+//! Each benchmark sorts a batch of unsorted columns both by comparing the
+//! columns one after the other ("lexsort"), as done by
+//! `arrow::compute::lexsort_to_indices`, and by comparing normalized keys
+//! ("row format"), as done by [`SortExec`]. Dictionary and nullable
+//! columns are included, as well as a sort with a limit.
+//!
+//! [`SortExec`]: datafusion::physical_plan::sorts::sort::SortExec
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, DictionaryArray, Float64Array, Int64Array, StringArray};
+use arrow::compute::{SortColumn, SortOptions};
+use arrow::datatypes::Int32Type;
+use criterion::{criterion_group, criterion_main, Criterion};
+use datafusion::physical_plan::sorts::row_sort;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Total number of rows to sort
+const INPUT_SIZE: usize = 100000;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut gen = DataGenerator::new();
+
+    let cases: Vec<(&str, Vec<ArrayRef>)> = vec![
+        (
+            "i64 tuple",
+            vec![Arc::new(gen.i64_values()), Arc::new(gen.i64_values())],
+        ),
+        (
+            "utf8 tuple",
+            vec![
+                Arc::new(gen.utf8_low_cardinality_values()),
+                Arc::new(gen.utf8_low_cardinality_values()),
+                Arc::new(gen.utf8_high_cardinality_values()),
+            ],
+        ),
+        (
+            "utf8 dictionary tuple",
+            vec![
+                Arc::new(gen.dictionary_values()),
+                Arc::new(gen.dictionary_values()),
+                Arc::new(gen.dictionary_values()),
+            ],
+        ),
+        (
+            "mixed tuple",
+            vec![
+                Arc::new(gen.f64_values()),
+                Arc::new(gen.utf8_low_cardinality_values()),
+                Arc::new(gen.dictionary_values()),
+                Arc::new(gen.i64_values()),
+            ],
+        ),
+    ];
+
+    for (name, arrays) in cases {
+        let columns: Vec<_> = arrays
+            .into_iter()
+            .map(|values| SortColumn {
+                values,
+                options: Some(SortOptions::default()),
+            })
+            .collect();
+
+        for fetch in [None, Some(10)] {
+            let suffix = match fetch {
+                Some(fetch) => format!(" limit {fetch}"),
+                None => String::new(),
+            };
+
+            c.bench_function(&format!("lexsort {name}{suffix}"), |b| {
+                b.iter(|| arrow::compute::lexsort_to_indices(&columns, fetch).unwrap())
+            });
+
+            c.bench_function(&format!("row format {name}{suffix}"), |b| {
+                b.iter(|| row_sort::lexsort_to_indices(&columns, fetch).unwrap())
+            });
+        }
+    }
+}
+
+/// Generates unsorted columns of random values, with about 10% nulls
+struct DataGenerator {
+    rng: StdRng,
+}
+
+impl DataGenerator {
+    fn new() -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(42),
+        }
+    }
+
+    fn maybe_null<T>(&mut self, value: T) -> Option<T> {
+        self.rng.gen_bool(0.9).then_some(value)
+    }
+
+    fn i64_values(&mut self) -> Int64Array {
+        (0..INPUT_SIZE)
+            .map(|_| {
+                let value = self.rng.gen_range(0..INPUT_SIZE as i64);
+                self.maybe_null(value)
+            })
+            .collect()
+    }
+
+    fn f64_values(&mut self) -> Float64Array {
+        (0..INPUT_SIZE)
+            .map(|_| {
+                let value = self.rng.gen_range(0.0..INPUT_SIZE as f64);
+                self.maybe_null(value)
+            })
+            .collect()
+    }
+
+    fn low_cardinality_strings(&mut self) -> Vec<Option<String>> {
+        (0..INPUT_SIZE)
+            .map(|_| {
+                let value = format!("value{}", self.rng.gen_range(0..100));
+                self.maybe_null(value)
+            })
+            .collect()
+    }
+
+    fn utf8_low_cardinality_values(&mut self) -> StringArray {
+        self.low_cardinality_strings().into_iter().collect()
+    }
+
+    fn utf8_high_cardinality_values(&mut self) -> StringArray {
+        (0..INPUT_SIZE)
+            .map(|_| {
+                let value = format!("value{}", self.rng.gen::<u64>());
+                self.maybe_null(value)
+            })
+            .collect()
+    }
+
+    fn dictionary_values(&mut self) -> DictionaryArray<Int32Type> {
+        self.low_cardinality_strings()
+            .iter()
+            .map(Option::as_deref)
+            .collect()
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -16,7 +16,7 @@
 // under the License.
 
 //! Describes the interface and built-in implementations of schemas,
-//! representing collections of named tables.
+//! representing collections of named tables and functions.
 
 use async_trait::async_trait;
 use dashmap::DashMap;
@@ -25,8 +25,16 @@ use std::sync::Arc;
 
 use crate::datasource::TableProvider;
 use crate::error::{DataFusionError, Result};
-
-/// Represents a schema, comprising a number of named tables.
+use crate::logical_expr::{AggregateUDF, ScalarUDF};
+
+/// Represents a schema, comprising a number of named tables, and optionally
+/// of functions.
+///
+/// The functions of a schema are called by their name qualified by the
+/// schema, such as `my_schema.my_func(x)`, or by their bare name when the
+/// schema is the default schema and no function of the session has this
+/// name. This allows several tenants of a session to each register their
+/// functions in their own schema.
 #[async_trait]
 pub trait SchemaProvider: Sync + Send {
     /// Returns the schema provider as [`Any`](std::any::Any)
@@ -65,18 +73,75 @@ pub trait SchemaProvider: Sync + Send {
     /// If no matched table in the schema provider, return false.
     /// Otherwise, return true.
     fn table_exist(&self, name: &str) -> bool;
+
+    /// Retrieves the list of the names of the functions in this schema.
+    fn function_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Retrieves a specific scalar function from the schema by name, provided
+    /// it exists.
+    #[allow(unused_variables)]
+    fn udf(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        None
+    }
+
+    /// Retrieves a specific aggregate function from the schema by name,
+    /// provided it exists.
+    #[allow(unused_variables)]
+    fn udaf(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        None
+    }
+
+    /// If supported by the implementation, adds a new scalar function to this
+    /// schema, under its name. If a function of the same name existed before,
+    /// it is replaced and returned.
+    #[allow(unused_variables)]
+    fn register_udf(&self, udf: Arc<ScalarUDF>) -> Result<Option<Arc<ScalarUDF>>> {
+        Err(DataFusionError::Execution(
+            "schema provider does not support registering functions".to_owned(),
+        ))
+    }
+
+    /// If supported by the implementation, adds a new aggregate function to
+    /// this schema, under its name. If a function of the same name existed
+    /// before, it is replaced and returned.
+    #[allow(unused_variables)]
+    fn register_udaf(
+        &self,
+        udaf: Arc<AggregateUDF>,
+    ) -> Result<Option<Arc<AggregateUDF>>> {
+        Err(DataFusionError::Execution(
+            "schema provider does not support registering functions".to_owned(),
+        ))
+    }
+
+    /// If supported by the implementation, removes the scalar and aggregate
+    /// functions of the given name from this schema. Returns true if there
+    /// was such a function.
+    #[allow(unused_variables)]
+    fn deregister_function(&self, name: &str) -> Result<bool> {
+        Err(DataFusionError::Execution(
+            "schema provider does not support deregistering functions".to_owned(),
+        ))
+    }
 }
 
 /// Simple in-memory implementation of a schema.
 pub struct MemorySchemaProvider {
     tables: DashMap<String, Arc<dyn TableProvider>>,
+    scalar_functions: DashMap<String, Arc<ScalarUDF>>,
+    aggregate_functions: DashMap<String, Arc<AggregateUDF>>,
 }
 
 impl MemorySchemaProvider {
-    /// Instantiates a new MemorySchemaProvider with an empty collection of tables.
+    /// Instantiates a new MemorySchemaProvider with empty collections of
+    /// tables and functions.
     pub fn new() -> Self {
         Self {
             tables: DashMap::new(),
+            scalar_functions: DashMap::new(),
+            aggregate_functions: DashMap::new(),
         }
     }
 }
@@ -124,6 +189,47 @@ impl SchemaProvider for MemorySchemaProvider {
     fn table_exist(&self, name: &str) -> bool {
         self.tables.contains_key(name)
     }
+
+    fn function_names(&self) -> Vec<String> {
+        self.scalar_functions
+            .iter()
+            .map(|function| function.key().clone())
+            .chain(
+                self.aggregate_functions
+                    .iter()
+                    .map(|function| function.key().clone()),
+            )
+            .collect()
+    }
+
+    fn udf(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.scalar_functions
+            .get(name)
+            .map(|function| function.value().clone())
+    }
+
+    fn udaf(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        self.aggregate_functions
+            .get(name)
+            .map(|function| function.value().clone())
+    }
+
+    fn register_udf(&self, udf: Arc<ScalarUDF>) -> Result<Option<Arc<ScalarUDF>>> {
+        Ok(self.scalar_functions.insert(udf.name.clone(), udf))
+    }
+
+    fn register_udaf(
+        &self,
+        udaf: Arc<AggregateUDF>,
+    ) -> Result<Option<Arc<AggregateUDF>>> {
+        Ok(self.aggregate_functions.insert(udaf.name.clone(), udaf))
+    }
+
+    fn deregister_function(&self, name: &str) -> Result<bool> {
+        let scalar = self.scalar_functions.remove(name);
+        let aggregate = self.aggregate_functions.remove(name);
+        Ok(scalar.is_some() || aggregate.is_some())
+    }
 }
 
 #[cfg(test)]
@@ -34,16 +34,23 @@ use crate::datasource::{MemTable, TableProvider};
 use crate::error::Result;
 use crate::execution::{
     context::{SessionState, TaskContext},
+    planning_metrics::PlanningMetrics,
+    result_cache::ResultCacheKey,
     FunctionRegistry,
 };
 use crate::logical_expr::{
     col, utils::find_window_exprs, Expr, JoinType, LogicalPlan, LogicalPlanBuilder,
     Partitioning, TableType,
 };
-use crate::physical_plan::file_format::{plan_to_csv, plan_to_json, plan_to_parquet};
+use crate::physical_plan::file_format::{
+    plan_to_arrow, plan_to_csv, plan_to_json, plan_to_parquet,
+};
 use crate::physical_plan::SendableRecordBatchStream;
 use crate::physical_plan::{collect, collect_partitioned};
-use crate::physical_plan::{execute_stream, execute_stream_partitioned, ExecutionPlan};
+use crate::physical_plan::{
+    collect_partitioned_with_properties, execute_into_sink, execute_stream,
+    execute_stream_partitioned, DataSink, ExecutionPlan, PartitionedBatches,
+};
 use crate::prelude::SessionContext;
 
 /// DataFrame represents a logical set of rows with the same named columns.
@@ -80,12 +87,51 @@ pub struct DataFrame {
 impl DataFrame {
     /// Create a new Table based on an existing logical plan
     pub fn new(session_state: SessionState, plan: LogicalPlan) -> Self {
+        let session_state = match session_state.planning_metrics() {
+            Some(_) => session_state,
+            None => session_state.with_planning_metrics(Arc::new(PlanningMetrics::new())),
+        };
         Self {
             session_state,
             plan,
         }
     }
 
+    /// Record the time spent planning this DataFrame in `planning_metrics`
+    pub(crate) fn with_planning_metrics(
+        mut self,
+        planning_metrics: Arc<PlanningMetrics>,
+    ) -> Self {
+        self.session_state = self.session_state.with_planning_metrics(planning_metrics);
+        self
+    }
+
+    /// Returns the time spent planning this DataFrame so far: parsing and
+    /// planning its SQL, if created by [`SessionContext::sql`], and
+    /// optimizing and physically planning it, once executed.
+    ///
+    /// The DataFrames derived from this one share its metrics.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.sql("SELECT 1").await?;
+    /// let metrics = df.planning_metrics();
+    /// df.collect().await?;
+    /// println!("{metrics:#}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn planning_metrics(&self) -> Arc<PlanningMetrics> {
+        self.session_state
+            .planning_metrics()
+            .expect("planning metrics of a DataFrame")
+            .clone()
+    }
+
     /// Create a physical plan
     pub async fn create_physical_plan(self) -> Result<Arc<dyn ExecutionPlan>> {
         self.session_state.create_physical_plan(&self.plan).await
@@ -236,6 +282,29 @@ impl DataFrame {
         Ok(DataFrame::new(self.session_state, plan))
     }
 
+    /// Calculate the union of two [`DataFrame`]s, preserving duplicate rows,
+    /// matching their columns by name instead of by position. The columns
+    /// only found in one of the [`DataFrame`]s are filled with nulls.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let d2 = df.clone().select_columns(&["c", "a"])?;
+    /// let df = df.union_by_name(d2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn union_by_name(self, dataframe: DataFrame) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .union_by_name(dataframe.plan)?
+            .build()?;
+        Ok(DataFrame::new(self.session_state, plan))
+    }
+
     /// Calculate the distinct union of two [`DataFrame`]s.  The
     /// two [`DataFrame`]s must have exactly the same schema
     ///
@@ -280,6 +349,40 @@ impl DataFrame {
         ))
     }
 
+    /// Return `select_expr` for the first row of each distinct value of
+    /// `on_expr`, in the order of `sort_expr`, whose leading expressions must
+    /// be the ones of `on_expr`. This is Postgres' `SELECT DISTINCT ON`,
+    /// which the SQL parser does not support yet.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.distinct_on(
+    ///     vec![col("a")],
+    ///     vec![col("a"), col("b")],
+    ///     Some(vec![col("a").sort(true, true), col("b").sort(false, true)]),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn distinct_on(
+        self,
+        on_expr: Vec<Expr>,
+        select_expr: Vec<Expr>,
+        sort_expr: Option<Vec<Expr>>,
+    ) -> Result<DataFrame> {
+        Ok(DataFrame::new(
+            self.session_state,
+            LogicalPlanBuilder::from(self.plan)
+                .distinct_on(on_expr, select_expr, sort_expr)?
+                .build()?,
+        ))
+    }
+
     /// Sort the DataFrame by the specified sorting expressions. Any expression can be turned into
     /// a sort expression by calling its [sort](../logical_plan/enum.Expr.html#method.sort) method.
     ///
@@ -375,10 +478,34 @@ impl DataFrame {
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// If the [`RuntimeEnv`](crate::execution::runtime_env::RuntimeEnv) has a
+    /// [`ResultCache`](crate::execution::result_cache::ResultCache), the results of deterministic queries are looked up in
+    /// it before executing them, and cached after.
     pub async fn collect(self) -> Result<Vec<RecordBatch>> {
         let task_ctx = Arc::new(self.task_ctx());
-        let plan = self.create_physical_plan().await?;
-        collect(plan, task_ctx).await
+        let cache = match &self.session_state.runtime_env().result_cache {
+            Some(cache) => cache.clone(),
+            None => {
+                let plan = self.create_physical_plan().await?;
+                return collect(plan, task_ctx).await;
+            }
+        };
+
+        let plan = self.session_state.optimize(&self.plan)?;
+        let key = ResultCacheKey::try_new(&plan)?;
+        if let Some(batches) = key.as_ref().and_then(|key| cache.get(key)) {
+            return Ok(batches);
+        }
+        let physical_plan = self
+            .session_state
+            .create_optimized_physical_plan(&plan)
+            .await?;
+        let batches = collect(physical_plan, task_ctx).await?;
+        if let Some(key) = key {
+            cache.insert(key, batches.clone());
+        }
+        Ok(batches)
     }
 
     /// Print results.
@@ -460,6 +587,32 @@ impl DataFrame {
         collect_partitioned(plan, task_ctx).await
     }
 
+    /// Executes this DataFrame and collects all results per partition, like
+    /// [`Self::collect_partitioned`], along with the partitioning and the
+    /// ordering of the partitions. The results can be re-imported without
+    /// losing these properties with [`PartitionedBatches::into_mem_table`].
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use std::sync::Arc;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let results = df.sort(vec![col("a").sort(true, true)])?
+    ///     .collect_partitioned_with_properties()
+    ///     .await?;
+    /// ctx.register_table("sorted", Arc::new(results.into_mem_table()?))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collect_partitioned_with_properties(self) -> Result<PartitionedBatches> {
+        let task_ctx = Arc::new(self.task_ctx());
+        let plan = self.create_physical_plan().await?;
+        collect_partitioned_with_properties(plan, task_ctx).await
+    }
+
     /// Executes this DataFrame and returns one stream per partition.
     ///
     /// ```
@@ -481,6 +634,41 @@ impl DataFrame {
         execute_stream_partitioned(plan, task_ctx)
     }
 
+    /// Executes all the partitions of this DataFrame concurrently, writing their
+    /// results into `sink` as they are produced, and returns the number of rows
+    /// written. See [`DataSink`] for how a slow sink bounds the memory used.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::arrow::record_batch::RecordBatch;
+    /// # use datafusion::physical_plan::DataSink;
+    /// # use async_trait::async_trait;
+    /// # use std::sync::Arc;
+    /// struct PrintSink;
+    ///
+    /// #[async_trait]
+    /// impl DataSink for PrintSink {
+    ///     async fn write(&self, partition: usize, batch: RecordBatch) -> Result<()> {
+    ///         println!("partition {partition}: {} rows", batch.num_rows());
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let rows = df.execute_into_sink(Arc::new(PrintSink)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_into_sink(self, sink: Arc<dyn DataSink>) -> Result<u64> {
+        let task_ctx = Arc::new(self.task_ctx());
+        let plan = self.create_physical_plan().await?;
+        execute_into_sink(plan, task_ctx, sink).await
+    }
+
     /// Returns the schema describing the output of this DataFrame in terms of columns returned,
     /// where each column has a name, data type, and nullability attribute.
 
@@ -656,6 +844,12 @@ impl DataFrame {
         plan_to_json(&self.session_state, plan, path).await
     }
 
+    /// Executes a query and writes the results to a partitioned Arrow IPC file.
+    pub async fn write_arrow(self, path: impl AsRef<str>) -> Result<()> {
+        let plan = self.session_state.create_physical_plan(&self.plan).await?;
+        plan_to_arrow(&self.session_state, plan, path).await
+    }
+
     /// Add an additional column to the DataFrame.
     ///
     /// ```
@@ -834,26 +1028,33 @@ impl TableProvider for DataFrameTableProvider {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
     use std::vec;
 
-    use arrow::array::Int32Array;
-    use arrow::datatypes::DataType;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field};
 
     use datafusion_expr::{
         avg, cast, count, count_distinct, create_udf, expr, lit, max, min, sum,
-        BuiltInWindowFunction, ScalarFunctionImplementation, Volatility, WindowFrame,
+        AggregateFunction, BuiltInWindowFunction, ScalarFunctionImplementation,
+        Volatility, WindowFrame, WindowFrameBound, WindowFrameExclude, WindowFrameUnits,
         WindowFunction,
     };
     use datafusion_physical_expr::expressions::Column;
 
     use crate::execution::context::SessionConfig;
     use crate::execution::options::{CsvReadOptions, ParquetReadOptions};
+    use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::displayable;
     use crate::physical_plan::ColumnarValue;
     use crate::physical_plan::Partitioning;
     use crate::physical_plan::PhysicalExpr;
     use crate::test_util;
     use crate::test_util::parquet_test_data;
-    use crate::{assert_batches_sorted_eq, execution::context::SessionContext};
+    use crate::{
+        assert_batches_eq, assert_batches_sorted_eq, execution::context::SessionContext,
+    };
 
     use super::*;
 
@@ -913,6 +1114,87 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn select_with_window_frame_exclude() -> Result<()> {
+        let a: Int32Array = [1, 2, 2, 3].into_iter().collect();
+        let b: Int32Array = [10, 20, 30, 40].into_iter().collect();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("a", Arc::new(a) as _),
+            ("b", Arc::new(b) as _),
+        ])?;
+        let ctx = SessionContext::new();
+        let df = ctx.read_batch(batch)?;
+
+        let whole_partition = WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(None)),
+            end_bound: WindowFrameBound::Following(ScalarValue::UInt64(None)),
+            exclude: WindowFrameExclude::NoOthers,
+        };
+        let window_expr = |fun: WindowFunction, window_frame: WindowFrame, name: &str| {
+            Expr::WindowFunction(expr::WindowFunction::new(
+                fun,
+                vec![col("b")],
+                vec![],
+                vec![col("a").sort(true, false)],
+                window_frame,
+            ))
+            .alias(name)
+        };
+        let sum_fun = WindowFunction::AggregateFunction(AggregateFunction::Sum);
+        let exprs = vec![
+            col("b"),
+            window_expr(
+                sum_fun.clone(),
+                whole_partition
+                    .clone()
+                    .with_exclude(WindowFrameExclude::CurrentRow),
+                "current_row",
+            ),
+            window_expr(
+                sum_fun.clone(),
+                whole_partition
+                    .clone()
+                    .with_exclude(WindowFrameExclude::Group),
+                "group",
+            ),
+            window_expr(
+                sum_fun.clone(),
+                whole_partition
+                    .clone()
+                    .with_exclude(WindowFrameExclude::Ties),
+                "ties",
+            ),
+            // the default frame, up to the peers of the current row
+            window_expr(
+                sum_fun,
+                WindowFrame::new(true).with_exclude(WindowFrameExclude::Group),
+                "preceding",
+            ),
+            window_expr(
+                WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::FirstValue),
+                whole_partition.with_exclude(WindowFrameExclude::CurrentRow),
+                "first",
+            ),
+        ];
+        let results = df.select(exprs)?.sort(vec![col("b").sort(true, false)])?;
+
+        assert_batches_eq!(
+            vec![
+                "+----+-------------+-------+------+-----------+-------+",
+                "| b  | current_row | group | ties | preceding | first |",
+                "+----+-------------+-------+------+-----------+-------+",
+                "| 10 | 90          | 90    | 100  |           | 20    |",
+                "| 20 | 80          | 50    | 70   | 10        | 10    |",
+                "| 30 | 70          | 50    | 80   | 10        | 10    |",
+                "| 40 | 60          | 60    | 100  | 60        | 10    |",
+                "+----+-------------+-------+------+-----------+-------+",
+            ],
+            &results.collect().await?
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn select_with_periods() -> Result<()> {
         // define data with a column name that has a "." in it:
@@ -1096,6 +1378,66 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn union_by_name() -> Result<()> {
+        let df = test_table().await?.select_columns(&["c1", "c2", "c3"])?;
+        let d2 = test_table().await?.select_columns(&["c3", "c1"])?;
+        let df = df.union_by_name(d2)?;
+
+        let schema = df.schema();
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name()).collect();
+        assert_eq!(names, vec!["c1", "c2", "c3"]);
+        assert!(schema.field(1).is_nullable());
+
+        // the rows of the second input have a null c2
+        let batches = df.filter(col("c2").is_null())?.collect().await?;
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 100);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn distinct_on() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Utf8, false),
+            Field::new("b", DataType::Int32, false),
+            Field::new("c", DataType::Int32, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["x", "y", "x", "y", "z", "x"])),
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5, 6])),
+                Arc::new(Int32Array::from(vec![10, 20, 30, 10, 50, 20])),
+            ],
+        )?;
+        let ctx = SessionContext::new();
+        let df = ctx.read_batch(batch)?;
+
+        // the row with the largest c of each a
+        let results = df
+            .distinct_on(
+                vec![col("a")],
+                vec![col("a"), col("b")],
+                Some(vec![col("a").sort(true, true), col("c").sort(false, true)]),
+            )?
+            .collect()
+            .await?;
+        assert_batches_sorted_eq!(
+            vec![
+                "+---+---+",
+                "| a | b |",
+                "+---+---+",
+                "| x | 3 |",
+                "| y | 2 |",
+                "| z | 5 |",
+                "+---+---+",
+            ],
+            &results
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn register_table() -> Result<()> {
         let df = test_table().await?.select_columns(&["c1", "c12"])?;
@@ -1151,6 +1493,59 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn collect_partitioned_with_properties() -> Result<()> {
+        let results = test_table()
+            .await?
+            .select_columns(&["c1", "c2"])?
+            .sort(vec![col("c2").sort(true, true), col("c1").sort(true, true)])?
+            .collect_partitioned_with_properties()
+            .await?;
+        assert_eq!(results.partitions.len(), 1);
+        assert_eq!(results.partitioning.partition_count(), 1);
+        let ordering: Vec<_> = results
+            .output_ordering
+            .iter()
+            .flatten()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(ordering, vec!["c2@1 ASC", "c1@0 ASC"]);
+
+        let ctx = SessionContext::new();
+        ctx.register_table("t", Arc::new(results.into_mem_table()?))?;
+        let plan = ctx
+            .sql("SELECT c2, c1 FROM t")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let plan = format!("{}", displayable(plan.as_ref()).indent());
+        assert!(
+            plan.contains("output_ordering=[c2@0 ASC, c1@1 ASC]"),
+            "{plan}"
+        );
+
+        // the ordering is lost without its first column
+        let plan = ctx
+            .sql("SELECT c1 FROM t")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let plan = format!("{}", displayable(plan.as_ref()).indent());
+        assert!(!plan.contains("output_ordering"), "{plan}");
+
+        let scanned = ctx.sql("SELECT c2, c1 FROM t").await?.collect().await?;
+        let sorted = ctx
+            .sql("SELECT c2, c1 FROM t ORDER BY c2, c1")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(
+            pretty::pretty_format_batches(&scanned)?.to_string(),
+            pretty::pretty_format_batches(&sorted)?.to_string()
+        );
+        Ok(())
+    }
+
     /// Compare the formatted string representation of two plans for equality
     fn assert_same_plan(plan1: &LogicalPlan, plan2: &LogicalPlan) {
         assert_eq!(format!("{plan1:?}"), format!("{plan2:?}"));
@@ -1531,6 +1926,70 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn result_cache() -> Result<()> {
+        let runtime = RuntimeEnv::new(RuntimeConfig::new().with_result_cache(1 << 20))?;
+        let ctx = SessionContext::with_config_rt(SessionConfig::new(), Arc::new(runtime));
+        let cache = ctx.runtime_env().result_cache.clone().unwrap();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice(&[1, 2, 3]))],
+        )?;
+        let table = MemTable::try_new(schema.clone(), vec![vec![batch.clone()]])?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        let sql = "SELECT sum(a) AS s FROM t";
+        let expected = vec!["+---+", "| s |", "+---+", "| 6 |", "+---+"];
+        assert_batches_eq!(expected, &ctx.sql(sql).await?.collect().await?);
+        assert_eq!(cache.len(), 1);
+        assert_batches_eq!(expected, &ctx.sql(sql).await?.collect().await?);
+        assert_eq!(cache.len(), 1);
+
+        // new contents of the table are not read from the cache
+        let table = MemTable::try_new(schema, vec![vec![batch.clone(), batch]])?;
+        ctx.deregister_table("t")?;
+        ctx.register_table("t", Arc::new(table))?;
+        assert_batches_eq!(
+            vec!["+----+", "| s  |", "+----+", "| 12 |", "+----+"],
+            &ctx.sql(sql).await?.collect().await?
+        );
+        assert_eq!(cache.len(), 2);
+
+        // nor are the results of volatile queries cached
+        ctx.sql("SELECT a, random() FROM t")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(cache.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn planning_metrics() -> Result<()> {
+        let ctx = SessionContext::new();
+        let df = ctx.sql("SELECT 1 + 2 AS a").await?;
+        let metrics = df.planning_metrics();
+        assert!(metrics.parsing() > Duration::ZERO);
+        assert!(metrics.optimizer_rules().is_empty());
+
+        // derived DataFrames share the metrics
+        let df = df.filter(col("a").gt(lit(1)))?;
+        assert!(Arc::ptr_eq(&metrics, &df.planning_metrics()));
+
+        df.collect().await?;
+        let rules = metrics
+            .optimizer_rules()
+            .into_iter()
+            .map(|(rule, _)| rule)
+            .collect::<Vec<_>>();
+        assert!(rules.contains(&"simplify_expressions".to_string()));
+        assert!(metrics.physical_planning() > Duration::ZERO);
+        assert!(metrics.total() >= metrics.optimization());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn partition_aware_union() -> Result<()> {
         let left = test_table().await?.select_columns(&["c1", "c2"])?;
@@ -1661,7 +2120,8 @@ mod tests {
                 JoinType::Inner
                 | JoinType::Left
                 | JoinType::LeftSemi
-                | JoinType::LeftAnti => {
+                | JoinType::LeftAnti
+                | JoinType::LeftMark => {
                     let left_exprs: Vec<Arc<dyn PhysicalExpr>> = vec![
                         Arc::new(Column::new_with_schema("c1", &join_schema)?),
                         Arc::new(Column::new_with_schema("c2", &join_schema)?),
@@ -0,0 +1,263 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Statistics of the tables computed by `ANALYZE TABLE`
+
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+
+use arrow::datatypes::DataType;
+use datafusion_common::{Column, ColumnStatistics, Precision, ScalarValue, Statistics};
+use datafusion_expr::type_coercion::aggregates::{
+    DATES, NUMERICS, STRINGS, TIMES, TIMESTAMPS,
+};
+use datafusion_expr::utils::COUNT_STAR_EXPANSION;
+use datafusion_expr::{
+    approx_distinct, count, max, min, LogicalPlanBuilder, TableProviderFilterPushDown,
+    UNNAMED_TABLE,
+};
+
+use crate::{
+    dataframe::DataFrame,
+    error::{DataFusionError, Result},
+    logical_expr::Expr,
+    physical_plan::{cached_statistics::CachedStatisticsExec, ExecutionPlan},
+};
+
+use crate::datasource::{provider_as_source, TableProvider};
+use crate::execution::context::SessionState;
+
+/// The statistics computed by [`compute_table_statistics`] for the tables of
+/// a session, which the physical planner reports for the scans of the tables
+/// so that they are available to the cost based optimizer rules.
+///
+/// The tables are identified by their [`TableProvider`] rather than by name,
+/// so their registration is left unchanged, and the statistics of a table
+/// are forgotten once it is dropped.
+#[derive(Clone, Default)]
+pub struct AnalyzedTables {
+    /// The statistics of each table, by the address of its provider
+    tables: HashMap<usize, (Weak<dyn TableProvider>, Statistics)>,
+}
+
+impl AnalyzedTables {
+    /// Stores the `statistics` of the whole `table`, replacing any previous
+    /// statistics of it
+    pub fn insert(
+        &mut self,
+        table: &Arc<dyn TableProvider>,
+        statistics: Statistics,
+    ) -> Result<()> {
+        if statistics.column_statistics.len() != table.schema().fields().len() {
+            return Err(DataFusionError::Internal(format!(
+                "Expected statistics for {} columns, got {}",
+                table.schema().fields().len(),
+                statistics.column_statistics.len()
+            )));
+        }
+        self.tables.retain(|_, (table, _)| table.strong_count() > 0);
+        self.tables
+            .insert(table_key(table), (Arc::downgrade(table), statistics));
+        Ok(())
+    }
+
+    /// The statistics of `table`, if it was analyzed
+    pub fn get(&self, table: &Arc<dyn TableProvider>) -> Option<&Statistics> {
+        // the weak reference keeps the address of a dropped table from being
+        // reused by another one
+        self.tables
+            .get(&table_key(table))
+            .filter(|(table, _)| table.strong_count() > 0)
+            .map(|(_, statistics)| statistics)
+    }
+}
+
+fn table_key(table: &Arc<dyn TableProvider>) -> usize {
+    Arc::as_ptr(table) as *const () as usize
+}
+
+/// Wraps the `plan` scanning `table` in a [`CachedStatisticsExec`] reporting
+/// the `statistics` computed for the whole table, unless the scan reduces
+/// the rows.
+///
+/// The statistics are marked as inexact, as the underlying data may change
+/// after they were computed. The scan reports them for any value it can not
+/// provide exactly.
+pub(crate) fn with_cached_statistics(
+    plan: Arc<dyn ExecutionPlan>,
+    table: &dyn TableProvider,
+    statistics: &Statistics,
+    projection: Option<&Vec<usize>>,
+    filters: &[Expr],
+    limit: Option<usize>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    // the cached statistics describe the whole table, which is no longer
+    // what the scan returns if it applies a limit or filters on its own
+    let reduces_rows = limit.is_some()
+        || table
+            .supports_filters_pushdown(&filters.iter().collect::<Vec<_>>())?
+            .contains(&TableProviderFilterPushDown::Exact);
+    if reduces_rows {
+        return Ok(plan);
+    }
+
+    let column_statistics = match projection {
+        Some(projection) => projection
+            .iter()
+            .map(|i| statistics.column_statistics[*i].clone())
+            .collect(),
+        None => statistics.column_statistics.clone(),
+    };
+    let statistics = Statistics {
+        column_statistics,
+        ..statistics.clone()
+    };
+    Ok(Arc::new(CachedStatisticsExec::new(plan, statistics)))
+}
+
+/// Computes the statistics of `table` by scanning it: the number of rows and,
+/// for each of the given columns (all columns if `None`), the number of nulls,
+/// the minimum and maximum values and an estimate of the number of distinct
+/// values. The statistics of the other columns are unknown.
+pub async fn compute_table_statistics(
+    state: &SessionState,
+    table: Arc<dyn TableProvider>,
+    columns: Option<&[String]>,
+) -> Result<Statistics> {
+    let schema = table.schema();
+    let mut indices: Vec<usize> = vec![];
+    match columns {
+        Some(columns) => {
+            for name in columns {
+                let index = schema.index_of(name)?;
+                if !indices.contains(&index) {
+                    indices.push(index);
+                }
+            }
+        }
+        None => indices.extend(0..schema.fields().len()),
+    }
+
+    // a single aggregation computes all the statistics; `outputs` records the
+    // position of each statistic of the analyzed columns in its output
+    let mut aggr_expr = vec![count(Expr::Literal(COUNT_STAR_EXPANSION))];
+    let mut outputs = vec![];
+    for &index in &indices {
+        let field = schema.field(index);
+        let column = Expr::Column(Column::from_name(field.name()));
+        let mut push = |expr: Expr| {
+            aggr_expr.push(expr);
+            aggr_expr.len() - 1
+        };
+
+        let non_null = push(count(column.clone()));
+        let (min_value, max_value) = if supports_min_max(field.data_type()) {
+            (
+                Some(push(min(column.clone()))),
+                Some(push(max(column.clone()))),
+            )
+        } else {
+            (None, None)
+        };
+        let distinct_count = if supports_approx_distinct(field.data_type()) {
+            Some(push(approx_distinct(column)))
+        } else {
+            None
+        };
+        outputs.push((index, non_null, min_value, max_value, distinct_count));
+    }
+
+    let plan = LogicalPlanBuilder::scan(UNNAMED_TABLE, provider_as_source(table), None)?
+        .aggregate(Vec::<Expr>::new(), aggr_expr)?
+        .build()?;
+    let batches = DataFrame::new(state.clone(), plan).collect().await?;
+    let batch = batches
+        .iter()
+        .find(|batch| batch.num_rows() > 0)
+        .ok_or_else(|| {
+            DataFusionError::Internal(
+                "Table statistics aggregation returned no rows".to_string(),
+            )
+        })?;
+    let value = |i: usize| ScalarValue::try_from_array(batch.column(i), 0);
+    let count_value = |i: usize| -> Result<usize> {
+        match value(i)? {
+            ScalarValue::Int64(Some(count)) => Ok(count as usize),
+            ScalarValue::UInt64(Some(count)) => Ok(count as usize),
+            other => Err(DataFusionError::Internal(format!(
+                "Unexpected count in table statistics: {other:?}"
+            ))),
+        }
+    };
+    let bound_value = |i: Option<usize>| -> Result<Precision<ScalarValue>> {
+        Ok(match i.map(value).transpose()? {
+            Some(v) if !v.is_null() => Precision::Inexact(v),
+            _ => Precision::Absent,
+        })
+    };
+
+    let num_rows = count_value(0)?;
+    let mut column_statistics =
+        vec![ColumnStatistics::new_unknown(); schema.fields().len()];
+    for (index, non_null, min_value, max_value, distinct_count) in outputs {
+        column_statistics[index] = ColumnStatistics {
+            null_count: Precision::Inexact(num_rows - count_value(non_null)?),
+            max_value: bound_value(max_value)?,
+            min_value: bound_value(min_value)?,
+            distinct_count: match distinct_count {
+                Some(i) => Precision::Inexact(count_value(i)?),
+                None => Precision::Absent,
+            },
+        };
+    }
+
+    Ok(Statistics {
+        num_rows: Precision::Inexact(num_rows),
+        total_byte_size: Precision::Absent,
+        column_statistics,
+    })
+}
+
+fn supports_min_max(data_type: &DataType) -> bool {
+    STRINGS
+        .iter()
+        .chain(NUMERICS.iter())
+        .chain(TIMESTAMPS.iter())
+        .chain(DATES.iter())
+        .chain(TIMES.iter())
+        .any(|t| t == data_type)
+        || matches!(data_type, DataType::Decimal128(_, _))
+}
+
+/// The types `approx_distinct` has a sketch for
+fn supports_approx_distinct(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Binary
+            | DataType::LargeBinary
+    )
+}
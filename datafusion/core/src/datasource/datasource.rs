@@ -18,6 +18,7 @@
 //! Data source traits
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -54,6 +55,15 @@ pub trait TableProvider: Sync + Send {
         None
     }
 
+    /// Get the expressions, by column name, replacing the values of the
+    /// masked columns of this table, for example to hash e-mail addresses or
+    /// truncate timestamps. Masks may refer to any column of the table and
+    /// are applied over the scan before any other expression of the query
+    /// sees the data.
+    fn column_masks(&self) -> HashMap<String, Expr> {
+        HashMap::new()
+    }
+
     /// Create an ExecutionPlan that will scan the table.
     /// The table provider will be usually responsible of grouping
     /// the source data into partitions that can be efficiently
@@ -70,6 +80,32 @@ pub trait TableProvider: Sync + Send {
         limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>>;
 
+    /// Create an ExecutionPlan that will scan the table, returning its rows
+    /// in the requested `ordering`, a list of [`Expr::Sort`].
+    ///
+    /// This is only called with a prefix of one of the orderings returned by
+    /// [`TableProvider::output_orderings`]. The planner removes sorts that
+    /// are satisfied by the `output_ordering` of the returned plan, so a
+    /// provider that does not report the ordering there simply keeps them.
+    /// The default implementation ignores the ordering and calls `scan`.
+    async fn scan_ordered(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        _ordering: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.scan(state, projection, filters, limit).await
+    }
+
+    /// Get the orderings, each a list of [`Expr::Sort`] over the table's
+    /// columns, in which [`TableProvider::scan_ordered`] can return the data
+    /// of this table
+    fn output_orderings(&self) -> Vec<Vec<Expr>> {
+        vec![]
+    }
+
     /// Tests whether the table provider can make use of a filter expression
     /// to optimise data retrieval.
     fn supports_filter_pushdown(
@@ -79,10 +115,54 @@ pub trait TableProvider: Sync + Send {
         Ok(TableProviderFilterPushDown::Unsupported)
     }
 
+    /// Tests whether the table provider can make use of each of the `filters`,
+    /// the conjuncts of a predicate, to optimise data retrieval. Returns one
+    /// result per filter, in the same order.
+    ///
+    /// Providers that can only handle some combinations of filters exactly,
+    /// such as remote databases or index backed stores, can decide on all of
+    /// them at once. The default implementation calls
+    /// [`TableProvider::supports_filter_pushdown`] for each filter.
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        filters
+            .iter()
+            .map(|filter| self.supports_filter_pushdown(filter))
+            .collect()
+    }
+
+    /// Returns, for each of the `filters` reported as
+    /// [`TableProviderFilterPushDown::Inexact`], the residual predicate that
+    /// still has to be evaluated on the rows returned by `scan`, or `None` if
+    /// the whole filter has to be evaluated again.
+    ///
+    /// On the rows returned by a scan the filter was pushed down to, the
+    /// residual predicate must be equivalent to the filter. For example, a
+    /// store that looks up `s LIKE 'abc%def'` in an index on the prefix of `s`
+    /// can return `s LIKE '%def'`.
+    fn residual_filters(&self, filters: &[&Expr]) -> Result<Vec<Option<Expr>>> {
+        Ok(vec![None; filters.len()])
+    }
+
     /// Get statistics for this table, if available
     fn statistics(&self) -> Option<Statistics> {
         None
     }
+
+    /// Get the version of the current contents of this table, if known.
+    ///
+    /// The version must change whenever the contents of the table change, and
+    /// must not be reused by another table registered under the same name, as
+    /// the [`ResultCache`] returns the same results for the queries over the
+    /// same versions of their tables. The results of queries over tables
+    /// without a version are not cached.
+    ///
+    /// [`ResultCache`]: crate::execution::result_cache::ResultCache
+    fn snapshot_version(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
@@ -22,6 +22,7 @@ use arrow::datatypes::SchemaRef;
 use datafusion_common::DataFusionError;
 use datafusion_expr::{Expr, TableProviderFilterPushDown, TableSource};
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// DataFusion default table source, wrapping TableProvider
@@ -61,9 +62,34 @@ impl TableSource for DefaultTableSource {
         self.table_provider.supports_filter_pushdown(filter)
     }
 
+    /// Tests whether the table provider can make use of each of the filter
+    /// expressions to optimise data retrieval.
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> datafusion_common::Result<Vec<TableProviderFilterPushDown>> {
+        self.table_provider.supports_filters_pushdown(filters)
+    }
+
+    /// Get the residual predicates of the inexactly pushed down filters
+    fn residual_filters(
+        &self,
+        filters: &[&Expr],
+    ) -> datafusion_common::Result<Vec<Option<Expr>>> {
+        self.table_provider.residual_filters(filters)
+    }
+
     fn get_logical_plan(&self) -> Option<&datafusion_expr::LogicalPlan> {
         self.table_provider.get_logical_plan()
     }
+
+    fn column_masks(&self) -> HashMap<String, Expr> {
+        self.table_provider.column_masks()
+    }
+
+    fn output_orderings(&self) -> Vec<Vec<Expr>> {
+        self.table_provider.output_orderings()
+    }
 }
 
 /// Wrap TableProvider in TableSource
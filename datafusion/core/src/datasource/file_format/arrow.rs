@@ -0,0 +1,183 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Apache Arrow IPC file format abstractions
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::Schema;
+use arrow::ipc::convert::fb_to_schema;
+use arrow::ipc::root_as_footer;
+use arrow::{self, datatypes::SchemaRef};
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+use object_store::{ObjectMeta, ObjectStore};
+
+use super::FileFormat;
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::SessionState;
+use crate::logical_expr::Expr;
+use crate::physical_plan::file_format::{ArrowExec, FileScanConfig};
+use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::Statistics;
+
+/// The default file extension of arrow files
+pub const DEFAULT_ARROW_EXTENSION: &str = ".arrow";
+
+/// The magic bytes ending an Arrow IPC file
+const ARROW_MAGIC: &[u8] = b"ARROW1";
+
+/// Arrow IPC file `FileFormat` implementation, also known as Feather V2.
+#[derive(Default, Debug)]
+pub struct ArrowFormat;
+
+#[async_trait]
+impl FileFormat for ArrowFormat {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn infer_schema(
+        &self,
+        state: &SessionState,
+        store: &Arc<dyn ObjectStore>,
+        objects: &[ObjectMeta],
+    ) -> Result<SchemaRef> {
+        let concurrency = state.config_options().execution.meta_fetch_concurrency;
+        let schemas: Vec<_> = futures::stream::iter(objects)
+            .map(|object| fetch_schema(store.as_ref(), object))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+        let merged_schema = Schema::try_merge(schemas)?;
+        Ok(Arc::new(merged_schema))
+    }
+
+    async fn infer_stats(
+        &self,
+        _state: &SessionState,
+        _store: &Arc<dyn ObjectStore>,
+        table_schema: SchemaRef,
+        _object: &ObjectMeta,
+    ) -> Result<Statistics> {
+        Ok(Statistics::new_unknown(&table_schema))
+    }
+
+    async fn create_physical_plan(
+        &self,
+        _state: &SessionState,
+        conf: FileScanConfig,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let exec = ArrowExec::new(conf);
+        Ok(Arc::new(exec))
+    }
+}
+
+/// Reads the schema of an Arrow IPC file from its footer, fetching only the end
+/// of the file
+async fn fetch_schema(store: &dyn ObjectStore, object: &ObjectMeta) -> Result<Schema> {
+    let invalid = || {
+        DataFusionError::Execution(format!(
+            "{} is not an Arrow IPC file",
+            object.location
+        ))
+    };
+
+    // the footer is followed by its length and the magic bytes
+    let trailer_len = 4 + ARROW_MAGIC.len();
+    if object.size < trailer_len {
+        return Err(invalid());
+    }
+    let trailer = store
+        .get_range(&object.location, object.size - trailer_len..object.size)
+        .await?;
+    if &trailer[4..] != ARROW_MAGIC {
+        return Err(invalid());
+    }
+    let footer_len = i32::from_le_bytes(trailer[..4].try_into().unwrap());
+    let footer_end = object.size - trailer_len;
+    let footer_start = usize::try_from(footer_len)
+        .ok()
+        .and_then(|len| footer_end.checked_sub(len))
+        .ok_or_else(invalid)?;
+
+    let footer = store
+        .get_range(&object.location, footer_start..footer_end)
+        .await?;
+    let footer = root_as_footer(&footer).map_err(|e| {
+        DataFusionError::Execution(format!(
+            "Invalid footer of Arrow IPC file {}: {e}",
+            object.location
+        ))
+    })?;
+    footer.schema().map(fb_to_schema).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_slice::FromSlice;
+    use crate::prelude::SessionContext;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field};
+    use arrow::ipc::writer::FileWriter;
+    use arrow::record_batch::RecordBatch;
+    use bytes::Bytes;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+
+    #[tokio::test]
+    async fn infer_schema_from_footer() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice([1, 2])),
+                Arc::new(StringArray::from(vec![Some("x"), None])),
+            ],
+        )?;
+        let mut writer = FileWriter::try_new(vec![], &schema)?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        let data = writer.into_inner()?;
+
+        let store = Arc::new(InMemory::new()) as Arc<dyn ObjectStore>;
+        let location = Path::from("file.arrow");
+        store.put(&location, Bytes::from(data)).await?;
+        let object = store.head(&location).await?;
+
+        let inferred = ArrowFormat.infer_schema(&state, &store, &[object]).await?;
+        assert_eq!(inferred, schema);
+
+        let location = Path::from("file.csv");
+        store.put(&location, Bytes::from("a,b\n1,2\n")).await?;
+        let object = store.head(&location).await?;
+        let err = ArrowFormat
+            .infer_schema(&state, &store, &[object])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not an Arrow IPC file"));
+        Ok(())
+    }
+}
@@ -23,6 +23,7 @@ use std::sync::Arc;
 use arrow::datatypes::Schema;
 use arrow::{self, datatypes::SchemaRef};
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
 use object_store::{GetResult, ObjectMeta, ObjectStore};
 
 use super::FileFormat;
@@ -48,22 +49,16 @@ impl FileFormat for AvroFormat {
 
     async fn infer_schema(
         &self,
-        _state: &SessionState,
+        state: &SessionState,
         store: &Arc<dyn ObjectStore>,
         objects: &[ObjectMeta],
     ) -> Result<SchemaRef> {
-        let mut schemas = vec![];
-        for object in objects {
-            let schema = match store.get(&object.location).await? {
-                GetResult::File(mut file, _) => read_avro_schema_from_reader(&mut file)?,
-                r @ GetResult::Stream(_) => {
-                    // TODO: Fetching entire file to get schema is potentially wasteful
-                    let data = r.bytes().await?;
-                    read_avro_schema_from_reader(&mut data.as_ref())?
-                }
-            };
-            schemas.push(schema);
-        }
+        let concurrency = state.config_options().execution.meta_fetch_concurrency;
+        let schemas: Vec<_> = futures::stream::iter(objects)
+            .map(|object| fetch_schema(store.as_ref(), object))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
         let merged_schema = Schema::try_merge(schemas)?;
         Ok(Arc::new(merged_schema))
     }
@@ -72,10 +67,10 @@ impl FileFormat for AvroFormat {
         &self,
         _state: &SessionState,
         _store: &Arc<dyn ObjectStore>,
-        _table_schema: SchemaRef,
+        table_schema: SchemaRef,
         _object: &ObjectMeta,
     ) -> Result<Statistics> {
-        Ok(Statistics::default())
+        Ok(Statistics::new_unknown(&table_schema))
     }
 
     async fn create_physical_plan(
@@ -89,6 +84,19 @@ impl FileFormat for AvroFormat {
     }
 }
 
+/// Reads the schema of the Avro file `object`
+async fn fetch_schema(store: &dyn ObjectStore, object: &ObjectMeta) -> Result<Schema> {
+    let schema = match store.get(&object.location).await? {
+        GetResult::File(mut file, _) => read_avro_schema_from_reader(&mut file)?,
+        r @ GetResult::Stream(_) => {
+            // TODO: Fetching entire file to get schema is potentially wasteful
+            let data = r.bytes().await?;
+            read_avro_schema_from_reader(&mut data.as_ref())?
+        }
+    };
+    Ok(schema)
+}
+
 #[cfg(test)]
 #[cfg(feature = "avro")]
 mod tests {
@@ -0,0 +1,323 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reading the byte ranges of blocked gzip (BGZF) files
+//!
+//! A BGZF file is a series of gzip members, the blocks, each compressing up
+//! to 64KB and recording its compressed size in the `BC` subfield of its gzip
+//! header. As any gzip file it can be decompressed as a whole, but it can also
+//! be decompressed from the start of any block, which is found by scanning for
+//! the headers of the blocks.
+
+use std::io::{Read, Write};
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use flate2::read::GzDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+use crate::error::{DataFusionError, Result};
+
+/// The maximum size of a block, compressed or not
+const MAX_BLOCK_SIZE: usize = 64 * 1024;
+
+/// The size of the uncompressed data of the blocks written by [`BgzfEncoder`],
+/// leaving room for incompressible data
+const BLOCK_DATA_SIZE: usize = 0xff00;
+
+/// The number of bytes fetched at once from the object store
+const FETCH_SIZE: usize = 1024 * 1024;
+
+/// Returns the size of the block starting at `buf`, or `None` if `buf` doesn't
+/// start with the header of a block
+fn block_size(buf: &[u8]) -> Option<usize> {
+    // gzip magic, deflate method, and the FEXTRA flag
+    if buf.len() < 12 || buf[..4] != [0x1f, 0x8b, 0x08, 0x04] {
+        return None;
+    }
+    let extra_len = u16::from_le_bytes([buf[10], buf[11]]) as usize;
+    let mut extra = buf.get(12..12 + extra_len)?;
+    // find the BC subfield holding the size of the block minus 1
+    while extra.len() >= 4 {
+        let len = u16::from_le_bytes([extra[2], extra[3]]) as usize;
+        if extra[..2] == [b'B', b'C'] && len == 2 {
+            let size = u16::from_le_bytes([*extra.get(4)?, *extra.get(5)?]) as usize;
+            return Some(size + 1);
+        }
+        extra = extra.get(4 + len..)?;
+    }
+    None
+}
+
+fn decompress(block: &[u8]) -> Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(MAX_BLOCK_SIZE);
+    GzDecoder::new(block).read_to_end(&mut data)?;
+    Ok(data)
+}
+
+fn corrupted(location: &Path, offset: usize) -> DataFusionError {
+    DataFusionError::Execution(format!(
+        "Invalid BGZF block at offset {offset} of {location}"
+    ))
+}
+
+/// Reads the rows of a byte range of a BGZF file.
+///
+/// The range owns the blocks starting in it, and the rows following each line
+/// break of these blocks, or the first row of the file for the range starting at
+/// 0. The rows owned by consecutive ranges are thus disjoint and cover the
+/// whole file: the bytes of the first block of a range up to its first line
+/// break belong to the previous range, and the last row of a range is read to
+/// its end from the blocks of the next ranges.
+struct RangeReader {
+    store: Arc<dyn ObjectStore>,
+    location: Path,
+    file_size: usize,
+    /// The end of the range
+    end: usize,
+    /// The offset in the file of the start of `buffer`
+    offset: usize,
+    /// The bytes fetched from the file and not yet decompressed
+    buffer: BytesMut,
+    /// True once a line break of the range has been read
+    emitting: bool,
+    done: bool,
+}
+
+impl RangeReader {
+    async fn try_new(
+        store: Arc<dyn ObjectStore>,
+        location: Path,
+        file_size: usize,
+        range: Range<usize>,
+    ) -> Result<Self> {
+        let mut reader = Self {
+            store,
+            location,
+            file_size,
+            end: range.end.min(file_size),
+            offset: range.start,
+            buffer: BytesMut::new(),
+            emitting: range.start == 0,
+            done: range.start >= range.end.min(file_size),
+        };
+        if reader.emitting || reader.done {
+            return Ok(reader);
+        }
+
+        // find the first block starting in the range, in the next
+        // MAX_BLOCK_SIZE bytes, checking that it is followed by another block
+        reader.fill(2 * MAX_BLOCK_SIZE).await?;
+        let start = (0..reader.buffer.len().min(MAX_BLOCK_SIZE)).find(|&i| {
+            match block_size(&reader.buffer[i..]) {
+                Some(size) if range.start + i + size >= reader.file_size => true,
+                Some(size) if i + size + 12 > reader.buffer.len() => true,
+                Some(size) => block_size(&reader.buffer[i + size..]).is_some(),
+                None => false,
+            }
+        });
+        match start {
+            Some(start) if range.start + start < reader.end => {
+                let _ = reader.buffer.split_to(start);
+                reader.offset += start;
+            }
+            _ => reader.done = true,
+        }
+        Ok(reader)
+    }
+
+    /// Fetches bytes of the file until at least `len` bytes are buffered, or the
+    /// end of the file
+    async fn fill(&mut self, len: usize) -> Result<()> {
+        while self.buffer.len() < len {
+            let start = self.offset + self.buffer.len();
+            if start >= self.file_size {
+                break;
+            }
+            let end = (start + FETCH_SIZE.max(len)).min(self.file_size);
+            let bytes = self.store.get_range(&self.location, start..end).await?;
+            self.buffer.extend_from_slice(&bytes);
+        }
+        Ok(())
+    }
+
+    /// Returns the next decompressed bytes of the rows of the range
+    async fn next_bytes(&mut self) -> Result<Option<Bytes>> {
+        while !self.done {
+            self.fill(MAX_BLOCK_SIZE).await?;
+            if self.buffer.is_empty() {
+                break;
+            }
+            let size = block_size(&self.buffer)
+                .filter(|size| *size <= self.buffer.len())
+                .ok_or_else(|| corrupted(&self.location, self.offset))?;
+            let block_offset = self.offset;
+            let block = self.buffer.split_to(size);
+            self.offset += size;
+            let data = Bytes::from(
+                decompress(&block)
+                    .map_err(|_| corrupted(&self.location, block_offset))?,
+            );
+            let line_break = data.iter().position(|b| *b == b'\n');
+
+            let bytes = match (block_offset < self.end, self.emitting, line_break) {
+                // a block of the range
+                (true, true, _) => data,
+                (true, false, Some(i)) => {
+                    self.emitting = true;
+                    data.slice(i + 1..)
+                }
+                (true, false, None) => continue,
+                // a block after the range, holding the end of its last row
+                (false, true, Some(i)) => {
+                    self.done = true;
+                    data.slice(..i + 1)
+                }
+                (false, true, None) => data,
+                (false, false, _) => break,
+            };
+            if !bytes.is_empty() {
+                return Ok(Some(bytes));
+            }
+        }
+        self.done = true;
+        Ok(None)
+    }
+}
+
+/// Returns a stream of the decompressed rows of the byte `range` of the BGZF
+/// file at `location`, of `file_size` bytes, see [`RangeReader`]
+pub(crate) fn range_stream(
+    store: Arc<dyn ObjectStore>,
+    location: Path,
+    file_size: usize,
+    range: Range<usize>,
+) -> BoxStream<'static, Result<Bytes>> {
+    futures::stream::once(RangeReader::try_new(store, location, file_size, range))
+        .map_ok(|reader| {
+            futures::stream::try_unfold(reader, |mut reader| async move {
+                Ok(reader.next_bytes().await?.map(|bytes| (bytes, reader)))
+            })
+        })
+        .try_flatten()
+        .boxed()
+}
+
+/// A writer compressing its data into BGZF blocks. The data buffered is written
+/// in a block on each flush.
+pub(crate) struct BgzfEncoder<W: Write> {
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> BgzfEncoder<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: Vec::with_capacity(BLOCK_DATA_SIZE),
+        }
+    }
+
+    fn write_block(&mut self) -> std::io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let mut encoder = DeflateEncoder::new(vec![], Compression::default());
+        encoder.write_all(&self.buffer)?;
+        let compressed = encoder.finish()?;
+        let mut crc = Crc::new();
+        crc.update(&self.buffer);
+
+        let size = (18 + compressed.len() + 8 - 1) as u16;
+        self.inner
+            .write_all(&[0x1f, 0x8b, 0x08, 0x04, 0, 0, 0, 0, 0, 0xff])?;
+        self.inner.write_all(&6u16.to_le_bytes())?;
+        self.inner.write_all(&[b'B', b'C'])?;
+        self.inner.write_all(&2u16.to_le_bytes())?;
+        self.inner.write_all(&size.to_le_bytes())?;
+        self.inner.write_all(&compressed)?;
+        self.inner.write_all(&crc.sum().to_le_bytes())?;
+        self.inner
+            .write_all(&(self.buffer.len() as u32).to_le_bytes())?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for BgzfEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(BLOCK_DATA_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        if self.buffer.len() == BLOCK_DATA_SIZE {
+            self.write_block()?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_block()?;
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn read_ranges() -> Result<()> {
+        let rows: Vec<String> = (0..20000).map(|i| format!("{i},row {i}\n")).collect();
+        let data = rows.concat();
+        let mut encoder = BgzfEncoder::new(vec![]);
+        // blocks ending with and within rows
+        for chunk in data.as_bytes().chunks(7000) {
+            encoder.write_all(chunk)?;
+            encoder.flush()?;
+        }
+        let file = Bytes::from(encoder.inner);
+        assert_eq!(decompress(&file)?.len(), 7000);
+
+        let store = Arc::new(InMemory::new());
+        let location = Path::from("file.csv.gz");
+        store.put(&location, file.clone()).await?;
+
+        for num_ranges in [1, 2, 3, 10, 1000] {
+            let range_size = (file.len() + num_ranges - 1) / num_ranges;
+            let mut read = vec![];
+            for start in (0..file.len()).step_by(range_size) {
+                let bytes: Vec<Bytes> = range_stream(
+                    store.clone(),
+                    location.clone(),
+                    file.len(),
+                    start..start + range_size,
+                )
+                .try_collect()
+                .await?;
+                read.extend(bytes.concat());
+            }
+            assert_eq!(String::from_utf8(read).unwrap(), data);
+        }
+        Ok(())
+    }
+}
@@ -19,13 +19,14 @@
 
 use std::any::Any;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use arrow::datatypes::{DataType, Field, Schema};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use arrow::{self, datatypes::SchemaRef};
 use async_trait::async_trait;
 use bytes::{Buf, Bytes};
+use chrono::NaiveDateTime;
 
 use datafusion_common::DataFusionError;
 
@@ -53,6 +54,9 @@ pub struct CsvFormat {
     delimiter: u8,
     schema_infer_max_rec: Option<usize>,
     file_compression_type: FileCompressionType,
+    null_markers: Vec<String>,
+    datetime_format: Option<String>,
+    schema_overrides: HashMap<String, DataType>,
 }
 
 impl Default for CsvFormat {
@@ -62,6 +66,9 @@ impl Default for CsvFormat {
             has_header: true,
             delimiter: b',',
             file_compression_type: FileCompressionType::UNCOMPRESSED,
+            null_markers: vec![],
+            datetime_format: None,
+            schema_overrides: HashMap::new(),
         }
     }
 }
@@ -107,6 +114,51 @@ impl CsvFormat {
     pub fn delimiter(&self) -> u8 {
         self.delimiter
     }
+
+    /// Set the values read as nulls, in addition to the empty values, for
+    /// example `NA` or `\N`
+    /// - defaults to none
+    pub fn with_null_markers(mut self, null_markers: Vec<String>) -> Self {
+        self.null_markers = null_markers;
+        self
+    }
+
+    /// The values read as nulls, in addition to the empty values.
+    pub fn null_markers(&self) -> &[String] {
+        &self.null_markers
+    }
+
+    /// Set the `chrono` format of the timestamps, for example
+    /// `%d/%m/%Y %H:%M:%S`. The columns of values in this format are inferred as
+    /// timestamps, instead of the columns of ISO 8601 timestamps.
+    /// - defaults to `None`, detecting the ISO 8601 timestamps
+    pub fn with_datetime_format(mut self, datetime_format: Option<String>) -> Self {
+        self.datetime_format = datetime_format;
+        self
+    }
+
+    /// The `chrono` format of the timestamps, if any.
+    pub fn datetime_format(&self) -> Option<&str> {
+        self.datetime_format.as_deref()
+    }
+
+    /// Set the types of columns, by name, overriding their inferred types
+    /// - defaults to none
+    pub fn with_schema_overrides(
+        mut self,
+        schema_overrides: HashMap<String, DataType>,
+    ) -> Self {
+        self.schema_overrides = schema_overrides;
+        self
+    }
+
+    /// The type of the value of a field, or `None` for nulls
+    fn infer_type(&self, value: &str) -> Option<DataType> {
+        if value.is_empty() || self.null_markers.iter().any(|marker| marker == value) {
+            return None;
+        }
+        Some(infer_value_type(value, self.datetime_format.as_deref()))
+    }
 }
 
 #[async_trait]
@@ -138,18 +190,46 @@ impl FileFormat for CsvFormat {
             }
         }
 
-        let merged_schema = Schema::try_merge(schemas)?;
-        Ok(Arc::new(merged_schema))
+        // merge the columns of the files by name, promoting the types of the
+        // columns whose values have different types in different files
+        let mut names: Vec<String> = vec![];
+        let mut types: Vec<DataType> = vec![];
+        for schema in schemas {
+            for field in schema.fields() {
+                match names.iter().position(|name| name == field.name()) {
+                    Some(i) => types[i] = promote_types(&types[i], field.data_type()),
+                    None => {
+                        names.push(field.name().clone());
+                        types.push(field.data_type().clone());
+                    }
+                }
+            }
+        }
+
+        let fields = names
+            .into_iter()
+            .zip(types)
+            .map(|(name, data_type)| {
+                let data_type = match self.schema_overrides.get(&name) {
+                    Some(data_type) => data_type.clone(),
+                    // no values of the column were read
+                    None if data_type == DataType::Null => DataType::Utf8,
+                    None => data_type,
+                };
+                Field::new(name, data_type, true)
+            })
+            .collect();
+        Ok(Arc::new(Schema::new(fields)))
     }
 
     async fn infer_stats(
         &self,
         _state: &SessionState,
         _store: &Arc<dyn ObjectStore>,
-        _table_schema: SchemaRef,
+        table_schema: SchemaRef,
         _object: &ObjectMeta,
     ) -> Result<Statistics> {
-        Ok(Statistics::default())
+        Ok(Statistics::new_unknown(&table_schema))
     }
 
     async fn create_physical_plan(
@@ -163,9 +243,15 @@ impl FileFormat for CsvFormat {
             self.has_header,
             self.delimiter,
             self.file_compression_type.to_owned(),
-        );
+        )
+        .with_null_markers(self.null_markers.clone())
+        .with_datetime_format(self.datetime_format.clone());
         Ok(Arc::new(exec))
     }
+
+    fn supports_file_ranges(&self) -> bool {
+        self.file_compression_type.is_splittable()
+    }
 }
 
 /// Return a newline delimited stream from the specified file on
@@ -203,54 +289,60 @@ impl CsvFormat {
     ) -> Result<(Schema, usize)> {
         let mut total_records_read = 0;
         let mut column_names = vec![];
-        let mut column_type_possibilities = vec![];
-        let mut first_chunk = true;
+        let mut column_type_possibilities: Vec<HashSet<DataType>> = vec![];
+        let mut first_record = true;
 
         pin_mut!(stream);
 
         while let Some(chunk) = stream.next().await.transpose()? {
-            let (Schema { fields, .. }, records_read) =
-                arrow::csv::reader::infer_reader_schema(
-                    self.file_compression_type.convert_read(chunk.reader())?,
-                    self.delimiter,
-                    Some(records_to_read),
-                    // only consider header for first chunk
-                    self.has_header && first_chunk,
-                )?;
-            records_to_read -= records_read;
-            total_records_read += records_read;
-
-            if first_chunk {
-                // set up initial structures for recording inferred schema across chunks
-                (column_names, column_type_possibilities) = fields
-                    .into_iter()
-                    .map(|field| {
-                        let mut possibilities = HashSet::new();
-                        if records_read > 0 {
-                            // at least 1 data row read, record the inferred datatype
-                            possibilities.insert(field.data_type().clone());
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .delimiter(self.delimiter)
+                .flexible(true)
+                .from_reader(self.file_compression_type.convert_read(chunk.reader())?);
+
+            for record in reader.records() {
+                let record =
+                    record.map_err(|e| DataFusionError::External(Box::new(e)))?;
+                if first_record {
+                    // set up initial structures for recording inferred schema across chunks
+                    first_record = false;
+                    column_names = match self.has_header {
+                        true => record.iter().map(|name| name.to_string()).collect(),
+                        false => {
+                            (1..=record.len()).map(|i| format!("column_{i}")).collect()
                         }
-                        (field.name().clone(), possibilities)
-                    })
-                    .unzip();
-                first_chunk = false;
-            } else {
-                if fields.len() != column_type_possibilities.len() {
+                    };
+                    column_type_possibilities = vec![HashSet::new(); record.len()];
+                    if self.has_header {
+                        continue;
+                    }
+                }
+                if records_to_read == 0 {
+                    break;
+                }
+
+                if record.len() != column_type_possibilities.len() {
                     return Err(DataFusionError::Execution(
                         format!(
                             "Encountered unequal lengths between records on CSV file whilst inferring schema. \
                              Expected {} records, found {} records",
                             column_type_possibilities.len(),
-                            fields.len()
+                            record.len()
                         )
                     ));
                 }
 
-                column_type_possibilities.iter_mut().zip(fields).for_each(
-                    |(possibilities, field)| {
-                        possibilities.insert(field.data_type().clone());
-                    },
-                );
+                column_type_possibilities
+                    .iter_mut()
+                    .zip(record.iter())
+                    .for_each(|(possibilities, value)| {
+                        if let Some(data_type) = self.infer_type(value) {
+                            possibilities.insert(data_type);
+                        }
+                    });
+                records_to_read -= 1;
+                total_records_read += 1;
             }
 
             if records_to_read == 0 {
@@ -268,44 +360,133 @@ fn build_schema_helper(names: Vec<String>, types: &[HashSet<DataType>]) -> Schem
         .into_iter()
         .zip(types)
         .map(|(field_name, data_type_possibilities)| {
-            // ripped from arrow::csv::reader::infer_reader_schema_with_csv_options
-            // determine data type based on possible types
-            // if there are incompatible types, use DataType::Utf8
-            match data_type_possibilities.len() {
-                1 => Field::new(
-                    field_name,
-                    data_type_possibilities.iter().next().unwrap().clone(),
-                    true,
-                ),
-                2 => {
-                    if data_type_possibilities.contains(&DataType::Int64)
-                        && data_type_possibilities.contains(&DataType::Float64)
-                    {
-                        // we have an integer and double, fall down to double
-                        Field::new(field_name, DataType::Float64, true)
-                    } else {
-                        // default to Utf8 for conflicting datatypes (e.g bool and int)
-                        Field::new(field_name, DataType::Utf8, true)
-                    }
-                }
-                _ => Field::new(field_name, DataType::Utf8, true),
-            }
+            // the columns without non null values are `Null` until merged with
+            // the columns of the other files
+            let data_type = data_type_possibilities
+                .iter()
+                .fold(DataType::Null, |data_type, possibility| {
+                    promote_types(&data_type, possibility)
+                });
+            Field::new(field_name, data_type, true)
         })
         .collect();
     Schema::new(fields)
 }
 
+/// The type of the values of two types: integers and floats are promoted to
+/// floats, ISO 8601 timestamps with and without fractional seconds to
+/// timestamps, and the other conflicting types to `Utf8`
+fn promote_types(left: &DataType, right: &DataType) -> DataType {
+    match (left, right) {
+        (left, right) if left == right => left.clone(),
+        (DataType::Null, other) | (other, DataType::Null) => other.clone(),
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            DataType::Float64
+        }
+        (DataType::Date64, DataType::Timestamp(_, None))
+        | (DataType::Timestamp(_, None), DataType::Date64) => {
+            DataType::Timestamp(TimeUnit::Nanosecond, None)
+        }
+        _ => DataType::Utf8,
+    }
+}
+
+/// The type of a non null value, as detected by the arrow csv reader:
+/// booleans, integers, floats, ISO 8601 dates and timestamps, or the timestamps
+/// in `datetime_format` instead of the ISO 8601 timestamps if set
+fn infer_value_type(value: &str, datetime_format: Option<&str>) -> DataType {
+    if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+        return DataType::Boolean;
+    }
+    if is_integer(value) {
+        return DataType::Int64;
+    }
+    if is_float(value) {
+        return DataType::Float64;
+    }
+    if matches_pattern(value.as_bytes(), b"dddd-dd-dd") {
+        return DataType::Date32;
+    }
+    match datetime_format {
+        Some(format) if NaiveDateTime::parse_from_str(value, format).is_ok() => {
+            DataType::Timestamp(TimeUnit::Nanosecond, None)
+        }
+        Some(_) => DataType::Utf8,
+        None => {
+            let (datetime, fraction) = value.split_at(value.len().min(19));
+            if !matches_pattern(datetime.as_bytes(), b"dddd-dd-dd?dd:dd:dd") {
+                DataType::Utf8
+            } else if fraction.is_empty() {
+                DataType::Date64
+            } else if fraction.len() <= 10
+                && fraction.starts_with('.')
+                && is_digits(&fraction[1..])
+            {
+                DataType::Timestamp(TimeUnit::Nanosecond, None)
+            } else {
+                DataType::Utf8
+            }
+        }
+    }
+}
+
+/// Returns true if `value` matches `pattern`, where `d` matches a digit, `?`
+/// the separator `T` or ` ` of a date and a time, and the other characters
+/// themselves
+fn matches_pattern(value: &[u8], pattern: &[u8]) -> bool {
+    value.len() == pattern.len()
+        && value.iter().zip(pattern).all(|(c, p)| match p {
+            b'd' => c.is_ascii_digit(),
+            b'?' => *c == b'T' || *c == b' ',
+            p => c == p,
+        })
+}
+
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|c| c.is_ascii_digit())
+}
+
+/// Returns true for an optional `-` followed by digits
+fn is_integer(value: &str) -> bool {
+    is_digits(value.strip_prefix('-').unwrap_or(value))
+}
+
+/// Returns true for an optional `-` followed by a decimal number with a `.`, an
+/// exponent or both
+fn is_float(value: &str) -> bool {
+    let value = value.strip_prefix('-').unwrap_or(value);
+    let (mantissa, exponent) = match value.find(|c| c == 'e' || c == 'E') {
+        Some(i) => (&value[..i], Some(&value[i + 1..])),
+        None => (value, None),
+    };
+    if let Some(exponent) = exponent {
+        if !is_integer(exponent) {
+            return false;
+        }
+    }
+    match mantissa.split_once('.') {
+        Some((integer, fraction)) => {
+            (is_digits(integer) || integer.is_empty())
+                && (is_digits(fraction) || fraction.is_empty())
+                && !(integer.is_empty() && fraction.is_empty())
+        }
+        None => exponent.is_some() && is_digits(mantissa),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_util::scan_format;
     use super::*;
     use crate::datasource::file_format::test_util::VariableStream;
-    use crate::physical_plan::collect;
+    use crate::physical_plan::{collect, Precision};
+    use crate::prelude::CsvReadOptions;
     use crate::prelude::{SessionConfig, SessionContext};
     use bytes::Bytes;
     use chrono::DateTime;
     use datafusion_common::cast::as_string_array;
     use futures::StreamExt;
+    use object_store::memory::InMemory;
     use object_store::path::Path;
 
     #[tokio::test]
@@ -331,8 +512,8 @@ mod tests {
         assert_eq!(tt_batches, 50 /* 100/2 */);
 
         // test metadata
-        assert_eq!(exec.statistics().num_rows, None);
-        assert_eq!(exec.statistics().total_byte_size, None);
+        assert_eq!(exec.statistics().num_rows, Precision::Absent);
+        assert_eq!(exec.statistics().total_byte_size, Precision::Absent);
 
         Ok(())
     }
@@ -465,6 +646,93 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn infer_schema_across_files() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let store = Arc::new(InMemory::new()) as Arc<dyn ObjectStore>;
+        let files = [
+            "a,b,c,d,e,f\n1,x,NA,2020-01-01 10:00:00,2020-01-01,31/01/2020 10:00:00\n",
+            "a,b,c,d,e,f\n1.5,,3,2020-01-02T10:00:00.123,NA,NA\n",
+        ];
+        let mut objects = vec![];
+        for (i, file) in files.iter().enumerate() {
+            let location = Path::from(format!("{i}.csv"));
+            store.put(&location, Bytes::from(*file)).await?;
+            objects.push(store.head(&location).await?);
+        }
+
+        let types = |schema: SchemaRef| -> Vec<String> {
+            schema
+                .fields()
+                .iter()
+                .map(|f| format!("{}: {:?}", f.name(), f.data_type()))
+                .collect()
+        };
+
+        let format = CsvFormat::default().with_null_markers(vec!["NA".to_string()]);
+        let schema = format.infer_schema(&state, &store, &objects).await?;
+        assert_eq!(
+            types(schema),
+            vec![
+                "a: Float64",
+                "b: Utf8",
+                "c: Int64",
+                "d: Timestamp(Nanosecond, None)",
+                "e: Date32",
+                "f: Utf8"
+            ]
+        );
+
+        let format = format
+            .with_datetime_format(Some("%d/%m/%Y %H:%M:%S".to_string()))
+            .with_schema_overrides(HashMap::from([("c".to_string(), DataType::Int32)]));
+        let schema = format.infer_schema(&state, &store, &objects).await?;
+        assert_eq!(
+            types(schema),
+            vec![
+                "a: Float64",
+                "b: Utf8",
+                "c: Int32",
+                "d: Utf8",
+                "e: Date32",
+                "f: Timestamp(Nanosecond, None)"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_null_markers() -> Result<()> {
+        let ctx = SessionContext::new();
+        let dir = tempfile::TempDir::new()?;
+        let path = dir.path().join("nulls.csv");
+        std::fs::write(&path, "a,b\n1,x\nNA,NA\n\\N,\"\"\n")?;
+
+        let options =
+            CsvReadOptions::new().null_markers(vec!["NA".to_string(), "\\N".to_string()]);
+        let batches = ctx
+            .read_csv(path.to_str().unwrap(), options)
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | x |",
+            "|   |   |",
+            "|   |   |",
+            "+---+---+",
+        ];
+        crate::assert_batches_eq!(expected, &batches);
+        assert_eq!(batches[0].column(0).null_count(), 2);
+        assert_eq!(batches[0].column(1).null_count(), 1);
+
+        Ok(())
+    }
+
     async fn get_exec(
         state: &SessionState,
         file_name: &str,
@@ -19,6 +19,7 @@
 
 use crate::error::{DataFusionError, Result};
 
+use crate::datasource::file_format::arrow::DEFAULT_ARROW_EXTENSION;
 use crate::datasource::file_format::avro::DEFAULT_AVRO_EXTENSION;
 use crate::datasource::file_format::csv::DEFAULT_CSV_EXTENSION;
 use crate::datasource::file_format::json::DEFAULT_JSON_EXTENSION;
@@ -26,22 +27,29 @@ use crate::datasource::file_format::parquet::DEFAULT_PARQUET_EXTENSION;
 #[cfg(feature = "compression")]
 use async_compression::tokio::bufread::{
     BzDecoder as AsyncBzDecoder, GzipDecoder as AsyncGzDecoder,
-    XzDecoder as AsyncXzDecoder,
+    XzDecoder as AsyncXzDecoder, ZstdDecoder as AsyncZstdDecoder,
 };
 use bytes::Bytes;
 #[cfg(feature = "compression")]
 use bzip2::read::BzDecoder;
 use datafusion_common::parsers::CompressionTypeVariant;
 #[cfg(feature = "compression")]
-use flate2::read::GzDecoder;
+use flate2::read::MultiGzDecoder;
+use futures::stream::BoxStream;
 use futures::Stream;
 #[cfg(feature = "compression")]
 use futures::TryStreamExt;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::ops::Range;
 use std::str::FromStr;
+use std::sync::Arc;
 #[cfg(feature = "compression")]
 use tokio_util::io::{ReaderStream, StreamReader};
 #[cfg(feature = "compression")]
 use xz2::read::XzDecoder;
+#[cfg(feature = "compression")]
+use zstd::Decoder as ZstdDecoder;
 use CompressionTypeVariant::*;
 
 /// Define each `FileType`/`FileCompressionType`'s extension
@@ -59,9 +67,10 @@ pub struct FileCompressionType {
 impl GetExt for FileCompressionType {
     fn get_ext(&self) -> String {
         match self.variant {
-            GZIP => ".gz".to_owned(),
+            GZIP | BGZIP => ".gz".to_owned(),
             BZIP2 => ".bz2".to_owned(),
             XZ => ".xz".to_owned(),
+            ZSTD => ".zst".to_owned(),
             UNCOMPRESSED => "".to_owned(),
         }
     }
@@ -95,16 +104,33 @@ impl FileCompressionType {
     /// Xz-ed file (liblzma)
     pub const XZ: Self = Self { variant: XZ };
 
+    /// Zstd-ed file
+    pub const ZSTD: Self = Self { variant: ZSTD };
+
+    /// Blocked gzip-ed file (BGZF), as written by `bgzip`
+    pub const BGZIP: Self = Self { variant: BGZIP };
+
     /// Uncompressed file
     pub const UNCOMPRESSED: Self = Self {
         variant: UNCOMPRESSED,
     };
 
+    /// Read only access to self.variant
+    pub fn get_variant(&self) -> &CompressionTypeVariant {
+        &self.variant
+    }
+
     /// The file is compressed or not
     pub const fn is_compressed(&self) -> bool {
         self.variant.is_compressed()
     }
 
+    /// The files can be decompressed from any byte range, see
+    /// [`Self::convert_range`]
+    pub const fn is_splittable(&self) -> bool {
+        matches!(self.variant, BGZIP)
+    }
+
     /// Given a `Stream`, create a `Stream` which data are decompressed with `FileCompressionType`.
     pub fn convert_stream<T: Stream<Item = Result<Bytes>> + Unpin + Send + 'static>(
         &self,
@@ -126,10 +152,12 @@ impl FileCompressionType {
 
         Ok(match self.variant {
             #[cfg(feature = "compression")]
-            GZIP => Box::new(
-                ReaderStream::new(AsyncGzDecoder::new(StreamReader::new(s)))
-                    .map_err(err_converter),
-            ),
+            GZIP | BGZIP => {
+                let mut decoder = AsyncGzDecoder::new(StreamReader::new(s));
+                // concatenated gzip files, and the blocks of BGZF files
+                decoder.multiple_members(true);
+                Box::new(ReaderStream::new(decoder).map_err(err_converter))
+            }
             #[cfg(feature = "compression")]
             BZIP2 => Box::new(
                 ReaderStream::new(AsyncBzDecoder::new(StreamReader::new(s)))
@@ -140,8 +168,13 @@ impl FileCompressionType {
                 ReaderStream::new(AsyncXzDecoder::new(StreamReader::new(s)))
                     .map_err(err_converter),
             ),
+            #[cfg(feature = "compression")]
+            ZSTD => Box::new(
+                ReaderStream::new(AsyncZstdDecoder::new(StreamReader::new(s)))
+                    .map_err(err_converter),
+            ),
             #[cfg(not(feature = "compression"))]
-            GZIP | BZIP2 | XZ => {
+            GZIP | BZIP2 | XZ | ZSTD | BGZIP => {
                 return Err(DataFusionError::NotImplemented(
                     "Compression feature is not enabled".to_owned(),
                 ))
@@ -157,13 +190,15 @@ impl FileCompressionType {
     ) -> Result<Box<dyn std::io::Read + Send>> {
         Ok(match self.variant {
             #[cfg(feature = "compression")]
-            GZIP => Box::new(GzDecoder::new(r)),
+            GZIP | BGZIP => Box::new(MultiGzDecoder::new(r)),
             #[cfg(feature = "compression")]
             BZIP2 => Box::new(BzDecoder::new(r)),
             #[cfg(feature = "compression")]
             XZ => Box::new(XzDecoder::new(r)),
+            #[cfg(feature = "compression")]
+            ZSTD => Box::new(ZstdDecoder::new(r)?),
             #[cfg(not(feature = "compression"))]
-            GZIP | BZIP2 | XZ => {
+            GZIP | BZIP2 | XZ | ZSTD | BGZIP => {
                 return Err(DataFusionError::NotImplemented(
                     "Compression feature is not enabled".to_owned(),
                 ))
@@ -171,11 +206,39 @@ impl FileCompressionType {
             UNCOMPRESSED => Box::new(r),
         })
     }
+
+    /// Given the byte `range` of the file at `location`, of `file_size` bytes,
+    /// create a `Stream` of the decompressed rows starting in the range.
+    ///
+    /// The rows of consecutive ranges of a file are disjoint and cover the
+    /// whole file, so that the ranges can be read in parallel. Only supported for
+    /// the splittable compression types, see [`Self::is_splittable`].
+    pub fn convert_range(
+        &self,
+        store: Arc<dyn ObjectStore>,
+        location: Path,
+        file_size: usize,
+        range: Range<usize>,
+    ) -> Result<BoxStream<'static, Result<Bytes>>> {
+        match self.variant {
+            #[cfg(feature = "compression")]
+            BGZIP => Ok(super::bgzf::range_stream(store, location, file_size, range)),
+            _ => {
+                let _ = (store, location, file_size, range);
+                Err(DataFusionError::NotImplemented(format!(
+                    "Reading byte ranges of {:?} files",
+                    self.variant
+                )))
+            }
+        }
+    }
 }
 
 /// Readable file type
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileType {
+    /// Apache Arrow IPC file
+    ARROW,
     /// Apache Avro file
     AVRO,
     /// Apache Parquet file
@@ -189,6 +252,7 @@ pub enum FileType {
 impl GetExt for FileType {
     fn get_ext(&self) -> String {
         match self {
+            FileType::ARROW => DEFAULT_ARROW_EXTENSION.to_owned(),
             FileType::AVRO => DEFAULT_AVRO_EXTENSION.to_owned(),
             FileType::PARQUET => DEFAULT_PARQUET_EXTENSION.to_owned(),
             FileType::CSV => DEFAULT_CSV_EXTENSION.to_owned(),
@@ -203,6 +267,7 @@ impl FromStr for FileType {
     fn from_str(s: &str) -> Result<Self> {
         let s = s.to_uppercase();
         match s.as_str() {
+            "ARROW" => Ok(FileType::ARROW),
             "AVRO" => Ok(FileType::AVRO),
             "PARQUET" => Ok(FileType::PARQUET),
             "CSV" => Ok(FileType::CSV),
@@ -221,7 +286,7 @@ impl FileType {
 
         match self {
             FileType::JSON | FileType::CSV => Ok(format!("{}{}", ext, c.get_ext())),
-            FileType::PARQUET | FileType::AVRO => match c.variant {
+            FileType::PARQUET | FileType::AVRO | FileType::ARROW => match c.variant {
                 UNCOMPRESSED => Ok(ext),
                 _ => Err(DataFusionError::Internal(
                     "FileCompressionType can be specified for CSV/JSON FileType.".into(),
@@ -307,6 +372,18 @@ mod tests {
             Err(DataFusionError::Internal(_))
         ));
 
+        let file_type = FileType::ARROW;
+        assert_eq!(
+            file_type
+                .get_ext_with_compression(FileCompressionType::UNCOMPRESSED)
+                .unwrap(),
+            ".arrow"
+        );
+        assert!(matches!(
+            file_type.get_ext_with_compression(FileCompressionType::GZIP),
+            Err(DataFusionError::Internal(_))
+        ));
+
         let file_type = FileType::PARQUET;
         assert_eq!(
             file_type
@@ -332,6 +409,9 @@ mod tests {
         assert_eq!(FileType::from_str("json").unwrap(), FileType::JSON);
         assert_eq!(FileType::from_str("JSON").unwrap(), FileType::JSON);
 
+        assert_eq!(FileType::from_str("arrow").unwrap(), FileType::ARROW);
+        assert_eq!(FileType::from_str("ARROW").unwrap(), FileType::ARROW);
+
         assert_eq!(FileType::from_str("avro").unwrap(), FileType::AVRO);
         assert_eq!(FileType::from_str("AVRO").unwrap(), FileType::AVRO);
 
@@ -133,10 +133,10 @@ impl FileFormat for JsonFormat {
         &self,
         _state: &SessionState,
         _store: &Arc<dyn ObjectStore>,
-        _table_schema: SchemaRef,
+        table_schema: SchemaRef,
         _object: &ObjectMeta,
     ) -> Result<Statistics> {
-        Ok(Statistics::default())
+        Ok(Statistics::new_unknown(&table_schema))
     }
 
     async fn create_physical_plan(
@@ -148,6 +148,10 @@ impl FileFormat for JsonFormat {
         let exec = NdJsonExec::new(conf, self.file_compression_type.to_owned());
         Ok(Arc::new(exec))
     }
+
+    fn supports_file_ranges(&self) -> bool {
+        self.file_compression_type.is_splittable()
+    }
 }
 
 #[cfg(test)]
@@ -158,7 +162,7 @@ mod tests {
     use object_store::local::LocalFileSystem;
 
     use super::*;
-    use crate::physical_plan::collect;
+    use crate::physical_plan::{collect, Precision};
     use crate::prelude::{SessionConfig, SessionContext};
     use crate::test::object_store::local_unpartitioned_file;
 
@@ -184,8 +188,8 @@ mod tests {
         assert_eq!(tt_batches, 6 /* 12/2 */);
 
         // test metadata
-        assert_eq!(exec.statistics().num_rows, None);
-        assert_eq!(exec.statistics().total_byte_size, None);
+        assert_eq!(exec.statistics().num_rows, Precision::Absent);
+        assert_eq!(exec.statistics().total_byte_size, Precision::Absent);
 
         Ok(())
     }
@@ -20,7 +20,10 @@
 /// default max records to scan to infer the schema
 pub const DEFAULT_SCHEMA_INFER_MAX_RECORD: usize = 1000;
 
+pub mod arrow;
 pub mod avro;
+#[cfg(feature = "compression")]
+pub(crate) mod bgzf;
 pub mod csv;
 pub mod file_type;
 pub mod json;
@@ -83,6 +86,15 @@ pub trait FileFormat: Send + Sync + fmt::Debug {
         conf: FileScanConfig,
         filters: &[Expr],
     ) -> Result<Arc<dyn ExecutionPlan>>;
+
+    /// Returns true if the execution plans of this format can scan byte ranges
+    /// of the files, see [`PartitionedFile::range`], so that large files are
+    /// split into ranges scanned in parallel.
+    ///
+    /// [`PartitionedFile::range`]: crate::datasource::listing::PartitionedFile::range
+    fn supports_file_ranges(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
@@ -124,6 +136,7 @@ pub(crate) mod test_util {
             partition_values: vec![],
             range: None,
             extensions: None,
+            statistics: None,
         }]];
 
         let exec = format
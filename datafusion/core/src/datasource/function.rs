@@ -0,0 +1,82 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Table functions, returning the rows of a table created from their
+//! arguments
+
+use std::sync::Arc;
+
+use crate::datasource::TableProvider;
+use crate::error::Result;
+use crate::logical_expr::Expr;
+
+/// A function called in the `FROM` clause of a query, such as
+/// `SELECT * FROM my_function(1, 'a')`, registered with
+/// [`SessionContext::register_udtf`](crate::execution::context::SessionContext::register_udtf)
+///
+/// For example, a function returning a table of one row holding its
+/// argument:
+///
+/// ```
+/// use datafusion::arrow::array::Int64Array;
+/// use datafusion::arrow::datatypes::{DataType, Field, Schema};
+/// use datafusion::arrow::record_batch::RecordBatch;
+/// use datafusion::common::ScalarValue;
+/// use datafusion::datasource::function::TableFunctionImpl;
+/// use datafusion::datasource::{MemTable, TableProvider};
+/// use datafusion::error::{DataFusionError, Result};
+/// use datafusion::logical_expr::Expr;
+/// use datafusion::prelude::*;
+/// use std::sync::Arc;
+///
+/// struct One;
+///
+/// impl TableFunctionImpl for One {
+///     fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+///         let value = match args {
+///             [Expr::Literal(ScalarValue::Int64(Some(value)))] => *value,
+///             _ => {
+///                 return Err(DataFusionError::Plan(
+///                     "one expects an integer".to_string(),
+///                 ))
+///             }
+///         };
+///         let schema = Arc::new(Schema::new(vec![Field::new(
+///             "value",
+///             DataType::Int64,
+///             false,
+///         )]));
+///         let values = Arc::new(Int64Array::from(vec![value]));
+///         let batch = RecordBatch::try_new(schema.clone(), vec![values])?;
+///         Ok(Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let ctx = SessionContext::new();
+/// ctx.register_udtf("one", Arc::new(One));
+/// let batches = ctx.sql("SELECT value FROM one(42)").await?.collect().await?;
+/// assert_eq!(batches[0].num_rows(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub trait TableFunctionImpl: Send + Sync {
+    /// Creates the table scanned by the query for the arguments `args`,
+    /// which are planned without any column in scope
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>>;
+}
@@ -21,8 +21,10 @@ use std::sync::Arc;
 
 use arrow::array::new_empty_array;
 use arrow::{
-    array::{ArrayBuilder, ArrayRef, Date64Builder, StringBuilder, UInt64Builder},
-    datatypes::{DataType, Field, Schema},
+    array::{
+        ArrayBuilder, ArrayRef, Date64Builder, StringBuilder, UInt64Array, UInt64Builder,
+    },
+    datatypes::{DataType, Field, Schema, SchemaRef},
     record_batch::RecordBatch,
 };
 use chrono::{TimeZone, Utc};
@@ -30,20 +32,29 @@ use futures::{stream::BoxStream, TryStreamExt};
 use log::debug;
 
 use crate::{
-    datasource::MemTable, error::Result, execution::context::SessionContext,
+    dataframe::DataFrame,
+    datasource::{provider_as_source, MemTable},
+    error::Result,
+    execution::context::SessionState,
+    execution::planning_metrics::PlanningMetrics,
+    physical_optimizer::pruning::{PruningPredicate, PruningStatistics},
+    physical_plan::{ColumnStatistics, Precision, Statistics},
     scalar::ScalarValue,
 };
 
-use super::PartitionedFile;
+use super::{FileRange, PartitionedFile};
 use crate::datasource::listing::ListingTableUrl;
 use datafusion_common::{
     cast::{as_date64_array, as_string_array, as_uint64_array},
+    tree_node::TreeNode,
     Column, DataFusionError,
 };
 use datafusion_expr::{
+    expr::BinaryExpr,
     expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion},
-    Expr, Volatility,
+    Expr, LogicalPlanBuilder, Operator, Volatility, UNNAMED_TABLE,
 };
+use datafusion_optimizer::utils::conjunction;
 use object_store::path::Path;
 use object_store::{ObjectMeta, ObjectStore};
 
@@ -57,13 +68,15 @@ const FILE_MODIFIED_COLUMN_NAME: &str = "_df_part_file_modified_";
 struct ApplicabilityVisitor<'a> {
     col_names: &'a [String],
     is_applicable: &'a mut bool,
+    /// Whether stable functions, such as `now()`, are applicable
+    allow_stable: bool,
 }
 
 impl ApplicabilityVisitor<'_> {
     fn visit_volatility(self, volatility: Volatility) -> Recursion<Self> {
         match volatility {
             Volatility::Immutable => Recursion::Continue(self),
-            // TODO: Stable functions could be `applicable`, but that would require access to the context
+            Volatility::Stable if self.allow_stable => Recursion::Continue(self),
             Volatility::Stable | Volatility::Volatile => {
                 *self.is_applicable = false;
                 Recursion::Stop(self)
@@ -141,11 +154,159 @@ pub fn expr_applicable_for_cols(col_names: &[String], expr: &Expr) -> bool {
     expr.accept(ApplicabilityVisitor {
         col_names,
         is_applicable: &mut is_applicable,
+        allow_stable: false,
     })
     .unwrap();
     is_applicable
 }
 
+/// Returns a predicate over only the columns `col_names` that is true for all the
+/// rows for which `expr` is true, if any. It is used to prune the partitions of a
+/// table with filters that also refer to its other columns:
+///
+/// - the conjuncts of an `AND` that can't be resolved with `col_names` are dropped,
+///   for example `year = 2021 AND x > 1` implies `year = 2021`
+/// - an `OR` implies a predicate only if both of its sides do, for example
+///   `year = 2021 OR (year = 2020 AND x > 1)` implies `year = 2021 OR year = 2020`
+///
+/// Unlike in [`expr_applicable_for_cols`], stable functions such as `now()` are
+/// applicable, as the partitions are pruned once for the query.
+pub fn partition_predicate(col_names: &[String], expr: &Expr) -> Option<Expr> {
+    let mut is_applicable = true;
+    expr.accept(ApplicabilityVisitor {
+        col_names,
+        is_applicable: &mut is_applicable,
+        allow_stable: true,
+    })
+    .unwrap();
+    if is_applicable {
+        return Some(expr.clone());
+    }
+
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => match (
+            partition_predicate(col_names, left),
+            partition_predicate(col_names, right),
+        ) {
+            (Some(left), Some(right)) => Some(left.and(right)),
+            (Some(predicate), None) | (None, Some(predicate)) => Some(predicate),
+            (None, None) => None,
+        },
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Or,
+            right,
+        }) => Some(
+            partition_predicate(col_names, left)?
+                .or(partition_predicate(col_names, right)?),
+        ),
+        _ => None,
+    }
+}
+
+/// The statistics of a file of a listing table, to prune it with a
+/// [`PruningPredicate`] before scanning it. Only the exact statistics are used.
+struct FilePruningStatistics<'a> {
+    schema: &'a Schema,
+    statistics: &'a Statistics,
+}
+
+impl FilePruningStatistics<'_> {
+    fn column(&self, column: &Column) -> Option<&ColumnStatistics> {
+        let idx = self.schema.index_of(&column.name).ok()?;
+        self.statistics.column_statistics.get(idx)
+    }
+}
+
+impl PruningStatistics for FilePruningStatistics<'_> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        match &self.column(column)?.min_value {
+            Precision::Exact(value) => Some(value.to_array()),
+            _ => None,
+        }
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        match &self.column(column)?.max_value {
+            Precision::Exact(value) => Some(value.to_array()),
+            _ => None,
+        }
+    }
+
+    fn num_containers(&self) -> usize {
+        1
+    }
+
+    fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
+        match self.column(column)?.null_count {
+            Precision::Exact(count) => {
+                Some(Arc::new(UInt64Array::from(vec![count as u64])))
+            }
+            _ => None,
+        }
+    }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        match self.statistics.num_rows {
+            Precision::Exact(rows) => {
+                Some(Arc::new(UInt64Array::from(vec![rows as u64])))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns a [`PruningPredicate`] over the columns of the files of a table for the
+/// `filters` that only refer to these columns, if any, to prune the files with
+/// their statistics
+pub fn file_pruning_predicate(
+    filters: &[Expr],
+    file_schema: &SchemaRef,
+) -> Option<PruningPredicate> {
+    let col_names: Vec<_> = file_schema
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+    let filters = filters
+        .iter()
+        .filter(|f| expr_applicable_for_cols(&col_names, f))
+        .cloned()
+        .collect::<Vec<_>>();
+    let predicate = conjunction(filters)?;
+    match PruningPredicate::try_new(predicate, file_schema.clone()) {
+        Ok(predicate) => (!predicate.allways_true()).then_some(predicate),
+        Err(e) => {
+            debug!("Could not create file pruning predicate: {}", e);
+            None
+        }
+    }
+}
+
+/// Returns false if the file with the `statistics` can't contain any row matching
+/// the `predicate`
+pub fn file_matches_predicate(
+    predicate: &PruningPredicate,
+    file_schema: &Schema,
+    statistics: &Statistics,
+) -> bool {
+    let statistics = FilePruningStatistics {
+        schema: file_schema,
+        statistics,
+    };
+    match predicate.prune(&statistics) {
+        Ok(matches) => matches[0],
+        Err(e) => {
+            debug!("Error evaluating file pruning predicate: {}", e);
+            true
+        }
+    }
+}
+
 /// Partition the list of files into `n` groups
 pub fn split_files(
     partitioned_files: Vec<PartitionedFile>,
@@ -162,15 +323,60 @@ pub fn split_files(
         .collect()
 }
 
+/// The minimum size of the byte ranges of [`split_file_ranges`]
+const MIN_FILE_RANGE_SIZE: usize = 1024 * 1024;
+
+/// Split the files into byte ranges of about the same size, so that they are
+/// scanned in parallel by `n` partitions. The files smaller than a range, of at
+/// least 1MB, are kept whole.
+pub fn split_file_ranges(
+    partitioned_files: Vec<PartitionedFile>,
+    n: usize,
+) -> Vec<PartitionedFile> {
+    let total_size: usize = partitioned_files
+        .iter()
+        .map(|file| file.object_meta.size)
+        .sum();
+    let range_size = (total_size / n.max(1)).max(MIN_FILE_RANGE_SIZE);
+    partitioned_files
+        .into_iter()
+        .flat_map(|file| {
+            let size = file.object_meta.size;
+            if file.range.is_some() || size <= range_size {
+                return vec![file];
+            }
+            let num_ranges = (size + range_size - 1) / range_size;
+            let range_size = (size + num_ranges - 1) / num_ranges;
+            (0..size)
+                .step_by(range_size)
+                .map(|start| PartitionedFile {
+                    range: Some(FileRange {
+                        start: start as i64,
+                        end: (start + range_size).min(size) as i64,
+                    }),
+                    // the statistics describe the whole file
+                    statistics: None,
+                    ..file.clone()
+                })
+                .collect()
+        })
+        .collect()
+}
+
 /// Discover the partitions on the given path and prune out files
 /// that belong to irrelevant partitions using `filters` expressions.
 /// `filters` might contain expressions that can be resolved only at the
 /// file level (e.g. Parquet row group pruning).
 ///
+/// The partitions are pruned with the predicates over the partition columns
+/// implied by the `filters`, see [`partition_predicate`], evaluated with the
+/// `ctx` of the query.
+///
 /// TODO for tables with many files (10k+), it will usually more efficient
 /// to first list the folders relative to the first partition dimension,
 /// prune those, then list only the contain of the remaining folders.
 pub async fn pruned_partition_list<'a>(
+    ctx: &'a SessionState,
     store: &'a dyn ObjectStore,
     table_path: &'a ListingTableUrl,
     filters: &'a [Expr],
@@ -184,17 +390,11 @@ pub async fn pruned_partition_list<'a>(
         return Ok(Box::pin(list.map_ok(|object_meta| object_meta.into())));
     }
 
+    let partition_col_names: Vec<_> =
+        table_partition_cols.iter().map(|x| x.0.clone()).collect();
     let applicable_filters: Vec<_> = filters
         .iter()
-        .filter(|f| {
-            expr_applicable_for_cols(
-                &table_partition_cols
-                    .iter()
-                    .map(|x| x.0.clone())
-                    .collect::<Vec<_>>(),
-                f,
-            )
-        })
+        .filter_map(|f| partition_predicate(&partition_col_names, f))
         .collect();
 
     if applicable_filters.is_empty() {
@@ -235,6 +435,7 @@ pub async fn pruned_partition_list<'a>(
                     object_meta,
                     range: None,
                     extensions: None,
+                    statistics: None,
                 }))
             },
         )))
@@ -245,13 +446,21 @@ pub async fn pruned_partition_list<'a>(
         let mem_table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
         debug!("get mem_table: {:?}", mem_table);
 
-        // Filter the partitions using a local datafusion context
-        // TODO having the external context would allow us to resolve `Volatility::Stable`
-        // scalar functions (`ScalarFunction` & `ScalarUDF`) and `ScalarVariable`s
-        let ctx = SessionContext::new();
-        let mut df = ctx.read_table(Arc::new(mem_table))?;
+        // Filter the partitions with the state of the query, so that stable
+        // functions evaluate to the same values as in the rest of the query
+        let scan = LogicalPlanBuilder::scan(
+            UNNAMED_TABLE,
+            provider_as_source(Arc::new(mem_table)),
+            None,
+        )?
+        .build()?;
+        // the time spent is part of the physical planning of the query
+        let state = ctx
+            .clone()
+            .with_planning_metrics(Arc::new(PlanningMetrics::new()));
+        let mut df = DataFrame::new(state, scan);
         for filter in applicable_filters {
-            df = df.filter(filter.clone())?;
+            df = df.filter(unqualify_columns(filter)?)?;
         }
         let filtered_batches = df.collect().await?;
         let paths = batches_to_paths(&filtered_batches)?;
@@ -260,6 +469,17 @@ pub async fn pruned_partition_list<'a>(
     }
 }
 
+/// Removes the table qualifiers of the columns of `expr`, which refer to the
+/// partition columns of the table, to evaluate it over the partitions
+fn unqualify_columns(expr: Expr) -> Result<Expr> {
+    expr.transform_up(&|expr| {
+        Ok(match expr {
+            Expr::Column(column) => Some(Expr::Column(Column::from_name(column.name))),
+            _ => None,
+        })
+    })
+}
+
 /// convert the paths of the files to a record batch with the following columns:
 /// - one column for the file size named `_df_part_file_size_`
 /// - one column for with the original path named `_df_part_file_path_`
@@ -356,6 +576,7 @@ fn batches_to_paths(batches: &[RecordBatch]) -> Result<Vec<PartitionedFile>> {
                         .collect(),
                     range: None,
                     extensions: None,
+                    statistics: None,
                 })
             })
         })
@@ -397,7 +618,8 @@ fn parse_partitions_for_path<'a>(
 mod tests {
     use futures::StreamExt;
 
-    use crate::logical_expr::{case, col, lit};
+    use crate::execution::context::SessionContext;
+    use crate::logical_expr::{case, col, lit, BuiltinScalarFunction};
     use crate::test::object_store::make_test_store;
 
     use super::*;
@@ -442,6 +664,48 @@ mod tests {
         assert_eq!(0, chunks.len());
     }
 
+    #[test]
+    fn test_split_file_ranges() {
+        const MB: u64 = 1024 * 1024;
+        let files = vec![
+            PartitionedFile::new("a".to_owned(), 10 * MB),
+            PartitionedFile::new("b".to_owned(), 2 * MB),
+            PartitionedFile::new("c".to_owned(), 100),
+        ];
+        let ranges = |files: &[PartitionedFile]| {
+            files
+                .iter()
+                .map(|file| {
+                    let range = file.range.as_ref().map(|r| (r.start, r.end));
+                    (file.object_meta.location.to_string(), range)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // ranges of about a quarter of the total size
+        let split = split_file_ranges(files.clone(), 4);
+        let mb = MB as i64;
+        assert_eq!(
+            ranges(&split),
+            vec![
+                ("a".to_owned(), Some((0, 5 * mb / 2))),
+                ("a".to_owned(), Some((5 * mb / 2, 5 * mb))),
+                ("a".to_owned(), Some((5 * mb, 15 * mb / 2))),
+                ("a".to_owned(), Some((15 * mb / 2, 10 * mb))),
+                ("b".to_owned(), None),
+                ("c".to_owned(), None),
+            ]
+        );
+
+        // ranges of at least 1MB
+        let split = split_file_ranges(files.clone(), 100);
+        assert_eq!(split.len(), 13);
+        assert!(split[..10].iter().all(|file| file.range.is_some()));
+
+        let split = split_file_ranges(files, 1);
+        assert_eq!(ranges(&split).len(), 3);
+    }
+
     #[tokio::test]
     async fn test_pruned_partition_list_empty() {
         let store = make_test_store(&[
@@ -449,7 +713,9 @@ mod tests {
             ("tablepath/file.parquet", 100),
         ]);
         let filter = Expr::eq(col("mypartition"), lit("val1"));
+        let state = SessionContext::new().state();
         let pruned = pruned_partition_list(
+            &state,
             store.as_ref(),
             &ListingTableUrl::parse("file:///tablepath/").unwrap(),
             &[filter],
@@ -472,7 +738,9 @@ mod tests {
             ("tablepath/mypartition=val1/other=val3/file.parquet", 100),
         ]);
         let filter = Expr::eq(col("mypartition"), lit("val1"));
+        let state = SessionContext::new().state();
         let pruned = pruned_partition_list(
+            &state,
             store.as_ref(),
             &ListingTableUrl::parse("file:///tablepath/").unwrap(),
             &[filter],
@@ -493,7 +761,7 @@ mod tests {
         );
         assert_eq!(
             &f1.partition_values,
-            &[ScalarValue::Utf8(Some(String::from("val1"))),]
+            &[ScalarValue::Utf8(Some("val1".into())),]
         );
         let f2 = &pruned[1];
         assert_eq!(
@@ -502,7 +770,7 @@ mod tests {
         );
         assert_eq!(
             f2.partition_values,
-            &[ScalarValue::Utf8(Some(String::from("val1"))),]
+            &[ScalarValue::Utf8(Some("val1".into())),]
         );
     }
 
@@ -519,7 +787,9 @@ mod tests {
         let filter2 = Expr::eq(col("part2"), lit("p2v1"));
         // filter3 cannot be resolved at partition pruning
         let filter3 = Expr::eq(col("part2"), col("other"));
+        let state = SessionContext::new().state();
         let pruned = pruned_partition_list(
+            &state,
             store.as_ref(),
             &ListingTableUrl::parse("file:///tablepath/").unwrap(),
             &[filter1, filter2, filter3],
@@ -544,8 +814,8 @@ mod tests {
         assert_eq!(
             &f1.partition_values,
             &[
-                ScalarValue::Utf8(Some(String::from("p1v2"))),
-                ScalarValue::Utf8(Some(String::from("p2v1")))
+                ScalarValue::Utf8(Some("p1v2".into())),
+                ScalarValue::Utf8(Some("p2v1".into()))
             ]
         );
         let f2 = &pruned[1];
@@ -556,8 +826,8 @@ mod tests {
         assert_eq!(
             &f2.partition_values,
             &[
-                ScalarValue::Utf8(Some(String::from("p1v2"))),
-                ScalarValue::Utf8(Some(String::from("p2v1")))
+                ScalarValue::Utf8(Some("p1v2".into())),
+                ScalarValue::Utf8(Some("p2v1".into()))
             ]
         );
     }
@@ -688,11 +958,11 @@ mod tests {
         assert_eq!(parsed_files.len(), 2);
         assert_eq!(
             &parsed_files[0].partition_values,
-            &[ScalarValue::Utf8(Some(String::from("val1")))]
+            &[ScalarValue::Utf8(Some("val1".into()))]
         );
         assert_eq!(
             &parsed_files[1].partition_values,
-            &[ScalarValue::Utf8(Some(String::from("val2")))]
+            &[ScalarValue::Utf8(Some("val2".into()))]
         );
 
         let parsed_metas = parsed_files
@@ -736,4 +1006,100 @@ mod tests {
         // this helper function
         assert!(expr_applicable_for_cols(&[], &lit(true)));
     }
+
+    #[test]
+    fn test_partition_predicate() {
+        let cols = [String::from("year"), String::from("month")];
+        let year = |y: i32| col("year").eq(lit(y));
+
+        // mixed conjunctions keep their partition conjuncts
+        let expr = year(2021).and(col("x").gt(lit(1)));
+        assert_eq!(partition_predicate(&cols, &expr), Some(year(2021)));
+
+        // disjunctions need a predicate on both sides
+        let expr = year(2021).or(year(2020).and(col("x").gt(lit(1))));
+        assert_eq!(
+            partition_predicate(&cols, &expr),
+            Some(year(2021).or(year(2020)))
+        );
+        let expr = year(2021).or(col("x").gt(lit(1)));
+        assert_eq!(partition_predicate(&cols, &expr), None);
+
+        // stable functions are applicable, unlike volatile ones
+        let expr = col("year").lt(Expr::ScalarFunction {
+            fun: BuiltinScalarFunction::Now,
+            args: vec![],
+        });
+        assert_eq!(partition_predicate(&cols, &expr), Some(expr.clone()));
+        assert!(!expr_applicable_for_cols(&cols, &expr));
+        let expr = col("year").lt(Expr::ScalarFunction {
+            fun: BuiltinScalarFunction::Random,
+            args: vec![],
+        });
+        assert_eq!(partition_predicate(&cols, &expr), None);
+    }
+
+    #[tokio::test]
+    async fn test_pruned_partition_list_complex_predicates() {
+        let store = make_test_store(&[
+            ("tablepath/year=2020/month=1/file.parquet", 100),
+            ("tablepath/year=2021/month=3/file.parquet", 100),
+            ("tablepath/year=2021/month=7/file.parquet", 100),
+            ("tablepath/year=2022/month=1/file.parquet", 100),
+        ]);
+        let partition_cols = [
+            (String::from("year"), DataType::Int32),
+            (String::from("month"), DataType::Int32),
+        ];
+        let state = SessionContext::new().state();
+        let prune = |filter: Expr| {
+            let state = &state;
+            let store = store.clone();
+            let partition_cols = &partition_cols;
+            async move {
+                let table_path = ListingTableUrl::parse("file:///tablepath/").unwrap();
+                let mut paths: Vec<_> = pruned_partition_list(
+                    state,
+                    store.as_ref(),
+                    &table_path,
+                    &[filter],
+                    ".parquet",
+                    partition_cols,
+                )
+                .await
+                .unwrap()
+                .map_ok(|f| f.object_meta.location.to_string())
+                .try_collect()
+                .await
+                .unwrap();
+                paths.sort();
+                paths
+            }
+        };
+
+        // ranges and mixed predicates
+        let filter = col("year")
+            .gt_eq(lit(2021))
+            .and(col("month").lt(lit(6)).or(col("x").eq(lit(1))));
+        assert_eq!(
+            prune(filter).await,
+            vec![
+                "tablepath/year=2021/month=3/file.parquet",
+                "tablepath/year=2021/month=7/file.parquet",
+                "tablepath/year=2022/month=1/file.parquet",
+            ]
+        );
+
+        // IN lists over an expression of the partition values
+        let filter = (col("year") * lit(100) + col("month"))
+            .in_list(vec![lit(202001), lit(202107)], false)
+            .and(col("x").eq(lit(1)));
+        assert_eq!(
+            prune(filter).await,
+            vec![
+                "tablepath/year=2020/month=1/file.parquet",
+                "tablepath/year=2021/month=7/file.parquet",
+            ]
+        );
+    }
 }
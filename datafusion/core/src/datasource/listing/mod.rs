@@ -24,7 +24,7 @@ mod url;
 
 use crate::error::Result;
 use chrono::TimeZone;
-use datafusion_common::ScalarValue;
+use datafusion_common::{ScalarValue, Statistics};
 use futures::Stream;
 use object_store::{path::Path, ObjectMeta};
 use std::pin::Pin;
@@ -61,6 +61,9 @@ pub struct PartitionedFile {
     pub range: Option<FileRange>,
     /// An optional field for user defined per object metadata  
     pub extensions: Option<Arc<dyn std::any::Any + Send + Sync>>,
+    /// The statistics of the file, if known. Ignored when `range` is set, as
+    /// they describe the whole file.
+    pub statistics: Option<Statistics>,
 }
 
 impl PartitionedFile {
@@ -75,6 +78,7 @@ impl PartitionedFile {
             partition_values: vec![],
             range: None,
             extensions: None,
+            statistics: None,
         }
     }
 
@@ -89,6 +93,7 @@ impl PartitionedFile {
             partition_values: vec![],
             range: Some(FileRange { start, end }),
             extensions: None,
+            statistics: None,
         }
     }
 }
@@ -100,6 +105,7 @@ impl From<ObjectMeta> for PartitionedFile {
             partition_values: vec![],
             range: None,
             extensions: None,
+            statistics: None,
         }
     }
 }
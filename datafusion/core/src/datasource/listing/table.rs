@@ -17,6 +17,7 @@
 
 //! The table implementation.
 
+use std::cmp::Ordering;
 use std::str::FromStr;
 use std::{any::Any, sync::Arc};
 
@@ -25,6 +26,7 @@ use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use async_trait::async_trait;
 use dashmap::DashMap;
 use datafusion_expr::expr::Sort;
+use datafusion_physical_expr::expressions::Column;
 use datafusion_physical_expr::PhysicalSortExpr;
 use futures::{future, stream, StreamExt, TryStreamExt};
 use object_store::path::Path;
@@ -33,8 +35,8 @@ use object_store::ObjectMeta;
 use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
 use crate::datasource::{
     file_format::{
-        avro::AvroFormat, csv::CsvFormat, json::JsonFormat, parquet::ParquetFormat,
-        FileFormat,
+        arrow::ArrowFormat, avro::AvroFormat, csv::CsvFormat, json::JsonFormat,
+        parquet::ParquetFormat, FileFormat,
     },
     get_statistics_with_limit,
     listing::ListingTableUrl,
@@ -42,7 +44,7 @@ use crate::datasource::{
 };
 use crate::logical_expr::TableProviderFilterPushDown;
 use crate::physical_plan;
-use crate::physical_plan::file_format::partition_type_wrap;
+use crate::physical_plan::file_format::{file_value_range, partition_type_wrap};
 use crate::{
     error::{DataFusionError, Result},
     execution::context::SessionState,
@@ -55,7 +57,10 @@ use crate::{
 
 use super::PartitionedFile;
 
-use super::helpers::{expr_applicable_for_cols, pruned_partition_list, split_files};
+use super::helpers::{
+    expr_applicable_for_cols, file_matches_predicate, file_pruning_predicate,
+    pruned_partition_list, split_file_ranges, split_files,
+};
 
 /// Configuration for creating a 'ListingTable'
 #[derive(Debug, Clone)]
@@ -130,6 +135,7 @@ impl ListingTableConfig {
             .map_err(|_| DataFusionError::Internal(err_msg))?;
 
         let file_format: Arc<dyn FileFormat> = match file_type {
+            FileType::ARROW => Arc::new(ArrowFormat::default()),
             FileType::AVRO => Arc::new(AvroFormat::default()),
             FileType::CSV => Arc::new(
                 CsvFormat::default().with_file_compression_type(file_compression_type),
@@ -227,6 +233,12 @@ pub struct ListingOptions {
     /// the future be automatically determined, for example using
     /// parquet metadata.
     ///
+    /// The files are read in the order of the leading sort column, so that
+    /// the scan keeps the ordering even with several files per partition
+    /// if the files don't overlap on this column, according to their
+    /// partition values, for example daily partitions sorted on their
+    /// `date`, or their statistics.
+    ///
     /// See <https://github.com/apache/arrow-datafusion/issues/4177>
     pub file_sort_order: Option<Vec<Expr>>,
     /// Infinite source means that the input is not guaranteed to end.
@@ -390,11 +402,17 @@ impl ListingOptions {
     ) -> Result<SchemaRef> {
         let store = state.runtime_env().object_store(table_path)?;
 
-        let files: Vec<_> = table_path
+        let mut files: Vec<_> = table_path
             .list_all_files(store.as_ref(), &self.file_extension)
             .try_collect()
             .await?;
 
+        // infer the schema from the first files only, if configured
+        if let Some(max_files) = state.config_options().execution.schema_infer_max_files {
+            files.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+            files.truncate(max_files);
+        }
+
         self.format.infer_schema(state, &store, &files).await
     }
 }
@@ -634,6 +652,11 @@ impl TableProvider for ListingTable {
         }
     }
 
+    fn output_orderings(&self) -> Vec<Vec<Expr>> {
+        // the scan always reports the file sort order as its output ordering
+        self.options.file_sort_order.iter().cloned().collect()
+    }
+
     fn get_table_definition(&self) -> Option<&str> {
         self.definition.as_deref()
     }
@@ -655,6 +678,7 @@ impl ListingTable {
         // list files (with partitions)
         let file_list = future::try_join_all(self.table_paths.iter().map(|table_path| {
             pruned_partition_list(
+                ctx,
                 store.as_ref(),
                 table_path,
                 filters,
@@ -666,8 +690,9 @@ impl ListingTable {
 
         let file_list = stream::iter(file_list).flatten();
 
-        // collect the statistics if required by the config
-        let files = file_list.then(|part_file| async {
+        // collect the statistics if required by the config, of several files at once
+        let concurrency = ctx.config_options().execution.meta_fetch_concurrency;
+        let files = file_list.map(|part_file| async {
             let part_file = part_file?;
             let statistics = if self.options.collect_stat {
                 match self.collected_statistics.get(&part_file.object_meta) {
@@ -689,13 +714,61 @@ impl ListingTable {
                     }
                 }
             } else {
-                Statistics::default()
+                Statistics::new_unknown(&self.file_schema)
             };
             Ok((part_file, statistics)) as Result<(PartitionedFile, Statistics)>
         });
+        let files = files.buffered(concurrency.max(1));
 
-        let (files, statistics) =
-            get_statistics_with_limit(files, self.schema(), limit).await?;
+        // prune the files that can't match the filters according to their statistics
+        let pruning_predicate = match self.options.collect_stat {
+            true => file_pruning_predicate(filters, &self.file_schema),
+            false => None,
+        };
+        let files = files.try_filter(|(_, statistics)| {
+            future::ready(pruning_predicate.as_ref().map_or(true, |predicate| {
+                file_matches_predicate(predicate, &self.file_schema, statistics)
+            }))
+        });
+
+        let (mut files, statistics) =
+            get_statistics_with_limit(files, self.file_schema.clone(), limit).await?;
+
+        // read the files in the order of the leading sort column, so that the
+        // partitions of files that don't overlap on it keep the sort order
+        let leading_sort_expr = self
+            .try_create_output_ordering()?
+            .and_then(|ordering| ordering.into_iter().next());
+        if let Some(sort_expr) = leading_sort_expr {
+            if let Some(column) = sort_expr.expr.as_any().downcast_ref::<Column>() {
+                let num_file_columns = self.file_schema.fields().len();
+                let descending = sort_expr.options.descending;
+                let sort_key = |file: &PartitionedFile| {
+                    file_value_range(file, column.index(), num_file_columns)
+                        .map(|(min, max)| if descending { max } else { min })
+                };
+                files.sort_by(|a, b| {
+                    let ordering = match (sort_key(a), sort_key(b)) {
+                        (Some(a), Some(b)) => {
+                            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+                        }
+                        (Some(_), None) => Ordering::Less,
+                        (None, Some(_)) => Ordering::Greater,
+                        (None, None) => Ordering::Equal,
+                    };
+                    if descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                });
+            }
+        }
+
+        // scan the large files in several byte ranges if the format supports it
+        if self.options.format.supports_file_ranges() {
+            files = split_file_ranges(files, self.options.target_partitions);
+        }
 
         Ok((
             split_files(files, self.options.target_partitions),
@@ -708,6 +781,7 @@ impl ListingTable {
 mod tests {
     use super::*;
     use crate::datasource::file_format::file_type::GetExt;
+    use crate::physical_plan::Precision;
     use crate::prelude::*;
     use crate::{
         datasource::file_format::{avro::AvroFormat, parquet::ParquetFormat},
@@ -764,8 +838,8 @@ mod tests {
         assert_eq!(exec.output_partitioning().partition_count(), 1);
 
         // test metadata
-        assert_eq!(exec.statistics().num_rows, Some(8));
-        assert_eq!(exec.statistics().total_byte_size, Some(671));
+        assert_eq!(exec.statistics().num_rows, Precision::Exact(8));
+        assert_eq!(exec.statistics().total_byte_size, Precision::Exact(671));
 
         Ok(())
     }
@@ -787,8 +861,37 @@ mod tests {
         let table = ListingTable::try_new(config)?;
 
         let exec = table.scan(&state, None, &[], None).await?;
-        assert_eq!(exec.statistics().num_rows, Some(8));
-        assert_eq!(exec.statistics().total_byte_size, Some(671));
+        assert_eq!(exec.statistics().num_rows, Precision::Exact(8));
+        assert_eq!(exec.statistics().total_byte_size, Precision::Exact(671));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn prune_files_with_stats() -> Result<()> {
+        let testdata = crate::test_util::parquet_test_data();
+        let filename = format!("{}/{}", testdata, "alltypes_plain.parquet");
+        let table_path = ListingTableUrl::parse(filename).unwrap();
+
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+
+        let opt = ListingOptions::new(Arc::new(ParquetFormat::default()));
+        let schema = opt.infer_schema(&state, &table_path).await?;
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(opt)
+            .with_schema(schema);
+        let table = ListingTable::try_new(config)?;
+
+        // the ids of the file are between 0 and 7
+        let filter = col("id").gt(lit(100));
+        let exec = table.scan(&state, None, &[filter], None).await?;
+        assert!(exec.as_any().is::<EmptyExec>());
+
+        let filter = col("id").lt(lit(3));
+        let exec = table.scan(&state, None, &[filter], None).await?;
+        assert!(!exec.as_any().is::<EmptyExec>());
+        assert_eq!(exec.statistics().num_rows, Precision::Exact(8));
 
         Ok(())
     }
@@ -811,8 +914,31 @@ mod tests {
         let table = ListingTable::try_new(config)?;
 
         let exec = table.scan(&state, None, &[], None).await?;
-        assert_eq!(exec.statistics().num_rows, None);
-        assert_eq!(exec.statistics().total_byte_size, None);
+        assert_eq!(exec.statistics().num_rows, Precision::Absent);
+        assert_eq!(exec.statistics().total_byte_size, Precision::Absent);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn infer_schema_from_first_files() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(dir.path().join("1.csv"), "a\n1\n")?;
+        std::fs::write(dir.path().join("2.csv"), "b\n2\n")?;
+        let table_path = ListingTableUrl::parse(dir.path().to_str().unwrap())?;
+        let opt = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_extension(".csv");
+
+        let ctx = SessionContext::new();
+        let schema = opt.infer_schema(&ctx.state(), &table_path).await?;
+        assert_eq!(schema.fields().len(), 2);
+
+        let config = SessionConfig::new()
+            .set_usize("datafusion.execution.schema_infer_max_files", 1);
+        let ctx = SessionContext::with_config(config);
+        let schema = opt.infer_schema(&ctx.state(), &table_path).await?;
+        assert_eq!(schema.fields().len(), 1);
+        assert_eq!(schema.field(0).name(), "a");
 
         Ok(())
     }
@@ -1284,7 +1410,7 @@ mod tests {
         let cache = StatisticsCache::default();
         assert!(cache.get(&meta).is_none());
 
-        cache.save(meta.clone(), Statistics::default());
+        cache.save(meta.clone(), Statistics::new_unknown(&Schema::empty()));
         assert!(cache.get(&meta).is_some());
 
         // file size changed
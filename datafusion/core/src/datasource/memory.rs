@@ -20,10 +20,17 @@
 //! repeatedly queried without incurring additional file I/O overhead.
 
 use futures::StreamExt;
+use memmap2::Mmap;
 use std::any::Any;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::ipc::reader::FileReader;
 use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
 
@@ -32,15 +39,24 @@ use crate::error::{DataFusionError, Result};
 use crate::execution::context::SessionState;
 use crate::logical_expr::Expr;
 use crate::physical_plan::common;
+use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
 use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::ExecutionPlan;
 use crate::physical_plan::{repartition::RepartitionExec, Partitioning};
 
+/// The next snapshot version of a [`MemTable`]
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(0);
+
 /// In-memory table
 #[derive(Debug)]
 pub struct MemTable {
     schema: SchemaRef,
     batches: Vec<Vec<RecordBatch>>,
+    /// The order of the rows of each partition, by columns of `schema`
+    sort_order: Vec<PhysicalSortExpr>,
+    /// The contents of a table never change, so each table has its own
+    /// snapshot version
+    version: u64,
 }
 
 impl MemTable {
@@ -54,6 +70,8 @@ impl MemTable {
             Ok(Self {
                 schema,
                 batches: partitions,
+                sort_order: vec![],
+                version: NEXT_VERSION.fetch_add(1, Ordering::Relaxed),
             })
         } else {
             Err(DataFusionError::Plan(
@@ -62,6 +80,80 @@ impl MemTable {
         }
     }
 
+    /// Create a new in-memory table whose rows are sorted by `sort_order` within
+    /// each partition, as declared by the `output_ordering` of an
+    /// [`ExecutionPlan`], so that the sorts of the queries over the table can be
+    /// optimized away.
+    ///
+    /// `sort_order` must only sort by columns of `schema`.
+    pub fn try_new_with_ordering(
+        schema: SchemaRef,
+        partitions: Vec<Vec<RecordBatch>>,
+        sort_order: Vec<PhysicalSortExpr>,
+    ) -> Result<Self> {
+        for sort_expr in &sort_order {
+            let valid = match sort_expr.expr.as_any().downcast_ref::<Column>() {
+                Some(column) => schema
+                    .fields()
+                    .get(column.index())
+                    .map_or(false, |field| field.name() == column.name()),
+                None => false,
+            };
+            if !valid {
+                return Err(DataFusionError::Plan(format!(
+                    "The sort order of a MemTable can only use its columns, got {sort_expr}"
+                )));
+            }
+        }
+        let mut table = Self::try_new(schema, partitions)?;
+        table.sort_order = sort_order;
+        Ok(table)
+    }
+
+    /// The order of the rows of each partition of the table
+    pub fn sort_order(&self) -> &[PhysicalSortExpr] {
+        &self.sort_order
+    }
+
+    /// Returns the longest prefix of the sort order of the table whose columns
+    /// are all projected, in terms of the projected schema
+    fn projected_sort_order(
+        &self,
+        projection: Option<&Vec<usize>>,
+    ) -> Vec<PhysicalSortExpr> {
+        let projection = match projection {
+            Some(projection) => projection,
+            None => return self.sort_order.clone(),
+        };
+        self.sort_order
+            .iter()
+            .map_while(|sort_expr| {
+                let column = sort_expr.expr.as_any().downcast_ref::<Column>()?;
+                let index = projection.iter().position(|i| *i == column.index())?;
+                Some(PhysicalSortExpr {
+                    expr: Arc::new(Column::new(column.name(), index)),
+                    options: sort_expr.options,
+                })
+            })
+            .collect()
+    }
+
+    /// Create an in-memory table from the Arrow IPC file at `path`, holding its
+    /// record batches in a single partition.
+    ///
+    /// The file is memory mapped and decoded once, so that the table can then be
+    /// shared by several sessions, see
+    /// [`SharedTableRegistry`](crate::datasource::shared::SharedTableRegistry).
+    pub fn try_new_from_arrow_file(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path.as_ref())?;
+        // SAFETY: the file must not be modified while it is decoded
+        let mmap = unsafe { Mmap::map(&file)? };
+        let reader = FileReader::try_new(Cursor::new(mmap), None)?;
+        let schema = reader.schema();
+        let batches = reader.collect::<ArrowResult<Vec<_>>>()?;
+        Self::try_new(schema, vec![batches])
+    }
+
     /// Create a mem table by reading from another data source
     pub async fn load(
         t: Arc<dyn TableProvider>,
@@ -133,6 +225,10 @@ impl TableProvider for MemTable {
         TableType::Base
     }
 
+    fn snapshot_version(&self) -> Option<u64> {
+        Some(self.version)
+    }
+
     async fn scan(
         &self,
         _state: &SessionState,
@@ -140,11 +236,13 @@ impl TableProvider for MemTable {
         _filters: &[Expr],
         _limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(MemoryExec::try_new(
+        let exec = MemoryExec::try_new(
             &self.batches.clone(),
             self.schema(),
             projection.cloned(),
-        )?))
+        )?
+        .with_sort_information(self.projected_sort_order(projection));
+        Ok(Arc::new(exec))
     }
 }
 
@@ -156,6 +254,7 @@ mod tests {
     use arrow::array::Int32Array;
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::error::ArrowError;
+    use arrow::ipc::writer::FileWriter;
     use futures::StreamExt;
     use std::collections::HashMap;
 
@@ -330,6 +429,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_invalid_ordering() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let sort_order = vec![PhysicalSortExpr {
+            expr: Arc::new(Column::new("b", 0)),
+            options: Default::default(),
+        }];
+
+        match MemTable::try_new_with_ordering(schema, vec![], sort_order) {
+            Err(DataFusionError::Plan(e)) => assert_eq!(
+                e,
+                "The sort order of a MemTable can only use its columns, got b@0 ASC NULLS LAST"
+            ),
+            _ => panic!("MemTable::try_new_with_ordering should have failed"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_from_arrow_file() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let batches = vec![
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from_slice([1, 2, 3])),
+                    Arc::new(Int32Array::from(vec![None, Some(5), Some(6)])),
+                ],
+            )?,
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from_slice([7])),
+                    Arc::new(Int32Array::from_slice([8])),
+                ],
+            )?,
+        ];
+
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("table.arrow");
+        let mut writer = FileWriter::try_new(File::create(&path)?, &schema)?;
+        for batch in &batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+
+        let provider = MemTable::try_new_from_arrow_file(&path)?;
+        assert_eq!(provider.schema(), schema);
+        let exec = provider.scan(&session_ctx.state(), None, &[], None).await?;
+        let read = common::collect(exec.execute(0, task_ctx)?).await?;
+        assert_eq!(read, batches);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_merged_schema() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -18,14 +18,20 @@
 //! DataFusion data sources
 
 #![allow(clippy::module_inception)]
+pub mod analyzed;
 pub mod datasource;
 pub mod default_table_source;
 pub mod empty;
 pub mod file_format;
+pub mod function;
 pub mod listing;
 pub mod listing_table_factory;
 pub mod memory;
 pub mod object_store;
+pub mod object_store_cache;
+#[cfg(feature = "remote_sql")]
+pub mod remote_sql;
+pub mod shared;
 pub mod streaming;
 pub mod view;
 
@@ -42,117 +48,74 @@ use crate::arrow::datatypes::{Schema, SchemaRef};
 use crate::error::Result;
 pub use crate::logical_expr::TableType;
 use crate::physical_plan::expressions::{MaxAccumulator, MinAccumulator};
-use crate::physical_plan::{Accumulator, ColumnStatistics, Statistics};
+use crate::physical_plan::{Accumulator, ColumnStatistics, Precision, Statistics};
 use futures::StreamExt;
 
 /// Get all files as well as the file level summary statistics (no statistic for partition columns).
 /// If the optional `limit` is provided, includes only sufficient files.
 /// Needed to read up to `limit` number of rows.
+///
+/// The statistics of each file are also attached to the returned
+/// [`PartitionedFile`]s, so that scans can report per-partition statistics.
 pub async fn get_statistics_with_limit(
     all_files: impl Stream<Item = Result<(PartitionedFile, Statistics)>>,
     file_schema: SchemaRef,
     limit: Option<usize>,
 ) -> Result<(Vec<PartitionedFile>, Statistics)> {
     let mut result_files = vec![];
-
-    let mut null_counts = vec![0; file_schema.fields().len()];
-    let mut has_statistics = false;
-    let (mut max_values, mut min_values) = create_max_min_accs(&file_schema);
-
-    let mut is_exact = true;
-
-    // The number of rows and the total byte size can be calculated as long as
-    // at least one file has them. If none of the files provide them, then they
-    // will be omitted from the statistics. The missing values will be counted
-    // as zero.
-    let mut num_rows = None;
-    let mut total_byte_size = None;
+    let mut statistics: Option<Statistics> = None;
 
     // fusing the stream allows us to call next safely even once it is finished
     let mut all_files = Box::pin(all_files.fuse());
     while let Some(res) = all_files.next().await {
-        let (file, file_stats) = res?;
+        let (mut file, file_stats) = res?;
+        statistics = Some(match statistics {
+            Some(statistics) => statistics.merge(&file_stats),
+            None => file_stats.clone(),
+        });
+        file.statistics = Some(file_stats);
         result_files.push(file);
-        is_exact &= file_stats.is_exact;
-        num_rows = if let Some(num_rows) = num_rows {
-            Some(num_rows + file_stats.num_rows.unwrap_or(0))
-        } else {
-            file_stats.num_rows
-        };
-        total_byte_size = if let Some(total_byte_size) = total_byte_size {
-            Some(total_byte_size + file_stats.total_byte_size.unwrap_or(0))
-        } else {
-            file_stats.total_byte_size
-        };
-        if let Some(vec) = &file_stats.column_statistics {
-            has_statistics = true;
-            for (i, cs) in vec.iter().enumerate() {
-                null_counts[i] += cs.null_count.unwrap_or(0);
-
-                if let Some(max_value) = &mut max_values[i] {
-                    if let Some(file_max) = cs.max_value.clone() {
-                        match max_value.update_batch(&[file_max.to_array()]) {
-                            Ok(_) => {}
-                            Err(_) => {
-                                max_values[i] = None;
-                            }
-                        }
-                    } else {
-                        max_values[i] = None;
-                    }
-                }
-
-                if let Some(min_value) = &mut min_values[i] {
-                    if let Some(file_min) = cs.min_value.clone() {
-                        match min_value.update_batch(&[file_min.to_array()]) {
-                            Ok(_) => {}
-                            Err(_) => {
-                                min_values[i] = None;
-                            }
-                        }
-                    } else {
-                        min_values[i] = None;
-                    }
-                }
-            }
-        }
 
         // If the number of rows exceeds the limit, we can stop processing
         // files. This only applies when we know the number of rows. It also
         // currently ignores tables that have no statistics regarding the
         // number of rows.
+        let num_rows = statistics
+            .as_ref()
+            .and_then(|s| s.num_rows.get_value().copied());
         if num_rows.unwrap_or(usize::MIN) > limit.unwrap_or(usize::MAX) {
             break;
         }
     }
+
+    let mut statistics = statistics.unwrap_or_else(|| empty_statistics(&file_schema));
+
     // if we still have files in the stream, it means that the limit kicked
     // in and that the statistic could have been different if we processed
     // the files in a different order.
     if all_files.next().await.is_some() {
-        is_exact = false;
+        statistics = statistics.into_inexact();
     }
 
-    let column_stats = if has_statistics {
-        Some(get_col_stats(
-            &file_schema,
-            null_counts,
-            &mut max_values,
-            &mut min_values,
-        ))
-    } else {
-        None
-    };
-
-    let statistics = Statistics {
-        num_rows,
-        total_byte_size,
-        column_statistics: column_stats,
-        is_exact,
-    };
-
     Ok((result_files, statistics))
 }
 
+/// The statistics of a relation without any rows
+fn empty_statistics(schema: &Schema) -> Statistics {
+    Statistics {
+        num_rows: Precision::Exact(0),
+        total_byte_size: Precision::Exact(0),
+        column_statistics: schema
+            .fields()
+            .iter()
+            .map(|_| ColumnStatistics {
+                null_count: Precision::Exact(0),
+                ..ColumnStatistics::new_unknown()
+            })
+            .collect(),
+    }
+}
+
 fn create_max_min_accs(
     schema: &Schema,
 ) -> (Vec<Option<MaxAccumulator>>, Vec<Option<MinAccumulator>>) {
@@ -186,10 +149,10 @@ fn get_col_stats(
                 None => None,
             };
             ColumnStatistics {
-                null_count: Some(null_counts[i]),
-                max_value,
-                min_value,
-                distinct_count: None,
+                null_count: Precision::Exact(null_counts[i]),
+                max_value: max_value.map_or(Precision::Absent, Precision::Exact),
+                min_value: min_value.map_or(Precision::Absent, Precision::Exact),
+                distinct_count: Precision::Absent,
             }
         })
         .collect()
@@ -19,11 +19,21 @@
 //! This allows the user to extend DataFusion with different storage systems such as S3 or HDFS
 //! and query data inside these systems.
 
+use async_trait::async_trait;
+use bytes::Bytes;
 use dashmap::DashMap;
 use datafusion_common::{DataFusionError, Result};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
 use object_store::local::LocalFileSystem;
-use object_store::ObjectStore;
+use object_store::path::Path;
+use object_store::{GetResult, ListResult, MultipartId, ObjectMeta, ObjectStore};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWrite;
+use tokio::sync::{Mutex, Semaphore};
 use url::Url;
 
 /// A parsed URL identifying a particular [`ObjectStore`]
@@ -105,6 +115,10 @@ pub trait ObjectStoreProvider: Send + Sync + 'static {
 /// - If an [`ObjectStore`] has been registered with [`ObjectStoreRegistry::register_store`] with
 /// scheme `s3` and host `my_bucket`, this [`ObjectStore`] will be returned
 ///
+/// - Otherwise, if an [`ObjectStore`] has been registered with scheme `s3` and a host pattern
+/// matching `my_bucket`, such as `my_*` or `*`, this [`ObjectStore`] will be returned. The
+/// longest matching pattern wins
+///
 /// - If an [`ObjectStoreProvider`] has been associated with this [`ObjectStoreRegistry`] using
 /// [`ObjectStoreRegistry::new_with_provider`], [`ObjectStoreProvider::get_by_url`] will be invoked,
 /// and the returned [`ObjectStore`] registered on this [`ObjectStoreRegistry`]. Any error will
@@ -173,7 +187,13 @@ impl ObjectStoreRegistry {
 
     /// Adds a new store to this registry.
     ///
-    /// If a store with the same schema and host existed before, it is replaced and returned
+    /// The host may end with a `*` wildcard, in which case the store serves all the hosts
+    /// starting with the preceding prefix that have no store of their own, for example
+    /// `register_store("s3", "logs-*", store)` for all the buckets named `logs-...`.
+    ///
+    /// If a store with the same schema and host existed before, it is replaced and returned.
+    /// Stores may be replaced at any time, for example to rotate their credentials; queries
+    /// use the store registered when they start scanning a file
     pub fn register_store(
         &self,
         scheme: impl AsRef<str>,
@@ -184,6 +204,29 @@ impl ObjectStoreRegistry {
         self.object_stores.insert(s, store)
     }
 
+    /// Removes the store registered with this scheme and host, or host pattern, returning it
+    pub fn deregister_store(
+        &self,
+        scheme: impl AsRef<str>,
+        host: impl AsRef<str>,
+    ) -> Option<Arc<dyn ObjectStore>> {
+        let s = format!("{}://{}", scheme.as_ref(), host.as_ref());
+        self.object_stores.remove(&s).map(|(_, store)| store)
+    }
+
+    /// Returns the store registered with the longest host pattern matching `key`, if any
+    fn get_by_pattern(&self, key: &str) -> Option<Arc<dyn ObjectStore>> {
+        self.object_stores
+            .iter()
+            .filter_map(|entry| {
+                let prefix = entry.key().strip_suffix('*')?;
+                key.starts_with(prefix)
+                    .then(|| (prefix.len(), entry.value().clone()))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, store)| store)
+    }
+
     /// Get a suitable store for the provided URL. For example:
     ///
     /// - URL with scheme `file:///` or no schema will return the default LocalFS store
@@ -194,7 +237,11 @@ impl ObjectStoreRegistry {
         let url = url.as_ref();
         // First check whether can get object store from registry
         let s = &url[url::Position::BeforeScheme..url::Position::BeforePath];
-        let store = self.object_stores.get(s).map(|o| o.value().clone());
+        let store = self
+            .object_stores
+            .get(s)
+            .map(|o| o.value().clone())
+            .or_else(|| self.get_by_pattern(s));
 
         match store {
             Some(store) => Ok(store),
@@ -214,6 +261,294 @@ impl ObjectStoreRegistry {
     }
 }
 
+/// Creates [`ObjectStore`]s with fresh, temporary credentials, such as the session tokens
+/// of AWS STS, for a [`RefreshingObjectStore`]
+#[async_trait]
+pub trait ObjectStoreCredentialProvider: Debug + Send + Sync + 'static {
+    /// Returns a store with fresh credentials, and the time at which they expire, if they do
+    async fn refresh(
+        &self,
+    ) -> object_store::Result<(Arc<dyn ObjectStore>, Option<Instant>)>;
+}
+
+/// An [`ObjectStore`] whose credentials are refreshed before they expire, so that queries
+/// running for longer than the lifetime of the credentials don't fail.
+///
+/// Each request is forwarded to the store last returned by its
+/// [`ObjectStoreCredentialProvider`], which is asked for a new store once the credentials
+/// of the current one are about to expire.
+#[derive(Debug)]
+pub struct RefreshingObjectStore {
+    provider: Arc<dyn ObjectStoreCredentialProvider>,
+    /// How long before their expiry the credentials are refreshed
+    refresh_margin: Duration,
+    current: Mutex<Option<(Arc<dyn ObjectStore>, Option<Instant>)>>,
+}
+
+impl RefreshingObjectStore {
+    /// Create a store refreshing its credentials with `provider`, one minute before they
+    /// expire
+    pub fn new(provider: Arc<dyn ObjectStoreCredentialProvider>) -> Self {
+        Self {
+            provider,
+            refresh_margin: Duration::from_secs(60),
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Refresh the credentials `refresh_margin` before they expire
+    pub fn with_refresh_margin(mut self, refresh_margin: Duration) -> Self {
+        self.refresh_margin = refresh_margin;
+        self
+    }
+
+    /// Returns the current store, refreshing its credentials if they are about to expire
+    async fn store(&self) -> object_store::Result<Arc<dyn ObjectStore>> {
+        let mut current = self.current.lock().await;
+        if let Some((store, expires_at)) = current.as_ref() {
+            let fresh = expires_at.map_or(true, |expires_at| {
+                Instant::now() + self.refresh_margin < expires_at
+            });
+            if fresh {
+                return Ok(store.clone());
+            }
+        }
+        let (store, expires_at) = self.provider.refresh().await?;
+        *current = Some((store.clone(), expires_at));
+        Ok(store)
+    }
+}
+
+impl Display for RefreshingObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RefreshingObjectStore({:?})", self.provider)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for RefreshingObjectStore {
+    async fn put(&self, location: &Path, bytes: Bytes) -> object_store::Result<()> {
+        self.store().await?.put(location, bytes).await
+    }
+
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> object_store::Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
+        self.store().await?.put_multipart(location).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        location: &Path,
+        multipart_id: &MultipartId,
+    ) -> object_store::Result<()> {
+        self.store()
+            .await?
+            .abort_multipart(location, multipart_id)
+            .await
+    }
+
+    async fn get(&self, location: &Path) -> object_store::Result<GetResult> {
+        self.store().await?.get(location).await
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<usize>,
+    ) -> object_store::Result<Bytes> {
+        self.store().await?.get_range(location, range).await
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        self.store().await?.get_ranges(location, ranges).await
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        self.store().await?.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.store().await?.delete(location).await
+    }
+
+    async fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<BoxStream<'_, object_store::Result<ObjectMeta>>> {
+        // The listing borrows the store, which may be replaced while it is consumed,
+        // so it is buffered
+        let store = self.store().await?;
+        let objects: Vec<_> = store.list(prefix).await?.try_collect().await?;
+        Ok(futures::stream::iter(objects.into_iter().map(Ok)).boxed())
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        self.store().await?.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.store().await?.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.store().await?.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.store().await?.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.store().await?.rename_if_not_exists(from, to).await
+    }
+}
+
+/// An [`ObjectStore`] limiting the number of concurrent requests to another store, with
+/// the permits of a semaphore that may be shared by several stores.
+///
+/// A permit is held until the response to the request has started, so the bytes of the
+/// objects returned by [`ObjectStore::get`] may still be streamed concurrently. It is used
+/// by [`TaskContext::object_store`] to limit the requests of each query.
+///
+/// [`TaskContext::object_store`]: crate::execution::context::TaskContext::object_store
+#[derive(Debug)]
+pub struct LimitedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    permits: Arc<Semaphore>,
+}
+
+impl LimitedObjectStore {
+    /// Create a store limiting the requests to `inner` with `permits`
+    pub fn new(inner: Arc<dyn ObjectStore>, permits: Arc<Semaphore>) -> Self {
+        Self { inner, permits }
+    }
+}
+
+impl Display for LimitedObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LimitedObjectStore({})", self.inner)
+    }
+}
+
+/// Forwards a request to the inner store of a [`LimitedObjectStore`] once a permit is
+/// acquired
+macro_rules! limited {
+    ($self:ident.$method:ident($($arg:expr),*)) => {{
+        let _permit = $self.permits.acquire().await.map_err(|e| {
+            object_store::Error::Generic {
+                store: "LimitedObjectStore",
+                source: Box::new(e),
+            }
+        })?;
+        $self.inner.$method($($arg),*).await
+    }};
+}
+
+#[async_trait]
+impl ObjectStore for LimitedObjectStore {
+    async fn put(&self, location: &Path, bytes: Bytes) -> object_store::Result<()> {
+        limited!(self.put(location, bytes))
+    }
+
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> object_store::Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
+        limited!(self.put_multipart(location))
+    }
+
+    async fn abort_multipart(
+        &self,
+        location: &Path,
+        multipart_id: &MultipartId,
+    ) -> object_store::Result<()> {
+        limited!(self.abort_multipart(location, multipart_id))
+    }
+
+    async fn get(&self, location: &Path) -> object_store::Result<GetResult> {
+        limited!(self.get(location))
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<usize>,
+    ) -> object_store::Result<Bytes> {
+        limited!(self.get_range(location, range))
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        limited!(self.get_ranges(location, ranges))
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        limited!(self.head(location))
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        limited!(self.delete(location))
+    }
+
+    async fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<BoxStream<'_, object_store::Result<ObjectMeta>>> {
+        limited!(self.list(prefix))
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        limited!(self.list_with_delimiter(prefix))
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        limited!(self.copy(from, to))
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        limited!(self.rename(from, to))
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        limited!(self.copy_if_not_exists(from, to))
+    }
+
+    async fn rename_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        limited!(self.rename_if_not_exists(from, to))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,4 +620,67 @@ mod tests {
         let url = ListingTableUrl::parse("../").unwrap();
         sut.get_by_url(&url).unwrap();
     }
+
+    #[test]
+    fn test_get_by_url_pattern() {
+        let sut = ObjectStoreRegistry::default();
+        let any: Arc<dyn ObjectStore> = Arc::new(LocalFileSystem::new());
+        let logs: Arc<dyn ObjectStore> = Arc::new(LocalFileSystem::new());
+        let bucket: Arc<dyn ObjectStore> = Arc::new(LocalFileSystem::new());
+        sut.register_store("s3", "*", any.clone());
+        sut.register_store("s3", "logs-*", logs.clone());
+        sut.register_store("s3", "logs-2023", bucket.clone());
+
+        let get = |url: &str| sut.get_by_url(ListingTableUrl::parse(url).unwrap());
+        assert!(Arc::ptr_eq(&get("s3://data/key").unwrap(), &any));
+        assert!(Arc::ptr_eq(&get("s3://logs-2022/key").unwrap(), &logs));
+        assert!(Arc::ptr_eq(&get("s3://logs-2023/key").unwrap(), &bucket));
+        get("gs://data/key").unwrap_err();
+
+        assert!(sut.deregister_store("s3", "logs-*").is_some());
+        assert!(Arc::ptr_eq(&get("s3://logs-2022/key").unwrap(), &any));
+    }
+
+    #[derive(Debug)]
+    struct CountingProvider {
+        store: Arc<dyn ObjectStore>,
+        lifetime: Duration,
+        refreshes: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ObjectStoreCredentialProvider for CountingProvider {
+        async fn refresh(
+            &self,
+        ) -> object_store::Result<(Arc<dyn ObjectStore>, Option<Instant>)> {
+            self.refreshes
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok((self.store.clone(), Some(Instant::now() + self.lifetime)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refreshing_object_store() {
+        let location = Path::from("file");
+        let provider = Arc::new(CountingProvider {
+            store: Arc::new(object_store::memory::InMemory::new()),
+            lifetime: Duration::from_secs(3600),
+            refreshes: Default::default(),
+        });
+        let refreshes = || provider.refreshes.load(std::sync::atomic::Ordering::SeqCst);
+
+        // the credentials are valid for longer than the margin
+        let store = RefreshingObjectStore::new(provider.clone());
+        store.put(&location, Bytes::from("data")).await.unwrap();
+        assert_eq!(store.head(&location).await.unwrap().size, 4);
+        assert_eq!(refreshes(), 1);
+
+        // the credentials expire within the margin
+        let store = RefreshingObjectStore::new(provider.clone())
+            .with_refresh_margin(Duration::from_secs(7200));
+        store.head(&location).await.unwrap();
+        let listed: Vec<_> = store.list(None).await.unwrap().try_collect().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(refreshes(), 3);
+    }
 }
@@ -0,0 +1,539 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Read-through cache of the bytes of remote objects on the local disk
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion_common::Result;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::{GetResult, ListResult, MultipartId, ObjectMeta, ObjectStore};
+use parking_lot::Mutex;
+use tokio::io::AsyncWrite;
+
+/// The key of cached bytes: the url of their store, their location in the store and
+/// their range, or `None` for a whole object
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    store: String,
+    location: Path,
+    range: Option<Range<usize>>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    /// The file holding the bytes
+    file: PathBuf,
+    size: usize,
+    /// The value of the clock when the bytes were last accessed
+    last_access: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// The version of each object last seen in a listing, see [`version`]
+    versions: HashMap<(String, Path), String>,
+    /// The total size of the cached bytes
+    size: usize,
+    /// The number of accesses to the cache so far
+    clock: u64,
+    /// The id of the next file created
+    next_file: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl CacheState {
+    /// Removes the entries matching `predicate`, returning their files
+    fn remove(&mut self, predicate: impl Fn(&CacheKey) -> bool) -> Vec<PathBuf> {
+        let keys: Vec<_> = self
+            .entries
+            .keys()
+            .filter(|k| predicate(k))
+            .cloned()
+            .collect();
+        keys.into_iter()
+            .map(|key| {
+                let entry = self.entries.remove(&key).unwrap();
+                self.size -= entry.size;
+                entry.file
+            })
+            .collect()
+    }
+}
+
+/// The version of an object: its last modification time and size
+fn version(meta: &ObjectMeta) -> String {
+    format!("{}/{}", meta.last_modified, meta.size)
+}
+
+/// A cache of the bytes fetched from remote object stores, in files of a local
+/// directory, evicting the least recently used bytes once they take more than
+/// `max_size` bytes.
+///
+/// The whole objects and the byte ranges of objects read through a
+/// [`CachedObjectStore`] are cached separately, keyed by the url of the store and
+/// the location of the object. The cached bytes of an object are invalidated when
+/// a listing of the store shows a new version of the object, that is a new
+/// modification time or size, or when the object is written through the
+/// [`CachedObjectStore`].
+///
+/// It is configured with [`RuntimeConfig::with_object_store_cache`], to speed up the
+/// repeated queries over remote files of interactive sessions. The cached files are
+/// removed when the cache is dropped.
+///
+/// [`RuntimeConfig::with_object_store_cache`]: crate::execution::runtime_env::RuntimeConfig::with_object_store_cache
+#[derive(Debug)]
+pub struct ObjectStoreCache {
+    dir: PathBuf,
+    max_size: usize,
+    state: Mutex<CacheState>,
+}
+
+impl ObjectStoreCache {
+    /// Create a cache holding up to `max_size` bytes in files of `dir`, which is
+    /// created if it doesn't exist
+    pub fn try_new(dir: impl Into<PathBuf>, max_size: usize) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            max_size,
+            state: Mutex::new(CacheState::default()),
+        })
+    }
+
+    /// The maximum size of the cached bytes
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// The total size of the cached bytes
+    pub fn size(&self) -> usize {
+        self.state.lock().size
+    }
+
+    /// The number of cached objects and byte ranges
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Returns true if nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of reads served from the cache so far
+    pub fn hits(&self) -> usize {
+        self.state.lock().hits
+    }
+
+    /// The number of reads fetched from the object stores so far
+    pub fn misses(&self) -> usize {
+        self.state.lock().misses
+    }
+
+    /// Removes all the cached bytes
+    pub fn clear(&self) {
+        let files = self.state.lock().remove(|_| true);
+        remove_files(files);
+    }
+
+    /// Returns the cached bytes for `key`, if any
+    async fn get(&self, key: &CacheKey) -> Option<Bytes> {
+        let file = {
+            let mut state = self.state.lock();
+            state.clock += 1;
+            let clock = state.clock;
+            match state.entries.get_mut(key) {
+                Some(entry) => {
+                    entry.last_access = clock;
+                    let file = entry.file.clone();
+                    state.hits += 1;
+                    file
+                }
+                None => {
+                    state.misses += 1;
+                    return None;
+                }
+            }
+        };
+        match tokio::fs::read(&file).await {
+            Ok(bytes) => Some(bytes.into()),
+            Err(_) => {
+                // the file was evicted concurrently, or removed from the disk
+                let mut state = self.state.lock();
+                if state.entries.get(key).map_or(false, |e| e.file == file) {
+                    remove_files(state.remove(|k| k == key));
+                }
+                None
+            }
+        }
+    }
+
+    /// Caches the bytes for `key`, evicting the least recently used bytes as
+    /// needed. The bytes are not cached if they are larger than the whole cache,
+    /// or can't be written to the disk.
+    async fn insert(&self, key: CacheKey, bytes: &Bytes) {
+        let size = bytes.len();
+        if size > self.max_size {
+            return;
+        }
+
+        let file = {
+            let mut state = self.state.lock();
+            state.next_file += 1;
+            self.dir.join(format!("{}.bin", state.next_file))
+        };
+        if tokio::fs::write(&file, bytes).await.is_err() {
+            let _ = std::fs::remove_file(&file);
+            return;
+        }
+
+        let mut state = self.state.lock();
+        let mut removed = state.remove(|k| k == &key);
+        while state.size + size > self.max_size {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| key.clone())
+                .expect("cached bytes");
+            removed.extend(state.remove(|k| k == &oldest));
+        }
+        state.clock += 1;
+        let last_access = state.clock;
+        state.size += size;
+        state.entries.insert(
+            key,
+            CacheEntry {
+                file,
+                size,
+                last_access,
+            },
+        );
+        drop(state);
+        remove_files(removed);
+    }
+
+    /// Records the version of an object seen in a listing, invalidating its
+    /// cached bytes if it changed
+    fn observe(&self, store: &str, meta: &ObjectMeta) {
+        let version = version(meta);
+        let mut state = self.state.lock();
+        let previous = state
+            .versions
+            .insert((store.to_string(), meta.location.clone()), version.clone());
+        if previous.map_or(false, |previous| previous != version) {
+            let files = state.remove(|k| k.store == store && k.location == meta.location);
+            drop(state);
+            remove_files(files);
+        }
+    }
+
+    /// Invalidates the cached bytes of an object written to its store
+    fn invalidate(&self, store: &str, location: &Path) {
+        let mut state = self.state.lock();
+        state
+            .versions
+            .remove(&(store.to_string(), location.clone()));
+        let files = state.remove(|k| k.store == store && &k.location == location);
+        drop(state);
+        remove_files(files);
+    }
+}
+
+impl Drop for ObjectStoreCache {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+/// Removes the files of evicted bytes, ignoring errors as they are no longer
+/// referenced by the cache
+fn remove_files(files: Vec<PathBuf>) {
+    for file in files {
+        let _ = std::fs::remove_file(file);
+    }
+}
+
+/// An [`ObjectStore`] reading the bytes of another store through an
+/// [`ObjectStoreCache`].
+///
+/// It is created by [`RuntimeEnv::object_store`] for the remote stores when the
+/// [`RuntimeEnv`] has an [`ObjectStoreCache`].
+///
+/// [`RuntimeEnv`]: crate::execution::runtime_env::RuntimeEnv
+/// [`RuntimeEnv::object_store`]: crate::execution::runtime_env::RuntimeEnv::object_store
+#[derive(Debug)]
+pub struct CachedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    cache: Arc<ObjectStoreCache>,
+    /// The url of the inner store, that is the scheme and authority of its urls
+    store: String,
+}
+
+impl CachedObjectStore {
+    /// Create a store caching the bytes read from `inner`, whose urls start with
+    /// `store`, in `cache`
+    pub fn new(
+        inner: Arc<dyn ObjectStore>,
+        cache: Arc<ObjectStoreCache>,
+        store: impl Into<String>,
+    ) -> Self {
+        Self {
+            inner,
+            cache,
+            store: store.into(),
+        }
+    }
+
+    fn key(&self, location: &Path, range: Option<Range<usize>>) -> CacheKey {
+        CacheKey {
+            store: self.store.clone(),
+            location: location.clone(),
+            range,
+        }
+    }
+}
+
+impl Display for CachedObjectStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CachedObjectStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CachedObjectStore {
+    async fn put(&self, location: &Path, bytes: Bytes) -> object_store::Result<()> {
+        self.cache.invalidate(&self.store, location);
+        self.inner.put(location, bytes).await
+    }
+
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> object_store::Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
+        self.cache.invalidate(&self.store, location);
+        self.inner.put_multipart(location).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        location: &Path,
+        multipart_id: &MultipartId,
+    ) -> object_store::Result<()> {
+        self.inner.abort_multipart(location, multipart_id).await
+    }
+
+    async fn get(&self, location: &Path) -> object_store::Result<GetResult> {
+        let key = self.key(location, None);
+        let bytes = match self.cache.get(&key).await {
+            Some(bytes) => bytes,
+            None => {
+                let bytes = self.inner.get(location).await?.bytes().await?;
+                self.cache.insert(key, &bytes).await;
+                bytes
+            }
+        };
+        Ok(GetResult::Stream(
+            futures::stream::once(async { Ok(bytes) }).boxed(),
+        ))
+    }
+
+    async fn get_range(
+        &self,
+        location: &Path,
+        range: Range<usize>,
+    ) -> object_store::Result<Bytes> {
+        let key = self.key(location, Some(range.clone()));
+        if let Some(bytes) = self.cache.get(&key).await {
+            return Ok(bytes);
+        }
+        let bytes = self.inner.get_range(location, range).await?;
+        self.cache.insert(key, &bytes).await;
+        Ok(bytes)
+    }
+
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> object_store::Result<Vec<Bytes>> {
+        let mut cached = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            cached.push(
+                self.cache
+                    .get(&self.key(location, Some(range.clone())))
+                    .await,
+            );
+        }
+
+        // fetch the missing ranges together
+        let missing: Vec<_> = ranges
+            .iter()
+            .zip(&cached)
+            .filter(|(_, bytes)| bytes.is_none())
+            .map(|(range, _)| range.clone())
+            .collect();
+        let fetched = match missing.is_empty() {
+            true => vec![],
+            false => self.inner.get_ranges(location, &missing).await?,
+        };
+        for (range, bytes) in missing.into_iter().zip(&fetched) {
+            self.cache
+                .insert(self.key(location, Some(range)), bytes)
+                .await;
+        }
+
+        let mut fetched = fetched.into_iter();
+        Ok(cached
+            .into_iter()
+            .map(|bytes| bytes.unwrap_or_else(|| fetched.next().unwrap()))
+            .collect())
+    }
+
+    async fn head(&self, location: &Path) -> object_store::Result<ObjectMeta> {
+        let meta = self.inner.head(location).await?;
+        self.cache.observe(&self.store, &meta);
+        Ok(meta)
+    }
+
+    async fn delete(&self, location: &Path) -> object_store::Result<()> {
+        self.cache.invalidate(&self.store, location);
+        self.inner.delete(location).await
+    }
+
+    async fn list(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<BoxStream<'_, object_store::Result<ObjectMeta>>> {
+        Ok(self
+            .inner
+            .list(prefix)
+            .await?
+            .map(move |meta| {
+                if let Ok(meta) = &meta {
+                    self.cache.observe(&self.store, meta);
+                }
+                meta
+            })
+            .boxed())
+    }
+
+    async fn list_with_delimiter(
+        &self,
+        prefix: Option<&Path>,
+    ) -> object_store::Result<ListResult> {
+        let result = self.inner.list_with_delimiter(prefix).await?;
+        for meta in &result.objects {
+            self.cache.observe(&self.store, meta);
+        }
+        Ok(result)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.cache.invalidate(&self.store, to);
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+        self.cache.invalidate(&self.store, from);
+        self.cache.invalidate(&self.store, to);
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> object_store::Result<()> {
+        self.cache.invalidate(&self.store, from);
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use object_store::memory::InMemory;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn read_through_cache() -> Result<()> {
+        let dir = TempDir::new()?;
+        let cache = Arc::new(ObjectStoreCache::try_new(dir.path(), 100)?);
+        let inner = Arc::new(InMemory::new());
+        let store = CachedObjectStore::new(inner.clone(), cache.clone(), "s3://bucket");
+        let location = Path::from("file");
+        inner.put(&location, Bytes::from(vec![1; 50])).await?;
+
+        assert_eq!(store.get_range(&location, 0..10).await?.len(), 10);
+        assert_eq!(store.get_range(&location, 0..10).await?.len(), 10);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        let ranges = store.get_ranges(&location, &[0..10, 10..40]).await?;
+        assert_eq!(ranges[1].len(), 30);
+        assert_eq!((cache.hits(), cache.misses()), (2, 2));
+        assert_eq!(cache.size(), 40);
+
+        // the whole object is cached separately from its ranges
+        let bytes = store.get(&location).await?.bytes().await?;
+        assert_eq!(bytes.len(), 50);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.size(), 90);
+
+        // a new version of the object invalidates its cached bytes
+        inner.put(&location, Bytes::from(vec![2; 20])).await?;
+        store.list(None).await?.try_collect::<Vec<_>>().await?;
+        inner.put(&location, Bytes::from(vec![3; 30])).await?;
+        store.list(None).await?.try_collect::<Vec<_>>().await?;
+        assert!(cache.is_empty());
+        let bytes = store.get(&location).await?.bytes().await?;
+        assert_eq!(bytes, Bytes::from(vec![3; 30]));
+
+        // evict the least recently used bytes, the whole object
+        store.get_range(&location, 0..30).await?;
+        store.get_range(&location, 0..25).await?;
+        store.get_range(&location, 0..29).await?;
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.size(), 84);
+
+        drop(store);
+        drop(cache);
+        assert_eq!(std::fs::read_dir(dir.path())?.count(), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Federation of queries to remote SQL databases.
+//!
+//! A [`RemoteSqlTable`] reads a table of a remote database through a
+//! [`SqlConnector`], which runs SQL queries and returns their results as
+//! Arrow record batches. The projections, filters, limits and, with the
+//! [`PushDownRemoteAggregate`] optimizer rule, aggregations of the query are
+//! translated by the [`Unparser`] into the [`Dialect`] of the database, so
+//! that it only returns the data needed.
+//!
+//! [`Unparser`]: datafusion_sql::unparser::Unparser
+//! [`Dialect`]: datafusion_sql::unparser::dialect::Dialect
+
+mod push_down_aggregate;
+mod table;
+
+pub use push_down_aggregate::PushDownRemoteAggregate;
+pub use table::{RemoteSqlExec, RemoteSqlTable, SqlConnector};
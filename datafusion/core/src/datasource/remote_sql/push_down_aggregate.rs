@@ -0,0 +1,104 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Optimizer rule to push down aggregations into remote SQL tables
+
+use std::sync::Arc;
+
+use arrow::datatypes::Schema;
+use datafusion_common::Result;
+use datafusion_expr::expr_rewriter::unnormalize_cols;
+use datafusion_expr::logical_plan::{Aggregate, LogicalPlan, TableScan};
+use datafusion_optimizer::optimizer::ApplyOrder;
+use datafusion_optimizer::{OptimizerConfig, OptimizerRule};
+
+use super::RemoteSqlTable;
+use crate::datasource::{provider_as_source, source_as_provider};
+
+/// Optimization rule that replaces an aggregation of a [`RemoteSqlTable`]
+/// by a scan of the aggregation evaluated by the remote database.
+///
+/// The aggregation is only pushed down if all its expressions and the
+/// filters of the scan can be evaluated by the database. The rule is not
+/// part of the default rules, add it with
+/// [`SessionState::add_optimizer_rule`](crate::execution::context::SessionState::add_optimizer_rule).
+#[derive(Default)]
+pub struct PushDownRemoteAggregate {}
+
+impl PushDownRemoteAggregate {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for PushDownRemoteAggregate {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        let (aggregate, scan) = match plan {
+            LogicalPlan::Aggregate(aggregate) => match aggregate.input.as_ref() {
+                LogicalPlan::TableScan(scan) if scan.fetch.is_none() => (aggregate, scan),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let provider = match source_as_provider(&scan.source) {
+            Ok(provider) => provider,
+            Err(_) => return Ok(None),
+        };
+        let table = match provider.as_any().downcast_ref::<RemoteSqlTable>() {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+
+        let Aggregate {
+            group_expr,
+            aggr_expr,
+            schema,
+            ..
+        } = aggregate;
+        // the database does not know how the table was referred to in the query
+        let aggregated = table.aggregate(
+            unnormalize_cols(scan.filters.iter().cloned()),
+            unnormalize_cols(group_expr.iter().cloned()),
+            unnormalize_cols(aggr_expr.iter().cloned()),
+            Arc::new(Schema::from(schema.as_ref().clone())),
+        )?;
+        Ok(aggregated.map(|aggregated| {
+            LogicalPlan::TableScan(TableScan {
+                table_name: scan.table_name.clone(),
+                source: provider_as_source(Arc::new(aggregated)),
+                projection: None,
+                projected_schema: schema.clone(),
+                filters: vec![],
+                fetch: None,
+                ordering: vec![],
+            })
+        }))
+    }
+
+    fn name(&self) -> &str {
+        "push_down_remote_aggregate"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+}
@@ -0,0 +1,790 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`TableProvider`] for tables of remote databases
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, SchemaRef};
+use arrow::error::ArrowError;
+use async_trait::async_trait;
+use datafusion_common::{DFSchema, DataFusionError, Result, ScalarValue};
+use datafusion_expr::expr::{AggregateFunction, Cast};
+use datafusion_expr::expr_rewriter::unnormalize_col;
+use datafusion_expr::expr_visitor::walk_expr_down;
+use datafusion_expr::{
+    aggregate_function, BuiltinScalarFunction, Expr, ExprSchemable,
+    TableProviderFilterPushDown, TableType,
+};
+use datafusion_optimizer::utils::conjunction;
+use datafusion_sql::unparser::dialect::Dialect;
+use datafusion_sql::unparser::Unparser;
+use futures::{stream, StreamExt, TryStreamExt};
+use sqlparser::ast;
+
+use crate::datasource::TableProvider;
+use crate::execution::context::{SessionState, TaskContext};
+use crate::physical_plan::expressions::PhysicalSortExpr;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::{
+    project_schema, DisplayFormatType, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+
+/// A connection to a remote database, such as Postgres, MySQL or SQLite
+#[async_trait]
+pub trait SqlConnector: Debug + Send + Sync {
+    /// Returns the SQL dialect of the database
+    fn dialect(&self) -> Arc<dyn Dialect + Send + Sync>;
+
+    /// Returns the schema of the remote `table`
+    async fn table_schema(&self, table: &str) -> Result<SchemaRef>;
+
+    /// Executes the `sql` query, returning its results as batches of
+    /// `schema`, whose columns are the ones of the `SELECT` list.
+    ///
+    /// The types of the results may differ from the ones of `schema`, for
+    /// example Postgres returns a `NUMERIC` for the sum of `BIGINT`s, in
+    /// which case the connector must cast them.
+    async fn execute(
+        &self,
+        sql: &str,
+        schema: SchemaRef,
+    ) -> Result<SendableRecordBatchStream>;
+}
+
+/// A table of a remote database, queried through a [`SqlConnector`].
+///
+/// Projections, filters and limits of scans are pushed down into the SQL
+/// query sent to the database, as long as the database can evaluate them
+/// with the same semantics as DataFusion. Aggregations are pushed down by
+/// the [`PushDownRemoteAggregate`](super::PushDownRemoteAggregate) optimizer
+/// rule.
+#[derive(Debug)]
+pub struct RemoteSqlTable {
+    connector: Arc<dyn SqlConnector>,
+    /// The name of the remote table, whose parts are separated by `.`
+    table: String,
+    /// The schema of the remote table
+    table_schema: SchemaRef,
+    /// The schema of this table: the one of the remote table, or the one of
+    /// the aggregation if one was pushed down
+    schema: SchemaRef,
+    aggregation: Option<RemoteAggregation>,
+}
+
+/// An aggregation of the remote table evaluated by the database
+#[derive(Debug, Clone)]
+struct RemoteAggregation {
+    filters: Vec<Expr>,
+    group_expr: Vec<Expr>,
+    aggr_expr: Vec<Expr>,
+}
+
+impl RemoteSqlTable {
+    /// Create a new RemoteSqlTable for the remote `table`, whose schema is
+    /// requested from the database
+    pub async fn try_new(
+        connector: Arc<dyn SqlConnector>,
+        table: impl Into<String>,
+    ) -> Result<Self> {
+        let table = table.into();
+        let schema = connector.table_schema(&table).await?;
+        Ok(Self::new_with_schema(connector, table, schema))
+    }
+
+    /// Create a new RemoteSqlTable for the remote `table` with `schema`
+    pub fn new_with_schema(
+        connector: Arc<dyn SqlConnector>,
+        table: impl Into<String>,
+        schema: SchemaRef,
+    ) -> Self {
+        Self {
+            connector,
+            table: table.into(),
+            table_schema: schema.clone(),
+            schema,
+            aggregation: None,
+        }
+    }
+
+    /// Get the connector to the remote database
+    pub fn connector(&self) -> &Arc<dyn SqlConnector> {
+        &self.connector
+    }
+
+    /// Returns true if an aggregation was pushed down into this table
+    pub fn is_aggregated(&self) -> bool {
+        self.aggregation.is_some()
+    }
+
+    /// Returns the table of the aggregation of this table's rows satisfying
+    /// `filters`, grouped by `group_expr`, whose output has `schema`.
+    ///
+    /// Returns `None` if an aggregation was already pushed down or if the
+    /// database can not evaluate the filters exactly or the expressions.
+    pub fn aggregate(
+        &self,
+        filters: Vec<Expr>,
+        group_expr: Vec<Expr>,
+        aggr_expr: Vec<Expr>,
+        schema: SchemaRef,
+    ) -> Result<Option<Self>> {
+        if self.is_aggregated() {
+            return Ok(None);
+        }
+        let filter_refs: Vec<&Expr> = filters.iter().collect();
+        let exact = self
+            .supports_filters_pushdown(&filter_refs)?
+            .iter()
+            .all(|support| *support == TableProviderFilterPushDown::Exact);
+        let table_schema = DFSchema::try_from(self.table_schema.as_ref().clone())?;
+        let dialect = self.connector.dialect();
+        let supported = group_expr
+            .iter()
+            .chain(aggr_expr.iter())
+            .all(|expr| expr_to_sql(expr, &table_schema, dialect.as_ref()).is_ok());
+        if !exact || !supported {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            connector: self.connector.clone(),
+            table: self.table.clone(),
+            table_schema: self.table_schema.clone(),
+            schema,
+            aggregation: Some(RemoteAggregation {
+                filters,
+                group_expr,
+                aggr_expr,
+            }),
+        }))
+    }
+
+    /// Returns the SQL query scanning this table with the given projection,
+    /// filters and limit. Filters the database can not evaluate are ignored.
+    pub fn query(
+        &self,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<String> {
+        let dialect = self.connector.dialect();
+        let unparser = Unparser::new(dialect.as_ref());
+        let table_schema = DFSchema::try_from(self.table_schema.as_ref().clone())?;
+        let to_sql = |expr: &Expr| {
+            expr_to_sql(expr, &table_schema, dialect.as_ref()).map(|sql| sql.to_string())
+        };
+
+        let (select_list, filters, group_by) = match &self.aggregation {
+            Some(aggregation) => {
+                let select_list = aggregation
+                    .group_expr
+                    .iter()
+                    .chain(aggregation.aggr_expr.iter())
+                    .map(to_sql)
+                    .collect::<Result<Vec<_>>>()?;
+                let group_by = aggregation
+                    .group_expr
+                    .iter()
+                    .map(to_sql)
+                    .collect::<Result<Vec<_>>>()?;
+                (select_list, aggregation.filters.as_slice(), group_by)
+            }
+            None => {
+                let select_list = self
+                    .table_schema
+                    .fields()
+                    .iter()
+                    .map(|field| unparser.new_ident(field.name()).to_string())
+                    .collect();
+                (select_list, filters, vec![])
+            }
+        };
+        let select_list = match projection {
+            Some(projection) => projection
+                .iter()
+                .map(|i| select_list[*i].clone())
+                .collect::<Vec<_>>(),
+            None => select_list,
+        };
+        if select_list.is_empty() {
+            return Err(DataFusionError::NotImplemented(
+                "Remote queries must select at least one column".to_string(),
+            ));
+        }
+
+        let table = unparser.object_name(&self.table);
+        let mut sql = format!("SELECT {} FROM {}", select_list.join(", "), table);
+        let predicates = filters.iter().filter(|expr| to_sql(expr).is_ok()).cloned();
+        if let Some(predicate) = conjunction(predicates) {
+            sql.push_str(&format!(" WHERE {}", to_sql(&predicate)?));
+        }
+        if !group_by.is_empty() {
+            sql.push_str(&format!(" GROUP BY {}", group_by.join(", ")));
+        }
+        if let Some(limit) = limit {
+            sql.push_str(&format!(" LIMIT {limit}"));
+        }
+        Ok(sql)
+    }
+
+    /// Returns how the database can evaluate `filter`
+    fn filter_pushdown(
+        &self,
+        filter: &Expr,
+        table_schema: &DFSchema,
+        dialect: &dyn Dialect,
+    ) -> Result<TableProviderFilterPushDown> {
+        if expr_to_sql(filter, table_schema, dialect).is_err() {
+            return Ok(TableProviderFilterPushDown::Unsupported);
+        }
+        // the database may match strings regardless of case
+        let mut compares_strings = false;
+        let mut has_like = false;
+        walk_expr_down(filter, |expr| {
+            match expr {
+                Expr::Column(column) => {
+                    let data_type = table_schema
+                        .field_with_unqualified_name(&column.name)?
+                        .data_type();
+                    compares_strings |=
+                        matches!(data_type, DataType::Utf8 | DataType::LargeUtf8);
+                }
+                Expr::Like(_) | Expr::ILike(_) => has_like = true,
+                _ => {}
+            }
+            Ok::<_, DataFusionError>(())
+        })?;
+        if (compares_strings && dialect.case_insensitive_strings())
+            || (has_like && dialect.case_insensitive_like())
+        {
+            Ok(TableProviderFilterPushDown::Inexact)
+        } else {
+            Ok(TableProviderFilterPushDown::Exact)
+        }
+    }
+}
+
+#[async_trait]
+impl TableProvider for RemoteSqlTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let sql = self.query(projection, filters, limit)?;
+        let schema = project_schema(&self.schema, projection)?;
+        Ok(Arc::new(RemoteSqlExec::new(
+            self.connector.clone(),
+            sql,
+            schema,
+        )))
+    }
+
+    fn supports_filter_pushdown(
+        &self,
+        filter: &Expr,
+    ) -> Result<TableProviderFilterPushDown> {
+        Ok(self.supports_filters_pushdown(&[filter])?[0].clone())
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        // filters are evaluated before an aggregation is
+        if self.is_aggregated() {
+            return Ok(vec![
+                TableProviderFilterPushDown::Unsupported;
+                filters.len()
+            ]);
+        }
+        let table_schema = DFSchema::try_from(self.table_schema.as_ref().clone())?;
+        let dialect = self.connector.dialect();
+        filters
+            .iter()
+            .map(|filter| self.filter_pushdown(filter, &table_schema, dialect.as_ref()))
+            .collect()
+    }
+}
+
+/// Converts `expr`, whose columns are columns of `schema`, into SQL in
+/// `dialect`.
+///
+/// Returns a [`DataFusionError::NotImplemented`] if the expression can not
+/// be evaluated by the remote database with the same semantics as by
+/// DataFusion. Qualifiers of columns are dropped, as the queries read a
+/// single table.
+fn expr_to_sql(
+    expr: &Expr,
+    schema: &DFSchema,
+    dialect: &dyn Dialect,
+) -> Result<ast::Expr> {
+    let expr = unnormalize_col(expr.clone());
+    walk_expr_down(&expr, |expr| {
+        if evaluated_alike(expr, schema)? {
+            Ok(())
+        } else {
+            Err(DataFusionError::NotImplemented(format!(
+                "Expression {expr} can not be evaluated by the remote database"
+            )))
+        }
+    })?;
+    Unparser::new(dialect).expr_to_sql(&expr)
+}
+
+/// Returns true if all databases evaluate `expr`, a node of an expression,
+/// like DataFusion. The operators, casts and literals evaluated differently
+/// by some databases are rejected by their [`Dialect`].
+fn evaluated_alike(expr: &Expr, schema: &DFSchema) -> Result<bool> {
+    Ok(match expr {
+        Expr::Alias(_, _)
+        | Expr::Column(_)
+        | Expr::BinaryExpr(_)
+        | Expr::Not(_)
+        | Expr::IsNull(_)
+        | Expr::IsNotNull(_)
+        | Expr::IsTrue(_)
+        | Expr::IsFalse(_)
+        | Expr::IsNotTrue(_)
+        | Expr::IsNotFalse(_)
+        | Expr::Negative(_)
+        | Expr::Between(_) => true,
+        Expr::Literal(value) => match value {
+            value if value.is_null() => true,
+            // NaN and infinities have no literal
+            ScalarValue::Float32(Some(v)) => v.is_finite(),
+            ScalarValue::Float64(Some(v)) => v.is_finite(),
+            ScalarValue::Boolean(_)
+            | ScalarValue::Int8(_)
+            | ScalarValue::Int16(_)
+            | ScalarValue::Int32(_)
+            | ScalarValue::Int64(_)
+            | ScalarValue::UInt8(_)
+            | ScalarValue::UInt16(_)
+            | ScalarValue::UInt32(_)
+            | ScalarValue::UInt64(_)
+            | ScalarValue::Utf8(_)
+            | ScalarValue::LargeUtf8(_)
+            | ScalarValue::Date32(_) => true,
+            _ => false,
+        },
+        // some databases use `\` as the default escape character, so
+        // patterns containing it are not pushed down
+        Expr::Like(like) | Expr::ILike(like) => matches!(
+            like.pattern.as_ref(),
+            Expr::Literal(ScalarValue::Utf8(Some(pattern))) if !pattern.contains('\\')
+        ),
+        Expr::InList { list, .. } => !list.is_empty(),
+        Expr::Cast(Cast { expr, data_type }) => {
+            is_exact_cast(&expr.get_type(schema)?, data_type)
+        }
+        Expr::ScalarFunction { fun, .. } => matches!(
+            fun,
+            BuiltinScalarFunction::Abs | BuiltinScalarFunction::Coalesce
+        ),
+        Expr::AggregateFunction(AggregateFunction {
+            fun,
+            filter: None,
+            order_by: None,
+            ..
+        }) => matches!(
+            fun,
+            aggregate_function::AggregateFunction::Count
+                | aggregate_function::AggregateFunction::Sum
+                | aggregate_function::AggregateFunction::Min
+                | aggregate_function::AggregateFunction::Max
+                | aggregate_function::AggregateFunction::Avg
+        ),
+        _ => false,
+    })
+}
+
+/// Returns true if casting from `from` to `to` can not lose information,
+/// in which case all databases agree on the result
+fn is_exact_cast(from: &DataType, to: &DataType) -> bool {
+    let integer_width = |data_type: &DataType| match data_type {
+        DataType::Int8 => Some(8),
+        DataType::Int16 | DataType::UInt8 => Some(16),
+        DataType::Int32 | DataType::UInt16 => Some(32),
+        DataType::Int64 | DataType::UInt32 => Some(64),
+        _ => None,
+    };
+    match (integer_width(from), to) {
+        (Some(width), DataType::Int16) => width <= 16,
+        (Some(width), DataType::Int32) => width <= 32,
+        (Some(_), DataType::Int64) => true,
+        (Some(width), DataType::Float64) => width <= 32,
+        _ => from == to,
+    }
+}
+
+/// Execution plan running a SQL query in a remote database
+#[derive(Debug)]
+pub struct RemoteSqlExec {
+    connector: Arc<dyn SqlConnector>,
+    sql: String,
+    schema: SchemaRef,
+}
+
+impl RemoteSqlExec {
+    /// Create a new RemoteSqlExec running `sql`, whose results have `schema`
+    pub fn new(connector: Arc<dyn SqlConnector>, sql: String, schema: SchemaRef) -> Self {
+        Self {
+            connector,
+            sql,
+            schema,
+        }
+    }
+
+    /// Get the SQL query run by this plan
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl ExecutionPlan for RemoteSqlExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "RemoteSqlExec invalid partition {partition}"
+            )));
+        }
+
+        let connector = self.connector.clone();
+        let sql = self.sql.clone();
+        let schema = self.schema.clone();
+        let batches = stream::once(async move {
+            connector
+                .execute(&sql, schema)
+                .await
+                .map_err(ArrowError::from)
+        })
+        .try_flatten()
+        .boxed();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            batches,
+        )))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "RemoteSqlExec: sql={}", self.sql)
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::new_unknown(&self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion_expr::{cast, col, count_distinct, lit, lower, sum};
+    use datafusion_sql::unparser::dialect::{MySqlDialect, PostgreSqlDialect};
+
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::datasource::remote_sql::PushDownRemoteAggregate;
+    use crate::datasource::MemTable;
+    use crate::prelude::{SessionConfig, SessionContext};
+
+    /// Connector running the queries in DataFusion, recording them
+    struct MockConnector {
+        ctx: SessionContext,
+        dialect: Arc<dyn Dialect + Send + Sync>,
+        queries: Mutex<Vec<String>>,
+    }
+
+    impl MockConnector {
+        fn try_new(dialect: Arc<dyn Dialect + Send + Sync>) -> Result<Self> {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new("a", DataType::Int64, false),
+                Field::new("b", DataType::Int64, true),
+                Field::new("c", DataType::Utf8, true),
+            ]));
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int64Array::from(vec![1, 2, 2, 3])),
+                    Arc::new(Int64Array::from(vec![Some(10), None, Some(20), Some(30)])),
+                    Arc::new(StringArray::from(vec!["x", "y", "X", "z"])),
+                ],
+            )?;
+            let ctx = SessionContext::new();
+            ctx.register_table(
+                "remote",
+                Arc::new(MemTable::try_new(schema, vec![vec![batch]])?),
+            )?;
+            Ok(Self {
+                ctx,
+                dialect,
+                queries: Mutex::new(vec![]),
+            })
+        }
+
+        fn queries(&self) -> Vec<String> {
+            self.queries.lock().unwrap().clone()
+        }
+    }
+
+    impl Debug for MockConnector {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("MockConnector").finish_non_exhaustive()
+        }
+    }
+
+    #[async_trait]
+    impl SqlConnector for MockConnector {
+        fn dialect(&self) -> Arc<dyn Dialect + Send + Sync> {
+            self.dialect.clone()
+        }
+
+        async fn table_schema(&self, table: &str) -> Result<SchemaRef> {
+            Ok(self.ctx.table_provider(table).await?.schema())
+        }
+
+        async fn execute(
+            &self,
+            sql: &str,
+            schema: SchemaRef,
+        ) -> Result<SendableRecordBatchStream> {
+            self.queries.lock().unwrap().push(sql.to_string());
+            let batches = self.ctx.sql(sql).await?.collect().await?;
+            let batches = batches
+                .into_iter()
+                .map(|batch| {
+                    RecordBatch::try_new(schema.clone(), batch.columns().to_vec())
+                })
+                .collect::<Vec<_>>();
+            Ok(Box::pin(RecordBatchStreamAdapter::new(
+                schema,
+                stream::iter(batches),
+            )))
+        }
+    }
+
+    async fn setup(
+        dialect: Arc<dyn Dialect + Send + Sync>,
+    ) -> Result<(SessionContext, Arc<MockConnector>)> {
+        let connector = Arc::new(MockConnector::try_new(dialect)?);
+        let table = RemoteSqlTable::try_new(connector.clone(), "remote").await?;
+        let state = SessionState::with_config_rt(
+            SessionConfig::new().with_target_partitions(1),
+            Arc::new(Default::default()),
+        )
+        .add_optimizer_rule(Arc::new(PushDownRemoteAggregate::new()));
+        let ctx = SessionContext::with_state(state);
+        ctx.register_table("t", Arc::new(table))?;
+        Ok((ctx, connector))
+    }
+
+    #[tokio::test]
+    async fn push_down_scan() -> Result<()> {
+        let (ctx, connector) = setup(Arc::new(PostgreSqlDialect {})).await?;
+        let batches = ctx
+            .sql("SELECT b FROM t WHERE a >= 2 AND b IS NOT NULL LIMIT 5")
+            .await?
+            .collect()
+            .await?;
+        let expected = ["+----+", "| b  |", "+----+", "| 20 |", "| 30 |", "+----+"];
+        assert_batches_sorted_eq!(expected, &batches);
+        assert_eq!(
+            connector.queries(),
+            vec!["SELECT b FROM remote WHERE (a >= 2) AND (b IS NOT NULL) LIMIT 5"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn push_down_aggregate() -> Result<()> {
+        let (ctx, connector) = setup(Arc::new(PostgreSqlDialect {})).await?;
+        let batches = ctx
+            .sql("SELECT a, SUM(b), COUNT(DISTINCT c) FROM t WHERE a > 1 GROUP BY a")
+            .await?
+            .collect()
+            .await?;
+        let expected = [
+            "+---+----------+---------------------+",
+            "| a | SUM(t.b) | COUNT(DISTINCT t.c) |",
+            "+---+----------+---------------------+",
+            "| 2 | 20       | 2                   |",
+            "| 3 | 30       | 1                   |",
+            "+---+----------+---------------------+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        assert_eq!(
+            connector.queries(),
+            vec![
+                "SELECT a, SUM(b), COUNT(DISTINCT c) FROM remote WHERE a > 1 GROUP BY a"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unparse_expressions() -> Result<()> {
+        let schema = DFSchema::try_from(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Float64, true),
+            Field::new("s", DataType::Utf8, true),
+        ]))?;
+        let to_sql = |expr: &Expr| {
+            expr_to_sql(expr, &schema, &PostgreSqlDialect {}).map(|sql| sql.to_string())
+        };
+
+        let cases = vec![
+            (
+                col("t.a").gt(lit(1)).and(col("b").lt_eq(lit(2.5))),
+                "(a > 1) AND (b <= 2.5)",
+            ),
+            (
+                cast(col("a"), DataType::Int64).eq(lit(3i64)),
+                "CAST(a AS BIGINT) = 3",
+            ),
+            (sum(col("a") * lit(2)), "SUM(a * 2)"),
+            (count_distinct(col("s")), "COUNT(DISTINCT s)"),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(to_sql(&expr)?, expected);
+        }
+
+        let unsupported = vec![
+            // casts that may round or fail
+            cast(col("b"), DataType::Int32),
+            cast(col("s"), DataType::Int64),
+            // escapes differ between databases
+            col("s").like(lit(r"a\%")),
+            // NaN has no literal
+            col("b").eq(lit(f64::NAN)),
+            lower(col("s")).eq(lit("a")),
+            col("a").in_list(vec![], false),
+        ];
+        for expr in unsupported {
+            assert!(to_sql(&expr).is_err(), "{expr}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn inexact_filters() -> Result<()> {
+        let connector = Arc::new(MockConnector::try_new(Arc::new(MySqlDialect {}))?);
+        let table = RemoteSqlTable::try_new(connector, "remote").await?;
+        let on_string = col("c").eq(lit("x"));
+        let on_int = col("a").gt(lit(1i64));
+        let unsupported = (col("a") / lit(2i64)).eq(lit(1i64));
+        assert_eq!(
+            table.supports_filters_pushdown(&[&on_string, &on_int, &unsupported])?,
+            vec![
+                TableProviderFilterPushDown::Inexact,
+                TableProviderFilterPushDown::Exact,
+                TableProviderFilterPushDown::Unsupported,
+            ]
+        );
+        assert_eq!(
+            table.query(Some(&vec![2]), &[on_string, on_int, unsupported], Some(3))?,
+            "SELECT c FROM remote WHERE (c = 'x') AND (a > 1) LIMIT 3"
+        );
+
+        // filters on the aggregated table are evaluated by DataFusion
+        let aggregated = table
+            .aggregate(
+                vec![col("a").gt(lit(1i64))],
+                vec![col("a")],
+                vec![],
+                Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)])),
+            )?
+            .unwrap();
+        assert_eq!(
+            aggregated.supports_filters_pushdown(&[&col("a").gt(lit(2i64))])?,
+            vec![TableProviderFilterPushDown::Unsupported]
+        );
+        assert_eq!(
+            aggregated.query(None, &[], None)?,
+            "SELECT a FROM remote WHERE a > 1 GROUP BY a"
+        );
+        assert!(table
+            .aggregate(
+                vec![col("c").eq(lit("x"))],
+                vec![col("a")],
+                vec![],
+                Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)])),
+            )?
+            .is_none());
+        Ok(())
+    }
+}
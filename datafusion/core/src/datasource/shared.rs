@@ -0,0 +1,150 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Registry of in-memory tables shared by several sessions
+//!
+//! A [`MemTable`] registered in a [`SharedTableRegistry`] can be attached to
+//! any number of [`SessionContext`](crate::execution::context::SessionContext)s
+//! with [`SessionContext::register_shared_table`], which registers the same
+//! table in each of them: its record batches are loaded once and never copied.
+//!
+//! [`SessionContext::register_shared_table`]: crate::execution::context::SessionContext::register_shared_table
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+
+use crate::datasource::MemTable;
+
+lazy_static! {
+    static ref GLOBAL_REGISTRY: SharedTableRegistry = SharedTableRegistry::new();
+}
+
+/// A registry of named in-memory tables
+#[derive(Debug, Default)]
+pub struct SharedTableRegistry {
+    tables: RwLock<HashMap<String, Arc<MemTable>>>,
+}
+
+impl SharedTableRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry of the process
+    pub fn global() -> &'static Self {
+        &GLOBAL_REGISTRY
+    }
+
+    /// Registers `table` under `name`, returning the table previously registered
+    /// under this name, if any
+    pub fn register(
+        &self,
+        name: impl Into<String>,
+        table: Arc<MemTable>,
+    ) -> Option<Arc<MemTable>> {
+        self.tables.write().insert(name.into(), table)
+    }
+
+    /// Deregisters the table registered under `name`, returning it if any.
+    ///
+    /// The sessions the table is attached to keep it until they deregister it.
+    pub fn deregister(&self, name: &str) -> Option<Arc<MemTable>> {
+        self.tables.write().remove(name)
+    }
+
+    /// Returns the table registered under `name`, if any
+    pub fn get(&self, name: &str) -> Option<Arc<MemTable>> {
+        self.tables.read().get(name).cloned()
+    }
+
+    /// Returns the names of the registered tables
+    pub fn table_names(&self) -> Vec<String> {
+        self.tables.read().keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::datasource::TableProvider;
+    use crate::error::Result;
+    use crate::from_slice::FromSlice;
+    use crate::prelude::SessionContext;
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    #[tokio::test]
+    async fn attach_to_several_sessions() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice([1, 2, 3]))],
+        )?;
+        let table = Arc::new(MemTable::try_new(schema, vec![vec![batch]])?);
+
+        let registry = SharedTableRegistry::new();
+        assert!(registry.register("shared", table.clone()).is_none());
+        assert_eq!(registry.table_names(), vec!["shared".to_string()]);
+
+        let expected = vec!["+---+", "| a |", "+---+", "| 2 |", "| 3 |", "+---+"];
+        for _ in 0..2 {
+            let ctx = SessionContext::new();
+            ctx.register_table("t", registry.get("shared").unwrap())?;
+            let provider = ctx.table_provider("t").await?;
+            let provider = provider.as_any().downcast_ref::<MemTable>().unwrap();
+            assert!(std::ptr::eq(provider, table.as_ref()));
+
+            let batches = ctx
+                .sql("SELECT a FROM t WHERE a > 1")
+                .await?
+                .collect()
+                .await?;
+            assert_batches_sorted_eq!(expected, &batches);
+        }
+
+        assert!(registry.deregister("shared").is_some());
+        assert!(registry.get("shared").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_from_global_registry() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let table = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+        SharedTableRegistry::global().register("register_from_global_registry", table);
+
+        let ctx = SessionContext::new();
+        ctx.register_shared_table("t", "register_from_global_registry")?;
+        let batches = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        assert_eq!(batches[0].num_rows(), 1);
+
+        let err = ctx.register_shared_table("u", "missing").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: No shared table named missing"
+        );
+
+        SharedTableRegistry::global().deregister("register_from_global_registry");
+        Ok(())
+    }
+}
@@ -18,30 +18,41 @@
 //! SessionContext contains methods for registering data sources and executing queries
 use crate::{
     catalog::catalog::{CatalogList, MemoryCatalogList},
+    datasource::analyzed::{compute_table_statistics, AnalyzedTables},
+    datasource::function::TableFunctionImpl,
     datasource::listing::{ListingOptions, ListingTable},
+    datasource::shared::SharedTableRegistry,
     datasource::{MemTable, ViewTable},
     logical_expr::{PlanType, ToStringifiedPlan},
-    optimizer::optimizer::Optimizer,
+    optimizer::{
+        analyzer::Analyzer,
+        optimizer::Optimizer,
+        policy::{ApplyPolicies, PolicyProvider},
+    },
     physical_optimizer::{
         aggregate_statistics::AggregateStatistics, join_selection::JoinSelection,
         optimizer::PhysicalOptimizerRule,
     },
 };
+use datafusion_physical_expr::evaluator::EvaluatorSelector;
 pub use datafusion_physical_expr::execution_props::ExecutionProps;
 use datafusion_physical_expr::var_provider::is_system_variables;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{
     any::{Any, TypeId},
     hash::{BuildHasherDefault, Hasher},
     string::String,
 };
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fmt::Debug,
 };
 use std::{ops::ControlFlow, sync::Weak};
 
+use arrow::compute::{cast, concat};
 use arrow::datatypes::{DataType, SchemaRef};
 use arrow::record_batch::RecordBatch;
 
@@ -56,20 +67,34 @@ use crate::datasource::{
 };
 use crate::error::{DataFusionError, Result};
 use crate::logical_expr::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateMemoryTable,
-    CreateView, DropTable, DropView, Explain, LogicalPlan, LogicalPlanBuilder,
-    SetVariable, TableSource, TableType, UNNAMED_TABLE,
+    AnalyzeTable, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+    CreateFunction, CreateMemoryTable, CreateView, DropFunction, DropTable, DropView,
+    Explain, Expr, LogicalPlan, LogicalPlanBuilder, SetVariable, TableSource, TableType,
+    Transaction as PlanTransaction, TransactionStatement, UNNAMED_TABLE,
 };
-use crate::optimizer::OptimizerRule;
+use crate::optimizer::{AnalyzerRule, OptimizerRule};
 use datafusion_sql::{ResolvedTableReference, TableReference};
 
+use crate::physical_optimizer::adaptive_partitions::AdaptivePartitions;
 use crate::physical_optimizer::coalesce_batches::CoalesceBatches;
 use crate::physical_optimizer::repartition::Repartition;
 
 use crate::config::ConfigOptions;
+use crate::execution::plan_cache::{
+    config_fingerprint, rules_fingerprint, PlanCache, PlanCacheKey, PlanDependencies,
+    ReferencedTables,
+};
+use crate::execution::planning_metrics::PlanningMetrics;
+use crate::execution::transaction::{Transaction, TransactionManager};
 use crate::execution::{runtime_env::RuntimeEnv, FunctionRegistry};
 use crate::physical_optimizer::dist_enforcement::EnforceDistribution;
-use crate::physical_plan::file_format::{plan_to_csv, plan_to_json, plan_to_parquet};
+use crate::physical_optimizer::exchange::{ExchangeProvider, ReplaceExchanges};
+use crate::physical_optimizer::offload::{AcceleratorProvider, OffloadToAccelerator};
+use crate::physical_plan::expressions::{SubqueryId, SubqueryResult};
+use crate::physical_plan::file_format::{
+    plan_to_arrow, plan_to_csv, plan_to_json, plan_to_parquet,
+};
+use crate::physical_plan::joins::utils::OnceFut;
 use crate::physical_plan::planner::DefaultPhysicalPlanner;
 use crate::physical_plan::udaf::AggregateUDF;
 use crate::physical_plan::udf::ScalarUDF;
@@ -78,28 +103,43 @@ use crate::physical_plan::PhysicalPlanner;
 use crate::variable::{VarProvider, VarType};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use datafusion_common::ScalarValue;
+use datafusion_common::{ScalarValue, Statistics};
 use datafusion_sql::{
-    parser::DFParser,
-    planner::{ContextProvider, SqlToRel},
+    diagnostic::{locate_diagnostic, offset_diagnostic, statement_ranges},
+    parser::{DFParser, Statement as DFStatement},
+    planner::{ContextProvider, ParserOptions, SqlToRel},
 };
+use futures::stream::{BoxStream, StreamExt};
+use itertools::Itertools;
 use parquet::file::properties::WriterProperties;
+use sqlparser::dialect::{
+    AnsiDialect, BigQueryDialect, ClickHouseDialect, Dialect, GenericDialect,
+    HiveDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, RedshiftSqlDialect,
+    SQLiteDialect, SnowflakeDialect,
+};
 use url::Url;
 
 use crate::catalog::information_schema::{InformationSchemaProvider, INFORMATION_SCHEMA};
 use crate::catalog::listing_schema::ListingSchemaProvider;
-use crate::datasource::object_store::ObjectStoreUrl;
+use crate::datasource::object_store::{LimitedObjectStore, ObjectStoreUrl};
 use crate::execution::memory_pool::MemoryPool;
+use crate::physical_optimizer::cte_materialization::CteMaterialization;
 use crate::physical_optimizer::global_sort_selection::GlobalSortSelection;
+use crate::physical_optimizer::limit_pushdown::LimitPushdown;
+use crate::physical_optimizer::output_batch_size::OutputBatchSize;
+use crate::physical_optimizer::partial_sort_selection::PartialSortSelection;
 use crate::physical_optimizer::pipeline_checker::PipelineChecker;
 use crate::physical_optimizer::pipeline_fixer::PipelineFixer;
 use crate::physical_optimizer::sort_enforcement::EnforceSorting;
-use datafusion_optimizer::OptimizerConfig;
+use datafusion_optimizer::{OptimizerConfig, RuleOutcome};
 use datafusion_sql::planner::object_name_to_table_reference;
+use object_store::ObjectStore;
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 
 use super::options::{
-    AvroReadOptions, CsvReadOptions, NdJsonReadOptions, ParquetReadOptions,
+    ArrowReadOptions, AvroReadOptions, CsvReadOptions, NdJsonReadOptions,
+    ParquetReadOptions,
 };
 
 /// SessionContext is the main interface for executing queries with DataFusion. It stands for
@@ -245,8 +285,80 @@ impl SessionContext {
     /// does not mutate the state based on such statements.
     pub async fn sql(&self, sql: &str) -> Result<DataFrame> {
         // create a query planner
-        let plan = self.state().create_logical_plan(sql).await?;
+        let planning_metrics = Arc::new(PlanningMetrics::new());
+        let plan = self
+            .state()
+            .with_planning_metrics(planning_metrics.clone())
+            .create_logical_plan(sql)
+            .await?;
+        let df = self.execute_logical_plan(plan).await?;
+        Ok(df.with_planning_metrics(planning_metrics))
+    }
 
+    /// Runs a SQL script of several statements separated by `;`, one after
+    /// the other, and returns a stream of the [`DataFrame`] of each statement.
+    ///
+    /// The statements share the state of this session: a statement is only
+    /// planned once the [`DataFrame`] of the previous one is returned, so
+    /// that it sees the tables, views and settings created by the DDL before
+    /// it, like [`SessionContext::sql`] does for a single statement. The
+    /// [`DataFrame`]s of the queries are lazy, and should be executed before
+    /// polling the next statement to see the state at their point of the
+    /// script.
+    ///
+    /// The stream ends after the first statement that fails to plan.
+    ///
+    /// ```
+    /// use datafusion::prelude::*;
+    /// use futures::StreamExt;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let mut results = ctx.sql_script(
+    ///     "CREATE TABLE t AS VALUES (1), (2); SELECT * FROM t; SELECT count(*) FROM t",
+    /// )?;
+    /// while let Some(df) = results.next().await {
+    ///     let batches = df?.collect().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sql_script(&self, sql: &str) -> Result<BoxStream<'static, Result<DataFrame>>> {
+        let state = self.state();
+        let statements = state.sql_to_statements(sql)?;
+        // the diagnostics of a statement are located in its own text, and
+        // then moved to their position in the script
+        let ranges = dialect_from_str(&state.config_options().sql_parser.dialect)
+            .ok()
+            .and_then(|dialect| statement_ranges(sql, dialect.as_ref()))
+            .filter(|ranges| ranges.len() == statements.len())
+            .unwrap_or_else(|| vec![0..sql.len(); statements.len()]);
+        let statements: VecDeque<_> = statements.into_iter().zip(ranges).collect();
+
+        let script = Some((self.clone(), statements, sql.to_string()));
+        let stream = futures::stream::unfold(script, |script| async move {
+            let (ctx, mut statements, sql) = script?;
+            let (statement, range) = statements.pop_front()?;
+            let plan = ctx
+                .state()
+                .statement_to_plan(statement, &sql[range.clone()])
+                .await
+                .map_err(|e| offset_diagnostic(e, range.start));
+            let result = match plan {
+                Ok(plan) => ctx.execute_logical_plan(plan).await,
+                Err(e) => Err(e),
+            };
+            let script = result.is_ok().then_some((ctx, statements, sql));
+            Some((result, script))
+        });
+        Ok(stream.boxed())
+    }
+
+    /// Creates a [`DataFrame`] that will execute the logical plan of a SQL
+    /// statement, after applying it to this session if it is a DDL statement,
+    /// as described in [`SessionContext::sql`].
+    pub async fn execute_logical_plan(&self, plan: LogicalPlan) -> Result<DataFrame> {
         match plan {
             LogicalPlan::CreateExternalTable(cmd) => {
                 self.create_external_table(&cmd).await
@@ -257,37 +369,36 @@ impl SessionContext {
                 input,
                 if_not_exists,
                 or_replace,
+                temporary,
             }) => {
                 let input = Arc::try_unwrap(input).unwrap_or_else(|e| e.as_ref().clone());
-                let table = self.table(&name).await;
-
-                match (if_not_exists, or_replace, table) {
-                    (true, false, Ok(_)) => self.return_empty_dataframe(),
-                    (false, true, Ok(_)) => {
-                        self.deregister_table(&name)?;
-                        let schema = Arc::new(input.schema().as_ref().into());
-                        let physical = DataFrame::new(self.state(), input);
-
-                        let batches: Vec<_> = physical.collect_partitioned().await?;
-                        let table = Arc::new(MemTable::try_new(schema, batches)?);
-
-                        self.register_table(&name, table)?;
+                // a temporary table may shadow a table of the same name of the
+                // catalog, which is left untouched, and the other way around
+                let schema = if temporary {
+                    self.state.read().temporary_schema()
+                } else {
+                    self.state.read().schema_for_ref(&name)?
+                };
+                let table_name = name.as_table_reference().table().to_owned();
+                let exists = schema.table_exist(&table_name);
+
+                match (if_not_exists, or_replace, exists) {
+                    (true, false, true) => self.return_empty_dataframe(),
+                    (false, true, true) => {
+                        schema.deregister_table(&table_name)?;
+                        let table = self.create_memory_table(input).await?;
+                        schema.register_table(table_name, table)?;
                         self.return_empty_dataframe()
                     }
-                    (true, true, Ok(_)) => Err(DataFusionError::Execution(
+                    (true, true, true) => Err(DataFusionError::Execution(
                         "'IF NOT EXISTS' cannot coexist with 'REPLACE'".to_string(),
                     )),
-                    (_, _, Err(_)) => {
-                        let schema = Arc::new(input.schema().as_ref().into());
-                        let physical = DataFrame::new(self.state(), input);
-
-                        let batches: Vec<_> = physical.collect_partitioned().await?;
-                        let table = Arc::new(MemTable::try_new(schema, batches)?);
-
-                        self.register_table(&name, table)?;
+                    (_, _, false) => {
+                        let table = self.create_memory_table(input).await?;
+                        schema.register_table(table_name, table)?;
                         self.return_empty_dataframe()
                     }
-                    (false, false, Ok(_)) => Err(DataFusionError::Execution(format!(
+                    (false, false, true) => Err(DataFusionError::Execution(format!(
                         "Table '{name}' already exists"
                     ))),
                 }
@@ -299,10 +410,15 @@ impl SessionContext {
                 or_replace,
                 definition,
             }) => {
-                let view = self.table(&name).await;
-
-                match (or_replace, view) {
-                    (true, Ok(_)) => {
+                // a temporary table of the same name is left untouched
+                let exists = self
+                    .state
+                    .read()
+                    .schema_for_ref(&name)?
+                    .table_exist(name.as_table_reference().table());
+
+                match (or_replace, exists) {
+                    (true, true) => {
                         self.deregister_table(&name)?;
                         let table =
                             Arc::new(ViewTable::try_new((*input).clone(), definition)?);
@@ -310,14 +426,14 @@ impl SessionContext {
                         self.register_table(&name, table)?;
                         self.return_empty_dataframe()
                     }
-                    (_, Err(_)) => {
+                    (_, false) => {
                         let table =
                             Arc::new(ViewTable::try_new((*input).clone(), definition)?);
 
                         self.register_table(&name, table)?;
                         self.return_empty_dataframe()
                     }
-                    (false, Ok(_)) => Err(DataFusionError::Execution(format!(
+                    (false, true) => Err(DataFusionError::Execution(format!(
                         "Table '{name}' already exists"
                     ))),
                 }
@@ -349,6 +465,42 @@ impl SessionContext {
                 }
             }
 
+            LogicalPlan::AnalyzeTable(AnalyzeTable { name, columns, .. }) => {
+                self.analyze_table(&name, columns.as_deref()).await?;
+                self.return_empty_dataframe()
+            }
+
+            LogicalPlan::CreateFunction(cmd) => {
+                self.create_function(cmd).await?;
+                self.return_empty_dataframe()
+            }
+
+            LogicalPlan::Transaction(PlanTransaction { statement, .. }) => {
+                self.execute_transaction_statement(statement).await?;
+                self.return_empty_dataframe()
+            }
+
+            LogicalPlan::DropFunction(DropFunction {
+                name, if_exists, ..
+            }) => {
+                let dropped = match self.state().schema_for_function(&name)? {
+                    Some((schema, function)) => schema.deregister_function(function)?,
+                    None => {
+                        let mut state = self.state.write();
+                        let scalar = state.scalar_functions.remove(&name);
+                        let aggregate = state.aggregate_functions.remove(&name);
+                        scalar.is_some() || aggregate.is_some()
+                    }
+                };
+
+                match (dropped, if_exists) {
+                    (true, _) | (_, true) => self.return_empty_dataframe(),
+                    (false, false) => Err(DataFusionError::Execution(format!(
+                        "Function '{name}' doesn't exist."
+                    ))),
+                }
+            }
+
             LogicalPlan::SetVariable(SetVariable {
                 variable, value, ..
             }) => {
@@ -436,6 +588,136 @@ impl SessionContext {
         }
     }
 
+    /// Computes `input` into a new [`MemTable`]
+    async fn create_memory_table(
+        &self,
+        input: LogicalPlan,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let schema = Arc::new(input.schema().as_ref().into());
+        let physical = DataFrame::new(self.state(), input);
+
+        let batches: Vec<_> = physical.collect_partitioned().await?;
+        Ok(Arc::new(MemTable::try_new(schema, batches)?))
+    }
+
+    /// Creates the function defined by `cmd` with the [`FunctionFactory`] of
+    /// this session and registers it
+    async fn create_function(&self, cmd: CreateFunction) -> Result<()> {
+        let state = self.state();
+        let factory = state.function_factory.clone().ok_or_else(|| {
+            DataFusionError::Execution(
+                "CREATE FUNCTION requires a FunctionFactory, see \
+                SessionState::with_function_factory"
+                    .to_string(),
+            )
+        })?;
+
+        let name = cmd.name.clone();
+        let schema = state.schema_for_function(&name)?;
+        let exists = match &schema {
+            Some((schema, function)) => {
+                schema.udf(function).is_some() || schema.udaf(function).is_some()
+            }
+            None => {
+                state.scalar_functions.contains_key(&name)
+                    || state.aggregate_functions.contains_key(&name)
+            }
+        };
+        if exists && !cmd.or_replace {
+            return Err(DataFusionError::Execution(format!(
+                "Function '{name}' already exists"
+            )));
+        }
+
+        let function = factory.create(&state, cmd).await?;
+        if let Some((schema, function_name)) = schema {
+            // the functions of a schema are registered under their unqualified name
+            schema.deregister_function(function_name)?;
+            match function {
+                RegisterFunction::Scalar(f) => {
+                    let mut f = f.as_ref().clone();
+                    f.name = function_name.to_string();
+                    schema.register_udf(Arc::new(f))?;
+                }
+                RegisterFunction::Aggregate(f) => {
+                    let mut f = f.as_ref().clone();
+                    f.name = function_name.to_string();
+                    schema.register_udaf(Arc::new(f))?;
+                }
+            }
+            return Ok(());
+        }
+
+        let mut state = self.state.write();
+        // a replaced function may be of a different kind
+        state.scalar_functions.remove(&name);
+        state.aggregate_functions.remove(&name);
+        match function {
+            RegisterFunction::Scalar(f) => {
+                state.scalar_functions.insert(name, f);
+            }
+            RegisterFunction::Aggregate(f) => {
+                state.aggregate_functions.insert(name, f);
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies a transaction control statement to the transaction in progress
+    /// in this session, started with its [`TransactionManager`]
+    async fn execute_transaction_statement(
+        &self,
+        statement: TransactionStatement,
+    ) -> Result<()> {
+        let state = self.state();
+        let manager = state.transaction_manager.clone().ok_or_else(|| {
+            DataFusionError::NotImplemented(format!(
+                "Transactions are not supported by this session: {statement} \
+                requires a TransactionManager, see SessionState::with_transaction_manager"
+            ))
+        })?;
+        let no_transaction = || {
+            DataFusionError::Execution(format!(
+                "{statement} requires a transaction in progress"
+            ))
+        };
+
+        match &statement {
+            TransactionStatement::Begin => {
+                if state.transaction.is_some() {
+                    return Err(DataFusionError::Execution(
+                        "There is already a transaction in progress".to_string(),
+                    ));
+                }
+                let transaction = manager.begin(&state).await?;
+                self.state.write().transaction = Some(transaction);
+                Ok(())
+            }
+            TransactionStatement::Commit | TransactionStatement::Rollback => {
+                // the transaction ends even if it fails to commit or roll back
+                let transaction = self.state.write().transaction.take();
+                let transaction = transaction.ok_or_else(no_transaction)?;
+                if statement == TransactionStatement::Commit {
+                    transaction.commit().await
+                } else {
+                    transaction.rollback().await
+                }
+            }
+            TransactionStatement::Savepoint(name) => {
+                let transaction = state.transaction.ok_or_else(no_transaction)?;
+                transaction.savepoint(name).await
+            }
+            TransactionStatement::RollbackToSavepoint(name) => {
+                let transaction = state.transaction.ok_or_else(no_transaction)?;
+                transaction.rollback_to_savepoint(name).await
+            }
+            TransactionStatement::ReleaseSavepoint(name) => {
+                let transaction = state.transaction.ok_or_else(no_transaction)?;
+                transaction.release_savepoint(name).await
+            }
+        }
+    }
+
     // return an empty dataframe
     fn return_empty_dataframe(&self) -> Result<DataFrame> {
         let plan = LogicalPlanBuilder::empty(false).build()?;
@@ -489,6 +771,17 @@ impl SessionContext {
         table_type: TableType,
     ) -> Result<bool> {
         let table_ref = table_ref.into();
+        // an unqualified name refers to the temporary table of that name first
+        if let TableReference::Bare { table } = table_ref {
+            let temporary_schema = self.state.read().temporary_schema();
+            if let Some(table_provider) = temporary_schema.table(table).await {
+                if table_provider.table_type() == table_type {
+                    temporary_schema.deregister_table(table)?;
+                    return Ok(true);
+                }
+            }
+        }
+
         let maybe_schema = {
             let state = self.state.read();
             let resolved = state.resolve_table_ref(table_ref);
@@ -510,6 +803,35 @@ impl SessionContext {
         Ok(false)
     }
 
+    /// Computes the statistics of the given table by scanning it, as done by
+    /// `ANALYZE TABLE`, and stores them in the session so that they are used
+    /// by the cost based optimizer rules. Column statistics are
+    /// only computed for the given `columns`, or for all columns if `None`.
+    ///
+    /// Returns the computed statistics.
+    pub async fn analyze_table<'a>(
+        &self,
+        table_ref: impl Into<TableReference<'a>>,
+        columns: Option<&[String]>,
+    ) -> Result<Statistics> {
+        let table_ref = table_ref.into();
+        let table = self.table_provider(table_ref).await?;
+        if table.table_type() == TableType::View {
+            return Err(DataFusionError::Plan(format!(
+                "Cannot analyze view '{}'",
+                table_ref.table()
+            )));
+        }
+
+        let statistics =
+            compute_table_statistics(&self.state(), Arc::clone(&table), columns).await?;
+        self.state
+            .write()
+            .analyzed_tables
+            .insert(&table, statistics.clone())?;
+        Ok(statistics)
+    }
+
     /// Registers a variable provider within this context.
     pub fn register_variable(
         &self,
@@ -550,6 +872,18 @@ impl SessionContext {
             .insert(f.name.clone(), Arc::new(f));
     }
 
+    /// Registers a table function within this context, called in the
+    /// `FROM` clause of SQL queries, such as `SELECT * FROM name(1, 'a')`.
+    ///
+    /// Like UDFs, table function names are looked up using lowercase unless
+    /// the query uses quotes.
+    pub fn register_udtf(&self, name: &str, fun: Arc<dyn TableFunctionImpl>) {
+        self.state
+            .write()
+            .table_functions
+            .insert(name.to_owned(), fun);
+    }
+
     /// Creates a [`DataFrame`] for reading an Avro data source.
     pub async fn read_avro(
         &self,
@@ -583,6 +917,39 @@ impl SessionContext {
         self.read_table(Arc::new(provider))
     }
 
+    /// Creates a [`DataFrame`] for reading an Arrow IPC data source.
+    pub async fn read_arrow(
+        &self,
+        table_path: impl AsRef<str>,
+        options: ArrowReadOptions<'_>,
+    ) -> Result<DataFrame> {
+        let table_path = ListingTableUrl::parse(table_path)?;
+        let target_partitions = self.copied_config().target_partitions();
+
+        let listing_options = options.to_listing_options(target_partitions);
+
+        let resolved_schema = match (options.schema, options.infinite) {
+            (Some(s), _) => Arc::new(s.to_owned()),
+            (None, false) => {
+                listing_options
+                    .infer_schema(&self.state(), &table_path)
+                    .await?
+            }
+            (None, true) => {
+                return Err(DataFusionError::Plan(
+                    "Schema inference for infinite data sources is not supported."
+                        .to_string(),
+                ))
+            }
+        };
+
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(listing_options)
+            .with_schema(resolved_schema);
+        let provider = ListingTable::try_new(config)?;
+        self.read_table(Arc::new(provider))
+    }
+
     /// Creates a [`DataFrame`] for reading an Json data source.
     pub async fn read_json(
         &self,
@@ -816,6 +1183,28 @@ impl SessionContext {
         Ok(())
     }
 
+    /// Registers an Arrow IPC file as a table that can be referenced from
+    /// SQL statements executed against this context.
+    pub async fn register_arrow(
+        &self,
+        name: &str,
+        table_path: &str,
+        options: ArrowReadOptions<'_>,
+    ) -> Result<()> {
+        let listing_options =
+            options.to_listing_options(self.copied_config().target_partitions());
+
+        self.register_listing_table(
+            name,
+            table_path,
+            listing_options,
+            options.schema.map(|s| Arc::new(s.to_owned())),
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Registers a named catalog using a custom `CatalogProvider` so that
     /// it can be referenced from SQL statements executed against this
     /// context.
@@ -861,6 +1250,24 @@ impl SessionContext {
             .register_table(table_ref.table().to_owned(), provider)
     }
 
+    /// Registers the table named `shared_name` in the global
+    /// [`SharedTableRegistry`] under `table_ref` in this context, without
+    /// copying its data.
+    ///
+    /// Returns the provider previously registered under `table_ref`, if any
+    pub fn register_shared_table<'a>(
+        &'a self,
+        table_ref: impl Into<TableReference<'a>>,
+        shared_name: &str,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        let table = SharedTableRegistry::global()
+            .get(shared_name)
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!("No shared table named {shared_name}"))
+            })?;
+        self.register_table(table_ref, table)
+    }
+
     /// Deregisters the given table.
     ///
     /// Returns the registered provider, if any
@@ -875,7 +1282,8 @@ impl SessionContext {
             .deregister_table(table_ref.table())
     }
 
-    /// Return true if the specified table exists in the schema provider.
+    /// Return true if the specified table exists in the schema provider,
+    /// or is a temporary table of this session.
     pub fn table_exist<'a>(
         &'a self,
         table_ref: impl Into<TableReference<'a>>,
@@ -884,7 +1292,7 @@ impl SessionContext {
         Ok(self
             .state
             .read()
-            .schema_for_ref(table_ref)?
+            .schema_for_table(table_ref)?
             .table_exist(table_ref.table()))
     }
 
@@ -911,12 +1319,15 @@ impl SessionContext {
     }
 
     /// Return a [`TableProvider`] for the specified table.
+    ///
+    /// An unqualified name refers to the temporary table of that name, if
+    /// any, before the tables of the default schema.
     pub async fn table_provider<'a>(
         &self,
         table_ref: impl Into<TableReference<'a>>,
     ) -> Result<Arc<dyn TableProvider>> {
         let table_ref = table_ref.into();
-        let schema = self.state.read().schema_for_ref(table_ref)?;
+        let schema = self.state.read().schema_for_table(table_ref)?;
         match schema.table(table_ref.table()).await {
             Some(ref provider) => Ok(Arc::clone(provider)),
             _ => Err(DataFusionError::Plan(format!(
@@ -997,6 +1408,16 @@ impl SessionContext {
         plan_to_parquet(&state, plan, path, writer_properties).await
     }
 
+    /// Executes a query and writes the results to a partitioned Arrow IPC file.
+    pub async fn write_arrow(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        path: impl AsRef<str>,
+    ) -> Result<()> {
+        let state = self.state.read().clone();
+        plan_to_arrow(&state, plan, path).await
+    }
+
     /// Get a new TaskContext to run in this session
     pub fn task_ctx(&self) -> Arc<TaskContext> {
         Arc::new(TaskContext::from(self))
@@ -1046,6 +1467,29 @@ pub trait QueryPlanner {
     ) -> Result<Arc<dyn ExecutionPlan>>;
 }
 
+/// A function created by a [`FunctionFactory`]
+pub enum RegisterFunction {
+    /// A scalar function
+    Scalar(Arc<ScalarUDF>),
+    /// An aggregate function
+    Aggregate(Arc<AggregateUDF>),
+}
+
+/// Turns the definition of a `CREATE FUNCTION` statement into a user defined
+/// function, for example by interpreting it as SQL or by compiling it.
+///
+/// The function is registered under the name given in the statement, and is
+/// removed again by `DROP FUNCTION`.
+#[async_trait]
+pub trait FunctionFactory: Sync + Send {
+    /// Create the function defined by `statement`
+    async fn create(
+        &self,
+        state: &SessionState,
+        statement: CreateFunction,
+    ) -> Result<RegisterFunction>;
+}
+
 /// The query planner used if no user defined planner is provided
 struct DefaultQueryPlanner {}
 
@@ -1152,7 +1596,7 @@ impl SessionConfig {
 
     /// Set a generic `str` configuration option
     pub fn set_str(self, key: &str, value: &str) -> Self {
-        self.set(key, ScalarValue::Utf8(Some(value.to_string())))
+        self.set(key, ScalarValue::Utf8(Some(value.into())))
     }
 
     /// Customize batch size
@@ -1264,11 +1708,50 @@ impl SessionConfig {
         self
     }
 
+    /// Sets the SQL dialect used to parse statements, like `mysql` or `mssql`
+    pub fn with_sql_dialect(mut self, dialect: &str) -> Self {
+        self.options.sql_parser.dialect = dialect.to_string();
+        self
+    }
+
+    /// Enables or disables the conversion of unquoted identifiers to lowercase
+    pub fn with_ident_normalization(mut self, enabled: bool) -> Self {
+        self.options.sql_parser.enable_ident_normalization = enabled;
+        self
+    }
+
+    /// Enables or disables the suggestion of valid names in the diagnostics
+    /// of planning errors about unknown names
+    pub fn with_suggestions(mut self, enabled: bool) -> Self {
+        self.options.sql_parser.enable_suggestions = enabled;
+        self
+    }
+
+    /// Sets the placement of nulls in ORDER BY clauses without NULLS FIRST or
+    /// NULLS LAST, like `nulls_first` or `nulls_min`
+    pub fn with_default_null_ordering(mut self, null_ordering: &str) -> Self {
+        self.options.sql_parser.default_null_ordering = null_ordering.to_string();
+        self
+    }
+
     /// Get the currently configured batch size
     pub fn batch_size(&self) -> usize {
         self.options.execution.batch_size
     }
 
+    /// Customize the number of rows of the batches of the query results
+    pub fn with_output_batch_size(mut self, n: usize) -> Self {
+        // output batch size must be greater than zero
+        assert!(n > 0);
+        self.options.execution.output_batch_size = Some(n);
+        self
+    }
+
+    /// Get the configured number of rows of the batches of the query results, if any
+    pub fn output_batch_size(&self) -> Option<usize> {
+        self.options.execution.output_batch_size
+    }
+
     /// Convert configuration options to name-value pairs with values
     /// converted to strings.
     ///
@@ -1383,6 +1866,8 @@ impl From<ConfigOptions> for SessionConfig {
 pub struct SessionState {
     /// Uuid for the session
     session_id: String,
+    /// Responsible for rewriting a logical plan before it is optimized
+    analyzer: Analyzer,
     /// Responsible for optimizing a logical plan
     optimizer: Optimizer,
     /// Responsible for optimizing a physical execution plan
@@ -1395,12 +1880,28 @@ pub struct SessionState {
     scalar_functions: HashMap<String, Arc<ScalarUDF>>,
     /// Aggregate functions registered in the context
     aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
+    /// Table functions registered in the context
+    table_functions: HashMap<String, Arc<dyn TableFunctionImpl>>,
+    /// Creates the functions defined by `CREATE FUNCTION` statements
+    function_factory: Option<Arc<dyn FunctionFactory>>,
+    /// Starts the transactions of `BEGIN` statements
+    transaction_manager: Option<Arc<dyn TransactionManager>>,
+    /// The transaction in progress, if any
+    transaction: Option<Arc<dyn Transaction>>,
+    /// The temporary tables of the session, dropped with it
+    temporary_schema: Arc<dyn SchemaProvider>,
     /// Session configuration
     config: SessionConfig,
     /// Execution properties
     execution_props: ExecutionProps,
     /// Runtime environment
     runtime_env: Arc<RuntimeEnv>,
+    /// Records the time spent planning, if any
+    planning_metrics: Option<Arc<PlanningMetrics>>,
+    /// Caches the plans of the SQL queries, if any
+    plan_cache: Option<Arc<PlanCache>>,
+    /// The statistics computed by `ANALYZE TABLE`
+    analyzed_tables: AnalyzedTables,
 }
 
 impl Debug for SessionState {
@@ -1478,9 +1979,30 @@ impl SessionState {
             // Note that one should always run this rule after running the EnforceDistribution rule
             // as the latter may break local sorting requirements.
             Arc::new(EnforceSorting::new()),
+            // The PartialSortSelection rule replaces the sorts whose input is already sorted on
+            // a prefix of the sort expressions with partial sorts. It runs after EnforceSorting,
+            // which adds the sorts and removes the unnecessary ones.
+            Arc::new(PartialSortSelection::new()),
+            // The LimitPushdown rule pushes the skip and fetch of the global limits into the
+            // merges of sorted partitions under them. It runs once all the sorts and merges
+            // are added by the rules above.
+            Arc::new(LimitPushdown::new()),
+            // The AdaptivePartitions rule changes the number of partitions of all the
+            // repartitions to `target_partitions` alike, so it runs once all of them are
+            // added by the rules above, and doesn't change the distribution requirements
+            // they satisfy.
+            Arc::new(AdaptivePartitions::new()),
             // The CoalesceBatches rule will not influence the distribution and ordering of the
             // whole plan tree. Therefore, to avoid influencing other rules, it should run last.
             Arc::new(CoalesceBatches::new()),
+            // The CteMaterialization rule shares the results of the common table expressions
+            // referenced several times, if all the rules above optimized their copies alike,
+            // and inlines the others. It runs after all the rules that change the copies.
+            Arc::new(CteMaterialization::new()),
+            // The OutputBatchSize rule re-chunks the output of the whole plan to the
+            // `output_batch_size` of the session, if any, so it runs after all the rules
+            // that change the plan.
+            Arc::new(OutputBatchSize::new()),
             // The PipelineChecker rule will reject non-runnable query plans that use
             // pipeline-breaking operators on infinite input(s). The rule generates a
             // diagnostic error message when this happens. It makes no changes to the
@@ -1490,15 +2012,24 @@ impl SessionState {
 
         SessionState {
             session_id,
+            analyzer: Analyzer::new(),
             optimizer: Optimizer::new(),
             physical_optimizers,
             query_planner: Arc::new(DefaultQueryPlanner {}),
             catalog_list,
             scalar_functions: HashMap::new(),
+            table_functions: HashMap::new(),
             aggregate_functions: HashMap::new(),
+            function_factory: None,
+            transaction_manager: None,
+            transaction: None,
+            temporary_schema: Arc::new(MemorySchemaProvider::new()),
             config,
             execution_props: ExecutionProps::new(),
             runtime_env: runtime,
+            planning_metrics: None,
+            plan_cache: None,
+            analyzed_tables: AnalyzedTables::default(),
         }
     }
 
@@ -1557,6 +2088,39 @@ impl SessionState {
             .resolve(&catalog.default_catalog, &catalog.default_schema)
     }
 
+    /// Returns the schema of the function `name` and its unqualified name, if
+    /// it is qualified by a schema, such as `my_schema.my_func`
+    fn schema_for_function<'a>(
+        &self,
+        name: &'a str,
+    ) -> Result<Option<(Arc<dyn SchemaProvider>, &'a str)>> {
+        let function_ref = TableReference::parse_str(name);
+        match function_ref {
+            TableReference::Bare { .. } => Ok(None),
+            _ => Ok(Some((
+                self.schema_for_ref(function_ref)?,
+                function_ref.table(),
+            ))),
+        }
+    }
+
+    /// Returns the schema of the table `table_ref` refers to: the temporary
+    /// schema of this session if it is the unqualified name of a temporary
+    /// table, which shadows the tables of the same name, and the schema it
+    /// resolves to otherwise
+    fn schema_for_table<'a>(
+        &'a self,
+        table_ref: impl Into<TableReference<'a>>,
+    ) -> Result<Arc<dyn SchemaProvider>> {
+        let table_ref = table_ref.into();
+        if let TableReference::Bare { table } = table_ref {
+            if self.temporary_schema.table_exist(table) {
+                return Ok(self.temporary_schema.clone());
+            }
+        }
+        self.schema_for_ref(table_ref)
+    }
+
     fn schema_for_ref<'a>(
         &'a self,
         table_ref: impl Into<TableReference<'a>>,
@@ -1594,6 +2158,15 @@ impl SessionState {
         self
     }
 
+    /// Replace the analyzer rules
+    pub fn with_analyzer_rules(
+        mut self,
+        rules: Vec<Arc<dyn AnalyzerRule + Send + Sync>>,
+    ) -> Self {
+        self.analyzer = Analyzer::with_rules(rules);
+        self
+    }
+
     /// Replace the optimizer rules
     pub fn with_optimizer_rules(
         mut self,
@@ -1612,6 +2185,102 @@ impl SessionState {
         self
     }
 
+    /// Adds a new [`AnalyzerRule`], applied after the existing ones
+    pub fn add_analyzer_rule(
+        mut self,
+        analyzer_rule: Arc<dyn AnalyzerRule + Send + Sync>,
+    ) -> Self {
+        self.analyzer.rules.push(analyzer_rule);
+        self
+    }
+
+    /// Applies the row and column access policies of `user` to every query
+    /// of this session, after the [`AnalyzerRule`]s added so far
+    pub fn with_policy_provider(
+        self,
+        provider: Arc<dyn PolicyProvider>,
+        user: impl Into<String>,
+    ) -> Self {
+        self.add_analyzer_rule(Arc::new(ApplyPolicies::new(provider, user)))
+    }
+
+    /// Use `function_factory` to create the functions defined by
+    /// `CREATE FUNCTION` statements
+    pub fn with_function_factory(
+        mut self,
+        function_factory: Arc<dyn FunctionFactory>,
+    ) -> Self {
+        self.function_factory = Some(function_factory);
+        self
+    }
+
+    /// Returns the [`FunctionFactory`] of this session, if any
+    pub fn function_factory(&self) -> Option<&Arc<dyn FunctionFactory>> {
+        self.function_factory.as_ref()
+    }
+
+    /// Use `transaction_manager` to start the transactions of `BEGIN`
+    /// statements, without which the session doesn't support transactions
+    pub fn with_transaction_manager(
+        mut self,
+        transaction_manager: Arc<dyn TransactionManager>,
+    ) -> Self {
+        self.transaction_manager = Some(transaction_manager);
+        self
+    }
+
+    /// Returns the [`TransactionManager`] of this session, if any
+    pub fn transaction_manager(&self) -> Option<&Arc<dyn TransactionManager>> {
+        self.transaction_manager.as_ref()
+    }
+
+    /// Returns the transaction in progress in this session, if any
+    pub fn transaction(&self) -> Option<&Arc<dyn Transaction>> {
+        self.transaction.as_ref()
+    }
+
+    /// Record the time spent planning with this state in `planning_metrics`
+    pub fn with_planning_metrics(
+        mut self,
+        planning_metrics: Arc<PlanningMetrics>,
+    ) -> Self {
+        self.planning_metrics = Some(planning_metrics);
+        self
+    }
+
+    /// Returns the [`PlanningMetrics`] recording the time spent planning with
+    /// this state, if any
+    pub fn planning_metrics(&self) -> Option<&Arc<PlanningMetrics>> {
+        self.planning_metrics.as_ref()
+    }
+
+    /// Reuse the plans of the SQL queries planned before with `plan_cache`,
+    /// which may be shared with other sessions, see [`PlanCache`]
+    pub fn with_plan_cache(mut self, plan_cache: Arc<PlanCache>) -> Self {
+        self.plan_cache = Some(plan_cache);
+        self
+    }
+
+    /// Returns the [`PlanCache`] of this state, if any
+    pub fn plan_cache(&self) -> Option<&Arc<PlanCache>> {
+        self.plan_cache.as_ref()
+    }
+
+    /// Returns the statistics of the tables computed by `ANALYZE TABLE`
+    pub fn analyzed_tables(&self) -> &AnalyzedTables {
+        &self.analyzed_tables
+    }
+
+    /// Returns the schema of the temporary tables created in this session
+    /// by `CREATE TEMPORARY TABLE`, which only this session sees, and which
+    /// are dropped with it.
+    ///
+    /// A temporary table shadows the tables of the same name when referenced
+    /// by its unqualified name.
+    pub fn temporary_schema(&self) -> Arc<dyn SchemaProvider> {
+        self.temporary_schema.clone()
+    }
+
     /// Adds a new [`OptimizerRule`]
     pub fn add_optimizer_rule(
         mut self,
@@ -1630,22 +2299,226 @@ impl SessionState {
         self
     }
 
-    /// Creates a [`LogicalPlan`] from the provided SQL string
-    ///
-    /// See [`SessionContext::sql`] for a higher-level interface that also handles DDL
-    pub async fn create_logical_plan(&self, sql: &str) -> Result<LogicalPlan> {
-        use crate::catalog::information_schema::INFORMATION_SCHEMA_TABLES;
-        use datafusion_sql::parser::Statement as DFStatement;
-        use sqlparser::ast::*;
-        use std::collections::hash_map::Entry;
+    /// Replaces the repartitions of the physical plans with the exchanges
+    /// created by `provider`, for example to shuffle the rows of the plans
+    /// between the nodes of a distributed engine. See [`ReplaceExchanges`].
+    pub fn with_exchange_provider(self, provider: Arc<dyn ExchangeProvider>) -> Self {
+        self.add_physical_optimizer_rule(Arc::new(ReplaceExchanges::new(provider)))
+    }
+
+    /// Executes the subtrees of the physical plans claimed by `provider` on
+    /// its accelerator, falling back to the DataFusion operators for the rest
+    /// of the plans. See [`OffloadToAccelerator`].
+    pub fn with_accelerator_provider(
+        self,
+        provider: Arc<dyn AcceleratorProvider>,
+    ) -> Self {
+        self.add_physical_optimizer_rule(Arc::new(OffloadToAccelerator::new(provider)))
+    }
+
+    /// Evaluates the planned expressions with the backends selected by
+    /// `selector`, such as a code generation backend, instead of their
+    /// physical expressions. See [`datafusion_physical_expr::evaluator`].
+    pub fn with_evaluator_selector(mut self, selector: EvaluatorSelector) -> Self {
+        self.execution_props.set_evaluator_selector(selector);
+        self
+    }
+
+    /// Inserts `optimizer_rule` right before the [`PhysicalOptimizerRule`]
+    /// named `name`, such as one of the built-in rules, returning an error if
+    /// there is no such rule
+    pub fn insert_physical_optimizer_rule_before(
+        self,
+        name: &str,
+        optimizer_rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+    ) -> Result<Self> {
+        self.insert_physical_optimizer_rule(name, 0, optimizer_rule)
+    }
+
+    /// Inserts `optimizer_rule` right after the [`PhysicalOptimizerRule`] named
+    /// `name`, such as one of the built-in rules, returning an error if there
+    /// is no such rule
+    pub fn insert_physical_optimizer_rule_after(
+        self,
+        name: &str,
+        optimizer_rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+    ) -> Result<Self> {
+        self.insert_physical_optimizer_rule(name, 1, optimizer_rule)
+    }
+
+    fn insert_physical_optimizer_rule(
+        mut self,
+        name: &str,
+        offset: usize,
+        optimizer_rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+    ) -> Result<Self> {
+        let index = self
+            .physical_optimizers
+            .iter()
+            .position(|rule| rule.name() == name)
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
+                    "No physical optimizer rule named '{name}'"
+                ))
+            })?;
+        self.physical_optimizers
+            .insert(index + offset, optimizer_rule);
+        Ok(self)
+    }
+
+    /// Removes all [`AnalyzerRule`]s named `name`
+    pub fn remove_analyzer_rule(mut self, name: &str) -> Self {
+        self.analyzer.rules.retain(|rule| rule.name() != name);
+        self
+    }
+
+    /// Removes all [`OptimizerRule`]s named `name`, such as one of the
+    /// built-in rules
+    pub fn remove_optimizer_rule(mut self, name: &str) -> Self {
+        self.optimizer.rules.retain(|rule| rule.name() != name);
+        self
+    }
+
+    /// Removes all [`PhysicalOptimizerRule`]s named `name`, such as one of
+    /// the built-in rules
+    pub fn remove_physical_optimizer_rule(mut self, name: &str) -> Self {
+        self.physical_optimizers.retain(|rule| rule.name() != name);
+        self
+    }
+
+    /// Creates a [`LogicalPlan`] from the provided SQL string
+    ///
+    /// See [`SessionContext::sql`] for a higher-level interface that also handles DDL
+    ///
+    /// If this state has a [`PlanCache`], the plan of a query is looked up in
+    /// it before parsing the SQL, and cached after.
+    pub async fn create_logical_plan(&self, sql: &str) -> Result<LogicalPlan> {
+        let cache = match &self.plan_cache {
+            Some(cache) => cache,
+            None => return Ok(self.plan_sql(sql).await?.0),
+        };
+        let key = PlanCacheKey::new(sql, self.config_options());
+        if let Some((plan, dependencies)) = cache.get(&key) {
+            if self.dependencies_unchanged(&dependencies).await {
+                cache.record_hit();
+                return Ok(plan);
+            }
+            cache.remove(&key);
+        }
 
-        let mut statements = DFParser::parse_sql(sql)?;
+        let (plan, tables) = self.plan_sql(sql).await?;
+        let dependencies = PlanDependencies {
+            tables,
+            scalar_functions: self.scalar_functions.clone(),
+            aggregate_functions: self.aggregate_functions.clone(),
+            table_functions: self.table_functions.clone(),
+        };
+        cache.insert(key, plan.clone(), dependencies)?;
+        Ok(plan)
+    }
+
+    /// Returns true if the objects a cached plan was planned with are still
+    /// those of this state
+    async fn dependencies_unchanged(&self, dependencies: &PlanDependencies) -> bool {
+        fn same_functions<T: ?Sized>(
+            cached: &HashMap<String, Arc<T>>,
+            current: &HashMap<String, Arc<T>>,
+        ) -> bool {
+            cached.len() == current.len()
+                && cached.iter().all(|(name, f)| {
+                    current.get(name).map_or(false, |c| Arc::ptr_eq(f, c))
+                })
+        }
+
+        for (reference, table) in &dependencies.tables {
+            let current = self
+                .referenced_table(reference.as_table_reference())
+                .await
+                .map(|(table, _)| table);
+            let unchanged = match (table, &current) {
+                (Some(table), Some(current)) => Arc::ptr_eq(table, current),
+                (None, None) => true,
+                _ => false,
+            };
+            if !unchanged {
+                return false;
+            }
+        }
+        same_functions(&dependencies.scalar_functions, &self.scalar_functions)
+            && same_functions(
+                &dependencies.aggregate_functions,
+                &self.aggregate_functions,
+            )
+            && same_functions(&dependencies.table_functions, &self.table_functions)
+    }
+
+    /// Parses and plans the single statement of `sql`, returning its plan and
+    /// the tables it references
+    async fn plan_sql(&self, sql: &str) -> Result<(LogicalPlan, ReferencedTables)> {
+        let start = Instant::now();
+        let mut statements = self.sql_to_statements(sql)?;
+        if let Some(metrics) = &self.planning_metrics {
+            metrics.add_parsing(start.elapsed());
+        }
         if statements.len() != 1 {
             return Err(DataFusionError::NotImplemented(
-                "The context currently only supports a single SQL statement".to_string(),
+                "The context currently only supports a single SQL statement, \
+                see SessionContext::sql_script to run several statements"
+                    .to_string(),
             ));
         }
         let statement = statements.pop_front().unwrap();
+        let start = Instant::now();
+        let plan = self.plan_statement(statement, sql).await;
+        if let Some(metrics) = &self.planning_metrics {
+            metrics.add_logical_planning(start.elapsed());
+        }
+        plan
+    }
+
+    /// Parses the provided SQL string, which may contain several statements,
+    /// with the SQL dialect of this session
+    pub fn sql_to_statements(&self, sql: &str) -> Result<VecDeque<DFStatement>> {
+        let dialect = dialect_from_str(&self.config_options().sql_parser.dialect)?;
+        Ok(DFParser::parse_sql_with_dialect(sql, dialect.as_ref())?)
+    }
+
+    /// Creates a [`LogicalPlan`] from a statement parsed from the SQL string
+    /// `sql`, which is used to locate the errors in the diagnostics, and so
+    /// should only hold the text of this statement
+    pub async fn statement_to_plan(
+        &self,
+        statement: DFStatement,
+        sql: &str,
+    ) -> Result<LogicalPlan> {
+        Ok(self.plan_statement(statement, sql).await?.0)
+    }
+
+    /// Returns the provider of the table `reference` in a statement, and
+    /// whether it is a temporary table
+    async fn referenced_table(
+        &self,
+        reference: TableReference<'_>,
+    ) -> Option<(Arc<dyn TableProvider>, bool)> {
+        if let TableReference::Bare { table } = reference {
+            if let Some(temporary) = self.temporary_schema.table(table).await {
+                return Some((temporary, true));
+            }
+        }
+        let resolved = self.resolve_table_ref(reference);
+        let schema = self.schema_for_ref(resolved).ok()?;
+        let table = schema.table(resolved.table).await?;
+        Some((table, false))
+    }
+
+    async fn plan_statement(
+        &self,
+        statement: DFStatement,
+        sql: &str,
+    ) -> Result<(LogicalPlan, ReferencedTables)> {
+        use crate::catalog::information_schema::INFORMATION_SCHEMA_TABLES;
+        use sqlparser::ast::*;
+        use std::collections::hash_map::Entry;
 
         // Getting `TableProviders` is async but planing is not -- thus pre-fetch
         // table providers for all relations referenced in this query
@@ -1690,50 +2563,123 @@ impl SessionState {
                 relations
                     .get_or_insert_with(&table.table_name, |_| table.table_name.clone());
             }
+            DFStatement::AnalyzeTable(table) => {
+                relations
+                    .get_or_insert_with(&table.table_name, |_| table.table_name.clone());
+            }
+            DFStatement::CreateFunction(_)
+            | DFStatement::DropFunction(_)
+            | DFStatement::RollbackToSavepoint(_)
+            | DFStatement::ReleaseSavepoint(_) => {}
         }
 
-        // Always include information_schema if available
+        // Always include information_schema if available. Its tables are
+        // not referenced by the statement, so they are not returned.
+        let mut information_schema = vec![];
         if self.config.information_schema() {
             for s in INFORMATION_SCHEMA_TABLES {
-                relations.insert(ObjectName(vec![
-                    Ident::new(INFORMATION_SCHEMA),
-                    Ident::new(*s),
-                ]));
+                let relation =
+                    ObjectName(vec![Ident::new(INFORMATION_SCHEMA), Ident::new(*s)]);
+                if !relations.contains(&relation) {
+                    information_schema.push(relation);
+                }
             }
         }
 
         let mut provider = SessionContextProvider {
             state: self,
-            tables: HashMap::with_capacity(relations.len()),
+            tables: HashMap::with_capacity(relations.len() + information_schema.len()),
+            temporary_tables: HashMap::new(),
         };
 
-        for relation in relations {
-            let reference = object_name_to_table_reference(relation)?;
-            let resolved = self.resolve_table_ref(reference.as_table_reference());
-            if let Entry::Vacant(v) = provider.tables.entry(resolved.to_string()) {
-                if let Ok(schema) = self.schema_for_ref(resolved) {
-                    if let Some(table) = schema.table(resolved.table).await {
-                        v.insert(provider_as_source(table));
+        let enable_ident_normalization =
+            self.config_options().sql_parser.enable_ident_normalization;
+        let mut referenced_tables = Vec::with_capacity(relations.len());
+        let information_schema = information_schema
+            .into_iter()
+            .map(|relation| (relation, false));
+        let relations = relations
+            .into_iter()
+            .map(|relation| (relation, true))
+            .chain(information_schema);
+        for (relation, referenced) in relations {
+            let reference =
+                object_name_to_table_reference(relation, enable_ident_normalization)?;
+            let table = self.referenced_table(reference.as_table_reference()).await;
+            match &table {
+                Some((table, true)) => {
+                    provider.temporary_tables.insert(
+                        reference.as_table_reference().table().to_owned(),
+                        provider_as_source(table.clone()),
+                    );
+                }
+                Some((table, false)) => {
+                    let resolved = self.resolve_table_ref(reference.as_table_reference());
+                    if let Entry::Vacant(v) = provider.tables.entry(resolved.to_string())
+                    {
+                        v.insert(provider_as_source(table.clone()));
                     }
                 }
+                None => {}
+            }
+            if referenced {
+                referenced_tables.push((reference, table.map(|(table, _)| table)));
             }
         }
 
-        let query = SqlToRel::new(&provider);
-        query.statement_to_plan(statement)
+        let options = ParserOptions {
+            parse_float_as_decimal: false,
+            enable_lateral_column_alias: self
+                .config_options()
+                .sql_parser
+                .enable_lateral_column_alias,
+            enable_ident_normalization,
+        };
+        let plan = SqlToRel::new_with_options(&provider, options)
+            .statement_to_plan(statement)
+            .map_err(|e| {
+                match dialect_from_str(&self.config_options().sql_parser.dialect) {
+                    Ok(dialect) => locate_diagnostic(sql, dialect.as_ref(), e),
+                    Err(_) => e,
+                }
+            })?;
+        Ok((plan, referenced_tables))
     }
 
-    /// Optimizes the logical plan by applying optimizer rules.
+    /// Optimizes the logical plan by applying analyzer and optimizer rules.
+    ///
+    /// If this state has a [`PlanCache`], the optimized plans of its plans
+    /// are cached.
     pub fn optimize(&self, plan: &LogicalPlan) -> Result<LogicalPlan> {
         if let LogicalPlan::Explain(e) = plan {
             let mut stringified_plans = e.stringified_plans.clone();
 
-            // optimize the child plan, capturing the output of each optimizer
-            let plan = self.optimizer.optimize(
+            // analyze the child plan, capturing the output of each analyzer
+            let analyzed_plan = self.analyzer.analyze_timed(
                 e.plan.as_ref(),
+                self.config_options(),
+                |analyzed_plan, analyzer, elapsed| {
+                    self.record_analyzer_rule(analyzer.name(), elapsed);
+                    let analyzer_name = analyzer.name().to_string();
+                    let plan_type = PlanType::AnalyzedLogicalPlan { analyzer_name };
+                    stringified_plans.push(analyzed_plan.to_stringified(plan_type));
+                },
+            )?;
+
+            // optimize the child plan, capturing the output of each optimizer
+            let plan = self.optimizer.optimize_with_outcomes(
+                &analyzed_plan,
                 self,
-                |optimized_plan, optimizer| {
-                    let optimizer_name = optimizer.name().to_string();
+                |optimized_plan, optimizer, outcome| {
+                    let optimizer_name = match outcome {
+                        RuleOutcome::Applied(elapsed) => {
+                            self.record_optimizer_rule(optimizer.name(), *elapsed);
+                            optimizer.name().to_string()
+                        }
+                        RuleOutcome::Skipped(reason) => {
+                            format!("{} (skipped: {})", optimizer.name(), reason)
+                        }
+                    };
                     let plan_type = PlanType::OptimizedLogicalPlan { optimizer_name };
                     stringified_plans.push(optimized_plan.to_stringified(plan_type));
                 },
@@ -1746,7 +2692,51 @@ impl SessionState {
                 schema: e.schema.clone(),
             }))
         } else {
-            self.optimizer.optimize(plan, self, |_, _| {})
+            // the plans of the plan cache are optimized once
+            let cached = match &self.plan_cache {
+                Some(cache) => {
+                    let config = config_fingerprint(self.config_options());
+                    let rules = rules_fingerprint(&self.analyzer, &self.optimizer);
+                    if let Some(optimized) = cache.get_optimized(plan, config, rules)? {
+                        return Ok(optimized);
+                    }
+                    Some((cache, config, rules))
+                }
+                None => None,
+            };
+
+            let analyzed_plan = self.analyzer.analyze_timed(
+                plan,
+                self.config_options(),
+                |_, analyzer, elapsed| {
+                    self.record_analyzer_rule(analyzer.name(), elapsed)
+                },
+            )?;
+            let optimized = self.optimizer.optimize_with_outcomes(
+                &analyzed_plan,
+                self,
+                |_, optimizer, outcome| {
+                    if let RuleOutcome::Applied(elapsed) = outcome {
+                        self.record_optimizer_rule(optimizer.name(), *elapsed)
+                    }
+                },
+            )?;
+            if let Some((cache, config, rules)) = cached {
+                cache.insert_optimized(plan, config, rules, &optimized)?;
+            }
+            Ok(optimized)
+        }
+    }
+
+    fn record_analyzer_rule(&self, name: &str, elapsed: Duration) {
+        if let Some(metrics) = &self.planning_metrics {
+            metrics.add_analyzer_rule(name, elapsed);
+        }
+    }
+
+    fn record_optimizer_rule(&self, name: &str, elapsed: Duration) {
+        if let Some(metrics) = &self.planning_metrics {
+            metrics.add_optimizer_rule(name, elapsed);
         }
     }
 
@@ -1758,9 +2748,24 @@ impl SessionState {
         logical_plan: &LogicalPlan,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let logical_plan = self.optimize(logical_plan)?;
-        self.query_planner
-            .create_physical_plan(&logical_plan, self)
-            .await
+        self.create_optimized_physical_plan(&logical_plan).await
+    }
+
+    /// Creates a physical plan from a logical plan already optimized by
+    /// [`Self::optimize`]
+    pub(crate) async fn create_optimized_physical_plan(
+        &self,
+        logical_plan: &LogicalPlan,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let start = Instant::now();
+        let plan = self
+            .query_planner
+            .create_physical_plan(logical_plan, self)
+            .await;
+        if let Some(metrics) = &self.planning_metrics {
+            metrics.add_physical_planning(start.elapsed());
+        }
+        plan
     }
 
     /// Return the session ID
@@ -1783,6 +2788,16 @@ impl SessionState {
         &self.config
     }
 
+    /// Return the analyzer rules
+    pub fn analyzer_rules(&self) -> &[Arc<dyn AnalyzerRule + Send + Sync>] {
+        &self.analyzer.rules
+    }
+
+    /// Return the optimizer rules
+    pub fn optimizer_rules(&self) -> &[Arc<dyn OptimizerRule + Send + Sync>] {
+        &self.optimizer.rules
+    }
+
     /// Return the physical optimizers
     pub fn physical_optimizers(&self) -> &[Arc<dyn PhysicalOptimizerRule + Send + Sync>] {
         &self.physical_optimizers
@@ -1804,13 +2819,45 @@ impl SessionState {
     }
 }
 
+/// Returns the sqlparser dialect of the `datafusion.sql_parser.dialect` option
+fn dialect_from_str(dialect_name: &str) -> Result<Box<dyn Dialect>> {
+    let dialect: Box<dyn Dialect> = match dialect_name.to_lowercase().as_str() {
+        "generic" => Box::new(GenericDialect {}),
+        "mysql" => Box::new(MySqlDialect {}),
+        "postgresql" | "postgres" => Box::new(PostgreSqlDialect {}),
+        "mssql" => Box::new(MsSqlDialect {}),
+        "hive" => Box::new(HiveDialect {}),
+        "snowflake" => Box::new(SnowflakeDialect {}),
+        "bigquery" => Box::new(BigQueryDialect {}),
+        "ansi" => Box::new(AnsiDialect {}),
+        "sqlite" => Box::new(SQLiteDialect {}),
+        "clickhouse" => Box::new(ClickHouseDialect {}),
+        "redshift" => Box::new(RedshiftSqlDialect {}),
+        _ => {
+            return Err(DataFusionError::Plan(format!(
+                "Unsupported SQL dialect: {dialect_name}. Available dialects: generic, \
+                 mysql, postgresql, mssql, hive, snowflake, bigquery, ansi, sqlite, \
+                 clickhouse, redshift"
+            )))
+        }
+    };
+    Ok(dialect)
+}
+
 struct SessionContextProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<String, Arc<dyn TableSource>>,
+    /// The temporary tables referenced by their unqualified name
+    temporary_tables: HashMap<String, Arc<dyn TableSource>>,
 }
 
 impl<'a> ContextProvider for SessionContextProvider<'a> {
     fn get_table_provider(&self, name: TableReference) -> Result<Arc<dyn TableSource>> {
+        if let TableReference::Bare { table } = name {
+            if let Some(temporary) = self.temporary_tables.get(table) {
+                return Ok(temporary.clone());
+            }
+        }
         let name = self.state.resolve_table_ref(name).to_string();
         self.tables
             .get(&name)
@@ -1818,12 +2865,37 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
             .ok_or_else(|| DataFusionError::Plan(format!("table '{name}' not found")))
     }
 
+    /// Looks up the functions of the session, then the functions of the
+    /// schema qualifying `name`, or of the default schema
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
-        self.state.scalar_functions.get(name).cloned()
+        self.state.scalar_functions.get(name).cloned().or_else(|| {
+            let function_ref = TableReference::parse_str(name);
+            let schema = self.state.schema_for_ref(function_ref).ok()?;
+            schema.udf(function_ref.table())
+        })
     }
 
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>> {
-        self.state.aggregate_functions.get(name).cloned()
+        self.state
+            .aggregate_functions
+            .get(name)
+            .cloned()
+            .or_else(|| {
+                let function_ref = TableReference::parse_str(name);
+                let schema = self.state.schema_for_ref(function_ref).ok()?;
+                schema.udaf(function_ref.table())
+            })
+    }
+
+    fn get_table_function_source(
+        &self,
+        name: &str,
+        args: Vec<Expr>,
+    ) -> Result<Arc<dyn TableSource>> {
+        let fun = self.state.table_functions.get(name).ok_or_else(|| {
+            DataFusionError::Plan(format!("table function '{name}' not found"))
+        })?;
+        Ok(provider_as_source(fun.call(&args)?))
     }
 
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType> {
@@ -1844,6 +2916,73 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
             .and_then(|provider| provider.get(&provider_type)?.get_type(variable_names))
     }
 
+    fn table_names(&self) -> Vec<String> {
+        let catalog = &self.state.config_options().catalog;
+        self.state
+            .catalog_list
+            .catalog(&catalog.default_catalog)
+            .and_then(|c| c.schema(&catalog.default_schema))
+            .map(|schema| schema.table_names())
+            .unwrap_or_default()
+    }
+
+    fn function_names(&self) -> Vec<String> {
+        let catalog = &self.state.config_options().catalog;
+        let schema_functions = self
+            .state
+            .catalog_list
+            .catalog(&catalog.default_catalog)
+            .and_then(|c| c.schema(&catalog.default_schema))
+            .map(|schema| schema.function_names())
+            .unwrap_or_default();
+        self.state
+            .scalar_functions
+            .keys()
+            .chain(self.state.aggregate_functions.keys())
+            .cloned()
+            .chain(schema_functions)
+            .collect()
+    }
+
+    /// Builds the columns of the VALUES list directly from its literals, and
+    /// scans them from a [`MemTable`]
+    fn create_values_table(
+        &self,
+        schema: SchemaRef,
+        rows: &[Vec<ScalarValue>],
+    ) -> Result<Option<Arc<dyn TableSource>>> {
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(j, field)| {
+                // the values of a column may have different types, so each run
+                // of values of the same type is cast to the type of the column
+                let arrays = rows
+                    .iter()
+                    .map(|row| &row[j])
+                    .group_by(|value| value.get_datatype())
+                    .into_iter()
+                    .map(|(_, values)| {
+                        let array = ScalarValue::iter_to_array(values.cloned())?;
+                        Ok(cast(&array, field.data_type())?)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let arrays = arrays.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+                Ok(concat(&arrays)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let batch = RecordBatch::try_new(schema.clone(), columns)?;
+
+        let batch_size = self.state.config.batch_size();
+        let batches = (0..batch.num_rows())
+            .step_by(batch_size)
+            .map(|offset| batch.slice(offset, batch_size.min(batch.num_rows() - offset)))
+            .collect();
+        let table = MemTable::try_new(schema, vec![batches])?;
+        Ok(Some(provider_as_source(Arc::new(table))))
+    }
+
     fn options(&self) -> &ConfigOptions {
         self.state.config_options()
     }
@@ -1899,6 +3038,10 @@ pub struct TaskContext {
     aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
     /// Runtime environment associated with this task context
     runtime: Arc<RuntimeEnv>,
+    /// Permits for the requests of this task to the object stores, if limited
+    object_store_permits: Option<Arc<Semaphore>>,
+    /// Results of the uncorrelated subqueries executed by this task
+    subquery_results: Mutex<HashMap<SubqueryId, OnceFut<SubqueryResult>>>,
 }
 
 impl TaskContext {
@@ -1916,6 +3059,7 @@ impl TaskContext {
             let _ = config.set(&k, &v);
         }
 
+        let object_store_permits = object_store_permits(&runtime);
         Self {
             task_id: Some(task_id),
             session_id,
@@ -1923,6 +3067,8 @@ impl TaskContext {
             scalar_functions,
             aggregate_functions,
             runtime,
+            object_store_permits,
+            subquery_results: Mutex::default(),
         }
     }
 
@@ -1950,6 +3096,42 @@ impl TaskContext {
     pub fn runtime_env(&self) -> Arc<RuntimeEnv> {
         self.runtime.clone()
     }
+
+    /// Retrieves the `ObjectStore` for a url from the [RuntimeEnv], limiting its
+    /// requests with those of the other stores returned by this [TaskContext] to
+    /// [`RuntimeEnv::max_concurrent_object_store_requests`], if specified
+    pub fn object_store(&self, url: impl AsRef<Url>) -> Result<Arc<dyn ObjectStore>> {
+        let store = self.runtime.object_store(url)?;
+        Ok(match &self.object_store_permits {
+            Some(permits) => Arc::new(LimitedObjectStore::new(store, permits.clone())),
+            None => store,
+        })
+    }
+
+    /// Returns the results of the uncorrelated subquery `id`, computed by the
+    /// future returned by `f` on the first call for `id` and then shared by all
+    /// the partitions executed with this [TaskContext]
+    pub(crate) fn subquery_results<F, Fut>(
+        &self,
+        id: SubqueryId,
+        f: F,
+    ) -> OnceFut<SubqueryResult>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<SubqueryResult>> + Send + 'static,
+    {
+        self.subquery_results
+            .lock()
+            .entry(id)
+            .or_insert_with(|| OnceFut::new(f()))
+            .clone()
+    }
+}
+
+fn object_store_permits(runtime: &RuntimeEnv) -> Option<Arc<Semaphore>> {
+    runtime
+        .max_concurrent_object_store_requests
+        .map(|max_requests| Arc::new(Semaphore::new(max_requests)))
 }
 
 /// Create a new task context instance from SessionContext
@@ -1967,6 +3149,7 @@ impl From<&SessionState> for TaskContext {
         let scalar_functions = state.scalar_functions.clone();
         let aggregate_functions = state.aggregate_functions.clone();
         let runtime = state.runtime_env.clone();
+        let object_store_permits = object_store_permits(&runtime);
         Self {
             task_id: None,
             session_id,
@@ -1974,6 +3157,8 @@ impl From<&SessionState> for TaskContext {
             scalar_functions,
             aggregate_functions,
             runtime,
+            object_store_permits,
+            subquery_results: Mutex::default(),
         }
     }
 }
@@ -2011,15 +3196,16 @@ mod tests {
     use crate::execution::context::QueryPlanner;
     use crate::execution::memory_pool::MemoryConsumer;
     use crate::execution::runtime_env::RuntimeConfig;
+    use crate::optimizer::policy::TablePolicy;
     use crate::physical_plan::expressions::AvgAccumulator;
     use crate::test;
     use crate::test_util::parquet_test_data;
     use crate::variable::VarType;
-    use arrow::array::ArrayRef;
+    use arrow::array::{ArrayRef, Int64Array};
     use arrow::datatypes::*;
     use arrow::record_batch::RecordBatch;
     use async_trait::async_trait;
-    use datafusion_expr::{create_udaf, create_udf, Expr, Volatility};
+    use datafusion_expr::{col, create_udaf, create_udf, lit, Expr, Volatility};
     use datafusion_physical_expr::functions::make_scalar_function;
     use std::fs::File;
     use std::path::PathBuf;
@@ -2107,6 +3293,46 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn sql_script() -> Result<()> {
+        let ctx = SessionContext::new();
+        let mut results = ctx.sql_script(
+            "CREATE TABLE t AS VALUES (2), (1); \
+            SELECT column1 FROM t ORDER BY column1; \
+            DROP TABLE t; \
+            SELECT * FROM t; \
+            SELECT 1",
+        )?;
+
+        let create = results.next().await.unwrap()?.collect().await?;
+        assert!(create.is_empty());
+
+        let query = results.next().await.unwrap()?.collect().await?;
+        let expected = vec![
+            "+---------+",
+            "| column1 |",
+            "+---------+",
+            "| 1       |",
+            "| 2       |",
+            "+---------+",
+        ];
+        assert_batches_eq!(expected, &query);
+
+        let drop = results.next().await.unwrap()?.collect().await?;
+        assert!(drop.is_empty());
+
+        // the table was dropped by the previous statement, and the script stops
+        let err = results.next().await.unwrap().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("table 'datafusion.public.t' not found"));
+        // the error is located in the failing statement, not at the first `t`
+        let span = err.diagnostic().unwrap().span;
+        assert_eq!(span, Some(datafusion_common::Span::new(103, 104)));
+        assert!(results.next().await.is_none());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn register_deregister() -> Result<()> {
         let tmp_dir = TempDir::new()?;
@@ -2163,6 +3389,43 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn user_defined_table_function() -> Result<()> {
+        struct Sequence;
+
+        impl TableFunctionImpl for Sequence {
+            fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+                match args {
+                    [Expr::Literal(ScalarValue::Int64(Some(end)))] => {
+                        test::table_with_sequence(1, *end as i32)
+                    }
+                    _ => Err(DataFusionError::Plan(
+                        "sequence expects an integer literal".to_string(),
+                    )),
+                }
+            }
+        }
+
+        let ctx = SessionContext::new();
+        ctx.register_udtf("sequence", Arc::new(Sequence));
+
+        let result =
+            plan_and_collect(&ctx, "SELECT i * 2 AS j FROM sequence(3) s WHERE s.i > 1")
+                .await?;
+        let expected = vec!["+---+", "| j |", "+---+", "| 4 |", "| 6 |", "+---+"];
+        assert_batches_eq!(expected, &result);
+
+        let err = plan_and_collect(&ctx, "SELECT * FROM sequence('a')")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: sequence expects an integer literal"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn case_sensitive_identifiers_user_defined_aggregates() -> Result<()> {
         let ctx = SessionContext::new();
@@ -2310,6 +3573,206 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn custom_analyzer_rule() -> Result<()> {
+        let session_state = SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+        .add_analyzer_rule(Arc::new(RowFilter {}));
+        let ctx = SessionContext::with_state(session_state);
+        ctx.sql("CREATE TABLE t AS VALUES (1, 'a'), (2, 'b'), (1, 'c')")
+            .await?;
+
+        let results = plan_and_collect(&ctx, "SELECT column2 FROM t").await?;
+        let expected = vec![
+            "+---------+",
+            "| column2 |",
+            "+---------+",
+            "| a       |",
+            "| c       |",
+            "+---------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        let results = plan_and_collect(&ctx, "EXPLAIN VERBOSE SELECT * FROM t").await?;
+        let plan_types = arrow::util::pretty::pretty_format_batches(&results)?;
+        assert!(plan_types
+            .to_string()
+            .contains("logical_plan after row_filter"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn policy_provider() -> Result<()> {
+        let session_state = SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+        .with_policy_provider(Arc::new(TenantPolicy {}), "tenant_1");
+        let ctx = SessionContext::with_state(session_state);
+        ctx.sql("CREATE TABLE t AS VALUES (1, 'a'), (2, 'b'), (1, 'c')")
+            .await?;
+        ctx.sql("CREATE VIEW v AS SELECT column2 AS name FROM t")
+            .await?;
+
+        let expected = vec![
+            "+------+", "| name |", "+------+", "| ***  |", "| ***  |", "+------+",
+        ];
+        let results = plan_and_collect(&ctx, "SELECT column2 AS name FROM t").await?;
+        assert_batches_eq!(expected, &results);
+        let results = plan_and_collect(&ctx, "SELECT * FROM v").await?;
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_and_drop_function() -> Result<()> {
+        let create =
+            "CREATE FUNCTION f(x BIGINT) RETURNS BIGINT LANGUAGE constant AS '42'";
+
+        let err = plan_and_collect(&SessionContext::new(), create)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: CREATE FUNCTION requires a FunctionFactory, \
+            see SessionState::with_function_factory"
+        );
+
+        let session_state = SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+        .with_function_factory(Arc::new(ConstantFunctionFactory {}));
+        let ctx = SessionContext::with_state(session_state);
+
+        plan_and_collect(&ctx, create).await?;
+        let results = plan_and_collect(&ctx, "SELECT f(1)").await?;
+        let expected = vec![
+            "+-------------+",
+            "| f(Int64(1)) |",
+            "+-------------+",
+            "| 42          |",
+            "+-------------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        let err = plan_and_collect(&ctx, create).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Function 'f' already exists"
+        );
+
+        plan_and_collect(
+            &ctx,
+            "CREATE OR REPLACE FUNCTION f(x BIGINT) LANGUAGE constant AS '7'",
+        )
+        .await?;
+        let results = plan_and_collect(&ctx, "SELECT f(1)").await?;
+        let expected = vec![
+            "+-------------+",
+            "| f(Int64(1)) |",
+            "+-------------+",
+            "| 7           |",
+            "+-------------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        plan_and_collect(&ctx, "DROP FUNCTION f").await?;
+        let err = plan_and_collect(&ctx, "SELECT f(1)").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Invalid function 'f'"
+        );
+
+        let err = plan_and_collect(&ctx, "DROP FUNCTION f").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Function 'f' doesn't exist."
+        );
+        plan_and_collect(&ctx, "DROP FUNCTION IF EXISTS f").await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn transactions() -> Result<()> {
+        let err = plan_and_collect(&SessionContext::new(), "BEGIN")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This feature is not implemented: Transactions are not supported by \
+            this session: BEGIN requires a TransactionManager, \
+            see SessionState::with_transaction_manager"
+        );
+
+        let manager = Arc::new(LoggingTransactionManager::default());
+        let session_state = SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+        .with_transaction_manager(manager.clone());
+        let ctx = SessionContext::with_state(session_state);
+
+        let err = plan_and_collect(&ctx, "COMMIT").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: COMMIT requires a transaction in progress"
+        );
+
+        plan_and_collect(&ctx, "BEGIN").await?;
+        assert!(ctx.state().transaction().is_some());
+        let err = plan_and_collect(&ctx, "BEGIN").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: There is already a transaction in progress"
+        );
+        plan_and_collect(&ctx, "SAVEPOINT s1").await?;
+        plan_and_collect(&ctx, "ROLLBACK TO SAVEPOINT s1").await?;
+        plan_and_collect(&ctx, "RELEASE SAVEPOINT s1").await?;
+        plan_and_collect(&ctx, "COMMIT").await?;
+        assert!(ctx.state().transaction().is_none());
+
+        plan_and_collect(&ctx, "START TRANSACTION").await?;
+        plan_and_collect(&ctx, "ROLLBACK").await?;
+
+        assert_eq!(
+            *manager.log.lock(),
+            vec![
+                "begin 1",
+                "savepoint 1 s1",
+                "rollback to 1 s1",
+                "release 1 s1",
+                "commit 1",
+                "begin 2",
+                "rollback 2",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn remove_builtin_rules() {
+        let session_state = SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+        .remove_optimizer_rule("simplify_expressions")
+        .remove_physical_optimizer_rule("coalesce_batches");
+
+        assert!(!session_state.optimizer_rules().is_empty());
+        assert!(session_state
+            .optimizer_rules()
+            .iter()
+            .all(|rule| rule.name() != "simplify_expressions"));
+        assert!(session_state
+            .physical_optimizers()
+            .iter()
+            .all(|rule| rule.name() != "coalesce_batches"));
+    }
+
     #[tokio::test]
     async fn disabled_default_catalog_and_schema() -> Result<()> {
         let ctx = SessionContext::with_config(
@@ -2596,6 +4059,151 @@ mod tests {
         }
     }
 
+    /// Only lets the rows of `t` with `column1 = 1` through
+    struct RowFilter {}
+
+    impl AnalyzerRule for RowFilter {
+        fn analyze(
+            &self,
+            plan: &LogicalPlan,
+            config: &ConfigOptions,
+        ) -> Result<LogicalPlan> {
+            match plan {
+                LogicalPlan::TableScan(scan) if scan.table_name == "t" => {
+                    LogicalPlanBuilder::from(plan.clone())
+                        .filter(col("t.column1").eq(lit(1)))?
+                        .build()
+                }
+                _ => {
+                    let inputs = plan
+                        .inputs()
+                        .into_iter()
+                        .map(|input| self.analyze(input, config))
+                        .collect::<Result<Vec<_>>>()?;
+                    plan.with_new_inputs(&inputs)
+                }
+            }
+        }
+
+        fn name(&self) -> &str {
+            "row_filter"
+        }
+    }
+
+    /// Only lets `tenant_1` see its own rows of `t`, with `column2` masked
+    struct TenantPolicy {}
+
+    impl PolicyProvider for TenantPolicy {
+        fn table_policy(
+            &self,
+            table: TableReference,
+            user: &str,
+        ) -> Result<Option<TablePolicy>> {
+            Ok((table.table() == "t").then(|| {
+                let tenant = user.trim_start_matches("tenant_").parse::<i64>().unwrap();
+                TablePolicy::with_predicate(col("column1").eq(lit(tenant)))
+                    .with_column_mask("column2", lit("***"))
+            }))
+        }
+    }
+
+    /// Logs the statements applied to its transactions
+    #[derive(Default)]
+    struct LoggingTransactionManager {
+        log: Arc<parking_lot::Mutex<Vec<String>>>,
+        started: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TransactionManager for LoggingTransactionManager {
+        async fn begin(&self, _state: &SessionState) -> Result<Arc<dyn Transaction>> {
+            let id = self
+                .started
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+                + 1;
+            self.log.lock().push(format!("begin {id}"));
+            Ok(Arc::new(LoggingTransaction {
+                id,
+                log: self.log.clone(),
+            }))
+        }
+    }
+
+    struct LoggingTransaction {
+        id: usize,
+        log: Arc<parking_lot::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Transaction for LoggingTransaction {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        async fn commit(&self) -> Result<()> {
+            self.log.lock().push(format!("commit {}", self.id));
+            Ok(())
+        }
+
+        async fn rollback(&self) -> Result<()> {
+            self.log.lock().push(format!("rollback {}", self.id));
+            Ok(())
+        }
+
+        async fn savepoint(&self, name: &str) -> Result<()> {
+            self.log
+                .lock()
+                .push(format!("savepoint {} {name}", self.id));
+            Ok(())
+        }
+
+        async fn rollback_to_savepoint(&self, name: &str) -> Result<()> {
+            self.log
+                .lock()
+                .push(format!("rollback to {} {name}", self.id));
+            Ok(())
+        }
+
+        async fn release_savepoint(&self, name: &str) -> Result<()> {
+            self.log.lock().push(format!("release {} {name}", self.id));
+            Ok(())
+        }
+    }
+
+    /// Creates functions returning the integer constant they are defined as
+    struct ConstantFunctionFactory {}
+
+    #[async_trait]
+    impl FunctionFactory for ConstantFunctionFactory {
+        async fn create(
+            &self,
+            _state: &SessionState,
+            statement: CreateFunction,
+        ) -> Result<RegisterFunction> {
+            let value = statement
+                .definition
+                .unwrap_or_default()
+                .parse::<i64>()
+                .map_err(|e| DataFusionError::Plan(e.to_string()))?;
+            let fun = make_scalar_function(move |args: &[ArrayRef]| {
+                Ok(Arc::new(Int64Array::from(vec![value; args[0].len()])) as ArrayRef)
+            });
+            let arg_types = statement
+                .args
+                .into_iter()
+                .map(|arg| arg.data_type)
+                .collect();
+
+            Ok(RegisterFunction::Scalar(Arc::new(create_udf(
+                &statement.name,
+                arg_types,
+                Arc::new(DataType::Int64),
+                Volatility::Immutable,
+                fun,
+            ))))
+        }
+    }
+
     /// Execute SQL and return results
     async fn plan_and_collect(
         ctx: &SessionContext,
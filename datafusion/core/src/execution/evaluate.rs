@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Evaluation of standalone [`Expr`]s, for using DataFusion as an
+//! expression engine without a [`SessionContext`].
+//!
+//! ```
+//! use std::sync::Arc;
+//! use datafusion::arrow::array::{ArrayRef, Int32Array, Int64Array};
+//! use datafusion::arrow::record_batch::RecordBatch;
+//! use datafusion::prelude::*;
+//!
+//! let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+//! let batch = RecordBatch::try_from_iter(vec![("a", a)]).unwrap();
+//!
+//! // `a` is coerced to BIGINT to be added to a BIGINT literal
+//! let result = (col("a") + lit(10i64)).evaluate_against(&batch).unwrap();
+//! let expected: ArrayRef = Arc::new(Int64Array::from(vec![11, 12, 13]));
+//! assert_eq!(&result, &expected);
+//! ```
+//!
+//! [`SessionContext`]: crate::execution::context::SessionContext
+
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{DFSchema, Result};
+use datafusion_expr::Expr;
+use datafusion_optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext};
+use datafusion_physical_expr::execution_props::ExecutionProps;
+use datafusion_physical_expr::{create_physical_expr, PhysicalExpr};
+
+/// Type-coerces, simplifies and plans `expr` for evaluation against
+/// batches of `schema`.
+///
+/// Columns are referred to by their unqualified name. Planning once and
+/// calling [`PhysicalExpr::evaluate`] is cheaper than
+/// [`evaluate_against`](ExprEvaluationExt::evaluate_against) for evaluating
+/// the same expression against many batches.
+pub fn create_evaluator(expr: Expr, schema: &Schema) -> Result<Arc<dyn PhysicalExpr>> {
+    let df_schema = Arc::new(DFSchema::try_from(schema.clone())?);
+    let props = ExecutionProps::new();
+    let simplifier =
+        ExprSimplifier::new(SimplifyContext::new(&props).with_schema(df_schema.clone()));
+    let expr = simplifier.simplify(simplifier.coerce(expr, df_schema.clone())?)?;
+    create_physical_expr(&expr, &df_schema, schema, &props)
+}
+
+/// Evaluation of a single [`Expr`] against a [`RecordBatch`]
+pub trait ExprEvaluationExt {
+    /// Type-coerce, plan and evaluate this expression against `batch`,
+    /// returning one value per row. See [`create_evaluator`].
+    fn evaluate_against(&self, batch: &RecordBatch) -> Result<ArrayRef>;
+}
+
+impl ExprEvaluationExt for Expr {
+    fn evaluate_against(&self, batch: &RecordBatch) -> Result<ArrayRef> {
+        let evaluator = create_evaluator(self.clone(), batch.schema().as_ref())?;
+        Ok(evaluator.evaluate(batch)?.into_array(batch.num_rows()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{BooleanArray, Float64Array, StringArray};
+    use datafusion_common::DataFusionError;
+    use datafusion_expr::{col, lit};
+
+    fn batch() -> RecordBatch {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![1.5, 2.5, 3.5]));
+        let b: ArrayRef = Arc::new(StringArray::from(vec!["x", "y", "z"]));
+        RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap()
+    }
+
+    #[test]
+    fn evaluate_coerced_expr() -> Result<()> {
+        let result = col("a").gt(lit(2)).evaluate_against(&batch())?;
+        let expected: ArrayRef = Arc::new(BooleanArray::from(vec![false, true, true]));
+        assert_eq!(&result, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_constant_expr() -> Result<()> {
+        // the result of constant expressions is expanded to one value per row
+        let result = (lit(1) + lit(2)).evaluate_against(&batch())?;
+        assert_eq!(result.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_column() {
+        let err = col("c").evaluate_against(&batch()).unwrap_err();
+        assert!(matches!(err, DataFusionError::SchemaError(_)), "{err}");
+    }
+}
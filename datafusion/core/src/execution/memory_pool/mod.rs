@@ -142,6 +142,17 @@ impl MemoryReservation {
         }
     }
 
+    /// Try to set the size of this reservation to `capacity`
+    pub fn try_resize(&mut self, capacity: usize) -> Result<()> {
+        use std::cmp::Ordering;
+        match capacity.cmp(&self.size) {
+            Ordering::Greater => self.try_grow(capacity - self.size)?,
+            Ordering::Less => self.shrink(self.size - capacity),
+            _ => {}
+        }
+        Ok(())
+    }
+
     /// Increase the size of this reservation by `capacity` bytes
     pub fn grow(&mut self, capacity: usize) {
         self.policy.grow(self, capacity);
@@ -219,4 +230,19 @@ mod tests {
         a2.try_grow(25).unwrap();
         assert_eq!(pool.reserved(), 25);
     }
+
+    #[test]
+    fn test_memory_reservation_try_resize() {
+        let pool = Arc::new(GreedyMemoryPool::new(50)) as _;
+        let mut a1 = MemoryConsumer::new("a1").register(&pool);
+
+        a1.try_resize(40).unwrap();
+        assert_eq!(pool.reserved(), 40);
+
+        a1.try_resize(60).unwrap_err();
+        assert_eq!(a1.size(), 40);
+
+        a1.try_resize(10).unwrap();
+        assert_eq!(pool.reserved(), 10);
+    }
 }
@@ -42,10 +42,17 @@
 
 pub mod context;
 pub mod disk_manager;
+pub mod evaluate;
 pub mod memory_pool;
 pub mod options;
+pub mod plan_cache;
+pub mod planning_metrics;
 pub mod registry;
+pub mod result_cache;
 pub mod runtime_env;
+pub mod transaction;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use disk_manager::DiskManager;
 pub use registry::FunctionRegistry;
@@ -17,10 +17,12 @@
 
 //! User facing options for the file formats readers
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use arrow::datatypes::{DataType, Schema};
 
+use crate::datasource::file_format::arrow::DEFAULT_ARROW_EXTENSION;
 use crate::datasource::file_format::avro::DEFAULT_AVRO_EXTENSION;
 use crate::datasource::file_format::csv::DEFAULT_CSV_EXTENSION;
 use crate::datasource::file_format::file_type::FileCompressionType;
@@ -29,7 +31,8 @@ use crate::datasource::file_format::parquet::DEFAULT_PARQUET_EXTENSION;
 use crate::datasource::file_format::DEFAULT_SCHEMA_INFER_MAX_RECORD;
 use crate::datasource::{
     file_format::{
-        avro::AvroFormat, csv::CsvFormat, json::JsonFormat, parquet::ParquetFormat,
+        arrow::ArrowFormat, avro::AvroFormat, csv::CsvFormat, json::JsonFormat,
+        parquet::ParquetFormat,
     },
     listing::ListingOptions,
 };
@@ -65,6 +68,13 @@ pub struct CsvReadOptions<'a> {
     pub file_compression_type: FileCompressionType,
     /// Flag indicating whether this file may be unbounded (as in a FIFO file).
     pub infinite: bool,
+    /// Values read as nulls, in addition to the empty values, for example `NA`.
+    pub null_markers: Vec<String>,
+    /// An optional `chrono` format of the timestamps. If None, ISO 8601
+    /// timestamps are detected.
+    pub datetime_format: Option<String>,
+    /// Types of columns, by name, overriding the types inferred for them.
+    pub schema_overrides: HashMap<String, DataType>,
 }
 
 impl<'a> Default for CsvReadOptions<'a> {
@@ -85,6 +95,9 @@ impl<'a> CsvReadOptions<'a> {
             table_partition_cols: vec![],
             file_compression_type: FileCompressionType::UNCOMPRESSED,
             infinite: false,
+            null_markers: vec![],
+            datetime_format: None,
+            schema_overrides: HashMap::new(),
         }
     }
 
@@ -150,13 +163,37 @@ impl<'a> CsvReadOptions<'a> {
         self
     }
 
+    /// Specify the values read as nulls
+    pub fn null_markers(mut self, null_markers: Vec<String>) -> Self {
+        self.null_markers = null_markers;
+        self
+    }
+
+    /// Specify the `chrono` format of the timestamps
+    pub fn datetime_format(mut self, datetime_format: impl Into<String>) -> Self {
+        self.datetime_format = Some(datetime_format.into());
+        self
+    }
+
+    /// Specify the types of columns overriding their inferred types
+    pub fn schema_overrides(
+        mut self,
+        schema_overrides: HashMap<String, DataType>,
+    ) -> Self {
+        self.schema_overrides = schema_overrides;
+        self
+    }
+
     /// Helper to convert these user facing options to `ListingTable` options
     pub fn to_listing_options(&self, target_partitions: usize) -> ListingOptions {
         let file_format = CsvFormat::default()
             .with_has_header(self.has_header)
             .with_delimiter(self.delimiter)
             .with_schema_infer_max_rec(Some(self.schema_infer_max_records))
-            .with_file_compression_type(self.file_compression_type.to_owned());
+            .with_file_compression_type(self.file_compression_type.to_owned())
+            .with_null_markers(self.null_markers.clone())
+            .with_datetime_format(self.datetime_format.clone())
+            .with_schema_overrides(self.schema_overrides.clone());
 
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
@@ -305,6 +342,71 @@ impl<'a> AvroReadOptions<'a> {
     }
 }
 
+/// Options that control the reading of Arrow IPC files.
+///
+/// Note this structure is supplied when a datasource is created and
+/// can not not vary from statement to statement. For settings that
+/// can vary statement to statement see
+/// [`ConfigOptions`](crate::config::ConfigOptions).
+#[derive(Clone)]
+pub struct ArrowReadOptions<'a> {
+    /// The data source schema.
+    pub schema: Option<&'a Schema>,
+
+    /// File extension; only files with this extension are selected for data input.
+    /// Defaults to `FileType::ARROW.get_ext().as_str()`.
+    pub file_extension: &'a str,
+    /// Partition Columns
+    pub table_partition_cols: Vec<(String, DataType)>,
+    /// Flag indicating whether this file may be unbounded (as in a FIFO file).
+    pub infinite: bool,
+}
+
+impl<'a> Default for ArrowReadOptions<'a> {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            file_extension: DEFAULT_ARROW_EXTENSION,
+            table_partition_cols: vec![],
+            infinite: false,
+        }
+    }
+}
+
+impl<'a> ArrowReadOptions<'a> {
+    /// Specify table_partition_cols for partition pruning
+    pub fn table_partition_cols(
+        mut self,
+        table_partition_cols: Vec<(String, DataType)>,
+    ) -> Self {
+        self.table_partition_cols = table_partition_cols;
+        self
+    }
+
+    /// Helper to convert these user facing options to `ListingTable` options
+    pub fn to_listing_options(&self, target_partitions: usize) -> ListingOptions {
+        let file_format = ArrowFormat::default();
+
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_target_partitions(target_partitions)
+            .with_table_partition_cols(self.table_partition_cols.clone())
+            .with_infinite_source(self.infinite)
+    }
+
+    /// Configure mark_infinite setting
+    pub fn mark_infinite(mut self, infinite: bool) -> Self {
+        self.infinite = infinite;
+        self
+    }
+
+    /// Specify schema to use for Arrow IPC read
+    pub fn schema(mut self, schema: &'a Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+}
+
 /// Options that control the reading of Line-delimited JSON files (NDJson)
 ///
 /// Note this structure is supplied when a datasource is created and
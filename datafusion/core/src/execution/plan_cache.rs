@@ -0,0 +1,621 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Cache of the logical plans of SQL queries, shared by the sessions
+//! configured with [`SessionState::with_plan_cache`]
+//!
+//! [`SessionState::with_plan_cache`]: super::context::SessionState::with_plan_cache
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use datafusion_common::fingerprint::Fingerprint;
+use datafusion_common::{DataFusionError, OwnedTableReference};
+use datafusion_expr::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
+use datafusion_expr::logical_plan::{LogicalPlan, PlanVisitor, TableScan};
+use datafusion_expr::{Expr, TableSource, Volatility};
+use parking_lot::Mutex;
+
+use crate::config::ConfigOptions;
+use crate::datasource::function::TableFunctionImpl;
+use crate::datasource::TableProvider;
+use crate::error::Result;
+use crate::optimizer::analyzer::Analyzer;
+use crate::optimizer::optimizer::Optimizer;
+use crate::physical_plan::udaf::AggregateUDF;
+use crate::physical_plan::udf::ScalarUDF;
+
+/// The key of the plan of a SQL statement in a [`PlanCache`]: its text, and
+/// the fingerprint of the configuration of the session planning it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct PlanCacheKey {
+    sql: String,
+    config: Fingerprint,
+}
+
+impl PlanCacheKey {
+    pub(crate) fn new(sql: &str, config: &ConfigOptions) -> Self {
+        Self {
+            sql: sql.to_string(),
+            config: config_fingerprint(config),
+        }
+    }
+}
+
+/// The fingerprint of the values of all the configuration options
+pub(crate) fn config_fingerprint(config: &ConfigOptions) -> Fingerprint {
+    let mut entries = String::new();
+    for entry in config.entries() {
+        entries.push_str(&entry.key);
+        if let Some(value) = &entry.value {
+            entries.push('=');
+            entries.push_str(value);
+        }
+        entries.push('\n');
+    }
+    Fingerprint::of(entries.as_bytes())
+}
+
+/// The fingerprint of the analyzer and optimizer rules optimizing a plan.
+///
+/// The rules are identified by their name and their instance, as rules of
+/// the same name may rewrite plans differently, such as the
+/// [`ApplyPolicies`] rules applying the policies of different users.
+/// Sessions thus only share the optimized plans of a cache when their rules
+/// are the same instances, such as the sessions whose states are cloned
+/// from the same [`SessionState`].
+///
+/// [`ApplyPolicies`]: crate::optimizer::policy::ApplyPolicies
+/// [`SessionState`]: super::context::SessionState
+pub(crate) fn rules_fingerprint(
+    analyzer: &Analyzer,
+    optimizer: &Optimizer,
+) -> Fingerprint {
+    let mut rules = String::new();
+    for rule in &analyzer.rules {
+        rules.push_str(&format!("{}@{:p}\n", rule.name(), Arc::as_ptr(rule)));
+    }
+    for rule in &optimizer.rules {
+        rules.push_str(&format!("{}@{:p}\n", rule.name(), Arc::as_ptr(rule)));
+    }
+    Fingerprint::of(rules.as_bytes())
+}
+
+/// The tables referenced by a statement, and the providers they resolved to,
+/// if any
+pub(crate) type ReferencedTables =
+    Vec<(OwnedTableReference, Option<Arc<dyn TableProvider>>)>;
+
+/// The catalog objects a SQL statement was planned with. Its cached plan is
+/// only reused while the session resolves them to the same objects.
+pub(crate) struct PlanDependencies {
+    /// The tables referenced by the statement
+    pub tables: ReferencedTables,
+    /// The scalar functions of the session
+    pub scalar_functions: HashMap<String, Arc<ScalarUDF>>,
+    /// The aggregate functions of the session
+    pub aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
+    /// The table functions of the session
+    pub table_functions: HashMap<String, Arc<dyn TableFunctionImpl>>,
+}
+
+/// A cache of the logical plans of SQL queries, and of their optimized
+/// plans, evicting the least recently used plans once it holds more than
+/// `max_entries` of them.
+///
+/// It is configured with [`SessionState::with_plan_cache`], and used by
+/// [`SessionState::create_logical_plan`] and [`SessionState::optimize`], so
+/// that the queries run again and again, for example by dashboards, are
+/// parsed, analyzed and optimized once. A plan is looked up by the text of
+/// its SQL and the configuration of the session, and reused as long as:
+///
+/// * the tables it references resolve to the same [`TableProvider`]s, so
+///   that registering, deregistering or replacing a table, or creating a
+///   temporary table shadowing it, invalidates it,
+/// * and the session has the same user defined functions.
+///
+/// The optimized plans of the queries calling stable functions, such as
+/// `now()`, which the optimizer replaces with their value, are not cached.
+///
+/// The optimized plans are also looked up by the instances of the analyzer
+/// and optimizer rules of the session, so that a session only reuses the
+/// plans optimized with the same rules, including the rule applying the
+/// access policies of its user, see [`SessionState::with_policy_provider`].
+/// Sessions thus share optimized plans when their states are cloned from
+/// the same [`SessionState`] before adding their policies.
+///
+/// [`SessionState::with_plan_cache`]: super::context::SessionState::with_plan_cache
+/// [`SessionState::create_logical_plan`]: super::context::SessionState::create_logical_plan
+/// [`SessionState::optimize`]: super::context::SessionState::optimize
+/// [`SessionState::with_policy_provider`]: super::context::SessionState::with_policy_provider
+/// [`SessionState`]: super::context::SessionState
+pub struct PlanCache {
+    max_entries: usize,
+    state: Mutex<PlanCacheState>,
+}
+
+#[derive(Default)]
+struct PlanCacheState {
+    entries: HashMap<PlanCacheKey, PlanCacheEntry>,
+    /// The number of accesses to the cache so far
+    clock: u64,
+    /// The number of queries whose plan was found in the cache
+    hits: u64,
+    /// The number of queries planned, as their plan was missing or
+    /// invalidated
+    misses: u64,
+}
+
+struct PlanCacheEntry {
+    plan: LogicalPlan,
+    dependencies: Arc<PlanDependencies>,
+    /// The fingerprint and the objects of `plan`, to recognize it when it is
+    /// optimized
+    fingerprint: Fingerprint,
+    objects: PlanObjects,
+    /// The optimized plan, and the fingerprint of the rules that optimized it
+    optimized: Option<(Fingerprint, LogicalPlan)>,
+    /// The value of the clock when the plan was last accessed
+    last_access: u64,
+}
+
+impl PlanCache {
+    /// Create a cache holding up to `max_entries` plans
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            state: Mutex::new(PlanCacheState::default()),
+        }
+    }
+
+    /// The maximum number of cached plans
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// The number of cached plans
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Returns true if no plans are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of queries whose plan was found in the cache
+    pub fn hits(&self) -> u64 {
+        self.state.lock().hits
+    }
+
+    /// The number of queries whose plan was not found in the cache, or was
+    /// invalidated
+    pub fn misses(&self) -> u64 {
+        self.state.lock().misses
+    }
+
+    /// The fraction of the queries whose plan was found in the cache, or 0 if
+    /// none was looked up
+    pub fn hit_rate(&self) -> f64 {
+        let state = self.state.lock();
+        match state.hits + state.misses {
+            0 => 0.0,
+            lookups => state.hits as f64 / lookups as f64,
+        }
+    }
+
+    /// Removes all the cached plans
+    pub fn clear(&self) {
+        self.state.lock().entries.clear();
+    }
+
+    /// Returns the cached plan for `key` and the objects it was planned with,
+    /// which the caller checks before calling [`Self::record_hit`]
+    pub(crate) fn get(
+        &self,
+        key: &PlanCacheKey,
+    ) -> Option<(LogicalPlan, Arc<PlanDependencies>)> {
+        let mut state = self.state.lock();
+        state.clock += 1;
+        let clock = state.clock;
+        let entry = state.entries.get_mut(key)?;
+        entry.last_access = clock;
+        Some((entry.plan.clone(), entry.dependencies.clone()))
+    }
+
+    pub(crate) fn record_hit(&self) {
+        self.state.lock().hits += 1;
+    }
+
+    /// Removes the cached plan for `key`, whose dependencies changed
+    pub(crate) fn remove(&self, key: &PlanCacheKey) {
+        self.state.lock().entries.remove(key);
+    }
+
+    /// Caches the plan for `key`, which was missing or invalidated, evicting
+    /// the least recently used plan if the cache is full. Only the plans of
+    /// queries are cached, and counted as misses.
+    pub(crate) fn insert(
+        &self,
+        key: PlanCacheKey,
+        plan: LogicalPlan,
+        dependencies: PlanDependencies,
+    ) -> Result<()> {
+        if !is_query(&plan) {
+            return Ok(());
+        }
+        let fingerprint = plan.fingerprint();
+        let objects = PlanObjects::try_new(&plan)?;
+
+        let mut state = self.state.lock();
+        state.misses += 1;
+        state.entries.remove(&key);
+        if self.max_entries == 0 {
+            return Ok(());
+        }
+        if state.entries.len() >= self.max_entries {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| key.clone())
+                .expect("cached plans");
+            state.entries.remove(&oldest);
+        }
+
+        state.clock += 1;
+        let last_access = state.clock;
+        state.entries.insert(
+            key,
+            PlanCacheEntry {
+                plan,
+                dependencies: Arc::new(dependencies),
+                fingerprint,
+                objects,
+                optimized: None,
+                last_access,
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns the cached optimized plan of `plan`, if `plan` is a plan of
+    /// the cache, optimized before with the same configuration and rules
+    pub(crate) fn get_optimized(
+        &self,
+        plan: &LogicalPlan,
+        config: Fingerprint,
+        rules: Fingerprint,
+    ) -> Result<Option<LogicalPlan>> {
+        if self.is_empty() || !is_query(plan) {
+            return Ok(None);
+        }
+        let fingerprint = plan.fingerprint();
+        let objects = PlanObjects::try_new(plan)?;
+        let state = self.state.lock();
+        Ok(state
+            .entries
+            .iter()
+            .find(|(key, entry)| {
+                key.config == config
+                    && entry.fingerprint == fingerprint
+                    && entry.objects.same_as(&objects)
+            })
+            .and_then(|(_, entry)| match &entry.optimized {
+                Some((optimized_rules, optimized)) if *optimized_rules == rules => {
+                    Some(optimized.clone())
+                }
+                _ => None,
+            }))
+    }
+
+    /// Caches `optimized`, the optimized plan of `plan` with `rules`, if
+    /// `plan` is a plan of the cache
+    pub(crate) fn insert_optimized(
+        &self,
+        plan: &LogicalPlan,
+        config: Fingerprint,
+        rules: Fingerprint,
+        optimized: &LogicalPlan,
+    ) -> Result<()> {
+        if self.is_empty() || !is_query(plan) {
+            return Ok(());
+        }
+        let fingerprint = plan.fingerprint();
+        let objects = PlanObjects::try_new(plan)?;
+        if objects.stable {
+            return Ok(());
+        }
+        let mut state = self.state.lock();
+        for (key, entry) in state.entries.iter_mut() {
+            if key.config == config
+                && entry.fingerprint == fingerprint
+                && entry.objects.same_as(&objects)
+            {
+                entry.optimized = Some((rules, optimized.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for PlanCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock();
+        f.debug_struct("PlanCache")
+            .field("max_entries", &self.max_entries)
+            .field("entries", &state.entries.len())
+            .field("hits", &state.hits)
+            .field("misses", &state.misses)
+            .finish()
+    }
+}
+
+/// Returns true if `plan` is a query, whose plan can be cached, rather than
+/// a statement changing the session or the catalog
+fn is_query(plan: &LogicalPlan) -> bool {
+    matches!(
+        plan,
+        LogicalPlan::Projection(_)
+            | LogicalPlan::Filter(_)
+            | LogicalPlan::Window(_)
+            | LogicalPlan::Aggregate(_)
+            | LogicalPlan::Sort(_)
+            | LogicalPlan::Join(_)
+            | LogicalPlan::CrossJoin(_)
+            | LogicalPlan::Repartition(_)
+            | LogicalPlan::Union(_)
+            | LogicalPlan::TableScan(_)
+            | LogicalPlan::EmptyRelation(_)
+            | LogicalPlan::Subquery(_)
+            | LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::SharedCte(_)
+            | LogicalPlan::Limit(_)
+            | LogicalPlan::Values(_)
+            | LogicalPlan::Distinct(_)
+    )
+}
+
+/// The table sources and the user defined functions of a plan, which its
+/// canonical form only shows by name, and whether it calls stable functions
+#[derive(Default)]
+struct PlanObjects {
+    sources: Vec<Arc<dyn TableSource>>,
+    scalar_functions: Vec<Arc<ScalarUDF>>,
+    aggregate_functions: Vec<Arc<AggregateUDF>>,
+    stable: bool,
+}
+
+impl PlanObjects {
+    fn try_new(plan: &LogicalPlan) -> Result<Self> {
+        let mut objects = Self::default();
+        plan.accept(&mut objects)?;
+        Ok(objects)
+    }
+
+    /// Returns true if both plans have the same objects
+    fn same_as(&self, other: &Self) -> bool {
+        fn same<T: ?Sized>(left: &[Arc<T>], right: &[Arc<T>]) -> bool {
+            left.len() == right.len()
+                && left.iter().zip(right).all(|(l, r)| Arc::ptr_eq(l, r))
+        }
+        same(&self.sources, &other.sources)
+            && same(&self.scalar_functions, &other.scalar_functions)
+            && same(&self.aggregate_functions, &other.aggregate_functions)
+    }
+}
+
+impl PlanVisitor for PlanObjects {
+    type Error = DataFusionError;
+
+    fn pre_visit(&mut self, plan: &LogicalPlan) -> Result<bool> {
+        if let LogicalPlan::TableScan(TableScan { source, .. }) = plan {
+            self.sources.push(source.clone());
+        }
+        for expr in plan.expressions() {
+            expr.accept(ExprObjects(&mut *self))?;
+        }
+        Ok(true)
+    }
+}
+
+/// Collects the user defined functions called by an expression into
+/// [`PlanObjects`]
+struct ExprObjects<'a>(&'a mut PlanObjects);
+
+impl<'a> ExpressionVisitor for ExprObjects<'a> {
+    fn pre_visit(self, expr: &Expr) -> Result<Recursion<Self>> {
+        let volatility = match expr {
+            Expr::ScalarFunction { fun, .. } => fun.volatility(),
+            Expr::ScalarUDF { fun, .. } => {
+                self.0.scalar_functions.push(fun.clone());
+                fun.signature.volatility
+            }
+            Expr::AggregateUDF { fun, .. } => {
+                self.0.aggregate_functions.push(fun.clone());
+                Volatility::Immutable
+            }
+            Expr::ScalarVariable(_, _) => Volatility::Stable,
+            _ => Volatility::Immutable,
+        };
+        if volatility == Volatility::Stable {
+            self.0.stable = true;
+        }
+        Ok(Recursion::Continue(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::MemTable;
+    use crate::execution::context::{SessionConfig, SessionState};
+    use crate::execution::runtime_env::RuntimeEnv;
+    use crate::from_slice::FromSlice;
+    use crate::optimizer::policy::{PolicyProvider, TablePolicy};
+    use crate::prelude::SessionContext;
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion_common::TableReference;
+    use datafusion_expr::{col, lit};
+    use std::time::Duration;
+
+    fn table(values: &[i32]) -> Result<Arc<MemTable>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice(values))],
+        )?;
+        Ok(Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))
+    }
+
+    fn context(cache: Arc<PlanCache>) -> Result<SessionContext> {
+        let state = SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+        .with_plan_cache(cache);
+        let ctx = SessionContext::with_state(state);
+        ctx.register_table("t", table(&[1, 2, 3])?)?;
+        Ok(ctx)
+    }
+
+    async fn sum(ctx: &SessionContext, sql: &str) -> Result<String> {
+        let batches = ctx.sql(sql).await?.collect().await?;
+        Ok(arrow::util::pretty::pretty_format_batches(&batches)?.to_string())
+    }
+
+    #[tokio::test]
+    async fn reuse_plans() -> Result<()> {
+        let cache = Arc::new(PlanCache::new(10));
+        let ctx = context(cache.clone())?;
+        let sql = "SELECT sum(a) AS s FROM t WHERE a > 1";
+
+        let first = sum(&ctx, sql).await?;
+        assert!(first.contains("| 5 |"), "{first}");
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert_eq!(cache.len(), 1);
+
+        // neither parsed, nor analyzed, nor optimized again
+        let df = ctx.sql(sql).await?;
+        let metrics = df.planning_metrics();
+        df.collect().await?;
+        assert_eq!(metrics.parsing(), Duration::ZERO);
+        assert!(metrics.analyzer_rules().is_empty());
+        assert!(metrics.optimizer_rules().is_empty());
+        assert_eq!(sum(&ctx, sql).await?, first);
+        assert_eq!((cache.hits(), cache.misses()), (2, 1));
+        assert!((cache.hit_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+
+        // statements are not cached
+        ctx.sql("CREATE VIEW v AS SELECT a FROM t").await?;
+        assert_eq!(cache.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalidate_plans() -> Result<()> {
+        let cache = Arc::new(PlanCache::new(10));
+        let ctx = context(cache.clone())?;
+        let sql = "SELECT sum(a) AS s FROM t";
+        assert!(sum(&ctx, sql).await?.contains("| 6 |"));
+
+        // replacing the table
+        ctx.deregister_table("t")?;
+        ctx.register_table("t", table(&[10])?)?;
+        assert!(sum(&ctx, sql).await?.contains("| 10 |"));
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+
+        // shadowing it with a temporary table
+        ctx.sql("CREATE TEMPORARY TABLE t AS VALUES (20)").await?;
+        let shadowed = ctx.sql("SELECT sum(column1) AS s FROM t").await?;
+        assert!(shadowed.collect().await.is_ok());
+        assert!(sum(&ctx, sql).await.is_err());
+
+        // changing the configuration
+        let ctx = context(cache.clone())?;
+        assert!(sum(&ctx, sql).await?.contains("| 6 |"));
+        let (hits, misses) = (cache.hits(), cache.misses());
+        assert!(sum(&ctx, sql).await?.contains("| 6 |"));
+        assert_eq!((cache.hits(), cache.misses()), (hits + 1, misses));
+        ctx.sql("SET datafusion.execution.batch_size = 1024")
+            .await?;
+        assert!(sum(&ctx, sql).await?.contains("| 6 |"));
+        assert_eq!((cache.hits(), cache.misses()), (hits + 1, misses + 1));
+        Ok(())
+    }
+
+    /// Only lets `tenant_<n>` see the rows of `t` with `a = n`
+    struct TenantPolicy {}
+
+    impl PolicyProvider for TenantPolicy {
+        fn table_policy(
+            &self,
+            table: TableReference,
+            user: &str,
+        ) -> Result<Option<TablePolicy>> {
+            Ok((table.table() == "t").then(|| {
+                let tenant = user.trim_start_matches("tenant_").parse::<i32>().unwrap();
+                TablePolicy::with_predicate(col("a").eq(lit(tenant)))
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn separate_plans_of_policies() -> Result<()> {
+        let cache = Arc::new(PlanCache::new(10));
+        let state = SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+        .with_plan_cache(cache.clone());
+        let policies: Arc<dyn PolicyProvider> = Arc::new(TenantPolicy {});
+        let t = table(&[1, 2, 3])?;
+        let tenant = |user: &str| -> Result<SessionContext> {
+            let state = state.clone().with_policy_provider(policies.clone(), user);
+            let ctx = SessionContext::with_state(state);
+            ctx.register_table("t", t.clone())?;
+            Ok(ctx)
+        };
+        let (tenant_1, tenant_2) = (tenant("tenant_1")?, tenant("tenant_2")?);
+        let sql = "SELECT sum(a) AS s FROM t";
+
+        assert!(sum(&tenant_1, sql).await?.contains("| 1 |"));
+        // the plan of tenant_1 is reused, but not its optimized plan
+        assert!(sum(&tenant_2, sql).await?.contains("| 2 |"));
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert!(sum(&tenant_1, sql).await?.contains("| 1 |"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn evict_least_recently_used() -> Result<()> {
+        let cache = Arc::new(PlanCache::new(2));
+        let ctx = context(cache.clone())?;
+        for sql in ["SELECT 1", "SELECT 2", "SELECT 1", "SELECT 3", "SELECT 1"] {
+            ctx.sql(sql).await?;
+        }
+        assert_eq!(cache.len(), 2);
+        assert_eq!((cache.hits(), cache.misses()), (2, 3));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        Ok(())
+    }
+}
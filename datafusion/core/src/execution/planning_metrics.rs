@@ -0,0 +1,169 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Times spent planning a query, from parsing its SQL to creating its
+//! physical plan
+
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+/// The time spent in each step of planning a [`DataFrame`]: parsing and
+/// planning its SQL, applying each analyzer and optimizer rule to its
+/// logical plan, and creating its physical plan.
+///
+/// The metrics are shared by the [`DataFrame`] and the [`SessionState`] it
+/// is planned with, which records the time of each step as it runs. The
+/// times of the steps that run several times, for example when a
+/// [`DataFrame`] is executed twice or an optimizer rule runs in several
+/// passes, are summed.
+///
+/// They are returned by [`DataFrame::planning_metrics`], and shown by
+/// `EXPLAIN ANALYZE`, to diagnose queries that are slow to plan, for example
+/// with huge `IN` lists or schemas of many columns.
+///
+/// [`DataFrame`]: crate::dataframe::DataFrame
+/// [`DataFrame::planning_metrics`]: crate::dataframe::DataFrame::planning_metrics
+/// [`SessionState`]: super::context::SessionState
+#[derive(Debug, Default)]
+pub struct PlanningMetrics {
+    inner: Mutex<PlanningMetricsInner>,
+}
+
+#[derive(Debug, Default)]
+struct PlanningMetricsInner {
+    parsing: Duration,
+    logical_planning: Duration,
+    analyzer_rules: Vec<(String, Duration)>,
+    optimizer_rules: Vec<(String, Duration)>,
+    physical_planning: Duration,
+}
+
+impl PlanningMetrics {
+    /// Create empty planning metrics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time spent parsing the SQL
+    pub fn parsing(&self) -> Duration {
+        self.inner.lock().parsing
+    }
+
+    /// Time spent creating the logical plan from the parsed SQL
+    pub fn logical_planning(&self) -> Duration {
+        self.inner.lock().logical_planning
+    }
+
+    /// Time spent in each analyzer rule, in the order they first ran
+    pub fn analyzer_rules(&self) -> Vec<(String, Duration)> {
+        self.inner.lock().analyzer_rules.clone()
+    }
+
+    /// Time spent in all the analyzer rules
+    pub fn analysis(&self) -> Duration {
+        self.inner
+            .lock()
+            .analyzer_rules
+            .iter()
+            .map(|(_, d)| *d)
+            .sum()
+    }
+
+    /// Time spent in each optimizer rule, in the order they first ran
+    pub fn optimizer_rules(&self) -> Vec<(String, Duration)> {
+        self.inner.lock().optimizer_rules.clone()
+    }
+
+    /// Time spent in all the optimizer rules
+    pub fn optimization(&self) -> Duration {
+        self.inner
+            .lock()
+            .optimizer_rules
+            .iter()
+            .map(|(_, d)| *d)
+            .sum()
+    }
+
+    /// Time spent creating the physical plan from the optimized logical plan,
+    /// including the physical optimizer rules
+    pub fn physical_planning(&self) -> Duration {
+        self.inner.lock().physical_planning
+    }
+
+    /// Total time spent planning
+    pub fn total(&self) -> Duration {
+        self.parsing()
+            + self.logical_planning()
+            + self.analysis()
+            + self.optimization()
+            + self.physical_planning()
+    }
+
+    pub(crate) fn add_parsing(&self, elapsed: Duration) {
+        self.inner.lock().parsing += elapsed;
+    }
+
+    pub(crate) fn add_logical_planning(&self, elapsed: Duration) {
+        self.inner.lock().logical_planning += elapsed;
+    }
+
+    pub(crate) fn add_analyzer_rule(&self, name: &str, elapsed: Duration) {
+        add_rule(&mut self.inner.lock().analyzer_rules, name, elapsed)
+    }
+
+    pub(crate) fn add_optimizer_rule(&self, name: &str, elapsed: Duration) {
+        add_rule(&mut self.inner.lock().optimizer_rules, name, elapsed)
+    }
+
+    pub(crate) fn add_physical_planning(&self, elapsed: Duration) {
+        self.inner.lock().physical_planning += elapsed;
+    }
+}
+
+fn add_rule(rules: &mut Vec<(String, Duration)>, name: &str, elapsed: Duration) {
+    match rules.iter_mut().find(|(rule, _)| rule == name) {
+        Some((_, total)) => *total += elapsed,
+        None => rules.push((name.to_string(), elapsed)),
+    }
+}
+
+impl Display for PlanningMetrics {
+    /// Formats the time of each step, and with the alternate flag `{:#}`, of
+    /// each rule on its own line
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "parsing={:?}, logical_planning={:?}, analysis={:?}, optimization={:?}, physical_planning={:?}",
+            self.parsing(),
+            self.logical_planning(),
+            self.analysis(),
+            self.optimization(),
+            self.physical_planning()
+        )?;
+        if f.alternate() {
+            for (rule, elapsed) in self.analyzer_rules() {
+                write!(f, "\n  analyzer rule {rule}={elapsed:?}")?;
+            }
+            for (rule, elapsed) in self.optimizer_rules() {
+                write!(f, "\n  optimizer rule {rule}={elapsed:?}")?;
+            }
+        }
+        Ok(())
+    }
+}
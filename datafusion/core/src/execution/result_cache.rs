@@ -0,0 +1,360 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Cache of the results of queries, shared by the sessions of a
+//! [`RuntimeEnv`](super::runtime_env::RuntimeEnv)
+
+use std::collections::HashMap;
+
+use arrow::record_batch::RecordBatch;
+use datafusion_common::DataFusionError;
+use datafusion_expr::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
+use datafusion_expr::logical_plan::{LogicalPlan, PlanVisitor, TableScan};
+use datafusion_expr::{Expr, Volatility};
+use parking_lot::Mutex;
+
+use crate::datasource::source_as_provider;
+use crate::error::Result;
+use crate::physical_plan::common::batch_byte_size;
+
+/// The key of the results of a query in a [`ResultCache`]: the canonical
+/// text of its optimized logical plan, and the snapshot versions of the
+/// tables it scans.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResultCacheKey {
+    /// The optimized logical plan, with the schema of each node
+    plan: String,
+    /// The name and [`TableProvider::snapshot_version`] of each scanned table,
+    /// in the order of the scans
+    ///
+    /// [`TableProvider::snapshot_version`]: crate::datasource::TableProvider::snapshot_version
+    versions: Vec<(String, u64)>,
+}
+
+impl ResultCacheKey {
+    /// Returns the key of the results of the optimized `plan`, or `None` if
+    /// they can't be cached, because
+    ///
+    /// * the plan is not a query,
+    /// * it calls volatile or stable functions, or reads variables, whose
+    ///   values may change between executions,
+    /// * or a table it scans doesn't have a snapshot version.
+    pub fn try_new(plan: &LogicalPlan) -> Result<Option<Self>> {
+        let mut visitor = CacheableVisitor {
+            versions: vec![],
+            cacheable: true,
+        };
+        plan.accept(&mut visitor)?;
+        if !visitor.cacheable {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            plan: plan.display_indent_schema().to_string(),
+            versions: visitor.versions,
+        }))
+    }
+}
+
+/// Collects the snapshot versions of the tables scanned by a plan, and
+/// checks that its results can be cached
+struct CacheableVisitor {
+    versions: Vec<(String, u64)>,
+    cacheable: bool,
+}
+
+impl PlanVisitor for CacheableVisitor {
+    type Error = DataFusionError;
+
+    fn pre_visit(&mut self, plan: &LogicalPlan) -> Result<bool> {
+        self.cacheable = match plan {
+            LogicalPlan::TableScan(TableScan {
+                table_name, source, ..
+            }) => {
+                let version = source_as_provider(source)
+                    .ok()
+                    .and_then(|provider| provider.snapshot_version());
+                match version {
+                    Some(version) => {
+                        self.versions.push((table_name.clone(), version));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            LogicalPlan::Projection(_)
+            | LogicalPlan::Filter(_)
+            | LogicalPlan::Window(_)
+            | LogicalPlan::Aggregate(_)
+            | LogicalPlan::Sort(_)
+            | LogicalPlan::Join(_)
+            | LogicalPlan::CrossJoin(_)
+            | LogicalPlan::Repartition(_)
+            | LogicalPlan::Union(_)
+            | LogicalPlan::EmptyRelation(_)
+            | LogicalPlan::Subquery(_)
+            | LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::SharedCte(_)
+            | LogicalPlan::Limit(_)
+            | LogicalPlan::Values(_)
+            | LogicalPlan::Distinct(_) => {
+                let mut deterministic = true;
+                for expr in plan.expressions() {
+                    deterministic = expr.accept(DeterministicVisitor(true))?.0;
+                    if !deterministic {
+                        break;
+                    }
+                }
+                deterministic
+            }
+            _ => false,
+        };
+        Ok(self.cacheable)
+    }
+}
+
+/// Checks that an expression evaluates to the same values for the same
+/// input in all executions
+struct DeterministicVisitor(bool);
+
+impl ExpressionVisitor for DeterministicVisitor {
+    fn pre_visit(self, expr: &Expr) -> Result<Recursion<Self>> {
+        let volatility = match expr {
+            Expr::ScalarFunction { fun, .. } => fun.volatility(),
+            Expr::ScalarUDF { fun, .. } => fun.signature.volatility,
+            Expr::ScalarVariable(_, _) => Volatility::Stable,
+            _ => Volatility::Immutable,
+        };
+        Ok(match volatility {
+            Volatility::Immutable => Recursion::Continue(self),
+            Volatility::Stable | Volatility::Volatile => Recursion::Stop(Self(false)),
+        })
+    }
+}
+
+/// A cache of the results of queries, evicting the least recently used
+/// results once they take more than `max_size` bytes.
+///
+/// It is configured with [`RuntimeConfig::with_result_cache`], and used by
+/// [`DataFrame::collect`] for the queries with a [`ResultCacheKey`], for
+/// example dashboards running the same queries over tables that seldom
+/// change.
+///
+/// [`RuntimeConfig::with_result_cache`]: super::runtime_env::RuntimeConfig::with_result_cache
+/// [`DataFrame::collect`]: crate::dataframe::DataFrame::collect
+#[derive(Debug)]
+pub struct ResultCache {
+    max_size: usize,
+    state: Mutex<ResultCacheState>,
+}
+
+#[derive(Debug, Default)]
+struct ResultCacheState {
+    entries: HashMap<ResultCacheKey, ResultCacheEntry>,
+    /// The total size of the cached results, in bytes
+    size: usize,
+    /// The number of accesses to the cache so far
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct ResultCacheEntry {
+    batches: Vec<RecordBatch>,
+    size: usize,
+    /// The value of the clock when the results were last accessed
+    last_access: u64,
+}
+
+impl ResultCache {
+    /// Create a cache holding up to `max_size` bytes of results
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            state: Mutex::new(ResultCacheState::default()),
+        }
+    }
+
+    /// The maximum size of the cached results, in bytes
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// The total size of the cached results, in bytes
+    pub fn size(&self) -> usize {
+        self.state.lock().size
+    }
+
+    /// The number of cached results
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Returns true if no results are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached results for `key`, if any
+    pub fn get(&self, key: &ResultCacheKey) -> Option<Vec<RecordBatch>> {
+        let mut state = self.state.lock();
+        state.clock += 1;
+        let clock = state.clock;
+        let entry = state.entries.get_mut(key)?;
+        entry.last_access = clock;
+        Some(entry.batches.clone())
+    }
+
+    /// Caches the results for `key`, evicting the least recently used
+    /// results as needed. Results larger than the whole cache are not
+    /// cached.
+    pub fn insert(&self, key: ResultCacheKey, batches: Vec<RecordBatch>) {
+        let size = batches.iter().map(batch_byte_size).sum::<usize>();
+        if size > self.max_size {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        if let Some(previous) = state.entries.remove(&key) {
+            state.size -= previous.size;
+        }
+        while state.size + size > self.max_size {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| key.clone())
+                .expect("cached results");
+            let evicted = state.entries.remove(&oldest).unwrap();
+            state.size -= evicted.size;
+        }
+
+        state.clock += 1;
+        let last_access = state.clock;
+        state.size += size;
+        state.entries.insert(
+            key,
+            ResultCacheEntry {
+                batches,
+                size,
+                last_access,
+            },
+        );
+    }
+
+    /// Removes all the cached results
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.size = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::MemTable;
+    use crate::from_slice::FromSlice;
+    use crate::prelude::SessionContext;
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    fn batch(values: &[i32]) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from_slice(values))])
+            .unwrap()
+    }
+
+    fn key(plan: &str) -> ResultCacheKey {
+        ResultCacheKey {
+            plan: plan.to_string(),
+            versions: vec![],
+        }
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let size = batch_byte_size(&batch(&[1, 2, 3]));
+        let cache = ResultCache::new(2 * size);
+
+        cache.insert(key("a"), vec![batch(&[1, 2, 3])]);
+        cache.insert(key("b"), vec![batch(&[4, 5, 6])]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.size(), 2 * size);
+
+        // "a" is used more recently than "b"
+        assert!(cache.get(&key("a")).is_some());
+        cache.insert(key("c"), vec![batch(&[7, 8, 9])]);
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("b")).is_none());
+        assert!(cache.get(&key("c")).is_some());
+        assert_eq!(cache.size(), 2 * size);
+
+        // results larger than the cache are not cached
+        cache.insert(
+            key("d"),
+            vec![batch(&[1, 2, 3]), batch(&[4, 5, 6]), batch(&[7])],
+        );
+        assert!(cache.get(&key("d")).is_none());
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.size(), 0);
+    }
+
+    async fn cache_key(
+        ctx: &SessionContext,
+        sql: &str,
+    ) -> Result<Option<ResultCacheKey>> {
+        let plan = ctx.sql(sql).await?.into_optimized_plan()?;
+        ResultCacheKey::try_new(&plan)
+    }
+
+    #[tokio::test]
+    async fn cacheable_queries() -> Result<()> {
+        let ctx = SessionContext::new();
+        let table = MemTable::try_new(batch(&[1]).schema(), vec![vec![batch(&[1, 2])]])?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        let key = cache_key(&ctx, "SELECT sum(a) FROM t WHERE a > 1").await?;
+        assert!(key.is_some());
+        assert_eq!(
+            key,
+            cache_key(&ctx, "SELECT sum(a) FROM t WHERE a > 1").await?
+        );
+        assert_ne!(
+            key,
+            cache_key(&ctx, "SELECT sum(a) FROM t WHERE a > 2").await?
+        );
+
+        // the same query over new contents of the table
+        let table = MemTable::try_new(batch(&[1]).schema(), vec![vec![batch(&[3])]])?;
+        ctx.deregister_table("t")?;
+        ctx.register_table("t", Arc::new(table))?;
+        let new_key = cache_key(&ctx, "SELECT sum(a) FROM t WHERE a > 1").await?;
+        assert!(new_key.is_some());
+        assert_ne!(key, new_key);
+
+        // volatile functions
+        assert!(cache_key(&ctx, "SELECT a, random() FROM t")
+            .await?
+            .is_none());
+        // not a query
+        assert!(cache_key(&ctx, "EXPLAIN SELECT a FROM t").await?.is_none());
+        Ok(())
+    }
+}
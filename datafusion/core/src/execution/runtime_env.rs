@@ -27,7 +27,9 @@ use std::collections::HashMap;
 use crate::datasource::datasource::TableProviderFactory;
 use crate::datasource::listing_table_factory::ListingTableFactory;
 use crate::datasource::object_store::ObjectStoreRegistry;
+use crate::datasource::object_store_cache::{CachedObjectStore, ObjectStoreCache};
 use crate::execution::memory_pool::{GreedyMemoryPool, MemoryPool, UnboundedMemoryPool};
+use crate::execution::result_cache::ResultCache;
 use datafusion_common::DataFusionError;
 use object_store::ObjectStore;
 use std::fmt::{Debug, Formatter};
@@ -46,6 +48,12 @@ pub struct RuntimeEnv {
     pub object_store_registry: Arc<ObjectStoreRegistry>,
     /// TableProviderFactories
     pub table_factories: HashMap<String, Arc<dyn TableProviderFactory>>,
+    /// Cache of the results of queries, if enabled
+    pub result_cache: Option<Arc<ResultCache>>,
+    /// Maximum number of concurrent requests of each query to the object stores
+    pub max_concurrent_object_store_requests: Option<usize>,
+    /// Local cache of the bytes read from remote object stores, if enabled
+    pub object_store_cache: Option<Arc<ObjectStoreCache>>,
 }
 
 impl Debug for RuntimeEnv {
@@ -62,6 +70,9 @@ impl RuntimeEnv {
             disk_manager,
             object_store_registry,
             table_factories,
+            result_cache_size,
+            max_concurrent_object_store_requests,
+            object_store_cache,
         } = config;
 
         let memory_pool =
@@ -72,6 +83,9 @@ impl RuntimeEnv {
             disk_manager: DiskManager::try_new(disk_manager)?,
             object_store_registry,
             table_factories,
+            result_cache: result_cache_size.map(|size| Arc::new(ResultCache::new(size))),
+            max_concurrent_object_store_requests,
+            object_store_cache,
         })
     }
 
@@ -94,6 +108,18 @@ impl RuntimeEnv {
             .register_store(scheme, host, object_store)
     }
 
+    /// Removes the `ObjectStore` registered for this scheme and host,
+    /// returning it, if any.
+    ///
+    /// See [`ObjectStoreRegistry`] for more details
+    pub fn deregister_object_store(
+        &self,
+        scheme: impl AsRef<str>,
+        host: impl AsRef<str>,
+    ) -> Option<Arc<dyn ObjectStore>> {
+        self.object_store_registry.deregister_store(scheme, host)
+    }
+
     /// Registers TableFactories
     pub fn register_table_factories(
         &mut self,
@@ -105,10 +131,22 @@ impl RuntimeEnv {
     /// Retrieves a `ObjectStore` instance for a url by consulting the
     /// registery. See [`ObjectStoreRegistry::get_by_url`] for more
     /// details.
+    ///
+    /// The stores of remote urls, that is other than `file://` urls, read
+    /// through the [`ObjectStoreCache`], if any.
     pub fn object_store(&self, url: impl AsRef<Url>) -> Result<Arc<dyn ObjectStore>> {
-        self.object_store_registry
+        let url = url.as_ref();
+        let store = self
+            .object_store_registry
             .get_by_url(url)
-            .map_err(DataFusionError::from)
+            .map_err(DataFusionError::from)?;
+        Ok(match &self.object_store_cache {
+            Some(cache) if url.scheme() != "file" => {
+                let key = &url[url::Position::BeforeScheme..url::Position::BeforePath];
+                Arc::new(CachedObjectStore::new(store, cache.clone(), key))
+            }
+            _ => store,
+        })
     }
 }
 
@@ -131,6 +169,18 @@ pub struct RuntimeConfig {
     pub object_store_registry: Arc<ObjectStoreRegistry>,
     /// Custom table factories for things like deltalake that are not part of core datafusion
     pub table_factories: HashMap<String, Arc<dyn TableProviderFactory>>,
+    /// Maximum size in bytes of the cache of the results of queries
+    ///
+    /// Defaults to no cache if `None`
+    pub result_cache_size: Option<usize>,
+    /// Maximum number of concurrent requests of each query to the object stores
+    ///
+    /// Defaults to no limit if `None`
+    pub max_concurrent_object_store_requests: Option<usize>,
+    /// Local cache of the bytes read from remote object stores
+    ///
+    /// Defaults to no cache if `None`
+    pub object_store_cache: Option<Arc<ObjectStoreCache>>,
 }
 
 impl RuntimeConfig {
@@ -143,6 +193,7 @@ impl RuntimeConfig {
         table_factories.insert("JSON".into(), Arc::new(ListingTableFactory::new()));
         table_factories.insert("NDJSON".into(), Arc::new(ListingTableFactory::new()));
         table_factories.insert("AVRO".into(), Arc::new(ListingTableFactory::new()));
+        table_factories.insert("ARROW".into(), Arc::new(ListingTableFactory::new()));
         Self {
             table_factories,
             ..Default::default()
@@ -190,6 +241,31 @@ impl RuntimeConfig {
         self.with_memory_pool(Arc::new(GreedyMemoryPool::new(pool_size)))
     }
 
+    /// Cache up to `max_size` bytes of the results of deterministic queries
+    /// over tables with a snapshot version, see [`ResultCache`]
+    pub fn with_result_cache(mut self, max_size: usize) -> Self {
+        self.result_cache_size = Some(max_size);
+        self
+    }
+
+    /// Limit the requests of each query to the object stores to `max_requests`
+    /// running concurrently, for example to not exceed the request rate of an
+    /// S3 bucket when scanning thousands of small files
+    pub fn with_max_concurrent_object_store_requests(
+        mut self,
+        max_requests: usize,
+    ) -> Self {
+        self.max_concurrent_object_store_requests = Some(max_requests);
+        self
+    }
+
+    /// Cache the bytes read from remote object stores in `cache`, see
+    /// [`ObjectStoreCache`]
+    pub fn with_object_store_cache(mut self, cache: Arc<ObjectStoreCache>) -> Self {
+        self.object_store_cache = Some(cache);
+        self
+    }
+
     /// Use the specified path to create any needed temporary files
     pub fn with_temp_file_path(self, path: impl Into<PathBuf>) -> Self {
         self.with_disk_manager(DiskManagerConfig::new_specified(vec![path.into()]))
@@ -0,0 +1,85 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Transactions of a session, started by `BEGIN` and ended by `COMMIT` or
+//! `ROLLBACK`
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::SessionState;
+
+/// Starts the transactions of a session.
+///
+/// DataFusion itself doesn't write to tables, so it leaves the meaning of a
+/// transaction to the table providers that do: a [`TransactionManager`]
+/// configured with [`SessionState::with_transaction_manager`] starts a
+/// [`Transaction`] on `BEGIN`, which the providers find with
+/// [`SessionState::transaction`] to make their writes part of it, until
+/// `COMMIT` or `ROLLBACK` ends it.
+///
+/// Sessions without a [`TransactionManager`] are read-only, and fail on the
+/// transaction control statements.
+#[async_trait]
+pub trait TransactionManager: Sync + Send {
+    /// Start a new transaction for the session with the given state
+    async fn begin(&self, state: &SessionState) -> Result<Arc<dyn Transaction>>;
+}
+
+/// A transaction started by a [`TransactionManager`].
+///
+/// The transaction ends once committed or rolled back, whether it succeeds
+/// or not.
+#[async_trait]
+pub trait Transaction: Sync + Send {
+    /// Returns the transaction as [`Any`] so that it can be downcast to its
+    /// concrete type by the table providers that take part in it
+    fn as_any(&self) -> &dyn Any;
+
+    /// Atomically apply the changes of the transaction
+    async fn commit(&self) -> Result<()>;
+
+    /// Discard the changes of the transaction
+    async fn rollback(&self) -> Result<()>;
+
+    /// Define a savepoint named `name`, replacing any previous savepoint of
+    /// the same name
+    async fn savepoint(&self, _name: &str) -> Result<()> {
+        Err(savepoints_not_supported())
+    }
+
+    /// Discard the changes made since the savepoint named `name` was defined,
+    /// and the savepoints defined after it
+    async fn rollback_to_savepoint(&self, _name: &str) -> Result<()> {
+        Err(savepoints_not_supported())
+    }
+
+    /// Forget the savepoint named `name`, and the savepoints defined after it,
+    /// keeping the changes made since then
+    async fn release_savepoint(&self, _name: &str) -> Result<()> {
+        Err(savepoints_not_supported())
+    }
+}
+
+fn savepoints_not_supported() -> DataFusionError {
+    DataFusionError::NotImplemented(
+        "Savepoints are not supported by this transaction".to_string(),
+    )
+}
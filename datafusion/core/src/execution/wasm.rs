@@ -0,0 +1,500 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Scalar functions compiled to WebAssembly, for deployments that can't
+//! link native code.
+//!
+//! Functions are loaded at runtime, either with
+//! [`WasmFunctionFactory::create_udf`] or with `CREATE FUNCTION` once the
+//! factory is registered with
+//! [`SessionState::with_function_factory`](crate::execution::context::SessionState::with_function_factory):
+//!
+//! ```sql
+//! CREATE FUNCTION add_one(BIGINT) RETURNS BIGINT LANGUAGE wasm AS '/path/to/module.wasm';
+//! ```
+//!
+//! # Sandbox
+//!
+//! Modules can't import anything, so they have no access to the host, and
+//! each batch is evaluated by a fresh instance of the module, which is
+//! limited in the amount of memory it may use and in the amount of fuel
+//! (roughly, the number of instructions) it may consume.
+//!
+//! # ABI
+//!
+//! A module exports its linear `memory`, an allocator
+//! `alloc(size: i32) -> i32` returning 8 byte aligned memory, and the
+//! function itself under the name it is registered with:
+//!
+//! ```text
+//! name(args: i32, num_args: i32, out: i32) -> i32
+//! ```
+//!
+//! `args` points to `num_args` pointers to the arguments, and `out` to the
+//! array the function fills with its result. Arrays are passed as
+//! [Arrow C data interface] `ArrowArray` structs, laid out for `wasm32`
+//! (pointers are 32 bit offsets into the linear memory). Their types are the
+//! ones declared when creating the function, so no `ArrowSchema` is passed.
+//! The `release` callbacks are not called: the memory of an instance is
+//! discarded as a whole after each call. The function returns 0 on success.
+//!
+//! Only fixed width primitive types (integers, floats and dates) are
+//! supported.
+//!
+//! [Arrow C data interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+
+use std::sync::Arc;
+
+use arrow::array::{make_array, Array, ArrayData, ArrayRef};
+use arrow::buffer::Buffer;
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion_expr::{create_udf, CreateFunction, ScalarUDF, Volatility};
+use wasmtime::{
+    Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+};
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::{FunctionFactory, RegisterFunction, SessionState};
+use crate::physical_plan::functions::make_scalar_function;
+
+/// Size of an `ArrowArray` struct on `wasm32`
+const ARROW_ARRAY_SIZE: usize = 64;
+
+/// Default amount of fuel of a call
+const DEFAULT_FUEL: u64 = 1_000_000_000;
+
+/// Default limit of the linear memory of an instance
+const DEFAULT_MAX_MEMORY: usize = 256 * 1024 * 1024;
+
+/// Creates [`ScalarUDF`]s from WebAssembly modules, see the
+/// [module documentation](self) for the ABI they must implement.
+///
+/// As a [`FunctionFactory`], it handles `CREATE FUNCTION` statements of
+/// `LANGUAGE wasm`, whose definition is the path of the module, either in
+/// binary or in text format.
+#[derive(Clone)]
+pub struct WasmFunctionFactory {
+    engine: Engine,
+    fuel: u64,
+    max_memory: usize,
+}
+
+impl WasmFunctionFactory {
+    /// Create a factory with the default limits
+    pub fn try_new() -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(wasm_error)?;
+        Ok(Self {
+            engine,
+            fuel: DEFAULT_FUEL,
+            max_memory: DEFAULT_MAX_MEMORY,
+        })
+    }
+
+    /// Limit the fuel each call, which evaluates one batch, may consume
+    pub fn with_fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    /// Limit the size in bytes of the linear memory of each call
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// Create the function `name` exported by `module`, which is either in
+    /// binary or in text format
+    pub fn create_udf(
+        &self,
+        name: &str,
+        module: impl AsRef<[u8]>,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+    ) -> Result<ScalarUDF> {
+        if arg_types.is_empty() {
+            return Err(DataFusionError::Plan(format!(
+                "WASM function '{name}' must have at least one argument"
+            )));
+        }
+        for data_type in arg_types.iter().chain([&return_type]) {
+            byte_width(data_type)?;
+        }
+
+        let module = Module::new(&self.engine, module).map_err(wasm_error)?;
+        let function = WasmFunction {
+            name: name.to_string(),
+            module,
+            fuel: self.fuel,
+            max_memory: self.max_memory,
+            return_type: return_type.clone(),
+        };
+
+        Ok(create_udf(
+            name,
+            arg_types,
+            Arc::new(return_type),
+            Volatility::Immutable,
+            make_scalar_function(move |args| function.invoke(args)),
+        ))
+    }
+}
+
+#[async_trait]
+impl FunctionFactory for WasmFunctionFactory {
+    async fn create(
+        &self,
+        _state: &SessionState,
+        statement: CreateFunction,
+    ) -> Result<RegisterFunction> {
+        if statement.language.as_deref() != Some("wasm") {
+            return Err(DataFusionError::Plan(format!(
+                "Function '{}' must be of LANGUAGE wasm",
+                statement.name
+            )));
+        }
+        let path = statement.definition.ok_or_else(|| {
+            DataFusionError::Plan(
+                "WASM functions must be defined as the path of their module".to_string(),
+            )
+        })?;
+        let return_type = statement.return_type.ok_or_else(|| {
+            DataFusionError::Plan("WASM functions must declare their return type".into())
+        })?;
+        let module = tokio::fs::read(&path).await?;
+        let arg_types = statement
+            .args
+            .into_iter()
+            .map(|arg| arg.data_type)
+            .collect();
+
+        let udf = self.create_udf(&statement.name, module, arg_types, return_type)?;
+        Ok(RegisterFunction::Scalar(Arc::new(udf)))
+    }
+}
+
+/// The state of a [`Store`]
+struct StoreState {
+    limits: StoreLimits,
+}
+
+/// A function exported by a compiled module
+struct WasmFunction {
+    name: String,
+    module: Module,
+    fuel: u64,
+    max_memory: usize,
+    return_type: DataType,
+}
+
+impl WasmFunction {
+    /// Evaluate the function on a batch, in a fresh instance of the module
+    fn invoke(&self, args: &[ArrayRef]) -> Result<ArrayRef> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.max_memory)
+            .instances(1)
+            .build();
+        let mut store = Store::new(self.module.engine(), StoreState { limits });
+        store.limiter(|state| &mut state.limits);
+        store.add_fuel(self.fuel).map_err(wasm_error)?;
+
+        let instance =
+            Instance::new(&mut store, &self.module, &[]).map_err(wasm_error)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| self.error("the module does not export its memory"))?;
+        let mut guest = Guest {
+            store,
+            instance,
+            memory,
+        };
+
+        let mut arg_pointers = Vec::with_capacity(args.len() * 4);
+        for arg in args {
+            let pointer = guest.write_array(arg.data())?;
+            arg_pointers.extend_from_slice(&pointer.to_le_bytes());
+        }
+        let args_pointer = guest.write(&arg_pointers)?;
+        let out_pointer = guest.alloc(ARROW_ARRAY_SIZE)?;
+
+        let function = guest
+            .instance
+            .get_typed_func::<(u32, u32, u32), i32>(&mut guest.store, &self.name)
+            .map_err(wasm_error)?;
+        let status = function
+            .call(
+                &mut guest.store,
+                (args_pointer, args.len() as u32, out_pointer),
+            )
+            .map_err(|e| self.error(e))?;
+        if status != 0 {
+            return Err(self.error(format!("returned status {status}")));
+        }
+
+        let result = guest.read_array(out_pointer, &self.return_type)?;
+        if result.len() != args[0].len() {
+            return Err(self.error(format!(
+                "returned {} values for {} rows",
+                result.len(),
+                args[0].len()
+            )));
+        }
+        Ok(make_array(result))
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> DataFusionError {
+        DataFusionError::Execution(format!(
+            "WASM function '{}' failed: {message}",
+            self.name
+        ))
+    }
+}
+
+/// An instance of a module, and the memory it exports
+struct Guest {
+    store: Store<StoreState>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl Guest {
+    fn alloc(&mut self, size: usize) -> Result<u32> {
+        let alloc = self
+            .instance
+            .get_typed_func::<u32, u32>(&mut self.store, "alloc")
+            .map_err(wasm_error)?;
+        alloc.call(&mut self.store, size as u32).map_err(wasm_error)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<u32> {
+        let pointer = self.alloc(bytes.len())?;
+        self.memory
+            .write(&mut self.store, pointer as usize, bytes)
+            .map_err(wasm_error)?;
+        Ok(pointer)
+    }
+
+    fn read(&self, pointer: u32, len: usize) -> Result<Vec<u8>> {
+        let mut bytes = vec![0; len];
+        self.memory
+            .read(&self.store, pointer as usize, &mut bytes)
+            .map_err(wasm_error)?;
+        Ok(bytes)
+    }
+
+    /// Copy `data` into the guest memory, returning the address of its
+    /// `ArrowArray`
+    fn write_array(&mut self, data: &ArrayData) -> Result<u32> {
+        let validity = match data.null_buffer() {
+            Some(buffer) => self.write(buffer.as_slice())?,
+            None => 0,
+        };
+        let values = self.write(data.buffers()[0].as_slice())?;
+        let mut buffers = validity.to_le_bytes().to_vec();
+        buffers.extend_from_slice(&values.to_le_bytes());
+        let buffers = self.write(&buffers)?;
+
+        let mut array = Vec::with_capacity(ARROW_ARRAY_SIZE);
+        array.extend_from_slice(&(data.len() as i64).to_le_bytes());
+        array.extend_from_slice(&(data.null_count() as i64).to_le_bytes());
+        array.extend_from_slice(&(data.offset() as i64).to_le_bytes());
+        // n_buffers and n_children
+        array.extend_from_slice(&2i64.to_le_bytes());
+        array.extend_from_slice(&0i64.to_le_bytes());
+        array.extend_from_slice(&buffers.to_le_bytes());
+        // children, dictionary, release and private_data
+        array.resize(ARROW_ARRAY_SIZE, 0);
+        self.write(&array)
+    }
+
+    /// Copy the `ArrowArray` at `pointer` out of the guest memory
+    fn read_array(&self, pointer: u32, data_type: &DataType) -> Result<ArrayData> {
+        let array = self.read(pointer, ARROW_ARRAY_SIZE)?;
+        let int64 = |offset: usize| {
+            i64::from_le_bytes(array[offset..offset + 8].try_into().unwrap())
+        };
+        let uint32 = |offset: usize| {
+            u32::from_le_bytes(array[offset..offset + 4].try_into().unwrap())
+        };
+
+        // the null count is recomputed from the validity buffer
+        let (len, offset) = (int64(0), int64(16));
+        let (n_buffers, n_children) = (int64(24), int64(32));
+        if len < 0 || offset < 0 || n_buffers != 2 || n_children != 0 {
+            return Err(DataFusionError::Execution(format!(
+                "Invalid result array of WASM function: length={len}, \
+                offset={offset}, n_buffers={n_buffers}, n_children={n_children}"
+            )));
+        }
+        let (len, offset) = (len as usize, offset as usize);
+
+        let buffers = self.read(uint32(40), 8)?;
+        let validity = u32::from_le_bytes(buffers[0..4].try_into().unwrap());
+        let values = u32::from_le_bytes(buffers[4..8].try_into().unwrap());
+
+        let values = self.read(values, (offset + len) * byte_width(data_type)?)?;
+        let validity = match validity {
+            0 => None,
+            validity => Some(Buffer::from(self.read(validity, (offset + len + 7) / 8)?)),
+        };
+
+        Ok(ArrayData::try_new(
+            data_type.clone(),
+            len,
+            validity,
+            offset,
+            vec![Buffer::from(values)],
+            vec![],
+        )?)
+    }
+}
+
+/// The size of a value of `data_type`, for the types that can be passed to
+/// WASM functions
+fn byte_width(data_type: &DataType) -> Result<usize> {
+    match data_type {
+        DataType::Int8 | DataType::UInt8 => Ok(1),
+        DataType::Int16 | DataType::UInt16 => Ok(2),
+        DataType::Int32 | DataType::UInt32 | DataType::Float32 | DataType::Date32 => {
+            Ok(4)
+        }
+        DataType::Int64 | DataType::UInt64 | DataType::Float64 | DataType::Date64 => {
+            Ok(8)
+        }
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Type {data_type} is not supported by WASM functions"
+        ))),
+    }
+}
+
+fn wasm_error(e: impl std::fmt::Display) -> DataFusionError {
+    DataFusionError::Execution(format!("WASM error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int64Array;
+    use datafusion_expr::ColumnarValue;
+
+    /// Adds one to its BIGINT argument, reusing its validity buffer
+    const ADD_ONE: &str = r#"
+    (module
+      (memory (export "memory") 1)
+      (global $next (mut i32) (i32.const 8))
+      (func $alloc (export "alloc") (param $size i32) (result i32)
+        (local $pointer i32)
+        (local.set $pointer (global.get $next))
+        (global.set $next
+          (i32.and
+            (i32.add (i32.add (global.get $next) (local.get $size)) (i32.const 7))
+            (i32.const -8)))
+        (local.get $pointer))
+      (func (export "add_one") (param $args i32) (param $num_args i32) (param $out i32)
+                               (result i32)
+        (local $arg i32) (local $in_buffers i32) (local $in_values i32)
+        (local $len i32) (local $offset i32) (local $values i32) (local $buffers i32)
+        (local $i i32)
+        (local.set $arg (i32.load (local.get $args)))
+        (local.set $len (i32.wrap_i64 (i64.load (local.get $arg))))
+        (local.set $offset (i32.wrap_i64 (i64.load offset=16 (local.get $arg))))
+        (local.set $in_buffers (i32.load offset=40 (local.get $arg)))
+        (local.set $in_values (i32.load offset=4 (local.get $in_buffers)))
+        (local.set $values
+          (call $alloc (i32.shl (i32.add (local.get $offset) (local.get $len))
+                                (i32.const 3))))
+        (local.set $i (local.get $offset))
+        (block $done
+          (loop $next_value
+            (br_if $done
+              (i32.ge_u (local.get $i) (i32.add (local.get $offset) (local.get $len))))
+            (i64.store
+              (i32.add (local.get $values) (i32.shl (local.get $i) (i32.const 3)))
+              (i64.add
+                (i64.load (i32.add (local.get $in_values)
+                                   (i32.shl (local.get $i) (i32.const 3))))
+                (i64.const 1)))
+            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+            (br $next_value)))
+        (local.set $buffers (call $alloc (i32.const 8)))
+        (i32.store (local.get $buffers) (i32.load (local.get $in_buffers)))
+        (i32.store offset=4 (local.get $buffers) (local.get $values))
+        (i64.store (local.get $out) (i64.load (local.get $arg)))
+        (i64.store offset=8 (local.get $out) (i64.load offset=8 (local.get $arg)))
+        (i64.store offset=16 (local.get $out) (i64.load offset=16 (local.get $arg)))
+        (i64.store offset=24 (local.get $out) (i64.const 2))
+        (i64.store offset=32 (local.get $out) (i64.const 0))
+        (i32.store offset=40 (local.get $out) (local.get $buffers))
+        (i32.const 0))
+      (func (export "spin") (param i32 i32 i32) (result i32)
+        (loop $forever (br $forever))
+        (i32.const 0)))
+    "#;
+
+    fn invoke(udf: &ScalarUDF, array: ArrayRef) -> Result<ArrayRef> {
+        match (udf.fun)(&[ColumnarValue::Array(array)])? {
+            ColumnarValue::Array(array) => Ok(array),
+            ColumnarValue::Scalar(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn add_one() -> Result<()> {
+        let udf = WasmFunctionFactory::try_new()?.create_udf(
+            "add_one",
+            ADD_ONE,
+            vec![DataType::Int64],
+            DataType::Int64,
+        )?;
+
+        let array: ArrayRef =
+            Arc::new(Int64Array::from(vec![Some(1), None, Some(3), Some(4)]));
+        let result = invoke(&udf, array.slice(1, 3))?;
+        let expected: ArrayRef = Arc::new(Int64Array::from(vec![None, Some(4), Some(5)]));
+        assert_eq!(&result, &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn out_of_fuel() -> Result<()> {
+        let udf = WasmFunctionFactory::try_new()?
+            .with_fuel(10_000)
+            .create_udf("spin", ADD_ONE, vec![DataType::Int64], DataType::Int64)?;
+
+        let err = invoke(&udf, Arc::new(Int64Array::from(vec![1]))).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Execution error: WASM function 'spin' failed"),
+            "{err}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_type() -> Result<()> {
+        let err = WasmFunctionFactory::try_new()?
+            .create_udf("add_one", ADD_ONE, vec![DataType::Utf8], DataType::Int64)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This feature is not implemented: Type Utf8 is not supported by WASM functions"
+        );
+        Ok(())
+    }
+}
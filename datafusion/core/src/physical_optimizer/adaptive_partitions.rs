@@ -0,0 +1,317 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! AdaptivePartitions optimizer that adapts the number of partitions of the
+//! plan to the size of its input
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::file_format::{AvroExec, CsvExec, NdJsonExec, ParquetExec};
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::rewrite::TreeNode;
+use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::Partitioning::*;
+
+/// Maximum number of times `target_partitions` that huge inputs are
+/// repartitioned to
+const MAX_PARTITIONS_FACTOR: usize = 4;
+
+/// Optimizer rule that adapts the number of partitions of the plan to the
+/// size of its input, so that tiny queries don't pay for the overhead of
+/// `target_partitions` threads, and huge scans are split in more partitions
+/// to be processed in parallel.
+///
+/// The size of the input is the sum of the `total_byte_size` statistics of
+/// the leaves of the plan, or of the size of the files scanned by the leaves
+/// without statistics. The plan is left unchanged when the size of any leaf
+/// is unknown.
+///
+/// Each `RepartitionExec` that repartitions to `target_partitions` is
+/// changed to repartition to one partition per `adaptive_partition_bytes` of
+/// input instead. Since all the hash repartitions are changed alike, the
+/// inputs of partitioned joins still have the same number of partitions. A
+/// round robin repartition whose input already has enough partitions is
+/// removed.
+#[derive(Default)]
+pub struct AdaptivePartitions {}
+
+impl AdaptivePartitions {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for AdaptivePartitions {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let target_partitions = config.execution.target_partitions;
+        if !config.optimizer.enable_adaptive_target_partitions {
+            return Ok(plan);
+        }
+
+        let input_bytes = match input_bytes(plan.as_ref()) {
+            Some(input_bytes) => input_bytes,
+            None => return Ok(plan),
+        };
+        let partitions = adaptive_partition_count(
+            input_bytes,
+            config.optimizer.adaptive_partition_bytes,
+            target_partitions,
+        );
+        if partitions == target_partitions {
+            return Ok(plan);
+        }
+
+        plan.transform_up(&|plan| {
+            let repartition = match plan.as_any().downcast_ref::<RepartitionExec>() {
+                Some(repartition) => repartition,
+                None => return Ok(None),
+            };
+            let input = repartition.input();
+
+            let partitioning = match repartition.partitioning() {
+                RoundRobinBatch(n) if *n == target_partitions => {
+                    if input.output_partitioning().partition_count() >= partitions {
+                        return Ok(Some(input.clone()));
+                    }
+                    RoundRobinBatch(partitions)
+                }
+                Hash(exprs, n) if *n == target_partitions => {
+                    Hash(exprs.clone(), partitions)
+                }
+                _ => return Ok(None),
+            };
+            Ok(Some(Arc::new(RepartitionExec::try_new(
+                input.clone(),
+                partitioning,
+            )?)))
+        })
+    }
+
+    fn name(&self) -> &str {
+        "adaptive_partitions"
+    }
+
+    fn run_after(&self) -> &[&str] {
+        &["repartition", "EnforceDistribution"]
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the number of partitions to process `input_bytes` bytes with
+/// `partition_bytes` bytes per partition
+fn adaptive_partition_count(
+    input_bytes: usize,
+    partition_bytes: usize,
+    target_partitions: usize,
+) -> usize {
+    let partition_bytes = partition_bytes.max(1);
+    let partitions = (input_bytes + partition_bytes - 1) / partition_bytes;
+    partitions.clamp(1, (target_partitions * MAX_PARTITIONS_FACTOR).max(1))
+}
+
+/// Returns the estimated number of bytes read by the leaves of `plan`, if
+/// known for all of them
+fn input_bytes(plan: &dyn ExecutionPlan) -> Option<usize> {
+    let children = plan.children();
+    if children.is_empty() {
+        plan.statistics()
+            .total_byte_size
+            .get_value()
+            .copied()
+            .or_else(|| scanned_file_bytes(plan))
+    } else {
+        children
+            .iter()
+            .map(|child| input_bytes(child.as_ref()))
+            .sum()
+    }
+}
+
+/// Returns the total size of the files scanned by `plan`, if it is a file scan
+fn scanned_file_bytes(plan: &dyn ExecutionPlan) -> Option<usize> {
+    let plan_any = plan.as_any();
+    let config = if let Some(exec) = plan_any.downcast_ref::<ParquetExec>() {
+        exec.base_config()
+    } else if let Some(exec) = plan_any.downcast_ref::<CsvExec>() {
+        exec.base_config()
+    } else if let Some(exec) = plan_any.downcast_ref::<NdJsonExec>() {
+        exec.base_config()
+    } else if let Some(exec) = plan_any.downcast_ref::<AvroExec>() {
+        exec.base_config()
+    } else {
+        return None;
+    };
+
+    Some(
+        config
+            .file_groups
+            .iter()
+            .flatten()
+            .map(|file| file.object_meta.size)
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::listing::PartitionedFile;
+    use crate::datasource::object_store::ObjectStoreUrl;
+    use crate::physical_plan::expressions::col;
+    use crate::physical_plan::file_format::FileScanConfig;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::{Partitioning, Statistics};
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use arrow::record_batch::RecordBatch;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]))
+    }
+
+    /// A single partition of 1000 rows, that is a bit more than 8000 bytes
+    fn memory_exec() -> Arc<dyn ExecutionPlan> {
+        let array = Int64Array::from_iter_values(0..1000);
+        let batch = RecordBatch::try_new(schema(), vec![Arc::new(array)]).unwrap();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema(), None).unwrap())
+    }
+
+    /// A scan of 2 files of 5000 bytes without statistics
+    fn parquet_exec() -> Arc<dyn ExecutionPlan> {
+        Arc::new(ParquetExec::new(
+            FileScanConfig {
+                object_store_url: ObjectStoreUrl::parse("test:///").unwrap(),
+                file_schema: schema(),
+                file_groups: vec![
+                    vec![PartitionedFile::new("x".to_string(), 5000)],
+                    vec![PartitionedFile::new("y".to_string(), 5000)],
+                ],
+                statistics: Statistics::new_unknown(&schema()),
+                projection: None,
+                limit: None,
+                table_partition_cols: vec![],
+                output_ordering: None,
+                infinite_source: false,
+            },
+            None,
+            None,
+        ))
+    }
+
+    /// Hash repartitions the round robin repartitioned `input`, to
+    /// `partitions` partitions each
+    fn repartition_exec(
+        input: Arc<dyn ExecutionPlan>,
+        partitions: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let round_robin =
+            RepartitionExec::try_new(input, RoundRobinBatch(partitions)).unwrap();
+        let hash = Hash(vec![col("a", &schema()).unwrap()], partitions);
+        Arc::new(RepartitionExec::try_new(Arc::new(round_robin), hash).unwrap())
+    }
+
+    fn optimize(
+        plan: Arc<dyn ExecutionPlan>,
+        partition_bytes: usize,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut config = ConfigOptions::new();
+        config.execution.target_partitions = 8;
+        config.optimizer.enable_adaptive_target_partitions = true;
+        config.optimizer.adaptive_partition_bytes = partition_bytes;
+        AdaptivePartitions::new().optimize(plan, &config)
+    }
+
+    /// Returns the partitioning of `plan` and the partition count of its input
+    fn partitioning(plan: &Arc<dyn ExecutionPlan>) -> (Partitioning, usize) {
+        let repartition = plan.as_any().downcast_ref::<RepartitionExec>().unwrap();
+        let input_partitions = repartition.input().output_partitioning();
+        (
+            repartition.partitioning().clone(),
+            input_partitions.partition_count(),
+        )
+    }
+
+    #[test]
+    fn small_input() -> Result<()> {
+        // about 8000 bytes with 6000 bytes per partition
+        let plan = optimize(repartition_exec(memory_exec(), 8), 6000)?;
+
+        // the round robin repartition is changed to 2 partitions
+        let (hash, input_partitions) = partitioning(&plan);
+        assert_eq!(hash.partition_count(), 2);
+        assert!(matches!(hash, Hash(_, _)));
+        assert_eq!(input_partitions, 2);
+
+        // with 1 partition, the round robin repartition is removed
+        let plan = optimize(repartition_exec(memory_exec(), 8), 1024 * 1024)?;
+        let (hash, input_partitions) = partitioning(&plan);
+        assert_eq!(hash.partition_count(), 1);
+        assert_eq!(input_partitions, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn huge_input() -> Result<()> {
+        // up to 4 * 8 partitions
+        let plan = optimize(repartition_exec(memory_exec(), 8), 100)?;
+        let (hash, input_partitions) = partitioning(&plan);
+        assert_eq!(hash.partition_count(), 32);
+        assert_eq!(input_partitions, 32);
+        Ok(())
+    }
+
+    #[test]
+    fn scanned_files() -> Result<()> {
+        // 10000 bytes with 1000 bytes per partition
+        let plan = optimize(repartition_exec(parquet_exec(), 8), 1000)?;
+        let (hash, input_partitions) = partitioning(&plan);
+        assert_eq!(hash.partition_count(), 10);
+        assert_eq!(input_partitions, 10);
+        Ok(())
+    }
+
+    #[test]
+    fn other_partition_counts_unchanged() -> Result<()> {
+        // not repartitioned to target_partitions
+        let plan = optimize(repartition_exec(memory_exec(), 3), 4096)?;
+        let (hash, input_partitions) = partitioning(&plan);
+        assert_eq!(hash.partition_count(), 3);
+        assert_eq!(input_partitions, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn disabled() -> Result<()> {
+        let mut config = ConfigOptions::new();
+        config.execution.target_partitions = 8;
+        let plan = repartition_exec(memory_exec(), 8);
+        let plan = AdaptivePartitions::new().optimize(plan, &config)?;
+        assert_eq!(partitioning(&plan).0.partition_count(), 8);
+        Ok(())
+    }
+}
@@ -25,7 +25,7 @@ use crate::physical_plan::aggregates::{AggregateExec, AggregateMode};
 use crate::physical_plan::empty::EmptyExec;
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::{
-    expressions, AggregateExpr, ColumnStatistics, ExecutionPlan, Statistics,
+    expressions, AggregateExpr, ColumnStatistics, ExecutionPlan, Precision, Statistics,
 };
 use crate::scalar::ScalarValue;
 
@@ -107,7 +107,7 @@ impl PhysicalOptimizerRule for AggregateStatistics {
 /// assert if the node passed as argument is a final `AggregateExec` node that can be optimized:
 /// - its child (with possible intermediate layers) is a partial `AggregateExec` node
 /// - they both have no grouping expression
-/// - the statistics are exact
+/// - the number of input rows is known exactly
 /// If this is the case, return a ref to the partial `AggregateExec`, else `None`.
 /// We would have preferred to return a casted ref to AggregateExec but the recursion requires
 /// the `ExecutionPlan.children()` method that returns an owned reference.
@@ -125,7 +125,7 @@ fn take_optimizable(node: &dyn ExecutionPlan) -> Option<Arc<dyn ExecutionPlan>>
                         && partial_agg_exec.group_expr().is_empty()
                     {
                         let stats = partial_agg_exec.input().statistics();
-                        if stats.is_exact {
+                        if stats.num_rows.is_exact() == Some(true) {
                             return Some(child);
                         }
                     }
@@ -146,7 +146,7 @@ fn take_optimizable_table_count(
     agg_expr: &dyn AggregateExpr,
     stats: &Statistics,
 ) -> Option<(ScalarValue, &'static str)> {
-    if let (Some(num_rows), Some(casted_expr)) = (
+    if let (Precision::Exact(num_rows), Some(casted_expr)) = (
         stats.num_rows,
         agg_expr.as_any().downcast_ref::<expressions::Count>(),
     ) {
@@ -173,9 +173,8 @@ fn take_optimizable_column_count(
     agg_expr: &dyn AggregateExpr,
     stats: &Statistics,
 ) -> Option<(ScalarValue, String)> {
-    if let (Some(num_rows), Some(col_stats), Some(casted_expr)) = (
+    if let (Precision::Exact(num_rows), Some(casted_expr)) = (
         stats.num_rows,
-        &stats.column_statistics,
         agg_expr.as_any().downcast_ref::<expressions::Count>(),
     ) {
         if casted_expr.expressions().len() == 1 {
@@ -185,9 +184,9 @@ fn take_optimizable_column_count(
                 .downcast_ref::<expressions::Column>()
             {
                 if let ColumnStatistics {
-                    null_count: Some(val),
+                    null_count: Precision::Exact(val),
                     ..
-                } = &col_stats[col_expr.index()]
+                } = &stats.column_statistics[col_expr.index()]
                 {
                     let expr = format!("COUNT({})", col_expr.name());
                     return Some((
@@ -206,10 +205,7 @@ fn take_optimizable_min(
     agg_expr: &dyn AggregateExpr,
     stats: &Statistics,
 ) -> Option<(ScalarValue, String)> {
-    if let (Some(col_stats), Some(casted_expr)) = (
-        &stats.column_statistics,
-        agg_expr.as_any().downcast_ref::<expressions::Min>(),
-    ) {
+    if let Some(casted_expr) = agg_expr.as_any().downcast_ref::<expressions::Min>() {
         if casted_expr.expressions().len() == 1 {
             // TODO optimize with exprs other than Column
             if let Some(col_expr) = casted_expr.expressions()[0]
@@ -217,9 +213,9 @@ fn take_optimizable_min(
                 .downcast_ref::<expressions::Column>()
             {
                 if let ColumnStatistics {
-                    min_value: Some(val),
+                    min_value: Precision::Exact(val),
                     ..
-                } = &col_stats[col_expr.index()]
+                } = &stats.column_statistics[col_expr.index()]
                 {
                     return Some((val.clone(), format!("MIN({})", col_expr.name())));
                 }
@@ -234,10 +230,7 @@ fn take_optimizable_max(
     agg_expr: &dyn AggregateExpr,
     stats: &Statistics,
 ) -> Option<(ScalarValue, String)> {
-    if let (Some(col_stats), Some(casted_expr)) = (
-        &stats.column_statistics,
-        agg_expr.as_any().downcast_ref::<expressions::Max>(),
-    ) {
+    if let Some(casted_expr) = agg_expr.as_any().downcast_ref::<expressions::Max>() {
         if casted_expr.expressions().len() == 1 {
             // TODO optimize with exprs other than Column
             if let Some(col_expr) = casted_expr.expressions()[0]
@@ -245,9 +238,9 @@ fn take_optimizable_max(
                 .downcast_ref::<expressions::Column>()
             {
                 if let ColumnStatistics {
-                    max_value: Some(val),
+                    max_value: Precision::Exact(val),
                     ..
-                } = &col_stats[col_expr.index()]
+                } = &stats.column_statistics[col_expr.index()]
                 {
                     return Some((val.clone(), format!("MAX({})", col_expr.name())));
                 }
@@ -24,11 +24,15 @@ use crate::{
     physical_optimizer::PhysicalOptimizerRule,
     physical_plan::{
         coalesce_batches::CoalesceBatchesExec, filter::FilterExec, joins::HashJoinExec,
-        repartition::RepartitionExec, rewrite::TreeNodeRewritable, Partitioning,
+        repartition::RepartitionExec, rewrite::TreeNode, Partitioning,
     },
 };
+use arrow::datatypes::{DataType, IntervalUnit, Schema};
 use std::sync::Arc;
 
+/// Assumed average width in bytes of the variable-width values, such as strings
+const VARIABLE_WIDTH_BYTES: usize = 32;
+
 /// Optimizer rule that introduces CoalesceBatchesExec to avoid overhead with small batches that
 /// are produced by highly selective filters
 #[derive(Default)]
@@ -50,7 +54,8 @@ impl PhysicalOptimizerRule for CoalesceBatches {
             return Ok(plan);
         }
 
-        let target_batch_size = config.execution.batch_size;
+        let batch_size = config.execution.batch_size;
+        let target_bytes = config.execution.coalesce_target_bytes;
         plan.transform_up(&|plan| {
             let plan_any = plan.as_any();
             // The goal here is to detect operators that could produce small batches and only
@@ -70,6 +75,8 @@ impl PhysicalOptimizerRule for CoalesceBatches {
                     })
                     .unwrap_or(false);
             if wrap_in_coalesce {
+                let target_batch_size =
+                    target_batch_size(&plan.schema(), batch_size, target_bytes);
                 Ok(Some(Arc::new(CoalesceBatchesExec::new(
                     plan.clone(),
                     target_batch_size,
@@ -84,7 +91,88 @@ impl PhysicalOptimizerRule for CoalesceBatches {
         "coalesce_batches"
     }
 
+    fn run_after(&self) -> &[&str] {
+        &["EnforceDistribution", "EnforceSorting"]
+    }
+
     fn schema_check(&self) -> bool {
         true
     }
 }
+
+/// Returns the number of rows of the coalesced batches of `schema`: `batch_size`,
+/// unless the rows are so wide that it would exceed `target_bytes`
+fn target_batch_size(schema: &Schema, batch_size: usize, target_bytes: usize) -> usize {
+    if target_bytes == 0 {
+        return batch_size;
+    }
+
+    let row_width: usize = schema
+        .fields()
+        .iter()
+        .map(|field| estimated_width(field.data_type()))
+        .sum();
+    (target_bytes / row_width.max(1)).clamp(1, batch_size.max(1))
+}
+
+/// Returns the estimated width in bytes of a value of type `data_type`
+fn estimated_width(data_type: &DataType) -> usize {
+    match data_type {
+        DataType::Null => 0,
+        DataType::Boolean | DataType::Int8 | DataType::UInt8 => 1,
+        DataType::Int16 | DataType::UInt16 | DataType::Float16 => 2,
+        DataType::Int32
+        | DataType::UInt32
+        | DataType::Float32
+        | DataType::Date32
+        | DataType::Time32(_)
+        | DataType::Interval(IntervalUnit::YearMonth) => 4,
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Float64
+        | DataType::Date64
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_)
+        | DataType::Interval(IntervalUnit::DayTime) => 8,
+        DataType::Decimal128(_, _) | DataType::Interval(IntervalUnit::MonthDayNano) => 16,
+        DataType::Decimal256(_, _) => 32,
+        DataType::FixedSizeBinary(size) => *size as usize,
+        DataType::FixedSizeList(field, size) => {
+            estimated_width(field.data_type()) * *size as usize
+        }
+        DataType::Struct(fields) => fields
+            .iter()
+            .map(|field| estimated_width(field.data_type()))
+            .sum(),
+        DataType::Dictionary(key_type, _) => estimated_width(key_type),
+        DataType::Utf8 | DataType::Binary => 4 + VARIABLE_WIDTH_BYTES,
+        _ => 8 + VARIABLE_WIDTH_BYTES,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::Field;
+
+    #[test]
+    fn target_batch_size_by_row_width() {
+        let narrow = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+        let wide = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::FixedSizeBinary(1000), true),
+        ]);
+
+        // 8 byte rows
+        assert_eq!(target_batch_size(&narrow, 8192, 8 * 1024 * 1024), 8192);
+        assert_eq!(target_batch_size(&narrow, 8192, 8 * 1024), 1024);
+        // 1044 byte rows
+        assert_eq!(target_batch_size(&wide, 8192, 8 * 1024 * 1024), 8035);
+        assert_eq!(target_batch_size(&wide, 8192, 1024 * 1024), 1004);
+        assert_eq!(target_batch_size(&wide, 8192, 100), 1);
+        // disabled
+        assert_eq!(target_batch_size(&wide, 8192, 0), 8192);
+    }
+}
@@ -0,0 +1,285 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decide whether the references to a common table expression share its
+//! results, or execute its plan each.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::rewrite::TreeNode;
+use crate::physical_plan::shared_cte::SharedCteExec;
+use crate::physical_plan::{displayable, ExecutionPlan};
+
+/// A common table expression referenced several times in a query is planned
+/// as a [SharedCteExec] for each reference, over a copy of its plan.
+///
+/// This rule replaces all the references by a single [SharedCteExec] with a
+/// work table, which executes the plan once and buffers its results for all
+/// the references, unless
+///
+/// * the `datafusion.optimizer.enable_cte_materialization` option is false,
+/// * the results are estimated to be larger than the
+///   `datafusion.optimizer.cte_materialization_threshold` option,
+/// * or the previous rules optimized the copies of the plan differently, for
+///   the requirements of each reference.
+///
+/// Otherwise, each reference is replaced by its copy of the plan.
+#[derive(Default)]
+pub struct CteMaterialization {}
+
+impl CteMaterialization {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for CteMaterialization {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut references = HashMap::new();
+        collect_references(&plan, &mut references);
+        if references.is_empty() {
+            return Ok(plan);
+        }
+
+        let enabled = config.optimizer.enable_cte_materialization;
+        let threshold = config.optimizer.cte_materialization_threshold;
+        let shared: RefCell<HashMap<usize, Arc<dyn ExecutionPlan>>> = Default::default();
+        plan.transform_up(&|plan| {
+            let cte = match plan.as_any().downcast_ref::<SharedCteExec>() {
+                Some(cte) if cte.work_table().is_none() => cte,
+                _ => return Ok(None),
+            };
+            let materialize = enabled
+                && match references.get(&cte.id()) {
+                    Some(references) => references.count > 1 && references.same_plans,
+                    None => false,
+                }
+                && !exceeds_threshold(cte.input().as_ref(), threshold);
+            if !materialize {
+                return Ok(Some(cte.input().clone()));
+            }
+            // the nested common table expressions of the first reference are
+            // already shared, so it becomes the reference of all the others
+            let shared = shared
+                .borrow_mut()
+                .entry(cte.id())
+                .or_insert_with(|| {
+                    Arc::new(
+                        SharedCteExec::new(cte.id(), cte.name(), cte.input().clone())
+                            .with_work_table(),
+                    )
+                })
+                .clone();
+            Ok(Some(shared))
+        })
+    }
+
+    fn name(&self) -> &str {
+        "cte_materialization"
+    }
+
+    fn run_after(&self) -> &[&str] {
+        &["EnforceDistribution", "EnforceSorting", "coalesce_batches"]
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// The references to a common table expression
+struct CteReferences {
+    /// The number of references
+    count: usize,
+    /// Whether the plans of all the references are the same
+    same_plans: bool,
+    /// The plan of the first reference
+    plan: String,
+}
+
+/// Collects the references to each common table expression of the plan
+fn collect_references(
+    plan: &Arc<dyn ExecutionPlan>,
+    references: &mut HashMap<usize, CteReferences>,
+) {
+    if let Some(cte) = plan.as_any().downcast_ref::<SharedCteExec>() {
+        let cte_plan = displayable(cte.input().as_ref()).indent().to_string();
+        references
+            .entry(cte.id())
+            .and_modify(|references| {
+                references.count += 1;
+                references.same_plans &= references.plan == cte_plan;
+            })
+            .or_insert_with(|| CteReferences {
+                count: 1,
+                same_plans: true,
+                plan: cte_plan.clone(),
+            });
+    }
+    for child in plan.children() {
+        collect_references(&child, references);
+    }
+}
+
+/// Returns true if the results of the plan are estimated to be larger than
+/// `threshold` bytes
+fn exceeds_threshold(plan: &dyn ExecutionPlan, threshold: usize) -> bool {
+    let stats = plan.statistics();
+    stats
+        .total_byte_size
+        .get_value()
+        .map_or(false, |size| *size > threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::expressions::{col, PhysicalSortExpr};
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::sorts::sort::SortExec;
+    use crate::physical_plan::union::UnionExec;
+    use crate::prelude::SessionConfig;
+    use arrow::array::Int32Array;
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    fn assert_optimized(
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+        expected: Vec<&str>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let optimized = CteMaterialization::new().optimize(plan, config)?;
+        let formatted = displayable(optimized.as_ref()).indent().to_string();
+        let actual: Vec<&str> = formatted.trim().lines().collect();
+        assert_eq!(
+            expected, actual,
+            "\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}\n\n"
+        );
+        Ok(optimized)
+    }
+
+    /// A union of `count` references to a common table expression sorting a
+    /// table of 3 rows
+    fn cte_references(count: usize) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice(&[3, 1, 2]))],
+        )?;
+        let source = Arc::new(MemoryExec::try_new(&[vec![batch]], schema.clone(), None)?);
+        let sort_expr = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let references = (0..count)
+            .map(|_| {
+                let sort =
+                    Arc::new(SortExec::try_new(sort_expr.clone(), source.clone(), None)?);
+                Ok(Arc::new(SharedCteExec::new(0, "cte", sort)) as _)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Arc::new(UnionExec::new(references)))
+    }
+
+    #[test]
+    fn share_cte_results() -> Result<()> {
+        let optimized = assert_optimized(
+            cte_references(2)?,
+            &ConfigOptions::new(),
+            vec![
+                "UnionExec",
+                "  SharedCteExec: name=cte, work_table=true",
+                "    SortExec: [a@0 ASC]",
+                "      MemoryExec: partitions=1, partition_sizes=[1]",
+                "  SharedCteExec: name=cte, work_table=true",
+                "    SortExec: [a@0 ASC]",
+                "      MemoryExec: partitions=1, partition_sizes=[1]",
+            ],
+        )?;
+        // both references are the same plan
+        let children = optimized.children();
+        assert!(Arc::ptr_eq(&children[0], &children[1]));
+        Ok(())
+    }
+
+    #[test]
+    fn inline_cte() -> Result<()> {
+        let inlined = vec![
+            "UnionExec",
+            "  SortExec: [a@0 ASC]",
+            "    MemoryExec: partitions=1, partition_sizes=[1]",
+            "  SortExec: [a@0 ASC]",
+            "    MemoryExec: partitions=1, partition_sizes=[1]",
+        ];
+
+        let config = SessionConfig::new()
+            .set_bool("datafusion.optimizer.enable_cte_materialization", false);
+        assert_optimized(cte_references(2)?, config.config_options(), inlined.clone())?;
+
+        // the results are larger than the threshold
+        let config = SessionConfig::new()
+            .set_usize("datafusion.optimizer.cte_materialization_threshold", 0);
+        assert_optimized(cte_references(2)?, config.config_options(), inlined)?;
+
+        // a single reference
+        assert_optimized(
+            cte_references(1)?,
+            &ConfigOptions::new(),
+            vec![
+                "UnionExec",
+                "  SortExec: [a@0 ASC]",
+                "    MemoryExec: partitions=1, partition_sizes=[1]",
+            ],
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn inline_cte_optimized_differently() -> Result<()> {
+        let plan = cte_references(2)?;
+        // the second reference doesn't need its input sorted
+        let second = plan.children()[1].clone();
+        let unsorted = second.children()[0].children()[0].clone();
+        let second = second.with_new_children(vec![unsorted])?;
+        let plan = plan
+            .clone()
+            .with_new_children(vec![plan.children()[0].clone(), second])?;
+        assert_optimized(
+            plan,
+            &ConfigOptions::new(),
+            vec![
+                "UnionExec",
+                "  SortExec: [a@0 ASC]",
+                "    MemoryExec: partitions=1, partition_sizes=[1]",
+                "  MemoryExec: partitions=1, partition_sizes=[1]",
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -19,7 +19,7 @@
 //! to distribution requirements and adds [RepartitionExec]s to satisfy them
 //! when necessary.
 use crate::config::ConfigOptions;
-use crate::error::Result;
+use crate::error::{DataFusionError, Result};
 use crate::physical_optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
 use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
@@ -28,11 +28,13 @@ use crate::physical_plan::joins::{
 };
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::repartition::RepartitionExec;
-use crate::physical_plan::rewrite::TreeNodeRewritable;
+use crate::physical_plan::rewrite::{TreeNode, VisitRecursion};
 use crate::physical_plan::sorts::sort::SortOptions;
 use crate::physical_plan::windows::WindowAggExec;
 use crate::physical_plan::Partitioning;
-use crate::physical_plan::{with_new_children_if_necessary, Distribution, ExecutionPlan};
+use crate::physical_plan::{
+    displayable, with_new_children_if_necessary, Distribution, ExecutionPlan,
+};
 use arrow::datatypes::SchemaRef;
 use datafusion_expr::logical_plan::JoinType;
 use datafusion_physical_expr::equivalence::EquivalenceProperties;
@@ -97,6 +99,14 @@ impl PhysicalOptimizerRule for EnforceDistribution {
         "EnforceDistribution"
     }
 
+    fn run_after(&self) -> &[&str] {
+        &["join_selection", "PipelineFixer"]
+    }
+
+    fn check_postconditions(&self, plan: &dyn ExecutionPlan) -> Result<()> {
+        check_distribution(plan)
+    }
+
     fn schema_check(&self) -> bool {
         true
     }
@@ -194,6 +204,7 @@ fn adjust_input_keys_ordering(
                     JoinType::Left
                     | JoinType::LeftSemi
                     | JoinType::LeftAnti
+                    | JoinType::LeftMark
                     | JoinType::Full => None,
                 };
 
@@ -840,6 +851,28 @@ fn new_join_conditions(
 /// operators to satisfy distribution requirements. Since this function
 /// takes care of such requirements, we should avoid manually adding data
 /// exchange operators in other places.
+/// Checks that the inputs of each operator of `plan` have the distribution it
+/// requires
+fn check_distribution(plan: &dyn ExecutionPlan) -> Result<()> {
+    for (child, required) in plan
+        .children()
+        .iter()
+        .zip(plan.required_input_distribution())
+    {
+        if !child
+            .output_partitioning()
+            .satisfy(required.clone(), || child.equivalence_properties())
+        {
+            return Err(DataFusionError::Internal(format!(
+                "EnforceDistribution left an input of {} without the distribution {required:?}",
+                displayable(plan).one_line()
+            )));
+        }
+        check_distribution(child.as_ref())?;
+    }
+    Ok(())
+}
+
 fn ensure_distribution(
     plan: Arc<dyn crate::physical_plan::ExecutionPlan>,
     target_partitions: usize,
@@ -925,7 +958,19 @@ impl PlanWithKeyRequirements {
     }
 }
 
-impl TreeNodeRewritable for PlanWithKeyRequirements {
+impl TreeNode for PlanWithKeyRequirements {
+    fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>,
+    {
+        for child in self.children() {
+            if op(&child)? == VisitRecursion::Stop {
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    }
+
     fn map_children<F>(self, transform: F) -> Result<Self>
     where
         F: FnMut(Self) -> Result<Self>,
@@ -1003,7 +1048,7 @@ mod tests {
                 object_store_url: ObjectStoreUrl::parse("test:///").unwrap(),
                 file_schema: schema(),
                 file_groups: vec![vec![PartitionedFile::new("x".to_string(), 100)]],
-                statistics: Statistics::default(),
+                statistics: Statistics::new_unknown(&schema()),
                 projection: None,
                 limit: None,
                 table_partition_cols: vec![],
@@ -1247,7 +1292,7 @@ mod tests {
                     };
                     assert_optimized!(expected, top_join);
                 }
-                JoinType::RightSemi | JoinType::RightAnti => {}
+                JoinType::RightSemi | JoinType::RightAnti | JoinType::LeftMark => {}
             }
 
             match join_type {
@@ -1304,7 +1349,7 @@ mod tests {
                     };
                     assert_optimized!(expected, top_join);
                 }
-                JoinType::LeftSemi | JoinType::LeftAnti => {}
+                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {}
             }
         }
 
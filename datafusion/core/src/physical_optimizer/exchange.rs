@@ -0,0 +1,292 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! ReplaceExchanges optimizer that replaces the repartitions of a plan with
+//! user provided exchange operators
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::{DataFusionError, Result};
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::rewrite::TreeNode;
+use crate::physical_plan::{ExecutionPlan, Partitioning};
+
+/// Creates the operators exchanging the rows of a plan between partitions, in
+/// place of the in-process [`RepartitionExec`], for example to shuffle them
+/// between the nodes of a distributed engine.
+///
+/// The plans using the exchanges can be sent to other nodes with
+/// `datafusion-proto`, by serializing the exchange operators with a
+/// `PhysicalExtensionCodec`.
+pub trait ExchangeProvider: Send + Sync {
+    /// Returns the operator repartitioning the output of `input` into
+    /// `partitioning`, or `None` to keep the [`RepartitionExec`].
+    ///
+    /// The operator must have the schema of `input`, and the number of
+    /// partitions of `partitioning`.
+    fn create_exchange(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        partitioning: &Partitioning,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>>;
+}
+
+/// Optimizer rule that replaces each [`RepartitionExec`] of the plan with the
+/// exchange created by an [`ExchangeProvider`].
+///
+/// It runs after the rules adding the repartitions, see
+/// [`SessionState::with_exchange_provider`].
+///
+/// [`SessionState::with_exchange_provider`]: crate::execution::context::SessionState::with_exchange_provider
+pub struct ReplaceExchanges {
+    provider: Arc<dyn ExchangeProvider>,
+}
+
+impl ReplaceExchanges {
+    /// Create a rule replacing the repartitions with the exchanges of `provider`
+    pub fn new(provider: Arc<dyn ExchangeProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+impl PhysicalOptimizerRule for ReplaceExchanges {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        _config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        plan.transform_up(&|plan| {
+            let repartition = match plan.as_any().downcast_ref::<RepartitionExec>() {
+                Some(repartition) => repartition,
+                None => return Ok(None),
+            };
+            let partitioning = repartition.partitioning();
+            let exchange = self
+                .provider
+                .create_exchange(repartition.input().clone(), partitioning)?;
+            if let Some(exchange) = &exchange {
+                let partition_count = exchange.output_partitioning().partition_count();
+                if partition_count != partitioning.partition_count() {
+                    return Err(DataFusionError::Plan(format!(
+                        "The exchange replacing {partitioning:?} has {partition_count} partitions"
+                    )));
+                }
+            }
+            Ok(exchange)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "replace_exchanges"
+    }
+
+    fn run_after(&self) -> &[&str] {
+        &["repartition", "EnforceDistribution", "adaptive_partitions"]
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use arrow::record_batch::RecordBatch;
+
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::datasource::MemTable;
+    use crate::execution::context::{SessionConfig, SessionState, TaskContext};
+    use crate::execution::runtime_env::RuntimeEnv;
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::expressions::PhysicalSortExpr;
+    use crate::physical_plan::{
+        displayable, DisplayFormatType, SendableRecordBatchStream, Statistics,
+    };
+    use crate::prelude::SessionContext;
+
+    /// An exchange shuffling its input in process, as a stand-in for the
+    /// shuffle of a distributed engine
+    #[derive(Debug)]
+    struct ShuffleExec {
+        repartition: Arc<RepartitionExec>,
+    }
+
+    impl ExecutionPlan for ShuffleExec {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.repartition.schema()
+        }
+
+        fn output_partitioning(&self) -> Partitioning {
+            self.repartition.output_partitioning()
+        }
+
+        fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+            None
+        }
+
+        fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+            vec![self.repartition.input().clone()]
+        }
+
+        fn with_new_children(
+            self: Arc<Self>,
+            children: Vec<Arc<dyn ExecutionPlan>>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(ShuffleExec {
+                repartition: Arc::new(RepartitionExec::try_new(
+                    children[0].clone(),
+                    self.repartition.partitioning().clone(),
+                )?),
+            }))
+        }
+
+        fn execute(
+            &self,
+            partition: usize,
+            context: Arc<TaskContext>,
+        ) -> Result<SendableRecordBatchStream> {
+            self.repartition.execute(partition, context)
+        }
+
+        fn fmt_as(
+            &self,
+            _t: DisplayFormatType,
+            f: &mut std::fmt::Formatter,
+        ) -> std::fmt::Result {
+            write!(f, "ShuffleExec: {:?}", self.repartition.partitioning())
+        }
+
+        fn statistics(&self) -> Statistics {
+            self.repartition.statistics()
+        }
+    }
+
+    #[derive(Default)]
+    struct ShuffleProvider {
+        exchanges: AtomicUsize,
+    }
+
+    impl ExchangeProvider for ShuffleProvider {
+        fn create_exchange(
+            &self,
+            input: Arc<dyn ExecutionPlan>,
+            partitioning: &Partitioning,
+        ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+            // keep the round robin repartitions in process
+            if !matches!(partitioning, Partitioning::Hash(_, _)) {
+                return Ok(None);
+            }
+            self.exchanges.fetch_add(1, Ordering::Relaxed);
+            let repartition = RepartitionExec::try_new(input, partitioning.clone())?;
+            Ok(Some(Arc::new(ShuffleExec {
+                repartition: Arc::new(repartition),
+            })))
+        }
+    }
+
+    struct WrongPartitionCount;
+
+    impl ExchangeProvider for WrongPartitionCount {
+        fn create_exchange(
+            &self,
+            input: Arc<dyn ExecutionPlan>,
+            _partitioning: &Partitioning,
+        ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+            let partitioning = Partitioning::RoundRobinBatch(1);
+            Ok(Some(Arc::new(RepartitionExec::try_new(
+                input,
+                partitioning,
+            )?)))
+        }
+    }
+
+    fn context(provider: Arc<dyn ExchangeProvider>) -> Result<SessionContext> {
+        let config = SessionConfig::new().with_target_partitions(4);
+        let state = SessionState::with_config_rt(config, Arc::new(RuntimeEnv::default()))
+            .with_exchange_provider(provider);
+        let ctx = SessionContext::with_state(state);
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Utf8, false),
+            Field::new("v", DataType::Int32, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from_slice(["a", "b", "a", "c"])),
+                Arc::new(Int32Array::from_slice([1, 2, 3, 4])),
+            ],
+        )?;
+        let table = MemTable::try_new(schema, vec![vec![batch.clone()], vec![batch]])?;
+        ctx.register_table("t", Arc::new(table))?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn replace_hash_repartitions() -> Result<()> {
+        let provider = Arc::new(ShuffleProvider::default());
+        let ctx = context(provider.clone())?;
+
+        let df = ctx.sql("SELECT k, SUM(v) AS s FROM t GROUP BY k").await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = format!("{}", displayable(plan.as_ref()).indent());
+        assert!(plan.contains("ShuffleExec: Hash"), "{plan}");
+        assert!(
+            !plan.contains("RepartitionExec: partitioning=Hash"),
+            "{plan}"
+        );
+        assert!(provider.exchanges.load(Ordering::Relaxed) > 0);
+
+        let expected = vec![
+            "+---+---+",
+            "| k | s |",
+            "+---+---+",
+            "| a | 8 |",
+            "| b | 4 |",
+            "| c | 8 |",
+            "+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &df.collect().await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn wrong_partition_count() -> Result<()> {
+        let ctx = context(Arc::new(WrongPartitionCount))?;
+
+        let err = ctx
+            .sql("SELECT k, SUM(v) FROM t GROUP BY k")
+            .await?
+            .create_physical_plan()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("has 1 partitions"), "{err}");
+        Ok(())
+    }
+}
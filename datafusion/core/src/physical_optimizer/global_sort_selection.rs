@@ -22,7 +22,7 @@ use std::sync::Arc;
 use crate::config::ConfigOptions;
 use crate::error::Result;
 use crate::physical_optimizer::PhysicalOptimizerRule;
-use crate::physical_plan::rewrite::TreeNodeRewritable;
+use crate::physical_plan::rewrite::TreeNode;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use crate::physical_plan::ExecutionPlan;
@@ -83,6 +83,10 @@ impl PhysicalOptimizerRule for GlobalSortSelection {
         "global_sort_selection"
     }
 
+    fn run_after(&self) -> &[&str] {
+        &["repartition"]
+    }
+
     fn schema_check(&self) -> bool {
         false
     }
@@ -31,8 +31,8 @@ use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::{ExecutionPlan, PhysicalExpr};
 
 use super::optimizer::PhysicalOptimizerRule;
-use crate::error::Result;
-use crate::physical_plan::rewrite::TreeNodeRewritable;
+use crate::error::{DataFusionError, Result};
+use crate::physical_plan::rewrite::TreeNode;
 
 /// For hash join with the partition mode [PartitionMode::Auto], JoinSelection rule will make
 /// a cost based decision to select which PartitionMode mode(Partitioned/CollectLeft) is optimal
@@ -60,13 +60,18 @@ impl JoinSelection {
 fn should_swap_join_order(left: &dyn ExecutionPlan, right: &dyn ExecutionPlan) -> bool {
     // Get the left and right table's total bytes
     // If both the left and right tables contain total_byte_size statistics,
-    // use `total_byte_size` to determine `should_swap_join_order`, else use `num_rows`
+    // use `total_byte_size` to determine `should_swap_join_order`, else use `num_rows`.
+    // Estimated values are good enough to pick a join order.
+    let (left_stats, right_stats) = (left.statistics(), right.statistics());
     let (left_size, right_size) = match (
-        left.statistics().total_byte_size,
-        right.statistics().total_byte_size,
+        left_stats.total_byte_size.get_value(),
+        right_stats.total_byte_size.get_value(),
     ) {
         (Some(l), Some(r)) => (Some(l), Some(r)),
-        _ => (left.statistics().num_rows, right.statistics().num_rows),
+        _ => (
+            left_stats.num_rows.get_value(),
+            right_stats.num_rows.get_value(),
+        ),
     };
 
     match (left_size, right_size) {
@@ -81,9 +86,10 @@ fn supports_collect_by_size(
 ) -> bool {
     // Currently we do not trust the 0 value from stats, due to stats collection might have bug
     // TODO check the logic in datasource::get_statistics_with_limit()
-    if let Some(size) = plan.statistics().total_byte_size {
+    let stats = plan.statistics();
+    if let Some(&size) = stats.total_byte_size.get_value() {
         size != 0 && size < collection_size_threshold
-    } else if let Some(row_count) = plan.statistics().num_rows {
+    } else if let Some(&row_count) = stats.num_rows.get_value() {
         row_count != 0 && row_count < collection_size_threshold
     } else {
         false
@@ -104,17 +110,21 @@ pub fn supports_swap(join_type: JoinType) -> bool {
     )
 }
 /// This function returns the new join type we get after swapping the given
-/// join's inputs.
-pub fn swap_join_type(join_type: JoinType) -> JoinType {
+/// join's inputs, or an error if the inputs of the join can't be swapped,
+/// see [`supports_swap`].
+pub fn swap_join_type(join_type: JoinType) -> Result<JoinType> {
     match join_type {
-        JoinType::Inner => JoinType::Inner,
-        JoinType::Full => JoinType::Full,
-        JoinType::Left => JoinType::Right,
-        JoinType::Right => JoinType::Left,
-        JoinType::LeftSemi => JoinType::RightSemi,
-        JoinType::RightSemi => JoinType::LeftSemi,
-        JoinType::LeftAnti => JoinType::RightAnti,
-        JoinType::RightAnti => JoinType::LeftAnti,
+        JoinType::Inner => Ok(JoinType::Inner),
+        JoinType::Full => Ok(JoinType::Full),
+        JoinType::Left => Ok(JoinType::Right),
+        JoinType::Right => Ok(JoinType::Left),
+        JoinType::LeftSemi => Ok(JoinType::RightSemi),
+        JoinType::RightSemi => Ok(JoinType::LeftSemi),
+        JoinType::LeftAnti => Ok(JoinType::RightAnti),
+        JoinType::RightAnti => Ok(JoinType::LeftAnti),
+        JoinType::LeftMark => Err(DataFusionError::Internal(format!(
+            "{join_type} joins don't support swapping their inputs"
+        ))),
     }
 }
 
@@ -134,7 +144,7 @@ pub fn swap_hash_join(
             .map(|(l, r)| (r.clone(), l.clone()))
             .collect(),
         swap_join_filter(hash_join.filter()),
-        &swap_join_type(*hash_join.join_type()),
+        &swap_join_type(*hash_join.join_type())?,
         partition_mode,
         hash_join.null_equals_null(),
     )?;
@@ -272,6 +282,10 @@ impl PhysicalOptimizerRule for JoinSelection {
         "join_selection"
     }
 
+    fn run_after(&self) -> &[&str] {
+        &["global_sort_selection"]
+    }
+
     fn schema_check(&self) -> bool {
         true
     }
@@ -293,7 +307,9 @@ fn try_collect_left(
     let join_type = hash_join.join_type();
 
     let left_can_collect = match join_type {
-        JoinType::Left | JoinType::Full | JoinType::LeftAnti => false,
+        JoinType::Left | JoinType::Full | JoinType::LeftAnti | JoinType::LeftMark => {
+            false
+        }
         JoinType::Inner
         | JoinType::LeftSemi
         | JoinType::Right
@@ -308,7 +324,8 @@ fn try_collect_left(
         | JoinType::RightSemi
         | JoinType::Left
         | JoinType::LeftSemi
-        | JoinType::LeftAnti => collect_threshold.map_or(true, |threshold| {
+        | JoinType::LeftAnti
+        | JoinType::LeftMark => collect_threshold.map_or(true, |threshold| {
             supports_collect_by_size(&**right, threshold)
         }),
     };
@@ -373,7 +390,7 @@ fn partitioned_hash_join(hash_join: &HashJoinExec) -> Result<Arc<dyn ExecutionPl
 mod tests {
     use crate::{
         physical_plan::{
-            displayable, joins::PartitionMode, ColumnStatistics, Statistics,
+            displayable, joins::PartitionMode, ColumnStatistics, Precision, Statistics,
         },
         test::exec::StatisticsExec,
     };
@@ -387,18 +404,18 @@ mod tests {
     fn create_big_and_small() -> (Arc<dyn ExecutionPlan>, Arc<dyn ExecutionPlan>) {
         let big = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(10),
-                total_byte_size: Some(100000),
-                ..Default::default()
+                num_rows: Precision::Exact(10),
+                total_byte_size: Precision::Exact(100000),
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("big_col", DataType::Int32, false)]),
         ));
 
         let small = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(100000),
-                total_byte_size: Some(10),
-                ..Default::default()
+                num_rows: Precision::Exact(100000),
+                total_byte_size: Precision::Exact(10),
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("small_col", DataType::Int32, false)]),
         ));
@@ -414,13 +431,19 @@ mod tests {
         min: Option<u64>,
         max: Option<u64>,
         distinct_count: Option<usize>,
-    ) -> Option<Vec<ColumnStatistics>> {
-        Some(vec![ColumnStatistics {
-            distinct_count,
-            min_value: min.map(|size| ScalarValue::UInt64(Some(size))),
-            max_value: max.map(|size| ScalarValue::UInt64(Some(size))),
+    ) -> Vec<ColumnStatistics> {
+        vec![ColumnStatistics {
+            distinct_count: distinct_count
+                .map(Precision::Exact)
+                .unwrap_or(Precision::Absent),
+            min_value: min
+                .map(|size| Precision::Exact(ScalarValue::UInt64(Some(size))))
+                .unwrap_or(Precision::Absent),
+            max_value: max
+                .map(|size| Precision::Exact(ScalarValue::UInt64(Some(size))))
+                .unwrap_or(Precision::Absent),
             ..Default::default()
-        }])
+        }]
     }
 
     /// Returns three plans with statistics of (min, max, distinct_count)
@@ -434,39 +457,39 @@ mod tests {
     ) {
         let big = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(100_000),
+                num_rows: Precision::Exact(100_000),
                 column_statistics: create_column_stats(
                     Some(0),
                     Some(50_000),
                     Some(50_000),
                 ),
-                ..Default::default()
+                total_byte_size: Precision::Absent,
             },
             Schema::new(vec![Field::new("big_col", DataType::Int32, false)]),
         ));
 
         let medium = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(10_000),
+                num_rows: Precision::Exact(10_000),
                 column_statistics: create_column_stats(
                     Some(1000),
                     Some(5000),
                     Some(1000),
                 ),
-                ..Default::default()
+                total_byte_size: Precision::Absent,
             },
             Schema::new(vec![Field::new("medium_col", DataType::Int32, false)]),
         ));
 
         let small = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(1000),
+                num_rows: Precision::Exact(1000),
                 column_statistics: create_column_stats(
                     Some(0),
                     Some(100_000),
                     Some(1000),
                 ),
-                ..Default::default()
+                total_byte_size: Precision::Absent,
             },
             Schema::new(vec![Field::new("small_col", DataType::Int32, false)]),
         ));
@@ -515,10 +538,13 @@ mod tests {
             .downcast_ref::<HashJoinExec>()
             .expect("The type of the plan should not be changed");
 
-        assert_eq!(swapped_join.left().statistics().total_byte_size, Some(10));
+        assert_eq!(
+            swapped_join.left().statistics().total_byte_size,
+            Precision::Exact(10)
+        );
         assert_eq!(
             swapped_join.right().statistics().total_byte_size,
-            Some(100000)
+            Precision::Exact(100000)
         );
     }
 
@@ -566,9 +592,12 @@ mod tests {
 
         assert_eq!(
             swapped_join.left().statistics().total_byte_size,
-            Some(100000)
+            Precision::Exact(100000)
+        );
+        assert_eq!(
+            swapped_join.right().statistics().total_byte_size,
+            Precision::Exact(10)
         );
-        assert_eq!(swapped_join.right().statistics().total_byte_size, Some(10));
     }
 
     #[tokio::test]
@@ -606,10 +635,13 @@ mod tests {
 
             assert_eq!(swapped_join.schema().fields().len(), 1);
 
-            assert_eq!(swapped_join.left().statistics().total_byte_size, Some(10));
+            assert_eq!(
+                swapped_join.left().statistics().total_byte_size,
+                Precision::Exact(10)
+            );
             assert_eq!(
                 swapped_join.right().statistics().total_byte_size,
-                Some(100000)
+                Precision::Exact(100000)
             );
 
             assert_eq!(original_schema, swapped_join.schema());
@@ -684,9 +716,9 @@ mod tests {
             "  HashJoinExec: mode=CollectLeft, join_type=Right, on=[(Column { name: \"small_col\", index: 1 }, Column { name: \"medium_col\", index: 0 })]",
             "    ProjectionExec: expr=[big_col@1 as big_col, small_col@0 as small_col]",
             "      HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(Column { name: \"small_col\", index: 0 }, Column { name: \"big_col\", index: 0 })]",
-            "        StatisticsExec: col_count=1, row_count=Some(1000)",
-            "        StatisticsExec: col_count=1, row_count=Some(100000)",
-            "    StatisticsExec: col_count=1, row_count=Some(10000)",
+            "        StatisticsExec: col_count=1, row_count=1000",
+            "        StatisticsExec: col_count=1, row_count=100000",
+            "    StatisticsExec: col_count=1, row_count=10000",
             ""
         ];
         assert_optimized!(expected, join);
@@ -718,10 +750,13 @@ mod tests {
             .downcast_ref::<HashJoinExec>()
             .expect("The type of the plan should not be changed");
 
-        assert_eq!(swapped_join.left().statistics().total_byte_size, Some(10));
+        assert_eq!(
+            swapped_join.left().statistics().total_byte_size,
+            Precision::Exact(10)
+        );
         assert_eq!(
             swapped_join.right().statistics().total_byte_size,
-            Some(100000)
+            Precision::Exact(100000)
         );
     }
 
@@ -764,27 +799,27 @@ mod tests {
     async fn test_join_selection_collect_left() {
         let big = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(10000000),
-                total_byte_size: Some(10000000),
-                ..Default::default()
+                num_rows: Precision::Exact(10000000),
+                total_byte_size: Precision::Exact(10000000),
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("big_col", DataType::Int32, false)]),
         ));
 
         let small = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(10),
-                total_byte_size: Some(10),
-                ..Default::default()
+                num_rows: Precision::Exact(10),
+                total_byte_size: Precision::Exact(10),
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("small_col", DataType::Int32, false)]),
         ));
 
         let empty = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: None,
-                total_byte_size: None,
-                ..Default::default()
+                num_rows: Precision::Absent,
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("empty_col", DataType::Int32, false)]),
         ));
@@ -842,27 +877,27 @@ mod tests {
     async fn test_join_selection_partitioned() {
         let big1 = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(10000000),
-                total_byte_size: Some(10000000),
-                ..Default::default()
+                num_rows: Precision::Exact(10000000),
+                total_byte_size: Precision::Exact(10000000),
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("big_col1", DataType::Int32, false)]),
         ));
 
         let big2 = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(20000000),
-                total_byte_size: Some(20000000),
-                ..Default::default()
+                num_rows: Precision::Exact(20000000),
+                total_byte_size: Precision::Exact(20000000),
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("big_col2", DataType::Int32, false)]),
         ));
 
         let empty = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: None,
-                total_byte_size: None,
-                ..Default::default()
+                num_rows: Precision::Absent,
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![ColumnStatistics::new_unknown()],
             },
             Schema::new(vec![Field::new("empty_col", DataType::Int32, false)]),
         ));
@@ -0,0 +1,236 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Push the skip and fetch of a global limit into the operator that merges
+//! the partitions under it.
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::limit::GlobalLimitExec;
+use crate::physical_plan::rewrite::TreeNode;
+use crate::physical_plan::sorts::sort::SortExec;
+use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// A `LIMIT fetch OFFSET skip` over a sorted input is planned as a
+/// [GlobalLimitExec] over a [SortPreservingMergeExec] that merges the
+/// sorted partitions, or over a single partition [SortExec]. The merge
+/// would then copy all the `skip + fetch` first rows to its output batches,
+/// only for most of them to be discarded by the limit, which is very slow
+/// for deep offsets.
+///
+/// This rule replaces such a [GlobalLimitExec] by a merge that skips the
+/// rows itself, without copying them, and stops once it produced `fetch`
+/// rows. It also makes sure that the sort under the limit only keeps the
+/// `skip + fetch` first rows.
+#[derive(Default)]
+pub struct LimitPushdown {}
+
+impl LimitPushdown {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for LimitPushdown {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        _config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        plan.transform_down(&|plan| {
+            let limit = match plan.as_any().downcast_ref::<GlobalLimitExec>() {
+                Some(limit) => limit,
+                None => return Ok(None),
+            };
+            let input = limit.input();
+            if let Some(merge) = input.as_any().downcast_ref::<SortPreservingMergeExec>()
+            {
+                let (skip, fetch) = merge_limits(
+                    (merge.skip(), merge.fetch()),
+                    (limit.skip(), limit.fetch()),
+                );
+                let merge_input = fetch
+                    .and_then(|fetch| with_sort_fetch(merge.input(), skip + fetch))
+                    .unwrap_or_else(|| merge.input().clone());
+                let merge: Arc<dyn ExecutionPlan> = Arc::new(
+                    SortPreservingMergeExec::new(merge.expr().to_vec(), merge_input)
+                        .with_skip(skip)
+                        .with_fetch(fetch),
+                );
+                return Ok(Some(merge));
+            }
+            let sort = limit
+                .fetch()
+                .and_then(|fetch| with_sort_fetch(input, limit.skip() + fetch));
+            Ok(sort.map(|sort| {
+                Arc::new(GlobalLimitExec::new(sort, limit.skip(), limit.fetch()))
+                    as Arc<dyn ExecutionPlan>
+            }))
+        })
+    }
+
+    fn name(&self) -> &str {
+        "limit_pushdown"
+    }
+
+    fn run_after(&self) -> &[&str] {
+        &["EnforceSorting"]
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the skip and fetch of the `parent` limit applied to the output of
+/// the `child` limit
+fn merge_limits(
+    (child_skip, child_fetch): (usize, Option<usize>),
+    (parent_skip, parent_fetch): (usize, Option<usize>),
+) -> (usize, Option<usize>) {
+    // the child produces the rows [child_skip, child_skip + child_fetch) of its input
+    let child_fetch = child_fetch.map(|fetch| fetch.saturating_sub(parent_skip));
+    let fetch = match (parent_fetch, child_fetch) {
+        (Some(parent), Some(child)) => Some(parent.min(child)),
+        (fetch, None) | (None, fetch) => fetch,
+    };
+    (child_skip + parent_skip, fetch)
+}
+
+/// Returns `plan` limited to its `fetch` first rows, if it is a sort that
+/// keeps more rows than that
+fn with_sort_fetch(
+    plan: &Arc<dyn ExecutionPlan>,
+    fetch: usize,
+) -> Option<Arc<dyn ExecutionPlan>> {
+    let sort = plan.as_any().downcast_ref::<SortExec>()?;
+    if sort.fetch().map_or(false, |f| f <= fetch) {
+        return None;
+    }
+    Some(Arc::new(SortExec::new_with_partitioning(
+        sort.expr().to_vec(),
+        sort.input().clone(),
+        sort.preserve_partitioning(),
+        Some(fetch),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::displayable;
+    use crate::physical_plan::expressions::{col, PhysicalSortExpr};
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::union::UnionExec;
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn assert_optimized(plan: Arc<dyn ExecutionPlan>, expected: Vec<&str>) -> Result<()> {
+        let optimized = LimitPushdown::new().optimize(plan, &ConfigOptions::new())?;
+        let formatted = displayable(optimized.as_ref()).indent().to_string();
+        let actual: Vec<&str> = formatted.trim().lines().collect();
+        assert_eq!(
+            expected, actual,
+            "\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}\n\n"
+        );
+        Ok(())
+    }
+
+    fn sort(preserve_partitioning: bool) -> Result<(Vec<PhysicalSortExpr>, SortExec)> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let source: Arc<dyn ExecutionPlan> =
+            Arc::new(MemoryExec::try_new(&[], schema.clone(), None)?);
+        let source = Arc::new(UnionExec::new(vec![source.clone(), source]));
+        let expr = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let sort = SortExec::new_with_partitioning(
+            expr.clone(),
+            source,
+            preserve_partitioning,
+            None,
+        );
+        Ok((expr, sort))
+    }
+
+    #[test]
+    fn push_limit_into_merge() -> Result<()> {
+        let (expr, sort) = sort(true)?;
+        let merge = Arc::new(SortPreservingMergeExec::new(expr, Arc::new(sort)));
+        let plan = Arc::new(GlobalLimitExec::new(merge, 1000, Some(10)));
+        assert_optimized(
+            plan,
+            vec![
+                "SortPreservingMergeExec: [a@0 ASC], skip=1000, fetch=10",
+                "  SortExec: [a@0 ASC]",
+                "    UnionExec",
+                "      MemoryExec: partitions=1, partition_sizes=[0]",
+                "      MemoryExec: partitions=1, partition_sizes=[0]",
+            ],
+        )
+    }
+
+    #[test]
+    fn merge_limits_into_merge() -> Result<()> {
+        let (expr, sort) = sort(true)?;
+        let merge = Arc::new(
+            SortPreservingMergeExec::new(expr, Arc::new(sort))
+                .with_skip(5)
+                .with_fetch(Some(20)),
+        );
+        let plan = Arc::new(GlobalLimitExec::new(merge, 10, None));
+        assert_optimized(
+            plan,
+            vec![
+                "SortPreservingMergeExec: [a@0 ASC], skip=15, fetch=10",
+                "  SortExec: [a@0 ASC]",
+                "    UnionExec",
+                "      MemoryExec: partitions=1, partition_sizes=[0]",
+                "      MemoryExec: partitions=1, partition_sizes=[0]",
+            ],
+        )
+    }
+
+    #[test]
+    fn push_fetch_into_sort() -> Result<()> {
+        let (_, sort) = sort(false)?;
+        let plan = Arc::new(GlobalLimitExec::new(Arc::new(sort), 1000, Some(10)));
+        let optimized = LimitPushdown::new().optimize(plan, &ConfigOptions::new())?;
+        let limit = optimized
+            .as_any()
+            .downcast_ref::<GlobalLimitExec>()
+            .unwrap();
+        assert_eq!((limit.skip(), limit.fetch()), (1000, Some(10)));
+        let sort = limit.input().as_any().downcast_ref::<SortExec>().unwrap();
+        assert_eq!(sort.fetch(), Some(1010));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_limits() {
+        assert_eq!(merge_limits((0, None), (10, Some(5))), (10, Some(5)));
+        assert_eq!(merge_limits((10, Some(5)), (0, None)), (10, Some(5)));
+        assert_eq!(merge_limits((5, Some(20)), (10, Some(50))), (15, Some(10)));
+        assert_eq!(merge_limits((5, Some(20)), (30, Some(5))), (35, Some(0)));
+    }
+}
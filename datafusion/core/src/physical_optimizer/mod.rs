@@ -18,12 +18,19 @@
 //! This module contains a query optimizer that operates against a physical plan and applies
 //! rules to a physical plan, such as "Repartition".
 
+pub mod adaptive_partitions;
 pub mod aggregate_statistics;
 pub mod coalesce_batches;
+pub mod cte_materialization;
 pub mod dist_enforcement;
+pub mod exchange;
 pub mod global_sort_selection;
 pub mod join_selection;
+pub mod limit_pushdown;
+pub mod offload;
 pub mod optimizer;
+pub mod output_batch_size;
+pub mod partial_sort_selection;
 pub mod pipeline_checker;
 pub mod pruning;
 pub mod repartition;
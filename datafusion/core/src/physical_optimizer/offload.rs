@@ -0,0 +1,321 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Offload optimizer that lets an accelerator, such as a GPU, execute the
+//! subtrees of a plan it supports in place of the DataFusion operators
+
+use std::sync::Arc;
+
+use log::warn;
+
+use crate::config::ConfigOptions;
+use crate::error::{DataFusionError, Result};
+use crate::physical_optimizer::utils::ordering_satisfy;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::{with_new_children_if_necessary, ExecutionPlan};
+
+/// Creates the operators executing the subtrees of a plan on an accelerator.
+///
+/// The operators can be sent to other nodes with `datafusion-proto`, by
+/// serializing them with a `PhysicalExtensionCodec`.
+pub trait AcceleratorProvider: Send + Sync {
+    /// The name of the accelerator, used in the logs
+    fn name(&self) -> &str;
+
+    /// Returns the operator executing `plan`, including all its inputs, on the
+    /// accelerator, or `None` to leave the root of `plan` to DataFusion, in
+    /// which case the provider is asked for each of its inputs.
+    ///
+    /// The operator must have the schema of `plan`, its number of partitions,
+    /// and an ordering satisfying its ordering. It may keep parts of `plan`
+    /// as its inputs, which are then not offered to the provider.
+    ///
+    /// When the operator is invalid, or an error is returned, the plan falls
+    /// back to the DataFusion operators.
+    fn try_offload(
+        &self,
+        plan: &Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>>;
+}
+
+/// Optimizer rule that offers the subtrees of the plan, top down, to an
+/// [`AcceleratorProvider`], replacing those it claims with its operators.
+///
+/// It runs after the rules fixing the distribution and the ordering of the
+/// plan, see [`SessionState::with_accelerator_provider`].
+///
+/// [`SessionState::with_accelerator_provider`]: crate::execution::context::SessionState::with_accelerator_provider
+pub struct OffloadToAccelerator {
+    provider: Arc<dyn AcceleratorProvider>,
+}
+
+impl OffloadToAccelerator {
+    /// Create a rule offloading the subtrees claimed by `provider`
+    pub fn new(provider: Arc<dyn AcceleratorProvider>) -> Self {
+        Self { provider }
+    }
+
+    fn offload(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let offloaded = self
+            .provider
+            .try_offload(&plan, config)
+            .and_then(|offloaded| match offloaded {
+                Some(offloaded) => validate(&plan, offloaded).map(Some),
+                None => Ok(None),
+            });
+        match offloaded {
+            Ok(Some(offloaded)) => return Ok(offloaded),
+            Ok(None) => {}
+            Err(e) => warn!(
+                "Falling back from the accelerator {} to DataFusion: {e}",
+                self.provider.name()
+            ),
+        }
+
+        let children = plan.children();
+        if children.is_empty() {
+            return Ok(plan);
+        }
+        let children = children
+            .into_iter()
+            .map(|child| self.offload(child, config))
+            .collect::<Result<Vec<_>>>()?;
+        with_new_children_if_necessary(plan, children)
+    }
+}
+
+/// Returns `offloaded` if it can replace `plan`
+fn validate(
+    plan: &Arc<dyn ExecutionPlan>,
+    offloaded: Arc<dyn ExecutionPlan>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let invalid = |reason: &str| {
+        Err(DataFusionError::Plan(format!(
+            "The accelerated operator {offloaded:?} can't replace {plan:?}: {reason}"
+        )))
+    };
+    if offloaded.schema().fields() != plan.schema().fields() {
+        return invalid("the schemas differ");
+    }
+    let partition_count = plan.output_partitioning().partition_count();
+    if offloaded.output_partitioning().partition_count() != partition_count {
+        return invalid("the numbers of partitions differ");
+    }
+    if !ordering_satisfy(offloaded.output_ordering(), plan.output_ordering(), || {
+        offloaded.equivalence_properties()
+    }) {
+        return invalid("the ordering is not preserved");
+    }
+    Ok(offloaded)
+}
+
+impl PhysicalOptimizerRule for OffloadToAccelerator {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.offload(plan, config)
+    }
+
+    fn name(&self) -> &str {
+        "offload_to_accelerator"
+    }
+
+    fn run_after(&self) -> &[&str] {
+        &["EnforceDistribution", "EnforceSorting", "coalesce_batches"]
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use arrow::record_batch::RecordBatch;
+
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::datasource::MemTable;
+    use crate::execution::context::{SessionConfig, SessionState, TaskContext};
+    use crate::execution::runtime_env::RuntimeEnv;
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::expressions::PhysicalSortExpr;
+    use crate::physical_plan::filter::FilterExec;
+    use crate::physical_plan::{
+        displayable, DisplayFormatType, Partitioning, SendableRecordBatchStream,
+        Statistics,
+    };
+    use crate::prelude::SessionContext;
+
+    /// An operator standing in for the execution of a subtree on an
+    /// accelerator, which executes the subtree with DataFusion
+    #[derive(Debug)]
+    struct AcceleratedExec {
+        plan: Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+    }
+
+    impl ExecutionPlan for AcceleratedExec {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.schema.clone()
+        }
+
+        fn output_partitioning(&self) -> Partitioning {
+            self.plan.output_partitioning()
+        }
+
+        fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+            self.plan.output_ordering()
+        }
+
+        fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+            vec![]
+        }
+
+        fn with_new_children(
+            self: Arc<Self>,
+            _: Vec<Arc<dyn ExecutionPlan>>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(self)
+        }
+
+        fn execute(
+            &self,
+            partition: usize,
+            context: Arc<TaskContext>,
+        ) -> Result<SendableRecordBatchStream> {
+            self.plan.execute(partition, context)
+        }
+
+        fn fmt_as(
+            &self,
+            _t: DisplayFormatType,
+            f: &mut std::fmt::Formatter,
+        ) -> std::fmt::Result {
+            write!(
+                f,
+                "AcceleratedExec: {}",
+                displayable(self.plan.as_ref()).one_line()
+            )
+        }
+
+        fn statistics(&self) -> Statistics {
+            self.plan.statistics()
+        }
+    }
+
+    /// Claims the filters, or fails on them
+    enum FilterAccelerator {
+        Claim,
+        WrongSchema,
+        Fail,
+    }
+
+    impl AcceleratorProvider for FilterAccelerator {
+        fn name(&self) -> &str {
+            "filters"
+        }
+
+        fn try_offload(
+            &self,
+            plan: &Arc<dyn ExecutionPlan>,
+            _config: &ConfigOptions,
+        ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+            if !plan.as_any().is::<FilterExec>() {
+                return Ok(None);
+            }
+            let schema = match self {
+                FilterAccelerator::Claim => plan.schema(),
+                FilterAccelerator::WrongSchema => Arc::new(Schema::empty()),
+                FilterAccelerator::Fail => {
+                    return Err(DataFusionError::Execution(
+                        "out of device memory".to_string(),
+                    ))
+                }
+            };
+            Ok(Some(Arc::new(AcceleratedExec {
+                plan: plan.clone(),
+                schema,
+            })))
+        }
+    }
+
+    fn context(provider: FilterAccelerator) -> Result<SessionContext> {
+        let config = SessionConfig::new().with_target_partitions(2);
+        let state = SessionState::with_config_rt(config, Arc::new(RuntimeEnv::default()))
+            .with_accelerator_provider(Arc::new(provider));
+        let ctx = SessionContext::with_state(state);
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice([1, 2, 3]))],
+        )?;
+        let table = MemTable::try_new(schema, vec![vec![batch.clone()], vec![batch]])?;
+        ctx.register_table("t", Arc::new(table))?;
+        Ok(ctx)
+    }
+
+    async fn plan_and_results(provider: FilterAccelerator) -> Result<String> {
+        let ctx = context(provider)?;
+        let df = ctx.sql("SELECT a FROM t WHERE a > 1").await?;
+        let plan = df.clone().create_physical_plan().await?;
+
+        let expected = vec![
+            "+---+", "| a |", "+---+", "| 2 |", "| 2 |", "| 3 |", "| 3 |", "+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &df.collect().await?);
+        Ok(displayable(plan.as_ref()).indent().to_string())
+    }
+
+    #[tokio::test]
+    async fn offload_filters() -> Result<()> {
+        let plan = plan_and_results(FilterAccelerator::Claim).await?;
+        assert!(plan.contains("AcceleratedExec: FilterExec"), "{plan}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fall_back_on_invalid_operator() -> Result<()> {
+        let plan = plan_and_results(FilterAccelerator::WrongSchema).await?;
+        assert!(!plan.contains("AcceleratedExec"), "{plan}");
+        assert!(plan.contains("FilterExec"), "{plan}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fall_back_on_error() -> Result<()> {
+        let plan = plan_and_results(FilterAccelerator::Fail).await?;
+        assert!(!plan.contains("AcceleratedExec"), "{plan}");
+        Ok(())
+    }
+}
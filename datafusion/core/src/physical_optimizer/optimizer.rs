@@ -20,7 +20,8 @@
 use std::sync::Arc;
 
 use crate::config::ConfigOptions;
-use crate::{error::Result, physical_plan::ExecutionPlan};
+use crate::error::{DataFusionError, Result};
+use crate::physical_plan::ExecutionPlan;
 
 /// `PhysicalOptimizerRule` transforms one ['ExecutionPlan'] into another which
 /// computes the same results, but in a potentially more efficient
@@ -41,4 +42,128 @@ pub trait PhysicalOptimizerRule {
     /// Some of the optimization rules might change the nullable properties of the schema
     /// and should disable the schema check.
     fn schema_check(&self) -> bool;
+
+    /// The names of the rules that must run before this rule when they are in
+    /// the same rule set, see [`check_rule_order`]
+    fn run_after(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Checks the properties that the plans optimized by this rule have, such
+    /// as the requirements it enforces. The physical planner only calls it in
+    /// debug builds, right after the rule.
+    fn check_postconditions(&self, _plan: &dyn ExecutionPlan) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Checks that each rule of `rules` comes after the rules it must run after,
+/// see [`PhysicalOptimizerRule::run_after`]
+pub fn check_rule_order(
+    rules: &[Arc<dyn PhysicalOptimizerRule + Send + Sync>],
+) -> Result<()> {
+    for (i, rule) in rules.iter().enumerate() {
+        for before in rule.run_after() {
+            if rules[i + 1..].iter().any(|other| other.name() == *before) {
+                return Err(DataFusionError::Internal(format!(
+                    "The physical optimizer rule '{}' must run after '{before}'",
+                    rule.name()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::context::{SessionConfig, SessionState};
+    use crate::execution::runtime_env::RuntimeEnv;
+    use crate::physical_optimizer::coalesce_batches::CoalesceBatches;
+    use crate::physical_optimizer::sort_enforcement::EnforceSorting;
+    use crate::physical_plan::expressions::{col, PhysicalSortExpr};
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn state() -> SessionState {
+        SessionState::with_config_rt(
+            SessionConfig::new(),
+            Arc::new(RuntimeEnv::default()),
+        )
+    }
+
+    fn rule_names(state: &SessionState) -> Vec<&str> {
+        state
+            .physical_optimizers()
+            .iter()
+            .map(|r| r.name())
+            .collect()
+    }
+
+    #[test]
+    fn default_rule_order() -> Result<()> {
+        check_rule_order(state().physical_optimizers())
+    }
+
+    #[test]
+    fn insert_rules() -> Result<()> {
+        let state = state()
+            .remove_physical_optimizer_rule("coalesce_batches")
+            .insert_physical_optimizer_rule_before(
+                "cte_materialization",
+                Arc::new(CoalesceBatches::new()),
+            )?;
+        check_rule_order(state.physical_optimizers())?;
+        let names = rule_names(&state);
+        let index = names.iter().position(|n| *n == "coalesce_batches").unwrap();
+        assert_eq!(names[index + 1], "cte_materialization");
+
+        // coalesce_batches must run after EnforceSorting
+        let state = state
+            .remove_physical_optimizer_rule("coalesce_batches")
+            .insert_physical_optimizer_rule_after(
+                "EnforceDistribution",
+                Arc::new(CoalesceBatches::new()),
+            )?;
+        let err = check_rule_order(state.physical_optimizers()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'coalesce_batches' must run after 'EnforceSorting'"),
+            "{err}"
+        );
+
+        let err = state
+            .insert_physical_optimizer_rule_after(
+                "missing",
+                Arc::new(CoalesceBatches::new()),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: No physical optimizer rule named 'missing'"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn enforce_sorting_postconditions() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let input = Arc::new(MemoryExec::try_new(
+            &[vec![], vec![]],
+            schema.clone(),
+            None,
+        )?);
+        let sort_expr = PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: Default::default(),
+        };
+        let plan = Arc::new(SortPreservingMergeExec::new(vec![sort_expr], input));
+
+        let rule = EnforceSorting::new();
+        assert!(rule.check_postconditions(plan.as_ref()).is_err());
+        let plan = rule.optimize(plan, &ConfigOptions::new())?;
+        rule.check_postconditions(plan.as_ref())
+    }
 }
@@ -0,0 +1,119 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Re-chunk the results of a query to the output batch size of the session.
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::rechunk_batches::RechunkBatchesExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// Adds a [RechunkBatchesExec] at the root of the plan when the
+/// `datafusion.execution.output_batch_size` option is set, so that the
+/// results of the query are returned in batches of this many rows.
+#[derive(Default)]
+pub struct OutputBatchSize {}
+
+impl OutputBatchSize {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for OutputBatchSize {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let batch_size = match config.execution.output_batch_size {
+            Some(batch_size) if batch_size > 0 => batch_size,
+            _ => return Ok(plan),
+        };
+        let input = match plan.as_any().downcast_ref::<RechunkBatchesExec>() {
+            Some(rechunk) if rechunk.batch_size() == batch_size => return Ok(plan),
+            Some(rechunk) => rechunk.input().clone(),
+            None => plan,
+        };
+        Ok(Arc::new(RechunkBatchesExec::new(input, batch_size)))
+    }
+
+    fn name(&self) -> &str {
+        "output_batch_size"
+    }
+
+    fn run_after(&self) -> &[&str] {
+        &["coalesce_batches"]
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::displayable;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::prelude::SessionConfig;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn assert_optimized(
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+        expected: Vec<&str>,
+    ) -> Result<()> {
+        let optimized = OutputBatchSize::new().optimize(plan, config)?;
+        let formatted = displayable(optimized.as_ref()).indent().to_string();
+        let actual: Vec<&str> = formatted.trim().lines().collect();
+        assert_eq!(
+            expected, actual,
+            "\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}\n\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rechunk_output() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let plan: Arc<dyn ExecutionPlan> =
+            Arc::new(MemoryExec::try_new(&[], schema, None)?);
+
+        // no output batch size
+        assert_optimized(
+            plan.clone(),
+            &ConfigOptions::new(),
+            vec!["MemoryExec: partitions=1, partition_sizes=[0]"],
+        )?;
+
+        let config = SessionConfig::new().with_output_batch_size(1000);
+        let expected = vec![
+            "RechunkBatchesExec: batch_size=1000",
+            "  MemoryExec: partitions=1, partition_sizes=[0]",
+        ];
+        assert_optimized(plan.clone(), config.config_options(), expected.clone())?;
+
+        // the plan is only re-chunked once
+        let plan = Arc::new(RechunkBatchesExec::new(plan, 10));
+        assert_optimized(plan, config.config_options(), expected)
+    }
+}
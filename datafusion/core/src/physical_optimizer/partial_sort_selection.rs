@@ -0,0 +1,253 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Select the partial sort implementation for sorts whose input is already
+//! sorted on a prefix of the sort expressions.
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::rewrite::TreeNode;
+use crate::physical_plan::sorts::partial_sort::PartialSortExec;
+use crate::physical_plan::sorts::sort::SortExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// Replaces a [SortExec] whose input is sorted on a strict prefix of its sort
+/// expressions with a [PartialSortExec], which only sorts the runs of rows
+/// with equal prefix values, with bounded memory, instead of its whole input.
+///
+/// Since [PartialSortExec] sorts each input partition on its own, a global
+/// sort is only replaced if its input has a single partition.
+#[derive(Default)]
+pub struct PartialSortSelection {}
+
+impl PartialSortSelection {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for PartialSortSelection {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_partial_sort {
+            return Ok(plan);
+        }
+        plan.transform_up(&|plan| {
+            let sort_exec = match plan.as_any().downcast_ref::<SortExec>() {
+                Some(sort_exec) => sort_exec,
+                None => return Ok(None),
+            };
+            let input = sort_exec.input();
+            if !sort_exec.preserve_partitioning()
+                && input.output_partitioning().partition_count() > 1
+            {
+                return Ok(None);
+            }
+            let common_prefix_length = match input.output_ordering() {
+                Some(ordering) => ordering
+                    .iter()
+                    .zip(sort_exec.expr())
+                    .take_while(|(input_expr, sort_expr)| input_expr == sort_expr)
+                    .count(),
+                None => 0,
+            };
+            if common_prefix_length == 0 || common_prefix_length >= sort_exec.expr().len()
+            {
+                return Ok(None);
+            }
+            let partial_sort: Arc<dyn ExecutionPlan> =
+                Arc::new(PartialSortExec::try_new(
+                    sort_exec.expr().to_vec(),
+                    input.clone(),
+                    common_prefix_length,
+                    sort_exec.fetch(),
+                )?);
+            Ok(Some(partial_sort))
+        })
+    }
+
+    fn name(&self) -> &str {
+        "partial_sort_selection"
+    }
+
+    fn run_after(&self) -> &[&str] {
+        &["EnforceSorting"]
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::displayable;
+    use crate::physical_plan::expressions::{col, PhysicalSortExpr};
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::union::UnionExec;
+    use crate::prelude::SessionConfig;
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]))
+    }
+
+    fn sort_exprs(names: &[&str], schema: &SchemaRef) -> Result<Vec<PhysicalSortExpr>> {
+        names
+            .iter()
+            .map(|name| {
+                Ok(PhysicalSortExpr {
+                    expr: col(name, schema)?,
+                    options: SortOptions::default(),
+                })
+            })
+            .collect()
+    }
+
+    /// A source sorted by `a`, with `partitions` partitions
+    fn sorted_source(partitions: usize) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = schema();
+        let source: Arc<dyn ExecutionPlan> =
+            Arc::new(MemoryExec::try_new(&[], schema.clone(), None)?);
+        let source = if partitions > 1 {
+            Arc::new(UnionExec::new(vec![source; partitions]))
+        } else {
+            source
+        };
+        Ok(Arc::new(SortExec::new_with_partitioning(
+            sort_exprs(&["a"], &schema)?,
+            source,
+            true,
+            None,
+        )))
+    }
+
+    fn assert_optimized(
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+        expected: Vec<&str>,
+    ) -> Result<()> {
+        let optimized = PartialSortSelection::new().optimize(plan, config)?;
+        let formatted = displayable(optimized.as_ref()).indent().to_string();
+        let actual: Vec<&str> = formatted.trim().lines().collect();
+        assert_eq!(
+            expected, actual,
+            "\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}\n\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn partial_sort_on_sorted_prefix() -> Result<()> {
+        let schema = schema();
+        let plan = Arc::new(SortExec::try_new(
+            sort_exprs(&["a", "b"], &schema)?,
+            sorted_source(1)?,
+            Some(10),
+        )?);
+        let config = ConfigOptions::new();
+        assert_optimized(
+            plan.clone(),
+            &config,
+            vec![
+                "PartialSortExec: [a@0 ASC,b@1 ASC], common_prefix_length=[1]",
+                "  SortExec: [a@0 ASC]",
+                "    MemoryExec: partitions=1, partition_sizes=[0]",
+            ],
+        )?;
+
+        let config = SessionConfig::new()
+            .set_bool("datafusion.optimizer.enable_partial_sort", false);
+        assert_optimized(
+            plan,
+            config.config_options(),
+            vec![
+                "SortExec: [a@0 ASC,b@1 ASC]",
+                "  SortExec: [a@0 ASC]",
+                "    MemoryExec: partitions=1, partition_sizes=[0]",
+            ],
+        )
+    }
+
+    #[test]
+    fn no_partial_sort() -> Result<()> {
+        let schema = schema();
+        let config = ConfigOptions::new();
+
+        // the input isn't sorted on a prefix
+        let plan = Arc::new(SortExec::try_new(
+            sort_exprs(&["b", "a"], &schema)?,
+            sorted_source(1)?,
+            None,
+        )?);
+        assert_optimized(
+            plan,
+            &config,
+            vec![
+                "SortExec: [b@1 ASC,a@0 ASC]",
+                "  SortExec: [a@0 ASC]",
+                "    MemoryExec: partitions=1, partition_sizes=[0]",
+            ],
+        )?;
+
+        // the input is already sorted
+        let plan = Arc::new(SortExec::try_new(
+            sort_exprs(&["a"], &schema)?,
+            sorted_source(1)?,
+            None,
+        )?);
+        assert_optimized(
+            plan,
+            &config,
+            vec![
+                "SortExec: [a@0 ASC]",
+                "  SortExec: [a@0 ASC]",
+                "    MemoryExec: partitions=1, partition_sizes=[0]",
+            ],
+        )?;
+
+        // a global sort of several partitions
+        let plan = Arc::new(SortExec::try_new(
+            sort_exprs(&["a", "b"], &schema)?,
+            sorted_source(2)?,
+            None,
+        )?);
+        assert_optimized(
+            plan,
+            &config,
+            vec![
+                "SortExec: [a@0 ASC,b@1 ASC]",
+                "  SortExec: [a@0 ASC]",
+                "    UnionExec",
+                "      MemoryExec: partitions=1, partition_sizes=[0]",
+                "      MemoryExec: partitions=1, partition_sizes=[0]",
+            ],
+        )
+    }
+}
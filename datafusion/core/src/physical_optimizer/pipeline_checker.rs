@@ -22,7 +22,7 @@
 use crate::config::ConfigOptions;
 use crate::error::Result;
 use crate::physical_optimizer::PhysicalOptimizerRule;
-use crate::physical_plan::rewrite::TreeNodeRewritable;
+use crate::physical_plan::rewrite::{TreeNode, VisitRecursion};
 use crate::physical_plan::{with_new_children_if_necessary, ExecutionPlan};
 use std::sync::Arc;
 
@@ -53,6 +53,10 @@ impl PhysicalOptimizerRule for PipelineChecker {
         "PipelineChecker"
     }
 
+    fn run_after(&self) -> &[&str] {
+        &["PipelineFixer"]
+    }
+
     fn schema_check(&self) -> bool {
         true
     }
@@ -78,7 +82,19 @@ impl PipelineStatePropagator {
     }
 }
 
-impl TreeNodeRewritable for PipelineStatePropagator {
+impl TreeNode for PipelineStatePropagator {
+    fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>,
+    {
+        for child in self.plan.children() {
+            if op(&PipelineStatePropagator::new(child))? == VisitRecursion::Stop {
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    }
+
     fn map_children<F>(self, transform: F) -> Result<Self>
     where
         F: FnMut(Self) -> Result<Self>,
@@ -30,7 +30,7 @@ use crate::physical_optimizer::pipeline_checker::{
 };
 use crate::physical_optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::joins::{HashJoinExec, PartitionMode};
-use crate::physical_plan::rewrite::TreeNodeRewritable;
+use crate::physical_plan::rewrite::TreeNode;
 use crate::physical_plan::ExecutionPlan;
 use datafusion_common::DataFusionError;
 use datafusion_expr::logical_plan::JoinType;
@@ -72,6 +72,10 @@ impl PhysicalOptimizerRule for PipelineFixer {
         "PipelineFixer"
     }
 
+    fn run_after(&self) -> &[&str] {
+        &["join_selection"]
+    }
+
     fn schema_check(&self) -> bool {
         true
     }
@@ -154,14 +158,19 @@ fn hash_join_swap_subrule(
 /// This function swaps sides of a hash join to make it runnable even if one of its
 /// inputs are infinite. Note that this is not always possible; i.e. [JoinType::Full],
 /// [JoinType::Right], [JoinType::RightAnti] and [JoinType::RightSemi] can not run with
-/// an unbounded left side, even if we swap. Therefore, we do not consider them here.
+/// an unbounded left side, even if we swap, and the inputs of [JoinType::LeftMark]
+/// can not be swapped. Therefore, we do not consider them here.
 fn swap(hash_join: &HashJoinExec) -> Result<Arc<dyn ExecutionPlan>> {
     let partition_mode = hash_join.partition_mode();
     let join_type = hash_join.join_type();
     match (*partition_mode, *join_type) {
         (
             _,
-            JoinType::Right | JoinType::RightSemi | JoinType::RightAnti | JoinType::Full,
+            JoinType::Right
+            | JoinType::RightSemi
+            | JoinType::RightAnti
+            | JoinType::Full
+            | JoinType::LeftMark,
         ) => Err(DataFusionError::Internal(format!(
             "{join_type} join cannot be swapped for unbounded input."
         ))),
@@ -267,6 +276,37 @@ mod hash_join_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_left_mark_join_is_not_swapped() -> Result<()> {
+        let left = Arc::new(UnboundedExec::new(
+            true,
+            Schema::new(vec![Field::new("a", DataType::Int32, false)]),
+        )) as Arc<dyn ExecutionPlan>;
+        let right = Arc::new(UnboundedExec::new(
+            false,
+            Schema::new(vec![Field::new("b", DataType::Int32, false)]),
+        )) as Arc<dyn ExecutionPlan>;
+        let join = HashJoinExec::try_new(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            vec![(
+                Column::new_with_schema("a", &left.schema())?,
+                Column::new_with_schema("b", &right.schema())?,
+            )],
+            None,
+            &JoinType::LeftMark,
+            PartitionMode::Partitioned,
+            &false,
+        )?;
+
+        let err = swap(&join).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("join cannot be swapped for unbounded input"));
+        assert!(swap_join_type(JoinType::LeftMark).is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_cases_without_collect_left_check() -> Result<()> {
         let mut cases = vec![];
@@ -278,7 +318,7 @@ mod hash_join_tests {
                 initial_join_type: join_type,
                 initial_mode: PartitionMode::CollectLeft,
                 expected_sources_unbounded: (SourceType::Bounded, SourceType::Unbounded),
-                expected_join_type: swap_join_type(join_type),
+                expected_join_type: swap_join_type(join_type)?,
                 expected_mode: PartitionMode::CollectLeft,
                 expecting_swap: true,
             });
@@ -321,7 +361,7 @@ mod hash_join_tests {
                 initial_join_type: join_type,
                 initial_mode: PartitionMode::Partitioned,
                 expected_sources_unbounded: (SourceType::Bounded, SourceType::Unbounded),
-                expected_join_type: swap_join_type(join_type),
+                expected_join_type: swap_join_type(join_type)?,
                 expected_mode: PartitionMode::Partitioned,
                 expecting_swap: true,
             });
@@ -379,7 +419,7 @@ mod hash_join_tests {
                 initial_join_type: join_type,
                 initial_mode: PartitionMode::Partitioned,
                 expected_sources_unbounded: (SourceType::Bounded, SourceType::Unbounded),
-                expected_join_type: swap_join_type(join_type),
+                expected_join_type: swap_join_type(join_type)?,
                 expected_mode: PartitionMode::Partitioned,
                 expecting_swap: true,
             });
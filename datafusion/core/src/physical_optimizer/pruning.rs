@@ -39,6 +39,7 @@ use crate::{
     logical_expr::{Expr, Operator},
     physical_plan::{ColumnarValue, PhysicalExpr},
 };
+use arrow::compute::can_cast_types;
 use arrow::record_batch::RecordBatchOptions;
 use arrow::{
     array::{new_null_array, ArrayRef, BooleanArray},
@@ -89,6 +90,12 @@ pub trait PruningStatistics {
     ///
     /// Note: the returned array must contain `num_containers()` rows.
     fn null_counts(&self, column: &Column) -> Option<ArrayRef>;
+
+    /// return the number of rows for the named column in each container
+    /// as an `Option<UInt64Array>`.
+    ///
+    /// Note: the returned array must contain `num_containers()` rows.
+    fn row_counts(&self, column: &Column) -> Option<ArrayRef>;
 }
 
 /// Evaluates filter expressions on statistics in order to
@@ -351,6 +358,22 @@ impl RequiredStatColumns {
             "null_count",
         )
     }
+
+    /// rewrite col --> col_row_count
+    fn row_count_column_expr(
+        &mut self,
+        column: &Column,
+        column_expr: &Expr,
+        field: &Field,
+    ) -> Result<Expr> {
+        self.stat_column_expr(
+            column,
+            column_expr,
+            field,
+            StatisticsType::RowCount,
+            "row_count",
+        )
+    }
 }
 
 impl From<Vec<(Column, StatisticsType, Field)>> for RequiredStatColumns {
@@ -400,6 +423,7 @@ fn build_statistics_record_batch<S: PruningStatistics>(
             StatisticsType::Min => statistics.min_values(column),
             StatisticsType::Max => statistics.max_values(column),
             StatisticsType::NullCount => statistics.null_counts(column),
+            StatisticsType::RowCount => statistics.row_counts(column),
         };
         let array = array.unwrap_or_else(|| new_null_array(data_type, num_containers));
 
@@ -524,6 +548,9 @@ impl<'a> PruningExpressionBuilder<'a> {
 /// 4. `abs(a - 10) > 0` not supported
 /// 5. `cast(can_prunable_expr) > 10`
 /// 6. `try_cast(can_prunable_expr) > 10`
+/// 7. `date_trunc('day', can_prunable_expr) = '2023-01-01'`, for functions
+///    that are monotonically non-decreasing in one argument whose other
+///    arguments are constants
 ///
 /// More rewrite rules are still in progress.
 fn rewrite_expr_to_prunable(
@@ -581,6 +608,43 @@ fn rewrite_expr_to_prunable(
                 ))),
             };
         }
+        // `f(col) op lit()`, where `f` is monotonically non-decreasing in `col`
+        Expr::ScalarFunction { fun, args } => {
+            let (args, op, right) = rewrite_monotonic_call_to_prunable(
+                &fun.to_string(),
+                fun.monotonic_argument(),
+                args,
+                op,
+                scalar_expr,
+                schema,
+            )?;
+            Ok((
+                Expr::ScalarFunction {
+                    fun: fun.clone(),
+                    args,
+                },
+                op,
+                right,
+            ))
+        }
+        Expr::ScalarUDF { fun, args } => {
+            let (args, op, right) = rewrite_monotonic_call_to_prunable(
+                &fun.name,
+                fun.monotonic_argument,
+                args,
+                op,
+                scalar_expr,
+                schema,
+            )?;
+            Ok((
+                Expr::ScalarUDF {
+                    fun: fun.clone(),
+                    args,
+                },
+                op,
+                right,
+            ))
+        }
 
         _ => Err(DataFusionError::Plan(format!(
             "column expression {column_expr:?} is not supported"
@@ -588,6 +652,49 @@ fn rewrite_expr_to_prunable(
     }
 }
 
+/// Rewrites the arguments of a call of function `fun`, monotonically
+/// non-decreasing in its argument at `monotonic_argument`, compared with
+/// `op` to `scalar_expr`
+fn rewrite_monotonic_call_to_prunable(
+    fun: &str,
+    monotonic_argument: Option<usize>,
+    args: &[Expr],
+    op: Operator,
+    scalar_expr: &Expr,
+    schema: DFSchema,
+) -> Result<(Vec<Expr>, Operator, Expr)> {
+    let index = monotonic_argument.ok_or_else(|| {
+        DataFusionError::Plan(format!("Function {fun} is not monotonic"))
+    })?;
+    let arg = args.get(index).ok_or_else(|| {
+        DataFusionError::Plan(format!("Function {fun} is missing its monotonic argument"))
+    })?;
+    for (i, other) in args.iter().enumerate() {
+        if i != index && !other.to_columns()?.is_empty() {
+            return Err(DataFusionError::Plan(format!(
+                "Function {fun} with non constant argument {other:?} is not supported"
+            )));
+        }
+    }
+    if !is_order_preserving_type(&arg.get_type(&schema)?) {
+        return Err(DataFusionError::Plan(format!(
+            "Function {fun} of argument {arg:?} is not supported"
+        )));
+    }
+
+    // the function is only monotonic in its argument itself, so the
+    // argument must not rewrite the operator or the literal
+    let (left, arg_op, right) = rewrite_expr_to_prunable(arg, op, scalar_expr, schema)?;
+    if arg_op != op || right != *scalar_expr {
+        return Err(DataFusionError::Plan(format!(
+            "Function {fun} of argument {arg:?} is not supported"
+        )));
+    }
+    let mut args = args.to_vec();
+    args[index] = left;
+    Ok((args, op, right))
+}
+
 fn is_compare_op(op: Operator) -> bool {
     matches!(
         op,
@@ -605,18 +712,13 @@ fn is_compare_op(op: Operator) -> bool {
 // For example, casts from string to numbers is not correct.
 // Because the "13" is less than "3" with UTF8 comparison order.
 fn verify_support_type_for_prune(from_type: &DataType, to_type: &DataType) -> Result<()> {
-    // TODO: support other data type for prunable cast or try cast
-    if matches!(
-        from_type,
-        DataType::Int8
-            | DataType::Int16
-            | DataType::Int32
-            | DataType::Int64
-            | DataType::Decimal128(_, _)
-    ) && matches!(
-        to_type,
-        DataType::Int8 | DataType::Int32 | DataType::Int64 | DataType::Decimal128(_, _)
-    ) {
+    // Casts between numeric and temporal types are monotonically
+    // non-decreasing. Values that overflow the target type become NULL
+    // (or an error), which conservatively keeps the container.
+    if is_order_preserving_type(from_type)
+        && is_order_preserving_type(to_type)
+        && can_cast_types(from_type, to_type)
+    {
         Ok(())
     } else {
         Err(DataFusionError::Plan(format!(
@@ -625,6 +727,28 @@ fn verify_support_type_for_prune(from_type: &DataType, to_type: &DataType) -> Re
     }
 }
 
+/// Returns true if values of `data_type` are ordered the same way as the
+/// numbers they represent, so that monotonic functions preserve that order
+fn is_order_preserving_type(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal128(_, _)
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Timestamp(_, _)
+    )
+}
+
 /// replaces a column with an old name with a new name in an expression
 fn rewrite_column_expr(
     e: Expr,
@@ -698,27 +822,39 @@ fn build_single_column_expr(
     }
 }
 
-/// Given an expression reference to `expr`, if `expr` is a column expression,
-/// returns a pruning expression in terms of IsNull that will evaluate to true
-/// if the column may contain null, and false if definitely does not
-/// contain null.
+/// Given an expression reference to `expr`, if `expr` is null exactly when
+/// a column is null (e.g. `col`, `CAST(col AS ...)` or `-col`), returns a
+/// pruning expression in terms of the null count of that column that will
+/// evaluate to true if `expr` may be null (or not null, if `is_not` is set),
+/// and false if it definitely is not.
 fn build_is_null_column_expr(
     expr: &Expr,
     schema: &Schema,
     required_columns: &mut RequiredStatColumns,
+    is_not: bool, // if true, treat as IsNotNull(expr)
 ) -> Option<Expr> {
     match expr {
         Expr::Column(ref col) => {
             let field = schema.field_with_name(&col.name).ok()?;
 
-            let null_count_field = &Field::new(field.name(), DataType::UInt64, true);
-            required_columns
-                .null_count_column_expr(col, expr, null_count_field)
-                .map(|null_count_column_expr| {
-                    // IsNull(column) => null_count > 0
-                    null_count_column_expr.gt(lit::<u64>(0))
-                })
-                .ok()
+            let count_field = &Field::new(field.name(), DataType::UInt64, true);
+            let null_count_column_expr = required_columns
+                .null_count_column_expr(col, expr, count_field)
+                .ok()?;
+            if is_not {
+                // IsNotNull(column) => null_count < row_count
+                let row_count_column_expr = required_columns
+                    .row_count_column_expr(col, expr, count_field)
+                    .ok()?;
+                Some(null_count_column_expr.lt(row_count_column_expr))
+            } else {
+                // IsNull(column) => null_count > 0
+                Some(null_count_column_expr.gt(lit::<u64>(0)))
+            }
+        }
+        // casts that fail raise an error rather than returning null
+        Expr::Cast(Cast { expr, .. }) | Expr::Negative(expr) | Expr::Not(expr) => {
+            build_is_null_column_expr(expr, schema, required_columns, is_not)
         }
         _ => None,
     }
@@ -742,7 +878,12 @@ fn build_predicate_expression(
     let (left, op, right) = match expr {
         Expr::BinaryExpr(BinaryExpr { left, op, right }) => (left, *op, right),
         Expr::IsNull(expr) => {
-            let expr = build_is_null_column_expr(expr, schema, required_columns)
+            let expr = build_is_null_column_expr(expr, schema, required_columns, false)
+                .unwrap_or(unhandled);
+            return Ok(expr);
+        }
+        Expr::IsNotNull(expr) => {
+            let expr = build_is_null_column_expr(expr, schema, required_columns, true)
                 .unwrap_or(unhandled);
             return Ok(expr);
         }
@@ -870,6 +1011,7 @@ enum StatisticsType {
     Min,
     Max,
     NullCount,
+    RowCount,
 }
 
 #[cfg(test)]
@@ -883,8 +1025,11 @@ mod tests {
         array::{BinaryArray, Int32Array, Int64Array, StringArray},
         datatypes::{DataType, TimeUnit},
     };
-    use datafusion_common::ScalarValue;
-    use datafusion_expr::{cast, is_null};
+    use datafusion_common::{cast::as_int32_array, ScalarValue};
+    use datafusion_expr::{
+        abs, cast, create_udf, date_bin, date_trunc, floor, is_null, Volatility,
+    };
+    use datafusion_physical_expr::functions::make_scalar_function;
     use std::collections::HashMap;
 
     #[derive(Debug)]
@@ -900,6 +1045,8 @@ mod tests {
         max: ArrayRef,
         /// Optional values
         null_counts: Option<ArrayRef>,
+        /// Optional values
+        row_counts: Option<ArrayRef>,
     }
 
     impl ContainerStats {
@@ -923,6 +1070,7 @@ mod tests {
                         .unwrap(),
                 ),
                 null_counts: None,
+                row_counts: None,
             }
         }
 
@@ -934,6 +1082,7 @@ mod tests {
                 min: Arc::new(min.into_iter().collect::<Int64Array>()),
                 max: Arc::new(max.into_iter().collect::<Int64Array>()),
                 null_counts: None,
+                row_counts: None,
             }
         }
 
@@ -945,6 +1094,7 @@ mod tests {
                 min: Arc::new(min.into_iter().collect::<Int32Array>()),
                 max: Arc::new(max.into_iter().collect::<Int32Array>()),
                 null_counts: None,
+                row_counts: None,
             }
         }
 
@@ -956,6 +1106,7 @@ mod tests {
                 min: Arc::new(min.into_iter().collect::<StringArray>()),
                 max: Arc::new(max.into_iter().collect::<StringArray>()),
                 null_counts: None,
+                row_counts: None,
             }
         }
 
@@ -967,6 +1118,7 @@ mod tests {
                 min: Arc::new(min.into_iter().collect::<BooleanArray>()),
                 max: Arc::new(max.into_iter().collect::<BooleanArray>()),
                 null_counts: None,
+                row_counts: None,
             }
         }
 
@@ -982,6 +1134,10 @@ mod tests {
             self.null_counts.clone()
         }
 
+        fn row_counts(&self) -> Option<ArrayRef> {
+            self.row_counts.clone()
+        }
+
         fn len(&self) -> usize {
             assert_eq!(self.min.len(), self.max.len());
             self.min.len()
@@ -1001,6 +1157,20 @@ mod tests {
             self.null_counts = Some(null_counts);
             self
         }
+
+        /// Add row counts. There must be the same number of row counts as
+        /// there are containers
+        fn with_row_counts(
+            mut self,
+            counts: impl IntoIterator<Item = Option<i64>>,
+        ) -> Self {
+            let row_counts: ArrayRef =
+                Arc::new(counts.into_iter().collect::<Int64Array>());
+
+            assert_eq!(row_counts.len(), self.len());
+            self.row_counts = Some(row_counts);
+            self
+        }
     }
 
     #[derive(Debug, Default)]
@@ -1045,6 +1215,28 @@ mod tests {
             self.stats.insert(col, container_stats);
             self
         }
+
+        /// Add row counts for the specified columm.
+        /// There must be the same number of row counts as
+        /// there are containers
+        fn with_row_counts(
+            mut self,
+            name: impl Into<String>,
+            counts: impl IntoIterator<Item = Option<i64>>,
+        ) -> Self {
+            let col = Column::from_name(name.into());
+
+            // take stats out and update them
+            let container_stats = self
+                .stats
+                .remove(&col)
+                .expect("Can not find stats for column")
+                .with_row_counts(counts);
+
+            // put stats back in
+            self.stats.insert(col, container_stats);
+            self
+        }
     }
 
     impl PruningStatistics for TestStatistics {
@@ -1076,6 +1268,13 @@ mod tests {
                 .map(|container_stats| container_stats.null_counts())
                 .unwrap_or(None)
         }
+
+        fn row_counts(&self, column: &Column) -> Option<ArrayRef> {
+            self.stats
+                .get(column)
+                .map(|container_stats| container_stats.row_counts())
+                .unwrap_or(None)
+        }
     }
 
     /// Returns the specified min/max container values
@@ -1101,6 +1300,10 @@ mod tests {
         fn null_counts(&self, _column: &Column) -> Option<ArrayRef> {
             None
         }
+
+        fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+            None
+        }
     }
 
     #[test]
@@ -2134,6 +2337,174 @@ mod tests {
         assert_eq!(result, expected_ret);
     }
 
+    #[test]
+    fn prune_int32_is_not_null() {
+        let (schema, statistics) = int32_setup();
+        let statistics = statistics
+            .with_null_counts("i", vec![Some(0), Some(10), None, Some(10), Some(3)])
+            .with_row_counts("i", vec![Some(10), Some(10), Some(10), None, Some(10)]);
+
+        // i IS NOT NULL
+        // i [-5, 5], no nulls ==> some rows could pass (must keep)
+        // i [1, 11], only nulls ==> no rows can pass (not keep)
+        // i [-11, -1], unknown nulls ==> unknown (must keep)
+        // i [NULL, NULL], unknown rows ==> unknown (must keep)
+        // i [1, NULL], some nulls ==> some rows could pass (must keep)
+        let expected_ret = vec![true, false, true, true, true];
+
+        let expr = col("i").is_not_null();
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        assert_eq!(p.prune(&statistics).unwrap(), expected_ret);
+
+        // the cast of a column is null exactly when the column is null
+        let expr = cast(col("i"), DataType::Int64).is_not_null();
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        assert_eq!(p.prune(&statistics).unwrap(), expected_ret);
+
+        // i IS NULL
+        let expected_ret = vec![false, true, true, true, true];
+        let expr = Expr::Negative(Box::new(cast(col("i"), DataType::Int64))).is_null();
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        assert_eq!(p.prune(&statistics).unwrap(), expected_ret);
+
+        // try_cast may return null for non null values
+        let expected_ret = vec![true; 5];
+        let expr = try_cast(col("i"), DataType::Int8).is_null();
+        let p = PruningPredicate::try_new(expr, schema).unwrap();
+        assert_eq!(p.prune(&statistics).unwrap(), expected_ret);
+    }
+
+    #[test]
+    fn prune_int32_col_cast_to_float() {
+        let (schema, statistics) = int32_setup();
+
+        // Expression "cast(i as float64) > 1.5"
+        // i [-5, 5] ==> some rows could pass (must keep)
+        // i [1, 11] ==> some rows could pass (must keep)
+        // i [-11, -1] ==>  no rows can pass (not keep)
+        // i [NULL, NULL]  ==> unknown (must keep)
+        // i [1, NULL]  ==> unknown (must keep)
+        let expected_ret = vec![true, true, false, true, true];
+
+        let expr = cast(col("i"), DataType::Float64).gt(lit(1.5f64));
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        let result = p.prune(&statistics).unwrap();
+        assert_eq!(result, expected_ret);
+
+        // Expression "cast(i as date) < '1970-01-01'"
+        let expected_ret = vec![true, false, true, true, false];
+        let expr = cast(col("i"), DataType::Date32).lt(lit(ScalarValue::Date32(Some(0))));
+        let p = PruningPredicate::try_new(expr, schema).unwrap();
+        let result = p.prune(&statistics).unwrap();
+        assert_eq!(result, expected_ret);
+    }
+
+    #[test]
+    fn prune_monotonic_function() {
+        let (schema, statistics) = int32_setup();
+
+        // Expression "ceil(cast(i as float64) / 1) = 3" is not prunable
+        // as the division is not supported, while "floor(cast(i as float64)) = 3"
+        // i [-5, 5] ==> some rows could pass (must keep)
+        // i [1, 11] ==> some rows could pass (must keep)
+        // i [-11, -1] ==>  no rows can pass (not keep)
+        // i [NULL, NULL]  ==> unknown (must keep)
+        // i [1, NULL]  ==> unknown (must keep)
+        let expected_ret = vec![true, true, false, true, true];
+        let expr = floor(cast(col("i"), DataType::Float64)).eq(lit(3f64));
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        let result = p.prune(&statistics).unwrap();
+        assert_eq!(result, expected_ret);
+
+        let expected_ret = vec![true; 5];
+        let expr = floor(cast(col("i"), DataType::Float64) / lit(1f64)).eq(lit(3f64));
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        let result = p.prune(&statistics).unwrap();
+        assert_eq!(result, expected_ret);
+
+        // abs is not monotonic
+        let expr = abs(col("i")).gt(lit(6));
+        let p = PruningPredicate::try_new(expr, schema).unwrap();
+        let result = p.prune(&statistics).unwrap();
+        assert_eq!(result, expected_ret);
+    }
+
+    #[test]
+    fn prune_monotonic_udf() {
+        let (schema, statistics) = int32_setup();
+        let plus_one = |args: &[ArrayRef]| {
+            let array = as_int32_array(&args[0])?;
+            Ok(Arc::new(arrow::compute::add_scalar(array, 1)?) as ArrayRef)
+        };
+        let plus_one = create_udf(
+            "plus_one",
+            vec![DataType::Int32],
+            Arc::new(DataType::Int32),
+            Volatility::Immutable,
+            make_scalar_function(plus_one),
+        );
+
+        // i [-5, 5] ==> some rows could pass (must keep)
+        // i [1, 11] ==> some rows could pass (must keep)
+        // i [-11, -1] ==>  no rows can pass (not keep)
+        // i [NULL, NULL]  ==> unknown (must keep)
+        // i [1, NULL]  ==> unknown (must keep)
+        let expected_ret = vec![true, true, false, true, true];
+        let expr = plus_one
+            .clone()
+            .with_monotonic_argument(0)
+            .call(vec![col("i")])
+            .eq(lit(3));
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        let result = p.prune(&statistics).unwrap();
+        assert_eq!(result, expected_ret);
+
+        // without the monotonic argument, nothing can be pruned
+        let expr = plus_one.call(vec![col("i")]).eq(lit(3));
+        let p = PruningPredicate::try_new(expr, schema).unwrap();
+        let result = p.prune(&statistics).unwrap();
+        assert_eq!(result, vec![true; 5]);
+    }
+
+    #[test]
+    fn prune_date_trunc() {
+        // ts ranges:
+        // [2023-01-01T10:00:00, 2023-01-01T20:00:00]
+        // [2023-01-02T10:00:00, 2023-01-03T10:00:00]
+        // [NULL, NULL]
+        let hour = 3_600_000_000_000;
+        let day = 24 * hour;
+        let jan_1 = 1_672_531_200_000_000_000;
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        )]));
+        let statistics = TestStatistics::new().with(
+            "ts",
+            ContainerStats::new_i64(
+                vec![Some(jan_1 + 10 * hour), Some(jan_1 + day + 10 * hour), None],
+                vec![
+                    Some(jan_1 + 20 * hour),
+                    Some(jan_1 + 2 * day + 10 * hour),
+                    None,
+                ],
+            ),
+        );
+        let date_trunc_day = date_trunc(lit("day"), col("ts"));
+        let jan_2 = lit(ScalarValue::TimestampNanosecond(Some(jan_1 + day), None));
+
+        // date_trunc('day', ts) = '2023-01-02'
+        let expr = date_trunc_day.clone().eq(jan_2.clone());
+        let p = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        assert_eq!(p.prune(&statistics).unwrap(), vec![false, true, true]);
+
+        // date_trunc('day', ts) < '2023-01-02'
+        let expr = date_trunc_day.lt(jan_2);
+        let p = PruningPredicate::try_new(expr, schema).unwrap();
+        assert_eq!(p.prune(&statistics).unwrap(), vec![true, false, true]);
+    }
+
     #[test]
     fn prune_cast_column_scalar() {
         // The data type of column i is INT32
@@ -2221,6 +2592,16 @@ mod tests {
             df_schema.clone(),
         );
         assert!(result.is_err());
+        // non constant argument of a monotonic function
+        let left_input = date_bin(col("a"), col("a"), lit(0));
+        let right_input = lit(ScalarValue::Int64(Some(12)));
+        let result = rewrite_expr_to_prunable(
+            &left_input,
+            Operator::Gt,
+            &right_input,
+            df_schema.clone(),
+        );
+        assert!(result.is_err());
         // other expr
         let left_input = is_null(col("a"));
         let right_input = lit(ScalarValue::Int64(Some(12)));
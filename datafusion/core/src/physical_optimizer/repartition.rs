@@ -24,6 +24,7 @@ use crate::error::Result;
 use crate::physical_plan::Partitioning::*;
 use crate::physical_plan::{
     repartition::RepartitionExec, with_new_children_if_necessary, ExecutionPlan,
+    Precision,
 };
 
 /// Optimizer that introduces repartition to introduce more
@@ -185,10 +186,8 @@ fn optimize_partitions(
     };
 
     // Don't need to apply when the returned row count is not greater than 1
-    let stats = new_plan.statistics();
-    if stats.is_exact {
-        could_repartition = could_repartition
-            && stats.num_rows.map(|num_rows| num_rows > 1).unwrap_or(true);
+    if let Precision::Exact(num_rows) = new_plan.statistics().num_rows {
+        could_repartition = could_repartition && num_rows > 1;
     }
 
     if would_benefit && could_repartition && can_reorder {
@@ -263,7 +262,7 @@ mod tests {
                 object_store_url: ObjectStoreUrl::parse("test:///").unwrap(),
                 file_schema: schema(),
                 file_groups: vec![vec![PartitionedFile::new("x".to_string(), 100)]],
-                statistics: Statistics::default(),
+                statistics: Statistics::new_unknown(&schema()),
                 projection: None,
                 limit: None,
                 table_partition_cols: vec![],
@@ -35,10 +35,10 @@ use crate::physical_optimizer::utils::{
     add_sort_above_child, ordering_satisfy, ordering_satisfy_concrete,
 };
 use crate::physical_optimizer::PhysicalOptimizerRule;
-use crate::physical_plan::rewrite::TreeNodeRewritable;
+use crate::physical_plan::rewrite::{TreeNode, VisitRecursion};
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::windows::{BoundedWindowAggExec, WindowAggExec};
-use crate::physical_plan::{with_new_children_if_necessary, ExecutionPlan};
+use crate::physical_plan::{displayable, with_new_children_if_necessary, ExecutionPlan};
 use arrow::datatypes::SchemaRef;
 use datafusion_common::{reverse_sort_options, DataFusionError};
 use datafusion_physical_expr::window::WindowExpr;
@@ -88,7 +88,19 @@ impl PlanWithCorrespondingSort {
     }
 }
 
-impl TreeNodeRewritable for PlanWithCorrespondingSort {
+impl TreeNode for PlanWithCorrespondingSort {
+    fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>,
+    {
+        for child in self.children() {
+            if op(&child)? == VisitRecursion::Stop {
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    }
+
     fn map_children<F>(self, transform: F) -> Result<Self>
     where
         F: FnMut(Self) -> Result<Self>,
@@ -139,11 +151,36 @@ impl PhysicalOptimizerRule for EnforceSorting {
         "EnforceSorting"
     }
 
+    fn run_after(&self) -> &[&str] {
+        &["global_sort_selection", "EnforceDistribution"]
+    }
+
+    fn check_postconditions(&self, plan: &dyn ExecutionPlan) -> Result<()> {
+        check_sorting(plan)
+    }
+
     fn schema_check(&self) -> bool {
         true
     }
 }
 
+/// Checks that the inputs of each operator of `plan` have the ordering it
+/// requires
+fn check_sorting(plan: &dyn ExecutionPlan) -> Result<()> {
+    for (child, required) in zip(plan.children(), plan.required_input_ordering()) {
+        if !ordering_satisfy(child.output_ordering(), required, || {
+            child.equivalence_properties()
+        }) {
+            return Err(DataFusionError::Internal(format!(
+                "EnforceSorting left an input of {} without the ordering {required:?}",
+                displayable(plan).one_line()
+            )));
+        }
+        check_sorting(child.as_ref())?;
+    }
+    Ok(())
+}
+
 fn ensure_sorting(
     requirements: PlanWithCorrespondingSort,
 ) -> Result<Option<PlanWithCorrespondingSort>> {
@@ -495,8 +532,9 @@ mod tests {
     use arrow::compute::SortOptions;
     use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
     use datafusion_common::Result;
+    use datafusion_expr::Operator;
     use datafusion_expr::{AggregateFunction, WindowFrame, WindowFunction};
-    use datafusion_physical_expr::expressions::{col, NotExpr};
+    use datafusion_physical_expr::expressions::{binary, col, lit, NotExpr};
     use datafusion_physical_expr::PhysicalSortExpr;
     use std::sync::Arc;
 
@@ -708,10 +746,10 @@ mod tests {
         let formatted = displayable(physical_plan.as_ref()).indent().to_string();
         let expected = {
             vec![
-                "WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: Preceding(NULL), end_bound: CurrentRow }]",
+                "WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: Preceding(NULL), end_bound: CurrentRow, exclude: NoOthers }]",
                 "  FilterExec: NOT non_nullable_col@1",
                 "    SortExec: [non_nullable_col@1 ASC NULLS LAST]",
-                "      WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: Preceding(NULL), end_bound: CurrentRow }]",
+                "      WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: Preceding(NULL), end_bound: CurrentRow, exclude: NoOthers }]",
                 "        SortExec: [non_nullable_col@1 DESC]",
                 "          MemoryExec: partitions=0, partition_sizes=[]",
             ]
@@ -728,9 +766,9 @@ mod tests {
             .to_string();
         let expected = {
             vec![
-                "WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: CurrentRow, end_bound: Following(NULL) }]",
+                "WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: CurrentRow, end_bound: Following(NULL), exclude: NoOthers }]",
                 "  FilterExec: NOT non_nullable_col@1",
-                "    WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: Preceding(NULL), end_bound: CurrentRow }]",
+                "    WindowAggExec: wdw=[count: Ok(Field { name: \"count\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }), frame: WindowFrame { units: Range, start_bound: Preceding(NULL), end_bound: CurrentRow, exclude: NoOthers }]",
                 "      SortExec: [non_nullable_col@1 DESC]",
                 "        MemoryExec: partitions=0, partition_sizes=[]",
             ]
@@ -909,4 +947,126 @@ mod tests {
         );
         Ok(())
     }
+
+    /// Optimizes the plan and compares its display with the expected lines
+    fn assert_optimized(
+        physical_plan: Arc<dyn ExecutionPlan>,
+        expected: Vec<&str>,
+    ) -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let optimized_physical_plan =
+            EnforceSorting::new().optimize(physical_plan, state.config_options())?;
+        let formatted = displayable(optimized_physical_plan.as_ref())
+            .indent()
+            .to_string();
+        let actual: Vec<&str> = formatted.trim().lines().collect();
+        assert_eq!(
+            expected, actual,
+            "\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}\n\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_unnecessary_sort_monotonic_expr() -> Result<()> {
+        let schema = create_test_schema()?;
+        let source = Arc::new(MemoryExec::try_new(&[], schema.clone(), None)?)
+            as Arc<dyn ExecutionPlan>;
+        let nullable_col = col("nullable_col", schema.as_ref())?;
+        let non_nullable_col = col("non_nullable_col", schema.as_ref())?;
+        let sort_exprs = vec![
+            PhysicalSortExpr {
+                expr: nullable_col.clone(),
+                options: SortOptions::default(),
+            },
+            PhysicalSortExpr {
+                expr: non_nullable_col.clone(),
+                options: SortOptions::default(),
+            },
+        ];
+        let sort_exec = Arc::new(SortExec::try_new(sort_exprs, source, None)?)
+            as Arc<dyn ExecutionPlan>;
+
+        // [1 - nullable_col DESC, non_nullable_col + 1 ASC] is satisfied
+        let reversed = SortOptions {
+            descending: true,
+            nulls_first: true,
+        };
+        let sort_exprs = vec![
+            PhysicalSortExpr {
+                expr: binary(lit(1), Operator::Minus, nullable_col.clone(), &schema)?,
+                options: reversed,
+            },
+            PhysicalSortExpr {
+                expr: binary(non_nullable_col.clone(), Operator::Plus, lit(1), &schema)?,
+                options: SortOptions::default(),
+            },
+        ];
+        let physical_plan =
+            Arc::new(SortExec::try_new(sort_exprs, sort_exec.clone(), None)?)
+                as Arc<dyn ExecutionPlan>;
+        let expected = vec![
+            "SortExec: [nullable_col@0 ASC,non_nullable_col@1 ASC]",
+            "  MemoryExec: partitions=0, partition_sizes=[]",
+        ];
+        assert_optimized(physical_plan, expected)?;
+
+        // nullable_col / 2 is not strictly monotonic, so the ties on its
+        // values are not sorted on non_nullable_col and the outer sort is kept
+        let sort_exprs = vec![
+            PhysicalSortExpr {
+                expr: binary(nullable_col, Operator::Divide, lit(2), &schema)?,
+                options: SortOptions::default(),
+            },
+            PhysicalSortExpr {
+                expr: non_nullable_col,
+                options: SortOptions::default(),
+            },
+        ];
+        let physical_plan = Arc::new(SortExec::try_new(sort_exprs, sort_exec, None)?)
+            as Arc<dyn ExecutionPlan>;
+        let expected = vec![
+            "SortExec: [nullable_col@0 / 2 ASC,non_nullable_col@1 ASC]",
+            "  MemoryExec: partitions=0, partition_sizes=[]",
+        ];
+        assert_optimized(physical_plan, expected)
+    }
+
+    #[tokio::test]
+    async fn test_remove_unnecessary_sort_constant() -> Result<()> {
+        let schema = create_test_schema()?;
+        let source = Arc::new(MemoryExec::try_new(&[], schema.clone(), None)?)
+            as Arc<dyn ExecutionPlan>;
+        let nullable_col = col("nullable_col", schema.as_ref())?;
+        let non_nullable_col = col("non_nullable_col", schema.as_ref())?;
+        let sort_exprs = vec![PhysicalSortExpr {
+            expr: nullable_col.clone(),
+            options: SortOptions::default(),
+        }];
+        let sort_exec = Arc::new(SortExec::try_new(sort_exprs, source, None)?)
+            as Arc<dyn ExecutionPlan>;
+        // non_nullable_col is constant after the filter
+        let predicate = binary(non_nullable_col.clone(), Operator::Eq, lit(1), &schema)?;
+        let filter = Arc::new(FilterExec::try_new(predicate, sort_exec)?)
+            as Arc<dyn ExecutionPlan>;
+        let sort_exprs = vec![
+            PhysicalSortExpr {
+                expr: non_nullable_col,
+                options: SortOptions::default(),
+            },
+            PhysicalSortExpr {
+                expr: nullable_col,
+                options: SortOptions::default(),
+            },
+        ];
+        let physical_plan = Arc::new(SortExec::try_new(sort_exprs, filter, None)?)
+            as Arc<dyn ExecutionPlan>;
+        let expected = vec![
+            "FilterExec: non_nullable_col@1 = 1",
+            "  SortExec: [nullable_col@0 ASC]",
+            "    MemoryExec: partitions=0, partition_sizes=[]",
+        ];
+        assert_optimized(physical_plan, expected)
+    }
 }
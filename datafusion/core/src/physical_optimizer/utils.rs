@@ -23,9 +23,11 @@ use crate::config::ConfigOptions;
 use crate::error::Result;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::{with_new_children_if_necessary, ExecutionPlan};
+use arrow::compute::SortOptions;
+use arrow::datatypes::Schema;
 use datafusion_physical_expr::{
-    normalize_sort_expr_with_equivalence_properties, EquivalenceProperties,
-    PhysicalSortExpr,
+    expr_monotonicity, normalize_sort_expr_with_equivalence_properties,
+    EquivalenceProperties, Monotonicity, PhysicalSortExpr,
 };
 use std::sync::Arc;
 
@@ -66,39 +68,72 @@ pub fn ordering_satisfy<F: FnOnce() -> EquivalenceProperties>(
     }
 }
 
+/// Checks whether the `required` ordering is satisfied by data sorted on
+/// `provided`, taking into account:
+/// - the equivalent columns, e.g. `b` is sorted if `a` is sorted and `a = b`,
+/// - the constant expressions, which can be ignored in both orderings,
+/// - the monotonic expressions, e.g. `a + 1` is sorted if `a` is sorted.
 pub fn ordering_satisfy_concrete<F: FnOnce() -> EquivalenceProperties>(
     provided: &[PhysicalSortExpr],
     required: &[PhysicalSortExpr],
     equal_properties: F,
 ) -> bool {
-    if required.len() > provided.len() {
-        false
-    } else if required
-        .iter()
-        .zip(provided.iter())
-        .all(|(order1, order2)| order1.eq(order2))
-    {
-        true
-    } else if let eq_classes @ [_, ..] = equal_properties().classes() {
-        let normalized_required_exprs = required
+    if required.len() <= provided.len()
+        && required
             .iter()
-            .map(|e| {
-                normalize_sort_expr_with_equivalence_properties(e.clone(), eq_classes)
-            })
-            .collect::<Vec<_>>();
-        let normalized_provided_exprs = provided
+            .zip(provided.iter())
+            .all(|(order1, order2)| order1.eq(order2))
+    {
+        return true;
+    }
+
+    let eq_properties = equal_properties();
+    let normalize = |sort_exprs: &[PhysicalSortExpr]| {
+        sort_exprs
             .iter()
             .map(|e| {
-                normalize_sort_expr_with_equivalence_properties(e.clone(), eq_classes)
+                normalize_sort_expr_with_equivalence_properties(
+                    e.clone(),
+                    eq_properties.classes(),
+                )
             })
-            .collect::<Vec<_>>();
-        normalized_required_exprs
-            .iter()
-            .zip(normalized_provided_exprs.iter())
-            .all(|(order1, order2)| order1.eq(order2))
-    } else {
-        false
+            .filter(|e| !eq_properties.is_constant(&e.expr))
+            .collect::<Vec<_>>()
+    };
+    let normalized_required_exprs = normalize(required);
+    let normalized_provided_exprs = normalize(provided);
+    if normalized_required_exprs.len() > normalized_provided_exprs.len() {
+        return false;
     }
+
+    // A non strictly monotonic expression maps distinct values to the same
+    // value, whose ties are not sorted on the following expressions.
+    let schema = eq_properties.schema();
+    let last = normalized_required_exprs.len().saturating_sub(1);
+    normalized_required_exprs
+        .iter()
+        .zip(normalized_provided_exprs.iter())
+        .enumerate()
+        .all(|(idx, (order1, order2))| {
+            sort_expr_monotonicity(order1, order2, &schema)
+                .map(|monotonicity| monotonicity.strict || idx == last)
+                .unwrap_or(false)
+        })
+}
+
+/// Returns the monotonicity of `required` with respect to `provided`, if
+/// data sorted on `provided` is also sorted on `required`
+fn sort_expr_monotonicity(
+    required: &PhysicalSortExpr,
+    provided: &PhysicalSortExpr,
+    schema: &Schema,
+) -> Option<Monotonicity> {
+    let monotonicity = expr_monotonicity(&required.expr, &provided.expr, schema)?;
+    let options = SortOptions {
+        descending: provided.options.descending != monotonicity.descending,
+        nulls_first: provided.options.nulls_first,
+    };
+    (options == required.options).then_some(monotonicity)
 }
 
 /// Util function to add SortExec above child
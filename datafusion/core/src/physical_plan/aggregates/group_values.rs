@@ -0,0 +1,730 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Interning of the group keys of hash aggregations
+
+use std::ops::Range;
+
+use ahash::RandomState;
+use arrow::array::{
+    as_primitive_array, make_array, new_empty_array, Array, ArrayData, ArrayRef,
+    BooleanBufferBuilder,
+};
+use arrow::buffer::Buffer;
+use arrow::datatypes::*;
+use arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion_physical_expr::hash_utils::{create_hashes, create_row_hashes_v2};
+use hashbrown::raw::RawTable;
+use hashbrown::HashMap;
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::memory_pool::proxy::{RawTableAllocExt, VecAllocExt};
+use crate::scalar::ScalarValue;
+
+/// Assigns an index to each distinct group key seen by a hash aggregation.
+///
+/// Groups are numbered densely in the order they are first seen, so that a
+/// group index equal to the number of groups known before a call to
+/// [`intern`](Self::intern) denotes a new group.
+pub(crate) trait GroupValues: Send {
+    /// Writes the group index of each row of `cols`, the values of the
+    /// group columns, to `groups`, creating groups for unseen keys.
+    fn intern(&mut self, cols: &[ArrayRef], groups: &mut Vec<usize>) -> Result<()>;
+
+    /// Returns the number of groups
+    fn len(&self) -> usize;
+
+    /// Returns the memory used by the group keys and their lookup table, in
+    /// bytes
+    fn size(&self) -> usize;
+
+    /// Returns the keys of the groups in `range`, one array per group column
+    fn emit(&self, range: Range<usize>) -> Result<Vec<ArrayRef>>;
+}
+
+/// Creates the [`GroupValues`] for the group columns of `schema`.
+///
+/// Keys made of fixed width primitive columns small enough to be packed in
+/// a `u128` are compared as integers. Other keys are converted to the
+/// [row format](arrow::row), or kept as [`ScalarValue`]s for the types
+/// that the row format does not support.
+pub(crate) fn new_group_values(schema: &Schema) -> Result<Box<dyn GroupValues>> {
+    if let Some(group_values) = GroupValuesFixedWidth::try_new(schema) {
+        return Ok(Box::new(group_values));
+    }
+
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|f| SortField::new(f.data_type().clone()))
+        .collect();
+    match RowConverter::new(fields) {
+        Ok(converter) => Ok(Box::new(GroupValuesRows::new(converter))),
+        Err(_) => Ok(Box::new(GroupValuesScalar::new(schema))),
+    }
+}
+
+/// Keys of fixed width columns whose values, along with one null bit per
+/// column, fit in 128 bits.
+///
+/// Each key is packed into a single `u128`: the value bits of the columns
+/// are laid out one after the other from the least significant bit, followed
+/// by the null bits. Nulls have all their value bits unset, so that equal
+/// keys have equal packed forms.
+struct GroupValuesFixedWidth {
+    data_types: Vec<DataType>,
+    /// Offset of the value bits of each column
+    shifts: Vec<u32>,
+    /// Offset of the null bit of the first column
+    null_shift: u32,
+    /// Maps packed keys to their group index
+    map: HashMap<u128, usize, RandomState>,
+    /// Packed key of each group
+    keys: Vec<u128>,
+    /// Scratch space for the packed keys of a batch
+    batch_keys: Vec<u128>,
+}
+
+/// Returns the width in bits of the values of `data_type`, if it can be
+/// packed by [`GroupValuesFixedWidth`]
+fn fixed_width_bits(data_type: &DataType) -> Option<u32> {
+    match data_type {
+        DataType::Int8 | DataType::UInt8 => Some(8),
+        DataType::Int16 | DataType::UInt16 => Some(16),
+        DataType::Int32 | DataType::UInt32 | DataType::Float32 | DataType::Date32 => {
+            Some(32)
+        }
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Float64
+        | DataType::Date64
+        | DataType::Timestamp(_, _) => Some(64),
+        _ => None,
+    }
+}
+
+/// Calls the generic function `$fn` with the [`ArrowPrimitiveType`] of a
+/// data type accepted by [`fixed_width_bits`]
+macro_rules! downcast_fixed_width {
+    ($data_type:expr, $fn:ident, $($args:expr),*) => {
+        match $data_type {
+            DataType::Int8 => $fn::<Int8Type>($($args),*),
+            DataType::Int16 => $fn::<Int16Type>($($args),*),
+            DataType::Int32 => $fn::<Int32Type>($($args),*),
+            DataType::Int64 => $fn::<Int64Type>($($args),*),
+            DataType::UInt8 => $fn::<UInt8Type>($($args),*),
+            DataType::UInt16 => $fn::<UInt16Type>($($args),*),
+            DataType::UInt32 => $fn::<UInt32Type>($($args),*),
+            DataType::UInt64 => $fn::<UInt64Type>($($args),*),
+            DataType::Float32 => $fn::<Float32Type>($($args),*),
+            DataType::Float64 => $fn::<Float64Type>($($args),*),
+            DataType::Date32 => $fn::<Date32Type>($($args),*),
+            DataType::Date64 => $fn::<Date64Type>($($args),*),
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                $fn::<TimestampSecondType>($($args),*)
+            }
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                $fn::<TimestampMillisecondType>($($args),*)
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                $fn::<TimestampMicrosecondType>($($args),*)
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                $fn::<TimestampNanosecondType>($($args),*)
+            }
+            other => unreachable!("{other:?} is not a fixed width group key type"),
+        }
+    };
+}
+
+impl GroupValuesFixedWidth {
+    fn try_new(schema: &Schema) -> Option<Self> {
+        let data_types: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+
+        let mut shifts = Vec::with_capacity(data_types.len());
+        let mut null_shift = 0;
+        for data_type in &data_types {
+            shifts.push(null_shift);
+            null_shift += fixed_width_bits(data_type)?;
+        }
+        if null_shift as usize + data_types.len() > 128 {
+            return None;
+        }
+
+        Some(Self {
+            data_types,
+            shifts,
+            null_shift,
+            map: HashMap::with_hasher(RandomState::new()),
+            keys: vec![],
+            batch_keys: vec![],
+        })
+    }
+}
+
+impl GroupValues for GroupValuesFixedWidth {
+    fn intern(&mut self, cols: &[ArrayRef], groups: &mut Vec<usize>) -> Result<()> {
+        let num_rows = cols.first().map(|col| col.len()).unwrap_or_default();
+        self.batch_keys.clear();
+        self.batch_keys.resize(num_rows, 0);
+
+        for (i, (col, data_type)) in cols.iter().zip(&self.data_types).enumerate() {
+            if col.data_type() != data_type {
+                return Err(DataFusionError::Internal(format!(
+                    "Group column of type {:?} does not match group key type {:?}",
+                    col.data_type(),
+                    data_type
+                )));
+            }
+            let shift = self.shifts[i];
+            let null_shift = self.null_shift + i as u32;
+            let keys = &mut self.batch_keys;
+            downcast_fixed_width!(data_type, pack, col, shift, null_shift, keys);
+        }
+
+        let map = &mut self.map;
+        let keys = &mut self.keys;
+        groups.clear();
+        groups.extend(self.batch_keys.iter().map(|key| {
+            *map.entry(*key).or_insert_with(|| {
+                keys.push(*key);
+                keys.len() - 1
+            })
+        }));
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn size(&self) -> usize {
+        // one control byte per bucket
+        self.map.capacity() * (std::mem::size_of::<(u128, usize)>() + 1)
+            + (self.keys.capacity() + self.batch_keys.capacity())
+                * std::mem::size_of::<u128>()
+    }
+
+    fn emit(&self, range: Range<usize>) -> Result<Vec<ArrayRef>> {
+        let keys = &self.keys[range];
+        self.data_types
+            .iter()
+            .enumerate()
+            .map(|(i, data_type)| {
+                let shift = self.shifts[i];
+                let null_shift = self.null_shift + i as u32;
+                downcast_fixed_width!(
+                    data_type, unpack, keys, data_type, shift, null_shift
+                )
+            })
+            .collect()
+    }
+}
+
+/// Conversion of fixed width values from and to the zero-extended bits of
+/// their binary representation
+trait FixedWidthKey: Copy {
+    fn to_key(self) -> u128;
+    fn from_key(key: u128) -> Self;
+}
+
+macro_rules! fixed_width_key {
+    ($($native:ty => $unsigned:ty),*) => {
+        $(
+            impl FixedWidthKey for $native {
+                fn to_key(self) -> u128 {
+                    self as $unsigned as u128
+                }
+
+                fn from_key(key: u128) -> Self {
+                    key as $unsigned as $native
+                }
+            }
+        )*
+    };
+}
+
+fixed_width_key!(
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64
+);
+
+impl FixedWidthKey for f32 {
+    fn to_key(self) -> u128 {
+        self.to_bits() as u128
+    }
+
+    fn from_key(key: u128) -> Self {
+        f32::from_bits(key as u32)
+    }
+}
+
+impl FixedWidthKey for f64 {
+    fn to_key(self) -> u128 {
+        self.to_bits() as u128
+    }
+
+    fn from_key(key: u128) -> Self {
+        f64::from_bits(key as u64)
+    }
+}
+
+/// Ors the values of `array` shifted by `shift`, or its null bit at
+/// `null_shift` for null values, into `keys`
+fn pack<T>(array: &ArrayRef, shift: u32, null_shift: u32, keys: &mut [u128])
+where
+    T: ArrowPrimitiveType,
+    T::Native: FixedWidthKey,
+{
+    let array = as_primitive_array::<T>(array);
+    if array.null_count() == 0 {
+        for (key, value) in keys.iter_mut().zip(array.values().iter()) {
+            *key |= value.to_key() << shift;
+        }
+    } else {
+        for (i, key) in keys.iter_mut().enumerate() {
+            if array.is_null(i) {
+                *key |= 1 << null_shift;
+            } else {
+                *key |= array.value(i).to_key() << shift;
+            }
+        }
+    }
+}
+
+/// Extracts the column packed at `shift` and `null_shift` out of `keys`
+fn unpack<T>(
+    keys: &[u128],
+    data_type: &DataType,
+    shift: u32,
+    null_shift: u32,
+) -> Result<ArrayRef>
+where
+    T: ArrowPrimitiveType,
+    T::Native: FixedWidthKey,
+{
+    let values: Vec<T::Native> = keys
+        .iter()
+        .map(|key| T::Native::from_key(key >> shift))
+        .collect();
+
+    let nulls = keys
+        .iter()
+        .any(|key| key & (1 << null_shift) != 0)
+        .then(|| {
+            let mut nulls = BooleanBufferBuilder::new(keys.len());
+            for key in keys {
+                nulls.append(key & (1 << null_shift) == 0);
+            }
+            nulls.finish()
+        });
+
+    let data = ArrayData::builder(data_type.clone())
+        .len(keys.len())
+        .add_buffer(Buffer::from_slice_ref(&values))
+        .null_bit_buffer(nulls)
+        .build()?;
+    Ok(make_array(data))
+}
+
+/// Keys converted to the [row format](arrow::row), which compare as bytes
+struct GroupValuesRows {
+    converter: RowConverter,
+    random_state: RandomState,
+    /// Maps the hashes of the keys to their group index
+    map: RawTable<(u64, usize)>,
+    /// Memory used by `map` and `group_rows`
+    map_size: usize,
+    /// Key of each group
+    group_rows: Vec<OwnedRow>,
+    /// Scratch space for the hashes of the keys of a batch
+    hashes: Vec<u64>,
+}
+
+impl GroupValuesRows {
+    fn new(converter: RowConverter) -> Self {
+        Self {
+            converter,
+            random_state: Default::default(),
+            map: RawTable::with_capacity(0),
+            map_size: 0,
+            group_rows: vec![],
+            hashes: vec![],
+        }
+    }
+}
+
+impl GroupValues for GroupValuesRows {
+    fn intern(&mut self, cols: &[ArrayRef], groups: &mut Vec<usize>) -> Result<()> {
+        let rows = self.converter.convert_columns(cols)?;
+
+        self.hashes.clear();
+        self.hashes.resize(rows.num_rows(), 0);
+        create_row_hashes_v2(&rows, &self.random_state, &mut self.hashes)?;
+
+        groups.clear();
+        for (row, hash) in self.hashes.iter().enumerate() {
+            let group_rows = &mut self.group_rows;
+            let entry = self.map.get(*hash, |(_hash, group_idx)| {
+                rows.row(row) == group_rows[*group_idx].row()
+            });
+
+            let group_idx = match entry {
+                Some((_hash, group_idx)) => *group_idx,
+                None => {
+                    let group_idx = group_rows.len();
+                    let group_row = rows.row(row).owned();
+                    self.map_size += group_row.as_ref().len();
+                    group_rows.push_accounted(group_row, &mut self.map_size);
+                    // for hasher function, use precomputed hash value
+                    self.map.insert_accounted(
+                        (*hash, group_idx),
+                        |(hash, _group_idx)| *hash,
+                        &mut self.map_size,
+                    );
+                    group_idx
+                }
+            };
+            groups.push(group_idx);
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.group_rows.len()
+    }
+
+    fn size(&self) -> usize {
+        self.converter.size()
+            + self.map_size
+            + self.hashes.capacity() * std::mem::size_of::<u64>()
+    }
+
+    fn emit(&self, range: Range<usize>) -> Result<Vec<ArrayRef>> {
+        let rows = self.group_rows[range].iter().map(|row| row.row());
+        Ok(self.converter.convert_rows(rows)?)
+    }
+}
+
+/// Keys copied out of their arrays into [`ScalarValue`]s, for the group
+/// column types that are not supported by the row format
+struct GroupValuesScalar {
+    data_types: Vec<DataType>,
+    random_state: RandomState,
+    /// Maps the hashes of the keys to their group index
+    map: RawTable<(u64, usize)>,
+    /// Memory used by `map` and `group_values`
+    map_size: usize,
+    /// Key of each group, one value per group column
+    group_values: Vec<Box<[ScalarValue]>>,
+    /// Scratch space for the hashes of the keys of a batch
+    hashes: Vec<u64>,
+}
+
+impl GroupValuesScalar {
+    fn new(schema: &Schema) -> Self {
+        Self {
+            data_types: schema
+                .fields()
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect(),
+            random_state: Default::default(),
+            map: RawTable::with_capacity(0),
+            map_size: 0,
+            group_values: vec![],
+            hashes: vec![],
+        }
+    }
+}
+
+impl GroupValues for GroupValuesScalar {
+    fn intern(&mut self, cols: &[ArrayRef], groups: &mut Vec<usize>) -> Result<()> {
+        let num_rows = cols.first().map(|col| col.len()).unwrap_or_default();
+        self.hashes.clear();
+        self.hashes.resize(num_rows, 0);
+        create_hashes(cols, &self.random_state, &mut self.hashes)?;
+
+        groups.clear();
+        for (row, hash) in self.hashes.iter().enumerate() {
+            let group_values = &mut self.group_values;
+            let entry = self.map.get(*hash, |(_hash, group_idx)| {
+                cols.iter()
+                    .zip(group_values[*group_idx].iter())
+                    .all(|(array, scalar)| scalar.eq_array(array, row))
+            });
+
+            let group_idx = match entry {
+                Some((_hash, group_idx)) => *group_idx,
+                None => {
+                    let group_idx = group_values.len();
+                    let values = cols
+                        .iter()
+                        .map(|col| ScalarValue::try_from_array(col, row))
+                        .collect::<Result<Box<[_]>>>()?;
+                    self.map_size += values.iter().map(|sv| sv.size()).sum::<usize>();
+                    group_values.push_accounted(values, &mut self.map_size);
+                    // for hasher function, use precomputed hash value
+                    self.map.insert_accounted(
+                        (*hash, group_idx),
+                        |(hash, _group_idx)| *hash,
+                        &mut self.map_size,
+                    );
+                    group_idx
+                }
+            };
+            groups.push(group_idx);
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.group_values.len()
+    }
+
+    fn size(&self) -> usize {
+        self.map_size + self.hashes.capacity() * std::mem::size_of::<u64>()
+    }
+
+    fn emit(&self, range: Range<usize>) -> Result<Vec<ArrayRef>> {
+        let group_values = &self.group_values[range];
+        self.data_types
+            .iter()
+            .enumerate()
+            .map(|(i, data_type)| {
+                if group_values.is_empty() {
+                    return Ok(new_empty_array(data_type));
+                }
+                ScalarValue::iter_to_array(
+                    group_values.iter().map(|values| values[i].clone()),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        DictionaryArray, Float64Array, Int16Array, Int32Array, Int64Array, ListArray,
+        StringArray, StructArray, TimestampNanosecondArray, UInt8Array,
+    };
+    use std::sync::Arc;
+
+    fn schema(cols: &[ArrayRef]) -> Schema {
+        Schema::new(
+            cols.iter()
+                .enumerate()
+                .map(|(i, col)| {
+                    Field::new(&format!("c{i}"), col.data_type().clone(), true)
+                })
+                .collect(),
+        )
+    }
+
+    /// Interns `cols` twice, checking the groups of each row and that the
+    /// emitted keys are the distinct rows of `cols`
+    fn assert_interned(
+        mut group_values: Box<dyn GroupValues>,
+        cols: &[ArrayRef],
+        expected_groups: &[usize],
+    ) -> Result<()> {
+        let mut groups = vec![];
+        for _ in 0..2 {
+            group_values.intern(cols, &mut groups)?;
+            assert_eq!(groups, expected_groups);
+        }
+
+        let num_groups = expected_groups.iter().max().map_or(0, |max| max + 1);
+        assert_eq!(group_values.len(), num_groups);
+        assert!(group_values.size() > 0);
+
+        let first_rows: Vec<_> = (0..num_groups)
+            .map(|group| {
+                let row = expected_groups.iter().position(|g| *g == group).unwrap();
+                row as u32
+            })
+            .collect();
+        let indices = arrow::array::UInt32Array::from(first_rows);
+        let emitted = group_values.emit(0..num_groups)?;
+        for (emitted, col) in emitted.iter().zip(cols) {
+            let expected = arrow::compute::take(col.as_ref(), &indices, None)?;
+            assert_eq!(emitted, &expected);
+        }
+
+        let emitted = group_values.emit(1..1)?;
+        assert!(emitted.iter().all(|array| array.is_empty()));
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_width_single_column() -> Result<()> {
+        let cols: Vec<ArrayRef> = vec![Arc::new(Int64Array::from(vec![
+            Some(-1),
+            None,
+            Some(i64::MAX),
+            Some(-1),
+            None,
+            Some(0),
+        ]))];
+        let schema = schema(&cols);
+        assert!(GroupValuesFixedWidth::try_new(&schema).is_some());
+        assert_interned(new_group_values(&schema)?, &cols, &[0, 1, 2, 0, 1, 3])
+    }
+
+    #[test]
+    fn fixed_width_composite() -> Result<()> {
+        let cols: Vec<ArrayRef> = vec![
+            Arc::new(Int16Array::from(vec![
+                Some(1),
+                Some(1),
+                None,
+                Some(1),
+                None,
+            ])),
+            Arc::new(Float64Array::from(vec![
+                Some(0.5),
+                Some(-0.5),
+                Some(0.5),
+                Some(0.5),
+                Some(0.5),
+            ])),
+            Arc::new(UInt8Array::from(vec![
+                Some(7),
+                Some(7),
+                Some(7),
+                Some(7),
+                None,
+            ])),
+        ];
+        let schema = schema(&cols);
+        assert!(GroupValuesFixedWidth::try_new(&schema).is_some());
+        assert_interned(new_group_values(&schema)?, &cols, &[0, 1, 2, 0, 3])
+    }
+
+    #[test]
+    fn fixed_width_preserves_timezone() -> Result<()> {
+        let timestamps = TimestampNanosecondArray::from(vec![Some(1), None, Some(1)])
+            .with_timezone("+01:00".to_string());
+        let cols: Vec<ArrayRef> = vec![Arc::new(timestamps)];
+        let schema = schema(&cols);
+        assert_interned(new_group_values(&schema)?, &cols, &[0, 1, 0])
+    }
+
+    #[test]
+    fn too_wide_for_fixed_width() -> Result<()> {
+        // two 64 bit values and their null bits do not fit in 128 bits
+        let cols: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![1, 2, 1])),
+            Arc::new(Int64Array::from(vec![3, 3, 3])),
+        ];
+        let schema = schema(&cols);
+        assert!(GroupValuesFixedWidth::try_new(&schema).is_none());
+        assert_interned(new_group_values(&schema)?, &cols, &[0, 1, 0])
+    }
+
+    #[test]
+    fn rows() -> Result<()> {
+        let cols: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![
+                Some("x"),
+                Some("x"),
+                Some("x"),
+                Some("x"),
+                None,
+            ])),
+            Arc::new(Int32Array::from(vec![
+                Some(1),
+                Some(2),
+                Some(1),
+                None,
+                Some(1),
+            ])),
+        ];
+        let schema = schema(&cols);
+        assert!(GroupValuesFixedWidth::try_new(&schema).is_none());
+        assert_interned(new_group_values(&schema)?, &cols, &[0, 1, 0, 2, 3])
+    }
+
+    #[test]
+    fn nested() -> Result<()> {
+        let structs = StructArray::from((
+            vec![
+                (
+                    Field::new("a", DataType::Int32, true),
+                    Arc::new(Int32Array::from(vec![
+                        Some(1),
+                        Some(1),
+                        None,
+                        Some(1),
+                        Some(1),
+                    ])) as ArrayRef,
+                ),
+                (
+                    Field::new("b", DataType::Utf8, true),
+                    Arc::new(StringArray::from(vec!["x", "y", "x", "x", "x"]))
+                        as ArrayRef,
+                ),
+            ],
+            Buffer::from([0b00010111u8]),
+        ));
+        let lists = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![]),
+            Some(vec![Some(1), Some(2)]),
+            None,
+        ]);
+        let structs: ArrayRef = Arc::new(structs);
+        let lists: ArrayRef = Arc::new(lists);
+
+        let cols = vec![structs.clone()];
+        assert_interned(new_group_values(&schema(&cols))?, &cols, &[0, 1, 2, 3, 0])?;
+
+        let cols = vec![lists.clone()];
+        assert_interned(new_group_values(&schema(&cols))?, &cols, &[0, 0, 1, 0, 2])?;
+
+        let cols = vec![structs, lists];
+        assert_interned(new_group_values(&schema(&cols))?, &cols, &[0, 1, 2, 3, 4])
+    }
+
+    #[test]
+    fn dictionaries() -> Result<()> {
+        let cols: Vec<ArrayRef> = vec![Arc::new(
+            vec![Some("x"), None, Some("y"), Some("x"), None]
+                .into_iter()
+                .collect::<DictionaryArray<Int8Type>>(),
+        )];
+        let schema = schema(&cols);
+        assert_interned(new_group_values(&schema)?, &cols, &[0, 1, 2, 0, 1])
+    }
+
+    #[test]
+    fn scalars() -> Result<()> {
+        let cols: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec!["x", "y", "x"])),
+            Arc::new(Int64Array::from(vec![None, Some(1), None])),
+        ];
+        let schema = schema(&cols);
+        assert_interned(Box::new(GroupValuesScalar::new(&schema)), &cols, &[0, 1, 0])
+    }
+}
@@ -0,0 +1,286 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Hash aggregation with columnar aggregation states
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::vec;
+
+use arrow::compute::cast;
+use arrow::datatypes::SchemaRef;
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::record_batch::RecordBatch;
+use datafusion_physical_expr::aggregate::groups_accumulator::GroupsAccumulator;
+use futures::stream::BoxStream;
+use futures::stream::{Stream, StreamExt};
+
+use crate::error::Result;
+use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use crate::physical_plan::aggregates::group_values::{new_group_values, GroupValues};
+use crate::physical_plan::aggregates::{
+    evaluate_group_by, evaluate_many, group_schema, AggregateMode, PhysicalGroupBy,
+};
+use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
+use crate::physical_plan::{aggregates, AggregateExpr, PhysicalExpr};
+use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
+
+/// Grouping aggregate with columnar aggregation states.
+///
+/// Each group key is assigned a group index, and each aggregate has a single
+/// [`GroupsAccumulator`] that stores the states of all the groups, indexed by
+/// group index. For each input batch:
+///
+/// 1. The group keys of the batch are interned, giving the group index of each row.
+/// 2. Each accumulator is updated with the whole input of the aggregate and the group indices.
+///
+/// At the end of the input, the states of all the groups are output at once,
+/// in batches of at most `batch_size` rows.
+pub(crate) struct GroupedHashAggregateStreamV3 {
+    stream: BoxStream<'static, ArrowResult<RecordBatch>>,
+    schema: SchemaRef,
+}
+
+/// Actual implementation of [`GroupedHashAggregateStreamV3`].
+///
+/// This is wrapped into yet another struct because we need to interact with the async memory management subsystem
+/// during poll. To have as little code "weirdness" as possible, we chose to just use [`BoxStream`] together with
+/// [`futures::stream::unfold`]. The latter requires a state object, which is [`GroupedHashAggregateStreamV3Inner`].
+struct GroupedHashAggregateStreamV3Inner {
+    schema: SchemaRef,
+    input: SendableRecordBatchStream,
+    mode: AggregateMode,
+    aggregate_expressions: Vec<Vec<Arc<dyn PhysicalExpr>>>,
+    group_by: PhysicalGroupBy,
+
+    /// The group values, which map to a group index
+    group_values: Box<dyn GroupValues>,
+    /// Accumulator of the states of all groups, one for each aggregate
+    accumulators: Vec<Box<dyn GroupsAccumulator>>,
+    /// scratch space for the group index of each row of a batch
+    groups: Vec<usize>,
+    reservation: MemoryReservation,
+
+    baseline_metrics: BaselineMetrics,
+    /// size to be used for resulting RecordBatches
+    batch_size: usize,
+    /// The output batches, once the input is exhausted
+    output: Option<vec::IntoIter<RecordBatch>>,
+}
+
+impl GroupedHashAggregateStreamV3 {
+    /// Create a new GroupedHashAggregateStreamV3
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mode: AggregateMode,
+        schema: SchemaRef,
+        group_by: PhysicalGroupBy,
+        aggr_expr: Vec<Arc<dyn AggregateExpr>>,
+        input: SendableRecordBatchStream,
+        baseline_metrics: BaselineMetrics,
+        batch_size: usize,
+        context: Arc<TaskContext>,
+        partition: usize,
+    ) -> Result<Self> {
+        let timer = baseline_metrics.elapsed_compute().timer();
+
+        // The expressions to evaluate the batch, one vec of expressions per aggregation.
+        // Assume create_schema() always put group columns in front of aggr columns, we set
+        // col_idx_base to group expression count.
+        let aggregate_expressions =
+            aggregates::aggregate_expressions(&aggr_expr, &mode, group_by.expr.len())?;
+
+        let accumulators = aggregates::create_groups_accumulators(&aggr_expr)?;
+        let group_values = new_group_values(&group_schema(&schema, group_by.expr.len()))?;
+
+        let reservation =
+            MemoryConsumer::new(format!("GroupedHashAggregateStreamV3[{partition}]"))
+                .register(context.memory_pool());
+
+        timer.done();
+
+        let inner = GroupedHashAggregateStreamV3Inner {
+            schema: Arc::clone(&schema),
+            input,
+            mode,
+            aggregate_expressions,
+            group_by,
+            group_values,
+            accumulators,
+            groups: vec![],
+            reservation,
+            baseline_metrics,
+            batch_size,
+            output: None,
+        };
+
+        let stream = futures::stream::unfold(inner, |mut this| async move {
+            let elapsed_compute = this.baseline_metrics.elapsed_compute().clone();
+
+            loop {
+                if let Some(output) = this.output.as_mut() {
+                    let batch = output.next()?;
+                    return Some((Ok(batch.record_output(&this.baseline_metrics)), this));
+                }
+
+                let result = match this.input.next().await {
+                    Some(Ok(batch)) => {
+                        let timer = elapsed_compute.timer();
+                        let result = this.group_aggregate_batch(batch);
+                        timer.done();
+
+                        // allocate memory
+                        // This happens AFTER we actually used the memory, but simplifies the whole accounting and we are OK with
+                        // overshooting a bit. Also this means we either store the whole record batch or not.
+                        match result
+                            .and_then(|allocated| this.reservation.try_grow(allocated))
+                        {
+                            Ok(_) => continue,
+                            Err(e) => Err(ArrowError::ExternalError(Box::new(e))),
+                        }
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => {
+                        let timer = elapsed_compute.timer();
+                        let result = this.create_output_batches();
+                        timer.done();
+
+                        match result {
+                            Ok(output) => {
+                                this.output = Some(output.into_iter());
+                                continue;
+                            }
+                            Err(e) => Err(ArrowError::ExternalError(Box::new(e))),
+                        }
+                    }
+                };
+
+                // no more output after an error
+                this.output = Some(vec![].into_iter());
+                return Some((result, this));
+            }
+        });
+
+        // seems like some consumers call this stream even after it returned `None`, so let's fuse the stream.
+        let stream = stream.fuse();
+        let stream = Box::pin(stream);
+
+        Ok(Self { schema, stream })
+    }
+}
+
+impl Stream for GroupedHashAggregateStreamV3 {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        this.stream.poll_next_unpin(cx)
+    }
+}
+
+impl RecordBatchStream for GroupedHashAggregateStreamV3 {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl GroupedHashAggregateStreamV3Inner {
+    /// Perform group-by aggregation for the given [`RecordBatch`].
+    ///
+    /// If successful, this returns the additional number of bytes that were allocated during this process.
+    fn group_aggregate_batch(&mut self, batch: RecordBatch) -> Result<usize> {
+        // evaluate the grouping expressions
+        let group_by_values = evaluate_group_by(&self.group_by, &batch)?;
+
+        // evaluate the aggregation expressions.
+        let aggr_input_values = evaluate_many(&self.aggregate_expressions, &batch)?;
+
+        let size_pre = self.size();
+
+        for grouping_set_values in group_by_values {
+            // calculate the group index of each row
+            self.group_values
+                .intern(&grouping_set_values, &mut self.groups)?;
+            let total_num_groups = self.group_values.len();
+
+            // update all the groups of each accumulator at once
+            for (accumulator, values) in
+                self.accumulators.iter_mut().zip(aggr_input_values.iter())
+            {
+                match self.mode {
+                    AggregateMode::Partial => {
+                        accumulator.update_batch(values, &self.groups, total_num_groups)
+                    }
+                    AggregateMode::FinalPartitioned | AggregateMode::Final => {
+                        // note: the aggregation here is over states, not values, thus the merge
+                        accumulator.merge_batch(values, &self.groups, total_num_groups)
+                    }
+                }?;
+            }
+        }
+
+        Ok(self.size().saturating_sub(size_pre))
+    }
+
+    /// Returns the memory used by the group values and the states
+    fn size(&self) -> usize {
+        self.group_values.size()
+            + self.groups.capacity() * std::mem::size_of::<usize>()
+            + self
+                .accumulators
+                .iter()
+                .map(|accumulator| accumulator.size())
+                .sum::<usize>()
+    }
+
+    /// Create the RecordBatches with all group keys and accumulator' states or values.
+    fn create_output_batches(&mut self) -> Result<Vec<RecordBatch>> {
+        let num_groups = self.group_values.len();
+        if num_groups == 0 {
+            return Ok(vec![RecordBatch::new_empty(self.schema.clone())]);
+        }
+
+        let mut columns = self.group_values.emit(0..num_groups)?;
+        for accumulator in self.accumulators.iter_mut() {
+            match self.mode {
+                AggregateMode::Partial => columns.extend(accumulator.state()?),
+                AggregateMode::Final | AggregateMode::FinalPartitioned => {
+                    columns.push(accumulator.evaluate()?)
+                }
+            }
+        }
+
+        // cast output if needed (e.g. for types like Dictionary where
+        // the intermediate GroupByScalar type was not the same as the
+        // output
+        let columns = columns
+            .iter()
+            .zip(self.schema.fields().iter())
+            .map(|(col, desired_field)| cast(col, desired_field.data_type()))
+            .collect::<ArrowResult<Vec<_>>>()?;
+
+        let batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+        Ok((0..num_groups)
+            .step_by(self.batch_size)
+            .map(|offset| batch.slice(offset, self.batch_size.min(num_groups - offset)))
+            .collect())
+    }
+}
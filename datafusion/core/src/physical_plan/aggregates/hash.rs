@@ -22,18 +22,18 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::vec;
 
-use ahash::RandomState;
 use datafusion_expr::Accumulator;
 use futures::stream::BoxStream;
 use futures::stream::{Stream, StreamExt};
 
 use crate::error::Result;
 use crate::execution::context::TaskContext;
-use crate::execution::memory_pool::proxy::{RawTableAllocExt, VecAllocExt};
+use crate::execution::memory_pool::proxy::VecAllocExt;
+use crate::physical_plan::aggregates::group_values::{new_group_values, GroupValues};
 use crate::physical_plan::aggregates::{
-    evaluate_group_by, evaluate_many, AccumulatorItem, AggregateMode, PhysicalGroupBy,
+    evaluate_group_by, evaluate_many, group_schema, AccumulatorItem, AggregateMode,
+    PhysicalGroupBy,
 };
-use crate::physical_plan::hash_utils::create_hashes;
 use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
 use crate::physical_plan::{aggregates, AggregateExpr, PhysicalExpr};
 use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
@@ -49,7 +49,6 @@ use arrow::{
     datatypes::{Schema, SchemaRef},
     record_batch::RecordBatch,
 };
-use hashbrown::raw::RawTable;
 
 /*
 The architecture is the following:
@@ -97,7 +96,6 @@ struct GroupedHashAggregateStreamInner {
     group_by: PhysicalGroupBy,
 
     baseline_metrics: BaselineMetrics,
-    random_state: RandomState,
     finished: bool,
 }
 
@@ -122,6 +120,8 @@ impl GroupedHashAggregateStream {
         let aggregate_expressions =
             aggregates::aggregate_expressions(&aggr_expr, &mode, group_by.expr.len())?;
 
+        let group_values = new_group_values(&group_schema(&schema, group_by.expr.len()))?;
+
         timer.done();
 
         let reservation =
@@ -138,10 +138,10 @@ impl GroupedHashAggregateStream {
             aggregate_expressions,
             accumulators: Some(Accumulators {
                 reservation,
-                map: RawTable::with_capacity(0),
+                group_values,
                 group_states: Vec::with_capacity(0),
+                groups: vec![],
             }),
-            random_state: Default::default(),
             finished: false,
         };
 
@@ -160,7 +160,6 @@ impl GroupedHashAggregateStream {
                             this.accumulators.as_mut().expect("not yet finished");
                         let result = group_aggregate_batch(
                             &this.mode,
-                            &this.random_state,
                             &this.group_by,
                             &this.aggr_expr,
                             batch,
@@ -188,7 +187,6 @@ impl GroupedHashAggregateStream {
                             &this.mode,
                             std::mem::take(&mut this.accumulators)
                                 .expect("not yet finished"),
-                            this.group_by.expr.len(),
                             &this.schema,
                         )
                         .record_output(&this.baseline_metrics);
@@ -236,7 +234,6 @@ impl RecordBatchStream for GroupedHashAggregateStream {
 /// TODO: Make this a member function of [`GroupedHashAggregateStream`]
 fn group_aggregate_batch(
     mode: &AggregateMode,
-    random_state: &RandomState,
     group_by: &PhysicalGroupBy,
     aggr_expr: &[Arc<dyn AggregateExpr>],
     batch: RecordBatch,
@@ -262,33 +259,25 @@ fn group_aggregate_batch(
         // track which entries in `accumulators` have rows in this batch to aggregate
         let mut groups_with_rows = vec![];
 
-        // 1.1 Calculate the group keys for the group values
-        let mut batch_hashes = vec![0; batch.num_rows()];
-        create_hashes(&grouping_set_values, random_state, &mut batch_hashes)?;
-
-        for (row, hash) in batch_hashes.into_iter().enumerate() {
-            let Accumulators {
-                map, group_states, ..
-            } = accumulators;
-
-            let entry = map.get_mut(hash, |(_hash, group_idx)| {
-                // verify that a group that we are inserting with hash is
-                // actually the same key value as the group in
-                // existing_idx  (aka group_values @ row)
-                let group_state = &group_states[*group_idx];
-                grouping_set_values
-                    .iter()
-                    .zip(group_state.group_by_values.iter())
-                    .all(|(array, scalar)| scalar.eq_array(array, row))
-            });
+        let Accumulators {
+            group_values,
+            group_states,
+            groups,
+            ..
+        } = accumulators;
 
-            match entry {
+        // 1.1 Calculate the group index of each row
+        let group_values_size_pre = group_values.size();
+        group_values.intern(&grouping_set_values, groups)?;
+        allocated += group_values.size().saturating_sub(group_values_size_pre);
+
+        for (row, &group_idx) in groups.iter().enumerate() {
+            match group_states.get_mut(group_idx) {
                 // Existing entry for this group value
-                Some((_hash, group_idx)) => {
-                    let group_state = &mut group_states[*group_idx];
+                Some(group_state) => {
                     // 1.3
                     if group_state.indices.is_empty() {
-                        groups_with_rows.push(*group_idx);
+                        groups_with_rows.push(group_idx);
                     };
                     group_state
                         .indices
@@ -298,27 +287,15 @@ fn group_aggregate_batch(
                 None => {
                     let accumulator_set = aggregates::create_accumulators(aggr_expr)?;
 
-                    // Copy group values out of arrays into `ScalarValue`s
-                    let group_by_values = grouping_set_values
-                        .iter()
-                        .map(|col| ScalarValue::try_from_array(col, row))
-                        .collect::<Result<Vec<_>>>()?;
-
-                    // Add new entry to group_states and save newly created index
+                    // Add new entry to group_states
                     let group_state = GroupState {
-                        group_by_values: group_by_values.into_boxed_slice(),
                         accumulator_set,
                         indices: vec![row as u32], // 1.3
                     };
                     // NOTE: do NOT include the `GroupState` struct size in here because this is captured by
                     // `group_states` (see allocation down below)
-                    allocated += group_state
-                        .group_by_values
-                        .iter()
-                        .map(|sv| sv.size())
-                        .sum::<usize>()
-                        + (std::mem::size_of::<Box<dyn Accumulator>>()
-                            * group_state.accumulator_set.capacity())
+                    allocated += (std::mem::size_of::<Box<dyn Accumulator>>()
+                        * group_state.accumulator_set.capacity())
                         + group_state
                             .accumulator_set
                             .iter()
@@ -326,18 +303,10 @@ fn group_aggregate_batch(
                             .sum::<usize>()
                         + (std::mem::size_of::<u32>() * group_state.indices.capacity());
 
-                    let group_idx = group_states.len();
                     group_states.push_accounted(group_state, &mut allocated);
                     groups_with_rows.push(group_idx);
-
-                    // for hasher function, use precomputed hash value
-                    map.insert_accounted(
-                        (hash, group_idx),
-                        |(hash, _group_idx)| *hash,
-                        &mut allocated,
-                    );
                 }
-            };
+            }
         }
 
         // Collect all indices + offsets based on keys in this vec
@@ -425,9 +394,6 @@ fn group_aggregate_batch(
 /// The state that is built for each output group.
 #[derive(Debug)]
 struct GroupState {
-    /// The actual group by values, one for each group column
-    group_by_values: Box<[ScalarValue]>,
-
     // Accumulator state, one for each aggregate
     accumulator_set: Vec<AccumulatorItem>,
 
@@ -440,25 +406,20 @@ struct GroupState {
 struct Accumulators {
     reservation: MemoryReservation,
 
-    /// Logically maps group values to an index in `group_states`
-    ///
-    /// Uses the raw API of hashbrown to avoid actually storing the
-    /// keys in the table
-    ///
-    /// keys: u64 hashes of the GroupValue
-    /// values: (hash, index into `group_states`)
-    map: RawTable<(u64, usize)>,
+    /// The group values, which map to an index in `group_states`
+    group_values: Box<dyn GroupValues>,
 
     /// State for each group
     group_states: Vec<GroupState>,
+
+    /// scratch space for the group index of each row of a batch
+    groups: Vec<usize>,
 }
 
 impl std::fmt::Debug for Accumulators {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        // hashes are not store inline, so could only get values
-        let map_string = "RawTable";
         f.debug_struct("Accumulators")
-            .field("map", &map_string)
+            .field("num_groups", &self.group_values.len())
             .field("group_states", &self.group_states)
             .finish()
     }
@@ -473,7 +434,6 @@ impl std::fmt::Debug for Accumulators {
 fn create_batch_from_map(
     mode: &AggregateMode,
     accumulators: Accumulators,
-    num_group_expr: usize,
     output_schema: &Schema,
 ) -> ArrowResult<RecordBatch> {
     if accumulators.group_states.is_empty() {
@@ -495,29 +455,17 @@ fn create_batch_from_map(
         }
     }
 
-    // make group states mutable
-    let (mut group_by_values_vec, mut accumulator_set_vec): (Vec<_>, Vec<_>) =
-        accumulators
-            .group_states
-            .into_iter()
-            .map(|group_state| {
-                (
-                    VecDeque::from(group_state.group_by_values.to_vec()),
-                    VecDeque::from(group_state.accumulator_set),
-                )
-            })
-            .unzip();
-
     // First, output all group by exprs
-    let mut columns = (0..num_group_expr)
-        .map(|_| {
-            ScalarValue::iter_to_array(
-                group_by_values_vec
-                    .iter_mut()
-                    .map(|x| x.pop_front().expect("invalid group_by_values")),
-            )
-        })
-        .collect::<Result<Vec<_>>>()?;
+    let mut columns = accumulators
+        .group_values
+        .emit(0..accumulators.group_values.len())?;
+
+    // make group states mutable
+    let mut accumulator_set_vec: Vec<_> = accumulators
+        .group_states
+        .into_iter()
+        .map(|group_state| VecDeque::from(group_state.accumulator_set))
+        .collect();
 
     // next, output aggregates: either intermediate state or final output
     for (x, &state_len) in acc_data_types.iter().enumerate() {
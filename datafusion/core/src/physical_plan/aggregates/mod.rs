@@ -24,7 +24,7 @@ use crate::physical_plan::metrics::{
     BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet,
 };
 use crate::physical_plan::{
-    DisplayFormatType, Distribution, ExecutionPlan, Partitioning,
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning, Precision,
     SendableRecordBatchStream, Statistics,
 };
 use arrow::array::ArrayRef;
@@ -34,20 +34,24 @@ use datafusion_common::{DataFusionError, Result};
 use datafusion_expr::Accumulator;
 use datafusion_physical_expr::expressions::Column;
 use datafusion_physical_expr::{
-    expressions, AggregateExpr, PhysicalExpr, PhysicalSortExpr,
+    evaluation_error_context, expressions, AggregateExpr, PhysicalExpr, PhysicalSortExpr,
 };
 use std::any::Any;
 use std::collections::HashMap;
 
 use std::sync::Arc;
 
+mod group_values;
+mod groups_hash;
 mod hash;
 mod no_grouping;
 mod row_hash;
 
+use crate::physical_plan::aggregates::groups_hash::GroupedHashAggregateStreamV3;
 use crate::physical_plan::aggregates::row_hash::GroupedHashAggregateStreamV2;
 use crate::physical_plan::EquivalenceProperties;
 pub use datafusion_expr::AggregateFunction;
+use datafusion_physical_expr::aggregate::groups_accumulator::GroupsAccumulator;
 use datafusion_physical_expr::aggregate::row_accumulator::RowAccumulator;
 use datafusion_physical_expr::equivalence::project_equivalence_properties;
 pub use datafusion_physical_expr::expressions::create_aggregate_expr;
@@ -151,6 +155,7 @@ impl PhysicalGroupBy {
 
 enum StreamType {
     AggregateStream(AggregateStream),
+    GroupedHashAggregateStreamV3(GroupedHashAggregateStreamV3),
     GroupedHashAggregateStreamV2(GroupedHashAggregateStreamV2),
     GroupedHashAggregateStream(GroupedHashAggregateStream),
 }
@@ -159,6 +164,7 @@ impl From<StreamType> for SendableRecordBatchStream {
     fn from(stream: StreamType) -> Self {
         match stream {
             StreamType::AggregateStream(stream) => Box::pin(stream),
+            StreamType::GroupedHashAggregateStreamV3(stream) => Box::pin(stream),
             StreamType::GroupedHashAggregateStreamV2(stream) => Box::pin(stream),
             StreamType::GroupedHashAggregateStream(stream) => Box::pin(stream),
         }
@@ -271,6 +277,10 @@ impl AggregateExec {
         self.input_schema.clone()
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        groups_accumulator_supported(&self.aggr_expr)
+    }
+
     fn row_aggregate_supported(&self) -> bool {
         accumulator_v2_supported(&self.aggr_expr)
     }
@@ -295,6 +305,26 @@ impl AggregateExec {
                 context,
                 partition,
             )?))
+        } else if context
+            .session_config()
+            .config_options()
+            .execution
+            .groups_accumulator
+            && self.groups_accumulator_supported()
+        {
+            Ok(StreamType::GroupedHashAggregateStreamV3(
+                GroupedHashAggregateStreamV3::new(
+                    self.mode,
+                    self.schema.clone(),
+                    self.group_by.clone(),
+                    self.aggr_expr.clone(),
+                    input,
+                    baseline_metrics,
+                    batch_size,
+                    context,
+                    partition,
+                )?,
+            ))
         } else if self.row_aggregate_supported() {
             Ok(StreamType::GroupedHashAggregateStreamV2(
                 GroupedHashAggregateStreamV2::new(
@@ -505,17 +535,17 @@ impl ExecutionPlan for AggregateExec {
         // - case where we group by on a column for which with have the `distinct` stat
         // TODO stats: aggr expression:
         // - aggregations somtimes also preserve invariants such as min, max...
-        match self.mode {
+        let num_rows = match self.mode {
             AggregateMode::Final | AggregateMode::FinalPartitioned
                 if self.group_by.expr.is_empty() =>
             {
-                Statistics {
-                    num_rows: Some(1),
-                    is_exact: true,
-                    ..Default::default()
-                }
+                Precision::Exact(1)
             }
-            _ => Statistics::default(),
+            _ => Precision::Absent,
+        };
+        Statistics {
+            num_rows,
+            ..Statistics::new_unknown(&self.schema)
         }
     }
 }
@@ -620,6 +650,21 @@ fn create_accumulators(
         .collect::<datafusion_common::Result<Vec<_>>>()
 }
 
+fn groups_accumulator_supported(aggr_expr: &[Arc<dyn AggregateExpr>]) -> bool {
+    aggr_expr
+        .iter()
+        .all(|expr| expr.groups_accumulator_supported())
+}
+
+fn create_groups_accumulators(
+    aggr_expr: &[Arc<dyn AggregateExpr>],
+) -> datafusion_common::Result<Vec<Box<dyn GroupsAccumulator>>> {
+    aggr_expr
+        .iter()
+        .map(|expr| expr.create_groups_accumulator())
+        .collect::<datafusion_common::Result<Vec<_>>>()
+}
+
 fn accumulator_v2_supported(aggr_expr: &[Arc<dyn AggregateExpr>]) -> bool {
     aggr_expr
         .iter()
@@ -676,7 +721,10 @@ fn evaluate(
     batch: &RecordBatch,
 ) -> Result<Vec<ArrayRef>> {
     expr.iter()
-        .map(|expr| expr.evaluate(batch))
+        .map(|expr| {
+            expr.evaluate(batch)
+                .map_err(|e| evaluation_error_context(expr.as_ref(), batch, e))
+        })
         .map(|r| r.map(|v| v.into_array(batch.num_rows())))
         .collect::<Result<Vec<_>>>()
 }
@@ -699,7 +747,9 @@ fn evaluate_group_by(
         .expr
         .iter()
         .map(|(expr, _)| {
-            let value = expr.evaluate(batch)?;
+            let value = expr
+                .evaluate(batch)
+                .map_err(|e| evaluation_error_context(expr.as_ref(), batch, e))?;
             Ok(value.into_array(batch.num_rows()))
         })
         .collect::<Result<Vec<_>>>()?;
@@ -1133,14 +1183,9 @@ mod tests {
             Arc::new(TestYieldingExec { yield_first: true });
         let input_schema = input.schema();
 
-        let session_ctx = SessionContext::with_config_rt(
-            SessionConfig::default(),
-            Arc::new(
-                RuntimeEnv::new(RuntimeConfig::default().with_memory_limit(1, 1.0))
-                    .unwrap(),
-            ),
+        let runtime = Arc::new(
+            RuntimeEnv::new(RuntimeConfig::default().with_memory_limit(1, 1.0)).unwrap(),
         );
-        let task_ctx = session_ctx.task_ctx();
 
         let groups_none = PhysicalGroupBy::default();
         let groups_some = PhysicalGroupBy {
@@ -1164,18 +1209,29 @@ mod tests {
                 DataType::UInt32,
             ))];
 
-        // use fast-path in `row_hash.rs`.
+        // use fast-path in `row_hash.rs`, or in `groups_hash.rs` if groups
+        // accumulators are enabled
         let aggregates_v2: Vec<Arc<dyn AggregateExpr>> = vec![Arc::new(Avg::new(
             col("b", &input_schema)?,
             "AVG(b)".to_string(),
             DataType::Float64,
         ))];
 
-        for (version, groups, aggregates) in [
-            (0, groups_none, aggregates_v0),
-            (1, groups_some.clone(), aggregates_v1),
-            (2, groups_some, aggregates_v2),
+        for (version, groups, aggregates, groups_accumulator) in [
+            (0, groups_none, aggregates_v0, true),
+            (1, groups_some.clone(), aggregates_v1, true),
+            (2, groups_some.clone(), aggregates_v2.clone(), false),
+            (3, groups_some, aggregates_v2, true),
         ] {
+            let session_ctx = SessionContext::with_config_rt(
+                SessionConfig::default().set_bool(
+                    "datafusion.execution.groups_accumulator",
+                    groups_accumulator,
+                ),
+                runtime.clone(),
+            );
+            let task_ctx = session_ctx.task_ctx();
+
             let partial_aggregate = Arc::new(AggregateExec::try_new(
                 AggregateMode::Partial,
                 groups,
@@ -1200,6 +1256,12 @@ mod tests {
                         StreamType::GroupedHashAggregateStreamV2(_)
                     ));
                 }
+                3 => {
+                    assert!(matches!(
+                        stream,
+                        StreamType::GroupedHashAggregateStreamV3(_)
+                    ));
+                }
                 _ => panic!("Unknown version: {version}"),
             }
 
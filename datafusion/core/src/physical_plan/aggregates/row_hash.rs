@@ -21,15 +21,13 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::vec;
 
-use ahash::RandomState;
-use arrow::row::{OwnedRow, RowConverter, SortField};
-use datafusion_physical_expr::hash_utils::create_row_hashes_v2;
 use futures::stream::BoxStream;
 use futures::stream::{Stream, StreamExt};
 
 use crate::error::Result;
 use crate::execution::context::TaskContext;
-use crate::execution::memory_pool::proxy::{RawTableAllocExt, VecAllocExt};
+use crate::execution::memory_pool::proxy::VecAllocExt;
+use crate::physical_plan::aggregates::group_values::{new_group_values, GroupValues};
 use crate::physical_plan::aggregates::{
     evaluate_group_by, evaluate_many, group_schema, AccumulatorItemV2, AggregateMode,
     PhysicalGroupBy,
@@ -52,7 +50,6 @@ use datafusion_row::accessor::RowAccessor;
 use datafusion_row::layout::RowLayout;
 use datafusion_row::reader::{read_row, RowReader};
 use datafusion_row::{MutableRecordBatch, RowType};
-use hashbrown::raw::RawTable;
 
 /// Grouping aggregate with row-format aggregation states inside.
 ///
@@ -90,12 +87,10 @@ struct GroupedHashAggregateStreamV2Inner {
     group_by: PhysicalGroupBy,
     accumulators: Vec<AccumulatorItemV2>,
 
-    row_converter: RowConverter,
     aggr_schema: SchemaRef,
     aggr_layout: Arc<RowLayout>,
 
     baseline_metrics: BaselineMetrics,
-    random_state: RandomState,
     /// size to be used for resulting RecordBatches
     batch_size: usize,
     /// if the result is chunked into batches,
@@ -135,14 +130,7 @@ impl GroupedHashAggregateStreamV2 {
 
         let accumulators = aggregates::create_accumulators_v2(&aggr_expr)?;
 
-        let group_schema = group_schema(&schema, group_by.expr.len());
-        let row_converter = RowConverter::new(
-            group_schema
-                .fields()
-                .iter()
-                .map(|f| SortField::new(f.data_type().clone()))
-                .collect(),
-        )?;
+        let group_values = new_group_values(&group_schema(&schema, group_by.expr.len()))?;
         let aggr_schema = aggr_state_schema(&aggr_expr)?;
 
         let aggr_layout = Arc::new(RowLayout::new(&aggr_schema, RowType::WordAligned));
@@ -152,8 +140,9 @@ impl GroupedHashAggregateStreamV2 {
 
         let aggr_state = AggregationState {
             reservation,
-            map: RawTable::with_capacity(0),
+            group_values,
             group_states: Vec::with_capacity(0),
+            groups: vec![],
         };
 
         timer.done();
@@ -164,13 +153,11 @@ impl GroupedHashAggregateStreamV2 {
             input,
             group_by,
             accumulators,
-            row_converter,
             aggr_schema,
             aggr_layout,
             baseline_metrics,
             aggregate_expressions,
             aggr_state,
-            random_state: Default::default(),
             batch_size,
             row_group_skip_position: 0,
         };
@@ -185,10 +172,8 @@ impl GroupedHashAggregateStreamV2 {
                             let timer = elapsed_compute.timer();
                             let result = group_aggregate_batch(
                                 &this.mode,
-                                &this.random_state,
                                 &this.group_by,
                                 &mut this.accumulators,
-                                &mut this.row_converter,
                                 this.aggr_layout.clone(),
                                 batch,
                                 &mut this.aggr_state,
@@ -212,7 +197,6 @@ impl GroupedHashAggregateStreamV2 {
                             let timer = this.baseline_metrics.elapsed_compute().timer();
                             let result = create_batch_from_map(
                                 &this.mode,
-                                &this.row_converter,
                                 &this.aggr_schema,
                                 this.batch_size,
                                 this.row_group_skip_position,
@@ -274,10 +258,8 @@ impl RecordBatchStream for GroupedHashAggregateStreamV2 {
 #[allow(clippy::too_many_arguments)]
 fn group_aggregate_batch(
     mode: &AggregateMode,
-    random_state: &RandomState,
     grouping_set: &PhysicalGroupBy,
     accumulators: &mut [AccumulatorItemV2],
-    row_converter: &mut RowConverter,
     state_layout: Arc<RowLayout>,
     batch: RecordBatch,
     aggr_state: &mut AggregationState,
@@ -287,14 +269,15 @@ fn group_aggregate_batch(
     let grouping_by_values = evaluate_group_by(grouping_set, &batch)?;
 
     let AggregationState {
-        map, group_states, ..
+        group_values,
+        group_states,
+        groups,
+        ..
     } = aggr_state;
     let mut allocated = 0usize;
-    let row_converter_size_pre = row_converter.size();
-
-    for group_values in grouping_by_values {
-        let group_rows = row_converter.convert_columns(&group_values)?;
+    let group_values_size_pre = group_values.size();
 
+    for grouping_set_values in grouping_by_values {
         // evaluate the aggregation expressions.
         // We could evaluate them after the `take`, but since we need to evaluate all
         // of them anyways, it is more performant to do it while they are together.
@@ -307,27 +290,16 @@ fn group_aggregate_batch(
         // track which entries in `aggr_state` have rows in this batch to aggregate
         let mut groups_with_rows = vec![];
 
-        // 1.1 Calculate the group keys for the group values
-        let mut batch_hashes = vec![0; batch.num_rows()];
-        create_row_hashes_v2(&group_rows, random_state, &mut batch_hashes)?;
-
-        for (row, hash) in batch_hashes.into_iter().enumerate() {
-            let entry = map.get_mut(hash, |(_hash, group_idx)| {
-                // verify that a group that we are inserting with hash is
-                // actually the same key value as the group in
-                // existing_idx  (aka group_values @ row)
-                let group_state = &group_states[*group_idx];
-                group_rows.row(row) == group_state.group_by_values.row()
-            });
+        // 1.1 Calculate the group index of each row
+        group_values.intern(&grouping_set_values, groups)?;
 
-            match entry {
+        for (row, &group_idx) in groups.iter().enumerate() {
+            match group_states.get_mut(group_idx) {
                 // Existing entry for this group value
-                Some((_hash, group_idx)) => {
-                    let group_state = &mut group_states[*group_idx];
-
+                Some(group_state) => {
                     // 1.3
                     if group_state.indices.is_empty() {
-                        groups_with_rows.push(*group_idx);
+                        groups_with_rows.push(group_idx);
                     };
 
                     group_state
@@ -336,29 +308,18 @@ fn group_aggregate_batch(
                 }
                 //  1.2 Need to create new entry
                 None => {
-                    // Add new entry to group_states and save newly created index
+                    // Add new entry to group_states
                     let group_state = RowGroupState {
-                        group_by_values: group_rows.row(row).owned(),
                         aggregation_buffer: vec![0; state_layout.fixed_part_width()],
                         indices: vec![row as u32], // 1.3
                     };
-                    let group_idx = group_states.len();
 
                     // NOTE: do NOT include the `RowGroupState` struct size in here because this is captured by
                     // `group_states` (see allocation down below)
                     allocated += (std::mem::size_of::<u8>()
-                        * group_state.group_by_values.as_ref().len())
-                        + (std::mem::size_of::<u8>()
-                            * group_state.aggregation_buffer.capacity())
+                        * group_state.aggregation_buffer.capacity())
                         + (std::mem::size_of::<u32>() * group_state.indices.capacity());
 
-                    // for hasher function, use precomputed hash value
-                    map.insert_accounted(
-                        (hash, group_idx),
-                        |(hash, _group_index)| *hash,
-                        &mut allocated,
-                    );
-
                     group_states.push_accounted(group_state, &mut allocated);
 
                     groups_with_rows.push(group_idx);
@@ -446,7 +407,7 @@ fn group_aggregate_batch(
             })?;
     }
 
-    allocated += row_converter.size().saturating_sub(row_converter_size_pre);
+    allocated += group_values.size().saturating_sub(group_values_size_pre);
 
     Ok(allocated)
 }
@@ -454,9 +415,6 @@ fn group_aggregate_batch(
 /// The state that is built for each output group.
 #[derive(Debug)]
 struct RowGroupState {
-    // Group key.
-    group_by_values: OwnedRow,
-
     // Accumulator state, stored sequentially
     aggregation_buffer: Vec<u8>,
 
@@ -469,25 +427,20 @@ struct RowGroupState {
 struct AggregationState {
     reservation: MemoryReservation,
 
-    /// Logically maps group values to an index in `group_states`
-    ///
-    /// Uses the raw API of hashbrown to avoid actually storing the
-    /// keys in the table
-    ///
-    /// keys: u64 hashes of the GroupValue
-    /// values: (hash, index into `group_states`)
-    map: RawTable<(u64, usize)>,
+    /// The group values, which map to an index in `group_states`
+    group_values: Box<dyn GroupValues>,
 
     /// State for each group
     group_states: Vec<RowGroupState>,
+
+    /// scratch space for the group index of each row of a batch
+    groups: Vec<usize>,
 }
 
 impl std::fmt::Debug for AggregationState {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        // hashes are not store inline, so could only get values
-        let map_string = "RawTable";
         f.debug_struct("AggregationState")
-            .field("map", &map_string)
+            .field("num_groups", &self.group_values.len())
             .field("group_states", &self.group_states)
             .finish()
     }
@@ -497,7 +450,6 @@ impl std::fmt::Debug for AggregationState {
 #[allow(clippy::too_many_arguments)]
 fn create_batch_from_map(
     mode: &AggregateMode,
-    converter: &RowConverter,
     aggr_schema: &Schema,
     batch_size: usize,
     skip_items: usize,
@@ -517,15 +469,13 @@ fn create_batch_from_map(
 
     let mut state_accessor = RowAccessor::new(aggr_schema, RowType::WordAligned);
 
-    let (group_buffers, mut state_buffers): (Vec<_>, Vec<_>) = aggr_state
-        .group_states
+    let end = aggr_state.group_states.len().min(skip_items + batch_size);
+    let mut state_buffers: Vec<_> = aggr_state.group_states[skip_items..end]
         .iter()
-        .skip(skip_items)
-        .take(batch_size)
-        .map(|gs| (gs.group_by_values.row(), gs.aggregation_buffer.clone()))
-        .unzip();
+        .map(|gs| gs.aggregation_buffer.clone())
+        .collect();
 
-    let mut columns: Vec<ArrayRef> = converter.convert_rows(group_buffers)?;
+    let mut columns: Vec<ArrayRef> = aggr_state.group_values.emit(skip_items..end)?;
 
     match mode {
         AggregateMode::Partial => columns.extend(read_as_batch(
@@ -33,6 +33,7 @@ use futures::StreamExt;
 use super::expressions::PhysicalSortExpr;
 use super::{stream::RecordBatchReceiverStream, Distribution, SendableRecordBatchStream};
 use crate::execution::context::TaskContext;
+use crate::execution::planning_metrics::PlanningMetrics;
 
 /// `EXPLAIN ANALYZE` execution plan operator. This operator runs its input,
 /// discards the results, and then prints out an annotated plan with metrics
@@ -44,6 +45,8 @@ pub struct AnalyzeExec {
     pub(crate) input: Arc<dyn ExecutionPlan>,
     /// The output schema for RecordBatches of this exec node
     schema: SchemaRef,
+    /// The time spent planning the analyzed plan, if known
+    planning_metrics: Option<Arc<PlanningMetrics>>,
 }
 
 impl AnalyzeExec {
@@ -53,8 +56,29 @@ impl AnalyzeExec {
             verbose,
             input,
             schema,
+            planning_metrics: None,
         }
     }
+
+    /// Report the time spent planning the analyzed plan, recorded in
+    /// `planning_metrics`
+    pub fn with_planning_metrics(
+        mut self,
+        planning_metrics: Arc<PlanningMetrics>,
+    ) -> Self {
+        self.planning_metrics = Some(planning_metrics);
+        self
+    }
+
+    /// Whether to print extra information
+    pub fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// The input plan (the plan being analyzed)
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
 }
 
 impl ExecutionPlan for AnalyzeExec {
@@ -103,11 +127,12 @@ impl ExecutionPlan for AnalyzeExec {
         self: Arc<Self>,
         mut children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(Self::new(
-            self.verbose,
-            children.pop().unwrap(),
-            self.schema.clone(),
-        )))
+        Ok(Arc::new(Self {
+            verbose: self.verbose,
+            input: children.pop().unwrap(),
+            schema: self.schema.clone(),
+            planning_metrics: self.planning_metrics.clone(),
+        }))
     }
 
     fn execute(
@@ -135,6 +160,7 @@ impl ExecutionPlan for AnalyzeExec {
         let mut input_stream = captured_input.execute(0, context)?;
         let captured_schema = self.schema.clone();
         let verbose = self.verbose;
+        let planning_metrics = self.planning_metrics.clone();
 
         // Task reads batches the input and when complete produce a
         // RecordBatch with a report that is written to `tx` when done
@@ -176,6 +202,15 @@ impl ExecutionPlan for AnalyzeExec {
                     .to_string();
             plan_builder.append_value(annotated_plan);
 
+            if let Some(planning_metrics) = planning_metrics {
+                type_builder.append_value("Planning Metrics");
+                plan_builder.append_value(if verbose {
+                    format!("{planning_metrics:#}")
+                } else {
+                    planning_metrics.to_string()
+                });
+            }
+
             // Verbose output
             // TODO make this more sophisticated
             if verbose {
@@ -226,7 +261,7 @@ impl ExecutionPlan for AnalyzeExec {
 
     fn statistics(&self) -> Statistics {
         // Statistics an an ANALYZE plan are not relevant
-        Statistics::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
 
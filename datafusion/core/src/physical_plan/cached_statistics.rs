@@ -0,0 +1,230 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Defines the CachedStatisticsExec operator, which reports statistics computed
+//! ahead of time (e.g. by `ANALYZE TABLE`) for its input
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use datafusion_common::{ColumnStatistics, Precision};
+
+use super::expressions::PhysicalSortExpr;
+use super::{
+    DisplayFormatType, EquivalenceProperties, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+use crate::error::Result;
+use crate::execution::context::TaskContext;
+
+/// Passes the batches of its input through unchanged, but reports the given
+/// statistics for any value its input can not provide exactly.
+#[derive(Debug)]
+pub struct CachedStatisticsExec {
+    /// The input plan
+    input: Arc<dyn ExecutionPlan>,
+    /// The statistics of the whole output of `input`
+    statistics: Statistics,
+}
+
+impl CachedStatisticsExec {
+    /// Create a new CachedStatisticsExec. The column statistics must match the
+    /// schema of `input`.
+    pub fn new(input: Arc<dyn ExecutionPlan>, statistics: Statistics) -> Self {
+        assert_eq!(
+            statistics.column_statistics.len(),
+            input.schema().fields().len(),
+            "the column statistics vector length should be the number of fields"
+        );
+        Self { input, statistics }
+    }
+
+    /// The input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The cached statistics
+    pub fn cached_statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+}
+
+impl ExecutionPlan for CachedStatisticsExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn unbounded_output(&self, children: &[bool]) -> Result<bool> {
+        Ok(children[0])
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.input.equivalence_properties()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(CachedStatisticsExec::new(
+            children[0].clone(),
+            self.statistics.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        self.input.execute(partition, context)
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "CachedStatisticsExec: num_rows={}",
+                    self.statistics.num_rows
+                )
+            }
+        }
+    }
+
+    /// Exact statistics of the input take precedence over the cached ones,
+    /// which may be out of date.
+    fn statistics(&self) -> Statistics {
+        let input = self.input.statistics();
+        let cached = &self.statistics;
+        Statistics {
+            num_rows: prefer_exact(input.num_rows, &cached.num_rows),
+            total_byte_size: prefer_exact(input.total_byte_size, &cached.total_byte_size),
+            column_statistics: input
+                .column_statistics
+                .into_iter()
+                .zip(cached.column_statistics.iter())
+                .map(|(input, cached)| ColumnStatistics {
+                    null_count: prefer_exact(input.null_count, &cached.null_count),
+                    max_value: prefer_exact(input.max_value, &cached.max_value),
+                    min_value: prefer_exact(input.min_value, &cached.min_value),
+                    distinct_count: prefer_exact(
+                        input.distinct_count,
+                        &cached.distinct_count,
+                    ),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns `input` if it is exact or there is no cached value, else `cached`
+fn prefer_exact<T>(input: Precision<T>, cached: &Precision<T>) -> Precision<T>
+where
+    T: std::fmt::Debug + Clone + PartialEq + Eq + PartialOrd,
+{
+    match (&input, cached) {
+        (Precision::Exact(_), _) | (_, Precision::Absent) => input,
+        _ => cached.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::exec::StatisticsExec;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::ScalarValue;
+
+    #[test]
+    fn cached_statistics_fill_in_inexact_values() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]);
+        let input = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Precision::Exact(100),
+                total_byte_size: Precision::Inexact(800),
+                column_statistics: vec![
+                    ColumnStatistics {
+                        null_count: Precision::Exact(3),
+                        ..Default::default()
+                    },
+                    ColumnStatistics::new_unknown(),
+                ],
+            },
+            schema,
+        ));
+        let cached = Statistics {
+            num_rows: Precision::Inexact(90),
+            total_byte_size: Precision::Absent,
+            column_statistics: vec![
+                ColumnStatistics {
+                    null_count: Precision::Inexact(5),
+                    min_value: Precision::Inexact(ScalarValue::Int32(Some(1))),
+                    ..Default::default()
+                },
+                ColumnStatistics {
+                    distinct_count: Precision::Inexact(7),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let exec = CachedStatisticsExec::new(input, cached);
+        assert_eq!(
+            exec.statistics(),
+            Statistics {
+                num_rows: Precision::Exact(100),
+                total_byte_size: Precision::Inexact(800),
+                column_statistics: vec![
+                    ColumnStatistics {
+                        null_count: Precision::Exact(3),
+                        min_value: Precision::Inexact(ScalarValue::Int32(Some(1))),
+                        ..Default::default()
+                    },
+                    ColumnStatistics {
+                        distinct_count: Precision::Inexact(7),
+                        ..Default::default()
+                    },
+                ],
+            }
+        );
+    }
+}
@@ -43,6 +43,10 @@ use super::{metrics::ExecutionPlanMetricsSet, Statistics};
 
 /// CoalesceBatchesExec combines small batches into larger batches for more efficient use of
 /// vectorized processing by upstream operators.
+///
+/// Batches are only copied when they are combined: input batches that already have
+/// `target_batch_size` rows, and a single buffered batch left at the end of the input,
+/// are passed through as is.
 #[derive(Debug)]
 pub struct CoalesceBatchesExec {
     /// The input plan
@@ -133,6 +137,7 @@ impl ExecutionPlan for CoalesceBatchesExec {
             target_batch_size: self.target_batch_size,
             buffer: Vec::new(),
             buffered_rows: 0,
+            pending: None,
             is_closed: false,
             baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
         }))
@@ -161,6 +166,10 @@ impl ExecutionPlan for CoalesceBatchesExec {
     fn statistics(&self) -> Statistics {
         self.input.statistics()
     }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        self.input.partition_statistics(partition)
+    }
 }
 
 struct CoalesceBatchesStream {
@@ -174,6 +183,8 @@ struct CoalesceBatchesStream {
     buffer: Vec<RecordBatch>,
     /// Buffered row count
     buffered_rows: usize,
+    /// Large input batch to return as is after the buffered batches
+    pending: Option<RecordBatch>,
     /// Whether the stream has finished returning all of its data or not
     is_closed: bool,
     /// Execution metrics
@@ -205,6 +216,9 @@ impl CoalesceBatchesStream {
         // Get a clone (uses same underlying atomic) as self gets borrowed below
         let cloned_time = self.baseline_metrics.elapsed_compute().clone();
 
+        if let Some(batch) = self.pending.take() {
+            return Poll::Ready(Some(Ok(batch)));
+        }
         if self.is_closed {
             return Poll::Ready(None);
         }
@@ -214,30 +228,25 @@ impl CoalesceBatchesStream {
             let _timer = cloned_time.timer();
             match input_batch {
                 Poll::Ready(x) => match x {
-                    Some(Ok(ref batch)) => {
-                        if batch.num_rows() >= self.target_batch_size
-                            && self.buffer.is_empty()
-                        {
-                            return Poll::Ready(Some(Ok(batch.clone())));
+                    Some(Ok(batch)) => {
+                        if batch.num_rows() >= self.target_batch_size {
+                            if self.buffer.is_empty() {
+                                return Poll::Ready(Some(Ok(batch)));
+                            }
+                            // rather than copying the large batch to append it to the
+                            // buffered ones, return it as is once they are returned
+                            self.pending = Some(batch);
+                            return Poll::Ready(Some(self.flush_buffer()));
                         } else if batch.num_rows() == 0 {
                             // discard empty batches
                         } else {
                             // add to the buffered batches
-                            self.buffer.push(batch.clone());
                             self.buffered_rows += batch.num_rows();
+                            self.buffer.push(batch);
                             // check to see if we have enough batches yet
                             if self.buffered_rows >= self.target_batch_size {
                                 // combine the batches and return
-                                let batch = concat_batches(
-                                    &self.schema,
-                                    &self.buffer,
-                                    self.buffered_rows,
-                                )?;
-                                // reset buffer state
-                                self.buffer.clear();
-                                self.buffered_rows = 0;
-                                // return batch
-                                return Poll::Ready(Some(Ok(batch)));
+                                return Poll::Ready(Some(self.flush_buffer()));
                             }
                         }
                     }
@@ -249,16 +258,7 @@ impl CoalesceBatchesStream {
                             return Poll::Ready(None);
                         } else {
                             // combine the batches and return
-                            let batch = concat_batches(
-                                &self.schema,
-                                &self.buffer,
-                                self.buffered_rows,
-                            )?;
-                            // reset buffer state
-                            self.buffer.clear();
-                            self.buffered_rows = 0;
-                            // return batch
-                            return Poll::Ready(Some(Ok(batch)));
+                            return Poll::Ready(Some(self.flush_buffer()));
                         }
                     }
                     other => return Poll::Ready(other),
@@ -267,6 +267,20 @@ impl CoalesceBatchesStream {
             }
         }
     }
+
+    /// Combines the buffered batches into one batch and resets the buffer. A single
+    /// buffered batch is returned as is, without copying it.
+    fn flush_buffer(&mut self) -> ArrowResult<RecordBatch> {
+        let batch = if self.buffer.len() == 1 {
+            self.buffer.pop().unwrap()
+        } else {
+            concat_batches(&self.schema, &self.buffer, self.buffered_rows)?
+        };
+        // reset buffer state
+        self.buffer.clear();
+        self.buffered_rows = 0;
+        Ok(batch)
+    }
 }
 
 impl RecordBatchStream for CoalesceBatchesStream {
@@ -315,6 +329,7 @@ mod tests {
     use crate::physical_plan::{memory::MemoryExec, repartition::RepartitionExec};
     use crate::prelude::SessionContext;
     use crate::test::create_vec_batches;
+    use arrow::array::{Array, UInt32Array};
     use arrow::datatypes::{DataType, Field, Schema};
 
     #[tokio::test]
@@ -351,6 +366,25 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_coalesce_target_bytes() -> Result<()> {
+        let mut config = ConfigOptions::new();
+        config.execution.batch_size = 1234;
+        // 4 byte rows
+        config.execution.coalesce_target_bytes = 1024;
+
+        let ctx = SessionContext::with_config(config.into());
+        let plan = create_physical_plan(ctx).await?;
+        let projection = plan.as_any().downcast_ref::<ProjectionExec>().unwrap();
+        let coalesce = projection
+            .input()
+            .as_any()
+            .downcast_ref::<CoalesceBatchesExec>()
+            .unwrap();
+        assert_eq!(256, coalesce.target_batch_size);
+        Ok(())
+    }
+
     async fn create_physical_plan(ctx: SessionContext) -> Result<Arc<dyn ExecutionPlan>> {
         let schema = test_schema();
         let partition = create_vec_batches(&schema, 10);
@@ -381,6 +415,35 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pass_through_without_copy() -> Result<()> {
+        let schema = test_schema();
+        let partition: Vec<_> = [8, 8, 30, 5]
+            .into_iter()
+            .map(|num_rows| {
+                let array = UInt32Array::from_iter_values(0..num_rows);
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap()
+            })
+            .collect();
+
+        let output_partitions =
+            coalesce_batches(&schema, vec![partition.clone()], 20).await?;
+        assert_eq!(1, output_partitions.len());
+
+        // the two small batches are combined before the large batch, which is
+        // returned as is, like the last batch of the input
+        let batches = &output_partitions[0];
+        let num_rows: Vec<_> = batches.iter().map(|batch| batch.num_rows()).collect();
+        assert_eq!(vec![16, 30, 5], num_rows);
+
+        let values_ptr =
+            |batch: &RecordBatch| batch.column(0).data().buffers()[0].as_ptr();
+        assert_eq!(values_ptr(&partition[2]), values_ptr(&batches[1]));
+        assert_eq!(values_ptr(&partition[3]), values_ptr(&batches[2]));
+
+        Ok(())
+    }
+
     fn test_schema() -> Arc<Schema> {
         Arc::new(Schema::new(vec![Field::new("c0", DataType::UInt32, false)]))
     }
@@ -0,0 +1,474 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the execution plan of `DISTINCT ON`, which returns the first row
+//! of each distinct value of its `ON` expressions
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use ahash::RandomState;
+use arrow::array::{ArrayRef, BooleanArray};
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion_physical_expr::hash_utils::create_row_hashes_v2;
+use futures::{ready, Stream, StreamExt};
+use hashbrown::raw::RawTable;
+
+use super::expressions::PhysicalSortExpr;
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::{
+    DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan, Partitioning,
+    PhysicalExpr, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+use crate::error::Result;
+use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::proxy::{RawTableAllocExt, VecAllocExt};
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+
+/// Execution plan of `DISTINCT ON`, returning the first row of its input for
+/// each distinct value of `on_exprs`.
+///
+/// The input is hash partitioned on `on_exprs`, so that all the rows of a
+/// group are in the same partition. With `sort_exprs`, which start with the
+/// expressions of `on_exprs`, the partitions are sorted and the first row of
+/// each group is the one following a row of another group. Otherwise, the
+/// first row found of each group is returned, and the groups already found
+/// are kept in a hash table. The values of `on_exprs` are compared in the
+/// [row format](arrow::row), in which NULLs are equal.
+#[derive(Debug)]
+pub struct DistinctOnExec {
+    /// The input
+    input: Arc<dyn ExecutionPlan>,
+    /// The expressions whose distinct values are returned
+    on_exprs: Vec<Arc<dyn PhysicalExpr>>,
+    /// The order of the rows of each group
+    sort_exprs: Option<Vec<PhysicalSortExpr>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl DistinctOnExec {
+    /// Create a new `DISTINCT ON` of `input`
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        on_exprs: Vec<Arc<dyn PhysicalExpr>>,
+        sort_exprs: Option<Vec<PhysicalSortExpr>>,
+    ) -> Self {
+        Self {
+            input,
+            on_exprs,
+            sort_exprs,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+
+    /// The input
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The expressions whose distinct values are returned
+    pub fn on_exprs(&self) -> &[Arc<dyn PhysicalExpr>] {
+        &self.on_exprs
+    }
+
+    /// The order of the rows of each group
+    pub fn sort_exprs(&self) -> Option<&[PhysicalSortExpr]> {
+        self.sort_exprs.as_deref()
+    }
+}
+
+impl ExecutionPlan for DistinctOnExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn unbounded_output(&self, children: &[bool]) -> Result<bool> {
+        Ok(children[0])
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(DistinctOnExec::new(
+            children[0].clone(),
+            self.on_exprs.clone(),
+            self.sort_exprs.clone(),
+        )))
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::HashPartitioned(self.on_exprs.clone())]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<&[PhysicalSortExpr]>> {
+        vec![self.sort_exprs.as_deref()]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> bool {
+        true
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.input.equivalence_properties()
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let fields = self
+            .on_exprs
+            .iter()
+            .map(|expr| Ok(SortField::new(expr.data_type(&self.input.schema())?)))
+            .collect::<Result<_>>()?;
+        let seen = match self.sort_exprs {
+            Some(_) => SeenGroups::Sorted(None),
+            None => SeenGroups::Hashed {
+                random_state: RandomState::new(),
+                map: RawTable::with_capacity(0),
+                rows: vec![],
+                hashes: vec![],
+                map_size: 0,
+                reservation: MemoryConsumer::new(format!("DistinctOnExec[{partition}]"))
+                    .register(context.memory_pool()),
+            },
+        };
+
+        Ok(Box::pin(DistinctOnStream {
+            schema: self.schema(),
+            input: self.input.execute(partition, context)?,
+            on_exprs: self.on_exprs.clone(),
+            converter: RowConverter::new(fields)?,
+            seen,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                let on_exprs: Vec<String> =
+                    self.on_exprs.iter().map(|e| e.to_string()).collect();
+                write!(f, "DistinctOnExec: on=[{}]", on_exprs.join(", "))?;
+                if let Some(sort_exprs) = &self.sort_exprs {
+                    let sort_exprs: Vec<String> =
+                        sort_exprs.iter().map(|e| e.to_string()).collect();
+                    write!(f, ", sort=[{}]", sort_exprs.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::new_unknown(&self.schema())
+    }
+}
+
+/// The groups already returned by a partition
+enum SeenGroups {
+    /// The group of the last row of the sorted input, if any
+    Sorted(Option<OwnedRow>),
+    /// All the groups found in the input
+    Hashed {
+        random_state: RandomState,
+        /// Maps the hashes of the groups to their index in `rows`
+        map: RawTable<(u64, usize)>,
+        /// The groups
+        rows: Vec<OwnedRow>,
+        /// Scratch space for the hashes of the rows of a batch
+        hashes: Vec<u64>,
+        /// The memory used by the hash table
+        map_size: usize,
+        /// Memory reserved for the hash table
+        reservation: MemoryReservation,
+    },
+}
+
+/// A stream returning the first row of each group of a partition
+struct DistinctOnStream {
+    schema: SchemaRef,
+    /// The input partition
+    input: SendableRecordBatchStream,
+    on_exprs: Vec<Arc<dyn PhysicalExpr>>,
+    /// Converts the values of `on_exprs` to the row format
+    converter: RowConverter,
+    seen: SeenGroups,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl DistinctOnStream {
+    /// Returns the rows of `batch` that are the first of their group
+    fn filter_batch(&mut self, batch: &RecordBatch) -> ArrowResult<RecordBatch> {
+        let columns = self
+            .on_exprs
+            .iter()
+            .map(|expr| Ok(expr.evaluate(batch)?.into_array(batch.num_rows())))
+            .collect::<Result<Vec<ArrayRef>>>()?;
+        let rows = self.converter.convert_columns(&columns)?;
+
+        let mask: BooleanArray = match &mut self.seen {
+            SeenGroups::Sorted(last) => {
+                let mask = (0..rows.num_rows())
+                    .map(|row| {
+                        let first = match row {
+                            0 => last.as_ref().map_or(true, |l| l.row() != rows.row(0)),
+                            _ => rows.row(row - 1) != rows.row(row),
+                        };
+                        Some(first)
+                    })
+                    .collect();
+                if rows.num_rows() > 0 {
+                    *last = Some(rows.row(rows.num_rows() - 1).owned());
+                }
+                mask
+            }
+            SeenGroups::Hashed {
+                random_state,
+                map,
+                rows: groups,
+                hashes,
+                map_size,
+                reservation,
+            } => {
+                hashes.clear();
+                hashes.resize(rows.num_rows(), 0);
+                create_row_hashes_v2(&rows, random_state, hashes)?;
+
+                let mask = hashes
+                    .iter()
+                    .enumerate()
+                    .map(|(row, hash)| {
+                        let entry = map.get(*hash, |(_hash, idx)| {
+                            rows.row(row) == groups[*idx].row()
+                        });
+                        if entry.is_some() {
+                            return Some(false);
+                        }
+                        let idx = groups.len();
+                        let owned_row = rows.row(row).owned();
+                        *map_size += owned_row.as_ref().len();
+                        groups.push_accounted(owned_row, map_size);
+                        map.insert_accounted(
+                            (*hash, idx),
+                            |(hash, _idx)| *hash,
+                            map_size,
+                        );
+                        Some(true)
+                    })
+                    .collect();
+
+                let size = *map_size + self.converter.size();
+                if size > reservation.size() {
+                    reservation.try_grow(size - reservation.size())?;
+                }
+                mask
+            }
+        };
+
+        filter_record_batch(batch, &mask)
+    }
+
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<ArrowResult<RecordBatch>>> {
+        loop {
+            match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let timer = self.baseline_metrics.elapsed_compute().timer();
+                    let result = self.filter_batch(&batch);
+                    timer.done();
+                    match result {
+                        // skip entirely filtered batches
+                        Ok(batch) if batch.num_rows() == 0 => continue,
+                        result => return Poll::Ready(Some(result)),
+                    }
+                }
+                other => return Poll::Ready(other),
+            }
+        }
+    }
+}
+
+impl Stream for DistinctOnStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_impl(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for DistinctOnStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::assert_batches_sorted_eq;
+    use crate::execution::context::SessionConfig;
+    use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+    use crate::physical_plan::expressions::col;
+    use crate::physical_plan::{collect, memory::MemoryExec};
+    use crate::prelude::SessionContext;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Utf8, true),
+            Field::new("b", DataType::Int32, true),
+        ]))
+    }
+
+    fn build_batch(a: Vec<Option<&str>>, b: Vec<i32>) -> RecordBatch {
+        RecordBatch::try_new(
+            schema(),
+            vec![
+                Arc::new(StringArray::from(a)),
+                Arc::new(Int32Array::from(b)),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn distinct_on_exec(
+        batches: Vec<RecordBatch>,
+        sorted: bool,
+    ) -> Arc<dyn ExecutionPlan> {
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema(), None).unwrap());
+        let a = col("a", &schema()).unwrap();
+        let sort_exprs = sorted.then(|| {
+            vec![
+                PhysicalSortExpr {
+                    expr: a.clone(),
+                    options: SortOptions::default(),
+                },
+                PhysicalSortExpr {
+                    expr: col("b", &schema()).unwrap(),
+                    options: SortOptions::default(),
+                },
+            ]
+        });
+        Arc::new(DistinctOnExec::new(input, vec![a], sort_exprs))
+    }
+
+    #[tokio::test]
+    async fn sorted_input() -> Result<()> {
+        // the group of x spans both batches
+        let batches = vec![
+            build_batch(vec![None, None, Some("x")], vec![1, 2, 1]),
+            build_batch(vec![Some("x"), Some("x"), Some("y")], vec![2, 3, 1]),
+        ];
+        let session_ctx = SessionContext::new();
+        let exec = distinct_on_exec(batches, true);
+        let batches = collect(exec, session_ctx.task_ctx()).await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "|   | 1 |",
+            "| x | 1 |",
+            "| y | 1 |",
+            "+---+---+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unsorted_input() -> Result<()> {
+        let batches = vec![
+            build_batch(vec![Some("x"), None, Some("x")], vec![1, 2, 3]),
+            build_batch(vec![Some("y"), None, Some("x")], vec![4, 5, 6]),
+        ];
+        let session_ctx = SessionContext::new();
+        let exec = distinct_on_exec(batches, false);
+        let batches = collect(exec, session_ctx.task_ctx()).await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "|   | 2 |",
+            "| x | 1 |",
+            "| y | 4 |",
+            "+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn overallocation() -> Result<()> {
+        let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let session_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime);
+
+        let batches = vec![build_batch(vec![Some("x"), Some("y")], vec![1, 2])];
+        let exec = distinct_on_exec(batches, false);
+        let err = collect(exec, session_ctx.task_ctx()).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Resources exhausted: Failed to allocate additional"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
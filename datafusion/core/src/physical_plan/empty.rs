@@ -163,6 +163,18 @@ impl ExecutionPlan for EmptyExec {
     }
 
     fn statistics(&self) -> Statistics {
+        let batch = self
+            .data()
+            .expect("Create empty RecordBatch should not fail");
+        // every partition produces the same data
+        common::compute_record_batch_statistics(
+            &vec![batch; self.partitions],
+            &self.schema,
+            None,
+        )
+    }
+
+    fn partition_statistics(&self, _partition: usize) -> Statistics {
         let batch = self
             .data()
             .expect("Create empty RecordBatch should not fail");
@@ -178,7 +178,7 @@ impl ExecutionPlan for ExplainExec {
 
     fn statistics(&self) -> Statistics {
         // Statistics an EXPLAIN plan are not relevant
-        Statistics::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
 
@@ -0,0 +1,276 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan for reading Arrow IPC files
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::{SessionState, TaskContext};
+use crate::physical_plan::expressions::PhysicalSortExpr;
+use crate::physical_plan::file_format::file_stream::{
+    FileOpenFuture, FileOpener, FileStream,
+};
+use crate::physical_plan::file_format::FileMeta;
+use crate::physical_plan::metrics::ExecutionPlanMetricsSet;
+use crate::physical_plan::{
+    DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
+};
+use arrow::datatypes::SchemaRef;
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+
+use futures::{StreamExt, TryStreamExt};
+use memmap2::Mmap;
+use object_store::{GetResult, ObjectStore};
+use std::any::Any;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::task::{self, JoinHandle};
+
+use super::{get_output_ordering, FileScanConfig};
+
+/// Execution plan for scanning Arrow IPC files
+#[derive(Debug, Clone)]
+pub struct ArrowExec {
+    base_config: FileScanConfig,
+    projected_statistics: Statistics,
+    projected_schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl ArrowExec {
+    /// Create a new Arrow IPC reader execution plan provided base configurations
+    pub fn new(base_config: FileScanConfig) -> Self {
+        let (projected_schema, projected_statistics) = base_config.project();
+
+        Self {
+            base_config,
+            projected_schema,
+            projected_statistics,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+    /// Ref to the base configs
+    pub fn base_config(&self) -> &FileScanConfig {
+        &self.base_config
+    }
+}
+
+impl ExecutionPlan for ArrowExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.projected_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(self.base_config.file_groups.len())
+    }
+
+    fn unbounded_output(&self, _: &[bool]) -> Result<bool> {
+        Ok(self.base_config().infinite_source)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        get_output_ordering(&self.base_config)
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let object_store = context.object_store(&self.base_config.object_store_url)?;
+
+        let opener = ArrowOpener {
+            object_store,
+            projection: self.base_config.file_column_projection_indices(),
+        };
+        let stream =
+            FileStream::new(&self.base_config, partition, opener, self.metrics.clone())?;
+        Ok(Box::pin(stream) as SendableRecordBatchStream)
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "ArrowExec: files={}, limit={:?}, projection={}",
+                    super::FileGroupsDisplay(&self.base_config.file_groups),
+                    self.base_config.limit,
+                    super::ProjectSchemaDisplay(&self.projected_schema),
+                )
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.projected_statistics.clone()
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        self.base_config.partition_statistics(partition)
+    }
+}
+
+struct ArrowOpener {
+    object_store: Arc<dyn ObjectStore>,
+    projection: Option<Vec<usize>>,
+}
+
+impl FileOpener for ArrowOpener {
+    fn open(&self, file_meta: FileMeta) -> Result<FileOpenFuture> {
+        let object_store = self.object_store.clone();
+        let projection = self.projection.clone();
+        Ok(Box::pin(async move {
+            match object_store.get(file_meta.location()).await? {
+                // the local files are memory mapped, only the projected columns are
+                // read from the disk
+                GetResult::File(file, _) => {
+                    // SAFETY: the file must not be modified while it is read, as
+                    // for any file read in a query
+                    let mmap = unsafe { Mmap::map(&file)? };
+                    let reader = FileReader::try_new(Cursor::new(mmap), projection)?;
+                    Ok(futures::stream::iter(reader).boxed())
+                }
+                r @ GetResult::Stream(_) => {
+                    let bytes = r.bytes().await?;
+                    let reader = FileReader::try_new(Cursor::new(bytes), projection)?;
+                    Ok(futures::stream::iter(reader).boxed())
+                }
+            }
+        }))
+    }
+}
+
+pub async fn plan_to_arrow(
+    state: &SessionState,
+    plan: Arc<dyn ExecutionPlan>,
+    path: impl AsRef<str>,
+) -> Result<()> {
+    let path = path.as_ref();
+    // create directory to contain the Arrow files (one per partition)
+    let fs_path = Path::new(path);
+    match fs::create_dir(fs_path) {
+        Ok(()) => {
+            let mut tasks = vec![];
+            for i in 0..plan.output_partitioning().partition_count() {
+                let plan = plan.clone();
+                let filename = format!("part-{i}.arrow");
+                let path = fs_path.join(filename);
+                let file = fs::File::create(path)?;
+                let mut writer = FileWriter::try_new(file, plan.schema().as_ref())?;
+                let task_ctx = Arc::new(TaskContext::from(state));
+                let stream = plan.execute(i, task_ctx)?;
+                let handle: JoinHandle<Result<()>> = task::spawn(async move {
+                    stream
+                        .map(|batch| writer.write(&batch?))
+                        .try_collect()
+                        .await
+                        .map_err(DataFusionError::from)?;
+                    writer.finish().map_err(DataFusionError::from)
+                });
+                tasks.push(handle);
+            }
+            futures::future::join_all(tasks)
+                .await
+                .into_iter()
+                .try_for_each(|result| {
+                    result.map_err(|e| DataFusionError::Execution(format!("{e}")))?
+                })?;
+            Ok(())
+        }
+        Err(e) => Err(DataFusionError::Execution(format!(
+            "Could not create directory {path}: {e:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::util::pretty::pretty_format_batches;
+    use crate::physical_plan::displayable;
+    use crate::prelude::{
+        ArrowReadOptions, CsvReadOptions, SessionConfig, SessionContext,
+    };
+    use crate::test_util::arrow_test_data;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn write_and_read_arrow_files() -> Result<()> {
+        let ctx =
+            SessionContext::with_config(SessionConfig::new().with_target_partitions(4));
+        let path = format!("{}/csv/aggregate_test_100.csv", arrow_test_data());
+        ctx.register_csv("test", &path, CsvReadOptions::new())
+            .await?;
+
+        let tmp_dir = TempDir::new()?;
+        let out_dir = tmp_dir.as_ref().to_str().unwrap().to_string() + "/out";
+        let df = ctx.sql("SELECT c1, c2, c3 FROM test").await?;
+        df.write_arrow(&out_dir).await?;
+
+        let read_ctx = SessionContext::new();
+        read_ctx
+            .register_arrow("t", &out_dir, ArrowReadOptions::default())
+            .await?;
+        let plan = read_ctx
+            .sql("SELECT c2 FROM t")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let plan = format!("{}", displayable(plan.as_ref()).indent());
+        assert!(plan.contains("ArrowExec"), "{plan}");
+        assert!(plan.contains("projection=[c2]"), "{plan}");
+
+        let query = "SELECT c1, c2, c3 FROM {} ORDER BY c1, c2, c3";
+        let expected = ctx
+            .sql(&query.replace("{}", "test"))
+            .await?
+            .collect()
+            .await?;
+        let actual = read_ctx
+            .sql(&query.replace("{}", "t"))
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(
+            pretty_format_batches(&expected)?.to_string(),
+            pretty_format_batches(&actual)?.to_string()
+        );
+        Ok(())
+    }
+}
@@ -30,11 +30,15 @@ use crate::physical_plan::metrics::ExecutionPlanMetricsSet;
 use crate::physical_plan::{
     DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
 };
+use arrow::array::{ArrayRef, StringArray};
 use arrow::csv;
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{DataType, SchemaRef};
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
 
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 
+use datafusion_common::cast::as_string_array;
 use futures::{StreamExt, TryStreamExt};
 use object_store::{GetResult, ObjectStore};
 use std::any::Any;
@@ -42,6 +46,7 @@ use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::task::{self, JoinHandle};
+use tokio_util::io::ReaderStream;
 
 use super::{get_output_ordering, FileScanConfig};
 
@@ -56,6 +61,8 @@ pub struct CsvExec {
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
     file_compression_type: FileCompressionType,
+    null_markers: Vec<String>,
+    datetime_format: Option<String>,
 }
 
 impl CsvExec {
@@ -76,9 +83,24 @@ impl CsvExec {
             delimiter,
             metrics: ExecutionPlanMetricsSet::new(),
             file_compression_type,
+            null_markers: vec![],
+            datetime_format: None,
         }
     }
 
+    /// Read the values equal to one of `null_markers` as nulls
+    pub fn with_null_markers(mut self, null_markers: Vec<String>) -> Self {
+        self.null_markers = null_markers;
+        self
+    }
+
+    /// Parse the timestamps with the `chrono` format `datetime_format`, instead of
+    /// as ISO 8601 timestamps
+    pub fn with_datetime_format(mut self, datetime_format: Option<String>) -> Self {
+        self.datetime_format = datetime_format;
+        self
+    }
+
     /// Ref to the base configs
     pub fn base_config(&self) -> &FileScanConfig {
         &self.base_config
@@ -91,6 +113,18 @@ impl CsvExec {
     pub fn delimiter(&self) -> u8 {
         self.delimiter
     }
+    /// The compression type of the scanned files
+    pub fn file_compression_type(&self) -> &FileCompressionType {
+        &self.file_compression_type
+    }
+    /// The values read as nulls, in addition to the empty values
+    pub fn null_markers(&self) -> &[String] {
+        &self.null_markers
+    }
+    /// The `chrono` format of the timestamps, if any
+    pub fn datetime_format(&self) -> Option<&str> {
+        self.datetime_format.as_deref()
+    }
 }
 
 impl ExecutionPlan for CsvExec {
@@ -135,9 +169,7 @@ impl ExecutionPlan for CsvExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        let object_store = context
-            .runtime_env()
-            .object_store(&self.base_config.object_store_url)?;
+        let object_store = context.object_store(&self.base_config.object_store_url)?;
 
         let config = Arc::new(CsvConfig {
             batch_size: context.session_config().batch_size(),
@@ -145,6 +177,8 @@ impl ExecutionPlan for CsvExec {
             file_projection: self.base_config.file_column_projection_indices(),
             has_header: self.has_header,
             delimiter: self.delimiter,
+            null_markers: self.null_markers.clone(),
+            datetime_format: self.datetime_format.clone(),
             object_store,
         });
 
@@ -179,6 +213,10 @@ impl ExecutionPlan for CsvExec {
     fn statistics(&self) -> Statistics {
         self.projected_statistics.clone()
     }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        self.base_config.partition_statistics(partition)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -188,12 +226,13 @@ struct CsvConfig {
     file_projection: Option<Vec<usize>>,
     has_header: bool,
     delimiter: u8,
+    null_markers: Vec<String>,
+    datetime_format: Option<String>,
     object_store: Arc<dyn ObjectStore>,
 }
 
 impl CsvConfig {
     fn open<R: std::io::Read>(&self, reader: R, first_chunk: bool) -> csv::Reader<R> {
-        let datetime_format = None;
         csv::Reader::new(
             reader,
             Arc::clone(&self.file_schema),
@@ -202,9 +241,79 @@ impl CsvConfig {
             self.batch_size,
             None,
             self.file_projection.clone(),
-            datetime_format,
+            self.datetime_format.clone(),
         )
     }
+
+    fn is_null_marker(&self, value: &[u8]) -> bool {
+        self.null_markers
+            .iter()
+            .any(|marker| marker.as_bytes() == value)
+    }
+
+    /// Empties the fields of the non string columns equal to a null marker in a
+    /// chunk of rows, as the empty fields of these columns are read as nulls
+    fn replace_null_markers(&self, bytes: Bytes, first_chunk: bool) -> Result<Bytes> {
+        if self.null_markers.is_empty() {
+            return Ok(bytes);
+        }
+        let mut reader = ::csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .from_reader(bytes.reader());
+        let mut writer = ::csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .from_writer(vec![]);
+        let fields = self.file_schema.fields();
+        for (i, record) in reader.byte_records().enumerate() {
+            let record = record.map_err(|e| DataFusionError::External(Box::new(e)))?;
+            let record = match i == 0 && first_chunk && self.has_header {
+                true => record,
+                false => record
+                    .iter()
+                    .enumerate()
+                    .map(|(j, value)| match fields.get(j).map(|f| f.data_type()) {
+                        Some(DataType::Utf8) => value,
+                        _ if self.is_null_marker(value) => &b""[..],
+                        _ => value,
+                    })
+                    .collect(),
+            };
+            writer
+                .write_byte_record(&record)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| DataFusionError::External(Box::new(e.into_error())))?;
+        Ok(bytes.into())
+    }
+
+    /// Replaces the values of the string columns equal to a null marker with nulls
+    fn nullify_markers(&self, batch: RecordBatch) -> ArrowResult<RecordBatch> {
+        if self.null_markers.is_empty() {
+            return Ok(batch);
+        }
+        let columns = batch
+            .columns()
+            .iter()
+            .map(|column| match column.data_type() {
+                DataType::Utf8 => {
+                    let values = as_string_array(column)?
+                        .iter()
+                        .map(|value| {
+                            value.filter(|value| !self.is_null_marker(value.as_bytes()))
+                        })
+                        .collect::<StringArray>();
+                    Ok(Arc::new(values) as ArrayRef)
+                }
+                _ => Ok(Arc::clone(column)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        RecordBatch::try_new(batch.schema(), columns)
+    }
 }
 
 struct CsvOpener {
@@ -217,25 +326,49 @@ impl FileOpener for CsvOpener {
         let config = self.config.clone();
         let file_compression_type = self.file_compression_type.to_owned();
         Ok(Box::pin(async move {
-            match config.object_store.get(file_meta.location()).await? {
-                GetResult::File(file, _) => {
-                    let decoder = file_compression_type.convert_read(file)?;
-                    Ok(futures::stream::iter(config.open(decoder, true)).boxed())
+            let (decoder, mut first_chunk) = match &file_meta.range {
+                // only the range at the start of the file has the header
+                Some(range) if file_compression_type.is_splittable() => {
+                    let decoder = file_compression_type.convert_range(
+                        Arc::clone(&config.object_store),
+                        file_meta.location().clone(),
+                        file_meta.object_meta.size,
+                        range.start as usize..range.end as usize,
+                    )?;
+                    (decoder, range.start == 0)
                 }
-                GetResult::Stream(s) => {
-                    let mut first_chunk = true;
-                    let s = s.map_err(Into::<DataFusionError>::into);
-                    let decoder = file_compression_type.convert_stream(s)?;
-                    Ok(newline_delimited_stream(decoder)
-                        .map_ok(move |bytes| {
-                            let reader = config.open(bytes.reader(), first_chunk);
-                            first_chunk = false;
-                            futures::stream::iter(reader)
-                        })
-                        .try_flatten()
-                        .boxed())
+                _ => {
+                    let s = match config.object_store.get(file_meta.location()).await? {
+                        GetResult::File(file, _) if config.null_markers.is_empty() => {
+                            let decoder = file_compression_type.convert_read(file)?;
+                            return Ok(
+                                futures::stream::iter(config.open(decoder, true)).boxed()
+                            );
+                        }
+                        // the null markers are replaced in each chunk of rows of the file
+                        GetResult::File(file, _) => {
+                            ReaderStream::new(tokio::fs::File::from_std(file))
+                                .map_err(Into::<DataFusionError>::into)
+                                .boxed()
+                        }
+                        GetResult::Stream(s) => {
+                            s.map_err(Into::<DataFusionError>::into).boxed()
+                        }
+                    };
+                    (file_compression_type.convert_stream(s)?.boxed(), true)
                 }
-            }
+            };
+            Ok(newline_delimited_stream(decoder)
+                .map(move |bytes| {
+                    let bytes = config.replace_null_markers(bytes?, first_chunk)?;
+                    let reader = config.open(bytes.reader(), first_chunk);
+                    first_chunk = false;
+                    let config = Arc::clone(&config);
+                    Ok(futures::stream::iter(reader)
+                        .map(move |batch| config.nullify_markers(batch?)))
+                })
+                .try_flatten()
+                .boxed())
         }))
     }
 }
@@ -305,7 +438,9 @@ mod tests {
         case(FileCompressionType::UNCOMPRESSED),
         case(FileCompressionType::GZIP),
         case(FileCompressionType::BZIP2),
-        case(FileCompressionType::XZ)
+        case(FileCompressionType::XZ),
+        case(FileCompressionType::ZSTD),
+        case(FileCompressionType::BGZIP)
     )]
     #[tokio::test]
     async fn csv_exec_with_projection(
@@ -484,7 +619,7 @@ mod tests {
         config.table_partition_cols =
             vec![("date".to_owned(), partition_type_wrap(DataType::Utf8))];
         config.file_groups[0][0].partition_values =
-            vec![ScalarValue::Utf8(Some("2021-10-26".to_owned()))];
+            vec![ScalarValue::Utf8(Some("2021-10-26".into()))];
 
         // We should be able to project on the partition column
         // Which is supposed to be after the file fields
@@ -28,10 +28,11 @@ use std::time::Instant;
 
 use arrow::datatypes::SchemaRef;
 use arrow::{error::Result as ArrowResult, record_batch::RecordBatch};
-use datafusion_common::ScalarValue;
+use datafusion_common::{DataFusionError, ScalarValue};
 use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use futures::{ready, FutureExt, Stream, StreamExt};
+use object_store::path::Path;
 
 use crate::datasource::listing::PartitionedFile;
 use crate::error::Result;
@@ -89,6 +90,8 @@ enum FileStreamState {
         future: FileOpenFuture,
         /// The partition values for this file
         partition_values: Vec<ScalarValue>,
+        /// The location of the file, to report errors
+        location: Path,
     },
     /// Scanning the [`BoxStream`] returned by the completion of a [`FileOpenFuture`]
     /// returned by [`FormatReader::open`]
@@ -97,6 +100,10 @@ enum FileStreamState {
         partition_values: Vec<ScalarValue>,
         /// The reader instance
         reader: BoxStream<'static, ArrowResult<RecordBatch>>,
+        /// The location of the file, to report errors
+        location: Path,
+        /// The index of the next batch read from the file
+        batch_index: usize,
     },
     /// Encountered an error
     Error,
@@ -204,6 +211,7 @@ impl<F: FileOpener> FileStream<F> {
                         None => return Poll::Ready(None),
                     };
 
+                    let location = part_file.object_meta.location.clone();
                     let file_meta = FileMeta {
                         object_meta: part_file.object_meta,
                         range: part_file.range,
@@ -217,10 +225,12 @@ impl<F: FileOpener> FileStream<F> {
                             self.state = FileStreamState::Open {
                                 future,
                                 partition_values: part_file.partition_values,
+                                location,
                             }
                         }
                         Err(e) => {
                             self.state = FileStreamState::Error;
+                            let e = e.context(format!("Failed to open file {location}"));
                             return Poll::Ready(Some(Err(e.into())));
                         }
                     }
@@ -228,6 +238,7 @@ impl<F: FileOpener> FileStream<F> {
                 FileStreamState::Open {
                     future,
                     partition_values,
+                    location,
                 } => match ready!(future.poll_unpin(cx)) {
                     Ok(reader) => {
                         self.file_stream_metrics.time_opening.stop();
@@ -235,9 +246,12 @@ impl<F: FileOpener> FileStream<F> {
                         self.state = FileStreamState::Scan {
                             partition_values: std::mem::take(partition_values),
                             reader,
+                            location: location.clone(),
+                            batch_index: 0,
                         };
                     }
                     Err(e) => {
+                        let e = e.context(format!("Failed to open file {location}"));
                         self.state = FileStreamState::Error;
                         return Poll::Ready(Some(Err(e.into())));
                     }
@@ -245,11 +259,22 @@ impl<F: FileOpener> FileStream<F> {
                 FileStreamState::Scan {
                     reader,
                     partition_values,
+                    location,
+                    batch_index,
                 } => match ready!(reader.poll_next_unpin(cx)) {
                     Some(result) => {
                         self.file_stream_metrics.time_scanning.stop();
+                        let index = *batch_index;
+                        *batch_index += 1;
                         let result = result
                             .and_then(|b| self.pc_projector.project(b, partition_values))
+                            .map_err(|e| {
+                                DataFusionError::from(e)
+                                    .context(format!(
+                                        "Failed to read batch {index} of file {location}"
+                                    ))
+                                    .into()
+                            })
                             .map(|batch| match &mut self.remain {
                                 Some(remain) => {
                                     if *remain > batch.num_rows() {
@@ -311,6 +336,7 @@ mod tests {
     use super::*;
     use crate::datasource::object_store::ObjectStoreUrl;
     use crate::physical_plan::metrics::ExecutionPlanMetricsSet;
+    use crate::physical_plan::Statistics;
     use crate::prelude::SessionContext;
     use crate::{
         error::Result,
@@ -341,12 +367,12 @@ mod tests {
 
         let config = FileScanConfig {
             object_store_url: ObjectStoreUrl::parse("test:///").unwrap(),
-            file_schema,
+            file_schema: file_schema.clone(),
             file_groups: vec![vec![
                 PartitionedFile::new("mock_file1".to_owned(), 10),
                 PartitionedFile::new("mock_file2".to_owned(), 20),
             ]],
-            statistics: Default::default(),
+            statistics: Statistics::new_unknown(&file_schema),
             projection: None,
             limit,
             table_partition_cols: vec![],
@@ -72,6 +72,16 @@ impl NdJsonExec {
             file_compression_type,
         }
     }
+
+    /// Ref to the base configs
+    pub fn base_config(&self) -> &FileScanConfig {
+        &self.base_config
+    }
+
+    /// The compression type of the scanned files
+    pub fn file_compression_type(&self) -> &FileCompressionType {
+        &self.file_compression_type
+    }
 }
 
 impl ExecutionPlan for NdJsonExec {
@@ -123,9 +133,7 @@ impl ExecutionPlan for NdJsonExec {
             options
         };
 
-        let object_store = context
-            .runtime_env()
-            .object_store(&self.base_config.object_store_url)?;
+        let object_store = context.object_store(&self.base_config.object_store_url)?;
         let opener = JsonOpener {
             file_schema,
             options,
@@ -159,6 +167,10 @@ impl ExecutionPlan for NdJsonExec {
     fn statistics(&self) -> Statistics {
         self.projected_statistics.clone()
     }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        self.base_config.partition_statistics(partition)
+    }
 }
 
 struct JsonOpener {
@@ -175,29 +187,39 @@ impl FileOpener for JsonOpener {
         let store = self.object_store.clone();
         let file_compression_type = self.file_compression_type.to_owned();
         Ok(Box::pin(async move {
-            match store.get(file_meta.location()).await? {
-                GetResult::File(file, _) => {
-                    let decoder = file_compression_type.convert_read(file)?;
-                    let reader = json::Reader::new(decoder, schema.clone(), options);
-                    Ok(futures::stream::iter(reader).boxed())
-                }
-                GetResult::Stream(s) => {
-                    let s = s.map_err(Into::into);
-                    let decoder = file_compression_type.convert_stream(s)?;
-
-                    Ok(newline_delimited_stream(decoder)
-                        .map_ok(move |bytes| {
-                            let reader = json::Reader::new(
-                                bytes.reader(),
-                                schema.clone(),
-                                options.clone(),
-                            );
-                            futures::stream::iter(reader)
-                        })
-                        .try_flatten()
-                        .boxed())
+            let decoder = match &file_meta.range {
+                Some(range) if file_compression_type.is_splittable() => {
+                    file_compression_type.convert_range(
+                        store,
+                        file_meta.location().clone(),
+                        file_meta.object_meta.size,
+                        range.start as usize..range.end as usize,
+                    )?
                 }
-            }
+                _ => match store.get(file_meta.location()).await? {
+                    GetResult::File(file, _) => {
+                        let decoder = file_compression_type.convert_read(file)?;
+                        let reader = json::Reader::new(decoder, schema.clone(), options);
+                        return Ok(futures::stream::iter(reader).boxed());
+                    }
+                    GetResult::Stream(s) => {
+                        let s = s.map_err(Into::into);
+                        file_compression_type.convert_stream(s)?.boxed()
+                    }
+                },
+            };
+
+            Ok(newline_delimited_stream(decoder)
+                .map_ok(move |bytes| {
+                    let reader = json::Reader::new(
+                        bytes.reader(),
+                        schema.clone(),
+                        options.clone(),
+                    );
+                    futures::stream::iter(reader)
+                })
+                .try_flatten()
+                .boxed())
         }))
     }
 }
@@ -361,7 +383,9 @@ mod tests {
         case(FileCompressionType::UNCOMPRESSED),
         case(FileCompressionType::GZIP),
         case(FileCompressionType::BZIP2),
-        case(FileCompressionType::XZ)
+        case(FileCompressionType::XZ),
+        case(FileCompressionType::ZSTD),
+        case(FileCompressionType::BGZIP)
     )]
     #[tokio::test]
     async fn nd_json_exec_file_without_projection(
@@ -379,8 +403,8 @@ mod tests {
             FileScanConfig {
                 object_store_url,
                 file_groups,
+                statistics: Statistics::new_unknown(&file_schema),
                 file_schema,
-                statistics: Statistics::default(),
                 projection: None,
                 limit: Some(3),
                 table_partition_cols: vec![],
@@ -454,8 +478,8 @@ mod tests {
             FileScanConfig {
                 object_store_url,
                 file_groups,
+                statistics: Statistics::new_unknown(&file_schema),
                 file_schema,
-                statistics: Statistics::default(),
                 projection: None,
                 limit: Some(3),
                 table_partition_cols: vec![],
@@ -499,8 +523,8 @@ mod tests {
             FileScanConfig {
                 object_store_url,
                 file_groups,
+                statistics: Statistics::new_unknown(&file_schema),
                 file_schema,
-                statistics: Statistics::default(),
                 projection: Some(vec![0, 2]),
                 limit: None,
                 table_partition_cols: vec![],
@@ -17,6 +17,7 @@
 
 //! Execution plans that read file formats
 
+mod arrow_file;
 mod avro;
 #[cfg(test)]
 mod chunked_store;
@@ -38,7 +39,10 @@ use arrow::{
     error::{ArrowError, Result as ArrowResult},
     record_batch::RecordBatch,
 };
+pub(crate) use arrow_file::plan_to_arrow;
+pub use arrow_file::ArrowExec;
 pub use avro::AvroExec;
+use datafusion_physical_expr::expressions::Column;
 use datafusion_physical_expr::PhysicalSortExpr;
 pub use file_stream::{FileOpenFuture, FileOpener, FileStream};
 pub(crate) use json::plan_to_json;
@@ -64,7 +68,7 @@ use std::{
     vec,
 };
 
-use super::{ColumnStatistics, Statistics};
+use super::{ColumnStatistics, Precision, Statistics};
 
 /// convert logical type of partition column to physical type: Dictionary(UInt16, val_type)
 pub fn partition_type_wrap(val_type: DataType) -> DataType {
@@ -112,51 +116,97 @@ impl FileScanConfig {
     /// Project the schema and the statistics on the given column indices
     fn project(&self) -> (SchemaRef, Statistics) {
         if self.projection.is_none() && self.table_partition_cols.is_empty() {
-            return (Arc::clone(&self.file_schema), self.statistics.clone());
+            return (
+                Arc::clone(&self.file_schema),
+                self.project_statistics(self.statistics.clone()),
+            );
         }
 
-        let proj_iter: Box<dyn Iterator<Item = usize>> = match &self.projection {
-            Some(proj) => Box::new(proj.iter().copied()),
-            None => Box::new(
-                0..(self.file_schema.fields().len() + self.table_partition_cols.len()),
-            ),
-        };
-
-        let mut table_fields = vec![];
-        let mut table_cols_stats = vec![];
-        for idx in proj_iter {
-            if idx < self.file_schema.fields().len() {
-                table_fields.push(self.file_schema.field(idx).clone());
-                if let Some(file_cols_stats) = &self.statistics.column_statistics {
-                    table_cols_stats.push(file_cols_stats[idx].clone())
+        let table_fields = self
+            .projection_indices()
+            .map(|idx| {
+                if idx < self.file_schema.fields().len() {
+                    self.file_schema.field(idx).clone()
                 } else {
-                    table_cols_stats.push(ColumnStatistics::default())
+                    let partition_idx = idx - self.file_schema.fields().len();
+                    Field::new(
+                        &self.table_partition_cols[partition_idx].0,
+                        self.table_partition_cols[partition_idx].1.to_owned(),
+                        false,
+                    )
                 }
-            } else {
-                let partition_idx = idx - self.file_schema.fields().len();
-                table_fields.push(Field::new(
-                    &self.table_partition_cols[partition_idx].0,
-                    self.table_partition_cols[partition_idx].1.to_owned(),
-                    false,
-                ));
-                // TODO provide accurate stat for partition column (#1186)
-                table_cols_stats.push(ColumnStatistics::default())
-            }
-        }
-
-        let table_stats = Statistics {
-            num_rows: self.statistics.num_rows,
-            is_exact: self.statistics.is_exact,
-            // TODO correct byte size?
-            total_byte_size: None,
-            column_statistics: Some(table_cols_stats),
-        };
+            })
+            .collect();
 
         let table_schema = Arc::new(
             Schema::new(table_fields).with_metadata(self.file_schema.metadata().clone()),
         );
 
-        (table_schema, table_stats)
+        (
+            table_schema,
+            self.project_statistics(self.statistics.clone()),
+        )
+    }
+
+    /// Returns the statistics of the files in output partition `partition`,
+    /// projected on the output columns. The statistics are unknown unless they
+    /// were collected for every file of the partition.
+    pub(crate) fn partition_statistics(&self, partition: usize) -> Statistics {
+        let file_stats = self.file_groups.get(partition).and_then(|files| {
+            files
+                .iter()
+                .map(|file| match file.range {
+                    None => file.statistics.clone(),
+                    Some(_) => None,
+                })
+                .reduce(|acc, stats| Some(acc?.merge(&stats?)))
+                .flatten()
+        });
+        let file_stats =
+            file_stats.unwrap_or_else(|| Statistics::new_unknown(&self.file_schema));
+        self.project_statistics(file_stats)
+    }
+
+    /// Projects statistics about the files on the output columns
+    fn project_statistics(&self, file_stats: Statistics) -> Statistics {
+        let file_stats = if self.limit.is_some() {
+            // only a prefix of the rows may be read
+            file_stats.into_inexact()
+        } else {
+            file_stats
+        };
+
+        if self.projection.is_none() && self.table_partition_cols.is_empty() {
+            return file_stats;
+        }
+
+        let column_statistics = self
+            .projection_indices()
+            .map(|idx| match file_stats.column_statistics.get(idx) {
+                Some(col_stats) if idx < self.file_schema.fields().len() => {
+                    col_stats.clone()
+                }
+                // TODO provide accurate stat for partition column (#1186)
+                _ => ColumnStatistics::new_unknown(),
+            })
+            .collect();
+
+        Statistics {
+            num_rows: file_stats.num_rows,
+            // TODO correct byte size?
+            total_byte_size: Precision::Absent,
+            column_statistics,
+        }
+    }
+
+    /// Indices of the output columns, including the partition columns
+    fn projection_indices(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        match &self.projection {
+            Some(proj) => Box::new(proj.iter().copied()),
+            None => Box::new(
+                0..(self.file_schema.fields().len() + self.table_partition_cols.len()),
+            ),
+        }
     }
 
     fn projected_file_column_names(&self) -> Option<Vec<String>> {
@@ -522,12 +572,19 @@ impl From<ObjectMeta> for FileMeta {
 ///
 ///              ParquetExec
 ///```
+///
+/// Unless the files of each partition don't overlap and are in order on the
+/// leading sort column, according to their partition values or their exact
+/// statistics, for example daily partitions of a table sorted on their `date`.
 pub(crate) fn get_output_ordering(
     base_config: &FileScanConfig,
 ) -> Option<&[PhysicalSortExpr]> {
+    let num_file_columns = base_config.file_schema.fields().len();
     base_config.output_ordering.as_ref()
-        .map(|output_ordering| if base_config.file_groups.iter().any(|group| group.len() > 1) {
-            debug!("Skipping specified output ordering {:?}. Some file group had more than one file: {:?}",
+        .map(|output_ordering| if base_config.file_groups.iter().any(|group| {
+            group.len() > 1 && !files_ordered(group, output_ordering, num_file_columns)
+        }) {
+            debug!("Skipping specified output ordering {:?}. Some file group had more than one file, not ordered: {:?}",
                    output_ordering, base_config.file_groups);
             None
         } else {
@@ -535,6 +592,72 @@ pub(crate) fn get_output_ordering(
         }).unwrap_or_else(|| None)
 }
 
+/// Returns the minimum and maximum values of the column `idx` of the table in
+/// `file`, from its partition values or its exact statistics, if known and not
+/// null. The columns after the `num_file_columns` columns of the files are the
+/// partition columns.
+pub(crate) fn file_value_range(
+    file: &PartitionedFile,
+    idx: usize,
+    num_file_columns: usize,
+) -> Option<(ScalarValue, ScalarValue)> {
+    if idx >= num_file_columns {
+        let value = file.partition_values.get(idx - num_file_columns)?;
+        return (!value.is_null()).then(|| (value.clone(), value.clone()));
+    }
+    let statistics = file.statistics.as_ref()?.column_statistics.get(idx)?;
+    match (
+        &statistics.min_value,
+        &statistics.max_value,
+        &statistics.null_count,
+    ) {
+        (Precision::Exact(min), Precision::Exact(max), Precision::Exact(0)) => {
+            Some((min.clone(), max.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if the files of `group` are in order and don't overlap on the
+/// leading expression of `ordering`, which must be a column, so that reading them
+/// one after the other preserves the `ordering`
+fn files_ordered(
+    group: &[PartitionedFile],
+    ordering: &[PhysicalSortExpr],
+    num_file_columns: usize,
+) -> bool {
+    let first = match ordering.first() {
+        Some(first) => first,
+        None => return true,
+    };
+    let column = match first.expr.as_any().downcast_ref::<Column>() {
+        Some(column) => column,
+        None => return false,
+    };
+    let ranges = group
+        .iter()
+        .map(|file| file_value_range(file, column.index(), num_file_columns))
+        .collect::<Option<Vec<_>>>();
+    let ranges = match ranges {
+        Some(ranges) => ranges,
+        None => return false,
+    };
+
+    // the files may share a value of the leading column only if it is the only one
+    let strict = ordering.len() > 1;
+    ranges.windows(2).all(|pair| {
+        let (prev, next) = match first.options.descending {
+            false => (&pair[0], &pair[1]),
+            true => (&pair[1], &pair[0]),
+        };
+        match prev.1.partial_cmp(&next.0) {
+            Some(std::cmp::Ordering::Less) => true,
+            Some(std::cmp::Ordering::Equal) => !strict,
+            _ => false,
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::Utc;
@@ -552,7 +675,7 @@ mod tests {
         let conf = config_for_projection(
             Arc::clone(&file_schema),
             None,
-            Statistics::default(),
+            Statistics::new_unknown(&file_schema),
             vec![("date".to_owned(), partition_type_wrap(DataType::Utf8))],
         );
 
@@ -564,10 +687,7 @@ mod tests {
             "partition columns are the last columns"
         );
         assert_eq!(
-            proj_statistics
-                .column_statistics
-                .expect("projection creates column statistics")
-                .len(),
+            proj_statistics.column_statistics.len(),
             file_schema.fields().len() + 1
         );
         // TODO implement tests for partition column statistics once implemented
@@ -586,18 +706,16 @@ mod tests {
             Arc::clone(&file_schema),
             Some(vec![file_schema.fields().len(), 0]),
             Statistics {
-                num_rows: Some(10),
+                num_rows: Precision::Exact(10),
+                total_byte_size: Precision::Absent,
                 // assign the column index to distinct_count to help assert
                 // the source statistic after the projection
-                column_statistics: Some(
-                    (0..file_schema.fields().len())
-                        .map(|i| ColumnStatistics {
-                            distinct_count: Some(i),
-                            ..Default::default()
-                        })
-                        .collect(),
-                ),
-                ..Default::default()
+                column_statistics: (0..file_schema.fields().len())
+                    .map(|i| ColumnStatistics {
+                        distinct_count: Precision::Exact(i),
+                        ..Default::default()
+                    })
+                    .collect(),
             },
             vec![("date".to_owned(), partition_type_wrap(DataType::Utf8))],
         );
@@ -607,13 +725,12 @@ mod tests {
             columns(&proj_schema),
             vec!["date".to_owned(), "c1".to_owned()]
         );
-        let proj_stat_cols = proj_statistics
-            .column_statistics
-            .expect("projection creates column statistics");
+        assert_eq!(proj_statistics.num_rows, Precision::Exact(10));
+        let proj_stat_cols = proj_statistics.column_statistics;
         assert_eq!(proj_stat_cols.len(), 2);
         // TODO implement tests for proj_stat_cols[0] once partition column
         // statistics are implemented
-        assert_eq!(proj_stat_cols[1].distinct_count, Some(0));
+        assert_eq!(proj_stat_cols[1].distinct_count, Precision::Exact(0));
 
         let col_names = conf.projected_file_column_names();
         assert_eq!(col_names, Some(vec!["c1".to_owned()]));
@@ -622,6 +739,65 @@ mod tests {
         assert_eq!(col_indices, Some(vec![0]));
     }
 
+    #[test]
+    fn physical_plan_config_partition_statistics() {
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let file_stats = |rows: usize, min: i32, max: i32| Statistics {
+            num_rows: Precision::Exact(rows),
+            total_byte_size: Precision::Exact(rows * 4),
+            column_statistics: vec![ColumnStatistics {
+                null_count: Precision::Exact(0),
+                max_value: Precision::Exact(ScalarValue::Int32(Some(max))),
+                min_value: Precision::Exact(ScalarValue::Int32(Some(min))),
+                distinct_count: Precision::Absent,
+            }],
+        };
+        let file = |name: &str, statistics: Option<Statistics>| PartitionedFile {
+            statistics,
+            ..PartitionedFile::new(name.to_owned(), 10)
+        };
+
+        let mut conf = config_for_projection(
+            Arc::clone(&file_schema),
+            None,
+            file_stats(30, 1, 30),
+            vec![],
+        );
+        conf.file_groups = vec![
+            vec![
+                file("a", Some(file_stats(10, 1, 5))),
+                file("b", Some(file_stats(15, 3, 30))),
+            ],
+            vec![file("c", Some(file_stats(5, 7, 8))), file("d", None)],
+        ];
+
+        let stats = conf.partition_statistics(0);
+        assert_eq!(stats.num_rows, Precision::Exact(25));
+        assert_eq!(stats.total_byte_size, Precision::Exact(100));
+        assert_eq!(
+            stats.column_statistics[0].min_value,
+            Precision::Exact(ScalarValue::Int32(Some(1)))
+        );
+        assert_eq!(
+            stats.column_statistics[0].max_value,
+            Precision::Exact(ScalarValue::Int32(Some(30)))
+        );
+
+        // the statistics of one of the files are missing
+        assert_eq!(
+            conf.partition_statistics(1),
+            Statistics::new_unknown(&file_schema)
+        );
+
+        // a limit only reads a prefix of the files
+        conf.limit = Some(5);
+        assert_eq!(
+            conf.partition_statistics(0).num_rows,
+            Precision::Inexact(25)
+        );
+    }
+
     #[test]
     fn partition_column_projector() {
         let file_batch = build_table_i32(
@@ -645,7 +821,7 @@ mod tests {
                 file_batch.schema().fields().len(),
                 file_batch.schema().fields().len() + 2,
             ]),
-            Statistics::default(),
+            Statistics::new_unknown(&file_batch.schema()),
             partition_cols.clone(),
         );
         let (proj_schema, _) = conf.project();
@@ -664,9 +840,9 @@ mod tests {
                 // file_batch is ok here because we kept all the file cols in the projection
                 file_batch,
                 &[
-                    ScalarValue::Utf8(Some("2021".to_owned())),
-                    ScalarValue::Utf8(Some("10".to_owned())),
-                    ScalarValue::Utf8(Some("26".to_owned())),
+                    ScalarValue::Utf8(Some("2021".into())),
+                    ScalarValue::Utf8(Some("10".into())),
+                    ScalarValue::Utf8(Some("26".into())),
                 ],
             )
             .expect("Projection of partition columns into record batch failed");
@@ -692,9 +868,9 @@ mod tests {
                 // file_batch is ok here because we kept all the file cols in the projection
                 file_batch,
                 &[
-                    ScalarValue::Utf8(Some("2021".to_owned())),
-                    ScalarValue::Utf8(Some("10".to_owned())),
-                    ScalarValue::Utf8(Some("27".to_owned())),
+                    ScalarValue::Utf8(Some("2021".into())),
+                    ScalarValue::Utf8(Some("10".into())),
+                    ScalarValue::Utf8(Some("27".into())),
                 ],
             )
             .expect("Projection of partition columns into record batch failed");
@@ -722,9 +898,9 @@ mod tests {
                 // file_batch is ok here because we kept all the file cols in the projection
                 file_batch,
                 &[
-                    ScalarValue::Utf8(Some("2021".to_owned())),
-                    ScalarValue::Utf8(Some("10".to_owned())),
-                    ScalarValue::Utf8(Some("28".to_owned())),
+                    ScalarValue::Utf8(Some("2021".into())),
+                    ScalarValue::Utf8(Some("10".into())),
+                    ScalarValue::Utf8(Some("28".into())),
                 ],
             )
             .expect("Projection of partition columns into record batch failed");
@@ -854,6 +1030,52 @@ mod tests {
             partition_values: vec![],
             range: None,
             extensions: None,
+            statistics: None,
         }
     }
+
+    #[test]
+    fn output_ordering_of_ordered_files() {
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("c1", DataType::Int32, true)]));
+        let date = |d: &str| {
+            let mut file = partitioned_file(&format!("date={d}/file.csv"));
+            file.partition_values = vec![ScalarValue::from(d)];
+            file
+        };
+        let sort_expr = |name: &str, index: usize, descending: bool| PhysicalSortExpr {
+            expr: Arc::new(Column::new(name, index)),
+            options: arrow::compute::SortOptions {
+                descending,
+                nulls_first: false,
+            },
+        };
+
+        let mut config = config_for_projection(
+            file_schema,
+            None,
+            Statistics::new_unknown(&Schema::empty()),
+            vec![("date".to_owned(), DataType::Utf8)],
+        );
+        config.file_groups = vec![
+            vec![date("2023-01-01"), date("2023-01-02")],
+            vec![date("2023-01-03")],
+        ];
+
+        // daily partitions in order on their date
+        config.output_ordering = Some(vec![sort_expr("date", 1, false)]);
+        assert!(get_output_ordering(&config).is_some());
+
+        // in reverse order, or sorted on a column without statistics
+        config.output_ordering = Some(vec![sort_expr("date", 1, true)]);
+        assert!(get_output_ordering(&config).is_none());
+        config.output_ordering = Some(vec![sort_expr("c1", 0, false)]);
+        assert!(get_output_ordering(&config).is_none());
+
+        // files sharing a value of the leading column with a second sort column
+        config.file_groups = vec![vec![date("2023-01-01"), date("2023-01-01")]];
+        config.output_ordering =
+            Some(vec![sort_expr("date", 1, false), sort_expr("c1", 0, false)]);
+        assert!(get_output_ordering(&config).is_none());
+    }
 }
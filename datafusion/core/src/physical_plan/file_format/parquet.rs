@@ -145,6 +145,8 @@ impl ParquetExec {
         let predicate = predicate.map(Arc::new);
 
         let (projected_schema, projected_statistics) = base_config.project();
+        let projected_statistics =
+            relax_pruned_statistics(projected_statistics, &predicate);
 
         Self {
             pushdown_filters: None,
@@ -280,11 +282,16 @@ impl ExecutionPlan for ParquetExec {
             .as_ref()
             .map(|f| Ok(Arc::clone(f)))
             .unwrap_or_else(|| {
-                ctx.runtime_env()
-                    .object_store(&self.base_config.object_store_url)
+                let options = &ctx.session_config().config_options().execution.parquet;
+                let coalesce_gap = options
+                    .coalesce_ranges
+                    .then_some(options.coalesce_range_gap);
+                ctx.object_store(&self.base_config.object_store_url)
                     .map(|store| {
-                        Arc::new(DefaultParquetFileReaderFactory::new(store))
-                            as Arc<dyn ParquetFileReaderFactory>
+                        Arc::new(
+                            DefaultParquetFileReaderFactory::new(store)
+                                .with_coalesce_gap(coalesce_gap),
+                        ) as Arc<dyn ParquetFileReaderFactory>
                     })
             })?;
 
@@ -361,6 +368,25 @@ impl ExecutionPlan for ParquetExec {
     fn statistics(&self) -> Statistics {
         self.projected_statistics.clone()
     }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        relax_pruned_statistics(
+            self.base_config.partition_statistics(partition),
+            &self.predicate,
+        )
+    }
+}
+
+/// Row groups, pages and rows may be skipped using the predicate, so the
+/// statistics of the files are only upper bounds of what is actually read
+fn relax_pruned_statistics(
+    statistics: Statistics,
+    predicate: &Option<Arc<Expr>>,
+) -> Statistics {
+    match predicate {
+        Some(_) => statistics.into_inexact(),
+        None => statistics,
+    }
 }
 
 fn make_output_ordering_string(ordering: &[PhysicalSortExpr]) -> String {
@@ -522,11 +548,22 @@ pub trait ParquetFileReaderFactory: Debug + Send + Sync + 'static {
 #[derive(Debug)]
 pub struct DefaultParquetFileReaderFactory {
     store: Arc<dyn ObjectStore>,
+    coalesce_gap: Option<usize>,
 }
 
 impl DefaultParquetFileReaderFactory {
     pub fn new(store: Arc<dyn ObjectStore>) -> Self {
-        Self { store }
+        Self {
+            store,
+            coalesce_gap: None,
+        }
+    }
+
+    /// Coalesce the byte ranges read together that are less than `coalesce_gap`
+    /// bytes apart into a single request, if specified
+    pub fn with_coalesce_gap(mut self, coalesce_gap: Option<usize>) -> Self {
+        self.coalesce_gap = coalesce_gap;
+        self
     }
 }
 
@@ -536,6 +573,7 @@ struct ParquetFileReader {
     meta: ObjectMeta,
     file_metrics: ParquetFileMetrics,
     metadata_size_hint: Option<usize>,
+    coalesce_gap: Option<usize>,
 }
 
 impl AsyncFileReader for ParquetFileReader {
@@ -564,14 +602,35 @@ impl AsyncFileReader for ParquetFileReader {
         self.file_metrics.bytes_scanned.add(total);
 
         async move {
-            self.store
-                .get_ranges(&self.meta.location, &ranges)
-                .await
-                .map_err(|e| {
-                    ParquetError::General(format!(
-                        "AsyncChunkReader::get_byte_ranges error: {e}"
-                    ))
-                })
+            let map_err = |e| {
+                ParquetError::General(format!(
+                    "AsyncChunkReader::get_byte_ranges error: {e}"
+                ))
+            };
+            match self.coalesce_gap {
+                Some(gap) => {
+                    let coalesced = coalesce_ranges(&ranges, gap);
+                    let fetched = self
+                        .store
+                        .get_ranges(&self.meta.location, &coalesced)
+                        .await
+                        .map_err(map_err)?;
+                    Ok(ranges
+                        .iter()
+                        .map(|range| {
+                            let idx =
+                                coalesced.partition_point(|r| r.start <= range.start) - 1;
+                            let start = coalesced[idx].start;
+                            fetched[idx].slice(range.start - start..range.end - start)
+                        })
+                        .collect())
+                }
+                None => self
+                    .store
+                    .get_ranges(&self.meta.location, &ranges)
+                    .await
+                    .map_err(map_err),
+            }
         }
         .boxed()
     }
@@ -596,6 +655,24 @@ impl AsyncFileReader for ParquetFileReader {
     }
 }
 
+/// Merges the `ranges` less than `gap` bytes apart, returning the merged ranges
+/// sorted by their start
+fn coalesce_ranges(ranges: &[Range<usize>], gap: usize) -> Vec<Range<usize>> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_unstable_by_key(|range| range.start);
+
+    let mut coalesced: Vec<Range<usize>> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match coalesced.last_mut() {
+            Some(last) if range.start <= last.end + gap => {
+                last.end = last.end.max(range.end)
+            }
+            _ => coalesced.push(range),
+        }
+    }
+    coalesced
+}
+
 impl ParquetFileReaderFactory for DefaultParquetFileReaderFactory {
     fn create_reader(
         &self,
@@ -615,6 +692,7 @@ impl ParquetFileReaderFactory for DefaultParquetFileReaderFactory {
             store: Arc::clone(&self.store),
             metadata_size_hint,
             file_metrics,
+            coalesce_gap: self.coalesce_gap,
         }))
     }
 }
@@ -801,8 +879,8 @@ mod tests {
             FileScanConfig {
                 object_store_url: ObjectStoreUrl::local_filesystem(),
                 file_groups: vec![file_groups],
+                statistics: Statistics::new_unknown(&file_schema),
                 file_schema,
-                statistics: Statistics::default(),
                 projection,
                 limit: None,
                 table_partition_cols: vec![],
@@ -1326,6 +1404,7 @@ mod tests {
                 partition_values: vec![],
                 range: Some(FileRange { start, end }),
                 extensions: None,
+                statistics: None,
             }
         }
 
@@ -1339,8 +1418,8 @@ mod tests {
                 FileScanConfig {
                     object_store_url: ObjectStoreUrl::local_filesystem(),
                     file_groups,
+                    statistics: Statistics::new_unknown(&file_schema),
                     file_schema,
-                    statistics: Statistics::default(),
                     projection: None,
                     limit: None,
                     table_partition_cols: vec![],
@@ -1412,20 +1491,21 @@ mod tests {
         let partitioned_file = PartitionedFile {
             object_meta: meta,
             partition_values: vec![
-                ScalarValue::Utf8(Some("2021".to_owned())),
-                ScalarValue::Utf8(Some("10".to_owned())),
-                ScalarValue::Utf8(Some("26".to_owned())),
+                ScalarValue::Utf8(Some("2021".into())),
+                ScalarValue::Utf8(Some("10".into())),
+                ScalarValue::Utf8(Some("26".into())),
             ],
             range: None,
             extensions: None,
+            statistics: None,
         };
 
         let parquet_exec = ParquetExec::new(
             FileScanConfig {
                 object_store_url,
                 file_groups: vec![vec![partitioned_file]],
+                statistics: Statistics::new_unknown(&schema),
                 file_schema: schema,
-                statistics: Statistics::default(),
                 // file has 10 cols so index 12 should be month
                 projection: Some(vec![0, 1, 2, 12]),
                 limit: None,
@@ -1483,6 +1563,7 @@ mod tests {
             partition_values: vec![],
             range: None,
             extensions: None,
+            statistics: None,
         };
 
         let parquet_exec = ParquetExec::new(
@@ -1490,7 +1571,7 @@ mod tests {
                 object_store_url: ObjectStoreUrl::local_filesystem(),
                 file_groups: vec![vec![partitioned_file]],
                 file_schema: Arc::new(Schema::empty()),
-                statistics: Statistics::default(),
+                statistics: Statistics::new_unknown(&Schema::empty()),
                 projection: None,
                 limit: None,
                 table_partition_cols: vec![],
@@ -1760,4 +1841,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn coalesce_byte_ranges() {
+        let ranges = vec![20..30, 0..10, 12..15, 100..110, 25..40];
+        assert_eq!(
+            coalesce_ranges(&ranges, 0),
+            vec![0..10, 12..15, 20..40, 100..110]
+        );
+        assert_eq!(coalesce_ranges(&ranges, 5), vec![0..40, 100..110]);
+        assert_eq!(coalesce_ranges(&ranges, 100), vec![0..110]);
+        assert!(coalesce_ranges(&[], 100).is_empty());
+    }
+
+    #[tokio::test]
+    async fn read_coalesced_byte_ranges() -> Result<()> {
+        let store = Arc::new(object_store::memory::InMemory::new());
+        let location = Path::from("file");
+        let data = Bytes::from((0..200u8).collect::<Vec<_>>());
+        store.put(&location, data.clone()).await?;
+        let meta = store.head(&location).await?;
+
+        let metrics = ExecutionPlanMetricsSet::new();
+        let mut reader = ParquetFileReader {
+            store,
+            file_metrics: ParquetFileMetrics::new(0, meta.location.as_ref(), &metrics),
+            meta,
+            metadata_size_hint: None,
+            coalesce_gap: Some(10),
+        };
+        let ranges = vec![50..60, 0..10, 15..30, 150..200];
+        let bytes = reader.get_byte_ranges(ranges.clone()).await?;
+        for (range, bytes) in ranges.into_iter().zip(bytes) {
+            assert_eq!(bytes, data.slice(range));
+        }
+        Ok(())
+    }
 }
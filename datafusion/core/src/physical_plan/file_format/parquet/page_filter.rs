@@ -19,7 +19,7 @@
 
 use arrow::array::{
     BooleanArray, Decimal128Array, Float32Array, Float64Array, Int32Array, Int64Array,
-    StringArray,
+    StringArray, UInt64Array,
 };
 use arrow::datatypes::DataType;
 use arrow::{array::ArrayRef, datatypes::SchemaRef, error::ArrowError};
@@ -240,6 +240,7 @@ fn prune_pages_in_one_row_group(
             col_page_indexes,
             col_offset_indexes,
             target_type: &target_type,
+            num_rows,
         };
 
         match predicate.prune(&pruning_stats) {
@@ -311,6 +312,8 @@ struct PagesPruningStatistics<'a> {
     // target_type means the logical type in schema: like 'DECIMAL' is the logical type, but the
     // real physical type in parquet file may be `INT32, INT64, FIXED_LEN_BYTE_ARRAY`
     target_type: &'a Option<DataType>,
+    // the number of rows in the row group
+    num_rows: usize,
 }
 
 // Extract the min or max value calling `func` from page idex
@@ -466,4 +469,12 @@ impl<'a> PruningStatistics for PagesPruningStatistics<'a> {
             ))),
         }
     }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        let row_counts =
+            create_row_count_in_each_page(self.col_offset_indexes, self.num_rows);
+        Some(Arc::new(UInt64Array::from_iter_values(
+            row_counts.into_iter().map(|count| count as u64),
+        )))
+    }
 }
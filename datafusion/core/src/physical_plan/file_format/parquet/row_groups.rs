@@ -142,9 +142,7 @@ macro_rules! get_statistic {
                         ))
                     }
                     _ => {
-                        let s = std::str::from_utf8(s.$bytes_func())
-                            .map(|s| s.to_string())
-                            .ok();
+                        let s = std::str::from_utf8(s.$bytes_func()).map(Into::into).ok();
                         Some(ScalarValue::Utf8(s))
                     }
                 }
@@ -235,6 +233,11 @@ impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
     fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
         get_null_count_values!(self, column)
     }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        let num_rows = self.row_group_metadata.num_rows() as u64;
+        Some(ScalarValue::UInt64(Some(num_rows)).to_array())
+    }
 }
 
 #[cfg(test)]
@@ -243,7 +246,7 @@ mod tests {
     use crate::physical_plan::metrics::ExecutionPlanMetricsSet;
     use arrow::datatypes::DataType::Decimal128;
     use arrow::datatypes::Schema;
-    use arrow::datatypes::{DataType, Field};
+    use arrow::datatypes::{DataType, Field, TimeUnit};
     use datafusion_expr::{cast, col, lit};
     use parquet::basic::LogicalType;
     use parquet::data_type::{ByteArray, FixedLenByteArray};
@@ -440,6 +443,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn row_group_pruning_predicate_is_not_null() {
+        use datafusion_expr::col;
+        // IsNotNull(cast(c1 as Int64)) => c1_null_count < c1_row_count
+        let expr = cast(col("c1"), DataType::Int64).is_not_null();
+        let schema = Arc::new(Schema::new(vec![Field::new("c1", DataType::Int32, true)]));
+        let pruning_predicate = PruningPredicate::try_new(expr, schema).unwrap();
+
+        let schema_descr = get_test_schema_descr(vec![(
+            "c1",
+            PhysicalType::INT32,
+            None,
+            None,
+            None,
+            None,
+        )]);
+        // every value of the first row group is null
+        let rgm1 = get_row_group_meta_data(
+            &schema_descr,
+            vec![ParquetStatistics::int32(None, None, None, 1000, false)],
+        );
+        let rgm2 = get_row_group_meta_data(
+            &schema_descr,
+            vec![ParquetStatistics::int32(
+                Some(11),
+                Some(20),
+                None,
+                10,
+                false,
+            )],
+        );
+
+        let metrics = parquet_file_metrics();
+        assert_eq!(
+            prune_row_groups(&[rgm1, rgm2], None, Some(&pruning_predicate), &metrics),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn row_group_pruning_predicate_cast_and_function() {
+        use datafusion_expr::{col, date_trunc, floor, lit};
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("c1", DataType::Int32, false),
+            Field::new("c2", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        ]));
+        let schema_descr = get_test_schema_descr(vec![
+            ("c1", PhysicalType::INT32, None, None, None, None),
+            ("c2", PhysicalType::INT64, None, None, None, None),
+        ]);
+        let hour = 3_600_000_000_000;
+        let day = 24 * hour;
+        let rgm1 = get_row_group_meta_data(
+            &schema_descr,
+            vec![
+                ParquetStatistics::int32(Some(1), Some(10), None, 0, false),
+                ParquetStatistics::int64(Some(hour), Some(20 * hour), None, 0, false),
+            ],
+        );
+        let rgm2 = get_row_group_meta_data(
+            &schema_descr,
+            vec![
+                ParquetStatistics::int32(Some(11), Some(20), None, 0, false),
+                ParquetStatistics::int64(Some(day), Some(day + hour), None, 0, false),
+            ],
+        );
+        let groups = &[rgm1, rgm2];
+        let metrics = parquet_file_metrics();
+
+        // floor(cast(c1 as float64)) > 10.5 => floor(cast(c1_max as float64)) > 10.5
+        let expr = floor(cast(col("c1"), DataType::Float64)).gt(lit(10.5f64));
+        let pruning_predicate = PruningPredicate::try_new(expr, schema.clone()).unwrap();
+        assert_eq!(
+            prune_row_groups(groups, None, Some(&pruning_predicate), &metrics),
+            vec![1]
+        );
+
+        // date_trunc('day', c2) = '1970-01-01'
+        let expr = date_trunc(lit("day"), col("c2"))
+            .eq(lit(ScalarValue::TimestampNanosecond(Some(0), None)));
+        let pruning_predicate = PruningPredicate::try_new(expr, schema).unwrap();
+        assert_eq!(
+            prune_row_groups(groups, None, Some(&pruning_predicate), &metrics),
+            vec![0]
+        );
+    }
+
     #[test]
     fn row_group_pruning_predicate_decimal_type() {
         // For the decimal data type, parquet can use `INT32`, `INT64`, `BYTE_ARRAY`, `FIXED_LENGTH_BYTE_ARRAY` to
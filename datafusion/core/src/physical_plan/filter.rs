@@ -24,7 +24,9 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use super::expressions::PhysicalSortExpr;
-use super::{ColumnStatistics, RecordBatchStream, SendableRecordBatchStream, Statistics};
+use super::{
+    ColumnStatistics, Precision, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
 use crate::error::{DataFusionError, Result};
 use crate::physical_plan::{
     metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet},
@@ -32,13 +34,15 @@ use crate::physical_plan::{
     PhysicalExpr,
 };
 use arrow::compute::filter_record_batch;
-use arrow::datatypes::{DataType, SchemaRef};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
 use arrow::error::Result as ArrowResult;
 use arrow::record_batch::RecordBatch;
 use datafusion_common::cast::as_boolean_array;
 use datafusion_expr::Operator;
-use datafusion_physical_expr::expressions::BinaryExpr;
-use datafusion_physical_expr::{split_conjunction, AnalysisContext};
+use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
+use datafusion_physical_expr::{
+    evaluation_error_context, split_conjunction, AnalysisContext,
+};
 
 use log::debug;
 
@@ -130,6 +134,11 @@ impl ExecutionPlan for FilterExec {
         for new_condition in equal_pairs {
             input_properties.add_equal_conditions(new_condition)
         }
+        input_properties.add_constants(
+            collect_constant_columns(&self.predicate)
+                .into_iter()
+                .cloned(),
+        );
         input_properties
     }
 
@@ -176,50 +185,72 @@ impl ExecutionPlan for FilterExec {
 
     /// The output statistics of a filtering operation can be estimated if the
     /// predicate's selectivity value can be determined for the incoming data.
+    /// Otherwise the input statistics are used as (inexact) upper bounds.
     fn statistics(&self) -> Statistics {
-        let input_stats = self.input.statistics();
-        let starter_ctx =
-            AnalysisContext::from_statistics(self.input.schema().as_ref(), &input_stats);
-
-        let analysis_ctx = self.predicate.analyze(starter_ctx);
-
-        match analysis_ctx.boundaries {
-            Some(boundaries) => {
-                // Build back the column level statistics from the boundaries inside the
-                // analysis context. It is possible that these are going to be different
-                // than the input statistics, especially when a comparison is made inside
-                // the predicate expression (e.g. `col1 > 100`).
-                let column_statistics = analysis_ctx
-                    .column_boundaries
-                    .iter()
-                    .map(|boundary| match boundary {
-                        Some(boundary) => ColumnStatistics {
-                            min_value: Some(boundary.min_value.clone()),
-                            max_value: Some(boundary.max_value.clone()),
-                            ..Default::default()
-                        },
-                        None => ColumnStatistics::default(),
-                    })
-                    .collect();
-
-                Statistics {
-                    num_rows: input_stats.num_rows.zip(boundaries.selectivity).map(
-                        |(num_rows, selectivity)| {
-                            (num_rows as f64 * selectivity).ceil() as usize
-                        },
-                    ),
-                    total_byte_size: input_stats
-                        .total_byte_size
-                        .zip(boundaries.selectivity)
-                        .map(|(num_rows, selectivity)| {
-                            (num_rows as f64 * selectivity).ceil() as usize
-                        }),
-                    column_statistics: Some(column_statistics),
-                    ..Default::default()
-                }
-            }
-            None => Statistics::default(),
-        }
+        estimate_filter_statistics(
+            &self.predicate,
+            self.input.schema().as_ref(),
+            self.input.statistics(),
+        )
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        estimate_filter_statistics(
+            &self.predicate,
+            self.input.schema().as_ref(),
+            self.input.partition_statistics(partition),
+        )
+    }
+}
+
+/// Estimates the statistics of the output of a filter with the given predicate
+/// and input statistics
+fn estimate_filter_statistics(
+    predicate: &Arc<dyn PhysicalExpr>,
+    input_schema: &Schema,
+    input_stats: Statistics,
+) -> Statistics {
+    let starter_ctx = AnalysisContext::from_statistics(input_schema, &input_stats);
+    let analysis_ctx = predicate.analyze(starter_ctx);
+    let input_stats = input_stats.into_inexact();
+
+    let selectivity = match analysis_ctx.boundaries {
+        Some(boundaries) => boundaries.selectivity,
+        None => return input_stats,
+    };
+
+    // Build back the column level statistics from the boundaries inside the
+    // analysis context. It is possible that these are going to be different
+    // than the input statistics, especially when a comparison is made inside
+    // the predicate expression (e.g. `col1 > 100`).
+    let column_statistics = analysis_ctx
+        .column_boundaries
+        .iter()
+        .zip(input_stats.column_statistics)
+        .map(|(boundary, input)| match boundary {
+            Some(boundary) => ColumnStatistics {
+                min_value: Precision::Inexact(boundary.min_value.clone()),
+                max_value: Precision::Inexact(boundary.max_value.clone()),
+                ..input
+            },
+            None => input,
+        })
+        .collect();
+
+    let (num_rows, total_byte_size) = match selectivity {
+        Some(selectivity) => (
+            input_stats.num_rows.with_estimated_selectivity(selectivity),
+            input_stats
+                .total_byte_size
+                .with_estimated_selectivity(selectivity),
+        ),
+        None => (input_stats.num_rows, input_stats.total_byte_size),
+    };
+
+    Statistics {
+        num_rows,
+        total_byte_size,
+        column_statistics,
     }
 }
 
@@ -242,6 +273,7 @@ fn batch_filter(
 ) -> ArrowResult<RecordBatch> {
     predicate
         .evaluate(batch)
+        .map_err(|e| evaluation_error_context(predicate.as_ref(), batch, e))
         .map(|v| v.into_array(batch.num_rows()))
         .map_err(DataFusionError::into)
         .and_then(|array| {
@@ -327,6 +359,29 @@ fn collect_columns_from_predicate(predicate: &Arc<dyn PhysicalExpr>) -> EqualAnd
 
     (eq_predicate_columns, ne_predicate_columns)
 }
+/// Return the columns compared for equality with a literal in the predicate,
+/// which are constant in the output of the filter
+fn collect_constant_columns(predicate: &Arc<dyn PhysicalExpr>) -> Vec<&Column> {
+    split_conjunction(predicate)
+        .into_iter()
+        .filter_map(|p| {
+            let binary = p.as_any().downcast_ref::<BinaryExpr>()?;
+            if *binary.op() != Operator::Eq {
+                return None;
+            }
+            let (left, right) = (binary.left().as_any(), binary.right().as_any());
+            match (
+                left.downcast_ref::<Column>(),
+                right.downcast_ref::<Column>(),
+            ) {
+                (Some(column), None) if right.is::<Literal>() => Some(column),
+                (None, Some(column)) if left.is::<Literal>() => Some(column),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
 /// The equals Column-Pairs and Non-equals Column-Pairs in the Predicates
 pub type EqualAndNonEqual<'a> =
     (Vec<(&'a Column, &'a Column)>, Vec<(&'a Column, &'a Column)>);
@@ -452,14 +507,13 @@ mod tests {
         let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
         let input = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(100),
-                total_byte_size: Some(100 * bytes_per_row),
-                column_statistics: Some(vec![ColumnStatistics {
-                    min_value: Some(ScalarValue::Int32(Some(1))),
-                    max_value: Some(ScalarValue::Int32(Some(100))),
+                num_rows: Precision::Exact(100),
+                total_byte_size: Precision::Exact(100 * bytes_per_row),
+                column_statistics: vec![ColumnStatistics {
+                    min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                    max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
                     ..Default::default()
-                }]),
-                ..Default::default()
+                }],
             },
             schema.clone(),
         ));
@@ -473,8 +527,11 @@ mod tests {
             Arc::new(FilterExec::try_new(predicate, input)?);
 
         let statistics = filter.statistics();
-        assert_eq!(statistics.num_rows, Some(25));
-        assert_eq!(statistics.total_byte_size, Some(25 * bytes_per_row));
+        assert_eq!(statistics.num_rows, Precision::Inexact(25));
+        assert_eq!(
+            statistics.total_byte_size,
+            Precision::Inexact(25 * bytes_per_row)
+        );
 
         Ok(())
     }
@@ -486,13 +543,13 @@ mod tests {
         let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
         let input = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(100),
-                column_statistics: Some(vec![ColumnStatistics {
-                    min_value: Some(ScalarValue::Int32(Some(1))),
-                    max_value: Some(ScalarValue::Int32(Some(100))),
+                num_rows: Precision::Exact(100),
+                column_statistics: vec![ColumnStatistics {
+                    min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                    max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
                     ..Default::default()
-                }]),
-                ..Default::default()
+                }],
+                ..Statistics::new_unknown(&schema)
             },
             schema.clone(),
         ));
@@ -508,14 +565,14 @@ mod tests {
         let statistics = filter.statistics();
 
         // a must be in [1, 25] range now!
-        assert_eq!(statistics.num_rows, Some(25));
+        assert_eq!(statistics.num_rows, Precision::Inexact(25));
         assert_eq!(
             statistics.column_statistics,
-            Some(vec![ColumnStatistics {
-                min_value: Some(ScalarValue::Int32(Some(1))),
-                max_value: Some(ScalarValue::Int32(Some(25))),
+            vec![ColumnStatistics {
+                min_value: Precision::Inexact(ScalarValue::Int32(Some(1))),
+                max_value: Precision::Inexact(ScalarValue::Int32(Some(25))),
                 ..Default::default()
-            }])
+            }]
         );
 
         Ok(())
@@ -528,13 +585,13 @@ mod tests {
         let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
         let input = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(100),
-                column_statistics: Some(vec![ColumnStatistics {
-                    min_value: Some(ScalarValue::Int32(Some(1))),
-                    max_value: Some(ScalarValue::Int32(Some(100))),
+                num_rows: Precision::Exact(100),
+                column_statistics: vec![ColumnStatistics {
+                    min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                    max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
                     ..Default::default()
-                }]),
-                ..Default::default()
+                }],
+                ..Statistics::new_unknown(&schema)
             },
             schema.clone(),
         ));
@@ -554,14 +611,14 @@ mod tests {
         )?);
 
         let statistics = filter.statistics();
-        assert_eq!(statistics.num_rows, Some(16));
+        assert_eq!(statistics.num_rows, Precision::Inexact(16));
         assert_eq!(
             statistics.column_statistics,
-            Some(vec![ColumnStatistics {
-                min_value: Some(ScalarValue::Int32(Some(10))),
-                max_value: Some(ScalarValue::Int32(Some(25))),
+            vec![ColumnStatistics {
+                min_value: Precision::Inexact(ScalarValue::Int32(Some(10))),
+                max_value: Precision::Inexact(ScalarValue::Int32(Some(25))),
                 ..Default::default()
-            }])
+            }]
         );
 
         Ok(())
@@ -578,20 +635,20 @@ mod tests {
         ]);
         let input = Arc::new(StatisticsExec::new(
             Statistics {
-                num_rows: Some(100),
-                column_statistics: Some(vec![
+                num_rows: Precision::Exact(100),
+                column_statistics: vec![
                     ColumnStatistics {
-                        min_value: Some(ScalarValue::Int32(Some(1))),
-                        max_value: Some(ScalarValue::Int32(Some(100))),
+                        min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                        max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
                         ..Default::default()
                     },
                     ColumnStatistics {
-                        min_value: Some(ScalarValue::Int32(Some(1))),
-                        max_value: Some(ScalarValue::Int32(Some(50))),
+                        min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                        max_value: Precision::Exact(ScalarValue::Int32(Some(50))),
                         ..Default::default()
                     },
-                ]),
-                ..Default::default()
+                ],
+                ..Statistics::new_unknown(&schema)
             },
             schema.clone(),
         ));
@@ -621,21 +678,93 @@ mod tests {
         //
         // Which would result with a selectivity of  '15/100 * 5/50' or 0.015
         // and that means about %1.5 of the all rows (rounded up to 2 rows).
-        assert_eq!(statistics.num_rows, Some(2));
+        assert_eq!(statistics.num_rows, Precision::Inexact(2));
         assert_eq!(
             statistics.column_statistics,
-            Some(vec![
+            vec![
                 ColumnStatistics {
-                    min_value: Some(ScalarValue::Int32(Some(10))),
-                    max_value: Some(ScalarValue::Int32(Some(25))),
+                    min_value: Precision::Inexact(ScalarValue::Int32(Some(10))),
+                    max_value: Precision::Inexact(ScalarValue::Int32(Some(25))),
                     ..Default::default()
                 },
                 ColumnStatistics {
-                    min_value: Some(ScalarValue::Int32(Some(45))),
-                    max_value: Some(ScalarValue::Int32(Some(50))),
+                    min_value: Precision::Inexact(ScalarValue::Int32(Some(45))),
+                    max_value: Precision::Inexact(ScalarValue::Int32(Some(50))),
                     ..Default::default()
                 }
-            ])
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_filter_statistics_conjunction_and_disjunction() -> Result<()> {
+        // Table:
+        //      a: min=1, max=100
+        //      b: min=1, max=50
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        let input = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Precision::Exact(100),
+                column_statistics: vec![
+                    ColumnStatistics {
+                        min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                        max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
+                        ..Default::default()
+                    },
+                    ColumnStatistics {
+                        min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                        max_value: Precision::Exact(ScalarValue::Int32(Some(50))),
+                        ..Default::default()
+                    },
+                ],
+                ..Statistics::new_unknown(&schema)
+            },
+            schema.clone(),
+        ));
+
+        // WHERE (a >= 10 AND a <= 25) AND (b > 45 OR b < 6)
+        let predicate = binary(
+            binary(
+                binary(col("a", &schema)?, Operator::GtEq, lit(10i32), &schema)?,
+                Operator::And,
+                binary(col("a", &schema)?, Operator::LtEq, lit(25i32), &schema)?,
+                &schema,
+            )?,
+            Operator::And,
+            binary(
+                binary(col("b", &schema)?, Operator::Gt, lit(45i32), &schema)?,
+                Operator::Or,
+                binary(col("b", &schema)?, Operator::Lt, lit(6i32), &schema)?,
+                &schema,
+            )?,
+            &schema,
+        )?;
+        let filter: Arc<dyn ExecutionPlan> =
+            Arc::new(FilterExec::try_new(predicate, input)?);
+
+        let statistics = filter.statistics();
+        // 16/100 rows satisfy the conditions on 'a', and 5/50 + 5/50 - 5/50 * 5/50
+        // (0.19) of them the ones on 'b', which gives 3.04 rows (rounded up).
+        assert_eq!(statistics.num_rows, Precision::Inexact(4));
+        assert_eq!(
+            statistics.column_statistics,
+            vec![
+                ColumnStatistics {
+                    min_value: Precision::Inexact(ScalarValue::Int32(Some(10))),
+                    max_value: Precision::Inexact(ScalarValue::Int32(Some(25))),
+                    ..Default::default()
+                },
+                ColumnStatistics {
+                    min_value: Precision::Inexact(ScalarValue::Int32(Some(1))),
+                    max_value: Precision::Inexact(ScalarValue::Int32(Some(50))),
+                    ..Default::default()
+                }
+            ]
         );
 
         Ok(())
@@ -647,12 +776,7 @@ mod tests {
         //      a: min=???, max=??? (missing)
         let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
         let input = Arc::new(StatisticsExec::new(
-            Statistics {
-                column_statistics: Some(vec![ColumnStatistics {
-                    ..Default::default()
-                }]),
-                ..Default::default()
-            },
+            Statistics::new_unknown(&schema),
             schema.clone(),
         ));
 
@@ -665,7 +789,7 @@ mod tests {
             Arc::new(FilterExec::try_new(predicate, input)?);
 
         let statistics = filter.statistics();
-        assert_eq!(statistics.num_rows, None);
+        assert_eq!(statistics.num_rows, Precision::Absent);
 
         Ok(())
     }
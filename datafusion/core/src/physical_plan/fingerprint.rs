@@ -0,0 +1,128 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Canonical forms and fingerprints of physical plans, the counterparts of
+//! [`LogicalPlan::canonical_form`] and [`LogicalPlan::fingerprint`]
+//!
+//! [`LogicalPlan::canonical_form`]: crate::logical_expr::LogicalPlan::canonical_form
+//! [`LogicalPlan::fingerprint`]: crate::logical_expr::LogicalPlan::fingerprint
+
+use std::fmt::{self, Write};
+
+use datafusion_common::fingerprint::normalize_generated_aliases;
+pub use datafusion_common::fingerprint::Fingerprint;
+
+use super::{DisplayFormatType, ExecutionPlan};
+use crate::logical_expr::logical_plan::display_schema;
+
+/// Returns the canonical form of `plan`: a line per operator with its
+/// display and its output schema, in which the aliases generated by the
+/// optimizer are renumbered in their order of appearance. Two plans with the
+/// same canonical form compute the same results, given the same inputs.
+pub fn canonical_form(plan: &dyn ExecutionPlan) -> String {
+    let mut form = String::new();
+    write_operators(plan, 0, &mut form).expect("writing to a String");
+    normalize_generated_aliases(&form)
+}
+
+/// Returns the fingerprint of the [`canonical_form`] of `plan`, which is stable
+/// across processes and releases as long as the display of the operators
+/// doesn't change
+pub fn fingerprint(plan: &dyn ExecutionPlan) -> Fingerprint {
+    Fingerprint::of(canonical_form(plan).as_bytes())
+}
+
+fn write_operators(
+    plan: &dyn ExecutionPlan,
+    indent: usize,
+    form: &mut String,
+) -> fmt::Result {
+    struct OperatorDisplay<'a>(&'a dyn ExecutionPlan);
+    impl<'a> fmt::Display for OperatorDisplay<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt_as(DisplayFormatType::Default, f)
+        }
+    }
+
+    let schema = plan.schema();
+    writeln!(
+        form,
+        "{:indent$}{} {}",
+        "",
+        OperatorDisplay(plan),
+        display_schema(&schema),
+        indent = indent * 2
+    )?;
+    for child in plan.children() {
+        write_operators(child.as_ref(), indent + 1, form)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    use super::*;
+    use crate::error::Result;
+    use crate::physical_plan::expressions::{col, lit, BinaryExpr};
+    use crate::physical_plan::filter::FilterExec;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::PhysicalExpr;
+    use datafusion_expr::Operator;
+
+    fn filter(column: &str, threshold: i32) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            column,
+            DataType::Int32,
+            false,
+        )]));
+        let input = Arc::new(MemoryExec::try_new(&[vec![]], schema.clone(), None)?);
+        let predicate = Arc::new(BinaryExpr::new(
+            col(column, &schema)?,
+            Operator::Gt,
+            lit(threshold),
+        )) as Arc<dyn PhysicalExpr>;
+        Ok(Arc::new(FilterExec::try_new(predicate, input)?))
+    }
+
+    #[test]
+    fn physical_fingerprint() -> Result<()> {
+        let plan = filter("__scalar_sq_4", 5)?;
+        assert_eq!(
+            canonical_form(plan.as_ref()),
+            "FilterExec: __scalar_sq_1@0 > 5 [__scalar_sq_1:Int32]\
+             \n  MemoryExec: partitions=1, partition_sizes=[0] [__scalar_sq_1:Int32]\n"
+        );
+
+        assert_eq!(
+            fingerprint(plan.as_ref()),
+            fingerprint(filter("__scalar_sq_2", 5)?.as_ref())
+        );
+        assert_ne!(
+            fingerprint(plan.as_ref()),
+            fingerprint(filter("__scalar_sq_4", 6)?.as_ref())
+        );
+        assert_ne!(
+            fingerprint(plan.as_ref()),
+            fingerprint(filter("a", 5)?.as_ref())
+        );
+        Ok(())
+    }
+}
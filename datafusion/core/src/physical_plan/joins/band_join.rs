@@ -0,0 +1,800 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the band join plan, that joins the rows of one side with the rows
+//! of the other side whose range contains them, such as in
+//! `a.ts BETWEEN b.start AND b.end`, by sorting both sides and sweeping over
+//! them instead of comparing every pair of rows.
+
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::Instant;
+
+use arrow::array::{Array, UInt32Array, UInt64Array};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::Statistics;
+use datafusion_expr::{JoinType, Operator};
+use datafusion_physical_expr::expressions::{BinaryExpr, Column};
+use datafusion_physical_expr::rewrite::TreeNode;
+use datafusion_physical_expr::utils::split_conjunction;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr, PhysicalSortExpr};
+use futures::{ready, Stream, StreamExt, TryStreamExt};
+use log::debug;
+use parking_lot::Mutex;
+
+use crate::error::Result;
+use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use crate::physical_plan::coalesce_batches::concat_batches;
+use crate::physical_plan::joins::utils::{
+    adjust_right_output_partitioning, apply_join_filter_to_indices,
+    build_batch_from_indices, build_join_schema, check_join_is_valid,
+    combine_join_equivalence_properties, estimate_join_statistics, ColumnIndex,
+    JoinFilter, JoinSide, OnceAsync, OnceFut,
+};
+use crate::physical_plan::{
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream,
+};
+
+/// A band condition `lower <= point AND point <= upper` of a join filter,
+/// where `point` is evaluated on the rows of one side of the join and the
+/// bounds of the band on the rows of the other side.
+///
+/// The band may be narrower than the join filter, for instance when its
+/// bounds are exclusive, as the join filter is still applied to the rows
+/// within the band.
+#[derive(Debug, Clone)]
+pub struct BandCondition {
+    /// Side of the join of the point, the bounds are on the other side
+    point_side: JoinSide,
+    /// Point expression, on the schema of the point side
+    point: Arc<dyn PhysicalExpr>,
+    /// Lower bound expression, on the schema of the range side
+    lower: Arc<dyn PhysicalExpr>,
+    /// Upper bound expression, on the schema of the range side
+    upper: Arc<dyn PhysicalExpr>,
+}
+
+impl BandCondition {
+    /// Finds a band condition in the conjunction of comparisons of `filter`,
+    /// whose expressions have the same type. Returns `None` if there is none.
+    pub fn try_new(
+        filter: &JoinFilter,
+        left_schema: &Schema,
+        right_schema: &Schema,
+    ) -> Result<Option<Self>> {
+        // each comparison `small <= big` between both sides is the lower bound
+        // of `big`, and the upper bound of `small`
+        let mut lower_bounds = vec![];
+        let mut upper_bounds = vec![];
+        for conjunct in split_conjunction(filter.expression()) {
+            let binary = match conjunct.as_any().downcast_ref::<BinaryExpr>() {
+                Some(binary) => binary,
+                None => continue,
+            };
+            let (small, big) = match binary.op() {
+                Operator::Lt | Operator::LtEq => (binary.left(), binary.right()),
+                Operator::Gt | Operator::GtEq => (binary.right(), binary.left()),
+                _ => continue,
+            };
+            let column_indices = filter.column_indices();
+            match (
+                side_expr(small, column_indices)?,
+                side_expr(big, column_indices)?,
+            ) {
+                (Some((small_side, small)), Some((big_side, big)))
+                    if small_side != big_side =>
+                {
+                    lower_bounds.push((big_side, big.clone(), small.clone()));
+                    upper_bounds.push((small_side, small, big));
+                }
+                _ => {}
+            }
+        }
+
+        for (point_side, point, lower) in &lower_bounds {
+            for (upper_side, upper_point, upper) in &upper_bounds {
+                if point_side != upper_side || !point.eq(upper_point.as_any()) {
+                    continue;
+                }
+
+                let (point_schema, range_schema) = match point_side {
+                    JoinSide::Left => (left_schema, right_schema),
+                    JoinSide::Right => (right_schema, left_schema),
+                };
+                let data_type = point.data_type(point_schema)?;
+                if lower.data_type(range_schema)? != data_type
+                    || upper.data_type(range_schema)? != data_type
+                    || !is_supported_type(&data_type)
+                {
+                    continue;
+                }
+
+                return Ok(Some(Self {
+                    point_side: *point_side,
+                    point: point.clone(),
+                    lower: lower.clone(),
+                    upper: upper.clone(),
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Side of the join of the point
+    pub fn point_side(&self) -> JoinSide {
+        self.point_side
+    }
+}
+
+/// Returns whether the values of `data_type` can be compared in the
+/// [row format](arrow::row)
+fn is_supported_type(data_type: &DataType) -> bool {
+    RowConverter::new(vec![SortField::new(data_type.clone())]).is_ok()
+}
+
+/// Returns the side of the join that all the columns of `expr` are on, and
+/// `expr` rewritten to the schema of that side. Returns `None` if `expr` has
+/// no column, or columns of both sides.
+fn side_expr(
+    expr: &Arc<dyn PhysicalExpr>,
+    column_indices: &[ColumnIndex],
+) -> Result<Option<(JoinSide, Arc<dyn PhysicalExpr>)>> {
+    fn collect_sides(
+        expr: &Arc<dyn PhysicalExpr>,
+        column_indices: &[ColumnIndex],
+        sides: &mut Vec<JoinSide>,
+    ) {
+        match expr.as_any().downcast_ref::<Column>() {
+            Some(column) => sides.push(column_indices[column.index()].side),
+            None => expr
+                .children()
+                .iter()
+                .for_each(|child| collect_sides(child, column_indices, sides)),
+        }
+    }
+
+    let mut sides = vec![];
+    collect_sides(expr, column_indices, &mut sides);
+    let side = match sides.first() {
+        Some(side) if sides.iter().all(|s| s == side) => *side,
+        _ => return Ok(None),
+    };
+
+    let expr = expr.clone().transform_up(&|expr| {
+        Ok(expr.as_any().downcast_ref::<Column>().map(|column| {
+            let index = column_indices[column.index()].index;
+            Arc::new(Column::new(column.name(), index)) as Arc<dyn PhysicalExpr>
+        }))
+    })?;
+    Ok(Some((side, expr)))
+}
+
+/// Data of the range side: its rows, the bounds of their ranges in the row
+/// format, and the order of the ranges by lower bound
+struct RangeData {
+    batch: RecordBatch,
+    /// Converter of the bounds, also used to convert the points so that they
+    /// can be compared with the bounds
+    converter: Mutex<RowConverter>,
+    lower: Rows,
+    upper: Rows,
+    /// Indices of the ranges without null bound, sorted by lower bound
+    sorted: Vec<usize>,
+    _reservation: MemoryReservation,
+}
+
+/// Inner join of the rows of one side with the rows of the other side
+/// whose range contains them, according to a [`BandCondition`] of the join
+/// filter.
+///
+/// The range side is collected in a single partition, and its ranges are
+/// sorted by lower bound. Each batch of the point side is then sorted by
+/// point, and joined in a single sweep over the ranges, keeping the ranges
+/// whose lower bound is below the current point in a heap ordered by upper
+/// bound. This finds the rows within the band in `O((n + m) log(n + m))`
+/// plus the size of the output, instead of the `O(n * m)` comparisons of a
+/// nested loop join.
+#[derive(Debug)]
+pub struct BandJoinExec {
+    /// left side
+    left: Arc<dyn ExecutionPlan>,
+    /// right side
+    right: Arc<dyn ExecutionPlan>,
+    /// Filter which is applied while finding matching rows
+    filter: JoinFilter,
+    /// Band condition of the filter
+    band: BandCondition,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Range side data
+    range_fut: OnceAsync<RangeData>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+}
+
+impl BandJoinExec {
+    /// Try to create a new [`BandJoinExec`]
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: JoinFilter,
+        band: BandCondition,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &[])?;
+        let (schema, column_indices) =
+            build_join_schema(&left_schema, &right_schema, &JoinType::Inner);
+        Ok(Self {
+            left,
+            right,
+            filter,
+            band,
+            schema: Arc::new(schema),
+            range_fut: Default::default(),
+            column_indices,
+        })
+    }
+
+    /// left side
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// right side
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Filter applied while finding matching rows
+    pub fn filter(&self) -> &JoinFilter {
+        &self.filter
+    }
+
+    /// Band condition of the filter
+    pub fn band(&self) -> &BandCondition {
+        &self.band
+    }
+
+    /// Returns the (point, range) sides of the join
+    fn sides(&self) -> (&Arc<dyn ExecutionPlan>, &Arc<dyn ExecutionPlan>) {
+        match self.band.point_side {
+            JoinSide::Left => (&self.left, &self.right),
+            JoinSide::Right => (&self.right, &self.left),
+        }
+    }
+}
+
+impl ExecutionPlan for BandJoinExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        // the output is partitioned like the point side
+        match self.band.point_side {
+            JoinSide::Left => self.left.output_partitioning(),
+            JoinSide::Right => adjust_right_output_partitioning(
+                self.right.output_partitioning(),
+                self.left.schema().fields.len(),
+            ),
+        }
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        // no specified order for the output
+        None
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        // the range side is collected, and each partition of the point side
+        // is joined with all of it
+        match self.band.point_side {
+            JoinSide::Left => vec![
+                Distribution::UnspecifiedDistribution,
+                Distribution::SinglePartition,
+            ],
+            JoinSide::Right => vec![
+                Distribution::SinglePartition,
+                Distribution::UnspecifiedDistribution,
+            ],
+        }
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        let left_columns_len = self.left.schema().fields.len();
+        combine_join_equivalence_properties(
+            JoinType::Inner,
+            self.left.equivalence_properties(),
+            self.right.equivalence_properties(),
+            left_columns_len,
+            &[], // empty join keys
+            self.schema(),
+        )
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(BandJoinExec::try_new(
+            children[0].clone(),
+            children[1].clone(),
+            self.filter.clone(),
+            self.band.clone(),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let (point_side, range_side) = self.sides();
+        let range_fut = self.range_fut.once(|| {
+            load_range_side(range_side.clone(), self.band.clone(), context.clone())
+        });
+        let points = point_side.execute(partition, context)?;
+
+        Ok(Box::pin(BandJoinStream {
+            schema: self.schema.clone(),
+            filter: self.filter.clone(),
+            band: self.band.clone(),
+            range_fut,
+            points,
+            column_indices: self.column_indices.clone(),
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "BandJoinExec: point_side={:?}, band=({} <= {} <= {}), filter={:?}",
+                    self.band.point_side,
+                    self.band.lower,
+                    self.band.point,
+                    self.band.upper,
+                    self.filter.expression()
+                )
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        estimate_join_statistics(
+            self.left.clone(),
+            self.right.clone(),
+            vec![],
+            &JoinType::Inner,
+            &self.schema,
+            Some(&self.filter),
+        )
+    }
+}
+
+/// Asynchronously collect the range side, and sort its ranges
+async fn load_range_side(
+    range_side: Arc<dyn ExecutionPlan>,
+    band: BandCondition,
+    context: Arc<TaskContext>,
+) -> Result<RangeData> {
+    let start = Instant::now();
+    let stream = range_side.execute(0, context.clone())?;
+    let reservation = MemoryConsumer::new("BandJoinLoad").register(context.memory_pool());
+
+    // Load all batches and count the rows, failing once the range side
+    // exceeds the memory limit
+    let (batches, num_rows, mut reservation) = stream
+        .try_fold((Vec::new(), 0usize, reservation), |mut acc, batch| async {
+            acc.2.try_grow(batch.get_array_memory_size())?;
+            acc.1 += batch.num_rows();
+            acc.0.push(batch);
+            Ok(acc)
+        })
+        .await?;
+    // concatenating copies the batches, so reserve for both copies before
+    let batches_size = reservation.size();
+    reservation.try_grow(batches_size)?;
+    let batch = concat_batches(&range_side.schema(), &batches, num_rows)?;
+    drop(batches);
+    let batch_size = batch.get_array_memory_size();
+    reservation.resize(batch_size);
+
+    let lower = band.lower.evaluate(&batch)?.into_array(num_rows);
+    let upper = band.upper.evaluate(&batch)?.into_array(num_rows);
+    let mut converter =
+        RowConverter::new(vec![SortField::new(lower.data_type().clone())])?;
+    // the bounds are converted in turn, each at least as large as its array
+    reservation.try_grow(lower.get_array_memory_size())?;
+    let lower_rows = converter.convert_columns(&[lower.clone()])?;
+    reservation.try_resize(batch_size + lower_rows.size())?;
+    reservation.try_grow(upper.get_array_memory_size())?;
+    let upper_rows = converter.convert_columns(&[upper.clone()])?;
+    reservation.try_resize(batch_size + lower_rows.size() + upper_rows.size())?;
+
+    reservation.try_grow(num_rows * std::mem::size_of::<usize>())?;
+    let mut sorted: Vec<_> = (0..num_rows)
+        .filter(|&i| lower.is_valid(i) && upper.is_valid(i))
+        .collect();
+    sorted.sort_unstable_by(|a, b| lower_rows.row(*a).cmp(&lower_rows.row(*b)));
+    sorted.shrink_to_fit();
+
+    reservation.resize(
+        batch_size
+            + lower_rows.size()
+            + upper_rows.size()
+            + sorted.capacity() * std::mem::size_of::<usize>(),
+    );
+
+    debug!(
+        "Built range side of band join containing {} rows in {} ms",
+        num_rows,
+        start.elapsed().as_millis(),
+    );
+
+    Ok(RangeData {
+        batch,
+        converter: Mutex::new(converter),
+        lower: lower_rows,
+        upper: upper_rows,
+        sorted,
+        _reservation: reservation,
+    })
+}
+
+/// A stream that joins the batches of the point side with the range side
+struct BandJoinStream {
+    /// Output schema
+    schema: SchemaRef,
+    /// join filter
+    filter: JoinFilter,
+    /// band condition of the filter
+    band: BandCondition,
+    /// future for data from the range side
+    range_fut: OnceFut<RangeData>,
+    /// point side
+    points: SendableRecordBatchStream,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+}
+
+/// Returns the indices of the (point, range) pairs of rows whose point is
+/// within the range
+fn band_indices(
+    band: &BandCondition,
+    range_data: &RangeData,
+    point_batch: &RecordBatch,
+) -> Result<(Vec<u32>, Vec<u32>)> {
+    let point = band
+        .point
+        .evaluate(point_batch)?
+        .into_array(point_batch.num_rows());
+    let points = range_data
+        .converter
+        .lock()
+        .convert_columns(&[point.clone()])?;
+
+    let mut sorted_points: Vec<_> =
+        (0..point.len()).filter(|&i| point.is_valid(i)).collect();
+    sorted_points.sort_unstable_by(|a, b| points.row(*a).cmp(&points.row(*b)));
+
+    let mut point_indices = vec![];
+    let mut range_indices = vec![];
+    let mut next_range = 0;
+    // the ranges whose lower bound is below the current point, by upper bound
+    let mut active = BinaryHeap::new();
+    for point_index in sorted_points {
+        let point = points.row(point_index);
+
+        while let Some(&range_index) = range_data.sorted.get(next_range) {
+            if range_data.lower.row(range_index) > point {
+                break;
+            }
+            active.push(Reverse((range_data.upper.row(range_index), range_index)));
+            next_range += 1;
+        }
+
+        // as the points are sorted, the ranges that end before this point
+        // end before all the next ones
+        while let Some(Reverse((upper, _))) = active.peek() {
+            if *upper >= point {
+                break;
+            }
+            active.pop();
+        }
+
+        for Reverse((_, range_index)) in active.iter() {
+            point_indices.push(point_index as u32);
+            range_indices.push(*range_index as u32);
+        }
+    }
+
+    Ok((point_indices, range_indices))
+}
+
+/// Joins a batch of the point side with the range side
+fn join_point_batch(
+    schema: &Schema,
+    filter: &JoinFilter,
+    band: &BandCondition,
+    column_indices: &[ColumnIndex],
+    range_data: &RangeData,
+    point_batch: &RecordBatch,
+) -> Result<RecordBatch> {
+    let (point_indices, range_indices) = band_indices(band, range_data, point_batch)?;
+
+    let (left, right, left_indices, right_indices) = match band.point_side {
+        JoinSide::Left => (point_batch, &range_data.batch, point_indices, range_indices),
+        JoinSide::Right => (&range_data.batch, point_batch, range_indices, point_indices),
+    };
+    let left_indices: UInt64Array = left_indices.into_iter().map(|i| i as u64).collect();
+    let right_indices = UInt32Array::from(right_indices);
+
+    // the rows within the band must still match the whole filter
+    let (left_indices, right_indices) =
+        apply_join_filter_to_indices(left, right, left_indices, right_indices, filter)?;
+    Ok(build_batch_from_indices(
+        schema,
+        left,
+        right,
+        left_indices,
+        right_indices,
+        column_indices,
+    )?)
+}
+
+impl Stream for BandJoinStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let range_data = match ready!(this.range_fut.get(cx)) {
+            Ok(range_data) => range_data,
+            Err(e) => return Poll::Ready(Some(Err(e))),
+        };
+
+        match ready!(this.points.poll_next_unpin(cx)) {
+            Some(Ok(point_batch)) => Poll::Ready(Some(
+                join_point_batch(
+                    &this.schema,
+                    &this.filter,
+                    &this.band,
+                    &this.column_indices,
+                    range_data,
+                    &point_batch,
+                )
+                .map_err(Into::into),
+            )),
+            other => Poll::Ready(other),
+        }
+    }
+}
+
+impl RecordBatchStream for BandJoinStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+    use crate::physical_plan::common;
+    use crate::physical_plan::expressions::{binary, col};
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::prelude::{SessionConfig, SessionContext};
+    use crate::test::build_table_i32;
+    use arrow::datatypes::Field;
+
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    /// The points in `a1`
+    fn build_points() -> Arc<dyn ExecutionPlan> {
+        build_table(
+            ("a1", &vec![1, 5, 10, 7]),
+            ("b1", &vec![0, 0, 0, 0]),
+            ("c1", &vec![10, 50, 100, 70]),
+        )
+    }
+
+    /// The ranges from `a2` to `b2`
+    fn build_ranges() -> Arc<dyn ExecutionPlan> {
+        build_table(
+            ("a2", &vec![0, 5, 9, 6, 12]),
+            ("b2", &vec![5, 8, 20, 6, 11]),
+            ("c2", &vec![1, 2, 3, 4, 5]),
+        )
+    }
+
+    /// Returns the filter `x <lower_op> lower AND x <upper_op> upper`, where
+    /// `x` is the first column of the point side, and `lower` and `upper`
+    /// the first two columns of the range side
+    fn band_filter(
+        point_side: JoinSide,
+        lower_op: Operator,
+        upper_op: Operator,
+    ) -> Result<JoinFilter> {
+        let range_side = match point_side {
+            JoinSide::Left => JoinSide::Right,
+            JoinSide::Right => JoinSide::Left,
+        };
+        let column_indices = vec![
+            ColumnIndex {
+                index: 0,
+                side: point_side,
+            },
+            ColumnIndex {
+                index: 0,
+                side: range_side,
+            },
+            ColumnIndex {
+                index: 1,
+                side: range_side,
+            },
+        ];
+        let schema = Schema::new(vec![
+            Field::new("x", DataType::Int32, false),
+            Field::new("lower", DataType::Int32, false),
+            Field::new("upper", DataType::Int32, false),
+        ]);
+        let expression = binary(
+            binary(
+                col("x", &schema)?,
+                lower_op,
+                col("lower", &schema)?,
+                &schema,
+            )?,
+            Operator::And,
+            binary(
+                col("x", &schema)?,
+                upper_op,
+                col("upper", &schema)?,
+                &schema,
+            )?,
+            &schema,
+        )?;
+        Ok(JoinFilter::new(expression, column_indices, schema))
+    }
+
+    async fn join_collect(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: JoinFilter,
+    ) -> Result<Vec<RecordBatch>> {
+        let band =
+            BandCondition::try_new(&filter, &left.schema(), &right.schema())?.unwrap();
+        let join = BandJoinExec::try_new(left, right, filter, band)?;
+        let task_ctx = SessionContext::new().task_ctx();
+        common::collect(join.execute(0, task_ctx)?).await
+    }
+
+    #[test]
+    fn band_condition() -> Result<()> {
+        let (points, ranges) = (build_points().schema(), build_ranges().schema());
+
+        let filter = band_filter(JoinSide::Left, Operator::GtEq, Operator::Lt)?;
+        let band = BandCondition::try_new(&filter, &points, &ranges)?.unwrap();
+        assert_eq!(band.point_side(), JoinSide::Left);
+        assert_eq!(band.point.to_string(), "a1@0");
+        assert_eq!(band.lower.to_string(), "a2@0");
+        assert_eq!(band.upper.to_string(), "b2@1");
+
+        let filter = band_filter(JoinSide::Right, Operator::GtEq, Operator::LtEq)?;
+        let band = BandCondition::try_new(&filter, &ranges, &points)?.unwrap();
+        assert_eq!(band.point_side(), JoinSide::Right);
+
+        // both bounds are lower bounds
+        let filter = band_filter(JoinSide::Left, Operator::Gt, Operator::GtEq)?;
+        assert!(BandCondition::try_new(&filter, &points, &ranges)?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn point_left() -> Result<()> {
+        let filter = band_filter(JoinSide::Left, Operator::GtEq, Operator::LtEq)?;
+        let batches = join_collect(build_points(), build_ranges(), filter).await?;
+        let expected = vec![
+            "+----+----+-----+----+----+----+",
+            "| a1 | b1 | c1  | a2 | b2 | c2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 0  | 10  | 0  | 5  | 1  |",
+            "| 10 | 0  | 100 | 9  | 20 | 3  |",
+            "| 5  | 0  | 50  | 0  | 5  | 1  |",
+            "| 5  | 0  | 50  | 5  | 8  | 2  |",
+            "| 7  | 0  | 70  | 5  | 8  | 2  |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn point_right_exclusive_bounds() -> Result<()> {
+        let filter = band_filter(JoinSide::Right, Operator::Gt, Operator::Lt)?;
+        let batches = join_collect(build_ranges(), build_points(), filter).await?;
+        let expected = vec![
+            "+----+----+----+----+----+-----+",
+            "| a2 | b2 | c2 | a1 | b1 | c1  |",
+            "+----+----+----+----+----+-----+",
+            "| 0  | 5  | 1  | 1  | 0  | 10  |",
+            "| 5  | 8  | 2  | 7  | 0  | 70  |",
+            "| 9  | 20 | 3  | 10 | 0  | 100 |",
+            "+----+----+----+----+----+-----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn overallocation() -> Result<()> {
+        // the range side fits in memory, but not its concatenated copy
+        let task_ctx = SessionContext::new().task_ctx();
+        let ranges = common::collect(build_ranges().execute(0, task_ctx)?).await?;
+        let runtime_config = RuntimeConfig::new()
+            .with_memory_limit(ranges[0].get_array_memory_size(), 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let task_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime).task_ctx();
+
+        let filter = band_filter(JoinSide::Left, Operator::GtEq, Operator::LtEq)?;
+        let (left, right) = (build_points(), build_ranges());
+        let band =
+            BandCondition::try_new(&filter, &left.schema(), &right.schema())?.unwrap();
+        let join = BandJoinExec::try_new(left, right, filter, band)?;
+        let err = common::collect(join.execute(0, task_ctx)?)
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.to_string().contains("Resources exhausted"),
+            "Wrong error: {err}",
+        );
+        Ok(())
+    }
+}
@@ -18,25 +18,30 @@
 //! Defines the cross join plan for loading the left side of the cross join
 //! and producing batches in parallel for the right partitions
 
-use futures::{ready, StreamExt};
-use futures::{Stream, TryStreamExt};
+use futures::{ready, Stream, StreamExt};
+use std::path::Path;
 use std::{any::Any, sync::Arc, task::Poll};
 
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow::error::Result as ArrowResult;
 use arrow::record_batch::RecordBatch;
+use tempfile::NamedTempFile;
+use tokio::task;
 
 use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use crate::physical_plan::sorts::sort::{read_spill, spill_partial_sorted_stream};
+use crate::physical_plan::stream::{RecordBatchReceiverStream, RecordBatchStreamAdapter};
 use crate::physical_plan::{
     coalesce_batches::concat_batches, coalesce_partitions::CoalescePartitionsExec,
     ColumnStatistics, DisplayFormatType, Distribution, EquivalenceProperties,
-    ExecutionPlan, Partitioning, PhysicalSortExpr, RecordBatchStream,
+    ExecutionPlan, Partitioning, PhysicalSortExpr, Precision, RecordBatchStream,
     SendableRecordBatchStream, Statistics,
 };
 use crate::{error::Result, scalar::ScalarValue};
 use async_trait::async_trait;
 use datafusion_common::DataFusionError;
-use log::debug;
+use log::{debug, error};
 use std::time::Instant;
 
 use super::utils::{
@@ -45,7 +50,15 @@ use super::utils::{
 };
 
 /// Data of the left side
-type JoinLeftData = RecordBatch;
+struct JoinLeftData {
+    /// The left batches that fit in memory, merged into a single batch
+    merged_batch: RecordBatch,
+    /// The spill file of the left batches that didn't fit in memory, which
+    /// are read back for each right batch
+    spill_file: Option<NamedTempFile>,
+    /// Memory reserved for `merged_batch`, freed once the join completes
+    _reservation: MemoryReservation,
+}
 
 /// executes partitions in parallel and combines them into a set of
 /// partitions by combining all values from the left with all values on the right
@@ -94,7 +107,11 @@ impl CrossJoinExec {
     }
 }
 
-/// Asynchronously collect the result of the left child
+/// Asynchronously collect the result of the left child.
+///
+/// The left batches are kept in memory as long as the memory pool allows it.
+/// Once a batch doesn't fit, it and all the remaining left batches are
+/// spilled to disk, which fails if the disk manager is disabled.
 async fn load_left_input(
     left: Arc<dyn ExecutionPlan>,
     context: Arc<TaskContext>,
@@ -109,26 +126,72 @@ async fn load_left_input(
             left.clone()
         }
     };
-    let stream = merge.execute(0, context)?;
-
-    // Load all batches and count the rows
-    let (batches, num_rows) = stream
-        .try_fold((Vec::new(), 0usize), |mut acc, batch| async {
-            acc.1 += batch.num_rows();
-            acc.0.push(batch);
-            Ok(acc)
-        })
-        .await?;
+    let mut stream = merge.execute(0, context.clone())?;
+    let mut reservation = MemoryConsumer::new("CrossJoinExec")
+        .with_can_spill(true)
+        .register(context.memory_pool());
+
+    // Load the batches that fit in memory and count their rows
+    let mut batches = vec![];
+    let mut num_rows = 0;
+    let mut spill_file = None;
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        if reservation.try_grow(batch.get_array_memory_size()).is_ok() {
+            num_rows += batch.num_rows();
+            batches.push(batch);
+            continue;
+        }
 
+        let file = context
+            .runtime_env()
+            .disk_manager
+            .create_tmp_file("loading the build side of CrossJoinExec")?;
+        let first = futures::stream::once(async move { Ok(batch) });
+        let mut remaining: SendableRecordBatchStream = Box::pin(
+            RecordBatchStreamAdapter::new(left.schema(), first.chain(stream)),
+        );
+        spill_partial_sorted_stream(&mut remaining, file.path(), left.schema()).await?;
+        spill_file = Some(file);
+        break;
+    }
+
+    // the merged batch is reserved alongside the batches it is built from,
+    // which are released once they are dropped
     let merged_batch = concat_batches(&left.schema(), &batches, num_rows)?;
+    let merged_size = merged_batch.get_array_memory_size();
+    reservation.try_resize(reservation.size() + merged_size)?;
+    drop(batches);
+    reservation.resize(merged_size);
 
     debug!(
-        "Built build-side of cross join containing {} rows in {} ms",
+        "Built build-side of cross join containing {} rows in memory{} in {} ms",
         num_rows,
+        if spill_file.is_some() {
+            " and spilled the remaining rows"
+        } else {
+            ""
+        },
         start.elapsed().as_millis()
     );
 
-    Ok(merged_batch)
+    Ok(JoinLeftData {
+        merged_batch,
+        spill_file,
+        _reservation: reservation,
+    })
+}
+
+/// Reads back the left batches spilled to `path`
+fn read_spilled_left(path: &Path, schema: SchemaRef) -> SendableRecordBatchStream {
+    let (sender, receiver) = tokio::sync::mpsc::channel(2);
+    let path = path.to_path_buf();
+    let join_handle = task::spawn_blocking(move || {
+        if let Err(e) = read_spill(sender, &path) {
+            error!("Failure while reading spill file: {:?}. Error: {}", path, e);
+        }
+    });
+    RecordBatchReceiverStream::create(&schema, receiver, join_handle)
 }
 
 impl ExecutionPlan for CrossJoinExec {
@@ -214,8 +277,11 @@ impl ExecutionPlan for CrossJoinExec {
             schema: self.schema.clone(),
             left_fut,
             right: stream,
-            right_batch: Arc::new(parking_lot::Mutex::new(None)),
+            right_batch: None,
             left_index: 0,
+            spilled_left: None,
+            spilled_batch: None,
+            spilled_index: 0,
             num_input_batches: 0,
             num_input_rows: 0,
             num_output_batches: 0,
@@ -237,73 +303,48 @@ impl ExecutionPlan for CrossJoinExec {
     }
 
     fn statistics(&self) -> Statistics {
-        stats_cartesian_product(
-            self.left.statistics(),
-            self.left.schema().fields().len(),
-            self.right.statistics(),
-            self.right.schema().fields().len(),
-        )
+        stats_cartesian_product(self.left.statistics(), self.right.statistics())
     }
 }
 
-/// [left/right]_col_count are required in case the column statistics are None
 fn stats_cartesian_product(
     left_stats: Statistics,
-    left_col_count: usize,
     right_stats: Statistics,
-    right_col_count: usize,
 ) -> Statistics {
     let left_row_count = left_stats.num_rows;
     let right_row_count = right_stats.num_rows;
 
     // calculate global stats
-    let is_exact = left_stats.is_exact && right_stats.is_exact;
-    let num_rows = left_stats
-        .num_rows
-        .zip(right_stats.num_rows)
-        .map(|(a, b)| a * b);
+    let num_rows = left_row_count.multiply(&right_row_count);
     // the result size is two times a*b because you have the columns of both left and right
     let total_byte_size = left_stats
         .total_byte_size
-        .zip(right_stats.total_byte_size)
-        .map(|(a, b)| 2 * a * b);
+        .multiply(&right_stats.total_byte_size)
+        .multiply(&Precision::Exact(2))
+        .to_inexact();
 
     // calculate column stats
-    let column_statistics =
-        // complete the column statistics if they are missing only on one side
-        match (left_stats.column_statistics, right_stats.column_statistics) {
-            (None, None) => None,
-            (None, Some(right_col_stat)) => Some((
-                vec![ColumnStatistics::default(); left_col_count],
-                right_col_stat,
-            )),
-            (Some(left_col_stat), None) => Some((
-                left_col_stat,
-                vec![ColumnStatistics::default(); right_col_count],
-            )),
-            (Some(left_col_stat), Some(right_col_stat)) => {
-                Some((left_col_stat, right_col_stat))
-            }
-        }
-        .map(|(left_col_stats, right_col_stats)| {
-            // the null counts must be multiplied by the row counts of the other side (if defined)
-            // Min, max and distinct_count on the other hand are invariants.
-            left_col_stats.into_iter().map(|s| ColumnStatistics{
-                null_count: s.null_count.zip(right_row_count).map(|(a, b)| a * b),
-                distinct_count: s.distinct_count,
-                min_value: s.min_value,
-                max_value: s.max_value,
-            }).chain(
-            right_col_stats.into_iter().map(|s| ColumnStatistics{
-                null_count: s.null_count.zip(left_row_count).map(|(a, b)| a * b),
-                distinct_count: s.distinct_count,
-                min_value: s.min_value,
-                max_value: s.max_value,
-            })).collect()
-        });
+    // the null counts must be multiplied by the row counts of the other side (if defined)
+    // Min, max and distinct_count on the other hand are invariants.
+    let column_statistics = left_stats
+        .column_statistics
+        .into_iter()
+        .map(|s| ColumnStatistics {
+            null_count: s.null_count.multiply(&right_row_count),
+            ..s
+        })
+        .chain(
+            right_stats
+                .column_statistics
+                .into_iter()
+                .map(|s| ColumnStatistics {
+                    null_count: s.null_count.multiply(&left_row_count),
+                    ..s
+                }),
+        )
+        .collect();
 
     Statistics {
-        is_exact,
         num_rows,
         total_byte_size,
         column_statistics,
@@ -321,7 +362,13 @@ struct CrossJoinStream {
     /// Current value on the left
     left_index: usize,
     /// Current batch being processed from the right side
-    right_batch: Arc<parking_lot::Mutex<Option<RecordBatch>>>,
+    right_batch: Option<RecordBatch>,
+    /// Stream of the spilled left batches, read back for the current right batch
+    spilled_left: Option<SendableRecordBatchStream>,
+    /// Current spilled left batch
+    spilled_batch: Option<RecordBatch>,
+    /// Current value of the spilled left batch
+    spilled_index: usize,
     /// number of input batches
     num_input_batches: usize,
     /// number of input rows
@@ -390,68 +437,176 @@ impl CrossJoinStream {
             Err(e) => return Poll::Ready(Some(Err(e))),
         };
 
-        if left_data.num_rows() == 0 {
+        if left_data.merged_batch.num_rows() == 0 && left_data.spill_file.is_none() {
             return Poll::Ready(None);
         }
 
-        if self.left_index > 0 && self.left_index < left_data.num_rows() {
-            let start = Instant::now();
-            let right_batch = {
-                let right_batch = self.right_batch.lock();
-                right_batch.clone().unwrap()
+        loop {
+            let right_batch = match &self.right_batch {
+                Some(right_batch) => right_batch.clone(),
+                None => match ready!(self.right.poll_next_unpin(cx)) {
+                    Some(Ok(batch)) => {
+                        self.num_input_batches += 1;
+                        self.num_input_rows += batch.num_rows();
+                        self.right_batch = Some(batch);
+                        self.left_index = 0;
+                        continue;
+                    }
+                    other => {
+                        debug!(
+                            "Processed {} probe-side input batches containing {} rows and \
+                            produced {} output batches containing {} rows in {} ms",
+                            self.num_input_batches,
+                            self.num_input_rows,
+                            self.num_output_batches,
+                            self.num_output_rows,
+                            self.join_time
+                        );
+                        return Poll::Ready(other);
+                    }
+                },
             };
-            let result =
-                build_batch(self.left_index, &right_batch, left_data, &self.schema);
-            self.num_input_rows += right_batch.num_rows();
+
+            // combine the right batch with the in memory left rows, then with
+            // the spilled left rows one batch at a time
+            let (left_batch, left_index) = if self.left_index
+                < left_data.merged_batch.num_rows()
+            {
+                self.left_index += 1;
+                (&left_data.merged_batch, self.left_index - 1)
+            } else if let Some(spill_file) = &left_data.spill_file {
+                match &self.spilled_batch {
+                    Some(batch) if self.spilled_index < batch.num_rows() => {
+                        self.spilled_index += 1;
+                        (batch, self.spilled_index - 1)
+                    }
+                    _ => {
+                        let schema = left_data.merged_batch.schema();
+                        let spilled_left = self.spilled_left.get_or_insert_with(|| {
+                            read_spilled_left(spill_file.path(), schema)
+                        });
+                        match ready!(spilled_left.poll_next_unpin(cx)) {
+                            Some(Ok(batch)) => {
+                                self.spilled_batch = Some(batch);
+                                self.spilled_index = 0;
+                            }
+                            Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                            None => {
+                                self.spilled_left = None;
+                                self.spilled_batch = None;
+                                self.right_batch = None;
+                            }
+                        }
+                        continue;
+                    }
+                }
+            } else {
+                self.right_batch = None;
+                continue;
+            };
+
+            let start = Instant::now();
+            let result = build_batch(left_index, &right_batch, left_batch, &self.schema);
             if let Ok(ref batch) = result {
                 self.join_time += start.elapsed().as_millis() as usize;
                 self.num_output_batches += 1;
                 self.num_output_rows += batch.num_rows();
             }
-            self.left_index += 1;
             return Poll::Ready(Some(result));
         }
-        self.left_index = 0;
-        self.right
-            .poll_next_unpin(cx)
-            .map(|maybe_batch| match maybe_batch {
-                Some(Ok(batch)) => {
-                    let start = Instant::now();
-                    let result =
-                        build_batch(self.left_index, &batch, left_data, &self.schema);
-                    self.num_input_batches += 1;
-                    self.num_input_rows += batch.num_rows();
-                    if let Ok(ref batch) = result {
-                        self.join_time += start.elapsed().as_millis() as usize;
-                        self.num_output_batches += 1;
-                        self.num_output_rows += batch.num_rows();
-                    }
-                    self.left_index = 1;
-
-                    let mut right_batch = self.right_batch.lock();
-                    *right_batch = Some(batch);
-
-                    Some(result)
-                }
-                other => {
-                    debug!(
-                        "Processed {} probe-side input batches containing {} rows and \
-                        produced {} output batches containing {} rows in {} ms",
-                        self.num_input_batches,
-                        self.num_input_rows,
-                        self.num_output_batches,
-                        self.num_output_rows,
-                        self.join_time
-                    );
-                    other
-                }
-            })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::execution::context::SessionConfig;
+    use crate::execution::disk_manager::DiskManagerConfig;
+    use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+    use crate::physical_plan::{common, memory::MemoryExec};
+    use crate::prelude::SessionContext;
+    use crate::test::build_table_i32;
+    use arrow::util::pretty::pretty_format_batches;
+
+    /// A left side of three batches of two rows each
+    fn build_left_table() -> Arc<dyn ExecutionPlan> {
+        let batches: Vec<_> = (0..3)
+            .map(|i| {
+                build_table_i32(
+                    ("a1", &vec![2 * i, 2 * i + 1]),
+                    ("b1", &vec![10 * i, 10 * i + 1]),
+                    ("c1", &vec![100 * i, 100 * i + 1]),
+                )
+            })
+            .collect();
+        let schema = batches[0].schema();
+        Arc::new(MemoryExec::try_new(&[batches], schema, None).unwrap())
+    }
+
+    fn build_right_table() -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(
+            ("a2", &vec![12, 2, 10]),
+            ("b2", &vec![10, 2, 10]),
+            ("c2", &vec![40, 80, 100]),
+        );
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    /// Executes the cross join of the test tables with `runtime_config`,
+    /// returning the sorted lines of its pretty printed output
+    async fn join_collect(runtime_config: RuntimeConfig) -> Result<Vec<String>> {
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let session_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime);
+        let task_ctx = session_ctx.task_ctx();
+
+        let join = CrossJoinExec::new(build_left_table(), build_right_table());
+        let batches = common::collect(join.execute(0, task_ctx)?).await?;
+        let mut lines: Vec<_> = pretty_format_batches(&batches)?
+            .to_string()
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+        lines.sort_unstable();
+        Ok(lines)
+    }
+
+    #[tokio::test]
+    async fn overallocation() -> Result<()> {
+        let runtime_config = RuntimeConfig::new()
+            .with_memory_limit(100, 1.0)
+            .with_disk_manager(DiskManagerConfig::Disabled);
+        let err = join_collect(runtime_config).await.unwrap_err();
+
+        assert!(
+            err.to_string().contains(
+                "Memory Exhausted while loading the build side of CrossJoinExec \
+                (DiskManager is disabled)"
+            ),
+            "Wrong error: {err}",
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spill_build_side() -> Result<()> {
+        let expected = join_collect(RuntimeConfig::new()).await?;
+        // the header, the separators and 6 * 3 rows
+        assert_eq!(expected.len(), 4 + 18);
+
+        // only the first left batch fits in memory
+        let task_ctx = SessionContext::new().task_ctx();
+        let left = common::collect(build_left_table().execute(0, task_ctx)?).await?;
+        let runtime_config =
+            RuntimeConfig::new().with_memory_limit(left[0].get_array_memory_size(), 1.0);
+        assert_eq!(join_collect(runtime_config).await?, expected);
+
+        // nothing fits in memory
+        let runtime_config = RuntimeConfig::new().with_memory_limit(1, 1.0);
+        assert_eq!(join_collect(runtime_config).await?, expected);
+        Ok(())
+    }
 
     #[tokio::test]
     async fn test_stats_cartesian_product() {
@@ -461,63 +616,60 @@ mod tests {
         let right_bytes = 27;
 
         let left = Statistics {
-            is_exact: true,
-            num_rows: Some(left_row_count),
-            total_byte_size: Some(left_bytes),
-            column_statistics: Some(vec![
+            num_rows: Precision::Exact(left_row_count),
+            total_byte_size: Precision::Exact(left_bytes),
+            column_statistics: vec![
                 ColumnStatistics {
-                    distinct_count: Some(5),
-                    max_value: Some(ScalarValue::Int64(Some(21))),
-                    min_value: Some(ScalarValue::Int64(Some(-4))),
-                    null_count: Some(0),
+                    distinct_count: Precision::Exact(5),
+                    max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
+                    min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
+                    null_count: Precision::Exact(0),
                 },
                 ColumnStatistics {
-                    distinct_count: Some(1),
-                    max_value: Some(ScalarValue::Utf8(Some(String::from("x")))),
-                    min_value: Some(ScalarValue::Utf8(Some(String::from("a")))),
-                    null_count: Some(3),
+                    distinct_count: Precision::Exact(1),
+                    max_value: Precision::Exact(ScalarValue::Utf8(Some("x".into()))),
+                    min_value: Precision::Exact(ScalarValue::Utf8(Some("a".into()))),
+                    null_count: Precision::Exact(3),
                 },
-            ]),
+            ],
         };
 
         let right = Statistics {
-            is_exact: true,
-            num_rows: Some(right_row_count),
-            total_byte_size: Some(right_bytes),
-            column_statistics: Some(vec![ColumnStatistics {
-                distinct_count: Some(3),
-                max_value: Some(ScalarValue::Int64(Some(12))),
-                min_value: Some(ScalarValue::Int64(Some(0))),
-                null_count: Some(2),
-            }]),
+            num_rows: Precision::Exact(right_row_count),
+            total_byte_size: Precision::Exact(right_bytes),
+            column_statistics: vec![ColumnStatistics {
+                distinct_count: Precision::Exact(3),
+                max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
+                min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
+                null_count: Precision::Exact(2),
+            }],
         };
 
-        let result = stats_cartesian_product(left, 3, right, 2);
+        let result = stats_cartesian_product(left, right);
 
         let expected = Statistics {
-            is_exact: true,
-            num_rows: Some(left_row_count * right_row_count),
-            total_byte_size: Some(2 * left_bytes * right_bytes),
-            column_statistics: Some(vec![
+            num_rows: Precision::Exact(left_row_count * right_row_count),
+            total_byte_size: Precision::Inexact(2 * left_bytes * right_bytes),
+            column_statistics: vec![
                 ColumnStatistics {
-                    distinct_count: Some(5),
-                    max_value: Some(ScalarValue::Int64(Some(21))),
-                    min_value: Some(ScalarValue::Int64(Some(-4))),
-                    null_count: Some(0),
+                    distinct_count: Precision::Exact(5),
+                    max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
+                    min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
+                    null_count: Precision::Exact(0),
                 },
                 ColumnStatistics {
-                    distinct_count: Some(1),
-                    max_value: Some(ScalarValue::Utf8(Some(String::from("x")))),
-                    min_value: Some(ScalarValue::Utf8(Some(String::from("a")))),
-                    null_count: Some(3 * right_row_count),
+                    distinct_count: Precision::Exact(1),
+                    max_value: Precision::Exact(ScalarValue::Utf8(Some("x".into()))),
+                    min_value: Precision::Exact(ScalarValue::Utf8(Some("a".into()))),
+                    null_count: Precision::Exact(3 * right_row_count),
                 },
                 ColumnStatistics {
-                    distinct_count: Some(3),
-                    max_value: Some(ScalarValue::Int64(Some(12))),
-                    min_value: Some(ScalarValue::Int64(Some(0))),
-                    null_count: Some(2 * left_row_count),
+                    distinct_count: Precision::Exact(3),
+                    max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
+                    min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
+                    null_count: Precision::Exact(2 * left_row_count),
                 },
-            ]),
+            ],
         };
 
         assert_eq!(result, expected);
@@ -528,63 +680,60 @@ mod tests {
         let left_row_count = 11;
 
         let left = Statistics {
-            is_exact: true,
-            num_rows: Some(left_row_count),
-            total_byte_size: Some(23),
-            column_statistics: Some(vec![
+            num_rows: Precision::Exact(left_row_count),
+            total_byte_size: Precision::Exact(23),
+            column_statistics: vec![
                 ColumnStatistics {
-                    distinct_count: Some(5),
-                    max_value: Some(ScalarValue::Int64(Some(21))),
-                    min_value: Some(ScalarValue::Int64(Some(-4))),
-                    null_count: Some(0),
+                    distinct_count: Precision::Exact(5),
+                    max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
+                    min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
+                    null_count: Precision::Exact(0),
                 },
                 ColumnStatistics {
-                    distinct_count: Some(1),
-                    max_value: Some(ScalarValue::Utf8(Some(String::from("x")))),
-                    min_value: Some(ScalarValue::Utf8(Some(String::from("a")))),
-                    null_count: Some(3),
+                    distinct_count: Precision::Exact(1),
+                    max_value: Precision::Exact(ScalarValue::Utf8(Some("x".into()))),
+                    min_value: Precision::Exact(ScalarValue::Utf8(Some("a".into()))),
+                    null_count: Precision::Exact(3),
                 },
-            ]),
+            ],
         };
 
         let right = Statistics {
-            is_exact: true,
-            num_rows: None,        // not defined!
-            total_byte_size: None, // not defined!
-            column_statistics: Some(vec![ColumnStatistics {
-                distinct_count: Some(3),
-                max_value: Some(ScalarValue::Int64(Some(12))),
-                min_value: Some(ScalarValue::Int64(Some(0))),
-                null_count: Some(2),
-            }]),
+            num_rows: Precision::Absent,        // not defined!
+            total_byte_size: Precision::Absent, // not defined!
+            column_statistics: vec![ColumnStatistics {
+                distinct_count: Precision::Exact(3),
+                max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
+                min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
+                null_count: Precision::Exact(2),
+            }],
         };
 
-        let result = stats_cartesian_product(left, 3, right, 2);
+        let result = stats_cartesian_product(left, right);
 
         let expected = Statistics {
-            is_exact: true,
-            num_rows: None,
-            total_byte_size: None,
-            column_statistics: Some(vec![
+            num_rows: Precision::Absent,
+            total_byte_size: Precision::Absent,
+            column_statistics: vec![
                 ColumnStatistics {
-                    distinct_count: Some(5),
-                    max_value: Some(ScalarValue::Int64(Some(21))),
-                    min_value: Some(ScalarValue::Int64(Some(-4))),
-                    null_count: None, // we don't know the row count on the right
+                    distinct_count: Precision::Exact(5),
+                    max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
+                    min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
+                    null_count: Precision::Absent, // we don't know the row count on the right
                 },
                 ColumnStatistics {
-                    distinct_count: Some(1),
-                    max_value: Some(ScalarValue::Utf8(Some(String::from("x")))),
-                    min_value: Some(ScalarValue::Utf8(Some(String::from("a")))),
-                    null_count: None, // we don't know the row count on the right
+                    distinct_count: Precision::Exact(1),
+                    max_value: Precision::Exact(ScalarValue::Utf8(Some("x".into()))),
+                    min_value: Precision::Exact(ScalarValue::Utf8(Some("a".into()))),
+                    null_count: Precision::Absent, // we don't know the row count on the right
                 },
                 ColumnStatistics {
-                    distinct_count: Some(3),
-                    max_value: Some(ScalarValue::Int64(Some(12))),
-                    min_value: Some(ScalarValue::Int64(Some(0))),
-                    null_count: Some(2 * left_row_count),
+                    distinct_count: Precision::Exact(3),
+                    max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
+                    min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
+                    null_count: Precision::Exact(2 * left_row_count),
                 },
-            ]),
+            ],
         };
 
         assert_eq!(result, expected);
@@ -53,6 +53,7 @@ use arrow::array::{
 };
 
 use datafusion_common::cast::{as_dictionary_array, as_string_array};
+use datafusion_common::ScalarValue;
 
 use hashbrown::raw::RawTable;
 
@@ -324,6 +325,7 @@ impl ExecutionPlan for HashJoinExec {
                         | JoinType::Full
                         | JoinType::LeftAnti
                         | JoinType::LeftSemi
+                        | JoinType::LeftMark
                 ));
 
         if breaking {
@@ -354,6 +356,7 @@ impl ExecutionPlan for HashJoinExec {
                 JoinType::Left
                 | JoinType::LeftSemi
                 | JoinType::LeftAnti
+                | JoinType::LeftMark
                 | JoinType::Full => Partitioning::UnknownPartitioning(
                     self.right.output_partitioning().partition_count(),
                 ),
@@ -489,6 +492,8 @@ impl ExecutionPlan for HashJoinExec {
             self.right.clone(),
             self.on.clone(),
             &self.join_type,
+            &self.schema,
+            self.filter.as_ref(),
         )
     }
 }
@@ -962,7 +967,7 @@ fn equal_rows(
                     false
                 }
             }
-            DataType::Timestamp(time_unit, None) => match time_unit {
+            DataType::Timestamp(time_unit, _) => match time_unit {
                 TimeUnit::Second => {
                     equal_rows_elem!(
                         TimestampSecondArray,
@@ -1128,6 +1133,21 @@ fn equal_rows(
                         }
                     }
                 }
+            DataType::Dictionary(_, _) | DataType::Struct(_) | DataType::List(_) => {
+                // compare the left row as a scalar, as these types have no
+                // typed accessor for a single value
+                match (l.is_valid(left), r.is_valid(right)) {
+                    (true, true) => match ScalarValue::try_from_array(l, left) {
+                        Ok(value) => value.eq_array(r, right),
+                        Err(e) => {
+                            err = Some(Err(e));
+                            false
+                        }
+                    },
+                    (false, false) => null_equals_null,
+                    _ => false,
+                }
+            }
             other => {
                 // This is internal because we should have caught this before.
                 err = Some(Err(DataFusionError::Internal(format!(
@@ -2244,6 +2264,60 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_left_mark_with_filter() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let left = build_semi_anti_left_table();
+        let right = build_semi_anti_right_table();
+
+        // left_table left mark join right_table on left_table.b1 = right_table.b2 and right_table.a2 != 8
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        let column_indices = vec![ColumnIndex {
+            index: 0,
+            side: JoinSide::Right,
+        }];
+        let intermediate_schema =
+            Schema::new(vec![Field::new("x", DataType::Int32, true)]);
+        let filter_expression = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("x", 0)),
+            Operator::NotEq,
+            Arc::new(Literal::new(ScalarValue::Int32(Some(8)))),
+        )) as Arc<dyn PhysicalExpr>;
+        let filter =
+            JoinFilter::new(filter_expression, column_indices, intermediate_schema);
+
+        let join = join_with_filter(left, right, on, filter, &JoinType::LeftMark, false)?;
+
+        let columns = columns(&join.schema());
+        assert_eq!(columns, vec!["a1", "b1", "c1", "mark"]);
+
+        let stream = join.execute(0, task_ctx)?;
+        let batches = common::collect(stream).await?;
+
+        // every left row, marked if it matched
+        let expected = vec![
+            "+----+----+-----+-------+",
+            "| a1 | b1 | c1  | mark  |",
+            "+----+----+-----+-------+",
+            "| 1  | 1  | 10  | false |",
+            "| 11 | 8  | 110 | false |",
+            "| 13 | 10 | 130 | true  |",
+            "| 3  | 3  | 30  | false |",
+            "| 5  | 5  | 50  | false |",
+            "| 7  | 7  | 70  | false |",
+            "| 9  | 8  | 90  | false |",
+            "+----+----+-----+-------+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn join_left_anti() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -17,6 +17,7 @@
 
 //! DataFusion Join implementations
 
+mod band_join;
 mod cross_join;
 mod hash_join;
 mod nested_loop_join;
@@ -35,6 +36,7 @@ pub enum PartitionMode {
     Auto,
 }
 
+pub use band_join::{BandCondition, BandJoinExec};
 pub use cross_join::CrossJoinExec;
 pub use hash_join::HashJoinExec;
 pub use nested_loop_join::NestedLoopJoinExec;
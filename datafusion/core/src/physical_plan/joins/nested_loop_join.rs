@@ -49,10 +49,11 @@ use std::time::Instant;
 
 use crate::error::Result;
 use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use crate::physical_plan::coalesce_batches::concat_batches;
 
-/// Data of the left side
-type JoinLeftData = RecordBatch;
+/// Data of the left side, and the reservation of the memory it uses
+type JoinLeftData = (RecordBatch, MemoryReservation);
 
 ///
 #[derive(Debug)]
@@ -97,6 +98,26 @@ impl NestedLoopJoinExec {
         })
     }
 
+    /// left side
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// right side
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Filters applied while finding matching rows
+    pub fn filter(&self) -> Option<&JoinFilter> {
+        self.filter.as_ref()
+    }
+
+    /// How the join is performed
+    pub fn join_type(&self) -> &JoinType {
+        &self.join_type
+    }
+
     fn is_single_partition_for_left(&self) -> bool {
         matches!(
             self.required_input_distribution()[0],
@@ -130,6 +151,7 @@ impl ExecutionPlan for NestedLoopJoinExec {
             | JoinType::Left
             | JoinType::LeftSemi
             | JoinType::LeftAnti
+            | JoinType::LeftMark
             | JoinType::Full => self.left.output_partitioning(),
             // use the right partition
             JoinType::Right => {
@@ -246,6 +268,8 @@ impl ExecutionPlan for NestedLoopJoinExec {
             self.right.clone(),
             vec![],
             &self.join_type,
+            &self.schema,
+            self.filter.as_ref(),
         )
     }
 }
@@ -254,7 +278,11 @@ impl ExecutionPlan for NestedLoopJoinExec {
 // left and right node.
 fn distribution_from_join_type(join_type: &JoinType) -> Vec<Distribution> {
     match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::LeftSemi
+        | JoinType::LeftAnti
+        | JoinType::LeftMark => {
             // need the left data, and the right should be one partition
             vec![
                 Distribution::UnspecifiedDistribution,
@@ -282,18 +310,28 @@ async fn load_left_specified_partition(
     context: Arc<TaskContext>,
 ) -> Result<JoinLeftData> {
     let start = Instant::now();
-    let stream = left.execute(partition, context)?;
-
-    // Load all batches and count the rows
-    let (batches, num_rows) = stream
-        .try_fold((Vec::new(), 0usize), |mut acc, batch| async {
+    let stream = left.execute(partition, context.clone())?;
+    let reservation = MemoryConsumer::new(format!("NestedLoopJoinLoad[{partition}]"))
+        .register(context.memory_pool());
+
+    // Load all batches and count the rows, failing once the left side
+    // exceeds the memory limit
+    let (batches, num_rows, mut reservation) = stream
+        .try_fold((Vec::new(), 0usize, reservation), |mut acc, batch| async {
+            acc.2.try_grow(batch.get_array_memory_size())?;
             acc.1 += batch.num_rows();
             acc.0.push(batch);
             Ok(acc)
         })
         .await?;
 
+    // the merged batch is reserved alongside the batches it is built from,
+    // which are released once they are dropped
     let merged_batch = concat_batches(&left.schema(), &batches, num_rows)?;
+    let merged_size = merged_batch.get_array_memory_size();
+    reservation.try_resize(reservation.size() + merged_size)?;
+    drop(batches);
+    reservation.resize(merged_size);
 
     debug!(
         "Built left-side of nested loop join containing {} rows in {} ms for partition {}",
@@ -302,7 +340,7 @@ async fn load_left_specified_partition(
         partition
     );
 
-    Ok(merged_batch)
+    Ok((merged_batch, reservation))
 }
 
 /// A stream that issues [RecordBatch]es as they arrive from the right  of the join.
@@ -359,7 +397,7 @@ impl NestedLoopJoinStream {
     ) -> Poll<Option<ArrowResult<RecordBatch>>> {
         // all left row
         let left_data = match ready!(self.left_fut.get(cx)) {
-            Ok(left_data) => left_data,
+            Ok((left_data, _)) => left_data,
             Err(e) => return Poll::Ready(Some(Err(e))),
         };
 
@@ -400,12 +438,7 @@ impl NestedLoopJoinStream {
                     let mut left_indices_builder = UInt64Builder::new();
                     let mut right_indices_builder = UInt32Builder::new();
                     let left_right_indices = match indices_result {
-                        Err(_) => {
-                            // TODO why the type of result stream is `Result<T, ArrowError>`, and not the `DataFusionError`
-                            Err(ArrowError::ComputeError(
-                                "Build left right indices error".to_string(),
-                            ))
-                        }
+                        Err(e) => Err(ArrowError::from(e)),
                         Ok(indices) => {
                             for (left_side, right_side) in indices {
                                 left_indices_builder.append_values(
@@ -516,6 +549,8 @@ mod tests {
     use datafusion_expr::Operator;
 
     use super::*;
+    use crate::execution::context::SessionConfig;
+    use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
     use crate::physical_plan::joins::utils::JoinSide;
     use crate::prelude::SessionContext;
     use datafusion_common::ScalarValue;
@@ -830,6 +865,38 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_left_mark_with_filter() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let left = build_left_table();
+        let right = build_right_table();
+
+        let filter = prepare_join_filter();
+        let (columns, batches) = multi_partitioned_join_collect(
+            left,
+            right,
+            &JoinType::LeftMark,
+            Some(filter),
+            task_ctx,
+        )
+        .await?;
+        assert_eq!(columns, vec!["a1", "b1", "c1", "mark"]);
+        let expected = vec![
+            "+----+----+-----+-------+",
+            "| a1 | b1 | c1  | mark  |",
+            "+----+----+-----+-------+",
+            "| 11 | 8  | 110 | false |",
+            "| 5  | 5  | 50  | true  |",
+            "| 9  | 8  | 90  | false |",
+            "+----+----+-----+-------+",
+        ];
+
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn join_right_semi_with_filter() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -890,4 +957,52 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn overallocation() -> Result<()> {
+        let left = build_table(
+            ("a1", &vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]),
+            ("b1", &vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]),
+            ("c1", &vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]),
+        );
+        let right = build_right_table();
+        let filter = prepare_join_filter();
+
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+        ];
+
+        for join_type in join_types {
+            let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+            let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+            let session_ctx =
+                SessionContext::with_config_rt(SessionConfig::default(), runtime);
+            let task_ctx = session_ctx.task_ctx();
+
+            let join = NestedLoopJoinExec::try_new(
+                left.clone(),
+                right.clone(),
+                Some(filter.clone()),
+                &join_type,
+            )?;
+            let stream = join.execute(0, task_ctx)?;
+            let err = common::collect(stream).await.unwrap_err();
+
+            assert!(
+                err.to_string()
+                    .contains("Resources exhausted: Failed to allocate additional")
+                    && err.to_string().contains("NestedLoopJoinLoad[0]"),
+                "Wrong error for {join_type}: {err}",
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -52,7 +52,7 @@ use crate::physical_plan::{
     Partitioning, PhysicalExpr, RecordBatchStream, SendableRecordBatchStream, Statistics,
 };
 
-use datafusion_physical_expr::rewrite::TreeNodeRewritable;
+use datafusion_physical_expr::rewrite::TreeNode;
 
 /// join execution plan executes partitions in parallel and combines them into a set of
 /// partitions.
@@ -98,10 +98,10 @@ impl SortMergeJoinExec {
         let left_schema = left.schema();
         let right_schema = right.schema();
 
-        if join_type == JoinType::RightSemi {
-            return Err(DataFusionError::NotImplemented(
-                "SortMergeJoinExec does not support JoinType::RightSemi".to_string(),
-            ));
+        if matches!(join_type, JoinType::RightSemi | JoinType::LeftMark) {
+            return Err(DataFusionError::NotImplemented(format!(
+                "SortMergeJoinExec does not support JoinType::{join_type}"
+            )));
         }
 
         check_join_is_valid(&left_schema, &right_schema, &on)?;
@@ -133,7 +133,8 @@ impl SortMergeJoinExec {
             JoinType::Inner
             | JoinType::Left
             | JoinType::LeftSemi
-            | JoinType::LeftAnti => {
+            | JoinType::LeftAnti
+            | JoinType::LeftMark => {
                 left.output_ordering().map(|sort_exprs| sort_exprs.to_vec())
             }
             JoinType::RightSemi | JoinType::RightAnti => right
@@ -193,6 +194,31 @@ impl SortMergeJoinExec {
     pub fn on(&self) -> &[(Column, Column)] {
         &self.on
     }
+
+    /// Left (streamed) side of the join
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// Right (buffered) side of the join
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// How the join is performed
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    /// Sort options of the join keys, one per entry of `on`
+    pub fn sort_options(&self) -> &[SortOptions] {
+        &self.sort_options
+    }
+
+    /// Whether null values in join keys are considered equal
+    pub fn null_equals_null(&self) -> bool {
+        self.null_equals_null
+    }
 }
 
 impl ExecutionPlan for SortMergeJoinExec {
@@ -284,7 +310,8 @@ impl ExecutionPlan for SortMergeJoinExec {
             | JoinType::Left
             | JoinType::Full
             | JoinType::LeftAnti
-            | JoinType::LeftSemi => (
+            | JoinType::LeftSemi
+            | JoinType::LeftMark => (
                 self.left.clone(),
                 self.right.clone(),
                 self.on.iter().map(|on| on.0.clone()).collect(),
@@ -345,6 +372,8 @@ impl ExecutionPlan for SortMergeJoinExec {
             self.right.clone(),
             self.on.clone(),
             &self.join_type,
+            &self.schema,
+            None,
         )
     }
 }
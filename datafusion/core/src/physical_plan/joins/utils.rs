@@ -26,12 +26,14 @@ use arrow::array::{
     UInt32Builder, UInt64Array,
 };
 use arrow::compute;
-use arrow::datatypes::{Field, Schema, UInt32Type, UInt64Type};
+use arrow::datatypes::{DataType, Field, Schema, UInt32Type, UInt64Type};
 use arrow::error::{ArrowError, Result as ArrowResult};
 use arrow::record_batch::RecordBatch;
 use datafusion_common::cast::as_boolean_array;
 use datafusion_common::ScalarValue;
-use datafusion_physical_expr::{EquivalentClass, PhysicalExpr};
+use datafusion_physical_expr::{
+    AnalysisContext, EquivalentClass, ExprBoundaries, PhysicalExpr,
+};
 use futures::future::{BoxFuture, Shared};
 use futures::{ready, FutureExt};
 use parking_lot::Mutex;
@@ -42,9 +44,10 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use crate::physical_plan::{
-    ColumnStatistics, EquivalenceProperties, ExecutionPlan, Partitioning, Statistics,
+    ColumnStatistics, EquivalenceProperties, ExecutionPlan, Partitioning, Precision,
+    Statistics,
 };
-use datafusion_physical_expr::rewrite::TreeNodeRewritable;
+use datafusion_physical_expr::rewrite::TreeNode;
 
 /// The on clause of the join, as vector of (left, right) columns.
 pub type JoinOn = Vec<(Column, Column)>;
@@ -100,9 +103,11 @@ pub fn partitioned_join_output_partitioning(
     left_columns_len: usize,
 ) -> Partitioning {
     match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
-            left_partitioning
-        }
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::LeftSemi
+        | JoinType::LeftAnti
+        | JoinType::LeftMark => left_partitioning,
         JoinType::RightSemi | JoinType::RightAnti => right_partitioning,
         JoinType::Right => {
             adjust_right_output_partitioning(right_partitioning, left_columns_len)
@@ -176,7 +181,7 @@ pub fn combine_join_equivalence_properties(
 
             new_properties.extend(new_right_properties);
         }
-        JoinType::LeftSemi | JoinType::LeftAnti => {
+        JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
             new_properties.extend(left_properties.classes().to_vec())
         }
         JoinType::RightSemi | JoinType::RightAnti => {
@@ -184,6 +189,27 @@ pub fn combine_join_equivalence_properties(
         }
     }
 
+    // The columns of the side of an outer join that may be padded with nulls
+    // are no longer constant
+    let new_right_constants = right_properties
+        .constants()
+        .iter()
+        .map(|col| Column::new(col.name(), left_columns_len + col.index()));
+    match join_type {
+        JoinType::Inner => {
+            new_properties.add_constants(left_properties.constants().to_vec());
+            new_properties.add_constants(new_right_constants);
+        }
+        JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
+            new_properties.add_constants(left_properties.constants().to_vec())
+        }
+        JoinType::Right => new_properties.add_constants(new_right_constants),
+        JoinType::RightSemi | JoinType::RightAnti => {
+            new_properties.add_constants(right_properties.constants().to_vec())
+        }
+        JoinType::Full => {}
+    }
+
     if join_type == JoinType::Inner {
         on.iter().for_each(|(column1, column2)| {
             let new_column2 =
@@ -218,11 +244,18 @@ pub fn cross_join_equivalence_properties(
         })
         .collect::<Vec<_>>();
     new_properties.extend(new_right_properties);
+    new_properties.add_constants(left_properties.constants().to_vec());
+    new_properties.add_constants(
+        right_properties
+            .constants()
+            .iter()
+            .map(|col| Column::new(col.name(), left_columns_len + col.index())),
+    );
     new_properties
 }
 
 /// Used in ColumnIndex to distinguish which side the index is for
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JoinSide {
     /// Left side of the join
     Left,
@@ -311,6 +344,7 @@ fn output_join_field(old_field: &Field, join_type: &JoinType, is_left: bool) ->
         JoinType::RightSemi => false, // doesn't introduce nulls
         JoinType::LeftAnti => false, // doesn't introduce nulls (or can it??)
         JoinType::RightAnti => false, // doesn't introduce nulls (or can it??)
+        JoinType::LeftMark => false, // doesn't introduce nulls
     };
 
     if force_nullable {
@@ -391,6 +425,26 @@ pub fn build_join_schema(
                 )
             })
             .unzip(),
+        JoinType::LeftMark => {
+            // the mark isn't a column of either side, see `build_batch_from_indices`
+            let (mut fields, column_indices): (Vec<Field>, Vec<ColumnIndex>) = left
+                .fields()
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(index, f)| {
+                    (
+                        f,
+                        ColumnIndex {
+                            index,
+                            side: JoinSide::Left,
+                        },
+                    )
+                })
+                .unzip();
+            fields.push(Field::new("mark", DataType::Boolean, false));
+            (fields, column_indices)
+        }
     };
 
     (Schema::new(fields), column_indices)
@@ -463,26 +517,30 @@ struct PartialJoinStatistics {
     pub column_statistics: Vec<ColumnStatistics>,
 }
 
-/// Estimate the statistics for the given join's output.
+/// Estimate the statistics for the given join's output, which has the given
+/// schema.
 pub(crate) fn estimate_join_statistics(
     left: Arc<dyn ExecutionPlan>,
     right: Arc<dyn ExecutionPlan>,
     on: JoinOn,
     join_type: &JoinType,
+    schema: &Schema,
+    filter: Option<&JoinFilter>,
 ) -> Statistics {
     let left_stats = left.statistics();
     let right_stats = right.statistics();
 
-    let join_stats = estimate_join_cardinality(join_type, left_stats, right_stats, &on);
-    let (num_rows, column_statistics) = match join_stats {
-        Some(stats) => (Some(stats.num_rows), Some(stats.column_statistics)),
-        None => (None, None),
-    };
-    Statistics {
-        num_rows,
-        total_byte_size: None,
-        column_statistics,
-        is_exact: false,
+    match estimate_join_cardinality(join_type, left_stats, right_stats, &on, filter) {
+        Some(stats) => Statistics {
+            num_rows: Precision::Inexact(stats.num_rows),
+            total_byte_size: Precision::Absent,
+            column_statistics: stats
+                .column_statistics
+                .into_iter()
+                .map(ColumnStatistics::to_inexact)
+                .collect(),
+        },
+        None => Statistics::new_unknown(schema),
     }
 }
 
@@ -492,16 +550,22 @@ fn estimate_join_cardinality(
     left_stats: Statistics,
     right_stats: Statistics,
     on: &JoinOn,
+    filter: Option<&JoinFilter>,
 ) -> Option<PartialJoinStatistics> {
     match join_type {
         JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
-            let left_num_rows = left_stats.num_rows?;
-            let right_num_rows = right_stats.num_rows?;
+            let left_num_rows = *left_stats.num_rows.get_value()?;
+            let right_num_rows = *right_stats.num_rows.get_value()?;
+            let filter_selectivity = filter
+                .and_then(|filter| {
+                    estimate_join_filter_selectivity(filter, &left_stats, &right_stats)
+                })
+                .unwrap_or(1.0);
 
             // Take the left_col_stats and right_col_stats using the index
             // obtained from index() method of the each element of 'on'.
-            let all_left_col_stats = left_stats.column_statistics?;
-            let all_right_col_stats = right_stats.column_statistics?;
+            let all_left_col_stats = left_stats.column_statistics;
+            let all_right_col_stats = right_stats.column_statistics;
             let (left_col_stats, right_col_stats) = on
                 .iter()
                 .map(|(left, right)| {
@@ -512,13 +576,30 @@ fn estimate_join_cardinality(
                 })
                 .unzip::<_, _, Vec<_>, Vec<_>>();
 
-            let ij_cardinality = estimate_inner_join_cardinality(
-                left_num_rows,
-                right_num_rows,
-                left_col_stats,
-                right_col_stats,
-                left_stats.is_exact && right_stats.is_exact,
-            )?;
+            let bounds_are_exact =
+                left_col_stats.iter().chain(&right_col_stats).all(|s| {
+                    s.min_value.is_exact() == Some(true)
+                        && s.max_value.is_exact() == Some(true)
+                });
+            let is_exact = left_stats.num_rows.is_exact() == Some(true)
+                && right_stats.num_rows.is_exact() == Some(true)
+                && bounds_are_exact;
+
+            let ij_cardinality = if on.is_empty() {
+                // Without equijoin columns, every pair of rows is a candidate
+                left_num_rows.checked_mul(right_num_rows)?
+            } else {
+                estimate_inner_join_cardinality(
+                    left_num_rows,
+                    right_num_rows,
+                    left_col_stats,
+                    right_col_stats,
+                    is_exact,
+                )?
+            };
+            // The filter is applied to the rows matched by the equijoin
+            let ij_cardinality =
+                (ij_cardinality as f64 * filter_selectivity).ceil() as usize;
 
             // The cardinality for inner join can also be used to estimate
             // the cardinality of left/right/full outer joins as long as it
@@ -552,10 +633,36 @@ fn estimate_join_cardinality(
         JoinType::LeftSemi
         | JoinType::RightSemi
         | JoinType::LeftAnti
-        | JoinType::RightAnti => None,
+        | JoinType::RightAnti
+        | JoinType::LeftMark => None,
     }
 }
 
+/// Estimate the fraction of the rows matched by the join that are selected by
+/// the join filter, using the statistics of the columns it references.
+fn estimate_join_filter_selectivity(
+    filter: &JoinFilter,
+    left_stats: &Statistics,
+    right_stats: &Statistics,
+) -> Option<f64> {
+    let column_boundaries = filter
+        .column_indices()
+        .iter()
+        .map(|column_index| {
+            let stats = match column_index.side {
+                JoinSide::Left => left_stats,
+                JoinSide::Right => right_stats,
+            };
+            stats
+                .column_statistics
+                .get(column_index.index)
+                .and_then(ExprBoundaries::from_column)
+        })
+        .collect();
+    let context = AnalysisContext::new(filter.schema(), column_boundaries);
+    filter.expression().analyze(context).boundaries?.selectivity
+}
+
 /// Estimate the inner join cardinality by using the basic building blocks of
 /// column-level statistics and the total row count. This is a very naive and
 /// a very conservative implementation that can quickly give up if there is not
@@ -572,8 +679,8 @@ fn estimate_inner_join_cardinality(
 
     let mut join_selectivity = None;
     for (left_stat, right_stat) in left_col_stats.iter().zip(right_col_stats.iter()) {
-        if (left_stat.min_value.clone()? > right_stat.max_value.clone()?)
-            || (left_stat.max_value.clone()? < right_stat.min_value.clone()?)
+        if (left_stat.min_value.get_value()? > right_stat.max_value.get_value()?)
+            || (left_stat.max_value.get_value()? < right_stat.min_value.get_value()?)
         {
             // If there is no overlap in any of the join columns, that means the join
             // itself is disjoint and the cardinality is 0. Though we can only assume
@@ -614,17 +721,21 @@ fn estimate_inner_join_cardinality(
 /// has min/max values, then they might be used as a fallback option. Otherwise,
 /// returns None.
 fn max_distinct_count(num_rows: usize, stats: ColumnStatistics) -> Option<usize> {
-    match (stats.distinct_count, stats.max_value, stats.min_value) {
-        (Some(_), _, _) => stats.distinct_count,
+    match (
+        stats.distinct_count.get_value(),
+        stats.max_value.get_value(),
+        stats.min_value.get_value(),
+    ) {
+        (Some(distinct_count), _, _) => Some(*distinct_count),
         (_, Some(max), Some(min)) => {
             // Note that float support is intentionally omitted here, since the computation
             // of a range between two float values is not trivial and the result would be
             // highly inaccurate.
-            let numeric_range = get_int_range(min, max)?;
+            let numeric_range = get_int_range(min.clone(), max.clone())?;
 
             // The number can never be greater than the number of rows we have (minus
             // the nulls, since they don't count as distinct values).
-            let ceiling = num_rows - stats.null_count.unwrap_or(0);
+            let ceiling = num_rows - stats.null_count.get_value().unwrap_or(&0);
             Some(numeric_range.min(ceiling))
         }
         _ => None,
@@ -706,7 +817,11 @@ impl<T: 'static> OnceFut<T> {
 pub(crate) fn need_produce_result_in_final(join_type: JoinType) -> bool {
     matches!(
         join_type,
-        JoinType::Left | JoinType::LeftAnti | JoinType::LeftSemi | JoinType::Full
+        JoinType::Left
+            | JoinType::LeftAnti
+            | JoinType::LeftSemi
+            | JoinType::LeftMark
+            | JoinType::Full
     )
 }
 
@@ -718,11 +833,21 @@ pub(crate) fn need_produce_result_in_final(join_type: JoinType) -> bool {
 /// join_type: `Left`
 ///
 /// The result is: ([1,4], [null, null])
+///
+/// For the `LeftMark` join, the result is every left row, with a non null
+/// right index for the matched rows: ([0,1,2,3,4], [0, null, 0, 0, null])
 pub(crate) fn get_final_indices_from_bit_map(
     left_bit_map: &BooleanBufferBuilder,
     join_type: JoinType,
 ) -> (UInt64Array, UInt32Array) {
     let left_size = left_bit_map.len();
+    if join_type == JoinType::LeftMark {
+        let left_indices = UInt64Array::from_iter_values(0..left_size as u64);
+        let right_indices = (0..left_size)
+            .map(|idx| left_bit_map.get_bit(idx).then_some(0))
+            .collect::<UInt32Array>();
+        return (left_indices, right_indices);
+    }
     let left_indices = if join_type == JoinType::LeftSemi {
         (0..left_size)
             .filter_map(|idx| (left_bit_map.get_bit(idx)).then_some(idx as u64))
@@ -820,6 +945,11 @@ pub(crate) fn build_batch_from_indices(
         };
         columns.push(array);
     }
+    // the mark of a `LeftMark` join, which follows the left columns, is true
+    // for the left rows that matched, whose right indices aren't null
+    if schema.fields().len() == column_indices.len() + 1 {
+        columns.push(Arc::new(compute::is_not_null(&right_indices)?));
+    }
     RecordBatch::try_new(Arc::new(schema.clone()), columns)
 }
 
@@ -862,7 +992,7 @@ pub(crate) fn adjust_indices_by_join_type(
             // the left_indices will not be used later for the `right anti` join
             (left_indices, right_indices)
         }
-        JoinType::LeftSemi | JoinType::LeftAnti => {
+        JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark => {
             // matched or unmatched left row will be produced in the end of loop
             // When visit the right batch, we can output the matched left row and don't need to wait the end of loop
             (
@@ -937,8 +1067,10 @@ pub(crate) fn get_semi_indices(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::physical_plan::expressions::{BinaryExpr, Literal};
     use arrow::datatypes::DataType;
     use datafusion_common::ScalarValue;
+    use datafusion_expr::Operator;
 
     fn check(left: &[Column], right: &[Column], on: &[(Column, Column)]) -> Result<()> {
         let left = left
@@ -1055,15 +1187,18 @@ mod tests {
     }
 
     fn create_stats(
-        num_rows: Option<usize>,
-        column_stats: Option<Vec<ColumnStatistics>>,
+        num_rows: usize,
+        column_stats: Vec<ColumnStatistics>,
         is_exact: bool,
     ) -> Statistics {
         Statistics {
-            num_rows,
+            num_rows: if is_exact {
+                Precision::Exact(num_rows)
+            } else {
+                Precision::Inexact(num_rows)
+            },
+            total_byte_size: Precision::Absent,
             column_statistics: column_stats,
-            is_exact,
-            ..Default::default()
         }
     }
 
@@ -1072,10 +1207,16 @@ mod tests {
         max: Option<i64>,
         distinct_count: Option<usize>,
     ) -> ColumnStatistics {
+        fn exact<T>(value: Option<T>) -> Precision<T>
+        where
+            T: std::fmt::Debug + Clone + PartialEq + Eq + PartialOrd,
+        {
+            value.map(Precision::Exact).unwrap_or(Precision::Absent)
+        }
         ColumnStatistics {
-            distinct_count,
-            min_value: min.map(|size| ScalarValue::Int64(Some(size))),
-            max_value: max.map(|size| ScalarValue::Int64(Some(size))),
+            distinct_count: exact(distinct_count),
+            min_value: exact(min.map(|size| ScalarValue::Int64(Some(size)))),
+            max_value: exact(max.map(|size| ScalarValue::Int64(Some(size)))),
             ..Default::default()
         }
     }
@@ -1240,9 +1381,10 @@ mod tests {
             let join_on = vec![(Column::new("a", 0), Column::new("b", 0))];
             let partial_join_stats = estimate_join_cardinality(
                 &join_type,
-                create_stats(Some(left_num_rows), Some(left_col_stats.clone()), false),
-                create_stats(Some(right_num_rows), Some(right_col_stats.clone()), false),
+                create_stats(left_num_rows, left_col_stats.clone(), false),
+                create_stats(right_num_rows, right_col_stats.clone(), false),
                 &join_on,
+                None,
             );
 
             assert_eq!(
@@ -1287,16 +1429,16 @@ mod tests {
     #[test]
     fn test_inner_join_cardinality_decimal_range() -> Result<()> {
         let left_col_stats = vec![ColumnStatistics {
-            distinct_count: None,
-            min_value: Some(ScalarValue::Decimal128(Some(32500), 14, 4)),
-            max_value: Some(ScalarValue::Decimal128(Some(35000), 14, 4)),
+            distinct_count: Precision::Absent,
+            min_value: Precision::Exact(ScalarValue::Decimal128(Some(32500), 14, 4)),
+            max_value: Precision::Exact(ScalarValue::Decimal128(Some(35000), 14, 4)),
             ..Default::default()
         }];
 
         let right_col_stats = vec![ColumnStatistics {
-            distinct_count: None,
-            min_value: Some(ScalarValue::Decimal128(Some(33500), 14, 4)),
-            max_value: Some(ScalarValue::Decimal128(Some(34000), 14, 4)),
+            distinct_count: Precision::Absent,
+            min_value: Precision::Exact(ScalarValue::Decimal128(Some(33500), 14, 4)),
+            max_value: Precision::Exact(ScalarValue::Decimal128(Some(34000), 14, 4)),
             ..Default::default()
         }];
 
@@ -1353,9 +1495,10 @@ mod tests {
 
             let partial_join_stats = estimate_join_cardinality(
                 &join_type,
-                create_stats(Some(1000), Some(left_col_stats.clone()), false),
-                create_stats(Some(2000), Some(right_col_stats.clone()), false),
+                create_stats(1000, left_col_stats.clone(), false),
+                create_stats(2000, right_col_stats.clone(), false),
                 &join_on,
+                None,
             )
             .unwrap();
             assert_eq!(partial_join_stats.num_rows, expected_num_rows);
@@ -1418,9 +1561,10 @@ mod tests {
         for (join_type, expected_num_rows) in cases {
             let partial_join_stats = estimate_join_cardinality(
                 &join_type,
-                create_stats(Some(1000), Some(left_col_stats.clone()), true),
-                create_stats(Some(2000), Some(right_col_stats.clone()), true),
+                create_stats(1000, left_col_stats.clone(), true),
+                create_stats(2000, right_col_stats.clone(), true),
                 &join_on,
+                None,
             )
             .unwrap();
             assert_eq!(partial_join_stats.num_rows, expected_num_rows);
@@ -1432,4 +1576,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_join_cardinality_with_filter() -> Result<()> {
+        let left_stats = create_stats(
+            1000,
+            vec![create_column_stats(Some(0), Some(100), Some(100))],
+            false,
+        );
+        let right_stats = create_stats(
+            2000,
+            vec![create_column_stats(Some(0), Some(100), Some(100))],
+            false,
+        );
+
+        // filter: left.a < 50
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("a", 0)),
+                Operator::Lt,
+                Arc::new(Literal::new(ScalarValue::Int64(Some(50)))),
+            )),
+            vec![ColumnIndex {
+                index: 0,
+                side: JoinSide::Left,
+            }],
+            Schema::new(vec![Field::new("a", DataType::Int64, false)]),
+        );
+
+        // 1000 * 2000 / 100 rows match the equijoin, and 50/101 of them
+        // pass the filter
+        let join_on = vec![(Column::new("a", 0), Column::new("b", 0))];
+        let partial_join_stats = estimate_join_cardinality(
+            &JoinType::Inner,
+            left_stats.clone(),
+            right_stats.clone(),
+            &join_on,
+            Some(&filter),
+        );
+        assert_eq!(partial_join_stats.map(|s| s.num_rows), Some(9901));
+
+        // Without equijoin columns, the filter is applied to the cartesian
+        // product
+        let partial_join_stats = estimate_join_cardinality(
+            &JoinType::Inner,
+            left_stats,
+            right_stats,
+            &vec![],
+            Some(&filter),
+        );
+        assert_eq!(partial_join_stats.map(|s| s.num_rows), Some(990100));
+
+        Ok(())
+    }
 }
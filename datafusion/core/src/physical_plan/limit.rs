@@ -37,7 +37,8 @@ use arrow::record_batch::RecordBatch;
 use super::expressions::PhysicalSortExpr;
 use super::{
     metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet},
-    RecordBatchStream, SendableRecordBatchStream, Statistics,
+    ColumnStatistics, Precision, RecordBatchStream, SendableRecordBatchStream,
+    Statistics,
 };
 
 use crate::execution::context::TaskContext;
@@ -187,36 +188,7 @@ impl ExecutionPlan for GlobalLimitExec {
     }
 
     fn statistics(&self) -> Statistics {
-        let input_stats = self.input.statistics();
-        let skip = self.skip;
-        match input_stats {
-            Statistics {
-                num_rows: Some(nr), ..
-            } => {
-                if nr <= skip {
-                    // if all input data will be skipped, return 0
-                    Statistics {
-                        num_rows: Some(0),
-                        is_exact: input_stats.is_exact,
-                        ..Default::default()
-                    }
-                } else if nr - skip <= self.fetch.unwrap_or(usize::MAX) {
-                    // if the input does not reach the "fetch" globally, return input stats
-                    input_stats
-                } else if nr - skip > self.fetch.unwrap_or(usize::MAX) {
-                    // if the input is greater than the "fetch", the num_row will be the "fetch",
-                    // but we won't be able to predict the other statistics
-                    Statistics {
-                        num_rows: self.fetch,
-                        is_exact: input_stats.is_exact,
-                        ..Default::default()
-                    }
-                } else {
-                    Statistics::default()
-                }
-            }
-            _ => Statistics::default(),
-        }
+        limit_statistics(self.input.statistics(), self.skip, self.fetch)
     }
 }
 
@@ -335,32 +307,95 @@ impl ExecutionPlan for LocalLimitExec {
     }
 
     fn statistics(&self) -> Statistics {
-        let input_stats = self.input.statistics();
-        match input_stats {
-            // if the input does not reach the limit globally, return input stats
-            Statistics {
-                num_rows: Some(nr), ..
-            } if nr <= self.fetch => input_stats,
-            // if the input is greater than the limit, the num_row will be greater
-            // than the limit because the partitions will be limited separatly
-            // the statistic
+        local_limit_statistics(
+            self.input.statistics(),
+            self.fetch,
+            self.output_partitioning().partition_count(),
+        )
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        limit_statistics(
+            self.input.partition_statistics(partition),
+            0,
+            Some(self.fetch),
+        )
+    }
+}
+
+/// Returns the statistics of the output of returning up to `fetch` rows of
+/// each of the `partitions` partitions of an input with the given statistics
+pub(crate) fn local_limit_statistics(
+    input_stats: Statistics,
+    fetch: usize,
+    partitions: usize,
+) -> Statistics {
+    match input_stats.num_rows {
+        // if the input does not reach the limit globally, return input stats
+        Precision::Exact(nr) if nr <= fetch => input_stats,
+        _ if partitions == 1 => limit_statistics(input_stats, 0, Some(fetch)),
+        // the partitions are limited separately, so up to `fetch` rows of
+        // each partition are returned
+        _ => {
+            let max_rows = fetch.saturating_mul(partitions);
             Statistics {
-                num_rows: Some(nr), ..
-            } if nr > self.fetch => Statistics {
-                num_rows: Some(self.fetch),
-                // this is not actually exact, but will be when GlobalLimit is applied
-                // TODO stats: find a more explicit way to vehiculate this information
-                is_exact: input_stats.is_exact,
-                ..Default::default()
-            },
-            // if we don't know the input size, we can't predict the limit's behaviour
-            _ => Statistics::default(),
+                num_rows: input_stats.num_rows.map(|nr| nr.min(max_rows)).to_inexact(),
+                total_byte_size: Precision::Absent,
+                column_statistics: input_stats
+                    .column_statistics
+                    .into_iter()
+                    .map(ColumnStatistics::to_inexact)
+                    .collect(),
+            }
+        }
+    }
+}
+
+/// Returns the statistics of the output of skipping `skip` rows of an input
+/// with the given statistics and then returning up to `fetch` rows
+pub(crate) fn limit_statistics(
+    input_stats: Statistics,
+    skip: usize,
+    fetch: Option<usize>,
+) -> Statistics {
+    let fetch = fetch.unwrap_or(usize::MAX);
+    let num_rows = match input_stats.num_rows {
+        Precision::Exact(nr) if nr <= skip => {
+            // if all input data will be skipped, return 0
+            return Statistics {
+                num_rows: Precision::Exact(0),
+                total_byte_size: Precision::Exact(0),
+                column_statistics: input_stats
+                    .column_statistics
+                    .iter()
+                    .map(|_| ColumnStatistics {
+                        null_count: Precision::Exact(0),
+                        ..ColumnStatistics::new_unknown()
+                    })
+                    .collect(),
+            };
         }
+        // if the input does not reach the "fetch" globally, return input stats
+        Precision::Exact(nr) if skip == 0 && nr <= fetch => return input_stats,
+        Precision::Exact(nr) => Precision::Exact((nr - skip).min(fetch)),
+        Precision::Inexact(nr) => Precision::Inexact(nr.saturating_sub(skip).min(fetch)),
+        Precision::Absent => Precision::Absent,
+    };
+
+    // we won't be able to predict the other statistics exactly
+    Statistics {
+        num_rows,
+        total_byte_size: Precision::Absent,
+        column_statistics: input_stats
+            .column_statistics
+            .into_iter()
+            .map(ColumnStatistics::to_inexact)
+            .collect(),
     }
 }
 
 /// A Limit stream skips `skip` rows, and then fetch up to `fetch` rows.
-struct LimitStream {
+pub(crate) struct LimitStream {
     /// The remaining number of rows to skip
     skip: usize,
     /// The remaining number of rows to produce
@@ -375,7 +410,7 @@ struct LimitStream {
 }
 
 impl LimitStream {
-    fn new(
+    pub(crate) fn new(
         input: SendableRecordBatchStream,
         skip: usize,
         fetch: Option<usize>,
@@ -46,6 +46,8 @@ pub struct MemoryExec {
     projected_schema: SchemaRef,
     /// Optional projection
     projection: Option<Vec<usize>>,
+    /// The order of the rows of each partition, in terms of the projected schema
+    sort_information: Vec<PhysicalSortExpr>,
 }
 
 impl fmt::Debug for MemoryExec {
@@ -78,7 +80,11 @@ impl ExecutionPlan for MemoryExec {
     }
 
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
-        None
+        if self.sort_information.is_empty() {
+            None
+        } else {
+            Some(&self.sort_information)
+        }
     }
 
     fn with_new_children(
@@ -116,7 +122,16 @@ impl ExecutionPlan for MemoryExec {
                     "MemoryExec: partitions={}, partition_sizes={:?}",
                     partitions.len(),
                     partitions
-                )
+                )?;
+                if !self.sort_information.is_empty() {
+                    let sort_information: Vec<_> = self
+                        .sort_information
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect();
+                    write!(f, ", output_ordering=[{}]", sort_information.join(", "))?;
+                }
+                Ok(())
             }
         }
     }
@@ -129,6 +144,14 @@ impl ExecutionPlan for MemoryExec {
             self.projection.clone(),
         )
     }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        common::compute_record_batch_statistics(
+            &self.partitions[partition..=partition],
+            &self.schema,
+            self.projection.clone(),
+        )
+    }
 }
 
 impl MemoryExec {
@@ -145,8 +168,19 @@ impl MemoryExec {
             schema,
             projected_schema,
             projection,
+            sort_information: vec![],
         })
     }
+
+    /// Declares that the rows of each partition are sorted by `sort_information`,
+    /// expressed in terms of the projected schema
+    pub fn with_sort_information(
+        mut self,
+        sort_information: Vec<PhysicalSortExpr>,
+    ) -> Self {
+        self.sort_information = sort_information;
+        self
+    }
 }
 
 /// Iterator over batches
@@ -216,7 +250,7 @@ impl RecordBatchStream for MemoryStream {
 mod tests {
     use super::*;
     use crate::from_slice::FromSlice;
-    use crate::physical_plan::ColumnStatistics;
+    use crate::physical_plan::{ColumnStatistics, Precision};
     use crate::prelude::SessionContext;
     use arrow::array::Int32Array;
     use arrow::datatypes::{DataType, Field, Schema};
@@ -252,23 +286,23 @@ mod tests {
         let executor = MemoryExec::try_new(&[vec![batch]], schema, Some(vec![2, 1]))?;
         let statistics = executor.statistics();
 
-        assert_eq!(statistics.num_rows, Some(3));
+        assert_eq!(statistics.num_rows, Precision::Exact(3));
         assert_eq!(
             statistics.column_statistics,
-            Some(vec![
+            vec![
                 ColumnStatistics {
-                    null_count: Some(2),
-                    max_value: None,
-                    min_value: None,
-                    distinct_count: None,
+                    null_count: Precision::Exact(2),
+                    max_value: Precision::Absent,
+                    min_value: Precision::Absent,
+                    distinct_count: Precision::Absent,
                 },
                 ColumnStatistics {
-                    null_count: Some(0),
-                    max_value: None,
-                    min_value: None,
-                    distinct_count: None,
+                    null_count: Precision::Exact(0),
+                    max_value: Precision::Absent,
+                    min_value: Precision::Absent,
+                    distinct_count: Precision::Absent,
                 },
-            ])
+            ]
         );
 
         // scan with projection
@@ -282,6 +316,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_partition_statistics() -> Result<()> {
+        let (schema, batch) = mock_data()?;
+
+        let executor = MemoryExec::try_new(
+            &[vec![batch.clone(), batch.clone()], vec![batch]],
+            schema,
+            Some(vec![2]),
+        )?;
+
+        let statistics = executor.statistics();
+        assert_eq!(statistics.num_rows, Precision::Exact(9));
+        assert_eq!(
+            statistics.column_statistics[0].null_count,
+            Precision::Exact(6)
+        );
+
+        let statistics = executor.partition_statistics(1);
+        assert_eq!(statistics.num_rows, Precision::Exact(3));
+        assert_eq!(
+            statistics.column_statistics[0].null_count,
+            Precision::Exact(2)
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_without_projection() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -291,35 +352,35 @@ mod tests {
         let executor = MemoryExec::try_new(&[vec![batch]], schema, None)?;
         let statistics = executor.statistics();
 
-        assert_eq!(statistics.num_rows, Some(3));
+        assert_eq!(statistics.num_rows, Precision::Exact(3));
         assert_eq!(
             statistics.column_statistics,
-            Some(vec![
+            vec![
                 ColumnStatistics {
-                    null_count: Some(0),
-                    max_value: None,
-                    min_value: None,
-                    distinct_count: None,
+                    null_count: Precision::Exact(0),
+                    max_value: Precision::Absent,
+                    min_value: Precision::Absent,
+                    distinct_count: Precision::Absent,
                 },
                 ColumnStatistics {
-                    null_count: Some(0),
-                    max_value: None,
-                    min_value: None,
-                    distinct_count: None,
+                    null_count: Precision::Exact(0),
+                    max_value: Precision::Absent,
+                    min_value: Precision::Absent,
+                    distinct_count: Precision::Absent,
                 },
                 ColumnStatistics {
-                    null_count: Some(2),
-                    max_value: None,
-                    min_value: None,
-                    distinct_count: None,
+                    null_count: Precision::Exact(2),
+                    max_value: Precision::Absent,
+                    min_value: Precision::Absent,
+                    distinct_count: Precision::Absent,
                 },
                 ColumnStatistics {
-                    null_count: Some(0),
-                    max_value: None,
-                    min_value: None,
-                    distinct_count: None,
+                    null_count: Precision::Exact(0),
+                    max_value: Precision::Absent,
+                    min_value: Precision::Absent,
+                    distinct_count: Precision::Absent,
                 },
-            ])
+            ]
         );
 
         let mut it = executor.execute(0, task_ctx)?;
@@ -22,7 +22,7 @@ use self::metrics::MetricsSet;
 use self::{
     coalesce_partitions::CoalescePartitionsExec, display::DisplayableExecutionPlan,
 };
-pub use crate::common::{ColumnStatistics, Statistics};
+pub use crate::common::{ColumnStatistics, Precision, Statistics};
 use crate::error::Result;
 use crate::physical_plan::expressions::PhysicalSortExpr;
 
@@ -226,7 +226,24 @@ pub trait ExecutionPlan: Debug + Send + Sync {
     }
 
     /// Returns the global output statistics for this `ExecutionPlan` node.
+    ///
+    /// Every value carries its own [`Precision`](datafusion_common::Precision):
+    /// operators must only report `Exact` values they can guarantee, and
+    /// should demote inputs they cannot reason about precisely to `Inexact`.
     fn statistics(&self) -> Statistics;
+
+    /// Returns the statistics for output partition `partition` of this
+    /// `ExecutionPlan` node.
+    ///
+    /// The default implementation returns the global statistics for plans with
+    /// a single output partition and unknown statistics otherwise.
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        if partition == 0 && self.output_partitioning().partition_count() == 1 {
+            self.statistics()
+        } else {
+            Statistics::new_unknown(&self.schema())
+        }
+    }
 }
 
 /// Indicate whether a data exchange is needed for the input of `plan`, which will be very helpful
@@ -465,6 +482,59 @@ pub fn execute_stream_partitioned(
     Ok(streams)
 }
 
+/// The results of an [ExecutionPlan] collected per output partition, with the
+/// properties of the plan that hold for them
+#[derive(Debug, Clone)]
+pub struct PartitionedBatches {
+    /// The schema of the batches
+    pub schema: SchemaRef,
+    /// The batches of each output partition
+    pub partitions: Vec<Vec<RecordBatch>>,
+    /// The partitioning of the batches, in terms of `schema`
+    pub partitioning: Partitioning,
+    /// The order of the rows of each partition, in terms of `schema`
+    pub output_ordering: Option<Vec<PhysicalSortExpr>>,
+}
+
+impl PartitionedBatches {
+    /// Converts the results into a [`MemTable`](crate::datasource::MemTable)
+    /// with the same partitions, keeping the longest prefix of their ordering
+    /// that only sorts by columns
+    pub fn into_mem_table(self) -> Result<crate::datasource::MemTable> {
+        let sort_order = self
+            .output_ordering
+            .unwrap_or_default()
+            .into_iter()
+            .take_while(|sort_expr| sort_expr.expr.as_any().is::<expressions::Column>())
+            .collect();
+        crate::datasource::MemTable::try_new_with_ordering(
+            self.schema,
+            self.partitions,
+            sort_order,
+        )
+    }
+}
+
+/// Execute the [ExecutionPlan] and collect the results in memory, along with
+/// the partitioning and ordering of the plan
+pub async fn collect_partitioned_with_properties(
+    plan: Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+) -> Result<PartitionedBatches> {
+    let schema = plan.schema();
+    let partitioning = plan.output_partitioning();
+    let output_ordering = plan.output_ordering().map(|ordering| ordering.to_vec());
+    let partitions = collect_partitioned(plan, context).await?;
+    Ok(PartitionedBatches {
+        schema,
+        partitions,
+        partitioning,
+        output_ordering,
+    })
+}
+
+pub use sink::{execute_into_sink, DataSink};
+
 /// Partitioning schemes supported by operators.
 #[derive(Debug, Clone)]
 pub enum Partitioning {
@@ -641,25 +711,34 @@ pub fn project_schema(
 
 pub mod aggregates;
 pub mod analyze;
+pub mod cached_statistics;
 pub mod coalesce_batches;
 pub mod coalesce_partitions;
 pub mod common;
 pub mod display;
+pub mod distinct_on;
 pub mod empty;
 pub mod explain;
 pub mod file_format;
 pub mod filter;
+pub mod fingerprint;
 pub mod joins;
 pub mod limit;
 pub mod memory;
 pub mod metrics;
 pub mod planner;
 pub mod projection;
+pub mod rechunk_batches;
 pub mod repartition;
 pub mod rewrite;
+pub mod set_operation;
+pub mod shared_cte;
+pub mod sink;
 pub mod sorts;
+pub mod stages;
 pub mod stream;
 pub mod streaming;
+pub mod subquery;
 pub mod udaf;
 pub mod union;
 pub mod values;
@@ -22,31 +22,38 @@ use super::{
     aggregates, empty::EmptyExec, joins::PartitionMode, udaf, union::UnionExec,
     values::ValuesExec, windows,
 };
+use crate::datasource::analyzed::with_cached_statistics;
 use crate::datasource::source_as_provider;
 use crate::execution::context::{ExecutionProps, SessionState};
 use crate::logical_expr::utils::generate_sort_key;
 use crate::logical_expr::{
-    Aggregate, Distinct, EmptyRelation, Join, Projection, Sort, SubqueryAlias, TableScan,
-    Window,
+    Aggregate, Distinct, EmptyRelation, Join, Projection, SharedCte, Sort, SubqueryAlias,
+    TableScan, Window,
 };
 use crate::logical_expr::{
     CrossJoin, Expr, LogicalPlan, Partitioning as LogicalPartitioning, PlanType,
     Repartition, ToStringifiedPlan, Union, UserDefinedLogicalNode,
 };
-use crate::logical_expr::{Limit, Values};
+use crate::logical_expr::{JoinType, Limit, Values};
 use crate::physical_expr::create_physical_expr;
 use crate::physical_optimizer::optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
+use crate::physical_plan::distinct_on::DistinctOnExec;
 use crate::physical_plan::explain::ExplainExec;
 use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
 use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::HashJoinExec;
 use crate::physical_plan::joins::SortMergeJoinExec;
-use crate::physical_plan::joins::{CrossJoinExec, NestedLoopJoinExec};
+use crate::physical_plan::joins::{
+    BandCondition, BandJoinExec, CrossJoinExec, NestedLoopJoinExec,
+};
 use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::set_operation::SetOperationAllExec;
+use crate::physical_plan::shared_cte::SharedCteExec;
 use crate::physical_plan::sorts::sort::SortExec;
+use crate::physical_plan::subquery::{SubqueryExec, SubqueryKind, SubqueryPlan};
 use crate::physical_plan::windows::{BoundedWindowAggExec, WindowAggExec};
 use crate::physical_plan::{joins::utils as join_utils, Partitioning};
 use crate::physical_plan::{AggregateExpr, ExecutionPlan, PhysicalExpr, WindowExpr};
@@ -57,6 +64,7 @@ use crate::{
 use arrow::compute::SortOptions;
 use arrow::datatypes::{Schema, SchemaRef};
 use async_trait::async_trait;
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
 use datafusion_common::{DFSchema, ScalarValue};
 use datafusion_expr::expr::{
     self, AggregateFunction, Between, BinaryExpr, Cast, GetIndexedField, GroupingSet,
@@ -66,9 +74,10 @@ use datafusion_expr::expr_rewriter::unnormalize_cols;
 use datafusion_expr::logical_plan;
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
 use datafusion_expr::utils::expand_wildcard;
+use datafusion_expr::{DistinctOn, SetOperationAll};
 use datafusion_expr::{WindowFrame, WindowFrameBound};
-use datafusion_optimizer::utils::unalias;
-use datafusion_physical_expr::expressions::Literal;
+use datafusion_optimizer::utils::{is_correlated, unalias};
+use datafusion_physical_expr::expressions::{Literal, SubqueryId};
 use datafusion_sql::utils::window_expr_common_partition_keys;
 use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt, TryStreamExt};
@@ -256,12 +265,11 @@ fn create_physical_name(e: &Expr, is_first_expr: bool) -> Result<String> {
         Expr::Exists { .. } => Err(DataFusionError::NotImplemented(
             "EXISTS is not yet supported in the physical plan".to_string(),
         )),
-        Expr::InSubquery { .. } => Err(DataFusionError::NotImplemented(
-            "IN subquery is not yet supported in the physical plan".to_string(),
-        )),
-        Expr::ScalarSubquery(_) => Err(DataFusionError::NotImplemented(
-            "Scalar subqueries are not yet supported in the physical plan".to_string(),
-        )),
+        Expr::InSubquery { negated: true, .. } => Ok("NOT IN".to_string()),
+        Expr::InSubquery { negated: false, .. } => Ok("IN".to_string()),
+        Expr::ScalarSubquery(subquery) => {
+            Ok(subquery.subquery.schema().field(0).name().clone())
+        }
         Expr::Between(Between {
             expr,
             negated,
@@ -474,6 +482,7 @@ impl DefaultPhysicalPlanner {
                     projection,
                     filters,
                     fetch,
+                    ordering,
                     ..
                 }) => {
                     let source = source_as_provider(source)?;
@@ -482,7 +491,32 @@ impl DefaultPhysicalPlanner {
                     // referred to in the query
                     let filters = unnormalize_cols(filters.iter().cloned());
                     let unaliased: Vec<Expr> = filters.into_iter().map(unalias).collect();
-                    source.scan(session_state, projection.as_ref(), &unaliased, *fetch).await
+                    let plan = if ordering.is_empty() {
+                        source.scan(session_state, projection.as_ref(), &unaliased, *fetch).await?
+                    } else {
+                        let ordering = unnormalize_cols(ordering.iter().cloned());
+                        source
+                            .scan_ordered(
+                                session_state,
+                                projection.as_ref(),
+                                &unaliased,
+                                *fetch,
+                                &ordering,
+                            )
+                            .await?
+                    };
+                    // report the statistics computed by `ANALYZE TABLE`, if any
+                    match session_state.analyzed_tables().get(&source) {
+                        Some(statistics) => with_cached_statistics(
+                            plan,
+                            source.as_ref(),
+                            statistics,
+                            projection.as_ref(),
+                            &unaliased,
+                            *fetch,
+                        ),
+                        None => Ok(plan),
+                    }
                 }
                 LogicalPlan::Values(Values {
                     values,
@@ -723,6 +757,8 @@ impl DefaultPhysicalPlanner {
                 LogicalPlan::Projection(Projection { input, expr, .. }) => {
                     let input_exec = self.create_initial_plan(input, session_state).await?;
                     let input_schema = input.as_ref().schema();
+                    let (subqueries, execution_props) =
+                        self.create_subquery_plans(expr, session_state).await?;
 
                     let physical_exprs = expr
                         .iter()
@@ -756,34 +792,43 @@ impl DefaultPhysicalPlanner {
                             };
 
                             tuple_err((
-                                self.create_physical_expr(
+                                create_physical_expr(
                                     e,
                                     input_schema,
                                     &input_exec.schema(),
-                                    session_state,
+                                    &execution_props,
                                 ),
                                 physical_name,
                             ))
                         })
                         .collect::<Result<Vec<_>>>()?;
 
-                    Ok(Arc::new(ProjectionExec::try_new(
+                    let projection = Arc::new(ProjectionExec::try_new(
                         physical_exprs,
                         input_exec,
-                    )?))
+                    )?);
+                    Ok(with_subqueries(projection, subqueries))
                 }
                 LogicalPlan::Filter(filter) => {
                     let physical_input = self.create_initial_plan(&filter.input, session_state).await?;
                     let input_schema = physical_input.as_ref().schema();
                     let input_dfschema = filter.input.schema();
 
-                    let runtime_expr = self.create_physical_expr(
+                    let (subqueries, execution_props) = self
+                        .create_subquery_plans(
+                            std::slice::from_ref(&filter.predicate),
+                            session_state,
+                        )
+                        .await?;
+
+                    let runtime_expr = create_physical_expr(
                         &filter.predicate,
                         input_dfschema,
                         &input_schema,
-                        session_state,
+                        &execution_props,
                     )?;
-                    Ok(Arc::new(FilterExec::try_new(runtime_expr, physical_input)?))
+                    let filter = Arc::new(FilterExec::try_new(runtime_expr, physical_input)?);
+                    Ok(with_subqueries(filter, subqueries))
                 }
                 LogicalPlan::Union(Union { inputs, schema }) => {
                     let physical_plans = futures::stream::iter(inputs)
@@ -1015,7 +1060,22 @@ impl DefaultPhysicalPlanner {
                     };
 
                     let prefer_hash_join = session_state.config_options().optimizer.prefer_hash_join;
-                    if join_on.is_empty() {
+                    let enable_band_join = session_state.config_options().optimizer.enable_band_join;
+                    let band = match &join_filter {
+                        Some(filter) if join_on.is_empty() && *join_type == JoinType::Inner && enable_band_join => {
+                            BandCondition::try_new(filter, &physical_left.schema(), &physical_right.schema())?
+                        }
+                        _ => None,
+                    };
+                    if let (Some(band), Some(filter)) = (band, join_filter.clone()) {
+                        // the inequality join condition is a band, sort both sides and sweep
+                        Ok(Arc::new(BandJoinExec::try_new(
+                            physical_left,
+                            physical_right,
+                            filter,
+                            band,
+                        )?))
+                    } else if join_on.is_empty() {
                         // there is no equal join condition, use the nested loop join
                         // TODO optimize the plan, and use the config of `target_partitions` and `repartition_joins`
                         Ok(Arc::new(NestedLoopJoinExec::try_new(
@@ -1027,6 +1087,7 @@ impl DefaultPhysicalPlanner {
                     } else if session_state.config().target_partitions() > 1
                         && session_state.config().repartition_joins()
                         && !prefer_hash_join
+                        && *join_type != JoinType::LeftMark
                     {
                         // Use SortMergeJoin if hash join is not preferred
                         // Sort-Merge join support currently is experimental
@@ -1046,7 +1107,7 @@ impl DefaultPhysicalPlanner {
                         }
                     } else if session_state.config().target_partitions() > 1
                         && session_state.config().repartition_joins()
-                        && prefer_hash_join {
+                        && (prefer_hash_join || *join_type == JoinType::LeftMark) {
                          let partition_mode = {
                             if session_state.config().collect_statistics() {
                                 PartitionMode::Auto
@@ -1091,6 +1152,12 @@ impl DefaultPhysicalPlanner {
                 LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
                     self.create_initial_plan(input, session_state).await
                 }
+                LogicalPlan::SharedCte(SharedCte { id, name, input }) => {
+                    // the physical optimizer decides whether the references to
+                    // the CTE share its results
+                    let input = self.create_initial_plan(input, session_state).await?;
+                    Ok(Arc::new(SharedCteExec::new(*id, name, input)))
+                }
                 LogicalPlan::Limit(Limit { input, skip, fetch, .. }) => {
                     let input = self.create_initial_plan(input, session_state).await?;
 
@@ -1171,6 +1238,32 @@ impl DefaultPhysicalPlanner {
                         "Unsupported logical plan: DropView".to_string(),
                     ))
                 }
+                LogicalPlan::AnalyzeTable(_) => {
+                    // There is no default plan for "ANALYZE TABLE".
+                    // It must be handled at a higher level (so
+                    // that the statistics can be stored with the
+                    // table registration)
+                    Err(DataFusionError::Internal(
+                        "Unsupported logical plan: AnalyzeTable".to_string(),
+                    ))
+                }
+                LogicalPlan::CreateFunction(_) | LogicalPlan::DropFunction(_) => {
+                    // There is no default plan for "CREATE FUNCTION" and
+                    // "DROP FUNCTION". They must be handled at a higher level
+                    // (so that the function can be (de)registered)
+                    Err(DataFusionError::Internal(
+                        "Unsupported logical plan: CreateFunction/DropFunction"
+                            .to_string(),
+                    ))
+                }
+                LogicalPlan::Transaction(_) => {
+                    // There is no default plan for the transaction statements.
+                    // They must be handled at a higher level (so that the
+                    // transaction of the session can be changed)
+                    Err(DataFusionError::Internal(
+                        "Unsupported logical plan: Transaction".to_string(),
+                    ))
+                }
                 LogicalPlan::CreateView(_) => {
                     // There is no default plan for "CREATE VIEW".
                     // It must be handled at a higher level (so
@@ -1191,7 +1284,11 @@ impl DefaultPhysicalPlanner {
                 LogicalPlan::Analyze(a) => {
                     let input = self.create_initial_plan(&a.input, session_state).await?;
                     let schema = SchemaRef::new((*a.schema).clone().into());
-                    Ok(Arc::new(AnalyzeExec::new(a.verbose, input, schema)))
+                    let analyze = AnalyzeExec::new(a.verbose, input, schema);
+                    Ok(Arc::new(match session_state.planning_metrics() {
+                        Some(metrics) => analyze.with_planning_metrics(metrics.clone()),
+                        None => analyze,
+                    }))
                 }
                 LogicalPlan::Extension(e) => {
                     let physical_inputs = futures::stream::iter(e.node.inputs())
@@ -1199,6 +1296,64 @@ impl DefaultPhysicalPlanner {
                         .try_collect::<Vec<_>>()
                         .await?;
 
+                    if let Some(set_op) = e.node.as_any().downcast_ref::<SetOperationAll>() {
+                        return Ok(Arc::new(SetOperationAllExec::try_new(
+                            set_op.op,
+                            physical_inputs[0].clone(),
+                            physical_inputs[1].clone(),
+                        )?) as Arc<dyn ExecutionPlan>);
+                    }
+
+                    if let Some(distinct_on) = e.node.as_any().downcast_ref::<DistinctOn>() {
+                        let input_exec = physical_inputs[0].clone();
+                        let input_schema = input_exec.schema();
+                        let input_dfschema = distinct_on.input.schema();
+                        let on_exprs = distinct_on
+                            .on_expr
+                            .iter()
+                            .map(|e| {
+                                self.create_physical_expr(
+                                    e,
+                                    input_dfschema,
+                                    &input_schema,
+                                    session_state,
+                                )
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        let sort_exprs = distinct_on
+                            .sort_expr
+                            .as_ref()
+                            .map(|sort_expr| {
+                                sort_expr
+                                    .iter()
+                                    .map(|e| match e {
+                                        Expr::Sort(expr::Sort {
+                                            expr,
+                                            asc,
+                                            nulls_first,
+                                        }) => create_physical_sort_expr(
+                                            expr,
+                                            input_dfschema,
+                                            &input_schema,
+                                            SortOptions {
+                                                descending: !*asc,
+                                                nulls_first: *nulls_first,
+                                            },
+                                            session_state.execution_props(),
+                                        ),
+                                        _ => Err(DataFusionError::Plan(
+                                            "DISTINCT ON only accepts sort expressions"
+                                                .to_string(),
+                                        )),
+                                    })
+                                    .collect::<Result<Vec<_>>>()
+                            })
+                            .transpose()?;
+                        return Ok(Arc::new(DistinctOnExec::new(
+                            input_exec, on_exprs, sort_exprs,
+                        )) as Arc<dyn ExecutionPlan>);
+                    }
+
                     let mut maybe_plan = None;
                     for planner in &self.extension_planners {
                         if maybe_plan.is_some() {
@@ -1242,6 +1397,51 @@ impl DefaultPhysicalPlanner {
         }.boxed()
     }
 
+    /// Plans the uncorrelated subqueries of `exprs`, returning their plans and
+    /// the execution props to create the expressions reading their results
+    async fn create_subquery_plans(
+        &self,
+        exprs: &[Expr],
+        session_state: &SessionState,
+    ) -> Result<(Vec<SubqueryPlan>, ExecutionProps)> {
+        let mut subqueries: Vec<(Arc<LogicalPlan>, SubqueryKind)> = vec![];
+        for expr in exprs {
+            expr.apply(&mut |expr| {
+                let (subquery, kind) = match expr {
+                    Expr::ScalarSubquery(subquery) => (subquery, SubqueryKind::Scalar),
+                    Expr::InSubquery { subquery, .. } => (subquery, SubqueryKind::In),
+                    _ => return Ok(VisitRecursion::Continue),
+                };
+                // the same subquery may be shared by several expressions
+                if !subqueries
+                    .iter()
+                    .any(|(plan, _)| Arc::ptr_eq(plan, &subquery.subquery))
+                {
+                    subqueries.push((subquery.subquery.clone(), kind));
+                }
+                Ok(VisitRecursion::Continue)
+            })?;
+        }
+
+        let mut execution_props = session_state.execution_props().clone();
+        let mut plans = Vec::with_capacity(subqueries.len());
+        for (subquery, kind) in subqueries {
+            if is_correlated(&subquery)? {
+                return Err(DataFusionError::NotImplemented(
+                    "Physical plan does not support correlated subqueries".to_string(),
+                ));
+            }
+            let id = SubqueryId::next();
+            plans.push(SubqueryPlan {
+                id,
+                kind,
+                plan: self.create_initial_plan(&subquery, session_state).await?,
+            });
+            execution_props.subqueries.push((subquery, id));
+        }
+        Ok((plans, execution_props))
+    }
+
     fn create_grouping_physical_expr(
         &self,
         group_expr: &[Expr],
@@ -1637,6 +1837,7 @@ pub fn create_aggregate_expr_with_name(
             fun,
             distinct,
             args,
+            order_by,
             ..
         }) => {
             let args = args
@@ -1650,10 +1851,34 @@ pub fn create_aggregate_expr_with_name(
                     )
                 })
                 .collect::<Result<Vec<_>>>()?;
+            let ordering_req = order_by
+                .iter()
+                .flatten()
+                .map(|e| match e {
+                    Expr::Sort(expr::Sort {
+                        expr,
+                        asc,
+                        nulls_first,
+                    }) => create_physical_sort_expr(
+                        expr,
+                        logical_input_schema,
+                        physical_input_schema,
+                        SortOptions {
+                            descending: !*asc,
+                            nulls_first: *nulls_first,
+                        },
+                        execution_props,
+                    ),
+                    _ => Err(DataFusionError::Plan(
+                        "Sort only accepts sort expressions".to_string(),
+                    )),
+                })
+                .collect::<Result<Vec<_>>>()?;
             aggregates::create_aggregate_expr(
                 fun,
                 *distinct,
                 &args,
+                &ordering_req,
                 physical_input_schema,
                 name,
             )
@@ -1786,10 +2011,15 @@ impl DefaultPhysicalPlanner {
         );
         trace!("Detailed input physical plan:\n{:?}", plan);
 
+        #[cfg(debug_assertions)]
+        crate::physical_optimizer::optimizer::check_rule_order(optimizers)?;
+
         let mut new_plan = plan;
         for optimizer in optimizers {
             let before_schema = new_plan.schema();
             new_plan = optimizer.optimize(new_plan, session_state.config_options())?;
+            #[cfg(debug_assertions)]
+            optimizer.check_postconditions(new_plan.as_ref())?;
             if optimizer.schema_check() && new_plan.schema() != before_schema {
                 return Err(DataFusionError::Internal(format!(
                         "PhysicalOptimizer rule '{}' failed, due to generate a different schema, original schema: {:?}, new schema: {:?}",
@@ -1814,6 +2044,19 @@ impl DefaultPhysicalPlanner {
     }
 }
 
+/// Executes the uncorrelated `subqueries` of the expressions of `plan`
+/// before it, if any
+fn with_subqueries(
+    plan: Arc<dyn ExecutionPlan>,
+    subqueries: Vec<SubqueryPlan>,
+) -> Arc<dyn ExecutionPlan> {
+    if subqueries.is_empty() {
+        plan
+    } else {
+        Arc::new(SubqueryExec::new(plan, subqueries))
+    }
+}
+
 fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
     match value {
         (Ok(e), Ok(e1)) => Ok((e, e1)),
@@ -0,0 +1,340 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! RechunkBatchesExec splits and combines the batches of its input into batches
+//! of a fixed number of rows, for the consumers of the results of a query.
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::error::Result;
+use crate::execution::context::TaskContext;
+use crate::physical_plan::coalesce_batches::concat_batches;
+use crate::physical_plan::{
+    DisplayFormatType, EquivalenceProperties, ExecutionPlan, Partitioning,
+    RecordBatchStream, SendableRecordBatchStream,
+};
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use futures::ready;
+use futures::stream::{Stream, StreamExt};
+
+use super::expressions::PhysicalSortExpr;
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::Statistics;
+
+/// RechunkBatchesExec returns the rows of each partition of its input in
+/// batches of exactly `batch_size` rows, except the last batch of the
+/// partition, which has the remaining rows.
+///
+/// Unlike [`CoalesceBatchesExec`](super::coalesce_batches::CoalesceBatchesExec),
+/// which only combines small batches to process them efficiently, this
+/// operator also splits large batches, so that a client reading the results
+/// of a query receives batches of a predictable size. The batches are split
+/// without copying them, and are produced one at a time as they are polled,
+/// so a slow consumer doesn't make the operator buffer more than a batch of
+/// its input.
+#[derive(Debug)]
+pub struct RechunkBatchesExec {
+    /// The input plan
+    input: Arc<dyn ExecutionPlan>,
+    /// Number of rows of the output batches
+    batch_size: usize,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl RechunkBatchesExec {
+    /// Create a new RechunkBatchesExec
+    pub fn new(input: Arc<dyn ExecutionPlan>, batch_size: usize) -> Self {
+        Self {
+            input,
+            batch_size,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+
+    /// The input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Number of rows of the output batches
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+}
+
+impl ExecutionPlan for RechunkBatchesExec {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn unbounded_output(&self, children: &[bool]) -> Result<bool> {
+        Ok(children[0])
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> bool {
+        true
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.input.equivalence_properties()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(RechunkBatchesExec::new(
+            children[0].clone(),
+            self.batch_size,
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        Ok(Box::pin(RechunkBatchesStream {
+            input: self.input.execute(partition, context)?,
+            schema: self.input.schema(),
+            batch_size: self.batch_size,
+            buffer: Vec::new(),
+            buffered_rows: 0,
+            pending: None,
+            is_closed: false,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "RechunkBatchesExec: batch_size={}", self.batch_size)
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        self.input.partition_statistics(partition)
+    }
+}
+
+struct RechunkBatchesStream {
+    /// The input plan
+    input: SendableRecordBatchStream,
+    /// The input schema
+    schema: SchemaRef,
+    /// Number of rows of the output batches
+    batch_size: usize,
+    /// Buffered batches, with fewer than `batch_size` rows in total
+    buffer: Vec<RecordBatch>,
+    /// Buffered row count
+    buffered_rows: usize,
+    /// Rows of an input batch that are not buffered yet
+    pending: Option<RecordBatch>,
+    /// Whether the input stream is exhausted
+    is_closed: bool,
+    /// Execution metrics
+    baseline_metrics: BaselineMetrics,
+}
+
+impl Stream for RechunkBatchesStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_inner(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RechunkBatchesStream {
+    fn poll_next_inner(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<ArrowResult<RecordBatch>>> {
+        loop {
+            if let Some(batch) = self.pending.take() {
+                let missing_rows = self.batch_size - self.buffered_rows;
+                if batch.num_rows() >= missing_rows {
+                    // complete the output batch, and keep the rest for the next ones
+                    let rest = batch.num_rows() - missing_rows;
+                    if rest > 0 {
+                        self.pending = Some(batch.slice(missing_rows, rest));
+                    }
+                    self.buffered_rows += missing_rows;
+                    self.buffer.push(batch.slice(0, missing_rows));
+                    return Poll::Ready(Some(self.flush_buffer()));
+                }
+                self.buffered_rows += batch.num_rows();
+                self.buffer.push(batch);
+            }
+            if self.is_closed {
+                return Poll::Ready(if self.buffer.is_empty() {
+                    None
+                } else {
+                    Some(self.flush_buffer())
+                });
+            }
+            match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    if batch.num_rows() > 0 {
+                        self.pending = Some(batch);
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => self.is_closed = true,
+            }
+        }
+    }
+
+    /// Combines the buffered batches into one batch and resets the buffer. A single
+    /// buffered batch is returned as is, without copying it.
+    fn flush_buffer(&mut self) -> ArrowResult<RecordBatch> {
+        // records time on drop
+        let _timer = self.baseline_metrics.elapsed_compute().timer();
+        let batch = if self.buffer.len() == 1 {
+            self.buffer.pop().unwrap()
+        } else {
+            concat_batches(&self.schema, &self.buffer, self.buffered_rows)?
+        };
+        self.buffer.clear();
+        self.buffered_rows = 0;
+        Ok(batch)
+    }
+}
+
+impl RecordBatchStream for RechunkBatchesStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::collect_partitioned;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::prelude::SessionContext;
+    use arrow::array::{Int32Array, UInt32Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::cast::as_int32_array;
+
+    fn batch(schema: &SchemaRef, values: std::ops::Range<i32>) -> RecordBatch {
+        let values = values.collect::<Vec<_>>();
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice(&values))],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rechunk_batches() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let partitions = vec![
+            // small, empty and large batches
+            vec![
+                batch(&schema, 0..1),
+                batch(&schema, 1..2),
+                batch(&schema, 2..2),
+                batch(&schema, 2..12),
+                batch(&schema, 12..14),
+            ],
+            // a batch of exactly the batch size
+            vec![batch(&schema, 0..4)],
+            vec![],
+        ];
+        let input = Arc::new(MemoryExec::try_new(&partitions, schema, None)?);
+        let plan = Arc::new(RechunkBatchesExec::new(input, 4));
+
+        let session_ctx = SessionContext::new();
+        let output = collect_partitioned(plan.clone(), session_ctx.task_ctx()).await?;
+
+        let sizes = output
+            .iter()
+            .map(|batches| batches.iter().map(|b| b.num_rows()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![vec![4, 4, 4, 2], vec![4], vec![]]);
+
+        // the rows are returned in order
+        let values = output[0]
+            .iter()
+            .flat_map(|b| as_int32_array(b.column(0)).unwrap().values().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..14).collect::<Vec<_>>());
+
+        assert_eq!(plan.metrics().unwrap().output_rows(), Some(18));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rechunk_large_batch() -> Result<()> {
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::UInt32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(UInt32Array::from_iter_values(0..10_000))],
+        )?;
+        let input = Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None)?);
+        let plan = Arc::new(RechunkBatchesExec::new(input, 1000));
+
+        let session_ctx = SessionContext::new();
+        let output = collect_partitioned(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(output[0].len(), 10);
+        assert!(output[0].iter().all(|b| b.num_rows() == 1000));
+        Ok(())
+    }
+}
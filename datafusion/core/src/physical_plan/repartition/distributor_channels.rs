@@ -126,6 +126,12 @@ impl<T> DistributionSender<T> {
             element: Box::new(Some(element)),
         }
     }
+
+    /// Number of elements buffered in the channel, that the
+    /// [receiver](DistributionReceiver) has not received yet.
+    pub fn n_buffered(&self) -> usize {
+        self.channel.lock().data.len()
+    }
 }
 
 impl<T> Clone for DistributionSender<T> {
@@ -421,6 +427,19 @@ mod tests {
         assert_eq!(poll_ready(&mut rxs[0].recv()), Some("bar"),);
     }
 
+    #[test]
+    fn test_n_buffered() {
+        let (txs, mut rxs) = channels(2);
+
+        poll_ready(&mut txs[0].send("foo")).unwrap();
+        poll_ready(&mut txs[0].send("bar")).unwrap();
+        assert_eq!(txs[0].n_buffered(), 2);
+        assert_eq!(txs[1].n_buffered(), 0);
+
+        assert_eq!(poll_ready(&mut rxs[0].recv()), Some("foo"),);
+        assert_eq!(txs[0].n_buffered(), 1);
+    }
+
     #[test]
     fn test_gate() {
         let (txs, mut rxs) = channels(2);
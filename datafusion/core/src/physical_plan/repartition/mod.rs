@@ -28,7 +28,8 @@ use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use crate::physical_plan::hash_utils::create_hashes;
 use crate::physical_plan::repartition::distributor_channels::channels;
 use crate::physical_plan::{
-    DisplayFormatType, EquivalenceProperties, ExecutionPlan, Partitioning, Statistics,
+    ColumnStatistics, DisplayFormatType, EquivalenceProperties, ExecutionPlan,
+    Partitioning, Precision, Statistics,
 };
 use arrow::array::{ArrayRef, UInt64Builder};
 use arrow::datatypes::SchemaRef;
@@ -37,6 +38,7 @@ use arrow::record_batch::RecordBatch;
 use log::debug;
 
 use self::distributor_channels::{DistributionReceiver, DistributionSender};
+use self::morsel::{MorselPool, MorselStream};
 
 use super::common::{AbortOnDropMany, AbortOnDropSingle};
 use super::expressions::PhysicalSortExpr;
@@ -52,6 +54,7 @@ use parking_lot::Mutex;
 use tokio::task::JoinHandle;
 
 mod distributor_channels;
+mod morsel;
 
 type MaybeBatch = Option<ArrowResult<RecordBatch>>;
 type SharedMemoryReservation = Arc<Mutex<MemoryReservation>>;
@@ -72,6 +75,10 @@ struct RepartitionExecState {
 
     /// Helper that ensures that that background job is killed once it is no longer needed.
     abort_helper: Arc<AbortOnDropMany<()>>,
+
+    /// Input partitions shared by the output partitions of a pull-driven round
+    /// robin repartition, when `execution.morsel_driven_repartition` is enabled
+    morsel_pool: Option<Arc<MorselPool>>,
 }
 
 /// A utility that can be used to partition batches based on [`Partitioning`]
@@ -362,6 +369,29 @@ impl ExecutionPlan for RepartitionExec {
         let num_input_partitions = self.input.output_partitioning().partition_count();
         let num_output_partitions = self.partitioning.partition_count();
 
+        // a round robin repartition may let the output partitions pull the
+        // batches from the inputs instead of spawning a task per input
+        let morsel_driven = context
+            .session_config()
+            .config_options()
+            .execution
+            .morsel_driven_repartition;
+        if morsel_driven && matches!(self.partitioning, Partitioning::RoundRobinBatch(_))
+        {
+            let pool = match &state.morsel_pool {
+                Some(pool) => Arc::clone(pool),
+                None => {
+                    let inputs = (0..num_input_partitions)
+                        .map(|i| self.input.execute(i, context.clone()))
+                        .collect::<Result<Vec<_>>>()?;
+                    let pool = Arc::new(MorselPool::new(inputs));
+                    state.morsel_pool = Some(Arc::clone(&pool));
+                    pool
+                }
+            };
+            return Ok(Box::pin(MorselStream::new(pool, self.input.schema())));
+        }
+
         // if this is the first partition to be invoked then we need to set up initial state
         if state.channels.is_empty() {
             // create one channel per *output* partition
@@ -453,6 +483,28 @@ impl ExecutionPlan for RepartitionExec {
     fn statistics(&self) -> Statistics {
         self.input.statistics()
     }
+
+    /// The rows are assumed to be spread evenly over the output partitions
+    fn partition_statistics(&self, _partition: usize) -> Statistics {
+        let input_stats = self.input.statistics();
+        let partitions = self.partitioning.partition_count();
+        let per_partition =
+            |count: Precision<usize>| count.map(|c| (c + partitions - 1) / partitions);
+        Statistics {
+            num_rows: per_partition(input_stats.num_rows).to_inexact(),
+            total_byte_size: per_partition(input_stats.total_byte_size).to_inexact(),
+            column_statistics: input_stats
+                .column_statistics
+                .into_iter()
+                .map(|col_stats| ColumnStatistics {
+                    null_count: per_partition(col_stats.null_count).to_inexact(),
+                    max_value: col_stats.max_value.to_inexact(),
+                    min_value: col_stats.min_value.to_inexact(),
+                    distinct_count: col_stats.distinct_count.to_inexact(),
+                })
+                .collect(),
+        }
+    }
 }
 
 impl RepartitionExec {
@@ -467,6 +519,7 @@ impl RepartitionExec {
             state: Arc::new(Mutex::new(RepartitionExecState {
                 channels: HashMap::new(),
                 abort_helper: Arc::new(AbortOnDropMany::<()>(vec![])),
+                morsel_pool: None,
             })),
             metrics: ExecutionPlanMetricsSet::new(),
         })
@@ -489,6 +542,15 @@ impl RepartitionExec {
         r_metrics: RepartitionMetrics,
         context: Arc<TaskContext>,
     ) -> Result<()> {
+        // round robin batches may be sent to the least loaded output partition
+        let dynamic_round_robin =
+            matches!(partitioning, Partitioning::RoundRobinBatch(_))
+                && context
+                    .session_config()
+                    .config_options()
+                    .execution
+                    .dynamic_round_robin_repartition;
+        let num_output_partitions = partitioning.partition_count();
         let mut partitioner =
             BatchPartitioner::try_new(partitioning, r_metrics.repart_time.clone())?;
 
@@ -512,7 +574,13 @@ impl RepartitionExec {
             };
 
             for res in partitioner.partition_iter(batch)? {
-                let (partition, batch) = res?;
+                let (mut partition, batch) = res?;
+                if dynamic_round_robin {
+                    partition =
+                        least_loaded_partition(partition, num_output_partitions, |p| {
+                            txs.get(&p).map(|(tx, _)| tx.n_buffered())
+                        });
+                }
                 let size = batch.get_array_memory_size();
 
                 let timer = r_metrics.send_time.timer();
@@ -580,6 +648,23 @@ impl RepartitionExec {
     }
 }
 
+/// Returns the output partition with the fewest buffered batches, where
+/// `n_buffered` returns `None` for the output partitions that have hung up.
+/// Ties are broken in round robin order starting at `preferred`, so that the
+/// batches are spread evenly while the output partitions keep up.
+fn least_loaded_partition(
+    preferred: usize,
+    num_partitions: usize,
+    n_buffered: impl Fn(usize) -> Option<usize>,
+) -> usize {
+    (0..num_partitions)
+        .map(|offset| (preferred + offset) % num_partitions)
+        .filter_map(|partition| Some((partition, n_buffered(partition)?)))
+        .min_by_key(|(_, n_buffered)| *n_buffered)
+        .map(|(partition, _)| partition)
+        .unwrap_or(preferred)
+}
+
 struct RepartitionStream {
     /// Number of input partitions that will be sending batches to this output channel
     num_input_partitions: usize,
@@ -760,6 +845,92 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn morsel_driven_round_robin() -> Result<()> {
+        let schema = test_schema();
+        let partition = create_vec_batches(&schema, 50);
+        let partitions = vec![partition.clone(), partition.clone(), partition];
+
+        let config = SessionConfig::new()
+            .set_bool("datafusion.execution.morsel_driven_repartition", true);
+        let task_ctx = SessionContext::with_config(config).task_ctx();
+        let exec = MemoryExec::try_new(&partitions, schema.clone(), None)?;
+        let exec =
+            RepartitionExec::try_new(Arc::new(exec), Partitioning::RoundRobinBatch(5))?;
+
+        // all the output partitions read from the same inputs concurrently
+        let handles: Vec<_> = (0..5)
+            .map(|i| exec.execute(i, task_ctx.clone()))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(|stream| tokio::spawn(crate::physical_plan::common::collect(stream)))
+            .collect();
+
+        let mut total_batches = 0;
+        for handle in handles {
+            total_batches += handle.await.unwrap()?.len();
+        }
+        assert_eq!(total_batches, 150);
+
+        Ok(())
+    }
+
+    #[test]
+    fn least_loaded() {
+        let buffered = [Some(2), None, Some(1), Some(3), Some(1)];
+        let n_buffered = |p: usize| buffered[p];
+        assert_eq!(least_loaded_partition(0, 5, n_buffered), 2);
+        assert_eq!(least_loaded_partition(3, 5, n_buffered), 4);
+        assert_eq!(least_loaded_partition(1, 5, n_buffered), 2);
+
+        // all the output partitions hung up
+        assert_eq!(least_loaded_partition(1, 2, |_| None), 1);
+    }
+
+    #[tokio::test]
+    async fn dynamic_round_robin() -> Result<()> {
+        let schema = test_schema();
+        let partition = create_vec_batches(&schema, 50);
+        let partitions = vec![partition.clone(), partition.clone(), partition];
+
+        let config = SessionConfig::new()
+            .set_bool("datafusion.execution.dynamic_round_robin_repartition", true);
+        let task_ctx = SessionContext::with_config(config).task_ctx();
+        let exec = MemoryExec::try_new(&partitions, schema.clone(), None)?;
+        let exec =
+            RepartitionExec::try_new(Arc::new(exec), Partitioning::RoundRobinBatch(4))?;
+
+        // a slow output partition receives fewer batches than the others
+        let handles: Vec<_> = (0..4)
+            .map(|i| exec.execute(i, task_ctx.clone()))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut stream)| {
+                tokio::spawn(async move {
+                    let mut batches = 0;
+                    while let Some(batch) = stream.next().await {
+                        batch?;
+                        batches += 1;
+                        if i == 0 {
+                            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                        }
+                    }
+                    Ok::<_, ArrowError>(batches)
+                })
+            })
+            .collect();
+
+        let mut batches = vec![];
+        for handle in handles {
+            batches.push(handle.await.unwrap()?);
+        }
+        assert_eq!(batches.iter().sum::<usize>(), 150);
+        assert!(batches[0] < batches[1]);
+
+        Ok(())
+    }
+
     fn test_schema() -> Arc<Schema> {
         Arc::new(Schema::new(vec![Field::new("c0", DataType::UInt32, false)]))
     }
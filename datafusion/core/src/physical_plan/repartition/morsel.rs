@@ -0,0 +1,266 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Pull-driven round robin repartitioning, where each output partition reads
+//! the next batch ("morsel") of any available input partition when it is polled
+//!
+//! # Design
+//!
+//! ```text
+//!                      +------+
+//! +-------+            | Pool |            +--------+
+//! | Input |<==========>|      |<==========>| Output |
+//! +-------+  idle      |      |  checked   +--------+
+//! +-------+  inputs    |      |  out       +--------+
+//! | Input |<==========>|      |<==========>| Output |
+//! +-------+            +------+            +--------+
+//! ```
+//!
+//! The input streams are kept in a shared pool. An output partition checks out
+//! an idle input, polls it, and puts it back into the pool once it returned a
+//! batch, so that no task is spawned and no batch is buffered. An input is
+//! only polled by one output at a time, which is the one woken up once the
+//! input is ready. The outputs that find no idle input are woken up whenever
+//! an input is put back into the pool, or is exhausted.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+
+use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
+
+/// The input partitions shared by all the output partitions
+pub(super) struct MorselPool {
+    state: Mutex<PoolState>,
+}
+
+struct PoolState {
+    /// Input streams that no output partition is reading
+    idle: VecDeque<SendableRecordBatchStream>,
+    /// Number of input streams checked out by an output partition
+    checked_out: usize,
+    /// Wakers of the output partitions waiting for an idle input
+    wakers: Vec<Waker>,
+}
+
+impl MorselPool {
+    /// Create a pool of the input streams `inputs`
+    pub(super) fn new(inputs: Vec<SendableRecordBatchStream>) -> Self {
+        Self {
+            state: Mutex::new(PoolState {
+                idle: inputs.into(),
+                checked_out: 0,
+                wakers: vec![],
+            }),
+        }
+    }
+
+    /// Checks out an idle input stream. Returns `Ready(None)` once all the
+    /// input streams are exhausted.
+    fn check_out(&self, cx: &mut Context<'_>) -> Poll<Option<SendableRecordBatchStream>> {
+        let mut state = self.state.lock();
+        match state.idle.pop_front() {
+            Some(input) => {
+                state.checked_out += 1;
+                Poll::Ready(Some(input))
+            }
+            None if state.checked_out == 0 => Poll::Ready(None),
+            None => {
+                if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    state.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Puts back an input stream checked out by [`Self::check_out`], or
+    /// `None` if it is exhausted, and wakes up the waiting output partitions
+    fn put_back(&self, input: Option<SendableRecordBatchStream>) {
+        let wakers = {
+            let mut state = self.state.lock();
+            state.checked_out -= 1;
+            state.idle.extend(input);
+            std::mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl Debug for MorselPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock();
+        f.debug_struct("MorselPool")
+            .field("idle", &state.idle.len())
+            .field("checked_out", &state.checked_out)
+            .finish()
+    }
+}
+
+/// An output partition of a pull-driven round robin repartition
+pub(super) struct MorselStream {
+    pool: Arc<MorselPool>,
+    /// The input stream checked out by this output partition while it is
+    /// pending
+    input: Option<SendableRecordBatchStream>,
+    schema: SchemaRef,
+}
+
+impl MorselStream {
+    /// Create an output partition reading the inputs of `pool`
+    pub(super) fn new(pool: Arc<MorselPool>, schema: SchemaRef) -> Self {
+        Self {
+            pool,
+            input: None,
+            schema,
+        }
+    }
+}
+
+impl Stream for MorselStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            let mut input = match self.input.take() {
+                Some(input) => input,
+                None => match self.pool.check_out(cx) {
+                    Poll::Ready(Some(input)) => input,
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+
+            match input.poll_next_unpin(cx) {
+                Poll::Ready(Some(result)) => {
+                    self.pool.put_back(Some(input));
+                    return Poll::Ready(Some(result));
+                }
+                // the input is exhausted, try another one
+                Poll::Ready(None) => self.pool.put_back(None),
+                Poll::Pending => {
+                    // keep the input, as only this output partition will be woken
+                    // up once it is ready
+                    self.input = Some(input);
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+impl RecordBatchStream for MorselStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Drop for MorselStream {
+    fn drop(&mut self) {
+        // let the other output partitions read the input this one was waiting for
+        if let Some(input) = self.input.take() {
+            self.pool.put_back(Some(input));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::stream::RecordBatchStreamAdapter;
+    use arrow::array::UInt32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use futures::channel::mpsc;
+    use futures::task::noop_waker;
+
+    fn test_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![Field::new("c0", DataType::UInt32, false)]))
+    }
+
+    fn test_batch(value: u32) -> RecordBatch {
+        let array = UInt32Array::from(vec![value]);
+        RecordBatch::try_new(test_schema(), vec![Arc::new(array)]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn waiting_outputs() {
+        let (tx, rx) = mpsc::unbounded();
+        let input = RecordBatchStreamAdapter::new(test_schema(), rx);
+        let pool = Arc::new(MorselPool::new(vec![Box::pin(input)]));
+
+        let mut output_1 = MorselStream::new(Arc::clone(&pool), test_schema());
+        let mut output_2 = MorselStream::new(Arc::clone(&pool), test_schema());
+
+        // output_1 checks out the only input, that is pending
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(output_1.poll_next_unpin(&mut cx).is_pending());
+        assert!(output_2.poll_next_unpin(&mut cx).is_pending());
+
+        // dropping output_1 puts back the input for output_2
+        drop(output_1);
+        tx.unbounded_send(Ok(test_batch(1))).unwrap();
+        let batch = output_2.next().await.unwrap().unwrap();
+        assert_eq!(batch, test_batch(1));
+
+        drop(tx);
+        assert!(output_2.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn all_inputs_read() {
+        let inputs: Vec<SendableRecordBatchStream> = (0..3)
+            .map(|i| {
+                let batches = (0..4).map(move |j| Ok(test_batch(i * 10 + j)));
+                let stream = futures::stream::iter(batches);
+                Box::pin(RecordBatchStreamAdapter::new(test_schema(), stream))
+                    as SendableRecordBatchStream
+            })
+            .collect();
+        let pool = Arc::new(MorselPool::new(inputs));
+
+        let outputs: Vec<_> = (0..2)
+            .map(|_| MorselStream::new(Arc::clone(&pool), test_schema()))
+            .map(|output| tokio::spawn(output.collect::<Vec<_>>()))
+            .collect();
+        let mut values = vec![];
+        for output in outputs {
+            for batch in output.await.unwrap() {
+                let batch = batch.unwrap();
+                let array = batch.column(0).as_any().downcast_ref::<UInt32Array>();
+                values.extend(array.unwrap().values().iter().copied());
+            }
+        }
+
+        values.sort_unstable();
+        let expected: Vec<u32> = (0..3)
+            .flat_map(|i| (0..4).map(move |j| i * 10 + j))
+            .collect();
+        assert_eq!(values, expected);
+    }
+}
@@ -0,0 +1,573 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the execution plan of `INTERSECT ALL` and `EXCEPT ALL`, which
+//! counts the multiplicity of each row of the right input in a hash table
+//! and streams the left input through it
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use ahash::RandomState;
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion_expr::SetOperationType;
+use datafusion_physical_expr::hash_utils::create_row_hashes_v2;
+use futures::{ready, Stream, StreamExt};
+use hashbrown::raw::RawTable;
+use log::debug;
+use parking_lot::Mutex;
+
+use super::expressions::PhysicalSortExpr;
+use super::joins::utils::{OnceAsync, OnceFut};
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::{
+    coalesce_partitions::CoalescePartitionsExec, DisplayFormatType, Distribution,
+    EquivalenceProperties, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::proxy::{RawTableAllocExt, VecAllocExt};
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+
+/// Execution plan of `INTERSECT ALL` and `EXCEPT ALL`.
+///
+/// The distinct rows of the right input are collected in a hash table, with
+/// the number of times each of them is found. Each row of the left input then
+/// consumes one occurrence of the same right row, if any is left: `INTERSECT
+/// ALL` returns the left rows that consumed an occurrence, and `EXCEPT ALL`
+/// the other ones. The rows are compared in the [row format](arrow::row), in
+/// which NULLs are equal.
+///
+/// The occurrences are shared by all the left partitions, so that a row
+/// found `m` times in the left input and `n` times in the right input is
+/// returned `min(m, n)` times by `INTERSECT ALL` and `max(m - n, 0)` times
+/// by `EXCEPT ALL`, whatever the partitioning of the left input.
+#[derive(Debug)]
+pub struct SetOperationAllExec {
+    /// The set operation
+    op: SetOperationType,
+    /// The input whose rows are returned
+    left: Arc<dyn ExecutionPlan>,
+    /// The input whose rows are counted
+    right: Arc<dyn ExecutionPlan>,
+    /// The counted rows of the right input
+    right_fut: OnceAsync<RightData>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl SetOperationAllExec {
+    /// Create a new set operation of `left` and `right`, which must have the
+    /// same column types
+    pub fn try_new(
+        op: SetOperationType,
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        let left_types = left_schema.fields().iter().map(|f| f.data_type());
+        let right_types = right_schema.fields().iter().map(|f| f.data_type());
+        if !left_types.eq(right_types) {
+            return Err(DataFusionError::Plan(format!(
+                "{op} inputs must have the same column types, left is {left_schema:?} and right is {right_schema:?}"
+            )));
+        }
+
+        Ok(Self {
+            op,
+            left,
+            right,
+            right_fut: Default::default(),
+            metrics: ExecutionPlanMetricsSet::new(),
+        })
+    }
+
+    /// The set operation
+    pub fn op(&self) -> SetOperationType {
+        self.op
+    }
+
+    /// The input whose rows are returned
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// The input whose rows are counted
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+}
+
+impl ExecutionPlan for SetOperationAllExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.left.schema()
+    }
+
+    fn unbounded_output(&self, children: &[bool]) -> Result<bool> {
+        if children[1] {
+            Err(DataFusionError::Plan(format!(
+                "{} is not supported for an unbounded right input",
+                self.op
+            )))
+        } else {
+            Ok(children[0])
+        }
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SetOperationAllExec::try_new(
+            self.op,
+            children[0].clone(),
+            children[1].clone(),
+        )?))
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![
+            Distribution::UnspecifiedDistribution,
+            Distribution::SinglePartition,
+        ]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.left.output_partitioning()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.left.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> bool {
+        true
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.left.equivalence_properties()
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let right_fut = self
+            .right_fut
+            .once(|| collect_right_input(self.right.clone(), context.clone()));
+
+        Ok(Box::pin(SetOperationAllStream {
+            op: self.op,
+            schema: self.schema(),
+            left: self.left.execute(partition, context)?,
+            right_fut,
+            hashes: vec![],
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "SetOperationAllExec: op={}", self.op)
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::new_unknown(&self.schema())
+    }
+}
+
+/// The distinct rows of the right input, with the number of their
+/// occurrences not consumed by a left row yet
+struct RightData {
+    /// Converts the columns of both inputs to the row format
+    converter: Mutex<RowConverter>,
+    random_state: RandomState,
+    /// Maps the hashes of the rows to their index in `rows`
+    map: RawTable<(u64, usize)>,
+    /// The distinct rows
+    rows: Vec<OwnedRow>,
+    /// The number of occurrences left of each row of `rows`
+    counts: Mutex<Vec<usize>>,
+    /// Memory reserved for the hash table
+    _reservation: MemoryReservation,
+}
+
+/// Collects the rows of the right input and counts their occurrences
+async fn collect_right_input(
+    right: Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+) -> Result<RightData> {
+    let merge = if right.output_partitioning().partition_count() != 1 {
+        Arc::new(CoalescePartitionsExec::new(right.clone()))
+    } else {
+        right.clone()
+    };
+    let mut stream = merge.execute(0, context.clone())?;
+
+    let fields = right
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| SortField::new(f.data_type().clone()))
+        .collect();
+    let mut converter = RowConverter::new(fields)?;
+    let random_state = RandomState::new();
+    let mut reservation =
+        MemoryConsumer::new("SetOperationAllExec").register(context.memory_pool());
+
+    let mut map: RawTable<(u64, usize)> = RawTable::with_capacity(0);
+    let mut rows: Vec<OwnedRow> = vec![];
+    let mut counts: Vec<usize> = vec![];
+    let mut map_size = 0;
+    let mut hashes = vec![];
+    let mut num_rows = 0;
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        num_rows += batch.num_rows();
+        let batch_rows = converter.convert_columns(batch.columns())?;
+
+        hashes.clear();
+        hashes.resize(batch_rows.num_rows(), 0);
+        create_row_hashes_v2(&batch_rows, &random_state, &mut hashes)?;
+
+        for (row, hash) in hashes.iter().enumerate() {
+            let entry = map.get(*hash, |(_hash, idx)| {
+                batch_rows.row(row) == rows[*idx].row()
+            });
+            match entry {
+                Some((_hash, idx)) => counts[*idx] += 1,
+                None => {
+                    let idx = rows.len();
+                    let owned_row = batch_rows.row(row).owned();
+                    map_size += owned_row.as_ref().len();
+                    rows.push_accounted(owned_row, &mut map_size);
+                    counts.push_accounted(1, &mut map_size);
+                    map.insert_accounted(
+                        (*hash, idx),
+                        |(hash, _idx)| *hash,
+                        &mut map_size,
+                    );
+                }
+            }
+        }
+
+        let size = map_size + converter.size();
+        if size > reservation.size() {
+            reservation.try_grow(size - reservation.size())?;
+        }
+    }
+
+    debug!(
+        "Counted {} distinct rows out of {} rows of the right input of a set operation",
+        rows.len(),
+        num_rows
+    );
+
+    Ok(RightData {
+        converter: Mutex::new(converter),
+        random_state,
+        map,
+        rows,
+        counts: Mutex::new(counts),
+        _reservation: reservation,
+    })
+}
+
+/// Returns the rows of the left batch `batch` returned by `op`, consuming
+/// the occurrences of the matching right rows
+fn filter_left_batch(
+    op: SetOperationType,
+    right_data: &RightData,
+    batch: &RecordBatch,
+    hashes: &mut Vec<u64>,
+) -> ArrowResult<RecordBatch> {
+    let rows = right_data
+        .converter
+        .lock()
+        .convert_columns(batch.columns())?;
+
+    hashes.clear();
+    hashes.resize(rows.num_rows(), 0);
+    create_row_hashes_v2(&rows, &right_data.random_state, hashes)?;
+
+    let mut counts = right_data.counts.lock();
+    let mask: BooleanArray = hashes
+        .iter()
+        .enumerate()
+        .map(|(row, hash)| {
+            let entry = right_data.map.get(*hash, |(_hash, idx)| {
+                rows.row(row) == right_data.rows[*idx].row()
+            });
+            let matched = match entry {
+                Some((_hash, idx)) if counts[*idx] > 0 => {
+                    counts[*idx] -= 1;
+                    true
+                }
+                _ => false,
+            };
+            Some(match op {
+                SetOperationType::Intersect => matched,
+                SetOperationType::Except => !matched,
+            })
+        })
+        .collect();
+
+    filter_record_batch(batch, &mask)
+}
+
+/// A stream returning the rows of a left partition selected by the set
+/// operation
+struct SetOperationAllStream {
+    op: SetOperationType,
+    schema: SchemaRef,
+    /// The left partition
+    left: SendableRecordBatchStream,
+    /// The counted rows of the right input
+    right_fut: OnceFut<RightData>,
+    /// Scratch space for the hashes of the rows of a batch
+    hashes: Vec<u64>,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl SetOperationAllStream {
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<ArrowResult<RecordBatch>>> {
+        let right_data = match ready!(self.right_fut.get(cx)) {
+            Ok(right_data) => right_data,
+            Err(e) => return Poll::Ready(Some(Err(e))),
+        };
+
+        loop {
+            match ready!(self.left.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let timer = self.baseline_metrics.elapsed_compute().timer();
+                    let result =
+                        filter_left_batch(self.op, right_data, &batch, &mut self.hashes);
+                    timer.done();
+                    match result {
+                        // skip entirely filtered batches
+                        Ok(batch) if batch.num_rows() == 0 => continue,
+                        result => return Poll::Ready(Some(result)),
+                    }
+                }
+                other => return Poll::Ready(other),
+            }
+        }
+    }
+}
+
+impl Stream for SetOperationAllStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_impl(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for SetOperationAllStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::execution::context::SessionConfig;
+    use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+    use crate::physical_plan::{collect, memory::MemoryExec};
+    use crate::prelude::SessionContext;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]))
+    }
+
+    fn build_batch(a: Vec<Option<i32>>, b: Vec<Option<&str>>) -> RecordBatch {
+        RecordBatch::try_new(
+            schema(),
+            vec![
+                Arc::new(Int32Array::from(a)),
+                Arc::new(StringArray::from(b)),
+            ],
+        )
+        .unwrap()
+    }
+
+    /// Left rows (1, x) 3 times, (NULL, y) twice, (2, NULL) once and (3, z)
+    /// once, in two partitions
+    fn left_exec() -> Arc<dyn ExecutionPlan> {
+        let partitions = vec![
+            vec![build_batch(
+                vec![Some(1), None, Some(1), Some(2)],
+                vec![Some("x"), Some("y"), Some("x"), None],
+            )],
+            vec![build_batch(
+                vec![Some(1), None, Some(3)],
+                vec![Some("x"), Some("y"), Some("z")],
+            )],
+        ];
+        Arc::new(MemoryExec::try_new(&partitions, schema(), None).unwrap())
+    }
+
+    /// Right rows (1, x) twice, (NULL, y) 3 times, (2, NULL) once and (4, w)
+    /// once
+    fn right_exec() -> Arc<dyn ExecutionPlan> {
+        let batches = vec![
+            build_batch(
+                vec![Some(1), None, None, Some(2)],
+                vec![Some("x"), Some("y"), Some("y"), None],
+            ),
+            build_batch(
+                vec![Some(4), None, Some(1)],
+                vec![Some("w"), Some("y"), Some("x")],
+            ),
+        ];
+        Arc::new(MemoryExec::try_new(&[batches], schema(), None).unwrap())
+    }
+
+    async fn execute(
+        op: SetOperationType,
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+    ) -> Result<Vec<RecordBatch>> {
+        let session_ctx = SessionContext::new();
+        let exec = Arc::new(SetOperationAllExec::try_new(op, left, right)?);
+        collect(exec, session_ctx.task_ctx()).await
+    }
+
+    #[tokio::test]
+    async fn intersect_all() -> Result<()> {
+        let batches =
+            execute(SetOperationType::Intersect, left_exec(), right_exec()).await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "|   | y |",
+            "|   | y |",
+            "| 1 | x |",
+            "| 1 | x |",
+            "| 2 |   |",
+            "+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn except_all() -> Result<()> {
+        let batches =
+            execute(SetOperationType::Except, left_exec(), right_exec()).await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | x |",
+            "| 3 | z |",
+            "+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        let batches =
+            execute(SetOperationType::Except, right_exec(), left_exec()).await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "|   | y |",
+            "| 4 | w |",
+            "+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[test]
+    fn mismatched_types() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let right = Arc::new(MemoryExec::try_new(&[vec![]], schema, None).unwrap());
+        let err =
+            SetOperationAllExec::try_new(SetOperationType::Intersect, left_exec(), right)
+                .unwrap_err();
+        assert!(err.to_string().contains("must have the same column types"));
+    }
+
+    #[tokio::test]
+    async fn overallocation() -> Result<()> {
+        let runtime_config = RuntimeConfig::new().with_memory_limit(100, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let session_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime);
+
+        let exec = Arc::new(SetOperationAllExec::try_new(
+            SetOperationType::Intersect,
+            left_exec(),
+            right_exec(),
+        )?);
+        let err = collect(exec, session_ctx.task_ctx()).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Resources exhausted: Failed to allocate additional"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,489 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SharedCteExec reads the results of a common table expression, which are
+//! computed once in a [`WorkTable`] for all its references.
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll, Waker};
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use crate::physical_plan::{
+    DisplayFormatType, EquivalenceProperties, ExecutionPlan, Partitioning,
+    RecordBatchStream, SendableRecordBatchStream,
+};
+use arrow::datatypes::SchemaRef;
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::record_batch::RecordBatch;
+use futures::stream::{Stream, StreamExt};
+use parking_lot::Mutex;
+
+use super::common::AbortOnDropSingle;
+use super::expressions::PhysicalSortExpr;
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::Statistics;
+
+/// SharedCteExec is a reference to the common table expression `name`, whose
+/// plan is its input.
+///
+/// Without a [`WorkTable`], it simply executes its input. With a work table,
+/// all the references to the common table expression are the same
+/// SharedCteExec, and read the results of the input from the work table,
+/// which executes each partition of the input once for all of them.
+#[derive(Debug)]
+pub struct SharedCteExec {
+    /// Identifies the common table expression
+    id: usize,
+    /// The name of the common table expression
+    name: String,
+    /// The plan of the common table expression
+    input: Arc<dyn ExecutionPlan>,
+    /// The results of the input, shared by the references
+    work_table: Option<Arc<WorkTable>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl SharedCteExec {
+    /// Create a new SharedCteExec, which executes its input for each reference
+    pub fn new(
+        id: usize,
+        name: impl Into<String>,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            input,
+            work_table: None,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+
+    /// Read the results of the input from a work table, which executes the
+    /// input once for all the readers
+    pub fn with_work_table(mut self) -> Self {
+        let partition_count = self.input.output_partitioning().partition_count();
+        self.work_table = Some(Arc::new(WorkTable::new(partition_count)));
+        self
+    }
+
+    /// Identifies the common table expression
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The name of the common table expression
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The plan of the common table expression
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The work table the results of the input are read from, if any
+    pub fn work_table(&self) -> Option<&Arc<WorkTable>> {
+        self.work_table.as_ref()
+    }
+}
+
+impl ExecutionPlan for SharedCteExec {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn unbounded_output(&self, children: &[bool]) -> Result<bool> {
+        Ok(children[0])
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> bool {
+        true
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.input.equivalence_properties()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // the results in the work table are those of the previous input
+        let cte = SharedCteExec::new(self.id, self.name.clone(), children[0].clone());
+        Ok(Arc::new(if self.work_table.is_some() {
+            cte.with_work_table()
+        } else {
+            cte
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let work_table = match &self.work_table {
+            Some(work_table) => work_table,
+            None => return self.input.execute(partition, context),
+        };
+        let partition_state = work_table.partition(partition, &self.input, context)?;
+        Ok(Box::pin(WorkTableStream {
+            partition: partition_state,
+            schema: self.input.schema(),
+            next_batch: 0,
+            is_done: false,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "SharedCteExec: name={}", self.name)?;
+                if self.work_table.is_some() {
+                    write!(f, ", work_table=true")?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        self.input.partition_statistics(partition)
+    }
+}
+
+/// The results of the plan of a common table expression, computed once and
+/// read by all its references.
+///
+/// Each partition of the plan is executed by a task, started by the first
+/// reader of the partition, which buffers its batches in memory until the
+/// work table is dropped. The readers get the batches as soon as they are
+/// buffered, so that the references don't wait for the whole partition, and
+/// a slow reader doesn't hold back the others.
+#[derive(Debug)]
+pub struct WorkTable {
+    /// The state of each partition of the plan
+    partitions: Vec<Arc<Mutex<WorkTablePartition>>>,
+}
+
+impl WorkTable {
+    /// Create an empty work table for a plan with `partition_count` partitions
+    fn new(partition_count: usize) -> Self {
+        Self {
+            partitions: (0..partition_count)
+                .map(|_| Arc::new(Mutex::new(WorkTablePartition::default())))
+                .collect(),
+        }
+    }
+
+    /// Returns the state of the partition, starting the task that executes it
+    /// if it is the first time the partition is read
+    fn partition(
+        &self,
+        partition: usize,
+        input: &Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<Arc<Mutex<WorkTablePartition>>> {
+        let partition_state = self.partitions.get(partition).ok_or_else(|| {
+            DataFusionError::Internal(format!(
+                "Invalid partition {partition} of a work table with {} partitions",
+                self.partitions.len()
+            ))
+        })?;
+        let mut state = partition_state.lock();
+        if state.task.is_none() {
+            let stream = input.execute(partition, context.clone())?;
+            state.reservation = Some(
+                MemoryConsumer::new(format!("WorkTable[{partition}]"))
+                    .register(context.memory_pool()),
+            );
+            // the tasks only keep a weak reference to the partition, so that
+            // they are aborted once the partition is dropped
+            let compute_task =
+                tokio::spawn(compute_partition(stream, Arc::downgrade(partition_state)));
+            // the readers are notified of the errors, including panics, of
+            // the task computing the partition
+            let state_on_error = Arc::downgrade(partition_state);
+            let task = tokio::spawn(async move {
+                if let Err(e) = AbortOnDropSingle::new(compute_task).await {
+                    if let Some(partition) = state_on_error.upgrade() {
+                        let mut state = partition.lock();
+                        state.finish(Some(ArrowError::ExternalError(Box::new(e))));
+                    }
+                }
+            });
+            state.task = Some(AbortOnDropSingle::new(task));
+        }
+        Ok(Arc::clone(partition_state))
+    }
+}
+
+/// The results of a partition of a work table
+#[derive(Debug, Default)]
+struct WorkTablePartition {
+    /// The task computing the partition, once started
+    task: Option<AbortOnDropSingle<()>>,
+    /// The memory used by the buffered batches
+    reservation: Option<MemoryReservation>,
+    /// The batches computed so far
+    batches: Vec<RecordBatch>,
+    /// Whether all the batches of the partition have been computed
+    is_complete: bool,
+    /// The error that stopped the computation of the partition
+    error: Option<Arc<ArrowError>>,
+    /// The readers waiting for the next batch
+    wakers: Vec<Waker>,
+}
+
+impl WorkTablePartition {
+    /// Ends the computation of the partition, with an optional error
+    fn finish(&mut self, error: Option<ArrowError>) {
+        if !self.is_complete {
+            self.is_complete = true;
+            self.error = error.map(Arc::new);
+        }
+        self.wake_readers();
+    }
+
+    fn wake_readers(&mut self) {
+        self.wakers.drain(..).for_each(|waker| waker.wake());
+    }
+}
+
+/// Buffers the batches of a partition of the plan of a common table expression
+async fn compute_partition(
+    mut stream: SendableRecordBatchStream,
+    partition: Weak<Mutex<WorkTablePartition>>,
+) {
+    while let Some(result) = stream.next().await {
+        let partition_state = match partition.upgrade() {
+            Some(partition_state) => partition_state,
+            None => return,
+        };
+        let mut state = partition_state.lock();
+        let result = result.and_then(|batch| {
+            if let Some(reservation) = state.reservation.as_mut() {
+                reservation
+                    .try_grow(batch.get_array_memory_size())
+                    .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+            }
+            Ok(batch)
+        });
+        match result {
+            Ok(batch) => {
+                state.batches.push(batch);
+                state.wake_readers();
+            }
+            Err(e) => return state.finish(Some(e)),
+        }
+    }
+    if let Some(partition_state) = partition.upgrade() {
+        partition_state.lock().finish(None)
+    }
+}
+
+/// Reads the batches of a partition of a work table
+struct WorkTableStream {
+    /// The partition of the work table
+    partition: Arc<Mutex<WorkTablePartition>>,
+    /// The schema of the batches
+    schema: SchemaRef,
+    /// The index of the next batch to read
+    next_batch: usize,
+    /// Whether all the batches, or the error, have been read
+    is_done: bool,
+    /// Execution metrics
+    baseline_metrics: BaselineMetrics,
+}
+
+impl Stream for WorkTableStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_inner(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl WorkTableStream {
+    fn poll_next_inner(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<ArrowResult<RecordBatch>>> {
+        if self.is_done {
+            return Poll::Ready(None);
+        }
+        let partition = Arc::clone(&self.partition);
+        let mut state = partition.lock();
+        if let Some(batch) = state.batches.get(self.next_batch) {
+            self.next_batch += 1;
+            return Poll::Ready(Some(Ok(batch.clone())));
+        }
+        if !state.is_complete {
+            state.wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        self.is_done = true;
+        Poll::Ready(
+            state
+                .error
+                .as_ref()
+                .map(|e| Err(ArrowError::ExternalError(Box::new(Arc::clone(e))))),
+        )
+    }
+}
+
+impl RecordBatchStream for WorkTableStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::collect_partitioned;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::union::UnionExec;
+    use crate::prelude::SessionContext;
+    use crate::test::exec::{BlockingExec, MockExec};
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn memory_exec() -> Result<Arc<MemoryExec>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = |values: &[i32]| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int32Array::from_slice(values))],
+            )
+            .unwrap()
+        };
+        let partitions = vec![vec![batch(&[1, 2]), batch(&[3])], vec![batch(&[4, 5, 6])]];
+        Ok(Arc::new(MemoryExec::try_new(&partitions, schema, None)?))
+    }
+
+    #[tokio::test]
+    async fn share_results() -> Result<()> {
+        let input = memory_exec()?;
+        let cte: Arc<dyn ExecutionPlan> =
+            Arc::new(SharedCteExec::new(0, "cte", input.clone()).with_work_table());
+        let plan = Arc::new(UnionExec::new(vec![cte.clone(), cte.clone()]));
+
+        let session_ctx = SessionContext::new();
+        let output = collect_partitioned(plan, session_ctx.task_ctx()).await?;
+        let sizes = output
+            .iter()
+            .map(|batches| batches.iter().map(|b| b.num_rows()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![vec![2, 1], vec![3], vec![2, 1], vec![3]]);
+
+        // the reads of both references are counted
+        assert_eq!(cte.metrics().unwrap().output_rows(), Some(12));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn without_work_table() -> Result<()> {
+        let cte = Arc::new(SharedCteExec::new(0, "cte", memory_exec()?));
+        let session_ctx = SessionContext::new();
+        let output = collect_partitioned(cte, session_ctx.task_ctx()).await?;
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[1][0].num_rows(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn share_error() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from_slice([1, 2]))],
+        )?;
+        let error = ArrowError::ComputeError("input failed".to_string());
+        let input = Arc::new(MockExec::new(vec![Ok(batch), Err(error)], schema));
+        let cte: Arc<dyn ExecutionPlan> =
+            Arc::new(SharedCteExec::new(0, "cte", input).with_work_table());
+
+        // both readers get the batch, and then the error
+        let session_ctx = SessionContext::new();
+        for _ in 0..2 {
+            let mut stream = cte.execute(0, session_ctx.task_ctx())?;
+            assert_eq!(stream.next().await.unwrap()?.num_rows(), 2);
+            let err = stream.next().await.unwrap().unwrap_err().to_string();
+            assert!(err.contains("input failed"), "{err}");
+            assert!(stream.next().await.is_none());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn drop_work_table_cancels_task() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let blocking_exec = Arc::new(BlockingExec::new(Arc::clone(&schema), 1));
+        let refs = blocking_exec.refs();
+        let cte = Arc::new(SharedCteExec::new(0, "cte", blocking_exec).with_work_table());
+
+        let session_ctx = SessionContext::new();
+        let stream = cte.execute(0, session_ctx.task_ctx())?;
+        drop(stream);
+        drop(cte);
+
+        crate::test::exec::assert_strong_count_converges_to_zero(refs).await;
+        Ok(())
+    }
+}
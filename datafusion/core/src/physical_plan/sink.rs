@@ -0,0 +1,186 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Streaming the results of an [`ExecutionPlan`] into a user provided
+//! [`DataSink`]
+
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use futures::StreamExt;
+
+use super::common::AbortOnDropMany;
+use super::{execute_stream_partitioned, ExecutionPlan};
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+
+/// The destination of the results of a query, written by
+/// [`execute_into_sink`] as they are produced.
+///
+/// The partitions of the query are written concurrently, but the batches of a
+/// partition are written one at a time: the next batch of a partition is only
+/// computed once the write of the previous one completes. A slow sink thus
+/// slows down the query instead of making it buffer its results.
+#[async_trait]
+pub trait DataSink: Send + Sync {
+    /// Writes the next batch of `partition`
+    async fn write(&self, partition: usize, batch: RecordBatch) -> Result<()>;
+
+    /// Called once all the batches of `partition` have been written
+    async fn finish_partition(&self, _partition: usize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once all the partitions have been written
+    async fn finish(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Executes all the partitions of `plan` concurrently, writing their results
+/// into `sink`, and returns the number of rows written.
+///
+/// On the first error of a partition, or of the sink, the execution of the other
+/// partitions is cancelled and the error is returned, without calling
+/// [`DataSink::finish`].
+pub async fn execute_into_sink(
+    plan: Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+    sink: Arc<dyn DataSink>,
+) -> Result<u64> {
+    let streams = execute_stream_partitioned(plan, context)?;
+    let handles = streams
+        .into_iter()
+        .enumerate()
+        .map(|(partition, mut stream)| {
+            let sink = sink.clone();
+            tokio::spawn(async move {
+                let mut rows = 0;
+                while let Some(batch) = stream.next().await {
+                    let batch = batch?;
+                    rows += batch.num_rows() as u64;
+                    sink.write(partition, batch).await?;
+                }
+                sink.finish_partition(partition).await?;
+                Ok::<_, DataFusionError>(rows)
+            })
+        })
+        .collect();
+    // the tasks still running are aborted when returning early
+    let mut handles = AbortOnDropMany(handles);
+
+    let rows =
+        futures::future::try_join_all(handles.0.iter_mut().map(|handle| async move {
+            handle
+                .await
+                .map_err(|e| DataFusionError::Execution(format!("{e}")))?
+        }))
+        .await?;
+    sink.finish().await?;
+    Ok(rows.into_iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::prelude::SessionContext;
+    use crate::test::create_vec_batches;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use parking_lot::Mutex;
+
+    /// A sink recording the batches of each partition
+    #[derive(Default)]
+    struct VecSink {
+        partitions: Mutex<Vec<Vec<RecordBatch>>>,
+        finished_partitions: Mutex<Vec<usize>>,
+        finished: Mutex<bool>,
+        /// The number of batches after which writes fail
+        fail_after: Option<usize>,
+    }
+
+    #[async_trait]
+    impl DataSink for VecSink {
+        async fn write(&self, partition: usize, batch: RecordBatch) -> Result<()> {
+            let mut partitions = self.partitions.lock();
+            if Some(partitions.iter().map(Vec::len).sum()) == self.fail_after {
+                return Err(DataFusionError::Execution("sink is full".to_string()));
+            }
+            if partitions.len() <= partition {
+                partitions.resize(partition + 1, vec![]);
+            }
+            partitions[partition].push(batch);
+            Ok(())
+        }
+
+        async fn finish_partition(&self, partition: usize) -> Result<()> {
+            self.finished_partitions.lock().push(partition);
+            Ok(())
+        }
+
+        async fn finish(&self) -> Result<()> {
+            *self.finished.lock() = true;
+            Ok(())
+        }
+    }
+
+    fn plan() -> Arc<dyn ExecutionPlan> {
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("c0", DataType::UInt32, false)]));
+        let partitions = vec![
+            create_vec_batches(&schema, 5),
+            create_vec_batches(&schema, 3),
+        ];
+        Arc::new(MemoryExec::try_new(&partitions, schema, None).unwrap())
+    }
+
+    #[tokio::test]
+    async fn write_all_partitions() -> Result<()> {
+        let task_ctx = SessionContext::new().task_ctx();
+        let sink = Arc::new(VecSink::default());
+
+        let rows = execute_into_sink(plan(), task_ctx, sink.clone()).await?;
+        assert_eq!(rows, 8 * 8);
+
+        let partitions = sink.partitions.lock();
+        assert_eq!(partitions.len(), 2);
+        assert_eq!(partitions[0].len(), 5);
+        assert_eq!(partitions[1].len(), 3);
+        let mut finished_partitions = sink.finished_partitions.lock().clone();
+        finished_partitions.sort_unstable();
+        assert_eq!(finished_partitions, vec![0, 1]);
+        assert!(*sink.finished.lock());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sink_error() -> Result<()> {
+        let task_ctx = SessionContext::new().task_ctx();
+        let sink = Arc::new(VecSink {
+            fail_after: Some(2),
+            ..Default::default()
+        });
+
+        let err = execute_into_sink(plan(), task_ctx, sink.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Execution error: sink is full");
+        assert!(!*sink.finished.lock());
+        Ok(())
+    }
+}
@@ -22,6 +22,8 @@ use std::fmt::{Debug, Formatter};
 
 mod cursor;
 mod index;
+pub mod partial_sort;
+pub mod row_sort;
 pub mod sort;
 pub mod sort_preserving_merge;
 
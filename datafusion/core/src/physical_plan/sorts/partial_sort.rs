@@ -0,0 +1,556 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Sort of an input that is already sorted on a prefix of the sort expressions.
+//!
+//! For example, if the input is sorted by `(a)` and the requested order is
+//! `(a, b)`, the rows only have to be sorted by `b` within each run of rows
+//! with the same value of `a`:
+//!
+//! ```text
+//!   input (sorted by a)        output (sorted by a, b)
+//! +---+---+                    +---+---+
+//! | a | b |                    | a | b |
+//! +---+---+                    +---+---+
+//! | 1 | 3 |  \                 | 1 | 2 |
+//! | 1 | 2 |   > run of a = 1   | 1 | 3 |
+//! +---+---+  /   sorted first  +---+---+
+//! | 2 | 9 |  \                 | 2 | 1 |
+//! | 2 | 1 |   > run of a = 2   | 2 | 9 |
+//! +---+---+  /                 +---+---+
+//! ```
+//!
+//! A run is emitted as soon as a row with a different prefix arrives, so
+//! unlike [`SortExec`](super::sort::SortExec) the operator doesn't have to
+//! buffer its whole input: its memory is bounded by the size of the largest
+//! run, and it can process an infinite input.
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use crate::physical_plan::common::batch_byte_size;
+use crate::physical_plan::expressions::PhysicalSortExpr;
+use crate::physical_plan::limit::{limit_statistics, local_limit_statistics};
+use crate::physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet,
+};
+use crate::physical_plan::sorts::row_sort::lexsort_to_indices;
+use crate::physical_plan::{
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use arrow::array::ArrayRef;
+use arrow::compute::{
+    concat_batches, lexicographical_partition_ranges, take, SortColumn, TakeOptions,
+};
+use arrow::datatypes::SchemaRef;
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::ScalarValue;
+use datafusion_physical_expr::EquivalenceProperties;
+use futures::{ready, Stream, StreamExt};
+use log::debug;
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Sorts each partition of its input, which must already be sorted on the
+/// first `common_prefix_length` sort expressions, by sorting the runs of
+/// rows with equal values of that prefix one after the other.
+#[derive(Debug)]
+pub struct PartialSortExec {
+    /// Input plan
+    input: Arc<dyn ExecutionPlan>,
+    /// Sort expressions
+    expr: Vec<PhysicalSortExpr>,
+    /// Length of the prefix of `expr` the input is already sorted on
+    common_prefix_length: usize,
+    /// Fetch highest/lowest n results
+    fetch: Option<usize>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl PartialSortExec {
+    /// Create a new partial sort execution plan, sorting `input` by `expr`
+    /// given that `input` is already sorted by the first
+    /// `common_prefix_length` expressions of `expr`
+    pub fn try_new(
+        expr: Vec<PhysicalSortExpr>,
+        input: Arc<dyn ExecutionPlan>,
+        common_prefix_length: usize,
+        fetch: Option<usize>,
+    ) -> Result<Self> {
+        if common_prefix_length == 0 || common_prefix_length >= expr.len() {
+            return Err(DataFusionError::Plan(format!(
+                "PartialSortExec requires a common prefix length between 1 and {}, got {}",
+                expr.len().saturating_sub(1),
+                common_prefix_length
+            )));
+        }
+        Ok(Self {
+            input,
+            expr,
+            common_prefix_length,
+            fetch,
+            metrics: ExecutionPlanMetricsSet::new(),
+        })
+    }
+
+    /// Input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Sort expressions
+    pub fn expr(&self) -> &[PhysicalSortExpr] {
+        &self.expr
+    }
+
+    /// Length of the prefix of the sort expressions the input is sorted on
+    pub fn common_prefix_length(&self) -> usize {
+        self.common_prefix_length
+    }
+
+    /// If `Some(fetch)`, limits output to only the first "fetch" items
+    pub fn fetch(&self) -> Option<usize> {
+        self.fetch
+    }
+}
+
+impl ExecutionPlan for PartialSortExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    /// Each partition of the input is sorted on its own
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    /// Runs are emitted as soon as they are complete, so an infinite input
+    /// produces an infinite output
+    fn unbounded_output(&self, children: &[bool]) -> Result<bool> {
+        Ok(children[0])
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::UnspecifiedDistribution]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<&[PhysicalSortExpr]>> {
+        vec![Some(&self.expr[..self.common_prefix_length])]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> bool {
+        false
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        Some(&self.expr)
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.input.equivalence_properties()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(PartialSortExec::try_new(
+            self.expr.clone(),
+            children[0].clone(),
+            self.common_prefix_length,
+            self.fetch,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        debug!("Start PartialSortExec::execute for partition {} of context session_id {} and task_id {:?}", partition, context.session_id(), context.task_id());
+
+        let input = self.input.execute(partition, context.clone())?;
+        let reservation = MemoryConsumer::new(format!("PartialSortExec[{partition}]"))
+            .register(context.memory_pool());
+
+        Ok(Box::pin(PartialSortStream {
+            input,
+            expr: self.expr.clone(),
+            common_prefix_length: self.common_prefix_length,
+            buffer: vec![],
+            fetch: self.fetch,
+            finished: false,
+            reservation,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                let expr: Vec<String> = self.expr.iter().map(|e| e.to_string()).collect();
+                write!(
+                    f,
+                    "PartialSortExec: [{}], common_prefix_length=[{}]",
+                    expr.join(","),
+                    self.common_prefix_length
+                )
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        let input_stats = self.input.statistics();
+        match self.fetch {
+            Some(fetch) => local_limit_statistics(
+                input_stats,
+                fetch,
+                self.output_partitioning().partition_count(),
+            ),
+            None => input_stats,
+        }
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        limit_statistics(self.input.partition_statistics(partition), 0, self.fetch)
+    }
+}
+
+/// Stream of [`PartialSortExec`]: buffers the rows of the last, possibly
+/// incomplete, run of the input and emits the runs before it sorted.
+struct PartialSortStream {
+    /// Input stream, sorted on the prefix of `expr`
+    input: SendableRecordBatchStream,
+    /// Sort expressions
+    expr: Vec<PhysicalSortExpr>,
+    /// Length of the prefix of `expr` the input is sorted on
+    common_prefix_length: usize,
+    /// Rows of the current run, all with the same prefix values
+    buffer: Vec<RecordBatch>,
+    /// Number of rows still to be produced, if limited
+    fetch: Option<usize>,
+    /// Whether the stream produced all its rows
+    finished: bool,
+    /// Memory used by `buffer`
+    reservation: MemoryReservation,
+    /// Execution metrics
+    baseline_metrics: BaselineMetrics,
+}
+
+impl PartialSortStream {
+    /// Adds `batch` to the rows of the stream, and returns the rows of the
+    /// runs it completes sorted, if any
+    fn insert_batch(&mut self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        if batch.num_rows() == 0 {
+            return Ok(None);
+        }
+        let prefix = self.evaluate_prefix(&batch)?;
+        // the rows before the last run of the batch complete the buffered run
+        let split = lexicographical_partition_ranges(&prefix)?
+            .last()
+            .map(|range| range.start)
+            .unwrap_or_default();
+
+        let completed = if split > 0 {
+            self.buffer.push(batch.slice(0, split));
+            Some(batch.slice(split, batch.num_rows() - split))
+        } else if self.continues_buffer(&prefix)? {
+            None
+        } else {
+            Some(batch.clone())
+        };
+
+        match completed {
+            Some(last_run) => {
+                let sorted = self.sort_buffer()?;
+                self.reservation.try_resize(batch_byte_size(&last_run))?;
+                self.buffer.push(last_run);
+                Ok(Some(sorted))
+            }
+            None => {
+                self.reservation.try_grow(batch_byte_size(&batch))?;
+                self.buffer.push(batch);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Whether the first row of a batch, of which `prefix` are the prefix
+    /// columns, belongs to the buffered run
+    fn continues_buffer(&self, prefix: &[SortColumn]) -> Result<bool> {
+        let last = match self.buffer.last() {
+            Some(last) => last,
+            None => return Ok(true),
+        };
+        let last_prefix = self.evaluate_prefix(last)?;
+        let last_row = last.num_rows() - 1;
+        for (buffered, column) in last_prefix.iter().zip(prefix) {
+            if ScalarValue::try_from_array(&buffered.values, last_row)?
+                != ScalarValue::try_from_array(&column.values, 0)?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn evaluate_prefix(&self, batch: &RecordBatch) -> Result<Vec<SortColumn>> {
+        self.expr[..self.common_prefix_length]
+            .iter()
+            .map(|e| e.evaluate_to_sort_column(batch))
+            .collect()
+    }
+
+    /// Sorts the buffered rows, at most `fetch` of them, and empties the buffer
+    fn sort_buffer(&mut self) -> Result<RecordBatch> {
+        // record compute time on drop
+        let _timer = self.baseline_metrics.elapsed_compute().timer();
+
+        let batches = std::mem::take(&mut self.buffer);
+        let batch = concat_batches(&self.input.schema(), &batches)?;
+        let sort_columns = self
+            .expr
+            .iter()
+            .map(|e| e.evaluate_to_sort_column(&batch))
+            .collect::<Result<Vec<_>>>()?;
+        let indices = lexsort_to_indices(&sort_columns, self.fetch)?;
+        let columns = batch
+            .columns()
+            .iter()
+            .map(|column| {
+                take(
+                    column.as_ref(),
+                    &indices,
+                    // the indices are generated from the same record batch
+                    Some(TakeOptions {
+                        check_bounds: false,
+                    }),
+                )
+            })
+            .collect::<ArrowResult<Vec<ArrayRef>>>()?;
+        let sorted = RecordBatch::try_new(batch.schema(), columns)?;
+
+        if let Some(fetch) = self.fetch.as_mut() {
+            *fetch -= sorted.num_rows();
+            if *fetch == 0 {
+                self.finished = true;
+            }
+        }
+        Ok(sorted)
+    }
+
+    fn poll_next_inner(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<ArrowResult<RecordBatch>>> {
+        loop {
+            if self.finished {
+                self.reservation.free();
+                return Poll::Ready(None);
+            }
+            let sorted = match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => self.insert_batch(batch),
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    self.finished = true;
+                    self.sort_buffer().map(Some)
+                }
+            };
+            match sorted {
+                Ok(Some(batch)) if batch.num_rows() > 0 => {
+                    return Poll::Ready(Some(Ok(batch)))
+                }
+                Ok(_) => continue,
+                Err(e) => {
+                    return Poll::Ready(Some(Err(ArrowError::ExternalError(Box::new(e)))))
+                }
+            }
+        }
+    }
+}
+
+impl Stream for PartialSortStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_inner(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for PartialSortStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::collect;
+    use crate::physical_plan::expressions::col;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::prelude::SessionContext;
+    use arrow::array::Int32Array;
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::util::pretty::pretty_format_batches;
+
+    fn batch(schema: &SchemaRef, a: Vec<i32>, b: Vec<i32>) -> RecordBatch {
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(a)), Arc::new(Int32Array::from(b))],
+        )
+        .unwrap()
+    }
+
+    fn partial_sort(fetch: Option<usize>) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        // the runs of `a` span several batches
+        let batches = vec![
+            batch(&schema, vec![1, 1, 1], vec![3, 1, 2]),
+            batch(&schema, vec![1, 2, 2], vec![0, 5, 4]),
+            batch(&schema, vec![2, 2, 2], vec![9, 3, 7]),
+            batch(&schema, vec![3, 3, 4], vec![2, 1, 8]),
+            batch(&schema, vec![], vec![]),
+            batch(&schema, vec![5], vec![6]),
+        ];
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+        let expr = vec![
+            PhysicalSortExpr {
+                expr: col("a", &schema)?,
+                options: SortOptions::default(),
+            },
+            PhysicalSortExpr {
+                expr: col("b", &schema)?,
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: true,
+                },
+            },
+        ];
+        Ok(Arc::new(PartialSortExec::try_new(expr, input, 1, fetch)?))
+    }
+
+    #[tokio::test]
+    async fn test_partial_sort() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let plan = partial_sort(None)?;
+        let result = collect(plan.clone(), session_ctx.task_ctx()).await?;
+
+        // one batch per completed run
+        assert_eq!(result.len(), 4);
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | 3 |",
+            "| 1 | 2 |",
+            "| 1 | 1 |",
+            "| 1 | 0 |",
+            "| 2 | 9 |",
+            "| 2 | 7 |",
+            "| 2 | 5 |",
+            "| 2 | 4 |",
+            "| 2 | 3 |",
+            "| 3 | 2 |",
+            "| 3 | 1 |",
+            "| 4 | 8 |",
+            "| 5 | 6 |",
+            "+---+---+",
+        ];
+        assert_eq!(
+            pretty_format_batches(&result)?.to_string(),
+            expected.join("\n")
+        );
+
+        let metrics = plan.metrics().unwrap();
+        assert_eq!(metrics.output_rows().unwrap(), 13);
+        assert_eq!(
+            session_ctx.runtime_env().memory_pool.reserved(),
+            0,
+            "The sort should have returned all memory used back to the memory pool"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_partial_sort_fetch() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let result = collect(partial_sort(Some(6))?, session_ctx.task_ctx()).await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | 3 |",
+            "| 1 | 2 |",
+            "| 1 | 1 |",
+            "| 1 | 0 |",
+            "| 2 | 9 |",
+            "| 2 | 7 |",
+            "+---+---+",
+        ];
+        assert_eq!(
+            pretty_format_batches(&result)?.to_string(),
+            expected.join("\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_sort_invalid_prefix() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let input = Arc::new(MemoryExec::try_new(&[], schema.clone(), None)?);
+        let expr = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let err = PartialSortExec::try_new(expr, input, 1, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: PartialSortExec requires a common prefix length between 1 and 0, got 1"
+        );
+        Ok(())
+    }
+}
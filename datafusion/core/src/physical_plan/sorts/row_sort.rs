@@ -0,0 +1,228 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Sorting of multiple columns using normalized keys
+
+use crate::error::Result;
+use arrow::array::{ArrayRef, UInt32Array};
+use arrow::compute::SortColumn;
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_physical_expr::sort_key::{is_nested, nested_sort_key};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// Sort the rows of `columns` lexicographically, returning the indices of
+/// the first `fetch` rows in sorted order, like
+/// [`arrow::compute::lexsort_to_indices`].
+///
+/// Multiple columns are first converted to the [row format](arrow::row),
+/// whose rows are normalized keys that compare with `memcmp`, instead of
+/// comparing each column in turn through a dynamic comparator. The row
+/// format handles the sort options of the columns, including the placement
+/// of nulls, and the dictionary encoded columns, which are compared by
+/// value. Structs and lists, which the row format doesn't support, are
+/// replaced by their [`nested_sort_key`]. A single column of another type is
+/// sorted directly, with a type specialized kernel.
+pub fn lexsort_to_indices(
+    columns: &[SortColumn],
+    fetch: Option<usize>,
+) -> Result<UInt32Array> {
+    let has_nested = columns
+        .iter()
+        .any(|column| is_nested(column.values.data_type()));
+    if columns.len() == 1 && !has_nested {
+        return Ok(arrow::compute::lexsort_to_indices(columns, fetch)?);
+    }
+
+    let arrays = columns
+        .iter()
+        .map(|column| sort_key(&column.values))
+        .collect::<Result<Vec<ArrayRef>>>()?;
+    let fields = arrays
+        .iter()
+        .zip(columns)
+        .map(|(array, column)| {
+            SortField::new_with_options(
+                array.data_type().clone(),
+                column.options.unwrap_or_default(),
+            )
+        })
+        .collect();
+    let mut converter = RowConverter::new(fields)?;
+    let rows = converter.convert_columns(&arrays)?;
+
+    Ok(UInt32Array::from(sort_rows(&rows, fetch)))
+}
+
+/// Returns the array whose rows are converted to sort `array`: the
+/// [`nested_sort_key`] of structs and lists, and `array` itself otherwise
+pub fn sort_key(array: &ArrayRef) -> Result<ArrayRef> {
+    if is_nested(array.data_type()) {
+        nested_sort_key(array.as_ref())
+    } else {
+        Ok(Arc::clone(array))
+    }
+}
+
+/// Returns the indices of the first `fetch` of `rows` in sorted order
+fn sort_rows(rows: &Rows, fetch: Option<usize>) -> Vec<u32> {
+    let compare = |a: &u32, b: &u32| -> Ordering {
+        rows.row(*a as usize).cmp(&rows.row(*b as usize))
+    };
+
+    let mut indices = (0..rows.num_rows() as u32).collect::<Vec<_>>();
+    match fetch {
+        Some(fetch) if fetch < indices.len() => {
+            if fetch > 0 {
+                // partition around the last row to keep before sorting
+                indices.select_nth_unstable_by(fetch - 1, compare);
+            }
+            indices.truncate(fetch);
+            indices.sort_unstable_by(compare);
+        }
+        _ => indices.sort_unstable_by(compare),
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        DictionaryArray, Float64Array, Int32Array, ListArray, StringArray, StructArray,
+    };
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Int32Type};
+    use std::sync::Arc;
+
+    fn columns() -> Vec<SortColumn> {
+        let a: DictionaryArray<Int32Type> =
+            vec![Some("b"), Some("a"), None, Some("b"), Some("a"), Some("b")]
+                .into_iter()
+                .collect();
+        let b = Float64Array::from(vec![
+            Some(1.0),
+            Some(2.0),
+            Some(3.0),
+            None,
+            Some(-1.0),
+            Some(f64::NAN),
+        ]);
+        vec![
+            SortColumn {
+                values: Arc::new(a),
+                options: Some(SortOptions {
+                    descending: false,
+                    nulls_first: true,
+                }),
+            },
+            SortColumn {
+                values: Arc::new(b),
+                options: Some(SortOptions {
+                    descending: true,
+                    nulls_first: false,
+                }),
+            },
+        ]
+    }
+
+    #[test]
+    fn matches_lexsort() -> Result<()> {
+        let columns = columns();
+        for fetch in [None, Some(0), Some(2), Some(6), Some(10)] {
+            let expected = arrow::compute::lexsort_to_indices(&columns, fetch)?;
+            assert_eq!(lexsort_to_indices(&columns, fetch)?, expected, "{fetch:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sort_multiple_columns() -> Result<()> {
+        let columns = vec![
+            SortColumn {
+                values: Arc::new(StringArray::from(vec!["x", "y", "x", "y"])),
+                options: None,
+            },
+            SortColumn {
+                values: Arc::new(Int32Array::from(vec![2, 1, 1, 0])),
+                options: None,
+            },
+        ];
+        let indices = lexsort_to_indices(&columns, None)?;
+        assert_eq!(indices, UInt32Array::from(vec![2, 0, 3, 1]));
+        Ok(())
+    }
+
+    #[test]
+    fn sort_nested_columns() -> Result<()> {
+        // {1, b}, {NULL, a}, {1, a}, NULL, {0, z}
+        let structs: ArrayRef = Arc::new(StructArray::from((
+            vec![
+                (
+                    Field::new("x", DataType::Int32, true),
+                    Arc::new(Int32Array::from(vec![
+                        Some(1),
+                        None,
+                        Some(1),
+                        Some(0),
+                        Some(0),
+                    ])) as ArrayRef,
+                ),
+                (
+                    Field::new("y", DataType::Utf8, true),
+                    Arc::new(StringArray::from(vec!["b", "a", "a", "", "z"])) as ArrayRef,
+                ),
+            ],
+            arrow::buffer::Buffer::from([0b00010111u8]),
+        )));
+        let column = |options| SortColumn {
+            values: Arc::clone(&structs),
+            options: Some(options),
+        };
+
+        let indices = lexsort_to_indices(&[column(SortOptions::default())], None)?;
+        assert_eq!(indices, UInt32Array::from(vec![3, 4, 2, 0, 1]));
+
+        let options = SortOptions {
+            descending: true,
+            nulls_first: false,
+        };
+        let indices = lexsort_to_indices(&[column(options)], Some(2))?;
+        assert_eq!(indices, UInt32Array::from(vec![1, 0]));
+
+        // [1], [0, 1], [1], [] with a tiebreaker
+        let lists = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1)]),
+            Some(vec![Some(0), Some(1)]),
+            Some(vec![Some(1)]),
+            Some(vec![]),
+        ]);
+        let columns = vec![
+            SortColumn {
+                values: Arc::new(lists),
+                options: None,
+            },
+            SortColumn {
+                values: Arc::new(Int32Array::from(vec![2, 0, 1, 0])),
+                options: None,
+            },
+        ];
+        let indices = lexsort_to_indices(&columns, None)?;
+        assert_eq!(indices, UInt32Array::from(vec![3, 1, 2, 0]));
+        Ok(())
+    }
+}
@@ -27,9 +27,11 @@ use crate::execution::memory_pool::{
 use crate::execution::runtime_env::RuntimeEnv;
 use crate::physical_plan::common::{batch_byte_size, IPCWriter, SizedRecordBatchStream};
 use crate::physical_plan::expressions::PhysicalSortExpr;
+use crate::physical_plan::limit::{limit_statistics, local_limit_statistics};
 use crate::physical_plan::metrics::{
     BaselineMetrics, CompositeMetricsSet, MemTrackingMetrics, MetricsSet,
 };
+use crate::physical_plan::sorts::row_sort::lexsort_to_indices;
 use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeStream;
 use crate::physical_plan::sorts::SortedStream;
 use crate::physical_plan::stream::{RecordBatchReceiverStream, RecordBatchStreamAdapter};
@@ -40,7 +42,7 @@ use crate::physical_plan::{
 use crate::prelude::SessionConfig;
 use arrow::array::{make_array, Array, ArrayRef, MutableArrayData};
 pub use arrow::compute::SortOptions;
-use arrow::compute::{concat, lexsort_to_indices, take, SortColumn, TakeOptions};
+use arrow::compute::{concat, take, SortColumn, TakeOptions};
 use arrow::datatypes::SchemaRef;
 use arrow::error::{ArrowError, Result as ArrowResult};
 use arrow::ipc::reader::FileReader;
@@ -70,6 +72,8 @@ use tokio::task;
 /// 2.2 if the memory threshold is reached, sort all buffered batches and spill to file.
 ///     buffer the batch in memory, go to 1.
 /// 3. when input is exhausted, merge all in memory batches and spills to get a total order.
+/// 3.1 if there are too many spills to merge them at once, merge the oldest spills into a
+///     new spill until there are few enough, see [`ExternalSorter::merge_fan_in`].
 struct ExternalSorter {
     schema: SchemaRef,
     in_mem_batches: Vec<BatchWithSortArray>,
@@ -82,6 +86,12 @@ struct ExternalSorter {
     metrics: BaselineMetrics,
     fetch: Option<usize>,
     reservation: MemoryReservation,
+    /// Reserves the memory of the batches buffered by the streams of the spills
+    /// merged into a new spill
+    merge_reservation: MemoryReservation,
+    /// The size of the largest batch inserted, to estimate the memory used by the
+    /// stream of a spill
+    max_batch_size: usize,
     partition_id: usize,
 }
 
@@ -100,6 +110,9 @@ impl ExternalSorter {
         let reservation = MemoryConsumer::new(format!("ExternalSorter[{partition_id}]"))
             .with_can_spill(true)
             .register(&runtime.memory_pool);
+        let merge_reservation =
+            MemoryConsumer::new(format!("ExternalSorterMerge[{partition_id}]"))
+                .register(&runtime.memory_pool);
 
         Self {
             schema,
@@ -112,6 +125,8 @@ impl ExternalSorter {
             metrics,
             fetch,
             reservation,
+            merge_reservation,
+            max_batch_size: 0,
             partition_id,
         }
     }
@@ -123,6 +138,7 @@ impl ExternalSorter {
     ) -> Result<()> {
         if input.num_rows() > 0 {
             let size = batch_byte_size(&input);
+            self.max_batch_size = self.max_batch_size.max(size);
             if self.reservation.try_grow(size).is_err() {
                 self.spill().await?;
                 self.reservation.try_grow(size)?
@@ -165,10 +181,11 @@ impl ExternalSorter {
     }
 
     /// MergeSort in mem batches as well as spills into total order with `SortPreservingMergeStream`.
-    fn sort(&mut self) -> Result<SendableRecordBatchStream> {
+    async fn sort(&mut self) -> Result<SendableRecordBatchStream> {
         let batch_size = self.session_config.batch_size();
 
         if self.spilled_before() {
+            self.merge_spills().await?;
             let tracking_metrics = self
                 .metrics_set
                 .new_intermediate_tracking(self.partition_id, &self.runtime.memory_pool);
@@ -220,6 +237,76 @@ impl ExternalSorter {
         }
     }
 
+    /// Returns how many sorted runs can be merged at once: at most
+    /// `sort_spill_merge_fan_in`, and at most as many as the memory pool can
+    /// buffer batches of, but at least 2. The memory is reserved by
+    /// `merge_reservation`.
+    fn merge_fan_in(&mut self) -> usize {
+        // the stream of a spill buffers the batches sent by the task reading
+        // the file, and the batch being merged
+        let stream_size = 3 * self.max_batch_size;
+        let mut fan_in = self
+            .session_config
+            .config_options()
+            .execution
+            .sort_spill_merge_fan_in
+            .max(2);
+        while fan_in > 2
+            && self
+                .merge_reservation
+                .try_resize(fan_in * stream_size)
+                .is_err()
+        {
+            fan_in /= 2;
+        }
+        fan_in.max(2)
+    }
+
+    /// Merges the oldest spills into a new spill, until the spills and the in
+    /// memory batches can be merged at once
+    async fn merge_spills(&mut self) -> Result<()> {
+        let in_mem_runs = usize::from(!self.in_mem_batches.is_empty());
+        let mut fan_in = self.merge_fan_in();
+        while self.spills.len() + in_mem_runs > fan_in {
+            debug!(
+                "Merging {} of {} spills of ExternalSorter[{}]",
+                fan_in,
+                self.spills.len(),
+                self.partition_id
+            );
+            let streams = self
+                .spills
+                .drain(..fan_in)
+                .map(|spill| {
+                    let stream = read_spill_as_stream(spill, self.schema.clone())?;
+                    Ok(SortedStream::new(stream, 0))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let tracking_metrics = self
+                .metrics_set
+                .new_intermediate_tracking(self.partition_id, &self.runtime.memory_pool);
+            let mut merged: SendableRecordBatchStream =
+                Box::pin(SortPreservingMergeStream::new_from_streams(
+                    streams,
+                    self.schema.clone(),
+                    &self.expr,
+                    tracking_metrics,
+                    self.session_config.batch_size(),
+                )?);
+            let spillfile = self.runtime.disk_manager.create_tmp_file("Sorting")?;
+            spill_partial_sorted_stream(
+                &mut merged,
+                spillfile.path(),
+                self.schema.clone(),
+            )
+            .await?;
+            self.spills.push(spillfile);
+            fan_in = self.merge_fan_in();
+        }
+        self.merge_reservation.free();
+        Ok(())
+    }
+
     fn used(&self) -> usize {
         self.metrics.mem_used().value()
     }
@@ -551,7 +638,7 @@ impl RecordBatchStream for SortedSizedRecordBatchStream {
     }
 }
 
-async fn spill_partial_sorted_stream(
+pub(crate) async fn spill_partial_sorted_stream(
     in_mem_stream: &mut SendableRecordBatchStream,
     path: &Path,
     schema: SchemaRef,
@@ -610,7 +697,10 @@ fn write_sorted(
     Ok(())
 }
 
-fn read_spill(sender: Sender<ArrowResult<RecordBatch>>, path: &Path) -> Result<()> {
+pub(crate) fn read_spill(
+    sender: Sender<ArrowResult<RecordBatch>>,
+    path: &Path,
+) -> Result<()> {
     let file = BufReader::new(File::open(path)?);
     let reader = FileReader::try_new(file, None)?;
     for batch in reader {
@@ -804,7 +894,22 @@ impl ExecutionPlan for SortExec {
     }
 
     fn statistics(&self) -> Statistics {
-        self.input.statistics()
+        let input_stats = self.input.statistics();
+        match self.fetch {
+            Some(fetch) => local_limit_statistics(
+                input_stats,
+                fetch,
+                self.output_partitioning().partition_count(),
+            ),
+            None => input_stats,
+        }
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        if !self.preserve_partitioning {
+            return self.statistics();
+        }
+        limit_statistics(self.input.partition_statistics(partition), 0, self.fetch)
     }
 }
 
@@ -895,7 +1000,7 @@ async fn do_sort(
         let batch = batch?;
         sorter.insert_batch(batch, &tracking_metrics).await?;
     }
-    let result = sorter.sort();
+    let result = sorter.sort().await;
     debug!(
         "End do_sort for partition {} of context session_id {} and task_id {:?}",
         partition_id,
@@ -1051,6 +1156,57 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sort_spill_cascaded_merge() -> Result<()> {
+        let sort = |session_ctx: SessionContext| async move {
+            let csv = test::scan_partitioned_csv(4)?;
+            let schema = csv.schema();
+            let sort_exec = Arc::new(SortExec::try_new(
+                vec![
+                    PhysicalSortExpr {
+                        expr: col("c1", &schema)?,
+                        options: SortOptions::default(),
+                    },
+                    PhysicalSortExpr {
+                        expr: col("c7", &schema)?,
+                        options: SortOptions::default(),
+                    },
+                    PhysicalSortExpr {
+                        expr: col("c9", &schema)?,
+                        options: SortOptions::default(),
+                    },
+                ],
+                Arc::new(CoalescePartitionsExec::new(csv)),
+                None,
+            )?);
+            let result = collect(sort_exec.clone(), session_ctx.task_ctx()).await?;
+            assert_eq!(
+                session_ctx.runtime_env().memory_pool.reserved(),
+                0,
+                "The sort should have returned all memory used back to the memory manager"
+            );
+            Ok::<_, DataFusionError>((result, sort_exec.metrics().unwrap()))
+        };
+
+        let (expected, _) = sort(SessionContext::new()).await?;
+
+        // spill every batch, and merge the spills two at a time
+        let config = RuntimeConfig::new().with_memory_limit(6000, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(config)?);
+        let session_config = SessionConfig::new()
+            .set_usize("datafusion.execution.sort_spill_merge_fan_in", 2);
+        let (result, metrics) =
+            sort(SessionContext::with_config_rt(session_config, runtime)).await?;
+
+        assert!(metrics.spill_count().unwrap() > 2);
+        assert_eq!(metrics.output_rows().unwrap(), 100);
+        assert_eq!(
+            arrow::util::pretty::pretty_format_batches(&result)?.to_string(),
+            arrow::util::pretty::pretty_format_batches(&expected)?.to_string(),
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sort_fetch_memory_calculation() -> Result<()> {
         // This test mirrors down the size from the example above.
@@ -27,7 +27,7 @@ use arrow::error::ArrowError;
 use arrow::row::{RowConverter, SortField};
 use arrow::{
     array::{make_array as make_arrow_array, MutableArrayData},
-    datatypes::SchemaRef,
+    datatypes::{DataType, SchemaRef},
     error::Result as ArrowResult,
     record_batch::RecordBatch,
 };
@@ -38,9 +38,11 @@ use tokio::sync::mpsc;
 
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::TaskContext;
+use crate::physical_plan::limit::{limit_statistics, LimitStream};
 use crate::physical_plan::metrics::{
-    ExecutionPlanMetricsSet, MemTrackingMetrics, MetricsSet,
+    BaselineMetrics, ExecutionPlanMetricsSet, MemTrackingMetrics, MetricsSet,
 };
+use crate::physical_plan::sorts::row_sort::sort_key;
 use crate::physical_plan::sorts::{RowIndex, SortKeyCursor, SortedStream};
 use crate::physical_plan::stream::RecordBatchReceiverStream;
 use crate::physical_plan::{
@@ -48,6 +50,7 @@ use crate::physical_plan::{
     Distribution, ExecutionPlan, Partitioning, PhysicalExpr, RecordBatchStream,
     SendableRecordBatchStream, Statistics,
 };
+use datafusion_physical_expr::sort_key::is_nested;
 use datafusion_physical_expr::EquivalenceProperties;
 
 /// Sort preserving merge execution plan
@@ -85,6 +88,10 @@ pub struct SortPreservingMergeExec {
     expr: Vec<PhysicalSortExpr>,
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
+    /// Number of merged rows to skip before fetch
+    skip: usize,
+    /// Maximum number of merged rows to produce, or `None` for all of them
+    fetch: Option<usize>,
 }
 
 impl SortPreservingMergeExec {
@@ -94,9 +101,24 @@ impl SortPreservingMergeExec {
             input,
             expr,
             metrics: ExecutionPlanMetricsSet::new(),
+            skip: 0,
+            fetch: None,
         }
     }
 
+    /// Skips the first `skip` merged rows. The skipped rows are only
+    /// compared, and are never copied to an output batch
+    pub fn with_skip(mut self, skip: usize) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    /// Stops merging once `fetch` rows, after the skipped ones, are produced
+    pub fn with_fetch(mut self, fetch: Option<usize>) -> Self {
+        self.fetch = fetch;
+        self
+    }
+
     /// Input schema
     pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
         &self.input
@@ -106,6 +128,16 @@ impl SortPreservingMergeExec {
     pub fn expr(&self) -> &[PhysicalSortExpr] {
         &self.expr
     }
+
+    /// Number of merged rows to skip before fetch
+    pub fn skip(&self) -> usize {
+        self.skip
+    }
+
+    /// If `Some(fetch)`, limits output to only the first "fetch" rows after the skipped ones
+    pub fn fetch(&self) -> Option<usize> {
+        self.fetch
+    }
 }
 
 impl ExecutionPlan for SortPreservingMergeExec {
@@ -147,10 +179,11 @@ impl ExecutionPlan for SortPreservingMergeExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(SortPreservingMergeExec::new(
-            self.expr.clone(),
-            children[0].clone(),
-        )))
+        Ok(Arc::new(
+            SortPreservingMergeExec::new(self.expr.clone(), children[0].clone())
+                .with_skip(self.skip)
+                .with_fetch(self.fetch),
+        ))
     }
 
     fn execute(
@@ -185,9 +218,17 @@ impl ExecutionPlan for SortPreservingMergeExec {
             )),
             1 => {
                 // bypass if there is only one partition to merge (no metrics in this case either)
-                let result = self.input.execute(0, context);
+                let result = self.input.execute(0, context)?;
                 debug!("Done getting stream for SortPreservingMergeExec::execute with 1 input");
-                result
+                if self.skip == 0 && self.fetch.is_none() {
+                    return Ok(result);
+                }
+                Ok(Box::pin(LimitStream::new(
+                    result,
+                    self.skip,
+                    self.fetch,
+                    BaselineMetrics::new(&self.metrics, partition),
+                )))
             }
             _ => {
                 // Use tokio only if running from a tokio context (#2201)
@@ -224,13 +265,16 @@ impl ExecutionPlan for SortPreservingMergeExec {
 
                 debug!("Done setting up sender-receiver for SortPreservingMergeExec::execute");
 
-                let result = Box::pin(SortPreservingMergeStream::new_from_streams(
-                    receivers,
-                    schema,
-                    &self.expr,
-                    tracking_metrics,
-                    context.session_config().batch_size(),
-                )?);
+                let result = Box::pin(
+                    SortPreservingMergeStream::new_from_streams(
+                        receivers,
+                        schema,
+                        &self.expr,
+                        tracking_metrics,
+                        context.session_config().batch_size(),
+                    )?
+                    .with_limit(self.skip, self.fetch),
+                );
 
                 debug!("Got stream result from SortPreservingMergeStream::new_from_receivers");
 
@@ -247,7 +291,16 @@ impl ExecutionPlan for SortPreservingMergeExec {
         match t {
             DisplayFormatType::Default => {
                 let expr: Vec<String> = self.expr.iter().map(|e| e.to_string()).collect();
-                write!(f, "SortPreservingMergeExec: [{}]", expr.join(","))
+                write!(f, "SortPreservingMergeExec: [{}]", expr.join(","))?;
+                if self.skip > 0 || self.fetch.is_some() {
+                    write!(
+                        f,
+                        ", skip={}, fetch={}",
+                        self.skip,
+                        self.fetch.map_or("None".to_string(), |x| x.to_string())
+                    )?;
+                }
+                Ok(())
             }
         }
     }
@@ -257,7 +310,7 @@ impl ExecutionPlan for SortPreservingMergeExec {
     }
 
     fn statistics(&self) -> Statistics {
-        self.input.statistics()
+        limit_statistics(self.input.statistics(), self.skip, self.fetch)
     }
 }
 
@@ -346,6 +399,12 @@ pub(crate) struct SortPreservingMergeStream {
 
     /// row converter
     row_converter: RowConverter,
+
+    /// The remaining number of merged rows to skip
+    skip: usize,
+
+    /// The remaining number of rows to produce, if limited
+    fetch: Option<usize>,
 }
 
 impl SortPreservingMergeStream {
@@ -367,7 +426,11 @@ impl SortPreservingMergeStream {
         let sort_fields = expressions
             .iter()
             .map(|expr| {
-                let data_type = expr.expr.data_type(&schema)?;
+                let mut data_type = expr.expr.data_type(&schema)?;
+                if is_nested(&data_type) {
+                    // structs and lists are converted to their binary keys
+                    data_type = DataType::Binary;
+                }
                 Ok(SortField::new_with_options(data_type, expr.options))
             })
             .collect::<Result<Vec<_>>>()?;
@@ -387,9 +450,19 @@ impl SortPreservingMergeStream {
             loser_tree_adjusted: false,
             batch_size,
             row_converter,
+            skip: 0,
+            fetch: None,
         })
     }
 
+    /// Skips the first `skip` merged rows, and ends the stream once `fetch`
+    /// rows are produced after them
+    pub(crate) fn with_limit(mut self, skip: usize, fetch: Option<usize>) -> Self {
+        self.skip = skip;
+        self.fetch = fetch;
+        self
+    }
+
     /// If the stream at the given index is not exhausted, and the last cursor for the
     /// stream is finished, poll the stream for the next RecordBatch and create a new
     /// cursor for the stream from the returned result
@@ -425,7 +498,9 @@ impl SortPreservingMergeStream {
                             .column_expressions
                             .iter()
                             .map(|expr| {
-                                Ok(expr.evaluate(&batch)?.into_array(batch.num_rows()))
+                                sort_key(
+                                    &expr.evaluate(&batch)?.into_array(batch.num_rows()),
+                                )
                             })
                             .collect::<Result<Vec<_>>>()?;
 
@@ -444,6 +519,11 @@ impl SortPreservingMergeStream {
                             rows,
                         ));
                         self.next_batch_id += 1;
+                        // the batches of the stream can only be referenced by
+                        // the rows in progress, which don't include the skipped ones
+                        if self.in_progress.is_empty() {
+                            self.batches[idx].clear();
+                        }
                         self.batches[idx].push_back(batch)
                     } else {
                         empty_batch = true;
@@ -527,6 +607,9 @@ impl SortPreservingMergeStream {
             })
             .collect();
 
+        if let Some(fetch) = self.fetch.as_mut() {
+            *fetch -= self.in_progress.len();
+        }
         self.in_progress.clear();
 
         // New cursors are only created once the previous cursor for the stream
@@ -566,7 +649,7 @@ impl SortPreservingMergeStream {
         self: &mut Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<ArrowResult<RecordBatch>>> {
-        if self.aborted {
+        if self.aborted || self.fetch == Some(0) {
             return Poll::Ready(None);
         }
         // try to initialize the loser tree
@@ -593,13 +676,19 @@ impl SortPreservingMergeStream {
 
             if let Some((stream_idx, row_idx)) = next {
                 self.loser_tree_adjusted = false;
+                if self.skip > 0 {
+                    self.skip -= 1;
+                    continue;
+                }
                 let batch_idx = self.batches[stream_idx].len() - 1;
                 self.in_progress.push(RowIndex {
                     stream_idx,
                     batch_idx,
                     row_idx,
                 });
-                if self.in_progress.len() == self.batch_size {
+                if self.in_progress.len() == self.batch_size
+                    || Some(self.in_progress.len()) == self.fetch
+                {
                     return Poll::Ready(Some(self.build_record_batch()));
                 }
             } else if !self.in_progress.is_empty() {
@@ -1497,4 +1586,54 @@ mod tests {
             collected.as_slice()
         );
     }
+
+    #[tokio::test]
+    async fn test_merge_skip_fetch() -> Result<()> {
+        let config = SessionConfig::new().with_batch_size(2);
+        let session_ctx = SessionContext::with_config(config);
+        let task_ctx = session_ctx.task_ctx();
+        let batch = |values: &[i32]| {
+            RecordBatch::try_from_iter(vec![(
+                "a",
+                Arc::new(Int32Array::from_slice(values)) as ArrayRef,
+            )])
+            .unwrap()
+        };
+        let partitions = vec![
+            vec![batch(&[1, 3, 5]), batch(&[7, 9])],
+            vec![batch(&[2, 4]), batch(&[6, 8, 10])],
+        ];
+        let schema = partitions[0][0].schema();
+        let sort = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: Default::default(),
+        }];
+
+        let exec = MemoryExec::try_new(&partitions, schema.clone(), None)?;
+        let merge = Arc::new(
+            SortPreservingMergeExec::new(sort.clone(), Arc::new(exec))
+                .with_skip(3)
+                .with_fetch(Some(4)),
+        );
+        let collected = collect(merge.clone(), task_ctx.clone()).await?;
+        assert_batches_eq!(
+            &["+---+", "| a |", "+---+", "| 4 |", "| 5 |", "| 6 |", "| 7 |", "+---+",],
+            collected.as_slice()
+        );
+        assert_eq!(merge.metrics().unwrap().output_rows(), Some(4));
+
+        // a single partition is only limited
+        let exec = MemoryExec::try_new(&partitions[..1], schema, None)?;
+        let merge = Arc::new(
+            SortPreservingMergeExec::new(sort, Arc::new(exec))
+                .with_skip(1)
+                .with_fetch(Some(2)),
+        );
+        let collected = collect(merge, task_ctx).await?;
+        assert_batches_eq!(
+            &["+---+", "| a |", "+---+", "| 3 |", "| 5 |", "+---+"],
+            collected.as_slice()
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,380 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Splitting a physical plan into stages at its exchanges, for the runners
+//! executing the stages of a plan on different nodes
+//!
+//! Each exchange of the plan, such as a [`RepartitionExec`], is cut: the plan
+//! under the exchange becomes a [`Stage`] whose output is partitioned as the
+//! output of the exchange, and the exchange is replaced with a
+//! [`StageReaderExec`] placeholder reading the output of the stage. The runner
+//! executes the stages in order, and replaces the placeholders with the
+//! operators reading their outputs with [`replace_stage_readers`].
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+
+use super::expressions::PhysicalSortExpr;
+use super::repartition::RepartitionExec;
+use super::rewrite::TreeNode;
+use super::{
+    with_new_children_if_necessary, DisplayFormatType, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+
+/// A part of a plan executed on its own, between exchanges
+#[derive(Debug, Clone)]
+pub struct Stage {
+    /// The id of the stage, its index in the stages of the plan
+    pub id: usize,
+    /// The plan of the stage, reading the outputs of its input stages with
+    /// [`StageReaderExec`]s
+    pub plan: Arc<dyn ExecutionPlan>,
+    /// The partitioning of the output of the stage: the partitioning of the
+    /// exchange cut, or the partitioning of `plan` for the final stage
+    pub output_partitioning: Partitioning,
+    /// The ids of the stages read by `plan`
+    pub input_stages: Vec<usize>,
+}
+
+/// Splits `plan` into stages at each of its [`RepartitionExec`]s, see
+/// [`split_into_stages_at`]
+pub fn split_into_stages(plan: Arc<dyn ExecutionPlan>) -> Result<Vec<Stage>> {
+    split_into_stages_at(plan, &|plan| plan.as_any().is::<RepartitionExec>())
+}
+
+/// Splits `plan` into stages at each of the exchanges for which `is_exchange`
+/// returns true. An exchange must have a single input, and output the rows of
+/// its input partitioned by its output partitioning.
+///
+/// The stages are returned in an order in which they can be executed: each
+/// stage comes after its input stages, and the last stage computes the results
+/// of `plan`.
+pub fn split_into_stages_at(
+    plan: Arc<dyn ExecutionPlan>,
+    is_exchange: &dyn Fn(&dyn ExecutionPlan) -> bool,
+) -> Result<Vec<Stage>> {
+    let mut stages = vec![];
+    let mut input_stages = vec![];
+    let output_partitioning = plan.output_partitioning();
+    let plan = cut_exchanges(plan, is_exchange, &mut stages, &mut input_stages)?;
+    stages.push(Stage {
+        id: stages.len(),
+        plan,
+        output_partitioning,
+        input_stages,
+    });
+    Ok(stages)
+}
+
+/// Replaces the exchanges of `plan` with stage readers, pushing the stages
+/// under them to `stages` and their ids to `input_stages`
+fn cut_exchanges(
+    plan: Arc<dyn ExecutionPlan>,
+    is_exchange: &dyn Fn(&dyn ExecutionPlan) -> bool,
+    stages: &mut Vec<Stage>,
+    input_stages: &mut Vec<usize>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let children = plan.children();
+    if is_exchange(plan.as_ref()) {
+        let input = match children.as_slice() {
+            [input] => input.clone(),
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "An exchange must have a single input, got {} in {plan:?}",
+                    children.len()
+                )))
+            }
+        };
+        let mut stage_inputs = vec![];
+        let stage_plan = cut_exchanges(input, is_exchange, stages, &mut stage_inputs)?;
+        let id = stages.len();
+        let output_partitioning = plan.output_partitioning();
+        stages.push(Stage {
+            id,
+            plan: stage_plan,
+            output_partitioning: output_partitioning.clone(),
+            input_stages: stage_inputs,
+        });
+        input_stages.push(id);
+        return Ok(Arc::new(StageReaderExec::new(
+            id,
+            plan.schema(),
+            output_partitioning,
+        )));
+    }
+
+    if children.is_empty() {
+        return Ok(plan);
+    }
+    let children = children
+        .into_iter()
+        .map(|child| cut_exchanges(child, is_exchange, stages, input_stages))
+        .collect::<Result<Vec<_>>>()?;
+    with_new_children_if_necessary(plan, children)
+}
+
+/// Replaces the subtree `subtree` of `plan`, compared by pointer, with a
+/// [`StageReaderExec`] reading the output of the stage `stage_id`, partitioned
+/// as the output of `subtree`
+pub fn replace_with_stage_reader(
+    plan: Arc<dyn ExecutionPlan>,
+    subtree: &Arc<dyn ExecutionPlan>,
+    stage_id: usize,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_down(&|plan| {
+        Ok(if Arc::ptr_eq(&plan, subtree) {
+            Some(Arc::new(StageReaderExec::new(
+                stage_id,
+                plan.schema(),
+                plan.output_partitioning(),
+            )) as Arc<dyn ExecutionPlan>)
+        } else {
+            None
+        })
+    })
+}
+
+/// Replaces each [`StageReaderExec`] of `plan` with the operator returned by
+/// `reader`, which reads the output of its stage
+pub fn replace_stage_readers(
+    plan: Arc<dyn ExecutionPlan>,
+    reader: &dyn Fn(&StageReaderExec) -> Result<Arc<dyn ExecutionPlan>>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_up(
+        &|plan| match plan.as_any().downcast_ref::<StageReaderExec>() {
+            Some(stage_reader) => reader(stage_reader).map(Some),
+            None => Ok(None),
+        },
+    )
+}
+
+/// Placeholder reading the output of a stage, to be replaced by the runner
+/// before executing the plan, see [`replace_stage_readers`]
+#[derive(Debug)]
+pub struct StageReaderExec {
+    stage_id: usize,
+    schema: SchemaRef,
+    partitioning: Partitioning,
+}
+
+impl StageReaderExec {
+    /// Create a placeholder reading the output of the stage `stage_id`, of
+    /// `schema` and partitioned by `partitioning`
+    pub fn new(stage_id: usize, schema: SchemaRef, partitioning: Partitioning) -> Self {
+        Self {
+            stage_id,
+            schema,
+            partitioning,
+        }
+    }
+
+    /// The id of the stage read
+    pub fn stage_id(&self) -> usize {
+        self.stage_id
+    }
+
+    /// The partitioning of the output of the stage
+    pub fn partitioning(&self) -> &Partitioning {
+        &self.partitioning
+    }
+}
+
+impl ExecutionPlan for StageReaderExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.partitioning.clone()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        _partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        Err(DataFusionError::Internal(format!(
+            "The reader of stage {} must be replaced before execution",
+            self.stage_id
+        )))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => write!(
+                f,
+                "StageReaderExec: stage={}, partitioning={:?}",
+                self.stage_id, self.partitioning
+            ),
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::new_unknown(&self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::datasource::MemTable;
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::{collect, collect_partitioned, displayable};
+    use crate::prelude::{SessionConfig, SessionContext};
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use std::collections::HashMap;
+
+    fn context() -> Result<SessionContext> {
+        let ctx =
+            SessionContext::with_config(SessionConfig::new().with_target_partitions(4));
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Utf8, false),
+            Field::new("v", DataType::Int32, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from_slice(["a", "b", "a", "c"])),
+                Arc::new(Int32Array::from_slice([1, 2, 3, 4])),
+            ],
+        )?;
+        let table = MemTable::try_new(schema, vec![vec![batch.clone()], vec![batch]])?;
+        ctx.register_table("t", Arc::new(table))?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn split_and_run_stages() -> Result<()> {
+        let ctx = context()?;
+        let plan = ctx
+            .sql("SELECT k, SUM(v) AS s FROM t GROUP BY k")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let repartitions = displayable(plan.as_ref())
+            .indent()
+            .to_string()
+            .matches("RepartitionExec")
+            .count();
+        assert!(repartitions > 0);
+
+        let stages = split_into_stages(plan)?;
+        assert_eq!(stages.len(), repartitions + 1);
+        for stage in &stages {
+            let plan = displayable(stage.plan.as_ref()).indent().to_string();
+            assert!(!plan.contains("RepartitionExec"), "{plan}");
+            assert_eq!(
+                plan.matches("StageReaderExec").count(),
+                stage.input_stages.len()
+            );
+            assert!(stage.input_stages.iter().all(|id| *id < stage.id));
+        }
+
+        // run the stages in order, shuffling their outputs in memory
+        let task_ctx = ctx.task_ctx();
+        let mut outputs: HashMap<usize, Vec<Vec<RecordBatch>>> = HashMap::new();
+        let mut results = vec![];
+        for stage in &stages {
+            let plan = replace_stage_readers(stage.plan.clone(), &|reader| {
+                let partitions = &outputs[&reader.stage_id()];
+                Ok(Arc::new(MemoryExec::try_new(
+                    partitions,
+                    reader.schema(),
+                    None,
+                )?))
+            })?;
+            if stage.id == stages.len() - 1 {
+                results = collect(plan, task_ctx.clone()).await?;
+            } else {
+                let shuffle =
+                    RepartitionExec::try_new(plan, stage.output_partitioning.clone())?;
+                let output =
+                    collect_partitioned(Arc::new(shuffle), task_ctx.clone()).await?;
+                outputs.insert(stage.id, output);
+            }
+        }
+
+        let expected = vec![
+            "+---+---+",
+            "| k | s |",
+            "+---+---+",
+            "| a | 8 |",
+            "| b | 4 |",
+            "| c | 8 |",
+            "+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &results);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replace_subtree() -> Result<()> {
+        let ctx = context()?;
+        let plan = ctx
+            .sql("SELECT k FROM t WHERE v > 1")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let subtree = plan.children()[0].clone();
+
+        let plan = replace_with_stage_reader(plan, &subtree, 7)?;
+        let reader = plan.children()[0].clone();
+        let reader = reader.as_any().downcast_ref::<StageReaderExec>().unwrap();
+        assert_eq!(reader.stage_id(), 7);
+        assert_eq!(reader.schema(), subtree.schema());
+        assert_eq!(
+            reader.partitioning().partition_count(),
+            subtree.output_partitioning().partition_count()
+        );
+
+        let err = collect(plan, ctx.task_ctx()).await.unwrap_err();
+        assert!(err.to_string().contains("must be replaced"), "{err}");
+        Ok(())
+    }
+}
@@ -118,6 +118,6 @@ impl ExecutionPlan for StreamingTableExec {
     }
 
     fn statistics(&self) -> Statistics {
-        Default::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
@@ -0,0 +1,429 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SubqueryExec executes the uncorrelated subqueries of the expressions of
+//! its input once per [`TaskContext`], and binds their results to the
+//! expressions before executing the input.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::physical_plan::expressions::{
+    bind_subquery_results, SubqueryId, SubqueryResult, SubqueryResults,
+};
+use crate::physical_plan::filter::FilterExec;
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::{
+    collect, with_new_children_if_necessary, DisplayFormatType, EquivalenceProperties,
+    ExecutionPlan, Partitioning, SendableRecordBatchStream,
+};
+use arrow::datatypes::SchemaRef;
+use arrow::error::ArrowError;
+use futures::future::{poll_fn, try_join_all};
+use futures::stream::{self, TryStreamExt};
+use itertools::Itertools;
+
+use super::expressions::PhysicalSortExpr;
+use super::Statistics;
+
+/// How the results of a subquery are read by the expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubqueryKind {
+    /// A scalar subquery, returning at most one row
+    Scalar,
+    /// The subquery of an `IN`, whose results are a set of values
+    In,
+}
+
+/// The plan of an uncorrelated subquery of the expressions of a
+/// [`SubqueryExec`]
+#[derive(Debug, Clone)]
+pub struct SubqueryPlan {
+    /// Identifies the subquery in the expressions
+    pub id: SubqueryId,
+    /// How the results of the subquery are read
+    pub kind: SubqueryKind,
+    /// The plan of the subquery, returning a single column
+    pub plan: Arc<dyn ExecutionPlan>,
+}
+
+/// SubqueryExec executes its subqueries, then its input with their results
+/// bound to the filters and projections of the input.
+///
+/// The subqueries are executed once per [`TaskContext`], with all their
+/// partitions collected in memory, and their results are shared by all the
+/// partitions of the input.
+#[derive(Debug)]
+pub struct SubqueryExec {
+    /// The plan whose expressions read the results of the subqueries
+    input: Arc<dyn ExecutionPlan>,
+    /// The uncorrelated subqueries of the expressions of the input
+    subqueries: Vec<SubqueryPlan>,
+}
+
+impl SubqueryExec {
+    /// Create a new SubqueryExec, executing `subqueries` before `input`
+    pub fn new(input: Arc<dyn ExecutionPlan>, subqueries: Vec<SubqueryPlan>) -> Self {
+        Self { input, subqueries }
+    }
+
+    /// The plan whose expressions read the results of the subqueries
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The uncorrelated subqueries of the expressions of the input
+    pub fn subqueries(&self) -> &[SubqueryPlan] {
+        &self.subqueries
+    }
+}
+
+impl ExecutionPlan for SubqueryExec {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    /// The input, followed by the plans of the subqueries
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        std::iter::once(self.input.clone())
+            .chain(self.subqueries.iter().map(|subquery| subquery.plan.clone()))
+            .collect()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn unbounded_output(&self, children: &[bool]) -> Result<bool> {
+        if children[1..].iter().any(|unbounded| *unbounded) {
+            return Err(DataFusionError::Plan(
+                "The results of a subquery can't be an unbounded stream".to_string(),
+            ));
+        }
+        Ok(children[0])
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> bool {
+        true
+    }
+
+    /// The input is bound for each partition, so it is repartitioned below
+    /// the filters and projections of the input, rather than above them
+    fn benefits_from_input_partitioning(&self) -> bool {
+        false
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.input.equivalence_properties()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if children.len() != self.subqueries.len() + 1 {
+            return Err(DataFusionError::Internal(
+                "SubqueryExec wrong number of children".to_string(),
+            ));
+        }
+        let mut children = children.into_iter();
+        let input = children.next().unwrap();
+        let subqueries = self
+            .subqueries
+            .iter()
+            .zip(children)
+            .map(|(subquery, plan)| SubqueryPlan {
+                plan,
+                ..subquery.clone()
+            })
+            .collect();
+        Ok(Arc::new(SubqueryExec::new(input, subqueries)))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let results = self
+            .subqueries
+            .iter()
+            .map(|subquery| {
+                let fut = context.subquery_results(subquery.id, || {
+                    let SubqueryPlan { kind, plan, .. } = subquery.clone();
+                    let context = context.clone();
+                    async move {
+                        let data_type = plan.schema().field(0).data_type().clone();
+                        let batches = collect(plan, context).await?;
+                        match kind {
+                            SubqueryKind::Scalar => {
+                                SubqueryResult::try_new_scalar(&data_type, &batches)
+                            }
+                            SubqueryKind::In => {
+                                SubqueryResult::try_new_set(&data_type, &batches)
+                            }
+                        }
+                    }
+                });
+                (subquery.id, fut)
+            })
+            .collect::<Vec<_>>();
+
+        let input = self.input.clone();
+        let stream = stream::once(async move {
+            let results =
+                try_join_all(results.into_iter().map(|(id, mut fut)| async move {
+                    let result =
+                        poll_fn(|cx| fut.get(cx).map_ok(SubqueryResult::clone)).await?;
+                    Ok::<_, ArrowError>((id, result))
+                }))
+                .await?;
+            let input = bind_plan(input, &results.into_iter().collect())?;
+            Ok::<_, ArrowError>(input.execute(partition, context)?)
+        })
+        .try_flatten();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                let subqueries = self
+                    .subqueries
+                    .iter()
+                    .map(|subquery| subquery.id.to_string())
+                    .join(", ");
+                write!(f, "SubqueryExec: subqueries=[{subqueries}]")
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        self.input.partition_statistics(partition)
+    }
+}
+
+/// Binds the `results` of the subqueries to the filters and projections of
+/// `plan`, up to the nested [`SubqueryExec`]s, which bind their own
+fn bind_plan(
+    plan: Arc<dyn ExecutionPlan>,
+    results: &SubqueryResults,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let any = plan.as_any();
+    if any.is::<SubqueryExec>() {
+        return Ok(plan);
+    }
+    let bound: Arc<dyn ExecutionPlan> =
+        if let Some(filter) = any.downcast_ref::<FilterExec>() {
+            let predicate = bind_subquery_results(filter.predicate().clone(), results)?;
+            if Arc::ptr_eq(&predicate, filter.predicate()) {
+                plan.clone()
+            } else {
+                Arc::new(FilterExec::try_new(predicate, filter.input().clone())?)
+            }
+        } else if let Some(projection) = any.downcast_ref::<ProjectionExec>() {
+            let exprs = projection
+                .expr()
+                .iter()
+                .map(|(expr, name)| {
+                    Ok((bind_subquery_results(expr.clone(), results)?, name.clone()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if exprs
+                .iter()
+                .zip(projection.expr())
+                .all(|((bound, _), (expr, _))| Arc::ptr_eq(bound, expr))
+            {
+                plan.clone()
+            } else {
+                Arc::new(ProjectionExec::try_new(exprs, projection.input().clone())?)
+            }
+        } else {
+            plan.clone()
+        };
+    let children = bound
+        .children()
+        .into_iter()
+        .map(|child| bind_plan(child, results))
+        .collect::<Result<Vec<_>>>()?;
+    with_new_children_if_necessary(bound, children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::streaming::PartitionStream;
+    use crate::from_slice::FromSlice;
+    use crate::physical_plan::expressions::{
+        binary, col, InSubqueryExpr, ScalarSubqueryExpr,
+    };
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::streaming::StreamingTableExec;
+    use crate::physical_plan::{collect_partitioned, PhysicalExpr};
+    use crate::prelude::SessionContext;
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion_expr::Operator;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A partition of values, counting its executions
+    struct CountingPartition {
+        schema: SchemaRef,
+        batch: RecordBatch,
+        executions: AtomicUsize,
+    }
+
+    impl PartitionStream for CountingPartition {
+        fn schema(&self) -> &SchemaRef {
+            &self.schema
+        }
+
+        fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+            self.executions.fetch_add(1, Ordering::SeqCst);
+            Box::pin(RecordBatchStreamAdapter::new(
+                self.schema.clone(),
+                stream::iter(vec![Ok(self.batch.clone())]),
+            ))
+        }
+    }
+
+    fn batch(name: &str, values: &[i32]) -> RecordBatch {
+        let schema = Schema::new(vec![Field::new(name, DataType::Int32, false)]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(Int32Array::from_slice(values))],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn execute_subqueries_once() -> Result<()> {
+        let values = batch("b", &[2, 3]);
+        let subquery_values = Arc::new(CountingPartition {
+            schema: values.schema(),
+            batch: values,
+            executions: AtomicUsize::new(0),
+        });
+        let subquery_plan = Arc::new(StreamingTableExec::try_new(
+            subquery_values.schema.clone(),
+            vec![subquery_values.clone()],
+            None,
+        )?);
+        let subquery_id = SubqueryId::next();
+
+        // 3 partitions of a filter `a IN (subquery)`
+        let partitions = vec![
+            vec![batch("a", &[1, 2])],
+            vec![batch("a", &[3, 4])],
+            vec![batch("a", &[2])],
+        ];
+        let schema = partitions[0][0].schema();
+        let input = Arc::new(MemoryExec::try_new(&partitions, schema.clone(), None)?);
+        let predicate =
+            Arc::new(InSubqueryExpr::new(col("a", &schema)?, subquery_id, false));
+        let filter = Arc::new(FilterExec::try_new(predicate, input)?);
+        let plan = Arc::new(SubqueryExec::new(
+            filter,
+            vec![SubqueryPlan {
+                id: subquery_id,
+                kind: SubqueryKind::In,
+                plan: subquery_plan,
+            }],
+        ));
+
+        let task_ctx = SessionContext::new().task_ctx();
+        let results = collect_partitioned(plan.clone(), task_ctx.clone()).await?;
+        let values = results
+            .iter()
+            .map(|batches| batches.iter().map(|batch| batch.num_rows()).sum::<usize>())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 1, 1]);
+        assert_eq!(subquery_values.executions.load(Ordering::SeqCst), 1);
+
+        // the results are computed again for another task
+        collect_partitioned(plan, SessionContext::new().task_ctx()).await?;
+        assert_eq!(subquery_values.executions.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scalar_subquery_errors() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let input = Arc::new(MemoryExec::try_new(
+            &[vec![batch("a", &[1, 2])]],
+            schema.clone(),
+            None,
+        )?);
+        let subquery_id = SubqueryId::next();
+        let subquery = Arc::new(ScalarSubqueryExpr::new(subquery_id, DataType::Int32))
+            as Arc<dyn PhysicalExpr>;
+        let expr = binary(col("a", &schema)?, Operator::Plus, subquery, &schema)?;
+        let projection = Arc::new(ProjectionExec::try_new(
+            vec![(expr, "a".to_string())],
+            input,
+        )?);
+        // a scalar subquery returning 2 rows
+        let values = batch("b", &[1, 2]);
+        let subquery_plan = Arc::new(MemoryExec::try_new(
+            &[vec![values.clone()]],
+            values.schema(),
+            None,
+        )?);
+        let plan = Arc::new(SubqueryExec::new(
+            projection,
+            vec![SubqueryPlan {
+                id: subquery_id,
+                kind: SubqueryKind::Scalar,
+                plan: subquery_plan,
+            }],
+        ));
+
+        let err = collect(plan, SessionContext::new().task_ctx())
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("A scalar subquery returned more than one row"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
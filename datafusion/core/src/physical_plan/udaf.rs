@@ -64,6 +64,13 @@ pub struct AggregateFunctionExpr {
     name: String,
 }
 
+impl AggregateFunctionExpr {
+    /// The UDAF this expression evaluates
+    pub fn fun(&self) -> &AggregateUDF {
+        &self.fun
+    }
+}
+
 impl AggregateExpr for AggregateFunctionExpr {
     /// Return a reference to Any that can be used for downcasting
     fn as_any(&self) -> &dyn Any {
@@ -25,7 +25,7 @@ use crate::physical_plan::{
         PhysicalSortExpr, RowNumber,
     },
     type_coercion::coerce,
-    udaf, PhysicalExpr,
+    udaf, ColumnStatistics, PhysicalExpr, Precision, Statistics,
 };
 use crate::scalar::ScalarValue;
 use arrow::datatypes::Schema;
@@ -33,9 +33,7 @@ use datafusion_expr::{
     window_function::{signature_for_built_in, BuiltInWindowFunction, WindowFunction},
     WindowFrame,
 };
-use datafusion_physical_expr::window::{
-    BuiltInWindowFunctionExpr, SlidingAggregateWindowExpr,
-};
+use datafusion_physical_expr::window::BuiltInWindowFunctionExpr;
 use std::convert::TryInto;
 use std::sync::Arc;
 
@@ -44,7 +42,7 @@ mod window_agg_exec;
 
 pub use bounded_window_agg_exec::BoundedWindowAggExec;
 pub use datafusion_physical_expr::window::{
-    AggregateWindowExpr, BuiltInWindowExpr, WindowExpr,
+    AggregateWindowExpr, BuiltInWindowExpr, SlidingAggregateWindowExpr, WindowExpr,
 };
 pub use window_agg_exec::WindowAggExec;
 
@@ -60,8 +58,14 @@ pub fn create_window_expr(
 ) -> Result<Arc<dyn WindowExpr>> {
     Ok(match fun {
         WindowFunction::AggregateFunction(fun) => {
-            let aggregate =
-                aggregates::create_aggregate_expr(fun, false, args, input_schema, name)?;
+            let aggregate = aggregates::create_aggregate_expr(
+                fun,
+                false,
+                args,
+                &[],
+                input_schema,
+                name,
+            )?;
             if !window_frame.start_bound.is_unbounded() {
                 Arc::new(SlidingAggregateWindowExpr::new(
                     aggregate,
@@ -133,8 +137,12 @@ fn create_built_in_window_expr(
                     )
                 })?
                 .try_into()?;
-            let n: u64 = n as u64;
-            Arc::new(Ntile::new(name, n))
+            if n <= 0 {
+                return Err(DataFusionError::Execution(
+                    "NTILE requires a positive number of buckets".to_string(),
+                ));
+            }
+            Arc::new(Ntile::new(name, n as u64))
         }
         BuiltInWindowFunction::Lag => {
             let coerced_args = coerce(args, input_schema, &signature_for_built_in(fun))?;
@@ -187,6 +195,19 @@ fn create_built_in_window_expr(
     })
 }
 
+/// Returns the statistics of the output of a window operator computing
+/// `window_expr_count` window functions over an input with the given statistics
+fn window_statistics(input_stats: Statistics, window_expr_count: usize) -> Statistics {
+    // TODO stats: some windowing function will maintain invariants such as min, max...
+    let mut column_statistics = input_stats.column_statistics;
+    column_statistics.extend(vec![ColumnStatistics::new_unknown(); window_expr_count]);
+    Statistics {
+        num_rows: input_stats.num_rows,
+        column_statistics,
+        total_byte_size: Precision::Absent,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
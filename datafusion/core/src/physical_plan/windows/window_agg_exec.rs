@@ -24,10 +24,10 @@ use crate::physical_plan::expressions::PhysicalSortExpr;
 use crate::physical_plan::metrics::{
     BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet,
 };
+use crate::physical_plan::windows::window_statistics;
 use crate::physical_plan::{
-    ColumnStatistics, DisplayFormatType, Distribution, EquivalenceProperties,
-    ExecutionPlan, Partitioning, PhysicalExpr, RecordBatchStream,
-    SendableRecordBatchStream, Statistics, WindowExpr,
+    DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan, Partitioning,
+    PhysicalExpr, RecordBatchStream, SendableRecordBatchStream, Statistics, WindowExpr,
 };
 use arrow::compute::{
     concat, concat_batches, lexicographical_partition_ranges, SortColumn,
@@ -251,23 +251,14 @@ impl ExecutionPlan for WindowAggExec {
     }
 
     fn statistics(&self) -> Statistics {
-        let input_stat = self.input.statistics();
-        let win_cols = self.window_expr.len();
-        let input_cols = self.input_schema.fields().len();
-        // TODO stats: some windowing function will maintain invariants such as min, max...
-        let mut column_statistics = Vec::with_capacity(win_cols + input_cols);
-        if let Some(input_col_stats) = input_stat.column_statistics {
-            column_statistics.extend(input_col_stats);
-        } else {
-            column_statistics.extend(vec![ColumnStatistics::default(); input_cols]);
-        }
-        column_statistics.extend(vec![ColumnStatistics::default(); win_cols]);
-        Statistics {
-            is_exact: input_stat.is_exact,
-            num_rows: input_stat.num_rows,
-            column_statistics: Some(column_statistics),
-            total_byte_size: None,
-        }
+        window_statistics(self.input.statistics(), self.window_expr.len())
+    }
+
+    fn partition_statistics(&self, partition: usize) -> Statistics {
+        window_statistics(
+            self.input.partition_statistics(partition),
+            self.window_expr.len(),
+        )
     }
 }
 
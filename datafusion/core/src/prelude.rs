@@ -27,8 +27,10 @@
 
 pub use crate::dataframe::DataFrame;
 pub use crate::execution::context::{SessionConfig, SessionContext};
+pub use crate::execution::evaluate::ExprEvaluationExt;
 pub use crate::execution::options::{
-    AvroReadOptions, CsvReadOptions, NdJsonReadOptions, ParquetReadOptions,
+    ArrowReadOptions, AvroReadOptions, CsvReadOptions, NdJsonReadOptions,
+    ParquetReadOptions,
 };
 
 pub use datafusion_common::Column;
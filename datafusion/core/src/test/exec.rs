@@ -424,7 +424,7 @@ impl ExecutionPlan for ErrorExec {
     }
 
     fn statistics(&self) -> Statistics {
-        Statistics::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
 
@@ -436,13 +436,10 @@ pub struct StatisticsExec {
 }
 impl StatisticsExec {
     pub fn new(stats: Statistics, schema: Schema) -> Self {
-        assert!(
-            stats
-                .column_statistics
-                .as_ref()
-                .map(|cols| cols.len() == schema.fields().len())
-                .unwrap_or(true),
-            "if defined, the column statistics vector length should be the number of fields"
+        assert_eq!(
+            stats.column_statistics.len(),
+            schema.fields().len(),
+            "the column statistics vector length should be the number of fields"
         );
         Self {
             stats,
@@ -499,7 +496,7 @@ impl ExecutionPlan for StatisticsExec {
             DisplayFormatType::Default => {
                 write!(
                     f,
-                    "StatisticsExec: col_count={}, row_count={:?}",
+                    "StatisticsExec: col_count={}, row_count={}",
                     self.schema.fields().len(),
                     self.stats.num_rows,
                 )
@@ -574,7 +571,7 @@ impl ExecutionPlan for UnboundedExec {
     }
 
     fn statistics(&self) -> Statistics {
-        Statistics::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
 
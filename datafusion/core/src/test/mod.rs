@@ -18,6 +18,8 @@
 //! Common unit test utility methods
 
 use crate::arrow::array::UInt32Array;
+#[cfg(feature = "compression")]
+use crate::datasource::file_format::bgzf::BgzfEncoder;
 use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
 use crate::datasource::listing::PartitionedFile;
 use crate::datasource::object_store::ObjectStoreUrl;
@@ -26,6 +28,7 @@ use crate::error::Result;
 use crate::from_slice::FromSlice;
 use crate::logical_expr::LogicalPlan;
 use crate::physical_plan::file_format::{CsvExec, FileScanConfig};
+use crate::physical_plan::Statistics;
 use crate::test::object_store::local_unpartitioned_file;
 use crate::test_util::{aggr_test_schema, arrow_test_data};
 use array::ArrayRef;
@@ -49,6 +52,8 @@ use std::sync::Arc;
 use tempfile::TempDir;
 #[cfg(feature = "compression")]
 use xz2::write::XzEncoder;
+#[cfg(feature = "compression")]
+use zstd::Encoder as ZstdEncoder;
 
 pub fn create_table_dual() -> Arc<dyn TableProvider> {
     let dual_schema = Arc::new(Schema::new(vec![
@@ -127,10 +132,18 @@ pub fn partitioned_file_groups(
             FileCompressionType::BZIP2 => {
                 Box::new(BzEncoder::new(file, BzCompression::default()))
             }
+            #[cfg(feature = "compression")]
+            FileCompressionType::ZSTD => {
+                Box::new(ZstdEncoder::new(file, 0).unwrap().auto_finish())
+            }
+            #[cfg(feature = "compression")]
+            FileCompressionType::BGZIP => Box::new(BgzfEncoder::new(file)),
             #[cfg(not(feature = "compression"))]
             FileCompressionType::GZIP
             | FileCompressionType::BZIP2
-            | FileCompressionType::XZ => {
+            | FileCompressionType::XZ
+            | FileCompressionType::ZSTD
+            | FileCompressionType::BGZIP => {
                 panic!("GZIP compression is not supported in this build")
             }
         };
@@ -175,9 +188,9 @@ pub fn partitioned_csv_config(
 ) -> Result<FileScanConfig> {
     Ok(FileScanConfig {
         object_store_url: ObjectStoreUrl::local_filesystem(),
+        statistics: Statistics::new_unknown(&schema),
         file_schema: schema,
         file_groups,
-        statistics: Default::default(),
         projection: None,
         limit: None,
         table_partition_cols: vec![],
@@ -17,6 +17,7 @@
 
 use arrow::array::{Int32Array, Int64Array};
 use arrow::compute::kernels::aggregate;
+use arrow::compute::SortOptions;
 use arrow::datatypes::{DataType, Field, Int32Type, Schema, SchemaRef};
 use arrow::error::Result as ArrowResult;
 use arrow::record_batch::RecordBatch;
@@ -26,17 +27,17 @@ use datafusion::logical_expr::{
     col, Expr, LogicalPlan, LogicalPlanBuilder, Projection, TableScan, UNNAMED_TABLE,
 };
 use datafusion::physical_plan::empty::EmptyExec;
-use datafusion::physical_plan::expressions::PhysicalSortExpr;
+use datafusion::physical_plan::expressions::{Column, PhysicalSortExpr};
 use datafusion::physical_plan::{
-    project_schema, ColumnStatistics, ExecutionPlan, Partitioning, RecordBatchStream,
-    SendableRecordBatchStream, Statistics,
+    displayable, project_schema, ColumnStatistics, ExecutionPlan, Partitioning,
+    Precision, RecordBatchStream, SendableRecordBatchStream, Statistics,
 };
 use datafusion::scalar::ScalarValue;
+use datafusion::{assert_batches_eq, error::Result, physical_plan::DisplayFormatType};
 use datafusion::{
     datasource::{TableProvider, TableType},
     physical_plan::collect,
 };
-use datafusion::{error::Result, physical_plan::DisplayFormatType};
 
 use datafusion_common::cast::as_primitive_array;
 use futures::stream::Stream;
@@ -53,6 +54,7 @@ struct CustomTableProvider;
 #[derive(Debug, Clone)]
 struct CustomExecutionPlan {
     projection: Option<Vec<usize>>,
+    ordering: Option<Vec<PhysicalSortExpr>>,
 }
 struct TestCustomRecordBatchStream {
     /// the nb of batches of TEST_CUSTOM_RECORD_BATCH generated
@@ -115,7 +117,7 @@ impl ExecutionPlan for CustomExecutionPlan {
     }
 
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
-        None
+        self.ordering.as_deref()
     }
 
     fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
@@ -152,26 +154,24 @@ impl ExecutionPlan for CustomExecutionPlan {
     fn statistics(&self) -> Statistics {
         let batch = TEST_CUSTOM_RECORD_BATCH!().unwrap();
         Statistics {
-            is_exact: true,
-            num_rows: Some(batch.num_rows()),
-            total_byte_size: None,
-            column_statistics: Some(
-                self.projection
-                    .clone()
-                    .unwrap_or_else(|| (0..batch.columns().len()).collect())
-                    .iter()
-                    .map(|i| ColumnStatistics {
-                        null_count: Some(batch.column(*i).null_count()),
-                        min_value: Some(ScalarValue::Int32(aggregate::min(
-                            as_primitive_array::<Int32Type>(batch.column(*i)).unwrap(),
-                        ))),
-                        max_value: Some(ScalarValue::Int32(aggregate::max(
-                            as_primitive_array::<Int32Type>(batch.column(*i)).unwrap(),
-                        ))),
-                        ..Default::default()
-                    })
-                    .collect(),
-            ),
+            num_rows: Precision::Exact(batch.num_rows()),
+            total_byte_size: Precision::Absent,
+            column_statistics: self
+                .projection
+                .clone()
+                .unwrap_or_else(|| (0..batch.columns().len()).collect())
+                .iter()
+                .map(|i| ColumnStatistics {
+                    null_count: Precision::Exact(batch.column(*i).null_count()),
+                    min_value: Precision::Exact(ScalarValue::Int32(aggregate::min(
+                        as_primitive_array::<Int32Type>(batch.column(*i)).unwrap(),
+                    ))),
+                    max_value: Precision::Exact(ScalarValue::Int32(aggregate::max(
+                        as_primitive_array::<Int32Type>(batch.column(*i)).unwrap(),
+                    ))),
+                    ..Default::default()
+                })
+                .collect(),
         }
     }
 }
@@ -199,10 +199,68 @@ impl TableProvider for CustomTableProvider {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         Ok(Arc::new(CustomExecutionPlan {
             projection: projection.cloned(),
+            ordering: None,
         }))
     }
 }
 
+/// Same data as [`CustomTableProvider`], which is sorted on `c1`
+struct OrderedTableProvider;
+
+#[async_trait]
+impl TableProvider for OrderedTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        TEST_CUSTOM_SCHEMA_REF!()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(CustomExecutionPlan {
+            projection: projection.cloned(),
+            ordering: None,
+        }))
+    }
+
+    async fn scan_ordered(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+        ordering: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        assert_eq!(ordering, &[col("c1").sort(true, false)]);
+        let schema = project_schema(&self.schema(), projection)?;
+        Ok(Arc::new(CustomExecutionPlan {
+            projection: projection.cloned(),
+            ordering: Some(vec![PhysicalSortExpr {
+                expr: Arc::new(Column::new_with_schema("c1", &schema)?),
+                options: SortOptions {
+                    descending: false,
+                    nulls_first: false,
+                },
+            }]),
+        }))
+    }
+
+    fn output_orderings(&self) -> Vec<Vec<Expr>> {
+        vec![vec![col("c1").sort(true, false)]]
+    }
+}
+
 #[tokio::test]
 async fn custom_source_dataframe() -> Result<()> {
     let ctx = SessionContext::new();
@@ -289,6 +347,45 @@ async fn optimizers_catch_all_statistics() {
     assert_eq!(format!("{:?}", actual[0]), format!("{expected:?}"));
 }
 
+#[tokio::test]
+async fn ordered_scan_removes_sort() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_table("test", Arc::new(OrderedTableProvider))?;
+
+    // the ordering requested from the scan satisfies the sort
+    let physical_plan = ctx
+        .sql("SELECT c1, c2 FROM test ORDER BY c1")
+        .await?
+        .create_physical_plan()
+        .await?;
+    let formatted = displayable(physical_plan.as_ref()).indent().to_string();
+    assert!(!formatted.contains("SortExec"), "{formatted}");
+
+    let batches = collect(physical_plan, ctx.task_ctx()).await?;
+    let expected = vec![
+        "+-----+-----+",
+        "| c1  | c2  |",
+        "+-----+-----+",
+        "| 1   | 2   |",
+        "| 10  | 12  |",
+        "| 10  | 12  |",
+        "| 100 | 120 |",
+        "+-----+-----+",
+    ];
+    assert_batches_eq!(expected, &batches);
+
+    // an ordering the source does not advertise still needs a sort
+    let physical_plan = ctx
+        .sql("SELECT c1, c2 FROM test ORDER BY c2 DESC")
+        .await?
+        .create_physical_plan()
+        .await?;
+    let formatted = displayable(physical_plan.as_ref()).indent().to_string();
+    assert!(formatted.contains("SortExec"), "{formatted}");
+
+    Ok(())
+}
+
 fn contains_empty_exec(plan: Arc<dyn ExecutionPlan>) -> bool {
     if plan.as_any().is::<EmptyExec>() {
         true
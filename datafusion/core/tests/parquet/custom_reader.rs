@@ -68,6 +68,7 @@ async fn route_data_access_ops_to_parquet_file_reader_factory() {
             partition_values: vec![],
             range: None,
             extensions: Some(Arc::new(String::from(EXPECTED_USER_DEFINED_METADATA))),
+            statistics: None,
         })
         .collect();
 
@@ -77,8 +78,8 @@ async fn route_data_access_ops_to_parquet_file_reader_factory() {
             // just any url that doesn't point to in memory object store
             object_store_url: ObjectStoreUrl::local_filesystem(),
             file_groups: vec![file_groups],
+            statistics: Statistics::new_unknown(&file_schema),
             file_schema,
-            statistics: Statistics::default(),
             projection: None,
             limit: None,
             table_partition_cols: vec![],
@@ -56,14 +56,15 @@ async fn get_parquet_exec(state: &SessionState, filter: Expr) -> ParquetExec {
         partition_values: vec![],
         range: None,
         extensions: None,
+        statistics: None,
     };
 
     let parquet_exec = ParquetExec::new(
         FileScanConfig {
             object_store_url,
             file_groups: vec![vec![partitioned_file]],
+            statistics: Statistics::new_unknown(&schema),
             file_schema: schema,
-            statistics: Statistics::default(),
             // file has 10 cols so index 12 should be month
             projection: None,
             limit: None,
@@ -96,6 +96,18 @@ async fn prune_timestamps_seconds() {
     .await;
 }
 
+#[tokio::test]
+async fn prune_timestamps_date_trunc() {
+    test_prune(
+        Scenario::Timestamps,
+        "SELECT * FROM t where date_trunc('day', nanos) = to_timestamp('2020-01-11T00:00:00Z')",
+        Some(0),
+        Some(3),
+        3,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn prune_date32() {
     test_prune(
@@ -199,6 +211,18 @@ async fn prune_int32_eq() {
     )
     .await;
 }
+#[tokio::test]
+async fn prune_int32_cast() {
+    test_prune(
+        Scenario::Int32,
+        "SELECT * FROM t where CAST(i AS DOUBLE) > 5.5",
+        Some(0),
+        Some(3),
+        4,
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn prune_int32_scalar_fun_and_eq() {
     test_prune(
@@ -34,7 +34,7 @@ use datafusion::{
         listing::{ListingOptions, ListingTable, ListingTableConfig},
     },
     error::Result,
-    physical_plan::ColumnStatistics,
+    physical_plan::{ColumnStatistics, Precision},
     prelude::SessionContext,
     test_util::{self, arrow_test_data, parquet_test_data},
 };
@@ -183,7 +183,7 @@ async fn parquet_distinct_partition_col() -> Result<()> {
 fn extract_as_utf(v: &ScalarValue) -> Option<String> {
     if let ScalarValue::Dictionary(_, v) = v {
         if let ScalarValue::Utf8(v) = v.as_ref() {
-            return v.clone();
+            return v.as_ref().map(|v| v.to_string());
         }
     }
     None
@@ -451,13 +451,10 @@ async fn parquet_statistics() -> Result<()> {
     let physical_plan = dataframe.create_physical_plan().await?;
     assert_eq!(physical_plan.schema().fields().len(), 4);
 
-    let stat_cols = physical_plan
-        .statistics()
-        .column_statistics
-        .expect("col stats should be defined");
+    let stat_cols = physical_plan.statistics().column_statistics;
     assert_eq!(stat_cols.len(), 4);
     // stats for the first col are read from the parquet file
-    assert_eq!(stat_cols[0].null_count, Some(3));
+    assert_eq!(stat_cols[0].null_count, Precision::Exact(3));
     // TODO assert partition column (1,2,3) stats once implemented (#1186)
     assert_eq!(stat_cols[1], ColumnStatistics::default());
     assert_eq!(stat_cols[2], ColumnStatistics::default());
@@ -468,13 +465,10 @@ async fn parquet_statistics() -> Result<()> {
     let physical_plan = dataframe.create_physical_plan().await?;
     assert_eq!(physical_plan.schema().fields().len(), 2);
 
-    let stat_cols = physical_plan
-        .statistics()
-        .column_statistics
-        .expect("col stats should be defined");
+    let stat_cols = physical_plan.statistics().column_statistics;
     assert_eq!(stat_cols.len(), 2);
     // stats for the first col are read from the parquet file
-    assert_eq!(stat_cols[0].null_count, Some(1));
+    assert_eq!(stat_cols[0].null_count, Precision::Exact(1));
     // TODO assert partition column stats once implemented (#1186)
     assert_eq!(stat_cols[1], ColumnStatistics::default());
 
@@ -117,7 +117,7 @@ impl ExecutionPlan for CustomPlan {
     fn statistics(&self) -> Statistics {
         // here we could provide more accurate statistics
         // but we want to test the filter pushdown not the CBOs
-        Statistics::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
 
@@ -109,28 +109,66 @@ async fn csv_query_array_agg_unsupported() -> Result<()> {
 
     let results = plan_and_collect(
         &ctx,
-        "SELECT array_agg(c13 ORDER BY c1) FROM aggregate_test_100",
+        "SELECT array_agg(c13 LIMIT 1) FROM aggregate_test_100",
     )
     .await
     .unwrap_err();
 
     assert_eq!(
         results.to_string(),
-        "This feature is not implemented: ORDER BY not supported in ARRAY_AGG: c1"
+        "This feature is not implemented: LIMIT not supported in ARRAY_AGG: 1"
     );
 
-    let results = plan_and_collect(
-        &ctx,
-        "SELECT array_agg(c13 LIMIT 1) FROM aggregate_test_100",
+    Ok(())
+}
+
+#[tokio::test]
+async fn array_agg_order_by() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE t (k INT, v VARCHAR, o INT) AS VALUES \
+         (1, 'a', 3), (1, 'b', NULL), (1, 'c', 1), (2, 'd', 2), (2, 'e', NULL)",
     )
-    .await
-    .unwrap_err();
+    .await?;
 
+    let sql = "SELECT k, \
+        ARRAY_AGG(v ORDER BY o) AS asc_default, \
+        ARRAY_AGG(v ORDER BY o ASC NULLS FIRST) AS asc_nulls_first, \
+        ARRAY_AGG(v ORDER BY o DESC) AS desc_default, \
+        ARRAY_AGG(v ORDER BY o DESC NULLS LAST) AS desc_nulls_last \
+        FROM t GROUP BY k ORDER BY k";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+-------------+-----------------+--------------+-----------------+",
+        "| k | asc_default | asc_nulls_first | desc_default | desc_nulls_last |",
+        "+---+-------------+-----------------+--------------+-----------------+",
+        "| 1 | [c, a, b]   | [b, c, a]       | [b, a, c]    | [a, c, b]       |",
+        "| 2 | [d, e]      | [e, d]          | [e, d]       | [d, e]          |",
+        "+---+-------------+-----------------+--------------+-----------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the session default applies to the sort keys without NULLS FIRST or NULLS LAST
+    ctx.sql("SET datafusion.sql_parser.default_null_ordering = 'nulls_first'")
+        .await?;
+    let sql = "SELECT ARRAY_AGG(v ORDER BY o) AS a FROM t WHERE k = 1";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----------+",
+        "| a         |",
+        "+-----------+",
+        "| [b, c, a] |",
+        "+-----------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let err = plan_and_collect(&ctx, "SELECT ARRAY_AGG(DISTINCT v ORDER BY o) FROM t")
+        .await
+        .unwrap_err();
     assert_eq!(
-        results.to_string(),
-        "This feature is not implemented: LIMIT not supported in ARRAY_AGG: 1"
+        err.to_string(),
+        "This feature is not implemented: ORDER BY not supported in ARRAY_AGG with DISTINCT"
     );
-
     Ok(())
 }
 
@@ -161,7 +199,8 @@ async fn csv_query_array_agg_distinct() -> Result<()> {
     let column = actual[0].column(0);
     assert_eq!(column.len(), 1);
 
-    if let ScalarValue::List(Some(mut v), _) = ScalarValue::try_from_array(column, 0)? {
+    if let ScalarValue::List(Some(v), _) = ScalarValue::try_from_array(column, 0)? {
+        let mut v = ScalarValue::convert_array_to_scalar_vec(&v)?;
         // workaround lack of Ord of ScalarValue
         let cmp = |a: &ScalarValue, b: &ScalarValue| {
             a.partial_cmp(b).expect("Can compare ScalarValues")
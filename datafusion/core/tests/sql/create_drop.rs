@@ -15,8 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use datafusion::datasource::MemTable;
 use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
 use datafusion::test_util::TestTableFactory;
+use datafusion_common::{ColumnStatistics, Precision, ScalarValue};
 
 use super::*;
 
@@ -58,6 +60,68 @@ async fn sql_create_table_if_not_exists() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn create_temporary_table() -> Result<()> {
+    let ctx = SessionContext::new();
+    plan_and_collect(&ctx, "CREATE TABLE t AS VALUES (1)").await?;
+    plan_and_collect(&ctx, "CREATE TEMPORARY TABLE t AS VALUES (2)").await?;
+
+    let temporary = vec![
+        "+---------+",
+        "| column1 |",
+        "+---------+",
+        "| 2       |",
+        "+---------+",
+    ];
+    let permanent = vec![
+        "+---------+",
+        "| column1 |",
+        "+---------+",
+        "| 1       |",
+        "+---------+",
+    ];
+
+    // the temporary table shadows the table of the same name
+    let results = plan_and_collect(&ctx, "SELECT * FROM t").await?;
+    assert_batches_eq!(temporary, &results);
+    let results = ctx.table("t").await?.collect().await?;
+    assert_batches_eq!(temporary, &results);
+    let results = plan_and_collect(&ctx, "SELECT * FROM public.t").await?;
+    assert_batches_eq!(permanent, &results);
+
+    let err = plan_and_collect(&ctx, "CREATE TEMPORARY TABLE t AS VALUES (3)")
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Execution error: Table 't' already exists");
+    let err = plan_and_collect(&ctx, "CREATE TEMPORARY TABLE public.u AS VALUES (3)")
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Temporary table 'public.u' can not be created in a schema"));
+
+    // other sessions sharing the catalog don't see the temporary table
+    let mut other = SessionContext::new();
+    other.register_catalog_list(ctx.state().catalog_list());
+    let results = plan_and_collect(&other, "SELECT * FROM t").await?;
+    assert_batches_eq!(permanent, &results);
+
+    // dropping the table drops the temporary table first
+    plan_and_collect(&ctx, "DROP TABLE t").await?;
+    let results = plan_and_collect(&ctx, "SELECT * FROM t").await?;
+    assert_batches_eq!(permanent, &results);
+
+    // the temporary tables are dropped with the session
+    plan_and_collect(&ctx, "CREATE TEMPORARY TABLE t AS VALUES (2)").await?;
+    let temporary_schema = Arc::downgrade(&ctx.state().temporary_schema());
+    drop(ctx);
+    assert!(temporary_schema.upgrade().is_none());
+    let results = plan_and_collect(&other, "SELECT * FROM t").await?;
+    assert_batches_eq!(permanent, &results);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn create_custom_table() -> Result<()> {
     let mut cfg = RuntimeConfig::new();
@@ -149,3 +213,85 @@ async fn create_csv_table_empty_file() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn analyze_table() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE t AS VALUES (1, 'a'), (2, NULL), (3, 'a')")
+        .await?
+        .collect()
+        .await?;
+
+    let result = ctx
+        .sql("ANALYZE TABLE t COMPUTE STATISTICS FOR COLUMNS column1")
+        .await?
+        .collect()
+        .await?;
+    assert_eq!(result, Vec::new());
+
+    // the table registration is left unchanged
+    let table = ctx.table_provider("t").await?;
+    assert!(table.as_any().downcast_ref::<MemTable>().is_some());
+    let state = ctx.state();
+    let stats = state
+        .analyzed_tables()
+        .get(&table)
+        .expect("statistics are cached");
+    assert_eq!(stats.num_rows, Precision::Inexact(3));
+    let column1 = &stats.column_statistics[0];
+    assert_eq!(column1.null_count, Precision::Inexact(0));
+    assert_eq!(
+        column1.min_value,
+        Precision::Inexact(ScalarValue::Int64(Some(1)))
+    );
+    assert_eq!(
+        column1.max_value,
+        Precision::Inexact(ScalarValue::Int64(Some(3)))
+    );
+    assert_eq!(column1.distinct_count, Precision::Inexact(3));
+    // column2 was not analyzed
+    assert_eq!(stats.column_statistics[1], ColumnStatistics::new_unknown());
+
+    // the cached statistics fill in what the scan does not know exactly
+    let plan = ctx
+        .sql("SELECT * FROM t")
+        .await?
+        .create_physical_plan()
+        .await?;
+    let plan_stats = plan.statistics();
+    assert_eq!(plan_stats.num_rows, Precision::Exact(3));
+    assert_eq!(
+        plan_stats.column_statistics[0].max_value,
+        Precision::Inexact(ScalarValue::Int64(Some(3)))
+    );
+
+    // analyzing all columns replaces the cached statistics
+    ctx.sql("ANALYZE TABLE t").await?.collect().await?;
+    let state = ctx.state();
+    let stats = state.analyzed_tables().get(&table).unwrap();
+    assert_eq!(stats.column_statistics[1].null_count, Precision::Inexact(1));
+    assert_eq!(
+        stats.column_statistics[1].distinct_count,
+        Precision::Inexact(1)
+    );
+
+    let err = ctx
+        .sql("ANALYZE TABLE t COMPUTE STATISTICS FOR COLUMNS c")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Column 'c' not found in table 't'"
+    );
+
+    // the statistics do not carry over to a new table of the same name
+    ctx.sql("DROP TABLE t").await?.collect().await?;
+    ctx.sql("CREATE TABLE t AS VALUES (1, 'a')")
+        .await?
+        .collect()
+        .await?;
+    let table = ctx.table_provider("t").await?;
+    assert!(ctx.state().analyzed_tables().get(&table).is_none());
+
+    Ok(())
+}
@@ -16,6 +16,7 @@
 // under the License.
 
 use super::*;
+use datafusion_common::{ErrorCode, Span};
 
 #[tokio::test]
 async fn csv_query_error() -> Result<()> {
@@ -43,6 +44,11 @@ async fn test_cast_expressions_error() -> Result<()> {
                 e.to_string(),
                 "Cannot cast string 'c' to value of Int32 type"
             );
+            // the error reports the expression and the row that failed
+            assert_contains!(
+                e.to_string(),
+                "Failed to evaluate CAST(c1@0 AS Int32) on row 0 with c1 = c"
+            );
         }
     }
 
@@ -124,10 +130,10 @@ async fn invalid_qualified_table_references() -> Result<()> {
         "way.too.many.namespaces.as.ident.prefixes.aggregate_test_100",
     ] {
         let sql = format!("SELECT COUNT(*) FROM {table_ref}");
-        let result = ctx.sql(&sql).await;
+        let err = ctx.sql(&sql).await.unwrap_err();
         assert!(
-            matches!(result, Err(DataFusionError::Plan(_))),
-            "result was: {result:?}"
+            matches!(err.find_root(), DataFusionError::Plan(_)),
+            "error was: {err:?}"
         );
     }
     Ok(())
@@ -171,3 +177,85 @@ async fn unsupported_sql_returns_error() -> Result<()> {
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn planning_error_diagnostics() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_aggregate_csv(&ctx).await?;
+
+    let sql = "SELECT c1,\n  C42 FROM aggregate_test_100";
+    let err = ctx.sql(sql).await.unwrap_err();
+    let diagnostic = err.diagnostic().unwrap();
+    assert_eq!(diagnostic.message, "No field named 'c42'");
+    assert_eq!(diagnostic.span, Some(Span::new(13, 16)));
+
+    let sql = "SELECT foo(c1) FROM aggregate_test_100";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Invalid function 'foo'"
+    );
+    let diagnostic = err.diagnostic().unwrap();
+    assert_eq!(diagnostic.span, Some(Span::new(7, 10)));
+
+    let sql = "SELECT * FROM aggregate_test_100 JOIN my_schema.missing ON true";
+    let err = ctx.sql(sql).await.unwrap_err();
+    let diagnostic = err.diagnostic().unwrap();
+    assert_eq!(diagnostic.span, Some(Span::new(38, 55)));
+    assert_eq!(&sql[38..55], "my_schema.missing");
+    Ok(())
+}
+
+#[tokio::test]
+async fn planning_error_suggestions() -> Result<()> {
+    let batch = RecordBatch::try_from_iter(vec![
+        (
+            "price",
+            Arc::new(Int32Array::from_slice([1, 2])) as ArrayRef,
+        ),
+        (
+            "quantity",
+            Arc::new(Int32Array::from_slice([3, 4])) as ArrayRef,
+        ),
+    ])?;
+    let ctx = SessionContext::new();
+    ctx.register_batch("t", batch.clone())?;
+
+    let sql = "SELECT price,\n  quantiy FROM t";
+    let err = ctx.sql(sql).await.unwrap_err();
+    let diagnostic = err.diagnostic().unwrap();
+    assert_eq!(diagnostic.code, Some(ErrorCode::UnknownColumn));
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("quantity"));
+    assert_eq!(diagnostic.span, Some(Span::new(16, 23)));
+
+    let sql = "SELECT sume(price) FROM t";
+    let err = ctx.sql(sql).await.unwrap_err();
+    let diagnostic = err.diagnostic().unwrap();
+    assert_eq!(diagnostic.code, Some(ErrorCode::UnknownFunction));
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("sum"));
+
+    let sql = "SELECT * FROM tt";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: table 'datafusion.public.tt' not found"
+    );
+    let diagnostic = err.diagnostic().unwrap();
+    assert_eq!(diagnostic.code, Some(ErrorCode::UnknownTable));
+    assert_eq!(diagnostic.suggestion.as_deref(), Some("t"));
+    assert_eq!(diagnostic.span, Some(Span::new(14, 16)));
+
+    // nothing close enough to suggest
+    let sql = "SELECT * FROM orders";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_eq!(err.diagnostic().unwrap().suggestion, None);
+
+    let config = SessionConfig::new().with_suggestions(false);
+    let ctx = SessionContext::with_config(config);
+    ctx.register_batch("t", batch)?;
+    let err = ctx.sql("SELECT * FROM tt").await.unwrap_err();
+    let diagnostic = err.diagnostic().unwrap();
+    assert_eq!(diagnostic.code, Some(ErrorCode::UnknownTable));
+    assert_eq!(diagnostic.suggestion, None);
+    Ok(())
+}
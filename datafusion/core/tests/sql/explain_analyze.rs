@@ -607,18 +607,17 @@ async fn test_physical_plan_display_indent() {
     let dataframe = ctx.sql(sql).await.unwrap();
     let physical_plan = dataframe.create_physical_plan().await.unwrap();
     let expected = vec![
-        "GlobalLimitExec: skip=0, fetch=10",
-        "  SortPreservingMergeExec: [the_min@2 DESC]",
-        "    SortExec: [the_min@2 DESC]",
-        "      ProjectionExec: expr=[c1@0 as c1, MAX(aggregate_test_100.c12)@1 as MAX(aggregate_test_100.c12), MIN(aggregate_test_100.c12)@2 as the_min]",
-        "        AggregateExec: mode=FinalPartitioned, gby=[c1@0 as c1], aggr=[MAX(aggregate_test_100.c12), MIN(aggregate_test_100.c12)]",
-        "          CoalesceBatchesExec: target_batch_size=4096",
-        "            RepartitionExec: partitioning=Hash([Column { name: \"c1\", index: 0 }], 9000)",
-        "              AggregateExec: mode=Partial, gby=[c1@0 as c1], aggr=[MAX(aggregate_test_100.c12), MIN(aggregate_test_100.c12)]",
-        "                CoalesceBatchesExec: target_batch_size=4096",
-        "                  FilterExec: c12@1 < 10",
-        "                    RepartitionExec: partitioning=RoundRobinBatch(9000)",
-        "                      CsvExec: files={1 group: [[ARROW_TEST_DATA/csv/aggregate_test_100.csv]]}, has_header=true, limit=None, projection=[c1, c12]",
+        "SortPreservingMergeExec: [the_min@2 DESC], skip=0, fetch=10",
+        "  SortExec: [the_min@2 DESC]",
+        "    ProjectionExec: expr=[c1@0 as c1, MAX(aggregate_test_100.c12)@1 as MAX(aggregate_test_100.c12), MIN(aggregate_test_100.c12)@2 as the_min]",
+        "      AggregateExec: mode=FinalPartitioned, gby=[c1@0 as c1], aggr=[MAX(aggregate_test_100.c12), MIN(aggregate_test_100.c12)]",
+        "        CoalesceBatchesExec: target_batch_size=4096",
+        "          RepartitionExec: partitioning=Hash([Column { name: \"c1\", index: 0 }], 9000)",
+        "            AggregateExec: mode=Partial, gby=[c1@0 as c1], aggr=[MAX(aggregate_test_100.c12), MIN(aggregate_test_100.c12)]",
+        "              CoalesceBatchesExec: target_batch_size=4096",
+        "                FilterExec: c12@1 < 10",
+        "                  RepartitionExec: partitioning=RoundRobinBatch(9000)",
+        "                    CsvExec: files={1 group: [[ARROW_TEST_DATA/csv/aggregate_test_100.csv]]}, has_header=true, limit=None, projection=[c1, c12]",
     ];
 
     let normalizer = ExplainNormalizer::new();
@@ -736,6 +735,11 @@ async fn csv_explain_analyze() {
     let needle = "CoalescePartitionsExec, metrics=[output_rows=5, elapsed_compute=";
     assert_contains!(&formatted, needle);
 
+    // the time spent planning the query, without the time of each rule
+    assert_contains!(&formatted, "Planning Metrics");
+    assert_contains!(&formatted, "parsing=");
+    assert_not_contains!(&formatted, "optimizer rule");
+
     let verbose_needle = "Output Rows";
     assert_not_contains!(formatted, verbose_needle);
 }
@@ -786,6 +790,9 @@ async fn csv_explain_analyze_verbose() {
         .unwrap()
         .to_string();
 
+    // the time spent in each optimizer rule
+    assert_contains!(&formatted, "optimizer rule push_down_projection=");
+
     let verbose_needle = "Output Rows";
     assert_contains!(formatted, verbose_needle);
 }
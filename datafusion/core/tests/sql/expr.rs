@@ -1168,6 +1168,22 @@ async fn test_cast_expressions() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_try_functions() -> Result<()> {
+    test_expression!("try_divide(7, 2)", "3");
+    test_expression!("try_divide(1, 0)", "NULL");
+    test_expression!("try_divide(1.0, 4)", "0.25");
+    test_expression!("try_divide(1.0, 0.0)", "NULL");
+    test_expression!("try_divide(NULL, 1)", "NULL");
+    test_expression!(
+        "try_to_timestamp('2020-09-08T12:00:00+00:00')",
+        "2020-09-08T12:00:00"
+    );
+    test_expression!("try_to_timestamp('not a timestamp')", "NULL");
+    test_expression!("try_to_timestamp(NULL)", "NULL");
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_random_expression() -> Result<()> {
     let ctx = create_ctx();
@@ -834,6 +834,104 @@ async fn group_by_dictionary() {
     run_test_case::<UInt64Type>().await;
 }
 
+#[tokio::test]
+async fn group_by_nested_types() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    // input data looks like:
+    // {a: 1, b: x}, [1, 2], 1
+    // {a: 1, b: y}, [1, 2], 2
+    // {a: 1, b: x}, [],     3
+    // NULL,         [1, 2], 4
+    // {a: 1, b: x}, NULL,   5
+    // NULL,         NULL,   6
+
+    let struct_array = StructArray::from((
+        vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![1, 1, 1, 0, 1, 0])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec!["x", "y", "x", "", "x", ""])) as ArrayRef,
+            ),
+        ],
+        arrow::buffer::Buffer::from([0b00010111u8]),
+    ));
+    let struct_array = Arc::new(struct_array);
+
+    let list_array = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+        Some(vec![Some(1), Some(2)]),
+        Some(vec![Some(1), Some(2)]),
+        Some(vec![]),
+        Some(vec![Some(1), Some(2)]),
+        None,
+        None,
+    ]);
+    let list_array = Arc::new(list_array);
+
+    let val_array: Int64Array = vec![1, 2, 3, 4, 5, 6].into();
+    let val_array = Arc::new(val_array);
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("s", struct_array.data_type().clone(), true),
+        Field::new("l", list_array.data_type().clone(), true),
+        Field::new("val", val_array.data_type().clone(), false),
+    ]));
+
+    let batch = RecordBatch::try_new(schema, vec![struct_array, list_array, val_array])?;
+
+    ctx.register_batch("t", batch)?;
+
+    let sql = "SELECT s['a'] AS a, s['b'] AS b, sum(val) AS total FROM t GROUP BY s";
+    let results = plan_and_collect(&ctx, sql).await?;
+
+    let expected = vec![
+        "+---+---+-------+",
+        "| a | b | total |",
+        "+---+---+-------+",
+        "|   |   | 10    |",
+        "| 1 | x | 9     |",
+        "| 1 | y | 2     |",
+        "+---+---+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    let sql = "SELECT l, sum(val) AS total FROM t GROUP BY l";
+    let results = plan_and_collect(&ctx, sql).await?;
+
+    let expected = vec![
+        "+--------+-------+",
+        "| l      | total |",
+        "+--------+-------+",
+        "|        | 11    |",
+        "| []     | 3     |",
+        "| [1, 2] | 7     |",
+        "+--------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    let sql = "SELECT s['b'] AS b, l, count(*) AS count FROM t GROUP BY s, l";
+    let results = plan_and_collect(&ctx, sql).await?;
+
+    let expected = vec![
+        "+---+--------+-------+",
+        "| b | l      | count |",
+        "+---+--------+-------+",
+        "|   |        | 1     |",
+        "|   | [1, 2] | 1     |",
+        "| x |        | 1     |",
+        "| x | []     | 1     |",
+        "| x | [1, 2] | 1     |",
+        "| y | [1, 2] | 1     |",
+        "+---+--------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn csv_query_group_by_order_by_substr() -> Result<()> {
     let ctx = SessionContext::new();
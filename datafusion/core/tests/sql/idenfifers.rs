@@ -237,3 +237,50 @@ async fn case_insensitive_in_sql_errors() {
     // This should pass (note the quotes)
     ctx.sql(r#"SELECT "Column1" from test"#).await.unwrap();
 }
+
+#[tokio::test]
+async fn identifiers_with_sql_dialect() {
+    let record_batch = RecordBatch::try_from_iter(vec![(
+        "Column1",
+        Arc::new(StringArray::from(vec!["content1"])) as _,
+    )])
+    .unwrap();
+
+    let config = SessionConfig::new()
+        .with_sql_dialect("mysql")
+        .with_ident_normalization(false);
+    let ctx = SessionContext::with_config(config);
+    ctx.register_batch("Test", record_batch).unwrap();
+
+    // the case of unquoted identifiers is preserved
+    let expected = vec![
+        "+----------+",
+        "| Column1  |",
+        "+----------+",
+        "| content1 |",
+        "+----------+",
+    ];
+    let result = plan_and_collect(&ctx, "SELECT Column1 FROM Test")
+        .await
+        .unwrap();
+    assert_batches_sorted_eq!(expected, &result);
+
+    // MySQL quotes identifiers with backticks
+    let result = plan_and_collect(&ctx, "SELECT `Column1` FROM `Test`")
+        .await
+        .unwrap();
+    assert_batches_sorted_eq!(expected, &result);
+
+    let actual = ctx
+        .sql("SELECT column1 FROM Test")
+        .await
+        .unwrap_err()
+        .to_string();
+    assert_contains!(actual, "No field named 'column1'");
+
+    ctx.sql("SET datafusion.sql_parser.dialect = 'unknown'")
+        .await
+        .unwrap();
+    let actual = ctx.sql("SELECT 1").await.unwrap_err().to_string();
+    assert_contains!(actual, "Unsupported SQL dialect: unknown");
+}
@@ -85,3 +85,69 @@ async fn test_intersect_distinct() -> Result<()> {
     assert_batches_eq!(expected, &actual);
     Ok(())
 }
+
+#[tokio::test]
+async fn intersect_except_coerce_types() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "SELECT CAST(a AS INT) AS a FROM (VALUES (1), (2), (2), (3)) AS t1(a)
+            INTERSECT ALL SELECT a FROM (VALUES (2), (3), (4)) AS t2(a)";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec!["+---+", "| a |", "+---+", "| 2 |", "| 3 |", "+---+"];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    let sql = "SELECT a FROM (VALUES (2), (3), (4)) AS t2(a)
+            EXCEPT SELECT CAST(a AS INT) AS a FROM (VALUES (1), (2), (3)) AS t1(a)";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec!["+---+", "| a |", "+---+", "| 4 |", "+---+"];
+    assert_batches_sorted_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn intersect_except_all_multiplicities() -> Result<()> {
+    let ctx = SessionContext::new();
+    let left = "SELECT * FROM (VALUES (1, 'x'), (1, 'x'), (1, 'x'), (NULL, 'y'), (NULL, 'y'), (2, NULL)) AS t1(a, b)";
+    let right = "SELECT * FROM (VALUES (1, 'x'), (NULL, 'y'), (NULL, 'y'), (NULL, 'y'), (2, NULL), (2, NULL)) AS t2(a, b)";
+
+    // a row found m times on the left and n times on the right is returned
+    // min(m, n) times, with NULLs equal
+    let sql = format!("{left} INTERSECT ALL {right}");
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "|   | y |",
+        "|   | y |",
+        "| 1 | x |",
+        "| 2 |   |",
+        "+---+---+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    // and max(m - n, 0) times
+    let sql = format!("{left} EXCEPT ALL {right}");
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 1 | x |",
+        "+---+---+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    let sql = format!("{right} EXCEPT ALL {left}");
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "|   | y |",
+        "| 2 |   |",
+        "+---+---+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+    Ok(())
+}
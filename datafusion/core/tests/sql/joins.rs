@@ -1166,6 +1166,137 @@ async fn join_timestamp() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn join_timestamp_with_timezone() -> Result<()> {
+    let ctx = SessionContext::new();
+    for (table, name) in [("t1", "a"), ("t2", "b")] {
+        let timestamps = TimestampNanosecondArray::from(vec![Some(1), None, Some(2)])
+            .with_timezone("+01:00".to_string());
+        let names = StringArray::from_slice([name; 3]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("ts", Arc::new(timestamps) as ArrayRef),
+            ("name", Arc::new(names) as ArrayRef),
+        ])?;
+        ctx.register_batch(table, batch)?;
+    }
+
+    let sql = "SELECT t1.name, t2.name, CAST(t1.ts AS BIGINT) AS ts \
+               FROM t1 JOIN t2 ON t1.ts = t2.ts";
+    let msg = format!("Creating logical plan for '{sql}'");
+    let dataframe = ctx.sql(&("explain ".to_owned() + sql)).await.expect(&msg);
+    let plan = dataframe.into_optimized_plan()?;
+    let formatted = plan.display_indent_schema().to_string();
+    assert_contains!(formatted, "Inner Join: t1.ts = t2.ts");
+
+    let expected = vec![
+        "+------+------+----+",
+        "| name | name | ts |",
+        "+------+------+----+",
+        "| a    | b    | 1  |",
+        "| a    | b    | 2  |",
+        "+------+------+----+",
+    ];
+    let results = execute_to_batches(&ctx, sql).await;
+    assert_batches_sorted_eq!(expected, &results);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn join_on_nested_keys() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let struct_fields = vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ];
+    let tables = [
+        (
+            "t1",
+            [(1, "x"), (2, "y"), (0, "")],
+            [Some(vec![1, 2]), Some(vec![]), None],
+        ),
+        (
+            "t2",
+            [(1, "x"), (2, "z"), (0, "")],
+            [Some(vec![1, 2]), Some(vec![2, 1]), Some(vec![])],
+        ),
+    ];
+    for (table, structs, lists) in tables {
+        let (a, b): (Vec<_>, Vec<_>) = structs.into_iter().unzip();
+        // the third struct is NULL
+        let structs = StructArray::from((
+            vec![
+                (
+                    struct_fields[0].clone(),
+                    Arc::new(Int32Array::from(a)) as ArrayRef,
+                ),
+                (
+                    struct_fields[1].clone(),
+                    Arc::new(StringArray::from(b)) as ArrayRef,
+                ),
+            ],
+            arrow::buffer::Buffer::from([0b011u8]),
+        ));
+        let lists = ListArray::from_iter_primitive::<Int32Type, _, _>(
+            lists.map(|list| list.map(|values| values.into_iter().map(Some))),
+        );
+        let names: StringArray = ["0", "1", "2"]
+            .iter()
+            .map(|i| Some(format!("{table}_{i}")))
+            .collect();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("s", Arc::new(structs) as ArrayRef),
+            ("l", Arc::new(lists) as ArrayRef),
+            ("name", Arc::new(names) as ArrayRef),
+        ])?;
+        ctx.register_batch(table, batch)?;
+    }
+
+    // NULL keys don't match
+    let sql = "SELECT t1.name, t2.name FROM t1 JOIN t2 ON t1.s = t2.s";
+    let msg = format!("Creating logical plan for '{sql}'");
+    let dataframe = ctx.sql(&("explain ".to_owned() + sql)).await.expect(&msg);
+    let plan = dataframe.into_optimized_plan()?;
+    let formatted = plan.display_indent_schema().to_string();
+    assert_contains!(formatted, "Inner Join: t1.s = t2.s");
+
+    let expected = vec![
+        "+------+------+",
+        "| name | name |",
+        "+------+------+",
+        "| t1_0 | t2_0 |",
+        "+------+------+",
+    ];
+    let results = execute_to_batches(&ctx, sql).await;
+    assert_batches_sorted_eq!(expected, &results);
+
+    let sql = "SELECT t1.name, t2.name FROM t1 JOIN t2 ON t1.l = t2.l";
+    let expected = vec![
+        "+------+------+",
+        "| name | name |",
+        "+------+------+",
+        "| t1_0 | t2_0 |",
+        "| t1_1 | t2_2 |",
+        "+------+------+",
+    ];
+    let results = execute_to_batches(&ctx, sql).await;
+    assert_batches_sorted_eq!(expected, &results);
+
+    let sql = "SELECT t1.name, t2.name FROM t1 JOIN t2 ON t1.s = t2.s AND t1.l = t2.l";
+    let expected = vec![
+        "+------+------+",
+        "| name | name |",
+        "+------+------+",
+        "| t1_0 | t2_0 |",
+        "+------+------+",
+    ];
+    let results = execute_to_batches(&ctx, sql).await;
+    assert_batches_sorted_eq!(expected, &results);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn left_join_should_not_panic_with_empty_side() -> Result<()> {
     let ctx = SessionContext::new();
@@ -3143,3 +3274,50 @@ async fn two_in_subquery_to_join_with_outer_filter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn band_join() -> Result<()> {
+    let ctx = create_join_context("t1_id", "t2_id", false)?;
+
+    let sql = "select t1.t1_int, t2.t2_id from t1 inner join t2 \
+               on t1.t1_int between t2.t2_int and t2.t2_int + 1";
+    let expected = vec![
+        "+--------+-------+",
+        "| t1_int | t2_id |",
+        "+--------+-------+",
+        "| 1      | 22    |",
+        "| 2      | 22    |",
+        "| 3      | 11    |",
+        "| 3      | 44    |",
+        "| 3      | 55    |",
+        "| 4      | 11    |",
+        "| 4      | 44    |",
+        "| 4      | 55    |",
+        "+--------+-------+",
+    ];
+
+    let msg = format!("Creating logical plan for '{sql}'");
+    let dataframe = ctx.sql(sql).await.expect(&msg);
+    let physical_plan = dataframe.create_physical_plan().await?;
+    let formatted = displayable(physical_plan.as_ref()).indent().to_string();
+    assert!(
+        formatted.contains("BandJoinExec: point_side=Left"),
+        "\n\nunexpected plan:\n\n{formatted}\n\n"
+    );
+
+    let results = execute_to_batches(&ctx, sql).await;
+    assert_batches_sorted_eq!(expected, &results);
+
+    // the nested loop join has the same results
+    ctx.sql("SET datafusion.optimizer.enable_band_join = false")
+        .await?;
+    let dataframe = ctx.sql(sql).await.expect(&msg);
+    let physical_plan = dataframe.create_physical_plan().await?;
+    let formatted = displayable(physical_plan.as_ref()).indent().to_string();
+    assert!(formatted.contains("NestedLoopJoinExec"));
+
+    let results = execute_to_batches(&ctx, sql).await;
+    assert_batches_sorted_eq!(expected, &results);
+
+    Ok(())
+}
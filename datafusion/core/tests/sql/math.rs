@@ -54,3 +54,52 @@ async fn test_atan2() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn signed_and_unsigned_integers() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let t1_schema = Arc::new(Schema::new(vec![
+        Field::new("u", DataType::UInt64, false),
+        Field::new("i", DataType::Int32, false),
+    ]));
+    let t1_data = RecordBatch::try_new(
+        t1_schema,
+        vec![
+            Arc::new(UInt64Array::from(vec![0, u64::MAX])),
+            Arc::new(Int32Array::from(vec![-1, 1])),
+        ],
+    )?;
+    ctx.register_batch("t1", t1_data)?;
+
+    let t2_schema = Arc::new(Schema::new(vec![Field::new("s", DataType::Int8, false)]));
+    let t2_data =
+        RecordBatch::try_new(t2_schema, vec![Arc::new(Int8Array::from(vec![-1, 0]))])?;
+    ctx.register_batch("t2", t2_data)?;
+
+    // UInt64 and signed integers are compared and added as decimals
+    let sql = "SELECT u > -1 AS a, u + i AS b, u IN (0, -1) AS c FROM t1 ORDER BY u";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------+----------------------+-------+",
+        "| a    | b                    | c     |",
+        "+------+----------------------+-------+",
+        "| true | -1                   | true  |",
+        "| true | 18446744073709551616 | false |",
+        "+------+----------------------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT u, s FROM t1 JOIN t2 ON u = s";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| u | s |",
+        "+---+---+",
+        "| 0 | 0 |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    Ok(())
+}
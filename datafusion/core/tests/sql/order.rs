@@ -137,6 +137,89 @@ async fn test_specific_nulls_first_asc() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_default_null_ordering() -> Result<()> {
+    let ctx = SessionContext::with_config(
+        SessionConfig::new().with_default_null_ordering("nulls_first"),
+    );
+    let values = "(VALUES (1, 'one'), (2, 'two'), (null, 'three')) AS t (num,letter)";
+
+    let sql = format!("SELECT * FROM {values} ORDER BY num");
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected = vec![
+        "+-----+--------+",
+        "| num | letter |",
+        "+-----+--------+",
+        "|     | three  |",
+        "| 1   | one    |",
+        "| 2   | two    |",
+        "+-----+--------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // an explicit NULLS LAST takes precedence over the default
+    let sql = format!("SELECT * FROM {values} ORDER BY num DESC NULLS LAST");
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected = vec![
+        "+-----+--------+",
+        "| num | letter |",
+        "+-----+--------+",
+        "| 2   | two    |",
+        "| 1   | one    |",
+        "|     | three  |",
+        "+-----+--------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // nulls sort as the smallest values
+    ctx.sql("SET datafusion.sql_parser.default_null_ordering = 'nulls_min'")
+        .await?;
+    let sql = format!("SELECT * FROM {values} ORDER BY num DESC");
+    let actual = execute_to_batches(&ctx, &sql).await;
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_default_null_ordering_of_windows() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.sql("SET datafusion.sql_parser.default_null_ordering = 'nulls_last'")
+        .await?;
+    let sql = "SELECT num, \
+        ROW_NUMBER() OVER (ORDER BY num DESC) AS desc_rn, \
+        ROW_NUMBER() OVER (ORDER BY num ASC NULLS FIRST) AS asc_rn \
+        FROM (VALUES (1), (2), (null)) AS t (num) ORDER BY desc_rn";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+---------+--------+",
+        "| num | desc_rn | asc_rn |",
+        "+-----+---------+--------+",
+        "| 2   | 1       | 3      |",
+        "| 1   | 2       | 2      |",
+        "|     | 3       | 1      |",
+        "+-----+---------+--------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_default_null_ordering() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.sql("SET datafusion.sql_parser.default_null_ordering = 'nulls_sideways'")
+        .await?;
+    let err = ctx
+        .sql("SELECT * FROM (VALUES (1)) AS t (num) ORDER BY num")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Invalid null ordering 'nulls_sideways', expected \
+         one of nulls_max, nulls_min, nulls_first and nulls_last"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn sort() -> Result<()> {
     let results =
@@ -232,3 +315,146 @@ async fn sort_with_lots_of_repetition_values() -> Result<()> {
     }
     Ok(())
 }
+
+/// Registers a table with struct and list columns:
+///
+/// id | s             | l
+/// 1  | {a: 1, b: y}  | [2]
+/// 2  | {a: 1, b: x}  | [1, 2]
+/// 3  | NULL          | NULL
+/// 4  | {a: 0, b: z}  | []
+/// 5  | {a: 1, b: x}  | [1]
+fn register_nested_table(ctx: &SessionContext) -> Result<()> {
+    let id_array = Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5]));
+    let struct_array = Arc::new(StructArray::from((
+        vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![1, 1, 0, 0, 1])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec!["y", "x", "", "z", "x"])) as ArrayRef,
+            ),
+        ],
+        arrow::buffer::Buffer::from([0b00011011u8]),
+    )));
+    let list_array = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+        Some(vec![Some(2)]),
+        Some(vec![Some(1), Some(2)]),
+        None,
+        Some(vec![]),
+        Some(vec![Some(1)]),
+    ]));
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("s", struct_array.data_type().clone(), true),
+        Field::new("l", list_array.data_type().clone(), true),
+    ]));
+    let batch = RecordBatch::try_new(schema, vec![id_array, struct_array, list_array])?;
+    ctx.register_batch("t", batch)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn sort_nested_types() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_nested_table(&ctx)?;
+
+    let sql = "SELECT id, s['b'] AS b FROM t ORDER BY s, id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+---+",
+        "| id | b |",
+        "+----+---+",
+        "| 4  | z |",
+        "| 2  | x |",
+        "| 5  | x |",
+        "| 1  | y |",
+        "| 3  |   |",
+        "+----+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT id, l FROM t ORDER BY l DESC";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+--------+",
+        "| id | l      |",
+        "+----+--------+",
+        "| 3  |        |",
+        "| 1  | [2]    |",
+        "| 2  | [1, 2] |",
+        "| 5  | [1]    |",
+        "| 4  | []     |",
+        "+----+--------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn compare_nested_types() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_nested_table(&ctx)?;
+
+    let sql = "SELECT t1.id AS id1, t2.id AS id2 FROM t t1, t t2 \
+               WHERE t1.s = t2.s AND t1.id < t2.id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+-----+",
+        "| id1 | id2 |",
+        "+-----+-----+",
+        "| 2   | 5   |",
+        "+-----+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    // NULL structs are not distinct from each other
+    let sql = "SELECT t1.id AS id1, t2.id AS id2 FROM t t1, t t2 \
+               WHERE t1.s IS NOT DISTINCT FROM t2.s AND t1.id <= t2.id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+-----+",
+        "| id1 | id2 |",
+        "+-----+-----+",
+        "| 1   | 1   |",
+        "| 2   | 2   |",
+        "| 2   | 5   |",
+        "| 3   | 3   |",
+        "| 4   | 4   |",
+        "| 5   | 5   |",
+        "+-----+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    let sql = "SELECT t2.id FROM t t1, t t2 WHERE t1.id = 5 AND t1.l < t2.l";
+    let actual = execute_to_batches(&ctx, sql).await;
+    #[rustfmt::skip]
+    let expected = vec![
+        "+----+",
+        "| id |",
+        "+----+",
+        "| 1  |",
+        "| 2  |",
+        "+----+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    let sql = "SELECT t2.id FROM t t1, t t2 \
+               WHERE t1.id = 2 AND t1.s IS DISTINCT FROM t2.s";
+    let actual = execute_to_batches(&ctx, sql).await;
+    #[rustfmt::skip]
+    let expected = vec![
+        "+----+",
+        "| id |",
+        "+----+",
+        "| 1  |",
+        "| 3  |",
+        "| 4  |",
+        "+----+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+    Ok(())
+}
@@ -44,8 +44,17 @@ async fn select_values_list() -> Result<()> {
         ctx.sql(sql).await.unwrap_err();
     }
     {
-        let sql = "VALUES (1),(2.0)";
-        ctx.sql(sql).await.unwrap_err();
+        let sql = "VALUES (1),(2.5)";
+        let actual = execute_to_batches(&ctx, sql).await;
+        let expected = vec![
+            "+---------+",
+            "| column1 |",
+            "+---------+",
+            "| 1       |",
+            "| 2.5     |",
+            "+---------+",
+        ];
+        assert_batches_eq!(expected, &actual);
     }
     {
         let sql = "VALUES (1,2), (1,'2')";
@@ -191,6 +200,49 @@ async fn select_values_list() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn select_large_values_list() -> Result<()> {
+    let config = SessionConfig::new()
+        .with_batch_size(2)
+        .set_usize("datafusion.sql_parser.values_table_threshold", 2);
+    let ctx = SessionContext::with_config(config);
+
+    let sql = "SELECT * FROM (VALUES (1, 'a'), (NULL, 'b'), (2.5, NULL)) AS t(c1, c2)";
+    let dataframe = ctx.sql(sql).await?;
+    let expected = "Projection: c1, c2\
+                    \n  Projection: t.column1 AS c1, t.column2 AS c2\
+                    \n    SubqueryAlias: t\
+                    \n      TableScan: values";
+    assert_eq!(expected, format!("{:?}", dataframe.logical_plan()));
+    let schema = dataframe.schema();
+    assert_eq!(schema.field(0).data_type(), &DataType::Float64);
+    assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+
+    let actual = dataframe.collect().await?;
+    let expected = vec![
+        "+-----+----+",
+        "| c1  | c2 |",
+        "+-----+----+",
+        "| 1   | a  |",
+        "|     | b  |",
+        "| 2.5 |    |",
+        "+-----+----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    assert_eq!(actual.len(), 2);
+
+    // the lists with expressions are still planned as expressions
+    let sql = "VALUES (1), (2), (1 + 2)";
+    let dataframe = ctx.sql(sql).await?;
+    assert!(matches!(dataframe.logical_plan(), LogicalPlan::Values(_)));
+
+    // the values are coerced like in the lists planned as expressions
+    let sql = "VALUES (1), (2), ('3')";
+    ctx.sql(sql).await.unwrap_err();
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn select_all() -> Result<()> {
     let ctx = SessionContext::new();
@@ -326,6 +326,7 @@ async fn set_time_zone_good_time_zone_format() {
         .unwrap();
 
     // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
+    // the wall clock time is kept, and is in the session time zone
     let result =
         plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
             .await
@@ -334,10 +335,9 @@ async fn set_time_zone_good_time_zone_format() {
         "+-----------------------------+",
         "| Utf8(\"2000-01-01T00:00:00\") |",
         "+-----------------------------+",
-        "| 2000-01-01T08:00:00+08:00   |",
+        "| 2000-01-01T00:00:00+08:00   |",
         "+-----------------------------+",
     ];
-    // this might break once https://github.com/apache/arrow-rs/issues/1936 fixed
     assert_batches_eq!(expected, &result);
 
     plan_and_collect(&ctx, "SET TIME ZONE = '-08:00'")
@@ -345,6 +345,7 @@ async fn set_time_zone_good_time_zone_format() {
         .unwrap();
 
     // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
+    // the wall clock time is kept, and is in the session time zone
     let result =
         plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
             .await
@@ -353,10 +354,9 @@ async fn set_time_zone_good_time_zone_format() {
         "+-----------------------------+",
         "| Utf8(\"2000-01-01T00:00:00\") |",
         "+-----------------------------+",
-        "| 1999-12-31T16:00:00-08:00   |",
+        "| 2000-01-01T00:00:00-08:00   |",
         "+-----------------------------+",
     ];
-    // this might break once https://github.com/apache/arrow-rs/issues/1936 fixed
     assert_batches_eq!(expected, &result);
 
     plan_and_collect(&ctx, "SET TIME ZONE = '+0800'")
@@ -364,6 +364,7 @@ async fn set_time_zone_good_time_zone_format() {
         .unwrap();
 
     // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
+    // the wall clock time is kept, and is in the session time zone
     let result =
         plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
             .await
@@ -372,10 +373,9 @@ async fn set_time_zone_good_time_zone_format() {
         "+-----------------------------+",
         "| Utf8(\"2000-01-01T00:00:00\") |",
         "+-----------------------------+",
-        "| 2000-01-01T08:00:00+08:00   |",
+        "| 2000-01-01T00:00:00+08:00   |",
         "+-----------------------------+",
     ];
-    // this might break once https://github.com/apache/arrow-rs/issues/1936 fixed
     assert_batches_eq!(expected, &result);
 
     plan_and_collect(&ctx, "SET TIME ZONE = '+08'")
@@ -383,6 +383,7 @@ async fn set_time_zone_good_time_zone_format() {
         .unwrap();
 
     // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
+    // the wall clock time is kept, and is in the session time zone
     let result =
         plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
             .await
@@ -391,107 +392,52 @@ async fn set_time_zone_good_time_zone_format() {
         "+-----------------------------+",
         "| Utf8(\"2000-01-01T00:00:00\") |",
         "+-----------------------------+",
-        "| 2000-01-01T08:00:00+08:00   |",
+        "| 2000-01-01T00:00:00+08:00   |",
         "+-----------------------------+",
     ];
-    // this might break once https://github.com/apache/arrow-rs/issues/1936 fixed
     assert_batches_eq!(expected, &result);
-}
-
-#[tokio::test]
-async fn set_time_zone_bad_time_zone_format() {
-    let ctx =
-        SessionContext::with_config(SessionConfig::new().with_information_schema(true));
-
-    plan_and_collect(&ctx, "SET TIME ZONE = '+08:00:00'")
-        .await
-        .unwrap();
 
-    // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
-    let result =
-        plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
-            .await
-            .unwrap();
-    let expected = vec![
-        "+-----------------------------------------------------+",
-        "| Utf8(\"2000-01-01T00:00:00\")                         |",
-        "+-----------------------------------------------------+",
-        "| 2000-01-01T00:00:00 (Unknown Time Zone '+08:00:00') |",
-        "+-----------------------------------------------------+",
-    ];
-    assert_batches_eq!(expected, &result);
-
-    plan_and_collect(&ctx, "SET TIME ZONE = '08:00'")
+    plan_and_collect(&ctx, "SET TIME ZONE = 'Asia/Taipei'")
         .await
         .unwrap();
 
-    // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
     let result =
         plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
             .await
             .unwrap();
     let expected = vec![
-        "+-------------------------------------------------+",
-        "| Utf8(\"2000-01-01T00:00:00\")                     |",
-        "+-------------------------------------------------+",
-        "| 2000-01-01T00:00:00 (Unknown Time Zone '08:00') |",
-        "+-------------------------------------------------+",
-    ];
-    assert_batches_eq!(expected, &result);
-
-    plan_and_collect(&ctx, "SET TIME ZONE = '08'")
-        .await
-        .unwrap();
-
-    // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
-    let result =
-        plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
-            .await
-            .unwrap();
-    let expected = vec![
-        "+----------------------------------------------+",
-        "| Utf8(\"2000-01-01T00:00:00\")                  |",
-        "+----------------------------------------------+",
-        "| 2000-01-01T00:00:00 (Unknown Time Zone '08') |",
-        "+----------------------------------------------+",
+        "+-----------------------------+",
+        "| Utf8(\"2000-01-01T00:00:00\") |",
+        "+-----------------------------+",
+        "| 2000-01-01T00:00:00+08:00   |",
+        "+-----------------------------+",
     ];
     assert_batches_eq!(expected, &result);
+}
 
-    // we dont support named time zone yet
-    plan_and_collect(&ctx, "SET TIME ZONE = 'Asia/Taipei'")
-        .await
-        .unwrap();
+#[tokio::test]
+async fn set_time_zone_bad_time_zone_format() {
+    let ctx =
+        SessionContext::with_config(SessionConfig::new().with_information_schema(true));
 
-    // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
-    let result =
-        plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
+    for time_zone in ["+08:00:00", "08:00", "08", "Asia/Taipei2"] {
+        plan_and_collect(&ctx, &format!("SET TIME ZONE = '{time_zone}'"))
             .await
             .unwrap();
-    let expected = vec![
-        "+-------------------------------------------------------+",
-        "| Utf8(\"2000-01-01T00:00:00\")                           |",
-        "+-------------------------------------------------------+",
-        "| 2000-01-01T00:00:00 (Unknown Time Zone 'Asia/Taipei') |",
-        "+-------------------------------------------------------+",
-    ];
-    assert_batches_eq!(expected, &result);
 
-    // this is invalid even after we support named time zone
-    plan_and_collect(&ctx, "SET TIME ZONE = 'Asia/Taipei2'")
+        // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
+        let err = plan_and_collect(
+            &ctx,
+            "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ",
+        )
         .await
-        .unwrap();
-
-    // casting UTF-8 to TimestampTZ isn't supported yet, add Timestamp as the middle layer for now
-    let result =
-        plan_and_collect(&ctx, "SELECT '2000-01-01T00:00:00'::TIMESTAMP::TIMESTAMPTZ")
-            .await
-            .unwrap();
-    let expected = vec![
-        "+--------------------------------------------------------+",
-        "| Utf8(\"2000-01-01T00:00:00\")                            |",
-        "+--------------------------------------------------------+",
-        "| 2000-01-01T00:00:00 (Unknown Time Zone 'Asia/Taipei2') |",
-        "+--------------------------------------------------------+",
-    ];
-    assert_batches_eq!(expected, &result);
+        .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            format!(
+                "Invalid time zone '{time_zone}', expected an offset like '+08:00' \
+                 or a name like 'America/New_York'"
+            )
+        );
+    }
 }
@@ -18,8 +18,14 @@
 use super::*;
 use crate::sql::execute_to_batches;
 use datafusion::assert_batches_eq;
+use datafusion::datasource::streaming::{PartitionStream, StreamingTable};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream;
 use datafusion::prelude::SessionContext;
+use datafusion_common::cast::as_int32_array;
 use log::debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[cfg(test)]
 #[ctor::ctor]
@@ -116,3 +122,198 @@ where o_orderstatus in (
 
     Ok(())
 }
+
+/// A partition of values, counting its executions
+struct CountingPartition {
+    schema: SchemaRef,
+    batch: RecordBatch,
+    executions: AtomicUsize,
+}
+
+impl PartitionStream for CountingPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        self.executions.fetch_add(1, Ordering::SeqCst);
+        Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            futures::stream::iter(vec![Ok(self.batch.clone())]),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn uncorrelated_subqueries_execute_once() -> Result<()> {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+    let partitions = [vec![1, 2], vec![3], vec![4]]
+        .iter()
+        .map(|values| {
+            let values = Arc::new(Int32Array::from_slice(values));
+            Ok(vec![RecordBatch::try_new(schema.clone(), vec![values])?])
+        })
+        .collect::<Result<Vec<_>>>()?;
+    ctx.register_table("t1", Arc::new(MemTable::try_new(schema, partitions)?))?;
+
+    let schema = Arc::new(Schema::new(vec![Field::new("b", DataType::Int32, false)]));
+    let values = Arc::new(Int32Array::from_slice([2, 3]));
+    let t2 = Arc::new(CountingPartition {
+        schema: schema.clone(),
+        batch: RecordBatch::try_new(schema.clone(), vec![values])?,
+        executions: AtomicUsize::new(0),
+    });
+    ctx.register_table(
+        "t2",
+        Arc::new(StreamingTable::try_new(schema, vec![t2.clone()])?),
+    )?;
+
+    // the subqueries in the projection and in the disjunction are not
+    // rewritten to joins
+    let sql = "SELECT a, (SELECT max(b) FROM t2) AS m FROM t1 \
+        WHERE a IN (SELECT b FROM t2) OR a > 3 ORDER BY a";
+    let results = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| a | m |",
+        "+---+---+",
+        "| 2 | 3 |",
+        "| 3 | 3 |",
+        "| 4 | 3 |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+    // once per subquery, rather than per partition of t1
+    assert_eq!(t2.executions.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+/// Registers t1 (id, a) and t2 (id, b), the tables of the correlated subqueries
+fn register_correlated_tables(ctx: &SessionContext) -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("a", DataType::Int32, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from_slice([1, 2, 3, 4])),
+            Arc::new(Int32Array::from(vec![Some(1), Some(2), None, Some(4)])),
+        ],
+    )?;
+    ctx.register_table(
+        "t1",
+        Arc::new(MemTable::try_new(schema, vec![vec![batch]])?),
+    )?;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("b", DataType::Int32, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from_slice([1, 2, 2, 4])),
+            Arc::new(Int32Array::from(vec![Some(1), Some(5), None, Some(9)])),
+        ],
+    )?;
+    ctx.register_table(
+        "t2",
+        Arc::new(MemTable::try_new(schema, vec![vec![batch]])?),
+    )?;
+
+    Ok(())
+}
+
+/// Returns the ids of the rows of t1 satisfying `predicate`
+async fn select_ids(ctx: &SessionContext, predicate: &str) -> Vec<i32> {
+    let sql = format!("SELECT t1.id FROM t1 WHERE {predicate} ORDER BY t1.id");
+    execute_to_batches(ctx, &sql)
+        .await
+        .iter()
+        .flat_map(|batch| as_int32_array(batch.column(0)).unwrap().values().to_vec())
+        .collect()
+}
+
+#[tokio::test]
+async fn correlated_subqueries_in_disjunctions() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_correlated_tables(&ctx)?;
+
+    let cases = [
+        (
+            "EXISTS (SELECT 1 FROM t2 WHERE t2.id = t1.id AND t2.b > 3) OR t1.a = 1",
+            vec![1, 2, 4],
+        ),
+        (
+            "NOT EXISTS (SELECT 1 FROM t2 WHERE t2.id = t1.id) OR t1.a = 2",
+            vec![2, 3],
+        ),
+        // the IN of 2 is NULL, as the values of t2 for id 2 contain a NULL
+        (
+            "t1.a IN (SELECT t2.b FROM t2 WHERE t2.id = t1.id) OR t1.id = 3",
+            vec![1, 3],
+        ),
+        // the NOT IN of the NULL of id 3 is true, as there is no value for it
+        (
+            "t1.a NOT IN (SELECT t2.b FROM t2 WHERE t2.id = t1.id) OR t1.id = 1",
+            vec![1, 3, 4],
+        ),
+    ];
+    for (predicate, expected) in cases {
+        assert_eq!(select_ids(&ctx, predicate).await, expected, "{predicate}");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn correlated_subqueries_with_aggregates_and_limits() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_correlated_tables(&ctx)?;
+
+    let cases = [
+        (
+            "EXISTS (SELECT 1 FROM t2 WHERE t2.id = t1.id \
+            GROUP BY t2.id HAVING count(*) > 1)",
+            vec![2],
+        ),
+        (
+            "EXISTS (SELECT t2.b FROM t2 WHERE t2.id = t1.id LIMIT 1)",
+            vec![1, 2, 4],
+        ),
+        // an aggregate without GROUP BY always has a row
+        (
+            "EXISTS (SELECT count(*) FROM t2 WHERE t2.id = t1.id)",
+            vec![1, 2, 3, 4],
+        ),
+        (
+            "t1.a IN (SELECT max(t2.b) FROM t2 WHERE t2.id = t1.id GROUP BY t2.id)",
+            vec![1],
+        ),
+        // the first value of t2 for id 2 is 5, or NULL in descending order
+        (
+            "t1.a IN (SELECT CAST(t2.b - 3 AS INT) FROM t2 WHERE t2.id = t1.id \
+            ORDER BY t2.b LIMIT 1)",
+            vec![2],
+        ),
+        (
+            "t1.a IN (SELECT CAST(t2.b - 3 AS INT) FROM t2 WHERE t2.id = t1.id \
+            ORDER BY t2.b DESC LIMIT 1)",
+            vec![],
+        ),
+        // the rows of t2 are numbered for each id
+        (
+            "t1.id IN (SELECT CAST(row_number() OVER (ORDER BY t2.b) AS INT) \
+            FROM t2 WHERE t2.id = t1.id)",
+            vec![1, 2],
+        ),
+    ];
+    for (predicate, expected) in cases {
+        assert_eq!(select_ids(&ctx, predicate).await, expected, "{predicate}");
+    }
+
+    Ok(())
+}
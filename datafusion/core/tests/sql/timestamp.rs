@@ -1348,9 +1348,9 @@ async fn date_bin() {
         (TIMESTAMP '2021-06-10 17:19:10Z', TIMESTAMP '2001-01-01T00:00:00Z', 0.3)
       ) as t (time, origin, val)";
     let result = try_execute_to_batches(&ctx, sql).await;
-    assert_eq!(
+    assert_contains!(
         result.err().unwrap().to_string(),
-        "Arrow error: External error: This feature is not implemented: DATE_BIN only supports literal values for the origin argument, not arrays"
+        "This feature is not implemented: DATE_BIN only supports literal values for the origin argument, not arrays"
     );
 }
 
@@ -1552,6 +1552,96 @@ async fn cast_timestamp_to_timestamptz() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn cast_timestamp_with_session_time_zone() -> Result<()> {
+    let ctx = SessionContext::new();
+    plan_and_collect(&ctx, "SET TIME ZONE = 'America/New_York'").await?;
+
+    // the wall clock times are kept, with the offsets of the daylight saving time
+    let sql = "SELECT \
+        '2022-01-01T12:00:00'::TIMESTAMP::TIMESTAMPTZ, \
+        '2022-07-01T12:00:00'::TIMESTAMP::TIMESTAMPTZ, \
+        '2022-07-01T12:00:00'::TIMESTAMP::TIMESTAMPTZ::TIMESTAMP";
+    let actual = execute(&ctx, sql).await;
+    let expected = vec![vec![
+        "2022-01-01T12:00:00-05:00",
+        "2022-07-01T12:00:00-04:00",
+        "2022-07-01T12:00:00",
+    ]];
+    assert_eq!(expected, actual);
+
+    // timestamps with another time zone are converted to the session time zone
+    let sql = "SELECT ('2022-07-01T12:00:00'::TIMESTAMP AT TIME ZONE 'UTC')::TIMESTAMP";
+    let actual = execute(&ctx, sql).await;
+    assert_eq!(vec![vec!["2022-07-01T08:00:00"]], actual);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn at_time_zone() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    // the wall clock times in the time zone, in the session time zone: like in
+    // PostgreSQL, the times skipped when the clocks are set forward are shifted
+    // by the offset before the transition, and the repeated times are the ones
+    // of the standard time
+    let sql = "SELECT \
+        '2022-07-01T12:00:00'::TIMESTAMP AT TIME ZONE 'America/New_York', \
+        '2022-03-13T02:30:00'::TIMESTAMP AT TIME ZONE 'America/New_York', \
+        '2022-11-06T01:30:00'::TIMESTAMP AT TIME ZONE 'America/New_York', \
+        '2022-07-01T12:00:00'::TIMESTAMP AT TIME ZONE '+08:00'";
+    let actual = execute(&ctx, sql).await;
+    let expected = vec![vec![
+        "2022-07-01T16:00:00+00:00",
+        "2022-03-13T07:30:00+00:00",
+        "2022-11-06T06:30:00+00:00",
+        "2022-07-01T04:00:00+00:00",
+    ]];
+    assert_eq!(expected, actual);
+
+    // the wall clock times of timestamps with a time zone
+    let sql = "SELECT \
+        '2022-11-06T05:30:00'::TIMESTAMP::TIMESTAMPTZ AT TIME ZONE 'America/New_York', \
+        '2022-11-06T06:30:00'::TIMESTAMP::TIMESTAMPTZ AT TIME ZONE 'America/New_York', \
+        '2022-11-06T06:30:00' AT TIME ZONE 'Asia/Tokyo'";
+    let actual = execute(&ctx, sql).await;
+    let expected = vec![vec![
+        "2022-11-06T01:30:00",
+        "2022-11-06T01:30:00",
+        "2022-11-06T15:30:00",
+    ]];
+    assert_eq!(expected, actual);
+
+    let sql = "SELECT 1 AT TIME ZONE 'UTC'";
+    let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: AT TIME ZONE expects a timestamp, got Int64"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn extract_with_time_zone() -> Result<()> {
+    let ctx = SessionContext::new();
+    plan_and_collect(&ctx, "SET TIME ZONE = 'America/New_York'").await?;
+
+    // the parts of the timestamps are the ones of their wall clock times
+    let sql =
+        "SELECT date_part('hour', ts), date_part('day', ts), date_part('month', ts) \
+        FROM (VALUES \
+            ('2022-01-01T03:00:00'::TIMESTAMP AT TIME ZONE 'UTC'), \
+            ('2022-07-01T03:00:00'::TIMESTAMP AT TIME ZONE 'UTC')\
+        ) AS t(ts)";
+    let actual = execute(&ctx, sql).await;
+    let expected = vec![vec!["22", "31", "12"], vec!["23", "30", "6"]];
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cast_to_time() -> Result<()> {
     let ctx = SessionContext::new();
@@ -1744,3 +1834,68 @@ async fn test_ts_dt_binary_ops() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn duration_arithmetic_and_aggregates() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        Field::new("d_ns", DataType::Duration(TimeUnit::Nanosecond), false),
+        Field::new("d_s", DataType::Duration(TimeUnit::Second), false),
+    ]));
+    let data = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(TimestampNanosecondArray::from_slice([
+                1672531200000000000,
+                1672531260000000000,
+            ])),
+            Arc::new(DurationNanosecondArray::from_slice([
+                1500000000, 3000000000,
+            ])),
+            Arc::new(DurationSecondArray::from_slice([2, 1])),
+        ],
+    )?;
+    ctx.register_batch("t", data)?;
+
+    let sql = "SELECT ts + d_ns AS a, ts - d_s AS b, d_ns + d_s AS c, d_ns > d_s AS d \
+               FROM t ORDER BY ts";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------------------------+---------------------+--------+-------+",
+        "| a                       | b                   | c      | d     |",
+        "+-------------------------+---------------------+--------+-------+",
+        "| 2023-01-01T00:00:01.500 | 2022-12-31T23:59:58 | PT3.5S | false |",
+        "| 2023-01-01T00:01:03     | 2023-01-01T00:00:59 | PT4S   | true  |",
+        "+-------------------------+---------------------+--------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT sum(d_ns) AS s, avg(d_ns) AS a, min(d_s) AS mi, max(d_s) AS ma \
+               FROM t";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+--------+---------+------+------+",
+        "| s      | a       | mi   | ma   |",
+        "+--------+---------+------+------+",
+        "| PT4.5S | PT2.25S | PT1S | PT2S |",
+        "+--------+---------+------+------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT count(*) AS c FROM t WHERE d_ns > INTERVAL '2 seconds'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec!["+---+", "| c |", "+---+", "| 1 |", "+---+"];
+    assert_batches_eq!(expected, &actual);
+
+    // the interval is cast back to a duration to be compared
+    let sql = "SELECT d_ns = CAST(d_ns AS INTERVAL) AS e FROM t";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------+", "| e    |", "+------+", "| true |", "| true |", "+------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    Ok(())
+}
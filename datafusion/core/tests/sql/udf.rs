@@ -18,11 +18,15 @@
 use super::*;
 use arrow::compute::add;
 use datafusion::{
+    catalog::schema::{MemorySchemaProvider, SchemaProvider},
     execution::registry::FunctionRegistry,
     physical_plan::{expressions::AvgAccumulator, functions::make_scalar_function},
 };
-use datafusion_common::{cast::as_int32_array, ScalarValue};
-use datafusion_expr::{create_udaf, Accumulator, LogicalPlanBuilder};
+use datafusion_common::{
+    cast::{as_int32_array, as_int64_array},
+    ScalarValue,
+};
+use datafusion_expr::{create_udaf, Accumulator, FunctionParameter, LogicalPlanBuilder};
 
 /// test that casting happens on udfs.
 /// c11 is f32, but `custom_sqrt` requires f64. Casting happens but the logical plan and
@@ -121,6 +125,53 @@ async fn scalar_udf() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn scalar_udf_named_arguments() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE t AS VALUES (1, 2), (10, 12)").await?;
+
+    let myfunc = |args: &[ArrayRef]| {
+        let l = as_int64_array(&args[0])?;
+        let r = as_int64_array(&args[1])?;
+        Ok(Arc::new(add(l, r)?) as ArrayRef)
+    };
+    let my_add = create_udf(
+        "my_add",
+        vec![DataType::Int64, DataType::Int64],
+        Arc::new(DataType::Int64),
+        Volatility::Immutable,
+        make_scalar_function(myfunc),
+    )
+    .with_parameters(vec![
+        FunctionParameter::required("x"),
+        FunctionParameter::optional("y", lit(100i64)),
+    ]);
+    ctx.register_udf(my_add);
+
+    let sql = "SELECT my_add(column1) AS a, my_add(y => column2, x => column1) AS b, \
+        my_add(column1, y => 1000) AS c FROM t";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+----+------+",
+        "| a   | b  | c    |",
+        "+-----+----+------+",
+        "| 101 | 3  | 1001 |",
+        "| 110 | 22 | 1010 |",
+        "+-----+----+------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let err = ctx
+        .sql("SELECT my_add(column1, z => 1) FROM t")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Function my_add has no parameter named z"
+    );
+    Ok(())
+}
+
 /// tests the creation, registration and usage of a UDAF
 #[tokio::test]
 async fn simple_udaf() -> Result<()> {
@@ -223,3 +274,52 @@ async fn udaf_as_window_func() -> Result<()> {
     assert_eq!(format!("{:?}", dataframe.logical_plan()), expected);
     Ok(())
 }
+
+#[tokio::test]
+async fn schema_qualified_udf() -> Result<()> {
+    let batch = RecordBatch::try_from_iter(vec![
+        ("a", Arc::new(Int32Array::from_slice([1, 10])) as ArrayRef),
+        ("b", Arc::new(Int32Array::from_slice([2, 12])) as ArrayRef),
+    ])?;
+
+    let ctx = SessionContext::new();
+    ctx.register_batch("t", batch)?;
+
+    let myfunc = |args: &[ArrayRef]| {
+        let l = as_int32_array(&args[0])?;
+        let r = as_int32_array(&args[1])?;
+        Ok(Arc::new(add(l, r)?) as ArrayRef)
+    };
+    let schema = Arc::new(MemorySchemaProvider::new());
+    schema.register_udf(Arc::new(create_udf(
+        "my_add",
+        vec![DataType::Int32, DataType::Int32],
+        Arc::new(DataType::Int32),
+        Volatility::Immutable,
+        make_scalar_function(myfunc),
+    )))?;
+    ctx.catalog("datafusion")
+        .unwrap()
+        .register_schema("tenant", schema)?;
+
+    let sql = "SELECT a, b, tenant.my_add(a, b) AS sum FROM t";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+----+-----+",
+        "| a  | b  | sum |",
+        "+----+----+-----+",
+        "| 1  | 2  | 3   |",
+        "| 10 | 12 | 22  |",
+        "+----+----+-----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the function is not visible outside of its schema
+    let err = ctx.sql("SELECT my_add(a, b) FROM t").await.unwrap_err();
+    assert!(
+        err.to_string().contains("Invalid function 'my_add'"),
+        "{err}"
+    );
+
+    Ok(())
+}
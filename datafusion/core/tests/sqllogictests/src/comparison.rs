@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use sqllogictest::{AsyncDB, DBOutput};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::error::{DFSqlLogicTestError, Result};
+use crate::postgres::Postgres;
+use crate::DataFusion;
+
+/// A statement for which DataFusion and Postgres produced different results
+#[derive(Debug)]
+pub struct Divergence {
+    pub file_name: String,
+    pub sql: String,
+    pub datafusion: String,
+    pub postgres: String,
+}
+
+/// Runs each statement on both DataFusion and Postgres, recording the
+/// statements whose results differ.
+///
+/// The DataFusion result is returned, so that it is still validated against
+/// the expected results of the test file.
+pub struct Comparison {
+    datafusion: DataFusion,
+    postgres: Postgres,
+    file_name: String,
+    divergences: Arc<Mutex<Vec<Divergence>>>,
+}
+
+impl Comparison {
+    pub fn new(
+        datafusion: DataFusion,
+        postgres: Postgres,
+        file_name: String,
+        divergences: Arc<Mutex<Vec<Divergence>>>,
+    ) -> Self {
+        Self {
+            datafusion,
+            postgres,
+            file_name,
+            divergences,
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncDB for Comparison {
+    type Error = DFSqlLogicTestError;
+
+    async fn run(&mut self, sql: &str) -> Result<DBOutput> {
+        let datafusion = self.datafusion.run(sql).await;
+        let postgres = self.postgres.run(sql).await;
+
+        let (datafusion_summary, postgres_summary) =
+            (summarize(&datafusion), summarize(&postgres));
+        if datafusion_summary != postgres_summary {
+            self.divergences.lock().unwrap().push(Divergence {
+                file_name: self.file_name.clone(),
+                sql: sql.to_string(),
+                datafusion: datafusion_summary.describe(),
+                postgres: postgres_summary.describe(),
+            });
+        }
+        datafusion
+    }
+
+    fn engine_name(&self) -> &str {
+        "DataFusion and Postgres"
+    }
+
+    async fn sleep(dur: Duration) {
+        tokio::time::sleep(dur).await;
+    }
+}
+
+/// The part of a statement result that must match between engines. Row
+/// order is ignored, as it is only defined for queries with an `ORDER BY`,
+/// and so are error messages and the number of rows changed by a statement.
+#[derive(Debug)]
+enum Summary {
+    Error(String),
+    StatementComplete,
+    Rows(Vec<Vec<String>>),
+}
+
+impl Summary {
+    fn describe(&self) -> String {
+        match self {
+            Self::Error(e) => format!("error: {e}"),
+            Self::StatementComplete => "statement complete".to_string(),
+            Self::Rows(rows) => rows
+                .iter()
+                .map(|row| row.join(" "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+impl PartialEq for Summary {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Error(_), Self::Error(_)) => true,
+            (Self::StatementComplete, Self::StatementComplete) => true,
+            (Self::Rows(a), Self::Rows(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn summarize(result: &Result<DBOutput>) -> Summary {
+    match result {
+        Err(e) => Summary::Error(e.to_string()),
+        Ok(DBOutput::Rows { rows, .. }) => {
+            let mut rows = rows.clone();
+            rows.sort();
+            Summary::Rows(rows)
+        }
+        Ok(_) => Summary::StatementComplete,
+    }
+}
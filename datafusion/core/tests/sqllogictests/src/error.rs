@@ -38,6 +38,9 @@ pub enum DFSqlLogicTestError {
     /// Error from arrow-rs
     #[error("Arrow error: {0}")]
     Arrow(ArrowError),
+    /// Error from Postgres, when comparing results with Postgres
+    #[error("Postgres error: {0}")]
+    Postgres(tokio_postgres::Error),
     /// Generic error
     #[error("Other Error: {0}")]
     Other(String),
@@ -67,6 +70,12 @@ impl From<ArrowError> for DFSqlLogicTestError {
     }
 }
 
+impl From<tokio_postgres::Error> for DFSqlLogicTestError {
+    fn from(value: tokio_postgres::Error) -> Self {
+        DFSqlLogicTestError::Postgres(value)
+    }
+}
+
 impl From<String> for DFSqlLogicTestError {
     fn from(value: String) -> Self {
         DFSqlLogicTestError::Other(value)
@@ -37,7 +37,13 @@ pub async fn insert(ctx: &SessionContext, insert_stmt: SQLStatement) -> Result<D
         SQLStatement::Insert {
             table_name, source, ..
         } => {
-            table_reference = object_name_to_table_reference(table_name)?;
+            table_reference = object_name_to_table_reference(
+                table_name,
+                ctx.copied_config()
+                    .config_options()
+                    .sql_parser
+                    .enable_ident_normalization,
+            )?;
 
             // Todo: check columns match table schema
             match *source.body {
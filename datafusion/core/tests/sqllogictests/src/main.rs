@@ -23,19 +23,23 @@ use log::info;
 use normalize::convert_batches;
 use sqllogictest::DBOutput;
 use sqlparser::ast::Statement as SQLStatement;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::error::{DFSqlLogicTestError, Result};
 use crate::insert::insert;
 
+mod comparison;
 mod error;
 mod insert;
 mod normalize;
+mod postgres;
 mod setup;
 mod utils;
 
 const TEST_DIRECTORY: &str = "tests/sqllogictests/test_files";
+/// Test files whose results are expected to match Postgres, see `--postgres`
+const PG_COMPAT_DIRECTORY: &str = "tests/sqllogictests/test_files/pg_compat";
 
 pub struct DataFusion {
     ctx: SessionContext,
@@ -79,20 +83,19 @@ pub async fn main() -> Result<()> {
 pub async fn main() -> Result<()> {
     // Enable logging (e.g. set RUST_LOG=debug to see debug logs)
 
+    use comparison::Comparison;
+    use postgres::Postgres;
     use sqllogictest::{default_validator, update_test_file};
+    use std::sync::{Arc, Mutex};
     env_logger::init();
 
     let options = Options::new();
 
-    // default to all files in test directory filtering based on name
-    let files: Vec<_> = std::fs::read_dir(TEST_DIRECTORY)
-        .unwrap()
-        .map(|path| path.unwrap().path())
-        .filter(|path| options.check_test_file(path.as_path()))
-        .collect();
+    let files = test_files(&options);
 
     info!("Running test files {:?}", files);
 
+    let divergences = Arc::new(Mutex::new(vec![]));
     for path in files {
         println!("Running: {}", path.display());
 
@@ -100,27 +103,75 @@ pub async fn main() -> Result<()> {
 
         // Create the test runner
         let ctx = context_for_test_file(&file_name).await;
-        let mut runner = sqllogictest::Runner::new(DataFusion { ctx, file_name });
+        let datafusion = DataFusion {
+            ctx,
+            file_name: file_name.clone(),
+        };
 
         // run each file using its own new DB
         //
         // We could run these tests in parallel eventually if we wanted.
         if options.complete_mode {
             info!("Using complete mode to complete {}", path.display());
+            let runner = sqllogictest::Runner::new(datafusion);
             let col_separator = " ";
             let validator = default_validator;
             update_test_file(path, runner, col_separator, validator)
                 .await
                 .map_err(|e| e.to_string())?;
+        } else if options.postgres {
+            info!("Comparing {} with Postgres", path.display());
+            let postgres = Postgres::connect(file_name.clone()).await?;
+            let comparison =
+                Comparison::new(datafusion, postgres, file_name, divergences.clone());
+            let mut runner = sqllogictest::Runner::new(comparison);
+            runner.run_file_async(path).await?;
         } else {
             // run the test normally:
+            let mut runner = sqllogictest::Runner::new(datafusion);
             runner.run_file_async(path).await?;
         }
     }
 
+    let divergences = divergences.lock().unwrap();
+    if !divergences.is_empty() {
+        for d in divergences.iter() {
+            println!(
+                "[{}] DataFusion and Postgres results differ for \"{}\"\n\
+                 DataFusion:\n{}\nPostgres:\n{}\n",
+                d.file_name, d.sql, d.datafusion, d.postgres
+            );
+        }
+        return Err(DFSqlLogicTestError::Other(format!(
+            "{} statements returned different results on DataFusion and Postgres",
+            divergences.len()
+        )));
+    }
+
     Ok(())
 }
 
+/// Returns the test files to run, only those in [`PG_COMPAT_DIRECTORY`] when
+/// comparing with Postgres
+fn test_files(options: &Options) -> Vec<PathBuf> {
+    let directories = if options.postgres {
+        vec![PG_COMPAT_DIRECTORY]
+    } else {
+        vec![TEST_DIRECTORY, PG_COMPAT_DIRECTORY]
+    };
+
+    // default to all files in test directories filtering based on name
+    directories
+        .into_iter()
+        .flat_map(|directory| {
+            std::fs::read_dir(directory)
+                .unwrap()
+                .map(|path| path.unwrap().path())
+        })
+        .filter(|path| path.is_file() && options.check_test_file(path.as_path()))
+        .collect()
+}
+
 /// Create a SessionContext, configured for the specific test
 async fn context_for_test_file(file_name: &str) -> SessionContext {
     match file_name {
@@ -164,6 +215,10 @@ struct Options {
 
     /// Auto complete mode to fill out expected results
     complete_mode: bool,
+
+    /// Run the files in [`PG_COMPAT_DIRECTORY`] on both DataFusion and
+    /// Postgres, failing if their results differ
+    postgres: bool,
 }
 
 impl Options {
@@ -171,6 +226,7 @@ impl Options {
         let args: Vec<_> = std::env::args().collect();
 
         let complete_mode = args.iter().any(|a| a == "--complete");
+        let postgres = args.iter().any(|a| a == "--postgres");
 
         // treat args after the first as filters to run (substring matching)
         let filters = if !args.is_empty() {
@@ -186,6 +242,7 @@ impl Options {
         Self {
             filters,
             complete_mode,
+            postgres,
         }
     }
 
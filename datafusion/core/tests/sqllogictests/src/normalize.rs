@@ -19,6 +19,7 @@ use crate::error::{DFSqlLogicTestError, Result};
 use arrow::{array::ArrayRef, datatypes::DataType, record_batch::RecordBatch};
 use datafusion::error::DataFusionError;
 use sqllogictest::{ColumnType, DBOutput};
+use tokio_postgres::types::Type;
 
 /// Converts `batches` to a DBOutput as expected by sqllogicteset.
 ///
@@ -93,3 +94,17 @@ pub fn cell_to_string(col: &ArrayRef, row: usize) -> Result<String> {
 
     Ok(s)
 }
+
+/// Normalizes a value returned by Postgres as text like [`cell_to_string`],
+/// so that results of both engines can be compared
+pub fn pg_cell_to_string(value: Option<&str>, pg_type: &Type) -> String {
+    match value {
+        None => "NULL".to_string(),
+        Some("") if matches!(*pg_type, Type::TEXT | Type::VARCHAR | Type::BPCHAR) => {
+            "(empty)".to_string()
+        }
+        Some("t") if *pg_type == Type::BOOL => "true".to_string(),
+        Some("f") if *pg_type == Type::BOOL => "false".to_string(),
+        Some(value) => value.to_string(),
+    }
+}
@@ -0,0 +1,124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use log::error;
+use sqllogictest::{ColumnType, DBOutput};
+use std::time::Duration;
+use tokio_postgres::{Client, NoTls, SimpleQueryMessage};
+
+use crate::error::Result;
+use crate::normalize::pg_cell_to_string;
+
+/// Connection used when `PG_URI` is not set
+const DEFAULT_PG_URI: &str = "postgresql://postgres@127.0.0.1:5432/test";
+
+/// Runs sqllogictest statements against Postgres
+pub struct Postgres {
+    client: Client,
+    file_name: String,
+}
+
+impl Postgres {
+    /// Connects to the Postgres database at the `PG_URI` environment variable
+    /// and creates an empty schema for `file_name`, so that tables created by
+    /// different test files do not interfere with each other
+    pub async fn connect(file_name: String) -> Result<Self> {
+        let uri = std::env::var("PG_URI").unwrap_or_else(|_| DEFAULT_PG_URI.to_string());
+        let (client, connection) = tokio_postgres::connect(&uri, NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                error!("Postgres connection error: {e}");
+            }
+        });
+
+        let schema = schema_name(&file_name);
+        client
+            .batch_execute(&format!(
+                "DROP SCHEMA IF EXISTS {schema} CASCADE;
+                 CREATE SCHEMA {schema};
+                 SET search_path TO {schema};"
+            ))
+            .await?;
+        Ok(Self { client, file_name })
+    }
+}
+
+/// Returns a valid identifier for the schema of a test file
+fn schema_name(file_name: &str) -> String {
+    let name = file_name
+        .trim_end_matches(".slt")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    format!("slt_{}", name.to_lowercase())
+}
+
+#[async_trait]
+impl sqllogictest::AsyncDB for Postgres {
+    type Error = crate::error::DFSqlLogicTestError;
+
+    async fn run(&mut self, sql: &str) -> Result<DBOutput> {
+        println!(
+            "[{}] Running query on Postgres: \"{}\"",
+            self.file_name, sql
+        );
+        let statement = self.client.prepare(sql).await?;
+        if statement.columns().is_empty() {
+            let count = self.client.execute(&statement, &[]).await?;
+            return Ok(DBOutput::StatementComplete(count));
+        }
+
+        // the simple query protocol returns the values as text, formatted by
+        // Postgres, while the prepared statement describes their types
+        let types = statement
+            .columns()
+            .iter()
+            .map(|c| c.type_().clone())
+            .collect::<Vec<_>>();
+        let rows = self
+            .client
+            .simple_query(sql)
+            .await?
+            .into_iter()
+            .filter_map(|message| match message {
+                SimpleQueryMessage::Row(row) => Some(row),
+                _ => None,
+            })
+            .map(|row| {
+                types
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| pg_cell_to_string(row.get(i), t))
+                    .collect()
+            })
+            .collect();
+
+        Ok(DBOutput::Rows {
+            types: vec![ColumnType::Any; types.len()],
+            rows,
+        })
+    }
+
+    fn engine_name(&self) -> &str {
+        "Postgres"
+    }
+
+    async fn sleep(dur: Duration) {
+        tokio::time::sleep(dur).await;
+    }
+}
@@ -496,7 +496,7 @@ impl ExecutionPlan for TopKExec {
     fn statistics(&self) -> Statistics {
         // to improve the optimizability of this plan
         // better statistics inference could be provided
-        Statistics::default()
+        Statistics::new_unknown(&self.schema())
     }
 }
 
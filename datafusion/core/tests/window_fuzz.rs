@@ -32,7 +32,7 @@ use datafusion::physical_plan::windows::{
 };
 use datafusion_expr::{
     AggregateFunction, BuiltInWindowFunction, WindowFrame, WindowFrameBound,
-    WindowFrameUnits, WindowFunction,
+    WindowFrameExclude, WindowFrameUnits, WindowFunction,
 };
 
 use datafusion::prelude::{SessionConfig, SessionContext};
@@ -214,6 +214,7 @@ async fn run_window_test(
             units,
             start_bound: WindowFrameBound::Preceding(ScalarValue::Int32(Some(preceding))),
             end_bound: WindowFrameBound::Following(ScalarValue::Int32(Some(following))),
+            exclude: WindowFrameExclude::NoOthers,
         },
         // In window queries, window frame boundary should be Uint64
         WindowFrameUnits::Rows => WindowFrame {
@@ -224,6 +225,7 @@ async fn run_window_test(
             end_bound: WindowFrameBound::Following(ScalarValue::UInt64(Some(
                 following as u64,
             ))),
+            exclude: WindowFrameExclude::NoOthers,
         },
         // Once GROUPS support is added construct window frame for this case also
         _ => todo!(),
@@ -72,41 +72,58 @@ impl fmt::Display for AggregateFunction {
     }
 }
 
+/// The SQL names of the built-in aggregate functions
+const NAME_TO_FUNCTION: &[(&str, AggregateFunction)] = &[
+    ("min", AggregateFunction::Min),
+    ("max", AggregateFunction::Max),
+    ("count", AggregateFunction::Count),
+    ("avg", AggregateFunction::Avg),
+    ("mean", AggregateFunction::Avg),
+    ("sum", AggregateFunction::Sum),
+    ("median", AggregateFunction::Median),
+    ("approx_distinct", AggregateFunction::ApproxDistinct),
+    ("array_agg", AggregateFunction::ArrayAgg),
+    ("var", AggregateFunction::Variance),
+    ("var_samp", AggregateFunction::Variance),
+    ("var_pop", AggregateFunction::VariancePop),
+    ("stddev", AggregateFunction::Stddev),
+    ("stddev_samp", AggregateFunction::Stddev),
+    ("stddev_pop", AggregateFunction::StddevPop),
+    ("covar", AggregateFunction::Covariance),
+    ("covar_samp", AggregateFunction::Covariance),
+    ("covar_pop", AggregateFunction::CovariancePop),
+    ("corr", AggregateFunction::Correlation),
+    (
+        "approx_percentile_cont",
+        AggregateFunction::ApproxPercentileCont,
+    ),
+    (
+        "approx_percentile_cont_with_weight",
+        AggregateFunction::ApproxPercentileContWithWeight,
+    ),
+    ("approx_median", AggregateFunction::ApproxMedian),
+    ("grouping", AggregateFunction::Grouping),
+];
+
 impl FromStr for AggregateFunction {
     type Err = DataFusionError;
     fn from_str(name: &str) -> Result<AggregateFunction> {
-        Ok(match name {
-            "min" => AggregateFunction::Min,
-            "max" => AggregateFunction::Max,
-            "count" => AggregateFunction::Count,
-            "avg" => AggregateFunction::Avg,
-            "mean" => AggregateFunction::Avg,
-            "sum" => AggregateFunction::Sum,
-            "median" => AggregateFunction::Median,
-            "approx_distinct" => AggregateFunction::ApproxDistinct,
-            "array_agg" => AggregateFunction::ArrayAgg,
-            "var" => AggregateFunction::Variance,
-            "var_samp" => AggregateFunction::Variance,
-            "var_pop" => AggregateFunction::VariancePop,
-            "stddev" => AggregateFunction::Stddev,
-            "stddev_samp" => AggregateFunction::Stddev,
-            "stddev_pop" => AggregateFunction::StddevPop,
-            "covar" => AggregateFunction::Covariance,
-            "covar_samp" => AggregateFunction::Covariance,
-            "covar_pop" => AggregateFunction::CovariancePop,
-            "corr" => AggregateFunction::Correlation,
-            "approx_percentile_cont" => AggregateFunction::ApproxPercentileCont,
-            "approx_percentile_cont_with_weight" => {
-                AggregateFunction::ApproxPercentileContWithWeight
-            }
-            "approx_median" => AggregateFunction::ApproxMedian,
-            "grouping" => AggregateFunction::Grouping,
-            _ => {
-                return Err(DataFusionError::Plan(format!(
+        NAME_TO_FUNCTION
+            .iter()
+            .find(|(function_name, _)| *function_name == name)
+            .map(|(_, fun)| fun.clone())
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
                     "There is no built-in function named {name}"
-                )));
-            }
-        })
+                ))
+            })
+    }
+}
+
+impl AggregateFunction {
+    /// Returns the SQL names of the built-in aggregate functions, including the aliases
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        NAME_TO_FUNCTION.iter().map(|(name, _)| *name)
     }
 }
 
@@ -176,13 +193,20 @@ pub fn signature(fun: &AggregateFunction) -> Signature {
                 .chain(TIMESTAMPS.iter())
                 .chain(DATES.iter())
                 .chain(TIMES.iter())
+                .chain(DURATIONS.iter())
+                .cloned()
+                .collect::<Vec<_>>();
+            Signature::uniform(1, valid, Volatility::Immutable)
+        }
+        AggregateFunction::Avg | AggregateFunction::Sum => {
+            let valid = NUMERICS
+                .iter()
+                .chain(DURATIONS.iter())
                 .cloned()
                 .collect::<Vec<_>>();
             Signature::uniform(1, valid, Volatility::Immutable)
         }
-        AggregateFunction::Avg
-        | AggregateFunction::Sum
-        | AggregateFunction::Variance
+        AggregateFunction::Variance
         | AggregateFunction::VariancePop
         | AggregateFunction::Stddev
         | AggregateFunction::StddevPop
@@ -174,9 +174,18 @@ pub enum BuiltinScalarFunction {
     Struct,
     /// arrow_typeof
     ArrowTypeof,
+    /// try_divide
+    TryDivide,
+    /// try_to_timestamp
+    TryToTimestamp,
 }
 
 impl BuiltinScalarFunction {
+    /// Returns the SQL names of the built-in scalar functions, including the aliases
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        NAME_TO_FUNCTION.iter().map(|(name, _)| *name)
+    }
+
     /// an allowlist of functions to take zero arguments, so that they will get special treatment
     /// while executing.
     pub fn supports_zero_argument(&self) -> bool {
@@ -261,6 +270,8 @@ impl BuiltinScalarFunction {
             BuiltinScalarFunction::Struct => Volatility::Immutable,
             BuiltinScalarFunction::FromUnixtime => Volatility::Immutable,
             BuiltinScalarFunction::ArrowTypeof => Volatility::Immutable,
+            BuiltinScalarFunction::TryDivide => Volatility::Immutable,
+            BuiltinScalarFunction::TryToTimestamp => Volatility::Immutable,
 
             // Stable builtin functions
             BuiltinScalarFunction::Now => Volatility::Stable,
@@ -272,6 +283,29 @@ impl BuiltinScalarFunction {
             BuiltinScalarFunction::Uuid => Volatility::Volatile,
         }
     }
+
+    /// Returns the index of the argument in which the function is monotonically
+    /// non-decreasing when all its other arguments are constant, if any. The
+    /// monotonicity only holds for numeric and temporal values of that argument
+    /// (e.g. `to_timestamp` of strings does not follow their lexicographic order).
+    pub fn monotonic_argument(&self) -> Option<usize> {
+        match self {
+            BuiltinScalarFunction::Ceil
+            | BuiltinScalarFunction::Floor
+            | BuiltinScalarFunction::Trunc
+            | BuiltinScalarFunction::Round
+            | BuiltinScalarFunction::Exp
+            | BuiltinScalarFunction::Signum
+            | BuiltinScalarFunction::Atan
+            | BuiltinScalarFunction::ToTimestamp
+            | BuiltinScalarFunction::ToTimestampMillis
+            | BuiltinScalarFunction::ToTimestampMicros
+            | BuiltinScalarFunction::ToTimestampSeconds
+            | BuiltinScalarFunction::FromUnixtime => Some(0),
+            BuiltinScalarFunction::DateTrunc | BuiltinScalarFunction::DateBin => Some(1),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for BuiltinScalarFunction {
@@ -281,97 +315,114 @@ impl fmt::Display for BuiltinScalarFunction {
     }
 }
 
+/// The SQL names of the built-in scalar functions
+const NAME_TO_FUNCTION: &[(&str, BuiltinScalarFunction)] = &[
+    // math functions
+    ("abs", BuiltinScalarFunction::Abs),
+    ("acos", BuiltinScalarFunction::Acos),
+    ("asin", BuiltinScalarFunction::Asin),
+    ("atan", BuiltinScalarFunction::Atan),
+    ("atan2", BuiltinScalarFunction::Atan2),
+    ("ceil", BuiltinScalarFunction::Ceil),
+    ("cos", BuiltinScalarFunction::Cos),
+    ("exp", BuiltinScalarFunction::Exp),
+    ("floor", BuiltinScalarFunction::Floor),
+    ("ln", BuiltinScalarFunction::Ln),
+    ("log", BuiltinScalarFunction::Log),
+    ("log10", BuiltinScalarFunction::Log10),
+    ("log2", BuiltinScalarFunction::Log2),
+    ("power", BuiltinScalarFunction::Power),
+    ("pow", BuiltinScalarFunction::Power),
+    ("round", BuiltinScalarFunction::Round),
+    ("signum", BuiltinScalarFunction::Signum),
+    ("sin", BuiltinScalarFunction::Sin),
+    ("sqrt", BuiltinScalarFunction::Sqrt),
+    ("tan", BuiltinScalarFunction::Tan),
+    ("trunc", BuiltinScalarFunction::Trunc),
+    // conditional functions
+    ("coalesce", BuiltinScalarFunction::Coalesce),
+    // array functions
+    ("make_array", BuiltinScalarFunction::MakeArray),
+    // string functions
+    ("ascii", BuiltinScalarFunction::Ascii),
+    ("bit_length", BuiltinScalarFunction::BitLength),
+    ("btrim", BuiltinScalarFunction::Btrim),
+    ("char_length", BuiltinScalarFunction::CharacterLength),
+    ("character_length", BuiltinScalarFunction::CharacterLength),
+    ("concat", BuiltinScalarFunction::Concat),
+    ("concat_ws", BuiltinScalarFunction::ConcatWithSeparator),
+    ("chr", BuiltinScalarFunction::Chr),
+    ("current_date", BuiltinScalarFunction::CurrentDate),
+    ("current_time", BuiltinScalarFunction::CurrentTime),
+    ("date_part", BuiltinScalarFunction::DatePart),
+    ("datepart", BuiltinScalarFunction::DatePart),
+    ("date_trunc", BuiltinScalarFunction::DateTrunc),
+    ("datetrunc", BuiltinScalarFunction::DateTrunc),
+    ("date_bin", BuiltinScalarFunction::DateBin),
+    ("initcap", BuiltinScalarFunction::InitCap),
+    ("left", BuiltinScalarFunction::Left),
+    ("length", BuiltinScalarFunction::CharacterLength),
+    ("lower", BuiltinScalarFunction::Lower),
+    ("lpad", BuiltinScalarFunction::Lpad),
+    ("ltrim", BuiltinScalarFunction::Ltrim),
+    ("md5", BuiltinScalarFunction::MD5),
+    ("nullif", BuiltinScalarFunction::NullIf),
+    ("octet_length", BuiltinScalarFunction::OctetLength),
+    ("random", BuiltinScalarFunction::Random),
+    ("regexp_replace", BuiltinScalarFunction::RegexpReplace),
+    ("repeat", BuiltinScalarFunction::Repeat),
+    ("replace", BuiltinScalarFunction::Replace),
+    ("reverse", BuiltinScalarFunction::Reverse),
+    ("right", BuiltinScalarFunction::Right),
+    ("rpad", BuiltinScalarFunction::Rpad),
+    ("rtrim", BuiltinScalarFunction::Rtrim),
+    ("sha224", BuiltinScalarFunction::SHA224),
+    ("sha256", BuiltinScalarFunction::SHA256),
+    ("sha384", BuiltinScalarFunction::SHA384),
+    ("sha512", BuiltinScalarFunction::SHA512),
+    ("digest", BuiltinScalarFunction::Digest),
+    ("split_part", BuiltinScalarFunction::SplitPart),
+    ("starts_with", BuiltinScalarFunction::StartsWith),
+    ("strpos", BuiltinScalarFunction::Strpos),
+    ("substr", BuiltinScalarFunction::Substr),
+    ("to_hex", BuiltinScalarFunction::ToHex),
+    ("to_timestamp", BuiltinScalarFunction::ToTimestamp),
+    (
+        "to_timestamp_millis",
+        BuiltinScalarFunction::ToTimestampMillis,
+    ),
+    (
+        "to_timestamp_micros",
+        BuiltinScalarFunction::ToTimestampMicros,
+    ),
+    (
+        "to_timestamp_seconds",
+        BuiltinScalarFunction::ToTimestampSeconds,
+    ),
+    ("now", BuiltinScalarFunction::Now),
+    ("translate", BuiltinScalarFunction::Translate),
+    ("trim", BuiltinScalarFunction::Trim),
+    ("upper", BuiltinScalarFunction::Upper),
+    ("uuid", BuiltinScalarFunction::Uuid),
+    ("regexp_match", BuiltinScalarFunction::RegexpMatch),
+    ("struct", BuiltinScalarFunction::Struct),
+    ("from_unixtime", BuiltinScalarFunction::FromUnixtime),
+    ("arrow_typeof", BuiltinScalarFunction::ArrowTypeof),
+    ("try_divide", BuiltinScalarFunction::TryDivide),
+    ("try_to_timestamp", BuiltinScalarFunction::TryToTimestamp),
+];
+
 impl FromStr for BuiltinScalarFunction {
     type Err = DataFusionError;
     fn from_str(name: &str) -> Result<BuiltinScalarFunction> {
-        Ok(match name {
-            // math functions
-            "abs" => BuiltinScalarFunction::Abs,
-            "acos" => BuiltinScalarFunction::Acos,
-            "asin" => BuiltinScalarFunction::Asin,
-            "atan" => BuiltinScalarFunction::Atan,
-            "atan2" => BuiltinScalarFunction::Atan2,
-            "ceil" => BuiltinScalarFunction::Ceil,
-            "cos" => BuiltinScalarFunction::Cos,
-            "exp" => BuiltinScalarFunction::Exp,
-            "floor" => BuiltinScalarFunction::Floor,
-            "ln" => BuiltinScalarFunction::Ln,
-            "log" => BuiltinScalarFunction::Log,
-            "log10" => BuiltinScalarFunction::Log10,
-            "log2" => BuiltinScalarFunction::Log2,
-            "power" | "pow" => BuiltinScalarFunction::Power,
-            "round" => BuiltinScalarFunction::Round,
-            "signum" => BuiltinScalarFunction::Signum,
-            "sin" => BuiltinScalarFunction::Sin,
-            "sqrt" => BuiltinScalarFunction::Sqrt,
-            "tan" => BuiltinScalarFunction::Tan,
-            "trunc" => BuiltinScalarFunction::Trunc,
-
-            // conditional functions
-            "coalesce" => BuiltinScalarFunction::Coalesce,
-
-            // array functions
-            "make_array" => BuiltinScalarFunction::MakeArray,
-
-            // string functions
-            "ascii" => BuiltinScalarFunction::Ascii,
-            "bit_length" => BuiltinScalarFunction::BitLength,
-            "btrim" => BuiltinScalarFunction::Btrim,
-            "char_length" => BuiltinScalarFunction::CharacterLength,
-            "character_length" => BuiltinScalarFunction::CharacterLength,
-            "concat" => BuiltinScalarFunction::Concat,
-            "concat_ws" => BuiltinScalarFunction::ConcatWithSeparator,
-            "chr" => BuiltinScalarFunction::Chr,
-            "current_date" => BuiltinScalarFunction::CurrentDate,
-            "current_time" => BuiltinScalarFunction::CurrentTime,
-            "date_part" | "datepart" => BuiltinScalarFunction::DatePart,
-            "date_trunc" | "datetrunc" => BuiltinScalarFunction::DateTrunc,
-            "date_bin" => BuiltinScalarFunction::DateBin,
-            "initcap" => BuiltinScalarFunction::InitCap,
-            "left" => BuiltinScalarFunction::Left,
-            "length" => BuiltinScalarFunction::CharacterLength,
-            "lower" => BuiltinScalarFunction::Lower,
-            "lpad" => BuiltinScalarFunction::Lpad,
-            "ltrim" => BuiltinScalarFunction::Ltrim,
-            "md5" => BuiltinScalarFunction::MD5,
-            "nullif" => BuiltinScalarFunction::NullIf,
-            "octet_length" => BuiltinScalarFunction::OctetLength,
-            "random" => BuiltinScalarFunction::Random,
-            "regexp_replace" => BuiltinScalarFunction::RegexpReplace,
-            "repeat" => BuiltinScalarFunction::Repeat,
-            "replace" => BuiltinScalarFunction::Replace,
-            "reverse" => BuiltinScalarFunction::Reverse,
-            "right" => BuiltinScalarFunction::Right,
-            "rpad" => BuiltinScalarFunction::Rpad,
-            "rtrim" => BuiltinScalarFunction::Rtrim,
-            "sha224" => BuiltinScalarFunction::SHA224,
-            "sha256" => BuiltinScalarFunction::SHA256,
-            "sha384" => BuiltinScalarFunction::SHA384,
-            "sha512" => BuiltinScalarFunction::SHA512,
-            "digest" => BuiltinScalarFunction::Digest,
-            "split_part" => BuiltinScalarFunction::SplitPart,
-            "starts_with" => BuiltinScalarFunction::StartsWith,
-            "strpos" => BuiltinScalarFunction::Strpos,
-            "substr" => BuiltinScalarFunction::Substr,
-            "to_hex" => BuiltinScalarFunction::ToHex,
-            "to_timestamp" => BuiltinScalarFunction::ToTimestamp,
-            "to_timestamp_millis" => BuiltinScalarFunction::ToTimestampMillis,
-            "to_timestamp_micros" => BuiltinScalarFunction::ToTimestampMicros,
-            "to_timestamp_seconds" => BuiltinScalarFunction::ToTimestampSeconds,
-            "now" => BuiltinScalarFunction::Now,
-            "translate" => BuiltinScalarFunction::Translate,
-            "trim" => BuiltinScalarFunction::Trim,
-            "upper" => BuiltinScalarFunction::Upper,
-            "uuid" => BuiltinScalarFunction::Uuid,
-            "regexp_match" => BuiltinScalarFunction::RegexpMatch,
-            "struct" => BuiltinScalarFunction::Struct,
-            "from_unixtime" => BuiltinScalarFunction::FromUnixtime,
-            "arrow_typeof" => BuiltinScalarFunction::ArrowTypeof,
-            _ => {
-                return Err(DataFusionError::Plan(format!(
+        NAME_TO_FUNCTION
+            .iter()
+            .find(|(function_name, _)| *function_name == name)
+            .map(|(_, fun)| fun.clone())
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
                     "There is no built-in function named {name}"
-                )))
-            }
-        })
+                ))
+            })
     }
 }
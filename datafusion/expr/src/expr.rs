@@ -460,6 +460,8 @@ pub struct AggregateFunction {
     pub distinct: bool,
     /// Optional filter
     pub filter: Option<Box<Expr>>,
+    /// Optional ordering of the input rows, as a list of sort expressions
+    pub order_by: Option<Vec<Expr>>,
 }
 
 impl AggregateFunction {
@@ -468,12 +470,14 @@ impl AggregateFunction {
         args: Vec<Expr>,
         distinct: bool,
         filter: Option<Box<Expr>>,
+        order_by: Option<Vec<Expr>>,
     ) -> Self {
         Self {
             fun,
             args,
             distinct,
             filter,
+            order_by,
         }
     }
 }
@@ -933,12 +937,15 @@ impl fmt::Debug for Expr {
                 distinct,
                 ref args,
                 filter,
-                ..
+                order_by,
             }) => {
                 fmt_function(f, &fun.to_string(), *distinct, args, true)?;
                 if let Some(fe) = filter {
                     write!(f, " FILTER (WHERE {fe})")?;
                 }
+                if let Some(ob) = order_by {
+                    write!(f, " ORDER BY {ob:?}")?;
+                }
                 Ok(())
             }
             Expr::AggregateUDF {
@@ -1286,13 +1293,16 @@ fn create_name(e: &Expr) -> Result<String> {
             distinct,
             args,
             filter,
+            order_by,
         }) => {
-            let name = create_function_name(&fun.to_string(), *distinct, args)?;
+            let mut name = create_function_name(&fun.to_string(), *distinct, args)?;
             if let Some(fe) = filter {
-                Ok(format!("{name} FILTER (WHERE {fe})"))
-            } else {
-                Ok(name)
+                name = format!("{name} FILTER (WHERE {fe})");
             }
+            if let Some(ob) = order_by {
+                name = format!("{name} ORDER BY {ob:?}");
+            }
+            Ok(name)
         }
         Expr::AggregateUDF { fun, args, filter } => {
             let mut names = Vec::with_capacity(args.len());
@@ -69,6 +69,7 @@ pub fn min(expr: Expr) -> Expr {
         vec![expr],
         false,
         None,
+        None,
     ))
 }
 
@@ -79,6 +80,7 @@ pub fn max(expr: Expr) -> Expr {
         vec![expr],
         false,
         None,
+        None,
     ))
 }
 
@@ -89,6 +91,7 @@ pub fn sum(expr: Expr) -> Expr {
         vec![expr],
         false,
         None,
+        None,
     ))
 }
 
@@ -99,6 +102,7 @@ pub fn avg(expr: Expr) -> Expr {
         vec![expr],
         false,
         None,
+        None,
     ))
 }
 
@@ -109,6 +113,7 @@ pub fn count(expr: Expr) -> Expr {
         vec![expr],
         false,
         None,
+        None,
     ))
 }
 
@@ -119,6 +124,7 @@ pub fn count_distinct(expr: Expr) -> Expr {
         vec![expr],
         true,
         None,
+        None,
     ))
 }
 
@@ -172,6 +178,7 @@ pub fn approx_distinct(expr: Expr) -> Expr {
         vec![expr],
         false,
         None,
+        None,
     ))
 }
 
@@ -182,6 +189,7 @@ pub fn approx_median(expr: Expr) -> Expr {
         vec![expr],
         false,
         None,
+        None,
     ))
 }
 
@@ -192,6 +200,7 @@ pub fn approx_percentile_cont(expr: Expr, percentile: Expr) -> Expr {
         vec![expr, percentile],
         false,
         None,
+        None,
     ))
 }
 
@@ -206,6 +215,7 @@ pub fn approx_percentile_cont_with_weight(
         vec![expr, weight_expr, percentile],
         false,
         None,
+        None,
     ))
 }
 
@@ -365,6 +375,7 @@ scalar_expr!(Ln, ln, num, "natural logarithm");
 scalar_expr!(NullIf, nullif, arg_1 arg_2, "returns NULL if value1 equals value2; otherwise it returns value1. This can be used to perform the inverse operation of the COALESCE expression.");
 scalar_expr!(Power, power, base exponent, "`base` raised to the power of `exponent`");
 scalar_expr!(Atan2, atan2, y x, "inverse tangent of a division given in the argument");
+scalar_expr!(TryDivide, try_divide, dividend divisor, "`dividend` divided by `divisor`, or NULL when the division fails, like for a division by zero");
 scalar_expr!(
     ToHex,
     to_hex,
@@ -481,6 +492,12 @@ nary_scalar_expr!(Concat, concat_expr, "concatenates several strings");
 scalar_expr!(DatePart, date_part, part date, "extracts a subfield from the date");
 scalar_expr!(DateTrunc, date_trunc, part date, "truncates the date to a specified level of precision");
 scalar_expr!(DateBin, date_bin, stride source origin, "coerces an arbitrary timestamp to the start of the nearest specified interval");
+scalar_expr!(
+    TryToTimestamp,
+    try_to_timestamp,
+    date,
+    "converts a string to a `Timestamp(Nanoseconds, None)`, or NULL if it isn't a valid timestamp"
+);
 scalar_expr!(
     ToTimestampMillis,
     to_timestamp_millis,
@@ -664,6 +681,7 @@ mod test {
         test_unary_scalar_expr!(Log10, log10);
         test_unary_scalar_expr!(Ln, ln);
         test_scalar_expr!(Atan2, atan2, y, x);
+        test_scalar_expr!(TryDivide, try_divide, dividend, divisor);
 
         test_scalar_expr!(Ascii, ascii, input);
         test_scalar_expr!(BitLength, bit_length, string);
@@ -722,6 +740,7 @@ mod test {
         test_scalar_expr!(DateTrunc, date_trunc, part, date);
         test_scalar_expr!(DateBin, date_bin, stride, source, origin);
         test_scalar_expr!(FromUnixtime, from_unixtime, unixtime);
+        test_scalar_expr!(TryToTimestamp, try_to_timestamp, date);
 
         test_unary_scalar_expr!(ArrowTypeof, arrow_typeof);
     }
@@ -17,30 +17,20 @@
 
 //! Expression rewriter
 
-use crate::expr::{
-    AggregateFunction, Between, BinaryExpr, Case, Cast, GetIndexedField, GroupingSet,
-    Like, Sort, TryCast, WindowFunction,
-};
+use crate::expr::Sort;
 use crate::logical_plan::{Aggregate, Projection};
 use crate::utils::grouping_set_to_exprlist;
 use crate::{Expr, ExprSchemable, LogicalPlan};
+use datafusion_common::tree_node::TreeNode;
 use datafusion_common::Result;
 use datafusion_common::{Column, DFSchema};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
-/// Controls how the [ExprRewriter] recursion should proceed.
-pub enum RewriteRecursion {
-    /// Continue rewrite / visit this expression.
-    Continue,
-    /// Call [ExprRewriter::mutate()] immediately and return.
-    Mutate,
-    /// Do not rewrite / visit the children of this expression.
-    Stop,
-    /// Keep recursive but skip mutate on this expression
-    Skip,
-}
+/// Controls how the [ExprRewriter] recursion should proceed, shared with
+/// the [`TreeNodeRewriter`](datafusion_common::tree_node::TreeNodeRewriter)
+pub use datafusion_common::tree_node::RewriteRecursion;
 
 /// Trait for potentially recursively rewriting an [`Expr`] expression
 /// tree. When passed to `Expr::rewrite`, `ExpressionVisitor::mutate` is
@@ -110,187 +100,7 @@ impl ExprRewritable for Expr {
         };
 
         // recurse into all sub expressions(and cover all expression types)
-        let expr = match self {
-            Expr::Alias(expr, name) => Expr::Alias(rewrite_boxed(expr, rewriter)?, name),
-            Expr::Column(_) => self.clone(),
-            Expr::Exists { .. } => self.clone(),
-            Expr::InSubquery {
-                expr,
-                subquery,
-                negated,
-            } => Expr::InSubquery {
-                expr: rewrite_boxed(expr, rewriter)?,
-                subquery,
-                negated,
-            },
-            Expr::ScalarSubquery(_) => self.clone(),
-            Expr::ScalarVariable(ty, names) => Expr::ScalarVariable(ty, names),
-            Expr::Literal(value) => Expr::Literal(value),
-            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
-                Expr::BinaryExpr(BinaryExpr::new(
-                    rewrite_boxed(left, rewriter)?,
-                    op,
-                    rewrite_boxed(right, rewriter)?,
-                ))
-            }
-            Expr::Like(Like {
-                negated,
-                expr,
-                pattern,
-                escape_char,
-            }) => Expr::Like(Like::new(
-                negated,
-                rewrite_boxed(expr, rewriter)?,
-                rewrite_boxed(pattern, rewriter)?,
-                escape_char,
-            )),
-            Expr::ILike(Like {
-                negated,
-                expr,
-                pattern,
-                escape_char,
-            }) => Expr::ILike(Like::new(
-                negated,
-                rewrite_boxed(expr, rewriter)?,
-                rewrite_boxed(pattern, rewriter)?,
-                escape_char,
-            )),
-            Expr::SimilarTo(Like {
-                negated,
-                expr,
-                pattern,
-                escape_char,
-            }) => Expr::SimilarTo(Like::new(
-                negated,
-                rewrite_boxed(expr, rewriter)?,
-                rewrite_boxed(pattern, rewriter)?,
-                escape_char,
-            )),
-            Expr::Not(expr) => Expr::Not(rewrite_boxed(expr, rewriter)?),
-            Expr::IsNotNull(expr) => Expr::IsNotNull(rewrite_boxed(expr, rewriter)?),
-            Expr::IsNull(expr) => Expr::IsNull(rewrite_boxed(expr, rewriter)?),
-            Expr::IsTrue(expr) => Expr::IsTrue(rewrite_boxed(expr, rewriter)?),
-            Expr::IsFalse(expr) => Expr::IsFalse(rewrite_boxed(expr, rewriter)?),
-            Expr::IsUnknown(expr) => Expr::IsUnknown(rewrite_boxed(expr, rewriter)?),
-            Expr::IsNotTrue(expr) => Expr::IsNotTrue(rewrite_boxed(expr, rewriter)?),
-            Expr::IsNotFalse(expr) => Expr::IsNotFalse(rewrite_boxed(expr, rewriter)?),
-            Expr::IsNotUnknown(expr) => {
-                Expr::IsNotUnknown(rewrite_boxed(expr, rewriter)?)
-            }
-            Expr::Negative(expr) => Expr::Negative(rewrite_boxed(expr, rewriter)?),
-            Expr::Between(Between {
-                expr,
-                negated,
-                low,
-                high,
-            }) => Expr::Between(Between::new(
-                rewrite_boxed(expr, rewriter)?,
-                negated,
-                rewrite_boxed(low, rewriter)?,
-                rewrite_boxed(high, rewriter)?,
-            )),
-            Expr::Case(case) => {
-                let expr = rewrite_option_box(case.expr, rewriter)?;
-                let when_then_expr = case
-                    .when_then_expr
-                    .into_iter()
-                    .map(|(when, then)| {
-                        Ok((
-                            rewrite_boxed(when, rewriter)?,
-                            rewrite_boxed(then, rewriter)?,
-                        ))
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                let else_expr = rewrite_option_box(case.else_expr, rewriter)?;
-
-                Expr::Case(Case::new(expr, when_then_expr, else_expr))
-            }
-            Expr::Cast(Cast { expr, data_type }) => {
-                Expr::Cast(Cast::new(rewrite_boxed(expr, rewriter)?, data_type))
-            }
-            Expr::TryCast(TryCast { expr, data_type }) => {
-                Expr::TryCast(TryCast::new(rewrite_boxed(expr, rewriter)?, data_type))
-            }
-            Expr::Sort(Sort {
-                expr,
-                asc,
-                nulls_first,
-            }) => Expr::Sort(Sort::new(rewrite_boxed(expr, rewriter)?, asc, nulls_first)),
-            Expr::ScalarFunction { args, fun } => Expr::ScalarFunction {
-                args: rewrite_vec(args, rewriter)?,
-                fun,
-            },
-            Expr::ScalarUDF { args, fun } => Expr::ScalarUDF {
-                args: rewrite_vec(args, rewriter)?,
-                fun,
-            },
-            Expr::WindowFunction(WindowFunction {
-                args,
-                fun,
-                partition_by,
-                order_by,
-                window_frame,
-            }) => Expr::WindowFunction(WindowFunction::new(
-                fun,
-                rewrite_vec(args, rewriter)?,
-                rewrite_vec(partition_by, rewriter)?,
-                rewrite_vec(order_by, rewriter)?,
-                window_frame,
-            )),
-            Expr::AggregateFunction(AggregateFunction {
-                args,
-                fun,
-                distinct,
-                filter,
-            }) => Expr::AggregateFunction(AggregateFunction::new(
-                fun,
-                rewrite_vec(args, rewriter)?,
-                distinct,
-                filter,
-            )),
-            Expr::GroupingSet(grouping_set) => match grouping_set {
-                GroupingSet::Rollup(exprs) => {
-                    Expr::GroupingSet(GroupingSet::Rollup(rewrite_vec(exprs, rewriter)?))
-                }
-                GroupingSet::Cube(exprs) => {
-                    Expr::GroupingSet(GroupingSet::Cube(rewrite_vec(exprs, rewriter)?))
-                }
-                GroupingSet::GroupingSets(lists_of_exprs) => {
-                    Expr::GroupingSet(GroupingSet::GroupingSets(
-                        lists_of_exprs
-                            .iter()
-                            .map(|exprs| rewrite_vec(exprs.clone(), rewriter))
-                            .collect::<Result<Vec<_>>>()?,
-                    ))
-                }
-            },
-            Expr::AggregateUDF { args, fun, filter } => Expr::AggregateUDF {
-                args: rewrite_vec(args, rewriter)?,
-                fun,
-                filter,
-            },
-            Expr::InList {
-                expr,
-                list,
-                negated,
-            } => Expr::InList {
-                expr: rewrite_boxed(expr, rewriter)?,
-                list: rewrite_vec(list, rewriter)?,
-                negated,
-            },
-            Expr::Wildcard => Expr::Wildcard,
-            Expr::QualifiedWildcard { qualifier } => {
-                Expr::QualifiedWildcard { qualifier }
-            }
-            Expr::GetIndexedField(GetIndexedField { key, expr }) => {
-                Expr::GetIndexedField(GetIndexedField::new(
-                    rewrite_boxed(expr, rewriter)?,
-                    key,
-                ))
-            }
-            Expr::Placeholder { id, data_type } => Expr::Placeholder { id, data_type },
-        };
+        let expr = self.map_children(|expr| expr.rewrite(rewriter))?;
 
         // now rewrite this expression itself
         if need_mutate {
@@ -301,38 +111,6 @@ impl ExprRewritable for Expr {
     }
 }
 
-#[allow(clippy::boxed_local)]
-fn rewrite_boxed<R>(boxed_expr: Box<Expr>, rewriter: &mut R) -> Result<Box<Expr>>
-where
-    R: ExprRewriter,
-{
-    // TODO: It might be possible to avoid an allocation (the
-    // Box::new) below by reusing the box.
-    let expr: Expr = *boxed_expr;
-    let rewritten_expr = expr.rewrite(rewriter)?;
-    Ok(Box::new(rewritten_expr))
-}
-
-fn rewrite_option_box<R>(
-    option_box: Option<Box<Expr>>,
-    rewriter: &mut R,
-) -> Result<Option<Box<Expr>>>
-where
-    R: ExprRewriter,
-{
-    option_box
-        .map(|expr| rewrite_boxed(expr, rewriter))
-        .transpose()
-}
-
-/// rewrite a `Vec` of `Expr`s with the rewriter
-fn rewrite_vec<R>(v: Vec<Expr>, rewriter: &mut R) -> Result<Vec<Expr>>
-where
-    R: ExprRewriter,
-{
-    v.into_iter().map(|expr| expr.rewrite(rewriter)).collect()
-}
-
 /// Rewrite sort on aggregate expressions to sort on the column of aggregate output
 /// For example, `max(x)` is written to `col("MAX(x)")`
 pub fn rewrite_sort_cols_by_aggs(
@@ -621,10 +399,10 @@ mod test {
         fn mutate(&mut self, expr: Expr) -> Result<Expr> {
             match expr {
                 Expr::Literal(ScalarValue::Utf8(Some(utf8_val))) => {
-                    let utf8_val = if utf8_val == "foo" {
-                        "bar".to_string()
+                    let utf8_val = if utf8_val.as_ref() == "foo" {
+                        "bar"
                     } else {
-                        utf8_val
+                        utf8_val.as_ref()
                     };
                     Ok(lit(utf8_val))
                 }
@@ -180,8 +180,17 @@ impl ExprVisitable for Expr {
             Expr::ScalarFunction { args, .. } | Expr::ScalarUDF { args, .. } => args
                 .iter()
                 .try_fold(visitor, |visitor, arg| arg.accept(visitor)),
-            Expr::AggregateFunction(AggregateFunction { args, filter, .. })
-            | Expr::AggregateUDF { args, filter, .. } => {
+            Expr::AggregateFunction(AggregateFunction {
+                args,
+                filter,
+                order_by,
+                ..
+            }) => args
+                .iter()
+                .chain(filter.as_deref())
+                .chain(order_by.iter().flatten())
+                .try_fold(visitor, |visitor, arg| arg.accept(visitor)),
+            Expr::AggregateUDF { args, filter, .. } => {
                 if let Some(f) = filter {
                     let mut aggr_exprs = args.clone();
                     aggr_exprs.push(f.as_ref().clone());
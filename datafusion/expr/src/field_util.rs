@@ -36,7 +36,7 @@ pub fn get_indexed_field(data_type: &DataType, key: &ScalarValue) -> Result<Fiel
                     "Struct based indexed access requires a non empty string".to_string(),
                 ))
             } else {
-                let field = fields.iter().find(|f| f.name() == s);
+                let field = fields.iter().find(|f| f.name() == s.as_ref());
                 match field {
                     None => Err(DataFusionError::Plan(format!(
                         "Field {s} not found in struct"
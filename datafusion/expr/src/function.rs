@@ -20,12 +20,14 @@
 use crate::nullif::SUPPORTED_NULLIF_TYPES;
 use crate::type_coercion::functions::data_types;
 use crate::ColumnarValue;
+use crate::Expr;
 use crate::{
     array_expressions, conditional_expressions, struct_expressions, Accumulator,
     BuiltinScalarFunction, Signature, TypeSignature,
 };
 use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
 use datafusion_common::{DataFusionError, Result};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Scalar function
@@ -43,6 +45,11 @@ pub type ScalarFunctionImplementation =
 pub type ReturnTypeFunction =
     Arc<dyn Fn(&[DataType]) -> Result<Arc<DataType>> + Send + Sync>;
 
+/// Rewrites a call of a scalar function given its arguments, returning
+/// `None` if the call can not be simplified
+pub type ScalarFunctionSimplifier =
+    Arc<dyn Fn(&[Expr]) -> Result<Option<Expr>> + Send + Sync>;
+
 /// Factory that returns an accumulator for the given aggregate, given
 /// its return datatype.
 pub type AccumulatorFunctionImplementation =
@@ -53,6 +60,91 @@ pub type AccumulatorFunctionImplementation =
 pub type StateTypeFunction =
     Arc<dyn Fn(&DataType) -> Result<Arc<Vec<DataType>>> + Send + Sync>;
 
+/// A named parameter of a user defined function. Arguments can be passed to
+/// it by name, as in `f(name => value)`, and may be omitted if it has a
+/// default value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionParameter {
+    /// The name of the parameter, in lower case unless it is always quoted
+    pub name: String,
+    /// The value of the parameter when no argument is passed to it
+    pub default: Option<Expr>,
+}
+
+impl FunctionParameter {
+    /// Create a parameter which must be passed an argument
+    pub fn required(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            default: None,
+        }
+    }
+
+    /// Create a parameter which is `default` when no argument is passed to it
+    pub fn optional(name: impl Into<String>, default: Expr) -> Self {
+        Self {
+            name: name.into(),
+            default: Some(default),
+        }
+    }
+}
+
+/// Returns the arguments of a call of function `name`, each with its name if
+/// it is passed by name, in the order of the function's `parameters`, with
+/// the defaults of the parameters no argument is passed to.
+///
+/// Positional arguments are passed to the parameters in order and must come
+/// before the named ones. Positional arguments in excess of the parameters,
+/// for variadic functions, are returned as is. Functions without declared
+/// parameters do not accept named arguments.
+pub fn resolve_function_arguments(
+    name: &str,
+    parameters: &[FunctionParameter],
+    mut args: Vec<(Option<String>, Expr)>,
+) -> Result<Vec<Expr>> {
+    let positional = args.iter().take_while(|(name, _)| name.is_none()).count();
+    let named_args = args.split_off(positional);
+    let mut resolved = args.into_iter().map(|(_, arg)| arg).collect::<Vec<_>>();
+
+    let mut named = HashMap::with_capacity(named_args.len());
+    for (arg_name, arg) in named_args {
+        let arg_name = arg_name.ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "Positional arguments of function {name} must come before the named ones"
+            ))
+        })?;
+        match parameters.iter().position(|p| p.name == arg_name) {
+            None => {
+                return Err(DataFusionError::Plan(format!(
+                    "Function {name} has no parameter named {arg_name}"
+                )))
+            }
+            Some(index) if index < positional || named.contains_key(&arg_name) => {
+                return Err(DataFusionError::Plan(format!(
+                    "Parameter {arg_name} of function {name} is passed more than once"
+                )))
+            }
+            Some(_) => {
+                named.insert(arg_name, arg);
+            }
+        }
+    }
+
+    for parameter in parameters.iter().skip(positional) {
+        let arg = named
+            .remove(&parameter.name)
+            .or_else(|| parameter.default.clone())
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
+                    "No argument passed to parameter {} of function {name}",
+                    parameter.name
+                ))
+            })?;
+        resolved.push(arg);
+    }
+    Ok(resolved)
+}
+
 macro_rules! make_utf8_to_return_type {
     ($FUNC:ident, $largeUtf8Type:expr, $utf8Type:expr) => {
         fn $FUNC(arg_type: &DataType, name: &str) -> Result<DataType> {
@@ -60,6 +152,8 @@ macro_rules! make_utf8_to_return_type {
                 DataType::LargeUtf8 => $largeUtf8Type,
                 DataType::Utf8 => $utf8Type,
                 DataType::Null => DataType::Null,
+                // dictionaries are cast to their value type by type coercion
+                DataType::Dictionary(_, value_type) => $FUNC(value_type, name)?,
                 _ => {
                     // this error is internal as `data_types` should have captured this.
                     return Err(DataFusionError::Internal(format!(
@@ -201,7 +295,7 @@ pub fn return_type(
                 ));
             }
         }),
-        BuiltinScalarFunction::ToTimestamp => {
+        BuiltinScalarFunction::ToTimestamp | BuiltinScalarFunction::TryToTimestamp => {
             Ok(DataType::Timestamp(TimeUnit::Nanosecond, None))
         }
         BuiltinScalarFunction::ToTimestampMillis => {
@@ -248,6 +342,12 @@ pub fn return_type(
             _ => Ok(DataType::Float64),
         },
 
+        BuiltinScalarFunction::TryDivide => match &input_expr_types[0] {
+            DataType::Int64 => Ok(DataType::Int64),
+            DataType::UInt64 => Ok(DataType::UInt64),
+            _ => Ok(DataType::Float64),
+        },
+
         BuiltinScalarFunction::Struct => Ok(DataType::Struct(vec![])),
 
         BuiltinScalarFunction::Atan2 => match &input_expr_types[0] {
@@ -377,18 +477,20 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
             ],
             fun.volatility(),
         ),
-        BuiltinScalarFunction::ToTimestamp => Signature::uniform(
-            1,
-            vec![
-                DataType::Int64,
-                DataType::Timestamp(TimeUnit::Nanosecond, None),
-                DataType::Timestamp(TimeUnit::Microsecond, None),
-                DataType::Timestamp(TimeUnit::Millisecond, None),
-                DataType::Timestamp(TimeUnit::Second, None),
-                DataType::Utf8,
-            ],
-            fun.volatility(),
-        ),
+        BuiltinScalarFunction::ToTimestamp | BuiltinScalarFunction::TryToTimestamp => {
+            Signature::uniform(
+                1,
+                vec![
+                    DataType::Int64,
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Timestamp(TimeUnit::Microsecond, None),
+                    DataType::Timestamp(TimeUnit::Millisecond, None),
+                    DataType::Timestamp(TimeUnit::Second, None),
+                    DataType::Utf8,
+                ],
+                fun.volatility(),
+            )
+        }
         BuiltinScalarFunction::ToTimestampMillis => Signature::uniform(
             1,
             vec![
@@ -607,6 +709,14 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
             ],
             fun.volatility(),
         ),
+        BuiltinScalarFunction::TryDivide => Signature::one_of(
+            vec![
+                TypeSignature::Exact(vec![DataType::Int64, DataType::Int64]),
+                TypeSignature::Exact(vec![DataType::UInt64, DataType::UInt64]),
+                TypeSignature::Exact(vec![DataType::Float64, DataType::Float64]),
+            ],
+            fun.volatility(),
+        ),
         BuiltinScalarFunction::ArrowTypeof => Signature::any(1, fun.volatility()),
         // math expressions expect 1 argument of type f64 or f32
         // priority is given to f64 because e.g. `sqrt(1i32)` is in IR (real numbers) and thus we
@@ -620,3 +730,74 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{col, lit};
+
+    fn parameters() -> Vec<FunctionParameter> {
+        vec![
+            FunctionParameter::required("x"),
+            FunctionParameter::optional("y", lit(1)),
+            FunctionParameter::optional("z", lit(2)),
+        ]
+    }
+
+    fn named(name: &str, arg: Expr) -> (Option<String>, Expr) {
+        (Some(name.to_string()), arg)
+    }
+
+    #[test]
+    fn resolve_named_and_default_arguments() -> Result<()> {
+        let args = vec![(None, col("a")), named("z", lit(3))];
+        let resolved = resolve_function_arguments("f", &parameters(), args)?;
+        assert_eq!(resolved, vec![col("a"), lit(1), lit(3)]);
+
+        let args = vec![named("y", lit(4)), named("x", col("a"))];
+        let resolved = resolve_function_arguments("f", &parameters(), args)?;
+        assert_eq!(resolved, vec![col("a"), lit(4), lit(2)]);
+
+        // positional arguments only, without declared parameters
+        let args = vec![(None, col("a")), (None, col("b"))];
+        let resolved = resolve_function_arguments("f", &[], args)?;
+        assert_eq!(resolved, vec![col("a"), col("b")]);
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_invalid_arguments() {
+        let cases = vec![
+            (
+                vec![named("y", lit(4)), (None, col("a"))],
+                "Positional arguments of function f must come before the named ones",
+            ),
+            (
+                vec![(None, col("a")), named("w", lit(4))],
+                "Function f has no parameter named w",
+            ),
+            (
+                vec![(None, col("a")), named("x", lit(4))],
+                "Parameter x of function f is passed more than once",
+            ),
+            (
+                vec![named("y", lit(4))],
+                "No argument passed to parameter x of function f",
+            ),
+        ];
+        for (args, expected) in cases {
+            let err = resolve_function_arguments("f", &parameters(), args).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Error during planning: {expected}")
+            );
+        }
+
+        let err =
+            resolve_function_arguments("f", &[], vec![named("x", lit(1))]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Function f has no parameter named x"
+        );
+    }
+}
@@ -45,10 +45,12 @@ mod operator;
 mod signature;
 pub mod struct_expressions;
 mod table_source;
+pub mod tree_node;
 pub mod type_coercion;
 mod udaf;
 mod udf;
 pub mod utils;
+pub mod window_definition;
 pub mod window_frame;
 pub mod window_function;
 
@@ -62,20 +64,23 @@ pub use expr::{
 pub use expr_fn::*;
 pub use expr_schema::ExprSchemable;
 pub use function::{
-    AccumulatorFunctionImplementation, ReturnTypeFunction, ScalarFunctionImplementation,
-    StateTypeFunction,
+    AccumulatorFunctionImplementation, FunctionParameter, ReturnTypeFunction,
+    ScalarFunctionImplementation, ScalarFunctionSimplifier, StateTypeFunction,
 };
 pub use literal::{lit, lit_timestamp_nano, Literal, TimestampLiteral};
 pub use logical_plan::{
     builder::{
-        build_join_schema, union, wrap_projection_for_join_if_necessary, UNNAMED_TABLE,
+        build_join_schema, union, values_schema, wrap_projection_for_join_if_necessary,
+        UNNAMED_TABLE,
     },
-    Aggregate, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
-    CreateMemoryTable, CreateView, CrossJoin, Distinct, DropTable, DropView,
-    EmptyRelation, Explain, Extension, Filter, Join, JoinConstraint, JoinType, Limit,
-    LogicalPlan, LogicalPlanBuilder, Partitioning, PlanType, PlanVisitor, Projection,
-    Repartition, SetVariable, Sort, StringifiedPlan, Subquery, SubqueryAlias, TableScan,
-    ToStringifiedPlan, Union, UserDefinedLogicalNode, Values, Window,
+    Aggregate, AnalyzeTable, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+    CreateFunction, CreateFunctionArg, CreateMemoryTable, CreateView, CrossJoin,
+    Distinct, DistinctOn, DropFunction, DropTable, DropView, EmptyRelation, Explain,
+    Extension, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan,
+    LogicalPlanBuilder, Partitioning, PlanType, PlanVisitor, Projection, Repartition,
+    SetOperationAll, SetOperationType, SetVariable, SharedCte, Sort, StringifiedPlan,
+    Subquery, SubqueryAlias, TableScan, ToStringifiedPlan, Transaction,
+    TransactionStatement, Union, UserDefinedLogicalNode, Values, Window,
 };
 pub use nullif::SUPPORTED_NULLIF_TYPES;
 pub use operator::Operator;
@@ -83,5 +88,8 @@ pub use signature::{Signature, TypeSignature, Volatility};
 pub use table_source::{TableProviderFilterPushDown, TableSource, TableType};
 pub use udaf::AggregateUDF;
 pub use udf::ScalarUDF;
-pub use window_frame::{WindowFrame, WindowFrameBound, WindowFrameUnits};
+pub use window_definition::WindowDefinition;
+pub use window_frame::{
+    WindowFrame, WindowFrameBound, WindowFrameExclude, WindowFrameUnits,
+};
 pub use window_function::{BuiltInWindowFunction, WindowFunction};
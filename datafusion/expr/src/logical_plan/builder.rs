@@ -26,10 +26,11 @@ use crate::utils::{columnize_expr, compare_sort_expr, exprlist_to_fields, from_p
 use crate::{and, binary_expr, Operator};
 use crate::{
     logical_plan::{
-        Aggregate, Analyze, CrossJoin, Distinct, EmptyRelation, Explain, Filter, Join,
-        JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-        Projection, Repartition, Sort, SubqueryAlias, TableScan, ToStringifiedPlan,
-        Union, Values, Window,
+        Aggregate, Analyze, CrossJoin, Distinct, DistinctOn, EmptyRelation, Explain,
+        Extension, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan,
+        Partitioning, PlanType, Prepare, Projection, Repartition, SetOperationAll,
+        SetOperationType, Sort, SubqueryAlias, TableScan, ToStringifiedPlan, Union,
+        Values, Window,
     },
     utils::{
         can_hash, expand_qualified_wildcard, expand_wildcard,
@@ -123,72 +124,45 @@ impl LogicalPlanBuilder {
     /// so it's usually better to override the default names with a table alias list.
     ///
     /// If the values include params/binders such as $1, $2, $3, etc, then the `param_data_types` should be provided.
-    pub fn values(mut values: Vec<Vec<Expr>>) -> Result<Self> {
-        if values.is_empty() {
-            return Err(DataFusionError::Plan("Values list cannot be empty".into()));
-        }
-        let n_cols = values[0].len();
-        if n_cols == 0 {
-            return Err(DataFusionError::Plan(
-                "Values list cannot be zero length".into(),
-            ));
-        }
+    ///
+    /// The values of each column are cast to the common type of the column, see
+    /// [`values_schema`].
+    pub fn values(values: Vec<Vec<Expr>>) -> Result<Self> {
         let empty_schema = DFSchema::empty();
-        let mut field_types: Vec<Option<DataType>> = Vec::with_capacity(n_cols);
-        for _ in 0..n_cols {
-            field_types.push(None);
-        }
-        // hold all the null holes so that we can correct their data types later
-        let mut nulls: Vec<(usize, usize)> = Vec::new();
-        for (i, row) in values.iter().enumerate() {
-            if row.len() != n_cols {
-                return Err(DataFusionError::Plan(format!(
-                    "Inconsistent data length across values list: got {} values in row {} but expected {}",
-                    row.len(),
-                    i,
-                    n_cols
-                )));
-            }
-            field_types = row
-                .iter()
-                .enumerate()
-                .map(|(j, expr)| {
-                    if let Expr::Literal(ScalarValue::Null) = expr {
-                        nulls.push((i, j));
-                        Ok(field_types[j].clone())
-                    } else {
-                        let data_type = expr.get_type(&empty_schema)?;
-                        if let Some(prev_data_type) = &field_types[j] {
-                            if prev_data_type != &data_type {
-                                let err = format!("Inconsistent data type across values list at row {i} column {j}");
-                                return Err(DataFusionError::Plan(err));
-                            }
-                        }
-                        Ok(Some(data_type))
-                    }
-                })
-                .collect::<Result<Vec<Option<DataType>>>>()?;
-        }
-        let fields = field_types
+        let row_types = values
             .iter()
-            .enumerate()
-            .map(|(j, data_type)| {
-                // naming is following convention https://www.postgresql.org/docs/current/queries-values.html
-                let name = &format!("column{}", j + 1);
-                DFField::new(
-                    None,
-                    name,
-                    data_type.clone().unwrap_or(DataType::Utf8),
-                    true,
-                )
+            .map(|row| {
+                row.iter()
+                    .map(|expr| match expr {
+                        Expr::Literal(ScalarValue::Null) => Ok(None),
+                        _ => expr.get_type(&empty_schema).map(Some),
+                    })
+                    .collect::<Result<Vec<_>>>()
             })
-            .collect::<Vec<_>>();
-        for (i, j) in nulls {
-            values[i][j] = Expr::Literal(ScalarValue::try_from(fields[j].data_type())?);
-        }
-        let schema =
-            DFSchemaRef::new(DFSchema::new_with_metadata(fields, HashMap::new())?);
-        Ok(Self::from(LogicalPlan::Values(Values { schema, values })))
+            .collect::<Result<Vec<_>>>()?;
+        let schema = values_schema(&row_types)?;
+        let values = values
+            .into_iter()
+            .zip(row_types)
+            .map(|(row, types)| {
+                row.into_iter()
+                    .zip(types)
+                    .zip(schema.fields())
+                    .map(|((expr, data_type), field)| match data_type {
+                        // the nulls are of the type of the column
+                        None => {
+                            Ok(Expr::Literal(ScalarValue::try_from(field.data_type())?))
+                        }
+                        Some(data_type) if &data_type == field.data_type() => Ok(expr),
+                        Some(_) => expr.cast_to(field.data_type(), &empty_schema),
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from(LogicalPlan::Values(Values {
+            schema: DFSchemaRef::new(schema),
+            values,
+        })))
     }
 
     /// Convert a table provider into a builder with a TableScan
@@ -240,6 +214,7 @@ impl LogicalPlanBuilder {
             projection,
             filters,
             fetch: None,
+            ordering: vec![],
         });
         Ok(Self::from(table_scan))
     }
@@ -436,6 +411,12 @@ impl LogicalPlanBuilder {
         Ok(Self::from(union(self.plan, plan)?))
     }
 
+    /// Apply a union, matching the columns of both inputs by name instead of
+    /// by position. See [`union_by_name`] for details.
+    pub fn union_by_name(self, plan: LogicalPlan) -> Result<Self> {
+        Ok(Self::from(union_by_name(self.plan, plan)?))
+    }
+
     /// Apply a union, removing duplicate rows
     pub fn union_distinct(self, plan: LogicalPlan) -> Result<Self> {
         // unwrap top-level Distincts, to avoid duplication
@@ -460,6 +441,28 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply a `DISTINCT ON`, returning `select_expr` for the first row of
+    /// each distinct value of `on_expr`, in the order of `sort_expr`.
+    ///
+    /// The leading expressions of `sort_expr` must be the ones of `on_expr`.
+    pub fn distinct_on(
+        self,
+        on_expr: Vec<Expr>,
+        select_expr: Vec<Expr>,
+        sort_expr: Option<Vec<Expr>>,
+    ) -> Result<Self> {
+        let on_expr = normalize_cols(on_expr, &self.plan)?;
+        let sort_expr = sort_expr
+            .map(|sort_expr| normalize_cols(sort_expr, &self.plan))
+            .transpose()?;
+
+        let distinct_on = DistinctOn::try_new(on_expr, sort_expr, Arc::new(self.plan))?;
+        Self::from(LogicalPlan::Extension(Extension {
+            node: Arc::new(distinct_on),
+        }))
+        .project(select_expr)
+    }
+
     /// Apply a join with on constraint.
     ///
     /// Filter expression expected to contain non-equality predicates that can not be pushed
@@ -793,6 +796,21 @@ impl LogicalPlanBuilder {
             )));
         }
 
+        let (left_plan, right_plan) = coerce_set_operation_inputs(left_plan, right_plan)?;
+        if is_all {
+            // the multiplicities of the rows are tracked by a dedicated node,
+            // as a join returns all the duplicates of the matching rows
+            let op = match join_type {
+                JoinType::LeftSemi => SetOperationType::Intersect,
+                _ => SetOperationType::Except,
+            };
+            let node =
+                SetOperationAll::try_new(op, Arc::new(left_plan), Arc::new(right_plan))?;
+            return Ok(LogicalPlan::Extension(Extension {
+                node: Arc::new(node),
+            }));
+        }
+
         let join_keys = left_plan
             .schema()
             .fields()
@@ -805,16 +823,10 @@ impl LogicalPlanBuilder {
                 )
             })
             .unzip();
-        if is_all {
-            LogicalPlanBuilder::from(left_plan)
-                .join_detailed(right_plan, join_type, join_keys, None, true)?
-                .build()
-        } else {
-            LogicalPlanBuilder::from(left_plan)
-                .distinct()?
-                .join_detailed(right_plan, join_type, join_keys, None, true)?
-                .build()
-        }
+        LogicalPlanBuilder::from(left_plan)
+            .distinct()?
+            .join_detailed(right_plan, join_type, join_keys, None, true)?
+            .build()
     }
 
     /// Build the plan
@@ -914,6 +926,18 @@ pub fn build_join_schema(
             // Only use the right side for the schema
             right.fields().clone()
         }
+        JoinType::LeftMark => {
+            // the left side, then the mark, qualified like the right side so
+            // that the marks of several joins can be told apart
+            let qualifier = right.fields().first().and_then(|f| f.qualifier());
+            let mark = DFField::new(
+                qualifier.map(|q| q.as_str()),
+                "mark",
+                DataType::Boolean,
+                false,
+            );
+            left.fields().iter().cloned().chain(Some(mark)).collect()
+        }
     };
 
     let mut metadata = left.metadata().clone();
@@ -1036,6 +1060,126 @@ pub fn union(left_plan: LogicalPlan, right_plan: LogicalPlan) -> Result<LogicalP
     }))
 }
 
+/// Union two logical plans, matching their columns by name instead of by
+/// position.
+///
+/// The columns of the result are the columns of `left_plan`, followed by the
+/// columns only found in `right_plan`. The columns missing from one of the
+/// inputs are filled with nulls, and the types of the columns found in both
+/// inputs are coerced as in [`union`].
+pub fn union_by_name(
+    left_plan: LogicalPlan,
+    right_plan: LogicalPlan,
+) -> Result<LogicalPlan> {
+    let mut fields: Vec<DFField> = vec![];
+    for plan in [&left_plan, &right_plan] {
+        let mut names = HashSet::new();
+        for field in plan.schema().fields() {
+            if !names.insert(field.name()) {
+                return Err(DataFusionError::Plan(format!(
+                    "UNION BY NAME input has duplicate column {}",
+                    field.name()
+                )));
+            }
+            if !fields.iter().any(|f| f.name() == field.name()) {
+                fields.push(field.clone());
+            }
+        }
+    }
+
+    let left_plan = project_by_name(left_plan, &fields)?;
+    let right_plan = project_by_name(right_plan, &fields)?;
+    union(left_plan, right_plan)
+}
+
+/// Projects the columns of `plan` named like `fields`, in the same order,
+/// filling the ones missing from `plan` with nulls of the type of the field
+fn project_by_name(plan: LogicalPlan, fields: &[DFField]) -> Result<LogicalPlan> {
+    let schema = plan.schema().clone();
+    let same_columns = schema.fields().len() == fields.len()
+        && schema
+            .fields()
+            .iter()
+            .zip(fields)
+            .all(|(a, b)| a.name() == b.name());
+    if same_columns {
+        return Ok(plan);
+    }
+
+    let exprs = fields
+        .iter()
+        .map(
+            |field| match schema.field_with_unqualified_name(field.name()) {
+                Ok(input_field) => Ok(Expr::Column(input_field.qualified_column())),
+                Err(_) => {
+                    let null = ScalarValue::try_from(field.data_type())?;
+                    Ok(Expr::Literal(null).alias(field.name()))
+                }
+            },
+        )
+        .collect::<Result<Vec<_>>>()?;
+    project(plan, exprs)
+}
+
+/// Casts the columns of the inputs of an INTERSECT or EXCEPT to the types
+/// that both sides can be compared as, projecting the inputs whose types
+/// differ
+fn coerce_set_operation_inputs(
+    left_plan: LogicalPlan,
+    right_plan: LogicalPlan,
+) -> Result<(LogicalPlan, LogicalPlan)> {
+    let data_types = left_plan
+        .schema()
+        .fields()
+        .iter()
+        .zip(right_plan.schema().fields())
+        .map(|(left_field, right_field)| {
+            comparison_coercion(left_field.data_type(), right_field.data_type())
+                .ok_or_else(|| {
+                    DataFusionError::Plan(format!(
+                        "INTERSECT/EXCEPT Column {} (type: {}) is not compatible with column {} (type: {})",
+                        right_field.name(),
+                        right_field.data_type(),
+                        left_field.name(),
+                        left_field.data_type()
+                    ))
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((
+        cast_columns(left_plan, &data_types)?,
+        cast_columns(right_plan, &data_types)?,
+    ))
+}
+
+/// Casts the columns of `plan` to `data_types`, keeping their names
+fn cast_columns(plan: LogicalPlan, data_types: &[DataType]) -> Result<LogicalPlan> {
+    let schema = plan.schema().clone();
+    let fields = schema.fields();
+    if fields
+        .iter()
+        .zip(data_types)
+        .all(|(f, t)| f.data_type() == t)
+    {
+        return Ok(plan);
+    }
+
+    let exprs = fields
+        .iter()
+        .zip(data_types)
+        .map(|(field, data_type)| {
+            let column = Expr::Column(field.qualified_column());
+            if field.data_type() == data_type {
+                Ok(column)
+            } else {
+                Ok(column.cast_to(data_type, &schema)?.alias(field.name()))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    project(plan, exprs)
+}
+
 /// Create Projection
 /// # Errors
 /// This function errors under any of the following conditions:
@@ -1083,6 +1227,74 @@ pub fn subquery_alias(
     )?))
 }
 
+/// Returns the schema of a VALUES list whose rows have values of `row_types`,
+/// `None` for the untyped nulls. The type of each column is the common type of
+/// its values, to which they are coerced like the inputs of a `UNION` except
+/// that numbers and strings can't be mixed, or `Utf8` if all its values are
+/// untyped nulls. The columns are named column1, column2, etc.
+pub fn values_schema(row_types: &[Vec<Option<DataType>>]) -> Result<DFSchema> {
+    let n_cols = match row_types.first() {
+        Some(row) => row.len(),
+        None => return Err(DataFusionError::Plan("Values list cannot be empty".into())),
+    };
+    if n_cols == 0 {
+        return Err(DataFusionError::Plan(
+            "Values list cannot be zero length".into(),
+        ));
+    }
+    let mut field_types: Vec<Option<DataType>> = vec![None; n_cols];
+    for (i, row) in row_types.iter().enumerate() {
+        if row.len() != n_cols {
+            return Err(DataFusionError::Plan(format!(
+                "Inconsistent data length across values list: got {} values in row {} but expected {}",
+                row.len(),
+                i,
+                n_cols
+            )));
+        }
+        for (j, data_type) in row.iter().enumerate() {
+            let data_type = match (&field_types[j], data_type) {
+                (_, None) => continue,
+                (None, Some(data_type)) => data_type.clone(),
+                (Some(prev_data_type), Some(data_type)) => {
+                    values_coercion(prev_data_type, data_type).ok_or_else(|| {
+                        DataFusionError::Plan(format!(
+                            "Inconsistent data type across values list at row {i} column {j}: \
+                             {data_type} can't be coerced to {prev_data_type}"
+                        ))
+                    })?
+                }
+            };
+            field_types[j] = Some(data_type);
+        }
+    }
+    let fields = field_types
+        .into_iter()
+        .enumerate()
+        .map(|(j, data_type)| {
+            // naming is following convention https://www.postgresql.org/docs/current/queries-values.html
+            DFField::new(
+                None,
+                &format!("column{}", j + 1),
+                data_type.unwrap_or(DataType::Utf8),
+                true,
+            )
+        })
+        .collect::<Vec<_>>();
+    DFSchema::new_with_metadata(fields, HashMap::new())
+}
+
+fn values_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType> {
+    let is_string =
+        |data_type: &DataType| matches!(data_type, DataType::Utf8 | DataType::LargeUtf8);
+    if (is_string(lhs_type) && DataType::is_numeric(rhs_type))
+        || (DataType::is_numeric(lhs_type) && is_string(rhs_type))
+    {
+        return None;
+    }
+    comparison_coercion(lhs_type, rhs_type)
+}
+
 /// Create a LogicalPlanBuilder representing a scan of a table with the provided name and schema.
 /// This is mostly used for testing and documentation.
 pub fn table_scan(
@@ -1338,6 +1550,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_union_by_name() -> Result<()> {
+        let plan1 =
+            table_scan(Some("employee_csv"), &employee_schema(), Some(vec![3, 4]))?;
+        let plan2 =
+            table_scan(Some("employee_csv"), &employee_schema(), Some(vec![4, 0]))?;
+
+        let plan = plan1.union_by_name(plan2.build()?)?.build()?;
+
+        // the columns missing from an input are filled with nulls
+        let expected = "Union\
+        \n  Projection: employee_csv.state, employee_csv.salary, Int32(NULL) AS id\
+        \n    TableScan: employee_csv projection=[state, salary]\
+        \n  Projection: Utf8(NULL) AS state, employee_csv.salary, employee_csv.id\
+        \n    TableScan: employee_csv projection=[salary, id]";
+
+        assert_eq!(expected, format!("{plan:?}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_union_by_name_duplicate_column_error() -> Result<()> {
+        let plan1 = table_scan(None, &employee_schema(), Some(vec![3]))?;
+        let plan2 = table_scan(Some("t1"), &employee_schema(), Some(vec![3]))?
+            .cross_join(
+                table_scan(Some("t2"), &employee_schema(), Some(vec![3]))?.build()?,
+            )?;
+
+        let expected =
+            "Error during planning: UNION BY NAME input has duplicate column state";
+        let err = plan1.union_by_name(plan2.build()?).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_simple_distinct() -> Result<()> {
         let plan =
@@ -1358,6 +1607,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_distinct_on() -> Result<()> {
+        let err = table_scan(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .distinct_on(
+            vec![col("state")],
+            vec![col("id"), col("state")],
+            Some(vec![col("salary").sort(false, true)]),
+        )
+        .unwrap_err();
+
+        let expected = "Error during planning: SELECT DISTINCT ON expressions must match initial ORDER BY expressions";
+        assert_eq!(err.to_string(), expected);
+
+        let plan = table_scan(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .distinct_on(
+            vec![col("state"), col("id")],
+            vec![col("id"), col("state")],
+            Some(vec![col("state").sort(false, true)]),
+        )?
+        .build()?;
+
+        // the missing ON expressions are added to the sort expressions
+        let expected = "\
+        Projection: employee_csv.id, employee_csv.state\
+        \n  DistinctOn: on_expr=[[employee_csv.state, employee_csv.id]], sort_expr=[[employee_csv.state DESC NULLS FIRST, employee_csv.id ASC NULLS LAST]]\
+        \n    TableScan: employee_csv projection=[id, state, salary]";
+
+        assert_eq!(expected, format!("{plan:?}"));
+
+        Ok(())
+    }
+
     #[test]
     fn exists_subquery() -> Result<()> {
         let foo = test_table_scan_with_name("foo")?;
@@ -1542,6 +1831,38 @@ mod tests {
         table_scan(Some(name), &schema, None)?.build()
     }
 
+    #[test]
+    fn plan_builder_intersect_coerces_types() -> Result<()> {
+        let plan1 = table_scan(
+            Some("t1"),
+            &Schema::new(vec![Field::new("a", DataType::Int32, false)]),
+            None,
+        )?;
+        let plan2 = table_scan(
+            Some("t2"),
+            &Schema::new(vec![Field::new("a", DataType::Int64, false)]),
+            None,
+        )?;
+
+        for is_all in [true, false] {
+            let plan = LogicalPlanBuilder::intersect(
+                plan1.clone().build()?,
+                plan2.clone().build()?,
+                is_all,
+            )?;
+            assert_eq!(plan.schema().field(0).data_type(), &DataType::Int64);
+
+            let plan = LogicalPlanBuilder::except(
+                plan2.clone().build()?,
+                plan1.clone().build()?,
+                is_all,
+            )?;
+            assert_eq!(plan.schema().field(0).data_type(), &DataType::Int64);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_intersect_different_num_columns_error() -> Result<()> {
         let plan1 = table_scan(None, &employee_schema(), Some(vec![3]))?;
@@ -1558,4 +1879,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn plan_builder_values_coercion() -> Result<()> {
+        let plan = LogicalPlanBuilder::values(vec![
+            vec![lit(1_i64), lit(ScalarValue::Null)],
+            vec![lit(2.5_f64), lit(ScalarValue::Null)],
+            vec![lit(ScalarValue::Null), lit(ScalarValue::Null)],
+        ])?
+        .build()?;
+        let expected = "Values: (CAST(Int64(1) AS Float64), Utf8(NULL)), \
+                        (Float64(2.5), Utf8(NULL)), (Float64(NULL), Utf8(NULL))";
+        assert_eq!(expected, format!("{plan:?}"));
+        assert_eq!(plan.schema().field(0).data_type(), &DataType::Float64);
+        assert_eq!(plan.schema().field(1).data_type(), &DataType::Utf8);
+
+        let err =
+            values_schema(&[vec![Some(DataType::Int64)], vec![Some(DataType::Utf8)]])
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Inconsistent data type across values list at \
+             row 1 column 0: Utf8 can't be coerced to Int64"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Logical node of `DISTINCT ON`
+
+use crate::expr::Sort;
+use crate::logical_plan::UserDefinedLogicalNode;
+use crate::{Expr, LogicalPlan};
+use datafusion_common::{DFSchemaRef, DataFusionError, Result};
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// Returns the first row of `input` for each distinct value of `on_expr`,
+/// as Postgres' `SELECT DISTINCT ON (on_expr) ... ORDER BY sort_expr`.
+///
+/// The first row of each group is the first one in the order of `sort_expr`,
+/// whose leading expressions are `on_expr`, or any row when there is no
+/// `sort_expr`. The output has the schema of `input`: the expressions
+/// selected by the query are evaluated by a projection above this node.
+#[derive(Debug, Clone)]
+pub struct DistinctOn {
+    /// The expressions whose distinct values are returned
+    pub on_expr: Vec<Expr>,
+    /// The `Expr::Sort` expressions ordering the rows of each group
+    pub sort_expr: Option<Vec<Expr>>,
+    /// The input
+    pub input: Arc<LogicalPlan>,
+}
+
+impl DistinctOn {
+    /// Create a new `DISTINCT ON` node. `sort_expr` must start with the
+    /// expressions of `on_expr`, in any order, and is completed with the
+    /// missing ones in ascending order.
+    pub fn try_new(
+        on_expr: Vec<Expr>,
+        sort_expr: Option<Vec<Expr>>,
+        input: Arc<LogicalPlan>,
+    ) -> Result<Self> {
+        if on_expr.is_empty() {
+            return Err(DataFusionError::Plan(
+                "DISTINCT ON requires at least one expression".to_string(),
+            ));
+        }
+
+        let sort_expr = match sort_expr {
+            Some(mut sort_expr) => {
+                for e in sort_expr.iter().take(on_expr.len()) {
+                    let matched = match e {
+                        Expr::Sort(Sort { expr, .. }) => on_expr.contains(expr),
+                        _ => {
+                            return Err(DataFusionError::Plan(format!(
+                                "DISTINCT ON only accepts sort expressions, got {e:?}"
+                            )))
+                        }
+                    };
+                    if !matched {
+                        return Err(DataFusionError::Plan(
+                            "SELECT DISTINCT ON expressions must match initial ORDER BY expressions"
+                                .to_string(),
+                        ));
+                    }
+                }
+
+                if sort_expr.len() < on_expr.len() {
+                    let missing = on_expr
+                        .iter()
+                        .filter(|e| {
+                            !sort_expr.iter().any(|s| {
+                                matches!(s, Expr::Sort(Sort { expr, .. }) if expr.as_ref() == *e)
+                            })
+                        })
+                        .map(|e| e.clone().sort(true, false))
+                        .collect::<Vec<_>>();
+                    sort_expr.extend(missing);
+                }
+                Some(sort_expr)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            on_expr,
+            sort_expr,
+            input,
+        })
+    }
+}
+
+impl UserDefinedLogicalNode for DistinctOn {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.input.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        let mut exprs = self.on_expr.clone();
+        exprs.extend(self.sort_expr.iter().flatten().cloned());
+        exprs
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DistinctOn: on_expr=[{:?}]", self.on_expr)?;
+        if let Some(sort_expr) = &self.sort_expr {
+            write!(f, ", sort_expr=[{sort_expr:?}]")?;
+        }
+        Ok(())
+    }
+
+    fn from_template(
+        &self,
+        exprs: &[Expr],
+        inputs: &[LogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode> {
+        let (on_expr, sort_expr) = exprs.split_at(self.on_expr.len());
+        Arc::new(Self {
+            on_expr: on_expr.to_vec(),
+            sort_expr: self.sort_expr.as_ref().map(|_| sort_expr.to_vec()),
+            input: Arc::new(inputs[0].clone()),
+        })
+    }
+}
@@ -0,0 +1,306 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Checks of the invariants of logical plans
+
+use std::collections::HashSet;
+
+use arrow::datatypes::DataType;
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
+use datafusion_common::{Column, DFSchema, DataFusionError, Result};
+
+use crate::logical_plan::{
+    Aggregate, CrossJoin, Filter, Join, JoinType, LogicalPlan, Projection, SubqueryAlias,
+    TableScan, Union, Window,
+};
+use crate::utils::{expr_to_columns, grouping_set_expr_count};
+use crate::{Expr, ExprSchemable};
+
+impl LogicalPlan {
+    /// Checks the invariants of the plan and of its inputs and subqueries,
+    /// returning a [`DataFusionError::Plan`] describing the first violated
+    /// one:
+    ///
+    /// * the schema of each node has a field for each of its expressions, or
+    ///   of its inputs, for example a [`Projection`] has a field for each of
+    ///   its expressions and an inner [`Join`] for each field of its inputs,
+    /// * the columns of the expressions of each node refer to fields of its
+    ///   inputs, and the columns of equijoin keys to the input of their
+    ///   side,
+    /// * the columns of subqueries that are not fields of the subquery refer
+    ///   to fields of the plans the subquery is nested in,
+    /// * the predicate of a [`Filter`] is a boolean.
+    ///
+    /// A plan violating them fails in physical planning or execution, with
+    /// errors far from their cause. The optimizer checks them after each
+    /// rule when the `datafusion.optimizer.check_plan_invariants` option is
+    /// set, and the authors of extensions can call it on the plans their
+    /// rules and nodes produce. The expressions of extension nodes and the
+    /// filters of table scans are not checked.
+    pub fn check_invariants(&self) -> Result<()> {
+        check_plan(self, &[])
+    }
+}
+
+/// Checks `plan`, nested in subqueries of the plans with the schemas `outer`
+fn check_plan(plan: &LogicalPlan, outer: &[&DFSchema]) -> Result<()> {
+    check_schema(plan)?;
+
+    let inputs = plan.inputs();
+    let input_schemas = inputs
+        .iter()
+        .map(|input| input.schema().as_ref())
+        .collect::<Vec<_>>();
+    match plan {
+        LogicalPlan::Extension(_) | LogicalPlan::TableScan(_) => {}
+        _ => {
+            for expr in plan.expressions() {
+                check_columns(plan, &expr, &input_schemas, outer)?;
+                check_subqueries(&expr, &input_schemas, outer)?;
+            }
+        }
+    }
+
+    if let LogicalPlan::Join(Join {
+        left, right, on, ..
+    }) = plan
+    {
+        for (left_key, right_key) in on {
+            check_columns(plan, left_key, &[left.schema()], outer)?;
+            check_columns(plan, right_key, &[right.schema()], outer)?;
+        }
+    }
+
+    if let LogicalPlan::Filter(Filter {
+        predicate, input, ..
+    }) = plan
+    {
+        // the type of correlated predicates can't be computed from the input
+        if let Ok(data_type) = predicate.get_type(input.schema()) {
+            if !matches!(data_type, DataType::Boolean | DataType::Null) {
+                return Err(invalid_plan(
+                    plan,
+                    format!("the predicate {predicate} is of type {data_type}"),
+                ));
+            }
+        }
+    }
+
+    inputs
+        .into_iter()
+        .try_for_each(|input| check_plan(input, outer))
+}
+
+/// Checks that the schema of `plan` has the fields of its expressions or
+/// inputs
+fn check_schema(plan: &LogicalPlan) -> Result<()> {
+    let fields = plan.schema().fields().len();
+    let expected = match plan {
+        LogicalPlan::Projection(Projection { expr, .. }) => expr.len(),
+        LogicalPlan::Aggregate(Aggregate {
+            group_expr,
+            aggr_expr,
+            ..
+        }) => grouping_set_expr_count(group_expr)? + aggr_expr.len(),
+        LogicalPlan::Window(Window {
+            input, window_expr, ..
+        }) => input.schema().fields().len() + window_expr.len(),
+        LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            input.schema().fields().len()
+        }
+        LogicalPlan::Join(Join {
+            left,
+            right,
+            join_type,
+            ..
+        }) => match join_type {
+            JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
+                left.schema().fields().len() + right.schema().fields().len()
+            }
+            JoinType::LeftSemi | JoinType::LeftAnti => left.schema().fields().len(),
+            JoinType::RightSemi | JoinType::RightAnti => right.schema().fields().len(),
+            JoinType::LeftMark => left.schema().fields().len() + 1,
+        },
+        LogicalPlan::CrossJoin(CrossJoin { left, right, .. }) => {
+            left.schema().fields().len() + right.schema().fields().len()
+        }
+        LogicalPlan::Union(Union { inputs, .. }) => {
+            for input in inputs {
+                let input_fields = input.schema().fields().len();
+                if input_fields != fields {
+                    return Err(invalid_plan(
+                        plan,
+                        format!(
+                            "an input has {input_fields} fields, but the schema has {fields}"
+                        ),
+                    ));
+                }
+            }
+            fields
+        }
+        LogicalPlan::TableScan(TableScan {
+            projection: Some(projection),
+            ..
+        }) => projection.len(),
+        _ => fields,
+    };
+    if fields != expected {
+        return Err(invalid_plan(
+            plan,
+            format!("the schema has {fields} fields, but {expected} are expected"),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the columns of `expr` refer to fields of `schemas` or, for
+/// correlated subqueries, of `outer`
+fn check_columns(
+    plan: &LogicalPlan,
+    expr: &Expr,
+    schemas: &[&DFSchema],
+    outer: &[&DFSchema],
+) -> Result<()> {
+    let mut columns = HashSet::new();
+    expr_to_columns(expr, &mut columns)?;
+    for column in columns {
+        if !resolves(&column, schemas) && !resolves(&column, outer) {
+            return Err(invalid_plan(
+                plan,
+                format!("the column {column} of {expr} is not a field of its input"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn resolves(column: &Column, schemas: &[&DFSchema]) -> bool {
+    schemas
+        .iter()
+        .any(|schema| schema.index_of_column(column).is_ok())
+}
+
+/// Checks the subqueries of `expr`, nested in the plans with the schemas
+/// `schemas` and `outer`
+fn check_subqueries(
+    expr: &Expr,
+    schemas: &[&DFSchema],
+    outer: &[&DFSchema],
+) -> Result<()> {
+    let outer = schemas.iter().chain(outer).copied().collect::<Vec<_>>();
+    expr.apply(&mut |expr| {
+        match expr {
+            Expr::Exists { subquery, .. }
+            | Expr::InSubquery { subquery, .. }
+            | Expr::ScalarSubquery(subquery) => {
+                check_plan(&subquery.subquery, &outer)?;
+            }
+            _ => {}
+        }
+        Ok(VisitRecursion::Continue)
+    })?;
+    Ok(())
+}
+
+fn invalid_plan(plan: &LogicalPlan, reason: String) -> DataFusionError {
+    let node = plan.display().to_string();
+    DataFusionError::Plan(format!("Invalid plan node {node}: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logical_plan::table_scan;
+    use crate::{col, exists, lit, LogicalPlanBuilder};
+    use arrow::datatypes::{Field, Schema};
+    use std::sync::Arc;
+
+    fn scan(name: &str) -> Result<LogicalPlanBuilder> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+        table_scan(Some(name), &schema, None)
+    }
+
+    #[test]
+    fn valid_plans() -> Result<()> {
+        scan("t")?
+            .filter(col("a").gt(lit(1)))?
+            .project(vec![col("a"), col("b") + lit(1)])?
+            .build()?
+            .check_invariants()?;
+
+        // a correlated subquery
+        let subquery = scan("u")?
+            .filter(col("u.a").eq(col("t.a")))?
+            .project(vec![col("u.b")])?
+            .build()?;
+        scan("t")?
+            .filter(exists(Arc::new(subquery)))?
+            .build()?
+            .check_invariants()?;
+        Ok(())
+    }
+
+    #[test]
+    fn dangling_column() -> Result<()> {
+        let input = scan("t")?.project(vec![col("a")])?.build()?;
+        // a projection of a column removed from its input
+        let plan = LogicalPlan::Projection(Projection::try_new_with_schema(
+            vec![col("t.b")],
+            Arc::new(input),
+            scan("t")?
+                .project(vec![col("b")])?
+                .build()?
+                .schema()
+                .clone(),
+        )?);
+        let err = plan.check_invariants().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Invalid plan node Projection: t.b: \
+             the column t.b of t.b is not a field of its input"
+        );
+
+        // a subquery referring to a column of no plan
+        let subquery = scan("u")?
+            .filter(col("u.a").eq(col("v.a")))?
+            .project(vec![col("u.b")])?
+            .build()?;
+        let plan = scan("t")?.filter(exists(Arc::new(subquery)))?.build()?;
+        let err = plan.check_invariants().unwrap_err();
+        assert!(err.to_string().contains("the column v.a"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn non_boolean_predicate() -> Result<()> {
+        let input = scan("t")?.build()?;
+        let plan = LogicalPlan::Filter(Filter {
+            predicate: col("t.a") + lit(1),
+            input: Arc::new(input),
+        });
+        let err = plan.check_invariants().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("the predicate t.a + Int32(1) is of type Int32"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -17,19 +17,27 @@
 
 pub mod builder;
 pub mod display;
+mod distinct_on;
 mod extension;
+mod invariants;
 mod plan;
+mod set_operation;
 
 pub use builder::{table_scan, LogicalPlanBuilder};
 pub use plan::{
-    Aggregate, Analyze, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
-    CreateMemoryTable, CreateView, CrossJoin, Distinct, DropTable, DropView,
-    EmptyRelation, Explain, Extension, Filter, Join, JoinConstraint, JoinType, Limit,
-    LogicalPlan, Partitioning, PlanType, PlanVisitor, Prepare, Projection, Repartition,
-    SetVariable, Sort, StringifiedPlan, Subquery, SubqueryAlias, TableScan,
-    ToStringifiedPlan, Union, Values, Window,
+    Aggregate, Analyze, AnalyzeTable, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable, CreateFunction, CreateFunctionArg, CreateMemoryTable,
+    CreateView, CrossJoin, Distinct, DropFunction, DropTable, DropView, EmptyRelation,
+    Explain, Extension, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan,
+    Partitioning, PlanType, PlanVisitor, Prepare, Projection, Repartition, SetVariable,
+    SharedCte, Sort, StringifiedPlan, Subquery, SubqueryAlias, TableScan,
+    ToStringifiedPlan, Transaction, TransactionStatement, Union, Values, Window,
 };
 
 pub use display::display_schema;
 
+pub use distinct_on::DistinctOn;
+
 pub use extension::UserDefinedLogicalNode;
+
+pub use set_operation::{SetOperationAll, SetOperationType};
@@ -29,6 +29,7 @@ use crate::{
     build_join_schema, Expr, ExprSchemable, TableProviderFilterPushDown, TableSource,
 };
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion_common::fingerprint::{normalize_generated_aliases, Fingerprint};
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::{
     plan_err, Column, DFSchema, DFSchemaRef, DataFusionError, OwnedTableReference,
@@ -37,6 +38,7 @@ use datafusion_common::{
 use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// A LogicalPlan represents the different types of relational
@@ -84,6 +86,9 @@ pub enum LogicalPlan {
     Subquery(Subquery),
     /// Aliased relation provides, or changes, the name of a relation.
     SubqueryAlias(SubqueryAlias),
+    /// A reference to a common table expression, which may be computed once
+    /// for all its references.
+    SharedCte(SharedCte),
     /// Skip some number of rows, and then fetch some number of rows.
     Limit(Limit),
     /// Creates an external table.
@@ -100,6 +105,14 @@ pub enum LogicalPlan {
     DropTable(DropTable),
     /// Drops a view.
     DropView(DropView),
+    /// Computes and caches the statistics of a table.
+    AnalyzeTable(AnalyzeTable),
+    /// Creates a user defined function.
+    CreateFunction(CreateFunction),
+    /// Drops a user defined function.
+    DropFunction(DropFunction),
+    /// Begins, commits or rolls back a transaction, or one of its savepoints.
+    Transaction(Transaction),
     /// Values expression. See
     /// [Postgres VALUES](https://www.postgresql.org/docs/current/queries-values.html)
     /// documentation for more details.
@@ -141,6 +154,7 @@ impl LogicalPlan {
             LogicalPlan::Limit(Limit { input, .. }) => input.schema(),
             LogicalPlan::Subquery(Subquery { subquery, .. }) => subquery.schema(),
             LogicalPlan::SubqueryAlias(SubqueryAlias { schema, .. }) => schema,
+            LogicalPlan::SharedCte(SharedCte { input, .. }) => input.schema(),
             LogicalPlan::CreateExternalTable(CreateExternalTable { schema, .. }) => {
                 schema
             }
@@ -157,6 +171,10 @@ impl LogicalPlan {
             LogicalPlan::CreateCatalog(CreateCatalog { schema, .. }) => schema,
             LogicalPlan::DropTable(DropTable { schema, .. }) => schema,
             LogicalPlan::DropView(DropView { schema, .. }) => schema,
+            LogicalPlan::AnalyzeTable(AnalyzeTable { schema, .. }) => schema,
+            LogicalPlan::CreateFunction(CreateFunction { schema, .. }) => schema,
+            LogicalPlan::DropFunction(DropFunction { schema, .. }) => schema,
+            LogicalPlan::Transaction(Transaction { schema, .. }) => schema,
             LogicalPlan::SetVariable(SetVariable { schema, .. }) => schema,
         }
     }
@@ -214,9 +232,14 @@ impl LogicalPlan {
             | LogicalPlan::CreateView(CreateView { input, .. })
             | LogicalPlan::Filter(Filter { input, .. })
             | LogicalPlan::Distinct(Distinct { input, .. })
+            | LogicalPlan::SharedCte(SharedCte { input, .. })
             | LogicalPlan::Prepare(Prepare { input, .. }) => input.all_schemas(),
             LogicalPlan::DropTable(_)
             | LogicalPlan::DropView(_)
+            | LogicalPlan::AnalyzeTable(_)
+            | LogicalPlan::CreateFunction(_)
+            | LogicalPlan::DropFunction(_)
+            | LogicalPlan::Transaction(_)
             | LogicalPlan::SetVariable(_) => vec![],
         }
     }
@@ -302,6 +325,7 @@ impl LogicalPlan {
             LogicalPlan::EmptyRelation(_)
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::SharedCte(_)
             | LogicalPlan::Limit(_)
             | LogicalPlan::CreateExternalTable(_)
             | LogicalPlan::CreateMemoryTable(_)
@@ -311,6 +335,10 @@ impl LogicalPlan {
             | LogicalPlan::DropTable(_)
             | LogicalPlan::SetVariable(_)
             | LogicalPlan::DropView(_)
+            | LogicalPlan::AnalyzeTable(_)
+            | LogicalPlan::CreateFunction(_)
+            | LogicalPlan::DropFunction(_)
+            | LogicalPlan::Transaction(_)
             | LogicalPlan::CrossJoin(_)
             | LogicalPlan::Analyze(_)
             | LogicalPlan::Explain(_)
@@ -335,6 +363,7 @@ impl LogicalPlan {
             LogicalPlan::Limit(Limit { input, .. }) => vec![input],
             LogicalPlan::Subquery(Subquery { subquery, .. }) => vec![subquery],
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => vec![input],
+            LogicalPlan::SharedCte(SharedCte { input, .. }) => vec![input],
             LogicalPlan::Extension(extension) => extension.node.inputs(),
             LogicalPlan::Union(Union { inputs, .. }) => {
                 inputs.iter().map(|arc| arc.as_ref()).collect()
@@ -356,7 +385,11 @@ impl LogicalPlan {
             | LogicalPlan::CreateCatalog(_)
             | LogicalPlan::DropTable(_)
             | LogicalPlan::SetVariable(_)
-            | LogicalPlan::DropView(_) => vec![],
+            | LogicalPlan::DropView(_)
+            | LogicalPlan::AnalyzeTable(_)
+            | LogicalPlan::CreateFunction(_)
+            | LogicalPlan::DropFunction(_)
+            | LogicalPlan::Transaction(_) => vec![],
         }
     }
 
@@ -531,6 +564,7 @@ impl LogicalPlan {
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
                 input.accept(visitor)?
             }
+            LogicalPlan::SharedCte(SharedCte { input, .. }) => input.accept(visitor)?,
             LogicalPlan::CreateMemoryTable(CreateMemoryTable { input, .. })
             | LogicalPlan::CreateView(CreateView { input, .. })
             | LogicalPlan::Prepare(Prepare { input, .. }) => input.accept(visitor)?,
@@ -553,7 +587,11 @@ impl LogicalPlan {
             | LogicalPlan::CreateCatalog(_)
             | LogicalPlan::DropTable(_)
             | LogicalPlan::SetVariable(_)
-            | LogicalPlan::DropView(_) => true,
+            | LogicalPlan::DropView(_)
+            | LogicalPlan::AnalyzeTable(_)
+            | LogicalPlan::CreateFunction(_)
+            | LogicalPlan::DropFunction(_)
+            | LogicalPlan::Transaction(_) => true,
         };
         if !recurse {
             return Ok(false);
@@ -748,6 +786,23 @@ impl LogicalPlan {
         Wrapper(self)
     }
 
+    /// Returns the canonical form of the plan: its [`Self::display_indent_schema`],
+    /// with the aliases generated by the optimizer renumbered in their order
+    /// of appearance. Two plans with the same canonical form compute the same
+    /// results, given the same tables and the same inputs for their volatile
+    /// functions.
+    pub fn canonical_form(&self) -> String {
+        normalize_generated_aliases(&self.display_indent_schema().to_string())
+    }
+
+    /// Returns the fingerprint of the [`Self::canonical_form`] of the plan,
+    /// which is stable across processes and releases as long as the display
+    /// of the plan doesn't change. Use [`Fingerprint::as_u64`] for a 64-bit
+    /// fingerprint.
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::of(self.canonical_form().as_bytes())
+    }
+
     /// Return a `format`able structure that produces lines meant for
     /// graphical display using the `DOT` language. This format can be
     /// visualized using software from
@@ -862,6 +917,7 @@ impl LogicalPlan {
                         ref projection,
                         ref filters,
                         ref fetch,
+                        ref ordering,
                         ..
                     }) => {
                         let projected_fields = match projection {
@@ -883,9 +939,12 @@ impl LogicalPlan {
                             let mut partial_filter = vec![];
                             let mut unsupported_filters = vec![];
 
-                            filters.iter().for_each(|x| {
-                                if let Ok(t) = source.supports_filter_pushdown(x) {
-                                    match t {
+                            let filter_refs: Vec<&Expr> = filters.iter().collect();
+                            if let Ok(supported) =
+                                source.supports_filters_pushdown(&filter_refs)
+                            {
+                                filters.iter().zip(supported).for_each(
+                                    |(x, t)| match t {
                                         TableProviderFilterPushDown::Exact => {
                                             full_filter.push(x)
                                         }
@@ -895,9 +954,9 @@ impl LogicalPlan {
                                         TableProviderFilterPushDown::Unsupported => {
                                             unsupported_filters.push(x)
                                         }
-                                    }
-                                }
-                            });
+                                    },
+                                );
+                            }
 
                             if !full_filter.is_empty() {
                                 write!(f, ", full_filters={full_filter:?}")?;
@@ -917,6 +976,10 @@ impl LogicalPlan {
                             write!(f, ", fetch={n}")?;
                         }
 
+                        if !ordering.is_empty() {
+                            write!(f, ", ordering={ordering:?}")?;
+                        }
+
                         Ok(())
                     }
                     LogicalPlan::Projection(Projection { ref expr, .. }) => {
@@ -1042,6 +1105,9 @@ impl LogicalPlan {
                     LogicalPlan::SubqueryAlias(SubqueryAlias { ref alias, .. }) => {
                         write!(f, "SubqueryAlias: {alias}")
                     }
+                    LogicalPlan::SharedCte(SharedCte { ref name, .. }) => {
+                        write!(f, "SharedCte: {name}")
+                    }
                     LogicalPlan::CreateExternalTable(CreateExternalTable {
                         ref name,
                         ..
@@ -1077,6 +1143,27 @@ impl LogicalPlan {
                     }) => {
                         write!(f, "DropView: {name:?} if not exist:={if_exists}")
                     }
+                    LogicalPlan::AnalyzeTable(AnalyzeTable { name, columns, .. }) => {
+                        match columns {
+                            Some(columns) => write!(
+                                f,
+                                "AnalyzeTable: {name:?} columns=[{}]",
+                                columns.join(", ")
+                            ),
+                            None => write!(f, "AnalyzeTable: {name:?}"),
+                        }
+                    }
+                    LogicalPlan::CreateFunction(CreateFunction { name, .. }) => {
+                        write!(f, "CreateFunction: {name:?}")
+                    }
+                    LogicalPlan::DropFunction(DropFunction {
+                        name, if_exists, ..
+                    }) => {
+                        write!(f, "DropFunction: {name:?} if not exist:={if_exists}")
+                    }
+                    LogicalPlan::Transaction(Transaction { statement, .. }) => {
+                        write!(f, "Transaction: {statement}")
+                    }
                     LogicalPlan::SetVariable(SetVariable {
                         variable, value, ..
                     }) => {
@@ -1132,6 +1219,11 @@ pub enum JoinType {
     LeftAnti,
     /// Right Anti Join
     RightAnti,
+    /// Left Mark Join: every row of the left input, followed by a non null
+    /// boolean `mark` column telling if the row matched a row of the right
+    /// input. It evaluates subqueries that can't filter rows with a semi or
+    /// anti join, such as EXISTS under an OR.
+    LeftMark,
 }
 
 impl JoinType {
@@ -1151,6 +1243,7 @@ impl Display for JoinType {
             JoinType::RightSemi => "RightSemi",
             JoinType::LeftAnti => "LeftAnti",
             JoinType::RightAnti => "RightAnti",
+            JoinType::LeftMark => "LeftMark",
         };
         write!(f, "{join_type}")
     }
@@ -1209,6 +1302,105 @@ pub struct DropView {
     pub schema: DFSchemaRef,
 }
 
+/// Computes the statistics of a table by scanning it and stores them on the
+/// table registration, where they are picked up by cost-based optimizations.
+#[derive(Clone)]
+pub struct AnalyzeTable {
+    /// The table name
+    pub name: OwnedTableReference,
+    /// The columns to compute column statistics for; all columns when `None`
+    pub columns: Option<Vec<String>>,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// An argument of a [`CreateFunction`] plan
+#[derive(Clone, Debug)]
+pub struct CreateFunctionArg {
+    /// The argument name, if any
+    pub name: Option<String>,
+    /// The argument type
+    pub data_type: DataType,
+    /// The value of the argument when it is omitted
+    pub default: Option<Expr>,
+}
+
+/// Creates a user defined function. How the definition is interpreted
+/// depends on its language and is up to the execution context.
+#[derive(Clone)]
+pub struct CreateFunction {
+    /// The function name
+    pub name: String,
+    /// Whether to replace an existing function of the same name
+    pub or_replace: bool,
+    /// The function arguments
+    pub args: Vec<CreateFunctionArg>,
+    /// The return type, if specified
+    pub return_type: Option<DataType>,
+    /// The language the function is defined in, if specified
+    pub language: Option<String>,
+    /// The definition of the function, if specified
+    pub definition: Option<String>,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// Drops a user defined function.
+#[derive(Clone)]
+pub struct DropFunction {
+    /// The function name
+    pub name: String,
+    /// If the function exists
+    pub if_exists: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// A transaction control statement
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionStatement {
+    /// `BEGIN`: starts a transaction
+    Begin,
+    /// `COMMIT`: commits the current transaction
+    Commit,
+    /// `ROLLBACK`: rolls back the current transaction
+    Rollback,
+    /// `SAVEPOINT name`: defines a savepoint in the current transaction
+    Savepoint(String),
+    /// `ROLLBACK TO SAVEPOINT name`: rolls back the current transaction to a
+    /// savepoint, which remains defined
+    RollbackToSavepoint(String),
+    /// `RELEASE SAVEPOINT name`: forgets a savepoint of the current transaction
+    ReleaseSavepoint(String),
+}
+
+impl Display for TransactionStatement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TransactionStatement::Begin => write!(f, "BEGIN"),
+            TransactionStatement::Commit => write!(f, "COMMIT"),
+            TransactionStatement::Rollback => write!(f, "ROLLBACK"),
+            TransactionStatement::Savepoint(name) => write!(f, "SAVEPOINT {name}"),
+            TransactionStatement::RollbackToSavepoint(name) => {
+                write!(f, "ROLLBACK TO SAVEPOINT {name}")
+            }
+            TransactionStatement::ReleaseSavepoint(name) => {
+                write!(f, "RELEASE SAVEPOINT {name}")
+            }
+        }
+    }
+}
+
+/// Controls the transaction of the session. How the statement is applied is
+/// up to the execution context.
+#[derive(Clone)]
+pub struct Transaction {
+    /// The transaction control statement
+    pub statement: TransactionStatement,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
 /// Set a Variable's value -- value in [`ConfigOptions`]
 #[derive(Clone)]
 pub struct SetVariable {
@@ -1336,6 +1528,36 @@ impl SubqueryAlias {
     }
 }
 
+/// A reference to the common table expression `name` of a `WITH` clause.
+///
+/// All the references to a common table expression share the same `id`, so
+/// that its results can be computed once and read by each of them, instead of
+/// executing its plan for each reference. Predicates and projections are not
+/// pushed into a shared common table expression, so that all its references
+/// keep computing the same relation.
+#[derive(Clone)]
+pub struct SharedCte {
+    /// Identifies the common table expression among all the plans
+    pub id: usize,
+    /// The name of the common table expression
+    pub name: String,
+    /// The plan of the common table expression
+    pub input: Arc<LogicalPlan>,
+}
+
+impl SharedCte {
+    /// Create a reference to a new common table expression, with an id that
+    /// is unique in the process
+    pub fn new(name: impl Into<String>, input: Arc<LogicalPlan>) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            name: name.into(),
+            input,
+        }
+    }
+}
+
 /// Filters rows from its input that do not match an
 /// expression (essentially a WHERE clause with a predicate
 /// expression).
@@ -1420,6 +1642,10 @@ pub struct TableScan {
     pub filters: Vec<Expr>,
     /// Optional number of rows to read
     pub fetch: Option<usize>,
+    /// Optional ordering, as a list of [`Expr::Sort`], requested from the
+    /// table provider. It is only set for orderings the source advertises
+    /// in [`TableSource::output_orderings`]
+    pub ordering: Vec<Expr>,
 }
 
 /// Apply Cross Join to two logical plans
@@ -1462,6 +1688,9 @@ pub struct CreateMemoryTable {
     pub if_not_exists: bool,
     /// Option to replace table content if table already exists
     pub or_replace: bool,
+    /// Whether the table is only visible to the session creating it, which
+    /// drops it when it ends
+    pub temporary: bool,
 }
 
 /// Creates a view.
@@ -1776,6 +2005,11 @@ pub enum Partitioning {
 pub enum PlanType {
     /// The initial LogicalPlan provided to DataFusion
     InitialLogicalPlan,
+    /// The LogicalPlan which results from applying an analyzer pass
+    AnalyzedLogicalPlan {
+        /// The name of the analyzer which produced this plan
+        analyzer_name: String,
+    },
     /// The LogicalPlan which results from applying an optimizer pass
     OptimizedLogicalPlan {
         /// The name of the optimizer which produced this plan
@@ -1798,6 +2032,9 @@ impl Display for PlanType {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             PlanType::InitialLogicalPlan => write!(f, "initial_logical_plan"),
+            PlanType::AnalyzedLogicalPlan { analyzer_name } => {
+                write!(f, "logical_plan after {analyzer_name}")
+            }
             PlanType::OptimizedLogicalPlan { optimizer_name } => {
                 write!(f, "logical_plan after {optimizer_name}")
             }
@@ -1905,6 +2142,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_fingerprint() -> Result<()> {
+        let aliased_plan = |alias: &str, threshold: i32| {
+            table_scan(Some("employee_csv"), &employee_schema(), Some(vec![0]))?
+                .alias(alias)?
+                .filter(col(format!("{alias}.id").as_str()).gt(lit(threshold)))?
+                .build()
+        };
+
+        let plan = aliased_plan("__correlated_sq_1", 5)?;
+        assert_eq!(
+            plan.canonical_form(),
+            "Filter: __correlated_sq_1.id > Int32(5) [id:Int32]\
+            \n  SubqueryAlias: __correlated_sq_1 [id:Int32]\
+            \n    TableScan: employee_csv projection=[id] [id:Int32]"
+        );
+        // the generated aliases don't change the fingerprint
+        let renamed = aliased_plan("__correlated_sq_7", 5)?;
+        assert_eq!(plan.canonical_form(), renamed.canonical_form());
+        assert_eq!(plan.fingerprint(), renamed.fingerprint());
+        // other differences do
+        let other_alias = aliased_plan("sq", 5)?;
+        assert_ne!(plan.fingerprint(), other_alias.fingerprint());
+        let other_filter = aliased_plan("__correlated_sq_1", 6)?;
+        assert_ne!(plan.fingerprint(), other_filter.fingerprint());
+        assert_ne!(
+            plan.fingerprint().as_u64(),
+            other_filter.fingerprint().as_u64()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_display_subquery_alias() -> Result<()> {
         let plan1 = table_scan(Some("employee_csv"), &employee_schema(), Some(vec![3]))?
@@ -0,0 +1,118 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Logical node of the set operations with `ALL` semantics
+
+use crate::logical_plan::UserDefinedLogicalNode;
+use crate::{Expr, LogicalPlan};
+use datafusion_common::{DFSchemaRef, DataFusionError, Result};
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// The set operations evaluated by [`SetOperationAll`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetOperationType {
+    /// `INTERSECT ALL`
+    Intersect,
+    /// `EXCEPT ALL`
+    Except,
+}
+
+impl fmt::Display for SetOperationType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetOperationType::Intersect => write!(f, "IntersectAll"),
+            SetOperationType::Except => write!(f, "ExceptAll"),
+        }
+    }
+}
+
+/// `INTERSECT ALL` or `EXCEPT ALL` of two inputs with the same number of
+/// columns and types, comparing the rows of both inputs with NULLs equal.
+///
+/// A row found `m` times in `left` and `n` times in `right` is returned
+/// `min(m, n)` times by `INTERSECT ALL`, and `max(m - n, 0)` times by
+/// `EXCEPT ALL`. The output has the schema of `left`.
+#[derive(Debug, Clone)]
+pub struct SetOperationAll {
+    /// The set operation
+    pub op: SetOperationType,
+    /// The input whose rows are returned
+    pub left: Arc<LogicalPlan>,
+    /// The input whose rows are counted
+    pub right: Arc<LogicalPlan>,
+}
+
+impl SetOperationAll {
+    /// Create a new set operation of `left` and `right`
+    pub fn try_new(
+        op: SetOperationType,
+        left: Arc<LogicalPlan>,
+        right: Arc<LogicalPlan>,
+    ) -> Result<Self> {
+        let left_types = left.schema().fields().iter().map(|f| f.data_type());
+        let right_types = right.schema().fields().iter().map(|f| f.data_type());
+        if !left_types.eq(right_types) {
+            return Err(DataFusionError::Plan(format!(
+                "{op} inputs must have the same column types, left is {:?} and right is {:?}",
+                left.schema(),
+                right.schema()
+            )));
+        }
+        Ok(Self { op, left, right })
+    }
+}
+
+impl UserDefinedLogicalNode for SetOperationAll {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.left, &self.right]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.left.schema()
+    }
+
+    /// All the columns of both inputs, so that none is pruned
+    fn expressions(&self) -> Vec<Expr> {
+        self.inputs()
+            .iter()
+            .flat_map(|input| input.schema().fields())
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.op)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[Expr],
+        inputs: &[LogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode> {
+        Arc::new(Self {
+            op: self.op,
+            left: Arc::new(inputs[0].clone()),
+            right: Arc::new(inputs[1].clone()),
+        })
+    }
+}
@@ -52,6 +52,15 @@ pub enum TypeSignature {
     Uniform(usize, Vec<DataType>),
     /// exact number of arguments of an exact type
     Exact(Vec<DataType>),
+    /// arbitrary number of arguments coerced to their common type, as for
+    /// comparisons, such as `coalesce`
+    VariadicCoercible,
+    /// fixed number of numeric arguments coerced to their common numeric type
+    Numeric(usize),
+    /// fixed number of arguments, each of one of a list of types in order of
+    /// preference. An argument keeps its type if it is in the list and is
+    /// otherwise coerced to the first type of the list it can be coerced to.
+    Coercible(Vec<Vec<DataType>>),
     /// fixed number of arguments of arbitrary types
     Any(usize),
     /// One of a list of signatures
@@ -107,6 +116,27 @@ impl Signature {
             volatility,
         }
     }
+    /// variadic_coercible - Creates a variadic signature that represents an arbitrary number of arguments coerced to their common type.
+    pub fn variadic_coercible(volatility: Volatility) -> Self {
+        Self {
+            type_signature: TypeSignature::VariadicCoercible,
+            volatility,
+        }
+    }
+    /// numeric - Creates a signature of a fixed number of numeric arguments coerced to their common numeric type.
+    pub fn numeric(arg_count: usize, volatility: Volatility) -> Self {
+        Self {
+            type_signature: TypeSignature::Numeric(arg_count),
+            volatility,
+        }
+    }
+    /// coercible - Creates a signature which coerces each argument to the first of its valid_types it can be coerced to.
+    pub fn coercible(valid_types: Vec<Vec<DataType>>, volatility: Volatility) -> Self {
+        Self {
+            type_signature: TypeSignature::Coercible(valid_types),
+            volatility,
+        }
+    }
     /// any - Creates a signature which can a be made of any type but of a specified number
     pub fn any(arg_count: usize, volatility: Volatility) -> Self {
         Signature {
@@ -18,6 +18,7 @@
 use crate::{Expr, LogicalPlan};
 use arrow::datatypes::SchemaRef;
 use std::any::Any;
+use std::collections::HashMap;
 
 ///! Table source
 
@@ -77,8 +78,53 @@ pub trait TableSource: Sync + Send {
         Ok(TableProviderFilterPushDown::Unsupported)
     }
 
+    /// Tests whether the table provider can make use of each of the `filters`,
+    /// the conjuncts of a predicate, to optimise data retrieval. Returns one
+    /// result per filter, in the same order.
+    ///
+    /// The filters are passed together so that the provider can decide on
+    /// them as a whole. The default implementation calls
+    /// [`TableSource::supports_filter_pushdown`] for each filter.
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> datafusion_common::Result<Vec<TableProviderFilterPushDown>> {
+        filters
+            .iter()
+            .map(|filter| self.supports_filter_pushdown(filter))
+            .collect()
+    }
+
+    /// Returns, for each of the `filters` reported as
+    /// [`TableProviderFilterPushDown::Inexact`], the residual predicate that
+    /// still has to be evaluated on the rows returned by the table provider,
+    /// or `None` if the whole filter has to be evaluated again.
+    ///
+    /// On the rows returned by a scan the filter was pushed down to, the
+    /// residual predicate must be equivalent to the filter.
+    fn residual_filters(
+        &self,
+        filters: &[&Expr],
+    ) -> datafusion_common::Result<Vec<Option<Expr>>> {
+        Ok(vec![None; filters.len()])
+    }
+
     /// Get the Logical plan of this table provider, if available.
     fn get_logical_plan(&self) -> Option<&LogicalPlan> {
         None
     }
+
+    /// Get the expressions, by column name, replacing the values of the
+    /// masked columns of this table, for example to redact e-mail addresses.
+    /// Masks are applied over the scan before any other expression of the
+    /// query sees the data.
+    fn column_masks(&self) -> HashMap<String, Expr> {
+        HashMap::new()
+    }
+
+    /// Get the orderings, each a list of [`Expr::Sort`] over the table's
+    /// columns, in which the table provider can return its data
+    fn output_orderings(&self) -> Vec<Vec<Expr>> {
+        vec![]
+    }
 }
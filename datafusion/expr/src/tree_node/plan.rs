@@ -0,0 +1,56 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Tree node implementation for logical plan
+
+use crate::utils::from_plan;
+use crate::LogicalPlan;
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
+use datafusion_common::Result;
+
+/// The children of a [`LogicalPlan`] are its inputs, as returned by
+/// [`LogicalPlan::inputs`]. The subqueries of its expressions are not
+/// walked.
+impl TreeNode for LogicalPlan {
+    fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>,
+    {
+        for child in self.inputs() {
+            if op(child)? == VisitRecursion::Stop {
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    }
+
+    fn map_children<F>(self, transform: F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Self>,
+    {
+        let inputs = self.inputs();
+        if inputs.is_empty() {
+            return Ok(self);
+        }
+        let new_inputs = inputs
+            .into_iter()
+            .cloned()
+            .map(transform)
+            .collect::<Result<Vec<_>>>()?;
+        from_plan(&self, &self.expressions(), &new_inputs)
+    }
+}
@@ -31,7 +31,7 @@
 //! i64. However, i64 -> i32 is never performed as there are i64
 //! values which can not be represented by i32 values.
 
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, IntervalUnit};
 
 /// Determine if a DataType is signed numeric or not
 pub fn is_signed_numeric(dt: &DataType) -> bool {
@@ -62,6 +62,21 @@ pub fn is_numeric(dt: &DataType) -> bool {
         )
 }
 
+/// Determine if a DataType is a signed or unsigned integer or not
+pub fn is_integer(dt: &DataType) -> bool {
+    matches!(
+        dt,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+    )
+}
+
 /// Determine if a DataType is Timestamp or not
 pub fn is_timestamp(dt: &DataType) -> bool {
     matches!(dt, DataType::Timestamp(_, _))
@@ -72,6 +87,34 @@ pub fn is_date(dt: &DataType) -> bool {
     matches!(dt, DataType::Date32 | DataType::Date64)
 }
 
+/// Determine if a DataType is Time or not
+pub fn is_time(dt: &DataType) -> bool {
+    matches!(dt, DataType::Time32(_) | DataType::Time64(_))
+}
+
+/// Determine if a DataType is Duration or not
+pub fn is_duration(dt: &DataType) -> bool {
+    matches!(dt, DataType::Duration(_))
+}
+
+/// Returns true if DataFusion can cast `from_type` to `to_type`: these are the
+/// casts of the arrow kernels, and the casts between durations of different
+/// units, and between durations and day-time intervals.
+pub fn can_cast_types(from_type: &DataType, to_type: &DataType) -> bool {
+    let is_day_time_interval = |dt: &DataType| {
+        matches!(
+            dt,
+            DataType::Interval(IntervalUnit::DayTime | IntervalUnit::MonthDayNano)
+        )
+    };
+    match (from_type, to_type) {
+        (DataType::Duration(_), DataType::Duration(_)) => true,
+        (DataType::Duration(_), _) if is_day_time_interval(to_type) => true,
+        (_, DataType::Duration(_)) if is_day_time_interval(from_type) => true,
+        _ => arrow::compute::can_cast_types(from_type, to_type),
+    }
+}
+
 pub mod aggregates;
 pub mod binary;
 pub mod functions;
@@ -56,6 +56,13 @@ pub static TIMES: &[DataType] = &[
     DataType::Time64(TimeUnit::Nanosecond),
 ];
 
+pub static DURATIONS: &[DataType] = &[
+    DataType::Duration(TimeUnit::Second),
+    DataType::Duration(TimeUnit::Millisecond),
+    DataType::Duration(TimeUnit::Microsecond),
+    DataType::Duration(TimeUnit::Nanosecond),
+];
+
 /// Returns the coerced data type for each `input_types`.
 /// Different aggregate function with different input data type will get corresponding coerced data type.
 pub fn coerce_types(
@@ -231,7 +238,9 @@ fn check_arg_count(
     signature: &TypeSignature,
 ) -> Result<()> {
     match signature {
-        TypeSignature::Uniform(agg_count, _) | TypeSignature::Any(agg_count) => {
+        TypeSignature::Uniform(agg_count, _)
+        | TypeSignature::Numeric(agg_count)
+        | TypeSignature::Any(agg_count) => {
             if input_types.len() != *agg_count {
                 return Err(DataFusionError::Plan(format!(
                     "The function {:?} expects {:?} arguments, but {:?} were provided",
@@ -306,6 +315,7 @@ pub fn sum_return_type(arg_type: &DataType) -> Result<DataType> {
             let new_precision = DECIMAL128_MAX_PRECISION.min(*precision + 10);
             Ok(DataType::Decimal128(new_precision, *scale))
         }
+        DataType::Duration(unit) => Ok(DataType::Duration(unit.clone())),
         other => Err(DataFusionError::Plan(format!(
             "SUM does not support type \"{other:?}\""
         ))),
@@ -398,6 +408,8 @@ pub fn avg_return_type(arg_type: &DataType) -> Result<DataType> {
             let new_scale = DECIMAL128_MAX_SCALE.min(*scale + 4);
             Ok(DataType::Decimal128(new_precision, new_scale))
         }
+        // like in PostgreSQL, the average of durations is a duration
+        DataType::Duration(unit) => Ok(DataType::Duration(unit.clone())),
         DataType::Int8
         | DataType::Int16
         | DataType::Int32
@@ -428,6 +440,7 @@ pub fn is_sum_support_arg_type(arg_type: &DataType) -> bool {
             | DataType::Float32
             | DataType::Float64
             | DataType::Decimal128(_, _)
+            | DataType::Duration(_)
     )
 }
 
@@ -445,6 +458,7 @@ pub fn is_avg_support_arg_type(arg_type: &DataType) -> bool {
             | DataType::Float32
             | DataType::Float64
             | DataType::Decimal128(_, _)
+            | DataType::Duration(_)
     )
 }
 
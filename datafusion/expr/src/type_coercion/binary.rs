@@ -17,11 +17,13 @@
 
 //! Coercion rules for matching argument types for binary operators
 
-use crate::type_coercion::{is_date, is_numeric, is_timestamp};
+use crate::type_coercion::{
+    is_date, is_duration, is_integer, is_numeric, is_signed_numeric, is_timestamp,
+};
 use crate::Operator;
 use arrow::compute::can_cast_types;
 use arrow::datatypes::{
-    DataType, TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL128_MAX_SCALE,
+    DataType, IntervalUnit, TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL128_MAX_SCALE,
 };
 use datafusion_common::DataFusionError;
 use datafusion_common::Result;
@@ -119,6 +121,8 @@ pub fn coerce_types(
             match rhs_type {
                 // timestamp/date +/- interval returns timestamp/date
                 DataType::Interval(_) => Some(lhs_type.clone()),
+                // timestamp +/- duration returns timestamp
+                DataType::Duration(_) if is_timestamp(lhs_type) => Some(lhs_type.clone()),
                 // providing more helpful error message
                 DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _) => {
                     return Err(DataFusionError::Plan(
@@ -131,6 +135,16 @@ pub fn coerce_types(
                 _ => None,
             }
         }
+        // duration + timestamp returns timestamp
+        Operator::Plus if is_duration(lhs_type) && is_timestamp(rhs_type) => {
+            Some(rhs_type.clone())
+        }
+        // duration +/- duration returns a duration of the finer unit
+        Operator::Plus | Operator::Minus
+            if is_duration(lhs_type) && is_duration(rhs_type) =>
+        {
+            temporal_coercion(lhs_type, rhs_type)
+        }
         // for math expressions, the final value of the coercion is also the return type
         // because coercion favours higher information types
         Operator::Plus
@@ -173,6 +187,11 @@ fn bitwise_coercion(left_type: &DataType, right_type: &DataType) -> Option<DataT
         return Some(left_type.clone());
     }
 
+    // the bitwise operations are only computed on signed integers
+    if is_integer(left_type) && is_integer(right_type) {
+        return integer_coercion(left_type, right_type).filter(is_signed_numeric);
+    }
+
     // TODO support other data type
     match (left_type, right_type) {
         (Int64, _) | (_, Int64) => Some(Int64),
@@ -238,15 +257,7 @@ fn comparison_binary_numeric_coercion(
         (_, Decimal128(_, _)) => get_comparison_common_decimal_type(rhs_type, lhs_type),
         (Float64, _) | (_, Float64) => Some(Float64),
         (_, Float32) | (Float32, _) => Some(Float32),
-        (Int64, _) | (_, Int64) => Some(Int64),
-        (Int32, _) | (_, Int32) => Some(Int32),
-        (Int16, _) | (_, Int16) => Some(Int16),
-        (Int8, _) | (_, Int8) => Some(Int8),
-        (UInt64, _) | (_, UInt64) => Some(UInt64),
-        (UInt32, _) | (_, UInt32) => Some(UInt32),
-        (UInt16, _) | (_, UInt16) => Some(UInt16),
-        (UInt8, _) | (_, UInt8) => Some(UInt8),
-        _ => None,
+        _ => integer_coercion(lhs_type, rhs_type),
     }
 }
 
@@ -259,10 +270,10 @@ fn get_comparison_common_decimal_type(
     let other_decimal_type = &match other_type {
         // This conversion rule is from spark
         // https://github.com/apache/spark/blob/1c81ad20296d34f137238dadd67cc6ae405944eb/sql/catalyst/src/main/scala/org/apache/spark/sql/types/DecimalType.scala#L127
-        DataType::Int8 => DataType::Decimal128(3, 0),
-        DataType::Int16 => DataType::Decimal128(5, 0),
-        DataType::Int32 => DataType::Decimal128(10, 0),
-        DataType::Int64 => DataType::Decimal128(20, 0),
+        DataType::Int8 | DataType::UInt8 => DataType::Decimal128(3, 0),
+        DataType::Int16 | DataType::UInt16 => DataType::Decimal128(5, 0),
+        DataType::Int32 | DataType::UInt32 => DataType::Decimal128(10, 0),
+        DataType::Int64 | DataType::UInt64 => DataType::Decimal128(20, 0),
         DataType::Float32 => DataType::Decimal128(14, 7),
         DataType::Float64 => DataType::Decimal128(30, 15),
         _ => {
@@ -297,13 +308,13 @@ fn get_wider_decimal_type(
 }
 
 /// Convert the numeric data type to the decimal data type.
-/// Now, we just support the signed integer type and floating-point type.
+/// Now, we just support the integer types and floating-point types.
 fn coerce_numeric_type_to_decimal(numeric_type: &DataType) -> Option<DataType> {
     match numeric_type {
-        DataType::Int8 => Some(DataType::Decimal128(3, 0)),
-        DataType::Int16 => Some(DataType::Decimal128(5, 0)),
-        DataType::Int32 => Some(DataType::Decimal128(10, 0)),
-        DataType::Int64 => Some(DataType::Decimal128(20, 0)),
+        DataType::Int8 | DataType::UInt8 => Some(DataType::Decimal128(3, 0)),
+        DataType::Int16 | DataType::UInt16 => Some(DataType::Decimal128(5, 0)),
+        DataType::Int32 | DataType::UInt32 => Some(DataType::Decimal128(10, 0)),
+        DataType::Int64 | DataType::UInt64 => Some(DataType::Decimal128(20, 0)),
         // TODO if we convert the floating-point data to the decimal type, it maybe overflow.
         DataType::Float32 => Some(DataType::Decimal128(14, 7)),
         DataType::Float64 => Some(DataType::Decimal128(30, 15)),
@@ -367,15 +378,17 @@ fn mathematics_numerical_coercion(
         }
         (Float64, _) | (_, Float64) => Some(Float64),
         (_, Float32) | (Float32, _) => Some(Float32),
-        (Int64, _) | (_, Int64) => Some(Int64),
-        (Int32, _) | (_, Int32) => Some(Int32),
-        (Int16, _) | (_, Int16) => Some(Int16),
-        (Int8, _) | (_, Int8) => Some(Int8),
-        (UInt64, _) | (_, UInt64) => Some(UInt64),
-        (UInt32, _) | (_, UInt32) => Some(UInt32),
-        (UInt16, _) | (_, UInt16) => Some(UInt16),
-        (UInt8, _) | (_, UInt8) => Some(UInt8),
-        _ => None,
+        (Null, _) => Some(rhs_type.clone()),
+        (_, Null) => Some(lhs_type.clone()),
+        _ => match integer_coercion(lhs_type, rhs_type)? {
+            // unsigned 64 bit integers and signed integers are computed as decimals
+            decimal_type @ Decimal128(_, _) => coercion_decimal_mathematics_type(
+                mathematics_op,
+                &decimal_type,
+                &decimal_type,
+            ),
+            integer_type => Some(integer_type),
+        },
     }
 }
 
@@ -481,6 +494,8 @@ fn dictionary_coercion(
 fn string_concat_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType> {
     use arrow::datatypes::DataType::*;
     string_coercion(lhs_type, rhs_type).or(match (lhs_type, rhs_type) {
+        (Dictionary(_, value_type), _) => string_concat_coercion(value_type, rhs_type),
+        (_, Dictionary(_, value_type)) => string_concat_coercion(lhs_type, value_type),
         (Utf8, from_type) | (from_type, Utf8) => {
             string_concat_internal_coercion(from_type, &Utf8)
         }
@@ -604,10 +619,34 @@ fn temporal_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataTyp
 
             Some(Timestamp(unit, tz))
         }
+        (Duration(lhs_unit), Duration(rhs_unit)) => {
+            Some(Duration(finer_time_unit(lhs_unit, rhs_unit)))
+        }
+        // durations are compared with the intervals of days and times in their unit
+        (
+            Duration(unit),
+            Interval(IntervalUnit::DayTime | IntervalUnit::MonthDayNano),
+        )
+        | (
+            Interval(IntervalUnit::DayTime | IntervalUnit::MonthDayNano),
+            Duration(unit),
+        ) => Some(Duration(unit.clone())),
         _ => None,
     }
 }
 
+/// Returns the finer of two time units, to which the values of both can be
+/// converted without loss
+fn finer_time_unit(lhs_unit: &TimeUnit, rhs_unit: &TimeUnit) -> TimeUnit {
+    use arrow::datatypes::TimeUnit::*;
+    match (lhs_unit, rhs_unit) {
+        (Nanosecond, _) | (_, Nanosecond) => Nanosecond,
+        (Microsecond, _) | (_, Microsecond) => Microsecond,
+        (Millisecond, _) | (_, Millisecond) => Millisecond,
+        (Second, Second) => Second,
+    }
+}
+
 /// Coercion rule for numerical types: The type that both lhs and rhs
 /// can be casted to for numerical calculation, while maintaining
 /// maximum precision
@@ -629,6 +668,25 @@ fn numerical_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataTy
     match (lhs_type, rhs_type) {
         (Float64, _) | (_, Float64) => Some(Float64),
         (_, Float32) | (Float32, _) => Some(Float32),
+        _ => integer_coercion(lhs_type, rhs_type),
+    }
+}
+
+/// Coercion rules for integers: the narrowest type that can hold any value
+/// of both `lhs_type` and `rhs_type`, like `Int16` for `Int8` and `UInt8`.
+///
+/// As no integer type can hold both the values of `UInt64` and of a signed
+/// integer type, they are coerced to `Decimal128(20, 0)`.
+fn integer_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType> {
+    use arrow::datatypes::DataType::*;
+    match (lhs_type, rhs_type) {
+        (UInt64, Int8 | Int16 | Int32 | Int64)
+        | (Int8 | Int16 | Int32 | Int64, UInt64) => Some(Decimal128(20, 0)),
+        (UInt32, Int8 | Int16 | Int32) | (Int8 | Int16 | Int32, UInt32) => Some(Int64),
+        (UInt16, Int8 | Int16) | (Int8 | Int16, UInt16) => Some(Int32),
+        (UInt8, Int8) | (Int8, UInt8) => Some(Int16),
+        _ if !is_integer(lhs_type) || !is_integer(rhs_type) => None,
+        // otherwise, the wider of the two types holds the values of both
         (Int64, _) | (_, Int64) => Some(Int64),
         (Int32, _) | (_, Int32) => Some(Int32),
         (Int16, _) | (_, Int16) => Some(Int16),
@@ -841,6 +899,16 @@ mod tests {
             dictionary_coercion(&lhs_type, &rhs_type, true),
             Some(rhs_type.clone())
         );
+
+        // dictionaries are concatenated as their values
+        let lhs_type = Dictionary(Box::new(Int32), Box::new(Utf8));
+        let rhs_type = Dictionary(Box::new(Int8), Box::new(LargeUtf8));
+        assert_eq!(string_concat_coercion(&lhs_type, &lhs_type), Some(Utf8));
+        assert_eq!(
+            string_concat_coercion(&lhs_type, &rhs_type),
+            Some(LargeUtf8)
+        );
+        assert_eq!(string_concat_coercion(&Int64, &lhs_type), Some(Utf8));
     }
 
     macro_rules! test_coercion_binary_rule {
@@ -959,6 +1027,12 @@ mod tests {
             Operator::BitwiseAnd,
             DataType::Int64
         );
+        test_coercion_binary_rule!(
+            DataType::UInt8,
+            DataType::Int8,
+            Operator::BitwiseOr,
+            DataType::Int16
+        );
         Ok(())
     }
 
@@ -969,7 +1043,7 @@ mod tests {
             DataType::Int32,
             DataType::UInt32,
             Operator::Plus,
-            DataType::Int32
+            DataType::Int64
         );
         test_coercion_binary_rule!(
             DataType::Int32,
@@ -983,6 +1057,18 @@ mod tests {
             Operator::Multiply,
             DataType::Int64
         );
+        test_coercion_binary_rule!(
+            DataType::UInt64,
+            DataType::Int64,
+            Operator::Plus,
+            DataType::Decimal128(21, 0)
+        );
+        test_coercion_binary_rule!(
+            DataType::Null,
+            DataType::UInt16,
+            Operator::Plus,
+            DataType::UInt16
+        );
         // float
         test_coercion_binary_rule!(
             DataType::Float32,
@@ -1038,6 +1124,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_type_coercion_duration() -> Result<()> {
+        let ts = DataType::Timestamp(TimeUnit::Millisecond, Some("+08:00".into()));
+        test_coercion_binary_rule!(
+            ts.clone(),
+            DataType::Duration(TimeUnit::Nanosecond),
+            Operator::Minus,
+            ts.clone()
+        );
+        test_coercion_binary_rule!(
+            DataType::Duration(TimeUnit::Second),
+            ts.clone(),
+            Operator::Plus,
+            ts
+        );
+        test_coercion_binary_rule!(
+            DataType::Duration(TimeUnit::Second),
+            DataType::Duration(TimeUnit::Microsecond),
+            Operator::Plus,
+            DataType::Duration(TimeUnit::Microsecond)
+        );
+        test_coercion_binary_rule!(
+            DataType::Duration(TimeUnit::Millisecond),
+            DataType::Duration(TimeUnit::Second),
+            Operator::Lt,
+            DataType::Duration(TimeUnit::Millisecond)
+        );
+
+        let err = coerce_types(
+            &DataType::Date32,
+            &Operator::Plus,
+            &DataType::Duration(TimeUnit::Second),
+        )
+        .unwrap_err()
+        .to_string();
+        assert_contains!(&err, "can't be evaluated because there isn't a common type");
+        Ok(())
+    }
+
     #[test]
     fn test_type_coercion_compare() -> Result<()> {
         // boolean
@@ -1080,6 +1205,31 @@ mod tests {
             Operator::Gt,
             DataType::UInt32
         );
+        // signed and unsigned integers
+        test_coercion_binary_rule!(
+            DataType::UInt8,
+            DataType::Int8,
+            Operator::Eq,
+            DataType::Int16
+        );
+        test_coercion_binary_rule!(
+            DataType::Int64,
+            DataType::UInt32,
+            Operator::NotEq,
+            DataType::Int64
+        );
+        test_coercion_binary_rule!(
+            DataType::UInt64,
+            DataType::Int32,
+            Operator::Lt,
+            DataType::Decimal128(20, 0)
+        );
+        test_coercion_binary_rule!(
+            DataType::UInt64,
+            DataType::Decimal128(10, 2),
+            Operator::Lt,
+            DataType::Decimal128(22, 2)
+        );
         // numeric/decimal
         test_coercion_binary_rule!(
             DataType::Int64,
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::type_coercion::binary::comparison_coercion;
+use crate::type_coercion::is_numeric;
 use crate::{Signature, TypeSignature};
 use arrow::{
     compute::can_cast_types,
@@ -76,15 +78,40 @@ fn get_valid_types(
                 .map(|_| current_types[0].clone())
                 .collect()]
         }
+        TypeSignature::VariadicCoercible => match common_type(current_types) {
+            Some(data_type) => vec![vec![data_type; current_types.len()]],
+            None => vec![],
+        },
+        TypeSignature::Numeric(number) => {
+            check_arg_count(*number, current_types)?;
+            match common_type(current_types) {
+                Some(data_type) if is_numeric(&data_type) => {
+                    vec![vec![data_type; *number]]
+                }
+                _ => vec![],
+            }
+        }
+        TypeSignature::Coercible(valid_types) => {
+            check_arg_count(valid_types.len(), current_types)?;
+            valid_types
+                .iter()
+                .zip(current_types)
+                .map(|(valid_types, current_type)| {
+                    if valid_types.contains(current_type) {
+                        return Some(current_type.clone());
+                    }
+                    valid_types
+                        .iter()
+                        .find(|valid_type| can_coerce_from(valid_type, current_type))
+                        .cloned()
+                })
+                .collect::<Option<Vec<_>>>()
+                .into_iter()
+                .collect()
+        }
         TypeSignature::Exact(valid_types) => vec![valid_types.clone()],
         TypeSignature::Any(number) => {
-            if current_types.len() != *number {
-                return Err(DataFusionError::Plan(format!(
-                    "The function expected {} arguments but received {}",
-                    number,
-                    current_types.len()
-                )));
-            }
+            check_arg_count(*number, current_types)?;
             vec![(0..*number).map(|i| current_types[i].clone()).collect()]
         }
         TypeSignature::OneOf(types) => types
@@ -97,6 +124,25 @@ fn get_valid_types(
     Ok(valid_types)
 }
 
+fn check_arg_count(number: usize, current_types: &[DataType]) -> Result<()> {
+    if current_types.len() != number {
+        return Err(DataFusionError::Plan(format!(
+            "The function expected {} arguments but received {}",
+            number,
+            current_types.len()
+        )));
+    }
+    Ok(())
+}
+
+/// The type all of `types` can be coerced to for comparisons, if any
+fn common_type(types: &[DataType]) -> Option<DataType> {
+    let (first, others) = types.split_first()?;
+    others.iter().try_fold(first.clone(), |common, data_type| {
+        comparison_coercion(&common, data_type)
+    })
+}
+
 /// Try to coerce current_types into valid_types.
 fn maybe_data_types(
     valid_types: &[DataType],
@@ -186,6 +232,7 @@ pub fn can_coerce_from(type_into: &DataType, type_from: &DataType) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Volatility;
     use arrow::datatypes::DataType;
 
     #[test]
@@ -225,6 +272,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_data_types_generic_signatures() -> Result<()> {
+        use DataType::*;
+
+        let signature = Signature::variadic_coercible(Volatility::Immutable);
+        assert_eq!(
+            data_types(&[Int32, Null, Int64], &signature)?,
+            vec![Int64, Int64, Int64]
+        );
+        assert!(data_types(&[Int32, Boolean], &signature).is_err());
+
+        let signature = Signature::numeric(2, Volatility::Immutable);
+        assert_eq!(
+            data_types(&[Int32, Float64], &signature)?,
+            vec![Float64, Float64]
+        );
+        assert!(data_types(&[Utf8, Int64], &signature).is_err());
+        assert!(data_types(&[Int64], &signature).is_err());
+
+        let signature = Signature::coercible(
+            vec![vec![Int64, Float64], vec![Utf8]],
+            Volatility::Immutable,
+        );
+        assert_eq!(data_types(&[Int32, Utf8], &signature)?, vec![Int64, Utf8]);
+        assert_eq!(
+            data_types(&[Float64, Int8], &signature)?,
+            vec![Float64, Utf8]
+        );
+        assert!(data_types(&[Float32, Utf8], &signature).is_ok());
+        assert!(data_types(&[Boolean, Utf8], &signature).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_get_valid_types_one_of() -> Result<()> {
         let signature =
@@ -19,7 +19,8 @@
 
 use crate::Expr;
 use crate::{
-    AccumulatorFunctionImplementation, ReturnTypeFunction, Signature, StateTypeFunction,
+    AccumulatorFunctionImplementation, FunctionParameter, ReturnTypeFunction, Signature,
+    StateTypeFunction,
 };
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
@@ -38,6 +39,8 @@ pub struct AggregateUDF {
     pub accumulator: AccumulatorFunctionImplementation,
     /// the accumulator's state's description as a function of the return type
     pub state_type: StateTypeFunction,
+    /// The named parameters of the function, if it accepts arguments by name
+    pub parameters: Vec<FunctionParameter>,
 }
 
 impl Debug for AggregateUDF {
@@ -80,9 +83,17 @@ impl AggregateUDF {
             return_type: return_type.clone(),
             accumulator: accumulator.clone(),
             state_type: state_type.clone(),
+            parameters: vec![],
         }
     }
 
+    /// Declare the named parameters of the function, so that its arguments
+    /// can be passed by name and those with a default value omitted
+    pub fn with_parameters(mut self, parameters: Vec<FunctionParameter>) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
     /// creates a logical expression with a call of the UDAF
     /// This utility allows using the UDAF without requiring access to the registry.
     pub fn call(&self, args: Vec<Expr>) -> Expr {
@@ -17,7 +17,10 @@
 
 //! Udf module contains foundational types that are used to represent UDFs in DataFusion.
 
-use crate::{Expr, ReturnTypeFunction, ScalarFunctionImplementation, Signature};
+use crate::{
+    Expr, FunctionParameter, ReturnTypeFunction, ScalarFunctionImplementation,
+    ScalarFunctionSimplifier, Signature,
+};
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
@@ -41,6 +44,17 @@ pub struct ScalarUDF {
     /// the batch's row count (so that the generative zero-argument function can know
     /// the result array size).
     pub fun: ScalarFunctionImplementation,
+    /// Optional rewrite of the calls of the function, applied by the
+    /// expression simplifier once the arguments are simplified
+    pub simplify: Option<ScalarFunctionSimplifier>,
+    /// The index of the argument in which the function is monotonically
+    /// non-decreasing when all its other arguments are constant, if any.
+    /// Used to compute the range of the function's results and to prune
+    /// containers on predicates over them, see
+    /// [`BuiltinScalarFunction::monotonic_argument`](crate::BuiltinScalarFunction::monotonic_argument)
+    pub monotonic_argument: Option<usize>,
+    /// The named parameters of the function, if it accepts arguments by name
+    pub parameters: Vec<FunctionParameter>,
 }
 
 impl Debug for ScalarUDF {
@@ -49,6 +63,7 @@ impl Debug for ScalarUDF {
             .field("name", &self.name)
             .field("signature", &self.signature)
             .field("fun", &"<FUNC>")
+            .field("monotonic_argument", &self.monotonic_argument)
             .finish()
     }
 }
@@ -81,9 +96,33 @@ impl ScalarUDF {
             signature: signature.clone(),
             return_type: return_type.clone(),
             fun: fun.clone(),
+            simplify: None,
+            monotonic_argument: None,
+            parameters: vec![],
         }
     }
 
+    /// Rewrite the calls of the function with `simplify` during
+    /// optimization, for example to fold calls with literal arguments
+    pub fn with_simplify(mut self, simplify: ScalarFunctionSimplifier) -> Self {
+        self.simplify = Some(simplify);
+        self
+    }
+
+    /// Declare that the function is monotonically non-decreasing in its
+    /// argument at `index` when all its other arguments are constant
+    pub fn with_monotonic_argument(mut self, index: usize) -> Self {
+        self.monotonic_argument = Some(index);
+        self
+    }
+
+    /// Declare the named parameters of the function, so that its arguments
+    /// can be passed by name and those with a default value omitted
+    pub fn with_parameters(mut self, parameters: Vec<FunctionParameter>) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
     /// creates a logical expression with a call of the UDF
     /// This utility allows using the UDF without requiring access to the registry.
     pub fn call(&self, args: Vec<Expr>) -> Expr {
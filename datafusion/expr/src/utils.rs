@@ -23,8 +23,8 @@ use crate::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
 use crate::logical_plan::builder::build_join_schema;
 use crate::logical_plan::{
     Aggregate, Analyze, CreateMemoryTable, CreateView, Distinct, Extension, Filter, Join,
-    Limit, Partitioning, Prepare, Projection, Repartition, Sort as SortPlan, Subquery,
-    SubqueryAlias, Union, Values, Window,
+    Limit, Partitioning, Prepare, Projection, Repartition, SharedCte, Sort as SortPlan,
+    Subquery, SubqueryAlias, Union, Values, Window,
 };
 use crate::{
     BinaryExpr, Cast, Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder, Operator,
@@ -370,6 +370,22 @@ pub fn find_aggregate_exprs(exprs: &[Expr]) -> Vec<Expr> {
     })
 }
 
+/// Collect all deeply nested `Expr::Column`s that are not in the arguments
+/// of an `Expr::AggregateFunction` or `Expr::AggregateUDF`, such as `a` of
+/// `a + MAX(b)`. They are returned in order of occurrence (depth first), with
+/// duplicates omitted.
+pub fn find_column_exprs_outside_aggregates(exprs: &[Expr]) -> Vec<Expr> {
+    find_exprs_in_exprs(exprs, &|nested_expr| {
+        matches!(
+            nested_expr,
+            Expr::Column(_) | Expr::AggregateFunction { .. } | Expr::AggregateUDF { .. }
+        )
+    })
+    .into_iter()
+    .filter(|expr| matches!(expr, Expr::Column(_)))
+    .collect()
+}
+
 /// Collect all deeply nested `Expr::Sort`. They are returned in order of occurrence
 /// (depth first), with duplicates omitted.
 pub fn find_sort_exprs(exprs: &[Expr]) -> Vec<Expr> {
@@ -646,6 +662,13 @@ pub fn from_plan(
                 schema,
             }))
         }
+        LogicalPlan::SharedCte(SharedCte { id, name, .. }) => {
+            Ok(LogicalPlan::SharedCte(SharedCte {
+                id: *id,
+                name: name.clone(),
+                input: Arc::new(inputs[0].clone()),
+            }))
+        }
         LogicalPlan::Limit(Limit { skip, fetch, .. }) => Ok(LogicalPlan::Limit(Limit {
             skip: *skip,
             fetch: *fetch,
@@ -655,12 +678,14 @@ pub fn from_plan(
             name,
             if_not_exists,
             or_replace,
+            temporary,
             ..
         }) => Ok(LogicalPlan::CreateMemoryTable(CreateMemoryTable {
             input: Arc::new(inputs[0].clone()),
             name: name.clone(),
             if_not_exists: *if_not_exists,
             or_replace: *or_replace,
+            temporary: *temporary,
         })),
         LogicalPlan::CreateView(CreateView {
             name,
@@ -728,6 +753,10 @@ pub fn from_plan(
         | LogicalPlan::CreateExternalTable(_)
         | LogicalPlan::DropTable(_)
         | LogicalPlan::DropView(_)
+        | LogicalPlan::AnalyzeTable(_)
+        | LogicalPlan::CreateFunction(_)
+        | LogicalPlan::DropFunction(_)
+        | LogicalPlan::Transaction(_)
         | LogicalPlan::SetVariable(_)
         | LogicalPlan::CreateCatalogSchema(_)
         | LogicalPlan::CreateCatalog(_) => {
@@ -944,7 +973,7 @@ pub fn can_hash(data_type: &DataType) -> bool {
         DataType::UInt64 => true,
         DataType::Float32 => true,
         DataType::Float64 => true,
-        DataType::Timestamp(time_unit, None) => match time_unit {
+        DataType::Timestamp(time_unit, _) => match time_unit {
             TimeUnit::Second => true,
             TimeUnit::Millisecond => true,
             TimeUnit::Microsecond => true,
@@ -955,11 +984,11 @@ pub fn can_hash(data_type: &DataType) -> bool {
         DataType::Decimal128(_, _) => true,
         DataType::Date32 => true,
         DataType::Date64 => true,
-        DataType::Dictionary(key_type, value_type)
-            if *value_type.as_ref() == DataType::Utf8 =>
-        {
-            DataType::is_dictionary_key_type(key_type)
+        DataType::Dictionary(key_type, value_type) => {
+            DataType::is_dictionary_key_type(key_type) && can_hash(value_type)
         }
+        DataType::Struct(fields) => fields.iter().all(|f| can_hash(f.data_type())),
+        DataType::List(field) => can_hash(field.data_type()),
         _ => false,
     }
 }
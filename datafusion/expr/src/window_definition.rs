@@ -0,0 +1,203 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Named window definitions, like the ones of the `WINDOW` clause of a query:
+//!
+//! ```sql
+//! SELECT sum(x) OVER w, avg(x) OVER (w ROWS 1 PRECEDING)
+//! FROM t
+//! WINDOW w AS (PARTITION BY y ORDER BY z)
+//! ```
+
+use crate::expr;
+use crate::window_frame::WindowFrame;
+use crate::window_function::WindowFunction;
+use crate::Expr;
+use datafusion_common::{DataFusionError, Result};
+
+/// A named window definition, that is the partitioning, ordering and frame
+/// shared by the window functions computed over it.
+///
+/// A window definition can be based on another one, with the rules of the
+/// SQL standard: it inherits the `PARTITION BY` of the other window, and may
+/// only add an `ORDER BY` and a frame that the other window doesn't have.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WindowDefinition {
+    /// The name of the window
+    pub name: String,
+    /// The partition by expressions
+    pub partition_by: Vec<Expr>,
+    /// The order by expressions
+    pub order_by: Vec<Expr>,
+    /// The window frame, or `None` for the default frame
+    pub window_frame: Option<WindowFrame>,
+}
+
+impl WindowDefinition {
+    /// Create a window definition `name` over all the rows, in any order
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            partition_by: vec![],
+            order_by: vec![],
+            window_frame: None,
+        }
+    }
+
+    /// Partitions the rows of the window by `partition_by`
+    pub fn with_partition_by(mut self, partition_by: Vec<Expr>) -> Self {
+        self.partition_by = partition_by;
+        self
+    }
+
+    /// Orders the rows of the window by the sort expressions `order_by`
+    pub fn with_order_by(mut self, order_by: Vec<Expr>) -> Self {
+        self.order_by = order_by;
+        self
+    }
+
+    /// Sets the frame of the window
+    pub fn with_window_frame(mut self, window_frame: WindowFrame) -> Self {
+        self.window_frame = Some(window_frame);
+        self
+    }
+
+    /// Create the window definition `name` based on this window, like
+    /// `WINDOW name AS (self ORDER BY ... frame)`. The `ORDER BY` of this
+    /// window can't be overridden, and this window must not have a frame.
+    pub fn refine(
+        &self,
+        name: impl Into<String>,
+        order_by: Vec<Expr>,
+        window_frame: Option<WindowFrame>,
+    ) -> Result<Self> {
+        if self.window_frame.is_some() {
+            return Err(DataFusionError::Plan(format!(
+                "Cannot copy window \"{}\" because it has a frame clause",
+                self.name
+            )));
+        }
+        if !self.order_by.is_empty() && !order_by.is_empty() {
+            return Err(DataFusionError::Plan(format!(
+                "Cannot override ORDER BY clause of window \"{}\"",
+                self.name
+            )));
+        }
+        let order_by = if order_by.is_empty() {
+            self.order_by.clone()
+        } else {
+            order_by
+        };
+        Ok(Self {
+            name: name.into(),
+            partition_by: self.partition_by.clone(),
+            order_by,
+            window_frame,
+        })
+    }
+
+    /// Returns the window function `fun` of `args` over this window, like
+    /// `fun(args) OVER name`
+    pub fn call(&self, fun: WindowFunction, args: Vec<Expr>) -> Expr {
+        let window_frame = self
+            .window_frame
+            .clone()
+            .unwrap_or_else(|| WindowFrame::new(!self.order_by.is_empty()));
+        Expr::WindowFunction(expr::WindowFunction::new(
+            fun,
+            args,
+            self.partition_by.clone(),
+            self.order_by.clone(),
+            window_frame,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window_frame::{WindowFrameBound, WindowFrameUnits};
+    use crate::{col, AggregateFunction};
+    use datafusion_common::ScalarValue;
+
+    fn rows_frame() -> WindowFrame {
+        WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(Some(1))),
+            end_bound: WindowFrameBound::CurrentRow,
+            exclude: Default::default(),
+        }
+    }
+
+    #[test]
+    fn refine_window() -> Result<()> {
+        let sum = WindowFunction::AggregateFunction(AggregateFunction::Sum);
+        let w = WindowDefinition::new("w").with_partition_by(vec![col("a")]);
+        let w1 = w.refine("w1", vec![col("b").sort(true, false)], None)?;
+        let w2 = w1.refine("w2", vec![], Some(rows_frame()))?;
+
+        assert_eq!(w1.partition_by, vec![col("a")]);
+        assert_eq!(
+            w2.call(sum.clone(), vec![col("c")]),
+            Expr::WindowFunction(expr::WindowFunction::new(
+                sum.clone(),
+                vec![col("c")],
+                vec![col("a")],
+                vec![col("b").sort(true, false)],
+                rows_frame(),
+            ))
+        );
+
+        // the default frame depends on the order by
+        match w.call(sum.clone(), vec![col("c")]) {
+            Expr::WindowFunction(fun) => {
+                assert_eq!(fun.window_frame, WindowFrame::new(false))
+            }
+            e => panic!("unexpected expression {e:?}"),
+        }
+        match w1.call(sum, vec![col("c")]) {
+            Expr::WindowFunction(fun) => {
+                assert_eq!(fun.window_frame, WindowFrame::new(true))
+            }
+            e => panic!("unexpected expression {e:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn refine_window_errors() -> Result<()> {
+        let w = WindowDefinition::new("w")
+            .with_order_by(vec![col("b").sort(true, false)])
+            .with_window_frame(rows_frame());
+        let err = w.refine("w1", vec![], None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Cannot copy window \"w\" because it has a frame clause"
+        );
+
+        let w =
+            WindowDefinition::new("w").with_order_by(vec![col("b").sort(true, false)]);
+        let err = w
+            .refine("w1", vec![col("c").sort(true, false)], None)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Cannot override ORDER BY clause of window \"w\""
+        );
+        Ok(())
+    }
+}
@@ -43,6 +43,8 @@ pub struct WindowFrame {
     pub start_bound: WindowFrameBound,
     /// An ending frame boundary
     pub end_bound: WindowFrameBound,
+    /// The rows excluded from the frame of each row
+    pub exclude: WindowFrameExclude,
 }
 
 impl fmt::Display for WindowFrame {
@@ -52,6 +54,9 @@ impl fmt::Display for WindowFrame {
             "{} BETWEEN {} AND {}",
             self.units, self.start_bound, self.end_bound
         )?;
+        if self.exclude != WindowFrameExclude::NoOthers {
+            write!(f, " {}", self.exclude)?;
+        }
         Ok(())
     }
 }
@@ -85,6 +90,7 @@ impl TryFrom<ast::WindowFrame> for WindowFrame {
             units: value.units.into(),
             start_bound,
             end_bound,
+            exclude: WindowFrameExclude::NoOthers,
         })
     }
 }
@@ -101,6 +107,7 @@ impl WindowFrame {
                 units: WindowFrameUnits::Range,
                 start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
                 end_bound: WindowFrameBound::CurrentRow,
+                exclude: WindowFrameExclude::NoOthers,
             }
         } else {
             // This window frame covers the whole table (or partition if `PARTITION BY` is used).
@@ -110,6 +117,7 @@ impl WindowFrame {
                 units: WindowFrameUnits::Rows,
                 start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(None)),
                 end_bound: WindowFrameBound::Following(ScalarValue::UInt64(None)),
+                exclude: WindowFrameExclude::NoOthers,
             }
         }
     }
@@ -140,8 +148,15 @@ impl WindowFrame {
             units: self.units,
             start_bound,
             end_bound,
+            exclude: self.exclude,
         }
     }
+
+    /// Returns this window frame with the `EXCLUDE` clause `exclude`
+    pub fn with_exclude(mut self, exclude: WindowFrameExclude) -> Self {
+        self.exclude = exclude;
+        self
+    }
 }
 
 /// There are five ways to describe starting and ending frame boundaries:
@@ -205,7 +220,7 @@ impl TryFrom<ast::WindowFrameBound> for WindowFrameBound {
 }
 
 pub fn convert_frame_bound_to_scalar_value(v: ast::Expr) -> Result<ScalarValue> {
-    Ok(ScalarValue::Utf8(Some(match v {
+    Ok(ScalarValue::from(match v {
         ast::Expr::Value(ast::Value::Number(value, false))
         | ast::Expr::Value(ast::Value::SingleQuotedString(value)) => value,
         ast::Expr::Interval {
@@ -230,7 +245,7 @@ pub fn convert_frame_bound_to_scalar_value(v: ast::Expr) -> Result<ScalarValue>
             let msg = format!("Window frame bound cannot be {e:?}");
             return Err(DataFusionError::Internal(msg));
         }
-    })))
+    }))
 }
 
 impl fmt::Display for WindowFrameBound {
@@ -294,6 +309,35 @@ impl From<ast::WindowFrameUnits> for WindowFrameUnits {
     }
 }
 
+/// The EXCLUDE clause removes rows around the current row from its frame,
+/// even if they are between the frame boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WindowFrameExclude {
+    /// No row is excluded. This is the default.
+    #[default]
+    NoOthers,
+    /// The current row is excluded from the frame.
+    CurrentRow,
+    /// The current row and its peers are excluded from the frame. The peers are
+    /// the rows with the same values of the window ORDER BY clause, or all the
+    /// rows of the partition without an ORDER BY clause.
+    Group,
+    /// The peers of the current row are excluded from the frame, but not the
+    /// current row itself.
+    Ties,
+}
+
+impl fmt::Display for WindowFrameExclude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            WindowFrameExclude::NoOthers => "EXCLUDE NO OTHERS",
+            WindowFrameExclude::CurrentRow => "EXCLUDE CURRENT ROW",
+            WindowFrameExclude::Group => "EXCLUDE GROUP",
+            WindowFrameExclude::Ties => "EXCLUDE TIES",
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -335,4 +379,19 @@ mod tests {
         assert!(result.is_ok());
         Ok(())
     }
+
+    #[test]
+    fn test_window_frame_exclude_display() {
+        let window_frame = WindowFrame::new(true);
+        assert_eq!(
+            window_frame.to_string(),
+            "RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW"
+        );
+
+        let window_frame = window_frame.with_exclude(WindowFrameExclude::Ties);
+        assert_eq!(
+            window_frame.to_string(),
+            "RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW EXCLUDE TIES"
+        );
+    }
 }
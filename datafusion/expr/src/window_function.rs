@@ -112,27 +112,40 @@ pub enum BuiltInWindowFunction {
     NthValue,
 }
 
+/// The SQL names of the built-in window functions
+const NAME_TO_FUNCTION: &[(&str, BuiltInWindowFunction)] = &[
+    ("row_number", BuiltInWindowFunction::RowNumber),
+    ("rank", BuiltInWindowFunction::Rank),
+    ("dense_rank", BuiltInWindowFunction::DenseRank),
+    ("percent_rank", BuiltInWindowFunction::PercentRank),
+    ("cume_dist", BuiltInWindowFunction::CumeDist),
+    ("ntile", BuiltInWindowFunction::Ntile),
+    ("lag", BuiltInWindowFunction::Lag),
+    ("lead", BuiltInWindowFunction::Lead),
+    ("first_value", BuiltInWindowFunction::FirstValue),
+    ("last_value", BuiltInWindowFunction::LastValue),
+    ("nth_value", BuiltInWindowFunction::NthValue),
+];
+
 impl FromStr for BuiltInWindowFunction {
     type Err = DataFusionError;
     fn from_str(name: &str) -> Result<BuiltInWindowFunction> {
-        Ok(match name.to_uppercase().as_str() {
-            "ROW_NUMBER" => BuiltInWindowFunction::RowNumber,
-            "RANK" => BuiltInWindowFunction::Rank,
-            "DENSE_RANK" => BuiltInWindowFunction::DenseRank,
-            "PERCENT_RANK" => BuiltInWindowFunction::PercentRank,
-            "CUME_DIST" => BuiltInWindowFunction::CumeDist,
-            "NTILE" => BuiltInWindowFunction::Ntile,
-            "LAG" => BuiltInWindowFunction::Lag,
-            "LEAD" => BuiltInWindowFunction::Lead,
-            "FIRST_VALUE" => BuiltInWindowFunction::FirstValue,
-            "LAST_VALUE" => BuiltInWindowFunction::LastValue,
-            "NTH_VALUE" => BuiltInWindowFunction::NthValue,
-            _ => {
-                return Err(DataFusionError::Plan(format!(
+        NAME_TO_FUNCTION
+            .iter()
+            .find(|(function_name, _)| function_name.eq_ignore_ascii_case(name))
+            .map(|(_, fun)| fun.clone())
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
                     "There is no built-in window function named {name}"
-                )))
-            }
-        })
+                ))
+            })
+    }
+}
+
+impl BuiltInWindowFunction {
+    /// Returns the SQL names of the built-in window functions
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        NAME_TO_FUNCTION.iter().map(|(name, _)| *name)
     }
 }
 
@@ -0,0 +1,203 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Differential checks of DataFusion against the [interpreter](crate::interpreter)
+
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::prelude::{DataFrame, SessionConfig, SessionContext};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Expr, ExprSchemable, JoinType};
+
+use crate::interpreter::{evaluate, rows, unpack, Row};
+
+/// Checks that DataFusion evaluates `expr` over `batches` to the same values
+/// as the interpreter. For boolean expressions, also checks that filtering by
+/// `expr` keeps exactly the rows for which the interpreter returns true.
+pub async fn check_expr(batches: &[RecordBatch], expr: &Expr) -> Result<()> {
+    let schema = batches[0].schema();
+    let input = rows(batches)?;
+
+    // a single partition, so that the output is in the order of the input
+    let ctx = SessionContext::with_config(SessionConfig::new().with_target_partitions(1));
+    let df = read_batches(&ctx, batches)?;
+
+    let expected = input
+        .iter()
+        .map(|row| evaluate(expr, &schema, row))
+        .collect::<Result<Vec<_>>>()?;
+    let actual = rows(&df.clone().select(vec![expr.clone()])?.collect().await?)?;
+    let actual: Vec<_> = actual.into_iter().map(|mut row| row.remove(0)).collect();
+    if actual != expected {
+        return Err(mismatch(&format!("{expr}"), &expected, &actual));
+    }
+
+    if expr.get_type(df.schema())? == DataType::Boolean {
+        let expected: Vec<_> = input
+            .into_iter()
+            .zip(&expected)
+            .filter(|(_, keep)| **keep == ScalarValue::Boolean(Some(true)))
+            .map(|(row, _)| row)
+            .collect();
+        let actual = rows(&df.filter(expr.clone())?.collect().await?)?;
+        if actual != expected {
+            return Err(mismatch(&format!("filter {expr}"), &expected, &actual));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that DataFusion joins `left` and `right` on the first two columns
+/// of each side to the same rows as a nested loop join of the interpreter's
+/// rows, ignoring the order of the rows.
+///
+/// `prefer_hash_join` selects between hash and sort merge joins.
+pub async fn check_join(
+    left: &[RecordBatch],
+    right: &[RecordBatch],
+    join_type: JoinType,
+    prefer_hash_join: bool,
+) -> Result<()> {
+    let left_schema = left[0].schema();
+    let right_schema = right[0].schema();
+
+    let config = SessionConfig::new()
+        .with_target_partitions(4)
+        .with_batch_size(7)
+        .set_bool("datafusion.optimizer.prefer_hash_join", prefer_hash_join);
+    let ctx = SessionContext::with_config(config);
+    let join_columns = |schema: &Schema| {
+        schema.fields()[0..2]
+            .iter()
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>()
+    };
+    let (left_on, right_on) = (join_columns(&left_schema), join_columns(&right_schema));
+    let df = read_batches(&ctx, left)?.join(
+        read_batches(&ctx, right)?,
+        join_type,
+        &left_on.iter().map(String::as_str).collect::<Vec<_>>(),
+        &right_on.iter().map(String::as_str).collect::<Vec<_>>(),
+        None,
+    )?;
+    let actual = sorted(rows(&df.collect().await?)?);
+
+    let (left_rows, right_rows) = (rows(left)?, rows(right)?);
+    let expected = sorted(nested_loop_join(
+        &left_rows,
+        &right_rows,
+        &nulls(&left_schema)?,
+        &nulls(&right_schema)?,
+        join_type,
+    ));
+    if actual != expected {
+        return Err(mismatch(&format!("{join_type} join"), &expected, &actual));
+    }
+    Ok(())
+}
+
+fn read_batches(ctx: &SessionContext, batches: &[RecordBatch]) -> Result<DataFrame> {
+    let table = MemTable::try_new(batches[0].schema(), vec![batches.to_vec()])?;
+    ctx.read_table(Arc::new(table))
+}
+
+/// Joins rows whose first two values are equal and not null
+fn nested_loop_join(
+    left: &[Row],
+    right: &[Row],
+    left_nulls: &Row,
+    right_nulls: &Row,
+    join_type: JoinType,
+) -> Vec<Row> {
+    let matches = |l: &Row, r: &Row| {
+        l[0..2]
+            .iter()
+            .zip(&r[0..2])
+            .all(|(l, r)| !l.is_null() && l == r)
+    };
+    let concat = |l: &Row, r: &Row| l.iter().chain(r).cloned().collect::<Row>();
+
+    let mut output = vec![];
+    for l in left {
+        let matched: Vec<_> = right.iter().filter(|r| matches(l, r)).collect();
+        match join_type {
+            JoinType::LeftSemi if !matched.is_empty() => output.push(l.clone()),
+            JoinType::LeftAnti if matched.is_empty() => output.push(l.clone()),
+            JoinType::LeftSemi | JoinType::LeftAnti => {}
+            JoinType::RightSemi | JoinType::RightAnti => {}
+            _ => {
+                output.extend(matched.iter().map(|r| concat(l, r)));
+                if matched.is_empty()
+                    && matches!(join_type, JoinType::Left | JoinType::Full)
+                {
+                    output.push(concat(l, right_nulls));
+                }
+            }
+        }
+    }
+    for r in right {
+        let matched = left.iter().any(|l| matches(l, r));
+        match join_type {
+            JoinType::RightSemi if matched => output.push(r.clone()),
+            JoinType::RightAnti if !matched => output.push(r.clone()),
+            JoinType::Right | JoinType::Full if !matched => {
+                output.push(concat(left_nulls, r))
+            }
+            _ => {}
+        }
+    }
+    output
+}
+
+/// Returns a row of nulls of the types of `schema`
+fn nulls(schema: &Schema) -> Result<Row> {
+    schema
+        .fields()
+        .iter()
+        .map(|f| ScalarValue::try_from(f.data_type()).map(unpack))
+        .collect()
+}
+
+/// Sorts rows by their debug representation, as [`ScalarValue`] is not [`Ord`]
+fn sorted(rows: Vec<Row>) -> Vec<Row> {
+    let mut rows: Vec<_> = rows.into_iter().map(|r| (format!("{r:?}"), r)).collect();
+    rows.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    rows.into_iter().map(|(_, r)| r).collect()
+}
+
+fn mismatch<T: std::fmt::Debug>(
+    what: &str,
+    expected: &[T],
+    actual: &[T],
+) -> DataFusionError {
+    let position = expected
+        .iter()
+        .zip(actual)
+        .position(|(e, a)| format!("{e:?}") != format!("{a:?}"))
+        .unwrap_or_else(|| expected.len().min(actual.len()));
+    DataFusionError::Execution(format!(
+        "DataFusion and the interpreter differ for {what}: {} rows expected, {} \
+         rows returned, first difference at row {position}: expected {:?}, got {:?}",
+        expected.len(),
+        actual.len(),
+        expected.get(position),
+        actual.get(position),
+    ))
+}
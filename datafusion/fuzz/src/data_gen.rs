@@ -0,0 +1,169 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Generation of random record batches
+
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BooleanArray, DictionaryArray, Float64Array, Int32Array, Int64Array,
+    StringArray,
+};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Strings used for generated string and dictionary columns. The small
+/// vocabulary makes equal values, and thus matching join keys, likely
+const WORDS: &[&str] = &["", "a", "A", "apple", "Banana", "cherry", "ß", "日本"];
+
+/// The schema of the batches created by [`expr_batches`]
+pub fn expr_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("i1", DataType::Int64, true),
+        Field::new("i2", DataType::Int64, true),
+        Field::new("f1", DataType::Float64, true),
+        Field::new("s1", DataType::Utf8, true),
+        Field::new(
+            "d1",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            true,
+        ),
+        Field::new("b1", DataType::Boolean, true),
+    ]))
+}
+
+/// Creates between one and four batches with the schema of [`expr_schema`].
+/// Batches have between zero and 100 rows, and each column a random fraction
+/// of nulls
+pub fn expr_batches(rng: &mut StdRng) -> Vec<RecordBatch> {
+    let schema = expr_schema();
+    (0..rng.gen_range(1..5))
+        .map(|_| {
+            let len = random_len(rng);
+            let columns = vec![
+                int64_array(rng, len),
+                int64_array(rng, len),
+                float64_array(rng, len),
+                string_array(rng, len),
+                dictionary_array(rng, len),
+                boolean_array(rng, len),
+            ];
+            RecordBatch::try_new(schema.clone(), columns).unwrap()
+        })
+        .collect()
+}
+
+/// The schema of the batches created by [`join_batches`], with column names
+/// prefixed by `prefix`. The second key column is dictionary encoded if
+/// `dictionary_keys` is true
+pub fn join_schema(prefix: &str, dictionary_keys: bool) -> SchemaRef {
+    let k2_type = if dictionary_keys {
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+    } else {
+        DataType::Utf8
+    };
+    Arc::new(Schema::new(vec![
+        Field::new(format!("{prefix}_k1"), DataType::Int32, true),
+        Field::new(format!("{prefix}_k2"), k2_type, true),
+        Field::new(format!("{prefix}_v"), DataType::Int64, true),
+    ]))
+}
+
+/// Creates between one and four batches with the schema of [`join_schema`].
+/// The key columns `k1` and `k2` have few distinct values, so that rows of
+/// independently generated inputs are likely to match
+pub fn join_batches(
+    rng: &mut StdRng,
+    prefix: &str,
+    dictionary_keys: bool,
+) -> Vec<RecordBatch> {
+    let schema = join_schema(prefix, dictionary_keys);
+    (0..rng.gen_range(1..5))
+        .map(|_| {
+            let len = random_len(rng);
+            let null_fraction = rng.gen_range(0.0..0.3);
+            let keys: Int32Array = (0..len)
+                .map(|_| (!rng.gen_bool(null_fraction)).then(|| rng.gen_range(0..8)))
+                .collect();
+            let k2 = if dictionary_keys {
+                dictionary_array(rng, len)
+            } else {
+                string_array(rng, len)
+            };
+            let columns = vec![Arc::new(keys) as ArrayRef, k2, int64_array(rng, len)];
+            RecordBatch::try_new(schema.clone(), columns).unwrap()
+        })
+        .collect()
+}
+
+/// Returns a batch length, favouring the edge cases of empty and single row
+/// batches
+fn random_len(rng: &mut StdRng) -> usize {
+    match rng.gen_range(0..10) {
+        0 => 0,
+        1 => 1,
+        _ => rng.gen_range(2..100),
+    }
+}
+
+/// Returns `len` values created by `f`, each null with a probability that is
+/// itself random, so that both sparse and fully null arrays are generated
+fn with_nulls<T>(
+    rng: &mut StdRng,
+    len: usize,
+    mut f: impl FnMut(&mut StdRng) -> T,
+) -> Vec<Option<T>> {
+    let null_fraction = match rng.gen_range(0..10) {
+        0 => 0.0,
+        1 => 1.0,
+        _ => rng.gen_range(0.0..0.5),
+    };
+    (0..len)
+        .map(|_| (!rng.gen_bool(null_fraction)).then(|| f(rng)))
+        .collect()
+}
+
+fn int64_array(rng: &mut StdRng, len: usize) -> ArrayRef {
+    let values = with_nulls(rng, len, |rng| rng.gen_range(-1000..1000));
+    Arc::new(Int64Array::from(values))
+}
+
+fn float64_array(rng: &mut StdRng, len: usize) -> ArrayRef {
+    let values = with_nulls(rng, len, |rng| rng.gen_range(-1000.0..1000.0));
+    Arc::new(Float64Array::from(values))
+}
+
+fn boolean_array(rng: &mut StdRng, len: usize) -> ArrayRef {
+    let values = with_nulls(rng, len, |rng| rng.gen_bool(0.5));
+    Arc::new(BooleanArray::from(values))
+}
+
+fn string_array(rng: &mut StdRng, len: usize) -> ArrayRef {
+    let values = with_nulls(rng, len, random_word);
+    Arc::new(values.into_iter().collect::<StringArray>())
+}
+
+fn dictionary_array(rng: &mut StdRng, len: usize) -> ArrayRef {
+    let values = with_nulls(rng, len, random_word);
+    Arc::new(values.into_iter().collect::<DictionaryArray<Int32Type>>())
+}
+
+fn random_word(rng: &mut StdRng) -> &'static str {
+    WORDS[rng.gen_range(0..WORDS.len())]
+}
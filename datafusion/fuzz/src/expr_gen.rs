@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Generation of random expressions over the columns of
+//! [`expr_schema`](crate::data_gen::expr_schema)
+
+use arrow::datatypes::DataType;
+use datafusion_common::ScalarValue;
+use datafusion_expr::{binary_expr, col, lit, lower, upper, when, Expr, Operator};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// The types of the expressions created by [`random_expr`]
+pub const EXPR_TYPES: &[DataType] = &[
+    DataType::Int64,
+    DataType::Float64,
+    DataType::Utf8,
+    DataType::Boolean,
+];
+
+/// Creates a random expression of type `data_type` with at most `depth`
+/// levels of nesting.
+///
+/// Only well typed expressions are generated, which are evaluated by
+/// DataFusion and the [interpreter](crate::interpreter) without errors.
+/// Integer literals are small enough that arithmetic on them does not
+/// overflow while constants are folded.
+pub fn random_expr(rng: &mut StdRng, data_type: &DataType, depth: usize) -> Expr {
+    if depth == 0 || rng.gen_bool(0.3) {
+        return random_leaf(rng, data_type);
+    }
+    let depth = depth - 1;
+
+    match data_type {
+        DataType::Int64 | DataType::Float64 => match rng.gen_range(0..4) {
+            0 => random_case(rng, data_type, depth),
+            n => binary_expr(
+                random_expr(rng, data_type, depth),
+                [Operator::Plus, Operator::Minus, Operator::Multiply][n - 1],
+                random_expr(rng, data_type, depth),
+            ),
+        },
+        DataType::Utf8 => match rng.gen_range(0..4) {
+            0 => random_case(rng, data_type, depth),
+            1 => upper(random_expr(rng, data_type, depth)),
+            2 => lower(random_expr(rng, data_type, depth)),
+            _ => binary_expr(
+                random_expr(rng, data_type, depth),
+                Operator::StringConcat,
+                random_expr(rng, data_type, depth),
+            ),
+        },
+        DataType::Boolean => match rng.gen_range(0..6) {
+            0 => random_case(rng, data_type, depth),
+            1 => {
+                let operand_type = &EXPR_TYPES[rng.gen_range(0..3)];
+                binary_expr(
+                    random_expr(rng, operand_type, depth),
+                    [
+                        Operator::Eq,
+                        Operator::NotEq,
+                        Operator::Lt,
+                        Operator::LtEq,
+                        Operator::Gt,
+                        Operator::GtEq,
+                    ][rng.gen_range(0..6)],
+                    random_expr(rng, operand_type, depth),
+                )
+            }
+            2 => binary_expr(
+                random_expr(rng, data_type, depth),
+                [Operator::And, Operator::Or][rng.gen_range(0..2)],
+                random_expr(rng, data_type, depth),
+            ),
+            3 => random_expr(rng, data_type, depth).not(),
+            _ => {
+                let operand_type = &EXPR_TYPES[rng.gen_range(0..EXPR_TYPES.len())];
+                let operand = random_expr(rng, operand_type, depth);
+                if rng.gen_bool(0.5) {
+                    operand.is_null()
+                } else {
+                    operand.is_not_null()
+                }
+            }
+        },
+        other => unreachable!("can not generate expressions of type {other}"),
+    }
+}
+
+fn random_case(rng: &mut StdRng, data_type: &DataType, depth: usize) -> Expr {
+    let mut case = when(
+        random_expr(rng, &DataType::Boolean, depth),
+        random_expr(rng, data_type, depth),
+    );
+    for _ in 0..rng.gen_range(0..2) {
+        case = case.when(
+            random_expr(rng, &DataType::Boolean, depth),
+            random_expr(rng, data_type, depth),
+        );
+    }
+    let case = if rng.gen_bool(0.5) {
+        case.otherwise(random_expr(rng, data_type, depth))
+    } else {
+        case.end()
+    };
+    case.unwrap()
+}
+
+fn random_leaf(rng: &mut StdRng, data_type: &DataType) -> Expr {
+    if rng.gen_bool(0.1) {
+        return lit(ScalarValue::try_from(data_type).unwrap());
+    }
+    let is_column = rng.gen_bool(0.7);
+    match data_type {
+        DataType::Int64 if is_column => col(["i1", "i2"][rng.gen_range(0..2)]),
+        DataType::Int64 => lit(rng.gen_range(-100i64..100)),
+        DataType::Float64 if is_column => col("f1"),
+        DataType::Float64 => lit(rng.gen_range(-100.0f64..100.0)),
+        DataType::Utf8 if is_column => col(["s1", "d1"][rng.gen_range(0..2)]),
+        DataType::Utf8 => lit(["", "a", "Apple", "ß"][rng.gen_range(0..4)]),
+        DataType::Boolean if is_column => col("b1"),
+        DataType::Boolean => lit(rng.gen_bool(0.5)),
+        other => unreachable!("can not generate expressions of type {other}"),
+    }
+}
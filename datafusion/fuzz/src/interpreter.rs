@@ -0,0 +1,179 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A row at a time interpreter for expressions, used as the reference
+//! implementation that DataFusion's vectorized evaluation is checked against
+//!
+//! The interpreter deliberately shares no code with DataFusion's physical
+//! expressions and evaluates each row independently of the others, so that
+//! kernels mishandling nulls, dictionaries or batch boundaries produce
+//! different results.
+
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{BinaryExpr, BuiltinScalarFunction, Case, Expr, Operator};
+use std::cmp::Ordering;
+
+/// A row of values, with dictionary values replaced by the dictionary value
+pub type Row = Vec<ScalarValue>;
+
+/// Returns the rows of `batches`, see [`Row`]
+pub fn rows(batches: &[RecordBatch]) -> Result<Vec<Row>> {
+    let mut rows = vec![];
+    for batch in batches {
+        for i in 0..batch.num_rows() {
+            let row = batch
+                .columns()
+                .iter()
+                .map(|column| ScalarValue::try_from_array(column, i).map(unpack))
+                .collect::<Result<_>>()?;
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// Replaces a dictionary value with the value it encodes
+pub fn unpack(value: ScalarValue) -> ScalarValue {
+    match value {
+        ScalarValue::Dictionary(_, value) => unpack(*value),
+        value => value,
+    }
+}
+
+/// Evaluates `expr` for a single `row` with the given `schema`
+pub fn evaluate(
+    expr: &Expr,
+    schema: &Schema,
+    row: &[ScalarValue],
+) -> Result<ScalarValue> {
+    match expr {
+        Expr::Alias(expr, _) => evaluate(expr, schema, row),
+        Expr::Column(column) => Ok(row[schema.index_of(&column.name)?].clone()),
+        Expr::Literal(value) => Ok(unpack(value.clone())),
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            let left = evaluate(left, schema, row)?;
+            let right = evaluate(right, schema, row)?;
+            evaluate_binary(left, *op, right)
+        }
+        Expr::Not(expr) => match evaluate(expr, schema, row)? {
+            ScalarValue::Boolean(v) => Ok(ScalarValue::Boolean(v.map(|v| !v))),
+            other => unsupported(&format!("NOT {other:?}")),
+        },
+        Expr::IsNull(expr) => Ok(ScalarValue::Boolean(Some(
+            evaluate(expr, schema, row)?.is_null(),
+        ))),
+        Expr::IsNotNull(expr) => Ok(ScalarValue::Boolean(Some(
+            !evaluate(expr, schema, row)?.is_null(),
+        ))),
+        Expr::Case(Case {
+            expr: None,
+            when_then_expr,
+            else_expr,
+        }) => {
+            for (when, then) in when_then_expr {
+                if evaluate(when, schema, row)? == ScalarValue::Boolean(Some(true)) {
+                    return evaluate(then, schema, row);
+                }
+            }
+            match else_expr {
+                Some(else_expr) => evaluate(else_expr, schema, row),
+                None => {
+                    // the result is a null of the type of the THEN branches
+                    let then = evaluate(&when_then_expr[0].1, schema, row)?;
+                    ScalarValue::try_from(&then.get_datatype())
+                }
+            }
+        }
+        Expr::ScalarFunction { fun, args } if args.len() == 1 => {
+            let arg = evaluate(&args[0], schema, row)?;
+            match (fun, arg) {
+                // DataFusion only changes the case of ASCII characters
+                (BuiltinScalarFunction::Upper, ScalarValue::Utf8(v)) => {
+                    Ok(ScalarValue::Utf8(v.map(|v| v.to_ascii_uppercase().into())))
+                }
+                (BuiltinScalarFunction::Lower, ScalarValue::Utf8(v)) => {
+                    Ok(ScalarValue::Utf8(v.map(|v| v.to_ascii_lowercase().into())))
+                }
+                (fun, arg) => unsupported(&format!("{fun}({arg:?})")),
+            }
+        }
+        other => unsupported(&other.to_string()),
+    }
+}
+
+fn evaluate_binary(
+    left: ScalarValue,
+    op: Operator,
+    right: ScalarValue,
+) -> Result<ScalarValue> {
+    use ScalarValue::*;
+
+    let value = match (op, left, right) {
+        (Operator::Plus, Int64(l), Int64(r)) => Int64(zip(l, r, i64::wrapping_add)),
+        (Operator::Minus, Int64(l), Int64(r)) => Int64(zip(l, r, i64::wrapping_sub)),
+        (Operator::Multiply, Int64(l), Int64(r)) => Int64(zip(l, r, i64::wrapping_mul)),
+        (Operator::Plus, Float64(l), Float64(r)) => Float64(zip(l, r, |l, r| l + r)),
+        (Operator::Minus, Float64(l), Float64(r)) => Float64(zip(l, r, |l, r| l - r)),
+        (Operator::Multiply, Float64(l), Float64(r)) => Float64(zip(l, r, |l, r| l * r)),
+        (Operator::StringConcat, Utf8(l), Utf8(r)) => {
+            Utf8(zip(l, r, |l, r| format!("{l}{r}").into()))
+        }
+        // Kleene logic: NULL is unknown, and only matters if the other side
+        // does not determine the result
+        (Operator::And, Boolean(l), Boolean(r)) => Boolean(match (l, r) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        }),
+        (Operator::Or, Boolean(l), Boolean(r)) => Boolean(match (l, r) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        }),
+        (op, l, r) if l.get_datatype() == r.get_datatype() => {
+            if l.is_null() || r.is_null() {
+                return Ok(Boolean(None));
+            }
+            let ordering = l.partial_cmp(&r).ok_or_else(|| {
+                DataFusionError::Internal(format!("can not compare {l:?} and {r:?}"))
+            })?;
+            Boolean(Some(match op {
+                Operator::Eq => ordering == Ordering::Equal,
+                Operator::NotEq => ordering != Ordering::Equal,
+                Operator::Lt => ordering == Ordering::Less,
+                Operator::LtEq => ordering != Ordering::Greater,
+                Operator::Gt => ordering == Ordering::Greater,
+                Operator::GtEq => ordering != Ordering::Less,
+                op => return unsupported(&format!("{l:?} {op} {r:?}")),
+            }))
+        }
+        (op, l, r) => return unsupported(&format!("{l:?} {op} {r:?}")),
+    };
+    Ok(value)
+}
+
+fn zip<T>(l: Option<T>, r: Option<T>, f: impl Fn(T, T) -> T) -> Option<T> {
+    Some(f(l?, r?))
+}
+
+fn unsupported<T>(expr: &str) -> Result<T> {
+    Err(DataFusionError::NotImplemented(format!(
+        "The fuzz interpreter does not support {expr}"
+    )))
+}
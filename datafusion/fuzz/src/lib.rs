@@ -0,0 +1,32 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Randomized differential testing of DataFusion.
+//!
+//! This crate generates random [record batches](data_gen), [expressions](expr_gen)
+//! and join inputs, and [checks](check) that DataFusion's vectorized execution
+//! produces the same results as a simple row at a time
+//! [interpreter](interpreter). The generated data favours edge cases such as
+//! empty batches, all null columns and dictionary encoded strings.
+//!
+//! The fuzz tests in `tests/` use deterministic seeds, which are included in
+//! failure messages so that failures can be reproduced.
+
+pub mod check;
+pub mod data_gen;
+pub mod expr_gen;
+pub mod interpreter;
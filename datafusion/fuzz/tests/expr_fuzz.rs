@@ -0,0 +1,41 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use datafusion_fuzz::check::check_expr;
+use datafusion_fuzz::data_gen::expr_batches;
+use datafusion_fuzz::expr_gen::{random_expr, EXPR_TYPES};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Number of random expressions checked per expression type
+const ITERATIONS: u64 = 100;
+
+#[tokio::test]
+async fn test_expr_fuzz() {
+    for (type_index, data_type) in EXPR_TYPES.iter().enumerate() {
+        for iteration in 0..ITERATIONS {
+            let seed = type_index as u64 * ITERATIONS + iteration;
+            let mut rng = StdRng::seed_from_u64(seed);
+            let batches = expr_batches(&mut rng);
+            let expr = random_expr(&mut rng, data_type, 4);
+
+            check_expr(&batches, &expr)
+                .await
+                .unwrap_or_else(|e| panic!("seed {seed}: {e}"));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use datafusion_expr::JoinType;
+use datafusion_fuzz::check::check_join;
+use datafusion_fuzz::data_gen::join_batches;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Number of random inputs checked per join type and algorithm
+const ITERATIONS: u64 = 20;
+
+async fn run_join_fuzz(join_type: JoinType) {
+    for prefer_hash_join in [true, false] {
+        // the sort merge join does not support dictionary encoded keys
+        let dictionary_keys = prefer_hash_join;
+        for seed in 0..ITERATIONS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let left = join_batches(&mut rng, "l", dictionary_keys);
+            let right = join_batches(&mut rng, "r", dictionary_keys);
+
+            check_join(&left, &right, join_type, prefer_hash_join)
+                .await
+                .unwrap_or_else(|e| {
+                    panic!("seed {seed}, prefer_hash_join {prefer_hash_join}: {e}")
+                });
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_inner_join_fuzz() {
+    run_join_fuzz(JoinType::Inner).await
+}
+
+#[tokio::test]
+async fn test_left_join_fuzz() {
+    run_join_fuzz(JoinType::Left).await
+}
+
+#[tokio::test]
+async fn test_right_join_fuzz() {
+    run_join_fuzz(JoinType::Right).await
+}
+
+#[tokio::test]
+async fn test_full_join_fuzz() {
+    run_join_fuzz(JoinType::Full).await
+}
+
+#[tokio::test]
+async fn test_left_semi_join_fuzz() {
+    run_join_fuzz(JoinType::LeftSemi).await
+}
+
+#[tokio::test]
+async fn test_left_anti_join_fuzz() {
+    run_join_fuzz(JoinType::LeftAnti).await
+}
+
+#[tokio::test]
+async fn test_right_semi_join_fuzz() {
+    run_join_fuzz(JoinType::RightSemi).await
+}
+
+#[tokio::test]
+async fn test_right_anti_join_fuzz() {
+    run_join_fuzz(JoinType::RightAnti).await
+}
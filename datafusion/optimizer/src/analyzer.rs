@@ -0,0 +1,173 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Analyzer traits, rewriting a plan before it is optimized
+
+use crate::mask_columns::MaskColumns;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::Result;
+use datafusion_expr::logical_plan::LogicalPlan;
+use log::debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// `AnalyzerRule` rewrites a [`LogicalPlan`] before it is optimized.
+///
+/// Unlike an [`OptimizerRule`](crate::OptimizerRule), an analyzer rule may
+/// change the results of the plan, for example to add the filters of a row
+/// level security policy. Each rule is applied once, in order, and the
+/// expressions it adds are type coerced by the optimizer afterwards.
+pub trait AnalyzerRule {
+    /// Rewrite `plan`
+    fn analyze(&self, plan: &LogicalPlan, config: &ConfigOptions) -> Result<LogicalPlan>;
+
+    /// A human readable name for this analyzer rule
+    fn name(&self) -> &str;
+}
+
+/// Applies [`AnalyzerRule`]s to a plan before it is optimized
+#[derive(Clone)]
+pub struct Analyzer {
+    /// All rules to apply
+    pub rules: Vec<Arc<dyn AnalyzerRule + Send + Sync>>,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer {
+    /// Create a new analyzer using the recommended list of rules
+    pub fn new() -> Self {
+        let rules: Vec<Arc<dyn AnalyzerRule + Send + Sync>> =
+            vec![Arc::new(MaskColumns::new())];
+        Self::with_rules(rules)
+    }
+
+    /// Create a new analyzer with the given rules
+    pub fn with_rules(rules: Vec<Arc<dyn AnalyzerRule + Send + Sync>>) -> Self {
+        Self { rules }
+    }
+
+    /// Analyzes the logical plan by applying the analyzer rules, and
+    /// invoking observer function after each call
+    pub fn analyze<F>(
+        &self,
+        plan: &LogicalPlan,
+        config: &ConfigOptions,
+        mut observer: F,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn AnalyzerRule),
+    {
+        self.analyze_timed(plan, config, |plan, rule, _| observer(plan, rule))
+    }
+
+    /// Analyzes the logical plan like [`Self::analyze`], also passing the
+    /// time spent in each call to the observer function
+    pub fn analyze_timed<F>(
+        &self,
+        plan: &LogicalPlan,
+        config: &ConfigOptions,
+        mut observer: F,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn AnalyzerRule, Duration),
+    {
+        let start_time = Instant::now();
+        let mut new_plan = plan.clone();
+        for rule in &self.rules {
+            let rule_start = Instant::now();
+            new_plan = rule.analyze(&new_plan, config)?;
+            observer(&new_plan, rule.as_ref(), rule_start.elapsed());
+            debug!(
+                "Plan after analyzer rule '{}':\n{}\n",
+                rule.name(),
+                new_plan.display_indent()
+            );
+        }
+        debug!("Analyzer took {} ms", start_time.elapsed().as_millis());
+        Ok(new_plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::test_table_scan;
+    use datafusion_common::DataFusionError;
+    use datafusion_expr::{col, lit, LogicalPlanBuilder};
+
+    struct AddFilter {}
+
+    impl AnalyzerRule for AddFilter {
+        fn analyze(&self, plan: &LogicalPlan, _: &ConfigOptions) -> Result<LogicalPlan> {
+            LogicalPlanBuilder::from(plan.clone())
+                .filter(col("a").gt(lit(1)))?
+                .build()
+        }
+
+        fn name(&self) -> &str {
+            "add_filter"
+        }
+    }
+
+    struct Reject {}
+
+    impl AnalyzerRule for Reject {
+        fn analyze(&self, _: &LogicalPlan, _: &ConfigOptions) -> Result<LogicalPlan> {
+            Err(DataFusionError::Plan("access denied".to_string()))
+        }
+
+        fn name(&self) -> &str {
+            "reject"
+        }
+    }
+
+    #[test]
+    fn apply_rules_in_order() -> Result<()> {
+        let analyzer =
+            Analyzer::with_rules(vec![Arc::new(AddFilter {}), Arc::new(AddFilter {})]);
+        let mut names = vec![];
+        let plan = analyzer.analyze(
+            &test_table_scan()?,
+            &ConfigOptions::default(),
+            |_, rule| names.push(rule.name().to_string()),
+        )?;
+        assert_eq!(names, ["add_filter", "add_filter"]);
+        assert_eq!(
+            format!("{plan:?}"),
+            "Filter: test.a > Int32(1)\
+            \n  Filter: test.a > Int32(1)\
+            \n    TableScan: test"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rule_error_is_returned_as_is() -> Result<()> {
+        let analyzer =
+            Analyzer::with_rules(vec![Arc::new(Reject {}), Arc::new(AddFilter {})]);
+        let err = analyzer
+            .analyze(&test_table_scan()?, &ConfigOptions::default(), |_, _| {})
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Error during planning: access denied");
+        Ok(())
+    }
+}
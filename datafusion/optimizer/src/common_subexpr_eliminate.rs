@@ -226,6 +226,7 @@ impl OptimizerRule for CommonSubexprEliminate {
             | LogicalPlan::EmptyRelation(_)
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::SharedCte(_)
             | LogicalPlan::Limit(_)
             | LogicalPlan::CreateExternalTable(_)
             | LogicalPlan::Explain(_)
@@ -236,6 +237,10 @@ impl OptimizerRule for CommonSubexprEliminate {
             | LogicalPlan::CreateCatalog(_)
             | LogicalPlan::DropTable(_)
             | LogicalPlan::DropView(_)
+            | LogicalPlan::AnalyzeTable(_)
+            | LogicalPlan::CreateFunction(_)
+            | LogicalPlan::DropFunction(_)
+            | LogicalPlan::Transaction(_)
             | LogicalPlan::SetVariable(_)
             | LogicalPlan::Distinct(_)
             | LogicalPlan::Extension(_)
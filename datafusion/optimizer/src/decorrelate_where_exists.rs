@@ -15,14 +15,17 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::alias::AliasGenerator;
 use crate::optimizer::ApplyOrder;
 use crate::utils::{
-    conjunction, exprs_to_join_cols, find_join_exprs, split_conjunction,
-    verify_not_disjunction,
+    conjunction, existence_input, exprs_to_join_cols, extract_join_filters,
+    find_join_exprs, is_correlated, mark_join, split_conjunction, verify_not_disjunction,
 };
 use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::tree_node::TreeNode;
 use datafusion_common::{context, Result};
 use datafusion_expr::{
+    lit,
     logical_plan::{Filter, JoinType, Subquery},
     Expr, LogicalPlan, LogicalPlanBuilder,
 };
@@ -30,12 +33,14 @@ use std::sync::Arc;
 
 /// Optimizer rule for rewriting subquery filters to joins
 #[derive(Default)]
-pub struct DecorrelateWhereExists {}
+pub struct DecorrelateWhereExists {
+    alias: AliasGenerator,
+}
 
 impl DecorrelateWhereExists {
     #[allow(missing_docs)]
     pub fn new() -> Self {
-        Self {}
+        Self::default()
     }
 
     /// Finds expressions that have a where in subquery (and recurses when found)
@@ -72,6 +77,32 @@ impl DecorrelateWhereExists {
 
         Ok((subqueries, others))
     }
+
+    /// Evaluates `EXISTS (subquery)`, nested in a predicate that a semi join
+    /// can't evaluate such as an OR, by a mark join of `outer` with the
+    /// subquery. Returns the join and its mark, or `None` if the subquery
+    /// can't be decorrelated.
+    fn mark_exists(
+        &self,
+        subquery: &Subquery,
+        outer: &LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Option<(LogicalPlan, Expr)>> {
+        let subquery = self
+            .try_optimize(&subquery.subquery, config)?
+            .unwrap_or_else(|| subquery.subquery.as_ref().clone());
+
+        let input = match existence_input(&subquery) {
+            Some(input) => input,
+            None => return Ok(Some((outer.clone(), lit(true)))),
+        };
+        let (join_filters, input) = extract_join_filters(input)?;
+        if is_correlated(&input)? {
+            return Ok(None);
+        }
+        let alias = self.alias.next("__correlated_sq");
+        mark_join(outer, input, conjunction(join_filters), &alias).map(Some)
+    }
 }
 
 impl OptimizerRule for DecorrelateWhereExists {
@@ -84,21 +115,62 @@ impl OptimizerRule for DecorrelateWhereExists {
             LogicalPlan::Filter(filter) => {
                 let (subqueries, other_exprs) =
                     self.extract_subquery_exprs(&filter.predicate, config)?;
-                if subqueries.is_empty() {
+
+                // evaluate the exists clauses nested in the other expressions,
+                // such as under an OR, with mark joins
+                let mut cur_input = filter.input.as_ref().clone();
+                let mut marked = false;
+                let other_exprs = other_exprs
+                    .into_iter()
+                    .map(|expr| {
+                        expr.transform_up_mut(&mut |expr| match &expr {
+                            Expr::Exists { subquery, negated } => {
+                                match self.mark_exists(subquery, &cur_input, config)? {
+                                    Some((plan, mark)) => {
+                                        cur_input = plan;
+                                        marked = true;
+                                        Ok(Some(if *negated { mark.not() } else { mark }))
+                                    }
+                                    None => Ok(None),
+                                }
+                            }
+                            _ => Ok(None),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if subqueries.is_empty() && !marked {
                     // regular filter, no subquery exists clause here
                     return Ok(None);
                 }
 
                 // iterate through all exists clauses in predicate, turning each into a join
-                let mut cur_input = filter.input.as_ref().clone();
+                let outer_other_exprs: &[Expr] = if marked { &[] } else { &other_exprs };
                 for subquery in subqueries {
-                    if let Some(x) = optimize_exists(&subquery, &cur_input, &other_exprs)?
+                    if let Some(x) =
+                        optimize_exists(&subquery, &cur_input, outer_other_exprs)?
                     {
                         cur_input = x;
                     } else {
                         return Ok(None);
                     }
                 }
+                if marked {
+                    // filter on the marks, then drop them
+                    let mut plan = LogicalPlanBuilder::from(cur_input);
+                    if let Some(expr) = conjunction(other_exprs) {
+                        plan = plan.filter(expr)?;
+                    }
+                    cur_input = plan
+                        .project(
+                            filter
+                                .input
+                                .schema()
+                                .fields()
+                                .iter()
+                                .map(|field| Expr::Column(field.qualified_column())),
+                        )?
+                        .build()?;
+                }
                 Ok(Some(cur_input))
             }
             _ => Ok(None),
@@ -134,23 +206,44 @@ fn optimize_exists(
     outer_input: &LogicalPlan,
     outer_other_exprs: &[Expr],
 ) -> Result<Option<LogicalPlan>> {
-    let subqry_filter = match query_info.query.subquery.as_ref() {
-        LogicalPlan::Distinct(subqry_distinct) => match subqry_distinct.input.as_ref() {
-            LogicalPlan::Projection(subqry_proj) => {
-                Filter::try_from_plan(&subqry_proj.input)
+    let subqry_input = match query_info.query.subquery.as_ref() {
+        LogicalPlan::Distinct(_)
+        | LogicalPlan::Projection(_)
+        | LogicalPlan::Sort(_)
+        | LogicalPlan::Limit(_) => existence_input(&query_info.query.subquery),
+        _ => {
+            // Subquery currently only supports distinct, projection, sort or limit
+            return Ok(None);
+        }
+    };
+    let subqry_input = match subqry_input {
+        Some(input) => input,
+        None => {
+            // the subquery always has a row, whatever the outer row
+            let mut new_plan = LogicalPlanBuilder::from(outer_input.clone());
+            if query_info.negated {
+                new_plan = new_plan.filter(lit(false))?;
             }
-            _ => {
-                // Subquery currently only supports distinct or projection
-                return Ok(None);
+            if let Some(expr) = conjunction(outer_other_exprs.to_vec()) {
+                new_plan = new_plan.filter(expr)?
             }
-        },
-        LogicalPlan::Projection(subqry_proj) => Filter::try_from_plan(&subqry_proj.input),
+            return Ok(Some(new_plan.build()?));
+        }
+    };
+    let subqry_filter = match subqry_input {
+        LogicalPlan::Filter(filter) if !is_correlated(&filter.input)? => filter.clone(),
         _ => {
-            // Subquery currently only supports distinct or projection
-            return Ok(None);
+            // the correlated predicates are below other operators, pull them
+            // up to a filter on top of the subquery
+            let (join_filters, input) = extract_join_filters(subqry_input)?;
+            match conjunction(join_filters) {
+                Some(predicate) => Filter::try_new(predicate, Arc::new(input))?,
+                None => Filter::try_from_plan(subqry_input)
+                    .map_err(|e| context!("cannot optimize non-correlated subquery", e))?
+                    .clone(),
+            }
         }
-    }
-    .map_err(|e| context!("cannot optimize non-correlated subquery", e))?;
+    };
 
     // split into filters
     let subqry_filter_exprs = split_conjunction(&subqry_filter.predicate);
@@ -209,9 +302,10 @@ impl SubqueryInfo {
 mod tests {
     use super::*;
     use crate::test::*;
+    use datafusion_common::Column;
     use datafusion_common::Result;
     use datafusion_expr::{
-        col, exists, lit, logical_plan::LogicalPlanBuilder, not_exists,
+        col, count, exists, lit, logical_plan::LogicalPlanBuilder, max, not_exists,
     };
     use std::ops::Add;
 
@@ -491,14 +585,68 @@ mod tests {
             .project(vec![col("customer.c_custkey")])?
             .build()?;
 
-        // not optimized
         let expected = r#"Projection: customer.c_custkey [c_custkey:Int64]
-  Filter: EXISTS (<subquery>) OR customer.c_custkey = Int32(1) [c_custkey:Int64, c_name:Utf8]
-    Subquery: [o_custkey:Int64]
-      Projection: orders.o_custkey [o_custkey:Int64]
-        Filter: customer.c_custkey = orders.o_custkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]
-          TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]
-    TableScan: customer [c_custkey:Int64, c_name:Utf8]"#;
+  Projection: customer.c_custkey, customer.c_name [c_custkey:Int64, c_name:Utf8]
+    Filter: __correlated_sq_1.mark OR customer.c_custkey = Int32(1) [c_custkey:Int64, c_name:Utf8, mark:Boolean]
+      LeftMark Join:  Filter: customer.c_custkey = __correlated_sq_1.o_custkey [c_custkey:Int64, c_name:Utf8, mark:Boolean]
+        TableScan: customer [c_custkey:Int64, c_name:Utf8]
+        SubqueryAlias: __correlated_sq_1 [o_custkey:Int64]
+          Projection: orders.o_custkey [o_custkey:Int64]
+            TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]"#;
+
+        assert_plan_eq(&plan, expected)
+    }
+
+    /// Test for exists subqueries in and out of disjunctions
+    #[test]
+    fn exists_subquery_conjunction_and_disjunction() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(col("customer.c_custkey").eq(col("orders.o_custkey")))?
+                .project(vec![col("orders.o_custkey")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(
+                exists(sq.clone())
+                    .and(not_exists(sq).or(col("customer.c_name").eq(lit("a")))),
+            )?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+
+        let expected = r#"Projection: customer.c_custkey [c_custkey:Int64]
+  Projection: customer.c_custkey, customer.c_name [c_custkey:Int64, c_name:Utf8]
+    Filter: NOT __correlated_sq_1.mark OR customer.c_name = Utf8("a") [c_custkey:Int64, c_name:Utf8, mark:Boolean]
+      LeftSemi Join: customer.c_custkey = orders.o_custkey [c_custkey:Int64, c_name:Utf8, mark:Boolean]
+        LeftMark Join:  Filter: customer.c_custkey = __correlated_sq_1.o_custkey [c_custkey:Int64, c_name:Utf8, mark:Boolean]
+          TableScan: customer [c_custkey:Int64, c_name:Utf8]
+          SubqueryAlias: __correlated_sq_1 [o_custkey:Int64]
+            Projection: orders.o_custkey [o_custkey:Int64]
+              TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]
+        TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]"#;
+
+        assert_plan_eq(&plan, expected)
+    }
+
+    /// Test for uncorrelated exists subquery in a disjunction
+    #[test]
+    fn exists_subquery_uncorrelated_disjunction() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(
+                not_exists(test_subquery_with_name("sq")?).or(col("a").eq(lit(1u32))),
+            )?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = r#"Projection: test.b [b:UInt32]
+  Projection: test.a, test.b, test.c [a:UInt32, b:UInt32, c:UInt32]
+    Filter: NOT __correlated_sq_1.mark OR test.a = UInt32(1) [a:UInt32, b:UInt32, c:UInt32, mark:Boolean]
+      LeftMark Join:  [a:UInt32, b:UInt32, c:UInt32, mark:Boolean]
+        TableScan: test [a:UInt32, b:UInt32, c:UInt32]
+        SubqueryAlias: __correlated_sq_1 [exists:Boolean]
+          Projection: Boolean(true) AS exists [exists:Boolean]
+            TableScan: sq [a:UInt32, b:UInt32, c:UInt32]"#;
 
         assert_plan_eq(&plan, expected)
     }
@@ -526,6 +674,94 @@ mod tests {
         assert_plan_eq(&plan, expected)
     }
 
+    /// Test for correlated exists subquery with a limit
+    #[test]
+    fn exists_subquery_with_limit() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(col("orders.o_custkey").eq(col("customer.c_custkey")))?
+                .project(vec![col("orders.o_custkey")])?
+                .limit(0, Some(1))?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(exists(sq))?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+
+        let expected = r#"Projection: customer.c_custkey [c_custkey:Int64]
+  LeftSemi Join: customer.c_custkey = orders.o_custkey [c_custkey:Int64, c_name:Utf8]
+    TableScan: customer [c_custkey:Int64, c_name:Utf8]
+    TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]"#;
+
+        assert_plan_eq(&plan, expected)
+    }
+
+    /// Test for correlated exists subquery filtering its groups
+    #[test]
+    fn exists_subquery_with_group_by_having() -> Result<()> {
+        let max_price = Expr::Column(Column::from_name("MAX(orders.o_totalprice)"));
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(col("orders.o_custkey").eq(col("customer.c_custkey")))?
+                .aggregate(
+                    vec![col("orders.o_orderstatus")],
+                    vec![max(col("orders.o_totalprice"))],
+                )?
+                .filter(max_price.gt(lit(100.0)))?
+                .project(vec![col("orders.o_orderstatus")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(exists(sq))?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+
+        // the groups are the ones of each customer
+        let expected = r#"Projection: customer.c_custkey [c_custkey:Int64]
+  LeftSemi Join: customer.c_custkey = orders.o_custkey [c_custkey:Int64, c_name:Utf8]
+    TableScan: customer [c_custkey:Int64, c_name:Utf8]
+    Filter: MAX(orders.o_totalprice) > Float64(100) [o_orderstatus:Utf8, o_custkey:Int64, MAX(orders.o_totalprice):Float64;N]
+      Aggregate: groupBy=[[orders.o_orderstatus, orders.o_custkey]], aggr=[[MAX(orders.o_totalprice)]] [o_orderstatus:Utf8, o_custkey:Int64, MAX(orders.o_totalprice):Float64;N]
+        TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]"#;
+
+        assert_plan_eq(&plan, expected)
+    }
+
+    /// Test for correlated exists subquery aggregating without grouping,
+    /// which always has a row
+    #[test]
+    fn exists_subquery_with_aggregate() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(col("orders.o_custkey").eq(col("customer.c_custkey")))?
+                .aggregate(Vec::<Expr>::new(), vec![count(col("orders.o_orderkey"))])?
+                .project(vec![Expr::Column(Column::from_name(
+                    "COUNT(orders.o_orderkey)",
+                ))])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(exists(sq.clone()))?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+        let expected = r#"Projection: customer.c_custkey [c_custkey:Int64]
+  TableScan: customer [c_custkey:Int64, c_name:Utf8]"#;
+        assert_plan_eq(&plan, expected)?;
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(not_exists(sq))?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+        let expected = r#"Projection: customer.c_custkey [c_custkey:Int64]
+  Filter: Boolean(false) [c_custkey:Int64, c_name:Utf8]
+    TableScan: customer [c_custkey:Int64, c_name:Utf8]"#;
+        assert_plan_eq(&plan, expected)
+    }
+
     /// Test for single exists subquery filter
     #[test]
     fn exists_subquery_simple() -> Result<()> {
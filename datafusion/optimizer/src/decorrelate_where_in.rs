@@ -17,15 +17,18 @@
 
 use crate::alias::AliasGenerator;
 use crate::optimizer::ApplyOrder;
-use crate::utils::{conjunction, only_or_err, split_conjunction};
+use crate::utils::{
+    conjunction, disjunction, extract_join_filters, is_correlated, mark_join,
+    only_or_err, replace_qualified_name, split_conjunction,
+};
 use crate::{OptimizerConfig, OptimizerRule};
-use datafusion_common::{context, Column, Result};
-use datafusion_expr::expr_rewriter::{replace_col, unnormalize_col};
+use datafusion_common::tree_node::TreeNode;
+use datafusion_common::{context, Column, Result, ScalarValue};
+use datafusion_expr::expr_rewriter::unnormalize_col;
 use datafusion_expr::logical_plan::{JoinType, Projection, Subquery};
-use datafusion_expr::utils::check_all_column_from_schema;
-use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder};
+use datafusion_expr::{lit, when, Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder};
 use log::debug;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 #[derive(Default)]
@@ -79,6 +82,79 @@ impl DecorrelateWhereIn {
 
         Ok((subqueries, others))
     }
+
+    /// Evaluates `expr IN (subquery)`, nested in a predicate that a semi join
+    /// can't evaluate such as an OR, by mark joins of `outer` with the
+    /// subquery. Returns the joins and the boolean expression replacing the
+    /// subquery, or `None` for the uncorrelated subqueries, which the physical
+    /// plan evaluates, and the subqueries that can't be decorrelated.
+    fn mark_where_in(
+        &self,
+        query_info: &SubqueryInfo,
+        outer: &LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Option<(LogicalPlan, Expr)>> {
+        let subquery = self
+            .try_optimize(&query_info.query.subquery, config)?
+            .unwrap_or_else(|| query_info.query.subquery.as_ref().clone());
+        let (subquery_expr, subquery_input) = match split_in_subquery(&subquery) {
+            Ok(split) => split,
+            Err(_) => return Ok(None),
+        };
+        let (join_filters, subquery_input) = extract_join_filters(&subquery_input)?;
+        if join_filters.is_empty() || is_correlated(&subquery_input)? {
+            return Ok(None);
+        }
+
+        let in_predicate =
+            Expr::eq(query_info.where_in_expr.clone(), subquery_expr.clone());
+        let correlation =
+            conjunction(remove_duplicated_filter(join_filters, in_predicate.clone()));
+        let and_correlation = |expr: Expr| match &correlation {
+            Some(correlation) => expr.and(correlation.clone()),
+            None => expr,
+        };
+
+        // expr IN (subquery) is true if a row of the subquery is equal to
+        // expr, otherwise it's NULL if expr is NULL and the subquery has rows,
+        // or if the subquery has a NULL row, and false otherwise
+        let (mut plan, matched) = mark_join(
+            outer,
+            subquery_input.clone(),
+            Some(and_correlation(in_predicate)),
+            &self.alias.next("__correlated_sq"),
+        )?;
+        let mut unknown = vec![];
+        if query_info.where_in_expr.nullable(outer.schema())? {
+            let (new_plan, not_empty) = mark_join(
+                &plan,
+                subquery_input.clone(),
+                correlation.clone(),
+                &self.alias.next("__correlated_sq"),
+            )?;
+            plan = new_plan;
+            unknown.push(query_info.where_in_expr.clone().is_null().and(not_empty));
+        }
+        if subquery_expr.nullable(subquery_input.schema())? {
+            let (new_plan, has_null) = mark_join(
+                &plan,
+                subquery_input,
+                Some(and_correlation(subquery_expr.is_null())),
+                &self.alias.next("__correlated_sq"),
+            )?;
+            plan = new_plan;
+            unknown.push(has_null);
+        }
+
+        let expr = match disjunction(unknown) {
+            Some(unknown) => when(matched, lit(true))
+                .when(unknown, lit(ScalarValue::Boolean(None)))
+                .otherwise(lit(false))?,
+            None => matched,
+        };
+        let expr = if query_info.negated { expr.not() } else { expr };
+        Ok(Some((plan, expr)))
+    }
 }
 
 impl OptimizerRule for DecorrelateWhereIn {
@@ -91,22 +167,72 @@ impl OptimizerRule for DecorrelateWhereIn {
             LogicalPlan::Filter(filter) => {
                 let (subqueries, other_exprs) =
                     self.extract_subquery_exprs(&filter.predicate, config)?;
-                if subqueries.is_empty() {
+
+                // evaluate the correlated subqueries nested in the other
+                // expressions, such as under an OR, with mark joins
+                let mut cur_input = filter.input.as_ref().clone();
+                let mut marked = false;
+                let other_exprs = other_exprs
+                    .into_iter()
+                    .map(|expr| {
+                        expr.transform_up_mut(&mut |expr| match expr {
+                            Expr::InSubquery {
+                                expr,
+                                subquery,
+                                negated,
+                            } => {
+                                let query_info =
+                                    SubqueryInfo::new(subquery, *expr, negated);
+                                match self.mark_where_in(
+                                    &query_info,
+                                    &cur_input,
+                                    config,
+                                )? {
+                                    Some((plan, expr)) => {
+                                        cur_input = plan;
+                                        marked = true;
+                                        Ok(Some(expr))
+                                    }
+                                    None => Ok(None),
+                                }
+                            }
+                            _ => Ok(None),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if subqueries.is_empty() && !marked {
                     // regular filter, no subquery exists clause here
                     return Ok(None);
                 }
 
                 // iterate through all exists clauses in predicate, turning each into a join
                 // iterate through all exists clauses in predicate, turning each into a join
-                let mut cur_input = filter.input.as_ref().clone();
+                let outer_other_exprs: &[Expr] = if marked { &[] } else { &other_exprs };
                 for subquery in subqueries {
                     cur_input = optimize_where_in(
                         &subquery,
                         &cur_input,
-                        &other_exprs,
+                        outer_other_exprs,
                         &self.alias,
                     )?;
                 }
+                if marked {
+                    // filter on the marks, then drop them
+                    let mut plan = LogicalPlanBuilder::from(cur_input);
+                    if let Some(expr) = conjunction(other_exprs) {
+                        plan = plan.filter(expr)?;
+                    }
+                    cur_input = plan
+                        .project(
+                            filter
+                                .input
+                                .schema()
+                                .fields()
+                                .iter()
+                                .map(|field| Expr::Column(field.qualified_column())),
+                        )?
+                        .build()?;
+                }
                 Ok(Some(cur_input))
             }
             _ => Ok(None),
@@ -144,14 +270,10 @@ fn optimize_where_in(
     outer_other_exprs: &[Expr],
     alias: &AliasGenerator,
 ) -> Result<LogicalPlan> {
-    let projection = Projection::try_from_plan(&query_info.query.subquery)
-        .map_err(|e| context!("a projection is required", e))?;
-    let subquery_input = projection.input.clone();
-    let subquery_expr = only_or_err(projection.expr.as_slice())
-        .map_err(|e| context!("single expression projection required", e))?;
+    let (subquery_expr, subquery_input) = split_in_subquery(&query_info.query.subquery)?;
 
     // extract join filters
-    let (join_filters, subquery_input) = extract_join_filters(subquery_input.as_ref())?;
+    let (join_filters, subquery_input) = extract_join_filters(&subquery_input)?;
 
     // in_predicate may be also include in the join filters, remove it from the join filters.
     let in_predicate = Expr::eq(query_info.where_in_expr.clone(), subquery_expr.clone());
@@ -178,7 +300,7 @@ fn optimize_where_in(
     })?;
 
     // add projection
-    if let Expr::Column(col) = subquery_expr {
+    if let Expr::Column(col) = &subquery_expr {
         subquery_cols.remove(col);
     }
     let subquery_expr_name = format!("{:?}", unnormalize_col(subquery_expr.clone()));
@@ -223,31 +345,28 @@ fn optimize_where_in(
     Ok(new_plan)
 }
 
-fn extract_join_filters(maybe_filter: &LogicalPlan) -> Result<(Vec<Expr>, LogicalPlan)> {
-    if let LogicalPlan::Filter(plan_filter) = maybe_filter {
-        let input_schema = plan_filter.input.schema();
-        let subquery_filter_exprs = split_conjunction(&plan_filter.predicate);
-
-        let mut join_filters: Vec<Expr> = vec![];
-        let mut subquery_filters: Vec<Expr> = vec![];
-        for expr in subquery_filter_exprs {
-            let cols = expr.to_columns()?;
-            if check_all_column_from_schema(&cols, input_schema.clone()) {
-                subquery_filters.push(expr.clone());
-            } else {
-                join_filters.push(expr.clone())
-            }
+/// Returns the expression of the single column of an IN subquery and the
+/// plan it's computed on: the input of the projection of the subquery, or the
+/// subquery itself when its projection is below a limit, a sort or a
+/// deduplication
+fn split_in_subquery(subquery: &LogicalPlan) -> Result<(Expr, LogicalPlan)> {
+    match subquery {
+        LogicalPlan::Limit(_)
+        | LogicalPlan::Sort(_)
+        | LogicalPlan::Distinct(_)
+        | LogicalPlan::Aggregate(_)
+            if subquery.schema().fields().len() == 1 =>
+        {
+            let column = subquery.schema().field(0).qualified_column();
+            Ok((Expr::Column(column), subquery.clone()))
         }
-
-        // if the subquery still has filter expressions, restore them.
-        let mut plan = LogicalPlanBuilder::from((*plan_filter.input).clone());
-        if let Some(expr) = conjunction(subquery_filters) {
-            plan = plan.filter(expr)?
+        _ => {
+            let projection = Projection::try_from_plan(subquery)
+                .map_err(|e| context!("a projection is required", e))?;
+            let subquery_expr = only_or_err(projection.expr.as_slice())
+                .map_err(|e| context!("single expression projection required", e))?;
+            Ok((subquery_expr.clone(), projection.input.as_ref().clone()))
         }
-
-        Ok((join_filters, plan.build()?))
-    } else {
-        Ok((vec![], maybe_filter.clone()))
     }
 }
 
@@ -272,23 +391,6 @@ fn remove_duplicated_filter(filters: Vec<Expr>, in_predicate: Expr) -> Vec<Expr>
         .collect::<Vec<_>>()
 }
 
-fn replace_qualified_name(
-    expr: Expr,
-    cols: &BTreeSet<Column>,
-    subquery_alias: &str,
-) -> Result<Expr> {
-    let alias_cols: Vec<Column> = cols
-        .iter()
-        .map(|col| {
-            Column::from_qualified_name(format!("{}.{}", subquery_alias, col.name))
-        })
-        .collect();
-    let replace_map: HashMap<&Column, &Column> =
-        cols.iter().zip(alias_cols.iter()).collect();
-
-    replace_col(expr, &replace_map)
-}
-
 struct SubqueryInfo {
     query: Subquery,
     where_in_expr: Expr,
@@ -310,9 +412,11 @@ mod tests {
     use super::*;
     use crate::test::*;
     use datafusion_common::Result;
+    use datafusion_expr::expr::WindowFunction;
     use datafusion_expr::{
-        and, binary_expr, col, in_subquery, lit, logical_plan::LogicalPlanBuilder,
-        not_in_subquery, or, Operator,
+        and, binary_expr, col, in_subquery, lit, logical_plan::LogicalPlanBuilder, max,
+        not_in_subquery, or, window_frame::WindowFrame, window_function,
+        BuiltInWindowFunction, Operator,
     };
     use std::ops::Add;
 
@@ -941,14 +1045,54 @@ mod tests {
             .project(vec![col("customer.c_custkey")])?
             .build()?;
 
-        // TODO: support disjunction - for now expect unaltered plan
         let expected = r#"Projection: customer.c_custkey [c_custkey:Int64]
-  Filter: customer.c_custkey IN (<subquery>) OR customer.c_custkey = Int32(1) [c_custkey:Int64, c_name:Utf8]
-    Subquery: [o_custkey:Int64]
-      Projection: orders.o_custkey [o_custkey:Int64]
-        Filter: customer.c_custkey = orders.o_custkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]
+  Projection: customer.c_custkey, customer.c_name [c_custkey:Int64, c_name:Utf8]
+    Filter: __correlated_sq_1.mark OR customer.c_custkey = Int32(1) [c_custkey:Int64, c_name:Utf8, mark:Boolean]
+      LeftMark Join:  Filter: customer.c_custkey = __correlated_sq_1.o_custkey [c_custkey:Int64, c_name:Utf8, mark:Boolean]
+        TableScan: customer [c_custkey:Int64, c_name:Utf8]
+        SubqueryAlias: __correlated_sq_1 [o_custkey:Int64]
+          Projection: orders.o_custkey [o_custkey:Int64]
+            TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]"#;
+
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateWhereIn::new()),
+            &plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    /// Test for correlated NOT IN subquery filter with disjunctions and a
+    /// nullable expression
+    #[test]
+    fn not_in_subquery_disjunction_nullable() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("lineitem"))
+                .filter(col("lineitem.l_orderkey").eq(col("orders.o_orderkey")))?
+                .project(vec![col("lineitem.l_extendedprice")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                not_in_subquery(col("orders.o_totalprice"), sq)
+                    .or(col("orders.o_orderstatus").eq(lit("F"))),
+            )?
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+
+        let expected = r#"Projection: orders.o_orderkey [o_orderkey:Int64]
+  Projection: orders.o_orderkey, orders.o_custkey, orders.o_orderstatus, orders.o_totalprice [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]
+    Filter: NOT CASE WHEN __correlated_sq_1.mark THEN Boolean(true) WHEN orders.o_totalprice IS NULL AND __correlated_sq_2.mark THEN Boolean(NULL) ELSE Boolean(false) END OR orders.o_orderstatus = Utf8("F") [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, mark:Boolean, mark:Boolean]
+      LeftMark Join:  Filter: __correlated_sq_2.l_orderkey = orders.o_orderkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, mark:Boolean, mark:Boolean]
+        LeftMark Join:  Filter: orders.o_totalprice = __correlated_sq_1.l_extendedprice AND __correlated_sq_1.l_orderkey = orders.o_orderkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, mark:Boolean]
           TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]
-    TableScan: customer [c_custkey:Int64, c_name:Utf8]"#;
+          SubqueryAlias: __correlated_sq_1 [l_extendedprice:Float64, l_orderkey:Int64]
+            Projection: lineitem.l_extendedprice, lineitem.l_orderkey [l_extendedprice:Float64, l_orderkey:Int64]
+              TableScan: lineitem [l_orderkey:Int64, l_partkey:Int64, l_suppkey:Int64, l_linenumber:Int32, l_quantity:Float64, l_extendedprice:Float64]
+        SubqueryAlias: __correlated_sq_2 [l_orderkey:Int64]
+          Projection: lineitem.l_orderkey [l_orderkey:Int64]
+            TableScan: lineitem [l_orderkey:Int64, l_partkey:Int64, l_suppkey:Int64, l_linenumber:Int32, l_quantity:Float64, l_extendedprice:Float64]"#;
 
         assert_optimized_plan_eq_display_indent(
             Arc::new(DecorrelateWhereIn::new()),
@@ -988,6 +1132,115 @@ mod tests {
         Ok(())
     }
 
+    /// Test for correlated IN subquery keeping the first row of each outer row
+    #[test]
+    fn in_subquery_with_limit() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(test_table_scan_with_name("sq")?)
+                .filter(col("test.a").eq(col("sq.a")))?
+                .sort(vec![col("sq.b").sort(true, false)])?
+                .project(vec![col("c")])?
+                .limit(0, Some(1))?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("test")?)
+            .filter(in_subquery(col("c"), sq))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftSemi Join:  Filter: test.c = __correlated_sq_1.c AND test.a = __correlated_sq_1.a [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [c:UInt32, a:UInt32]\
+        \n      Projection: sq.c AS c, sq.a [c:UInt32, a:UInt32]\
+        \n        Projection: sq.c, sq.a [c:UInt32, a:UInt32]\
+        \n          Filter: __row_number <= UInt64(1) [a:UInt32, b:UInt32, c:UInt32, __row_number:UInt64;N]\
+        \n            WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [sq.a] ORDER BY [sq.b ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS __row_number]] [a:UInt32, b:UInt32, c:UInt32, __row_number:UInt64;N]\
+        \n              TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateWhereIn::new()),
+            &plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    /// Test for correlated IN subquery grouping its rows
+    #[test]
+    fn in_subquery_with_group_by() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(test_table_scan_with_name("sq")?)
+                .filter(col("sq.a").eq(col("test.a")))?
+                .aggregate(vec![col("sq.b")], vec![max(col("sq.c"))])?
+                .project(vec![Expr::Column(Column::from_name("MAX(sq.c)"))])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("test")?)
+            .filter(in_subquery(col("c"), sq))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftSemi Join:  Filter: test.c = __correlated_sq_1.MAX(sq.c) AND __correlated_sq_1.a = test.a [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [MAX(sq.c):UInt32;N, a:UInt32]\
+        \n      Projection: MAX(sq.c) AS MAX(sq.c), sq.a [MAX(sq.c):UInt32;N, a:UInt32]\
+        \n        Aggregate: groupBy=[[sq.b, sq.a]], aggr=[[MAX(sq.c)]] [b:UInt32, a:UInt32, MAX(sq.c):UInt32;N]\
+        \n          TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateWhereIn::new()),
+            &plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    /// Test for correlated IN subquery of a window function
+    #[test]
+    fn in_subquery_with_window() -> Result<()> {
+        let row_number = Expr::WindowFunction(WindowFunction::new(
+            window_function::WindowFunction::BuiltInWindowFunction(
+                BuiltInWindowFunction::RowNumber,
+            ),
+            vec![],
+            vec![],
+            vec![col("sq.b").sort(true, false)],
+            WindowFrame::new(true),
+        ));
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(test_table_scan_with_name("sq")?)
+                .filter(col("sq.a").eq(col("test.a")))?
+                .window(vec![row_number.alias("rn")])?
+                .project(vec![col("rn")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("test")?)
+            .filter(in_subquery(col("c"), sq))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        // the rows are numbered for each outer row
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftSemi Join:  Filter: test.c = __correlated_sq_1.rn AND __correlated_sq_1.a = test.a [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [rn:UInt64;N, a:UInt32]\
+        \n      Projection: rn AS rn, sq.a [rn:UInt64;N, a:UInt32]\
+        \n        WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [sq.a] ORDER BY [sq.b ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS rn]] [a:UInt32, b:UInt32, c:UInt32, rn:UInt64;N]\
+        \n          TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateWhereIn::new()),
+            &plan,
+            expected,
+        );
+        Ok(())
+    }
+
     /// Test for single IN subquery filter
     #[test]
     fn in_subquery_simple() -> Result<()> {
@@ -0,0 +1,262 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Optimizer rule to inline the common table expressions whose results are
+//! not worth sharing between their references
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::logical_plan::{LogicalPlan, PlanVisitor, SharedCte};
+use datafusion_expr::utils::from_plan;
+use std::collections::{HashMap, HashSet};
+
+/// Optimization rule that replaces a [SharedCte] by the plan of the common
+/// table expression, which is then executed for each of its references, when
+///
+/// * it is the only reference to the common table expression,
+/// * the common table expression only scans, filters and projects tables: it
+///   is cheaper to scan them again than to buffer the results, and the
+///   predicates and projections of each reference can then be pushed down
+///   into the scans,
+/// * or the `datafusion.optimizer.enable_cte_materialization` option is false.
+///
+/// The other common table expressions remain shared, so that the physical
+/// planner computes them once for all their references.
+#[derive(Default)]
+pub struct InlineCte {}
+
+impl InlineCte {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for InlineCte {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        let mut visitor = CteReferenceVisitor::default();
+        plan.accept(&mut visitor)?;
+
+        let enabled = config.options().optimizer.enable_cte_materialization;
+        let inlined = visitor
+            .references
+            .into_iter()
+            .filter(|(_, (count, is_scan))| !enabled || *count == 1 || *is_scan)
+            .map(|(id, _)| id)
+            .collect::<HashSet<_>>();
+        if inlined.is_empty() {
+            return Ok(None);
+        }
+        inline_ctes(plan, &inlined).map(Some)
+    }
+
+    fn name(&self) -> &str {
+        "inline_cte"
+    }
+}
+
+/// Counts the references to each common table expression of a plan,
+/// including the references in subqueries
+#[derive(Default)]
+struct CteReferenceVisitor {
+    /// The number of references to each common table expression, and whether
+    /// it only scans, filters and projects tables
+    references: HashMap<usize, (usize, bool)>,
+}
+
+impl PlanVisitor for CteReferenceVisitor {
+    type Error = DataFusionError;
+
+    fn pre_visit(&mut self, plan: &LogicalPlan) -> Result<bool> {
+        if let LogicalPlan::SharedCte(SharedCte { id, input, .. }) = plan {
+            match self.references.get_mut(id) {
+                Some((count, _)) => *count += 1,
+                None => {
+                    let is_scan = is_scan(input)?;
+                    self.references.insert(*id, (1, is_scan));
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Returns true if the plan only scans, filters and projects tables, without
+/// subqueries
+fn is_scan(plan: &LogicalPlan) -> Result<bool> {
+    struct ScanVisitor {
+        is_scan: bool,
+    }
+
+    impl PlanVisitor for ScanVisitor {
+        type Error = DataFusionError;
+
+        fn pre_visit(&mut self, plan: &LogicalPlan) -> Result<bool> {
+            // the subqueries of a plan are visited before its inputs, so a
+            // plan that is not a scan stops the visit of the next ones
+            self.is_scan = self.is_scan
+                && matches!(
+                    plan,
+                    LogicalPlan::TableScan(_)
+                        | LogicalPlan::Projection(_)
+                        | LogicalPlan::Filter(_)
+                        | LogicalPlan::SubqueryAlias(_)
+                        | LogicalPlan::EmptyRelation(_)
+                        | LogicalPlan::Values(_)
+                );
+            Ok(self.is_scan)
+        }
+    }
+
+    let mut visitor = ScanVisitor { is_scan: true };
+    plan.accept(&mut visitor)?;
+    Ok(visitor.is_scan)
+}
+
+/// Replaces the references to the `inlined` common table expressions by their
+/// plans
+fn inline_ctes(plan: &LogicalPlan, inlined: &HashSet<usize>) -> Result<LogicalPlan> {
+    match plan {
+        LogicalPlan::SharedCte(SharedCte { id, input, .. }) if inlined.contains(id) => {
+            inline_ctes(input, inlined)
+        }
+        _ => {
+            let new_inputs = plan
+                .inputs()
+                .into_iter()
+                .map(|input| inline_ctes(input, inlined))
+                .collect::<Result<Vec<_>>>()?;
+            from_plan(plan, &plan.expressions(), &new_inputs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use crate::OptimizerContext;
+    use datafusion_expr::{col, count, lit, LogicalPlanBuilder};
+    use std::sync::Arc;
+
+    fn assert_optimized_plan_equal(
+        plan: &LogicalPlan,
+        config: &OptimizerContext,
+        expected: &str,
+    ) -> Result<()> {
+        let optimized_plan = InlineCte::new()
+            .try_optimize(plan, config)?
+            .unwrap_or_else(|| plan.clone());
+        let formatted_plan = format!("{optimized_plan:?}");
+        assert_eq!(formatted_plan, expected);
+        Ok(())
+    }
+
+    /// A common table expression counting the rows of `test` by `a`
+    fn aggregate_cte() -> Result<LogicalPlan> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![count(col("b")).alias("n")])?
+            .alias("cte")?
+            .build()?;
+        Ok(LogicalPlan::SharedCte(SharedCte::new(
+            "cte",
+            Arc::new(plan),
+        )))
+    }
+
+    /// Joins two references to the common table expression
+    fn self_join(cte: LogicalPlan) -> Result<LogicalPlan> {
+        let right = LogicalPlanBuilder::from(cte.clone()).alias("b")?.build()?;
+        LogicalPlanBuilder::from(cte)
+            .alias("a")?
+            .cross_join(right)?
+            .build()
+    }
+
+    #[test]
+    fn share_cte_referenced_twice() -> Result<()> {
+        let plan = self_join(aggregate_cte()?)?;
+        let expected = "CrossJoin:\
+        \n  SubqueryAlias: a\
+        \n    SharedCte: cte\
+        \n      SubqueryAlias: cte\
+        \n        Aggregate: groupBy=[[test.a]], aggr=[[COUNT(test.b) AS n]]\
+        \n          TableScan: test\
+        \n  SubqueryAlias: b\
+        \n    SharedCte: cte\
+        \n      SubqueryAlias: cte\
+        \n        Aggregate: groupBy=[[test.a]], aggr=[[COUNT(test.b) AS n]]\
+        \n          TableScan: test";
+        assert_optimized_plan_equal(&plan, &OptimizerContext::new(), expected)
+    }
+
+    #[test]
+    fn inline_cte_referenced_once() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(aggregate_cte()?)
+            .filter(col("n").gt(lit(1i64)))?
+            .build()?;
+        let expected = "Filter: cte.n > Int64(1)\
+        \n  SubqueryAlias: cte\
+        \n    Aggregate: groupBy=[[test.a]], aggr=[[COUNT(test.b) AS n]]\
+        \n      TableScan: test";
+        assert_optimized_plan_equal(&plan, &OptimizerContext::new(), expected)
+    }
+
+    #[test]
+    fn inline_scan_cte() -> Result<()> {
+        let scan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .project(vec![col("a"), col("b")])?
+            .alias("cte")?
+            .build()?;
+        let plan = self_join(LogicalPlan::SharedCte(SharedCte::new(
+            "cte",
+            Arc::new(scan),
+        )))?;
+        let expected = "CrossJoin:\
+        \n  SubqueryAlias: a\
+        \n    SubqueryAlias: cte\
+        \n      Projection: test.a, test.b\
+        \n        Filter: test.a > UInt32(1)\
+        \n          TableScan: test\
+        \n  SubqueryAlias: b\
+        \n    SubqueryAlias: cte\
+        \n      Projection: test.a, test.b\
+        \n        Filter: test.a > UInt32(1)\
+        \n          TableScan: test";
+        assert_optimized_plan_equal(&plan, &OptimizerContext::new(), expected)
+    }
+
+    #[test]
+    fn inline_cte_without_materialization() -> Result<()> {
+        let plan = self_join(aggregate_cte()?)?;
+        let config = OptimizerContext::new().with_cte_materialization(false);
+        let expected = "CrossJoin:\
+        \n  SubqueryAlias: a\
+        \n    SubqueryAlias: cte\
+        \n      Aggregate: groupBy=[[test.a]], aggr=[[COUNT(test.b) AS n]]\
+        \n        TableScan: test\
+        \n  SubqueryAlias: b\
+        \n    SubqueryAlias: cte\
+        \n      Aggregate: groupBy=[[test.a]], aggr=[[COUNT(test.b) AS n]]\
+        \n        TableScan: test";
+        assert_optimized_plan_equal(&plan, &config, expected)
+    }
+}
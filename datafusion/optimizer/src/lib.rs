@@ -16,6 +16,7 @@
 // under the License.
 
 pub mod alias;
+pub mod analyzer;
 pub mod common_subexpr_eliminate;
 pub mod decorrelate_where_exists;
 pub mod decorrelate_where_in;
@@ -25,12 +26,16 @@ pub mod eliminate_limit;
 pub mod eliminate_outer_join;
 pub mod extract_equijoin_predicate;
 pub mod filter_null_join_keys;
+pub mod inline_cte;
 pub mod inline_table_scan;
+pub mod mask_columns;
 pub mod optimizer;
+pub mod policy;
 pub mod propagate_empty_relation;
 pub mod push_down_filter;
 pub mod push_down_limit;
 pub mod push_down_projection;
+pub mod push_down_sort;
 pub mod scalar_subquery_to_join;
 pub mod simplify_expressions;
 pub mod single_distinct_to_groupby;
@@ -42,5 +47,6 @@ pub mod rewrite_disjunctive_predicate;
 pub mod test;
 pub mod unwrap_cast_in_comparison;
 
-pub use optimizer::{OptimizerConfig, OptimizerContext, OptimizerRule};
+pub use analyzer::AnalyzerRule;
+pub use optimizer::{OptimizerConfig, OptimizerContext, OptimizerRule, RuleOutcome};
 pub use utils::optimize_children;
@@ -0,0 +1,138 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Analyzer rule applying the column masks declared by table sources
+
+use std::collections::HashMap;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::Result;
+use datafusion_expr::expr_rewriter::normalize_col;
+use datafusion_expr::{Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder};
+
+use crate::analyzer::AnalyzerRule;
+use crate::utils::rewrite_table_scans;
+
+/// Replaces the columns of every table scan for which the
+/// [`TableSource`](datafusion_expr::TableSource) declares a mask by the
+/// masking expression, so that none of the expressions of the query see
+/// the original values.
+#[derive(Default)]
+pub struct MaskColumns {}
+
+impl MaskColumns {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl AnalyzerRule for MaskColumns {
+    fn analyze(&self, plan: &LogicalPlan, _: &ConfigOptions) -> Result<LogicalPlan> {
+        rewrite_table_scans(plan, &|scan, input| {
+            mask_columns(input, &scan.source.column_masks(), &scan.table_name)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "mask_columns"
+    }
+}
+
+/// Projects the columns of `input`, the plan of table `table_name`,
+/// replacing the ones named in `masks` by their masking expression.
+///
+/// Masks may refer to any column of `input` and are cast to the type of the
+/// column they replace. The output columns are qualified with `table_name`.
+pub(crate) fn mask_columns(
+    input: LogicalPlan,
+    masks: &HashMap<String, Expr>,
+    table_name: &str,
+) -> Result<LogicalPlan> {
+    if masks.is_empty() {
+        return Ok(input);
+    }
+
+    let schema = input.schema().clone();
+    let exprs = schema
+        .fields()
+        .iter()
+        .map(|field| match masks.get(field.name()) {
+            Some(mask) => Ok(normalize_col(mask.clone(), &input)?
+                .cast_to(field.data_type(), schema.as_ref())?
+                .alias(field.name())),
+            None => Ok(Expr::Column(field.qualified_column())),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    LogicalPlanBuilder::from(input)
+        .project(exprs)?
+        .alias(table_name)?
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::test_table_scan;
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion_expr::{col, lit, TableSource};
+    use std::any::Any;
+    use std::sync::Arc;
+
+    /// A table whose column `b` is masked by `a + b`
+    struct MaskedSource {}
+
+    impl TableSource for MaskedSource {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            Arc::new(Schema::new(vec![
+                Field::new("a", DataType::UInt32, false),
+                Field::new("b", DataType::UInt32, false),
+            ]))
+        }
+
+        fn column_masks(&self) -> HashMap<String, Expr> {
+            HashMap::from([("b".to_string(), col("a") + col("b"))])
+        }
+    }
+
+    #[test]
+    fn mask_declared_columns() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan("t", Arc::new(MaskedSource {}), None)?
+            .filter(col("b").gt(lit(1u32)))?
+            .build()?;
+        let plan = MaskColumns::new().analyze(&plan, &ConfigOptions::default())?;
+        let expected = "Filter: t.b > UInt32(1)\
+        \n  SubqueryAlias: t\
+        \n    Projection: t.a, t.a + t.b AS b\
+        \n      TableScan: t";
+        assert_eq!(format!("{plan:?}"), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn unmasked_scan_is_unchanged() -> Result<()> {
+        let plan =
+            MaskColumns::new().analyze(&test_table_scan()?, &ConfigOptions::default())?;
+        assert_eq!(format!("{plan:?}"), "TableScan: test");
+        Ok(())
+    }
+}
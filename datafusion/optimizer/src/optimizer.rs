@@ -26,11 +26,13 @@ use crate::eliminate_limit::EliminateLimit;
 use crate::eliminate_outer_join::EliminateOuterJoin;
 use crate::extract_equijoin_predicate::ExtractEquijoinPredicate;
 use crate::filter_null_join_keys::FilterNullJoinKeys;
+use crate::inline_cte::InlineCte;
 use crate::inline_table_scan::InlineTableScan;
 use crate::propagate_empty_relation::PropagateEmptyRelation;
 use crate::push_down_filter::PushDownFilter;
 use crate::push_down_limit::PushDownLimit;
 use crate::push_down_projection::PushDownProjection;
+use crate::push_down_sort::PushDownSort;
 use crate::rewrite_disjunctive_predicate::RewriteDisjunctivePredicate;
 use crate::scalar_subquery_to_join::ScalarSubqueryToJoin;
 use crate::simplify_expressions::SimplifyExpressions;
@@ -40,10 +42,13 @@ use crate::unwrap_cast_in_comparison::UnwrapCastInComparison;
 use chrono::{DateTime, Utc};
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::logical_plan::LogicalPlan;
+use datafusion_expr::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
+use datafusion_expr::logical_plan::{LogicalPlan, PlanVisitor};
+use datafusion_expr::Expr;
 use log::{debug, trace, warn};
+use std::collections::HashSet;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// `OptimizerRule` transforms one ['LogicalPlan'] into another which
 /// computes the same results, but in a potentially more efficient
@@ -67,6 +72,25 @@ pub trait OptimizerRule {
     fn apply_order(&self) -> Option<ApplyOrder> {
         None
     }
+
+    /// Whether the optimizer may skip this rule when the plan exceeds its
+    /// budgets, see [`RuleOutcome::Skipped`]. Rules whose rewrites are
+    /// required to execute the plan return false.
+    fn skippable(&self) -> bool {
+        true
+    }
+}
+
+/// The outcome of a call of an optimizer rule, passed to the observer of
+/// [`Optimizer::optimize_with_outcomes`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleOutcome {
+    /// The rule was applied, taking the given time
+    Applied(Duration),
+    /// The rule was skipped for the given reason, because it exceeded the
+    /// `max_rule_duration_ms` or `max_rule_plan_size` budgets of the
+    /// optimizer
+    Skipped(String),
 }
 
 /// Options to control the DataFusion Optimizer.
@@ -129,6 +153,34 @@ impl OptimizerContext {
         self.options.optimizer.max_passes = v as usize;
         self
     }
+
+    /// Specify whether the common table expressions referenced several times
+    /// are computed once for all their references
+    pub fn with_cte_materialization(mut self, enabled: bool) -> Self {
+        self.options.optimizer.enable_cte_materialization = enabled;
+        self
+    }
+
+    /// Specify the time in milliseconds after which a call of a rule makes
+    /// the optimizer skip the rule in the next passes
+    pub fn with_max_rule_duration_ms(mut self, v: Option<usize>) -> Self {
+        self.options.optimizer.max_rule_duration_ms = v;
+        self
+    }
+
+    /// Specify the number of expressions of a plan above which the optimizer
+    /// skips the rules
+    pub fn with_max_rule_plan_size(mut self, v: Option<usize>) -> Self {
+        self.options.optimizer.max_rule_plan_size = v;
+        self
+    }
+
+    /// Specify whether to check the invariants of the plan produced by each
+    /// rule
+    pub fn with_check_plan_invariants(mut self, enabled: bool) -> Self {
+        self.options.optimizer.check_plan_invariants = enabled;
+        self
+    }
 }
 
 impl Default for OptimizerContext {
@@ -204,6 +256,9 @@ impl Optimizer {
     pub fn new() -> Self {
         let rules: Vec<Arc<dyn OptimizerRule + Sync + Send>> = vec![
             Arc::new(InlineTableScan::new()),
+            // inline the common table expressions before any predicate or
+            // projection is pushed down into them
+            Arc::new(InlineCte::new()),
             Arc::new(TypeCoercion::new()),
             Arc::new(SimplifyExpressions::new()),
             Arc::new(UnwrapCastInComparison::new()),
@@ -233,6 +288,7 @@ impl Optimizer {
             Arc::new(UnwrapCastInComparison::new()),
             Arc::new(CommonSubexprEliminate::new()),
             Arc::new(PushDownProjection::new()),
+            Arc::new(PushDownSort::new()),
         ];
 
         Self::with_rules(rules)
@@ -253,17 +309,77 @@ impl Optimizer {
     ) -> Result<LogicalPlan>
     where
         F: FnMut(&LogicalPlan, &dyn OptimizerRule),
+    {
+        self.optimize_with_outcomes(plan, config, |plan, rule, outcome| {
+            if let RuleOutcome::Applied(_) = outcome {
+                observer(plan, rule)
+            }
+        })
+    }
+
+    /// Optimizes the logical plan like [`Self::optimize`], also invoking the
+    /// observer function for the skipped rules, with the outcome of each call
+    pub fn optimize_with_outcomes<F>(
+        &self,
+        plan: &LogicalPlan,
+        config: &dyn OptimizerConfig,
+        mut observer: F,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn OptimizerRule, &RuleOutcome),
     {
         let options = config.options();
+        let max_rule_duration = options
+            .optimizer
+            .max_rule_duration_ms
+            .map(|ms| Duration::from_millis(ms as u64));
         let start_time = Instant::now();
         let mut plan_str = format!("{}", plan.display_indent());
         let mut new_plan = plan.clone();
+        // the indexes of the rules that exceeded `max_rule_duration`
+        let mut slow_rules = HashSet::new();
         let mut i = 0;
         while i < options.optimizer.max_passes {
             log_plan(&format!("Optimizer input (pass {i})"), &new_plan);
 
-            for rule in &self.rules {
+            for (rule_index, rule) in self.rules.iter().enumerate() {
+                if rule.skippable() {
+                    let reason = if slow_rules.contains(&rule_index) {
+                        Some(format!(
+                            "a previous call took longer than {} ms",
+                            options.optimizer.max_rule_duration_ms.unwrap_or_default()
+                        ))
+                    } else {
+                        options.optimizer.max_rule_plan_size.and_then(|max_size| {
+                            let size = plan_size(&new_plan);
+                            (size > max_size).then(|| {
+                                format!(
+                                    "the plan has {size} expressions, more than {max_size}"
+                                )
+                            })
+                        })
+                    };
+                    if let Some(reason) = reason {
+                        warn!("Skipping optimizer rule '{}': {}", rule.name(), reason);
+                        observer(&new_plan, rule.as_ref(), &RuleOutcome::Skipped(reason));
+                        continue;
+                    }
+                }
+
+                let rule_start = Instant::now();
                 let result = self.optimize_recursively(rule, &new_plan, config);
+                let elapsed = rule_start.elapsed();
+                if max_rule_duration.map_or(false, |max| elapsed > max)
+                    && rule.skippable()
+                {
+                    warn!(
+                        "Optimizer rule '{}' took {:?}, it is skipped in the next passes",
+                        rule.name(),
+                        elapsed
+                    );
+                    slow_rules.insert(rule_index);
+                }
+                let outcome = RuleOutcome::Applied(elapsed);
 
                 match result {
                     Ok(Some(plan)) => {
@@ -275,12 +391,21 @@ impl Optimizer {
                                 plan.schema()
                             )));
                         }
+                        if options.optimizer.check_plan_invariants {
+                            plan.check_invariants().map_err(|e| {
+                                DataFusionError::Internal(format!(
+                                    "Optimizer rule '{}' produced an invalid plan: {}",
+                                    rule.name(),
+                                    e
+                                ))
+                            })?;
+                        }
                         new_plan = plan;
-                        observer(&new_plan, rule.as_ref());
+                        observer(&new_plan, rule.as_ref(), &outcome);
                         log_plan(rule.name(), &new_plan);
                     }
                     Ok(None) => {
-                        observer(&new_plan, rule.as_ref());
+                        observer(&new_plan, rule.as_ref(), &outcome);
                         debug!(
                             "Plan unchanged by optimizer rule '{}' (pass {})",
                             rule.name(),
@@ -399,6 +524,36 @@ impl Optimizer {
     }
 }
 
+/// Returns the number of expressions of the plan, including the nested
+/// expressions and the expressions of its inputs and subqueries
+fn plan_size(plan: &LogicalPlan) -> usize {
+    struct ExprCounter(usize);
+
+    impl ExpressionVisitor for ExprCounter {
+        fn pre_visit(self, _expr: &Expr) -> Result<Recursion<Self>> {
+            Ok(Recursion::Continue(Self(self.0 + 1)))
+        }
+    }
+
+    struct PlanSizeVisitor(usize);
+
+    impl PlanVisitor for PlanSizeVisitor {
+        type Error = DataFusionError;
+
+        fn pre_visit(&mut self, plan: &LogicalPlan) -> Result<bool> {
+            for expr in plan.expressions() {
+                self.0 += expr.accept(ExprCounter(0))?.0;
+            }
+            Ok(true)
+        }
+    }
+
+    let mut visitor = PlanSizeVisitor(0);
+    // counting the expressions can't fail
+    let _ = plan.accept(&mut visitor);
+    visitor.0
+}
+
 /// Log the plan in debug/tracing mode after some part of the optimizer runs
 fn log_plan(description: &str, plan: &LogicalPlan) {
     debug!("{description}:\n{}\n", plan.display_indent());
@@ -409,11 +564,12 @@ fn log_plan(description: &str, plan: &LogicalPlan) {
 mod tests {
     use crate::optimizer::Optimizer;
     use crate::test::test_table_scan;
-    use crate::{OptimizerConfig, OptimizerContext, OptimizerRule};
+    use crate::{OptimizerConfig, OptimizerContext, OptimizerRule, RuleOutcome};
     use datafusion_common::{DFField, DFSchema, DFSchemaRef, DataFusionError, Result};
     use datafusion_expr::logical_plan::EmptyRelation;
-    use datafusion_expr::{col, LogicalPlan, LogicalPlanBuilder, Projection};
+    use datafusion_expr::{col, Expr, LogicalPlan, LogicalPlanBuilder, Projection};
     use std::sync::Arc;
+    use std::time::Duration;
 
     #[test]
     fn skip_failing_rule() {
@@ -490,6 +646,83 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn skip_rule_for_large_plan() -> Result<()> {
+        let opt = Optimizer::with_rules(vec![Arc::new(BadRule {})]);
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(vec![col("a"), col("b"), col("c")])?
+            .build()?;
+
+        // the plan has 3 expressions, so the failing rule is skipped
+        let config = OptimizerContext::new()
+            .with_skip_failing_rules(false)
+            .with_max_rule_plan_size(Some(2));
+        let mut outcomes = vec![];
+        opt.optimize_with_outcomes(&plan, &config, |_, rule, outcome| {
+            outcomes.push((rule.name().to_string(), outcome.clone()))
+        })?;
+        assert_eq!(
+            outcomes,
+            vec![(
+                "bad rule".to_string(),
+                RuleOutcome::Skipped(
+                    "the plan has 3 expressions, more than 2".to_string()
+                )
+            )]
+        );
+
+        let config = OptimizerContext::new()
+            .with_skip_failing_rules(false)
+            .with_max_rule_plan_size(Some(3));
+        assert!(opt.optimize(&plan, &config, &observe).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn skip_slow_rule() -> Result<()> {
+        let opt = Optimizer::with_rules(vec![Arc::new(SlowRule {})]);
+        let config = OptimizerContext::new().with_max_rule_duration_ms(Some(0));
+        let plan = test_table_scan()?;
+
+        // the rule runs in the first pass only
+        let mut outcomes = vec![];
+        let optimized = opt.optimize_with_outcomes(&plan, &config, |_, _, outcome| {
+            outcomes.push(outcome.clone())
+        })?;
+        assert!(matches!(outcomes[0], RuleOutcome::Applied(_)));
+        assert_eq!(
+            outcomes[1..],
+            [RuleOutcome::Skipped(
+                "a previous call took longer than 0 ms".to_string()
+            )]
+        );
+        assert!(matches!(optimized, LogicalPlan::Limit(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn check_plan_invariants() -> Result<()> {
+        let opt = Optimizer::with_rules(vec![Arc::new(DropInputColumnsRule {})]);
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(vec![col("a"), col("b")])?
+            .build()?;
+
+        let config = OptimizerContext::new().with_check_plan_invariants(false);
+        opt.optimize(&plan, &config, &observe)?;
+
+        let config = OptimizerContext::new().with_check_plan_invariants(true);
+        let err = opt.optimize(&plan, &config, &observe).unwrap_err();
+        assert!(
+            err.to_string().starts_with(
+                "Internal error: Optimizer rule 'drop input columns rule' produced an \
+                 invalid plan: Error during planning: Invalid plan node Projection: \
+                 test.a, test.b: the column test.b of test.b is not a field of its input"
+            ),
+            "{err}"
+        );
+        Ok(())
+    }
+
     fn add_metadata_to_fields(schema: &DFSchema) -> DFSchemaRef {
         let new_fields = schema
             .fields()
@@ -530,6 +763,62 @@ mod tests {
         }
     }
 
+    /// Sleeps, then limits the plan to one row
+    struct SlowRule {}
+
+    impl OptimizerRule for SlowRule {
+        fn try_optimize(
+            &self,
+            plan: &LogicalPlan,
+            _: &dyn OptimizerConfig,
+        ) -> Result<Option<LogicalPlan>> {
+            std::thread::sleep(Duration::from_millis(2));
+            Ok(Some(
+                LogicalPlanBuilder::from(plan.clone())
+                    .limit(0, Some(1))?
+                    .build()?,
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "slow rule"
+        }
+    }
+
+    /// Removes all the columns but the first one from the input of a
+    /// projection, keeping its schema
+    struct DropInputColumnsRule {}
+
+    impl OptimizerRule for DropInputColumnsRule {
+        fn try_optimize(
+            &self,
+            plan: &LogicalPlan,
+            _: &dyn OptimizerConfig,
+        ) -> Result<Option<LogicalPlan>> {
+            match plan {
+                LogicalPlan::Projection(projection) => {
+                    let first = projection.input.schema().field(0).qualified_column();
+                    let input =
+                        LogicalPlanBuilder::from(projection.input.as_ref().clone())
+                            .project(vec![Expr::Column(first)])?
+                            .build()?;
+                    Ok(Some(LogicalPlan::Projection(
+                        Projection::try_new_with_schema(
+                            projection.expr.clone(),
+                            Arc::new(input),
+                            projection.schema.clone(),
+                        )?,
+                    )))
+                }
+                _ => Ok(None),
+            }
+        }
+
+        fn name(&self) -> &str {
+            "drop input columns rule"
+        }
+    }
+
     /// Replaces whatever plan with a single table scan
     struct GetTableScanRule {}
 
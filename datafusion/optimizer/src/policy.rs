@@ -0,0 +1,206 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Analyzer rule applying row and column access policies to table scans
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::{Result, TableReference};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, TableScan};
+
+use crate::analyzer::AnalyzerRule;
+use crate::mask_columns::mask_columns;
+use crate::utils::rewrite_table_scans;
+
+/// The access policy of a user on a single table
+#[derive(Debug, Clone, Default)]
+pub struct TablePolicy {
+    /// Only the rows for which this predicate is true are visible
+    pub predicate: Option<Expr>,
+    /// Expressions replacing the values of the named columns, for example
+    /// to hide all but the last digits of a phone number. Masks may refer
+    /// to any column of the table and are cast to the type of the column
+    /// they replace. They see the values after the masks of the table
+    /// source, if any, are applied.
+    pub column_masks: HashMap<String, Expr>,
+}
+
+impl TablePolicy {
+    /// Create a policy which only lets the rows matching `predicate` through
+    pub fn with_predicate(predicate: Expr) -> Self {
+        Self {
+            predicate: Some(predicate),
+            column_masks: HashMap::new(),
+        }
+    }
+
+    /// Replace the values of `column` with `mask`
+    pub fn with_column_mask(mut self, column: impl Into<String>, mask: Expr) -> Self {
+        self.column_masks.insert(column.into(), mask);
+        self
+    }
+}
+
+/// Provides the [`TablePolicy`]s of the users of a session.
+///
+/// Tables (and views) without a policy are accessed unrestricted, so a
+/// provider that denies access altogether should return an error.
+pub trait PolicyProvider: Send + Sync {
+    /// Return the policy of `user` on `table`, if any
+    fn table_policy(
+        &self,
+        table: TableReference,
+        user: &str,
+    ) -> Result<Option<TablePolicy>>;
+}
+
+/// Applies the [`TablePolicy`]s of a [`PolicyProvider`] to every table scan
+/// of a plan, including those in subqueries and in the definition of views.
+///
+/// The predicate of a policy becomes a filter directly above the scan, and
+/// masked columns are replaced by a projection above that filter, so that
+/// none of the expressions of the query see the original values.
+pub struct ApplyPolicies {
+    provider: Arc<dyn PolicyProvider>,
+    user: String,
+}
+
+impl ApplyPolicies {
+    /// Create a rule applying the policies of `user`
+    pub fn new(provider: Arc<dyn PolicyProvider>, user: impl Into<String>) -> Self {
+        Self {
+            provider,
+            user: user.into(),
+        }
+    }
+
+    fn secure_scan(&self, scan: &TableScan, input: LogicalPlan) -> Result<LogicalPlan> {
+        let policy = match self
+            .provider
+            .table_policy(scan.table_name.as_str().into(), &self.user)?
+        {
+            Some(policy) => policy,
+            None => return Ok(input),
+        };
+
+        let mut builder = LogicalPlanBuilder::from(input);
+        if let Some(predicate) = policy.predicate {
+            builder = builder.filter(predicate)?;
+        }
+        mask_columns(builder.build()?, &policy.column_masks, &scan.table_name)
+    }
+}
+
+impl AnalyzerRule for ApplyPolicies {
+    fn analyze(&self, plan: &LogicalPlan, _: &ConfigOptions) -> Result<LogicalPlan> {
+        rewrite_table_scans(plan, &|scan, input| self.secure_scan(scan, input))
+    }
+
+    fn name(&self) -> &str {
+        "apply_policies"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{test_table_scan, test_table_scan_with_name};
+    use arrow::datatypes::DataType;
+    use datafusion_common::DataFusionError;
+    use datafusion_expr::{col, exists, lit};
+
+    /// Restricts table `test` to the rows of tenant `user`, masks column `c`
+    /// and denies access to table `secret`
+    struct TenantPolicies {}
+
+    impl PolicyProvider for TenantPolicies {
+        fn table_policy(
+            &self,
+            table: TableReference,
+            user: &str,
+        ) -> Result<Option<TablePolicy>> {
+            match table.table() {
+                "test" => {
+                    let tenant = if user == "alice" { 1 } else { 2 };
+                    Ok(Some(
+                        TablePolicy::with_predicate(col("a").eq(lit(tenant as u32)))
+                            .with_column_mask("c", lit(0u32)),
+                    ))
+                }
+                "secret" => Err(DataFusionError::Plan(format!(
+                    "{user} may not read table secret"
+                ))),
+                _ => Ok(None),
+            }
+        }
+    }
+
+    fn analyze(plan: &LogicalPlan, user: &str) -> Result<LogicalPlan> {
+        ApplyPolicies::new(Arc::new(TenantPolicies {}), user)
+            .analyze(plan, &ConfigOptions::default())
+    }
+
+    #[test]
+    fn filter_and_mask_scan() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(vec![col("b"), col("c")])?
+            .build()?;
+
+        let expected = "Projection: test.b, test.c\
+        \n  SubqueryAlias: test\
+        \n    Projection: test.a, test.b, UInt32(0) AS c\
+        \n      Filter: test.a = UInt32(1)\
+        \n        TableScan: test";
+        assert_eq!(format!("{:?}", analyze(&plan, "alice")?), expected);
+
+        let plan = analyze(&plan, "bob")?;
+        assert!(format!("{plan:?}").contains("Filter: test.a = UInt32(2)"));
+        assert_eq!(
+            plan.schema().field_with_name(None, "c")?.data_type(),
+            &DataType::UInt32
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn subquery_is_secured() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(vec![col("a")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("other")?)
+            .filter(exists(Arc::new(subquery)))?
+            .build()?;
+
+        let plan = format!("{:?}", analyze(&plan, "alice")?);
+        assert!(plan.contains("Filter: test.a = UInt32(1)"));
+        assert!(plan.contains("TableScan: other"));
+        Ok(())
+    }
+
+    #[test]
+    fn denied_table() -> Result<()> {
+        let plan = test_table_scan_with_name("secret")?;
+        let err = analyze(&plan, "alice").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: alice may not read table secret"
+        );
+        Ok(())
+    }
+}
@@ -90,6 +90,8 @@ fn lr_is_preserved(plan: &LogicalPlan) -> Result<(bool, bool)> {
             // No columns from the left side of the join can be referenced in output
             // predicates for semi/anti joins, so whether we specify t/f doesn't matter.
             JoinType::RightSemi | JoinType::RightAnti => Ok((false, true)),
+            // Every left row is output once, whether it matched or not
+            JoinType::LeftMark => Ok((true, false)),
         },
         LogicalPlan::CrossJoin(_) => Ok((true, true)),
         _ => Err(DataFusionError::Internal(
@@ -108,6 +110,8 @@ fn on_lr_is_preserved(plan: &LogicalPlan) -> Result<(bool, bool)> {
             JoinType::Left => Ok((false, true)),
             JoinType::Right => Ok((true, false)),
             JoinType::Full => Ok((false, false)),
+            // Filtering the right side only removes rows that couldn't match
+            JoinType::LeftMark => Ok((false, true)),
             JoinType::LeftSemi
             | JoinType::LeftAnti
             | JoinType::RightSemi
@@ -322,18 +326,19 @@ fn push_down_all_join(
     }
 
     let mut keep_condition = vec![];
-    if !on_filter.is_empty() {
-        let (on_left_preserved, on_right_preserved) = on_lr_is_preserved(plan)?;
-        for on in on_filter {
-            if on_left_preserved && can_pushdown_join_predicate(&on, left.schema())? {
-                left_push.push(on)
-            } else if on_right_preserved
-                && can_pushdown_join_predicate(&on, right.schema())?
-            {
-                right_push.push(on)
-            } else {
-                keep_condition.push(on)
-            }
+    let (on_left_preserved, on_right_preserved) = if on_filter_empty {
+        (false, false)
+    } else {
+        on_lr_is_preserved(plan)?
+    };
+    for on in on_filter {
+        if on_left_preserved && can_pushdown_join_predicate(&on, left.schema())? {
+            left_push.push(on)
+        } else if on_right_preserved && can_pushdown_join_predicate(&on, right.schema())?
+        {
+            right_push.push(on)
+        } else {
+            keep_condition.push(on)
         }
     }
 
@@ -352,12 +357,12 @@ fn push_down_all_join(
     let on_or_to_left = extract_or_clauses_for_join(
         &keep_condition.iter().collect::<Vec<_>>(),
         left.schema(),
-        left_preserved,
+        on_left_preserved,
     );
     let on_or_to_right = extract_or_clauses_for_join(
         &keep_condition.iter().collect::<Vec<_>>(),
         right.schema(),
-        right_preserved,
+        on_right_preserved,
     );
 
     left_push.extend(or_to_left);
@@ -707,24 +712,57 @@ impl OptimizerRule for PushDownFilter {
                 let mut new_scan_filters = scan.filters.clone();
                 let mut new_predicate = vec![];
 
-                let filter_predicates =
-                    utils::split_conjunction_owned(filter.predicate.clone());
+                let filter_predicates = split_conjunction(&filter.predicate);
+                let supported =
+                    scan.source.supports_filters_pushdown(&filter_predicates)?;
+                if supported.len() != filter_predicates.len() {
+                    return Err(DataFusionError::Internal(format!(
+                        "Expected {} filter pushdown results from table source, got {}",
+                        filter_predicates.len(),
+                        supported.len()
+                    )));
+                }
 
-                for filter_expr in &filter_predicates {
-                    let (preserve_filter_node, add_to_provider) =
-                        match scan.source.supports_filter_pushdown(filter_expr)? {
-                            TableProviderFilterPushDown::Unsupported => (true, false),
-                            TableProviderFilterPushDown::Inexact => (true, true),
-                            TableProviderFilterPushDown::Exact => (false, true),
-                        };
-                    if preserve_filter_node {
-                        new_predicate.push(filter_expr.clone());
-                    }
-                    if add_to_provider {
-                        // avoid reduplicated filter expr.
-                        if new_scan_filters.contains(filter_expr) {
-                            continue;
+                // the residual predicates of the inexactly pushed down filters
+                let inexact_predicates: Vec<&Expr> = filter_predicates
+                    .iter()
+                    .zip(supported.iter())
+                    .filter(|(_, support)| {
+                        **support == TableProviderFilterPushDown::Inexact
+                    })
+                    .map(|(filter_expr, _)| *filter_expr)
+                    .collect();
+                let residuals = if inexact_predicates.is_empty() {
+                    vec![]
+                } else {
+                    scan.source.residual_filters(&inexact_predicates)?
+                };
+                if residuals.len() != inexact_predicates.len() {
+                    return Err(DataFusionError::Internal(format!(
+                        "Expected {} residual filters from table source, got {}",
+                        inexact_predicates.len(),
+                        residuals.len()
+                    )));
+                }
+                let mut residuals = residuals.into_iter();
+
+                for (filter_expr, support) in filter_predicates.into_iter().zip(supported)
+                {
+                    let add_to_provider = match support {
+                        TableProviderFilterPushDown::Unsupported => {
+                            new_predicate.push(filter_expr.clone());
+                            false
+                        }
+                        TableProviderFilterPushDown::Inexact => {
+                            let residual = residuals.next().flatten();
+                            new_predicate
+                                .push(residual.unwrap_or_else(|| filter_expr.clone()));
+                            true
                         }
+                        TableProviderFilterPushDown::Exact => true,
+                    };
+                    // avoid reduplicated filter expr.
+                    if add_to_provider && !new_scan_filters.contains(filter_expr) {
                         new_scan_filters.push(filter_expr.clone());
                     }
                 }
@@ -736,6 +774,7 @@ impl OptimizerRule for PushDownFilter {
                     table_name: scan.table_name.clone(),
                     filters: new_scan_filters,
                     fetch: scan.fetch,
+                    ordering: scan.ordering.clone(),
                 });
 
                 match conjunction(new_predicate) {
@@ -1939,6 +1978,7 @@ mod tests {
             projection: None,
             source: Arc::new(test_provider),
             fetch: None,
+            ordering: vec![],
         });
 
         LogicalPlanBuilder::from(table_scan)
@@ -2011,6 +2051,7 @@ mod tests {
             projection: Some(vec![0]),
             source: Arc::new(test_provider),
             fetch: None,
+            ordering: vec![],
         });
 
         let plan = LogicalPlanBuilder::from(table_scan)
@@ -2025,6 +2066,116 @@ mod tests {
         assert_optimized_plan_eq(&plan, expected)
     }
 
+    /// Handles filters on `a` exactly, and filters on `b` inexactly, with
+    /// `b IS NOT NULL` left to evaluate. If `all_or_nothing` is set, the
+    /// filters are only pushed down if all of them are supported.
+    struct ResidualProvider {
+        all_or_nothing: bool,
+    }
+
+    impl TableSource for ResidualProvider {
+        fn schema(&self) -> SchemaRef {
+            Arc::new(Schema::new(vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Int32, true),
+                Field::new("c", DataType::Int32, true),
+            ]))
+        }
+
+        fn supports_filters_pushdown(
+            &self,
+            filters: &[&Expr],
+        ) -> Result<Vec<TableProviderFilterPushDown>> {
+            let supported: Vec<_> = filters
+                .iter()
+                .map(|filter| {
+                    let columns = filter.to_columns()?;
+                    Ok(if columns.iter().all(|c| c.name == "a") {
+                        TableProviderFilterPushDown::Exact
+                    } else if columns.iter().all(|c| c.name == "b") {
+                        TableProviderFilterPushDown::Inexact
+                    } else {
+                        TableProviderFilterPushDown::Unsupported
+                    })
+                })
+                .collect::<Result<_>>()?;
+            if self.all_or_nothing
+                && supported.contains(&TableProviderFilterPushDown::Unsupported)
+            {
+                return Ok(vec![
+                    TableProviderFilterPushDown::Unsupported;
+                    filters.len()
+                ]);
+            }
+            Ok(supported)
+        }
+
+        fn residual_filters(&self, filters: &[&Expr]) -> Result<Vec<Option<Expr>>> {
+            Ok(filters
+                .iter()
+                .map(|_| Some(col("b").is_not_null()))
+                .collect())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn table_scan_with_residual_provider(all_or_nothing: bool) -> Result<LogicalPlan> {
+        let test_provider = ResidualProvider { all_or_nothing };
+        let table_scan = LogicalPlan::TableScan(TableScan {
+            table_name: "test".to_string(),
+            filters: vec![],
+            projected_schema: Arc::new(DFSchema::try_from(
+                (*test_provider.schema()).clone(),
+            )?),
+            projection: None,
+            source: Arc::new(test_provider),
+            fetch: None,
+            ordering: vec![],
+        });
+        Ok(table_scan)
+    }
+
+    #[test]
+    fn filter_with_table_provider_residual() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(table_scan_with_residual_provider(false)?)
+            .filter(
+                col("a")
+                    .eq(lit(1i64))
+                    .and(col("b").gt(lit(2i64)))
+                    .and(col("c").lt(col("a"))),
+            )?
+            .build()?;
+
+        // the filter on `a` is removed, the one on `b` is replaced by its residual
+        let expected = "\
+        Filter: b IS NOT NULL AND c < a\
+        \n  TableScan: test, full_filters=[a = Int64(1)], partial_filters=[b > Int64(2)]";
+        assert_optimized_plan_eq(&plan, expected)
+    }
+
+    #[test]
+    fn filter_with_table_provider_all_or_nothing() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(table_scan_with_residual_provider(true)?)
+            .filter(col("a").eq(lit(1i64)).and(col("c").lt(col("a"))))?
+            .build()?;
+
+        let expected = "\
+        Filter: a = Int64(1) AND c < a\
+        \n  TableScan: test";
+        assert_optimized_plan_eq(&plan, expected)?;
+
+        let plan = LogicalPlanBuilder::from(table_scan_with_residual_provider(true)?)
+            .filter(col("a").eq(lit(1i64)).and(col("a").lt(lit(5i64))))?
+            .build()?;
+
+        let expected = "\
+        TableScan: test, full_filters=[a = Int64(1), a < Int64(5)]";
+        assert_optimized_plan_eq(&plan, expected)
+    }
+
     #[test]
     fn test_filter_with_alias() -> Result<()> {
         // in table scan the true col name is 'test.a',
@@ -133,6 +133,7 @@ impl OptimizerRule for PushDownLimit {
                     filters: scan.filters.clone(),
                     fetch: scan.fetch.map(|x| std::cmp::min(x, limit)).or(Some(limit)),
                     projected_schema: scan.projected_schema.clone(),
+                    ordering: scan.ordering.clone(),
                 });
                 plan.with_new_inputs(&[new_input])?
             }
@@ -197,7 +198,9 @@ impl OptimizerRule for PushDownLimit {
                         // push right
                         push_down_join(join, None, Some(limit))
                     }
-                    JoinType::Left => push_down_join(join, Some(limit), None),
+                    JoinType::Left | JoinType::LeftMark => {
+                        push_down_join(join, Some(limit), None)
+                    }
                     JoinType::Right => push_down_join(join, None, Some(limit)),
                     _ => push_down_join(join, None, None),
                 };
@@ -28,8 +28,8 @@ use datafusion_expr::utils::grouping_set_to_exprlist;
 use datafusion_expr::{
     logical_plan::{
         builder::{build_join_schema, LogicalPlanBuilder},
-        Aggregate, Analyze, Join, LogicalPlan, Projection, SubqueryAlias, TableScan,
-        Union, Window,
+        Aggregate, Analyze, Join, JoinType, LogicalPlan, Projection, SubqueryAlias,
+        TableScan, Union, Window,
     },
     utils::{expr_to_columns, exprlist_to_columns, find_sort_exprs, from_plan},
     Expr,
@@ -165,6 +165,13 @@ fn optimize_plan(
                 expr_to_columns(expr, &mut new_required_columns)?;
             }
 
+            if *join_type == JoinType::LeftMark {
+                // the mark is qualified like the first column of the right side
+                if let Some(field) = right.schema().fields().first() {
+                    new_required_columns.insert(field.qualified_column());
+                }
+            }
+
             let optimized_left = Arc::new(optimize_plan(
                 _optimizer,
                 left,
@@ -392,6 +399,25 @@ fn optimize_plan(
             )?;
             from_plan(plan, &[], &[child])
         }
+        // at a shared common table expression, all columns are required so
+        // that all its references compute the same relation
+        LogicalPlan::SharedCte(cte) => {
+            let new_required_columns = cte
+                .input
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.qualified_column())
+                .collect();
+            let child = optimize_plan(
+                _optimizer,
+                cte.input.as_ref(),
+                &new_required_columns,
+                has_projection,
+                _config,
+            )?;
+            from_plan(plan, &[], &[child])
+        }
         // all other nodes: Add any additional columns used by
         // expressions in this node to the list of required columns
         LogicalPlan::Limit(_)
@@ -408,6 +434,10 @@ fn optimize_plan(
         | LogicalPlan::CreateCatalog(_)
         | LogicalPlan::DropTable(_)
         | LogicalPlan::DropView(_)
+        | LogicalPlan::AnalyzeTable(_)
+        | LogicalPlan::CreateFunction(_)
+        | LogicalPlan::DropFunction(_)
+        | LogicalPlan::Transaction(_)
         | LogicalPlan::SetVariable(_)
         | LogicalPlan::CrossJoin(_)
         | LogicalPlan::Extension { .. }
@@ -533,6 +563,7 @@ fn push_down_scan(
         projected_schema,
         filters: scan.filters.clone(),
         fetch: scan.fetch,
+        ordering: scan.ordering.clone(),
     }))
 }
 
@@ -1009,6 +1040,7 @@ mod tests {
             vec![col("b")],
             false,
             Some(Box::new(col("c").gt(lit(42)))),
+            None,
         ));
 
         let plan = LogicalPlanBuilder::from(table_scan)
@@ -0,0 +1,263 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Optimizer rule to push down SORT into table scans whose source can
+//! return its data in the requested order.
+//! It will push down through projections and filters.
+use crate::optimizer::ApplyOrder;
+use crate::push_down_filter::replace_cols_by_name;
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::Result;
+use datafusion_expr::expr_rewriter::unnormalize_col;
+use datafusion_expr::{
+    logical_plan::{LogicalPlan, TableScan},
+    Expr,
+};
+use std::collections::HashMap;
+
+/// Optimization rule that requests the ordering of a SORT from the
+/// [`TableScan`] below it, if the table source advertises a matching
+/// ordering in [`output_orderings`](datafusion_expr::TableSource::output_orderings).
+///
+/// The SORT itself is kept: it is removed during physical planning once the
+/// scan reports that its output is already ordered.
+#[derive(Default)]
+pub struct PushDownSort {}
+
+impl PushDownSort {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for PushDownSort {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        let sort = match plan {
+            LogicalPlan::Sort(sort) => sort,
+            _ => return Ok(None),
+        };
+
+        match push_down_sort(&sort.expr, &sort.input)? {
+            Some(new_input) => Ok(Some(plan.with_new_inputs(&[new_input])?)),
+            None => Ok(None),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "push_down_sort"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+/// Returns `plan` with the ordering `sort_exprs` requested from its table
+/// scan, or `None` if the ordering can not be pushed down
+fn push_down_sort(
+    sort_exprs: &[Expr],
+    plan: &LogicalPlan,
+) -> Result<Option<LogicalPlan>> {
+    let new_plan = match plan {
+        LogicalPlan::TableScan(scan) => {
+            if !scan.ordering.is_empty() || !source_provides_ordering(scan, sort_exprs) {
+                return Ok(None);
+            }
+            LogicalPlan::TableScan(TableScan {
+                ordering: sort_exprs.to_vec(),
+                ..scan.clone()
+            })
+        }
+        LogicalPlan::Filter(filter) => match push_down_sort(sort_exprs, &filter.input)? {
+            Some(new_input) => plan.with_new_inputs(&[new_input])?,
+            None => return Ok(None),
+        },
+        LogicalPlan::Projection(projection) => {
+            // re-write the sort expressions in terms of the projection input
+            let replace_map = projection
+                .schema
+                .fields()
+                .iter()
+                .zip(projection.expr.iter())
+                .map(|(field, expr)| {
+                    let expr = match expr {
+                        Expr::Alias(expr, _) => expr.as_ref().clone(),
+                        expr => expr.clone(),
+                    };
+                    (field.qualified_name(), expr)
+                })
+                .collect::<HashMap<_, _>>();
+            let sort_exprs = sort_exprs
+                .iter()
+                .map(|expr| replace_cols_by_name(expr.clone(), &replace_map))
+                .collect::<Result<Vec<_>>>()?;
+
+            match push_down_sort(&sort_exprs, &projection.input)? {
+                Some(new_input) => plan.with_new_inputs(&[new_input])?,
+                None => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(new_plan))
+}
+
+/// Returns true if `sort_exprs` is a prefix of one of the orderings
+/// advertised by the source of `scan`
+fn source_provides_ordering(scan: &TableScan, sort_exprs: &[Expr]) -> bool {
+    // the source does not know how the relation was referred to in the query
+    let requested: Vec<Expr> = sort_exprs.iter().cloned().map(unnormalize_col).collect();
+    scan.source.output_orderings().into_iter().any(|ordering| {
+        ordering.len() >= requested.len()
+            && ordering
+                .into_iter()
+                .map(unnormalize_col)
+                .zip(requested.iter())
+                .all(|(provided, requested)| &provided == requested)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::*;
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion_common::DFSchema;
+    use datafusion_expr::{col, lit, LogicalPlanBuilder, TableSource, TableType};
+    use std::sync::Arc;
+
+    struct OrderedSource {
+        orderings: Vec<Vec<Expr>>,
+    }
+
+    impl TableSource for OrderedSource {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            Arc::new(Schema::new(vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Int32, true),
+                Field::new("c", DataType::Int32, true),
+            ]))
+        }
+
+        fn table_type(&self) -> TableType {
+            TableType::Base
+        }
+
+        fn output_orderings(&self) -> Vec<Vec<Expr>> {
+            self.orderings.clone()
+        }
+    }
+
+    fn ordered_scan(orderings: Vec<Vec<Expr>>) -> Result<LogicalPlan> {
+        let source = OrderedSource { orderings };
+        Ok(LogicalPlan::TableScan(TableScan {
+            table_name: "test".to_string(),
+            projected_schema: Arc::new(DFSchema::try_from_qualified_schema(
+                "test",
+                &source.schema(),
+            )?),
+            source: Arc::new(source),
+            projection: None,
+            filters: vec![],
+            fetch: None,
+            ordering: vec![],
+        }))
+    }
+
+    fn a_b_ordering() -> Vec<Vec<Expr>> {
+        vec![vec![col("a").sort(true, false), col("b").sort(false, true)]]
+    }
+
+    fn assert_optimized_plan_equal(plan: &LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq(Arc::new(PushDownSort::new()), plan, expected)
+    }
+
+    #[test]
+    fn sort_on_table_scan() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(ordered_scan(a_b_ordering())?)
+            .sort(vec![col("a").sort(true, false)])?
+            .build()?;
+
+        let expected = "Sort: test.a ASC NULLS LAST\
+        \n  TableScan: test, ordering=[test.a ASC NULLS LAST]";
+        assert_optimized_plan_equal(&plan, expected)
+    }
+
+    #[test]
+    fn sort_through_projection_and_filter() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(ordered_scan(a_b_ordering())?)
+            .filter(col("c").gt(lit(1)))?
+            .project(vec![col("a").alias("x"), col("b"), col("c")])?
+            .sort(vec![col("x").sort(true, false), col("b").sort(false, true)])?
+            .build()?;
+
+        let expected = "Sort: x ASC NULLS LAST, test.b DESC NULLS FIRST\
+        \n  Projection: test.a AS x, test.b, test.c\
+        \n    Filter: test.c > Int32(1)\
+        \n      TableScan: test, ordering=[test.a ASC NULLS LAST, test.b DESC NULLS FIRST]";
+        assert_optimized_plan_equal(&plan, expected)
+    }
+
+    #[test]
+    fn sort_not_provided_by_source() -> Result<()> {
+        // not a prefix of the advertised ordering
+        let plan = LogicalPlanBuilder::from(ordered_scan(a_b_ordering())?)
+            .sort(vec![col("b").sort(false, true)])?
+            .build()?;
+        let expected = "Sort: test.b DESC NULLS FIRST\
+        \n  TableScan: test";
+        assert_optimized_plan_equal(&plan, expected)?;
+
+        // different direction
+        let plan = LogicalPlanBuilder::from(ordered_scan(a_b_ordering())?)
+            .sort(vec![col("a").sort(false, false)])?
+            .build()?;
+        let expected = "Sort: test.a DESC NULLS LAST\
+        \n  TableScan: test";
+        assert_optimized_plan_equal(&plan, expected)?;
+
+        // no advertised ordering
+        let plan = LogicalPlanBuilder::from(ordered_scan(vec![])?)
+            .sort(vec![col("a").sort(true, false)])?
+            .build()?;
+        let expected = "Sort: test.a ASC NULLS LAST\
+        \n  TableScan: test";
+        assert_optimized_plan_equal(&plan, expected)
+    }
+
+    #[test]
+    fn sort_on_projected_expression() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(ordered_scan(a_b_ordering())?)
+            .project(vec![(col("a") + lit(1)).alias("x")])?
+            .sort(vec![col("x").sort(true, false)])?
+            .build()?;
+
+        let expected = "Sort: x ASC NULLS LAST\
+        \n  Projection: test.a + Int32(1) AS x\
+        \n    TableScan: test";
+        assert_optimized_plan_equal(&plan, expected)
+    }
+}
@@ -30,6 +30,7 @@ use arrow::{
 use datafusion_common::{DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue};
 use datafusion_expr::{
     and,
+    expr::Case,
     expr_rewriter::{ExprRewritable, ExprRewriter, RewriteRecursion},
     lit, or, BinaryExpr, BuiltinScalarFunction, ColumnarValue, Expr, Volatility,
 };
@@ -716,33 +717,40 @@ impl<'a, S: SimplifyInfo> ExprRewriter for Simplifier<'a, S> {
             //
             // ---> (X AND A) OR (Y AND B AND NOT X) OR ... (NOT (X OR Y) AND Q)
             //
+            // where a nullable X or Y is replaced by `X IS TRUE`, as a null
+            // WHEN predicate does not match, and a missing ELSE by NULL.
+            //
             // Note: the rationale for this rewrite is that the expr can then be further
             // simplified using the existing rules for AND/OR
-            Expr::Case(case)
-                if !case.when_then_expr.is_empty()
-                && case.when_then_expr.len() < 3 // The rewrite is O(n!) so limit to small number
-                && info.is_boolean_type(&case.when_then_expr[0].1)? =>
+            Expr::Case(Case {
+                expr: None,
+                when_then_expr,
+                else_expr,
+            }) if !when_then_expr.is_empty()
+                && when_then_expr.len() < 3 // The rewrite is O(n!) so limit to small number
+                && info.is_boolean_type(&when_then_expr[0].1)? =>
             {
-                // The disjunction of all the when predicates encountered so far
+                // The disjunction of all the when predicates encountered so far,
+                // which is never null
                 let mut filter_expr = lit(false);
                 // The disjunction of all the cases
                 let mut out_expr = lit(false);
 
-                for (when, then) in case.when_then_expr {
-                    let case_expr = when
-                        .as_ref()
-                        .clone()
-                        .and(filter_expr.clone().not())
-                        .and(*then);
+                for (when, then) in when_then_expr {
+                    let when = if info.nullable(&when)? {
+                        when.is_true()
+                    } else {
+                        *when
+                    };
+                    let case_expr =
+                        when.clone().and(filter_expr.clone().not()).and(*then);
 
                     out_expr = out_expr.or(case_expr);
-                    filter_expr = filter_expr.or(*when);
+                    filter_expr = filter_expr.or(when);
                 }
 
-                if let Some(else_expr) = case.else_expr {
-                    let case_expr = filter_expr.not().and(*else_expr);
-                    out_expr = out_expr.or(case_expr);
-                }
+                let else_expr = else_expr.map(|e| *e).unwrap_or_else(lit_bool_null);
+                out_expr = out_expr.or(filter_expr.not().and(else_expr));
 
                 // Do a first pass at simplification
                 out_expr.rewrite(self)?
@@ -766,6 +774,15 @@ impl<'a, S: SimplifyInfo> ExprRewriter for Simplifier<'a, S> {
                 },
             },
 
+            // user defined functions with their own simplification
+            Expr::ScalarUDF { fun, args } => {
+                let simplified = match &fun.simplify {
+                    Some(simplify) => simplify(&args)?,
+                    None => None,
+                };
+                simplified.unwrap_or(Expr::ScalarUDF { fun, args })
+            }
+
             //
             // Rules for Between
             //
@@ -912,6 +929,8 @@ mod tests {
         let execution_props = ExecutionProps {
             query_execution_start_time: *date_time,
             var_providers: None,
+            evaluator_selector: None,
+            subqueries: vec![],
         };
 
         let mut const_evaluator = ConstEvaluator::try_new(&execution_props).unwrap();
@@ -1169,6 +1188,25 @@ mod tests {
         assert_eq!(simplify(expr_b), expected);
     }
 
+    #[test]
+    fn test_simplify_udf() {
+        // udf_add(A, 0) --> A
+        let add_zero: ScalarFunctionSimplifier = Arc::new(|args: &[Expr]| {
+            Ok(match args {
+                [arg, Expr::Literal(ScalarValue::Int32(Some(0)))] => Some(arg.clone()),
+                _ => None,
+            })
+        });
+        let fun = make_udf_add(Volatility::Immutable)
+            .as_ref()
+            .clone()
+            .with_simplify(add_zero);
+
+        assert_eq!(simplify(fun.call(vec![col("c2"), lit(0)])), col("c2"));
+        let expr = fun.call(vec![col("c2"), lit(1)]);
+        assert_eq!(simplify(expr.clone()), expr);
+    }
+
     #[test]
     fn test_simplify_multiply_by_one() {
         let expr_a = binary_expr(col("c2"), Operator::Multiply, lit(1));
@@ -1883,7 +1921,7 @@ mod tests {
                 )],
                 Some(Box::new(col("c2").eq(lit(true)))),
             ))),
-            col("c2").not().and(col("c2")) // #1716
+            col("c2").is_true().not().and(col("c2")) // #1716
         );
 
         // CASE WHERE c2 != false THEN "ok" == "ok" ELSE c2
@@ -1903,7 +1941,9 @@ mod tests {
                 )],
                 Some(Box::new(col("c2").eq(lit(true)))),
             )))),
-            col("c2").or(col("c2").not().and(col("c2"))) // #1716
+            col("c2")
+                .is_true()
+                .or(col("c2").is_true().not().and(col("c2"))) // #1716
         );
 
         // CASE WHERE ISNULL(c2) THEN true ELSE c2
@@ -1927,6 +1967,7 @@ mod tests {
         // --> c1 OR (NOT(c1) AND c2 AND FALSE) OR (NOT(c1 OR c2) AND TRUE)
         // --> c1 OR (NOT(c1) AND NOT(c2))
         // --> c1 OR NOT(c2)
+        // with nullable c1 and c2 replaced by `c1 IS TRUE` and `c2 IS TRUE`
         //
         // Need to call simplify 2x due to
         // https://github.com/apache/arrow-datafusion/issues/1160
@@ -1939,13 +1980,16 @@ mod tests {
                 ],
                 Some(Box::new(lit(true))),
             )))),
-            col("c1").or(col("c1").not().and(col("c2").not()))
+            col("c1")
+                .is_true()
+                .or(col("c1").is_true().not().and(col("c2").is_true().not()))
         );
 
         // CASE WHERE c1 then true WHERE c2 then true ELSE false
         // --> c1 OR (NOT(c1) AND c2 AND TRUE) OR (NOT(c1 OR c2) AND FALSE)
         // --> c1 OR (NOT(c1) AND c2)
         // --> c1 OR c2
+        // with nullable c1 and c2 replaced by `c1 IS TRUE` and `c2 IS TRUE`
         //
         // Need to call simplify 2x due to
         // https://github.com/apache/arrow-datafusion/issues/1160
@@ -1958,8 +2002,32 @@ mod tests {
                 ],
                 Some(Box::new(lit(true))),
             )))),
-            col("c1").or(col("c1").not().and(col("c2").not()))
+            col("c1")
+                .is_true()
+                .or(col("c1").is_true().not().and(col("c2").is_true().not()))
         );
+
+        // CASE WHERE c2 THEN false END
+        // --> (c2 IS TRUE AND false) OR (NOT(c2 IS TRUE) AND NULL)
+        // --> NOT(c2 IS TRUE) AND NULL
+        //
+        // which, unlike `false`, is null when c2 is not true
+        assert_eq!(
+            simplify(Expr::Case(Case::new(
+                None,
+                vec![(Box::new(col("c2")), Box::new(lit(false)))],
+                None,
+            ))),
+            col("c2").is_true().not().and(lit_bool_null())
+        );
+
+        // CASE c2 WHEN true THEN false END is not rewritten
+        let case = Expr::Case(Case::new(
+            Some(Box::new(col("c2"))),
+            vec![(Box::new(lit(true)), Box::new(lit(false)))],
+            None,
+        ));
+        assert_eq!(simplify(case.clone()), case);
     }
 
     #[test]
@@ -270,7 +270,7 @@ pub fn simpl_concat(args: Vec<Expr>) -> Result<Expr> {
             // Concatenate it with the `contiguous_scalar`.
             Expr::Literal(
                 ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)),
-            ) => contiguous_scalar += &v,
+            ) => contiguous_scalar += v.as_ref(),
             Expr::Literal(x) => {
                 return Err(DataFusionError::Internal(format!(
                 "The scalar {x} should be casted to string type during the type coercion."
@@ -314,7 +314,7 @@ pub fn simpl_concat_ws(delimiter: &Expr, args: &[Expr]) -> Result<Expr> {
                 Some(delimiter) if delimiter.is_empty() => simpl_concat(args.to_vec()),
                 Some(delimiter) => {
                     let mut new_args = Vec::with_capacity(args.len());
-                    new_args.push(lit(delimiter));
+                    new_args.push(lit(delimiter.as_ref()));
                     let mut contiguous_scalar = None;
                     for arg in args {
                         match arg {
@@ -324,8 +324,8 @@ pub fn simpl_concat_ws(delimiter: &Expr, args: &[Expr]) -> Result<Expr> {
                                 match contiguous_scalar {
                                     None => contiguous_scalar = Some(v.to_string()),
                                     Some(mut pre) => {
-                                        pre += delimiter;
-                                        pre += v;
+                                        pre += delimiter.as_ref();
+                                        pre += v.as_ref();
                                         contiguous_scalar = Some(pre)
                                     }
                                 }
@@ -64,9 +64,16 @@ fn is_single_distinct_agg(plan: &LogicalPlan) -> Result<bool> {
             let mut distinct_count = 0;
             for expr in aggr_expr {
                 if let Expr::AggregateFunction(AggregateFunction {
-                    distinct, args, ..
+                    distinct,
+                    args,
+                    order_by,
+                    ..
                 }) = expr
                 {
+                    // the ordering of the input of the aggregates would be lost
+                    if order_by.is_some() {
+                        return Ok(false);
+                    }
                     if *distinct {
                         distinct_count += 1;
                     }
@@ -144,6 +151,7 @@ impl OptimizerRule for SingleDistinctToGroupBy {
                                     vec![col(SINGLE_DISTINCT_ALIAS)],
                                     false, // intentional to remove distinct here
                                     filter.clone(),
+                                    None,
                                 )))
                             }
                             _ => Ok(aggr_expr.clone()),
@@ -399,6 +407,7 @@ mod tests {
                         vec![col("b")],
                         true,
                         None,
+                        None,
                     )),
                 ],
             )?
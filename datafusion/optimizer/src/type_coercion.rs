@@ -34,7 +34,7 @@ use datafusion_expr::type_coercion::functions::data_types;
 use datafusion_expr::type_coercion::other::{
     get_coerce_type_for_case_when, get_coerce_type_for_list,
 };
-use datafusion_expr::type_coercion::{is_date, is_numeric, is_timestamp};
+use datafusion_expr::type_coercion::{is_date, is_numeric, is_time, is_timestamp};
 use datafusion_expr::utils::from_plan;
 use datafusion_expr::{
     aggregate_function, function, is_false, is_not_false, is_not_true, is_not_unknown,
@@ -67,6 +67,11 @@ impl OptimizerRule for TypeCoercion {
     ) -> Result<Option<LogicalPlan>> {
         Ok(Some(optimize_internal(&DFSchema::empty(), plan)?))
     }
+
+    fn skippable(&self) -> bool {
+        // the plan can't be executed without the coercions
+        false
+    }
 }
 
 fn optimize_internal(
@@ -234,7 +239,9 @@ impl ExprRewriter for TypeCoercionRewriter {
                     (
                         DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _),
                         &DataType::Interval(_),
-                    ) => {
+                    )
+                    | (DataType::Timestamp(_, _), DataType::Duration(_))
+                    | (DataType::Duration(_), DataType::Timestamp(_, _)) => {
                         // this is a workaround for https://github.com/apache/arrow-datafusion/issues/3419
                         Ok(expr.clone())
                     }
@@ -384,6 +391,7 @@ impl ExprRewriter for TypeCoercionRewriter {
                 args,
                 distinct,
                 filter,
+                order_by,
             }) => {
                 let new_expr = coerce_agg_exprs_for_signature(
                     &fun,
@@ -392,7 +400,7 @@ impl ExprRewriter for TypeCoercionRewriter {
                     &aggregate_function::signature(&fun),
                 )?;
                 let expr = Expr::AggregateFunction(expr::AggregateFunction::new(
-                    fun, new_expr, distinct, filter,
+                    fun, new_expr, distinct, filter, order_by,
                 ));
                 Ok(expr)
             }
@@ -450,7 +458,7 @@ fn convert_to_coerced_type(
             if let DataType::Interval(..) = coerced_type {
                 parse_interval("millisecond", val)
             } else {
-                ScalarValue::try_from_string(val.clone(), coerced_type)
+                ScalarValue::try_from_string(val.to_string(), coerced_type)
             }
         }
         s => {
@@ -486,7 +494,10 @@ fn get_coerced_window_frame(
     fn get_coerced_type(column_type: &DataType) -> Result<DataType> {
         if is_numeric(column_type) {
             Ok(column_type.clone())
-        } else if is_timestamp(column_type) || is_date(column_type) {
+        } else if is_timestamp(column_type)
+            || is_date(column_type)
+            || is_time(column_type)
+        {
             Ok(DataType::Interval(IntervalUnit::MonthDayNano))
         } else {
             Err(DataFusionError::Internal(format!(
@@ -780,6 +791,7 @@ mod test {
             vec![lit(12i64)],
             false,
             None,
+            None,
         ));
         let plan = LogicalPlan::Projection(Projection::try_new(vec![agg_expr], empty)?);
         let expected = "Projection: AVG(Int64(12))\n  EmptyRelation";
@@ -792,6 +804,7 @@ mod test {
             vec![col("a")],
             false,
             None,
+            None,
         ));
         let plan = LogicalPlan::Projection(Projection::try_new(vec![agg_expr], empty)?);
         let expected = "Projection: AVG(a)\n  EmptyRelation";
@@ -808,6 +821,7 @@ mod test {
             vec![lit("1")],
             false,
             None,
+            None,
         ));
         let err = Projection::try_new(vec![agg_expr], empty).err().unwrap();
         assert_eq!(
@@ -308,6 +308,14 @@ fn try_cast_literal_to_type(
     if !is_support_data_type(&lit_data_type) || !is_support_data_type(target_type) {
         return Ok(None);
     }
+    // the casts between timestamps with and without a time zone change their values
+    if let (DataType::Timestamp(_, lit_tz), DataType::Timestamp(_, target_tz)) =
+        (&lit_data_type, target_type)
+    {
+        if lit_tz.is_some() != target_tz.is_some() {
+            return Ok(None);
+        }
+    }
     if lit_value.is_null() {
         // null value can be cast to any type of null value
         return Ok(Some(ScalarValue::try_from(target_type)?));
@@ -684,11 +692,11 @@ mod tests {
     fn test_unwrap_cast_with_timestamp_nanos() {
         let schema = expr_test_schema();
         // cast(ts_nano as Timestamp(Nanosecond, UTC)) < 1666612093000000000::Timestamp(Nanosecond, Utc))
+        // is not unwrapped: the casts from timestamps without a time zone keep
+        // their wall clock times
         let expr_lt = try_cast(col("ts_nano_none"), timestamp_nano_utc_type())
             .lt(lit_timestamp_nano_utc(1666612093000000000));
-        let expected =
-            col("ts_nano_none").lt(lit_timestamp_nano_none(1666612093000000000));
-        assert_eq!(optimize_test(expr_lt, &schema), expected);
+        assert_eq!(optimize_test(expr_lt.clone(), &schema), expr_lt);
     }
 
     fn optimize_test(expr: Expr, schema: &DFSchemaRef) -> Expr {
@@ -733,10 +741,6 @@ mod tests {
         lit(ScalarValue::Decimal128(Some(value), precision, scale))
     }
 
-    fn lit_timestamp_nano_none(ts: i64) -> Expr {
-        lit(ScalarValue::TimestampNanosecond(Some(ts), None))
-    }
-
     fn lit_timestamp_nano_utc(ts: i64) -> Expr {
         let utc = Some("+0:00".to_string());
         lit(ScalarValue::TimestampNanosecond(Some(ts), utc))
@@ -951,14 +955,14 @@ mod tests {
             expect_cast(
                 lit_tz_none.clone(),
                 dt_tz_utc.clone(),
-                ExpectedCast::Value(lit_tz_utc.clone()),
+                ExpectedCast::NoValue,
             );
 
             // Utc <--> None
             expect_cast(
                 lit_tz_utc.clone(),
                 dt_tz_none.clone(),
-                ExpectedCast::Value(lit_tz_none.clone()),
+                ExpectedCast::NoValue,
             );
 
             // Utc <--> Utc
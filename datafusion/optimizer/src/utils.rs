@@ -18,18 +18,25 @@
 //! Collection of utility functions that are leveraged by the query optimizer rules
 
 use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
 use datafusion_common::Result;
-use datafusion_common::{plan_err, Column, DFSchemaRef};
-use datafusion_expr::expr::{BinaryExpr, Sort};
-use datafusion_expr::expr_rewriter::{ExprRewritable, ExprRewriter};
+use datafusion_common::{plan_err, Column, DFSchemaRef, DataFusionError};
+use datafusion_expr::expr::{BinaryExpr, Sort, WindowFunction};
+use datafusion_expr::expr_rewriter::{
+    replace_col, ExprRewritable, ExprRewriter, RewriteRecursion,
+};
 use datafusion_expr::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
 use datafusion_expr::{
-    and, col,
-    logical_plan::{Filter, LogicalPlan},
-    utils::from_plan,
-    Expr, Operator,
+    and, col, lit,
+    logical_plan::{
+        Distinct, Filter, JoinType, LogicalPlan, Projection, Subquery, TableScan, Window,
+    },
+    utils::{check_all_column_from_schema, from_plan},
+    window_frame::WindowFrame,
+    window_function::{self, BuiltInWindowFunction},
+    Expr, LogicalPlanBuilder, Operator,
 };
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
 /// Convenience rule for writing optimizers: recursively invoke
@@ -518,6 +525,484 @@ fn add_alias_if_changed(original_name: String, expr: Expr) -> Result<Expr> {
     })
 }
 
+/// Rewrites every [`TableScan`] of `plan`, including the ones in subquery
+/// expressions, with `rewrite`.
+///
+/// `rewrite` is called with the scan and the plan replacing it so far: the
+/// scan itself or, for views, their definition with its own scans rewritten.
+/// Views are inlined as [`InlineTableScan`](crate::inline_table_scan::InlineTableScan)
+/// would do, so that the rewrite reaches the tables they read.
+pub(crate) fn rewrite_table_scans(
+    plan: &LogicalPlan,
+    rewrite: &dyn Fn(&TableScan, LogicalPlan) -> Result<LogicalPlan>,
+) -> Result<LogicalPlan> {
+    if let LogicalPlan::TableScan(scan) = plan {
+        let input = match scan.source.get_logical_plan() {
+            Some(sub_plan) if scan.filters.is_empty() => {
+                LogicalPlanBuilder::from(rewrite_table_scans(sub_plan, rewrite)?)
+                    .project(vec![Expr::Wildcard])?
+                    .alias(&scan.table_name)?
+                    .build()?
+            }
+            _ => plan.clone(),
+        };
+        return rewrite(scan, input);
+    }
+
+    let new_inputs = plan
+        .inputs()
+        .iter()
+        .map(|input| rewrite_table_scans(input, rewrite))
+        .collect::<Result<Vec<_>>>()?;
+    let mut rewriter = TableScanRewriter { rewrite };
+    let new_exprs = plan
+        .expressions()
+        .into_iter()
+        .map(|expr| rewrite_preserving_name(expr, &mut rewriter))
+        .collect::<Result<Vec<_>>>()?;
+
+    from_plan(plan, &new_exprs, &new_inputs)
+}
+
+/// Rewrites the table scans of subquery expressions
+struct TableScanRewriter<'a> {
+    rewrite: &'a dyn Fn(&TableScan, LogicalPlan) -> Result<LogicalPlan>,
+}
+
+impl<'a> ExprRewriter for TableScanRewriter<'a> {
+    fn pre_visit(&mut self, _expr: &Expr) -> Result<RewriteRecursion> {
+        Ok(RewriteRecursion::Continue)
+    }
+
+    fn mutate(&mut self, expr: Expr) -> Result<Expr> {
+        let rewrite = |subquery: &LogicalPlan| {
+            rewrite_table_scans(subquery, self.rewrite).map(Subquery::new)
+        };
+        match expr {
+            Expr::ScalarSubquery(Subquery { subquery }) => {
+                Ok(Expr::ScalarSubquery(rewrite(subquery.as_ref())?))
+            }
+            Expr::Exists { subquery, negated } => Ok(Expr::Exists {
+                subquery: rewrite(subquery.subquery.as_ref())?,
+                negated,
+            }),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => Ok(Expr::InSubquery {
+                expr,
+                subquery: rewrite(subquery.subquery.as_ref())?,
+                negated,
+            }),
+            expr => Ok(expr),
+        }
+    }
+}
+
+/// Returns whether `subquery` refers to the columns of an outer query, which
+/// aren't in the schemas of the inputs of its operators
+pub fn is_correlated(subquery: &LogicalPlan) -> Result<bool> {
+    let mut correlated = false;
+    subquery.apply(&mut |plan| {
+        let inputs = plan.inputs();
+        if inputs.is_empty() {
+            return Ok(VisitRecursion::Continue);
+        }
+        for expr in plan.expressions() {
+            for column in expr.to_columns()? {
+                if !inputs
+                    .iter()
+                    .any(|input| input.schema().index_of_column(&column).is_ok())
+                {
+                    correlated = true;
+                    return Ok(VisitRecursion::Stop);
+                }
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    })?;
+    Ok(correlated)
+}
+
+/// The name of the row numbers computed to evaluate a correlated limit
+const ROW_NUMBER_COLUMN: &str = "__row_number";
+
+/// Pulls the predicates of the filters of a subquery that refer to the outer
+/// query up to its top, returning them and the subquery without them.
+///
+/// The predicates are pulled up through the projections, which keep the
+/// columns of the subquery they refer to. They are pulled up through the
+/// aggregations, window functions and limits only if they all compare a
+/// column of the subquery for equality with the outer query: grouping,
+/// partitioning or numbering the rows by that column then evaluates the
+/// subquery for all the rows of the outer query at once. The plans the
+/// predicates can't be pulled up through are returned unchanged, and still
+/// correlated.
+pub(crate) fn extract_join_filters(
+    plan: &LogicalPlan,
+) -> Result<(Vec<Expr>, LogicalPlan)> {
+    let unchanged = || Ok((vec![], plan.clone()));
+    match plan {
+        LogicalPlan::Filter(filter) => {
+            let (mut join_filters, input) = extract_join_filters(&filter.input)?;
+            let input_schema = filter.input.schema();
+            let mut subquery_filters: Vec<Expr> = vec![];
+            for expr in split_conjunction(&filter.predicate) {
+                let cols = expr.to_columns()?;
+                if check_all_column_from_schema(&cols, input_schema.clone()) {
+                    subquery_filters.push(expr.clone());
+                } else {
+                    join_filters.push(expr.clone())
+                }
+            }
+            if join_filters.is_empty() {
+                return unchanged();
+            }
+
+            // if the subquery still has filter expressions, restore them.
+            let mut plan = LogicalPlanBuilder::from(input);
+            if let Some(expr) = conjunction(subquery_filters) {
+                plan = plan.filter(expr)?
+            }
+            Ok((join_filters, plan.build()?))
+        }
+        LogicalPlan::Projection(projection) => {
+            let (join_filters, input) = extract_join_filters(&projection.input)?;
+            if join_filters.is_empty() {
+                return unchanged();
+            }
+            let exprs =
+                with_columns_of(projection.expr.clone(), &join_filters, input.schema())?;
+            let plan = LogicalPlanBuilder::from(input).project(exprs)?.build()?;
+            Ok((join_filters, plan))
+        }
+        LogicalPlan::Distinct(distinct) => {
+            let (join_filters, input) = extract_join_filters(&distinct.input)?;
+            if join_filters.is_empty() {
+                return unchanged();
+            }
+            let plan = LogicalPlanBuilder::from(input).distinct()?.build()?;
+            Ok((join_filters, plan))
+        }
+        LogicalPlan::Sort(sort) if sort.fetch.is_none() => {
+            let (join_filters, input) = extract_join_filters(&sort.input)?;
+            if join_filters.is_empty() {
+                return unchanged();
+            }
+            let plan = LogicalPlanBuilder::from(input)
+                .sort(sort.expr.clone())?
+                .build()?;
+            Ok((join_filters, plan))
+        }
+        LogicalPlan::Sort(sort) => {
+            extract_join_filters_through_limit(plan, 0, sort.fetch, plan)
+        }
+        LogicalPlan::Limit(limit) => extract_join_filters_through_limit(
+            plan,
+            limit.skip,
+            limit.fetch,
+            &limit.input,
+        ),
+        LogicalPlan::Aggregate(aggregate) => {
+            // an aggregation without grouping has a row even for the rows of
+            // the outer query without matching rows, which grouping by the
+            // compared columns would lose
+            if aggregate.group_expr.is_empty()
+                || aggregate
+                    .group_expr
+                    .iter()
+                    .any(|expr| matches!(expr, Expr::GroupingSet(_)))
+            {
+                return unchanged();
+            }
+            let (join_filters, input) = extract_join_filters(&aggregate.input)?;
+            let cols = match equi_correlated_columns(&join_filters, input.schema())? {
+                Some(cols) if !join_filters.is_empty() => cols,
+                _ => return unchanged(),
+            };
+            let mut group_expr = aggregate.group_expr.clone();
+            for col in cols {
+                let expr = Expr::Column(col);
+                if !group_expr.contains(&expr) {
+                    group_expr.push(expr);
+                }
+            }
+            let plan = LogicalPlanBuilder::from(input)
+                .aggregate(group_expr, aggregate.aggr_expr.clone())?
+                .build()?;
+            Ok((join_filters, plan))
+        }
+        LogicalPlan::Window(window) => {
+            let (join_filters, input) = extract_join_filters(&window.input)?;
+            let cols = match equi_correlated_columns(&join_filters, input.schema())? {
+                Some(cols) if !join_filters.is_empty() => cols,
+                _ => return unchanged(),
+            };
+            let window_expr = window
+                .window_expr
+                .iter()
+                .map(|expr| partition_window_expr(expr, &cols))
+                .collect::<Result<Vec<_>>>()?;
+            let plan = LogicalPlanBuilder::from(input)
+                .window(window_expr)?
+                .build()?;
+            Ok((join_filters, plan))
+        }
+        _ => unchanged(),
+    }
+}
+
+/// Pulls the predicates referring to the outer query up through `plan`,
+/// which keeps the rows `skip..skip + fetch` of `input`, by numbering the
+/// rows of each value of the columns they compare, in the order of the sort
+/// under the projections of `input` if there is one
+fn extract_join_filters_through_limit(
+    plan: &LogicalPlan,
+    skip: usize,
+    fetch: Option<usize>,
+    input: &LogicalPlan,
+) -> Result<(Vec<Expr>, LogicalPlan)> {
+    let mut projections = vec![];
+    let mut input = input;
+    while let LogicalPlan::Projection(projection) = input {
+        projections.push(projection);
+        input = projection.input.as_ref();
+    }
+    let (order_by, input) = match input {
+        LogicalPlan::Sort(sort) => (sort.expr.clone(), sort.input.as_ref()),
+        _ => (vec![], input),
+    };
+
+    let (join_filters, input) = extract_join_filters(input)?;
+    let cols = match equi_correlated_columns(&join_filters, input.schema())? {
+        Some(cols) if !join_filters.is_empty() => cols,
+        _ => return Ok((vec![], plan.clone())),
+    };
+
+    let input_cols = input
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| Expr::Column(field.qualified_column()))
+        .collect::<Vec<_>>();
+    let row_number = Expr::WindowFunction(WindowFunction::new(
+        window_function::WindowFunction::BuiltInWindowFunction(
+            BuiltInWindowFunction::RowNumber,
+        ),
+        vec![],
+        cols.into_iter().map(Expr::Column).collect(),
+        order_by.clone(),
+        WindowFrame::new(!order_by.is_empty()),
+    ))
+    .alias(ROW_NUMBER_COLUMN);
+    let mut bounds = vec![];
+    if skip > 0 {
+        bounds.push(col(ROW_NUMBER_COLUMN).gt(lit(skip as u64)));
+    }
+    if let Some(fetch) = fetch {
+        bounds.push(col(ROW_NUMBER_COLUMN).lt_eq(lit((skip + fetch) as u64)));
+    }
+
+    let mut plan = LogicalPlanBuilder::from(input).window(vec![row_number])?;
+    if let Some(expr) = conjunction(bounds) {
+        plan = plan.filter(expr)?;
+    }
+    // drop the row numbers
+    if projections.is_empty() {
+        plan = plan.project(input_cols)?;
+    }
+    for projection in projections.into_iter().rev() {
+        let exprs =
+            with_columns_of(projection.expr.clone(), &join_filters, plan.schema())?;
+        plan = plan.project(exprs)?;
+    }
+    Ok((join_filters, plan.build()?))
+}
+
+/// Adds the columns of `schema` that `join_filters` refer to to `exprs`,
+/// unless they already are in them
+fn with_columns_of(
+    mut exprs: Vec<Expr>,
+    join_filters: &[Expr],
+    schema: &DFSchemaRef,
+) -> Result<Vec<Expr>> {
+    let mut cols = BTreeSet::new();
+    for filter in join_filters {
+        cols.extend(filter.to_columns()?);
+    }
+    for col in cols {
+        if schema.field_from_column(&col).is_ok() {
+            let expr = Expr::Column(col);
+            if !exprs.contains(&expr) {
+                exprs.push(expr);
+            }
+        }
+    }
+    Ok(exprs)
+}
+
+/// Returns the columns of `schema` that `join_filters` compare for equality
+/// with expressions of the outer query, or `None` if some of `join_filters`
+/// aren't such equalities
+fn equi_correlated_columns(
+    join_filters: &[Expr],
+    schema: &DFSchemaRef,
+) -> Result<Option<Vec<Column>>> {
+    let mut cols: Vec<Column> = vec![];
+    for filter in join_filters {
+        let (left, right) = match filter {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            }) => (left.as_ref(), right.as_ref()),
+            _ => return Ok(None),
+        };
+        let (col, outer) = match (left, right) {
+            (Expr::Column(col), outer) if schema.field_from_column(col).is_ok() => {
+                (col, outer)
+            }
+            (outer, Expr::Column(col)) if schema.field_from_column(col).is_ok() => {
+                (col, outer)
+            }
+            _ => return Ok(None),
+        };
+        if outer
+            .to_columns()?
+            .iter()
+            .any(|col| schema.field_from_column(col).is_ok())
+        {
+            return Ok(None);
+        }
+        if !cols.contains(col) {
+            cols.push(col.clone());
+        }
+    }
+    Ok(Some(cols))
+}
+
+/// Adds `cols` to the partitioning of the window function `expr`, keeping
+/// its name
+fn partition_window_expr(expr: &Expr, cols: &[Column]) -> Result<Expr> {
+    let (window, name) = match expr {
+        Expr::Alias(expr, alias) => (expr.as_ref(), alias.clone()),
+        _ => (expr, expr.display_name()?),
+    };
+    match window {
+        Expr::WindowFunction(window) => {
+            let mut window = window.clone();
+            for col in cols {
+                let expr = Expr::Column(col.clone());
+                if !window.partition_by.contains(&expr) {
+                    window.partition_by.push(expr);
+                }
+            }
+            Ok(Expr::WindowFunction(window).alias(name))
+        }
+        _ => Err(DataFusionError::Internal(format!(
+            "Expected a window function, found {window:?}"
+        ))),
+    }
+}
+
+/// Returns the plan of `subquery` whose rows decide whether it has rows,
+/// skipping the operators on top of it which keep a row of a non-empty input,
+/// or `None` if `subquery` always has a row
+pub(crate) fn existence_input(subquery: &LogicalPlan) -> Option<&LogicalPlan> {
+    let mut plan = subquery;
+    loop {
+        let input = match plan {
+            LogicalPlan::Distinct(Distinct { input })
+            | LogicalPlan::Projection(Projection { input, .. })
+            | LogicalPlan::Window(Window { input, .. }) => input,
+            LogicalPlan::Sort(sort) if sort.fetch != Some(0) => &sort.input,
+            LogicalPlan::Limit(limit) if limit.skip == 0 && limit.fetch != Some(0) => {
+                &limit.input
+            }
+            LogicalPlan::Aggregate(aggregate)
+                if !aggregate
+                    .group_expr
+                    .iter()
+                    .any(|expr| matches!(expr, Expr::GroupingSet(_))) =>
+            {
+                if aggregate.group_expr.is_empty() {
+                    return None;
+                }
+                &aggregate.input
+            }
+            _ => return Some(plan),
+        };
+        plan = input.as_ref();
+    }
+}
+
+/// Qualifies the columns `cols` of `expr` by `subquery_alias`
+pub(crate) fn replace_qualified_name(
+    expr: Expr,
+    cols: &BTreeSet<Column>,
+    subquery_alias: &str,
+) -> Result<Expr> {
+    let alias_cols: Vec<Column> = cols
+        .iter()
+        .map(|col| {
+            Column::from_qualified_name(format!("{}.{}", subquery_alias, col.name))
+        })
+        .collect();
+    let replace_map: HashMap<&Column, &Column> =
+        cols.iter().zip(alias_cols.iter()).collect();
+
+    replace_col(expr, &replace_map)
+}
+
+/// Joins `outer` with `subquery`, aliased as `alias`, by a
+/// [`JoinType::LeftMark`] join on `filter`, which refers to the columns of
+/// both plans.
+///
+/// Returns the join and its mark column, true for the rows of `outer` for
+/// which some row of `subquery` satisfies `filter`.
+pub(crate) fn mark_join(
+    outer: &LogicalPlan,
+    subquery: LogicalPlan,
+    filter: Option<Expr>,
+    alias: &str,
+) -> Result<(LogicalPlan, Expr)> {
+    let subquery_schema = subquery.schema().clone();
+    let subquery_cols = match &filter {
+        Some(filter) => filter
+            .to_columns()?
+            .into_iter()
+            .filter(|col| subquery_schema.field_from_column(col).is_ok())
+            .collect(),
+        None => BTreeSet::new(),
+    };
+    let filter = filter
+        .map(|filter| replace_qualified_name(filter, &subquery_cols, alias))
+        .transpose()?;
+
+    // only keep the columns of the filter, or a constant if there are none,
+    // which qualifies the mark by the alias
+    let projection = if subquery_cols.is_empty() {
+        vec![lit(true).alias("exists")]
+    } else {
+        subquery_cols.into_iter().map(Expr::Column).collect()
+    };
+    let right = LogicalPlanBuilder::from(subquery)
+        .project(projection)?
+        .alias(alias)?
+        .build()?;
+
+    let join = LogicalPlanBuilder::from(outer.clone())
+        .join(
+            right,
+            JoinType::LeftMark,
+            (Vec::<Column>::new(), Vec::<Column>::new()),
+            filter,
+        )?
+        .build()?;
+    Ok((join, Expr::Column(Column::new(Some(alias), "mark"))))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,114 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::{Array, ArrayRef, Decimal128Array, DictionaryArray};
+use arrow::datatypes::{Field, Int32Type, Schema};
+use arrow::record_batch::RecordBatch;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use datafusion_common::ScalarValue;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{col, lit, BinaryExpr};
+use datafusion_physical_expr::PhysicalExpr;
+use rand::prelude::*;
+use std::sync::Arc;
+
+fn do_bench(c: &mut Criterion, name: &str, batch: &RecordBatch, expr: &BinaryExpr) {
+    c.bench_function(name, |b| {
+        b.iter(|| black_box(expr.evaluate(black_box(batch)).unwrap()))
+    });
+}
+
+fn decimal_array(rng: &mut StdRng, array_length: usize, null_percent: f64) -> ArrayRef {
+    let values: Decimal128Array = (0..array_length)
+        .map(|_| {
+            (!rng.gen_bool(null_percent)).then(|| rng.gen_range(-100_000_i128..100_000))
+        })
+        .collect();
+    Arc::new(values.with_precision_and_scale(20, 2).unwrap())
+}
+
+fn do_benches(c: &mut Criterion, array_length: usize, null_percent: f64) {
+    let mut rng = StdRng::seed_from_u64(120320);
+    let ops = [("eq", Operator::Eq), ("lt", Operator::Lt)];
+
+    // dictionary encoded strings with 100 distinct values compared with a constant
+    let values: DictionaryArray<Int32Type> = (0..array_length)
+        .map(|_| {
+            (!rng.gen_bool(null_percent))
+                .then(|| format!("value{}", rng.gen_range(0..100)))
+        })
+        .collect();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        values.data_type().clone(),
+        true,
+    )]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(values)]).unwrap();
+    for (op_name, op) in ops {
+        let expr = BinaryExpr::new(
+            col("a", &schema).unwrap(),
+            op,
+            lit(ScalarValue::Utf8(Some("value50".into()))),
+        );
+        do_bench(
+            c,
+            &format!("dict_utf8 {op_name} scalar ({array_length}, {null_percent})"),
+            &batch,
+            &expr,
+        );
+    }
+
+    // decimals compared with a constant and with each other
+    let left = decimal_array(&mut rng, array_length, null_percent);
+    let right = decimal_array(&mut rng, array_length, null_percent);
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", left.data_type().clone(), true),
+        Field::new("b", right.data_type().clone(), true),
+    ]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![left, right]).unwrap();
+    for (op_name, op) in ops {
+        let expr = BinaryExpr::new(
+            col("a", &schema).unwrap(),
+            op,
+            lit(ScalarValue::Decimal128(Some(0), 20, 2)),
+        );
+        do_bench(
+            c,
+            &format!("decimal {op_name} scalar ({array_length}, {null_percent})"),
+            &batch,
+            &expr,
+        );
+
+        let expr =
+            BinaryExpr::new(col("a", &schema).unwrap(), op, col("b", &schema).unwrap());
+        do_bench(
+            c,
+            &format!("decimal {op_name} decimal ({array_length}, {null_percent})"),
+            &batch,
+            &expr,
+        );
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    for null_percent in [0., 0.2] {
+        do_benches(c, 8192, null_percent)
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
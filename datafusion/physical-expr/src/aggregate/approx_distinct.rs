@@ -182,8 +182,7 @@ where
 
 impl<T: Hash> From<&HyperLogLog<T>> for ScalarValue {
     fn from(v: &HyperLogLog<T>) -> ScalarValue {
-        let values = v.as_ref().to_vec();
-        ScalarValue::Binary(Some(values))
+        ScalarValue::Binary(Some(v.as_ref().into()))
     }
 }
 
@@ -203,7 +202,7 @@ impl<T: Hash> TryFrom<&ScalarValue> for HyperLogLog<T> {
     type Error = DataFusionError;
     fn try_from(v: &ScalarValue) -> Result<HyperLogLog<T>> {
         if let ScalarValue::Binary(Some(slice)) = v {
-            slice.as_slice().try_into()
+            (&slice[..]).try_into()
         } else {
             Err(DataFusionError::Internal(
                 "Impossibly got invalid scalar value while converting to HyperLogLog"
@@ -133,7 +133,8 @@ impl Accumulator for ArrayAggAccumulator {
         (0..arr.len()).try_for_each(|index| {
             let scalar = ScalarValue::try_from_array(arr, index)?;
             if let ScalarValue::List(Some(values), _) = scalar {
-                self.values.extend(values);
+                self.values
+                    .extend(ScalarValue::convert_array_to_scalar_vec(&values)?);
                 Ok(())
             } else {
                 Err(DataFusionError::Internal(
@@ -148,10 +149,7 @@ impl Accumulator for ArrayAggAccumulator {
     }
 
     fn evaluate(&self) -> Result<ScalarValue> {
-        Ok(ScalarValue::new_list(
-            Some(self.values.clone()),
-            self.datatype.clone(),
-        ))
+        ScalarValue::new_list(Some(self.values.clone()), self.datatype.clone())
     }
 
     fn size(&self) -> usize {
@@ -187,7 +185,8 @@ mod tests {
                 ScalarValue::Int32(Some(5)),
             ]),
             DataType::Int32,
-        );
+        )
+        .unwrap();
 
         generic_test_op!(a, DataType::Int32, ArrayAgg, list, DataType::Int32)
     }
@@ -203,41 +202,50 @@ mod tests {
                         ScalarValue::from(3i32),
                     ]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(4i32), ScalarValue::from(5i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
             ]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let l2 = ScalarValue::new_list(
             Some(vec![
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(6i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(7i32), ScalarValue::from(8i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
             ]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let l3 = ScalarValue::new_list(
             Some(vec![ScalarValue::new_list(
                 Some(vec![ScalarValue::from(9i32)]),
                 DataType::Int32,
-            )]),
+            )
+            .unwrap()]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let list = ScalarValue::new_list(
             Some(vec![l1.clone(), l2.clone(), l3.clone()]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let array = ScalarValue::iter_to_array(vec![l1, l2, l3]).unwrap();
 
@@ -123,7 +123,7 @@ impl Accumulator for DistinctArrayAggAccumulator {
         Ok(vec![ScalarValue::new_list(
             Some(self.values.clone().into_iter().collect()),
             self.datatype.clone(),
-        )])
+        )?])
     }
 
     fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
@@ -151,10 +151,10 @@ impl Accumulator for DistinctArrayAggAccumulator {
     }
 
     fn evaluate(&self) -> Result<ScalarValue> {
-        Ok(ScalarValue::new_list(
+        ScalarValue::new_list(
             Some(self.values.clone().into_iter().collect()),
             self.datatype.clone(),
-        ))
+        )
     }
 
     fn size(&self) -> usize {
@@ -190,7 +190,10 @@ mod tests {
         let actual = aggregate(&batch, agg)?;
 
         match (expected, actual) {
-            (ScalarValue::List(Some(mut e), _), ScalarValue::List(Some(mut a), _)) => {
+            (ScalarValue::List(Some(e), _), ScalarValue::List(Some(a), _)) => {
+                let mut e = ScalarValue::convert_array_to_scalar_vec(&e)?;
+                let mut a = ScalarValue::convert_array_to_scalar_vec(&a)?;
+
                 // workaround lack of Ord of ScalarValue
                 let cmp = |a: &ScalarValue, b: &ScalarValue| {
                     a.partial_cmp(b).expect("Can compare ScalarValues")
@@ -222,7 +225,8 @@ mod tests {
                 ScalarValue::Int32(Some(5)),
             ]),
             DataType::Int32,
-        );
+        )
+        .unwrap();
 
         check_distinct_array_agg(col, out, DataType::Int32)
     }
@@ -239,14 +243,17 @@ mod tests {
                         ScalarValue::from(3i32),
                     ]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(4i32), ScalarValue::from(5i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
             ]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         // [[6], [7, 8]]
         let l2 = ScalarValue::new_list(
@@ -254,28 +261,34 @@ mod tests {
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(6i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
                 ScalarValue::new_list(
                     Some(vec![ScalarValue::from(7i32), ScalarValue::from(8i32)]),
                     DataType::Int32,
-                ),
+                )
+                .unwrap(),
             ]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         // [[9]]
         let l3 = ScalarValue::new_list(
             Some(vec![ScalarValue::new_list(
                 Some(vec![ScalarValue::from(9i32)]),
                 DataType::Int32,
-            )]),
+            )
+            .unwrap()]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         let list = ScalarValue::new_list(
             Some(vec![l1.clone(), l2.clone(), l3.clone()]),
             DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
-        );
+        )
+        .unwrap();
 
         // Duplicate l1 in the input array and check that it is deduped in the output.
         let array = ScalarValue::iter_to_array(vec![l1.clone(), l2, l3, l1]).unwrap();
@@ -0,0 +1,358 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ARRAY_AGG aggregate expression with an ORDER BY clause
+
+use crate::expressions::format_state_name;
+use crate::{AggregateExpr, PhysicalExpr, PhysicalSortExpr};
+use arrow::array::ArrayRef;
+use arrow::compute::{lexsort_to_indices, SortColumn};
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::ScalarValue;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::Accumulator;
+use std::any::Any;
+use std::sync::Arc;
+
+/// ARRAY_AGG aggregate expression whose values are ordered by sort keys,
+/// like `ARRAY_AGG(a ORDER BY b DESC NULLS FIRST)`
+#[derive(Debug)]
+pub struct OrderSensitiveArrayAgg {
+    name: String,
+    input_data_type: DataType,
+    expr: Arc<dyn PhysicalExpr>,
+    ordering_req: Vec<PhysicalSortExpr>,
+    ordering_types: Vec<DataType>,
+}
+
+impl OrderSensitiveArrayAgg {
+    /// Create a new OrderSensitiveArrayAgg aggregate function, whose sort
+    /// keys have the types `ordering_types`
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        input_data_type: DataType,
+        ordering_req: Vec<PhysicalSortExpr>,
+        ordering_types: Vec<DataType>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            input_data_type,
+            expr,
+            ordering_req,
+            ordering_types,
+        }
+    }
+
+    /// The sort keys of the values
+    pub fn ordering_req(&self) -> &[PhysicalSortExpr] {
+        &self.ordering_req
+    }
+}
+
+impl AggregateExpr for OrderSensitiveArrayAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(
+            &self.name,
+            DataType::List(Box::new(Field::new(
+                "item",
+                self.input_data_type.clone(),
+                true,
+            ))),
+            false,
+        ))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(OrderSensitiveArrayAggAccumulator::try_new(
+            &self.input_data_type,
+            &self.ordering_req,
+            &self.ordering_types,
+        )?))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        // the values, followed by the values of each sort key
+        let mut fields = vec![Field::new(
+            format_state_name(&self.name, "array_agg"),
+            DataType::List(Box::new(Field::new(
+                "item",
+                self.input_data_type.clone(),
+                true,
+            ))),
+            false,
+        )];
+        for (idx, data_type) in self.ordering_types.iter().enumerate() {
+            fields.push(Field::new(
+                format_state_name(&self.name, &format!("array_agg_key_{idx}")),
+                DataType::List(Box::new(Field::new("item", data_type.clone(), true))),
+                false,
+            ));
+        }
+        Ok(fields)
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        std::iter::once(self.expr.clone())
+            .chain(self.ordering_req.iter().map(|sort| sort.expr.clone()))
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OrderSensitiveArrayAggAccumulator {
+    values: Vec<ScalarValue>,
+    /// The values of each sort key, one vector per key, aligned with `values`
+    ordering_values: Vec<Vec<ScalarValue>>,
+    datatype: DataType,
+    ordering_req: Vec<PhysicalSortExpr>,
+    ordering_types: Vec<DataType>,
+}
+
+impl OrderSensitiveArrayAggAccumulator {
+    /// new ordered array_agg accumulator based on the given item data type
+    /// and sort keys
+    pub fn try_new(
+        datatype: &DataType,
+        ordering_req: &[PhysicalSortExpr],
+        ordering_types: &[DataType],
+    ) -> Result<Self> {
+        if ordering_req.len() != ordering_types.len() {
+            return Err(DataFusionError::Internal(format!(
+                "array_agg expects the types of its {} sort keys, got {}",
+                ordering_req.len(),
+                ordering_types.len()
+            )));
+        }
+        Ok(Self {
+            values: vec![],
+            ordering_values: vec![vec![]; ordering_req.len()],
+            datatype: datatype.clone(),
+            ordering_req: ordering_req.to_vec(),
+            ordering_types: ordering_types.to_vec(),
+        })
+    }
+
+    /// The positions of the values, sorted by their sort keys
+    fn sorted_indices(&self) -> Result<Vec<usize>> {
+        let columns = self
+            .ordering_values
+            .iter()
+            .zip(self.ordering_req.iter())
+            .map(|(keys, sort)| {
+                Ok(SortColumn {
+                    values: ScalarValue::iter_to_array(keys.iter().cloned())?,
+                    options: Some(sort.options),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(lexsort_to_indices(&columns, None)?
+            .values()
+            .iter()
+            .map(|idx| *idx as usize)
+            .collect())
+    }
+}
+
+impl Accumulator for OrderSensitiveArrayAggAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        if values.len() != self.ordering_values.len() + 1 {
+            return Err(DataFusionError::Internal(format!(
+                "array_agg expects a value and {} sort keys, got {} arrays",
+                self.ordering_values.len(),
+                values.len()
+            )));
+        }
+        let arr = &values[0];
+        for index in 0..arr.len() {
+            self.values.push(ScalarValue::try_from_array(arr, index)?);
+            for (keys, key_arr) in self.ordering_values.iter_mut().zip(&values[1..]) {
+                keys.push(ScalarValue::try_from_array(key_arr, index)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        if states.len() != self.ordering_values.len() + 1 {
+            return Err(DataFusionError::Internal(format!(
+                "array_agg expects {} states, got {}",
+                self.ordering_values.len() + 1,
+                states.len()
+            )));
+        }
+        let targets = std::iter::once(&mut self.values)
+            .chain(self.ordering_values.iter_mut())
+            .collect::<Vec<_>>();
+        for (target, state) in targets.into_iter().zip(states) {
+            for index in 0..state.len() {
+                match ScalarValue::try_from_array(state, index)? {
+                    ScalarValue::List(Some(values), _) => {
+                        target.extend(ScalarValue::convert_array_to_scalar_vec(&values)?)
+                    }
+                    _ => {
+                        return Err(DataFusionError::Internal(
+                            "array_agg state must be list!".into(),
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let mut state = vec![ScalarValue::new_list(
+            Some(self.values.clone()),
+            self.datatype.clone(),
+        )?];
+        for (keys, data_type) in self.ordering_values.iter().zip(&self.ordering_types) {
+            state.push(ScalarValue::new_list(
+                Some(keys.clone()),
+                data_type.clone(),
+            )?);
+        }
+        Ok(state)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let values = if self.values.is_empty() || self.ordering_values.is_empty() {
+            self.values.clone()
+        } else {
+            self.sorted_indices()?
+                .into_iter()
+                .map(|idx| self.values[idx].clone())
+                .collect()
+        };
+        ScalarValue::new_list(Some(values), self.datatype.clone())
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + ScalarValue::size_of_vec(&self.values)
+            - std::mem::size_of_val(&self.values)
+            + self
+                .ordering_values
+                .iter()
+                .map(ScalarValue::size_of_vec)
+                .sum::<usize>()
+            + std::mem::size_of::<Vec<ScalarValue>>()
+                * (self.ordering_values.capacity() - self.ordering_values.len())
+            + self.datatype.size()
+            - std::mem::size_of_val(&self.datatype)
+            + std::mem::size_of::<PhysicalSortExpr>() * self.ordering_req.capacity()
+            + std::mem::size_of::<DataType>() * self.ordering_types.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+    use arrow::array::Int32Array;
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::Schema;
+
+    fn accumulator(options: SortOptions) -> Result<OrderSensitiveArrayAggAccumulator> {
+        let schema = Schema::new(vec![Field::new("b", DataType::Int32, true)]);
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: col("b", &schema)?,
+            options,
+        }];
+        OrderSensitiveArrayAggAccumulator::try_new(
+            &DataType::Int32,
+            &ordering_req,
+            &[DataType::Int32],
+        )
+    }
+
+    fn int32_list(values: Vec<Option<i32>>) -> ScalarValue {
+        ScalarValue::new_list(
+            Some(values.into_iter().map(ScalarValue::Int32).collect()),
+            DataType::Int32,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn array_agg_ordered() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3, 4]));
+        let b: ArrayRef =
+            Arc::new(Int32Array::from(vec![Some(3), None, Some(1), Some(2)]));
+
+        let cases = [
+            (false, false, vec![Some(3), Some(4), Some(1), Some(2)]),
+            (false, true, vec![Some(2), Some(3), Some(4), Some(1)]),
+            (true, true, vec![Some(2), Some(1), Some(4), Some(3)]),
+            (true, false, vec![Some(1), Some(4), Some(3), Some(2)]),
+        ];
+        for (descending, nulls_first, expected) in cases {
+            let mut acc = accumulator(SortOptions {
+                descending,
+                nulls_first,
+            })?;
+            acc.update_batch(&[a.clone(), b.clone()])?;
+            assert_eq!(acc.evaluate()?, int32_list(expected));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn array_agg_ordered_merge() -> Result<()> {
+        let options = SortOptions {
+            descending: false,
+            nulls_first: true,
+        };
+        let mut acc1 = accumulator(options)?;
+        acc1.update_batch(&[
+            Arc::new(Int32Array::from(vec![1, 2])),
+            Arc::new(Int32Array::from(vec![Some(4), Some(2)])),
+        ])?;
+        let mut acc2 = accumulator(options)?;
+        acc2.update_batch(&[
+            Arc::new(Int32Array::from(vec![3, 4])),
+            Arc::new(Int32Array::from(vec![Some(3), None])),
+        ])?;
+
+        let mut acc = accumulator(options)?;
+        for state in [acc1.state()?, acc2.state()?] {
+            let state = state
+                .iter()
+                .map(|s| s.to_array())
+                .collect::<Vec<ArrayRef>>();
+            acc.merge_batch(&state)?;
+        }
+        assert_eq!(
+            acc.evaluate()?,
+            int32_list(vec![Some(4), Some(2), Some(3), Some(1)])
+        );
+        Ok(())
+    }
+}
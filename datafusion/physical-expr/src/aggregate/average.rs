@@ -21,6 +21,7 @@ use std::any::Any;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
+use crate::aggregate::groups_accumulator::{accumulate, GroupsAccumulator};
 use crate::aggregate::row_accumulator::{
     is_row_accumulator_support_dtype, RowAccumulator,
 };
@@ -29,9 +30,10 @@ use crate::aggregate::sum::sum_batch;
 use crate::expressions::format_state_name;
 use crate::{AggregateExpr, PhysicalExpr};
 use arrow::compute;
-use arrow::datatypes::DataType;
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Float64Type, UInt64Type};
 use arrow::{
-    array::{ArrayRef, UInt64Array},
+    array::{as_primitive_array, ArrayRef, Float64Array, UInt64Array},
     datatypes::Field,
 };
 use datafusion_common::{downcast_value, ScalarValue};
@@ -54,10 +56,10 @@ impl Avg {
         name: impl Into<String>,
         data_type: DataType,
     ) -> Self {
-        // the result of avg just support FLOAT64 and Decimal data type.
+        // the result of avg just support FLOAT64, Decimal and Duration data type.
         assert!(matches!(
             data_type,
-            DataType::Float64 | DataType::Decimal128(_, _)
+            DataType::Float64 | DataType::Decimal128(_, _) | DataType::Duration(_)
         ));
         Self {
             name: name.into(),
@@ -79,7 +81,7 @@ impl AggregateExpr for Avg {
 
     fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
         Ok(Box::new(AvgAccumulator::try_new(
-            // avg is f64 or decimal
+            // avg is f64, decimal or duration
             &self.data_type,
         )?))
     }
@@ -121,6 +123,14 @@ impl AggregateExpr for Avg {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        self.data_type == DataType::Float64
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(Box::new(AvgGroupsAccumulator::new()))
+    }
+
     fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
         Ok(Box::new(AvgAccumulator::try_new(&self.data_type)?))
     }
@@ -195,6 +205,19 @@ impl Accumulator for AvgAccumulator {
                     ),
                 })
             }
+            // the average of durations is truncated to their unit
+            ScalarValue::DurationSecond(e) => Ok(ScalarValue::DurationSecond(
+                e.map(|v| v / self.count as i64),
+            )),
+            ScalarValue::DurationMillisecond(e) => Ok(ScalarValue::DurationMillisecond(
+                e.map(|v| v / self.count as i64),
+            )),
+            ScalarValue::DurationMicrosecond(e) => Ok(ScalarValue::DurationMicrosecond(
+                e.map(|v| v / self.count as i64),
+            )),
+            ScalarValue::DurationNanosecond(e) => Ok(ScalarValue::DurationNanosecond(
+                e.map(|v| v / self.count as i64),
+            )),
             _ => Err(DataFusionError::Internal(
                 "Sum should be f64 on average".to_string(),
             )),
@@ -276,6 +299,106 @@ impl RowAccumulator for AvgRowAccumulator {
     }
 }
 
+/// A [`GroupsAccumulator`] to compute the average of each group, as a
+/// `Float64`
+struct AvgGroupsAccumulator {
+    /// The number of non-null values of each group
+    counts: Vec<u64>,
+    /// The sum of the values of each group
+    sums: Vec<f64>,
+}
+
+impl AvgGroupsAccumulator {
+    pub fn new() -> Self {
+        Self {
+            counts: vec![],
+            sums: vec![],
+        }
+    }
+
+    /// Returns the sums, null for the groups without values
+    fn sums_array(&self) -> Float64Array {
+        self.sums
+            .iter()
+            .zip(&self.counts)
+            .map(|(sum, count)| (*count > 0).then_some(*sum))
+            .collect()
+    }
+}
+
+impl GroupsAccumulator for AvgGroupsAccumulator {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let values = cast(&values[0], &DataType::Float64)?;
+        self.counts.resize(total_num_groups, 0);
+        self.sums.resize(total_num_groups, 0.0);
+
+        accumulate(
+            as_primitive_array::<Float64Type>(&values),
+            group_indices,
+            |group_index, value| {
+                self.counts[group_index] += 1;
+                self.sums[group_index] += value;
+            },
+        );
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let counts = as_primitive_array::<UInt64Type>(&values[0]);
+        let sums = cast(&values[1], &DataType::Float64)?;
+        self.counts.resize(total_num_groups, 0);
+        self.sums.resize(total_num_groups, 0.0);
+
+        // counts are summed
+        accumulate(counts, group_indices, |group_index, count| {
+            self.counts[group_index] += count;
+        });
+        // sums are summed
+        accumulate(
+            as_primitive_array::<Float64Type>(&sums),
+            group_indices,
+            |group_index, sum| {
+                self.sums[group_index] += sum;
+            },
+        );
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let averages: Float64Array = self
+            .sums
+            .iter()
+            .zip(&self.counts)
+            .map(|(sum, count)| (*count > 0).then(|| sum / *count as f64))
+            .collect();
+        self.counts = vec![];
+        self.sums = vec![];
+        Ok(Arc::new(averages))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        let sums = self.sums_array();
+        let counts = UInt64Array::from(std::mem::take(&mut self.counts));
+        self.sums = vec![];
+        Ok(vec![Arc::new(counts), Arc::new(sums)])
+    }
+
+    fn size(&self) -> usize {
+        self.counts.capacity() * std::mem::size_of::<u64>()
+            + self.sums.capacity() * std::mem::size_of::<f64>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,7 +26,7 @@
 //! * Signature: see `Signature`
 //! * Return type: a function `(arg_types) -> return_type`. E.g. for min, ([f32]) -> f32, ([f64]) -> f64.
 
-use crate::{expressions, AggregateExpr, PhysicalExpr};
+use crate::{expressions, AggregateExpr, PhysicalExpr, PhysicalSortExpr};
 use arrow::datatypes::Schema;
 use datafusion_common::{DataFusionError, Result};
 use datafusion_expr::aggregate_function::return_type;
@@ -35,10 +35,13 @@ use std::sync::Arc;
 
 /// Create a physical aggregation expression.
 /// This function errors when `input_phy_exprs`' can't be coerced to a valid argument type of the aggregation function.
+/// `ordering_req` is the ordering of the input rows requested with an ORDER BY
+/// clause, which only changes the result of order sensitive aggregates like ARRAY_AGG.
 pub fn create_aggregate_expr(
     fun: &AggregateFunction,
     distinct: bool,
     input_phy_exprs: &[Arc<dyn PhysicalExpr>],
+    ordering_req: &[PhysicalSortExpr],
     input_schema: &Schema,
     name: impl Into<String>,
 ) -> Result<Arc<dyn AggregateExpr>> {
@@ -50,6 +53,10 @@ pub fn create_aggregate_expr(
         .collect::<Result<Vec<_>>>()?;
     let return_type = return_type(fun, &input_phy_types)?;
     let input_phy_exprs = input_phy_exprs.to_vec();
+    let ordering_types = ordering_req
+        .iter()
+        .map(|sort| sort.expr.data_type(input_schema))
+        .collect::<Result<Vec<_>>>()?;
 
     Ok(match (fun, distinct) {
         (AggregateFunction::Count, false) => Arc::new(expressions::Count::new(
@@ -85,11 +92,25 @@ pub fn create_aggregate_expr(
                 input_phy_types[0].clone(),
             ))
         }
+        (AggregateFunction::ArrayAgg, false) if !ordering_req.is_empty() => {
+            Arc::new(expressions::OrderSensitiveArrayAgg::new(
+                input_phy_exprs[0].clone(),
+                name,
+                input_phy_types[0].clone(),
+                ordering_req.to_vec(),
+                ordering_types,
+            ))
+        }
         (AggregateFunction::ArrayAgg, false) => Arc::new(expressions::ArrayAgg::new(
             input_phy_exprs[0].clone(),
             name,
             input_phy_types[0].clone(),
         )),
+        (AggregateFunction::ArrayAgg, true) if !ordering_req.is_empty() => {
+            return Err(DataFusionError::NotImplemented(
+                "ORDER BY not supported in ARRAY_AGG with DISTINCT".to_string(),
+            ));
+        }
         (AggregateFunction::ArrayAgg, true) => {
             Arc::new(expressions::DistinctArrayAgg::new(
                 input_phy_exprs[0].clone(),
@@ -260,13 +281,62 @@ mod tests {
     use crate::expressions::{
         try_cast, ApproxDistinct, ApproxMedian, ApproxPercentileCont, ArrayAgg, Avg,
         Correlation, Count, Covariance, DistinctArrayAgg, DistinctCount, Max, Min,
-        Stddev, Sum, Variance,
+        OrderSensitiveArrayAgg, Stddev, Sum, Variance,
     };
     use arrow::datatypes::{DataType, Field};
     use datafusion_common::ScalarValue;
     use datafusion_expr::type_coercion::aggregates::NUMERICS;
     use datafusion_expr::{aggregate_function, type_coercion, Signature};
 
+    #[test]
+    fn test_array_agg_ordering() -> Result<()> {
+        let input_schema = Schema::new(vec![
+            Field::new("c1", DataType::Int32, true),
+            Field::new("c2", DataType::Utf8, true),
+        ]);
+        let input_phy_exprs: Vec<Arc<dyn PhysicalExpr>> = vec![Arc::new(
+            expressions::Column::new_with_schema("c1", &input_schema)?,
+        )];
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: Arc::new(expressions::Column::new_with_schema("c2", &input_schema)?),
+            options: arrow::compute::SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        }];
+
+        let agg = create_aggregate_expr(
+            &AggregateFunction::ArrayAgg,
+            false,
+            &input_phy_exprs,
+            &ordering_req,
+            &input_schema,
+            "c1",
+        )?;
+        let ordered = agg
+            .as_any()
+            .downcast_ref::<OrderSensitiveArrayAgg>()
+            .unwrap();
+        assert_eq!(ordered.ordering_req(), ordering_req.as_slice());
+        assert_eq!(agg.expressions().len(), 2);
+        assert_eq!(
+            agg.state_fields()?[1].data_type(),
+            &DataType::List(Box::new(Field::new("item", DataType::Utf8, true)))
+        );
+
+        let err = create_aggregate_expr(
+            &AggregateFunction::ArrayAgg,
+            true,
+            &input_phy_exprs,
+            &ordering_req,
+            &input_schema,
+            "c1",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("ORDER BY not supported"));
+        Ok(())
+    }
+
     #[test]
     fn test_count_arragg_approx_expr() -> Result<()> {
         let funcs = vec![
@@ -1065,7 +1135,7 @@ mod tests {
                 "Invalid or wrong number of arguments passed to aggregate: '{name}'",
             )));
         }
-        create_aggregate_expr(fun, distinct, &coerced_phy_exprs, input_schema, name)
+        create_aggregate_expr(fun, distinct, &coerced_phy_exprs, &[], input_schema, name)
     }
 
     // Returns the coerced exprs for each `input_exprs`.
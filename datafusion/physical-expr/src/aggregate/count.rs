@@ -21,11 +21,12 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use crate::aggregate::groups_accumulator::{accumulate, GroupsAccumulator};
 use crate::aggregate::row_accumulator::RowAccumulator;
 use crate::{AggregateExpr, PhysicalExpr};
 use arrow::array::Int64Array;
 use arrow::compute;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, Int64Type};
 use arrow::{array::ArrayRef, datatypes::Field};
 use datafusion_common::{downcast_value, ScalarValue};
 use datafusion_common::{DataFusionError, Result};
@@ -109,6 +110,14 @@ impl AggregateExpr for Count {
         Ok(Box::new(CountRowAccumulator::new(start_index)))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        self.data_type == DataType::Int64
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(Box::new(CountGroupsAccumulator::new()))
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -211,6 +220,70 @@ impl RowAccumulator for CountRowAccumulator {
     }
 }
 
+/// A [`GroupsAccumulator`] to count the non-null values of each group
+struct CountGroupsAccumulator {
+    counts: Vec<i64>,
+}
+
+impl CountGroupsAccumulator {
+    pub fn new() -> Self {
+        Self { counts: vec![] }
+    }
+}
+
+impl GroupsAccumulator for CountGroupsAccumulator {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let array = &values[0];
+        self.counts.resize(total_num_groups, 0);
+
+        if array.null_count() == 0 {
+            for group_index in group_indices {
+                self.counts[*group_index] += 1;
+            }
+        } else {
+            for (i, group_index) in group_indices.iter().enumerate() {
+                if array.is_valid(i) {
+                    self.counts[*group_index] += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let counts = downcast_value!(values[0], Int64Array);
+        self.counts.resize(total_num_groups, 0);
+
+        accumulate::<Int64Type, _>(counts, group_indices, |group_index, count| {
+            self.counts[group_index] += count;
+        });
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let counts = std::mem::take(&mut self.counts);
+        Ok(Arc::new(Int64Array::from(counts)))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn size(&self) -> usize {
+        self.counts.capacity() * std::mem::size_of::<i64>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,7 +134,9 @@ impl DistinctCountAccumulator {
         let col_values = states
             .iter()
             .map(|state| match state {
-                ScalarValue::List(Some(values), _) => Ok(values),
+                ScalarValue::List(Some(values), _) => {
+                    ScalarValue::convert_array_to_scalar_vec(values)
+                }
                 _ => Err(DataFusionError::Internal(format!(
                     "Unexpected accumulator state {state:?}"
                 ))),
@@ -177,24 +179,8 @@ impl Accumulator for DistinctCountAccumulator {
         })
     }
     fn state(&self) -> Result<Vec<ScalarValue>> {
-        let mut cols_out = self
-            .state_data_types
-            .iter()
-            .map(|state_data_type| {
-                ScalarValue::new_list(Some(Vec::new()), state_data_type.clone())
-            })
-            .collect::<Vec<_>>();
-
-        let mut cols_vec = cols_out
-            .iter_mut()
-            .map(|c| match c {
-                ScalarValue::List(Some(ref mut v), _) => Ok(v),
-                t => Err(DataFusionError::Internal(format!(
-                    "cols_out should only consist of ScalarValue::List. {t:?} is found"
-                ))),
-            })
-            .into_iter()
-            .collect::<Result<Vec<_>>>()?;
+        let mut cols_vec =
+            vec![Vec::with_capacity(self.values.len()); self.state_data_types.len()];
 
         self.values.iter().for_each(|distinct_values| {
             distinct_values.0.iter().enumerate().for_each(
@@ -204,7 +190,13 @@ impl Accumulator for DistinctCountAccumulator {
             )
         });
 
-        Ok(cols_out.into_iter().collect())
+        cols_vec
+            .into_iter()
+            .zip(self.state_data_types.iter())
+            .map(|(values, state_data_type)| {
+                ScalarValue::new_list(Some(values), state_data_type.clone())
+            })
+            .collect()
     }
 
     fn evaluate(&self) -> Result<ScalarValue> {
@@ -261,7 +253,8 @@ mod tests {
             match $LIST {
                 ScalarValue::List(None, _) => None,
                 ScalarValue::List(Some(scalar_values), _) => {
-                    let vec = scalar_values
+                    let vec = ScalarValue::convert_array_to_scalar_vec(scalar_values)
+                        .unwrap()
                         .iter()
                         .map(|scalar_value| match scalar_value {
                             ScalarValue::$DATA_TYPE(value) => *value,
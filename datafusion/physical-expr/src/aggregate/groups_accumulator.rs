@@ -0,0 +1,224 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Accumulator of the states of all the groups of a hash aggregation
+
+use arrow::array::{Array, ArrayRef, PrimitiveArray};
+use arrow::datatypes::ArrowPrimitiveType;
+use datafusion_common::Result;
+
+/// Columnar accumulator, which stores the states of all the groups of a hash
+/// aggregation in flat vectors indexed by group.
+///
+/// Unlike the [`datafusion_expr::Accumulator`], of which there is one
+/// instance per group, a single [`GroupsAccumulator`] is updated with all the
+/// rows of a batch at once, along with the group index of each row. This
+/// avoids slicing the input of each group, and lets the state be updated in
+/// a tight loop specialized for the input type.
+///
+/// For example, when evaluating `SELECT a, sum(b) FROM t GROUP BY a`, the
+/// groups of the values of `a` are numbered from 0 as they are first seen,
+/// and the accumulator for `sum(b)` holds one sum per group:
+///
+/// ```text
+///  a   b   group_indices           sums
+/// ---+---+---------------         +----+
+///  x | 1 |       0         ----->  |  4 |  group 0 (x)
+///  y | 2 |       1         ----->  |  2 |  group 1 (y)
+///  x | 3 |       0                 +----+
+/// ```
+pub trait GroupsAccumulator: Send {
+    /// Updates the states from the rows of `values`, where `group_indices`
+    /// holds the group index of each row.
+    ///
+    /// `total_num_groups` is the number of groups seen so far, so the states
+    /// must be resized to hold that many groups; it is greater than all of
+    /// `group_indices`.
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()>;
+
+    /// Merges the intermediate states `values`, as returned by
+    /// [`state`](Self::state), into the states, where `group_indices` holds
+    /// the group index of each row.
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()>;
+
+    /// Returns the final value of each group, in group order, and resets the
+    /// states.
+    fn evaluate(&mut self) -> Result<ArrayRef>;
+
+    /// Returns the intermediate state of each group, in group order, as one
+    /// array per [state field](crate::AggregateExpr::state_fields), and
+    /// resets the states.
+    fn state(&mut self) -> Result<Vec<ArrayRef>>;
+
+    /// Returns the memory used by the states, in bytes
+    fn size(&self) -> usize;
+}
+
+/// Calls `f` with the group index and the value of each non-null value of
+/// `values`
+pub(crate) fn accumulate<T, F>(
+    values: &PrimitiveArray<T>,
+    group_indices: &[usize],
+    mut f: F,
+) where
+    T: ArrowPrimitiveType,
+    F: FnMut(usize, T::Native),
+{
+    if values.null_count() == 0 {
+        for (group_index, value) in group_indices.iter().zip(values.values().iter()) {
+            f(*group_index, *value)
+        }
+    } else {
+        for (i, group_index) in group_indices.iter().enumerate() {
+            if values.is_valid(i) {
+                f(*group_index, values.value(i))
+            }
+        }
+    }
+}
+
+/// Creates the boxed [`GroupsAccumulator`] `$accumulator`, generic over
+/// the [`ArrowPrimitiveType`] of the numeric `$data_type`, with the
+/// constructor arguments `$args`
+macro_rules! instantiate_primitive {
+    ($data_type:expr, $accumulator:ident $(, $args:expr)*) => {{
+        use arrow::datatypes::*;
+        let accumulator: Box<dyn GroupsAccumulator> = match $data_type {
+            DataType::Int8 => Box::new($accumulator::<Int8Type>::new($($args),*)),
+            DataType::Int16 => Box::new($accumulator::<Int16Type>::new($($args),*)),
+            DataType::Int32 => Box::new($accumulator::<Int32Type>::new($($args),*)),
+            DataType::Int64 => Box::new($accumulator::<Int64Type>::new($($args),*)),
+            DataType::UInt8 => Box::new($accumulator::<UInt8Type>::new($($args),*)),
+            DataType::UInt16 => Box::new($accumulator::<UInt16Type>::new($($args),*)),
+            DataType::UInt32 => Box::new($accumulator::<UInt32Type>::new($($args),*)),
+            DataType::UInt64 => Box::new($accumulator::<UInt64Type>::new($($args),*)),
+            DataType::Float32 => Box::new($accumulator::<Float32Type>::new($($args),*)),
+            DataType::Float64 => Box::new($accumulator::<Float64Type>::new($($args),*)),
+            other => {
+                return Err(DataFusionError::Internal(format!(
+                    "{} does not support {other:?}",
+                    stringify!($accumulator)
+                )))
+            }
+        };
+        accumulator
+    }};
+}
+pub(crate) use instantiate_primitive;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{col, Avg, Count, Max, Min, Sum};
+    use crate::AggregateExpr;
+    use arrow::array::{Float64Array, Int32Array, Int64Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    /// Aggregates the two halves of `values` into partial states, which are
+    /// then merged and evaluated
+    fn aggregate(
+        expr: &dyn AggregateExpr,
+        values: &ArrayRef,
+        group_indices: &[usize],
+    ) -> Result<ArrayRef> {
+        assert!(expr.groups_accumulator_supported());
+        let num_groups = group_indices.iter().max().unwrap() + 1;
+        let mid = values.len() / 2;
+
+        let mut states = vec![];
+        for (offset, len) in [(0, mid), (mid, values.len() - mid)] {
+            let mut accumulator = expr.create_groups_accumulator()?;
+            accumulator.update_batch(
+                &[values.slice(offset, len)],
+                &group_indices[offset..offset + len],
+                num_groups,
+            )?;
+            states.push(accumulator.state()?);
+        }
+
+        let mut accumulator = expr.create_groups_accumulator()?;
+        let all_groups: Vec<_> = (0..num_groups).collect();
+        for state in states {
+            accumulator.merge_batch(&state, &all_groups, num_groups)?;
+        }
+        accumulator.evaluate()
+    }
+
+    #[test]
+    fn builtin_groups_accumulators() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let a = col("a", &schema)?;
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(2),
+            None,
+            Some(4),
+            None,
+            None,
+        ]));
+        // group 2 only has nulls
+        let group_indices = [0, 1, 0, 1, 2, 2];
+
+        let cases: Vec<(Arc<dyn AggregateExpr>, ArrayRef)> = vec![
+            (
+                Arc::new(Sum::new(a.clone(), "SUM(a)", DataType::Int64)),
+                Arc::new(Int64Array::from(vec![Some(1), Some(6), None])),
+            ),
+            (
+                Arc::new(Count::new(a.clone(), "COUNT(a)", DataType::Int64)),
+                Arc::new(Int64Array::from(vec![1, 2, 0])),
+            ),
+            (
+                Arc::new(Min::new(a.clone(), "MIN(a)", DataType::Int32)),
+                Arc::new(Int32Array::from(vec![Some(1), Some(2), None])),
+            ),
+            (
+                Arc::new(Max::new(a.clone(), "MAX(a)", DataType::Int32)),
+                Arc::new(Int32Array::from(vec![Some(1), Some(4), None])),
+            ),
+            (
+                Arc::new(Avg::new(a, "AVG(a)", DataType::Float64)),
+                Arc::new(Float64Array::from(vec![Some(1.0), Some(3.0), None])),
+            ),
+        ];
+
+        for (expr, expected) in cases {
+            let result = aggregate(expr.as_ref(), &values, &group_indices)?;
+            assert_eq!(&result, &expected, "{}", expr.name());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_type() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Utf8, true)]);
+        let expr = Max::new(col("a", &schema).unwrap(), "MAX(a)", DataType::Utf8);
+        assert!(!expr.groups_accumulator_supported());
+        assert!(expr.create_groups_accumulator().is_err());
+    }
+}
@@ -103,7 +103,7 @@ struct MedianAccumulator {
 impl Accumulator for MedianAccumulator {
     fn state(&self) -> Result<Vec<ScalarValue>> {
         let state =
-            ScalarValue::new_list(Some(self.all_values.clone()), self.data_type.clone());
+            ScalarValue::new_list(Some(self.all_values.clone()), self.data_type.clone())?;
         Ok(vec![state])
     }
 
@@ -128,8 +128,9 @@ impl Accumulator for MedianAccumulator {
         assert!(matches!(array.data_type(), DataType::List(_)));
         for index in 0..array.len() {
             match ScalarValue::try_from_array(array, index)? {
-                ScalarValue::List(Some(mut values), _) => {
-                    self.all_values.append(&mut values);
+                ScalarValue::List(Some(values), _) => {
+                    self.all_values
+                        .extend(ScalarValue::convert_array_to_scalar_vec(&values)?);
                 }
                 ScalarValue::List(None, _) => {} // skip empty state
                 v => {
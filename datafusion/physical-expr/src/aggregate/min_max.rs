@@ -18,6 +18,7 @@
 //! Defines physical expressions that can evaluated at runtime during query execution
 
 use std::any::Any;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -26,12 +27,13 @@ use arrow::compute;
 use arrow::datatypes::{DataType, TimeUnit};
 use arrow::{
     array::{
-        ArrayRef, Date32Array, Date64Array, Float32Array, Float64Array, Int16Array,
-        Int32Array, Int64Array, Int8Array, LargeStringArray, StringArray,
-        Time32MillisecondArray, Time32SecondArray, Time64MicrosecondArray,
-        Time64NanosecondArray, TimestampMicrosecondArray, TimestampMillisecondArray,
-        TimestampNanosecondArray, TimestampSecondArray, UInt16Array, UInt32Array,
-        UInt64Array, UInt8Array,
+        ArrayRef, Date32Array, Date64Array, DurationMicrosecondArray,
+        DurationMillisecondArray, DurationNanosecondArray, DurationSecondArray,
+        Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        LargeStringArray, StringArray, Time32MillisecondArray, Time32SecondArray,
+        Time64MicrosecondArray, Time64NanosecondArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+        UInt16Array, UInt32Array, UInt64Array, UInt8Array,
     },
     datatypes::Field,
 };
@@ -39,12 +41,17 @@ use datafusion_common::ScalarValue;
 use datafusion_common::{downcast_value, DataFusionError, Result};
 use datafusion_expr::Accumulator;
 
+use crate::aggregate::groups_accumulator::{
+    accumulate, instantiate_primitive, GroupsAccumulator,
+};
 use crate::aggregate::row_accumulator::{
     is_row_accumulator_support_dtype, RowAccumulator,
 };
 use crate::expressions::format_state_name;
 use arrow::array::Array;
-use arrow::array::Decimal128Array;
+use arrow::array::{as_primitive_array, Decimal128Array, PrimitiveArray};
+use arrow::compute::cast;
+use arrow::datatypes::ArrowPrimitiveType;
 use datafusion_row::accessor::RowAccessor;
 
 use super::moving_min_max;
@@ -138,6 +145,19 @@ impl AggregateExpr for Max {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        is_row_accumulator_support_dtype(&self.data_type)
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(instantiate_primitive!(
+            &self.data_type,
+            MinMaxGroupsAccumulator,
+            self.data_type.clone(),
+            Ordering::Greater
+        ))
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -152,7 +172,7 @@ macro_rules! typed_min_max_batch_string {
     ($VALUES:expr, $ARRAYTYPE:ident, $SCALAR:ident, $OP:ident) => {{
         let array = downcast_value!($VALUES, $ARRAYTYPE);
         let value = compute::$OP(array);
-        let value = value.and_then(|e| Some(e.to_string()));
+        let value = value.map(Arc::from);
         ScalarValue::$SCALAR(value)
     }};
 }
@@ -283,6 +303,33 @@ macro_rules! min_max_batch {
                     $OP
                 )
             }
+            DataType::Duration(TimeUnit::Second) => {
+                typed_min_max_batch!($VALUES, DurationSecondArray, DurationSecond, $OP)
+            }
+            DataType::Duration(TimeUnit::Millisecond) => {
+                typed_min_max_batch!(
+                    $VALUES,
+                    DurationMillisecondArray,
+                    DurationMillisecond,
+                    $OP
+                )
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                typed_min_max_batch!(
+                    $VALUES,
+                    DurationMicrosecondArray,
+                    DurationMicrosecond,
+                    $OP
+                )
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                typed_min_max_batch!(
+                    $VALUES,
+                    DurationNanosecondArray,
+                    DurationNanosecond,
+                    $OP
+                )
+            }
             other => {
                 // This should have been handled before
                 return Err(DataFusionError::Internal(format!(
@@ -469,6 +516,30 @@ macro_rules! min_max {
             ) => {
                 typed_min_max!(lhs, rhs, Time64Nanosecond, $OP)
             }
+            (
+                ScalarValue::DurationSecond(lhs),
+                ScalarValue::DurationSecond(rhs),
+            ) => {
+                typed_min_max!(lhs, rhs, DurationSecond, $OP)
+            }
+            (
+                ScalarValue::DurationMillisecond(lhs),
+                ScalarValue::DurationMillisecond(rhs),
+            ) => {
+                typed_min_max!(lhs, rhs, DurationMillisecond, $OP)
+            }
+            (
+                ScalarValue::DurationMicrosecond(lhs),
+                ScalarValue::DurationMicrosecond(rhs),
+            ) => {
+                typed_min_max!(lhs, rhs, DurationMicrosecond, $OP)
+            }
+            (
+                ScalarValue::DurationNanosecond(lhs),
+                ScalarValue::DurationNanosecond(rhs),
+            ) => {
+                typed_min_max!(lhs, rhs, DurationNanosecond, $OP)
+            }
             e => {
                 return Err(DataFusionError::Internal(format!(
                     "MIN/MAX is not expected to receive scalars of incompatible types {:?}",
@@ -756,6 +827,19 @@ impl AggregateExpr for Min {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        is_row_accumulator_support_dtype(&self.data_type)
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(instantiate_primitive!(
+            &self.data_type,
+            MinMaxGroupsAccumulator,
+            self.data_type.clone(),
+            Ordering::Less
+        ))
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -908,6 +992,95 @@ impl RowAccumulator for MinRowAccumulator {
     }
 }
 
+/// A [`GroupsAccumulator`] to compute the minimum or maximum value of each
+/// group, whose state is the same as its result
+struct MinMaxGroupsAccumulator<T: ArrowPrimitiveType> {
+    data_type: DataType,
+    /// The ordering of a value relative to the current one for it to
+    /// replace it: `Less` for min and `Greater` for max
+    replace_if: Ordering,
+    /// The current value of each group
+    values: Vec<T::Native>,
+    /// Whether each group has seen a non-null value
+    seen: Vec<bool>,
+}
+
+impl<T> MinMaxGroupsAccumulator<T>
+where
+    T: ArrowPrimitiveType,
+    T::Native: PartialOrd,
+{
+    fn new(data_type: DataType, replace_if: Ordering) -> Self {
+        Self {
+            data_type,
+            replace_if,
+            values: vec![],
+            seen: vec![],
+        }
+    }
+}
+
+impl<T> GroupsAccumulator for MinMaxGroupsAccumulator<T>
+where
+    T: ArrowPrimitiveType,
+    T::Native: PartialOrd,
+{
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let values = cast(&values[0], &self.data_type)?;
+        self.values.resize(total_num_groups, T::default_value());
+        self.seen.resize(total_num_groups, false);
+
+        let replace_if = Some(self.replace_if);
+        accumulate(
+            as_primitive_array::<T>(&values),
+            group_indices,
+            |group_index, value| {
+                let current = &mut self.values[group_index];
+                let seen = &mut self.seen[group_index];
+                if !*seen || value.partial_cmp(current) == replace_if {
+                    *current = value;
+                    *seen = true;
+                }
+            },
+        );
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        // min(min1, min2, min3, ...) = min of the mins
+        self.update_batch(values, group_indices, total_num_groups)
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let values = std::mem::take(&mut self.values);
+        let seen = std::mem::take(&mut self.seen);
+        let array: PrimitiveArray<T> = values
+            .into_iter()
+            .zip(seen)
+            .map(|(value, seen)| seen.then_some(value))
+            .collect();
+        Ok(Arc::new(array))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn size(&self) -> usize {
+        self.values.capacity() * std::mem::size_of::<T::Native>() + self.seen.capacity()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1101,12 +1274,7 @@ mod tests {
     #[test]
     fn max_utf8() -> Result<()> {
         let a: ArrayRef = Arc::new(StringArray::from(vec!["d", "a", "c", "b"]));
-        generic_test_op!(
-            a,
-            DataType::Utf8,
-            Max,
-            ScalarValue::Utf8(Some("d".to_string()))
-        )
+        generic_test_op!(a, DataType::Utf8, Max, ScalarValue::Utf8(Some("d".into())))
     }
 
     #[test]
@@ -1116,19 +1284,14 @@ mod tests {
             a,
             DataType::LargeUtf8,
             Max,
-            ScalarValue::LargeUtf8(Some("d".to_string()))
+            ScalarValue::LargeUtf8(Some("d".into()))
         )
     }
 
     #[test]
     fn min_utf8() -> Result<()> {
         let a: ArrayRef = Arc::new(StringArray::from(vec!["d", "a", "c", "b"]));
-        generic_test_op!(
-            a,
-            DataType::Utf8,
-            Min,
-            ScalarValue::Utf8(Some("a".to_string()))
-        )
+        generic_test_op!(a, DataType::Utf8, Min, ScalarValue::Utf8(Some("a".into())))
     }
 
     #[test]
@@ -1138,7 +1301,7 @@ mod tests {
             a,
             DataType::LargeUtf8,
             Min,
-            ScalarValue::LargeUtf8(Some("a".to_string()))
+            ScalarValue::LargeUtf8(Some("a".into()))
         )
     }
 
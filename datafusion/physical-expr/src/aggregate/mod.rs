@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::aggregate::groups_accumulator::GroupsAccumulator;
 use crate::aggregate::row_accumulator::RowAccumulator;
 use crate::PhysicalExpr;
 use arrow::datatypes::Field;
@@ -30,12 +31,14 @@ pub(crate) mod approx_percentile_cont;
 pub(crate) mod approx_percentile_cont_with_weight;
 pub(crate) mod array_agg;
 pub(crate) mod array_agg_distinct;
+pub(crate) mod array_agg_ordered;
 pub(crate) mod average;
 pub(crate) mod correlation;
 pub(crate) mod count;
 pub(crate) mod count_distinct;
 pub(crate) mod covariance;
 pub(crate) mod grouping;
+pub mod groups_accumulator;
 pub(crate) mod median;
 #[macro_use]
 pub(crate) mod min_max;
@@ -108,6 +111,23 @@ pub trait AggregateExpr: Send + Sync + Debug {
         )))
     }
 
+    /// If the aggregate expression has a [`GroupsAccumulator`]
+    fn groups_accumulator_supported(&self) -> bool {
+        false
+    }
+
+    /// GroupsAccumulator to update the states of all the groups of a hash
+    /// aggregation at once, in columnar form.
+    ///
+    /// We recommend implementing `GroupsAccumulator` for aggregates whose
+    /// state is made of a few values of primitive types, as it avoids
+    /// creating an `Accumulator` per group and slicing the input by group.
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Err(DataFusionError::NotImplemented(format!(
+            "GroupsAccumulator hasn't been implemented for {self:?} yet"
+        )))
+    }
+
     /// Construct an expression that calculates the aggregate in reverse.
     /// Typically the "reverse" expression is itself (e.g. SUM, COUNT).
     /// For aggregates that do not support calculation in reverse,
@@ -23,24 +23,30 @@ use std::sync::Arc;
 
 use crate::{AggregateExpr, PhysicalExpr};
 use arrow::compute;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, TimeUnit};
 use arrow::{
     array::{
-        ArrayRef, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array,
-        Int8Array, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+        ArrayRef, DurationMicrosecondArray, DurationMillisecondArray,
+        DurationNanosecondArray, DurationSecondArray, Float32Array, Float64Array,
+        Int16Array, Int32Array, Int64Array, Int8Array, UInt16Array, UInt32Array,
+        UInt64Array, UInt8Array,
     },
     datatypes::Field,
 };
 use datafusion_common::{downcast_value, DataFusionError, Result, ScalarValue};
 use datafusion_expr::Accumulator;
 
+use crate::aggregate::groups_accumulator::{
+    accumulate, instantiate_primitive, GroupsAccumulator,
+};
 use crate::aggregate::row_accumulator::{
     is_row_accumulator_support_dtype, RowAccumulator,
 };
 use crate::expressions::format_state_name;
 use arrow::array::Array;
-use arrow::array::Decimal128Array;
+use arrow::array::{as_primitive_array, Decimal128Array, PrimitiveArray};
 use arrow::compute::cast;
+use arrow::datatypes::{ArrowNativeTypeOp, ArrowPrimitiveType};
 use datafusion_row::accessor::RowAccessor;
 
 /// SUM aggregate expression
@@ -127,6 +133,18 @@ impl AggregateExpr for Sum {
         )))
     }
 
+    fn groups_accumulator_supported(&self) -> bool {
+        is_row_accumulator_support_dtype(&self.data_type)
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(instantiate_primitive!(
+            &self.data_type,
+            SumGroupsAccumulator,
+            self.data_type.clone()
+        ))
+    }
+
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
         Some(Arc::new(self.clone()))
     }
@@ -195,6 +213,18 @@ pub(crate) fn sum_batch(values: &ArrayRef, sum_type: &DataType) -> Result<Scalar
         DataType::UInt32 => typed_sum_delta_batch!(values, UInt32Array, UInt32),
         DataType::UInt16 => typed_sum_delta_batch!(values, UInt16Array, UInt16),
         DataType::UInt8 => typed_sum_delta_batch!(values, UInt8Array, UInt8),
+        DataType::Duration(TimeUnit::Second) => {
+            typed_sum_delta_batch!(values, DurationSecondArray, DurationSecond)
+        }
+        DataType::Duration(TimeUnit::Millisecond) => {
+            typed_sum_delta_batch!(values, DurationMillisecondArray, DurationMillisecond)
+        }
+        DataType::Duration(TimeUnit::Microsecond) => {
+            typed_sum_delta_batch!(values, DurationMicrosecondArray, DurationMicrosecond)
+        }
+        DataType::Duration(TimeUnit::Nanosecond) => {
+            typed_sum_delta_batch!(values, DurationNanosecondArray, DurationNanosecond)
+        }
         e => {
             return Err(DataFusionError::Internal(format!(
                 "Sum is not expected to receive the type {e:?}"
@@ -323,6 +353,91 @@ impl RowAccumulator for SumRowAccumulator {
     }
 }
 
+/// A [`GroupsAccumulator`] to compute the sum of each group
+pub(crate) struct SumGroupsAccumulator<T: ArrowPrimitiveType> {
+    data_type: DataType,
+    /// The sum of each group
+    sums: Vec<T::Native>,
+    /// The number of non-null values of each group, which have a null sum
+    /// when zero
+    counts: Vec<u64>,
+}
+
+impl<T: ArrowPrimitiveType> SumGroupsAccumulator<T> {
+    pub(crate) fn new(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            sums: vec![],
+            counts: vec![],
+        }
+    }
+
+    /// Returns the sums, null for the groups without values, and resets
+    /// them
+    pub(crate) fn take_sums(&mut self) -> PrimitiveArray<T> {
+        let sums = std::mem::take(&mut self.sums);
+        sums.into_iter()
+            .zip(&self.counts)
+            .map(|(sum, count)| (*count > 0).then_some(sum))
+            .collect()
+    }
+}
+
+impl<T> GroupsAccumulator for SumGroupsAccumulator<T>
+where
+    T: ArrowPrimitiveType,
+    T::Native: ArrowNativeTypeOp,
+{
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        let values = cast(&values[0], &self.data_type)?;
+        self.sums.resize(total_num_groups, T::default_value());
+        self.counts.resize(total_num_groups, 0);
+
+        accumulate(
+            as_primitive_array::<T>(&values),
+            group_indices,
+            |group_index, value| {
+                let sum = &mut self.sums[group_index];
+                *sum = sum.add_wrapping(value);
+                self.counts[group_index] += 1;
+            },
+        );
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        total_num_groups: usize,
+    ) -> Result<()> {
+        // sum(sum1, sum2, sum3, ...) = sum1 + sum2 + sum3 + ...
+        self.update_batch(values, group_indices, total_num_groups)
+    }
+
+    fn evaluate(&mut self) -> Result<ArrayRef> {
+        let sums = self.take_sums();
+        self.counts = vec![];
+        Ok(Arc::new(sums))
+    }
+
+    fn state(&mut self) -> Result<Vec<ArrayRef>> {
+        let sums = self.take_sums();
+        let counts = UInt64Array::from(std::mem::take(&mut self.counts));
+        Ok(vec![Arc::new(sums), Arc::new(counts)])
+    }
+
+    fn size(&self) -> usize {
+        self.sums.capacity() * std::mem::size_of::<T::Native>()
+            + self.counts.capacity() * std::mem::size_of::<u64>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
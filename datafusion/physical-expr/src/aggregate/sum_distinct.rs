@@ -117,7 +117,9 @@ impl DistinctSumAccumulator {
         }
 
         states.iter().try_for_each(|state| match state {
-            ScalarValue::List(Some(values), _) => self.update(values.as_ref()),
+            ScalarValue::List(Some(values), _) => {
+                self.update(&ScalarValue::convert_array_to_scalar_vec(values)?)
+            }
             _ => Err(DataFusionError::Internal(format!(
                 "Unexpected accumulator state {state:?}"
             ))),
@@ -137,7 +139,7 @@ impl Accumulator for DistinctSumAccumulator {
             vec![ScalarValue::new_list(
                 Some(distinct_values),
                 self.data_type.clone(),
-            )]
+            )?]
         };
         Ok(state_out)
     }
@@ -27,10 +27,13 @@
 //! [TDigest sketch algorithm]: https://arxiv.org/abs/1902.04023
 //! [Facebook's Folly TDigest]: https://github.com/facebook/folly/blob/main/folly/stats/TDigest.h
 
+use arrow::array::Float64Array;
 use arrow::datatypes::DataType;
+use datafusion_common::cast::as_float64_array;
 use datafusion_common::Result;
 use datafusion_common::ScalarValue;
 use std::cmp::Ordering;
+use std::sync::Arc;
 
 pub const DEFAULT_MAX_SIZE: usize = 100;
 
@@ -566,11 +569,11 @@ impl TDigest {
     /// [`TDigest`].
     pub(crate) fn to_scalar_state(&self) -> Vec<ScalarValue> {
         // Gather up all the centroids
-        let centroids: Vec<_> = self
+        let centroids: Float64Array = self
             .centroids
             .iter()
             .flat_map(|c| [c.mean(), c.weight()])
-            .map(|v| ScalarValue::Float64(Some(v)))
+            .map(Some)
             .collect();
 
         vec![
@@ -579,7 +582,10 @@ impl TDigest {
             ScalarValue::Float64(Some(self.count)),
             ScalarValue::Float64(Some(self.max)),
             ScalarValue::Float64(Some(self.min)),
-            ScalarValue::new_list(Some(centroids), DataType::Float64),
+            ScalarValue::new_list_from_array(
+                Some(Arc::new(centroids)),
+                DataType::Float64,
+            ),
         ]
     }
 
@@ -600,10 +606,14 @@ impl TDigest {
         };
 
         let centroids: Vec<_> = match &state[5] {
-            ScalarValue::List(Some(c), f) if *f.data_type() == DataType::Float64 => c
-                .chunks(2)
-                .map(|v| Centroid::new(cast_scalar_f64!(v[0]), cast_scalar_f64!(v[1])))
-                .collect(),
+            ScalarValue::List(Some(c), f) if *f.data_type() == DataType::Float64 => {
+                as_float64_array(c)
+                    .expect("invalid centroids array")
+                    .values()
+                    .chunks(2)
+                    .map(|v| Centroid::new(v[0], v[1]))
+                    .collect()
+            }
             v => panic!("invalid centroids type {v:?}"),
         };
 
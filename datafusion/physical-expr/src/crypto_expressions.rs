@@ -70,12 +70,10 @@ fn digest_process(
             ))),
         },
         ColumnarValue::Scalar(scalar) => match scalar {
-            ScalarValue::Utf8(a) | ScalarValue::LargeUtf8(a) => {
-                Ok(digest_algorithm
-                    .digest_scalar(a.as_ref().map(|s: &String| s.as_bytes())))
-            }
+            ScalarValue::Utf8(a) | ScalarValue::LargeUtf8(a) => Ok(digest_algorithm
+                .digest_scalar(a.as_ref().map(|s: &Arc<str>| s.as_bytes()))),
             ScalarValue::Binary(a) | ScalarValue::LargeBinary(a) => Ok(digest_algorithm
-                .digest_scalar(a.as_ref().map(|v: &Vec<u8>| v.as_slice()))),
+                .digest_scalar(a.as_ref().map(|v: &Arc<[u8]>| v.as_ref()))),
             other => Err(DataFusionError::Internal(format!(
                 "Unsupported data type {other:?} for function {digest_algorithm}",
             ))),
@@ -104,7 +102,7 @@ macro_rules! digest_to_scalar {
         ScalarValue::Binary($INPUT.as_ref().map(|v| {
             let mut digest = $METHOD::default();
             digest.update(v);
-            digest.finalize().as_slice().to_vec()
+            digest.finalize().as_slice().into()
         }))
     }};
 }
@@ -123,7 +121,7 @@ impl DigestAlgorithm {
             Self::Blake3 => ScalarValue::Binary(value.map(|v| {
                 let mut digest = Blake3::default();
                 digest.update(v);
-                Blake3::finalize(&digest).as_bytes().to_vec()
+                Blake3::finalize(&digest).as_bytes().as_slice().into()
             })),
         })
     }
@@ -281,7 +279,7 @@ pub fn md5(args: &[ColumnarValue]) -> Result<ColumnarValue> {
             ColumnarValue::Array(Arc::new(string_array))
         }
         ColumnarValue::Scalar(ScalarValue::Binary(opt)) => {
-            ColumnarValue::Scalar(ScalarValue::Utf8(opt.map(hex_encode::<_>)))
+            ColumnarValue::Scalar(ScalarValue::Utf8(opt.map(|v| hex_encode(v).into())))
         }
         _ => {
             return Err(DataFusionError::Internal(
@@ -134,6 +134,41 @@ pub fn to_timestamp(args: &[ColumnarValue]) -> Result<ColumnarValue> {
     )
 }
 
+/// try_to_timestamp SQL function: like `to_timestamp`, but returns NULL for
+/// the strings that aren't valid timestamps
+pub fn try_to_timestamp(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let parse = |s: Option<&str>| s.and_then(|s| string_to_timestamp_nanos(s).ok());
+    match &args[0] {
+        ColumnarValue::Array(a) => {
+            let array: TimestampNanosecondArray = match a.data_type() {
+                DataType::Utf8 => as_generic_string_array::<i32>(a)?
+                    .iter()
+                    .map(parse)
+                    .collect(),
+                DataType::LargeUtf8 => as_generic_string_array::<i64>(a)?
+                    .iter()
+                    .map(parse)
+                    .collect(),
+                other => {
+                    return Err(DataFusionError::Internal(format!(
+                        "Unsupported data type {other:?} for function try_to_timestamp",
+                    )))
+                }
+            };
+            Ok(ColumnarValue::Array(Arc::new(array)))
+        }
+        ColumnarValue::Scalar(ScalarValue::Utf8(s) | ScalarValue::LargeUtf8(s)) => {
+            Ok(ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(
+                parse(s.as_deref()),
+                None,
+            )))
+        }
+        ColumnarValue::Scalar(other) => Err(DataFusionError::Internal(format!(
+            "Unsupported data type {other:?} for function try_to_timestamp"
+        ))),
+    }
+}
+
 /// to_timestamp_millis SQL function
 pub fn to_timestamp_millis(args: &[ColumnarValue]) -> Result<ColumnarValue> {
     handle::<TimestampMillisecondType, _, TimestampMillisecondType>(
@@ -575,6 +610,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn try_to_timestamp_invalid_strings() -> Result<()> {
+        let mut string_builder = StringBuilder::with_capacity(3, 1024);
+        string_builder.append_value("2020-09-08T13:42:29.190855Z");
+        string_builder.append_value("not a timestamp");
+        string_builder.append_null();
+
+        let string_array =
+            ColumnarValue::Array(Arc::new(string_builder.finish()) as ArrayRef);
+        let parsed_timestamps = try_to_timestamp(&[string_array])?.into_array(3);
+        let expected_timestamps =
+            TimestampNanosecondArray::from(vec![Some(1599572549190855000), None, None]);
+        assert_eq!(
+            &expected_timestamps as &dyn Array,
+            parsed_timestamps.as_ref()
+        );
+
+        let scalar = ColumnarValue::Scalar(ScalarValue::Utf8(Some("yesterday".into())));
+        match try_to_timestamp(&[scalar])? {
+            ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(None, None)) => {}
+            other => panic!("Expected a null timestamp, got {other:?}"),
+        }
+        Ok(())
+    }
+
     #[test]
     fn date_trunc_test() {
         let cases = vec![
@@ -15,17 +15,22 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::expressions::Column;
+use crate::expressions::{Column, Literal};
+use crate::PhysicalExpr;
 
 use arrow::datatypes::SchemaRef;
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::Arc;
 
-/// Equivalence Properties is a vec of EquivalentClass.
+/// Equivalence Properties is a vec of EquivalentClass, along with the
+/// columns that are known to be constant.
 #[derive(Debug, Clone)]
 pub struct EquivalenceProperties {
     classes: Vec<EquivalentClass>,
+    /// Columns that have the same value in all tuples, e.g. `a` after `a = 1`
+    constants: Vec<Column>,
     schema: SchemaRef,
 }
 
@@ -33,6 +38,7 @@ impl EquivalenceProperties {
     pub fn new(schema: SchemaRef) -> Self {
         EquivalenceProperties {
             classes: vec![],
+            constants: vec![],
             schema,
         }
     }
@@ -41,6 +47,10 @@ impl EquivalenceProperties {
         &self.classes
     }
 
+    pub fn constants(&self) -> &[Column] {
+        &self.constants
+    }
+
     pub fn schema(&self) -> SchemaRef {
         self.schema.clone()
     }
@@ -54,6 +64,36 @@ impl EquivalenceProperties {
         }
     }
 
+    /// Add columns that are known to be constant, usually coming from the
+    /// equality predicates between a column and a literal in Filter
+    pub fn add_constants<I: IntoIterator<Item = Column>>(&mut self, iter: I) {
+        for column in iter {
+            assert_eq!(column.name(), self.schema.fields()[column.index()].name());
+            if !self.constants.contains(&column) {
+                self.constants.push(column)
+            }
+        }
+    }
+
+    /// Whether `expr` has the same value in all tuples: it is a literal, a
+    /// constant column (or a column equal to one), or only has constant inputs
+    pub fn is_constant(&self, expr: &Arc<dyn PhysicalExpr>) -> bool {
+        if expr.as_any().is::<Literal>() {
+            return true;
+        }
+        if let Some(column) = expr.as_any().downcast_ref::<Column>() {
+            return self.constants.iter().any(|constant| {
+                constant == column
+                    || self
+                        .classes
+                        .iter()
+                        .any(|class| class.contains(constant) && class.contains(column))
+            });
+        }
+        let children = expr.children();
+        !children.is_empty() && children.iter().all(|child| self.is_constant(child))
+    }
+
     /// Add new equal conditions into the EquivalenceProperties, the new equal conditions are usually comming from the
     /// equality predicates in Join or Filter
     pub fn add_equal_conditions(&mut self, new_conditions: (&Column, &Column)) {
@@ -178,12 +218,27 @@ impl EquivalentClass {
 /// Project Equivalence Properties.
 /// 1) Add Alias, Alias can introduce additional equivalence properties,
 ///    For example:  Projection(a, a as a1, a as a2)
-/// 2) Truncate the EquivalentClasses that are not in the output schema
+/// 2) Truncate the EquivalentClasses and constants that are not in the output schema
 pub fn project_equivalence_properties(
     input_eq: EquivalenceProperties,
     alias_map: &HashMap<Column, Vec<Column>>,
     output_eq: &mut EquivalenceProperties,
 ) {
+    let schema = output_eq.schema();
+    let is_valid = |column: &Column| {
+        column.index() < schema.fields().len()
+            && schema.fields()[column.index()].name() == column.name()
+    };
+    let mut constants = vec![];
+    for constant in input_eq.constants() {
+        if let Some(columns) = alias_map.get(constant) {
+            constants.extend(columns.iter().cloned());
+        }
+        constants.push(constant.clone());
+    }
+    constants.retain(|column| is_valid(column));
+    output_eq.add_constants(constants);
+
     let mut ec_classes = input_eq.classes().to_vec();
     for (column, columns) in alias_map {
         let mut find_match = false;
@@ -201,13 +256,10 @@ pub fn project_equivalence_properties(
         }
     }
 
-    let schema = output_eq.schema();
     for class in ec_classes.iter_mut() {
         let mut columns_to_remove = vec![];
         for column in class.iter() {
-            if column.index() >= schema.fields().len()
-                || schema.fields()[column.index()].name() != column.name()
-            {
+            if !is_valid(column) {
                 columns_to_remove.push(column.clone());
             }
         }
@@ -222,9 +274,10 @@ pub fn project_equivalence_properties(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::expressions::Column;
+    use crate::expressions::{BinaryExpr, Column};
     use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_common::Result;
+    use datafusion_expr::Operator;
 
     use std::sync::Arc;
 
@@ -318,4 +371,46 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn constants_test() -> Result<()> {
+        let input_schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int64, true),
+            Field::new("c", DataType::Int64, true),
+        ]));
+        let a: Arc<dyn PhysicalExpr> = Arc::new(Column::new("a", 0));
+        let b: Arc<dyn PhysicalExpr> = Arc::new(Column::new("b", 1));
+        let c: Arc<dyn PhysicalExpr> = Arc::new(Column::new("c", 2));
+
+        let mut input_properties = EquivalenceProperties::new(input_schema);
+        input_properties.add_constants(vec![Column::new("a", 0)]);
+        input_properties
+            .add_equal_conditions((&Column::new("a", 0), &Column::new("b", 1)));
+        assert!(input_properties.is_constant(&a));
+        assert!(input_properties.is_constant(&b));
+        assert!(!input_properties.is_constant(&c));
+        let one: Arc<dyn PhysicalExpr> = Arc::new(Literal::new(1i64.into()));
+        assert!(input_properties.is_constant(&one));
+        let a_plus_b: Arc<dyn PhysicalExpr> =
+            Arc::new(BinaryExpr::new(a.clone(), Operator::Plus, b));
+        assert!(input_properties.is_constant(&a_plus_b));
+        let a_plus_c: Arc<dyn PhysicalExpr> =
+            Arc::new(BinaryExpr::new(a, Operator::Plus, c));
+        assert!(!input_properties.is_constant(&a_plus_c));
+
+        // Projection(a as a1, c)
+        let out_schema = Arc::new(Schema::new(vec![
+            Field::new("a1", DataType::Int64, true),
+            Field::new("c", DataType::Int64, true),
+        ]));
+        let mut alias_map = HashMap::new();
+        alias_map.insert(Column::new("a", 0), vec![Column::new("a1", 0)]);
+        let mut out_properties = EquivalenceProperties::new(out_schema);
+
+        project_equivalence_properties(input_properties, &alias_map, &mut out_properties);
+        assert_eq!(out_properties.constants(), &[Column::new("a1", 0)]);
+
+        Ok(())
+    }
 }
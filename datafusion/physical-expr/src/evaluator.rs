@@ -0,0 +1,249 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Alternative backends evaluating physical expressions
+//!
+//! When planning an expression, the [`EvaluatorSelector`] of the
+//! [`ExecutionProps`] is given its [`ExprIr`], and can return a
+//! [`PhysicalExprEvaluator`], for example compiling the expression, which then
+//! evaluates the expression in place of its physical expression.
+//!
+//! [`ExecutionProps`]: crate::execution_props::ExecutionProps
+
+use std::any::Any;
+use std::fmt::{self, Debug, Display};
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::ColumnarValue;
+
+use crate::ir::ExprIr;
+use crate::physical_expr::down_cast_any_ref;
+use crate::PhysicalExpr;
+
+/// Evaluates an expression in place of its physical expression
+pub trait PhysicalExprEvaluator: Send + Sync + Debug {
+    /// Evaluates the expression against `batch`, returning the result the
+    /// physical expression would return
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue>;
+}
+
+/// Selects the evaluator of each planned expression, given its representation
+/// and the schema of its input, or returns `None` to evaluate it with its
+/// physical expression
+pub type EvaluatorSelector = Arc<
+    dyn Fn(&ExprIr, &Schema) -> Result<Option<Arc<dyn PhysicalExprEvaluator>>>
+        + Send
+        + Sync,
+>;
+
+/// Returns `expr` evaluated by the evaluator selected by `selector`, if any.
+///
+/// The columns, literals and the expressions without an [`ExprIr`] are not
+/// given to `selector`.
+pub fn select_evaluator(
+    expr: Arc<dyn PhysicalExpr>,
+    input_schema: &Schema,
+    selector: &EvaluatorSelector,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    let ir = match ExprIr::try_new(&expr) {
+        Ok(ExprIr::Column { .. } | ExprIr::Literal(_)) => return Ok(expr),
+        Ok(ir) => ir,
+        Err(DataFusionError::NotImplemented(_)) => return Ok(expr),
+        Err(e) => return Err(e),
+    };
+    Ok(match selector(&ir, input_schema)? {
+        Some(evaluator) => Arc::new(EvaluatorExpr::new(expr, evaluator)),
+        None => expr,
+    })
+}
+
+/// A physical expression evaluated by a [`PhysicalExprEvaluator`].
+///
+/// The expression keeps the type, children and display of the physical
+/// expression it replaces. The evaluator is dropped when the children are
+/// replaced, as it was selected for the original children.
+#[derive(Debug)]
+pub struct EvaluatorExpr {
+    expr: Arc<dyn PhysicalExpr>,
+    evaluator: Arc<dyn PhysicalExprEvaluator>,
+}
+
+impl EvaluatorExpr {
+    /// Create an expression evaluating `expr` with `evaluator`
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        evaluator: Arc<dyn PhysicalExprEvaluator>,
+    ) -> Self {
+        Self { expr, evaluator }
+    }
+
+    /// The physical expression evaluated
+    pub fn expr(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.expr
+    }
+
+    /// The evaluator of the expression
+    pub fn evaluator(&self) -> &Arc<dyn PhysicalExprEvaluator> {
+        &self.evaluator
+    }
+}
+
+impl Display for EvaluatorExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.expr)
+    }
+}
+
+impl PhysicalExpr for EvaluatorExpr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, input_schema: &Schema) -> Result<DataType> {
+        self.expr.data_type(input_schema)
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> Result<bool> {
+        self.expr.nullable(input_schema)
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        self.evaluator.evaluate(batch)
+    }
+
+    fn children(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        self.expr.children()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        self.expr.clone().with_new_children(children)
+    }
+}
+
+impl PartialEq<dyn Any> for EvaluatorExpr {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| self.expr.eq(&x.expr))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_props::ExecutionProps;
+    use crate::physical_expr::with_new_children_if_necessary;
+    use crate::{create_physical_expr, expressions::col};
+    use arrow::array::{ArrayRef, Int64Array};
+    use arrow::compute::kernels::arithmetic::add;
+    use arrow::datatypes::Field;
+    use datafusion_common::cast::as_int64_array;
+    use datafusion_common::{DFSchema, ScalarValue};
+    use datafusion_expr::{col as logical_col, lit, Operator};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Evaluates `a + b` on the columns at `left` and `right`, counting the
+    /// evaluations
+    #[derive(Debug)]
+    struct AddEvaluator {
+        left: usize,
+        right: usize,
+        evaluations: Arc<AtomicUsize>,
+    }
+
+    impl PhysicalExprEvaluator for AddEvaluator {
+        fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+            self.evaluations.fetch_add(1, Ordering::Relaxed);
+            let left = as_int64_array(batch.column(self.left))?;
+            let right = as_int64_array(batch.column(self.right))?;
+            Ok(ColumnarValue::Array(Arc::new(add(left, right)?)))
+        }
+    }
+
+    fn add_selector(evaluations: Arc<AtomicUsize>) -> EvaluatorSelector {
+        Arc::new(
+            move |ir: &ExprIr,
+                  _schema: &Schema|
+                  -> Result<Option<Arc<dyn PhysicalExprEvaluator>>> {
+                Ok(match ir {
+                    ExprIr::Binary {
+                        left,
+                        op: Operator::Plus,
+                        right,
+                    } => match (left.as_ref(), right.as_ref()) {
+                        (
+                            ExprIr::Column { index: left, .. },
+                            ExprIr::Column { index: right, .. },
+                        ) => Some(Arc::new(AddEvaluator {
+                            left: *left,
+                            right: *right,
+                            evaluations: evaluations.clone(),
+                        })
+                            as Arc<dyn PhysicalExprEvaluator>),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+            },
+        )
+    }
+
+    #[test]
+    fn evaluate_with_selected_evaluator() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, false),
+        ]);
+        let df_schema = DFSchema::try_from(schema.clone())?;
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+                Arc::new(Int64Array::from(vec![10, 20])),
+            ],
+        )?;
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let mut props = ExecutionProps::new();
+        props.set_evaluator_selector(add_selector(evaluations.clone()));
+
+        let expr = logical_col("a") + logical_col("b");
+        let physical = create_physical_expr(&expr, &df_schema, &schema, &props)?;
+        assert!(physical.as_any().is::<EvaluatorExpr>());
+        assert_eq!(physical.to_string(), "a@0 + b@1");
+        let result = physical.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(as_int64_array(&result)?, &Int64Array::from(vec![11, 22]));
+        assert_eq!(evaluations.load(Ordering::Relaxed), 1);
+
+        // rewriting the children falls back to the physical expression
+        let children = vec![col("b", &schema)?, col("a", &schema)?];
+        let rewritten = with_new_children_if_necessary(physical, children)?;
+        assert!(!rewritten.as_any().is::<EvaluatorExpr>());
+
+        // the expressions not selected are evaluated as usual
+        let expr = logical_col("a") + lit(ScalarValue::Int64(Some(1)));
+        let physical = create_physical_expr(&expr, &df_schema, &schema, &props)?;
+        assert!(!physical.as_any().is::<EvaluatorExpr>());
+        Ok(())
+    }
+}
@@ -15,8 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::evaluator::EvaluatorSelector;
+use crate::expressions::SubqueryId;
 use crate::var_provider::{VarProvider, VarType};
 use chrono::{DateTime, TimeZone, Utc};
+use datafusion_expr::LogicalPlan;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -32,6 +35,11 @@ pub struct ExecutionProps {
     pub query_execution_start_time: DateTime<Utc>,
     /// providers for scalar variables
     pub var_providers: Option<HashMap<VarType, Arc<dyn VarProvider + Send + Sync>>>,
+    /// selects the backends evaluating the planned expressions
+    pub evaluator_selector: Option<EvaluatorSelector>,
+    /// the uncorrelated subqueries planned before the expressions reading
+    /// their results, see [`crate::expressions::ScalarSubqueryExpr`]
+    pub subqueries: Vec<(Arc<LogicalPlan>, SubqueryId)>,
 }
 
 impl Default for ExecutionProps {
@@ -48,6 +56,8 @@ impl ExecutionProps {
             // not being updated / propagated correctly
             query_execution_start_time: Utc.timestamp_nanos(0),
             var_providers: None,
+            evaluator_selector: None,
+            subqueries: vec![],
         }
     }
 
@@ -82,4 +92,10 @@ impl ExecutionProps {
             .as_ref()
             .and_then(|var_providers| var_providers.get(&var_type).map(Arc::clone))
     }
+
+    /// Sets the selector of the backends evaluating the planned expressions,
+    /// see [`crate::evaluator`]
+    pub fn set_evaluator_selector(&mut self, selector: EvaluatorSelector) {
+        self.evaluator_selector = Some(selector);
+    }
 }
@@ -16,6 +16,7 @@
 // under the License.
 
 mod adapter;
+mod comparison;
 mod kernels;
 mod kernels_arrow;
 
@@ -52,6 +53,10 @@ use arrow::compute::kernels::comparison::{
 
 use adapter::{eq_dyn, gt_dyn, gt_eq_dyn, lt_dyn, lt_eq_dyn, neq_dyn};
 use arrow::compute::kernels::concat_elements::concat_elements_utf8;
+use comparison::{
+    compare_dict_scalar, eq_decimal_scalar, gt_decimal_scalar, gt_eq_decimal_scalar,
+    lt_decimal_scalar, lt_eq_decimal_scalar, neq_decimal_scalar,
+};
 use kernels::{
     bitwise_and, bitwise_and_scalar, bitwise_or, bitwise_or_scalar, bitwise_shift_left,
     bitwise_shift_left_scalar, bitwise_shift_right, bitwise_shift_right_scalar,
@@ -59,18 +64,21 @@ use kernels::{
 };
 use kernels_arrow::{
     add_decimal, add_decimal_scalar, divide_decimal_scalar, divide_opt_decimal,
-    is_distinct_from, is_distinct_from_bool, is_distinct_from_decimal,
-    is_distinct_from_null, is_distinct_from_utf8, is_not_distinct_from,
-    is_not_distinct_from_bool, is_not_distinct_from_decimal, is_not_distinct_from_null,
-    is_not_distinct_from_utf8, modulus_decimal, modulus_decimal_scalar, multiply_decimal,
-    multiply_decimal_scalar, subtract_decimal, subtract_decimal_scalar,
+    is_distinct_from, is_distinct_from_binary, is_distinct_from_bool,
+    is_distinct_from_decimal, is_distinct_from_null, is_distinct_from_utf8,
+    is_not_distinct_from, is_not_distinct_from_binary, is_not_distinct_from_bool,
+    is_not_distinct_from_decimal, is_not_distinct_from_null, is_not_distinct_from_utf8,
+    modulus_decimal, modulus_decimal_scalar, multiply_decimal, multiply_decimal_scalar,
+    subtract_decimal, subtract_decimal_scalar,
 };
 
 use arrow::datatypes::{DataType, Schema, TimeUnit};
 use arrow::record_batch::RecordBatch;
 
 use super::column::Column;
+use crate::intervals::Interval;
 use crate::physical_expr::down_cast_any_ref;
+use crate::sort_key::{is_nested, nested_sort_key};
 use crate::{analysis_expect, AnalysisContext, ExprBoundaries, PhysicalExpr};
 use datafusion_common::cast::{as_boolean_array, as_decimal128_array};
 use datafusion_common::ScalarValue;
@@ -122,7 +130,9 @@ macro_rules! compute_decimal_op_dyn_scalar {
     ($LEFT:expr, $RIGHT:expr, $OP:ident, $OP_TYPE:expr) => {{
         let ll = as_decimal128_array($LEFT).unwrap();
         if let ScalarValue::Decimal128(Some(v_i128), _, _) = $RIGHT {
-            Ok(Arc::new(paste::expr! {[<$OP _dyn_scalar>]}(ll, v_i128)?))
+            Ok(Arc::new(paste::expr! {[<$OP _decimal_scalar>]}(
+                ll, v_i128,
+            )?))
         } else {
             // when the $RIGHT is a NULL, generate a NULL array of $OP_TYPE type
             Ok(Arc::new(new_null_array($OP_TYPE, $LEFT.len())))
@@ -339,6 +349,21 @@ macro_rules! compute_op {
     }};
 }
 
+/// Invoke a compute kernel on a pair of binary arrays
+macro_rules! compute_binary_op {
+    ($LEFT:expr, $RIGHT:expr, $OP:ident, $DT:ident) => {{
+        let ll = $LEFT
+            .as_any()
+            .downcast_ref::<$DT>()
+            .expect("compute_op failed to downcast array");
+        let rr = $RIGHT
+            .as_any()
+            .downcast_ref::<$DT>()
+            .expect("compute_op failed to downcast array");
+        Ok(Arc::new(paste::expr! {[<$OP _binary>]}(&ll, &rr)?))
+    }};
+}
+
 macro_rules! binary_string_array_op {
     ($LEFT:expr, $RIGHT:expr, $OP:ident) => {{
         match $LEFT.data_type() {
@@ -421,6 +446,7 @@ macro_rules! binary_array_op {
             DataType::Float32 => compute_op!($LEFT, $RIGHT, $OP, Float32Array),
             DataType::Float64 => compute_op!($LEFT, $RIGHT, $OP, Float64Array),
             DataType::Utf8 => compute_utf8_op!($LEFT, $RIGHT, $OP, StringArray),
+            DataType::Binary => compute_binary_op!($LEFT, $RIGHT, $OP, BinaryArray),
             DataType::Timestamp(TimeUnit::Nanosecond, _) => {
                 compute_op!($LEFT, $RIGHT, $OP, TimestampNanosecondArray)
             }
@@ -604,6 +630,8 @@ impl PhysicalExpr for BinaryExpr {
 
         // Attempt to use special kernels if one input is scalar and the other is an array
         let scalar_result = match (&left_value, &right_value) {
+            // nested values are only compared as arrays, through their keys
+            _ if is_nested(&left_data_type) => None,
             (ColumnarValue::Array(array), ColumnarValue::Scalar(scalar)) => {
                 // if left is array and right is literal - use scalar operations
                 self.evaluate_array_scalar(array, scalar)?
@@ -683,11 +711,109 @@ impl PhysicalExpr for BinaryExpr {
                         )
                     }
                     _ => {
-                        // Both sides are columns, so we give up.
-                        context.with_boundaries(None)
+                        // Neither side is a scalar, so we can only tell whether
+                        // the comparison is always true or false (e.g. `a < b`
+                        // where the max of `a` is less than the min of `b`).
+                        let result = analysis_expect!(
+                            context,
+                            Interval::from(&left_boundaries)
+                                .apply(&self.op, &Interval::from(&right_boundaries))
+                                .ok()
+                                .and_then(|result| result.certain_value())
+                        );
+                        let selectivity = if result { 1.0 } else { 0.0 };
+                        context.with_boundaries(Some(ExprBoundaries::new_predicate(
+                            selectivity,
+                        )))
                     }
                 }
             }
+            Operator::Plus | Operator::Minus | Operator::Multiply | Operator::Divide => {
+                // The result is bounded by applying the operator to the
+                // boundaries of both sides.
+                let context = self.left.analyze(context);
+                let left = analysis_expect!(context, context.boundaries().cloned());
+                let context = self.right.analyze(context);
+                let right = analysis_expect!(context, context.boundaries().cloned());
+
+                let result = analysis_expect!(
+                    context,
+                    Interval::from(&left)
+                        .apply(&self.op, &Interval::from(&right))
+                        .ok()
+                );
+                // Shifting by a constant keeps the number of distinct values.
+                let distinct_count = match (left.reduce(), right.reduce()) {
+                    (_, Some(_))
+                        if matches!(self.op, Operator::Plus | Operator::Minus) =>
+                    {
+                        left.distinct_count
+                    }
+                    (Some(_), _)
+                        if matches!(self.op, Operator::Plus | Operator::Minus) =>
+                    {
+                        right.distinct_count
+                    }
+                    _ => None,
+                };
+                context.with_boundaries(result.to_boundaries(distinct_count))
+            }
+            Operator::NotEq => {
+                // The inverse of the equality, which must not narrow the column
+                // boundaries (since `a != 5` tells nothing about the range of `a`).
+                let eq =
+                    BinaryExpr::new(self.left.clone(), Operator::Eq, self.right.clone());
+                let eq_boundaries =
+                    analysis_expect!(context, eq.analyze(context.clone()).boundaries);
+                let selectivity = analysis_expect!(context, eq_boundaries.selectivity);
+                context.with_boundaries(Some(ExprBoundaries::new_predicate(
+                    1.0 - selectivity,
+                )))
+            }
+            Operator::And => {
+                // The right side is analyzed with the column boundaries narrowed
+                // by the left side, so its selectivity is relative to the rows
+                // selected by the left side (e.g. `a > 10 AND a < 20`). When only
+                // one side can be estimated, the other is assumed to select
+                // everything.
+                let context = self.left.analyze(context);
+                let left = context.boundaries().and_then(|b| b.selectivity);
+                let context = self.right.analyze(context);
+                let right = context.boundaries().and_then(|b| b.selectivity);
+
+                let selectivity = match (left, right) {
+                    (Some(left), Some(right)) => left * right,
+                    (Some(selectivity), None) | (None, Some(selectivity)) => selectivity,
+                    (None, None) => return context.with_boundaries(None),
+                };
+                context.with_boundaries(Some(ExprBoundaries::new_predicate(selectivity)))
+            }
+            Operator::Or => {
+                // Both sides are analyzed independently of each other, and the
+                // column boundaries are the union of what remains of them on the
+                // sides that select anything.
+                let left = self.left.analyze(context.clone());
+                let left_selectivity = analysis_expect!(
+                    context,
+                    left.boundaries().and_then(|b| b.selectivity)
+                );
+                let right = self.right.analyze(context.clone());
+                let right_selectivity = analysis_expect!(
+                    context,
+                    right.boundaries().and_then(|b| b.selectivity)
+                );
+
+                let selectivity = left_selectivity + right_selectivity
+                    - left_selectivity * right_selectivity;
+                let branches = [(left, left_selectivity), (right, right_selectivity)]
+                    .into_iter()
+                    .filter(|(_, selectivity)| *selectivity > 0.0)
+                    .map(|(branch, _)| branch)
+                    .collect::<Vec<_>>();
+                context
+                    .with_column_union(&branches)
+                    .with_boundaries(Some(ExprBoundaries::new_predicate(selectivity)))
+            }
             _ => context.with_boundaries(None),
         }
     }
@@ -872,6 +998,10 @@ macro_rules! binary_array_op_dyn_scalar {
             ScalarValue::TimestampMillisecond(..) => compute_op_scalar!($LEFT, right, $OP, TimestampMillisecondArray),
             ScalarValue::TimestampMicrosecond(..) => compute_op_scalar!($LEFT, right, $OP, TimestampMicrosecondArray),
             ScalarValue::TimestampNanosecond(..) => compute_op_scalar!($LEFT, right, $OP, TimestampNanosecondArray),
+            ScalarValue::DurationSecond(_) => compute_op_scalar!($LEFT, right, $OP, DurationSecondArray),
+            ScalarValue::DurationMillisecond(_) => compute_op_scalar!($LEFT, right, $OP, DurationMillisecondArray),
+            ScalarValue::DurationMicrosecond(_) => compute_op_scalar!($LEFT, right, $OP, DurationMicrosecondArray),
+            ScalarValue::DurationNanosecond(_) => compute_op_scalar!($LEFT, right, $OP, DurationNanosecondArray),
             other => Err(DataFusionError::Internal(format!(
                 "Data type {:?} not supported for scalar operation '{}' on dyn array",
                 other, stringify!($OP)))
@@ -896,6 +1026,19 @@ pub(crate) fn array_eq_scalar(lhs: &dyn Array, rhs: &ScalarValue) -> Result<Arra
 }
 
 impl BinaryExpr {
+    /// Returns true if the operator compares its inputs
+    fn is_comparison(&self) -> bool {
+        matches!(
+            self.op,
+            Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq
+                | Operator::Eq
+                | Operator::NotEq
+        )
+    }
+
     /// Evaluate the expression of the left input is an array and
     /// right is literal - use scalar operations
     fn evaluate_array_scalar(
@@ -903,6 +1046,15 @@ impl BinaryExpr {
         array: &dyn Array,
         scalar: &ScalarValue,
     ) -> Result<Option<Result<ArrayRef>>> {
+        if self.is_comparison() {
+            // compare the scalar with the distinct values of a dictionary only once
+            if let Some(result) = compare_dict_scalar(array, |values| {
+                self.evaluate_array_scalar(values.as_ref(), scalar)
+            })? {
+                return Ok(Some(result));
+            }
+        }
+
         let bool_type = &DataType::Boolean;
         let scalar_result = match &self.op {
             Operator::Lt => {
@@ -989,6 +1141,15 @@ impl BinaryExpr {
         scalar: &ScalarValue,
         array: &ArrayRef,
     ) -> Result<Option<Result<ArrayRef>>> {
+        if self.is_comparison() {
+            // compare the scalar with the distinct values of a dictionary only once
+            if let Some(result) = compare_dict_scalar(array.as_ref(), |values| {
+                self.evaluate_scalar_array(scalar, values)
+            })? {
+                return Ok(Some(result));
+            }
+        }
+
         let bool_type = &DataType::Boolean;
         let scalar_result = match &self.op {
             Operator::Lt => {
@@ -1029,6 +1190,19 @@ impl BinaryExpr {
             Operator::GtEq => gt_eq_dyn(&left, &right),
             Operator::Eq => eq_dyn(&left, &right),
             Operator::NotEq => neq_dyn(&left, &right),
+            Operator::IsDistinctFrom | Operator::IsNotDistinctFrom
+                if is_nested(left_data_type) =>
+            {
+                // compare nested values through their order preserving keys
+                let left = nested_sort_key(left.as_ref())?;
+                let right = nested_sort_key(right.as_ref())?;
+                self.evaluate_with_resolved_args(
+                    left,
+                    &DataType::Binary,
+                    right,
+                    &DataType::Binary,
+                )
+            }
             Operator::IsDistinctFrom => {
                 match (left_data_type, right_data_type) {
                     // exchange lhs and rhs when lhs is Null, since `binary_array_op` is
@@ -1117,10 +1291,11 @@ mod tests {
     use super::*;
     use crate::expressions::try_cast;
     use crate::expressions::{col, lit};
+    use arrow::buffer::Buffer;
     use arrow::datatypes::{
         ArrowNumericType, Decimal128Type, Field, Int32Type, SchemaRef,
     };
-    use datafusion_common::{ColumnStatistics, Result, Statistics};
+    use datafusion_common::{ColumnStatistics, Precision, Result, Statistics};
     use datafusion_expr::type_coercion::binary::coerce_types;
 
     // Create a binary expression without coercion. Used here when we do not want to coerce the expressions
@@ -1271,11 +1446,23 @@ mod tests {
             vec![1i32, 2i32],
             UInt32Array,
             DataType::UInt32,
-            vec![1u32, 2u32],
+            vec![1u32, 4294967295u32],
             Operator::Plus,
-            Int32Array,
-            DataType::Int32,
-            vec![2i32, 4i32],
+            Int64Array,
+            DataType::Int64,
+            vec![2i64, 4294967297i64],
+        );
+        test_coercion!(
+            Int8Array,
+            DataType::Int8,
+            vec![-1i8, 1i8],
+            UInt8Array,
+            DataType::UInt8,
+            vec![255u8, 1u8],
+            Operator::Lt,
+            BooleanArray,
+            DataType::Boolean,
+            vec![true, false],
         );
         test_coercion!(
             Int32Array,
@@ -1545,6 +1732,64 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn dictionary_scalar_comparison() -> Result<()> {
+        let keys = Int32Array::from(vec![Some(0), None, Some(2), Some(1), Some(0)]);
+        let strings = StringArray::from(vec!["b", "a", "c"]);
+        let dates = Date32Array::from(vec![2, 1, 3]);
+
+        let cases = vec![
+            (
+                Arc::new(DictionaryArray::try_new(&keys, &strings)?) as ArrayRef,
+                ScalarValue::Utf8(Some("b".into())),
+            ),
+            (
+                Arc::new(DictionaryArray::try_new(&keys, &dates)?),
+                ScalarValue::Date32(Some(2)),
+            ),
+        ];
+
+        for (dict_array, scalar) in cases {
+            let schema = Arc::new(Schema::new(vec![Field::new(
+                "a",
+                dict_array.data_type().clone(),
+                true,
+            )]));
+            let batch = RecordBatch::try_new(schema.clone(), vec![dict_array])?;
+
+            // the dictionary values are [b, a, c] and the keys are [0, NULL, 2, 1, 0]
+            for (op, reversed_op, expected) in [
+                (Operator::Eq, Operator::Eq, [true, false, false, true]),
+                (Operator::NotEq, Operator::NotEq, [false, true, true, false]),
+                (Operator::Lt, Operator::Gt, [false, false, true, false]),
+                (Operator::LtEq, Operator::GtEq, [true, false, true, true]),
+                (Operator::Gt, Operator::Lt, [false, true, false, false]),
+                (Operator::GtEq, Operator::LtEq, [true, true, false, true]),
+            ] {
+                let expected = BooleanArray::from(vec![
+                    Some(expected[0]),
+                    None,
+                    Some(expected[1]),
+                    Some(expected[2]),
+                    Some(expected[3]),
+                ]);
+
+                // a <op> scalar
+                let expr = BinaryExpr::new(col("a", &schema)?, op, lit(scalar.clone()));
+                let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+                assert_eq!(result.as_ref(), &expected, "{expr}");
+
+                // scalar <reversed_op> a
+                let expr =
+                    BinaryExpr::new(lit(scalar.clone()), reversed_op, col("a", &schema)?);
+                let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+                assert_eq!(result.as_ref(), &expected, "{expr}");
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn plus_op() -> Result<()> {
         let schema = Schema::new(vec![
@@ -2286,6 +2531,123 @@ mod tests {
         apply_logic_op(&schema, &a, &b, Operator::IsNotDistinctFrom, expected).unwrap();
     }
 
+    #[test]
+    fn comparison_op_struct() -> Result<()> {
+        let field = Field::new("x", DataType::Int32, true);
+        let structs = |values: Vec<Option<i32>>, validity: u8| -> ArrayRef {
+            Arc::new(StructArray::from((
+                vec![(
+                    field.clone(),
+                    Arc::new(Int32Array::from(values)) as ArrayRef,
+                )],
+                Buffer::from([validity]),
+            )))
+        };
+        // {1}, {1}, {NULL}, NULL, {2}
+        let a = structs(vec![Some(1), Some(1), None, Some(0), Some(2)], 0b10111);
+        // {1}, {2}, {NULL}, {1}, {NULL}
+        let b = structs(vec![Some(1), Some(2), None, Some(1), None], 0b11111);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", a.data_type().clone(), true),
+            Field::new("b", b.data_type().clone(), true),
+        ]));
+
+        // NULL fields are equal to each other and greater than other values
+        let cases = [
+            (
+                Operator::Eq,
+                [Some(true), Some(false), Some(true), None, Some(false)],
+            ),
+            (
+                Operator::NotEq,
+                [Some(false), Some(true), Some(false), None, Some(true)],
+            ),
+            (
+                Operator::Lt,
+                [Some(false), Some(true), Some(false), None, Some(true)],
+            ),
+            (
+                Operator::GtEq,
+                [Some(true), Some(false), Some(true), None, Some(false)],
+            ),
+            (
+                Operator::IsDistinctFrom,
+                [Some(false), Some(true), Some(false), Some(true), Some(true)],
+            ),
+            (
+                Operator::IsNotDistinctFrom,
+                [
+                    Some(true),
+                    Some(false),
+                    Some(true),
+                    Some(false),
+                    Some(false),
+                ],
+            ),
+        ];
+        for (op, expected) in cases {
+            apply_logic_op(&schema, &a, &b, op, expected.iter().collect())?;
+        }
+
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            a.data_type().clone(),
+            true,
+        )]));
+        let scalar = ScalarValue::Struct(
+            Some(vec![ScalarValue::Int32(Some(1))]),
+            Box::new(vec![field.clone()]),
+        );
+        let expected = [Some(true), Some(true), Some(false), None, Some(false)]
+            .iter()
+            .collect();
+        apply_logic_op_arr_scalar(&schema, &a, &scalar, Operator::Eq, &expected)?;
+        let expected = [Some(false), Some(false), Some(true), None, Some(true)]
+            .iter()
+            .collect();
+        apply_logic_op_scalar_arr(&schema, &scalar, &a, Operator::Lt, &expected)?;
+        Ok(())
+    }
+
+    #[test]
+    fn comparison_op_list() -> Result<()> {
+        // [1, 2], [1], NULL, []
+        let a: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                Some(vec![Some(1), Some(2)]),
+                Some(vec![Some(1)]),
+                None,
+                Some(vec![]),
+            ]));
+        // [1, 2], [1, 2], NULL, [NULL]
+        let b: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                Some(vec![Some(1), Some(2)]),
+                Some(vec![Some(1), Some(2)]),
+                None,
+                Some(vec![None]),
+            ]));
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", a.data_type().clone(), true),
+            Field::new("b", b.data_type().clone(), true),
+        ]));
+
+        // a list is smaller than the lists it is a prefix of
+        let cases = [
+            (Operator::Eq, [Some(true), Some(false), None, Some(false)]),
+            (Operator::Gt, [Some(false), Some(false), None, Some(false)]),
+            (Operator::LtEq, [Some(true), Some(true), None, Some(true)]),
+            (
+                Operator::IsDistinctFrom,
+                [Some(false), Some(true), Some(false), Some(true)],
+            ),
+        ];
+        for (op, expected) in cases {
+            apply_logic_op(&schema, &a, &b, op, expected.iter().collect())?;
+        }
+        Ok(())
+    }
+
     #[test]
     fn relatively_deeply_nested() {
         // Reproducer for https://github.com/apache/arrow-datafusion/issues/419
@@ -3094,14 +3456,14 @@ mod tests {
         assert_eq!(min_value.get_datatype(), max_value.get_datatype());
         let schema = Schema::new(vec![Field::new("a", min_value.get_datatype(), false)]);
         let columns = vec![ColumnStatistics {
-            min_value: Some(min_value),
-            max_value: Some(max_value),
-            null_count: None,
-            distinct_count: None,
+            min_value: Precision::Exact(min_value),
+            max_value: Precision::Exact(max_value),
+            null_count: Precision::Absent,
+            distinct_count: Precision::Absent,
         }];
         let statistics = Statistics {
-            column_statistics: Some(columns),
-            ..Default::default()
+            column_statistics: columns,
+            ..Statistics::new_unknown(&schema)
         };
         (schema, statistics)
     }
@@ -3365,4 +3727,196 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_conjunction_boundaries() -> Result<()> {
+        // A table where the column 'a' has a min of 1, a max of 100.
+        let (schema, statistics) =
+            get_test_table_stats(ScalarValue::from(1), ScalarValue::from(100));
+
+        // expression: "a >= 25 AND a <= 50"
+        let a = col("a", &schema).unwrap();
+        let predicate = binary_simple(
+            binary_simple(a.clone(), Operator::GtEq, lit(25), &schema),
+            Operator::And,
+            binary_simple(a, Operator::LtEq, lit(50), &schema),
+            &schema,
+        );
+
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let context = predicate.analyze(context);
+        // 76/100 rows have a >= 25, of which 26/76 have a <= 50
+        let selectivity = context.boundaries().unwrap().selectivity.unwrap();
+        assert!((selectivity - 0.26).abs() < 1e-9);
+        assert_eq!(
+            context.column_boundaries[0],
+            Some(ExprBoundaries::new(
+                ScalarValue::from(25),
+                ScalarValue::from(50),
+                None
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_disjunction_boundaries() -> Result<()> {
+        // A table where the column 'a' has a min of 1, a max of 100.
+        let (schema, statistics) =
+            get_test_table_stats(ScalarValue::from(1), ScalarValue::from(100));
+        let a = col("a", &schema).unwrap();
+
+        // expression: "a < 10 OR a > 90"
+        let predicate = binary_simple(
+            binary_simple(a.clone(), Operator::Lt, lit(10), &schema),
+            Operator::Or,
+            binary_simple(a.clone(), Operator::Gt, lit(90), &schema),
+            &schema,
+        );
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let context = predicate.analyze(context);
+        let selectivity = context.boundaries().unwrap().selectivity.unwrap();
+        assert!((selectivity - (0.09 + 0.1 - 0.09 * 0.1)).abs() < 1e-9);
+        assert_eq!(
+            context.column_boundaries[0],
+            Some(ExprBoundaries::new(
+                ScalarValue::from(1),
+                ScalarValue::from(100),
+                None
+            ))
+        );
+
+        // expression: "a = 200 OR a <= 50", where the left side never selects
+        let predicate = binary_simple(
+            binary_simple(a.clone(), Operator::Eq, lit(200), &schema),
+            Operator::Or,
+            binary_simple(a, Operator::LtEq, lit(50), &schema),
+            &schema,
+        );
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let context = predicate.analyze(context);
+        assert_eq!(context.boundaries().unwrap().selectivity, Some(0.5));
+        assert_eq!(
+            context.column_boundaries[0],
+            Some(ExprBoundaries::new(
+                ScalarValue::from(1),
+                ScalarValue::from(50),
+                None
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_eq_boundaries() -> Result<()> {
+        // A table where the column 'a' has a min of 1, a max of 100.
+        let (schema, statistics) =
+            get_test_table_stats(ScalarValue::from(1), ScalarValue::from(100));
+
+        // expression: "a != 50"
+        let a = col("a", &schema).unwrap();
+        let predicate = binary_simple(a, Operator::NotEq, lit(50), &schema);
+
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let context = predicate.analyze(context);
+        assert_eq!(context.boundaries().unwrap().selectivity, Some(0.99));
+        // the boundaries of the column are unchanged
+        assert_eq!(
+            context.column_boundaries[0],
+            Some(ExprBoundaries::new(
+                ScalarValue::from(1),
+                ScalarValue::from(100),
+                None
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_arithmetic_boundaries() -> Result<()> {
+        // A table where the column 'a' has a min of -10, a max of 20.
+        let (schema, statistics) =
+            get_test_table_stats(ScalarValue::from(-10i64), ScalarValue::from(20i64));
+        let a = col("a", &schema).unwrap();
+
+        let cases = [
+            (Operator::Plus, 5i64, -5i64, 25i64),
+            (Operator::Minus, 5, -15, 15),
+            (Operator::Multiply, -2, -40, 20),
+            (Operator::Divide, 5, -2, 4),
+        ];
+        for (operator, rhs, expected_min, expected_max) in cases {
+            let expr = binary_simple(a.clone(), operator, lit(rhs), &schema);
+            let context = AnalysisContext::from_statistics(&schema, &statistics);
+            let boundaries = expr
+                .analyze(context)
+                .boundaries
+                .expect("boundaries should be known");
+            assert_eq!(boundaries.min_value, ScalarValue::from(expected_min));
+            assert_eq!(boundaries.max_value, ScalarValue::from(expected_max));
+        }
+
+        // The sign of the divisor is unknown, so the result is unbounded.
+        let expr = binary_simple(lit(100i64), Operator::Divide, a.clone(), &schema);
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        assert!(expr.analyze(context).boundaries.is_none());
+
+        // expression: "a * 2 > 50", which can never be true.
+        let product = binary_simple(a, Operator::Multiply, lit(2i64), &schema);
+        let predicate = binary_simple(product, Operator::Gt, lit(50i64), &schema);
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let context = predicate.analyze(context);
+        assert_eq!(context.boundaries().unwrap().selectivity, Some(0.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_comparison_boundaries() -> Result<()> {
+        // A table where 'a' is in [1, 10] and 'b' is in [20, 30].
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, false),
+        ]);
+        let column = |min: i64, max: i64| ColumnStatistics {
+            min_value: Precision::Exact(ScalarValue::from(min)),
+            max_value: Precision::Exact(ScalarValue::from(max)),
+            null_count: Precision::Absent,
+            distinct_count: Precision::Absent,
+        };
+        let statistics = Statistics {
+            column_statistics: vec![column(1, 10), column(20, 30)],
+            ..Statistics::new_unknown(&schema)
+        };
+        let a = col("a", &schema).unwrap();
+        let b = col("b", &schema).unwrap();
+
+        let cases = [
+            (Operator::Lt, Some(1.0)),
+            (Operator::Gt, Some(0.0)),
+            (Operator::Eq, Some(0.0)),
+            (Operator::NotEq, Some(1.0)),
+        ];
+        for (operator, expected) in cases {
+            let predicate = binary_simple(a.clone(), operator, b.clone(), &schema);
+            let context = AnalysisContext::from_statistics(&schema, &statistics);
+            let context = predicate.analyze(context);
+            assert_eq!(
+                context.boundaries().and_then(|b| b.selectivity),
+                expected,
+                "{operator}"
+            );
+        }
+
+        // expression: "a + 15 > b", which may or may not hold.
+        let sum = binary_simple(a, Operator::Plus, lit(15i64), &schema);
+        let predicate = binary_simple(sum, Operator::Gt, b, &schema);
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        assert!(predicate.analyze(context).boundaries.is_none());
+
+        Ok(())
+    }
 }
@@ -21,8 +21,12 @@
 use std::sync::Arc;
 
 use arrow::array::*;
+use arrow::datatypes::DataType;
+use datafusion_common::cast::as_decimal128_array;
 use datafusion_common::Result;
 
+use crate::sort_key::{is_nested, nested_sort_key};
+
 /// create a `dyn_op` wrapper function for the specified operation
 /// that call the underlying dyn_op arrow kernel if the type is
 /// supported, and translates ArrowError to DataFusionError
@@ -32,9 +36,27 @@ macro_rules! make_dyn_comp_op {
             /// wrapper over arrow compute kernel that maps Error types and
             /// patches missing support in arrow
             pub(crate) fn [<$OP _dyn>] (left: &dyn Array, right: &dyn Array) -> Result<ArrayRef> {
-                arrow::compute::kernels::comparison::[<$OP _dyn>](left, right)
+                match (left.data_type(), right.data_type()) {
+                    // compare decimals directly on their i128 values
+                    (DataType::Decimal128(..), DataType::Decimal128(..)) => {
+                        super::comparison::[<$OP _decimal>](
+                            as_decimal128_array(left)?,
+                            as_decimal128_array(right)?,
+                        )
+                        .map(|a| Arc::new(a) as ArrayRef)
+                    }
+                    // compare nested values through their order preserving keys
+                    (left_type, _) if is_nested(left_type) => {
+                        let left = nested_sort_key(left)?;
+                        let right = nested_sort_key(right)?;
+                        arrow::compute::kernels::comparison::[<$OP _dyn>](&left, &right)
                             .map_err(|e| e.into())
                             .map(|a| Arc::new(a) as ArrayRef)
+                    }
+                    _ => arrow::compute::kernels::comparison::[<$OP _dyn>](left, right)
+                            .map_err(|e| e.into())
+                            .map(|a| Arc::new(a) as ArrayRef),
+                }
             }
         }
     };
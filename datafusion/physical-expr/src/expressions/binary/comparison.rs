@@ -0,0 +1,344 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This module contains specialized comparison kernels for types that
+//! the generic arrow comparison kernels handle slowly: decimals, which
+//! are compared directly on their `i128` representation, and dictionary
+//! arrays compared with a scalar, whose distinct values are only compared
+//! once.
+
+use arrow::array::*;
+use arrow::buffer::{buffer_bin_and, Buffer, MutableBuffer};
+use arrow::compute::take;
+use arrow::datatypes::{
+    DataType, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow::util::bit_util;
+use datafusion_common::cast::as_dictionary_array;
+use datafusion_common::{DataFusionError, Result};
+
+/// Packs the results of `f` for the indices `0..len` into a bitmap, 64
+/// bits at a time so that the comparisons can be vectorized
+fn collect_bool(len: usize, f: impl Fn(usize) -> bool) -> Buffer {
+    let mut buffer = MutableBuffer::new(bit_util::ceil(len, 64) * 8);
+
+    let chunks = len / 64;
+    for chunk in 0..chunks {
+        let mut packed = 0_u64;
+        for bit in 0..64 {
+            packed |= (f(chunk * 64 + bit) as u64) << bit;
+        }
+        buffer.push(packed);
+    }
+
+    let remainder = len % 64;
+    if remainder != 0 {
+        let mut packed = 0_u64;
+        for bit in 0..remainder {
+            packed |= (f(chunks * 64 + bit) as u64) << bit;
+        }
+        buffer.push(packed);
+    }
+
+    buffer.truncate(bit_util::ceil(len, 8));
+    buffer.into()
+}
+
+/// Returns the validity bitmap of `len` values starting at the offset
+/// of `data`, if it has any null
+fn null_bitmap(data: &ArrayData) -> Option<Buffer> {
+    data.null_buffer()
+        .map(|nulls| nulls.bit_slice(data.offset(), data.len()))
+}
+
+/// Returns the validity bitmap that is valid where both `left` and `right`
+/// are valid
+fn combine_null_bitmaps(left: &ArrayData, right: &ArrayData) -> Option<Buffer> {
+    match (left.null_buffer(), right.null_buffer()) {
+        (None, None) => None,
+        (Some(_), None) => null_bitmap(left),
+        (None, Some(_)) => null_bitmap(right),
+        (Some(l), Some(r)) => Some(buffer_bin_and(
+            l,
+            left.offset(),
+            r,
+            right.offset(),
+            left.len(),
+        )),
+    }
+}
+
+fn build_boolean_array(
+    len: usize,
+    values: Buffer,
+    nulls: Option<Buffer>,
+) -> Result<BooleanArray> {
+    let data = ArrayData::builder(DataType::Boolean)
+        .len(len)
+        .add_buffer(values)
+        .null_bit_buffer(nulls)
+        .build()?;
+    Ok(BooleanArray::from(data))
+}
+
+/// Compares two decimal arrays element-wise with `op`, directly on their
+/// `i128` values. The result is null where either side is null.
+fn compare_decimal<F>(
+    left: &Decimal128Array,
+    right: &Decimal128Array,
+    op: F,
+) -> Result<BooleanArray>
+where
+    F: Fn(i128, i128) -> bool,
+{
+    if left.len() != right.len() {
+        return Err(DataFusionError::Internal(format!(
+            "Cannot compare decimal arrays of different lengths {} and {}",
+            left.len(),
+            right.len()
+        )));
+    }
+
+    let (l, r) = (left.values(), right.values());
+    let values = collect_bool(left.len(), |i| op(l[i], r[i]));
+    let nulls = combine_null_bitmaps(left.data(), right.data());
+    build_boolean_array(left.len(), values, nulls)
+}
+
+/// Compares each element of a decimal array with the `i128` value of a
+/// decimal scalar of the same precision and scale. The result is null
+/// where the array is null.
+fn compare_decimal_scalar<F>(
+    left: &Decimal128Array,
+    right: i128,
+    op: F,
+) -> Result<BooleanArray>
+where
+    F: Fn(i128, i128) -> bool,
+{
+    let l = left.values();
+    let values = collect_bool(left.len(), |i| op(l[i], right));
+    build_boolean_array(left.len(), values, null_bitmap(left.data()))
+}
+
+/// Creates the `<op>_decimal` and `<op>_decimal_scalar` kernels of a
+/// comparison operator
+macro_rules! make_decimal_comp_op {
+    ($OP:ident, $CMP:expr) => {
+        paste::paste! {
+            pub(crate) fn [<$OP _decimal>](
+                left: &Decimal128Array,
+                right: &Decimal128Array,
+            ) -> Result<BooleanArray> {
+                compare_decimal(left, right, $CMP)
+            }
+
+            pub(crate) fn [<$OP _decimal_scalar>](
+                left: &Decimal128Array,
+                right: i128,
+            ) -> Result<BooleanArray> {
+                compare_decimal_scalar(left, right, $CMP)
+            }
+        }
+    };
+}
+
+make_decimal_comp_op!(eq, |l, r| l == r);
+make_decimal_comp_op!(neq, |l, r| l != r);
+make_decimal_comp_op!(lt, |l, r| l < r);
+make_decimal_comp_op!(lt_eq, |l, r| l <= r);
+make_decimal_comp_op!(gt, |l, r| l > r);
+make_decimal_comp_op!(gt_eq, |l, r| l >= r);
+
+/// Evaluates a comparison of the dictionary array `array` with a scalar by
+/// comparing only its distinct values with `compare_values`, and then
+/// looking up the result of each key.
+///
+/// Returns `None` when `compare_values` does not support the dictionary
+/// values, in which case the caller falls back to the generic kernels.
+pub(crate) fn compare_dict_scalar<F>(
+    array: &dyn Array,
+    compare_values: F,
+) -> Result<Option<Result<ArrayRef>>>
+where
+    F: FnOnce(&ArrayRef) -> Result<Option<Result<ArrayRef>>>,
+{
+    macro_rules! compare_keys {
+        ($KEY:ty) => {{
+            let dict = as_dictionary_array::<$KEY>(array)?;
+            match compare_values(dict.values())? {
+                Some(Ok(values)) => Ok(Some(
+                    take(values.as_ref(), dict.keys(), None).map_err(Into::into),
+                )),
+                other => Ok(other),
+            }
+        }};
+    }
+
+    match array.data_type() {
+        DataType::Dictionary(key_type, _) => match key_type.as_ref() {
+            DataType::Int8 => compare_keys!(Int8Type),
+            DataType::Int16 => compare_keys!(Int16Type),
+            DataType::Int32 => compare_keys!(Int32Type),
+            DataType::Int64 => compare_keys!(Int64Type),
+            DataType::UInt8 => compare_keys!(UInt8Type),
+            DataType::UInt16 => compare_keys!(UInt16Type),
+            DataType::UInt32 => compare_keys!(UInt32Type),
+            DataType::UInt64 => compare_keys!(UInt64Type),
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::compute::eq_dyn_utf8_scalar;
+    use std::sync::Arc;
+
+    fn create_decimal_array(values: &[Option<i128>]) -> Decimal128Array {
+        values
+            .iter()
+            .copied()
+            .collect::<Decimal128Array>()
+            .with_precision_and_scale(25, 3)
+            .unwrap()
+    }
+
+    /// Unwraps the array returned by [`compare_dict_scalar`]
+    fn as_boolean(result: Result<Option<Result<ArrayRef>>>) -> BooleanArray {
+        let array = result.unwrap().unwrap().unwrap();
+        array
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn decimal_comparison() -> Result<()> {
+        // more than 64 values to cover full chunks of the bitmap
+        let left: Vec<_> = (0..100)
+            .map(|i| (i % 7 != 0).then_some(i as i128 - 50))
+            .collect();
+        let right: Vec<_> = (0..100)
+            .map(|i| (i % 11 != 0).then_some(50 - i as i128))
+            .collect();
+        let left_array = create_decimal_array(&left);
+        let right_array = create_decimal_array(&right);
+
+        let expected = |f: fn(&i128, &i128) -> bool| -> BooleanArray {
+            left.iter()
+                .zip(right.iter())
+                .map(|(l, r)| Some(f(l.as_ref()?, r.as_ref()?)))
+                .collect()
+        };
+        assert_eq!(eq_decimal(&left_array, &right_array)?, expected(i128::eq));
+        assert_eq!(neq_decimal(&left_array, &right_array)?, expected(i128::ne));
+        assert_eq!(lt_decimal(&left_array, &right_array)?, expected(i128::lt));
+        assert_eq!(
+            lt_eq_decimal(&left_array, &right_array)?,
+            expected(i128::le)
+        );
+        assert_eq!(gt_decimal(&left_array, &right_array)?, expected(i128::gt));
+        assert_eq!(
+            gt_eq_decimal(&left_array, &right_array)?,
+            expected(i128::ge)
+        );
+
+        // sliced arrays at different offsets
+        let left_slice = left_array.slice(3, 70);
+        let right_slice = right_array.slice(10, 70);
+        let result = lt_decimal(
+            left_slice.as_any().downcast_ref().unwrap(),
+            right_slice.as_any().downcast_ref().unwrap(),
+        )?;
+        let expected: BooleanArray = left[3..73]
+            .iter()
+            .zip(right[10..80].iter())
+            .map(|(l, r)| Some(l.as_ref()? < r.as_ref()?))
+            .collect();
+        assert_eq!(result, expected);
+
+        assert!(eq_decimal(&left_array, &create_decimal_array(&[Some(1)])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_scalar_comparison() -> Result<()> {
+        let values = [Some(-100), None, Some(0), Some(123), Some(124), None];
+        let array = create_decimal_array(&values);
+        let sliced = array.slice(1, 5);
+        let sliced = sliced.as_any().downcast_ref::<Decimal128Array>().unwrap();
+
+        assert_eq!(
+            gt_eq_decimal_scalar(&array, 123)?,
+            BooleanArray::from(vec![
+                Some(false),
+                None,
+                Some(false),
+                Some(true),
+                Some(true),
+                None
+            ])
+        );
+        assert_eq!(
+            eq_decimal_scalar(sliced, 0)?,
+            BooleanArray::from(vec![None, Some(true), Some(false), Some(false), None])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dictionary_scalar_comparison() {
+        let array: DictionaryArray<Int8Type> =
+            vec![Some("a"), None, Some("b"), Some("a"), Some("c")]
+                .into_iter()
+                .collect();
+
+        let result = compare_dict_scalar(&array, |values| {
+            // the values are compared once, not once per key
+            assert_eq!(values.len(), 3);
+            Ok(Some(
+                eq_dyn_utf8_scalar(values.as_ref(), "a")
+                    .map(|a| Arc::new(a) as ArrayRef)
+                    .map_err(Into::into),
+            ))
+        });
+        assert_eq!(
+            as_boolean(result),
+            BooleanArray::from(vec![
+                Some(true),
+                None,
+                Some(false),
+                Some(true),
+                Some(false)
+            ])
+        );
+
+        // unsupported values
+        let result = compare_dict_scalar(&array, |_| Ok(None)).unwrap();
+        assert!(result.is_none());
+
+        // not a dictionary
+        let array = StringArray::from(vec!["a"]);
+        let result = compare_dict_scalar(&array, |_| unreachable!()).unwrap();
+        assert!(result.is_none());
+    }
+}
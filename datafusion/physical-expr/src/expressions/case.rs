@@ -23,7 +23,7 @@ use crate::physical_expr::down_cast_any_ref;
 use crate::PhysicalExpr;
 use arrow::array::*;
 use arrow::compute::kernels::zip::zip;
-use arrow::compute::{and, eq_dyn, is_null, not, or, or_kleene};
+use arrow::compute::{and, eq_dyn, is_null, not, or, or_kleene, prep_null_mask_filter};
 use arrow::datatypes::{DataType, Schema};
 use arrow::record_batch::RecordBatch;
 use datafusion_common::{cast::as_boolean_array, DataFusionError, Result};
@@ -112,6 +112,18 @@ impl CaseExpr {
     }
 }
 
+/// Returns the rows for which `when_value` is true and that are in `remainder`,
+/// that is that were not matched by a previous WHEN, which a scalar WHEN value
+/// is not restricted to. Nulls are returned as false, as [`zip`] only looks at
+/// the values of its mask.
+fn matches(when_value: &BooleanArray, remainder: &BooleanArray) -> Result<BooleanArray> {
+    let matches = and(when_value, remainder)?;
+    Ok(match matches.data().null_buffer() {
+        Some(_) => prep_null_mask_filter(&matches),
+        None => matches,
+    })
+}
+
 impl CaseExpr {
     /// This function evaluates the form of CASE that matches an expression to fixed values.
     ///
@@ -138,6 +150,7 @@ impl CaseExpr {
             let when_value = when_value.into_array(batch.num_rows());
             // build boolean array representing which rows match the "when" value
             let when_match = eq_dyn(&when_value, base_value.as_ref())?;
+            let when_match = matches(&when_match, &remainder)?;
 
             let then_value = self.when_then_expr[i]
                 .1
@@ -197,6 +210,7 @@ impl CaseExpr {
             let when_value = when_value.into_array(batch.num_rows());
             let when_value = as_boolean_array(&when_value)
                 .expect("WHEN expression did not return a BooleanArray");
+            let when_value = &matches(when_value, &remainder)?;
 
             let then_value = self.when_then_expr[i]
                 .1
@@ -387,7 +401,7 @@ mod tests {
     use crate::expressions::col;
     use crate::expressions::lit;
     use crate::expressions::{binary, cast};
-    use crate::rewrite::TreeNodeRewritable;
+    use crate::rewrite::TreeNode;
     use arrow::array::StringArray;
     use arrow::buffer::Buffer;
     use arrow::datatypes::DataType::Float64;
@@ -522,6 +536,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn case_first_matching_literal_when() -> Result<()> {
+        let batch = case_test_batch()?;
+        let schema = batch.schema();
+
+        // CASE a WHEN 'foo' THEN 123 WHEN 'foo' THEN 456 END
+        let expr = generate_case_when_with_type_coercion(
+            Some(col("a", &schema)?),
+            vec![(lit("foo"), lit(123i32)), (lit("foo"), lit(456i32))],
+            None,
+            schema.as_ref(),
+        )?;
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        let result = as_int32_array(&result)?;
+
+        let expected = &Int32Array::from(vec![Some(123), None, None, None]);
+        assert_eq!(expected, result);
+
+        // CASE WHEN a = 'foo' THEN 123 WHEN true THEN 456 END
+        let when1 = binary(
+            col("a", &schema)?,
+            Operator::Eq,
+            lit("foo"),
+            &batch.schema(),
+        )?;
+        let expr = generate_case_when_with_type_coercion(
+            None,
+            vec![(when1, lit(123i32)), (lit(true), lit(456i32))],
+            None,
+            schema.as_ref(),
+        )?;
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        let result = as_int32_array(&result)?;
+
+        let expected =
+            &Int32Array::from(vec![Some(123), Some(456), Some(456), Some(456)]);
+        assert_eq!(expected, result);
+
+        Ok(())
+    }
+
     #[test]
     fn case_without_expr_divide_by_zero() -> Result<()> {
         let batch = case_test_batch1()?;
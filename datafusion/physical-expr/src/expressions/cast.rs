@@ -19,16 +19,26 @@ use std::any::Any;
 use std::fmt;
 use std::sync::Arc;
 
+use crate::intervals::{is_numeric_or_temporal, Interval};
 use crate::physical_expr::down_cast_any_ref;
-use crate::PhysicalExpr;
-use arrow::compute;
+use crate::{analysis_expect, AnalysisContext, PhysicalExpr};
+use arrow::array::{
+    ArrayRef, Int64Array, IntervalDayTimeArray, IntervalMonthDayNanoArray,
+};
 use arrow::compute::kernels;
+use arrow::compute::kernels::arity::try_unary;
 use arrow::compute::CastOptions;
-use arrow::datatypes::{DataType, Schema};
+use arrow::datatypes::{
+    DataType, Int64Type, IntervalDayTimeType, IntervalMonthDayNanoType, IntervalUnit,
+    Schema, TimeUnit,
+};
+use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
-use compute::can_cast_types;
+use chrono::{Duration, FixedOffset, LocalResult, NaiveDateTime, Offset, TimeZone};
+use datafusion_common::cast::{as_int64_array, as_primitive_array};
 use datafusion_common::ScalarValue;
 use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::type_coercion::can_cast_types;
 use datafusion_expr::ColumnarValue;
 
 /// provide DataFusion default cast options
@@ -111,6 +121,33 @@ impl PhysicalExpr for CastExpr {
             },
         )))
     }
+
+    /// Return the boundaries of the cast values, for the casts between numeric
+    /// and temporal types, which preserve the order of their input.
+    fn analyze(&self, context: AnalysisContext) -> AnalysisContext {
+        let context = self.expr.analyze(context);
+        let bounds = analysis_expect!(context, context.boundaries().cloned());
+        if !is_numeric_or_temporal(&bounds.min_value.get_datatype())
+            || !is_numeric_or_temporal(&self.cast_type)
+        {
+            return context.with_boundaries(None);
+        }
+
+        // Values that can not be cast are NULL, so the boundaries are unknown.
+        let cast = |value: &ScalarValue| {
+            let array =
+                cast_array(&value.to_array(), &self.cast_type, &self.cast_options)
+                    .ok()?;
+            ScalarValue::try_from_array(&array, 0)
+                .ok()
+                .filter(|value| !value.is_null())
+        };
+        let min_value = analysis_expect!(context, cast(&bounds.min_value));
+        let max_value = analysis_expect!(context, cast(&bounds.max_value));
+        context.with_boundaries(
+            Interval::new(min_value, max_value).to_boundaries(bounds.distinct_count),
+        )
+    }
 }
 
 impl PartialEq<dyn Any> for CastExpr {
@@ -134,19 +171,333 @@ pub fn cast_column(
     cast_options: &CastOptions,
 ) -> Result<ColumnarValue> {
     match value {
-        ColumnarValue::Array(array) => Ok(ColumnarValue::Array(
-            kernels::cast::cast_with_options(array, cast_type, cast_options)?,
-        )),
+        ColumnarValue::Array(array) => Ok(ColumnarValue::Array(cast_array(
+            array,
+            cast_type,
+            cast_options,
+        )?)),
         ColumnarValue::Scalar(scalar) => {
             let scalar_array = scalar.to_array();
-            let cast_array =
-                kernels::cast::cast_with_options(&scalar_array, cast_type, cast_options)?;
+            let cast_array = cast_array(&scalar_array, cast_type, cast_options)?;
             let cast_scalar = ScalarValue::try_from_array(&cast_array, 0)?;
             Ok(ColumnarValue::Scalar(cast_scalar))
         }
     }
 }
 
+/// Casts `array` to `cast_type`.
+///
+/// Unlike the arrow kernel, which keeps the values of the timestamps, the casts
+/// between timestamps with and without a time zone keep their wall clock times
+/// in the time zone, like in PostgreSQL: `2000-01-01T00:00:00` cast to a
+/// timestamp in `+08:00` is `2000-01-01T00:00:00+08:00`, and back.
+pub(crate) fn cast_array(
+    array: &ArrayRef,
+    cast_type: &DataType,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    match (array.data_type(), cast_type) {
+        (DataType::Timestamp(_, None), DataType::Timestamp(unit, Some(tz))) => {
+            let local_type = DataType::Timestamp(unit.clone(), None);
+            let local =
+                kernels::cast::cast_with_options(array, &local_type, cast_options)?;
+            let utc = shift_timestamps(&local, unit, tz, true)?;
+            Ok(kernels::cast::cast(&utc, cast_type)?)
+        }
+        (DataType::Timestamp(_, Some(tz)), DataType::Timestamp(unit, None)) => {
+            let utc_type = DataType::Timestamp(unit.clone(), Some(tz.clone()));
+            let utc = kernels::cast::cast_with_options(array, &utc_type, cast_options)?;
+            let local = shift_timestamps(&utc, unit, tz, false)?;
+            Ok(kernels::cast::cast(&local, cast_type)?)
+        }
+        (from_type, to_type) if is_duration_cast(from_type, to_type) => {
+            cast_durations(array, cast_type, cast_options)
+        }
+        _ => Ok(kernels::cast::cast_with_options(
+            array,
+            cast_type,
+            cast_options,
+        )?),
+    }
+}
+
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
+/// Returns true for the casts between durations of different units, and
+/// between durations and day-time intervals, that the arrow kernel lacks
+fn is_duration_cast(from_type: &DataType, to_type: &DataType) -> bool {
+    let is_day_time_interval = |dt: &DataType| {
+        matches!(
+            dt,
+            DataType::Interval(IntervalUnit::DayTime | IntervalUnit::MonthDayNano)
+        )
+    };
+    match (from_type, to_type) {
+        (DataType::Duration(_), DataType::Duration(_)) => true,
+        (DataType::Duration(_), _) => is_day_time_interval(to_type),
+        (_, DataType::Duration(_)) => is_day_time_interval(from_type),
+        _ => false,
+    }
+}
+
+/// Casts the durations or intervals of `array` to `cast_type` through their
+/// numbers of nanoseconds. The values are truncated to the unit of
+/// `cast_type`, and the intervals of months can't be cast to durations.
+fn cast_durations(
+    array: &ArrayRef,
+    cast_type: &DataType,
+    cast_options: &CastOptions,
+) -> Result<ArrayRef> {
+    let values = durations_to_nanos(array)?
+        .into_iter()
+        .map(
+            |nanos| match nanos.map(|n| n.and_then(|n| nanos_to(n, cast_type))) {
+                Some(Ok(value)) => Ok(Some(value)),
+                Some(Err(_)) if cast_options.safe => Ok(None),
+                Some(Err(e)) => Err(e),
+                None => Ok(None),
+            },
+        )
+        .collect::<Result<Vec<_>>>()?;
+    Ok(match cast_type {
+        DataType::Duration(_) => {
+            let values: ArrayRef = Arc::new(
+                values
+                    .into_iter()
+                    .map(|v| v.map(|v| v as i64))
+                    .collect::<Int64Array>(),
+            );
+            kernels::cast::cast(&values, cast_type)?
+        }
+        DataType::Interval(IntervalUnit::DayTime) => Arc::new(
+            values
+                .into_iter()
+                .map(|v| v.map(|v| v as i64))
+                .collect::<IntervalDayTimeArray>(),
+        ),
+        _ => Arc::new(values.into_iter().collect::<IntervalMonthDayNanoArray>()),
+    })
+}
+
+/// Returns the numbers of nanoseconds of the durations or day-time intervals
+/// of `array`
+fn durations_to_nanos(array: &ArrayRef) -> Result<Vec<Option<Result<i128>>>> {
+    Ok(match array.data_type() {
+        DataType::Duration(unit) => {
+            let per_unit = nanos_per_unit(unit);
+            let values = kernels::cast::cast(array, &DataType::Int64)?;
+            as_int64_array(&values)?
+                .iter()
+                .map(|v| v.map(|v| Ok(v as i128 * per_unit)))
+                .collect()
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            as_primitive_array::<IntervalDayTimeType>(array)?
+                .iter()
+                .map(|v| {
+                    v.map(|v| {
+                        let (days, millis) = IntervalDayTimeType::to_parts(v);
+                        Ok(days as i128 * NANOS_PER_DAY + millis as i128 * 1_000_000)
+                    })
+                })
+                .collect()
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            as_primitive_array::<IntervalMonthDayNanoType>(array)?
+                .iter()
+                .map(|v| {
+                    v.map(|v| {
+                        let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(v);
+                        if months != 0 {
+                            return Err(DataFusionError::Execution(format!(
+                                "Cannot cast an interval of {months} months to a duration"
+                            )));
+                        }
+                        Ok(days as i128 * NANOS_PER_DAY + nanos as i128)
+                    })
+                })
+                .collect()
+        }
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "Cannot cast {other:?} to a duration"
+            )))
+        }
+    })
+}
+
+/// Returns the native value of `cast_type` for a duration of `nanos`
+/// nanoseconds
+fn nanos_to(nanos: i128, cast_type: &DataType) -> Result<i128> {
+    let out_of_range = || {
+        DataFusionError::Execution(format!(
+            "Duration of {nanos} nanoseconds out of range for {cast_type:?}"
+        ))
+    };
+    match cast_type {
+        DataType::Duration(unit) => {
+            let value = nanos / nanos_per_unit(unit);
+            i64::try_from(value)
+                .map(i128::from)
+                .map_err(|_| out_of_range())
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            let days =
+                i32::try_from(nanos / NANOS_PER_DAY).map_err(|_| out_of_range())?;
+            let millis = ((nanos % NANOS_PER_DAY) / 1_000_000) as i32;
+            Ok(IntervalDayTimeType::make_value(days, millis) as i128)
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            let days =
+                i32::try_from(nanos / NANOS_PER_DAY).map_err(|_| out_of_range())?;
+            let nanos = (nanos % NANOS_PER_DAY) as i64;
+            Ok(IntervalMonthDayNanoType::make_value(0, days, nanos))
+        }
+        other => Err(DataFusionError::Internal(format!(
+            "Cannot cast a duration to {other:?}"
+        ))),
+    }
+}
+
+fn nanos_per_unit(unit: &TimeUnit) -> i128 {
+    match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    }
+}
+
+/// Converts the timestamps of `unit` of `array` between UTC and the wall clock
+/// times of the time zone `tz`, to UTC if `to_utc` is true
+fn shift_timestamps(
+    array: &ArrayRef,
+    unit: &TimeUnit,
+    tz: &str,
+    to_utc: bool,
+) -> Result<ArrayRef> {
+    let values = kernels::cast::cast(array, &DataType::Int64)?;
+    let values = values
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .ok_or_else(|| {
+            DataFusionError::Internal("Failed to cast timestamps to Int64".to_string())
+        })?;
+    let per_second = match unit {
+        TimeUnit::Second => 1,
+        TimeUnit::Millisecond => 1_000,
+        TimeUnit::Microsecond => 1_000_000,
+        TimeUnit::Nanosecond => 1_000_000_000,
+    };
+    let shifted = match parse_time_zone(tz)? {
+        ParsedTimeZone::Fixed(offset) => {
+            shift_values(values, per_second, &offset, to_utc)?
+        }
+        ParsedTimeZone::Named(tz) => shift_values(values, per_second, &tz, to_utc)?,
+    };
+    Ok(Arc::new(shifted))
+}
+
+fn shift_values<T: TimeZone>(
+    values: &Int64Array,
+    per_second: i64,
+    tz: &T,
+    to_utc: bool,
+) -> Result<Int64Array> {
+    let nanos_per_unit = 1_000_000_000 / per_second;
+    Ok(try_unary::<Int64Type, _, Int64Type>(values, |value| {
+        let datetime = NaiveDateTime::from_timestamp_opt(
+            value.div_euclid(per_second),
+            (value.rem_euclid(per_second) * nanos_per_unit) as u32,
+        )
+        .ok_or_else(|| {
+            ArrowError::ComputeError(format!("Timestamp {value} out of range"))
+        })?;
+        let offset = if to_utc {
+            local_offset(tz, &datetime)
+        } else {
+            tz.offset_from_utc_datetime(&datetime).fix()
+        };
+        let shift = offset.local_minus_utc() as i64 * per_second;
+        let shifted = if to_utc {
+            value.checked_sub(shift)
+        } else {
+            value.checked_add(shift)
+        };
+        shifted.ok_or_else(|| {
+            ArrowError::ComputeError(format!("Timestamp {value} out of range"))
+        })
+    })?)
+}
+
+/// Returns the offset of the wall clock time `local` in the time zone `tz`.
+/// Like in PostgreSQL, the times repeated when the clocks are set back are the
+/// ones of the later offset, and the times skipped when the clocks are set
+/// forward are shifted by the offset before the transition.
+fn local_offset<T: TimeZone>(tz: &T, local: &NaiveDateTime) -> FixedOffset {
+    match tz.offset_from_local_datetime(local) {
+        LocalResult::Single(offset) => offset.fix(),
+        LocalResult::Ambiguous(first, second) => {
+            let (first, second) = (first.fix(), second.fix());
+            if first.local_minus_utc() < second.local_minus_utc() {
+                first
+            } else {
+                second
+            }
+        }
+        LocalResult::None => tz
+            .offset_from_utc_datetime(&(*local - Duration::days(1)))
+            .fix(),
+    }
+}
+
+/// The time zone of a timestamp type
+enum ParsedTimeZone {
+    /// A fixed offset, like `+08:00`, `+0800` or `+08`
+    Fixed(FixedOffset),
+    /// A time zone of the IANA database, like `America/New_York`
+    Named(chrono_tz::Tz),
+}
+
+fn parse_time_zone(tz: &str) -> Result<ParsedTimeZone> {
+    if let Some(offset) = parse_fixed_offset(tz) {
+        return Ok(ParsedTimeZone::Fixed(offset));
+    }
+    tz.parse::<chrono_tz::Tz>()
+        .map(ParsedTimeZone::Named)
+        .map_err(|_| {
+            DataFusionError::Execution(format!(
+                "Invalid time zone '{tz}', expected an offset like '+08:00' \
+                 or a name like 'America/New_York'"
+            ))
+        })
+}
+
+fn parse_fixed_offset(tz: &str) -> Option<FixedOffset> {
+    let sign = match tz.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = &tz[1..];
+    let (hours, minutes) = match digits.len() {
+        2 => (digits, "00"),
+        4 => (&digits[..2], &digits[2..]),
+        5 if digits.as_bytes()[2] == b':' => (&digits[..2], &digits[3..]),
+        _ => return None,
+    };
+    if !hours
+        .bytes()
+        .chain(minutes.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
 /// Return a PhysicalExpression representing `expr` casted to
 /// `cast_type`, if any casting is needed.
 ///
@@ -194,11 +545,11 @@ mod tests {
         array::{
             Array, Decimal128Array, Float32Array, Float64Array, Int16Array, Int32Array,
             Int64Array, Int8Array, StringArray, Time64NanosecondArray,
-            TimestampNanosecondArray, UInt32Array,
+            TimestampNanosecondArray, TimestampSecondArray, UInt32Array,
         },
         datatypes::*,
     };
-    use datafusion_common::Result;
+    use datafusion_common::{ColumnStatistics, Precision, Result, Statistics};
 
     // runs an end-to-end test of physical type cast
     // 1. construct a record batch with a column "a" of type A
@@ -633,6 +984,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn cast_timestamps_with_time_zone() -> Result<()> {
+        let new_york = Some("America/New_York".to_string());
+        let tz_type = DataType::Timestamp(TimeUnit::Second, new_york.clone());
+        let naive_type = DataType::Timestamp(TimeUnit::Second, None);
+
+        // 2022-07-01T12:00:00, 2022-03-13T02:30:00 (skipped by the DST) and
+        // 2022-11-06T01:30:00 (repeated by the DST)
+        let naive: ArrayRef = Arc::new(TimestampSecondArray::from(vec![
+            Some(1656676800),
+            Some(1647138600),
+            Some(1667698200),
+            None,
+        ]));
+        let options = DEFAULT_DATAFUSION_CAST_OPTIONS;
+        let utc = cast_array(&naive, &tz_type, &options)?;
+        assert_eq!(utc.data_type(), &tz_type);
+        let expected: ArrayRef = Arc::new(
+            TimestampSecondArray::from(vec![
+                Some(1656691200),
+                Some(1647156600),
+                Some(1667716200),
+                None,
+            ])
+            .with_timezone_opt(new_york),
+        );
+        assert_eq!(&utc, &expected);
+
+        // 2022-03-13T03:30:00 and 2022-11-06T01:30:00 again
+        let local = cast_array(&utc, &naive_type, &options)?;
+        let expected: ArrayRef = Arc::new(TimestampSecondArray::from(vec![
+            Some(1656676800),
+            Some(1647142200),
+            Some(1667698200),
+            None,
+        ]));
+        assert_eq!(&local, &expected);
+
+        let err = cast_array(
+            &naive,
+            &DataType::Timestamp(TimeUnit::Second, Some("08:00".to_string())),
+            &options,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Invalid time zone '08:00', expected an offset like \
+             '+08:00' or a name like 'America/New_York'"
+        );
+        Ok(())
+    }
+
     #[test]
     fn invalid_cast() {
         // Ensure a useful error happens at plan time if invalid casts are used
@@ -666,4 +1069,36 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn cast_boundaries() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let statistics = Statistics {
+            column_statistics: vec![ColumnStatistics {
+                min_value: Precision::Exact(ScalarValue::Int32(Some(-3))),
+                max_value: Precision::Exact(ScalarValue::Int32(Some(300))),
+                ..ColumnStatistics::new_unknown()
+            }],
+            ..Statistics::new_unknown(&schema)
+        };
+
+        // CAST(a AS Int64) keeps the boundaries of 'a'
+        let expr = cast(col("a", &schema)?, &schema, DataType::Int64)?;
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let boundaries = expr.analyze(context).boundaries.unwrap();
+        assert_eq!(boundaries.min_value, ScalarValue::Int64(Some(-3)));
+        assert_eq!(boundaries.max_value, ScalarValue::Int64(Some(300)));
+
+        // 300 does not fit in an Int8, so the boundaries are unknown
+        let expr = cast(col("a", &schema)?, &schema, DataType::Int8)?;
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        assert!(expr.analyze(context).boundaries.is_none());
+
+        // Casts to strings do not preserve the order of the values
+        let expr = cast(col("a", &schema)?, &schema, DataType::Utf8)?;
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        assert!(expr.analyze(context).boundaries.is_none());
+
+        Ok(())
+    }
 }
@@ -214,7 +214,9 @@ mod test {
     use arrow::array::StringArray;
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::record_batch::RecordBatch;
-    use datafusion_common::{ColumnStatistics, Result, ScalarValue, Statistics};
+    use datafusion_common::{
+        ColumnStatistics, Precision, Result, ScalarValue, Statistics,
+    };
     use std::sync::Arc;
 
     #[test]
@@ -266,23 +268,23 @@ mod test {
 
         let columns = vec![
             ColumnStatistics {
-                min_value: Some(ScalarValue::Int32(Some(1))),
-                max_value: Some(ScalarValue::Int32(Some(100))),
-                distinct_count: Some(15),
+                min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                max_value: Precision::Exact(ScalarValue::Int32(Some(100))),
+                distinct_count: Precision::Exact(15),
                 ..Default::default()
             },
             ColumnStatistics::default(),
             ColumnStatistics {
-                min_value: Some(ScalarValue::Int32(Some(1))),
-                max_value: Some(ScalarValue::Int32(Some(75))),
-                distinct_count: None,
+                min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+                max_value: Precision::Inexact(ScalarValue::Int32(Some(75))),
+                distinct_count: Precision::Absent,
                 ..Default::default()
             },
         ];
 
         let statistics = Statistics {
-            column_statistics: Some(columns),
-            ..Default::default()
+            column_statistics: columns,
+            ..Statistics::new_unknown(&schema)
         };
 
         (schema, statistics)
@@ -331,7 +331,7 @@ mod tests {
         let struct_col_expr = col("s", &schema).unwrap();
         let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(struct_col)])?;
 
-        let int_field_key = ScalarValue::Utf8(Some("foo".to_string()));
+        let int_field_key = ScalarValue::Utf8(Some("foo".into()));
         let get_field_expr = Arc::new(GetIndexedFieldExpr::new(
             struct_col_expr.clone(),
             int_field_key,
@@ -343,7 +343,7 @@ mod tests {
         let expected = &Int64Array::from(expected_ints);
         assert_eq!(expected, result);
 
-        let list_field_key = ScalarValue::Utf8(Some("bar".to_string()));
+        let list_field_key = ScalarValue::Utf8(Some("bar".into()));
         let get_list_expr =
             Arc::new(GetIndexedFieldExpr::new(struct_col_expr, list_field_key));
         let result = get_list_expr.evaluate(&batch)?.into_array(batch.num_rows());
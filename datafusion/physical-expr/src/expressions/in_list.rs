@@ -22,10 +22,11 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use crate::expressions::BinaryExpr;
 use crate::hash_utils::HashValue;
 use crate::physical_expr::down_cast_any_ref;
 use crate::utils::expr_list_eq_any_order;
-use crate::PhysicalExpr;
+use crate::{analysis_expect, AnalysisContext, ExprBoundaries, PhysicalExpr};
 use arrow::array::*;
 use arrow::compute::take;
 use arrow::datatypes::*;
@@ -38,7 +39,7 @@ use datafusion_common::{
     },
     DataFusionError, Result, ScalarValue,
 };
-use datafusion_expr::ColumnarValue;
+use datafusion_expr::{ColumnarValue, Operator};
 use hashbrown::hash_map::RawEntryMut;
 use hashbrown::HashMap;
 
@@ -238,6 +239,50 @@ fn try_cast_static_filter_to_set(
     make_set(evaluate_list(list, &batch)?.as_ref())
 }
 
+/// A hash set of values, against which `IN` tests the values of an array, for
+/// example the results of the subquery of an `IN`
+pub struct InSet {
+    set: Box<dyn Set>,
+    data_type: DataType,
+}
+
+impl InSet {
+    /// Create the set of the values of `array`, whose dictionaries are
+    /// flattened
+    pub fn try_new(array: &dyn Array) -> Result<Self> {
+        let array = match array.data_type() {
+            DataType::Dictionary(_, value_type) => {
+                arrow::compute::cast(&make_array(array.data().clone()), value_type)?
+            }
+            _ => make_array(array.data().clone()),
+        };
+        Ok(Self {
+            set: make_set(array.as_ref())?,
+            data_type: array.data_type().clone(),
+        })
+    }
+
+    /// The type of the values of the set
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    /// Returns whether each value of `array`, of the type of the set or a
+    /// dictionary of it, is in the set, or not in it if `negated`: null if
+    /// the value is null, or if it is not in the set and the set has a null.
+    pub fn contains(&self, array: &dyn Array, negated: bool) -> Result<BooleanArray> {
+        self.set.contains(array, negated)
+    }
+}
+
+impl Debug for InSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("InSet")
+            .field("data_type", &self.data_type)
+            .finish()
+    }
+}
+
 impl InListExpr {
     /// Create a new InList expression
     pub fn new(
@@ -332,6 +377,47 @@ impl PhysicalExpr for InListExpr {
             &self.input_schema,
         )
     }
+
+    /// Return the boundaries of this expression's result, estimated as the
+    /// disjunction of the (mutually exclusive) equalities with each of the
+    /// distinct values of the list.
+    fn analyze(&self, context: AnalysisContext) -> AnalysisContext {
+        let mut values: Vec<ScalarValue> = vec![];
+        let mut branches = vec![];
+        let mut selectivity = 0.0;
+        for item in &self.list {
+            let value = analysis_expect!(
+                context,
+                item.analyze(context.clone())
+                    .boundaries()
+                    .and_then(ExprBoundaries::reduce)
+            );
+            // NULLs never match
+            if value.is_null() || values.contains(&value) {
+                continue;
+            }
+            values.push(value);
+
+            let eq = BinaryExpr::new(self.expr.clone(), Operator::Eq, item.clone());
+            let branch = eq.analyze(context.clone());
+            let item_selectivity = analysis_expect!(
+                context,
+                branch.boundaries().and_then(|b| b.selectivity)
+            );
+            if item_selectivity > 0.0 {
+                branches.push(branch);
+            }
+            selectivity += item_selectivity;
+        }
+
+        if self.negated {
+            return context
+                .with_boundaries(Some(ExprBoundaries::new_predicate(1.0 - selectivity)));
+        }
+        context
+            .with_column_union(&branches)
+            .with_boundaries(Some(ExprBoundaries::new_predicate(selectivity)))
+    }
 }
 
 impl PartialEq<dyn Any> for InListExpr {
@@ -1040,4 +1126,40 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn in_list_boundaries() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        let context = AnalysisContext::new(
+            &schema,
+            vec![Some(ExprBoundaries::new(
+                ScalarValue::Int64(Some(1)),
+                ScalarValue::Int64(Some(100)),
+                None,
+            ))],
+        );
+        // 200 is out of the range of the column, and duplicates are ignored
+        let list = vec![lit(10i64), lit(20i64), lit(200i64), lit(10i64)];
+
+        // a IN (10, 20, 200, 10)
+        let expr = in_list(col("a", &schema)?, list.clone(), &false, &schema)?;
+        let result = expr.analyze(context.clone());
+        assert_eq!(result.boundaries().unwrap().selectivity, Some(0.02));
+        assert_eq!(
+            result.column_boundaries[0],
+            Some(ExprBoundaries::new(
+                ScalarValue::Int64(Some(10)),
+                ScalarValue::Int64(Some(20)),
+                None
+            ))
+        );
+
+        // a NOT IN (10, 20, 200, 10)
+        let expr = in_list(col("a", &schema)?, list, &true, &schema)?;
+        let result = expr.analyze(context.clone());
+        assert_eq!(result.boundaries().unwrap().selectivity, Some(0.98));
+        assert_eq!(result.column_boundaries, context.column_boundaries);
+
+        Ok(())
+    }
 }
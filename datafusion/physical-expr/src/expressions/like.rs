@@ -25,7 +25,11 @@ use arrow_schema::{DataType, Schema};
 use datafusion_common::{DataFusionError, Result, ScalarValue};
 use datafusion_expr::ColumnarValue;
 
-use crate::{physical_expr::down_cast_any_ref, AnalysisContext, PhysicalExpr};
+use crate::expressions::Column;
+use crate::{
+    analysis_expect, physical_expr::down_cast_any_ref, AnalysisContext, ExprBoundaries,
+    PhysicalExpr,
+};
 
 use arrow::compute::kernels::comparison::{
     ilike_utf8, like_utf8, nilike_utf8, nlike_utf8,
@@ -182,9 +186,108 @@ impl PhysicalExpr for LikeExpr {
         )))
     }
 
-    /// Return the boundaries of this binary expression's result.
+    /// Return the boundaries of this expression's result. Only case sensitive
+    /// patterns starting with a literal prefix (e.g. `'abc%'`) can be analyzed,
+    /// since they can only select the values within `['abc', 'abd')`.
     fn analyze(&self, context: AnalysisContext) -> AnalysisContext {
-        context.with_boundaries(None)
+        if self.case_insensitive {
+            return context.with_boundaries(None);
+        }
+        let pattern = analysis_expect!(
+            context,
+            self.pattern
+                .analyze(context.clone())
+                .boundaries()
+                .and_then(ExprBoundaries::reduce)
+        );
+        let pattern = match pattern {
+            ScalarValue::Utf8(Some(pattern)) | ScalarValue::LargeUtf8(Some(pattern)) => {
+                pattern
+            }
+            _ => return context.with_boundaries(None),
+        };
+        let (prefix, has_wildcards) = like_prefix(&pattern);
+        if prefix.is_empty() {
+            return context.with_boundaries(None);
+        }
+
+        let bounds =
+            analysis_expect!(context, self.expr.analyze(context.clone()).boundaries);
+        let (min, max, large) = match (&bounds.min_value, &bounds.max_value) {
+            (ScalarValue::Utf8(Some(min)), ScalarValue::Utf8(Some(max))) => {
+                (min.as_ref(), max.as_ref(), false)
+            }
+            (ScalarValue::LargeUtf8(Some(min)), ScalarValue::LargeUtf8(Some(max))) => {
+                (min.as_ref(), max.as_ref(), true)
+            }
+            _ => return context.with_boundaries(None),
+        };
+
+        // The selected values are within [prefix, upper), or equal to the
+        // prefix when there are no wildcards.
+        let upper = if has_wildcards {
+            prefix_upper_bound(prefix)
+        } else {
+            None
+        };
+        let never_selects = prefix > max
+            || match &upper {
+                Some(upper) => upper.as_str() <= min,
+                None => !has_wildcards && prefix < min,
+            };
+        let always_selects = if has_wildcards {
+            &pattern[prefix.len()..] == "%"
+                && min.starts_with(prefix)
+                && max.starts_with(prefix)
+        } else {
+            min == prefix && max == prefix
+        };
+
+        let selectivity = if never_selects {
+            0.0
+        } else if always_selects {
+            1.0
+        } else if has_wildcards {
+            analysis_expect!(
+                context,
+                string_range_fraction(min, max, prefix, upper.as_deref())
+            )
+        } else {
+            let distinct_count = analysis_expect!(
+                context,
+                bounds.distinct_count.filter(|count| *count > 0)
+            );
+            1.0 / distinct_count as f64
+        };
+
+        if self.negated {
+            return context
+                .with_boundaries(Some(ExprBoundaries::new_predicate(1.0 - selectivity)));
+        }
+
+        // Narrow down the boundaries of the column to the selected range.
+        let context = match self.expr.as_any().downcast_ref::<Column>() {
+            Some(column) if selectivity > 0.0 => {
+                let new_min = if prefix > min { prefix } else { min };
+                let new_max = match &upper {
+                    Some(upper) if upper.as_str() < max => upper.as_str(),
+                    None if !has_wildcards => prefix,
+                    _ => max,
+                };
+                let scalar = |value: &str| match large {
+                    true => ScalarValue::LargeUtf8(Some(value.into())),
+                    false => ScalarValue::Utf8(Some(value.into())),
+                };
+                let column_bounds = ExprBoundaries::new(
+                    scalar(new_min),
+                    scalar(new_max),
+                    bounds.distinct_count,
+                );
+                context.with_column_update(column.index(), column_bounds)
+            }
+            _ => context,
+        };
+        context.with_boundaries(Some(ExprBoundaries::new_predicate(selectivity)))
     }
 }
 
@@ -202,6 +305,64 @@ impl PartialEq<dyn Any> for LikeExpr {
     }
 }
 
+/// Split a LIKE pattern into its literal prefix (everything before the first
+/// wildcard or escape character) and whether there is anything after it.
+fn like_prefix(pattern: &str) -> (&str, bool) {
+    match pattern.find(['%', '_', '\\']) {
+        Some(end) => (&pattern[..end], true),
+        None => (pattern, false),
+    }
+}
+
+/// The smallest string that is greater than all strings starting with `prefix`,
+/// if it can be built by incrementing the last character of the prefix.
+fn prefix_upper_bound(prefix: &str) -> Option<String> {
+    let mut chars = prefix.chars();
+    let last = chars.next_back()?;
+    let next = char::from_u32(last as u32 + 1)?;
+    Some(chars.chain(std::iter::once(next)).collect())
+}
+
+/// Estimate which fraction of the strings between `min` and `max` are within
+/// `[start, end)` (`end` being unbounded when `None`), assuming they are
+/// uniformly distributed. Strings are mapped to numbers using up to 8 bytes
+/// after the prefix that `min` and `max` have in common.
+fn string_range_fraction(
+    min: &str,
+    max: &str,
+    start: &str,
+    end: Option<&str>,
+) -> Option<f64> {
+    let common = min
+        .bytes()
+        .zip(max.bytes())
+        .take_while(|(min, max)| min == max)
+        .count();
+    let position = |value: &str| {
+        let bytes = value.as_bytes().get(common..).unwrap_or_default();
+        let mut buffer = [0u8; 8];
+        let len = bytes.len().min(8);
+        buffer[..len].copy_from_slice(&bytes[..len]);
+        u64::from_be_bytes(buffer) as f64
+    };
+
+    let (low, high) = (position(min), position(max));
+    if high <= low {
+        return None;
+    }
+    let clamp = |value: f64| value.clamp(low, high);
+    let start = if start < min {
+        low
+    } else {
+        clamp(position(start))
+    };
+    let end = match end {
+        Some(end) if end <= max => clamp(position(end)),
+        _ => high,
+    };
+    Some(((end - start) / (high - low)).clamp(0.0, 1.0))
+}
+
 macro_rules! binary_string_array_op_scalar {
     ($LEFT:expr, $RIGHT:expr, $OP:ident, $OP_TYPE:expr) => {{
         let result: Result<Arc<dyn Array>> = match $LEFT.data_type() {
@@ -292,7 +453,7 @@ pub fn like(
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::expressions::col;
+    use crate::expressions::{col, lit};
     use arrow::array::BooleanArray;
     use arrow_schema::Field;
     use datafusion_common::cast::as_boolean_array;
@@ -364,4 +525,67 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn like_boundaries() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Utf8, false)]);
+        let context = AnalysisContext::new(
+            &schema,
+            vec![Some(ExprBoundaries::new(
+                ScalarValue::from("apple"),
+                ScalarValue::from("cherry"),
+                None,
+            ))],
+        );
+        let analyze = |negated: bool, case_insensitive: bool, pattern: &str| {
+            let expr = like(
+                negated,
+                case_insensitive,
+                col("a", &schema).unwrap(),
+                lit(pattern),
+                &schema,
+            )
+            .unwrap();
+            expr.analyze(context.clone())
+        };
+
+        // about half of the range between "apple" and "cherry" starts with "b"
+        let result = analyze(false, false, "b%");
+        let selectivity = result.boundaries().unwrap().selectivity.unwrap();
+        assert!(selectivity > 0.5 && selectivity < 0.52, "{selectivity}");
+        assert_eq!(
+            result.column_boundaries[0],
+            Some(ExprBoundaries::new(
+                ScalarValue::from("b"),
+                ScalarValue::from("c"),
+                None
+            ))
+        );
+
+        // outside of the range of the column
+        let result = analyze(false, false, "d%");
+        assert_eq!(result.boundaries().unwrap().selectivity, Some(0.0));
+        let result = analyze(true, false, "d%");
+        assert_eq!(result.boundaries().unwrap().selectivity, Some(1.0));
+
+        // all the values start with the prefix
+        let context = context.clone().with_column_update(
+            0,
+            ExprBoundaries::new(
+                ScalarValue::from("blue"),
+                ScalarValue::from("brown"),
+                None,
+            ),
+        );
+        let expr = like(false, false, col("a", &schema)?, lit("b%"), &schema)?;
+        let result = expr.analyze(context);
+        assert_eq!(result.boundaries().unwrap().selectivity, Some(1.0));
+
+        // patterns without a literal prefix, or case insensitive ones, are unknown
+        assert_eq!(analyze(false, false, "%").boundaries(), None);
+        assert_eq!(analyze(false, false, "%b").boundaries(), None);
+        assert_eq!(analyze(false, true, "b%").boundaries(), None);
+
+        Ok(())
+    }
 }
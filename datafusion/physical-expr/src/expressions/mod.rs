@@ -33,6 +33,7 @@ mod negative;
 mod no_op;
 mod not;
 mod nullif;
+mod subquery;
 mod try_cast;
 
 /// Module with some convenient methods used in expression building
@@ -46,6 +47,7 @@ pub use crate::aggregate::approx_percentile_cont::ApproxPercentileCont;
 pub use crate::aggregate::approx_percentile_cont_with_weight::ApproxPercentileContWithWeight;
 pub use crate::aggregate::array_agg::ArrayAgg;
 pub use crate::aggregate::array_agg_distinct::DistinctArrayAgg;
+pub use crate::aggregate::array_agg_ordered::OrderSensitiveArrayAgg;
 pub use crate::aggregate::average::{Avg, AvgAccumulator};
 pub use crate::aggregate::build_in::create_aggregate_expr;
 pub use crate::aggregate::correlation::Correlation;
@@ -80,7 +82,7 @@ pub use cast::{
 pub use column::{col, Column, UnKnownColumn};
 pub use datetime::DateTimeIntervalExpr;
 pub use get_indexed_field::GetIndexedFieldExpr;
-pub use in_list::{in_list, InListExpr};
+pub use in_list::{in_list, InListExpr, InSet};
 pub use is_not_null::{is_not_null, IsNotNullExpr};
 pub use is_null::{is_null, IsNullExpr};
 pub use like::{like, LikeExpr};
@@ -89,6 +91,10 @@ pub use negative::{negative, NegativeExpr};
 pub use no_op::NoOp;
 pub use not::{not, NotExpr};
 pub use nullif::nullif_func;
+pub use subquery::{
+    bind_subquery_results, InSubqueryExpr, ScalarSubqueryExpr, SubqueryId,
+    SubqueryResult, SubqueryResults,
+};
 pub use try_cast::{try_cast, TryCastExpr};
 
 /// returns the name of the state
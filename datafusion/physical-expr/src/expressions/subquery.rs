@@ -0,0 +1,405 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Expressions reading the results of uncorrelated subqueries, which are
+//! computed once per execution of a plan and then bound to the expressions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use arrow::array::{new_empty_array, ArrayRef};
+use arrow::compute::{cast, concat};
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::tree_node::TreeNode;
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::ColumnarValue;
+
+use super::in_list::InSet;
+use super::Literal;
+use crate::physical_expr::down_cast_any_ref;
+use crate::PhysicalExpr;
+
+static NEXT_SUBQUERY_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Identifies an uncorrelated subquery planned in a physical plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubqueryId(usize);
+
+impl SubqueryId {
+    /// Returns a new identifier, unique in the process
+    pub fn next() -> Self {
+        Self(NEXT_SUBQUERY_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Displayed like the aliases generated by the optimizer, so that the
+/// canonical forms of plans don't depend on the planned subqueries
+impl fmt::Display for SubqueryId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "__subquery_{}", self.0)
+    }
+}
+
+/// The results of an uncorrelated subquery
+#[derive(Debug, Clone)]
+pub enum SubqueryResult {
+    /// The value of a scalar subquery, null if it returns no rows
+    Scalar(ScalarValue),
+    /// The values returned by the subquery of an `IN`
+    Set(Arc<InSet>),
+}
+
+impl SubqueryResult {
+    /// The value of a scalar subquery returning a column of `data_type`,
+    /// which fails if it returns more than one row
+    pub fn try_new_scalar(data_type: &DataType, batches: &[RecordBatch]) -> Result<Self> {
+        let mut rows = batches
+            .iter()
+            .flat_map(|batch| (0..batch.num_rows()).map(move |row| (batch, row)));
+        let value = match (rows.next(), rows.next()) {
+            (None, _) => ScalarValue::try_from(data_type)?,
+            (Some((batch, row)), None) => {
+                ScalarValue::try_from_array(batch.column(0), row)?
+            }
+            (Some(_), Some(_)) => {
+                return Err(DataFusionError::Execution(
+                    "A scalar subquery returned more than one row".to_string(),
+                ))
+            }
+        };
+        Ok(Self::Scalar(value))
+    }
+
+    /// The set of the values returned by the subquery of an `IN`, returning a
+    /// column of `data_type`
+    pub fn try_new_set(data_type: &DataType, batches: &[RecordBatch]) -> Result<Self> {
+        let arrays = batches
+            .iter()
+            .map(|batch| batch.column(0).as_ref())
+            .collect::<Vec<_>>();
+        let values: ArrayRef = if arrays.is_empty() {
+            new_empty_array(data_type)
+        } else {
+            concat(&arrays)?
+        };
+        Ok(Self::Set(Arc::new(InSet::try_new(values.as_ref())?)))
+    }
+}
+
+/// The results of the uncorrelated subqueries of an execution, by subquery
+pub type SubqueryResults = HashMap<SubqueryId, SubqueryResult>;
+
+fn unbound(id: SubqueryId) -> DataFusionError {
+    DataFusionError::Internal(format!(
+        "The results of {id} are not bound to its expressions"
+    ))
+}
+
+/// A scalar subquery, replaced with its value by [`bind_subquery_results`]
+#[derive(Debug)]
+pub struct ScalarSubqueryExpr {
+    id: SubqueryId,
+    data_type: DataType,
+}
+
+impl ScalarSubqueryExpr {
+    /// Create the scalar subquery `id`, returning a value of `data_type`
+    pub fn new(id: SubqueryId, data_type: DataType) -> Self {
+        Self { id, data_type }
+    }
+
+    /// Identifies the subquery
+    pub fn id(&self) -> SubqueryId {
+        self.id
+    }
+}
+
+impl fmt::Display for ScalarSubqueryExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({})", self.id)
+    }
+}
+
+impl PhysicalExpr for ScalarSubqueryExpr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(self.data_type.clone())
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn evaluate(&self, _batch: &RecordBatch) -> Result<ColumnarValue> {
+        Err(unbound(self.id))
+    }
+
+    fn children(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(self)
+    }
+}
+
+impl PartialEq<dyn Any> for ScalarSubqueryExpr {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| self.id == x.id)
+            .unwrap_or(false)
+    }
+}
+
+/// Tests whether the values of `expr` are in the results of the subquery
+/// `id`, or not in them if `negated`, which are bound to the expression by
+/// [`bind_subquery_results`]
+#[derive(Debug)]
+pub struct InSubqueryExpr {
+    expr: Arc<dyn PhysicalExpr>,
+    id: SubqueryId,
+    negated: bool,
+    set: Option<Arc<InSet>>,
+}
+
+impl InSubqueryExpr {
+    /// Create the test of the values of `expr` against the results of the
+    /// subquery `id`
+    pub fn new(expr: Arc<dyn PhysicalExpr>, id: SubqueryId, negated: bool) -> Self {
+        Self {
+            expr,
+            id,
+            negated,
+            set: None,
+        }
+    }
+
+    /// The expression whose values are tested
+    pub fn expr(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.expr
+    }
+
+    /// Identifies the subquery
+    pub fn id(&self) -> SubqueryId {
+        self.id
+    }
+
+    /// Returns true for a `NOT IN`
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
+}
+
+impl fmt::Display for InSubqueryExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negated {
+            write!(f, "{} NOT IN ({})", self.expr, self.id)
+        } else {
+            write!(f, "{} IN ({})", self.expr, self.id)
+        }
+    }
+}
+
+impl PhysicalExpr for InSubqueryExpr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        let set = self.set.as_ref().ok_or_else(|| unbound(self.id))?;
+        let value = self.expr.evaluate(batch)?.into_array(batch.num_rows());
+        let value = match value.data_type() {
+            DataType::Dictionary(_, value_type)
+                if value_type.as_ref() == set.data_type() =>
+            {
+                value
+            }
+            data_type if data_type == set.data_type() => value,
+            _ => cast(&value, set.data_type())?,
+        };
+        let contains = set.contains(value.as_ref(), self.negated)?;
+        Ok(ColumnarValue::Array(Arc::new(contains)))
+    }
+
+    fn children(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(Self {
+            expr: children[0].clone(),
+            id: self.id,
+            negated: self.negated,
+            set: self.set.clone(),
+        }))
+    }
+}
+
+impl PartialEq<dyn Any> for InSubqueryExpr {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                self.expr.eq(&x.expr) && self.id == x.id && self.negated == x.negated
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Returns `expr` reading the `results` of its subqueries: its scalar
+/// subqueries are replaced with their values, and its `IN` subqueries test
+/// the values against the results. Fails if the results of a subquery are
+/// missing.
+pub fn bind_subquery_results(
+    expr: Arc<dyn PhysicalExpr>,
+    results: &SubqueryResults,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    expr.transform_up(&|expr| {
+        let any = expr.as_any();
+        if let Some(subquery) = any.downcast_ref::<ScalarSubqueryExpr>() {
+            return match results.get(&subquery.id) {
+                Some(SubqueryResult::Scalar(value)) => {
+                    Ok(Some(Arc::new(Literal::new(value.clone())) as _))
+                }
+                _ => Err(unbound(subquery.id)),
+            };
+        }
+        if let Some(subquery) = any.downcast_ref::<InSubqueryExpr>() {
+            return match results.get(&subquery.id) {
+                Some(SubqueryResult::Set(set)) => Ok(Some(Arc::new(InSubqueryExpr {
+                    expr: subquery.expr.clone(),
+                    id: subquery.id,
+                    negated: subquery.negated,
+                    set: Some(set.clone()),
+                }) as _)),
+                _ => Err(unbound(subquery.id)),
+            };
+        }
+        Ok(None)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, col};
+    use arrow::array::{BooleanArray, Int32Array, Int64Array};
+    use arrow::datatypes::Field;
+    use datafusion_common::cast::as_boolean_array;
+    use datafusion_expr::Operator;
+
+    fn batch(values: Vec<Option<i32>>) -> Result<RecordBatch> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        Ok(RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(Int32Array::from(values))],
+        )?)
+    }
+
+    #[test]
+    fn scalar_subquery() -> Result<()> {
+        let batch = batch(vec![Some(1), Some(2)])?;
+        let schema = batch.schema();
+        let id = SubqueryId::next();
+        let subquery = Arc::new(ScalarSubqueryExpr::new(id, DataType::Int32));
+        let expr = binary(col("a", &schema)?, Operator::Plus, subquery, &schema)?;
+        assert_eq!(expr.to_string(), format!("a@0 + ({id})"));
+        assert!(expr.evaluate(&batch).is_err());
+
+        let result = SubqueryResult::try_new_scalar(
+            &DataType::Int32,
+            &[self::batch(vec![])?, self::batch(vec![Some(10)])?],
+        )?;
+        let bound = bind_subquery_results(expr.clone(), &[(id, result)].into())?;
+        let value = bound.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(
+            value.as_any().downcast_ref::<Int32Array>().unwrap(),
+            &Int32Array::from(vec![11, 12])
+        );
+
+        // no rows
+        let result = SubqueryResult::try_new_scalar(&DataType::Int32, &[])?;
+        assert!(matches!(
+            result,
+            SubqueryResult::Scalar(ScalarValue::Int32(None))
+        ));
+        // more than one row
+        let rows = self::batch(vec![Some(1), Some(2)])?;
+        assert!(SubqueryResult::try_new_scalar(&DataType::Int32, &[rows]).is_err());
+        // missing results
+        assert!(bind_subquery_results(expr, &SubqueryResults::new()).is_err());
+        Ok(())
+    }
+
+    fn evaluate_in(negated: bool, values: Vec<Option<i64>>) -> Result<BooleanArray> {
+        let batch = batch(vec![Some(1), Some(2), None])?;
+        let id = SubqueryId::next();
+        let expr = Arc::new(InSubqueryExpr::new(col("a", &batch.schema())?, id, negated));
+        let values = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("b", DataType::Int64, true)])),
+            vec![Arc::new(Int64Array::from(values))],
+        )?;
+        let result = SubqueryResult::try_new_set(&DataType::Int64, &[values])?;
+        let bound = bind_subquery_results(expr, &[(id, result)].into())?;
+        let value = bound.evaluate(&batch)?.into_array(batch.num_rows());
+        Ok(as_boolean_array(&value)?.clone())
+    }
+
+    #[test]
+    fn in_subquery() -> Result<()> {
+        assert_eq!(
+            evaluate_in(false, vec![Some(1), Some(3)])?,
+            BooleanArray::from(vec![Some(true), Some(false), None])
+        );
+        assert_eq!(
+            evaluate_in(true, vec![Some(1), Some(3)])?,
+            BooleanArray::from(vec![Some(false), Some(true), None])
+        );
+        // the values not in a set containing a null are unknown
+        assert_eq!(
+            evaluate_in(true, vec![Some(1), None])?,
+            BooleanArray::from(vec![Some(false), None, None])
+        );
+        assert_eq!(
+            evaluate_in(false, vec![])?,
+            BooleanArray::from(vec![Some(false), Some(false), None])
+        );
+        Ok(())
+    }
+}
@@ -88,6 +88,25 @@ pub fn create_physical_expr(
                 }
             })
         }
+        BuiltinScalarFunction::TryToTimestamp => {
+            Arc::new(match input_phy_exprs[0].data_type(input_schema) {
+                Ok(DataType::Int64) | Ok(DataType::Timestamp(_, None)) => {
+                    |col_values: &[ColumnarValue]| {
+                        cast_column(
+                            &col_values[0],
+                            &DataType::Timestamp(TimeUnit::Nanosecond, None),
+                            &DEFAULT_DATAFUSION_CAST_OPTIONS,
+                        )
+                    }
+                }
+                Ok(DataType::Utf8) => datetime_expressions::try_to_timestamp,
+                other => {
+                    return Err(DataFusionError::Internal(format!(
+                        "Unsupported data type {other:?} for function try_to_timestamp",
+                    )));
+                }
+            })
+        }
         BuiltinScalarFunction::ToTimestampMillis => {
             Arc::new(match input_phy_exprs[0].data_type(input_schema) {
                 Ok(DataType::Int64) | Ok(DataType::Timestamp(_, None)) => {
@@ -164,9 +183,9 @@ pub fn create_physical_expr(
         BuiltinScalarFunction::ArrowTypeof => {
             let input_data_type = input_phy_exprs[0].data_type(input_schema)?;
             Arc::new(move |_| {
-                Ok(ColumnarValue::Scalar(ScalarValue::Utf8(Some(format!(
+                Ok(ColumnarValue::Scalar(ScalarValue::from(format!(
                     "{input_data_type}"
-                )))))
+                ))))
             })
         }
         // These don't need args and input schema
@@ -340,6 +359,9 @@ pub fn create_physical_fun(
         BuiltinScalarFunction::Atan2 => {
             Arc::new(|args| make_scalar_function(math_expressions::atan2)(args))
         }
+        BuiltinScalarFunction::TryDivide => {
+            Arc::new(|args| make_scalar_function(math_expressions::try_divide)(args))
+        }
 
         // string functions
         BuiltinScalarFunction::MakeArray => Arc::new(array_expressions::array),
@@ -744,19 +766,23 @@ pub fn create_physical_fun(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::expressions::{col, lit};
+    use crate::expressions::{binary, cast, col, lit};
     use crate::from_slice::FromSlice;
     use crate::type_coercion::coerce;
+    use crate::AnalysisContext;
     use arrow::{
         array::{
             Array, ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array,
             Int32Array, StringArray, UInt32Array, UInt64Array,
         },
-        datatypes::Field,
+        datatypes::{Field, TimeUnit},
         record_batch::RecordBatch,
     };
     use datafusion_common::cast::{as_fixed_size_list_array, as_uint64_array};
-    use datafusion_common::{Result, ScalarValue};
+    use datafusion_common::{
+        ColumnStatistics, Precision, Result, ScalarValue, Statistics,
+    };
+    use datafusion_expr::Operator;
 
     /// $FUNC function to test
     /// $ARGS arguments (vec) to pass to function
@@ -3036,4 +3062,74 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_monotonic_function_boundaries() -> Result<()> {
+        let schema = Schema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        )]);
+        // 2023-01-01T00:00:00
+        let jan_1 = 1672531200000000000;
+        let hour = 3_600_000_000_000;
+        let day = 24 * hour;
+        // 'ts' ranges from 2023-01-02T05:00:00 to 2023-01-05T10:00:00
+        let statistics = Statistics {
+            column_statistics: vec![ColumnStatistics {
+                min_value: Precision::Exact(ScalarValue::TimestampNanosecond(
+                    Some(jan_1 + day + 5 * hour),
+                    None,
+                )),
+                max_value: Precision::Exact(ScalarValue::TimestampNanosecond(
+                    Some(jan_1 + 4 * day + 10 * hour),
+                    None,
+                )),
+                ..ColumnStatistics::new_unknown()
+            }],
+            ..Statistics::new_unknown(&schema)
+        };
+        let execution_props = ExecutionProps::new();
+
+        // date_trunc('day', ts) ranges from 2023-01-02 to 2023-01-05
+        let date_trunc = create_physical_expr(
+            &BuiltinScalarFunction::DateTrunc,
+            &[lit("day"), col("ts", &schema)?],
+            &schema,
+            &execution_props,
+        )?;
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let boundaries = date_trunc.analyze(context).boundaries.unwrap();
+        assert_eq!(
+            boundaries.min_value,
+            ScalarValue::TimestampNanosecond(Some(jan_1 + day), None)
+        );
+        assert_eq!(
+            boundaries.max_value,
+            ScalarValue::TimestampNanosecond(Some(jan_1 + 4 * day), None)
+        );
+
+        // date_trunc('day', ts) = '2023-01-01' can never be true
+        let predicate = binary(
+            date_trunc,
+            Operator::Eq,
+            lit(ScalarValue::TimestampNanosecond(Some(jan_1), None)),
+            &schema,
+        )?;
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        let context = predicate.analyze(context);
+        assert_eq!(context.boundaries().unwrap().selectivity, Some(0.0));
+
+        // md5(ts) is not monotonic, so its boundaries are unknown
+        let md5 = create_physical_expr(
+            &BuiltinScalarFunction::MD5,
+            &[cast(col("ts", &schema)?, &schema, DataType::Utf8)?],
+            &schema,
+            &execution_props,
+        )?;
+        let context = AnalysisContext::from_statistics(&schema, &statistics);
+        assert!(md5.analyze(context).boundaries.is_none());
+
+        Ok(())
+    }
 }
@@ -25,7 +25,8 @@ use arrow::{downcast_dictionary_array, downcast_primitive_array};
 use arrow_buffer::i256;
 use datafusion_common::{
     cast::{
-        as_boolean_array, as_generic_binary_array, as_primitive_array, as_string_array,
+        as_boolean_array, as_generic_binary_array, as_generic_list_array,
+        as_primitive_array, as_string_array, as_struct_array,
     },
     DataFusionError, Result,
 };
@@ -149,6 +150,62 @@ fn hash_dictionary<K: ArrowDictionaryKeyType>(
     Ok(())
 }
 
+/// Hash the rows of a struct array, combining the hashes of their fields
+fn hash_struct_array(
+    array: &StructArray,
+    random_state: &RandomState,
+    hashes_buffer: &mut [u64],
+    multi_col: bool,
+) -> Result<()> {
+    let columns = (0..array.num_columns())
+        .map(|i| Arc::clone(array.column(i)))
+        .collect::<Vec<_>>();
+    let mut values_hashes = vec![0; array.len()];
+    create_hashes(&columns, random_state, &mut values_hashes)?;
+
+    for (i, hash) in hashes_buffer.iter_mut().enumerate() {
+        if array.is_valid(i) {
+            *hash = if multi_col {
+                combine_hashes(values_hashes[i], *hash)
+            } else {
+                values_hashes[i]
+            };
+        } // no update for Null, consistent with other hashes
+    }
+    Ok(())
+}
+
+/// Hash the rows of a list array, combining the hashes of their elements
+fn hash_list_array<OffsetSize: OffsetSizeTrait>(
+    array: &GenericListArray<OffsetSize>,
+    random_state: &RandomState,
+    hashes_buffer: &mut [u64],
+    multi_col: bool,
+) -> Result<()> {
+    // Hash all the elements once, and then fold the hashes of the
+    // elements of each list
+    let values = array.values();
+    let mut values_hashes = vec![0; values.len()];
+    create_hashes(&[values], random_state, &mut values_hashes)?;
+
+    let offsets = array.value_offsets();
+    for (i, hash) in hashes_buffer.iter_mut().enumerate() {
+        if array.is_valid(i) {
+            let start = offsets[i].as_usize();
+            let end = offsets[i + 1].as_usize();
+            let list_hash = values_hashes[start..end]
+                .iter()
+                .fold(0, |acc, value_hash| combine_hashes(acc, *value_hash));
+            *hash = if multi_col {
+                combine_hashes(list_hash, *hash)
+            } else {
+                list_hash
+            };
+        } // no update for Null, consistent with other hashes
+    }
+    Ok(())
+}
+
 /// Test version of `create_hashes` that produces the same value for
 /// all hashes (to test collisions)
 ///
@@ -238,6 +295,18 @@ pub fn create_hashes<'a>(
                 array => hash_dictionary(array, random_state, hashes_buffer, multi_col)?,
                 _ => unreachable!()
             }
+            DataType::Struct(_) => {
+                let array = as_struct_array(array)?;
+                hash_struct_array(array, random_state, hashes_buffer, multi_col)?
+            }
+            DataType::List(_) => {
+                let array = as_generic_list_array::<i32>(array)?;
+                hash_list_array(array, random_state, hashes_buffer, multi_col)?
+            }
+            DataType::LargeList(_) => {
+                let array = as_generic_list_array::<i64>(array)?;
+                hash_list_array(array, random_state, hashes_buffer, multi_col)?
+            }
             _ => {
                 // This is internal because we should have caught this before.
                 return Err(DataFusionError::Internal(format!(
@@ -397,6 +466,70 @@ mod tests {
         assert_ne!(dict_hashes[0], dict_hashes[2]);
     }
 
+    #[test]
+    // Tests actual values of hashes, which are different if forcing collisions
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn create_hashes_for_struct_arrays() {
+        let ints = Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(1), None]));
+        let strings = Arc::new(StringArray::from(vec!["a", "b", "a", "b"]));
+        let struct_array = Arc::new(StructArray::from(vec![
+            (Field::new("i", DataType::Int32, true), ints as ArrayRef),
+            (Field::new("s", DataType::Utf8, false), strings as ArrayRef),
+        ]));
+
+        let random_state = RandomState::with_seeds(0, 0, 0, 0);
+        let mut hashes = vec![0; struct_array.len()];
+        create_hashes(&[struct_array], &random_state, &mut hashes).unwrap();
+
+        // Same values should map to same hash values
+        assert_eq!(hashes[0], hashes[2]);
+        // different values should map to different hash values
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[1], hashes[3]);
+    }
+
+    #[test]
+    // Tests actual values of hashes, which are different if forcing collisions
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn create_hashes_for_list_arrays() {
+        let data = vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(2), Some(1)]),
+            Some(vec![Some(1)]),
+        ];
+        let list_array = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(
+            data.clone(),
+        ));
+        let large_list_array =
+            Arc::new(LargeListArray::from_iter_primitive::<Int32Type, _, _>(data));
+
+        let random_state = RandomState::with_seeds(0, 0, 0, 0);
+        let mut hashes = vec![0; list_array.len()];
+        create_hashes(&[list_array.clone()], &random_state, &mut hashes).unwrap();
+
+        // Null values result in a zero hash
+        assert_eq!(hashes[1], 0);
+        // Same values should map to same hash values
+        assert_eq!(hashes[0], hashes[2]);
+        // different values, or the same values in a different order,
+        // should map to different hash values
+        assert_ne!(hashes[0], hashes[3]);
+        assert_ne!(hashes[0], hashes[4]);
+
+        // the offsets of large lists don't change the hashes
+        let mut large_hashes = vec![0; large_list_array.len()];
+        create_hashes(&[large_list_array], &random_state, &mut large_hashes).unwrap();
+        assert_eq!(hashes, large_hashes);
+
+        // slicing the array doesn't change the hashes of the lists
+        let sliced = list_array.slice(2, 2);
+        let mut sliced_hashes = vec![0; sliced.len()];
+        create_hashes(&[sliced], &random_state, &mut sliced_hashes).unwrap();
+        assert_eq!(sliced_hashes, hashes[2..4]);
+    }
+
     #[test]
     // Tests actual values of hashes, which are different if forcing collisions
     #[cfg(not(feature = "force_hash_collisions"))]
@@ -0,0 +1,727 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Interval arithmetic, used to compute the range of the values an expression
+//! can produce from the ranges of its inputs.
+
+use std::cmp::Ordering;
+
+use arrow::datatypes::DataType;
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::Operator;
+
+use crate::ExprBoundaries;
+
+/// A closed interval `[lower, upper]` of values of the same type. A NULL bound
+/// means that the interval is unbounded on that side.
+///
+/// Boolean intervals represent the possible results of a predicate: `[false,
+/// false]` if it is certainly false, `[true, true]` if it is certainly true and
+/// `[false, true]` if it is unknown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interval {
+    /// Lower bound, NULL if unbounded
+    pub lower: ScalarValue,
+    /// Upper bound, NULL if unbounded
+    pub upper: ScalarValue,
+}
+
+impl Interval {
+    /// Create a new interval
+    pub fn new(lower: ScalarValue, upper: ScalarValue) -> Self {
+        Self { lower, upper }
+    }
+
+    /// Create an interval containing a single value
+    pub fn singleton(value: ScalarValue) -> Self {
+        Self::new(value.clone(), value)
+    }
+
+    /// Create an interval unbounded on both sides, of the given type
+    pub fn unbounded(data_type: &DataType) -> Result<Self> {
+        let null = ScalarValue::try_from(data_type)?;
+        Ok(Self::new(null.clone(), null))
+    }
+
+    /// The boolean interval of a predicate that is certainly `value`
+    pub fn certainly(value: bool) -> Self {
+        Self::singleton(ScalarValue::Boolean(Some(value)))
+    }
+
+    /// The boolean interval of a predicate that may be true or false
+    pub fn uncertain() -> Self {
+        Self::new(
+            ScalarValue::Boolean(Some(false)),
+            ScalarValue::Boolean(Some(true)),
+        )
+    }
+
+    /// The type of the values of the interval
+    pub fn get_datatype(&self) -> DataType {
+        self.lower.get_datatype()
+    }
+
+    /// Whether the interval contains exactly one value
+    pub fn is_singleton(&self) -> bool {
+        !self.lower.is_null() && self.lower == self.upper
+    }
+
+    /// Whether the interval is unbounded on at least one side
+    pub fn is_unbounded(&self) -> bool {
+        self.lower.is_null() || self.upper.is_null()
+    }
+
+    /// The value of a boolean interval, if it is certain
+    pub fn certain_value(&self) -> Option<bool> {
+        match (&self.lower, &self.upper) {
+            (ScalarValue::Boolean(Some(lower)), ScalarValue::Boolean(Some(upper)))
+                if lower == upper =>
+            {
+                Some(*lower)
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert the interval to the boundaries of an expression, if it is
+    /// bounded on both sides
+    pub fn to_boundaries(&self, distinct_count: Option<usize>) -> Option<ExprBoundaries> {
+        if self.is_unbounded()
+            || matches!(
+                self.lower.partial_cmp(&self.upper),
+                None | Some(Ordering::Greater)
+            )
+        {
+            return None;
+        }
+        Some(ExprBoundaries::new(
+            self.lower.clone(),
+            self.upper.clone(),
+            distinct_count,
+        ))
+    }
+
+    /// Return the values that are in both intervals, or `None` if the
+    /// intervals are disjoint
+    pub fn intersect(&self, other: &Interval) -> Result<Option<Interval>> {
+        let lower = match (self.lower.is_null(), other.lower.is_null()) {
+            (true, _) => other.lower.clone(),
+            (_, true) => self.lower.clone(),
+            _ => max_of(&self.lower, &other.lower)?,
+        };
+        let upper = match (self.upper.is_null(), other.upper.is_null()) {
+            (true, _) => other.upper.clone(),
+            (_, true) => self.upper.clone(),
+            _ => min_of(&self.upper, &other.upper)?,
+        };
+        if !lower.is_null() && !upper.is_null() && lower > upper {
+            return Ok(None);
+        }
+        Ok(Some(Interval::new(lower, upper)))
+    }
+
+    /// Apply the given arithmetic or comparison operator
+    pub fn apply(&self, op: &Operator, other: &Interval) -> Result<Interval> {
+        match op {
+            Operator::Plus => self.add(other),
+            Operator::Minus => self.sub(other),
+            Operator::Multiply => self.mul(other),
+            Operator::Divide => self.div(other),
+            Operator::Eq => Ok(self.equal(other)),
+            Operator::NotEq => self.equal(other).not(),
+            Operator::Gt => Ok(self.gt(other)),
+            Operator::GtEq => Ok(self.gt_eq(other)),
+            Operator::Lt => Ok(other.gt(self)),
+            Operator::LtEq => Ok(other.gt_eq(self)),
+            Operator::And => self.and(other),
+            Operator::Or => self.or(other),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "Interval arithmetic for operator {op}"
+            ))),
+        }
+    }
+
+    /// `self + other`
+    pub fn add(&self, other: &Interval) -> Result<Interval> {
+        Ok(Interval::new(
+            add_bounds(&self.lower, other, |other| &other.lower, Operator::Plus)?,
+            add_bounds(&self.upper, other, |other| &other.upper, Operator::Plus)?,
+        ))
+    }
+
+    /// `self - other`
+    pub fn sub(&self, other: &Interval) -> Result<Interval> {
+        Ok(Interval::new(
+            add_bounds(&self.lower, other, |other| &other.upper, Operator::Minus)?,
+            add_bounds(&self.upper, other, |other| &other.lower, Operator::Minus)?,
+        ))
+    }
+
+    /// `self * other`
+    pub fn mul(&self, other: &Interval) -> Result<Interval> {
+        self.corners(other, Operator::Multiply)
+    }
+
+    /// `self / other`. The result is unbounded if `other` contains zero.
+    pub fn div(&self, other: &Interval) -> Result<Interval> {
+        match (sign(&other.lower), sign(&other.upper)) {
+            (Some(Ordering::Greater), Some(Ordering::Greater))
+            | (Some(Ordering::Less), Some(Ordering::Less)) => {
+                self.corners(other, Operator::Divide)
+            }
+            _ => Interval::unbounded(&self.get_datatype()),
+        }
+    }
+
+    /// Apply an operator which, for a fixed sign of its operands, is monotonic
+    /// in both of them, by taking the smallest and largest result over the
+    /// corners of the intervals
+    fn corners(&self, other: &Interval, op: Operator) -> Result<Interval> {
+        let data_type = self.get_datatype();
+        if self.is_unbounded() || other.is_unbounded() {
+            return Interval::unbounded(&data_type);
+        }
+        let mut lower: Option<ScalarValue> = None;
+        let mut upper: Option<ScalarValue> = None;
+        for left in [&self.lower, &self.upper] {
+            for right in [&other.lower, &other.upper] {
+                let value = apply_numeric(left, &op, right)?;
+                if value.is_null() {
+                    // overflow
+                    return Interval::unbounded(&data_type);
+                }
+                lower = Some(match lower {
+                    Some(lower) => min_of(&lower, &value)?,
+                    None => value.clone(),
+                });
+                upper = Some(match upper {
+                    Some(upper) => max_of(&upper, &value)?,
+                    None => value,
+                });
+            }
+        }
+        Ok(Interval::new(lower.unwrap(), upper.unwrap()))
+    }
+
+    /// `self > other`
+    pub fn gt(&self, other: &Interval) -> Interval {
+        if !self.lower.is_null() && !other.upper.is_null() && self.lower > other.upper {
+            Interval::certainly(true)
+        } else if !self.upper.is_null()
+            && !other.lower.is_null()
+            && self.upper <= other.lower
+        {
+            Interval::certainly(false)
+        } else {
+            Interval::uncertain()
+        }
+    }
+
+    /// `self >= other`
+    pub fn gt_eq(&self, other: &Interval) -> Interval {
+        if !self.lower.is_null() && !other.upper.is_null() && self.lower >= other.upper {
+            Interval::certainly(true)
+        } else if !self.upper.is_null()
+            && !other.lower.is_null()
+            && self.upper < other.lower
+        {
+            Interval::certainly(false)
+        } else {
+            Interval::uncertain()
+        }
+    }
+
+    /// `self = other`
+    pub fn equal(&self, other: &Interval) -> Interval {
+        if self.is_singleton() && other.is_singleton() && self.lower == other.lower {
+            Interval::certainly(true)
+        } else if other.gt(self).certain_value() == Some(true)
+            || self.gt(other).certain_value() == Some(true)
+        {
+            Interval::certainly(false)
+        } else {
+            Interval::uncertain()
+        }
+    }
+
+    /// `self AND other`, for boolean intervals
+    pub fn and(&self, other: &Interval) -> Result<Interval> {
+        Ok(match (self.as_bool()?, other.as_bool()?) {
+            (Some(false), _) | (_, Some(false)) => Interval::certainly(false),
+            (Some(true), Some(true)) => Interval::certainly(true),
+            _ => Interval::uncertain(),
+        })
+    }
+
+    /// `self OR other`, for boolean intervals
+    pub fn or(&self, other: &Interval) -> Result<Interval> {
+        Ok(match (self.as_bool()?, other.as_bool()?) {
+            (Some(true), _) | (_, Some(true)) => Interval::certainly(true),
+            (Some(false), Some(false)) => Interval::certainly(false),
+            _ => Interval::uncertain(),
+        })
+    }
+
+    /// `NOT self`, for boolean intervals
+    pub fn not(&self) -> Result<Interval> {
+        Ok(match self.as_bool()? {
+            Some(value) => Interval::certainly(!value),
+            None => Interval::uncertain(),
+        })
+    }
+
+    fn as_bool(&self) -> Result<Option<bool>> {
+        match self.get_datatype() {
+            DataType::Boolean => Ok(self.certain_value()),
+            other => Err(DataFusionError::Internal(format!(
+                "Expected a boolean interval, got {other:?}"
+            ))),
+        }
+    }
+}
+
+impl From<&ExprBoundaries> for Interval {
+    fn from(boundaries: &ExprBoundaries) -> Self {
+        Interval::new(boundaries.min_value.clone(), boundaries.max_value.clone())
+    }
+}
+
+/// Whether values of the given type are ordered numerically or by time, so
+/// that order preserving functions of them can be bounded
+pub(crate) fn is_numeric_or_temporal(data_type: &DataType) -> bool {
+    data_type.is_numeric()
+        || matches!(
+            data_type,
+            DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _)
+        )
+}
+
+/// Add or subtract the bound of `other` selected by `bound` to `value`.
+/// Temporal values can only be shifted by a constant interval, since intervals
+/// (e.g. `1 month` and `30 days`) are not totally ordered.
+fn add_bounds(
+    value: &ScalarValue,
+    other: &Interval,
+    bound: impl Fn(&Interval) -> &ScalarValue,
+    op: Operator,
+) -> Result<ScalarValue> {
+    match (value.get_datatype(), other.get_datatype()) {
+        (
+            DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _),
+            DataType::Interval(_),
+        ) => {
+            if !other.is_singleton() {
+                return Err(DataFusionError::NotImplemented(
+                    "Interval arithmetic with a non constant interval".to_string(),
+                ));
+            }
+            if value.is_null() {
+                return Ok(value.clone());
+            }
+            match op {
+                Operator::Plus => value.add(&other.lower),
+                _ => value.sub(&other.lower),
+            }
+        }
+        _ => apply_numeric(value, &op, bound(other)),
+    }
+}
+
+/// Apply an arithmetic operator to two numeric values of the same type. The
+/// result is NULL if any of them is NULL or if the result overflows.
+fn apply_numeric(
+    left: &ScalarValue,
+    op: &Operator,
+    right: &ScalarValue,
+) -> Result<ScalarValue> {
+    let data_type = left.get_datatype();
+    if data_type != right.get_datatype() {
+        return Err(DataFusionError::NotImplemented(format!(
+            "Interval arithmetic between {data_type:?} and {:?}",
+            right.get_datatype()
+        )));
+    }
+    if left.is_null() || right.is_null() {
+        return ScalarValue::try_from(&data_type);
+    }
+
+    match (left, right) {
+        (ScalarValue::Float32(Some(l)), ScalarValue::Float32(Some(r))) => {
+            let value = apply_float(*l as f64, op, *r as f64)? as f32;
+            Ok(ScalarValue::Float32(value.is_finite().then_some(value)))
+        }
+        (ScalarValue::Float64(Some(l)), ScalarValue::Float64(Some(r))) => {
+            let value = apply_float(*l, op, *r)?;
+            Ok(ScalarValue::Float64(value.is_finite().then_some(value)))
+        }
+        (
+            ScalarValue::Decimal128(Some(l), p, s),
+            ScalarValue::Decimal128(Some(r), ..),
+        ) if matches!(op, Operator::Plus | Operator::Minus) => {
+            Ok(ScalarValue::Decimal128(apply_integer(*l, op, *r)?, *p, *s))
+        }
+        _ => match (as_integer(left), as_integer(right)) {
+            (Some(l), Some(r)) => from_integer(apply_integer(l, op, r)?, &data_type),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "Interval arithmetic for operator {op} on {data_type:?}"
+            ))),
+        },
+    }
+}
+
+fn apply_float(left: f64, op: &Operator, right: f64) -> Result<f64> {
+    Ok(match op {
+        Operator::Plus => left + right,
+        Operator::Minus => left - right,
+        Operator::Multiply => left * right,
+        Operator::Divide => left / right,
+        _ => {
+            return Err(DataFusionError::Internal(format!(
+                "Unexpected arithmetic operator {op}"
+            )))
+        }
+    })
+}
+
+/// Integer arithmetic, which is `None` on overflow
+fn apply_integer(left: i128, op: &Operator, right: i128) -> Result<Option<i128>> {
+    Ok(match op {
+        Operator::Plus => left.checked_add(right),
+        Operator::Minus => left.checked_sub(right),
+        Operator::Multiply => left.checked_mul(right),
+        Operator::Divide => left.checked_div(right),
+        _ => {
+            return Err(DataFusionError::Internal(format!(
+                "Unexpected arithmetic operator {op}"
+            )))
+        }
+    })
+}
+
+fn as_integer(value: &ScalarValue) -> Option<i128> {
+    match value {
+        ScalarValue::Int8(Some(v)) => Some(*v as i128),
+        ScalarValue::Int16(Some(v)) => Some(*v as i128),
+        ScalarValue::Int32(Some(v)) => Some(*v as i128),
+        ScalarValue::Int64(Some(v)) => Some(*v as i128),
+        ScalarValue::UInt8(Some(v)) => Some(*v as i128),
+        ScalarValue::UInt16(Some(v)) => Some(*v as i128),
+        ScalarValue::UInt32(Some(v)) => Some(*v as i128),
+        ScalarValue::UInt64(Some(v)) => Some(*v as i128),
+        _ => None,
+    }
+}
+
+/// Convert an integer back to the given type, NULL if it does not fit
+fn from_integer(value: Option<i128>, data_type: &DataType) -> Result<ScalarValue> {
+    Ok(match data_type {
+        DataType::Int8 => ScalarValue::Int8(value.and_then(|v| v.try_into().ok())),
+        DataType::Int16 => ScalarValue::Int16(value.and_then(|v| v.try_into().ok())),
+        DataType::Int32 => ScalarValue::Int32(value.and_then(|v| v.try_into().ok())),
+        DataType::Int64 => ScalarValue::Int64(value.and_then(|v| v.try_into().ok())),
+        DataType::UInt8 => ScalarValue::UInt8(value.and_then(|v| v.try_into().ok())),
+        DataType::UInt16 => ScalarValue::UInt16(value.and_then(|v| v.try_into().ok())),
+        DataType::UInt32 => ScalarValue::UInt32(value.and_then(|v| v.try_into().ok())),
+        DataType::UInt64 => ScalarValue::UInt64(value.and_then(|v| v.try_into().ok())),
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "Unexpected integer type {other:?}"
+            )))
+        }
+    })
+}
+
+/// The sign of a numeric value, `None` if it is NULL or not numeric
+pub(crate) fn sign(value: &ScalarValue) -> Option<Ordering> {
+    match value {
+        ScalarValue::Float32(Some(v)) => v.partial_cmp(&0.0),
+        ScalarValue::Float64(Some(v)) => v.partial_cmp(&0.0),
+        ScalarValue::Decimal128(Some(v), _, _) => Some(v.cmp(&0)),
+        _ => as_integer(value).map(|v| v.cmp(&0)),
+    }
+}
+
+fn min_of(left: &ScalarValue, right: &ScalarValue) -> Result<ScalarValue> {
+    match left.partial_cmp(right) {
+        Some(Ordering::Greater) => Ok(right.clone()),
+        Some(_) => Ok(left.clone()),
+        None => Err(DataFusionError::Internal(format!(
+            "Can not compare {left:?} and {right:?}"
+        ))),
+    }
+}
+
+fn max_of(left: &ScalarValue, right: &ScalarValue) -> Result<ScalarValue> {
+    match left.partial_cmp(right) {
+        Some(Ordering::Less) => Ok(right.clone()),
+        Some(_) => Ok(left.clone()),
+        None => Err(DataFusionError::Internal(format!(
+            "Can not compare {left:?} and {right:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::IntervalUnit;
+
+    fn int(lower: Option<i64>, upper: Option<i64>) -> Interval {
+        Interval::new(ScalarValue::Int64(lower), ScalarValue::Int64(upper))
+    }
+
+    #[test]
+    fn arithmetic() -> Result<()> {
+        let cases = vec![
+            // [1, 10] + [-5, 5]
+            (
+                int(Some(1), Some(10)),
+                Operator::Plus,
+                int(Some(-5), Some(5)),
+                int(Some(-4), Some(15)),
+            ),
+            // [1, 10] - [-5, 5]
+            (
+                int(Some(1), Some(10)),
+                Operator::Minus,
+                int(Some(-5), Some(5)),
+                int(Some(-4), Some(15)),
+            ),
+            // [1, 10] - [2, 3]
+            (
+                int(Some(1), Some(10)),
+                Operator::Minus,
+                int(Some(2), Some(3)),
+                int(Some(-2), Some(8)),
+            ),
+            // [-2, 10] * [-5, 3]
+            (
+                int(Some(-2), Some(10)),
+                Operator::Multiply,
+                int(Some(-5), Some(3)),
+                int(Some(-50), Some(30)),
+            ),
+            // [-20, 10] / [2, 5]
+            (
+                int(Some(-20), Some(10)),
+                Operator::Divide,
+                int(Some(2), Some(5)),
+                int(Some(-10), Some(5)),
+            ),
+            // [1, 10] / [-1, 1] is unbounded, since it contains zero
+            (
+                int(Some(1), Some(10)),
+                Operator::Divide,
+                int(Some(-1), Some(1)),
+                int(None, None),
+            ),
+            // (-inf, 10] + [1, 2]
+            (
+                int(None, Some(10)),
+                Operator::Plus,
+                int(Some(1), Some(2)),
+                int(None, Some(12)),
+            ),
+            // (-inf, 10] * [1, 2]
+            (
+                int(None, Some(10)),
+                Operator::Multiply,
+                int(Some(1), Some(2)),
+                int(None, None),
+            ),
+            // an overflowing upper bound is unbounded
+            (
+                int(Some(0), Some(i64::MAX)),
+                Operator::Plus,
+                int(Some(1), Some(1)),
+                int(Some(1), None),
+            ),
+        ];
+        for (left, op, right, expected) in cases {
+            assert_eq!(
+                left.apply(&op, &right)?,
+                expected,
+                "{left:?} {op} {right:?}"
+            );
+        }
+
+        let left = Interval::new(
+            ScalarValue::Float64(Some(0.5)),
+            ScalarValue::Float64(Some(2.0)),
+        );
+        let right = Interval::singleton(ScalarValue::Float64(Some(-2.0)));
+        assert_eq!(
+            left.mul(&right)?,
+            Interval::new(
+                ScalarValue::Float64(Some(-4.0)),
+                ScalarValue::Float64(Some(-1.0))
+            )
+        );
+
+        let left =
+            Interval::new(ScalarValue::UInt8(Some(10)), ScalarValue::UInt8(Some(20)));
+        let right =
+            Interval::new(ScalarValue::UInt8(Some(5)), ScalarValue::UInt8(Some(15)));
+        // the lower bound would be negative
+        assert_eq!(
+            left.sub(&right)?,
+            Interval::new(ScalarValue::UInt8(None), ScalarValue::UInt8(Some(15)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn temporal_arithmetic() -> Result<()> {
+        let dates =
+            Interval::new(ScalarValue::Date32(Some(10)), ScalarValue::Date32(Some(20)));
+        let days = Interval::singleton(ScalarValue::new_interval_dt(5, 0));
+        assert_eq!(
+            dates.add(&days)?,
+            Interval::new(ScalarValue::Date32(Some(15)), ScalarValue::Date32(Some(25)))
+        );
+        assert_eq!(
+            dates.sub(&days)?,
+            Interval::new(ScalarValue::Date32(Some(5)), ScalarValue::Date32(Some(15)))
+        );
+
+        // intervals are not totally ordered
+        let not_constant = Interval::new(
+            ScalarValue::new_interval_dt(5, 0),
+            ScalarValue::new_interval_dt(6, 0),
+        );
+        assert!(dates.add(&not_constant).is_err());
+        assert_eq!(
+            not_constant.get_datatype(),
+            DataType::Interval(IntervalUnit::DayTime)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn comparison() -> Result<()> {
+        let cases = vec![
+            (
+                int(Some(1), Some(10)),
+                Operator::Gt,
+                int(Some(11), Some(20)),
+                Some(false),
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::Lt,
+                int(Some(11), Some(20)),
+                Some(true),
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::LtEq,
+                int(Some(10), Some(20)),
+                Some(true),
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::Lt,
+                int(Some(10), Some(20)),
+                None,
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::GtEq,
+                int(None, Some(1)),
+                Some(true),
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::Gt,
+                int(None, Some(1)),
+                None,
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::Eq,
+                int(Some(11), None),
+                Some(false),
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::NotEq,
+                int(Some(11), None),
+                Some(true),
+            ),
+            (
+                int(Some(5), Some(5)),
+                Operator::Eq,
+                int(Some(5), Some(5)),
+                Some(true),
+            ),
+            (
+                int(Some(1), Some(10)),
+                Operator::Eq,
+                int(Some(5), Some(5)),
+                None,
+            ),
+        ];
+        for (left, op, right, expected) in cases {
+            let result = left.apply(&op, &right)?;
+            assert_eq!(result.certain_value(), expected, "{left:?} {op} {right:?}");
+        }
+
+        let unknown = Interval::uncertain();
+        let t = Interval::certainly(true);
+        let f = Interval::certainly(false);
+        assert_eq!(unknown.and(&f)?, f);
+        assert_eq!(unknown.and(&t)?, unknown);
+        assert_eq!(unknown.or(&t)?, t);
+        assert_eq!(unknown.or(&f)?, unknown);
+        assert_eq!(f.not()?, t);
+        assert!(int(Some(1), Some(2)).not().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn intersect() -> Result<()> {
+        assert_eq!(
+            int(Some(1), Some(10)).intersect(&int(Some(5), None))?,
+            Some(int(Some(5), Some(10)))
+        );
+        assert_eq!(
+            int(None, Some(10)).intersect(&int(None, Some(5)))?,
+            Some(int(None, Some(5)))
+        );
+        assert_eq!(
+            int(Some(1), Some(10)).intersect(&int(Some(11), None))?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_boundaries() {
+        assert_eq!(int(None, Some(10)).to_boundaries(None), None);
+        assert_eq!(
+            int(Some(1), Some(10)).to_boundaries(Some(3)),
+            Some(ExprBoundaries::new(
+                ScalarValue::Int64(Some(1)),
+                ScalarValue::Int64(Some(10)),
+                Some(3)
+            ))
+        );
+    }
+}
@@ -0,0 +1,568 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A compact intermediate representation of physical expressions, for the
+//! alternative expression backends such as code generation or GPUs
+//!
+//! An [`ExprIr`] is a plain tree of values, without trait objects or function
+//! pointers, which a backend can inspect with the [`TreeNode`] visitors and
+//! compile. It is created from a physical expression with [`ExprIr::try_new`],
+//! and converted back with [`ExprIr::to_physical_expr`].
+
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Schema};
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{BuiltinScalarFunction, Operator};
+
+use crate::execution_props::ExecutionProps;
+use crate::expressions::{
+    BinaryExpr, CaseExpr, CastExpr, Column, InListExpr, IsNotNullExpr, IsNullExpr,
+    LikeExpr, Literal, NegativeExpr, NotExpr, TryCastExpr,
+    DEFAULT_DATAFUSION_CAST_OPTIONS,
+};
+use crate::{functions, PhysicalExpr, ScalarFunctionExpr};
+
+/// The intermediate representation of a physical expression.
+///
+/// The representation is normalized: comparisons have their literal on the
+/// right when possible, and double negations are removed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprIr {
+    /// The column at `index` in the input batch
+    Column { name: String, index: usize },
+    /// A constant value
+    Literal(ScalarValue),
+    /// `left op right`, on operands of the same type
+    Binary {
+        left: Box<ExprIr>,
+        op: Operator,
+        right: Box<ExprIr>,
+    },
+    /// Boolean negation
+    Not(Box<ExprIr>),
+    /// Arithmetic negation
+    Negative(Box<ExprIr>),
+    /// `expr IS NULL`
+    IsNull(Box<ExprIr>),
+    /// `expr IS NOT NULL`
+    IsNotNull(Box<ExprIr>),
+    /// `CAST(expr AS data_type)`, failing on the values that can't be cast
+    Cast {
+        expr: Box<ExprIr>,
+        data_type: DataType,
+    },
+    /// `TRY_CAST(expr AS data_type)`, returning null for the values that can't
+    /// be cast
+    TryCast {
+        expr: Box<ExprIr>,
+        data_type: DataType,
+    },
+    /// `CASE [expr] WHEN .. THEN .. [ELSE else_expr] END`
+    Case {
+        expr: Option<Box<ExprIr>>,
+        when_then: Vec<(ExprIr, ExprIr)>,
+        else_expr: Option<Box<ExprIr>>,
+    },
+    /// `expr [NOT] IN (list)`
+    InList {
+        expr: Box<ExprIr>,
+        list: Vec<ExprIr>,
+        negated: bool,
+    },
+    /// `expr [NOT] [I]LIKE pattern`
+    Like {
+        expr: Box<ExprIr>,
+        pattern: Box<ExprIr>,
+        negated: bool,
+        case_insensitive: bool,
+    },
+    /// A call of the scalar function `name`
+    ScalarFunction {
+        name: String,
+        args: Vec<ExprIr>,
+        return_type: DataType,
+    },
+}
+
+impl ExprIr {
+    /// Creates the representation of `expr`, returning a `NotImplemented`
+    /// error if `expr` contains an expression that has none
+    pub fn try_new(expr: &Arc<dyn PhysicalExpr>) -> Result<Self> {
+        let any = expr.as_any();
+        let ir = if let Some(column) = any.downcast_ref::<Column>() {
+            ExprIr::Column {
+                name: column.name().to_owned(),
+                index: column.index(),
+            }
+        } else if let Some(literal) = any.downcast_ref::<Literal>() {
+            ExprIr::Literal(literal.value().clone())
+        } else if let Some(binary) = any.downcast_ref::<BinaryExpr>() {
+            let left = Self::try_new(binary.left())?;
+            let right = Self::try_new(binary.right())?;
+            let op = *binary.op();
+            let is_literal = |expr: &ExprIr| matches!(expr, ExprIr::Literal(_));
+            match op.swap() {
+                Some(swapped) if is_literal(&left) && !is_literal(&right) => {
+                    ExprIr::Binary {
+                        left: Box::new(right),
+                        op: swapped,
+                        right: Box::new(left),
+                    }
+                }
+                _ => ExprIr::Binary {
+                    left: Box::new(left),
+                    op,
+                    right: Box::new(right),
+                },
+            }
+        } else if let Some(not) = any.downcast_ref::<NotExpr>() {
+            match Self::try_new(not.arg())? {
+                ExprIr::Not(arg) => *arg,
+                arg => ExprIr::Not(Box::new(arg)),
+            }
+        } else if let Some(negative) = any.downcast_ref::<NegativeExpr>() {
+            ExprIr::Negative(Box::new(Self::try_new(negative.arg())?))
+        } else if let Some(is_null) = any.downcast_ref::<IsNullExpr>() {
+            ExprIr::IsNull(Box::new(Self::try_new(is_null.arg())?))
+        } else if let Some(is_not_null) = any.downcast_ref::<IsNotNullExpr>() {
+            ExprIr::IsNotNull(Box::new(Self::try_new(is_not_null.arg())?))
+        } else if let Some(cast) = any.downcast_ref::<CastExpr>() {
+            ExprIr::Cast {
+                expr: Box::new(Self::try_new(cast.expr())?),
+                data_type: cast.cast_type().clone(),
+            }
+        } else if let Some(try_cast) = any.downcast_ref::<TryCastExpr>() {
+            ExprIr::TryCast {
+                expr: Box::new(Self::try_new(try_cast.expr())?),
+                data_type: try_cast.cast_type().clone(),
+            }
+        } else if let Some(case) = any.downcast_ref::<CaseExpr>() {
+            ExprIr::Case {
+                expr: case
+                    .expr()
+                    .as_ref()
+                    .map(|expr| Self::try_new(expr).map(Box::new))
+                    .transpose()?,
+                when_then: case
+                    .when_then_expr()
+                    .iter()
+                    .map(|(when, then)| Ok((Self::try_new(when)?, Self::try_new(then)?)))
+                    .collect::<Result<_>>()?,
+                else_expr: case
+                    .else_expr()
+                    .map(|expr| Self::try_new(expr).map(Box::new))
+                    .transpose()?,
+            }
+        } else if let Some(in_list) = any.downcast_ref::<InListExpr>() {
+            ExprIr::InList {
+                expr: Box::new(Self::try_new(in_list.expr())?),
+                list: in_list
+                    .list()
+                    .iter()
+                    .map(Self::try_new)
+                    .collect::<Result<_>>()?,
+                negated: in_list.negated(),
+            }
+        } else if let Some(like) = any.downcast_ref::<LikeExpr>() {
+            ExprIr::Like {
+                expr: Box::new(Self::try_new(like.expr())?),
+                pattern: Box::new(Self::try_new(like.pattern())?),
+                negated: like.negated(),
+                case_insensitive: like.case_insensitive(),
+            }
+        } else if let Some(function) = any.downcast_ref::<ScalarFunctionExpr>() {
+            ExprIr::ScalarFunction {
+                name: function.name().to_owned(),
+                args: function
+                    .args()
+                    .iter()
+                    .map(Self::try_new)
+                    .collect::<Result<_>>()?,
+                return_type: function.return_type().clone(),
+            }
+        } else {
+            return Err(DataFusionError::NotImplemented(format!(
+                "The expression {expr} has no intermediate representation"
+            )));
+        };
+        Ok(ir)
+    }
+
+    /// Creates the physical expression represented, evaluated on batches of
+    /// `input_schema`.
+    ///
+    /// Only the built-in scalar functions can be created: the other functions
+    /// return a `NotImplemented` error.
+    pub fn to_physical_expr(
+        &self,
+        input_schema: &Schema,
+        execution_props: &ExecutionProps,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        let create = |expr: &ExprIr| expr.to_physical_expr(input_schema, execution_props);
+        Ok(match self {
+            ExprIr::Column { name, index } => Arc::new(Column::new(name, *index)),
+            ExprIr::Literal(value) => Arc::new(Literal::new(value.clone())),
+            ExprIr::Binary { left, op, right } => {
+                Arc::new(BinaryExpr::new(create(left)?, *op, create(right)?))
+            }
+            ExprIr::Not(arg) => Arc::new(NotExpr::new(create(arg)?)),
+            ExprIr::Negative(arg) => Arc::new(NegativeExpr::new(create(arg)?)),
+            ExprIr::IsNull(arg) => Arc::new(IsNullExpr::new(create(arg)?)),
+            ExprIr::IsNotNull(arg) => Arc::new(IsNotNullExpr::new(create(arg)?)),
+            ExprIr::Cast { expr, data_type } => Arc::new(CastExpr::new(
+                create(expr)?,
+                data_type.clone(),
+                DEFAULT_DATAFUSION_CAST_OPTIONS,
+            )),
+            ExprIr::TryCast { expr, data_type } => {
+                Arc::new(TryCastExpr::new(create(expr)?, data_type.clone()))
+            }
+            ExprIr::Case {
+                expr,
+                when_then,
+                else_expr,
+            } => Arc::new(CaseExpr::try_new(
+                expr.as_deref().map(create).transpose()?,
+                when_then
+                    .iter()
+                    .map(|(when, then)| Ok((create(when)?, create(then)?)))
+                    .collect::<Result<_>>()?,
+                else_expr.as_deref().map(create).transpose()?,
+            )?),
+            ExprIr::InList {
+                expr,
+                list,
+                negated,
+            } => Arc::new(InListExpr::new(
+                create(expr)?,
+                list.iter().map(create).collect::<Result<_>>()?,
+                *negated,
+                input_schema,
+            )),
+            ExprIr::Like {
+                expr,
+                pattern,
+                negated,
+                case_insensitive,
+            } => Arc::new(LikeExpr::new(
+                *negated,
+                *case_insensitive,
+                create(expr)?,
+                create(pattern)?,
+            )),
+            ExprIr::ScalarFunction { name, args, .. } => {
+                let fun = name.parse::<BuiltinScalarFunction>().map_err(|_| {
+                    DataFusionError::NotImplemented(format!(
+                        "Cannot create the scalar function {name} from its \
+                         intermediate representation"
+                    ))
+                })?;
+                let args = args.iter().map(create).collect::<Result<Vec<_>>>()?;
+                functions::create_physical_expr(
+                    &fun,
+                    &args,
+                    input_schema,
+                    execution_props,
+                )?
+            }
+        })
+    }
+
+    /// The direct children of the expression
+    pub fn children(&self) -> Vec<&ExprIr> {
+        match self {
+            ExprIr::Column { .. } | ExprIr::Literal(_) => vec![],
+            ExprIr::Binary { left, right, .. } => vec![left.as_ref(), right.as_ref()],
+            ExprIr::Not(arg)
+            | ExprIr::Negative(arg)
+            | ExprIr::IsNull(arg)
+            | ExprIr::IsNotNull(arg)
+            | ExprIr::Cast { expr: arg, .. }
+            | ExprIr::TryCast { expr: arg, .. } => vec![arg.as_ref()],
+            ExprIr::Case {
+                expr,
+                when_then,
+                else_expr,
+            } => expr
+                .as_deref()
+                .into_iter()
+                .chain(when_then.iter().flat_map(|(when, then)| [when, then]))
+                .chain(else_expr.as_deref())
+                .collect(),
+            ExprIr::InList { expr, list, .. } => {
+                std::iter::once(expr.as_ref()).chain(list).collect()
+            }
+            ExprIr::Like { expr, pattern, .. } => vec![expr.as_ref(), pattern.as_ref()],
+            ExprIr::ScalarFunction { args, .. } => args.iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for ExprIr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprIr::Column { name, index } => write!(f, "{name}@{index}"),
+            ExprIr::Literal(value) => write!(f, "{value}"),
+            ExprIr::Binary { left, op, right } => write!(f, "({left} {op} {right})"),
+            ExprIr::Not(arg) => write!(f, "NOT {arg}"),
+            ExprIr::Negative(arg) => write!(f, "(- {arg})"),
+            ExprIr::IsNull(arg) => write!(f, "{arg} IS NULL"),
+            ExprIr::IsNotNull(arg) => write!(f, "{arg} IS NOT NULL"),
+            ExprIr::Cast { expr, data_type } => {
+                write!(f, "CAST({expr} AS {data_type:?})")
+            }
+            ExprIr::TryCast { expr, data_type } => {
+                write!(f, "TRY_CAST({expr} AS {data_type:?})")
+            }
+            ExprIr::Case {
+                expr,
+                when_then,
+                else_expr,
+            } => {
+                write!(f, "CASE ")?;
+                if let Some(expr) = expr {
+                    write!(f, "{expr} ")?;
+                }
+                for (when, then) in when_then {
+                    write!(f, "WHEN {when} THEN {then} ")?;
+                }
+                if let Some(else_expr) = else_expr {
+                    write!(f, "ELSE {else_expr} ")?;
+                }
+                write!(f, "END")
+            }
+            ExprIr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let not = if *negated { "NOT " } else { "" };
+                let list = list.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                write!(f, "{expr} {not}IN ({})", list.join(", "))
+            }
+            ExprIr::Like {
+                expr,
+                pattern,
+                negated,
+                case_insensitive,
+            } => {
+                let not = if *negated { "NOT " } else { "" };
+                let like = if *case_insensitive { "ILIKE" } else { "LIKE" };
+                write!(f, "{expr} {not}{like} {pattern}")
+            }
+            ExprIr::ScalarFunction { name, args, .. } => {
+                let args = args.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                write!(f, "{name}({})", args.join(", "))
+            }
+        }
+    }
+}
+
+impl TreeNode for ExprIr {
+    fn apply_children<F>(&self, op: &mut F) -> Result<VisitRecursion>
+    where
+        F: FnMut(&Self) -> Result<VisitRecursion>,
+    {
+        for child in self.children() {
+            if op(child)? == VisitRecursion::Stop {
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    }
+
+    fn map_children<F>(self, mut transform: F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Self>,
+    {
+        Ok(match self {
+            ExprIr::Column { .. } | ExprIr::Literal(_) => self,
+            ExprIr::Binary { left, op, right } => ExprIr::Binary {
+                left: Box::new(transform(*left)?),
+                op,
+                right: Box::new(transform(*right)?),
+            },
+            ExprIr::Not(arg) => ExprIr::Not(Box::new(transform(*arg)?)),
+            ExprIr::Negative(arg) => ExprIr::Negative(Box::new(transform(*arg)?)),
+            ExprIr::IsNull(arg) => ExprIr::IsNull(Box::new(transform(*arg)?)),
+            ExprIr::IsNotNull(arg) => ExprIr::IsNotNull(Box::new(transform(*arg)?)),
+            ExprIr::Cast { expr, data_type } => ExprIr::Cast {
+                expr: Box::new(transform(*expr)?),
+                data_type,
+            },
+            ExprIr::TryCast { expr, data_type } => ExprIr::TryCast {
+                expr: Box::new(transform(*expr)?),
+                data_type,
+            },
+            ExprIr::Case {
+                expr,
+                when_then,
+                else_expr,
+            } => {
+                let expr = match expr {
+                    Some(expr) => Some(Box::new(transform(*expr)?)),
+                    None => None,
+                };
+                let when_then = when_then
+                    .into_iter()
+                    .map(|(when, then)| Ok((transform(when)?, transform(then)?)))
+                    .collect::<Result<_>>()?;
+                let else_expr = match else_expr {
+                    Some(else_expr) => Some(Box::new(transform(*else_expr)?)),
+                    None => None,
+                };
+                ExprIr::Case {
+                    expr,
+                    when_then,
+                    else_expr,
+                }
+            }
+            ExprIr::InList {
+                expr,
+                list,
+                negated,
+            } => ExprIr::InList {
+                expr: Box::new(transform(*expr)?),
+                list: list
+                    .into_iter()
+                    .map(&mut transform)
+                    .collect::<Result<_>>()?,
+                negated,
+            },
+            ExprIr::Like {
+                expr,
+                pattern,
+                negated,
+                case_insensitive,
+            } => ExprIr::Like {
+                expr: Box::new(transform(*expr)?),
+                pattern: Box::new(transform(*pattern)?),
+                negated,
+                case_insensitive,
+            },
+            ExprIr::ScalarFunction {
+                name,
+                args,
+                return_type,
+            } => ExprIr::ScalarFunction {
+                name,
+                args: args.into_iter().map(transform).collect::<Result<_>>()?,
+                return_type,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, col, lit, not};
+    use arrow::datatypes::Field;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ])
+    }
+
+    #[test]
+    fn normalize() -> Result<()> {
+        let schema = schema();
+        let expr = not(not(binary(
+            lit(5i32),
+            Operator::Lt,
+            col("b", &schema)?,
+            &schema,
+        )?)?)?;
+
+        let ir = ExprIr::try_new(&expr)?;
+        assert_eq!(
+            ir,
+            ExprIr::Binary {
+                left: Box::new(ExprIr::Column {
+                    name: "b".to_owned(),
+                    index: 1,
+                }),
+                op: Operator::Gt,
+                right: Box::new(ExprIr::Literal(ScalarValue::Int32(Some(5)))),
+            }
+        );
+        assert_eq!(ir.to_string(), "(b@1 > 5)");
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let schema = schema();
+        let sum = binary(
+            col("a", &schema)?,
+            Operator::Plus,
+            col("b", &schema)?,
+            &schema,
+        )?;
+        let expr = Arc::new(InListExpr::new(
+            sum,
+            vec![lit(1i32), lit(2i32)],
+            true,
+            &schema,
+        )) as Arc<dyn PhysicalExpr>;
+
+        let ir = ExprIr::try_new(&expr)?;
+        assert_eq!(ir.to_string(), "(a@0 + b@1) NOT IN (1, 2)");
+        let round_trip = ir.to_physical_expr(&schema, &ExecutionProps::new())?;
+        assert!(round_trip.eq(&expr));
+        Ok(())
+    }
+
+    #[test]
+    fn visit() -> Result<()> {
+        let schema = schema();
+        let expr = binary(
+            binary(col("a", &schema)?, Operator::Plus, lit(1i32), &schema)?,
+            Operator::Multiply,
+            col("b", &schema)?,
+            &schema,
+        )?;
+        let ir = ExprIr::try_new(&expr)?;
+
+        let mut columns = vec![];
+        ir.apply(&mut |expr| {
+            if let ExprIr::Column { index, .. } = expr {
+                columns.push(*index);
+            }
+            Ok(VisitRecursion::Continue)
+        })?;
+        assert_eq!(columns, vec![0, 1]);
+
+        // shift the column indices, as a backend reading a wider batch would
+        let shifted = ir.transform_up(&|expr| {
+            Ok(match expr {
+                ExprIr::Column { name, index } => Some(ExprIr::Column {
+                    name,
+                    index: index + 2,
+                }),
+                _ => None,
+            })
+        })?;
+        assert_eq!(shifted.to_string(), "((a@2 + 1) * b@3)");
+        Ok(())
+    }
+}
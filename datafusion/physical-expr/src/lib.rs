@@ -22,10 +22,13 @@ pub mod conditional_expressions;
 pub mod crypto_expressions;
 pub mod datetime_expressions;
 pub mod equivalence;
+pub mod evaluator;
 pub mod execution_props;
 pub mod expressions;
 pub mod functions;
 pub mod hash_utils;
+pub mod intervals;
+pub mod ir;
 pub mod math_expressions;
 mod physical_expr;
 pub mod planner;
@@ -34,6 +37,7 @@ pub mod regex_expressions;
 pub mod rewrite;
 mod scalar_function;
 mod sort_expr;
+pub mod sort_key;
 pub mod string_expressions;
 pub mod struct_expressions;
 pub mod type_coercion;
@@ -54,8 +58,9 @@ pub use planner::create_physical_expr;
 pub use scalar_function::ScalarFunctionExpr;
 pub use sort_expr::PhysicalSortExpr;
 pub use utils::{
-    expr_list_eq_any_order, expr_list_eq_strict_order,
-    normalize_expr_with_equivalence_properties, normalize_out_expr_with_alias_schema,
+    evaluation_error_context, expr_list_eq_any_order, expr_list_eq_strict_order,
+    expr_monotonicity, normalize_expr_with_equivalence_properties,
+    normalize_out_expr_with_alias_schema,
     normalize_sort_expr_with_equivalence_properties, sort_expr_list_eq_strict_order,
-    split_conjunction,
+    split_conjunction, Monotonicity,
 };
@@ -18,7 +18,7 @@
 //! Math expressions
 
 use arrow::array::ArrayRef;
-use arrow::array::{Float32Array, Float64Array, Int64Array};
+use arrow::array::{Float32Array, Float64Array, Int64Array, UInt64Array};
 use arrow::datatypes::DataType;
 use datafusion_common::ScalarValue;
 use datafusion_common::{DataFusionError, Result};
@@ -115,6 +115,21 @@ macro_rules! make_function_inputs2 {
     }};
 }
 
+macro_rules! make_checked_function_inputs2 {
+    ($ARG1: expr, $ARG2: expr, $NAME1:expr, $NAME2: expr, $ARRAY_TYPE:ident, $FUNC: block) => {{
+        let arg1 = downcast_arg!($ARG1, $NAME1, $ARRAY_TYPE);
+        let arg2 = downcast_arg!($ARG2, $NAME2, $ARRAY_TYPE);
+
+        arg1.iter()
+            .zip(arg2.iter())
+            .map(|(a1, a2)| match (a1, a2) {
+                (Some(a1), Some(a2)) => $FUNC(a1, a2),
+                _ => None,
+            })
+            .collect::<$ARRAY_TYPE>()
+    }};
+}
+
 math_unary_function!("sqrt", sqrt);
 math_unary_function!("sin", sin);
 math_unary_function!("cos", cos);
@@ -175,6 +190,43 @@ pub fn power(args: &[ArrayRef]) -> Result<ArrayRef> {
     }
 }
 
+/// try_divide SQL function: divides like `/`, but returns NULL instead of
+/// failing on a division by zero or an overflow
+pub fn try_divide(args: &[ArrayRef]) -> Result<ArrayRef> {
+    match args[0].data_type() {
+        DataType::Int64 => Ok(Arc::new(make_checked_function_inputs2!(
+            &args[0],
+            &args[1],
+            "dividend",
+            "divisor",
+            Int64Array,
+            { i64::checked_div }
+        )) as ArrayRef),
+
+        DataType::UInt64 => Ok(Arc::new(make_checked_function_inputs2!(
+            &args[0],
+            &args[1],
+            "dividend",
+            "divisor",
+            UInt64Array,
+            { u64::checked_div }
+        )) as ArrayRef),
+
+        DataType::Float64 => Ok(Arc::new(make_checked_function_inputs2!(
+            &args[0],
+            &args[1],
+            "dividend",
+            "divisor",
+            Float64Array,
+            { |a: f64, b: f64| (b != 0.0).then(|| a / b) }
+        )) as ArrayRef),
+
+        other => Err(DataFusionError::Internal(format!(
+            "Unsupported data type {other:?} for function try_divide"
+        ))),
+    }
+}
+
 pub fn atan2(args: &[ArrayRef]) -> Result<ArrayRef> {
     match args[0].data_type() {
         DataType::Float64 => Ok(Arc::new(make_function_inputs2!(
@@ -206,7 +258,7 @@ mod tests {
 
     use super::*;
     use arrow::array::{Float64Array, NullArray};
-    use datafusion_common::cast::{as_float32_array, as_float64_array};
+    use datafusion_common::cast::{as_float32_array, as_float64_array, as_int64_array};
 
     #[test]
     fn test_random_expression() {
@@ -251,4 +303,31 @@ mod tests {
         assert_eq!(floats.value(2), (4.0_f32).atan2(-3.0));
         assert_eq!(floats.value(3), (-5.0_f32).atan2(-4.0));
     }
+
+    #[test]
+    fn test_try_divide() {
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![
+                Some(7),
+                Some(1),
+                Some(i64::MIN),
+                None,
+            ])),
+            Arc::new(Int64Array::from(vec![Some(2), Some(0), Some(-1), Some(1)])),
+        ];
+        let result = try_divide(&args).expect("fail");
+        let ints = as_int64_array(&result).expect("fail");
+        assert_eq!(
+            ints.iter().collect::<Vec<_>>(),
+            vec![Some(3), None, None, None]
+        );
+
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Float64Array::from(vec![1.0, 1.0])),
+            Arc::new(Float64Array::from(vec![4.0, 0.0])),
+        ];
+        let result = try_divide(&args).expect("fail");
+        let floats = as_float64_array(&result).expect("fail");
+        assert_eq!(floats.iter().collect::<Vec<_>>(), vec![Some(0.25), None]);
+    }
 }
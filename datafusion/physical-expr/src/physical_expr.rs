@@ -108,16 +108,14 @@ impl AnalysisContext {
 
     /// Create a new analysis context from column statistics.
     pub fn from_statistics(input_schema: &Schema, statistics: &Statistics) -> Self {
-        // Even if the underlying statistics object doesn't have any column level statistics,
-        // we can still create an analysis context with the same number of columns and see whether
-        // we can infer it during the way.
-        let column_boundaries = match &statistics.column_statistics {
-            Some(columns) => columns
-                .iter()
-                .map(ExprBoundaries::from_column)
-                .collect::<Vec<_>>(),
-            None => vec![None; input_schema.fields().len()],
-        };
+        // Even if the underlying statistics object doesn't know anything about some
+        // columns, we can still create an analysis context with the same number of
+        // columns and see whether we can infer it during the way.
+        let column_boundaries = statistics
+            .column_statistics
+            .iter()
+            .map(ExprBoundaries::from_column)
+            .collect::<Vec<_>>();
         Self::new(input_schema, column_boundaries)
     }
 
@@ -140,6 +138,35 @@ impl AnalysisContext {
         self.column_boundaries[column] = Some(boundaries);
         self
     }
+
+    /// Update the column boundaries with the ones known after any of the
+    /// given `branches` (alternative analyses that started from this context)
+    /// holds. Each column gets the smallest range covering its range in all
+    /// the branches, and keeps its current boundaries when it is unknown in
+    /// any of them.
+    pub fn with_column_union(mut self, branches: &[AnalysisContext]) -> Self {
+        if branches.is_empty() {
+            return self;
+        }
+        for (index, current) in self.column_boundaries.iter_mut().enumerate() {
+            let union = branches.iter().try_fold(None, |acc, branch| {
+                let boundaries = branch.column_boundaries[index].as_ref()?;
+                Some(Some(match acc {
+                    None => boundaries.clone(),
+                    Some(acc) => ExprBoundaries::union(&acc, boundaries)?,
+                }))
+            });
+            if let Some(Some(mut union)) = union {
+                // the branches narrow the current boundaries, so the current
+                // distinct count is still an upper bound
+                if let Some(current) = current {
+                    union.distinct_count = current.distinct_count;
+                }
+                *current = Some(union);
+            }
+        }
+        self
+    }
 }
 
 /// Represents the boundaries of the resulting value from a physical expression,
@@ -187,16 +214,54 @@ impl ExprBoundaries {
         }
     }
 
-    /// Create a new `ExprBoundaries` from a column level statistics.
+    /// Create a new `ExprBoundaries` from a column level statistics. Inexact
+    /// bounds are used as if they were exact.
     pub fn from_column(column: &ColumnStatistics) -> Option<Self> {
         Some(Self {
-            min_value: column.min_value.clone()?,
-            max_value: column.max_value.clone()?,
-            distinct_count: column.distinct_count,
+            min_value: column.min_value.get_value()?.clone(),
+            max_value: column.max_value.get_value()?.clone(),
+            distinct_count: column.distinct_count.get_value().copied(),
             selectivity: None,
         })
     }
 
+    /// Create the boundaries of a boolean predicate that selects the given
+    /// fraction of the rows.
+    pub fn new_predicate(selectivity: f64) -> Self {
+        let selectivity = selectivity.clamp(0.0, 1.0);
+        let (min_value, max_value, distinct_count) = if selectivity == 0.0 {
+            (false, false, 1)
+        } else if selectivity == 1.0 {
+            (true, true, 1)
+        } else {
+            (false, true, 2)
+        };
+        Self::new_with_selectivity(
+            ScalarValue::Boolean(Some(min_value)),
+            ScalarValue::Boolean(Some(max_value)),
+            Some(distinct_count),
+            Some(selectivity),
+        )
+    }
+
+    /// Return the smallest boundaries containing both `self` and `other`, or
+    /// `None` if their values can not be compared.
+    pub fn union(&self, other: &ExprBoundaries) -> Option<Self> {
+        let min_value = match self.min_value.partial_cmp(&other.min_value)? {
+            Ordering::Greater => other.min_value.clone(),
+            _ => self.min_value.clone(),
+        };
+        let max_value = match self.max_value.partial_cmp(&other.max_value)? {
+            Ordering::Less => other.max_value.clone(),
+            _ => self.max_value.clone(),
+        };
+        let distinct_count = match (self.distinct_count, other.distinct_count) {
+            (Some(left), Some(right)) => Some(left + right),
+            _ => None,
+        };
+        Some(Self::new(min_value, max_value, distinct_count))
+    }
+
     /// Try to reduce the boundaries into a single scalar value, if possible.
     pub fn reduce(&self) -> Option<ScalarValue> {
         // TODO: should we check distinct_count is `Some(1) | None`?
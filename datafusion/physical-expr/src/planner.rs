@@ -15,21 +15,24 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::evaluator::select_evaluator;
 use crate::var_provider::is_system_variables;
 use crate::{
     execution_props::ExecutionProps,
     expressions::{
-        self, binary, like, Column, DateTimeIntervalExpr, GetIndexedFieldExpr, Literal,
+        self, binary, like, Column, DateTimeIntervalExpr, GetIndexedFieldExpr,
+        InSubqueryExpr, Literal, ScalarSubqueryExpr, SubqueryId,
     },
     functions, udf,
     var_provider::VarType,
     PhysicalExpr,
 };
-use arrow::datatypes::{DataType, Schema};
+use arrow::datatypes::{DataType, Schema, TimeUnit};
 use datafusion_common::{DFSchema, DataFusionError, Result, ScalarValue};
 use datafusion_expr::expr::Cast;
 use datafusion_expr::{
-    binary_expr, Between, BinaryExpr, Expr, GetIndexedField, Like, Operator, TryCast,
+    binary_expr, Between, BinaryExpr, Expr, GetIndexedField, Like, LogicalPlan, Operator,
+    TryCast,
 };
 use std::sync::Arc;
 
@@ -47,6 +50,20 @@ pub fn create_physical_expr(
     input_dfschema: &DFSchema,
     input_schema: &Schema,
     execution_props: &ExecutionProps,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    let expr = plan_physical_expr(e, input_dfschema, input_schema, execution_props)?;
+    match &execution_props.evaluator_selector {
+        Some(selector) => select_evaluator(expr, input_schema, selector),
+        None => Ok(expr),
+    }
+}
+
+/// Create the physical expression of `e`, without selecting its evaluator
+fn plan_physical_expr(
+    e: &Expr,
+    input_dfschema: &DFSchema,
+    input_schema: &Schema,
+    execution_props: &ExecutionProps,
 ) -> Result<Arc<dyn PhysicalExpr>> {
     if input_schema.fields.len() != input_dfschema.fields().len() {
         return Err(DataFusionError::Internal(format!(
@@ -57,7 +74,7 @@ pub fn create_physical_expr(
         )));
     }
     match e {
-        Expr::Alias(expr, ..) => Ok(create_physical_expr(
+        Expr::Alias(expr, ..) => Ok(plan_physical_expr(
             expr,
             input_dfschema,
             input_schema,
@@ -97,12 +114,7 @@ pub fn create_physical_expr(
                 Operator::IsNotDistinctFrom,
                 Expr::Literal(ScalarValue::Boolean(Some(true))),
             );
-            create_physical_expr(
-                &binary_op,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )
+            plan_physical_expr(&binary_op, input_dfschema, input_schema, execution_props)
         }
         Expr::IsNotTrue(expr) => {
             let binary_op = binary_expr(
@@ -110,12 +122,7 @@ pub fn create_physical_expr(
                 Operator::IsDistinctFrom,
                 Expr::Literal(ScalarValue::Boolean(Some(true))),
             );
-            create_physical_expr(
-                &binary_op,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )
+            plan_physical_expr(&binary_op, input_dfschema, input_schema, execution_props)
         }
         Expr::IsFalse(expr) => {
             let binary_op = binary_expr(
@@ -123,12 +130,7 @@ pub fn create_physical_expr(
                 Operator::IsNotDistinctFrom,
                 Expr::Literal(ScalarValue::Boolean(Some(false))),
             );
-            create_physical_expr(
-                &binary_op,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )
+            plan_physical_expr(&binary_op, input_dfschema, input_schema, execution_props)
         }
         Expr::IsNotFalse(expr) => {
             let binary_op = binary_expr(
@@ -136,12 +138,7 @@ pub fn create_physical_expr(
                 Operator::IsDistinctFrom,
                 Expr::Literal(ScalarValue::Boolean(Some(false))),
             );
-            create_physical_expr(
-                &binary_op,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )
+            plan_physical_expr(&binary_op, input_dfschema, input_schema, execution_props)
         }
         Expr::IsUnknown(expr) => {
             let binary_op = binary_expr(
@@ -149,12 +146,7 @@ pub fn create_physical_expr(
                 Operator::IsNotDistinctFrom,
                 Expr::Literal(ScalarValue::Boolean(None)),
             );
-            create_physical_expr(
-                &binary_op,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )
+            plan_physical_expr(&binary_op, input_dfschema, input_schema, execution_props)
         }
         Expr::IsNotUnknown(expr) => {
             let binary_op = binary_expr(
@@ -162,26 +154,13 @@ pub fn create_physical_expr(
                 Operator::IsDistinctFrom,
                 Expr::Literal(ScalarValue::Boolean(None)),
             );
-            create_physical_expr(
-                &binary_op,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )
+            plan_physical_expr(&binary_op, input_dfschema, input_schema, execution_props)
         }
         Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
-            let lhs = create_physical_expr(
-                left,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )?;
-            let rhs = create_physical_expr(
-                right,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )?;
+            let lhs =
+                plan_physical_expr(left, input_dfschema, input_schema, execution_props)?;
+            let rhs =
+                plan_physical_expr(right, input_dfschema, input_schema, execution_props)?;
             match (
                 lhs.data_type(input_schema)?,
                 op,
@@ -197,6 +176,19 @@ pub fn create_physical_expr(
                     rhs,
                     input_schema,
                 )?)),
+                (
+                    DataType::Timestamp(unit, tz),
+                    Operator::Plus | Operator::Minus,
+                    DataType::Duration(_),
+                ) => timestamp_duration_arithmetic(lhs, *op, rhs, unit, tz, input_schema),
+                (
+                    DataType::Duration(_),
+                    Operator::Plus,
+                    DataType::Timestamp(unit, tz),
+                ) => timestamp_duration_arithmetic(rhs, *op, lhs, unit, tz, input_schema),
+                (DataType::Duration(unit), _, DataType::Duration(_)) => {
+                    duration_arithmetic(lhs, *op, rhs, unit, input_schema)
+                }
                 _ => {
                     // Note that the logical planner is responsible
                     // for type coercion on the arguments (e.g. if one
@@ -220,13 +212,9 @@ pub fn create_physical_expr(
                     "LIKE does not support escape_char".to_string(),
                 ));
             }
-            let physical_expr = create_physical_expr(
-                expr,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )?;
-            let physical_pattern = create_physical_expr(
+            let physical_expr =
+                plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?;
+            let physical_pattern = plan_physical_expr(
                 pattern,
                 input_dfschema,
                 input_schema,
@@ -251,13 +239,9 @@ pub fn create_physical_expr(
                     "ILIKE does not support escape_char".to_string(),
                 ));
             }
-            let physical_expr = create_physical_expr(
-                expr,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )?;
-            let physical_pattern = create_physical_expr(
+            let physical_expr =
+                plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?;
+            let physical_pattern = plan_physical_expr(
                 pattern,
                 input_dfschema,
                 input_schema,
@@ -273,7 +257,7 @@ pub fn create_physical_expr(
         }
         Expr::Case(case) => {
             let expr: Option<Arc<dyn PhysicalExpr>> = if let Some(e) = &case.expr {
-                Some(create_physical_expr(
+                Some(plan_physical_expr(
                     e.as_ref(),
                     input_dfschema,
                     input_schema,
@@ -286,7 +270,7 @@ pub fn create_physical_expr(
                 .when_then_expr
                 .iter()
                 .map(|(w, _)| {
-                    create_physical_expr(
+                    plan_physical_expr(
                         w.as_ref(),
                         input_dfschema,
                         input_schema,
@@ -298,7 +282,7 @@ pub fn create_physical_expr(
                 .when_then_expr
                 .iter()
                 .map(|(_, t)| {
-                    create_physical_expr(
+                    plan_physical_expr(
                         t.as_ref(),
                         input_dfschema,
                         input_schema,
@@ -314,7 +298,7 @@ pub fn create_physical_expr(
                     .collect();
             let else_expr: Option<Arc<dyn PhysicalExpr>> =
                 if let Some(e) = &case.else_expr {
-                    Some(create_physical_expr(
+                    Some(plan_physical_expr(
                         e.as_ref(),
                         input_dfschema,
                         input_schema,
@@ -326,32 +310,32 @@ pub fn create_physical_expr(
             Ok(expressions::case(expr, when_then_expr, else_expr)?)
         }
         Expr::Cast(Cast { expr, data_type }) => expressions::cast(
-            create_physical_expr(expr, input_dfschema, input_schema, execution_props)?,
+            plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?,
             input_schema,
             data_type.clone(),
         ),
         Expr::TryCast(TryCast { expr, data_type }) => expressions::try_cast(
-            create_physical_expr(expr, input_dfschema, input_schema, execution_props)?,
+            plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?,
             input_schema,
             data_type.clone(),
         ),
-        Expr::Not(expr) => expressions::not(create_physical_expr(
+        Expr::Not(expr) => expressions::not(plan_physical_expr(
             expr,
             input_dfschema,
             input_schema,
             execution_props,
         )?),
         Expr::Negative(expr) => expressions::negative(
-            create_physical_expr(expr, input_dfschema, input_schema, execution_props)?,
+            plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?,
             input_schema,
         ),
-        Expr::IsNull(expr) => expressions::is_null(create_physical_expr(
+        Expr::IsNull(expr) => expressions::is_null(plan_physical_expr(
             expr,
             input_dfschema,
             input_schema,
             execution_props,
         )?),
-        Expr::IsNotNull(expr) => expressions::is_not_null(create_physical_expr(
+        Expr::IsNotNull(expr) => expressions::is_not_null(plan_physical_expr(
             expr,
             input_dfschema,
             input_schema,
@@ -359,12 +343,7 @@ pub fn create_physical_expr(
         )?),
         Expr::GetIndexedField(GetIndexedField { key, expr }) => {
             Ok(Arc::new(GetIndexedFieldExpr::new(
-                create_physical_expr(
-                    expr,
-                    input_dfschema,
-                    input_schema,
-                    execution_props,
-                )?,
+                plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?,
                 key.clone(),
             )))
         }
@@ -373,7 +352,7 @@ pub fn create_physical_expr(
             let physical_args = args
                 .iter()
                 .map(|e| {
-                    create_physical_expr(e, input_dfschema, input_schema, execution_props)
+                    plan_physical_expr(e, input_dfschema, input_schema, execution_props)
                 })
                 .collect::<Result<Vec<_>>>()?;
             functions::create_physical_expr(
@@ -386,7 +365,7 @@ pub fn create_physical_expr(
         Expr::ScalarUDF { fun, args } => {
             let mut physical_args = vec![];
             for e in args {
-                physical_args.push(create_physical_expr(
+                physical_args.push(plan_physical_expr(
                     e,
                     input_dfschema,
                     input_schema,
@@ -402,20 +381,12 @@ pub fn create_physical_expr(
             low,
             high,
         }) => {
-            let value_expr = create_physical_expr(
-                expr,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )?;
+            let value_expr =
+                plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?;
             let low_expr =
-                create_physical_expr(low, input_dfschema, input_schema, execution_props)?;
-            let high_expr = create_physical_expr(
-                high,
-                input_dfschema,
-                input_schema,
-                execution_props,
-            )?;
+                plan_physical_expr(low, input_dfschema, input_schema, execution_props)?;
+            let high_expr =
+                plan_physical_expr(high, input_dfschema, input_schema, execution_props)?;
 
             // rewrite the between into the two binary operators
             let binary_expr = binary(
@@ -440,7 +411,7 @@ pub fn create_physical_expr(
                 Ok(expressions::lit(ScalarValue::Boolean(None)))
             }
             _ => {
-                let value_expr = create_physical_expr(
+                let value_expr = plan_physical_expr(
                     expr,
                     input_dfschema,
                     input_schema,
@@ -450,7 +421,7 @@ pub fn create_physical_expr(
                 let list_exprs = list
                     .iter()
                     .map(|expr| {
-                        create_physical_expr(
+                        plan_physical_expr(
                             expr,
                             input_dfschema,
                             input_schema,
@@ -461,8 +432,80 @@ pub fn create_physical_expr(
                 expressions::in_list(value_expr, list_exprs, negated, input_schema)
             }
         },
+        Expr::ScalarSubquery(subquery) => {
+            let id = planned_subquery(&subquery.subquery, execution_props)?;
+            let data_type = subquery.subquery.schema().field(0).data_type().clone();
+            Ok(Arc::new(ScalarSubqueryExpr::new(id, data_type)))
+        }
+        Expr::InSubquery {
+            expr,
+            subquery,
+            negated,
+        } => {
+            let id = planned_subquery(&subquery.subquery, execution_props)?;
+            let value_expr =
+                plan_physical_expr(expr, input_dfschema, input_schema, execution_props)?;
+            Ok(Arc::new(InSubqueryExpr::new(value_expr, id, *negated)))
+        }
         other => Err(DataFusionError::NotImplemented(format!(
             "Physical plan does not support logical expression {other:?}"
         ))),
     }
 }
+
+/// Returns the identifier of the uncorrelated `subquery` planned before the
+/// expression reading its results
+fn planned_subquery(
+    subquery: &Arc<LogicalPlan>,
+    execution_props: &ExecutionProps,
+) -> Result<SubqueryId> {
+    execution_props
+        .subqueries
+        .iter()
+        .find(|(plan, _)| Arc::ptr_eq(plan, subquery))
+        .map(|(_, id)| *id)
+        .ok_or_else(|| {
+            DataFusionError::NotImplemented(
+                "Physical plan only supports the uncorrelated subqueries of \
+                 filters and projections"
+                    .to_string(),
+            )
+        })
+}
+
+/// Adds or subtracts the durations `rhs` to the timestamps `lhs` of `unit`,
+/// as the numbers of `unit`s of both
+fn timestamp_duration_arithmetic(
+    lhs: Arc<dyn PhysicalExpr>,
+    op: Operator,
+    rhs: Arc<dyn PhysicalExpr>,
+    unit: TimeUnit,
+    tz: Option<String>,
+    input_schema: &Schema,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    let lhs = expressions::cast(lhs, input_schema, DataType::Int64)?;
+    let rhs = expressions::cast(rhs, input_schema, DataType::Duration(unit.clone()))?;
+    let rhs = expressions::cast(rhs, input_schema, DataType::Int64)?;
+    let result = binary(lhs, op, rhs, input_schema)?;
+    expressions::cast(result, input_schema, DataType::Timestamp(unit, tz))
+}
+
+/// Computes `lhs op rhs` on the values of the durations `lhs` and `rhs` of
+/// `unit`, which is a duration for additions and subtractions
+fn duration_arithmetic(
+    lhs: Arc<dyn PhysicalExpr>,
+    op: Operator,
+    rhs: Arc<dyn PhysicalExpr>,
+    unit: TimeUnit,
+    input_schema: &Schema,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    let lhs = expressions::cast(lhs, input_schema, DataType::Int64)?;
+    let rhs = expressions::cast(rhs, input_schema, DataType::Int64)?;
+    let result = binary(lhs, op, rhs, input_schema)?;
+    match op {
+        Operator::Plus | Operator::Minus => {
+            expressions::cast(result, input_schema, DataType::Duration(unit))
+        }
+        _ => Ok(result),
+    }
+}
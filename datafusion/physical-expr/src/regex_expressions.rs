@@ -512,7 +512,7 @@ mod tests {
     fn test_regexp_can_specialize_all_cases() {
         macro_rules! make_scalar {
             () => {
-                ColumnarValue::Scalar(ScalarValue::Utf8(Some("foo".to_string())))
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some("foo".into())))
             };
         }
 
@@ -29,15 +29,17 @@
 //! This module also has a set of coercion rules to improve user experience: if an argument i32 is passed
 //! to a function that supports f64, it is coerced to f64.
 
+use crate::intervals::{is_numeric_or_temporal, Interval};
 use crate::physical_expr::down_cast_any_ref;
 use crate::utils::expr_list_eq_strict_order;
-use crate::PhysicalExpr;
+use crate::{analysis_expect, AnalysisContext, PhysicalExpr};
 use arrow::datatypes::{DataType, Schema};
 use arrow::record_batch::RecordBatch;
-use datafusion_common::Result;
+use datafusion_common::{Result, ScalarValue};
 use datafusion_expr::BuiltinScalarFunction;
 use datafusion_expr::ColumnarValue;
 use datafusion_expr::ScalarFunctionImplementation;
+use datafusion_expr::ScalarUDF;
 use std::any::Any;
 use std::fmt::Debug;
 use std::fmt::{self, Formatter};
@@ -49,6 +51,9 @@ pub struct ScalarFunctionExpr {
     name: String,
     args: Vec<Arc<dyn PhysicalExpr>>,
     return_type: DataType,
+    monotonic_argument: Option<usize>,
+    /// The user defined function, if the function is one
+    udf: Option<Arc<ScalarUDF>>,
 }
 
 impl Debug for ScalarFunctionExpr {
@@ -58,26 +63,47 @@ impl Debug for ScalarFunctionExpr {
             .field("name", &self.name)
             .field("args", &self.args)
             .field("return_type", &self.return_type)
+            .field("monotonic_argument", &self.monotonic_argument)
             .finish()
     }
 }
 
 impl ScalarFunctionExpr {
-    /// Create a new Scalar function
+    /// Create a new Scalar function. The function is monotonic in the same
+    /// argument as the built-in function named `name`, if any.
     pub fn new(
         name: &str,
         fun: ScalarFunctionImplementation,
         args: Vec<Arc<dyn PhysicalExpr>>,
         return_type: &DataType,
     ) -> Self {
+        let monotonic_argument = name
+            .parse::<BuiltinScalarFunction>()
+            .ok()
+            .and_then(|fun| fun.monotonic_argument());
         Self {
             fun,
             name: name.to_owned(),
             args,
             return_type: return_type.clone(),
+            monotonic_argument,
+            udf: None,
         }
     }
 
+    /// Set the index of the argument in which the function is monotonically
+    /// non-decreasing when all its other arguments are constant, if any
+    pub fn with_monotonic_argument(mut self, monotonic_argument: Option<usize>) -> Self {
+        self.monotonic_argument = monotonic_argument;
+        self
+    }
+
+    /// Set the user defined function this expression calls
+    pub fn with_udf(mut self, udf: Arc<ScalarUDF>) -> Self {
+        self.udf = Some(udf);
+        self
+    }
+
     /// Get the scalar function implementation
     pub fn fun(&self) -> &ScalarFunctionImplementation {
         &self.fun
@@ -97,6 +123,17 @@ impl ScalarFunctionExpr {
     pub fn return_type(&self) -> &DataType {
         &self.return_type
     }
+
+    /// The index of the argument in which the function is monotonically
+    /// non-decreasing when all its other arguments are constant, if any
+    pub fn monotonic_argument(&self) -> Option<usize> {
+        self.monotonic_argument
+    }
+
+    /// The user defined function this expression calls, if any
+    pub fn udf(&self) -> Option<&Arc<ScalarUDF>> {
+        self.udf.as_ref()
+    }
 }
 
 impl fmt::Display for ScalarFunctionExpr {
@@ -155,12 +192,64 @@ impl PhysicalExpr for ScalarFunctionExpr {
         self: Arc<Self>,
         children: Vec<Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn PhysicalExpr>> {
-        Ok(Arc::new(ScalarFunctionExpr::new(
-            &self.name,
-            self.fun.clone(),
-            children,
-            self.return_type(),
-        )))
+        Ok(Arc::new(ScalarFunctionExpr {
+            fun: self.fun.clone(),
+            name: self.name.clone(),
+            args: children,
+            return_type: self.return_type.clone(),
+            monotonic_argument: self.monotonic_argument,
+            udf: self.udf.clone(),
+        }))
+    }
+
+    /// Return the boundaries of this function's result, if it is monotonic in
+    /// one of its arguments and all the others are constant. The result is
+    /// then within the results of the function at the boundaries of that
+    /// argument (e.g. `date_trunc('day', ts)` is within the days of the
+    /// boundaries of `ts`).
+    fn analyze(&self, context: AnalysisContext) -> AnalysisContext {
+        let monotonic = analysis_expect!(context, self.monotonic_argument);
+
+        let mut distinct_count = None;
+        let mut lower_args = Vec::with_capacity(self.args.len());
+        let mut upper_args = Vec::with_capacity(self.args.len());
+        for (index, arg) in self.args.iter().enumerate() {
+            let bounds =
+                analysis_expect!(context, arg.analyze(context.clone()).boundaries);
+            if index == monotonic {
+                if !is_numeric_or_temporal(&bounds.min_value.get_datatype()) {
+                    return context.with_boundaries(None);
+                }
+                // the function can not produce more values than its input
+                distinct_count = bounds.distinct_count;
+                lower_args.push(ColumnarValue::Scalar(bounds.min_value));
+                upper_args.push(ColumnarValue::Scalar(bounds.max_value));
+            } else {
+                let value = analysis_expect!(context, bounds.reduce());
+                lower_args.push(ColumnarValue::Scalar(value.clone()));
+                upper_args.push(ColumnarValue::Scalar(value));
+            }
+        }
+
+        let evaluate = |args: &[ColumnarValue]| -> Option<ScalarValue> {
+            let value = match (self.fun)(args).ok()? {
+                ColumnarValue::Scalar(value) => value,
+                ColumnarValue::Array(array) => {
+                    ScalarValue::try_from_array(&array, 0).ok()?
+                }
+            };
+            match value {
+                ScalarValue::Float32(Some(v)) if v.is_nan() => None,
+                ScalarValue::Float64(Some(v)) if v.is_nan() => None,
+                value if value.is_null() => None,
+                value => Some(value),
+            }
+        };
+        let min_value = analysis_expect!(context, evaluate(&lower_args));
+        let max_value = analysis_expect!(context, evaluate(&upper_args));
+        context.with_boundaries(
+            Interval::new(min_value, max_value).to_boundaries(distinct_count),
+        )
     }
 }
 
@@ -0,0 +1,257 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Order preserving binary keys of nested values, used to compare and sort
+//! structs and lists with the comparison kernels and the row format, which
+//! only support flat types.
+
+use arrow::array::{
+    Array, ArrayRef, BinaryArray, BinaryBuilder, GenericListArray, OffsetSizeTrait,
+};
+use arrow::compute::{cast, SortOptions};
+use arrow::datatypes::{ArrowNativeType, DataType};
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::cast::{as_generic_list_array, as_struct_array};
+use datafusion_common::{DataFusionError, Result};
+use std::sync::Arc;
+
+/// The order of the fields of structs and of the elements of lists: NULL
+/// fields and elements are equal to each other, and greater than any other
+/// value, like the comparisons of composite values in Postgres.
+const NESTED_SORT_OPTIONS: SortOptions = SortOptions {
+    descending: false,
+    nulls_first: false,
+};
+
+/// Returns true if values of `data_type` are compared through their
+/// [`nested_sort_key`]
+pub fn is_nested(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Struct(_) | DataType::List(_) | DataType::LargeList(_)
+    )
+}
+
+/// Returns the binary keys of the values of a nested array, whose byte-wise
+/// order is the lexicographic order of the values.
+///
+/// Structs are ordered by their fields, in order, and lists by their
+/// elements, a list being smaller than the lists it is a prefix of. NULL
+/// fields and elements are equal to each other and greater than any other
+/// value. NULL structs and lists have NULL keys, so that the comparison
+/// kernels and the sort options of the caller handle them like any other
+/// NULL value.
+///
+/// The keys of equal values are equal whatever the arrays they come from, so
+/// the keys of two arrays of the same type can be compared with each other.
+pub fn nested_sort_key(array: &dyn Array) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Struct(_) => {
+            let array = as_struct_array(array)?;
+            let columns = (0..array.num_columns())
+                .map(|i| flat_key(array.column(i)))
+                .collect::<Result<Vec<_>>>()?;
+            if columns.is_empty() {
+                return Ok(Arc::new(
+                    (0..array.len())
+                        .map(|i| array.is_valid(i).then_some(&[] as &[u8]))
+                        .collect::<BinaryArray>(),
+                ));
+            }
+            let rows = convert(&columns)?;
+            Ok(Arc::new(
+                (0..array.len())
+                    .map(|i| array.is_valid(i).then(|| rows.row(i)))
+                    .collect::<BinaryArray>(),
+            ))
+        }
+        DataType::List(_) => list_sort_key(as_generic_list_array::<i32>(array)?),
+        DataType::LargeList(_) => list_sort_key(as_generic_list_array::<i64>(array)?),
+        other => Err(DataFusionError::Internal(format!(
+            "Can't compute the sort key of the non nested type {other:?}"
+        ))),
+    }
+}
+
+/// Returns the keys of the elements of a list array, followed by an end
+/// marker smaller than the marker preceding each element
+fn list_sort_key<OffsetSize: OffsetSizeTrait>(
+    array: &GenericListArray<OffsetSize>,
+) -> Result<ArrayRef> {
+    let values = flat_key(&array.values())?;
+    let rows = convert(&[values])?;
+    let offsets = array.value_offsets();
+
+    let mut builder = BinaryBuilder::new();
+    let mut key = vec![];
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            builder.append_null();
+            continue;
+        }
+        key.clear();
+        for element in offsets[i].as_usize()..offsets[i + 1].as_usize() {
+            key.push(1);
+            key.extend_from_slice(rows.row(element).as_ref());
+        }
+        key.push(0);
+        builder.append_value(&key);
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Converts the fields of structs and the elements of lists to an array the
+/// row format supports, comparing like the original values
+fn flat_key(array: &ArrayRef) -> Result<ArrayRef> {
+    match array.data_type() {
+        data_type if is_nested(data_type) => nested_sort_key(array.as_ref()),
+        // the row format interns dictionary values in an order that depends
+        // on the converter, so compare their values instead
+        DataType::Dictionary(_, value_type) => flat_key(&cast(array, value_type)?),
+        _ => Ok(Arc::clone(array)),
+    }
+}
+
+/// Converts `columns` to the row format, ordering each column ascending with
+/// NULLs last
+fn convert(columns: &[ArrayRef]) -> Result<Rows> {
+    let fields = columns
+        .iter()
+        .map(|column| {
+            SortField::new_with_options(column.data_type().clone(), NESTED_SORT_OPTIONS)
+        })
+        .collect();
+    Ok(RowConverter::new(fields)?.convert_columns(columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, ListArray, StringArray, StructArray};
+    use arrow::buffer::Buffer;
+    use arrow::datatypes::{Field, Int32Type};
+    use datafusion_common::cast::as_binary_array;
+
+    /// Asserts that the keys of `array` are in the order of `expected`,
+    /// the positions of its values sorted by increasing value
+    fn assert_order(array: &dyn Array, expected: &[usize]) -> Result<()> {
+        let keys = nested_sort_key(array)?;
+        let keys = as_binary_array(&keys)?;
+        for pair in expected.windows(2) {
+            assert!(
+                keys.value(pair[0]) < keys.value(pair[1]),
+                "{pair:?} in {expected:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn struct_sort_key() -> Result<()> {
+        let array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![
+                    Some(2),
+                    Some(1),
+                    None,
+                    Some(1),
+                    Some(1),
+                ])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    Some("b"),
+                    Some("a"),
+                    None,
+                    Some("a"),
+                ])) as ArrayRef,
+            ),
+        ]);
+        // {1, a} < {1, b} < {1, NULL} < {2, a} < {NULL, a}
+        assert_order(&array, &[4, 1, 3, 0, 2])?;
+
+        let keys = nested_sort_key(array.slice(3, 2).as_ref())?;
+        assert_eq!(keys.len(), 2);
+        assert!(keys.is_valid(1));
+        Ok(())
+    }
+
+    #[test]
+    fn struct_sort_key_nulls() -> Result<()> {
+        let array = StructArray::from((
+            vec![(
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![1, 1, 2])) as ArrayRef,
+            )],
+            Buffer::from([0b00000101u8]),
+        ));
+        let keys = nested_sort_key(&array)?;
+        assert_eq!(keys.null_count(), 1);
+        assert!(keys.is_null(1));
+        Ok(())
+    }
+
+    #[test]
+    fn list_sort_key() -> Result<()> {
+        let array = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![]),
+            Some(vec![Some(1)]),
+            Some(vec![Some(1), None]),
+            Some(vec![Some(0), Some(5), Some(5)]),
+            None,
+            Some(vec![Some(2)]),
+        ]);
+        // [] < [0, 5, 5] < [1] < [1, 2] < [1, NULL] < [2]
+        assert_order(&array, &[1, 4, 2, 0, 3, 6])?;
+
+        let keys = nested_sort_key(&array)?;
+        assert!(keys.is_null(5));
+
+        // equal values have equal keys whatever the array
+        let other = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![
+            Some(1),
+            Some(2),
+        ])]);
+        let other_keys = nested_sort_key(&other)?;
+        assert_eq!(
+            as_binary_array(&keys)?.value(0),
+            as_binary_array(&other_keys)?.value(0)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn nested_list_of_structs_sort_key() -> Result<()> {
+        let structs = StructArray::from(vec![(
+            Field::new("a", DataType::Utf8, true),
+            Arc::new(StringArray::from(vec!["b", "a", "a", "ab"])) as ArrayRef,
+        )]);
+        let field = Field::new("item", structs.data_type().clone(), true);
+        let data = arrow::array::ArrayData::builder(DataType::List(Box::new(field)))
+            .len(3)
+            .add_buffer(Buffer::from_slice_ref([0i32, 1, 3, 4]))
+            .add_child_data(structs.data().clone())
+            .build()?;
+        let array = ListArray::from(data);
+        // [{a}, {a}] < [{ab}] < [{b}]
+        assert_order(&array, &[1, 2, 0])
+    }
+}
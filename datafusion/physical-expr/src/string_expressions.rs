@@ -104,11 +104,11 @@ where
         },
         ColumnarValue::Scalar(scalar) => match scalar {
             ScalarValue::Utf8(a) => {
-                let result = a.as_ref().map(|x| (op)(x).as_ref().to_string());
+                let result = a.as_ref().map(|x| (op)(x).as_ref().into());
                 Ok(ColumnarValue::Scalar(ScalarValue::Utf8(result)))
             }
             ScalarValue::LargeUtf8(a) => {
-                let result = a.as_ref().map(|x| (op)(x).as_ref().to_string());
+                let result = a.as_ref().map(|x| (op)(x).as_ref().into());
                 Ok(ColumnarValue::Scalar(ScalarValue::LargeUtf8(result)))
             }
             other => Err(DataFusionError::Internal(format!(
@@ -271,7 +271,9 @@ pub fn concat(args: &[ColumnarValue]) -> Result<ColumnarValue> {
             };
             acc
         });
-        Ok(ColumnarValue::Scalar(ScalarValue::Utf8(result)))
+        Ok(ColumnarValue::Scalar(ScalarValue::Utf8(
+            result.map(Arc::from),
+        )))
     }
 }
 
@@ -17,15 +17,23 @@
 
 use crate::equivalence::EquivalentClass;
 use crate::expressions::BinaryExpr;
+use crate::expressions::CastExpr;
 use crate::expressions::Column;
+use crate::expressions::Literal;
+use crate::expressions::NegativeExpr;
 use crate::expressions::UnKnownColumn;
-use crate::rewrite::TreeNodeRewritable;
+use crate::intervals::{is_numeric_or_temporal, sign};
+use crate::rewrite::TreeNode;
 use crate::PhysicalExpr;
 use crate::PhysicalSortExpr;
+use crate::ScalarFunctionExpr;
+use datafusion_common::{DataFusionError, ScalarValue};
 use datafusion_expr::Operator;
 
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -185,14 +193,240 @@ pub fn normalize_sort_expr_with_equivalence_properties(
     }
 }
 
+/// How an expression changes as one of its inputs increases, see [`expr_monotonicity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Monotonicity {
+    /// The expression decreases (rather than increases) with its input
+    pub descending: bool,
+    /// Distinct inputs are mapped to distinct outputs
+    pub strict: bool,
+}
+
+impl Monotonicity {
+    fn reverse(self) -> Self {
+        Self {
+            descending: !self.descending,
+            ..self
+        }
+    }
+
+    fn non_strict(self) -> Self {
+        Self {
+            strict: false,
+            ..self
+        }
+    }
+
+    /// The monotonicity of `expr + value`, where `expr` has this monotonicity
+    fn shifted_by(self, value: &ScalarValue) -> Self {
+        // the addition of floats rounds, and months and days are not totally ordered
+        match value.get_datatype() {
+            DataType::Decimal128(_, _) => self,
+            data_type if integer_width(&data_type).is_some() => self,
+            _ => self.non_strict(),
+        }
+    }
+
+    /// The monotonicity of `expr * value`, where `expr` has this monotonicity
+    fn scaled_by(self, value: &ScalarValue) -> Option<Self> {
+        let monotonicity = match sign(value)? {
+            Ordering::Greater => self,
+            Ordering::Less => self.reverse(),
+            Ordering::Equal => return None,
+        };
+        Some(match value.get_datatype() {
+            data_type if integer_width(&data_type).is_some() => monotonicity,
+            _ => monotonicity.non_strict(),
+        })
+    }
+}
+
+/// Returns how `expr` changes as `input` increases, if `expr` is `input`
+/// wrapped into order preserving (or reversing) operations, such as `input + 1`,
+/// `-input`, `CAST(input AS BIGINT)` or `date_trunc('day', input)`.
+///
+/// These operations map NULL inputs to NULL outputs, so data sorted on `input`
+/// is sorted on `expr` with the same NULLS FIRST / LAST option.
+pub fn expr_monotonicity(
+    expr: &Arc<dyn PhysicalExpr>,
+    input: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+) -> Option<Monotonicity> {
+    if expr.eq(input) {
+        return Some(Monotonicity {
+            descending: false,
+            strict: true,
+        });
+    }
+
+    let any = expr.as_any();
+    if let Some(binary) = any.downcast_ref::<BinaryExpr>() {
+        let left = literal_value(binary.left());
+        let right = literal_value(binary.right());
+        let monotonicity = match (binary.op(), left, right) {
+            (Operator::Plus, Some(value), None) => {
+                expr_monotonicity(binary.right(), input, schema)?.shifted_by(value)
+            }
+            (Operator::Plus | Operator::Minus, None, Some(value)) => {
+                expr_monotonicity(binary.left(), input, schema)?.shifted_by(value)
+            }
+            (Operator::Minus, Some(value), None) => {
+                expr_monotonicity(binary.right(), input, schema)?
+                    .shifted_by(value)
+                    .reverse()
+            }
+            (Operator::Multiply, Some(value), None) => {
+                expr_monotonicity(binary.right(), input, schema)?.scaled_by(value)?
+            }
+            (Operator::Multiply, None, Some(value)) => {
+                expr_monotonicity(binary.left(), input, schema)?.scaled_by(value)?
+            }
+            // the division of integers truncates, so it is not strict
+            (Operator::Divide, None, Some(value)) => {
+                expr_monotonicity(binary.left(), input, schema)?
+                    .scaled_by(value)?
+                    .non_strict()
+            }
+            _ => return None,
+        };
+        // NULL literals make the whole expression NULL
+        (!left.or(right)?.is_null()).then_some(monotonicity)
+    } else if let Some(negative) = any.downcast_ref::<NegativeExpr>() {
+        Some(expr_monotonicity(negative.arg(), input, schema)?.reverse())
+    } else if let Some(cast) = any.downcast_ref::<CastExpr>() {
+        let from_type = cast.expr().data_type(schema).ok()?;
+        if !is_numeric_or_temporal(&from_type)
+            || !is_numeric_or_temporal(cast.cast_type())
+        {
+            return None;
+        }
+        let monotonicity = expr_monotonicity(cast.expr(), input, schema)?;
+        Some(if is_widening_integer_cast(&from_type, cast.cast_type()) {
+            monotonicity
+        } else {
+            monotonicity.non_strict()
+        })
+    } else if let Some(function) = any.downcast_ref::<ScalarFunctionExpr>() {
+        let index = function.monotonic_argument()?;
+        let args = function.args();
+        let arg = args.get(index)?;
+        let other_args_are_literals = args
+            .iter()
+            .enumerate()
+            .all(|(i, other)| i == index || other.as_any().is::<Literal>());
+        if !other_args_are_literals
+            || !is_numeric_or_temporal(&arg.data_type(schema).ok()?)
+        {
+            return None;
+        }
+        Some(expr_monotonicity(arg, input, schema)?.non_strict())
+    } else {
+        None
+    }
+}
+
+fn literal_value(expr: &Arc<dyn PhysicalExpr>) -> Option<&ScalarValue> {
+    expr.as_any()
+        .downcast_ref::<Literal>()
+        .map(|literal| literal.value())
+}
+
+/// The signedness and bit width of an integer type
+fn integer_width(data_type: &DataType) -> Option<(bool, usize)> {
+    match data_type {
+        DataType::Int8 => Some((true, 8)),
+        DataType::Int16 => Some((true, 16)),
+        DataType::Int32 => Some((true, 32)),
+        DataType::Int64 => Some((true, 64)),
+        DataType::UInt8 => Some((false, 8)),
+        DataType::UInt16 => Some((false, 16)),
+        DataType::UInt32 => Some((false, 32)),
+        DataType::UInt64 => Some((false, 64)),
+        _ => None,
+    }
+}
+
+/// Whether every value of the integer type `from` is represented in `to`
+fn is_widening_integer_cast(from: &DataType, to: &DataType) -> bool {
+    match (integer_width(from), integer_width(to)) {
+        (Some((from_signed, from_width)), Some((to_signed, to_width))) => {
+            match (from_signed, to_signed) {
+                (true, true) | (false, false) => from_width <= to_width,
+                (false, true) => from_width < to_width,
+                (true, false) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Adds to the `error` of the evaluation of `expr` on `batch` the expression
+/// and, if the evaluation fails on a single row, the position of the first
+/// failing row and its values of the columns referenced by `expr`.
+///
+/// The failing row is found by evaluating `expr` on each row of `batch`, which
+/// is only done once the evaluation of the whole batch has failed.
+pub fn evaluation_error_context(
+    expr: &dyn PhysicalExpr,
+    batch: &RecordBatch,
+    error: DataFusionError,
+) -> DataFusionError {
+    let failing_row =
+        (0..batch.num_rows()).find(|row| expr.evaluate(&batch.slice(*row, 1)).is_err());
+    let description = match failing_row {
+        Some(row) => {
+            let mut columns = vec![];
+            collect_columns(expr, &mut columns);
+            columns.sort_by_key(|column| column.index());
+            columns.dedup_by_key(|column| column.index());
+            let values = columns
+                .iter()
+                .map(|column| {
+                    let value =
+                        ScalarValue::try_from_array(batch.column(column.index()), row)
+                            .map(|value| value.to_string())
+                            .unwrap_or_else(|_| "?".to_string());
+                    format!("{} = {}", column.name(), value)
+                })
+                .collect::<Vec<_>>();
+            if values.is_empty() {
+                format!("Failed to evaluate {expr} on row {row}")
+            } else {
+                format!(
+                    "Failed to evaluate {expr} on row {row} with {}",
+                    values.join(", ")
+                )
+            }
+        }
+        None => format!("Failed to evaluate {expr}"),
+    };
+    error.context(description)
+}
+
+/// Appends the columns referenced by `expr` to `columns`
+fn collect_columns(expr: &dyn PhysicalExpr, columns: &mut Vec<Column>) {
+    match expr.as_any().downcast_ref::<Column>() {
+        Some(column) => columns.push(column.clone()),
+        None => expr
+            .children()
+            .iter()
+            .for_each(|child| collect_columns(child.as_ref(), columns)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use crate::expressions::Column;
+    use crate::execution_props::ExecutionProps;
+    use crate::expressions::{binary, cast, col, lit, Column};
+    use crate::functions::create_physical_expr;
     use crate::PhysicalSortExpr;
+    use arrow::array::{Int32Array, StringArray};
     use arrow::compute::SortOptions;
+    use arrow::datatypes::{Field, TimeUnit};
     use datafusion_common::Result;
+    use datafusion_expr::BuiltinScalarFunction;
 
     use std::sync::Arc;
 
@@ -342,4 +576,148 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn expr_monotonicity_test() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Float64, true),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+        ]);
+        let a = col("a", &schema)?;
+        let b = col("b", &schema)?;
+        let ts = col("ts", &schema)?;
+        let increasing = |strict| {
+            Some(Monotonicity {
+                descending: false,
+                strict,
+            })
+        };
+        let decreasing = |strict| {
+            Some(Monotonicity {
+                descending: true,
+                strict,
+            })
+        };
+        let binary = |left, op, right| binary(left, op, right, &schema).unwrap();
+
+        let a_plus_one = binary(a.clone(), Operator::Plus, lit(1i32));
+        let cases = vec![
+            (a.clone(), a.clone(), increasing(true)),
+            (a_plus_one.clone(), a.clone(), increasing(true)),
+            (
+                binary(lit(1i32), Operator::Minus, a_plus_one.clone()),
+                a.clone(),
+                decreasing(true),
+            ),
+            (
+                binary(a_plus_one, Operator::Multiply, lit(-2i32)),
+                a.clone(),
+                decreasing(true),
+            ),
+            (
+                binary(a.clone(), Operator::Divide, lit(2i32)),
+                a.clone(),
+                increasing(false),
+            ),
+            (
+                binary(a.clone(), Operator::Multiply, lit(0i32)),
+                a.clone(),
+                None,
+            ),
+            (
+                binary(a.clone(), Operator::Plus, lit(ScalarValue::Int32(None))),
+                a.clone(),
+                None,
+            ),
+            (
+                binary(a.clone(), Operator::Plus, a.clone()),
+                a.clone(),
+                None,
+            ),
+            (
+                Arc::new(NegativeExpr::new(b.clone())),
+                b.clone(),
+                decreasing(true),
+            ),
+            (
+                binary(b.clone(), Operator::Plus, lit(1f64)),
+                b.clone(),
+                increasing(false),
+            ),
+            (
+                cast(a.clone(), &schema, DataType::Int64)?,
+                a.clone(),
+                increasing(true),
+            ),
+            (
+                cast(a.clone(), &schema, DataType::Int16)?,
+                a.clone(),
+                increasing(false),
+            ),
+            (cast(a.clone(), &schema, DataType::Utf8)?, a.clone(), None),
+            (
+                create_physical_expr(
+                    &BuiltinScalarFunction::DateTrunc,
+                    &[lit("day"), ts.clone()],
+                    &schema,
+                    &ExecutionProps::new(),
+                )?,
+                ts,
+                increasing(false),
+            ),
+            (
+                create_physical_expr(
+                    &BuiltinScalarFunction::Abs,
+                    std::slice::from_ref(&a),
+                    &schema,
+                    &ExecutionProps::new(),
+                )?,
+                a,
+                None,
+            ),
+            (b.clone(), Arc::new(Column::new("a", 0)), None),
+        ];
+        for (expr, input, expected) in cases {
+            assert_eq!(
+                expr_monotonicity(&expr, &input, &schema),
+                expected,
+                "{expr} with respect to {input}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluation_error_context() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("s", DataType::Utf8, true),
+            Field::new("i", DataType::Int32, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(StringArray::from(vec!["1", "x", "3"])),
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+            ],
+        )?;
+        let expr = binary(
+            cast(col("s", &schema)?, &schema, DataType::Int32)?,
+            Operator::Plus,
+            col("i", &schema)?,
+            &schema,
+        )?;
+
+        let err = expr.evaluate(&batch).unwrap_err();
+        let err = evaluation_error_context(expr.as_ref(), &batch, err);
+        let message = err.to_string();
+        assert!(
+            message.starts_with(
+                "Failed to evaluate CAST(s@0 AS Int32) + i@1 on row 1 with s = x, i = 2\ncaused by\n"
+            ),
+            "{message}"
+        );
+        Ok(())
+    }
 }
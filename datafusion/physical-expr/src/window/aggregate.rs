@@ -22,21 +22,21 @@ use std::iter::IntoIterator;
 use std::ops::Range;
 use std::sync::Arc;
 
-use arrow::array::Array;
-use arrow::compute::SortOptions;
+use arrow::array::{Array, UInt32Array};
+use arrow::compute::{take, SortOptions};
 use arrow::record_batch::RecordBatch;
 use arrow::{array::ArrayRef, datatypes::Field};
 
 use datafusion_common::Result;
 use datafusion_common::ScalarValue;
-use datafusion_expr::{WindowFrame, WindowFrameUnits};
+use datafusion_expr::{WindowFrame, WindowFrameExclude, WindowFrameUnits};
 
 use crate::window::window_expr::reverse_order_bys;
 use crate::window::SlidingAggregateWindowExpr;
 use crate::{expressions::PhysicalSortExpr, PhysicalExpr};
 use crate::{window::WindowExpr, AggregateExpr};
 
-use super::window_frame_state::WindowFrameContext;
+use super::window_frame_state::{frame_indices_excluding, WindowFrameContext};
 
 /// A window expr that takes the form of an aggregate function
 #[derive(Debug)]
@@ -108,7 +108,15 @@ impl WindowExpr for AggregateWindowExpr {
         for i in 0..length {
             let cur_range =
                 window_frame_ctx.calculate_range(&order_bys, &sort_options, length, i)?;
-            let value = if cur_range.end == cur_range.start {
+            let value = if self.window_frame.exclude != WindowFrameExclude::NoOthers {
+                let indices = frame_indices_excluding(
+                    &self.window_frame,
+                    &order_bys,
+                    cur_range.clone(),
+                    i,
+                )?;
+                aggregate_rows(&self.aggregate, &values, &indices)?
+            } else if cur_range.end == cur_range.start {
                 // We produce None if the window is empty.
                 ScalarValue::try_from(self.aggregate.field()?.data_type())?
             } else {
@@ -166,8 +174,29 @@ impl WindowExpr for AggregateWindowExpr {
     fn uses_bounded_memory(&self) -> bool {
         // NOTE: Currently, groups queries do not support the bounded memory variant.
         self.aggregate.supports_bounded_execution()
+            && self.window_frame.exclude == WindowFrameExclude::NoOthers
             && !self.window_frame.start_bound.is_unbounded()
             && !self.window_frame.end_bound.is_unbounded()
             && !matches!(self.window_frame.units, WindowFrameUnits::Groups)
     }
 }
+
+/// Evaluates `aggregate` from scratch on the rows of `values` at `indices`,
+/// which are the rows of a window frame with an `EXCLUDE` clause
+pub(crate) fn aggregate_rows(
+    aggregate: &Arc<dyn AggregateExpr>,
+    values: &[ArrayRef],
+    indices: &UInt32Array,
+) -> Result<ScalarValue> {
+    if indices.is_empty() {
+        // We produce None if the window is empty.
+        return ScalarValue::try_from(aggregate.field()?.data_type());
+    }
+    let rows = values
+        .iter()
+        .map(|v| take(v.as_ref(), indices, None))
+        .collect::<arrow::error::Result<Vec<_>>>()?;
+    let mut accumulator = aggregate.create_accumulator()?;
+    accumulator.update_batch(&rows)?;
+    accumulator.evaluate()
+}
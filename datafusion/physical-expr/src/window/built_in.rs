@@ -17,7 +17,7 @@
 
 //! Physical exec for built-in window function expressions.
 
-use super::window_frame_state::WindowFrameContext;
+use super::window_frame_state::{frame_indices_excluding, WindowFrameContext};
 use super::BuiltInWindowFunctionExpr;
 use super::WindowExpr;
 use crate::window::window_expr::{
@@ -27,12 +27,13 @@ use crate::window::{
     PartitionBatches, PartitionWindowAggStates, WindowAggState, WindowState,
 };
 use crate::{expressions::PhysicalSortExpr, PhysicalExpr};
-use arrow::compute::{concat, SortOptions};
+use arrow::array::Array;
+use arrow::compute::{concat, take, SortOptions};
 use arrow::record_batch::RecordBatch;
 use arrow::{array::ArrayRef, datatypes::Field};
 use datafusion_common::ScalarValue;
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::{WindowFrame, WindowFrameUnits};
+use datafusion_expr::{WindowFrame, WindowFrameExclude, WindowFrameUnits};
 use std::any::Any;
 use std::sync::Arc;
 
@@ -112,7 +113,27 @@ impl WindowExpr for BuiltInWindowExpr {
                     num_rows,
                     idx,
                 )?;
-                let value = evaluator.evaluate_inside_range(&values, range)?;
+                let value = if self.window_frame.exclude != WindowFrameExclude::NoOthers {
+                    let indices = frame_indices_excluding(
+                        &self.window_frame,
+                        &order_bys,
+                        range,
+                        idx,
+                    )?;
+                    if indices.is_empty() {
+                        // We produce None if the window is empty.
+                        ScalarValue::try_from(self.expr.field()?.data_type())?
+                    } else {
+                        // Evaluate on the rows of the frame, as if they were contiguous
+                        let rows = values
+                            .iter()
+                            .map(|v| take(v.as_ref(), &indices, None))
+                            .collect::<arrow::error::Result<Vec<_>>>()?;
+                        evaluator.evaluate_inside_range(&rows, 0..indices.len())?
+                    }
+                } else {
+                    evaluator.evaluate_inside_range(&values, range)?
+                };
                 row_wise_results.push(value);
             }
             ScalarValue::iter_to_array(row_wise_results.into_iter())
@@ -242,7 +263,8 @@ impl WindowExpr for BuiltInWindowExpr {
         // NOTE: Currently, groups queries do not support the bounded memory variant.
         self.expr.supports_bounded_execution()
             && (!self.expr.uses_window_frame()
-                || !(self.window_frame.start_bound.is_unbounded()
+                || !(self.window_frame.exclude != WindowFrameExclude::NoOthers
+                    || self.window_frame.start_bound.is_unbounded()
                     || self.window_frame.end_bound.is_unbounded()
                     || matches!(self.window_frame.units, WindowFrameUnits::Groups)))
     }
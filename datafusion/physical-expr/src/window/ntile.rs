@@ -38,6 +38,11 @@ impl Ntile {
     pub fn new(name: String, n: u64) -> Self {
         Self { name, n }
     }
+
+    /// Get the number of buckets
+    pub fn get_n(&self) -> u64 {
+        self.n
+    }
 }
 
 impl BuiltInWindowFunctionExpr for Ntile {
@@ -70,13 +75,46 @@ pub(crate) struct NtileEvaluator {
 }
 
 impl PartitionEvaluator for NtileEvaluator {
+    /// Splits the rows of the partition in `n` buckets whose sizes differ by at
+    /// most 1, the larger buckets coming first like in Postgres
     fn evaluate(&self, _values: &[ArrayRef], num_rows: usize) -> Result<ArrayRef> {
         let num_rows = num_rows as u64;
-        let mut vec: Vec<u64> = Vec::new();
-        for i in 0..num_rows {
-            let res = i * self.n / num_rows;
-            vec.push(res + 1)
-        }
-        Ok(Arc::new(UInt64Array::from_iter_values(vec)))
+        let bucket_size = num_rows / self.n;
+        // the first `num_rows % n` buckets have one more row
+        let large_buckets_rows = (num_rows % self.n) * (bucket_size + 1);
+        let result = (0..num_rows).map(|i| {
+            if i < large_buckets_rows {
+                i / (bucket_size + 1) + 1
+            } else {
+                (i - large_buckets_rows) / bucket_size + num_rows % self.n + 1
+            }
+        });
+        Ok(Arc::new(UInt64Array::from_iter_values(result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion_common::cast::as_uint64_array;
+
+    fn test_ntile(n: u64, num_rows: usize, expected: Vec<u64>) -> Result<()> {
+        let result = Ntile::new("ntile".to_string(), n)
+            .create_evaluator()?
+            .evaluate(&[], num_rows)?;
+        let result = as_uint64_array(&result)?;
+        assert_eq!(expected, result.values());
+        Ok(())
+    }
+
+    #[test]
+    fn ntile() -> Result<()> {
+        test_ntile(3, 0, vec![])?;
+        test_ntile(3, 9, vec![1, 1, 1, 2, 2, 2, 3, 3, 3])?;
+        test_ntile(4, 10, vec![1, 1, 1, 2, 2, 2, 3, 3, 4, 4])?;
+        test_ntile(5, 7, vec![1, 1, 2, 2, 3, 4, 5])?;
+        // more buckets than rows
+        test_ntile(5, 3, vec![1, 2, 3])?;
+        Ok(())
     }
 }
@@ -29,8 +29,9 @@ use arrow::{array::ArrayRef, datatypes::Field};
 
 use datafusion_common::ScalarValue;
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::{Accumulator, WindowFrame, WindowFrameUnits};
+use datafusion_expr::{Accumulator, WindowFrame, WindowFrameExclude, WindowFrameUnits};
 
+use crate::window::aggregate::aggregate_rows;
 use crate::window::window_expr::{reverse_order_bys, WindowFn, WindowFunctionState};
 use crate::window::{
     AggregateWindowExpr, PartitionBatches, PartitionWindowAggStates, WindowAggState,
@@ -39,7 +40,7 @@ use crate::window::{
 use crate::{expressions::PhysicalSortExpr, PhysicalExpr};
 use crate::{window::WindowExpr, AggregateExpr};
 
-use super::window_frame_state::WindowFrameContext;
+use super::window_frame_state::{frame_indices_excluding, WindowFrameContext};
 
 /// A window expr that takes the form of an aggregate function
 #[derive(Debug)]
@@ -202,6 +203,7 @@ impl WindowExpr for SlidingAggregateWindowExpr {
     fn uses_bounded_memory(&self) -> bool {
         // NOTE: Currently, groups queries do not support the bounded memory variant.
         self.aggregate.supports_bounded_execution()
+            && self.window_frame.exclude == WindowFrameExclude::NoOthers
             && !self.window_frame.start_bound.is_unbounded()
             && !self.window_frame.end_bound.is_unbounded()
             && !matches!(self.window_frame.units, WindowFrameUnits::Groups)
@@ -273,12 +275,22 @@ impl SlidingAggregateWindowExpr {
             if cur_range.end == length && !is_end {
                 break;
             }
-            let value = self.get_aggregate_result_inside_range(
-                last_range,
-                &cur_range,
-                &values,
-                accumulator,
-            )?;
+            let value = if self.window_frame.exclude != WindowFrameExclude::NoOthers {
+                let indices = frame_indices_excluding(
+                    &self.window_frame,
+                    &order_bys,
+                    cur_range.clone(),
+                    *idx,
+                )?;
+                aggregate_rows(&self.aggregate, &values, &indices)?
+            } else {
+                self.get_aggregate_result_inside_range(
+                    last_range,
+                    &cur_range,
+                    &values,
+                    accumulator,
+                )?
+            };
             row_wise_results.push(value);
             last_range.start = cur_range.start;
             last_range.end = cur_range.end;
@@ -18,11 +18,13 @@
 //! This module provides utilities for window frame index calculations depending on the window frame mode:
 //! RANGE, ROWS, GROUPS.
 
-use arrow::array::ArrayRef;
+use arrow::array::{ArrayRef, UInt32Array};
 use arrow::compute::kernels::sort::SortOptions;
 use datafusion_common::bisect::{bisect, find_bisect_point};
 use datafusion_common::{DataFusionError, Result, ScalarValue};
-use datafusion_expr::{WindowFrame, WindowFrameBound, WindowFrameUnits};
+use datafusion_expr::{
+    WindowFrame, WindowFrameBound, WindowFrameExclude, WindowFrameUnits,
+};
 use std::cmp::min;
 use std::collections::VecDeque;
 use std::fmt::Debug;
@@ -295,7 +297,7 @@ impl WindowFrameStateRange {
 // The syntax is as follows:
 //     GROUPS frame_start [ frame_exclusion ]
 //     GROUPS BETWEEN frame_start AND frame_end [ frame_exclusion ]
-// The optional frame_exclusion specifier is not yet supported.
+// The optional frame_exclusion specifier is applied by `frame_indices_excluding`.
 // The frame_start and frame_end parameters allow us to specify which rows the window
 // frame starts and ends with. They accept the following values:
 //    - UNBOUNDED PRECEDING: Start with the first row of the partition. Possible only in frame_start.
@@ -633,6 +635,44 @@ impl WindowFrameStateGroups {
     }
 }
 
+/// Returns the indices of the rows of `range`, the frame of the row `idx`,
+/// that are not removed by the `EXCLUDE` clause of `window_frame`. The peers
+/// of the row `idx` are the rows of the frame with the same values of
+/// `range_columns`.
+pub fn frame_indices_excluding(
+    window_frame: &WindowFrame,
+    range_columns: &[ArrayRef],
+    range: Range<usize>,
+    idx: usize,
+) -> Result<UInt32Array> {
+    let current_row_values = range_columns
+        .iter()
+        .map(|col| ScalarValue::try_from_array(col, idx))
+        .collect::<Result<Vec<ScalarValue>>>()?;
+    let is_peer = |row: usize| -> Result<bool> {
+        for (col, value) in range_columns.iter().zip(&current_row_values) {
+            if ScalarValue::try_from_array(col, row)? != *value {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    };
+
+    let mut indices = Vec::with_capacity(range.len());
+    for row in range {
+        let excluded = match window_frame.exclude {
+            WindowFrameExclude::NoOthers => false,
+            WindowFrameExclude::CurrentRow => row == idx,
+            WindowFrameExclude::Group => is_peer(row)?,
+            WindowFrameExclude::Ties => row != idx && is_peer(row)?,
+        };
+        if !excluded {
+            indices.push(row as u32);
+        }
+    }
+    Ok(UInt32Array::from(indices))
+}
+
 #[cfg(test)]
 mod tests {
     use arrow::array::Float64Array;
@@ -991,4 +1031,23 @@ mod tests {
             assert_eq!(start, test_data.group_indices[idx]);
         }
     }
+
+    #[test]
+    fn test_frame_indices_excluding() -> Result<()> {
+        let test_data = test_data();
+        let window_frame = WindowFrame::new(true);
+        // the rows 2 and 3 are peers
+        for (exclude, expected) in [
+            (WindowFrameExclude::NoOthers, vec![0, 1, 2, 3, 4, 5]),
+            (WindowFrameExclude::CurrentRow, vec![0, 1, 3, 4, 5]),
+            (WindowFrameExclude::Group, vec![0, 1, 4, 5]),
+            (WindowFrameExclude::Ties, vec![0, 1, 2, 4, 5]),
+        ] {
+            let window_frame = window_frame.clone().with_exclude(exclude);
+            let indices =
+                frame_indices_excluding(&window_frame, &test_data.arrays, 0..6, 2)?;
+            assert_eq!(indices, UInt32Array::from(expected), "{exclude}");
+        }
+        Ok(())
+    }
 }
@@ -23,9 +23,8 @@ use crate::physical_plan::{
     AsExecutionPlan, DefaultPhysicalExtensionCodec, PhysicalExtensionCodec,
 };
 use crate::protobuf;
-use datafusion::physical_plan::functions::make_scalar_function;
 use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::{create_udaf, create_udf, Expr, LogicalPlan, Volatility};
+use datafusion_expr::{Expr, LogicalPlan};
 use prost::{
     bytes::{Bytes, BytesMut},
     Message,
@@ -95,36 +94,11 @@ impl Serializeable for Expr {
         // Until the underlying prost issue ( https://github.com/tokio-rs/prost/issues/736 ) is fixed, we try to
         // deserialize the data here and check for errors.
         //
-        // Need to provide some placeholder registry because the stream may contain UDFs
-        struct PlaceHolderRegistry;
-
-        impl FunctionRegistry for PlaceHolderRegistry {
-            fn udfs(&self) -> std::collections::HashSet<String> {
-                std::collections::HashSet::default()
-            }
-
-            fn udf(&self, name: &str) -> Result<Arc<datafusion_expr::ScalarUDF>> {
-                Ok(Arc::new(create_udf(
-                    name,
-                    vec![],
-                    Arc::new(arrow::datatypes::DataType::Null),
-                    Volatility::Immutable,
-                    make_scalar_function(|_| unimplemented!()),
-                )))
-            }
-
-            fn udaf(&self, name: &str) -> Result<Arc<datafusion_expr::AggregateUDF>> {
-                Ok(Arc::new(create_udaf(
-                    name,
-                    arrow::datatypes::DataType::Null,
-                    Arc::new(arrow::datatypes::DataType::Null),
-                    Volatility::Immutable,
-                    Arc::new(|_| unimplemented!()),
-                    Arc::new(vec![]),
-                )))
-            }
-        }
-        Expr::from_bytes_with_registry(&bytes, &PlaceHolderRegistry)?;
+        // Only the protobuf is decoded, as resolving any UDFs would require
+        // the functions (and their fingerprints) to be registered
+        protobuf::LogicalExprNode::decode(bytes.as_ref()).map_err(|e| {
+            DataFusionError::Plan(format!("Error decoding expr as protobuf: {e}"))
+        })?;
 
         Ok(bytes)
     }
@@ -206,6 +180,72 @@ pub fn logical_plan_from_bytes_with_extension_codec(
     protobuf.try_into_logical_plan(ctx, extension_codec)
 }
 
+/// The version of the logical plan format written by
+/// [`logical_plan_to_versioned_bytes`].
+///
+/// Plans written with any version up to and including this one can be
+/// read back by this release. The version is only bumped for changes to
+/// `datafusion.proto` that can not be made in a backwards compatible way.
+pub const LOGICAL_PLAN_FORMAT_VERSION: u32 = 1;
+
+/// Serialize a LogicalPlan as bytes tagged with
+/// [`LOGICAL_PLAN_FORMAT_VERSION`], suitable for persisting plans (such
+/// as views or prepared statements) that may be read by a later release
+pub fn logical_plan_to_versioned_bytes(plan: &LogicalPlan) -> Result<Bytes> {
+    let extension_codec = DefaultLogicalExtensionCodec {};
+    logical_plan_to_versioned_bytes_with_extension_codec(plan, &extension_codec)
+}
+
+/// Serialize a LogicalPlan as versioned bytes, using the provided extension codec
+pub fn logical_plan_to_versioned_bytes_with_extension_codec(
+    plan: &LogicalPlan,
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<Bytes> {
+    let protobuf = protobuf::VersionedLogicalPlanNode {
+        version: LOGICAL_PLAN_FORMAT_VERSION,
+        plan: Some(protobuf::LogicalPlanNode::try_from_logical_plan(
+            plan,
+            extension_codec,
+        )?),
+    };
+    let mut buffer = BytesMut::new();
+    protobuf.encode(&mut buffer).map_err(|e| {
+        DataFusionError::Plan(format!("Error encoding protobuf as bytes: {e}"))
+    })?;
+    Ok(buffer.into())
+}
+
+/// Deserialize a LogicalPlan from bytes written by
+/// [`logical_plan_to_versioned_bytes`]
+pub fn logical_plan_from_versioned_bytes(
+    bytes: &[u8],
+    ctx: &SessionContext,
+) -> Result<LogicalPlan> {
+    let extension_codec = DefaultLogicalExtensionCodec {};
+    logical_plan_from_versioned_bytes_with_extension_codec(bytes, ctx, &extension_codec)
+}
+
+/// Deserialize a LogicalPlan from versioned bytes, using the provided extension codec
+pub fn logical_plan_from_versioned_bytes_with_extension_codec(
+    bytes: &[u8],
+    ctx: &SessionContext,
+    extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<LogicalPlan> {
+    let protobuf = protobuf::VersionedLogicalPlanNode::decode(bytes).map_err(|e| {
+        DataFusionError::Plan(format!("Error decoding plan as protobuf: {e}"))
+    })?;
+    if protobuf.version == 0 || protobuf.version > LOGICAL_PLAN_FORMAT_VERSION {
+        return Err(DataFusionError::Plan(format!(
+            "Unsupported logical plan format version {}, expected at most {}",
+            protobuf.version, LOGICAL_PLAN_FORMAT_VERSION
+        )));
+    }
+    protobuf
+        .plan
+        .ok_or_else(|| DataFusionError::Plan("Missing logical plan".to_string()))?
+        .try_into_logical_plan(ctx, extension_codec)
+}
+
 /// Serialize a PhysicalPlan as bytes
 pub fn physical_plan_to_bytes(plan: Arc<dyn ExecutionPlan>) -> Result<Bytes> {
     let extension_codec = DefaultPhysicalExtensionCodec {};
@@ -275,10 +315,18 @@ pub fn physical_plan_from_bytes_with_extension_codec(
 #[cfg(test)]
 mod test {
     use super::*;
-    use arrow::{array::ArrayRef, datatypes::DataType};
+    use crate::function_registry::FunctionFactory;
+    use arrow::{
+        array::ArrayRef,
+        datatypes::{DataType, SchemaRef},
+    };
+    use datafusion::datasource::TableProvider;
     use datafusion::physical_plan::functions::make_scalar_function;
     use datafusion::prelude::SessionContext;
-    use datafusion_expr::{col, create_udf, lit, Volatility};
+    use datafusion_expr::logical_plan::Extension;
+    use datafusion_expr::{
+        col, create_udf, lit, LogicalPlanBuilder, ScalarUDF, Volatility,
+    };
     use std::sync::Arc;
 
     #[test]
@@ -470,6 +518,103 @@ mod test {
         }).expect("spawning thread").join().expect("joining thread");
     }
 
+    /// A factory creating the `dummy` UDF of [`context_with_udf`]
+    #[derive(Debug)]
+    struct DummyFunctionFactory {}
+
+    impl FunctionFactory for DummyFunctionFactory {
+        fn create_udf(&self, name: &str) -> Result<Option<Arc<ScalarUDF>>> {
+            Ok((name == "dummy").then(|| context_with_udf().udf("dummy").unwrap()))
+        }
+    }
+
+    #[derive(Debug)]
+    struct FactoryCodec {}
+
+    impl LogicalExtensionCodec for FactoryCodec {
+        fn try_decode(
+            &self,
+            _buf: &[u8],
+            _inputs: &[LogicalPlan],
+            _ctx: &SessionContext,
+        ) -> Result<Extension> {
+            unimplemented!()
+        }
+
+        fn try_encode(&self, _node: &Extension, _buf: &mut Vec<u8>) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn try_decode_table_provider(
+            &self,
+            _buf: &[u8],
+            _schema: SchemaRef,
+            _ctx: &SessionContext,
+        ) -> Result<Arc<dyn TableProvider>> {
+            unimplemented!()
+        }
+
+        fn try_encode_table_provider(
+            &self,
+            _node: Arc<dyn TableProvider>,
+            _buf: &mut Vec<u8>,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn function_factories(&self) -> Vec<Arc<dyn FunctionFactory>> {
+            vec![Arc::new(DummyFunctionFactory {})]
+        }
+    }
+
+    #[test]
+    fn udf_plan_roundtrip_with_function_factory() {
+        let expr = context_with_udf()
+            .udf("dummy")
+            .expect("could not find udf")
+            .call(vec![lit("")]);
+        let plan = LogicalPlanBuilder::empty(true)
+            .project(vec![expr])
+            .unwrap()
+            .build()
+            .unwrap();
+        let bytes = logical_plan_to_bytes(&plan).unwrap();
+
+        // the function is not registered, so it is created by the factory
+        let ctx = SessionContext::new();
+        let err = logical_plan_from_bytes(&bytes, &ctx).unwrap_err();
+        assert!(err.to_string().contains("dummy"), "{err}");
+        let round_trip =
+            logical_plan_from_bytes_with_extension_codec(&bytes, &ctx, &FactoryCodec {})
+                .unwrap();
+        assert_eq!(format!("{plan:?}"), format!("{round_trip:?}"));
+    }
+
+    #[test]
+    fn udf_roundtrip_fingerprint_mismatch() {
+        let expr = context_with_udf()
+            .udf("dummy")
+            .expect("could not find udf")
+            .call(vec![lit("")]);
+        let bytes = expr.to_bytes().unwrap();
+
+        // same name, different signature
+        let ctx = SessionContext::new();
+        ctx.register_udf(create_udf(
+            "dummy",
+            vec![DataType::Int64],
+            Arc::new(DataType::Utf8),
+            Volatility::Immutable,
+            make_scalar_function(|args: &[ArrayRef]| Ok(args[0].clone())),
+        ));
+        let err = Expr::from_bytes_with_registry(&bytes, &ctx).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("does not match the serialized function fingerprint"),
+            "{err}"
+        );
+    }
+
     /// return a `SessionContext` with a `dummy` function registered as a UDF
     fn context_with_udf() -> SessionContext {
         let fn_impl = |args: &[ArrayRef]| Ok(Arc::new(args[0].clone()) as ArrayRef);
@@ -0,0 +1,331 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Resolution of user defined functions referenced by serialized plans
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+use datafusion::execution::registry::FunctionRegistry;
+use datafusion_common::fingerprint::Fingerprint;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::type_coercion::functions::data_types;
+use datafusion_expr::{AggregateUDF, ScalarUDF, Signature, TypeSignature, Volatility};
+use prost::Message;
+
+use crate::protobuf;
+
+/// Materializes user defined functions that are referenced by a
+/// serialized plan but are not registered with the [`FunctionRegistry`]
+/// used for deserialization.
+///
+/// This allows, for example, distributed workers to lazily create only the
+/// functions a plan actually requires.
+pub trait FunctionFactory: Debug + Send + Sync {
+    /// Create the scalar function named `name`, or return `None` if this
+    /// factory does not know about it
+    fn create_udf(&self, _name: &str) -> Result<Option<Arc<ScalarUDF>>> {
+        Ok(None)
+    }
+
+    /// Create the aggregate function named `name`, or return `None` if this
+    /// factory does not know about it
+    fn create_udaf(&self, _name: &str) -> Result<Option<Arc<AggregateUDF>>> {
+        Ok(None)
+    }
+}
+
+/// A [`FunctionRegistry`] that resolves functions from an inner registry
+/// and falls back to a list of [`FunctionFactory`]s, tried in order, for
+/// functions the inner registry does not know about.
+pub struct FallbackFunctionRegistry<'a> {
+    inner: &'a dyn FunctionRegistry,
+    factories: Vec<Arc<dyn FunctionFactory>>,
+}
+
+impl<'a> FallbackFunctionRegistry<'a> {
+    /// Create a registry resolving from `inner` first, then `factories`
+    pub fn new(
+        inner: &'a dyn FunctionRegistry,
+        factories: Vec<Arc<dyn FunctionFactory>>,
+    ) -> Self {
+        Self { inner, factories }
+    }
+}
+
+impl FunctionRegistry for FallbackFunctionRegistry<'_> {
+    fn udfs(&self) -> HashSet<String> {
+        self.inner.udfs()
+    }
+
+    fn udf(&self, name: &str) -> Result<Arc<ScalarUDF>> {
+        let err = match self.inner.udf(name) {
+            Ok(udf) => return Ok(udf),
+            Err(e) => e,
+        };
+        for factory in &self.factories {
+            if let Some(udf) = factory.create_udf(name)? {
+                return Ok(udf);
+            }
+        }
+        Err(err)
+    }
+
+    fn udaf(&self, name: &str) -> Result<Arc<AggregateUDF>> {
+        let err = match self.inner.udaf(name) {
+            Ok(udaf) => return Ok(udaf),
+            Err(e) => e,
+        };
+        for factory in &self.factories {
+            if let Some(udaf) = factory.create_udaf(name)? {
+                return Ok(udaf);
+            }
+        }
+        Err(err)
+    }
+}
+
+/// Returns the fingerprint of a scalar function, which is serialized
+/// alongside references to the function so that deserialization can
+/// detect a function of the same name but a different signature.
+///
+/// The fingerprint is computed from a canonical encoding of the signature,
+/// with the data types in their protobuf form, so it is stable across
+/// versions of DataFusion. A signature that can not be encoded has the
+/// fingerprint `0`, which matches any function.
+pub fn udf_fingerprint(udf: &ScalarUDF) -> u64 {
+    fingerprint(&udf.name, &udf.signature)
+}
+
+/// Returns the fingerprint of an aggregate function, see [`udf_fingerprint`]
+pub fn udaf_fingerprint(udaf: &AggregateUDF) -> u64 {
+    fingerprint(&udaf.name, &udaf.signature)
+}
+
+fn fingerprint(name: &str, signature: &Signature) -> u64 {
+    let mut buf = vec![];
+    put_bytes(&mut buf, name.as_bytes());
+    buf.push(match signature.volatility {
+        Volatility::Immutable => 0,
+        Volatility::Stable => 1,
+        Volatility::Volatile => 2,
+    });
+    match encode_type_signature(&mut buf, &signature.type_signature) {
+        Ok(()) => Fingerprint::of(&buf).as_u64(),
+        Err(_) => 0,
+    }
+}
+
+/// Appends `bytes` to `buf`, prefixed with their length
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn put_len(buf: &mut Vec<u8>, len: usize) {
+    buf.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+fn encode_types(buf: &mut Vec<u8>, types: &[DataType]) -> Result<()> {
+    put_len(buf, types.len());
+    for data_type in types {
+        let arrow_type = protobuf::ArrowType::try_from(data_type)
+            .map_err(|e| DataFusionError::Internal(e.to_string()))?;
+        put_bytes(buf, &arrow_type.encode_to_vec());
+    }
+    Ok(())
+}
+
+fn encode_type_signature(buf: &mut Vec<u8>, signature: &TypeSignature) -> Result<()> {
+    match signature {
+        TypeSignature::Variadic(types) => {
+            buf.push(0);
+            encode_types(buf, types)?;
+        }
+        TypeSignature::VariadicEqual => buf.push(1),
+        TypeSignature::Uniform(n, types) => {
+            buf.push(2);
+            put_len(buf, *n);
+            encode_types(buf, types)?;
+        }
+        TypeSignature::Exact(types) => {
+            buf.push(3);
+            encode_types(buf, types)?;
+        }
+        TypeSignature::VariadicCoercible => buf.push(4),
+        TypeSignature::Numeric(n) => {
+            buf.push(5);
+            put_len(buf, *n);
+        }
+        TypeSignature::Coercible(types) => {
+            buf.push(6);
+            put_len(buf, types.len());
+            for types in types {
+                encode_types(buf, types)?;
+            }
+        }
+        TypeSignature::Any(n) => {
+            buf.push(7);
+            put_len(buf, *n);
+        }
+        TypeSignature::OneOf(signatures) => {
+            buf.push(8);
+            put_len(buf, signatures.len());
+            for signature in signatures {
+                encode_type_signature(buf, signature)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a resolved function matches the serialized `expected`
+/// fingerprint. A fingerprint of `0` was written by a version that did not
+/// record fingerprints and always matches.
+pub(crate) fn check_fingerprint(name: &str, expected: u64, actual: u64) -> Result<()> {
+    if expected == 0 || expected == actual {
+        Ok(())
+    } else {
+        Err(DataFusionError::Plan(format!(
+            "User defined function '{name}' does not match the serialized function fingerprint"
+        )))
+    }
+}
+
+/// Checks that the scalar function `udf`, resolved for a serialized call of
+/// the function, accepts the arguments of the call, of types `arg_types`,
+/// and returns the serialized `return_type` for them. A function of the same
+/// name but with another signature is rejected rather than called with
+/// arguments it doesn't expect.
+pub(crate) fn check_udf_call(
+    udf: &ScalarUDF,
+    arg_types: &[DataType],
+    return_type: &DataType,
+) -> Result<()> {
+    let name = &udf.name;
+    match data_types(arg_types, &udf.signature) {
+        Ok(types) if types == arg_types => {}
+        _ => {
+            return Err(DataFusionError::Plan(format!(
+                "User defined function '{name}' does not accept the serialized \
+                arguments of types {arg_types:?}"
+            )))
+        }
+    }
+    let actual = (udf.return_type)(arg_types)?;
+    if actual.as_ref() != return_type {
+        return Err(DataFusionError::Plan(format!(
+            "User defined function '{name}' returns {actual:?} instead of the \
+            serialized return type {return_type:?}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::physical_plan::functions::make_scalar_function;
+    use datafusion::prelude::SessionContext;
+    use datafusion_expr::create_udf;
+
+    #[derive(Debug)]
+    struct DummyFactory {}
+
+    fn dummy_udf(volatility: Volatility) -> ScalarUDF {
+        create_udf(
+            "dummy",
+            vec![DataType::Utf8],
+            Arc::new(DataType::Utf8),
+            volatility,
+            make_scalar_function(|args| Ok(args[0].clone())),
+        )
+    }
+
+    impl FunctionFactory for DummyFactory {
+        fn create_udf(&self, name: &str) -> Result<Option<Arc<ScalarUDF>>> {
+            Ok((name == "dummy").then(|| Arc::new(dummy_udf(Volatility::Immutable))))
+        }
+    }
+
+    #[test]
+    fn falls_back_to_factories() {
+        let ctx = SessionContext::new();
+        let registry =
+            FallbackFunctionRegistry::new(&ctx, vec![Arc::new(DummyFactory {})]);
+
+        assert_eq!(registry.udf("dummy").unwrap().name, "dummy");
+        let err = registry.udf("missing").unwrap_err();
+        assert!(err.to_string().contains("missing"), "{err}");
+        assert!(registry.udaf("dummy").is_err());
+    }
+
+    #[test]
+    fn fingerprint_detects_signature_change() {
+        let immutable = udf_fingerprint(&dummy_udf(Volatility::Immutable));
+        let volatile = udf_fingerprint(&dummy_udf(Volatility::Volatile));
+
+        assert_eq!(
+            immutable,
+            udf_fingerprint(&dummy_udf(Volatility::Immutable))
+        );
+        assert_ne!(immutable, volatile);
+        check_fingerprint("dummy", immutable, immutable).unwrap();
+        check_fingerprint("dummy", 0, immutable).unwrap();
+        check_fingerprint("dummy", volatile, immutable).unwrap_err();
+    }
+
+    #[test]
+    fn udf_call_checks_signature_and_return_type() {
+        let udf = dummy_udf(Volatility::Immutable);
+        check_udf_call(&udf, &[DataType::Utf8], &DataType::Utf8).unwrap();
+        let err = check_udf_call(&udf, &[DataType::Int64], &DataType::Utf8).unwrap_err();
+        assert!(err.to_string().contains("does not accept"), "{err}");
+        let err = check_udf_call(&udf, &[DataType::Utf8], &DataType::Int64).unwrap_err();
+        assert!(err.to_string().contains("returns Utf8"), "{err}");
+    }
+
+    #[test]
+    fn fingerprint_of_canonical_signature() {
+        let utf8 = Signature::exact(vec![DataType::Utf8], Volatility::Immutable);
+        let large_utf8 =
+            Signature::exact(vec![DataType::LargeUtf8], Volatility::Immutable);
+        let one_of = Signature::one_of(
+            vec![TypeSignature::Exact(vec![DataType::Utf8])],
+            Volatility::Immutable,
+        );
+
+        let fingerprints = [
+            fingerprint("dummy", &utf8),
+            fingerprint("dummy", &large_utf8),
+            fingerprint("dummy", &one_of),
+            fingerprint("dummy2", &utf8),
+        ];
+        for (i, a) in fingerprints.iter().enumerate() {
+            assert_ne!(*a, 0);
+            for b in &fingerprints[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert_eq!(
+            fingerprints[0],
+            udf_fingerprint(&dummy_udf(Volatility::Immutable))
+        );
+    }
+}
@@ -19,6 +19,7 @@
 
 pub mod bytes;
 pub mod common;
+pub mod function_registry;
 pub mod generated;
 pub mod logical_plan;
 pub mod physical_plan;
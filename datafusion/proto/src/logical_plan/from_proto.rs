@@ -15,15 +15,18 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::function_registry::{check_fingerprint, udaf_fingerprint, udf_fingerprint};
 use crate::protobuf::{
     self,
     plan_type::PlanTypeEnum::{
-        FinalLogicalPlan, FinalPhysicalPlan, InitialLogicalPlan, InitialPhysicalPlan,
-        OptimizedLogicalPlan, OptimizedPhysicalPlan,
+        AnalyzedLogicalPlan, FinalLogicalPlan, FinalPhysicalPlan, InitialLogicalPlan,
+        InitialPhysicalPlan, OptimizedLogicalPlan, OptimizedPhysicalPlan,
     },
-    CubeNode, GroupingSetNode, OptimizedLogicalPlanType, OptimizedPhysicalPlanType,
-    PlaceholderNode, RollupNode,
+    AnalyzedLogicalPlanType, CubeNode, GroupingSetNode, OptimizedLogicalPlanType,
+    OptimizedPhysicalPlanType, PlaceholderNode, RollupNode,
 };
+use arrow::array::new_empty_array;
+use arrow::compute::cast;
 use arrow::datatypes::{
     DataType, Field, IntervalMonthDayNanoType, IntervalUnit, Schema, TimeUnit, UnionMode,
 };
@@ -43,12 +46,12 @@ use datafusion_expr::{
     regexp_replace, repeat, replace, reverse, right, round, rpad, rtrim, sha224, sha256,
     sha384, sha512, signum, sin, split_part, sqrt, starts_with, strpos, substr,
     substring, tan, to_hex, to_timestamp_micros, to_timestamp_millis,
-    to_timestamp_seconds, translate, trim, trunc, upper, uuid, AggregateFunction,
-    Between, BinaryExpr, BuiltInWindowFunction, BuiltinScalarFunction, Case, Cast, Expr,
-    GetIndexedField, GroupingSet,
+    to_timestamp_seconds, translate, trim, trunc, try_divide, try_to_timestamp, upper,
+    uuid, AggregateFunction, Between, BinaryExpr, BuiltInWindowFunction,
+    BuiltinScalarFunction, Case, Cast, Expr, GetIndexedField, GroupingSet,
     GroupingSet::GroupingSets,
     JoinConstraint, JoinType, Like, Operator, TryCast, WindowFrame, WindowFrameBound,
-    WindowFrameUnits,
+    WindowFrameExclude, WindowFrameUnits,
 };
 use std::sync::Arc;
 
@@ -376,6 +379,11 @@ impl From<&protobuf::StringifiedPlan> for StringifiedPlan {
                     )
                 }) {
                 InitialLogicalPlan(_) => PlanType::InitialLogicalPlan,
+                AnalyzedLogicalPlan(AnalyzedLogicalPlanType { analyzer_name }) => {
+                    PlanType::AnalyzedLogicalPlan {
+                        analyzer_name: analyzer_name.clone(),
+                    }
+                }
                 OptimizedLogicalPlan(OptimizedLogicalPlanType { optimizer_name }) => {
                     PlanType::OptimizedLogicalPlan {
                         optimizer_name: optimizer_name.clone(),
@@ -472,6 +480,8 @@ impl From<&protobuf::ScalarFunction> for BuiltinScalarFunction {
             ScalarFunction::FromUnixtime => Self::FromUnixtime,
             ScalarFunction::Atan2 => Self::Atan2,
             ScalarFunction::ArrowTypeof => Self::ArrowTypeof,
+            ScalarFunction::TryDivide => Self::TryDivide,
+            ScalarFunction::TryToTimestamp => Self::TryToTimestamp,
         }
     }
 }
@@ -560,8 +570,8 @@ impl TryFrom<&protobuf::ScalarValue> for ScalarValue {
 
         Ok(match value {
             Value::BoolValue(v) => Self::Boolean(Some(*v)),
-            Value::Utf8Value(v) => Self::Utf8(Some(v.to_owned())),
-            Value::LargeUtf8Value(v) => Self::LargeUtf8(Some(v.to_owned())),
+            Value::Utf8Value(v) => Self::Utf8(Some(v.as_str().into())),
+            Value::LargeUtf8Value(v) => Self::LargeUtf8(Some(v.as_str().into())),
             Value::Int8Value(v) => Self::Int8(Some(*v as i8)),
             Value::Int16Value(v) => Self::Int16(Some(*v as i16)),
             Value::Int32Value(v) => Self::Int32(Some(*v)),
@@ -589,7 +599,17 @@ impl TryFrom<&protobuf::ScalarValue> for ScalarValue {
 
                 validate_list_values(field.as_ref(), &values)?;
 
-                let values = if *is_null { None } else { Some(values) };
+                let values = if *is_null {
+                    None
+                } else if values.is_empty() {
+                    Some(new_empty_array(field.data_type()))
+                } else {
+                    let values = ScalarValue::iter_to_array(values)?;
+                    Some(
+                        cast(&values, field.data_type())
+                            .map_err(DataFusionError::from)?,
+                    )
+                };
 
                 Self::List(values, field)
             }
@@ -673,8 +693,8 @@ impl TryFrom<&protobuf::ScalarValue> for ScalarValue {
 
                 Self::Dictionary(Box::new(index_type), Box::new(value))
             }
-            Value::BinaryValue(v) => Self::Binary(Some(v.clone())),
-            Value::LargeBinaryValue(v) => Self::LargeBinary(Some(v.clone())),
+            Value::BinaryValue(v) => Self::Binary(Some(v.as_slice().into())),
+            Value::LargeBinaryValue(v) => Self::LargeBinary(Some(v.as_slice().into())),
             Value::IntervalMonthDayNano(v) => Self::IntervalMonthDayNano(Some(
                 IntervalMonthDayNanoType::make_value(v.months, v.days, v.nanos),
             )),
@@ -701,7 +721,7 @@ impl TryFrom<&protobuf::ScalarValue> for ScalarValue {
                 Self::Struct(values, Box::new(fields))
             }
             Value::FixedSizeBinaryValue(v) => {
-                Self::FixedSizeBinary(v.length, Some(v.clone().values))
+                Self::FixedSizeBinary(v.length, Some(v.values.as_slice().into()))
             }
         })
     }
@@ -728,6 +748,7 @@ impl TryFrom<protobuf::WindowFrame> for WindowFrame {
             units,
             start_bound,
             end_bound,
+            exclude: WindowFrameExclude::NoOthers,
         })
     }
 }
@@ -787,6 +808,7 @@ impl From<protobuf::JoinType> for JoinType {
             protobuf::JoinType::Rightsemi => JoinType::RightSemi,
             protobuf::JoinType::Leftanti => JoinType::LeftAnti,
             protobuf::JoinType::Rightanti => JoinType::RightAnti,
+            protobuf::JoinType::Leftmark => JoinType::LeftMark,
         }
     }
 }
@@ -955,6 +977,16 @@ pub fn parse_expr(
         }
         ExprType::AggregateExpr(expr) => {
             let fun = parse_i32_to_aggregate_function(&expr.aggr_function)?;
+            let order_by = if expr.order_by.is_empty() {
+                None
+            } else {
+                Some(
+                    expr.order_by
+                        .iter()
+                        .map(|e| parse_expr(e, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            };
 
             Ok(Expr::AggregateFunction(expr::AggregateFunction::new(
                 fun,
@@ -964,6 +996,7 @@ pub fn parse_expr(
                     .collect::<Result<Vec<_>, _>>()?,
                 expr.distinct,
                 parse_optional_expr(&expr.filter, registry)?.map(Box::new),
+                order_by,
             )))
         }
         ExprType::Alias(alias) => Ok(Expr::Alias(
@@ -1270,13 +1303,25 @@ pub fn parse_expr(
                     parse_expr(&args[0], registry)?,
                     parse_expr(&args[1], registry)?,
                 )),
+                ScalarFunction::TryDivide => Ok(try_divide(
+                    parse_expr(&args[0], registry)?,
+                    parse_expr(&args[1], registry)?,
+                )),
+                ScalarFunction::TryToTimestamp => {
+                    Ok(try_to_timestamp(parse_expr(&args[0], registry)?))
+                }
                 _ => Err(proto_error(
                     "Protobuf deserialization error: Unsupported scalar function",
                 )),
             }
         }
-        ExprType::ScalarUdfExpr(protobuf::ScalarUdfExprNode { fun_name, args }) => {
+        ExprType::ScalarUdfExpr(protobuf::ScalarUdfExprNode {
+            fun_name,
+            args,
+            fingerprint,
+        }) => {
             let scalar_fn = registry.udf(fun_name.as_str())?;
+            check_fingerprint(fun_name, *fingerprint, udf_fingerprint(&scalar_fn))?;
             Ok(Expr::ScalarUDF {
                 fun: scalar_fn,
                 args: args
@@ -1287,6 +1332,7 @@ pub fn parse_expr(
         }
         ExprType::AggregateUdfExpr(pb) => {
             let agg_fn = registry.udaf(pb.fun_name.as_str())?;
+            check_fingerprint(&pb.fun_name, pb.fingerprint, udaf_fingerprint(&agg_fn))?;
 
             Ok(Expr::AggregateUDF {
                 fun: agg_fn,
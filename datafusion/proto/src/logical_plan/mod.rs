@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::common::{byte_to_string, proto_error, str_to_byte};
+use crate::function_registry::{FallbackFunctionRegistry, FunctionFactory};
 use crate::protobuf::logical_plan_node::LogicalPlanType::CustomScan;
 use crate::protobuf::CustomTableScanNode;
 use crate::{
@@ -45,10 +46,10 @@ use datafusion_expr::{
     logical_plan::{
         builder::project, Aggregate, CreateCatalog, CreateCatalogSchema,
         CreateExternalTable, CreateView, CrossJoin, Distinct, EmptyRelation, Extension,
-        Join, JoinConstraint, Limit, Prepare, Projection, Repartition, Sort,
+        Join, JoinConstraint, Limit, Prepare, Projection, Repartition, SharedCte, Sort,
         SubqueryAlias, TableScan, Values, Window,
     },
-    Expr, LogicalPlan, LogicalPlanBuilder,
+    DistinctOn, Expr, LogicalPlan, LogicalPlanBuilder, SetOperationAll, SetOperationType,
 };
 use prost::bytes::BufMut;
 use prost::Message;
@@ -121,6 +122,12 @@ pub trait LogicalExtensionCodec: Debug + Send + Sync {
         node: Arc<dyn TableProvider>,
         buf: &mut Vec<u8>,
     ) -> Result<(), DataFusionError>;
+
+    /// Factories used to create user defined functions that are not
+    /// registered with the [`SessionContext`] used for deserialization
+    fn function_factories(&self) -> Vec<Arc<dyn FunctionFactory>> {
+        vec![]
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -219,6 +226,8 @@ impl AsLogicalPlan for LogicalPlanNode {
         ctx: &SessionContext,
         extension_codec: &dyn LogicalExtensionCodec,
     ) -> Result<LogicalPlan, DataFusionError> {
+        let registry =
+            &FallbackFunctionRegistry::new(ctx, extension_codec.function_factories());
         let plan = self.logical_plan_type.as_ref().ok_or_else(|| {
             proto_error(format!(
                 "logical_plan::from_proto() Unsupported logical plan '{self:?}'"
@@ -241,7 +250,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                         .chunks_exact(n_cols)
                         .map(|r| {
                             r.iter()
-                                .map(|expr| from_proto::parse_expr(expr, ctx))
+                                .map(|expr| from_proto::parse_expr(expr, registry))
                                 .collect::<Result<Vec<_>, from_proto::Error>>()
                         })
                         .collect::<Result<Vec<_>, _>>()
@@ -255,7 +264,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let expr: Vec<Expr> = projection
                     .expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<_>, _>>()?;
 
                 let new_proj = project(input, expr)?;
@@ -276,7 +285,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let expr: Expr = selection
                     .expr
                     .as_ref()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .transpose()?
                     .ok_or_else(|| {
                         DataFusionError::Internal("expression required".to_string())
@@ -290,7 +299,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let window_expr = window
                     .window_expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<Expr>, _>>()?;
                 LogicalPlanBuilder::from(input).window(window_expr)?.build()
             }
@@ -300,12 +309,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let group_expr = aggregate
                     .group_expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<Expr>, _>>()?;
                 let aggr_expr = aggregate
                     .aggr_expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<Expr>, _>>()?;
                 LogicalPlanBuilder::from(input)
                     .aggregate(group_expr, aggr_expr)?
@@ -327,13 +336,13 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let filters = scan
                     .filters
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<_>, _>>()?;
 
                 let file_sort_order = scan
                     .file_sort_order
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<_>, _>>()?;
 
                 // Protobuf doesn't distinguish between "not present"
@@ -422,7 +431,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let filters = scan
                     .filters
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<_>, _>>()?;
                 let provider = extension_codec.try_decode_table_provider(
                     &scan.custom_table_data,
@@ -444,7 +453,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let sort_expr: Vec<Expr> = sort
                     .expr
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<Expr>, _>>()?;
                 LogicalPlanBuilder::from(input).sort(sort_expr)?.build()
             }
@@ -466,7 +475,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                     }) => Partitioning::Hash(
                         pb_hash_expr
                             .iter()
-                            .map(|expr| from_proto::parse_expr(expr, ctx))
+                            .map(|expr| from_proto::parse_expr(expr, registry))
                             .collect::<Result<Vec<_>, _>>()?,
                         partition_count as usize,
                     ),
@@ -607,12 +616,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let left_keys: Vec<Expr> = join
                     .left_join_key
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<_>, _>>()?;
                 let right_keys: Vec<Expr> = join
                     .right_join_key
                     .iter()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .collect::<Result<Vec<_>, _>>()?;
                 let join_type =
                     protobuf::JoinType::from_i32(join.join_type).ok_or_else(|| {
@@ -633,7 +642,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let filter: Option<Expr> = join
                     .filter
                     .as_ref()
-                    .map(|expr| from_proto::parse_expr(expr, ctx))
+                    .map(|expr| from_proto::parse_expr(expr, registry))
                     .map_or(Ok(None), |v| v.map(Some))?;
 
                 let builder = LogicalPlanBuilder::from(into_logical_plan!(
@@ -707,6 +716,45 @@ impl AsLogicalPlan for LogicalPlanNode {
                     into_logical_plan!(distinct.input, ctx, extension_codec)?;
                 LogicalPlanBuilder::from(input).distinct()?.build()
             }
+            LogicalPlanType::DistinctOn(distinct_on) => {
+                let input: LogicalPlan =
+                    into_logical_plan!(distinct_on.input, ctx, extension_codec)?;
+                let on_expr = distinct_on
+                    .on_expr
+                    .iter()
+                    .map(|expr| from_proto::parse_expr(expr, registry))
+                    .collect::<Result<Vec<Expr>, _>>()?;
+                let sort_expr = distinct_on
+                    .sort_expr
+                    .iter()
+                    .map(|expr| from_proto::parse_expr(expr, registry))
+                    .collect::<Result<Vec<Expr>, _>>()?;
+                let sort_expr = (!sort_expr.is_empty()).then_some(sort_expr);
+                let node = DistinctOn::try_new(on_expr, sort_expr, Arc::new(input))?;
+                Ok(LogicalPlan::Extension(Extension {
+                    node: Arc::new(node),
+                }))
+            }
+            LogicalPlanType::SetOperationAll(set_op) => {
+                let left = into_logical_plan!(set_op.left, ctx, extension_codec)?;
+                let right = into_logical_plan!(set_op.right, ctx, extension_codec)?;
+                let op = match protobuf::SetOperationType::from_i32(set_op.op) {
+                    Some(protobuf::SetOperationType::Intersect) => {
+                        SetOperationType::Intersect
+                    }
+                    Some(protobuf::SetOperationType::Except) => SetOperationType::Except,
+                    None => {
+                        return Err(proto_error(format!(
+                            "Received a SetOperationAllNode message with unknown SetOperationType {}",
+                            set_op.op
+                        )))
+                    }
+                };
+                let node = SetOperationAll::try_new(op, Arc::new(left), Arc::new(right))?;
+                Ok(LogicalPlan::Extension(Extension {
+                    node: Arc::new(node),
+                }))
+            }
             LogicalPlanType::ViewScan(scan) => {
                 let schema: Schema = convert_required!(scan.schema)?;
 
@@ -1064,6 +1112,14 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
+            LogicalPlan::SharedCte(SharedCte { input, .. }) => {
+                // the references to a common table expression are serialized
+                // as its plan, which is then executed for each of them
+                protobuf::LogicalPlanNode::try_from_logical_plan(
+                    input.as_ref(),
+                    extension_codec,
+                )
+            }
             LogicalPlan::Limit(Limit { input, skip, fetch }) => {
                 let input: protobuf::LogicalPlanNode =
                     protobuf::LogicalPlanNode::try_from_logical_plan(
@@ -1290,6 +1346,59 @@ impl AsLogicalPlan for LogicalPlanNode {
                 })
             }
             LogicalPlan::Extension(extension) => {
+                let node = extension.node.as_any();
+                if let Some(distinct_on) = node.downcast_ref::<DistinctOn>() {
+                    let input = protobuf::LogicalPlanNode::try_from_logical_plan(
+                        &distinct_on.input,
+                        extension_codec,
+                    )?;
+                    let on_expr = distinct_on
+                        .on_expr
+                        .iter()
+                        .map(|expr| expr.try_into())
+                        .collect::<Result<Vec<_>, to_proto::Error>>()?;
+                    let sort_expr = distinct_on
+                        .sort_expr
+                        .iter()
+                        .flatten()
+                        .map(|expr| expr.try_into())
+                        .collect::<Result<Vec<_>, to_proto::Error>>()?;
+                    return Ok(protobuf::LogicalPlanNode {
+                        logical_plan_type: Some(LogicalPlanType::DistinctOn(Box::new(
+                            protobuf::DistinctOnNode {
+                                on_expr,
+                                sort_expr,
+                                input: Some(Box::new(input)),
+                            },
+                        ))),
+                    });
+                }
+                if let Some(set_op) = node.downcast_ref::<SetOperationAll>() {
+                    let left = protobuf::LogicalPlanNode::try_from_logical_plan(
+                        &set_op.left,
+                        extension_codec,
+                    )?;
+                    let right = protobuf::LogicalPlanNode::try_from_logical_plan(
+                        &set_op.right,
+                        extension_codec,
+                    )?;
+                    let op = match set_op.op {
+                        SetOperationType::Intersect => {
+                            protobuf::SetOperationType::Intersect
+                        }
+                        SetOperationType::Except => protobuf::SetOperationType::Except,
+                    };
+                    return Ok(protobuf::LogicalPlanNode {
+                        logical_plan_type: Some(LogicalPlanType::SetOperationAll(
+                            Box::new(protobuf::SetOperationAllNode {
+                                op: op.into(),
+                                left: Some(Box::new(left)),
+                                right: Some(Box::new(right)),
+                            }),
+                        )),
+                    });
+                }
+
                 let mut buf: Vec<u8> = vec![];
                 extension_codec.try_encode(extension, &mut buf)?;
 
@@ -1342,6 +1451,18 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::DropView(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropView",
             )),
+            LogicalPlan::AnalyzeTable(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for AnalyzeTable",
+            )),
+            LogicalPlan::CreateFunction(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for CreateFunction",
+            )),
+            LogicalPlan::DropFunction(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for DropFunction",
+            )),
+            LogicalPlan::Transaction(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for Transaction",
+            )),
             LogicalPlan::SetVariable(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropView",
             )),
@@ -1355,7 +1476,9 @@ mod roundtrip_tests {
     use super::protobuf;
     use crate::bytes::{
         logical_plan_from_bytes, logical_plan_from_bytes_with_extension_codec,
-        logical_plan_to_bytes, logical_plan_to_bytes_with_extension_codec,
+        logical_plan_from_versioned_bytes, logical_plan_to_bytes,
+        logical_plan_to_bytes_with_extension_codec, logical_plan_to_versioned_bytes,
+        LOGICAL_PLAN_FORMAT_VERSION,
     };
     use crate::logical_plan::LogicalExtensionCodec;
     use arrow::datatypes::{Schema, SchemaRef};
@@ -1382,10 +1505,11 @@ mod roundtrip_tests {
     use datafusion_expr::{
         col, lit, Accumulator, AggregateFunction,
         BuiltinScalarFunction::{Sqrt, Substr},
-        Expr, LogicalPlan, Operator, Volatility,
+        Expr, LogicalPlan, LogicalPlanBuilder, Operator, Volatility,
     };
     use datafusion_expr::{
-        create_udaf, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction,
+        create_udaf, WindowFrame, WindowFrameBound, WindowFrameExclude, WindowFrameUnits,
+        WindowFunction,
     };
     use prost::Message;
     use std::any::Any;
@@ -1596,6 +1720,44 @@ mod roundtrip_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn roundtrip_logical_plan_distinct_on() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        ctx.register_csv("t1", "testdata/test.csv", CsvReadOptions::default())
+            .await?;
+        for sort_expr in [
+            None,
+            Some(vec![col("a").sort(true, true), col("b").sort(false, true)]),
+        ] {
+            let plan = ctx
+                .table("t1")
+                .await?
+                .distinct_on(vec![col("a")], vec![col("a"), col("b")], sort_expr)?
+                .into_optimized_plan()?;
+            let bytes = logical_plan_to_bytes(&plan)?;
+            let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+            assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_logical_plan_set_operation_all() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        ctx.register_csv("t1", "testdata/test.csv", CsvReadOptions::default())
+            .await?;
+        for query in [
+            "SELECT a, b FROM t1 INTERSECT ALL SELECT a, b FROM t1",
+            "SELECT a, b FROM t1 EXCEPT ALL SELECT a, b FROM t1",
+        ] {
+            let plan = ctx.sql(query).await?.into_optimized_plan()?;
+            let bytes = logical_plan_to_bytes(&plan)?;
+            let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+            assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn roundtrip_logical_plan_with_view_scan() -> Result<(), DataFusionError> {
         let ctx = SessionContext::new();
@@ -1613,6 +1775,75 @@ mod roundtrip_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn roundtrip_logical_plan_prepare() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        ctx.register_csv("t1", "testdata/test.csv", CsvReadOptions::default())
+            .await?;
+        let plan = ctx
+            .state()
+            .create_logical_plan(
+                "PREPARE my_plan(BIGINT, BIGINT) AS SELECT a, b FROM t1 WHERE a > $1 AND b < $2",
+            )
+            .await?;
+        assert!(matches!(plan, LogicalPlan::Prepare(_)));
+        let bytes = logical_plan_to_versioned_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_versioned_bytes(&bytes, &ctx)?;
+        assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_logical_plan_versioned_view() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        ctx.register_csv("t1", "testdata/test.csv", CsvReadOptions::default())
+            .await?;
+        let plan = ctx
+            .state()
+            .create_logical_plan("CREATE VIEW view_t1(a, b) AS SELECT a, b FROM t1")
+            .await?;
+        let bytes = logical_plan_to_versioned_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_versioned_bytes(&bytes, &ctx)?;
+        assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+        Ok(())
+    }
+
+    #[test]
+    fn versioned_logical_plan_format_is_stable() -> Result<(), DataFusionError> {
+        // Written by format version 1, this must remain readable by later
+        // releases. Do not regenerate these bytes when changing the proto
+        // definition, fix the compatibility break instead.
+        let bytes: &[u8] = &[
+            8, 1, 18, 59, 26, 57, 10, 42, 34, 40, 10, 11, 130, 1, 8, 8, 1, 18, 4, 26, 2,
+            56, 1, 18, 25, 34, 23, 10, 11, 10, 9, 10, 7, 99, 111, 108, 117, 109, 110, 49,
+            10, 4, 26, 2, 56, 0, 26, 2, 71, 116, 18, 11, 10, 9, 10, 7, 99, 111, 108, 117,
+            109, 110, 49,
+        ];
+        let expected = LogicalPlanBuilder::values(vec![vec![lit(1i64)]])?
+            .filter(col("column1").gt(lit(0i64)))?
+            .project(vec![col("column1")])?
+            .build()?;
+
+        let ctx = SessionContext::new();
+        let decoded = logical_plan_from_versioned_bytes(bytes, &ctx)?;
+        assert_eq!(format!("{expected:?}"), format!("{decoded:?}"));
+        Ok(())
+    }
+
+    #[test]
+    fn versioned_logical_plan_rejects_newer_version() {
+        let plan = protobuf::VersionedLogicalPlanNode {
+            version: LOGICAL_PLAN_FORMAT_VERSION + 1,
+            plan: None,
+        };
+        let ctx = SessionContext::new();
+        let err =
+            logical_plan_from_versioned_bytes(&plan.encode_to_vec(), &ctx).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unsupported logical plan format version 2"));
+    }
+
     pub mod proto {
         #[derive(Clone, PartialEq, ::prost::Message)]
         pub struct TopKPlanProto {
@@ -1782,59 +2013,6 @@ mod roundtrip_tests {
                 Some(vec![]),
                 Box::new(vec![Field::new("item", DataType::Int16, true)]),
             ),
-            // Should fail due to inconsistent types in the list
-            ScalarValue::new_list(
-                Some(vec![
-                    ScalarValue::Int16(None),
-                    ScalarValue::Float32(Some(32.0)),
-                ]),
-                DataType::List(new_box_field("item", DataType::Int16, true)),
-            ),
-            ScalarValue::new_list(
-                Some(vec![
-                    ScalarValue::Float32(None),
-                    ScalarValue::Float32(Some(32.0)),
-                ]),
-                DataType::List(new_box_field("item", DataType::Int16, true)),
-            ),
-            ScalarValue::new_list(
-                Some(vec![
-                    ScalarValue::Float32(None),
-                    ScalarValue::Float32(Some(32.0)),
-                ]),
-                DataType::Int16,
-            ),
-            ScalarValue::new_list(
-                Some(vec![
-                    ScalarValue::new_list(
-                        None,
-                        DataType::List(new_box_field("level2", DataType::Float32, true)),
-                    ),
-                    ScalarValue::new_list(
-                        Some(vec![
-                            ScalarValue::Float32(Some(-213.1)),
-                            ScalarValue::Float32(None),
-                            ScalarValue::Float32(Some(5.5)),
-                            ScalarValue::Float32(Some(2.0)),
-                            ScalarValue::Float32(Some(1.0)),
-                        ]),
-                        DataType::List(new_box_field("level2", DataType::Float32, true)),
-                    ),
-                    ScalarValue::new_list(
-                        None,
-                        DataType::List(new_box_field(
-                            "lists are typed inconsistently",
-                            DataType::Int16,
-                            true,
-                        )),
-                    ),
-                ]),
-                DataType::List(new_box_field(
-                    "level1",
-                    DataType::List(new_box_field("level2", DataType::Float32, true)),
-                    true,
-                )),
-            ),
         ];
 
         for test_case in should_fail_on_seralize.into_iter() {
@@ -1851,6 +2029,23 @@ mod roundtrip_tests {
                 );
             }
         }
+
+        // Should fail due to inconsistent types in the list
+        let field = Field::new("item", DataType::Int16, true);
+        let proto = super::protobuf::ScalarValue {
+            value: Some(super::protobuf::scalar_value::Value::ListValue(
+                super::protobuf::ScalarListValue {
+                    is_null: false,
+                    field: Some((&field).try_into().unwrap()),
+                    values: vec![
+                        (&ScalarValue::Int16(None)).try_into().unwrap(),
+                        (&ScalarValue::Float32(Some(32.0))).try_into().unwrap(),
+                    ],
+                },
+            )),
+        };
+        let res: Result<ScalarValue, _> = (&proto).try_into();
+        assert!(res.is_err(), "Inconsistent list unexpectedly deserialized");
     }
 
     #[test]
@@ -1869,7 +2064,7 @@ mod roundtrip_tests {
             ScalarValue::UInt64(None),
             ScalarValue::Utf8(None),
             ScalarValue::LargeUtf8(None),
-            ScalarValue::new_list(None, DataType::Boolean),
+            ScalarValue::new_list_from_array(None, DataType::Boolean),
             ScalarValue::Date32(None),
             ScalarValue::Boolean(Some(true)),
             ScalarValue::Boolean(Some(false)),
@@ -1905,8 +2100,8 @@ mod roundtrip_tests {
             ScalarValue::UInt32(Some(0)),
             ScalarValue::UInt64(Some(u64::MAX)),
             ScalarValue::UInt64(Some(0)),
-            ScalarValue::Utf8(Some(String::from("Test string   "))),
-            ScalarValue::LargeUtf8(Some(String::from("Test Large utf8"))),
+            ScalarValue::Utf8(Some("Test string   ".into())),
+            ScalarValue::LargeUtf8(Some("Test Large utf8".into())),
             ScalarValue::Date32(Some(0)),
             ScalarValue::Date32(Some(i32::MAX)),
             ScalarValue::Date32(None),
@@ -1967,10 +2162,11 @@ mod roundtrip_tests {
                     ScalarValue::Float32(Some(1.0)),
                 ]),
                 DataType::Float32,
-            ),
+            )
+            .unwrap(),
             ScalarValue::new_list(
                 Some(vec![
-                    ScalarValue::new_list(None, DataType::Float32),
+                    ScalarValue::new_list_from_array(None, DataType::Float32),
                     ScalarValue::new_list(
                         Some(vec![
                             ScalarValue::Float32(Some(-213.1)),
@@ -1980,10 +2176,12 @@ mod roundtrip_tests {
                             ScalarValue::Float32(Some(1.0)),
                         ]),
                         DataType::Float32,
-                    ),
+                    )
+                    .unwrap(),
                 ]),
                 DataType::List(new_box_field("item", DataType::Float32, true)),
-            ),
+            )
+            .unwrap(),
             ScalarValue::Dictionary(
                 Box::new(DataType::Int32),
                 Box::new(ScalarValue::Utf8(Some("foo".into()))),
@@ -1992,9 +2190,9 @@ mod roundtrip_tests {
                 Box::new(DataType::Int32),
                 Box::new(ScalarValue::Utf8(None)),
             ),
-            ScalarValue::Binary(Some(b"bar".to_vec())),
+            ScalarValue::Binary(Some(b"bar".as_slice().into())),
             ScalarValue::Binary(None),
-            ScalarValue::LargeBinary(Some(b"bar".to_vec())),
+            ScalarValue::LargeBinary(Some(b"bar".as_slice().into())),
             ScalarValue::LargeBinary(None),
             ScalarValue::Struct(
                 Some(vec![
@@ -2015,7 +2213,7 @@ mod roundtrip_tests {
             ),
             ScalarValue::FixedSizeBinary(
                 b"bar".to_vec().len() as i32,
-                Some(b"bar".to_vec()),
+                Some(b"bar".as_slice().into()),
             ),
             ScalarValue::FixedSizeBinary(0, None),
             ScalarValue::FixedSizeBinary(5, None),
@@ -2463,6 +2661,7 @@ mod roundtrip_tests {
             vec![col("bananas")],
             false,
             None,
+            None,
         ));
         let ctx = SessionContext::new();
         roundtrip_expr_test(test_expr, ctx);
@@ -2475,6 +2674,20 @@ mod roundtrip_tests {
             vec![col("bananas")],
             true,
             None,
+            None,
+        ));
+        let ctx = SessionContext::new();
+        roundtrip_expr_test(test_expr, ctx);
+    }
+
+    #[test]
+    fn roundtrip_array_agg_order_by() {
+        let test_expr = Expr::AggregateFunction(expr::AggregateFunction::new(
+            AggregateFunction::ArrayAgg,
+            vec![col("bananas")],
+            false,
+            None,
+            Some(vec![col("apples").sort(false, true)]),
         ));
         let ctx = SessionContext::new();
         roundtrip_expr_test(test_expr, ctx);
@@ -2487,6 +2700,7 @@ mod roundtrip_tests {
             vec![col("bananas"), lit(0.42_f32)],
             false,
             None,
+            None,
         ));
 
         let ctx = SessionContext::new();
@@ -2654,6 +2868,7 @@ mod roundtrip_tests {
             units: WindowFrameUnits::Range,
             start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(Some(2))),
             end_bound: WindowFrameBound::Following(ScalarValue::UInt64(Some(2))),
+            exclude: WindowFrameExclude::NoOthers,
         };
 
         let test_expr3 = Expr::WindowFunction(expr::WindowFunction::new(
@@ -2671,6 +2886,7 @@ mod roundtrip_tests {
             units: WindowFrameUnits::Rows,
             start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(Some(2))),
             end_bound: WindowFrameBound::Following(ScalarValue::UInt64(Some(2))),
+            exclude: WindowFrameExclude::NoOthers,
         };
 
         let test_expr4 = Expr::WindowFunction(expr::WindowFunction::new(
@@ -19,15 +19,16 @@
 //! DataFusion logical plans to be serialized and transmitted between
 //! processes.
 
+use crate::function_registry::{udaf_fingerprint, udf_fingerprint};
 use crate::protobuf::{
     self,
     arrow_type::ArrowTypeEnum,
     plan_type::PlanTypeEnum::{
-        FinalLogicalPlan, FinalPhysicalPlan, InitialLogicalPlan, InitialPhysicalPlan,
-        OptimizedLogicalPlan, OptimizedPhysicalPlan,
+        AnalyzedLogicalPlan, FinalLogicalPlan, FinalPhysicalPlan, InitialLogicalPlan,
+        InitialPhysicalPlan, OptimizedLogicalPlan, OptimizedPhysicalPlan,
     },
-    CubeNode, EmptyMessage, GroupingSetNode, LogicalExprList, OptimizedLogicalPlanType,
-    OptimizedPhysicalPlanType, PlaceholderNode, RollupNode,
+    AnalyzedLogicalPlanType, CubeNode, EmptyMessage, GroupingSetNode, LogicalExprList,
+    OptimizedLogicalPlanType, OptimizedPhysicalPlanType, PlaceholderNode, RollupNode,
 };
 use arrow::datatypes::{
     DataType, Field, IntervalMonthDayNanoType, IntervalUnit, Schema, SchemaRef, TimeUnit,
@@ -40,7 +41,7 @@ use datafusion_expr::expr::{
 use datafusion_expr::{
     logical_plan::PlanType, logical_plan::StringifiedPlan, AggregateFunction,
     BuiltInWindowFunction, BuiltinScalarFunction, Expr, JoinConstraint, JoinType,
-    WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction,
+    WindowFrame, WindowFrameBound, WindowFrameExclude, WindowFrameUnits, WindowFunction,
 };
 
 #[derive(Debug)]
@@ -310,6 +311,13 @@ impl From<&StringifiedPlan> for protobuf::StringifiedPlan {
                 PlanType::InitialLogicalPlan => Some(protobuf::PlanType {
                     plan_type_enum: Some(InitialLogicalPlan(EmptyMessage {})),
                 }),
+                PlanType::AnalyzedLogicalPlan { analyzer_name } => {
+                    Some(protobuf::PlanType {
+                        plan_type_enum: Some(AnalyzedLogicalPlan(
+                            AnalyzedLogicalPlanType { analyzer_name },
+                        )),
+                    })
+                }
                 PlanType::OptimizedLogicalPlan { optimizer_name } => {
                     Some(protobuf::PlanType {
                         plan_type_enum: Some(OptimizedLogicalPlan(
@@ -421,6 +429,12 @@ impl TryFrom<&WindowFrame> for protobuf::WindowFrame {
     type Error = Error;
 
     fn try_from(window: &WindowFrame) -> Result<Self, Self::Error> {
+        if window.exclude != WindowFrameExclude::NoOthers {
+            return Err(Error::NotImplemented(format!(
+                "Proto serialization of {}",
+                window.exclude
+            )));
+        }
         Ok(Self {
             window_frame_units: protobuf::WindowFrameUnits::from(window.units).into(),
             start_bound: Some((&window.start_bound).try_into()?),
@@ -582,7 +596,8 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                 ref fun,
                 ref args,
                 ref distinct,
-                ref filter
+                ref filter,
+                ref order_by,
             }) => {
                 let aggr_function = match fun {
                     AggregateFunction::ApproxDistinct => {
@@ -635,6 +650,13 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                         Some(e) => Some(Box::new(e.as_ref().try_into()?)),
                         None => None,
                     },
+                    order_by: match order_by {
+                        Some(e) => e
+                            .iter()
+                            .map(|expr| expr.try_into())
+                            .collect::<Result<Vec<_>, _>>()?,
+                        None => vec![],
+                    },
                 };
                 Self {
                     expr_type: Some(ExprType::AggregateExpr(Box::new(aggregate_expr))),
@@ -663,6 +685,7 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                         .iter()
                         .map(|expr| expr.try_into())
                         .collect::<Result<Vec<_>, Error>>()?,
+                    fingerprint: udf_fingerprint(fun),
                 })),
             },
             Expr::AggregateUDF { fun, args, filter } => {
@@ -678,6 +701,7 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                                 Some(e) => Some(Box::new(e.as_ref().try_into()?)),
                                 None => None,
                             },
+                            fingerprint: udaf_fingerprint(fun),
                         },
                         ))),
                 }
@@ -956,19 +980,20 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
             }
             scalar::ScalarValue::Utf8(val) => {
                 create_proto_scalar(val.as_ref(), &data_type, |s| {
-                    Value::Utf8Value(s.to_owned())
+                    Value::Utf8Value(s.to_string())
                 })
             }
             scalar::ScalarValue::LargeUtf8(val) => {
                 create_proto_scalar(val.as_ref(), &data_type, |s| {
-                    Value::LargeUtf8Value(s.to_owned())
+                    Value::LargeUtf8Value(s.to_string())
                 })
             }
             scalar::ScalarValue::List(values, boxed_field) => {
                 let is_null = values.is_none();
 
                 let values = if let Some(values) = values.as_ref() {
-                    values
+                    ScalarValue::convert_array_to_scalar_vec(values)
+                        .map_err(|_| Error::InvalidScalarValue(val.clone()))?
                         .iter()
                         .map(|v| v.try_into())
                         .collect::<Result<Vec<protobuf::ScalarValue>, _>>()?
@@ -1074,18 +1099,18 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
 
             scalar::ScalarValue::Binary(val) => {
                 create_proto_scalar(val.as_ref(), &data_type, |s| {
-                    Value::BinaryValue(s.to_owned())
+                    Value::BinaryValue(s.to_vec())
                 })
             }
             scalar::ScalarValue::LargeBinary(val) => {
                 create_proto_scalar(val.as_ref(), &data_type, |s| {
-                    Value::LargeBinaryValue(s.to_owned())
+                    Value::LargeBinaryValue(s.to_vec())
                 })
             }
             scalar::ScalarValue::FixedSizeBinary(length, val) => {
                 create_proto_scalar(val.as_ref(), &data_type, |s| {
                     Value::FixedSizeBinaryValue(protobuf::ScalarFixedSizeBinary {
-                        values: s.to_owned(),
+                        values: s.to_vec(),
                         length: *length,
                     })
                 })
@@ -1152,6 +1177,19 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
                 Ok(protobuf::ScalarValue { value: Some(value) })
             }
 
+            datafusion::scalar::ScalarValue::DurationSecond(v)
+            | datafusion::scalar::ScalarValue::DurationMillisecond(v)
+            | datafusion::scalar::ScalarValue::DurationMicrosecond(v)
+            | datafusion::scalar::ScalarValue::DurationNanosecond(v) => match v {
+                // only null durations can be encoded, as a typed null
+                None => Ok(protobuf::ScalarValue {
+                    value: Some(Value::NullValue((&data_type).try_into()?)),
+                }),
+                Some(_) => Err(Error::NotImplemented(format!(
+                    "Serializing the duration {val:?} is not supported"
+                ))),
+            },
+
             datafusion::scalar::ScalarValue::Struct(values, fields) => {
                 // encode null as empty field values list
                 let field_values = if let Some(values) = values {
@@ -1272,6 +1310,8 @@ impl TryFrom<&BuiltinScalarFunction> for protobuf::ScalarFunction {
             BuiltinScalarFunction::FromUnixtime => Self::FromUnixtime,
             BuiltinScalarFunction::Atan2 => Self::Atan2,
             BuiltinScalarFunction::ArrowTypeof => Self::ArrowTypeof,
+            BuiltinScalarFunction::TryDivide => Self::TryDivide,
+            BuiltinScalarFunction::TryToTimestamp => Self::TryToTimestamp,
         };
 
         Ok(scalar_function)
@@ -1340,6 +1380,7 @@ impl From<JoinType> for protobuf::JoinType {
             JoinType::RightSemi => protobuf::JoinType::Rightsemi,
             JoinType::LeftAnti => protobuf::JoinType::Leftanti,
             JoinType::RightAnti => protobuf::JoinType::Rightanti,
+            JoinType::LeftMark => protobuf::JoinType::Leftmark,
         }
     }
 }
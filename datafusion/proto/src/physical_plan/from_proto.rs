@@ -27,10 +27,12 @@ use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::execution::context::ExecutionProps;
 use datafusion::execution::FunctionRegistry;
 use datafusion::logical_expr::window_function::WindowFunction;
+use datafusion::logical_expr::WindowFrame;
 use datafusion::physical_expr::expressions::DateTimeIntervalExpr;
 use datafusion::physical_expr::{PhysicalSortExpr, ScalarFunctionExpr};
 use datafusion::physical_plan::expressions::LikeExpr;
 use datafusion::physical_plan::file_format::FileScanConfig;
+use datafusion::physical_plan::windows::create_window_expr;
 use datafusion::physical_plan::{
     expressions::{
         BinaryExpr, CaseExpr, CastExpr, Column, InListExpr, IsNotNullExpr, IsNullExpr,
@@ -38,19 +40,22 @@ use datafusion::physical_plan::{
     },
     functions, Partitioning,
 };
-use datafusion::physical_plan::{ColumnStatistics, PhysicalExpr, Statistics};
-use datafusion_common::DataFusionError;
+use datafusion::physical_plan::{
+    ColumnStatistics, PhysicalExpr, Precision, Statistics, WindowExpr,
+};
+use datafusion_common::{DataFusionError, ScalarValue};
 use object_store::path::Path;
 use object_store::ObjectMeta;
 use std::convert::{TryFrom, TryInto};
-use std::ops::Deref;
 use std::sync::Arc;
 
 use crate::common::proto_error;
 use crate::convert_required;
+use crate::function_registry::{check_fingerprint, check_udf_call, udaf_fingerprint};
 use crate::logical_plan;
+use crate::physical_plan::PhysicalExtensionCodec;
 use crate::protobuf::physical_expr_node::ExprType;
-use datafusion::physical_plan::joins::utils::JoinSide;
+use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter, JoinSide};
 use datafusion::physical_plan::sorts::sort::SortOptions;
 
 impl From<&protobuf::PhysicalColumn> for Column {
@@ -63,6 +68,7 @@ pub(crate) fn parse_physical_expr(
     proto: &protobuf::PhysicalExprNode,
     registry: &dyn FunctionRegistry,
     input_schema: &Schema,
+    codec: &dyn PhysicalExtensionCodec,
 ) -> Result<Arc<dyn PhysicalExpr>, DataFusionError> {
     let expr_type = proto
         .expr_type
@@ -81,6 +87,7 @@ pub(crate) fn parse_physical_expr(
                 registry,
                 "left",
                 input_schema,
+                codec,
             )?,
             logical_plan::from_proto::from_proto_binary_op(&binary_expr.op)?,
             parse_required_physical_box_expr(
@@ -88,12 +95,25 @@ pub(crate) fn parse_physical_expr(
                 registry,
                 "right",
                 input_schema,
+                codec,
             )?,
         )),
         ExprType::DateTimeIntervalExpr(expr) => Arc::new(DateTimeIntervalExpr::try_new(
-            parse_required_physical_box_expr(&expr.l, registry, "left", input_schema)?,
+            parse_required_physical_box_expr(
+                &expr.l,
+                registry,
+                "left",
+                input_schema,
+                codec,
+            )?,
             logical_plan::from_proto::from_proto_binary_op(&expr.op)?,
-            parse_required_physical_box_expr(&expr.r, registry, "right", input_schema)?,
+            parse_required_physical_box_expr(
+                &expr.r,
+                registry,
+                "right",
+                input_schema,
+                codec,
+            )?,
             input_schema,
         )?),
         ExprType::AggregateExpr(_) => {
@@ -111,26 +131,51 @@ pub(crate) fn parse_physical_expr(
                 "Cannot convert sort expr node to physical expression".to_owned(),
             ));
         }
-        ExprType::IsNullExpr(e) => Arc::new(IsNullExpr::new(
-            parse_required_physical_box_expr(&e.expr, registry, "expr", input_schema)?,
-        )),
-        ExprType::IsNotNullExpr(e) => Arc::new(IsNotNullExpr::new(
-            parse_required_physical_box_expr(&e.expr, registry, "expr", input_schema)?,
-        )),
+        ExprType::IsNullExpr(e) => {
+            Arc::new(IsNullExpr::new(parse_required_physical_box_expr(
+                &e.expr,
+                registry,
+                "expr",
+                input_schema,
+                codec,
+            )?))
+        }
+        ExprType::IsNotNullExpr(e) => {
+            Arc::new(IsNotNullExpr::new(parse_required_physical_box_expr(
+                &e.expr,
+                registry,
+                "expr",
+                input_schema,
+                codec,
+            )?))
+        }
         ExprType::NotExpr(e) => Arc::new(NotExpr::new(parse_required_physical_box_expr(
             &e.expr,
             registry,
             "expr",
             input_schema,
+            codec,
         )?)),
-        ExprType::Negative(e) => Arc::new(NegativeExpr::new(
-            parse_required_physical_box_expr(&e.expr, registry, "expr", input_schema)?,
-        )),
+        ExprType::Negative(e) => {
+            Arc::new(NegativeExpr::new(parse_required_physical_box_expr(
+                &e.expr,
+                registry,
+                "expr",
+                input_schema,
+                codec,
+            )?))
+        }
         ExprType::InList(e) => Arc::new(InListExpr::new(
-            parse_required_physical_box_expr(&e.expr, registry, "expr", input_schema)?,
+            parse_required_physical_box_expr(
+                &e.expr,
+                registry,
+                "expr",
+                input_schema,
+                codec,
+            )?,
             e.list
                 .iter()
-                .map(|x| parse_physical_expr(x, registry, input_schema))
+                .map(|x| parse_physical_expr(x, registry, input_schema, codec))
                 .collect::<Result<Vec<_>, _>>()?,
             e.negated,
             input_schema,
@@ -138,7 +183,7 @@ pub(crate) fn parse_physical_expr(
         ExprType::Case(e) => Arc::new(CaseExpr::try_new(
             e.expr
                 .as_ref()
-                .map(|e| parse_physical_expr(e.as_ref(), registry, input_schema))
+                .map(|e| parse_physical_expr(e.as_ref(), registry, input_schema, codec))
                 .transpose()?,
             e.when_then_expr
                 .iter()
@@ -149,28 +194,42 @@ pub(crate) fn parse_physical_expr(
                             registry,
                             "when_expr",
                             input_schema,
+                            codec,
                         )?,
                         parse_required_physical_expr(
                             e.then_expr.as_ref(),
                             registry,
                             "then_expr",
                             input_schema,
+                            codec,
                         )?,
                     ))
                 })
                 .collect::<Result<Vec<_>, DataFusionError>>()?,
             e.else_expr
                 .as_ref()
-                .map(|e| parse_physical_expr(e.as_ref(), registry, input_schema))
+                .map(|e| parse_physical_expr(e.as_ref(), registry, input_schema, codec))
                 .transpose()?,
         )?),
         ExprType::Cast(e) => Arc::new(CastExpr::new(
-            parse_required_physical_box_expr(&e.expr, registry, "expr", input_schema)?,
+            parse_required_physical_box_expr(
+                &e.expr,
+                registry,
+                "expr",
+                input_schema,
+                codec,
+            )?,
             convert_required!(e.arrow_type)?,
             DEFAULT_DATAFUSION_CAST_OPTIONS,
         )),
         ExprType::TryCast(e) => Arc::new(TryCastExpr::new(
-            parse_required_physical_box_expr(&e.expr, registry, "expr", input_schema)?,
+            parse_required_physical_box_expr(
+                &e.expr,
+                registry,
+                "expr",
+                input_schema,
+                codec,
+            )?,
             convert_required!(e.arrow_type)?,
         )),
         ExprType::ScalarFunction(e) => {
@@ -184,7 +243,7 @@ pub(crate) fn parse_physical_expr(
             let args = e
                 .args
                 .iter()
-                .map(|x| parse_physical_expr(x, registry, input_schema))
+                .map(|x| parse_physical_expr(x, registry, input_schema, codec))
                 .collect::<Result<Vec<_>, _>>()?;
 
             // TODO Do not create new the ExecutionProps
@@ -203,20 +262,34 @@ pub(crate) fn parse_physical_expr(
             ))
         }
         ExprType::ScalarUdf(e) => {
-            let scalar_fun = registry.udf(e.name.as_str())?.deref().clone().fun;
+            let udf = if e.fun_definition.is_empty() {
+                registry.udf(e.name.as_str())?
+            } else {
+                codec.try_decode_udf(&e.name, &e.fun_definition)?
+            };
 
             let args = e
                 .args
                 .iter()
-                .map(|x| parse_physical_expr(x, registry, input_schema))
+                .map(|x| parse_physical_expr(x, registry, input_schema, codec))
                 .collect::<Result<Vec<_>, _>>()?;
-
-            Arc::new(ScalarFunctionExpr::new(
-                e.name.as_str(),
-                scalar_fun,
-                args,
-                &convert_required!(e.return_type)?,
-            ))
+            let arg_types = args
+                .iter()
+                .map(|arg| arg.data_type(input_schema))
+                .collect::<Result<Vec<_>, _>>()?;
+            let return_type = convert_required!(e.return_type)?;
+            check_udf_call(&udf, &arg_types, &return_type)?;
+
+            Arc::new(
+                ScalarFunctionExpr::new(
+                    e.name.as_str(),
+                    udf.fun.clone(),
+                    args,
+                    &return_type,
+                )
+                .with_monotonic_argument(udf.monotonic_argument)
+                .with_udf(udf),
+            )
         }
         ExprType::LikeExpr(like_expr) => Arc::new(LikeExpr::new(
             like_expr.negated,
@@ -226,12 +299,14 @@ pub(crate) fn parse_physical_expr(
                 registry,
                 "expr",
                 input_schema,
+                codec,
             )?,
             parse_required_physical_box_expr(
                 &like_expr.pattern,
                 registry,
                 "pattern",
                 input_schema,
+                codec,
             )?,
         )),
     };
@@ -244,9 +319,10 @@ fn parse_required_physical_box_expr(
     registry: &dyn FunctionRegistry,
     field: &str,
     input_schema: &Schema,
+    codec: &dyn PhysicalExtensionCodec,
 ) -> Result<Arc<dyn PhysicalExpr>, DataFusionError> {
     expr.as_ref()
-        .map(|e| parse_physical_expr(e.as_ref(), registry, input_schema))
+        .map(|e| parse_physical_expr(e.as_ref(), registry, input_schema, codec))
         .transpose()?
         .ok_or_else(|| {
             DataFusionError::Internal(format!("Missing required field {field:?}"))
@@ -258,9 +334,10 @@ fn parse_required_physical_expr(
     registry: &dyn FunctionRegistry,
     field: &str,
     input_schema: &Schema,
+    codec: &dyn PhysicalExtensionCodec,
 ) -> Result<Arc<dyn PhysicalExpr>, DataFusionError> {
     expr.as_ref()
-        .map(|e| parse_physical_expr(e, registry, input_schema))
+        .map(|e| parse_physical_expr(e, registry, input_schema, codec))
         .transpose()?
         .ok_or_else(|| {
             DataFusionError::Internal(format!("Missing required field {field:?}"))
@@ -293,21 +370,146 @@ impl TryFrom<&protobuf::physical_window_expr_node::WindowFunction> for WindowFun
 
                 Ok(WindowFunction::BuiltInWindowFunction(f.into()))
             }
+            protobuf::physical_window_expr_node::WindowFunction::UserDefinedAggrFunction(
+                name,
+            ) => Err(proto_error(format!(
+                "Window user defined aggregate function {name} requires a FunctionRegistry, use parse_physical_window_expr instead"
+            ))),
         }
     }
 }
 
+pub(crate) fn parse_physical_sort_expr(
+    proto: &protobuf::PhysicalSortExprNode,
+    registry: &dyn FunctionRegistry,
+    input_schema: &Schema,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<PhysicalSortExpr, DataFusionError> {
+    let expr = parse_required_physical_box_expr(
+        &proto.expr,
+        registry,
+        "expr",
+        input_schema,
+        codec,
+    )?;
+    Ok(PhysicalSortExpr {
+        expr,
+        options: SortOptions {
+            descending: !proto.asc,
+            nulls_first: proto.nulls_first,
+        },
+    })
+}
+
+pub(crate) fn parse_physical_window_expr(
+    proto: &protobuf::PhysicalWindowExprNode,
+    name: &str,
+    registry: &dyn FunctionRegistry,
+    input_schema: &Schema,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<Arc<dyn WindowExpr>, DataFusionError> {
+    let window_function = match proto.window_function.as_ref() {
+        Some(
+            protobuf::physical_window_expr_node::WindowFunction::UserDefinedAggrFunction(
+                udaf_name,
+            ),
+        ) => {
+            let udaf = if proto.fun_definition.is_empty() {
+                registry.udaf(udaf_name)?
+            } else {
+                codec.try_decode_udaf(udaf_name, &proto.fun_definition)?
+            };
+            check_fingerprint(udaf_name, proto.fingerprint, udaf_fingerprint(&udaf))?;
+            WindowFunction::AggregateUDF(udaf)
+        }
+        Some(window_function) => window_function.try_into()?,
+        None => return Err(proto_error("Missing required field window_function")),
+    };
+
+    let args = proto
+        .args
+        .iter()
+        .map(|e| parse_physical_expr(e, registry, input_schema, codec))
+        .collect::<Result<Vec<_>, _>>()?;
+    let partition_by = proto
+        .partition_by
+        .iter()
+        .map(|e| parse_physical_expr(e, registry, input_schema, codec))
+        .collect::<Result<Vec<_>, _>>()?;
+    let order_by = proto
+        .order_by
+        .iter()
+        .map(|o| parse_physical_sort_expr(o, registry, input_schema, codec))
+        .collect::<Result<Vec<_>, _>>()?;
+    let window_frame = match proto.window_frame.as_ref() {
+        Some(window_frame) => window_frame.clone().try_into()?,
+        None => WindowFrame::new(!order_by.is_empty()),
+    };
+
+    create_window_expr(
+        &window_function,
+        name.to_owned(),
+        &args,
+        &partition_by,
+        &order_by,
+        Arc::new(window_frame),
+        input_schema,
+    )
+}
+
+pub(crate) fn parse_protobuf_join_filter(
+    proto: &protobuf::JoinFilter,
+    registry: &dyn FunctionRegistry,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<JoinFilter, DataFusionError> {
+    let schema = proto
+        .schema
+        .as_ref()
+        .ok_or_else(|| proto_error("Missing JoinFilter schema"))?
+        .try_into()?;
+
+    let expression = parse_physical_expr(
+        proto
+            .expression
+            .as_ref()
+            .ok_or_else(|| proto_error("Unexpected empty filter expression"))?,
+        registry,
+        &schema,
+        codec,
+    )?;
+    let column_indices = proto
+        .column_indices
+        .iter()
+        .map(|i| {
+            let side = protobuf::JoinSide::from_i32(i.side).ok_or_else(|| {
+                proto_error(format!(
+                    "Received a JoinFilter message with unknown JoinSide {}",
+                    i.side
+                ))
+            })?;
+
+            Ok(ColumnIndex {
+                index: i.index as usize,
+                side: side.into(),
+            })
+        })
+        .collect::<Result<Vec<_>, DataFusionError>>()?;
+
+    Ok(JoinFilter::new(expression, column_indices, schema))
+}
+
 pub fn parse_protobuf_hash_partitioning(
     partitioning: Option<&protobuf::PhysicalHashRepartition>,
     registry: &dyn FunctionRegistry,
     input_schema: &Schema,
+    codec: &dyn PhysicalExtensionCodec,
 ) -> Result<Option<Partitioning>, DataFusionError> {
     match partitioning {
         Some(hash_part) => {
             let expr = hash_part
                 .hash_expr
                 .iter()
-                .map(|e| parse_physical_expr(e, registry, input_schema))
+                .map(|e| parse_physical_expr(e, registry, input_schema, codec))
                 .collect::<Result<Vec<Arc<dyn PhysicalExpr>>, _>>()?;
 
             Ok(Some(Partitioning::Hash(
@@ -322,6 +524,7 @@ pub fn parse_protobuf_hash_partitioning(
 pub fn parse_protobuf_file_scan_config(
     proto: &protobuf::FileScanExecConf,
     registry: &dyn FunctionRegistry,
+    codec: &dyn PhysicalExtensionCodec,
 ) -> Result<FileScanConfig, DataFusionError> {
     let schema: Arc<Schema> = Arc::new(convert_required!(proto.schema)?);
     let projection = proto
@@ -362,20 +565,7 @@ pub fn parse_protobuf_file_scan_config(
     let output_ordering = proto
         .output_ordering
         .iter()
-        .map(|o| {
-            let expr = o
-                .expr
-                .as_ref()
-                .map(|e| parse_physical_expr(e.as_ref(), registry, &schema))
-                .unwrap()?;
-            Ok(PhysicalSortExpr {
-                expr,
-                options: SortOptions {
-                    descending: !o.asc,
-                    nulls_first: o.nulls_first,
-                },
-            })
-        })
+        .map(|o| parse_physical_sort_expr(o, registry, &schema, codec))
         .collect::<Result<Vec<PhysicalSortExpr>, DataFusionError>>()?;
     let output_ordering = if output_ordering.is_empty() {
         None
@@ -413,6 +603,7 @@ impl TryFrom<&protobuf::PartitionedFile> for PartitionedFile {
                 .collect::<Result<Vec<_>, _>>()?,
             range: val.range.as_ref().map(|v| v.try_into()).transpose()?,
             extensions: None,
+            statistics: val.statistics.as_ref().map(|v| v.try_into()).transpose()?,
         })
     }
 }
@@ -439,14 +630,63 @@ impl TryFrom<&protobuf::FileGroup> for Vec<PartitionedFile> {
     }
 }
 
-impl From<&protobuf::ColumnStats> for ColumnStatistics {
-    fn from(cs: &protobuf::ColumnStats) -> ColumnStatistics {
-        ColumnStatistics {
-            null_count: Some(cs.null_count as usize),
-            max_value: cs.max_value.as_ref().map(|m| m.try_into().unwrap()),
-            min_value: cs.min_value.as_ref().map(|m| m.try_into().unwrap()),
-            distinct_count: Some(cs.distinct_count as usize),
-        }
+impl TryFrom<&protobuf::Precision> for Precision<ScalarValue> {
+    type Error = DataFusionError;
+
+    fn try_from(s: &protobuf::Precision) -> Result<Self, Self::Error> {
+        let value = s.val.as_ref().map(|v| v.try_into()).transpose()?;
+        Ok(match (s.precision_info(), value) {
+            (protobuf::PrecisionInfo::Exact, Some(v)) => Precision::Exact(v),
+            (protobuf::PrecisionInfo::Inexact, Some(v)) => Precision::Inexact(v),
+            _ => Precision::Absent,
+        })
+    }
+}
+
+impl TryFrom<&protobuf::Precision> for Precision<usize> {
+    type Error = DataFusionError;
+
+    fn try_from(s: &protobuf::Precision) -> Result<Self, Self::Error> {
+        let count = |v: ScalarValue| match v {
+            ScalarValue::UInt64(Some(n)) => Ok(n as usize),
+            other => Err(proto_error(format!(
+                "Expected a UInt64 count in statistics, got {other:?}"
+            ))),
+        };
+        let value: Precision<ScalarValue> = s.try_into()?;
+        Ok(match value {
+            Precision::Exact(v) => Precision::Exact(count(v)?),
+            Precision::Inexact(v) => Precision::Inexact(count(v)?),
+            Precision::Absent => Precision::Absent,
+        })
+    }
+}
+
+/// Converts an optional protobuf [`Precision`](protobuf::Precision), treating
+/// a missing message as [`Precision::Absent`]
+fn parse_precision<T>(
+    precision: &Option<protobuf::Precision>,
+) -> Result<Precision<T>, DataFusionError>
+where
+    T: std::fmt::Debug + Clone + PartialEq + Eq + PartialOrd,
+    for<'a> Precision<T>: TryFrom<&'a protobuf::Precision, Error = DataFusionError>,
+{
+    match precision {
+        Some(p) => p.try_into(),
+        None => Ok(Precision::Absent),
+    }
+}
+
+impl TryFrom<&protobuf::ColumnStats> for ColumnStatistics {
+    type Error = DataFusionError;
+
+    fn try_from(cs: &protobuf::ColumnStats) -> Result<Self, Self::Error> {
+        Ok(ColumnStatistics {
+            null_count: parse_precision(&cs.null_count)?,
+            max_value: parse_precision(&cs.max_value)?,
+            min_value: parse_precision(&cs.min_value)?,
+            distinct_count: parse_precision(&cs.distinct_count)?,
+        })
     }
 }
 
@@ -463,28 +703,14 @@ impl TryFrom<&protobuf::Statistics> for Statistics {
     type Error = DataFusionError;
 
     fn try_from(s: &protobuf::Statistics) -> Result<Self, Self::Error> {
-        // Keep it sync with Statistics::to_proto
-        let none_value = -1_i64;
-        let column_statistics =
-            s.column_stats.iter().map(|s| s.into()).collect::<Vec<_>>();
         Ok(Statistics {
-            num_rows: if s.num_rows == none_value {
-                None
-            } else {
-                Some(s.num_rows as usize)
-            },
-            total_byte_size: if s.total_byte_size == none_value {
-                None
-            } else {
-                Some(s.total_byte_size as usize)
-            },
-            // No column statistic (None) is encoded with empty array
-            column_statistics: if column_statistics.is_empty() {
-                None
-            } else {
-                Some(column_statistics)
-            },
-            is_exact: s.is_exact,
+            num_rows: parse_precision(&s.num_rows)?,
+            total_byte_size: parse_precision(&s.total_byte_size)?,
+            column_statistics: s
+                .column_stats
+                .iter()
+                .map(|s| s.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
         })
     }
 }
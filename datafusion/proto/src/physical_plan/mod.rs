@@ -17,33 +17,43 @@
 
 use std::convert::TryInto;
 use std::fmt::Debug;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use datafusion::arrow::compute::SortOptions;
-use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
 use datafusion::datasource::file_format::file_type::FileCompressionType;
 use datafusion::execution::runtime_env::RuntimeEnv;
 use datafusion::execution::FunctionRegistry;
-use datafusion::logical_expr::WindowFrame;
+use datafusion::logical_expr::{AggregateUDF, ScalarUDF, SetOperationType};
 use datafusion::physical_plan::aggregates::{create_aggregate_expr, AggregateMode};
 use datafusion::physical_plan::aggregates::{AggregateExec, PhysicalGroupBy};
+use datafusion::physical_plan::analyze::AnalyzeExec;
 use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::distinct_on::DistinctOnExec;
 use datafusion::physical_plan::empty::EmptyExec;
 use datafusion::physical_plan::explain::ExplainExec;
 use datafusion::physical_plan::expressions::{Column, PhysicalSortExpr};
-use datafusion::physical_plan::file_format::{AvroExec, CsvExec, ParquetExec};
+use datafusion::physical_plan::file_format::{
+    AvroExec, CsvExec, NdJsonExec, ParquetExec,
+};
 use datafusion::physical_plan::filter::FilterExec;
-use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
 use datafusion::physical_plan::joins::CrossJoinExec;
-use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion::physical_plan::joins::{
+    BandCondition, BandJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
+    SortMergeJoinExec,
+};
 use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::set_operation::SetOperationAllExec;
+use datafusion::physical_plan::sorts::partial_sort::PartialSortExec;
 use datafusion::physical_plan::sorts::sort::SortExec;
 use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use datafusion::physical_plan::udaf;
 use datafusion::physical_plan::union::UnionExec;
-use datafusion::physical_plan::windows::{create_window_expr, WindowAggExec};
+use datafusion::physical_plan::windows::{BoundedWindowAggExec, WindowAggExec};
 use datafusion::physical_plan::{
     AggregateExpr, ExecutionPlan, Partitioning, PhysicalExpr, WindowExpr,
 };
@@ -53,10 +63,20 @@ use prost::Message;
 
 use crate::common::proto_error;
 use crate::common::{csv_delimiter_to_string, str_to_byte};
+use crate::function_registry::{
+    check_fingerprint, udaf_fingerprint, FallbackFunctionRegistry, FunctionFactory,
+};
 use crate::logical_plan;
 use crate::physical_plan::from_proto::{
-    parse_physical_expr, parse_protobuf_file_scan_config,
+    parse_physical_expr, parse_physical_sort_expr, parse_physical_window_expr,
+    parse_protobuf_file_scan_config, parse_protobuf_join_filter,
+};
+use crate::physical_plan::to_proto::{
+    serialize_file_scan_config, serialize_join_filter, serialize_physical_aggr_expr,
+    serialize_physical_expr, serialize_physical_sort_expr,
+    serialize_physical_window_expr,
 };
+use crate::protobuf::physical_aggregate_expr_node::AggregateFunction;
 use crate::protobuf::physical_expr_node::ExprType;
 use crate::protobuf::physical_plan_node::PhysicalPlanType;
 use crate::protobuf::repartition_exec_node::PartitionMethod;
@@ -93,6 +113,10 @@ impl AsExecutionPlan for PhysicalPlanNode {
         runtime: &RuntimeEnv,
         extension_codec: &dyn PhysicalExtensionCodec,
     ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
+        let registry = &FallbackFunctionRegistry::new(
+            registry,
+            extension_codec.function_factories(),
+        );
         let plan = self.physical_plan_type.as_ref().ok_or_else(|| {
             proto_error(format!(
                 "physical_plan::from_proto() Unsupported physical plan '{self:?}'"
@@ -119,7 +143,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     .expr
                     .iter()
                     .zip(projection.expr_name.iter())
-                    .map(|(expr, name)| Ok((parse_physical_expr(expr,registry, input.schema().as_ref())?, name.to_string())))
+                    .map(|(expr, name)| Ok((parse_physical_expr(expr,registry, input.schema().as_ref(), extension_codec)?, name.to_string())))
                     .collect::<Result<Vec<(Arc<dyn PhysicalExpr>, String)>, DataFusionError>>(
                     )?;
                 Ok(Arc::new(ProjectionExec::try_new(exprs, input)?))
@@ -135,7 +159,12 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     .expr
                     .as_ref()
                     .map(|expr| {
-                        parse_physical_expr(expr, registry, input.schema().as_ref())
+                        parse_physical_expr(
+                            expr,
+                            registry,
+                            input.schema().as_ref(),
+                            extension_codec,
+                        )
                     })
                     .transpose()?
                     .ok_or_else(|| {
@@ -150,10 +179,19 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 parse_protobuf_file_scan_config(
                     scan.base_conf.as_ref().unwrap(),
                     registry,
+                    extension_codec,
                 )?,
                 scan.has_header,
                 str_to_byte(&scan.delimiter)?,
-                FileCompressionType::UNCOMPRESSED,
+                FileCompressionType::from_str(&scan.file_compression_type)?,
+            ))),
+            PhysicalPlanType::JsonScan(scan) => Ok(Arc::new(NdJsonExec::new(
+                parse_protobuf_file_scan_config(
+                    scan.base_conf.as_ref().unwrap(),
+                    registry,
+                    extension_codec,
+                )?,
+                FileCompressionType::from_str(&scan.file_compression_type)?,
             ))),
             PhysicalPlanType::ParquetScan(scan) => {
                 let predicate = scan
@@ -165,6 +203,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     parse_protobuf_file_scan_config(
                         scan.base_conf.as_ref().unwrap(),
                         registry,
+                        extension_codec,
                     )?,
                     predicate,
                     None,
@@ -174,6 +213,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 Ok(Arc::new(AvroExec::new(parse_protobuf_file_scan_config(
                     scan.base_conf.as_ref().unwrap(),
                     registry,
+                    extension_codec,
                 )?)))
             }
             PhysicalPlanType::CoalesceBatches(coalesce_batches) => {
@@ -206,7 +246,12 @@ impl AsExecutionPlan for PhysicalPlanNode {
                             .hash_expr
                             .iter()
                             .map(|e| {
-                                parse_physical_expr(e, registry, input.schema().as_ref())
+                                parse_physical_expr(
+                                    e,
+                                    registry,
+                                    input.schema().as_ref(),
+                                    extension_codec,
+                                )
                             })
                             .collect::<Result<Vec<Arc<dyn PhysicalExpr>>, _>>()?;
 
@@ -277,60 +322,119 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 let physical_schema: SchemaRef =
                     SchemaRef::new((&input_schema).try_into()?);
 
-                let physical_window_expr: Vec<Arc<dyn WindowExpr>> = window_agg
-                    .window_expr
+                let physical_window_expr = parse_window_exprs(
+                    &window_agg.window_expr,
+                    &window_agg.window_expr_name,
+                    registry,
+                    &physical_schema,
+                    extension_codec,
+                )?;
+                let partition_keys = window_agg
+                    .partition_keys
                     .iter()
-                    .zip(window_agg.window_expr_name.iter())
-                    .map(|(expr, name)| {
-                        let expr_type = expr.expr_type.as_ref().ok_or_else(|| {
-                            proto_error("Unexpected empty window physical expression")
-                        })?;
-
-                        match expr_type {
-                            ExprType::WindowExpr(window_node) => {
-                                let window_node_expr = window_node
-                                    .expr
-                                    .as_ref()
-                                    .map(|e| {
-                                        parse_physical_expr(
-                                            e.as_ref(),
-                                            registry,
-                                            &physical_schema,
-                                        )
-                                    })
-                                    .transpose()?
-                                    .ok_or_else(|| {
-                                        proto_error(
-                                            "missing window_node expr expression"
-                                                .to_string(),
-                                        )
-                                    })?;
-
-                                Ok(create_window_expr(
-                                    &convert_required!(window_node.window_function)?,
-                                    name.to_owned(),
-                                    &[window_node_expr],
-                                    &[],
-                                    &[],
-                                    Arc::new(WindowFrame::new(false)),
-                                    &physical_schema,
-                                )?)
-                            }
-                            _ => Err(DataFusionError::Internal(
-                                "Invalid expression for WindowAggrExec".to_string(),
-                            )),
-                        }
+                    .map(|e| {
+                        parse_physical_expr(
+                            e,
+                            registry,
+                            input.schema().as_ref(),
+                            extension_codec,
+                        )
                     })
                     .collect::<Result<Vec<_>, _>>()?;
-                //todo fill partition keys and sort keys
+                let sort_keys = parse_sort_keys(
+                    &window_agg.sort_keys,
+                    registry,
+                    input.schema().as_ref(),
+                    extension_codec,
+                )?;
                 Ok(Arc::new(WindowAggExec::try_new(
                     physical_window_expr,
                     input,
-                    Arc::new((&input_schema).try_into()?),
-                    vec![],
-                    None,
+                    physical_schema,
+                    partition_keys,
+                    sort_keys,
+                )?))
+            }
+            PhysicalPlanType::BoundedWindow(window_agg) => {
+                let input: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    window_agg.input,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let input_schema = window_agg
+                    .input_schema
+                    .as_ref()
+                    .ok_or_else(|| {
+                        DataFusionError::Internal(
+                            "input_schema in BoundedWindowAggExecNode is missing."
+                                .to_owned(),
+                        )
+                    })?
+                    .clone();
+                let physical_schema: SchemaRef =
+                    SchemaRef::new((&input_schema).try_into()?);
+
+                let physical_window_expr = parse_window_exprs(
+                    &window_agg.window_expr,
+                    &window_agg.window_expr_name,
+                    registry,
+                    &physical_schema,
+                    extension_codec,
+                )?;
+                let partition_keys = window_agg
+                    .partition_keys
+                    .iter()
+                    .map(|e| {
+                        parse_physical_expr(
+                            e,
+                            registry,
+                            input.schema().as_ref(),
+                            extension_codec,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let sort_keys = parse_sort_keys(
+                    &window_agg.sort_keys,
+                    registry,
+                    input.schema().as_ref(),
+                    extension_codec,
+                )?;
+                Ok(Arc::new(BoundedWindowAggExec::try_new(
+                    physical_window_expr,
+                    input,
+                    physical_schema,
+                    partition_keys,
+                    sort_keys,
                 )?))
             }
+            PhysicalPlanType::DistinctOn(distinct_on) => {
+                let input: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    distinct_on.input,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let on_exprs = distinct_on
+                    .on_expr
+                    .iter()
+                    .map(|e| {
+                        parse_physical_expr(
+                            e,
+                            registry,
+                            input.schema().as_ref(),
+                            extension_codec,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let sort_exprs = parse_sort_keys(
+                    &distinct_on.sort_expr,
+                    registry,
+                    input.schema().as_ref(),
+                    extension_codec,
+                )?;
+                Ok(Arc::new(DistinctOnExec::new(input, on_exprs, sort_exprs)))
+            }
             PhysicalPlanType::Aggregate(hash_agg) => {
                 let input: Arc<dyn ExecutionPlan> = into_physical_plan!(
                     hash_agg.input,
@@ -361,8 +465,13 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     .iter()
                     .zip(hash_agg.group_expr_name.iter())
                     .map(|(expr, name)| {
-                        parse_physical_expr(expr, registry, input.schema().as_ref())
-                            .map(|expr| (expr, name.to_string()))
+                        parse_physical_expr(
+                            expr,
+                            registry,
+                            input.schema().as_ref(),
+                            extension_codec,
+                        )
+                        .map(|expr| (expr, name.to_string()))
                     })
                     .collect::<Result<Vec<_>, _>>()?;
 
@@ -371,8 +480,13 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     .iter()
                     .zip(hash_agg.group_expr_name.iter())
                     .map(|(expr, name)| {
-                        parse_physical_expr(expr, registry, input.schema().as_ref())
-                            .map(|expr| (expr, name.to_string()))
+                        parse_physical_expr(
+                            expr,
+                            registry,
+                            input.schema().as_ref(),
+                            extension_codec,
+                        )
+                        .map(|expr| (expr, name.to_string()))
                     })
                     .collect::<Result<Vec<_>, _>>()?;
 
@@ -409,29 +523,59 @@ impl AsExecutionPlan for PhysicalPlanNode {
 
                         match expr_type {
                             ExprType::AggregateExpr(agg_node) => {
-                                let aggr_function =
-                                    protobuf::AggregateFunction::from_i32(
-                                        agg_node.aggr_function,
-                                    )
-                                        .ok_or_else(
-                                            || {
+                                let input_phy_expr: Vec<Arc<dyn PhysicalExpr>> = agg_node.expr.iter()
+                                    .map(|e| parse_physical_expr(e, registry, &physical_schema, extension_codec))
+                                    .collect::<Result<Vec<_>, _>>()?;
+
+                                match agg_node.aggregate_function.as_ref() {
+                                    Some(AggregateFunction::AggrFunction(i)) => {
+                                        let aggr_function = protobuf::AggregateFunction::from_i32(*i)
+                                            .ok_or_else(|| {
                                                 proto_error(format!(
-                                                    "Received an unknown aggregate function: {}",
-                                                    agg_node.aggr_function
+                                                    "Received an unknown aggregate function: {i}"
                                                 ))
-                                            },
+                                            })?;
+
+                                        let ordering_req = agg_node
+                                            .ordering_req
+                                            .iter()
+                                            .map(|e| parse_physical_sort_expr(e, registry, &physical_schema, extension_codec))
+                                            .collect::<Result<Vec<_>, _>>()?;
+
+                                        Ok(create_aggregate_expr(
+                                            &aggr_function.into(),
+                                            agg_node.distinct,
+                                            input_phy_expr.as_slice(),
+                                            &ordering_req,
+                                            &physical_schema,
+                                            name.to_string(),
+                                        )?)
+                                    }
+                                    Some(AggregateFunction::UserDefinedAggrFunction(udaf_name)) => {
+                                        let agg_udf = if agg_node.fun_definition.is_empty() {
+                                            registry.udaf(udaf_name)?
+                                        } else {
+                                            extension_codec.try_decode_udaf(
+                                                udaf_name,
+                                                &agg_node.fun_definition,
+                                            )?
+                                        };
+                                        check_fingerprint(
+                                            udaf_name,
+                                            agg_node.fingerprint,
+                                            udaf_fingerprint(&agg_udf),
                                         )?;
-
-                                let input_phy_expr: Vec<Arc<dyn PhysicalExpr>> = agg_node.expr.iter()
-                                    .map(|e| parse_physical_expr(e, registry, &physical_schema).unwrap()).collect();
-
-                                Ok(create_aggregate_expr(
-                                    &aggr_function.into(),
-                                    agg_node.distinct,
-                                    input_phy_expr.as_slice(),
-                                    &physical_schema,
-                                    name.to_string(),
-                                )?)
+                                        Ok(udaf::create_aggregate_expr(
+                                            agg_udf.as_ref(),
+                                            input_phy_expr.as_slice(),
+                                            &physical_schema,
+                                            name,
+                                        )?)
+                                    }
+                                    None => Err(proto_error(
+                                        "Missing required field aggregate_function",
+                                    )),
+                                }
                             }
                             _ => Err(DataFusionError::Internal(
                                 "Invalid aggregate expression for AggregateExec"
@@ -481,38 +625,8 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 let filter = hashjoin
                     .filter
                     .as_ref()
-                    .map(|f| {
-                        let schema = f
-                            .schema
-                            .as_ref()
-                            .ok_or_else(|| proto_error("Missing JoinFilter schema"))?
-                            .try_into()?;
-
-                        let expression = parse_physical_expr(
-                            f.expression.as_ref().ok_or_else(|| {
-                                proto_error("Unexpected empty filter expression")
-                            })?,
-                            registry, &schema
-                        )?;
-                        let column_indices = f.column_indices
-                            .iter()
-                            .map(|i| {
-                                let side = protobuf::JoinSide::from_i32(i.side)
-                                    .ok_or_else(|| proto_error(format!(
-                                        "Received a HashJoinNode message with JoinSide in Filter {}",
-                                        i.side))
-                                    )?;
-
-                                Ok(ColumnIndex{
-                                    index: i.index as usize,
-                                    side: side.into(),
-                                })
-                            })
-                            .collect::<Result<Vec<_>, DataFusionError>>()?;
-
-                        Ok(JoinFilter::new(expression, column_indices, schema))
-                    })
-                    .map_or(Ok(None), |v: Result<JoinFilter, DataFusionError>| v.map(Some))?;
+                    .map(|f| parse_protobuf_join_filter(f, registry, extension_codec))
+                    .transpose()?;
 
                 let partition_mode =
                     protobuf::PartitionMode::from_i32(hashjoin.partition_mode)
@@ -537,6 +651,104 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     &hashjoin.null_equals_null,
                 )?))
             }
+            PhysicalPlanType::NestedLoopJoin(join) => {
+                let left: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(join.left, registry, runtime, extension_codec)?;
+                let right: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(join.right, registry, runtime, extension_codec)?;
+                let join_type =
+                    protobuf::JoinType::from_i32(join.join_type).ok_or_else(|| {
+                        proto_error(format!(
+                            "Received a NestedLoopJoinExecNode message with unknown JoinType {}",
+                            join.join_type
+                        ))
+                    })?;
+                let filter = join
+                    .filter
+                    .as_ref()
+                    .map(|f| parse_protobuf_join_filter(f, registry, extension_codec))
+                    .transpose()?;
+                Ok(Arc::new(NestedLoopJoinExec::try_new(
+                    left,
+                    right,
+                    filter,
+                    &join_type.into(),
+                )?))
+            }
+            PhysicalPlanType::BandJoin(join) => {
+                let left: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(join.left, registry, runtime, extension_codec)?;
+                let right: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(join.right, registry, runtime, extension_codec)?;
+                let filter = join
+                    .filter
+                    .as_ref()
+                    .map(|f| parse_protobuf_join_filter(f, registry, extension_codec))
+                    .transpose()?
+                    .ok_or_else(|| {
+                        proto_error("Received a BandJoinExecNode message without filter")
+                    })?;
+                // the band condition is found again in the filter
+                let band =
+                    BandCondition::try_new(&filter, &left.schema(), &right.schema())?
+                        .ok_or_else(|| {
+                            proto_error(
+                                "Received a BandJoinExecNode message whose filter has no band condition",
+                            )
+                        })?;
+                Ok(Arc::new(BandJoinExec::try_new(left, right, filter, band)?))
+            }
+            PhysicalPlanType::SortMergeJoin(join) => {
+                let left: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(join.left, registry, runtime, extension_codec)?;
+                let right: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(join.right, registry, runtime, extension_codec)?;
+                let on: Vec<(Column, Column)> = join
+                    .on
+                    .iter()
+                    .map(|col| {
+                        let left = into_required!(col.left)?;
+                        let right = into_required!(col.right)?;
+                        Ok((left, right))
+                    })
+                    .collect::<Result<_, DataFusionError>>()?;
+                let join_type =
+                    protobuf::JoinType::from_i32(join.join_type).ok_or_else(|| {
+                        proto_error(format!(
+                            "Received a SortMergeJoinExecNode message with unknown JoinType {}",
+                            join.join_type
+                        ))
+                    })?;
+                let sort_options = join
+                    .sort_options
+                    .iter()
+                    .map(|o| SortOptions {
+                        descending: !o.asc,
+                        nulls_first: o.nulls_first,
+                    })
+                    .collect();
+                Ok(Arc::new(SortMergeJoinExec::try_new(
+                    left,
+                    right,
+                    on,
+                    join_type.into(),
+                    sort_options,
+                    join.null_equals_null,
+                )?))
+            }
+            PhysicalPlanType::Analyze(analyze) => {
+                let input: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    analyze.input,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                Ok(Arc::new(AnalyzeExec::new(
+                    analyze.verbose,
+                    input,
+                    Arc::new(convert_required!(analyze.schema)?),
+                )))
+            }
             PhysicalPlanType::Union(union) => {
                 let mut inputs: Vec<Arc<dyn ExecutionPlan>> = vec![];
                 for input in &union.inputs {
@@ -548,6 +760,29 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 }
                 Ok(Arc::new(UnionExec::new(inputs)))
             }
+            PhysicalPlanType::SetOperationAll(set_op) => {
+                let left: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(set_op.left, registry, runtime, extension_codec)?;
+                let right: Arc<dyn ExecutionPlan> = into_physical_plan!(
+                    set_op.right,
+                    registry,
+                    runtime,
+                    extension_codec
+                )?;
+                let op = match protobuf::SetOperationType::from_i32(set_op.op) {
+                    Some(protobuf::SetOperationType::Intersect) => {
+                        SetOperationType::Intersect
+                    }
+                    Some(protobuf::SetOperationType::Except) => SetOperationType::Except,
+                    None => {
+                        return Err(proto_error(format!(
+                            "Received a SetOperationAllExecNode message with unknown SetOperationType {}",
+                            set_op.op
+                        )))
+                    }
+                };
+                Ok(Arc::new(SetOperationAllExec::try_new(op, left, right)?))
+            }
             PhysicalPlanType::CrossJoin(crossjoin) => {
                 let left: Arc<dyn ExecutionPlan> = into_physical_plan!(
                     crossjoin.left,
@@ -590,7 +825,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                                 })?
                                 .as_ref();
                             Ok(PhysicalSortExpr {
-                                expr: parse_physical_expr(expr,registry, input.schema().as_ref())?,
+                                expr: parse_physical_expr(expr,registry, input.schema().as_ref(), extension_codec)?,
                                 options: SortOptions {
                                     descending: !sort_expr.asc,
                                     nulls_first: sort_expr.nulls_first,
@@ -610,6 +845,28 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 };
                 Ok(Arc::new(SortExec::try_new(exprs, input, fetch)?))
             }
+            PhysicalPlanType::PartialSort(sort) => {
+                let input: Arc<dyn ExecutionPlan> =
+                    into_physical_plan!(sort.input, registry, runtime, extension_codec)?;
+                let exprs = parse_sort_keys(
+                    &sort.expr,
+                    registry,
+                    input.schema().as_ref(),
+                    extension_codec,
+                )?
+                .unwrap_or_default();
+                let fetch = if sort.fetch < 0 {
+                    None
+                } else {
+                    Some(sort.fetch as usize)
+                };
+                Ok(Arc::new(PartialSortExec::try_new(
+                    exprs,
+                    input,
+                    sort.common_prefix_length as usize,
+                    fetch,
+                )?))
+            }
             PhysicalPlanType::SortPreservingMerge(sort) => {
                 let input: Arc<dyn ExecutionPlan> =
                     into_physical_plan!(sort.input, registry, runtime, extension_codec)?;
@@ -633,7 +890,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                                 })?
                                 .as_ref();
                             Ok(PhysicalSortExpr {
-                                expr: parse_physical_expr(expr,registry, input.schema().as_ref())?,
+                                expr: parse_physical_expr(expr,registry, input.schema().as_ref(), extension_codec)?,
                                 options: SortOptions {
                                     descending: !sort_expr.asc,
                                     nulls_first: sort_expr.nulls_first,
@@ -646,7 +903,16 @@ impl AsExecutionPlan for PhysicalPlanNode {
                         }
                     })
                     .collect::<Result<Vec<_>, _>>()?;
-                Ok(Arc::new(SortPreservingMergeExec::new(exprs, input)))
+                let fetch = if sort.fetch < 0 {
+                    None
+                } else {
+                    Some(sort.fetch as usize)
+                };
+                Ok(Arc::new(
+                    SortPreservingMergeExec::new(exprs, input)
+                        .with_skip(sort.skip as usize)
+                        .with_fetch(fetch),
+                ))
             }
             PhysicalPlanType::Extension(extension) => {
                 let inputs: Vec<Arc<dyn ExecutionPlan>> = extension
@@ -698,7 +964,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
             let expr = exec
                 .expr()
                 .iter()
-                .map(|expr| expr.0.clone().try_into())
+                .map(|expr| serialize_physical_expr(expr.0.clone(), extension_codec))
                 .collect::<Result<Vec<_>, DataFusionError>>()?;
             let expr_name = exec.expr().iter().map(|expr| expr.1.clone()).collect();
             Ok(protobuf::PhysicalPlanNode {
@@ -719,7 +985,10 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 physical_plan_type: Some(PhysicalPlanType::Filter(Box::new(
                     protobuf::FilterExecNode {
                         input: Some(Box::new(input)),
-                        expr: Some(exec.predicate().clone().try_into()?),
+                        expr: Some(serialize_physical_expr(
+                            exec.predicate().clone(),
+                            extension_codec,
+                        )?),
                     },
                 ))),
             })
@@ -780,31 +1049,8 @@ impl AsExecutionPlan for PhysicalPlanNode {
             let join_type: protobuf::JoinType = exec.join_type().to_owned().into();
             let filter = exec
                 .filter()
-                .as_ref()
-                .map(|f| {
-                    let expression = f.expression().to_owned().try_into()?;
-                    let column_indices = f
-                        .column_indices()
-                        .iter()
-                        .map(|i| {
-                            let side: protobuf::JoinSide = i.side.to_owned().into();
-                            protobuf::ColumnIndex {
-                                index: i.index as u32,
-                                side: side.into(),
-                            }
-                        })
-                        .collect();
-                    let schema = f.schema().try_into()?;
-                    Ok(protobuf::JoinFilter {
-                        expression: Some(expression),
-                        column_indices,
-                        schema: Some(schema),
-                    })
-                })
-                .map_or(
-                    Ok(None),
-                    |v: Result<protobuf::JoinFilter, DataFusionError>| v.map(Some),
-                )?;
+                .map(|f| serialize_join_filter(f, extension_codec))
+                .transpose()?;
 
             let partition_mode = match exec.partition_mode() {
                 PartitionMode::CollectLeft => protobuf::PartitionMode::CollectLeft,
@@ -842,82 +1088,257 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     },
                 ))),
             })
-        } else if let Some(exec) = plan.downcast_ref::<AggregateExec>() {
-            let groups: Vec<bool> = exec
-                .group_expr()
-                .groups()
-                .iter()
-                .flatten()
-                .copied()
-                .collect();
-
-            let group_names = exec
-                .group_expr()
-                .expr()
-                .iter()
-                .map(|expr| expr.1.to_owned())
-                .collect();
-
-            let agg = exec
-                .aggr_expr()
-                .iter()
-                .map(|expr| expr.to_owned().try_into())
-                .collect::<Result<Vec<_>, DataFusionError>>()?;
-            let agg_names = exec
-                .aggr_expr()
-                .iter()
-                .map(|expr| match expr.field() {
-                    Ok(field) => Ok(field.name().clone()),
-                    Err(e) => Err(e),
-                })
-                .collect::<Result<_, DataFusionError>>()?;
-
-            let agg_mode = match exec.mode() {
-                AggregateMode::Partial => protobuf::AggregateMode::Partial,
-                AggregateMode::Final => protobuf::AggregateMode::Final,
-                AggregateMode::FinalPartitioned => {
-                    protobuf::AggregateMode::FinalPartitioned
-                }
-            };
-            let input_schema = exec.input_schema();
-            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
-                exec.input().to_owned(),
+        } else if let Some(exec) = plan.downcast_ref::<NestedLoopJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
                 extension_codec,
             )?;
-
-            let null_expr = exec
-                .group_expr()
-                .null_expr()
-                .iter()
-                .map(|expr| expr.0.to_owned().try_into())
-                .collect::<Result<Vec<_>, DataFusionError>>()?;
-
-            let group_expr = exec
-                .group_expr()
-                .expr()
-                .iter()
-                .map(|expr| expr.0.to_owned().try_into())
-                .collect::<Result<Vec<_>, DataFusionError>>()?;
-
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let join_type: protobuf::JoinType = exec.join_type().to_owned().into();
+            let filter = exec
+                .filter()
+                .map(|f| serialize_join_filter(f, extension_codec))
+                .transpose()?;
             Ok(protobuf::PhysicalPlanNode {
-                physical_plan_type: Some(PhysicalPlanType::Aggregate(Box::new(
-                    protobuf::AggregateExecNode {
-                        group_expr,
-                        group_expr_name: group_names,
-                        aggr_expr: agg,
-                        aggr_expr_name: agg_names,
-                        mode: agg_mode as i32,
-                        input: Some(Box::new(input)),
-                        input_schema: Some(input_schema.as_ref().try_into()?),
-                        null_expr,
-                        groups,
+                physical_plan_type: Some(PhysicalPlanType::NestedLoopJoin(Box::new(
+                    protobuf::NestedLoopJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        join_type: join_type.into(),
+                        filter,
                     },
                 ))),
             })
-        } else if let Some(empty) = plan.downcast_ref::<EmptyExec>() {
-            let schema = empty.schema().as_ref().try_into()?;
-            Ok(protobuf::PhysicalPlanNode {
-                physical_plan_type: Some(PhysicalPlanType::Empty(
+        } else if let Some(exec) = plan.downcast_ref::<BandJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let filter = serialize_join_filter(exec.filter(), extension_codec)?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::BandJoin(Box::new(
+                    protobuf::BandJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        filter: Some(filter),
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<SortMergeJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let on: Vec<protobuf::JoinOn> = exec
+                .on()
+                .iter()
+                .map(|tuple| protobuf::JoinOn {
+                    left: Some(protobuf::PhysicalColumn {
+                        name: tuple.0.name().to_string(),
+                        index: tuple.0.index() as u32,
+                    }),
+                    right: Some(protobuf::PhysicalColumn {
+                        name: tuple.1.name().to_string(),
+                        index: tuple.1.index() as u32,
+                    }),
+                })
+                .collect();
+            let join_type: protobuf::JoinType = exec.join_type().into();
+            let sort_options = exec
+                .sort_options()
+                .iter()
+                .map(|o| protobuf::SortOptionsNode {
+                    asc: !o.descending,
+                    nulls_first: o.nulls_first,
+                })
+                .collect();
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::SortMergeJoin(Box::new(
+                    protobuf::SortMergeJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        on,
+                        join_type: join_type.into(),
+                        sort_options,
+                        null_equals_null: exec.null_equals_null(),
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<AnalyzeExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Analyze(Box::new(
+                    protobuf::AnalyzeExecNode {
+                        verbose: exec.verbose(),
+                        input: Some(Box::new(input)),
+                        schema: Some(exec.schema().as_ref().try_into()?),
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<WindowAggExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            let (window_expr, window_expr_name) =
+                serialize_window_exprs(exec.window_expr(), extension_codec)?;
+            let partition_keys = exec
+                .partition_keys
+                .iter()
+                .map(|e| serialize_physical_expr(e.clone(), extension_codec))
+                .collect::<Result<Vec<_>, DataFusionError>>()?;
+            let sort_keys =
+                serialize_sort_keys(exec.sort_keys.as_deref(), extension_codec)?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Window(Box::new(
+                    protobuf::WindowAggExecNode {
+                        input: Some(Box::new(input)),
+                        window_expr,
+                        window_expr_name,
+                        input_schema: Some(exec.input_schema().as_ref().try_into()?),
+                        partition_keys,
+                        sort_keys,
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<BoundedWindowAggExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            let (window_expr, window_expr_name) =
+                serialize_window_exprs(exec.window_expr(), extension_codec)?;
+            let partition_keys = exec
+                .partition_keys
+                .iter()
+                .map(|e| serialize_physical_expr(e.clone(), extension_codec))
+                .collect::<Result<Vec<_>, DataFusionError>>()?;
+            let sort_keys =
+                serialize_sort_keys(exec.sort_keys.as_deref(), extension_codec)?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::BoundedWindow(Box::new(
+                    protobuf::BoundedWindowAggExecNode {
+                        input: Some(Box::new(input)),
+                        window_expr,
+                        window_expr_name,
+                        input_schema: Some(exec.input_schema().as_ref().try_into()?),
+                        partition_keys,
+                        sort_keys,
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<DistinctOnExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            let on_expr = exec
+                .on_exprs()
+                .iter()
+                .map(|e| serialize_physical_expr(e.clone(), extension_codec))
+                .collect::<Result<Vec<_>, DataFusionError>>()?;
+            let sort_expr = serialize_sort_keys(exec.sort_exprs(), extension_codec)?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::DistinctOn(Box::new(
+                    protobuf::DistinctOnExecNode {
+                        input: Some(Box::new(input)),
+                        on_expr,
+                        sort_expr,
+                    },
+                ))),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<AggregateExec>() {
+            let groups: Vec<bool> = exec
+                .group_expr()
+                .groups()
+                .iter()
+                .flatten()
+                .copied()
+                .collect();
+
+            let group_names = exec
+                .group_expr()
+                .expr()
+                .iter()
+                .map(|expr| expr.1.to_owned())
+                .collect();
+
+            let agg = exec
+                .aggr_expr()
+                .iter()
+                .map(|expr| {
+                    serialize_physical_aggr_expr(expr.to_owned(), extension_codec)
+                })
+                .collect::<Result<Vec<_>, DataFusionError>>()?;
+            let agg_names = exec
+                .aggr_expr()
+                .iter()
+                .map(|expr| match expr.field() {
+                    Ok(field) => Ok(field.name().clone()),
+                    Err(e) => Err(e),
+                })
+                .collect::<Result<_, DataFusionError>>()?;
+
+            let agg_mode = match exec.mode() {
+                AggregateMode::Partial => protobuf::AggregateMode::Partial,
+                AggregateMode::Final => protobuf::AggregateMode::Final,
+                AggregateMode::FinalPartitioned => {
+                    protobuf::AggregateMode::FinalPartitioned
+                }
+            };
+            let input_schema = exec.input_schema();
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+
+            let null_expr = exec
+                .group_expr()
+                .null_expr()
+                .iter()
+                .map(|expr| serialize_physical_expr(expr.0.to_owned(), extension_codec))
+                .collect::<Result<Vec<_>, DataFusionError>>()?;
+
+            let group_expr = exec
+                .group_expr()
+                .expr()
+                .iter()
+                .map(|expr| serialize_physical_expr(expr.0.to_owned(), extension_codec))
+                .collect::<Result<Vec<_>, DataFusionError>>()?;
+
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Aggregate(Box::new(
+                    protobuf::AggregateExecNode {
+                        group_expr,
+                        group_expr_name: group_names,
+                        aggr_expr: agg,
+                        aggr_expr_name: agg_names,
+                        mode: agg_mode as i32,
+                        input: Some(Box::new(input)),
+                        input_schema: Some(input_schema.as_ref().try_into()?),
+                        null_expr,
+                        groups,
+                    },
+                ))),
+            })
+        } else if let Some(empty) = plan.downcast_ref::<EmptyExec>() {
+            let schema = empty.schema().as_ref().try_into()?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Empty(
                     protobuf::EmptyExecNode {
                         produce_one_row: empty.produce_one_row(),
                         schema: Some(schema),
@@ -942,9 +1363,31 @@ impl AsExecutionPlan for PhysicalPlanNode {
             Ok(protobuf::PhysicalPlanNode {
                 physical_plan_type: Some(PhysicalPlanType::CsvScan(
                     protobuf::CsvScanExecNode {
-                        base_conf: Some(exec.base_config().try_into()?),
+                        base_conf: Some(serialize_file_scan_config(
+                            exec.base_config(),
+                            extension_codec,
+                        )?),
                         has_header: exec.has_header(),
                         delimiter: csv_delimiter_to_string(exec.delimiter())?,
+                        file_compression_type: exec
+                            .file_compression_type()
+                            .get_variant()
+                            .to_string(),
+                    },
+                )),
+            })
+        } else if let Some(exec) = plan.downcast_ref::<NdJsonExec>() {
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::JsonScan(
+                    protobuf::NdJsonScanExecNode {
+                        base_conf: Some(serialize_file_scan_config(
+                            exec.base_config(),
+                            extension_codec,
+                        )?),
+                        file_compression_type: exec
+                            .file_compression_type()
+                            .get_variant()
+                            .to_string(),
                     },
                 )),
             })
@@ -956,7 +1399,10 @@ impl AsExecutionPlan for PhysicalPlanNode {
             Ok(protobuf::PhysicalPlanNode {
                 physical_plan_type: Some(PhysicalPlanType::ParquetScan(
                     protobuf::ParquetScanExecNode {
-                        base_conf: Some(exec.base_config().try_into()?),
+                        base_conf: Some(serialize_file_scan_config(
+                            exec.base_config(),
+                            extension_codec,
+                        )?),
                         pruning_predicate: pruning_expr,
                     },
                 )),
@@ -965,7 +1411,10 @@ impl AsExecutionPlan for PhysicalPlanNode {
             Ok(protobuf::PhysicalPlanNode {
                 physical_plan_type: Some(PhysicalPlanType::AvroScan(
                     protobuf::AvroScanExecNode {
-                        base_conf: Some(exec.base_config().try_into()?),
+                        base_conf: Some(serialize_file_scan_config(
+                            exec.base_config(),
+                            extension_codec,
+                        )?),
                     },
                 )),
             })
@@ -992,7 +1441,9 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     PartitionMethod::Hash(protobuf::PhysicalHashRepartition {
                         hash_expr: exprs
                             .iter()
-                            .map(|expr| expr.clone().try_into())
+                            .map(|expr| {
+                                serialize_physical_expr(expr.clone(), extension_codec)
+                            })
                             .collect::<Result<Vec<_>, DataFusionError>>()?,
                         partition_count: *partition_count as u64,
                     })
@@ -1023,7 +1474,10 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 .iter()
                 .map(|expr| {
                     let sort_expr = Box::new(protobuf::PhysicalSortExprNode {
-                        expr: Some(Box::new(expr.expr.to_owned().try_into()?)),
+                        expr: Some(Box::new(serialize_physical_expr(
+                            expr.expr.to_owned(),
+                            extension_codec,
+                        )?)),
                         asc: !expr.options.descending,
                         nulls_first: expr.options.nulls_first,
                     });
@@ -1046,6 +1500,25 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     },
                 ))),
             })
+        } else if let Some(exec) = plan.downcast_ref::<PartialSortExec>() {
+            let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.input().to_owned(),
+                extension_codec,
+            )?;
+            let expr = serialize_sort_keys(Some(exec.expr()), extension_codec)?;
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::PartialSort(Box::new(
+                    protobuf::PartialSortExecNode {
+                        input: Some(Box::new(input)),
+                        expr,
+                        common_prefix_length: exec.common_prefix_length() as u32,
+                        fetch: match exec.fetch() {
+                            Some(n) => n as i64,
+                            _ => -1,
+                        },
+                    },
+                ))),
+            })
         } else if let Some(union) = plan.downcast_ref::<UnionExec>() {
             let mut inputs: Vec<PhysicalPlanNode> = vec![];
             for input in union.inputs() {
@@ -1059,6 +1532,28 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     protobuf::UnionExecNode { inputs },
                 )),
             })
+        } else if let Some(exec) = plan.downcast_ref::<SetOperationAllExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let op = match exec.op() {
+                SetOperationType::Intersect => protobuf::SetOperationType::Intersect,
+                SetOperationType::Except => protobuf::SetOperationType::Except,
+            };
+            Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::SetOperationAll(Box::new(
+                    protobuf::SetOperationAllExecNode {
+                        op: op.into(),
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                    },
+                ))),
+            })
         } else if let Some(exec) = plan.downcast_ref::<SortPreservingMergeExec>() {
             let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
                 exec.input().to_owned(),
@@ -1069,7 +1564,10 @@ impl AsExecutionPlan for PhysicalPlanNode {
                 .iter()
                 .map(|expr| {
                     let sort_expr = Box::new(protobuf::PhysicalSortExprNode {
-                        expr: Some(Box::new(expr.expr.to_owned().try_into()?)),
+                        expr: Some(Box::new(serialize_physical_expr(
+                            expr.expr.to_owned(),
+                            extension_codec,
+                        )?)),
                         asc: !expr.options.descending,
                         nulls_first: expr.options.nulls_first,
                     });
@@ -1085,6 +1583,11 @@ impl AsExecutionPlan for PhysicalPlanNode {
                     Box::new(protobuf::SortPreservingMergeExecNode {
                         input: Some(Box::new(input)),
                         expr,
+                        skip: exec.skip() as u32,
+                        fetch: match exec.fetch() {
+                            Some(n) => n as i64,
+                            _ => -1,
+                        },
                     }),
                 )),
             })
@@ -1117,6 +1620,86 @@ impl AsExecutionPlan for PhysicalPlanNode {
     }
 }
 
+fn parse_window_exprs(
+    window_expr: &[protobuf::PhysicalExprNode],
+    window_expr_name: &[String],
+    registry: &dyn FunctionRegistry,
+    input_schema: &SchemaRef,
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<Vec<Arc<dyn WindowExpr>>, DataFusionError> {
+    window_expr
+        .iter()
+        .zip(window_expr_name.iter())
+        .map(|(expr, name)| {
+            let expr_type = expr.expr_type.as_ref().ok_or_else(|| {
+                proto_error("Unexpected empty window physical expression")
+            })?;
+
+            match expr_type {
+                ExprType::WindowExpr(window_node) => parse_physical_window_expr(
+                    window_node,
+                    name,
+                    registry,
+                    input_schema,
+                    extension_codec,
+                ),
+                _ => Err(DataFusionError::Internal(
+                    "Invalid expression for WindowAggrExec".to_string(),
+                )),
+            }
+        })
+        .collect()
+}
+
+fn parse_sort_keys(
+    sort_keys: &[protobuf::PhysicalSortExprNode],
+    registry: &dyn FunctionRegistry,
+    input_schema: &Schema,
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<Option<Vec<PhysicalSortExpr>>, DataFusionError> {
+    if sort_keys.is_empty() {
+        return Ok(None);
+    }
+    sort_keys
+        .iter()
+        .map(|o| parse_physical_sort_expr(o, registry, input_schema, extension_codec))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+fn serialize_window_exprs(
+    window_expr: &[Arc<dyn WindowExpr>],
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<(Vec<protobuf::PhysicalExprNode>, Vec<String>), DataFusionError> {
+    let exprs = window_expr
+        .iter()
+        .map(|expr| {
+            Ok(protobuf::PhysicalExprNode {
+                expr_type: Some(ExprType::WindowExpr(serialize_physical_window_expr(
+                    expr.clone(),
+                    extension_codec,
+                )?)),
+            })
+        })
+        .collect::<Result<Vec<_>, DataFusionError>>()?;
+    let names = window_expr
+        .iter()
+        .map(|expr| expr.name().to_string())
+        .collect();
+    Ok((exprs, names))
+}
+
+fn serialize_sort_keys(
+    sort_keys: Option<&[PhysicalSortExpr]>,
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<Vec<protobuf::PhysicalSortExprNode>, DataFusionError> {
+    sort_keys
+        .unwrap_or_default()
+        .iter()
+        .map(|o| serialize_physical_sort_expr(o, extension_codec))
+        .collect()
+}
+
 pub trait AsExecutionPlan: Debug + Send + Sync + Clone {
     fn try_decode(buf: &[u8]) -> Result<Self, DataFusionError>
     where
@@ -1155,6 +1738,56 @@ pub trait PhysicalExtensionCodec: Debug + Send + Sync {
         node: Arc<dyn ExecutionPlan>,
         buf: &mut Vec<u8>,
     ) -> Result<(), DataFusionError>;
+
+    /// Factories used to create user defined functions that are not
+    /// registered with the [`FunctionRegistry`] used for deserialization
+    fn function_factories(&self) -> Vec<Arc<dyn FunctionFactory>> {
+        vec![]
+    }
+
+    /// Decodes a scalar UDF from the state written by [`Self::try_encode_udf`].
+    /// Only called when that state is not empty, otherwise the UDF is
+    /// looked up by name in the [`FunctionRegistry`]
+    fn try_decode_udf(
+        &self,
+        name: &str,
+        _buf: &[u8],
+    ) -> Result<Arc<ScalarUDF>, DataFusionError> {
+        Err(DataFusionError::NotImplemented(format!(
+            "PhysicalExtensionCodec is not provided for scalar function {name}"
+        )))
+    }
+
+    /// Encodes the state of a scalar UDF, such as its configuration.
+    /// Writes nothing by default, so the UDF is resolved by name when decoding
+    fn try_encode_udf(
+        &self,
+        _node: &ScalarUDF,
+        _buf: &mut Vec<u8>,
+    ) -> Result<(), DataFusionError> {
+        Ok(())
+    }
+
+    /// Decodes an aggregate UDF from the state written by
+    /// [`Self::try_encode_udaf`]
+    fn try_decode_udaf(
+        &self,
+        name: &str,
+        _buf: &[u8],
+    ) -> Result<Arc<AggregateUDF>, DataFusionError> {
+        Err(DataFusionError::NotImplemented(format!(
+            "PhysicalExtensionCodec is not provided for aggregate function {name}"
+        )))
+    }
+
+    /// Encodes the state of an aggregate UDF. Writes nothing by default
+    fn try_encode_udaf(
+        &self,
+        _node: &AggregateUDF,
+        _buf: &mut Vec<u8>,
+    ) -> Result<(), DataFusionError> {
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -1202,43 +1835,65 @@ mod roundtrip_tests {
     use std::sync::Arc;
 
     use super::super::protobuf;
-    use crate::physical_plan::{AsExecutionPlan, DefaultPhysicalExtensionCodec};
-    use datafusion::arrow::array::ArrayRef;
+    use crate::physical_plan::{
+        AsExecutionPlan, DefaultPhysicalExtensionCodec, PhysicalExtensionCodec,
+    };
+    use datafusion::arrow::array::{ArrayRef, Int64Array};
     use datafusion::arrow::datatypes::IntervalUnit;
+    use datafusion::datasource::file_format::file_type::FileCompressionType;
     use datafusion::datasource::object_store::ObjectStoreUrl;
     use datafusion::execution::context::ExecutionProps;
-    use datafusion::logical_expr::create_udf;
-    use datafusion::logical_expr::{BuiltinScalarFunction, Volatility};
+    use datafusion::execution::FunctionRegistry;
+    use datafusion::logical_expr::{
+        create_udaf, create_udf, Accumulator, AggregateFunction, BuiltInWindowFunction,
+        WindowFrame, WindowFrameBound, WindowFrameExclude, WindowFrameUnits,
+        WindowFunction,
+    };
+    use datafusion::logical_expr::{
+        BuiltinScalarFunction, ColumnarValue, ScalarUDF, Volatility,
+    };
     use datafusion::physical_expr::expressions::DateTimeIntervalExpr;
     use datafusion::physical_expr::ScalarFunctionExpr;
     use datafusion::physical_plan::aggregates::PhysicalGroupBy;
+    use datafusion::physical_plan::analyze::AnalyzeExec;
+    use datafusion::physical_plan::distinct_on::DistinctOnExec;
     use datafusion::physical_plan::expressions::like;
     use datafusion::physical_plan::functions;
     use datafusion::physical_plan::functions::make_scalar_function;
     use datafusion::physical_plan::projection::ProjectionExec;
+    use datafusion::physical_plan::set_operation::SetOperationAllExec;
     use datafusion::{
         arrow::{
             compute::kernels::sort::SortOptions,
             datatypes::{DataType, Field, Schema},
         },
         datasource::listing::PartitionedFile,
-        logical_expr::{JoinType, Operator},
+        logical_expr::{JoinType, Operator, SetOperationType},
         physical_plan::{
-            aggregates::{AggregateExec, AggregateMode},
+            aggregates::{create_aggregate_expr, AggregateExec, AggregateMode},
             empty::EmptyExec,
             expressions::{binary, col, lit, InListExpr, NotExpr},
             expressions::{Avg, Column, DistinctCount, PhysicalSortExpr},
-            file_format::{FileScanConfig, ParquetExec},
+            file_format::{CsvExec, FileScanConfig, NdJsonExec, ParquetExec},
             filter::FilterExec,
-            joins::{HashJoinExec, PartitionMode},
+            joins::utils::{ColumnIndex, JoinFilter, JoinSide},
+            joins::{
+                BandCondition, BandJoinExec, HashJoinExec, NestedLoopJoinExec,
+                PartitionMode, SortMergeJoinExec,
+            },
             limit::{GlobalLimitExec, LocalLimitExec},
+            sorts::partial_sort::PartialSortExec,
             sorts::sort::SortExec,
-            AggregateExpr, ExecutionPlan, PhysicalExpr, Statistics,
+            sorts::sort_preserving_merge::SortPreservingMergeExec,
+            udaf,
+            windows::{create_window_expr, BoundedWindowAggExec, WindowAggExec},
+            AggregateExpr, ColumnStatistics, ExecutionPlan, PhysicalExpr, Precision,
+            Statistics, WindowExpr,
         },
         prelude::SessionContext,
         scalar::ScalarValue,
     };
-    use datafusion_common::Result;
+    use datafusion_common::{DataFusionError, Result};
 
     fn roundtrip_test(exec_plan: Arc<dyn ExecutionPlan>) -> Result<()> {
         let ctx = SessionContext::new();
@@ -1348,6 +2003,7 @@ mod roundtrip_tests {
             JoinType::RightAnti,
             JoinType::LeftSemi,
             JoinType::RightSemi,
+            JoinType::LeftMark,
         ] {
             for partition_mode in
                 &[PartitionMode::Partitioned, PartitionMode::CollectLeft]
@@ -1390,6 +2046,341 @@ mod roundtrip_tests {
         )?))
     }
 
+    #[test]
+    fn roundtrip_aggregate_with_ordering() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Int64, true);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+        let groups: Vec<(Arc<dyn PhysicalExpr>, String)> =
+            vec![(col("a", &schema)?, "unused".to_string())];
+
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: col("b", &schema)?,
+            options: SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        }];
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![create_aggregate_expr(
+            &AggregateFunction::ArrayAgg,
+            false,
+            &[col("a", &schema)?],
+            &ordering_req,
+            &schema,
+            "ARRAYAGG(a)",
+        )?];
+
+        roundtrip_test(Arc::new(AggregateExec::try_new(
+            AggregateMode::Partial,
+            PhysicalGroupBy::new_single(groups),
+            aggregates,
+            Arc::new(EmptyExec::new(false, schema.clone())),
+            schema,
+        )?))
+    }
+
+    #[test]
+    fn roundtrip_nested_loop_join() -> Result<()> {
+        let field_a = Field::new("col", DataType::Int64, false);
+        let schema_left = Arc::new(Schema::new(vec![field_a.clone()]));
+        let schema_right = Arc::new(Schema::new(vec![field_a]));
+
+        for join_type in &[JoinType::Inner, JoinType::Left, JoinType::RightSemi] {
+            roundtrip_test(Arc::new(NestedLoopJoinExec::try_new(
+                Arc::new(EmptyExec::new(false, schema_left.clone())),
+                Arc::new(EmptyExec::new(false, schema_right.clone())),
+                None,
+                join_type,
+            )?))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip_band_join() -> Result<()> {
+        let schema_left =
+            Arc::new(Schema::new(vec![Field::new("ts", DataType::Int64, false)]));
+        let schema_right = Arc::new(Schema::new(vec![
+            Field::new("start", DataType::Int64, false),
+            Field::new("end", DataType::Int64, false),
+        ]));
+
+        // left.ts >= right.start AND left.ts < right.end
+        let filter_schema = Schema::new(vec![
+            Field::new("ts", DataType::Int64, false),
+            Field::new("start", DataType::Int64, false),
+            Field::new("end", DataType::Int64, false),
+        ]);
+        let column_indices = vec![
+            ColumnIndex {
+                index: 0,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 0,
+                side: JoinSide::Right,
+            },
+            ColumnIndex {
+                index: 1,
+                side: JoinSide::Right,
+            },
+        ];
+        let expression = binary(
+            binary(
+                col("ts", &filter_schema)?,
+                Operator::GtEq,
+                col("start", &filter_schema)?,
+                &filter_schema,
+            )?,
+            Operator::And,
+            binary(
+                col("ts", &filter_schema)?,
+                Operator::Lt,
+                col("end", &filter_schema)?,
+                &filter_schema,
+            )?,
+            &filter_schema,
+        )?;
+        let filter = JoinFilter::new(expression, column_indices, filter_schema);
+        let band = BandCondition::try_new(&filter, &schema_left, &schema_right)?
+            .expect("band condition");
+
+        roundtrip_test(Arc::new(BandJoinExec::try_new(
+            Arc::new(EmptyExec::new(false, schema_left)),
+            Arc::new(EmptyExec::new(false, schema_right)),
+            filter,
+            band,
+        )?))
+    }
+
+    #[test]
+    fn roundtrip_set_operation_all() -> Result<()> {
+        let field_a = Field::new("col", DataType::Int64, false);
+        let schema_left = Arc::new(Schema::new(vec![field_a.clone()]));
+        let schema_right = Arc::new(Schema::new(vec![field_a]));
+
+        for op in [SetOperationType::Intersect, SetOperationType::Except] {
+            roundtrip_test(Arc::new(SetOperationAllExec::try_new(
+                op,
+                Arc::new(EmptyExec::new(false, schema_left.clone())),
+                Arc::new(EmptyExec::new(false, schema_right.clone())),
+            )?))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip_distinct_on() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Int64, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+        let input = Arc::new(EmptyExec::new(false, schema.clone()));
+
+        let on_exprs = vec![col("a", &schema)?];
+        roundtrip_test(Arc::new(DistinctOnExec::new(
+            input.clone(),
+            on_exprs.clone(),
+            None,
+        )))?;
+
+        let sort_exprs = vec![
+            PhysicalSortExpr {
+                expr: col("a", &schema)?,
+                options: SortOptions::default(),
+            },
+            PhysicalSortExpr {
+                expr: col("b", &schema)?,
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: false,
+                },
+            },
+        ];
+        roundtrip_test(Arc::new(DistinctOnExec::new(
+            input,
+            on_exprs,
+            Some(sort_exprs),
+        )))
+    }
+
+    #[test]
+    fn roundtrip_sort_merge_join() -> Result<()> {
+        let field_a = Field::new("col", DataType::Int64, false);
+        let schema_left = Schema::new(vec![field_a.clone()]);
+        let schema_right = Schema::new(vec![field_a]);
+        let on = vec![(
+            Column::new("col", schema_left.index_of("col")?),
+            Column::new("col", schema_right.index_of("col")?),
+        )];
+
+        let schema_left = Arc::new(schema_left);
+        let schema_right = Arc::new(schema_right);
+        for join_type in &[JoinType::Inner, JoinType::Full, JoinType::LeftAnti] {
+            roundtrip_test(Arc::new(SortMergeJoinExec::try_new(
+                Arc::new(EmptyExec::new(false, schema_left.clone())),
+                Arc::new(EmptyExec::new(false, schema_right.clone())),
+                on.clone(),
+                *join_type,
+                vec![SortOptions {
+                    descending: true,
+                    nulls_first: false,
+                }],
+                true,
+            )?))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip_analyze() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "plan_type",
+            DataType::Utf8,
+            false,
+        )]));
+        let input = Arc::new(EmptyExec::new(false, Arc::new(Schema::empty())));
+        roundtrip_test(Arc::new(AnalyzeExec::new(true, input, schema)))
+    }
+
+    #[test]
+    fn roundtrip_window() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Int64, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+        let partition_by = vec![col("a", &schema)?];
+        let order_by = vec![PhysicalSortExpr {
+            expr: col("b", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let window_frame = Arc::new(WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(Some(2))),
+            end_bound: WindowFrameBound::CurrentRow,
+            exclude: WindowFrameExclude::NoOthers,
+        });
+
+        let window_exprs: Vec<Arc<dyn WindowExpr>> = vec![
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+                "ROW_NUMBER()".to_string(),
+                &[],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::Lead),
+                "LEAD(b, 2)".to_string(),
+                &[col("b", &schema)?, lit(ScalarValue::Int64(Some(2)))],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::Ntile),
+                "NTILE(3)".to_string(),
+                &[lit(ScalarValue::Int64(Some(3)))],
+                &partition_by,
+                &order_by,
+                Arc::new(WindowFrame::new(true)),
+                &schema,
+            )?,
+            create_window_expr(
+                &WindowFunction::AggregateFunction(AggregateFunction::Sum),
+                "SUM(b)".to_string(),
+                &[col("b", &schema)?],
+                &partition_by,
+                &order_by,
+                window_frame,
+                &schema,
+            )?,
+        ];
+
+        let input = Arc::new(EmptyExec::new(false, schema.clone()));
+        roundtrip_test(Arc::new(WindowAggExec::try_new(
+            window_exprs.clone(),
+            input.clone(),
+            schema.clone(),
+            partition_by.clone(),
+            Some(order_by.clone()),
+        )?))?;
+        roundtrip_test(Arc::new(BoundedWindowAggExec::try_new(
+            window_exprs,
+            input,
+            schema,
+            partition_by,
+            Some(order_by),
+        )?))
+    }
+
+    #[test]
+    fn roundtrip_aggregate_udaf() -> Result<()> {
+        #[derive(Debug)]
+        struct Example;
+        impl Accumulator for Example {
+            fn state(&self) -> Result<Vec<ScalarValue>> {
+                Ok(vec![ScalarValue::Int64(Some(0))])
+            }
+
+            fn update_batch(&mut self, _values: &[ArrayRef]) -> Result<()> {
+                Ok(())
+            }
+
+            fn merge_batch(&mut self, _states: &[ArrayRef]) -> Result<()> {
+                Ok(())
+            }
+
+            fn evaluate(&self) -> Result<ScalarValue> {
+                Ok(ScalarValue::Int64(Some(0)))
+            }
+
+            fn size(&self) -> usize {
+                0
+            }
+        }
+
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Int64, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+        let udaf = create_udaf(
+            "example",
+            DataType::Int64,
+            Arc::new(DataType::Int64),
+            Volatility::Immutable,
+            Arc::new(|_| Ok(Box::new(Example))),
+            Arc::new(vec![DataType::Int64]),
+        );
+
+        let ctx = SessionContext::new();
+        ctx.register_udaf(udaf.clone());
+
+        let groups: Vec<(Arc<dyn PhysicalExpr>, String)> =
+            vec![(col("a", &schema)?, "unused".to_string())];
+
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![udaf::create_aggregate_expr(
+            &udaf,
+            &[col("b", &schema)?],
+            &schema,
+            "example_agg",
+        )?];
+
+        roundtrip_test_with_context(
+            Arc::new(AggregateExec::try_new(
+                AggregateMode::Final,
+                PhysicalGroupBy::new_single(groups),
+                aggregates,
+                Arc::new(EmptyExec::new(false, schema.clone())),
+                schema,
+            )?),
+            ctx,
+        )
+    }
+
     #[test]
     fn roundtrip_filter_with_not_and_in_list() -> Result<()> {
         let field_a = Field::new("a", DataType::Boolean, false);
@@ -1441,6 +2432,59 @@ mod roundtrip_tests {
         )?))
     }
 
+    #[test]
+    fn roundtrip_partial_sort() -> Result<()> {
+        let field_a = Field::new("a", DataType::Boolean, false);
+        let field_b = Field::new("b", DataType::Int64, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+        let sort_exprs = vec![
+            PhysicalSortExpr {
+                expr: col("a", &schema)?,
+                options: SortOptions::default(),
+            },
+            PhysicalSortExpr {
+                expr: col("b", &schema)?,
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: false,
+                },
+            },
+        ];
+        roundtrip_test(Arc::new(PartialSortExec::try_new(
+            sort_exprs.clone(),
+            Arc::new(EmptyExec::new(false, schema.clone())),
+            1,
+            None,
+        )?))?;
+        roundtrip_test(Arc::new(PartialSortExec::try_new(
+            sort_exprs,
+            Arc::new(EmptyExec::new(false, schema)),
+            1,
+            Some(5),
+        )?))
+    }
+
+    #[test]
+    fn roundtrip_sort_preserving_merge() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let sort_exprs = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+        roundtrip_test(Arc::new(SortPreservingMergeExec::new(
+            sort_exprs.clone(),
+            Arc::new(EmptyExec::new(false, schema.clone())),
+        )))?;
+        roundtrip_test(Arc::new(
+            SortPreservingMergeExec::new(
+                sort_exprs,
+                Arc::new(EmptyExec::new(false, schema)),
+            )
+            .with_skip(10)
+            .with_fetch(Some(5)),
+        ))
+    }
+
     #[test]
     fn roundtrip_parquet_exec_with_pruning_predicate() -> Result<()> {
         let scan_config = FileScanConfig {
@@ -1450,15 +2494,28 @@ mod roundtrip_tests {
                 DataType::Utf8,
                 false,
             )])),
-            file_groups: vec![vec![PartitionedFile::new(
-                "/path/to/file.parquet".to_string(),
-                1024,
-            )]],
+            file_groups: vec![vec![PartitionedFile {
+                statistics: Some(Statistics {
+                    num_rows: Precision::Exact(100),
+                    total_byte_size: Precision::Exact(1024),
+                    column_statistics: vec![ColumnStatistics {
+                        null_count: Precision::Exact(0),
+                        max_value: Precision::Exact(ScalarValue::from("z")),
+                        min_value: Precision::Exact(ScalarValue::from("a")),
+                        distinct_count: Precision::Absent,
+                    }],
+                }),
+                ..PartitionedFile::new("/path/to/file.parquet".to_string(), 1024)
+            }]],
             statistics: Statistics {
-                num_rows: Some(100),
-                total_byte_size: Some(1024),
-                column_statistics: None,
-                is_exact: false,
+                num_rows: Precision::Inexact(100),
+                total_byte_size: Precision::Inexact(1024),
+                column_statistics: vec![ColumnStatistics {
+                    null_count: Precision::Inexact(0),
+                    max_value: Precision::Inexact(ScalarValue::from("z")),
+                    min_value: Precision::Inexact(ScalarValue::from("a")),
+                    distinct_count: Precision::Absent,
+                }],
             },
             projection: None,
             limit: None,
@@ -1475,6 +2532,43 @@ mod roundtrip_tests {
         )))
     }
 
+    #[test]
+    fn roundtrip_json_and_csv_exec_with_compression() -> Result<()> {
+        let scan_config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_schema: Arc::new(Schema::new(vec![Field::new(
+                "col",
+                DataType::Utf8,
+                false,
+            )])),
+            file_groups: vec![vec![PartitionedFile::new(
+                "/path/to/file.gz".to_string(),
+                1024,
+            )]],
+            statistics: Statistics::new_unknown(&Schema::new(vec![Field::new(
+                "col",
+                DataType::Utf8,
+                false,
+            )])),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            output_ordering: None,
+            infinite_source: false,
+        };
+
+        roundtrip_test(Arc::new(NdJsonExec::new(
+            scan_config.clone(),
+            FileCompressionType::GZIP,
+        )))?;
+        roundtrip_test(Arc::new(CsvExec::new(
+            scan_config,
+            true,
+            b',',
+            FileCompressionType::BZIP2,
+        )))
+    }
+
     #[test]
     fn roundtrip_builtin_scalar_function() -> Result<()> {
         let field_a = Field::new("a", DataType::Int64, false);
@@ -1540,6 +2634,143 @@ mod roundtrip_tests {
         roundtrip_test_with_context(Arc::new(project), ctx)
     }
 
+    /// Creates a UDF whose state, the factor to multiply by, lives in its closure
+    fn scale_udf(factor: i64) -> ScalarUDF {
+        let fun = make_scalar_function(move |args: &[ArrayRef]| {
+            let input = args[0].as_any().downcast_ref::<Int64Array>().unwrap();
+            Ok(Arc::new(
+                input
+                    .iter()
+                    .map(|v| v.map(|v| v * factor))
+                    .collect::<Int64Array>(),
+            ) as ArrayRef)
+        });
+        create_udf(
+            "scale",
+            vec![DataType::Int64],
+            Arc::new(DataType::Int64),
+            Volatility::Immutable,
+            fun,
+        )
+    }
+
+    fn scale_factor(udf: &ScalarUDF) -> Result<i64> {
+        match (udf.fun)(&[ColumnarValue::Scalar(ScalarValue::Int64(Some(1)))])? {
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(factor))) => Ok(factor),
+            other => Err(DataFusionError::Internal(format!(
+                "Unexpected result of scale: {other:?}"
+            ))),
+        }
+    }
+
+    #[derive(Debug)]
+    struct ScaleUdfCodec {}
+
+    impl PhysicalExtensionCodec for ScaleUdfCodec {
+        fn try_decode(
+            &self,
+            _buf: &[u8],
+            _inputs: &[Arc<dyn ExecutionPlan>],
+            _registry: &dyn FunctionRegistry,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            unreachable!()
+        }
+
+        fn try_encode(
+            &self,
+            _node: Arc<dyn ExecutionPlan>,
+            _buf: &mut Vec<u8>,
+        ) -> Result<()> {
+            unreachable!()
+        }
+
+        fn try_decode_udf(&self, name: &str, buf: &[u8]) -> Result<Arc<ScalarUDF>> {
+            assert_eq!(name, "scale");
+            let factor = i64::from_le_bytes(buf.try_into().unwrap());
+            Ok(Arc::new(scale_udf(factor)))
+        }
+
+        fn try_encode_udf(&self, node: &ScalarUDF, buf: &mut Vec<u8>) -> Result<()> {
+            buf.extend_from_slice(&scale_factor(node)?.to_le_bytes());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn roundtrip_scalar_udf_with_state() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let input = Arc::new(EmptyExec::new(false, schema.clone()));
+        let udf = scale_udf(3);
+        let expr = ScalarFunctionExpr::new(
+            "scale",
+            udf.fun.clone(),
+            vec![col("a", &schema)?],
+            &DataType::Int64,
+        )
+        .with_udf(Arc::new(udf));
+        let project: Arc<dyn ExecutionPlan> = Arc::new(ProjectionExec::try_new(
+            vec![(Arc::new(expr), "a".to_string())],
+            input,
+        )?);
+
+        // the UDF is not registered, so it can only be rebuilt from its state
+        let ctx = SessionContext::new();
+        let codec = ScaleUdfCodec {};
+        let proto =
+            protobuf::PhysicalPlanNode::try_from_physical_plan(project.clone(), &codec)?;
+        let result =
+            proto.try_into_physical_plan(&ctx, ctx.runtime_env().deref(), &codec)?;
+        assert_eq!(format!("{project:?}"), format!("{result:?}"));
+
+        let result = result.as_any().downcast_ref::<ProjectionExec>().unwrap();
+        let expr = result.expr()[0]
+            .0
+            .as_any()
+            .downcast_ref::<ScalarFunctionExpr>()
+            .unwrap();
+        assert_eq!(scale_factor(expr.udf().unwrap())?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_udf_with_other_signature() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let input = Arc::new(EmptyExec::new(false, schema.clone()));
+        let scalar_fn = make_scalar_function(|args: &[ArrayRef]| Ok(args[0].clone()));
+        let expr = ScalarFunctionExpr::new(
+            "dummy",
+            scalar_fn.clone(),
+            vec![col("a", &schema)?],
+            &DataType::Int64,
+        );
+        let project =
+            ProjectionExec::try_new(vec![(Arc::new(expr), "a".to_string())], input)?;
+        let codec = DefaultPhysicalExtensionCodec {};
+        let proto = protobuf::PhysicalPlanNode::try_from_physical_plan(
+            Arc::new(project),
+            &codec,
+        )?;
+
+        // a function of the same name, but taking and returning strings
+        let ctx = SessionContext::new();
+        ctx.register_udf(create_udf(
+            "dummy",
+            vec![DataType::Utf8],
+            Arc::new(DataType::Utf8),
+            Volatility::Immutable,
+            scalar_fn,
+        ));
+        let err = proto
+            .try_into_physical_plan(&ctx, ctx.runtime_env().deref(), &codec)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("User defined function 'dummy' does not accept"),
+            "{err}"
+        );
+        Ok(())
+    }
+
     #[test]
     fn roundtrip_distinct_count() -> Result<()> {
         let field_a = Field::new("a", DataType::Int64, false);
@@ -28,7 +28,7 @@ use datafusion::physical_plan::{
     expressions::{
         CaseExpr, InListExpr, IsNotNullExpr, IsNullExpr, NegativeExpr, NotExpr,
     },
-    Statistics,
+    Precision, Statistics,
 };
 
 use datafusion::datasource::listing::{FileRange, PartitionedFile};
@@ -37,327 +37,624 @@ use datafusion::physical_plan::file_format::FileScanConfig;
 use datafusion::physical_plan::expressions::{Count, DistinctCount, Literal};
 
 use datafusion::physical_plan::expressions::{
-    Avg, BinaryExpr, Column, LikeExpr, Max, Min, Sum,
+    Avg, BinaryExpr, Column, CumeDist, LikeExpr, Max, Min, NthValue, NthValueKind, Ntile,
+    PhysicalSortExpr, Rank, RankType, RowNumber, Sum, WindowShift,
 };
-use datafusion::physical_plan::{AggregateExpr, PhysicalExpr};
+use datafusion::physical_plan::udaf::AggregateFunctionExpr;
+use datafusion::physical_plan::windows::{
+    AggregateWindowExpr, BuiltInWindowExpr, SlidingAggregateWindowExpr,
+};
+use datafusion::physical_plan::{AggregateExpr, PhysicalExpr, WindowExpr};
 
+use crate::function_registry::udaf_fingerprint;
+use crate::physical_plan::PhysicalExtensionCodec;
 use crate::protobuf;
 use crate::protobuf::PhysicalSortExprNode;
 use datafusion::logical_expr::BuiltinScalarFunction;
 use datafusion::physical_expr::expressions::DateTimeIntervalExpr;
 use datafusion::physical_expr::ScalarFunctionExpr;
-use datafusion::physical_plan::joins::utils::JoinSide;
-use datafusion_common::DataFusionError;
+use datafusion::physical_plan::joins::utils::{JoinFilter, JoinSide};
+use datafusion_common::{DataFusionError, ScalarValue};
 
-impl TryFrom<Arc<dyn AggregateExpr>> for protobuf::PhysicalExprNode {
-    type Error = DataFusionError;
+/// Serializes an aggregate expression, encoding the state of user defined
+/// functions with `codec`
+pub fn serialize_physical_aggr_expr(
+    a: Arc<dyn AggregateExpr>,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<protobuf::PhysicalExprNode, DataFusionError> {
+    use datafusion::physical_plan::expressions;
+    use protobuf::physical_aggregate_expr_node;
+    use protobuf::AggregateFunction;
 
-    fn try_from(a: Arc<dyn AggregateExpr>) -> Result<Self, Self::Error> {
-        use datafusion::physical_plan::expressions;
-        use protobuf::AggregateFunction;
-
-        let mut distinct = false;
-        let aggr_function = if a.as_any().downcast_ref::<Avg>().is_some() {
-            Ok(AggregateFunction::Avg.into())
-        } else if a.as_any().downcast_ref::<Sum>().is_some() {
-            Ok(AggregateFunction::Sum.into())
-        } else if a.as_any().downcast_ref::<Count>().is_some() {
-            Ok(AggregateFunction::Count.into())
-        } else if a.as_any().downcast_ref::<DistinctCount>().is_some() {
-            distinct = true;
-            Ok(AggregateFunction::Count.into())
-        } else if a.as_any().downcast_ref::<Min>().is_some() {
-            Ok(AggregateFunction::Min.into())
-        } else if a.as_any().downcast_ref::<Max>().is_some() {
-            Ok(AggregateFunction::Max.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::ApproxDistinct>()
-            .is_some()
-        {
-            Ok(AggregateFunction::ApproxDistinct.into())
-        } else if a.as_any().downcast_ref::<expressions::ArrayAgg>().is_some() {
-            Ok(AggregateFunction::ArrayAgg.into())
-        } else if a.as_any().downcast_ref::<expressions::Variance>().is_some() {
-            Ok(AggregateFunction::Variance.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::VariancePop>()
-            .is_some()
-        {
-            Ok(AggregateFunction::VariancePop.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::Covariance>()
-            .is_some()
-        {
-            Ok(AggregateFunction::Covariance.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::CovariancePop>()
-            .is_some()
-        {
-            Ok(AggregateFunction::CovariancePop.into())
-        } else if a.as_any().downcast_ref::<expressions::Stddev>().is_some() {
-            Ok(AggregateFunction::Stddev.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::StddevPop>()
-            .is_some()
-        {
-            Ok(AggregateFunction::StddevPop.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::Correlation>()
-            .is_some()
-        {
-            Ok(AggregateFunction::Correlation.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::ApproxPercentileCont>()
-            .is_some()
-        {
-            Ok(AggregateFunction::ApproxPercentileCont.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::ApproxPercentileContWithWeight>()
+    let mut expressions: Vec<protobuf::PhysicalExprNode> = a
+        .expressions()
+        .iter()
+        .map(|e| serialize_physical_expr(e.clone(), codec))
+        .collect::<Result<Vec<_>, DataFusionError>>()?;
+
+    // the sort keys of an ordered ARRAY_AGG follow its argument in its
+    // expressions, and are serialized with their sort options instead
+    let mut ordering_req = vec![];
+    if let Some(array_agg) = a
+        .as_any()
+        .downcast_ref::<expressions::OrderSensitiveArrayAgg>()
+    {
+        expressions.truncate(1);
+        ordering_req = array_agg
+            .ordering_req()
+            .iter()
+            .map(|sort| serialize_physical_sort_expr(sort, codec))
+            .collect::<Result<Vec<_>, DataFusionError>>()?;
+    }
+
+    if let Some(udaf_expr) = a.as_any().downcast_ref::<AggregateFunctionExpr>() {
+        let mut fun_definition = vec![];
+        codec.try_encode_udaf(udaf_expr.fun(), &mut fun_definition)?;
+        return Ok(protobuf::PhysicalExprNode {
+                expr_type: Some(protobuf::physical_expr_node::ExprType::AggregateExpr(
+                    protobuf::PhysicalAggregateExprNode {
+                        aggregate_function: Some(
+                            physical_aggregate_expr_node::AggregateFunction::UserDefinedAggrFunction(
+                                udaf_expr.fun().name.clone(),
+                            ),
+                        ),
+                        expr: expressions,
+                        distinct: false,
+                        fingerprint: udaf_fingerprint(udaf_expr.fun()),
+                        ordering_req,
+                        fun_definition,
+                    },
+                )),
+            });
+    }
+
+    let mut distinct = false;
+    let aggr_function = if a.as_any().downcast_ref::<Avg>().is_some() {
+        Ok(AggregateFunction::Avg.into())
+    } else if a.as_any().downcast_ref::<Sum>().is_some() {
+        Ok(AggregateFunction::Sum.into())
+    } else if a.as_any().downcast_ref::<Count>().is_some() {
+        Ok(AggregateFunction::Count.into())
+    } else if a.as_any().downcast_ref::<DistinctCount>().is_some() {
+        distinct = true;
+        Ok(AggregateFunction::Count.into())
+    } else if a.as_any().downcast_ref::<Min>().is_some() {
+        Ok(AggregateFunction::Min.into())
+    } else if a.as_any().downcast_ref::<Max>().is_some() {
+        Ok(AggregateFunction::Max.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::ApproxDistinct>()
+        .is_some()
+    {
+        Ok(AggregateFunction::ApproxDistinct.into())
+    } else if a.as_any().downcast_ref::<expressions::ArrayAgg>().is_some()
+        || a.as_any()
+            .downcast_ref::<expressions::OrderSensitiveArrayAgg>()
             .is_some()
+    {
+        Ok(AggregateFunction::ArrayAgg.into())
+    } else if a.as_any().downcast_ref::<expressions::Variance>().is_some() {
+        Ok(AggregateFunction::Variance.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::VariancePop>()
+        .is_some()
+    {
+        Ok(AggregateFunction::VariancePop.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::Covariance>()
+        .is_some()
+    {
+        Ok(AggregateFunction::Covariance.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::CovariancePop>()
+        .is_some()
+    {
+        Ok(AggregateFunction::CovariancePop.into())
+    } else if a.as_any().downcast_ref::<expressions::Stddev>().is_some() {
+        Ok(AggregateFunction::Stddev.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::StddevPop>()
+        .is_some()
+    {
+        Ok(AggregateFunction::StddevPop.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::Correlation>()
+        .is_some()
+    {
+        Ok(AggregateFunction::Correlation.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::ApproxPercentileCont>()
+        .is_some()
+    {
+        Ok(AggregateFunction::ApproxPercentileCont.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::ApproxPercentileContWithWeight>()
+        .is_some()
+    {
+        Ok(AggregateFunction::ApproxPercentileContWithWeight.into())
+    } else if a
+        .as_any()
+        .downcast_ref::<expressions::ApproxMedian>()
+        .is_some()
+    {
+        Ok(AggregateFunction::ApproxMedian.into())
+    } else {
+        Err(DataFusionError::NotImplemented(format!(
+            "Aggregate function not supported: {a:?}"
+        )))
+    }?;
+    Ok(protobuf::PhysicalExprNode {
+        expr_type: Some(protobuf::physical_expr_node::ExprType::AggregateExpr(
+            protobuf::PhysicalAggregateExprNode {
+                aggregate_function: Some(
+                    physical_aggregate_expr_node::AggregateFunction::AggrFunction(
+                        aggr_function,
+                    ),
+                ),
+                expr: expressions,
+                distinct,
+                fingerprint: 0,
+                ordering_req,
+                fun_definition: vec![],
+            },
+        )),
+    })
+}
+
+/// Serializes a window expression, encoding the state of user defined
+/// functions with `codec`
+pub fn serialize_physical_window_expr(
+    window_expr: Arc<dyn WindowExpr>,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<protobuf::PhysicalWindowExprNode, DataFusionError> {
+    use protobuf::physical_window_expr_node::WindowFunction;
+
+    let expr = window_expr.as_any();
+    let mut args = window_expr.expressions();
+
+    let (window_function, fingerprint, fun_definition) =
+        if let Some(built_in_window_expr) = expr.downcast_ref::<BuiltInWindowExpr>() {
+            let built_in_fn_expr = built_in_window_expr.get_built_in_func_expr();
+            let built_in_fn = built_in_fn_expr.as_any();
+            // Arguments that were folded into the function at planning time are
+            // turned back into literals so the function can be re-created
+            let fun = if built_in_fn.downcast_ref::<RowNumber>().is_some() {
+                protobuf::BuiltInWindowFunction::RowNumber
+            } else if let Some(rank) = built_in_fn.downcast_ref::<Rank>() {
+                match rank.get_type() {
+                    RankType::Basic => protobuf::BuiltInWindowFunction::Rank,
+                    RankType::Dense => protobuf::BuiltInWindowFunction::DenseRank,
+                    RankType::Percent => protobuf::BuiltInWindowFunction::PercentRank,
+                }
+            } else if built_in_fn.downcast_ref::<CumeDist>().is_some() {
+                protobuf::BuiltInWindowFunction::CumeDist
+            } else if let Some(ntile) = built_in_fn.downcast_ref::<Ntile>() {
+                args.insert(
+                    0,
+                    Arc::new(Literal::new(ScalarValue::Int64(
+                        Some(ntile.get_n() as i64),
+                    ))),
+                );
+                protobuf::BuiltInWindowFunction::Ntile
+            } else if let Some(window_shift) = built_in_fn.downcast_ref::<WindowShift>() {
+                // lead stores a negated offset
+                let shift_offset = window_shift.get_shift_offset();
+                args.push(Arc::new(Literal::new(ScalarValue::Int64(Some(
+                    shift_offset.abs(),
+                )))));
+                if let Some(default_value) = window_shift.get_default_value() {
+                    args.push(Arc::new(Literal::new(default_value)));
+                }
+                if shift_offset < 0 {
+                    protobuf::BuiltInWindowFunction::Lead
+                } else {
+                    protobuf::BuiltInWindowFunction::Lag
+                }
+            } else if let Some(nth_value) = built_in_fn.downcast_ref::<NthValue>() {
+                match nth_value.get_kind() {
+                    NthValueKind::First => protobuf::BuiltInWindowFunction::FirstValue,
+                    NthValueKind::Last => protobuf::BuiltInWindowFunction::LastValue,
+                    NthValueKind::Nth(n) => {
+                        args.push(Arc::new(Literal::new(ScalarValue::Int64(Some(
+                            n as i64,
+                        )))));
+                        protobuf::BuiltInWindowFunction::NthValue
+                    }
+                }
+            } else {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "BuiltIn window function not supported: {built_in_fn_expr:?}"
+                )));
+            };
+            (WindowFunction::BuiltInFunction(fun as i32), 0, vec![])
+        } else if let Some(aggr_window_expr) = expr.downcast_ref::<AggregateWindowExpr>()
         {
-            Ok(AggregateFunction::ApproxPercentileContWithWeight.into())
-        } else if a
-            .as_any()
-            .downcast_ref::<expressions::ApproxMedian>()
-            .is_some()
+            aggr_expr_to_window_function(aggr_window_expr.get_aggregate_expr(), codec)?
+        } else if let Some(sliding_aggr_window_expr) =
+            expr.downcast_ref::<SlidingAggregateWindowExpr>()
         {
-            Ok(AggregateFunction::ApproxMedian.into())
+            aggr_expr_to_window_function(
+                sliding_aggr_window_expr.get_aggregate_expr(),
+                codec,
+            )?
         } else {
-            Err(DataFusionError::NotImplemented(format!(
-                "Aggregate function not supported: {a:?}"
-            )))
-        }?;
-        let expressions: Vec<protobuf::PhysicalExprNode> = a
-            .expressions()
-            .iter()
-            .map(|e| e.clone().try_into())
-            .collect::<Result<Vec<_>, DataFusionError>>()?;
-        Ok(protobuf::PhysicalExprNode {
-            expr_type: Some(protobuf::physical_expr_node::ExprType::AggregateExpr(
-                protobuf::PhysicalAggregateExprNode {
-                    aggr_function,
-                    expr: expressions,
-                    distinct,
-                },
-            )),
-        })
+            return Err(DataFusionError::NotImplemented(format!(
+                "WindowExpr not supported: {window_expr:?}"
+            )));
+        };
+
+    let args = args
+        .into_iter()
+        .map(|e| serialize_physical_expr(e, codec))
+        .collect::<Result<Vec<_>, DataFusionError>>()?;
+    let partition_by = window_expr
+        .partition_by()
+        .iter()
+        .map(|e| serialize_physical_expr(e.clone(), codec))
+        .collect::<Result<Vec<_>, DataFusionError>>()?;
+    let order_by = window_expr
+        .order_by()
+        .iter()
+        .map(|o| serialize_physical_sort_expr(o, codec))
+        .collect::<Result<Vec<_>, DataFusionError>>()?;
+    let window_frame = window_expr.get_window_frame().as_ref().try_into()?;
+
+    Ok(protobuf::PhysicalWindowExprNode {
+        window_function: Some(window_function),
+        args,
+        partition_by,
+        order_by,
+        window_frame: Some(window_frame),
+        fingerprint,
+        fun_definition,
+    })
+}
+
+/// Converts the aggregate function of an aggregate window expression,
+/// returning it along with its fingerprint and encoded state
+fn aggr_expr_to_window_function(
+    aggr_expr: &Arc<dyn AggregateExpr>,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<
+    (
+        protobuf::physical_window_expr_node::WindowFunction,
+        u64,
+        Vec<u8>,
+    ),
+    DataFusionError,
+> {
+    use protobuf::physical_aggregate_expr_node::AggregateFunction;
+    use protobuf::physical_window_expr_node::WindowFunction;
+
+    let node = serialize_physical_aggr_expr(aggr_expr.clone(), codec)?;
+    match node.expr_type {
+        Some(protobuf::physical_expr_node::ExprType::AggregateExpr(
+            protobuf::PhysicalAggregateExprNode {
+                aggregate_function: Some(aggregate_function),
+                distinct: false,
+                fingerprint,
+                fun_definition,
+                ..
+            },
+        )) => {
+            let window_function = match aggregate_function {
+                AggregateFunction::AggrFunction(fun) => WindowFunction::AggrFunction(fun),
+                AggregateFunction::UserDefinedAggrFunction(name) => {
+                    WindowFunction::UserDefinedAggrFunction(name)
+                }
+            };
+            Ok((window_function, fingerprint, fun_definition))
+        }
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Aggregate window function not supported: {aggr_expr:?}"
+        ))),
     }
 }
 
-impl TryFrom<Arc<dyn PhysicalExpr>> for protobuf::PhysicalExprNode {
-    type Error = DataFusionError;
+/// Serializes a join filter, encoding the state of user defined functions
+/// with `codec`
+pub fn serialize_join_filter(
+    filter: &JoinFilter,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<protobuf::JoinFilter, DataFusionError> {
+    let expression = serialize_physical_expr(filter.expression().to_owned(), codec)?;
+    let column_indices = filter
+        .column_indices()
+        .iter()
+        .map(|i| {
+            let side: protobuf::JoinSide = i.side.to_owned().into();
+            protobuf::ColumnIndex {
+                index: i.index as u32,
+                side: side.into(),
+            }
+        })
+        .collect();
+    let schema = filter.schema().try_into()?;
+    Ok(protobuf::JoinFilter {
+        expression: Some(expression),
+        column_indices,
+        schema: Some(schema),
+    })
+}
 
-    fn try_from(value: Arc<dyn PhysicalExpr>) -> Result<Self, Self::Error> {
-        let expr = value.as_any();
+/// Serializes a sort expression, encoding the state of user defined
+/// functions with `codec`
+pub fn serialize_physical_sort_expr(
+    sort_expr: &PhysicalSortExpr,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<protobuf::PhysicalSortExprNode, DataFusionError> {
+    Ok(protobuf::PhysicalSortExprNode {
+        expr: Some(Box::new(serialize_physical_expr(
+            sort_expr.expr.clone(),
+            codec,
+        )?)),
+        asc: !sort_expr.options.descending,
+        nulls_first: sort_expr.options.nulls_first,
+    })
+}
 
-        if let Some(expr) = expr.downcast_ref::<Column>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::Column(
-                    protobuf::PhysicalColumn {
-                        name: expr.name().to_string(),
-                        index: expr.index() as u32,
-                    },
-                )),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<BinaryExpr>() {
-            let binary_expr = Box::new(protobuf::PhysicalBinaryExprNode {
-                l: Some(Box::new(expr.left().to_owned().try_into()?)),
-                r: Some(Box::new(expr.right().to_owned().try_into()?)),
-                op: format!("{:?}", expr.op()),
-            });
+/// Serializes a physical expression, encoding the state of user defined
+/// functions with `codec`
+pub fn serialize_physical_expr(
+    value: Arc<dyn PhysicalExpr>,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<protobuf::PhysicalExprNode, DataFusionError> {
+    let expr = value.as_any();
 
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::BinaryExpr(
-                    binary_expr,
-                )),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<CaseExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(
-                    protobuf::physical_expr_node::ExprType::Case(
-                        Box::new(
-                            protobuf::PhysicalCaseNode {
-                                expr: expr
-                                    .expr()
-                                    .as_ref()
-                                    .map(|exp| exp.clone().try_into().map(Box::new))
-                                    .transpose()?,
-                                when_then_expr: expr
-                                    .when_then_expr()
-                                    .iter()
-                                    .map(|(when_expr, then_expr)| {
-                                        try_parse_when_then_expr(when_expr, then_expr)
-                                    })
-                                    .collect::<Result<
-                                        Vec<protobuf::PhysicalWhenThen>,
-                                        Self::Error,
-                                    >>()?,
-                                else_expr: expr
-                                    .else_expr()
-                                    .map(|a| a.clone().try_into().map(Box::new))
-                                    .transpose()?,
-                            },
-                        ),
+    if let Some(expr) = expr.downcast_ref::<Column>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::Column(
+                protobuf::PhysicalColumn {
+                    name: expr.name().to_string(),
+                    index: expr.index() as u32,
+                },
+            )),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<BinaryExpr>() {
+        let binary_expr = Box::new(protobuf::PhysicalBinaryExprNode {
+            l: Some(Box::new(serialize_physical_expr(
+                expr.left().to_owned(),
+                codec,
+            )?)),
+            r: Some(Box::new(serialize_physical_expr(
+                expr.right().to_owned(),
+                codec,
+            )?)),
+            op: format!("{:?}", expr.op()),
+        });
+
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::BinaryExpr(
+                binary_expr,
+            )),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<CaseExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(
+                protobuf::physical_expr_node::ExprType::Case(
+                    Box::new(
+                        protobuf::PhysicalCaseNode {
+                            expr: expr
+                                .expr()
+                                .as_ref()
+                                .map(|exp| {
+                                    serialize_physical_expr(exp.clone(), codec)
+                                        .map(Box::new)
+                                })
+                                .transpose()?,
+                            when_then_expr: expr
+                                .when_then_expr()
+                                .iter()
+                                .map(|(when_expr, then_expr)| {
+                                    try_parse_when_then_expr(when_expr, then_expr, codec)
+                                })
+                                .collect::<Result<
+                                    Vec<protobuf::PhysicalWhenThen>,
+                                    DataFusionError,
+                                >>()?,
+                            else_expr: expr
+                                .else_expr()
+                                .map(|a| {
+                                    serialize_physical_expr(a.clone(), codec)
+                                        .map(Box::new)
+                                })
+                                .transpose()?,
+                        },
                     ),
                 ),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<NotExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::NotExpr(
-                    Box::new(protobuf::PhysicalNot {
-                        expr: Some(Box::new(expr.arg().to_owned().try_into()?)),
-                    }),
-                )),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<IsNullExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::IsNullExpr(
-                    Box::new(protobuf::PhysicalIsNull {
-                        expr: Some(Box::new(expr.arg().to_owned().try_into()?)),
-                    }),
-                )),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<IsNotNullExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::IsNotNullExpr(
-                    Box::new(protobuf::PhysicalIsNotNull {
-                        expr: Some(Box::new(expr.arg().to_owned().try_into()?)),
-                    }),
-                )),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<InListExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(
-                    protobuf::physical_expr_node::ExprType::InList(
-                        Box::new(
-                            protobuf::PhysicalInListNode {
-                                expr: Some(Box::new(expr.expr().to_owned().try_into()?)),
-                                list: expr
-                                    .list()
-                                    .iter()
-                                    .map(|a| a.clone().try_into())
-                                    .collect::<Result<
+            ),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<NotExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::NotExpr(Box::new(
+                protobuf::PhysicalNot {
+                    expr: Some(Box::new(serialize_physical_expr(
+                        expr.arg().to_owned(),
+                        codec,
+                    )?)),
+                },
+            ))),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<IsNullExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::IsNullExpr(
+                Box::new(protobuf::PhysicalIsNull {
+                    expr: Some(Box::new(serialize_physical_expr(
+                        expr.arg().to_owned(),
+                        codec,
+                    )?)),
+                }),
+            )),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<IsNotNullExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::IsNotNullExpr(
+                Box::new(protobuf::PhysicalIsNotNull {
+                    expr: Some(Box::new(serialize_physical_expr(
+                        expr.arg().to_owned(),
+                        codec,
+                    )?)),
+                }),
+            )),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<InListExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(
+                protobuf::physical_expr_node::ExprType::InList(
+                    Box::new(
+                        protobuf::PhysicalInListNode {
+                            expr: Some(Box::new(serialize_physical_expr(
+                                expr.expr().to_owned(),
+                                codec,
+                            )?)),
+                            list: expr
+                                .list()
+                                .iter()
+                                .map(|a| serialize_physical_expr(a.clone(), codec))
+                                .collect::<Result<
                                     Vec<protobuf::PhysicalExprNode>,
-                                    Self::Error,
+                                    DataFusionError,
                                 >>()?,
-                                negated: expr.negated(),
-                            },
-                        ),
+                            negated: expr.negated(),
+                        },
                     ),
                 ),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<NegativeExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::Negative(
-                    Box::new(protobuf::PhysicalNegativeNode {
-                        expr: Some(Box::new(expr.arg().to_owned().try_into()?)),
-                    }),
-                )),
-            })
-        } else if let Some(lit) = expr.downcast_ref::<Literal>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::Literal(
-                    lit.value().try_into()?,
-                )),
-            })
-        } else if let Some(cast) = expr.downcast_ref::<CastExpr>() {
+            ),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<NegativeExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::Negative(Box::new(
+                protobuf::PhysicalNegativeNode {
+                    expr: Some(Box::new(serialize_physical_expr(
+                        expr.arg().to_owned(),
+                        codec,
+                    )?)),
+                },
+            ))),
+        })
+    } else if let Some(lit) = expr.downcast_ref::<Literal>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::Literal(
+                lit.value().try_into()?,
+            )),
+        })
+    } else if let Some(cast) = expr.downcast_ref::<CastExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::Cast(Box::new(
+                protobuf::PhysicalCastNode {
+                    expr: Some(Box::new(serialize_physical_expr(
+                        cast.expr().clone(),
+                        codec,
+                    )?)),
+                    arrow_type: Some(cast.cast_type().try_into()?),
+                },
+            ))),
+        })
+    } else if let Some(cast) = expr.downcast_ref::<TryCastExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::TryCast(Box::new(
+                protobuf::PhysicalTryCastNode {
+                    expr: Some(Box::new(serialize_physical_expr(
+                        cast.expr().clone(),
+                        codec,
+                    )?)),
+                    arrow_type: Some(cast.cast_type().try_into()?),
+                },
+            ))),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<ScalarFunctionExpr>() {
+        let args: Vec<protobuf::PhysicalExprNode> = expr
+            .args()
+            .iter()
+            .map(|e| serialize_physical_expr(e.to_owned(), codec))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Ok(fun) = BuiltinScalarFunction::from_str(expr.name()) {
+            let fun: protobuf::ScalarFunction = (&fun).try_into()?;
+
             Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::Cast(Box::new(
-                    protobuf::PhysicalCastNode {
-                        expr: Some(Box::new(cast.expr().clone().try_into()?)),
-                        arrow_type: Some(cast.cast_type().try_into()?),
+                expr_type: Some(protobuf::physical_expr_node::ExprType::ScalarFunction(
+                    protobuf::PhysicalScalarFunctionNode {
+                        name: expr.name().to_string(),
+                        fun: fun.into(),
+                        args,
+                        return_type: Some(expr.return_type().try_into()?),
                     },
-                ))),
-            })
-        } else if let Some(cast) = expr.downcast_ref::<TryCastExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::TryCast(
-                    Box::new(protobuf::PhysicalTryCastNode {
-                        expr: Some(Box::new(cast.expr().clone().try_into()?)),
-                        arrow_type: Some(cast.cast_type().try_into()?),
-                    }),
                 )),
             })
-        } else if let Some(expr) = expr.downcast_ref::<ScalarFunctionExpr>() {
-            let args: Vec<protobuf::PhysicalExprNode> = expr
-                .args()
-                .iter()
-                .map(|e| e.to_owned().try_into())
-                .collect::<Result<Vec<_>, _>>()?;
-            if let Ok(fun) = BuiltinScalarFunction::from_str(expr.name()) {
-                let fun: protobuf::ScalarFunction = (&fun).try_into()?;
-
-                Ok(protobuf::PhysicalExprNode {
-                    expr_type: Some(
-                        protobuf::physical_expr_node::ExprType::ScalarFunction(
-                            protobuf::PhysicalScalarFunctionNode {
-                                name: expr.name().to_string(),
-                                fun: fun.into(),
-                                args,
-                                return_type: Some(expr.return_type().try_into()?),
-                            },
-                        ),
-                    ),
-                })
-            } else {
-                Ok(protobuf::PhysicalExprNode {
-                    expr_type: Some(protobuf::physical_expr_node::ExprType::ScalarUdf(
-                        protobuf::PhysicalScalarUdfNode {
-                            name: expr.name().to_string(),
-                            args,
-                            return_type: Some(expr.return_type().try_into()?),
-                        },
-                    )),
-                })
+        } else {
+            let mut fun_definition = vec![];
+            if let Some(udf) = expr.udf() {
+                codec.try_encode_udf(udf, &mut fun_definition)?;
             }
-        } else if let Some(expr) = expr.downcast_ref::<DateTimeIntervalExpr>() {
-            let dti_expr = Box::new(protobuf::PhysicalDateTimeIntervalExprNode {
-                l: Some(Box::new(expr.lhs().to_owned().try_into()?)),
-                r: Some(Box::new(expr.rhs().to_owned().try_into()?)),
-                op: format!("{:?}", expr.op()),
-            });
-
             Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(
-                    protobuf::physical_expr_node::ExprType::DateTimeIntervalExpr(
-                        dti_expr,
-                    ),
-                ),
-            })
-        } else if let Some(expr) = expr.downcast_ref::<LikeExpr>() {
-            Ok(protobuf::PhysicalExprNode {
-                expr_type: Some(protobuf::physical_expr_node::ExprType::LikeExpr(
-                    Box::new(protobuf::PhysicalLikeExprNode {
-                        negated: expr.negated(),
-                        case_insensitive: expr.case_insensitive(),
-                        expr: Some(Box::new(expr.expr().to_owned().try_into()?)),
-                        pattern: Some(Box::new(expr.pattern().to_owned().try_into()?)),
-                    }),
+                expr_type: Some(protobuf::physical_expr_node::ExprType::ScalarUdf(
+                    protobuf::PhysicalScalarUdfNode {
+                        name: expr.name().to_string(),
+                        args,
+                        return_type: Some(expr.return_type().try_into()?),
+                        fun_definition,
+                    },
                 )),
             })
-        } else {
-            Err(DataFusionError::Internal(format!(
-                "physical_plan::to_proto() unsupported expression {value:?}"
-            )))
         }
+    } else if let Some(expr) = expr.downcast_ref::<DateTimeIntervalExpr>() {
+        let dti_expr = Box::new(protobuf::PhysicalDateTimeIntervalExprNode {
+            l: Some(Box::new(serialize_physical_expr(
+                expr.lhs().to_owned(),
+                codec,
+            )?)),
+            r: Some(Box::new(serialize_physical_expr(
+                expr.rhs().to_owned(),
+                codec,
+            )?)),
+            op: format!("{:?}", expr.op()),
+        });
+
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(
+                protobuf::physical_expr_node::ExprType::DateTimeIntervalExpr(dti_expr),
+            ),
+        })
+    } else if let Some(expr) = expr.downcast_ref::<LikeExpr>() {
+        Ok(protobuf::PhysicalExprNode {
+            expr_type: Some(protobuf::physical_expr_node::ExprType::LikeExpr(Box::new(
+                protobuf::PhysicalLikeExprNode {
+                    negated: expr.negated(),
+                    case_insensitive: expr.case_insensitive(),
+                    expr: Some(Box::new(serialize_physical_expr(
+                        expr.expr().to_owned(),
+                        codec,
+                    )?)),
+                    pattern: Some(Box::new(serialize_physical_expr(
+                        expr.pattern().to_owned(),
+                        codec,
+                    )?)),
+                },
+            ))),
+        })
+    } else {
+        Err(DataFusionError::Internal(format!(
+            "physical_plan::to_proto() unsupported expression {value:?}"
+        )))
     }
 }
 
 fn try_parse_when_then_expr(
     when_expr: &Arc<dyn PhysicalExpr>,
     then_expr: &Arc<dyn PhysicalExpr>,
+    codec: &dyn PhysicalExtensionCodec,
 ) -> Result<protobuf::PhysicalWhenThen, DataFusionError> {
     Ok(protobuf::PhysicalWhenThen {
-        when_expr: Some(when_expr.clone().try_into()?),
-        then_expr: Some(then_expr.clone().try_into()?),
+        when_expr: Some(serialize_physical_expr(when_expr.clone(), codec)?),
+        then_expr: Some(serialize_physical_expr(then_expr.clone(), codec)?),
     })
 }
 
@@ -375,6 +672,7 @@ impl TryFrom<&PartitionedFile> for protobuf::PartitionedFile {
                 .map(|v| v.try_into())
                 .collect::<Result<Vec<_>, _>>()?,
             range: pf.range.as_ref().map(|r| r.try_into()).transpose()?,
+            statistics: pf.statistics.as_ref().map(|s| s.try_into()).transpose()?,
         })
     }
 }
@@ -403,83 +701,118 @@ impl TryFrom<&[PartitionedFile]> for protobuf::FileGroup {
     }
 }
 
-impl From<&ColumnStatistics> for protobuf::ColumnStats {
-    fn from(cs: &ColumnStatistics) -> protobuf::ColumnStats {
-        protobuf::ColumnStats {
-            min_value: cs.min_value.as_ref().map(|m| m.try_into().unwrap()),
-            max_value: cs.max_value.as_ref().map(|m| m.try_into().unwrap()),
-            null_count: cs.null_count.map(|n| n as u32).unwrap_or(0),
-            distinct_count: cs.distinct_count.map(|n| n as u32).unwrap_or(0),
-        }
+impl TryFrom<&Precision<ScalarValue>> for protobuf::Precision {
+    type Error = DataFusionError;
+
+    fn try_from(s: &Precision<ScalarValue>) -> Result<Self, Self::Error> {
+        let (precision_info, val) = match s {
+            Precision::Exact(v) => (protobuf::PrecisionInfo::Exact, Some(v.try_into()?)),
+            Precision::Inexact(v) => {
+                (protobuf::PrecisionInfo::Inexact, Some(v.try_into()?))
+            }
+            Precision::Absent => (protobuf::PrecisionInfo::Absent, None),
+        };
+        Ok(protobuf::Precision {
+            precision_info: precision_info.into(),
+            val,
+        })
     }
 }
 
-impl From<&Statistics> for protobuf::Statistics {
-    fn from(s: &Statistics) -> protobuf::Statistics {
-        let none_value = -1_i64;
-        let column_stats = match &s.column_statistics {
-            None => vec![],
-            Some(column_stats) => column_stats.iter().map(|s| s.into()).collect(),
+impl TryFrom<&Precision<usize>> for protobuf::Precision {
+    type Error = DataFusionError;
+
+    fn try_from(s: &Precision<usize>) -> Result<Self, Self::Error> {
+        let value = match s {
+            Precision::Exact(n) => Precision::Exact(ScalarValue::UInt64(Some(*n as u64))),
+            Precision::Inexact(n) => {
+                Precision::Inexact(ScalarValue::UInt64(Some(*n as u64)))
+            }
+            Precision::Absent => Precision::Absent,
         };
-        protobuf::Statistics {
-            num_rows: s.num_rows.map(|n| n as i64).unwrap_or(none_value),
-            total_byte_size: s.total_byte_size.map(|n| n as i64).unwrap_or(none_value),
-            column_stats,
-            is_exact: s.is_exact,
-        }
+        (&value).try_into()
     }
 }
 
-impl TryFrom<&FileScanConfig> for protobuf::FileScanExecConf {
+impl TryFrom<&ColumnStatistics> for protobuf::ColumnStats {
     type Error = DataFusionError;
-    fn try_from(
-        conf: &FileScanConfig,
-    ) -> Result<protobuf::FileScanExecConf, Self::Error> {
-        let file_groups = conf
-            .file_groups
-            .iter()
-            .map(|p| p.as_slice().try_into())
-            .collect::<Result<Vec<_>, _>>()?;
 
-        let output_ordering = if let Some(output_ordering) = &conf.output_ordering {
-            output_ordering
-                .iter()
-                .map(|o| {
-                    let expr = o.expr.clone().try_into()?;
-                    Ok(PhysicalSortExprNode {
-                        expr: Some(Box::new(expr)),
-                        asc: !o.options.descending,
-                        nulls_first: o.options.nulls_first,
-                    })
-                })
-                .collect::<Result<Vec<PhysicalSortExprNode>, DataFusionError>>()?
-        } else {
-            vec![]
-        };
+    fn try_from(cs: &ColumnStatistics) -> Result<Self, Self::Error> {
+        Ok(protobuf::ColumnStats {
+            min_value: Some((&cs.min_value).try_into()?),
+            max_value: Some((&cs.max_value).try_into()?),
+            null_count: Some((&cs.null_count).try_into()?),
+            distinct_count: Some((&cs.distinct_count).try_into()?),
+        })
+    }
+}
 
-        Ok(protobuf::FileScanExecConf {
-            file_groups,
-            statistics: Some((&conf.statistics).into()),
-            limit: conf.limit.map(|l| protobuf::ScanLimit { limit: l as u32 }),
-            projection: conf
-                .projection
-                .as_ref()
-                .unwrap_or(&vec![])
-                .iter()
-                .map(|n| *n as u32)
-                .collect(),
-            schema: Some(conf.file_schema.as_ref().try_into()?),
-            table_partition_cols: conf
-                .table_partition_cols
+impl TryFrom<&Statistics> for protobuf::Statistics {
+    type Error = DataFusionError;
+
+    fn try_from(s: &Statistics) -> Result<Self, Self::Error> {
+        Ok(protobuf::Statistics {
+            num_rows: Some((&s.num_rows).try_into()?),
+            total_byte_size: Some((&s.total_byte_size).try_into()?),
+            column_stats: s
+                .column_statistics
                 .iter()
-                .map(|x| x.0.clone())
-                .collect::<Vec<_>>(),
-            object_store_url: conf.object_store_url.to_string(),
-            output_ordering,
+                .map(|s| s.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
         })
     }
 }
 
+/// Serializes the configuration of a file scan, encoding the state of user
+/// defined functions with `codec`
+pub fn serialize_file_scan_config(
+    conf: &FileScanConfig,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<protobuf::FileScanExecConf, DataFusionError> {
+    let file_groups = conf
+        .file_groups
+        .iter()
+        .map(|p| p.as_slice().try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let output_ordering = if let Some(output_ordering) = &conf.output_ordering {
+        output_ordering
+            .iter()
+            .map(|o| {
+                let expr = serialize_physical_expr(o.expr.clone(), codec)?;
+                Ok(PhysicalSortExprNode {
+                    expr: Some(Box::new(expr)),
+                    asc: !o.options.descending,
+                    nulls_first: o.options.nulls_first,
+                })
+            })
+            .collect::<Result<Vec<PhysicalSortExprNode>, DataFusionError>>()?
+    } else {
+        vec![]
+    };
+
+    Ok(protobuf::FileScanExecConf {
+        file_groups,
+        statistics: Some((&conf.statistics).try_into()?),
+        limit: conf.limit.map(|l| protobuf::ScanLimit { limit: l as u32 }),
+        projection: conf
+            .projection
+            .as_ref()
+            .unwrap_or(&vec![])
+            .iter()
+            .map(|n| *n as u32)
+            .collect(),
+        schema: Some(conf.file_schema.as_ref().try_into()?),
+        table_partition_cols: conf
+            .table_partition_cols
+            .iter()
+            .map(|x| x.0.clone())
+            .collect::<Vec<_>>(),
+        object_store_url: conf.object_store_url.to_string(),
+        output_ordering,
+    })
+}
+
 impl From<JoinSide> for protobuf::JoinSide {
     fn from(t: JoinSide) -> Self {
         match t {
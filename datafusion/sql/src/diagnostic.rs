@@ -0,0 +1,203 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Locates the [`Diagnostic`]s of planning errors in the SQL text
+
+use datafusion_common::{DataFusionError, Diagnostic, Span};
+use sqlparser::dialect::Dialect;
+use sqlparser::tokenizer::{Location, Token, Tokenizer};
+use std::ops::Range;
+
+/// Sets the [`Span`] of the [`Diagnostic`] of `error`, planning `sql`
+/// with `dialect`, to the first occurrence of the snippet of the diagnostic
+/// in `sql`.
+pub fn locate_diagnostic(
+    sql: &str,
+    dialect: &dyn Dialect,
+    mut error: DataFusionError,
+) -> DataFusionError {
+    if let Some(diagnostic) = error.diagnostic_mut() {
+        if diagnostic.span.is_none() {
+            diagnostic.span = diagnostic
+                .snippet
+                .as_deref()
+                .and_then(|snippet| find_snippet(sql, dialect, snippet));
+        }
+    }
+    error
+}
+
+/// Moves the [`Span`] of the [`Diagnostic`] of `error`, located in a
+/// statement that starts at byte `offset` of a script, to its range in the
+/// script
+pub fn offset_diagnostic(mut error: DataFusionError, offset: usize) -> DataFusionError {
+    if let Some(span) = error
+        .diagnostic_mut()
+        .and_then(|diagnostic| diagnostic.span.as_mut())
+    {
+        *span = Span::new(span.start + offset, span.end + offset);
+    }
+    error
+}
+
+/// Returns the byte ranges of the statements of `sql` separated by `;`,
+/// parsed with `dialect`, without the empty statements and the whitespaces
+/// and comments around them
+pub fn statement_ranges(sql: &str, dialect: &dyn Dialect) -> Option<Vec<Range<usize>>> {
+    let tokens = Tokenizer::new(dialect, sql).tokenize_with_location().ok()?;
+    let offsets: Vec<usize> = tokens
+        .iter()
+        .map(|token| byte_offset(sql, &token.location))
+        .chain(std::iter::once(sql.len()))
+        .collect();
+
+    let mut ranges = vec![];
+    let mut statement: Option<Range<usize>> = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match &token.token {
+            Token::SemiColon => ranges.extend(statement.take()),
+            Token::Whitespace(_) | Token::EOF => {}
+            _ => {
+                let start = statement.as_ref().map_or(offsets[i], |range| range.start);
+                statement = Some(start..offsets[i + 1]);
+            }
+        }
+    }
+    ranges.extend(statement);
+    Some(ranges)
+}
+
+/// Returns the span of the first tokens of `sql` that are the tokens of
+/// `snippet`, ignoring whitespaces and the case of the words
+fn find_snippet(sql: &str, dialect: &dyn Dialect, snippet: &str) -> Option<Span> {
+    let tokens = Tokenizer::new(dialect, sql).tokenize_with_location().ok()?;
+    let snippet: Vec<Token> = Tokenizer::new(dialect, snippet)
+        .tokenize()
+        .ok()?
+        .into_iter()
+        .filter(|token| !matches!(token, Token::Whitespace(_)))
+        .collect();
+    if snippet.is_empty() {
+        return None;
+    }
+
+    // a token ends where the next one, maybe a whitespace, starts
+    let offsets: Vec<usize> = tokens
+        .iter()
+        .map(|token| byte_offset(sql, &token.location))
+        .chain(std::iter::once(sql.len()))
+        .collect();
+    let words: Vec<usize> = (0..tokens.len())
+        .filter(|i| !matches!(tokens[*i].token, Token::Whitespace(_)))
+        .collect();
+    words
+        .windows(snippet.len())
+        .find(|window| {
+            window
+                .iter()
+                .zip(&snippet)
+                .all(|(i, token)| tokens_match(&tokens[*i].token, token))
+        })
+        .map(|window| {
+            Span::new(offsets[window[0]], offsets[window[window.len() - 1] + 1])
+        })
+}
+
+fn tokens_match(left: &Token, right: &Token) -> bool {
+    match (left, right) {
+        (Token::Word(left), Token::Word(right)) => {
+            left.value.eq_ignore_ascii_case(&right.value)
+        }
+        _ => left == right,
+    }
+}
+
+/// Converts the line and column of a token, counted in characters from 1,
+/// to its byte offset in `sql`
+fn byte_offset(sql: &str, location: &Location) -> usize {
+    let line = (location.line as usize).saturating_sub(1);
+    let column = (location.column as usize).saturating_sub(1);
+    let line_start: usize = sql.split_inclusive('\n').take(line).map(str::len).sum();
+    let column_offset: usize = sql[line_start..]
+        .chars()
+        .take(column)
+        .map(char::len_utf8)
+        .sum();
+    line_start + column_offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::dialect::GenericDialect;
+
+    fn span_of(sql: &str, error: DataFusionError) -> Option<Span> {
+        locate_diagnostic(sql, &GenericDialect {}, error)
+            .diagnostic()
+            .and_then(|diagnostic| diagnostic.span)
+    }
+
+    #[test]
+    fn locate_snippet() {
+        let sql = "SELECT a,\n  Foo( b )  FROM \"T\"";
+        let error = |snippet: &str| {
+            DataFusionError::Plan("foo".to_string())
+                .with_diagnostic(Diagnostic::new("foo").with_snippet(snippet))
+        };
+        assert_eq!(span_of(sql, error("foo(b)")), Some(Span::new(12, 20)));
+        assert_eq!(&sql[12..20], "Foo( b )");
+        assert_eq!(span_of(sql, error("\"T\"")), Some(Span::new(27, 30)));
+        assert_eq!(span_of(sql, error("c")), None);
+
+        // the span of the diagnostic is kept
+        let error = DataFusionError::Plan("foo".to_string()).with_diagnostic(
+            Diagnostic::new("foo")
+                .with_snippet("a")
+                .with_span(Span::new(0, 1)),
+        );
+        assert_eq!(span_of(sql, error), Some(Span::new(0, 1)));
+    }
+
+    #[test]
+    fn split_statements() {
+        let sql = "SELECT 1; ;\n-- comment\nSELECT ';' FROM t ;  SELECT 2";
+        let ranges = statement_ranges(sql, &GenericDialect {}).unwrap();
+        let statements: Vec<&str> = ranges.into_iter().map(|range| &sql[range]).collect();
+        assert_eq!(
+            statements,
+            vec!["SELECT 1", "SELECT ';' FROM t", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn offset_span() {
+        let error = DataFusionError::Plan("foo".to_string())
+            .with_diagnostic(Diagnostic::new("foo").with_span(Span::new(1, 3)));
+        let error = offset_diagnostic(error, 10);
+        assert_eq!(error.diagnostic().unwrap().span, Some(Span::new(11, 13)));
+    }
+
+    #[test]
+    fn locate_after_multibyte_characters() {
+        let sql = "SELECT é, t.b FROM t";
+        let error = DataFusionError::Plan("foo".to_string())
+            .with_diagnostic(Diagnostic::new("foo").with_snippet("t.b"))
+            .context("bar");
+        assert_eq!(span_of(sql, error), Some(Span::new(11, 14)));
+        assert_eq!(&sql[11..14], "t.b");
+    }
+}
@@ -17,7 +17,7 @@
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 use datafusion_common::{DFSchema, DataFusionError, Result};
-use datafusion_expr::{BinaryExpr, Expr, Operator};
+use datafusion_expr::{Expr, Operator};
 use sqlparser::ast::{BinaryOperator, Expr as SQLExpr};
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -58,10 +58,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             ))),
         }?;
 
-        Ok(Expr::BinaryExpr(BinaryExpr::new(
-            Box::new(self.sql_expr_to_logical_expr(left, schema, planner_context)?),
-            operator,
-            Box::new(self.sql_expr_to_logical_expr(right, schema, planner_context)?),
-        )))
+        self.sql_binary_expr_to_expr(left, operator, right, schema, planner_context)
     }
 }
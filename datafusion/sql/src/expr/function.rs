@@ -16,12 +16,14 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use crate::utils::normalize_ident;
-use datafusion_common::{DFSchema, DataFusionError, Result};
+use crate::utils::function_name;
+use datafusion_common::{DFSchema, DataFusionError, Diagnostic, ErrorCode, Result};
+use datafusion_expr::function::resolve_function_arguments;
 use datafusion_expr::utils::COUNT_STAR_EXPANSION;
 use datafusion_expr::{
-    expr, window_function, AggregateFunction, BuiltinScalarFunction, Expr, WindowFrame,
-    WindowFrameUnits, WindowFunction,
+    expr, window_function, AggregateFunction, BuiltInWindowFunction,
+    BuiltinScalarFunction, Expr, FunctionParameter, WindowFrame, WindowFrameUnits,
+    WindowFunction,
 };
 use sqlparser::ast::{
     Expr as SQLExpr, Function as SQLFunction, FunctionArg, FunctionArgExpr,
@@ -35,13 +37,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
-        let name = if function.name.0.len() > 1 {
-            // DF doesn't handle compound identifiers
-            // (e.g. "foo.bar") for function names yet
-            function.name.to_string()
-        } else {
-            normalize_ident(function.name.0[0].clone())
-        };
+        let name = function_name(function.name.clone());
 
         // next, scalar built-in
         if let Ok(fun) = BuiltinScalarFunction::from_str(&name) {
@@ -95,6 +91,21 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         window_frame,
                     ))
                 }
+                WindowFunction::AggregateUDF(ref aggregate_udf) => {
+                    let args = self.named_function_args_to_expr(
+                        &name,
+                        &aggregate_udf.parameters,
+                        function.args,
+                        schema,
+                    )?;
+                    Expr::WindowFunction(expr::WindowFunction::new(
+                        fun,
+                        args,
+                        partition_by,
+                        order_by,
+                        window_frame,
+                    ))
+                }
                 _ => Expr::WindowFunction(expr::WindowFunction::new(
                     fun,
                     self.function_args_to_expr(function.args, schema)?,
@@ -111,27 +122,49 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             let distinct = function.distinct;
             let (fun, args) = self.aggregate_fn_to_expr(fun, function.args, schema)?;
             return Ok(Expr::AggregateFunction(expr::AggregateFunction::new(
-                fun, args, distinct, None,
+                fun, args, distinct, None, None,
             )));
         };
 
         // finally, user-defined functions (UDF) and UDAF
         match self.schema_provider.get_function_meta(&name) {
             Some(fm) => {
-                let args = self.function_args_to_expr(function.args, schema)?;
+                let args = self.named_function_args_to_expr(
+                    &name,
+                    &fm.parameters,
+                    function.args,
+                    schema,
+                )?;
 
                 Ok(Expr::ScalarUDF { fun: fm, args })
             }
             None => match self.schema_provider.get_aggregate_meta(&name) {
                 Some(fm) => {
-                    let args = self.function_args_to_expr(function.args, schema)?;
+                    let args = self.named_function_args_to_expr(
+                        &name,
+                        &fm.parameters,
+                        function.args,
+                        schema,
+                    )?;
                     Ok(Expr::AggregateUDF {
                         fun: fm,
                         args,
                         filter: None,
                     })
                 }
-                _ => Err(DataFusionError::Plan(format!("Invalid function '{name}'"))),
+                _ => {
+                    let message = format!("Invalid function '{name}'");
+                    let function_names = self.schema_provider.function_names();
+                    let candidates = BuiltinScalarFunction::names()
+                        .chain(AggregateFunction::names())
+                        .chain(BuiltInWindowFunction::names())
+                        .chain(function_names.iter().map(|name| name.as_str()));
+                    let diagnostic = Diagnostic::new(message.clone())
+                        .with_code(ErrorCode::UnknownFunction)
+                        .with_snippet(function.name.to_string())
+                        .with_suggestion(self.suggest(&name, candidates));
+                    Err(DataFusionError::Plan(message).with_diagnostic(diagnostic))
+                }
             },
         }
     }
@@ -159,7 +192,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             })
     }
 
-    fn sql_fn_arg_to_logical_expr(
+    pub(crate) fn sql_fn_arg_to_logical_expr(
         &self,
         sql: FunctionArg,
         schema: &DFSchema,
@@ -196,6 +229,35 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .collect::<Result<Vec<Expr>>>()
     }
 
+    /// Plans the arguments of a call of the user defined function `name`,
+    /// passing the named ones to its `parameters`
+    fn named_function_args_to_expr(
+        &self,
+        name: &str,
+        parameters: &[FunctionParameter],
+        args: Vec<FunctionArg>,
+        schema: &DFSchema,
+    ) -> Result<Vec<Expr>> {
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                let arg_name = match &arg {
+                    FunctionArg::Named { name, .. } => {
+                        Some(self.normalizer.normalize(name.clone()))
+                    }
+                    FunctionArg::Unnamed(_) => None,
+                };
+                let arg = self.sql_fn_arg_to_logical_expr(
+                    arg,
+                    schema,
+                    &mut PlannerContext::new(),
+                )?;
+                Ok((arg_name, arg))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        resolve_function_arguments(name, parameters, args)
+    }
+
     pub(super) fn aggregate_fn_to_expr(
         &self,
         fun: AggregateFunction,
@@ -18,7 +18,6 @@
 use crate::planner::{
     idents_to_table_reference, ContextProvider, PlannerContext, SqlToRel,
 };
-use crate::utils::normalize_ident;
 use datafusion_common::{
     Column, DFSchema, DataFusionError, OwnedTableReference, Result, ScalarValue,
 };
@@ -47,7 +46,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
             Ok(Expr::Column(Column {
                 relation: None,
-                name: normalize_ident(id),
+                name: self.normalizer.normalize(id),
             }))
         }
     }
@@ -58,7 +57,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         schema: &DFSchema,
     ) -> Result<Expr> {
         if ids[0].value.starts_with('@') {
-            let var_names: Vec<_> = ids.into_iter().map(normalize_ident).collect();
+            let var_names: Vec<_> = ids
+                .into_iter()
+                .map(|id| self.normalizer.normalize(id))
+                .collect();
             let ty = self
                 .schema_provider
                 .get_variable_type(&var_names)
@@ -70,7 +72,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             Ok(Expr::ScalarVariable(ty, var_names))
         } else {
             // only support "schema.table" type identifiers here
-            let (name, relation) = match idents_to_table_reference(ids)? {
+            let (name, relation) = match idents_to_table_reference(
+                ids,
+                self.options.enable_ident_normalization,
+            )? {
                 OwnedTableReference::Partial { schema, table } => (table, schema),
                 r @ OwnedTableReference::Bare { .. }
                 | r @ OwnedTableReference::Full { .. } => {
@@ -96,7 +101,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         // Access to a field of a column which is a structure, example: SELECT my_struct.key
                         Ok(Expr::GetIndexedField(GetIndexedField::new(
                             Box::new(Expr::Column(field.qualified_column())),
-                            ScalarValue::Utf8(Some(name)),
+                            ScalarValue::from(name),
                         )))
                     } else {
                         // table.column identifier
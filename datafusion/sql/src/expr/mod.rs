@@ -20,21 +20,24 @@ mod function;
 mod grouping_set;
 mod identifier;
 mod order_by;
+mod row_value;
 mod subquery;
 mod substring;
+mod time_zone;
 mod unary_op;
 mod value;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use crate::utils::normalize_ident;
+use crate::utils::resolve_aliases_to_exprs;
 use arrow_schema::DataType;
 use datafusion_common::{Column, DFSchema, DataFusionError, Result, ScalarValue};
 use datafusion_expr::{
-    col, expr, lit, AggregateFunction, Between, BinaryExpr, BuiltinScalarFunction, Cast,
-    Expr, ExprSchemable, GetIndexedField, Like, Operator, TryCast,
+    col, expr, lit, AggregateFunction, Between, BuiltinScalarFunction, Cast, Expr,
+    ExprSchemable, GetIndexedField, Like, Operator, TryCast,
 };
 use sqlparser::ast::{ArrayAgg, Expr as SQLExpr, TrimWhereField, Value};
 use sqlparser::parser::ParserError::ParserError;
+use std::collections::HashMap;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     pub(crate) fn sql_expr_to_logical_expr(
@@ -79,6 +82,26 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(expr)
     }
 
+    /// Generate a relational expression from a SQL expression, whose unqualified
+    /// columns named like the keys of `aliases` are replaced by the aliased
+    /// expressions
+    pub(crate) fn sql_to_expr_with_aliases(
+        &self,
+        sql: SQLExpr,
+        schema: &DFSchema,
+        aliases: &HashMap<String, Expr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        if aliases.is_empty() {
+            return self.sql_to_expr(sql, schema, planner_context);
+        }
+        let expr = self.sql_expr_to_logical_expr(sql, schema, planner_context)?;
+        let mut expr = resolve_aliases_to_exprs(&expr, aliases)?;
+        expr = self.rewrite_partial_qualifier(expr, schema);
+        self.validate_schema_satisfies_exprs(schema, &[expr.clone()])?;
+        Ok(expr)
+    }
+
     /// Rewrite aliases which are not-complete (e.g. ones that only include only table qualifier in a schema.table qualified relation)
     fn rewrite_partial_qualifier(&self, expr: Expr, schema: &DFSchema) -> Expr {
         match expr {
@@ -122,7 +145,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             SQLExpr::Extract { field, expr } => Ok(Expr::ScalarFunction {
                 fun: BuiltinScalarFunction::DatePart,
                 args: vec![
-                    Expr::Literal(ScalarValue::Utf8(Some(format!("{field}")))),
+                    Expr::Literal(ScalarValue::from(field.to_string())),
                     self.sql_expr_to_logical_expr(*expr, schema, planner_context)?,
                 ],
             }),
@@ -145,7 +168,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
             SQLExpr::MapAccess { column, keys } => {
                 if let SQLExpr::Identifier(id) = *column {
-                    plan_indexed(col(normalize_ident(id)), keys)
+                    plan_indexed(col(self.normalizer.normalize(id)), keys)
                 } else {
                     Err(DataFusionError::NotImplemented(format!(
                         "map access requires an identifier, found column {column} instead"
@@ -170,18 +193,32 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             SQLExpr::Cast {
                 expr,
                 data_type,
-            } => Ok(Expr::Cast(Cast::new(
-                Box::new(self.sql_expr_to_logical_expr(*expr, schema, planner_context)?),
-                self.convert_data_type(&data_type)?,
-            ))),
+            } => {
+                let data_type = self.convert_data_type(&data_type)?;
+                let expr = self.sql_expr_to_logical_expr(*expr, schema, planner_context)?;
+                let expr = self.cast_input_to_session_time_zone(expr, &data_type, schema);
+                Ok(Expr::Cast(Cast::new(Box::new(expr), data_type)))
+            }
 
             SQLExpr::TryCast {
                 expr,
                 data_type,
-            } => Ok(Expr::TryCast(TryCast::new(
-                Box::new(self.sql_expr_to_logical_expr(*expr, schema, planner_context)?),
-                self.convert_data_type(&data_type)?,
-            ))),
+            } => {
+                let data_type = self.convert_data_type(&data_type)?;
+                let expr = self.sql_expr_to_logical_expr(*expr, schema, planner_context)?;
+                let expr = self.cast_input_to_session_time_zone(expr, &data_type, schema);
+                Ok(Expr::TryCast(TryCast::new(Box::new(expr), data_type)))
+            }
+
+            SQLExpr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => self.sql_at_time_zone_to_expr(
+                *timestamp,
+                time_zone,
+                schema,
+                planner_context,
+            ),
 
             SQLExpr::TypedString {
                 data_type,
@@ -199,17 +236,9 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.sql_expr_to_logical_expr(*expr, schema, planner_context)?,
             ))),
 
-            SQLExpr::IsDistinctFrom(left, right) => Ok(Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(self.sql_expr_to_logical_expr(*left, schema, planner_context)?),
-                Operator::IsDistinctFrom,
-                Box::new(self.sql_expr_to_logical_expr(*right, schema, planner_context)?),
-            ))),
+            SQLExpr::IsDistinctFrom(left, right) => self.sql_binary_expr_to_expr(*left, Operator::IsDistinctFrom, *right, schema, planner_context),
 
-            SQLExpr::IsNotDistinctFrom(left, right) => Ok(Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(self.sql_expr_to_logical_expr(*left, schema, planner_context)?),
-                Operator::IsNotDistinctFrom,
-                Box::new(self.sql_expr_to_logical_expr(*right, schema, planner_context)?),
-            ))),
+            SQLExpr::IsNotDistinctFrom(left, right) => self.sql_binary_expr_to_expr(*left, Operator::IsNotDistinctFrom, *right, schema, planner_context),
 
             SQLExpr::IsTrue(expr) => Ok(Expr::IsTrue(Box::new(self.sql_expr_to_logical_expr(*expr, schema, planner_context)?))),
 
@@ -315,12 +344,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             within_group,
         } = array_agg;
 
-        if let Some(order_by) = order_by {
-            return Err(DataFusionError::NotImplemented(format!(
-                "ORDER BY not supported in ARRAY_AGG: {order_by}"
-            )));
-        }
-
         if let Some(limit) = limit {
             return Err(DataFusionError::NotImplemented(format!(
                 "LIMIT not supported in ARRAY_AGG: {limit}"
@@ -335,11 +358,17 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
         let args =
             vec![self.sql_expr_to_logical_expr(*expr, input_schema, planner_context)?];
+        let order_by = order_by
+            .map(|order_by| {
+                self.order_by_to_sort_expr(*order_by, input_schema)
+                    .map(|sort| vec![sort])
+            })
+            .transpose()?;
         // next, aggregate built-ins
         let fun = AggregateFunction::ArrayAgg;
 
         Ok(Expr::AggregateFunction(expr::AggregateFunction::new(
-            fun, args, distinct, None,
+            fun, args, distinct, None, order_by,
         )))
     }
 
@@ -351,6 +380,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
+        if let SQLExpr::Tuple(fields) = expr {
+            return self.sql_row_value_in_list_to_expr(
+                fields,
+                list,
+                negated,
+                schema,
+                planner_context,
+            );
+        }
+
         let list_expr = list
             .into_iter()
             .map(|e| self.sql_expr_to_logical_expr(e, schema, planner_context))
@@ -477,6 +516,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 fun,
                 args,
                 distinct,
+                order_by,
                 ..
             }) => Ok(Expr::AggregateFunction(expr::AggregateFunction::new(
                 fun,
@@ -487,6 +527,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     schema,
                     planner_context,
                 )?)),
+                order_by,
             ))),
             _ => Err(DataFusionError::Internal(
                 "AggregateExpressionWithFilter expression was not an AggregateFunction"
@@ -503,7 +544,7 @@ fn plan_key(key: SQLExpr) -> Result<ScalarValue> {
                 .map_err(|_| ParserError(format!("Cannot parse {s} as i64.")))?,
         )),
         SQLExpr::Value(Value::SingleQuotedString(s) | Value::DoubleQuotedString(s)) => {
-            ScalarValue::Utf8(Some(s))
+            ScalarValue::from(s)
         }
         _ => {
             return Err(DataFusionError::SQL(ParserError(format!(
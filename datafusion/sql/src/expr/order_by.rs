@@ -20,6 +20,7 @@ use datafusion_common::{DFSchema, DataFusionError, Result};
 use datafusion_expr::expr::Sort;
 use datafusion_expr::Expr;
 use sqlparser::ast::{Expr as SQLExpr, OrderByExpr, Value};
+use std::str::FromStr;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// convert sql OrderByExpr to Expr::Sort
@@ -57,15 +58,66 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             }
             e => self.sql_expr_to_logical_expr(e, schema, &mut PlannerContext::new())?,
         };
-        Ok({
-            let asc = asc.unwrap_or(true);
-            Expr::Sort(Sort::new(
-                Box::new(expr),
-                asc,
-                // when asc is true, by default nulls last to be consistent with postgres
-                // postgres rule: https://www.postgresql.org/docs/current/queries-order.html
-                nulls_first.unwrap_or(!asc),
-            ))
-        })
+        let asc = asc.unwrap_or(true);
+        let nulls_first = match nulls_first {
+            Some(nulls_first) => nulls_first,
+            None => self.default_null_ordering()?.nulls_first(asc),
+        };
+        Ok(Expr::Sort(Sort::new(Box::new(expr), asc, nulls_first)))
+    }
+
+    /// Returns the placement of nulls of the sort keys without NULLS FIRST or
+    /// NULLS LAST, set by the `datafusion.sql_parser.default_null_ordering` option
+    pub(crate) fn default_null_ordering(&self) -> Result<NullOrdering> {
+        self.schema_provider
+            .options()
+            .sql_parser
+            .default_null_ordering
+            .parse()
+    }
+}
+
+/// The placement of nulls in a sort without NULLS FIRST or NULLS LAST
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NullOrdering {
+    /// Nulls sort as if they were larger than any value: last in ascending
+    /// order and first in descending order, like in postgres
+    /// <https://www.postgresql.org/docs/current/queries-order.html>
+    NullsMax,
+    /// Nulls sort as if they were smaller than any value: first in ascending
+    /// order and last in descending order
+    NullsMin,
+    /// Nulls sort first regardless of the direction
+    NullsFirst,
+    /// Nulls sort last regardless of the direction
+    NullsLast,
+}
+
+impl NullOrdering {
+    /// Whether nulls sort first in a sort with the given direction
+    pub(crate) fn nulls_first(&self, asc: bool) -> bool {
+        match self {
+            NullOrdering::NullsMax => !asc,
+            NullOrdering::NullsMin => asc,
+            NullOrdering::NullsFirst => true,
+            NullOrdering::NullsLast => false,
+        }
+    }
+}
+
+impl FromStr for NullOrdering {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "nulls_max" => Ok(NullOrdering::NullsMax),
+            "nulls_min" => Ok(NullOrdering::NullsMin),
+            "nulls_first" => Ok(NullOrdering::NullsFirst),
+            "nulls_last" => Ok(NullOrdering::NullsLast),
+            _ => Err(DataFusionError::Plan(format!(
+                "Invalid null ordering '{s}', expected one of nulls_max, \
+                 nulls_min, nulls_first and nulls_last"
+            ))),
+        }
     }
 }
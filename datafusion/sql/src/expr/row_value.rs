@@ -0,0 +1,150 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::{DFSchema, DataFusionError, Result};
+use datafusion_expr::{binary_expr, BinaryExpr, Expr, Operator};
+use sqlparser::ast::Expr as SQLExpr;
+
+impl<'a, S: ContextProvider> SqlToRel<'a, S> {
+    /// Plans `left op right`, expanding the comparison of two row values such
+    /// as `(a, b) < (1, 2)` to the comparisons of their fields
+    pub(super) fn sql_binary_expr_to_expr(
+        &self,
+        left: SQLExpr,
+        op: Operator,
+        right: SQLExpr,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        match (left, right) {
+            (SQLExpr::Tuple(left), SQLExpr::Tuple(right)) => {
+                let left = self.sql_row_value_to_exprs(left, schema, planner_context)?;
+                let right =
+                    self.sql_row_value_to_exprs(right, schema, planner_context)?;
+                row_comparison(left, op, right)
+            }
+            (left, right) => Ok(Expr::BinaryExpr(BinaryExpr::new(
+                Box::new(self.sql_expr_to_logical_expr(left, schema, planner_context)?),
+                op,
+                Box::new(self.sql_expr_to_logical_expr(
+                    right,
+                    schema,
+                    planner_context,
+                )?),
+            ))),
+        }
+    }
+
+    /// Plans `(a, b) [NOT] IN ((1, 2), (3, 4))` as the disjunction of the
+    /// equalities of the row value with each row value of the list
+    pub(super) fn sql_row_value_in_list_to_expr(
+        &self,
+        fields: Vec<SQLExpr>,
+        list: Vec<SQLExpr>,
+        negated: bool,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let fields = self.sql_row_value_to_exprs(fields, schema, planner_context)?;
+        let comparisons = list
+            .into_iter()
+            .map(|value| match value {
+                SQLExpr::Tuple(values) => {
+                    let values =
+                        self.sql_row_value_to_exprs(values, schema, planner_context)?;
+                    row_comparison(fields.clone(), Operator::Eq, values)
+                }
+                other => Err(DataFusionError::Plan(format!(
+                    "IN list of a row value can only contain row values, found {other}"
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let expr = comparisons
+            .into_iter()
+            .reduce(Expr::or)
+            .ok_or_else(|| DataFusionError::Plan("IN list can't be empty".to_string()))?;
+
+        Ok(if negated {
+            Expr::Not(Box::new(expr))
+        } else {
+            expr
+        })
+    }
+
+    fn sql_row_value_to_exprs(
+        &self,
+        fields: Vec<SQLExpr>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Vec<Expr>> {
+        fields
+            .into_iter()
+            .map(|field| self.sql_expr_to_logical_expr(field, schema, planner_context))
+            .collect()
+    }
+}
+
+/// Expands the comparison of two row values to the comparisons of their
+/// fields, following the SQL standard: rows are equal if all their fields are
+/// equal, and otherwise ordered like their first unequal fields. NULL fields
+/// make the comparison NULL, unless it's decided by the fields before them.
+fn row_comparison(left: Vec<Expr>, op: Operator, right: Vec<Expr>) -> Result<Expr> {
+    if left.len() != right.len() {
+        return Err(DataFusionError::Plan(format!(
+            "Can't compare a row value of {} fields with a row value of {} fields",
+            left.len(),
+            right.len()
+        )));
+    }
+
+    let mut pairs = left.into_iter().zip(right);
+    let strict = match op {
+        Operator::Eq | Operator::IsNotDistinctFrom => {
+            return combine_fields(pairs.map(|(l, r)| binary_expr(l, op, r)), Expr::and)
+        }
+        Operator::NotEq | Operator::IsDistinctFrom => {
+            return combine_fields(pairs.map(|(l, r)| binary_expr(l, op, r)), Expr::or)
+        }
+        Operator::Lt | Operator::LtEq => Operator::Lt,
+        Operator::Gt | Operator::GtEq => Operator::Gt,
+        _ => {
+            return Err(DataFusionError::Plan(format!(
+                "Unsupported operator {op} between row values"
+            )))
+        }
+    };
+
+    // (a, b, c) < (x, y, z) is a < x OR (a = x AND (b < y OR (b = y AND c < z)))
+    let (l, r) = pairs.next_back().ok_or_else(|| {
+        DataFusionError::Plan("Row values must have at least one field".to_string())
+    })?;
+    Ok(pairs.rev().fold(binary_expr(l, op, r), |expr, (l, r)| {
+        binary_expr(l.clone(), strict, r.clone())
+            .or(binary_expr(l, Operator::Eq, r).and(expr))
+    }))
+}
+
+/// Combines the comparisons of the fields of two row values with `combine`
+fn combine_fields(
+    exprs: impl Iterator<Item = Expr>,
+    combine: fn(Expr, Expr) -> Expr,
+) -> Result<Expr> {
+    exprs.reduce(combine).ok_or_else(|| {
+        DataFusionError::Plan("Row values must have at least one field".to_string())
+    })
+}
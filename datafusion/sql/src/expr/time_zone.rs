@@ -0,0 +1,103 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use arrow_schema::{DataType, TimeUnit};
+use datafusion_common::{DFSchema, DataFusionError, Result};
+use datafusion_expr::{Cast, Expr, ExprSchemable};
+use sqlparser::ast::Expr as SQLExpr;
+
+impl<'a, S: ContextProvider> SqlToRel<'a, S> {
+    /// Plans `timestamp AT TIME ZONE time_zone` like PostgreSQL:
+    ///
+    /// * a timestamp without a time zone is the wall clock time in `time_zone`,
+    /// and the result is the timestamp in the session time zone of that instant
+    /// * a timestamp with a time zone is converted to the wall clock time in
+    /// `time_zone`, and the result is a timestamp without a time zone
+    pub(super) fn sql_at_time_zone_to_expr(
+        &self,
+        timestamp: SQLExpr,
+        time_zone: String,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let expr = self.sql_expr_to_logical_expr(timestamp, schema, planner_context)?;
+        let session_time_zone =
+            self.schema_provider.options().execution.time_zone.clone();
+        let (expr, unit, has_time_zone) = match expr.get_type(schema)? {
+            DataType::Timestamp(unit, tz) => (expr, unit, tz.is_some()),
+            // strings are timestamps in the session time zone
+            DataType::Utf8 | DataType::LargeUtf8 => {
+                let unit = TimeUnit::Nanosecond;
+                let session_time_zone = session_time_zone
+                    .clone()
+                    .unwrap_or_else(|| "+00:00".to_string());
+                let expr = cast(expr, DataType::Timestamp(unit.clone(), None));
+                let expr = cast(
+                    expr,
+                    DataType::Timestamp(unit.clone(), Some(session_time_zone)),
+                );
+                (expr, unit, true)
+            }
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "AT TIME ZONE expects a timestamp, got {other:?}"
+                )))
+            }
+        };
+
+        let at_time_zone = cast(expr, DataType::Timestamp(unit.clone(), Some(time_zone)));
+        if has_time_zone {
+            Ok(cast(at_time_zone, DataType::Timestamp(unit, None)))
+        } else if let Some(session_time_zone) = session_time_zone {
+            Ok(cast(
+                at_time_zone,
+                DataType::Timestamp(unit, Some(session_time_zone)),
+            ))
+        } else {
+            Ok(at_time_zone)
+        }
+    }
+
+    /// Returns the input of a cast of `expr` to `data_type`: the timestamps with a
+    /// time zone cast to timestamps without one are first converted to the session
+    /// time zone, so that the cast returns their wall clock times in the session
+    /// time zone
+    pub(super) fn cast_input_to_session_time_zone(
+        &self,
+        expr: Expr,
+        data_type: &DataType,
+        schema: &DFSchema,
+    ) -> Expr {
+        let session_time_zone = &self.schema_provider.options().execution.time_zone;
+        match (expr.get_type(schema), data_type, session_time_zone) {
+            (
+                Ok(DataType::Timestamp(unit, Some(tz))),
+                DataType::Timestamp(_, None),
+                Some(session_time_zone),
+            ) if &tz != session_time_zone => cast(
+                expr,
+                DataType::Timestamp(unit, Some(session_time_zone.clone())),
+            ),
+            _ => expr,
+        }
+    }
+}
+
+fn cast(expr: Expr, data_type: DataType) -> Expr {
+    Expr::Cast(Cast::new(Box::new(expr), data_type))
+}
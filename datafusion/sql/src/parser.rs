@@ -20,8 +20,8 @@
 use datafusion_common::parsers::CompressionTypeVariant;
 use sqlparser::{
     ast::{
-        ColumnDef, ColumnOptionDef, ObjectName, Statement as SQLStatement,
-        TableConstraint,
+        ColumnDef, ColumnOptionDef, DataType, Expr, Ident, ObjectName,
+        Statement as SQLStatement, TableConstraint,
     },
     dialect::{keywords::Keyword, Dialect, GenericDialect},
     parser::{Parser, ParserError},
@@ -41,6 +41,13 @@ fn parse_file_type(s: &str) -> Result<String, ParserError> {
     Ok(s.to_uppercase())
 }
 
+fn ensure_not_set<T>(field: &Option<T>, name: &str) -> Result<(), ParserError> {
+    if field.is_some() {
+        return parser_err!(format!("{name} specified more than once"));
+    }
+    Ok(())
+}
+
 /// DataFusion extension DDL for `CREATE EXTERNAL TABLE`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CreateExternalTable {
@@ -60,7 +67,7 @@ pub struct CreateExternalTable {
     pub table_partition_cols: Vec<String>,
     /// Option to not error if table already exists
     pub if_not_exists: bool,
-    /// File compression type (GZIP, BZIP2, XZ)
+    /// File compression type (GZIP, BZIP2, XZ, ZSTD, BGZIP)
     pub file_compression_type: CompressionTypeVariant,
     /// Table(provider) specific options
     pub options: HashMap<String, String>,
@@ -85,6 +92,87 @@ pub struct DescribeTable {
     pub table_name: ObjectName,
 }
 
+/// DataFusion extension for `ANALYZE TABLE`
+///
+/// ```sql
+/// ANALYZE TABLE t [COMPUTE STATISTICS [FOR COLUMNS c1, c2, ...]]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyzeTable {
+    /// Table name
+    pub table_name: ObjectName,
+    /// Columns to compute statistics for, all columns if `None`
+    pub columns: Option<Vec<Ident>>,
+}
+
+/// An argument of a [`CreateFunction`] statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateFunctionArg {
+    /// Argument name, if any
+    pub name: Option<Ident>,
+    /// Argument type
+    pub data_type: DataType,
+    /// Value of the argument when it is omitted
+    pub default: Option<Expr>,
+}
+
+/// DataFusion extension DDL for `CREATE FUNCTION`
+///
+/// ```sql
+/// CREATE [OR REPLACE] FUNCTION name([arg_name] arg_type [DEFAULT expr], ...)
+///     [RETURNS return_type] [LANGUAGE language] [AS 'definition']
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateFunction {
+    /// Replace the function if it already exists
+    pub or_replace: bool,
+    /// Function name
+    pub name: ObjectName,
+    /// Function arguments
+    pub args: Vec<CreateFunctionArg>,
+    /// Return type
+    pub return_type: Option<DataType>,
+    /// Language the function is defined in
+    pub language: Option<Ident>,
+    /// Definition of the function, interpreted according to its language
+    pub definition: Option<String>,
+}
+
+/// DataFusion extension DDL for `DROP FUNCTION`
+///
+/// ```sql
+/// DROP FUNCTION [IF EXISTS] name
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropFunction {
+    /// Option to not error if the function doesn't exist
+    pub if_exists: bool,
+    /// Function name
+    pub name: ObjectName,
+}
+
+/// DataFusion extension for `ROLLBACK TO SAVEPOINT`
+///
+/// ```sql
+/// ROLLBACK [WORK | TRANSACTION] TO [SAVEPOINT] name
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackToSavepoint {
+    /// Savepoint name
+    pub name: Ident,
+}
+
+/// DataFusion extension for `RELEASE SAVEPOINT`
+///
+/// ```sql
+/// RELEASE [SAVEPOINT] name
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseSavepoint {
+    /// Savepoint name
+    pub name: Ident,
+}
+
 /// DataFusion Statement representations.
 ///
 /// Tokens parsed by [`DFParser`] are converted into these values.
@@ -96,6 +184,16 @@ pub enum Statement {
     CreateExternalTable(CreateExternalTable),
     /// Extension: `DESCRIBE TABLE`
     DescribeTable(DescribeTable),
+    /// Extension: `ANALYZE TABLE`
+    AnalyzeTable(AnalyzeTable),
+    /// Extension: `CREATE FUNCTION`
+    CreateFunction(CreateFunction),
+    /// Extension: `DROP FUNCTION`
+    DropFunction(DropFunction),
+    /// Extension: `ROLLBACK TO SAVEPOINT`
+    RollbackToSavepoint(RollbackToSavepoint),
+    /// Extension: `RELEASE SAVEPOINT`
+    ReleaseSavepoint(ReleaseSavepoint),
 }
 
 /// DataFusion SQL Parser based on [`sqlparser`]
@@ -190,6 +288,53 @@ impl<'a> DFParser<'a> {
                         // use custom parsing
                         self.parse_describe()
                     }
+                    Keyword::ANALYZE => {
+                        // move one token forward
+                        self.parser.next_token();
+                        // use custom parsing
+                        self.parse_analyze()
+                    }
+                    Keyword::DROP => {
+                        // move one token forward
+                        self.parser.next_token();
+                        if self.parser.parse_keyword(Keyword::FUNCTION) {
+                            // use custom parsing
+                            self.parse_drop_function()
+                        } else {
+                            // rewind and use the native parser
+                            self.parser.prev_token();
+                            Ok(Statement::Statement(Box::from(
+                                self.parser.parse_statement()?,
+                            )))
+                        }
+                    }
+                    Keyword::ROLLBACK => {
+                        // move one token forward
+                        self.parser.next_token();
+                        let work = self
+                            .parser
+                            .parse_one_of_keywords(&[Keyword::TRANSACTION, Keyword::WORK])
+                            .is_some();
+                        if self.parser.parse_keyword(Keyword::TO) {
+                            // use custom parsing
+                            self.parse_rollback_to_savepoint()
+                        } else {
+                            // rewind and use the native parser
+                            if work {
+                                self.parser.prev_token();
+                            }
+                            self.parser.prev_token();
+                            Ok(Statement::Statement(Box::from(
+                                self.parser.parse_statement()?,
+                            )))
+                        }
+                    }
+                    _ if w.value.eq_ignore_ascii_case("RELEASE") => {
+                        // move one token forward
+                        self.parser.next_token();
+                        // use custom parsing
+                        self.parse_release_savepoint()
+                    }
                     _ => {
                         // use the native parser
                         Ok(Statement::Statement(Box::from(
@@ -213,13 +358,135 @@ impl<'a> DFParser<'a> {
         Ok(Statement::DescribeTable(DescribeTable { table_name }))
     }
 
-    /// Parse a SQL `CREATE` statementm handling `CREATE EXTERNAL TABLE`
+    /// Parse a SQL `ANALYZE TABLE` statement
+    pub fn parse_analyze(&mut self) -> Result<Statement, ParserError> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let table_name = self.parser.parse_object_name()?;
+
+        let mut columns = None;
+        if self
+            .parser
+            .parse_keywords(&[Keyword::COMPUTE, Keyword::STATISTICS])
+            && self
+                .parser
+                .parse_keywords(&[Keyword::FOR, Keyword::COLUMNS])
+        {
+            columns = Some(
+                self.parser
+                    .parse_comma_separated(Parser::parse_identifier)?,
+            );
+        }
+
+        Ok(Statement::AnalyzeTable(AnalyzeTable {
+            table_name,
+            columns,
+        }))
+    }
+
+    /// Parse a SQL `CREATE` statement, handling `CREATE EXTERNAL TABLE` and
+    /// `CREATE FUNCTION`
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
-            self.parse_create_external_table()
+            return self.parse_create_external_table();
+        }
+
+        let or_replace = self.parser.parse_keywords(&[Keyword::OR, Keyword::REPLACE]);
+        if self.parser.parse_keyword(Keyword::FUNCTION) {
+            return self.parse_create_function(or_replace);
+        }
+        if or_replace {
+            // rewind so that the native parser sees `OR REPLACE`
+            self.parser.prev_token();
+            self.parser.prev_token();
+        }
+        Ok(Statement::Statement(Box::from(self.parser.parse_create()?)))
+    }
+
+    /// Parse a SQL `CREATE FUNCTION` statement, after `FUNCTION`
+    fn parse_create_function(
+        &mut self,
+        or_replace: bool,
+    ) -> Result<Statement, ParserError> {
+        let name = self.parser.parse_object_name()?;
+
+        self.parser.expect_token(&Token::LParen)?;
+        let args = if self.parser.consume_token(&Token::RParen) {
+            vec![]
         } else {
-            Ok(Statement::Statement(Box::from(self.parser.parse_create()?)))
+            let args = self
+                .parser
+                .parse_comma_separated(Self::parse_function_arg)?;
+            self.parser.expect_token(&Token::RParen)?;
+            args
+        };
+
+        let mut return_type = None;
+        let mut language = None;
+        let mut definition = None;
+        loop {
+            if self.parser.parse_keyword(Keyword::RETURNS) {
+                ensure_not_set(&return_type, "RETURNS")?;
+                return_type = Some(self.parser.parse_data_type()?);
+            } else if self.parser.parse_keyword(Keyword::LANGUAGE) {
+                ensure_not_set(&language, "LANGUAGE")?;
+                language = Some(self.parser.parse_identifier()?);
+            } else if self.parser.parse_keyword(Keyword::AS) {
+                ensure_not_set(&definition, "AS")?;
+                definition = Some(self.parser.parse_literal_string()?);
+            } else {
+                break;
+            }
         }
+
+        Ok(Statement::CreateFunction(CreateFunction {
+            or_replace,
+            name,
+            args,
+            return_type,
+            language,
+            definition,
+        }))
+    }
+
+    fn parse_function_arg(parser: &mut Parser) -> Result<CreateFunctionArg, ParserError> {
+        // the argument name is optional: `f(INT)` or `f(x INT)`
+        let name = match parser.peek_nth_token(1).token {
+            Token::Comma | Token::RParen | Token::LParen => None,
+            Token::Word(w) if w.keyword == Keyword::DEFAULT => None,
+            _ => Some(parser.parse_identifier()?),
+        };
+        let data_type = parser.parse_data_type()?;
+        let default = if parser.parse_keyword(Keyword::DEFAULT) {
+            Some(parser.parse_expr()?)
+        } else {
+            None
+        };
+        Ok(CreateFunctionArg {
+            name,
+            data_type,
+            default,
+        })
+    }
+
+    /// Parse a SQL `DROP FUNCTION` statement, after `FUNCTION`
+    fn parse_drop_function(&mut self) -> Result<Statement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+        Ok(Statement::DropFunction(DropFunction { if_exists, name }))
+    }
+
+    /// Parse the rest of a `ROLLBACK TO SAVEPOINT` statement, after `TO`
+    fn parse_rollback_to_savepoint(&mut self) -> Result<Statement, ParserError> {
+        self.parser.parse_keyword(Keyword::SAVEPOINT);
+        let name = self.parser.parse_identifier()?;
+        Ok(Statement::RollbackToSavepoint(RollbackToSavepoint { name }))
+    }
+
+    /// Parse the rest of a `RELEASE SAVEPOINT` statement, after `RELEASE`
+    fn parse_release_savepoint(&mut self) -> Result<Statement, ParserError> {
+        self.parser.parse_keyword(Keyword::SAVEPOINT);
+        let name = self.parser.parse_identifier()?;
+        Ok(Statement::ReleaseSavepoint(ReleaseSavepoint { name }))
     }
 
     fn parse_partitions(&mut self) -> Result<Vec<String>, ParserError> {
@@ -397,7 +664,7 @@ impl<'a> DFParser<'a> {
         let token = self.parser.next_token();
         match &token.token {
             Token::Word(w) => CompressionTypeVariant::from_str(&w.value),
-            _ => self.expected("one of GZIP, BZIP2, XZ", token),
+            _ => self.expected("one of GZIP, BZIP2, XZ, ZSTD, BGZIP", token),
         }
     }
 
@@ -461,7 +728,7 @@ impl<'a> DFParser<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sqlparser::ast::{DataType, Ident};
+    use sqlparser::ast::Value;
     use CompressionTypeVariant::UNCOMPRESSED;
 
     fn expect_parse_ok(sql: &str, expected: Statement) -> Result<(), ParserError> {
@@ -736,6 +1003,137 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn analyze_table() -> Result<(), ParserError> {
+        let sql = "ANALYZE TABLE t";
+        let expected = Statement::AnalyzeTable(AnalyzeTable {
+            table_name: ObjectName(vec![Ident::new("t")]),
+            columns: None,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "ANALYZE TABLE s.t COMPUTE STATISTICS";
+        let expected = Statement::AnalyzeTable(AnalyzeTable {
+            table_name: ObjectName(vec![Ident::new("s"), Ident::new("t")]),
+            columns: None,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "ANALYZE TABLE t COMPUTE STATISTICS FOR COLUMNS c1, c2";
+        let expected = Statement::AnalyzeTable(AnalyzeTable {
+            table_name: ObjectName(vec![Ident::new("t")]),
+            columns: Some(vec![Ident::new("c1"), Ident::new("c2")]),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        expect_parse_error("ANALYZE t", "sql parser error: Expected TABLE, found: t");
+
+        Ok(())
+    }
+
+    #[test]
+    fn create_function() -> Result<(), ParserError> {
+        let sql = "CREATE FUNCTION add_one(INT) RETURNS INT LANGUAGE sql AS '$1 + 1'";
+        let expected = Statement::CreateFunction(CreateFunction {
+            or_replace: false,
+            name: ObjectName(vec![Ident::new("add_one")]),
+            args: vec![CreateFunctionArg {
+                name: None,
+                data_type: DataType::Int(None),
+                default: None,
+            }],
+            return_type: Some(DataType::Int(None)),
+            language: Some(Ident::new("sql")),
+            definition: Some("$1 + 1".to_string()),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "CREATE OR REPLACE FUNCTION s.f(x DOUBLE, y DOUBLE DEFAULT 1) \
+            AS 'x * y' LANGUAGE sql";
+        let expected = Statement::CreateFunction(CreateFunction {
+            or_replace: true,
+            name: ObjectName(vec![Ident::new("s"), Ident::new("f")]),
+            args: vec![
+                CreateFunctionArg {
+                    name: Some(Ident::new("x")),
+                    data_type: DataType::Double,
+                    default: None,
+                },
+                CreateFunctionArg {
+                    name: Some(Ident::new("y")),
+                    data_type: DataType::Double,
+                    default: Some(Expr::Value(Value::Number("1".to_string(), false))),
+                },
+            ],
+            return_type: None,
+            language: Some(Ident::new("sql")),
+            definition: Some("x * y".to_string()),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        // other `CREATE OR REPLACE` statements are left to the native parser
+        let sql = "CREATE OR REPLACE VIEW v AS SELECT 1";
+        let statements = DFParser::parse_sql(sql)?;
+        assert!(matches!(&statements[0], Statement::Statement(_)));
+
+        expect_parse_error(
+            "CREATE FUNCTION f() RETURNS INT RETURNS INT",
+            "sql parser error: RETURNS specified more than once",
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn drop_function() -> Result<(), ParserError> {
+        let expected = Statement::DropFunction(DropFunction {
+            if_exists: false,
+            name: ObjectName(vec![Ident::new("f")]),
+        });
+        expect_parse_ok("DROP FUNCTION f", expected)?;
+
+        let expected = Statement::DropFunction(DropFunction {
+            if_exists: true,
+            name: ObjectName(vec![Ident::new("f")]),
+        });
+        expect_parse_ok("DROP FUNCTION IF EXISTS f", expected)?;
+
+        // other `DROP` statements are left to the native parser
+        let statements = DFParser::parse_sql("DROP TABLE t")?;
+        assert!(matches!(&statements[0], Statement::Statement(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn savepoints() -> Result<(), ParserError> {
+        let expected = Statement::RollbackToSavepoint(RollbackToSavepoint {
+            name: Ident::new("s"),
+        });
+        expect_parse_ok("ROLLBACK TO SAVEPOINT s", expected.clone())?;
+        expect_parse_ok("ROLLBACK WORK TO s", expected)?;
+
+        let expected = Statement::ReleaseSavepoint(ReleaseSavepoint {
+            name: Ident::new("s"),
+        });
+        expect_parse_ok("RELEASE SAVEPOINT s", expected.clone())?;
+        expect_parse_ok("release s", expected)?;
+
+        // other transaction statements are left to the native parser
+        for sql in [
+            "BEGIN",
+            "SAVEPOINT s",
+            "ROLLBACK",
+            "ROLLBACK WORK",
+            "COMMIT",
+        ] {
+            let statements = DFParser::parse_sql(sql)?;
+            assert!(matches!(&statements[0], Statement::Statement(_)));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn invalid_compression_type() {
         let sql = "CREATE EXTERNAL TABLE t STORED AS CSV COMPRESSION TYPE ZZZ LOCATION 'blahblah'";
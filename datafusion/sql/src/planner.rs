@@ -27,7 +27,10 @@ use sqlparser::ast::{ColumnDef as SQLColumnDef, ColumnOption};
 use sqlparser::ast::{DataType as SQLDataType, Ident, ObjectName, TableAlias};
 
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::{field_not_found, DFSchema, DataFusionError, Result};
+use datafusion_common::{
+    field_not_found, find_closest_match, DFSchema, DataFusionError, Diagnostic,
+    ErrorCode, Result, ScalarValue, SchemaError,
+};
 use datafusion_common::{OwnedTableReference, TableReference};
 use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
 use datafusion_expr::utils::find_column_exprs;
@@ -47,15 +50,91 @@ pub trait ContextProvider {
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>>;
     /// Getter for system/user-defined variable type
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType>;
+    /// Getter for the names of the tables that can be referenced without
+    /// qualifier, to suggest a table when a name is misspelled
+    fn table_names(&self) -> Vec<String> {
+        vec![]
+    }
+    /// Getter for the names of the UDFs and UDAFs, to suggest a function when
+    /// a name is misspelled
+    fn function_names(&self) -> Vec<String> {
+        vec![]
+    }
+    /// Getter for a datasource returning the rows of the table function
+    /// `name` called with `args`, such as `SELECT * FROM name(1, 'a')`
+    fn get_table_function_source(
+        &self,
+        name: &str,
+        _args: Vec<Expr>,
+    ) -> Result<Arc<dyn TableSource>> {
+        Err(DataFusionError::Plan(format!(
+            "table function '{name}' not found"
+        )))
+    }
+    /// Getter for a datasource scanning the rows of a large VALUES list of
+    /// literals, cast to the types of `schema`, or `None` to plan
+    /// the list as expressions
+    fn create_values_table(
+        &self,
+        _schema: SchemaRef,
+        _rows: &[Vec<ScalarValue>],
+    ) -> Result<Option<Arc<dyn TableSource>>> {
+        Ok(None)
+    }
 
     /// Get configuration options
     fn options(&self) -> &ConfigOptions;
 }
 
 /// SQL parser options
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ParserOptions {
     pub parse_float_as_decimal: bool,
+    /// Whether the SELECT list and the WHERE clause can reference the aliases
+    /// of the previous expressions of the SELECT list
+    pub enable_lateral_column_alias: bool,
+    /// Whether unquoted identifiers are converted to lowercase
+    pub enable_ident_normalization: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            parse_float_as_decimal: false,
+            enable_lateral_column_alias: false,
+            enable_ident_normalization: true,
+        }
+    }
+}
+
+/// Converts the identifiers of a statement to the names of tables, columns,
+/// aliases, etc.
+#[derive(Debug)]
+pub struct IdentNormalizer {
+    normalize: bool,
+}
+
+impl Default for IdentNormalizer {
+    fn default() -> Self {
+        Self { normalize: true }
+    }
+}
+
+impl IdentNormalizer {
+    /// Create a normalizer that converts unquoted identifiers to lowercase if
+    /// `normalize` is true, and preserves the case of all identifiers otherwise
+    pub fn new(normalize: bool) -> Self {
+        Self { normalize }
+    }
+
+    /// Returns the name of the identifier `id`
+    pub fn normalize(&self, id: Ident) -> String {
+        if self.normalize {
+            normalize_ident(id)
+        } else {
+            id.value
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -98,6 +177,7 @@ impl PlannerContext {
 pub struct SqlToRel<'a, S: ContextProvider> {
     pub(crate) schema_provider: &'a S,
     pub(crate) options: ParserOptions,
+    pub(crate) normalizer: IdentNormalizer,
 }
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -108,12 +188,62 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
     /// Create a new query planner
     pub fn new_with_options(schema_provider: &'a S, options: ParserOptions) -> Self {
+        let normalizer = IdentNormalizer::new(options.enable_ident_normalization);
         SqlToRel {
             schema_provider,
             options,
+            normalizer,
+        }
+    }
+
+    /// Returns the candidate to suggest instead of the unknown `name`, if
+    /// suggestions are enabled
+    pub(crate) fn suggest<'b>(
+        &self,
+        name: &str,
+        candidates: impl IntoIterator<Item = &'b str>,
+    ) -> Option<String> {
+        if self.schema_provider.options().sql_parser.enable_suggestions {
+            find_closest_match(name, candidates)
+        } else {
+            None
         }
     }
 
+    /// Attaches a [`Diagnostic`] to the schema errors about columns, which
+    /// are raised while building the plan rather than by the planner
+    pub(crate) fn diagnose(&self, error: DataFusionError) -> DataFusionError {
+        if error.diagnostic().is_some() {
+            return error;
+        }
+        let diagnostic = match error.find_root() {
+            DataFusionError::SchemaError(SchemaError::FieldNotFound {
+                field,
+                valid_fields,
+            }) => {
+                let candidates = valid_fields.iter().flatten().map(|f| f.name.as_str());
+                Diagnostic::new(format!("No field named '{}'", field.name))
+                    .with_code(ErrorCode::UnknownColumn)
+                    .with_snippet(field.flat_name())
+                    .with_suggestion(self.suggest(&field.name, candidates))
+            }
+            DataFusionError::SchemaError(SchemaError::AmbiguousReference {
+                qualifier,
+                name,
+            }) => {
+                let snippet = match qualifier {
+                    Some(qualifier) => format!("{qualifier}.{name}"),
+                    None => name.clone(),
+                };
+                Diagnostic::new(format!("Ambiguous reference to field '{name}'"))
+                    .with_code(ErrorCode::AmbiguousColumn)
+                    .with_snippet(snippet)
+            }
+            _ => return error,
+        };
+        error.with_diagnostic(diagnostic)
+    }
+
     pub(crate) fn build_schema(&self, columns: Vec<SQLColumnDef>) -> Result<Schema> {
         let mut fields = Vec::with_capacity(columns.len());
 
@@ -124,7 +254,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 .iter()
                 .any(|x| x.option == ColumnOption::Null);
             fields.push(Field::new(
-                normalize_ident(column.name),
+                self.normalizer.normalize(column.name),
                 data_type,
                 allow_null,
             ));
@@ -141,7 +271,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     ) -> Result<LogicalPlan> {
         let apply_name_plan = LogicalPlan::SubqueryAlias(SubqueryAlias::try_new(
             plan,
-            normalize_ident(alias.name),
+            self.normalizer.normalize(alias.name),
         )?);
 
         self.apply_expr_alias(apply_name_plan, alias.columns)
@@ -164,7 +294,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             let fields = plan.schema().fields().clone();
             LogicalPlanBuilder::from(plan)
                 .project(fields.iter().zip(idents.into_iter()).map(|(field, ident)| {
-                    col(field.name()).alias(normalize_ident(ident))
+                    col(field.name()).alias(self.normalizer.normalize(ident))
                 }))?
                 .build()
         }
@@ -277,6 +407,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 make_decimal_type(precision, scale)
             }
             SQLDataType::Bytea => Ok(DataType::Binary),
+            SQLDataType::Interval => Ok(DataType::Interval(IntervalUnit::MonthDayNano)),
             // Explicitly list all other types so that if sqlparser
             // adds/changes the `SQLDataType` the compiler will tell us on upgrade
             // and avoid bugs like https://github.com/apache/arrow-datafusion/issues/3059
@@ -286,7 +417,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             | SQLDataType::Varbinary(_)
             | SQLDataType::Blob(_)
             | SQLDataType::Datetime(_)
-            | SQLDataType::Interval
             | SQLDataType::Regclass
             | SQLDataType::Custom(_, _)
             | SQLDataType::Array(_)
@@ -321,31 +451,42 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 /// ['foo', 'bar']   -> Partial { schema: "foo", table: "bar" }
 /// ['foo', '"Bar"'] -> Partial { schema: "foo", table: "Bar" }
 /// ```
+///
+/// When `enable_normalization` is false, the case of the identifiers is
+/// preserved: `['foo', 'Bar']` is `Partial { schema: "foo", table: "Bar" }`
 pub fn object_name_to_table_reference(
     object_name: ObjectName,
+    enable_normalization: bool,
 ) -> Result<OwnedTableReference> {
     // use destructure to make it clear no fields on ObjectName are ignored
     let ObjectName(idents) = object_name;
-    idents_to_table_reference(idents)
+    idents_to_table_reference(idents, enable_normalization)
 }
 
 /// Create a [`OwnedTableReference`] after normalizing the specified identifier
 pub(crate) fn idents_to_table_reference(
     idents: Vec<Ident>,
+    enable_normalization: bool,
 ) -> Result<OwnedTableReference> {
-    struct IdentTaker(Vec<Ident>);
+    struct IdentTaker {
+        normalizer: IdentNormalizer,
+        idents: Vec<Ident>,
+    }
     /// take the next identifier from the back of idents, panic'ing if
     /// there are none left
     impl IdentTaker {
         fn take(&mut self) -> String {
-            let ident = self.0.pop().expect("no more identifiers");
-            normalize_ident(ident)
+            let ident = self.idents.pop().expect("no more identifiers");
+            self.normalizer.normalize(ident)
         }
     }
 
-    let mut taker = IdentTaker(idents);
+    let mut taker = IdentTaker {
+        normalizer: IdentNormalizer::new(enable_normalization),
+        idents,
+    };
 
-    match taker.0.len() {
+    match taker.idents.len() {
         1 => {
             let table = taker.take();
             Ok(OwnedTableReference::Bare { table })
@@ -367,14 +508,18 @@ pub(crate) fn idents_to_table_reference(
         }
         _ => Err(DataFusionError::Plan(format!(
             "Unsupported compound identifier '{:?}'",
-            taker.0,
+            taker.idents,
         ))),
     }
 }
 
 /// Construct a WHERE qualifier suitable for e.g. information_schema filtering
 /// from the provided object identifiers (catalog, schema and table names).
-pub fn object_name_to_qualifier(sql_table_name: &ObjectName) -> String {
+pub fn object_name_to_qualifier(
+    sql_table_name: &ObjectName,
+    enable_normalization: bool,
+) -> String {
+    let normalizer = IdentNormalizer::new(enable_normalization);
     let columns = vec!["table_name", "table_schema", "table_catalog"].into_iter();
     sql_table_name
         .0
@@ -382,7 +527,11 @@ pub fn object_name_to_qualifier(sql_table_name: &ObjectName) -> String {
         .rev()
         .zip(columns)
         .map(|(ident, column_name)| {
-            format!(r#"{} = '{}'"#, column_name, normalize_ident(ident.clone()))
+            format!(
+                r#"{} = '{}'"#,
+                column_name,
+                normalizer.normalize(ident.clone())
+            )
         })
         .collect::<Vec<_>>()
         .join(" AND ")
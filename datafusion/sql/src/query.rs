@@ -16,10 +16,11 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use crate::utils::normalize_ident;
+use crate::utils::is_all_keyword;
 use datafusion_common::{DFSchema, DataFusionError, Result, ScalarValue};
-use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{Expr as SQLExpr, Offset as SQLOffset, OrderByExpr, Query};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, SharedCte};
+use sqlparser::ast::{Expr as SQLExpr, Offset as SQLOffset, OrderByExpr, Query, Value};
+use std::sync::Arc;
 
 use sqlparser::parser::ParserError::ParserError;
 
@@ -64,7 +65,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
             for cte in with.cte_tables {
                 // A `WITH` block can't use the same name more than once
-                let cte_name = normalize_ident(cte.alias.name.clone());
+                let cte_name = self.normalizer.normalize(cte.alias.name.clone());
                 if planner_context.ctes.contains_key(&cte_name) {
                     return Err(DataFusionError::SQL(ParserError(format!(
                         "WITH query name {cte_name:?} specified more than once"
@@ -79,6 +80,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
                 let logical_plan = self.apply_table_alias(logical_plan, cte.alias)?;
 
+                // All the references to the CTE share its id, so that its plan can
+                // be executed once for all of them
+                let logical_plan = LogicalPlan::SharedCte(SharedCte::new(
+                    cte_name.clone(),
+                    Arc::new(logical_plan),
+                ));
                 planner_context.ctes.insert(cte_name, logical_plan);
             }
         }
@@ -150,6 +157,22 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             return Ok(plan);
         }
 
+        // ORDER BY ALL sorts by all the columns, from left to right
+        let order_by = match order_by.as_slice() {
+            [OrderByExpr {
+                expr,
+                asc,
+                nulls_first,
+            }] if is_all_keyword(expr) => (1..=plan.schema().fields().len())
+                .map(|position| OrderByExpr {
+                    expr: SQLExpr::Value(Value::Number(position.to_string(), false)),
+                    asc: *asc,
+                    nulls_first: *nulls_first,
+                })
+                .collect(),
+            _ => order_by,
+        };
+
         let order_by_rex = order_by
             .into_iter()
             .map(|e| self.order_by_to_sort_expr(e, plan.schema()))
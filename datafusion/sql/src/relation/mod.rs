@@ -18,7 +18,7 @@
 use crate::planner::{
     object_name_to_table_reference, ContextProvider, PlannerContext, SqlToRel,
 };
-use datafusion_common::{DataFusionError, Result};
+use datafusion_common::{DFSchema, DataFusionError, Diagnostic, ErrorCode, Result};
 use datafusion_expr::{LogicalPlan, LogicalPlanBuilder};
 use sqlparser::ast::TableFactor;
 
@@ -31,9 +31,42 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         let (plan, alias) = match relation {
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                ..
+            } => {
+                let table_ref = object_name_to_table_reference(
+                    name,
+                    self.options.enable_ident_normalization,
+                )?;
+                let table_name = table_ref.to_string();
+                let args = args
+                    .into_iter()
+                    .map(|arg| {
+                        self.sql_fn_arg_to_logical_expr(
+                            arg,
+                            &DFSchema::empty(),
+                            planner_context,
+                        )
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let source = self
+                    .schema_provider
+                    .get_table_function_source(&table_name, args)?;
+                (
+                    LogicalPlanBuilder::scan(&table_name, source, None)?.build()?,
+                    alias,
+                )
+            }
             TableFactor::Table { name, alias, .. } => {
+                let sql_name = name.to_string();
                 // normalize name and alias
-                let table_ref = object_name_to_table_reference(name)?;
+                let table_ref = object_name_to_table_reference(
+                    name,
+                    self.options.enable_ident_normalization,
+                )?;
                 let table_name = table_ref.to_string();
                 let cte = planner_context.ctes.get(&table_name);
                 (
@@ -45,7 +78,21 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         (_, Ok(provider)) => {
                             LogicalPlanBuilder::scan(&table_name, provider, None)?.build()
                         }
-                        (None, Err(e)) => Err(e),
+                        (None, Err(e)) => {
+                            let table_names = self.schema_provider.table_names();
+                            let candidates = table_names
+                                .iter()
+                                .chain(planner_context.ctes.keys())
+                                .map(|name| name.as_str());
+                            let diagnostic = Diagnostic::new(e.to_string())
+                                .with_code(ErrorCode::UnknownTable)
+                                .with_suggestion(self.suggest(
+                                    table_ref.as_table_reference().table(),
+                                    candidates,
+                                ))
+                                .with_snippet(sql_name);
+                            Err(e.with_diagnostic(diagnostic))
+                        }
                     }?,
                     alias,
                 )
@@ -17,7 +17,7 @@
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 use crate::utils::{
-    check_columns_satisfy_exprs, extract_aliases, normalize_ident, rebase_expr,
+    check_columns_satisfy_exprs, extract_aliases, is_all_keyword, rebase_expr,
     resolve_aliases_to_exprs, resolve_columns, resolve_positions_to_exprs,
 };
 use datafusion_common::{DFSchema, DFSchemaRef, DataFusionError, Result};
@@ -27,15 +27,19 @@ use datafusion_expr::logical_plan::Join as HashJoin;
 use datafusion_expr::logical_plan::JoinConstraint as HashJoinConstraint;
 use datafusion_expr::utils::{
     expand_qualified_wildcard, expand_wildcard, expr_as_column_expr, expr_to_columns,
-    find_aggregate_exprs, find_column_exprs, find_window_exprs,
+    find_aggregate_exprs, find_column_exprs, find_column_exprs_outside_aggregates,
+    find_window_exprs,
 };
 use datafusion_expr::Expr::Alias;
 use datafusion_expr::{
     Expr, Filter, GroupingSet, LogicalPlan, LogicalPlanBuilder, Partitioning,
 };
-use sqlparser::ast::{Expr as SQLExpr, WildcardAdditionalOptions};
+use sqlparser::ast::{
+    ExcludeSelectItem, Expr as SQLExpr, IdentWithAlias, RenameSelectItem,
+    WildcardAdditionalOptions,
+};
 use sqlparser::ast::{Select, SelectItem, TableWithJoins};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -67,22 +71,53 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         // we should get only one field for unqualifier column from schema.
         let from_schema = self.build_schema_for_ambiguous_check(&plan)?;
 
-        // process `where` clause
-        let plan = self.plan_selection(
-            select.selection,
-            plan,
-            outer_query_schema,
-            planner_context,
-        )?;
+        let (plan, select_exprs) = if self.options.enable_lateral_column_alias {
+            // process the SELECT expressions first, as the `where` clause may
+            // reference their aliases
+            let select_exprs = self.prepare_select_exprs(
+                &plan,
+                select.projection,
+                empty_from,
+                planner_context,
+                &from_schema,
+            )?;
+            let aliases: HashMap<_, _> = extract_aliases(&select_exprs)
+                .into_iter()
+                .filter(|(name, expr)| {
+                    let expr = std::slice::from_ref(expr);
+                    plan.schema().fields_with_unqualified_name(name).is_empty()
+                        && find_aggregate_exprs(expr).is_empty()
+                        && find_window_exprs(expr).is_empty()
+                })
+                .collect();
+            let plan = self.plan_selection(
+                select.selection,
+                plan,
+                outer_query_schema,
+                &aliases,
+                planner_context,
+            )?;
+            (plan, select_exprs)
+        } else {
+            // process `where` clause
+            let plan = self.plan_selection(
+                select.selection,
+                plan,
+                outer_query_schema,
+                &HashMap::new(),
+                planner_context,
+            )?;
 
-        // process the SELECT expressions, with wildcards expanded.
-        let select_exprs = self.prepare_select_exprs(
-            &plan,
-            select.projection,
-            empty_from,
-            planner_context,
-            &from_schema,
-        )?;
+            // process the SELECT expressions, with wildcards expanded.
+            let select_exprs = self.prepare_select_exprs(
+                &plan,
+                select.projection,
+                empty_from,
+                planner_context,
+                &from_schema,
+            )?;
+            (plan, select_exprs)
+        };
 
         // having and group by clause may reference aliases defined in select projection
         let projected_plan = self.project(plan.clone(), select_exprs.clone())?;
@@ -131,29 +166,57 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let aggr_exprs = find_aggregate_exprs(&aggr_expr_haystack);
 
         // All of the group by expressions
-        let group_by_exprs = select
-            .group_by
-            .into_iter()
-            .map(|e| {
-                let group_by_expr =
-                    self.sql_expr_to_logical_expr(e, &combined_schema, planner_context)?;
-                // aliases from the projection can conflict with same-named expressions in the input
-                let mut alias_map = alias_map.clone();
-                for f in plan.schema().fields() {
-                    alias_map.remove(f.name());
+        let group_by_all = matches!(select.group_by.as_slice(), [e] if is_all_keyword(e));
+        let group_by_exprs = if group_by_all {
+            // GROUP BY ALL groups by the SELECT expressions without aggregate or
+            // window functions, and by the columns the other expressions
+            // reference outside of aggregate functions
+            let mut group_by_exprs = vec![];
+            for select_expr in &select_exprs {
+                let e = std::slice::from_ref(select_expr);
+                let keys = if find_aggregate_exprs(e).is_empty()
+                    && find_window_exprs(e).is_empty()
+                {
+                    vec![select_expr.clone().unalias()]
+                } else {
+                    find_column_exprs_outside_aggregates(e)
+                };
+                for key in keys {
+                    if !group_by_exprs.contains(&key) {
+                        group_by_exprs.push(key);
+                    }
                 }
-                let group_by_expr = resolve_aliases_to_exprs(&group_by_expr, &alias_map)?;
-                let group_by_expr =
-                    resolve_positions_to_exprs(&group_by_expr, &select_exprs)
-                        .unwrap_or(group_by_expr);
-                let group_by_expr = normalize_col(group_by_expr, &projected_plan)?;
-                self.validate_schema_satisfies_exprs(
-                    plan.schema(),
-                    &[group_by_expr.clone()],
-                )?;
-                Ok(group_by_expr)
-            })
-            .collect::<Result<Vec<Expr>>>()?;
+            }
+            group_by_exprs
+        } else {
+            select
+                .group_by
+                .into_iter()
+                .map(|e| {
+                    let group_by_expr = self.sql_expr_to_logical_expr(
+                        e,
+                        &combined_schema,
+                        planner_context,
+                    )?;
+                    // aliases from the projection can conflict with same-named expressions in the input
+                    let mut alias_map = alias_map.clone();
+                    for f in plan.schema().fields() {
+                        alias_map.remove(f.name());
+                    }
+                    let group_by_expr =
+                        resolve_aliases_to_exprs(&group_by_expr, &alias_map)?;
+                    let group_by_expr =
+                        resolve_positions_to_exprs(&group_by_expr, &select_exprs)
+                            .unwrap_or(group_by_expr);
+                    let group_by_expr = normalize_col(group_by_expr, &projected_plan)?;
+                    self.validate_schema_satisfies_exprs(
+                        plan.schema(),
+                        &[group_by_expr.clone()],
+                    )?;
+                    Ok(group_by_expr)
+                })
+                .collect::<Result<Vec<Expr>>>()?
+        };
 
         // process group by, aggregation or having
         let (plan, mut select_exprs_post_aggr, having_expr_post_aggr) = if !group_by_exprs
@@ -233,11 +296,14 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
     /// Generate a logic plan from selection clause, the function contain optimization for cross join to inner join
     /// Related PR: <https://github.com/apache/arrow-datafusion/pull/1566>
+    ///
+    /// The selection may reference the SELECT expressions of `aliases`.
     fn plan_selection(
         &self,
         selection: Option<SQLExpr>,
         plan: LogicalPlan,
         outer_query_schema: Option<&DFSchema>,
+        aliases: &HashMap<String, Expr>,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         match selection {
@@ -253,8 +319,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 }
                 let x: Vec<&DFSchemaRef> = all_schemas.iter().collect();
 
-                let filter_expr =
-                    self.sql_to_expr(predicate_expr, &join_schema, planner_context)?;
+                let filter_expr = self.sql_to_expr_with_aliases(
+                    predicate_expr,
+                    &join_schema,
+                    aliases,
+                    planner_context,
+                )?;
                 let mut using_columns = HashSet::new();
                 expr_to_columns(&filter_expr, &mut using_columns)?;
                 let filter_expr = normalize_col_with_schemas(
@@ -309,22 +379,31 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         planner_context: &mut PlannerContext,
         from_schema: &DFSchema,
     ) -> Result<Vec<Expr>> {
-        projection
-            .into_iter()
-            .map(|expr| {
-                self.sql_select_to_rex(
-                    expr,
-                    plan,
-                    empty_from,
-                    planner_context,
-                    from_schema,
-                )
-            })
-            .flat_map(|result| match result {
-                Ok(vec) => vec.into_iter().map(Ok).collect(),
-                Err(err) => vec![Err(err)],
-            })
-            .collect::<Result<Vec<Expr>>>()
+        let mut select_exprs = vec![];
+        // the aliases of the previous SELECT expressions that can be referenced
+        let mut lateral_aliases = HashMap::new();
+        for item in projection {
+            let exprs = self.sql_select_to_rex(
+                item,
+                plan,
+                empty_from,
+                planner_context,
+                from_schema,
+                &lateral_aliases,
+            )?;
+            if self.options.enable_lateral_column_alias {
+                for expr in &exprs {
+                    if let Alias(expr, name) = expr {
+                        // the columns of the input take precedence over the aliases
+                        if plan.schema().fields_with_unqualified_name(name).is_empty() {
+                            lateral_aliases.insert(name.clone(), *expr.clone());
+                        }
+                    }
+                }
+            }
+            select_exprs.extend(exprs);
+        }
+        Ok(select_exprs)
     }
 
     /// Generate a relational expression from a select SQL expression
@@ -335,40 +414,49 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         empty_from: bool,
         planner_context: &mut PlannerContext,
         from_schema: &DFSchema,
+        lateral_aliases: &HashMap<String, Expr>,
     ) -> Result<Vec<Expr>> {
         match sql {
             SelectItem::UnnamedExpr(expr) => {
-                let expr = self.sql_to_expr(expr, plan.schema(), planner_context)?;
+                let expr = self.sql_to_expr_with_aliases(
+                    expr,
+                    plan.schema(),
+                    lateral_aliases,
+                    planner_context,
+                )?;
                 self.column_reference_ambiguous_check(from_schema, &[expr.clone()])?;
                 Ok(vec![normalize_col(expr, plan)?])
             }
             SelectItem::ExprWithAlias { expr, alias } => {
-                let select_expr =
-                    self.sql_to_expr(expr, plan.schema(), planner_context)?;
+                let select_expr = self.sql_to_expr_with_aliases(
+                    expr,
+                    plan.schema(),
+                    lateral_aliases,
+                    planner_context,
+                )?;
                 self.column_reference_ambiguous_check(
                     from_schema,
                     &[select_expr.clone()],
                 )?;
-                let expr = Alias(Box::new(select_expr), normalize_ident(alias));
+                let expr = Alias(Box::new(select_expr), self.normalizer.normalize(alias));
                 Ok(vec![normalize_col(expr, plan)?])
             }
             SelectItem::Wildcard(options) => {
-                Self::check_wildcard_options(options)?;
-
                 if empty_from {
                     return Err(DataFusionError::Plan(
                         "SELECT * with no tables specified is not valid".to_string(),
                     ));
                 }
                 // do not expand from outer schema
-                expand_wildcard(plan.schema().as_ref(), plan)
+                let exprs = expand_wildcard(plan.schema().as_ref(), plan)?;
+                self.apply_wildcard_options(exprs, options)
             }
             SelectItem::QualifiedWildcard(ref object_name, options) => {
-                Self::check_wildcard_options(options)?;
-
                 let qualifier = format!("{object_name}");
                 // do not expand from outer schema
-                expand_qualified_wildcard(&qualifier, plan.schema().as_ref())
+                let exprs =
+                    expand_qualified_wildcard(&qualifier, plan.schema().as_ref())?;
+                self.apply_wildcard_options(exprs, options)
             }
         }
     }
@@ -400,20 +488,78 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             })
     }
 
-    fn check_wildcard_options(options: WildcardAdditionalOptions) -> Result<()> {
+    /// Applies the `EXCLUDE`, `EXCEPT` and `RENAME` options of a wildcard to
+    /// the columns it expands to
+    fn apply_wildcard_options(
+        &self,
+        exprs: Vec<Expr>,
+        options: WildcardAdditionalOptions,
+    ) -> Result<Vec<Expr>> {
         let WildcardAdditionalOptions {
             opt_exclude,
             opt_except,
             opt_rename,
         } = options;
 
-        if opt_exclude.is_some() || opt_except.is_some() || opt_rename.is_some() {
-            Err(DataFusionError::NotImplemented(
-                "wildcard * with EXCLUDE, EXCEPT or RENAME not supported ".to_string(),
-            ))
-        } else {
-            Ok(())
+        let mut excluded = vec![];
+        if let Some(except) = opt_except {
+            excluded.push(except.first_element);
+            excluded.extend(except.additional_elements);
+        }
+        match opt_exclude {
+            Some(ExcludeSelectItem::Single(ident)) => excluded.push(ident),
+            Some(ExcludeSelectItem::Multiple(idents)) => excluded.extend(idents),
+            None => {}
+        }
+        let excluded = excluded
+            .into_iter()
+            .map(|ident| self.normalizer.normalize(ident))
+            .collect::<Vec<_>>();
+        let renamed = match opt_rename {
+            Some(RenameSelectItem::Single(rename)) => vec![rename],
+            Some(RenameSelectItem::Multiple(renames)) => renames,
+            None => vec![],
+        }
+        .into_iter()
+        .map(|IdentWithAlias { ident, alias }| {
+            (
+                self.normalizer.normalize(ident),
+                self.normalizer.normalize(alias),
+            )
+        })
+        .collect::<Vec<_>>();
+
+        let names = excluded.iter().chain(renamed.iter().map(|(name, _)| name));
+        let mut seen = HashSet::new();
+        for name in names {
+            if !seen.insert(name) {
+                return Err(DataFusionError::Plan(format!(
+                    "Column {name} is excluded or renamed more than once in the wildcard options"
+                )));
+            }
+            if !exprs
+                .iter()
+                .any(|expr| matches!(expr, Expr::Column(col) if &col.name == name))
+            {
+                return Err(DataFusionError::Plan(format!(
+                    "Column {name} of the wildcard options is not a column of the wildcard"
+                )));
+            }
         }
+
+        Ok(exprs
+            .into_iter()
+            .filter_map(|expr| match expr {
+                Expr::Column(col) if excluded.contains(&col.name) => None,
+                Expr::Column(col) => {
+                    match renamed.iter().find(|(name, _)| name == &col.name) {
+                        Some((_, alias)) => Some(Expr::Column(col).alias(alias)),
+                        None => Some(Expr::Column(col)),
+                    }
+                }
+                expr => Some(expr),
+            })
+            .collect())
     }
 
     /// build schema for unqualifier column ambiguous check
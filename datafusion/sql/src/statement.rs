@@ -16,12 +16,14 @@
 // under the License.
 
 use crate::parser::{
-    CreateExternalTable, DFParser, DescribeTable, Statement as DFStatement,
+    AnalyzeTable, CreateExternalTable, CreateFunction, DFParser, DescribeTable,
+    DropFunction, ReleaseSavepoint, RollbackToSavepoint, Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, object_name_to_table_reference, ContextProvider,
     PlannerContext, SqlToRel,
 };
+use crate::utils::{function_name, normalize_ident};
 use arrow_schema::DataType;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::{
@@ -30,10 +32,11 @@ use datafusion_common::{
 };
 use datafusion_expr::logical_plan::{Analyze, Prepare};
 use datafusion_expr::{
-    cast, col, CreateCatalog, CreateCatalogSchema,
-    CreateExternalTable as PlanCreateExternalTable, CreateMemoryTable, CreateView,
+    cast, col, AnalyzeTable as PlanAnalyzeTable, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable as PlanCreateExternalTable, CreateFunction as PlanCreateFunction,
+    CreateFunctionArg, CreateMemoryTable, CreateView, DropFunction as PlanDropFunction,
     DropTable, DropView, Explain, LogicalPlan, LogicalPlanBuilder, PlanType, SetVariable,
-    ToStringifiedPlan,
+    ToStringifiedPlan, Transaction, TransactionStatement,
 };
 use sqlparser::ast::{
     Expr as SQLExpr, Ident, ObjectName, ObjectType, ShowCreateObject,
@@ -45,16 +48,29 @@ use std::sync::Arc;
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// Generate a logical plan from an DataFusion SQL statement
     pub fn statement_to_plan(&self, statement: DFStatement) -> Result<LogicalPlan> {
-        match statement {
+        let plan = match statement {
             DFStatement::CreateExternalTable(s) => self.external_table_to_plan(s),
             DFStatement::Statement(s) => self.sql_statement_to_plan(*s),
             DFStatement::DescribeTable(s) => self.describe_table_to_plan(s),
-        }
+            DFStatement::AnalyzeTable(s) => self.analyze_table_to_plan(s),
+            DFStatement::CreateFunction(s) => self.create_function_to_plan(s),
+            DFStatement::DropFunction(s) => self.drop_function_to_plan(s),
+            DFStatement::RollbackToSavepoint(RollbackToSavepoint { name }) => {
+                let name = self.normalizer.normalize(name);
+                self.transaction_to_plan(TransactionStatement::RollbackToSavepoint(name))
+            }
+            DFStatement::ReleaseSavepoint(ReleaseSavepoint { name }) => {
+                let name = self.normalizer.normalize(name);
+                self.transaction_to_plan(TransactionStatement::ReleaseSavepoint(name))
+            }
+        };
+        plan.map_err(|e| self.diagnose(e))
     }
 
     /// Generate a logical plan from an SQL statement
     pub fn sql_statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
         self.sql_statement_to_plan_with_context(statement, &mut PlannerContext::new())
+            .map_err(|e| self.diagnose(e))
     }
 
     /// Generate a logical plan from an SQL statement
@@ -91,11 +107,17 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 with_options,
                 if_not_exists,
                 or_replace,
+                temporary,
                 ..
             } if constraints.is_empty()
                 && table_properties.is_empty()
                 && with_options.is_empty() =>
             {
+                if temporary && name.0.len() > 1 {
+                    return Err(DataFusionError::Plan(format!(
+                        "Temporary table '{name}' can not be created in a schema"
+                    )));
+                }
                 let plan = self.query_to_plan(*query, planner_context)?;
                 let input_schema = plan.schema();
 
@@ -126,10 +148,14 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 };
 
                 Ok(LogicalPlan::CreateMemoryTable(CreateMemoryTable {
-                    name: object_name_to_table_reference(name)?,
+                    name: object_name_to_table_reference(
+                        name,
+                        self.options.enable_ident_normalization,
+                    )?,
                     input: Arc::new(plan),
                     if_not_exists,
                     or_replace,
+                    temporary,
                 }))
             }
             Statement::CreateView {
@@ -144,7 +170,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 plan = self.apply_expr_alias(plan, columns)?;
 
                 Ok(LogicalPlan::CreateView(CreateView {
-                    name: object_name_to_table_reference(name)?,
+                    name: object_name_to_table_reference(
+                        name,
+                        self.options.enable_ident_normalization,
+                    )?,
                     input: Arc::new(plan),
                     or_replace,
                     definition: sql,
@@ -189,7 +218,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 // nor do we support multiple object names
                 let name = match names.len() {
                     0 => Err(ParserError("Missing table name.".to_string()).into()),
-                    1 => object_name_to_table_reference(names.pop().unwrap()),
+                    1 => object_name_to_table_reference(
+                        names.pop().unwrap(),
+                        self.options.enable_ident_normalization,
+                    ),
                     _ => {
                         Err(ParserError("Multiple objects not supported".to_string())
                             .into())
@@ -253,6 +285,35 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 table_name,
                 filter,
             } => self.show_columns_to_plan(extended, full, table_name, filter),
+
+            Statement::StartTransaction { modes } => {
+                if !modes.is_empty() {
+                    return Err(DataFusionError::NotImplemented(
+                        "Transaction modes are not supported".to_string(),
+                    ));
+                }
+                self.transaction_to_plan(TransactionStatement::Begin)
+            }
+            Statement::Commit { chain } => {
+                if chain {
+                    return Err(DataFusionError::NotImplemented(
+                        "COMMIT AND CHAIN is not supported".to_string(),
+                    ));
+                }
+                self.transaction_to_plan(TransactionStatement::Commit)
+            }
+            Statement::Rollback { chain } => {
+                if chain {
+                    return Err(DataFusionError::NotImplemented(
+                        "ROLLBACK AND CHAIN is not supported".to_string(),
+                    ));
+                }
+                self.transaction_to_plan(TransactionStatement::Rollback)
+            }
+            Statement::Savepoint { name } => {
+                let name = self.normalizer.normalize(name);
+                self.transaction_to_plan(TransactionStatement::Savepoint(name))
+            }
             _ => Err(DataFusionError::NotImplemented(format!(
                 "Unsupported SQL statement: {sql:?}"
             ))),
@@ -291,8 +352,14 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     fn describe_table_to_plan(&self, statement: DescribeTable) -> Result<LogicalPlan> {
         let DescribeTable { table_name } = statement;
 
-        let where_clause = object_name_to_qualifier(&table_name);
-        let table_ref = object_name_to_table_reference(table_name)?;
+        let where_clause = object_name_to_qualifier(
+            &table_name,
+            self.options.enable_ident_normalization,
+        );
+        let table_ref = object_name_to_table_reference(
+            table_name,
+            self.options.enable_ident_normalization,
+        )?;
 
         // check if table_name exists
         let _ = self
@@ -314,6 +381,113 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 
+    fn analyze_table_to_plan(&self, statement: AnalyzeTable) -> Result<LogicalPlan> {
+        let AnalyzeTable {
+            table_name,
+            columns,
+        } = statement;
+
+        let table_ref = object_name_to_table_reference(
+            table_name,
+            self.options.enable_ident_normalization,
+        )?;
+        let table_source = self
+            .schema_provider
+            .get_table_provider((&table_ref).into())?;
+
+        let columns = columns
+            .map(|columns| {
+                let schema = table_source.schema();
+                columns
+                    .into_iter()
+                    .map(|ident| {
+                        let name = self.normalizer.normalize(ident);
+                        schema.field_with_name(&name).map_err(|_| {
+                            DataFusionError::Plan(format!(
+                                "Column '{name}' not found in table '{table_ref}'"
+                            ))
+                        })?;
+                        Ok(name)
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+
+        Ok(LogicalPlan::AnalyzeTable(PlanAnalyzeTable {
+            name: table_ref,
+            columns,
+            schema: DFSchemaRef::new(DFSchema::empty()),
+        }))
+    }
+
+    /// Generate a logical plan from a CREATE FUNCTION statement
+    fn create_function_to_plan(&self, statement: CreateFunction) -> Result<LogicalPlan> {
+        let CreateFunction {
+            or_replace,
+            name,
+            args,
+            return_type,
+            language,
+            definition,
+        } = statement;
+
+        let empty_schema = DFSchema::empty();
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                let data_type = self.convert_data_type(&arg.data_type)?;
+                let default = arg
+                    .default
+                    .map(|default| {
+                        self.sql_to_expr(
+                            default,
+                            &empty_schema,
+                            &mut PlannerContext::new(),
+                        )
+                    })
+                    .transpose()?;
+                Ok(CreateFunctionArg {
+                    name: arg.name.map(|name| self.normalizer.normalize(name)),
+                    data_type,
+                    default,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(LogicalPlan::CreateFunction(PlanCreateFunction {
+            name: function_name(name),
+            or_replace,
+            args,
+            return_type: return_type
+                .map(|data_type| self.convert_data_type(&data_type))
+                .transpose()?,
+            language: language.map(normalize_ident),
+            definition,
+            schema: DFSchemaRef::new(DFSchema::empty()),
+        }))
+    }
+
+    /// Generate a logical plan from a DROP FUNCTION statement
+    fn drop_function_to_plan(&self, statement: DropFunction) -> Result<LogicalPlan> {
+        let DropFunction { if_exists, name } = statement;
+        Ok(LogicalPlan::DropFunction(PlanDropFunction {
+            name: function_name(name),
+            if_exists,
+            schema: DFSchemaRef::new(DFSchema::empty()),
+        }))
+    }
+
+    /// Generate a logical plan from a transaction control statement
+    fn transaction_to_plan(
+        &self,
+        statement: TransactionStatement,
+    ) -> Result<LogicalPlan> {
+        Ok(LogicalPlan::Transaction(Transaction {
+            statement,
+            schema: DFSchemaRef::new(DFSchema::empty()),
+        }))
+    }
+
     /// Generate a logical plan from a CREATE EXTERNAL TABLE statement
     fn external_table_to_plan(
         &self,
@@ -525,10 +699,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             ));
         }
         // Figure out the where clause
-        let where_clause = object_name_to_qualifier(&sql_table_name);
+        let where_clause = object_name_to_qualifier(
+            &sql_table_name,
+            self.options.enable_ident_normalization,
+        );
 
         // Do a table lookup to verify the table exists
-        let table_ref = object_name_to_table_reference(sql_table_name)?;
+        let table_ref = object_name_to_table_reference(
+            sql_table_name,
+            self.options.enable_ident_normalization,
+        )?;
         let _ = self
             .schema_provider
             .get_table_provider((&table_ref).into())?;
@@ -560,10 +740,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             ));
         }
         // Figure out the where clause
-        let where_clause = object_name_to_qualifier(&sql_table_name);
+        let where_clause = object_name_to_qualifier(
+            &sql_table_name,
+            self.options.enable_ident_normalization,
+        );
 
         // Do a table lookup to verify the table exists
-        let table_ref = object_name_to_table_reference(sql_table_name)?;
+        let table_ref = object_name_to_table_reference(
+            sql_table_name,
+            self.options.enable_ident_normalization,
+        )?;
         let _ = self
             .schema_provider
             .get_table_provider((&table_ref).into())?;
@@ -0,0 +1,131 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Builders of the [`sqlparser`] AST nodes produced by the unparser
+
+use sqlparser::ast;
+
+/// The parts of a query around its `SELECT`, filled in while unparsing a plan
+#[derive(Debug, Default)]
+pub(super) struct QueryBuilder {
+    /// The body replacing the `SELECT`, such as a `UNION`
+    pub body: Option<ast::SetExpr>,
+    pub order_by: Vec<ast::OrderByExpr>,
+    pub limit: Option<ast::Expr>,
+    pub offset: Option<ast::Offset>,
+}
+
+impl QueryBuilder {
+    /// Returns true if the query has an `ORDER BY`, `LIMIT` or `OFFSET`
+    pub fn has_modifiers(&self) -> bool {
+        !self.order_by.is_empty() || self.limit.is_some() || self.offset.is_some()
+    }
+
+    pub fn build(self, select: SelectBuilder) -> ast::Query {
+        let body = match self.body {
+            Some(body) => body,
+            None => ast::SetExpr::Select(Box::new(select.build())),
+        };
+        ast::Query {
+            with: None,
+            body: Box::new(body),
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            fetch: None,
+            lock: None,
+        }
+    }
+}
+
+/// The clauses of a `SELECT`, filled in while unparsing a plan
+#[derive(Debug, Default)]
+pub(super) struct SelectBuilder {
+    pub distinct: bool,
+    pub projection: Option<Vec<ast::SelectItem>>,
+    pub from: Option<ast::TableWithJoins>,
+    pub selection: Option<ast::Expr>,
+    /// The `GROUP BY`, set once an aggregation was unparsed, even without
+    /// grouping expressions
+    pub group_by: Option<Vec<ast::Expr>>,
+    pub having: Option<ast::Expr>,
+}
+
+impl SelectBuilder {
+    pub fn already_projected(&self) -> bool {
+        self.projection.is_some()
+    }
+
+    /// Returns true if no clause was set
+    pub fn is_empty(&self) -> bool {
+        !self.distinct
+            && self.projection.is_none()
+            && self.from.is_none()
+            && self.selection.is_none()
+            && self.group_by.is_none()
+            && self.having.is_none()
+    }
+
+    /// Adds `predicate` to the `WHERE` clause
+    pub fn and_selection(&mut self, predicate: ast::Expr) {
+        self.selection = Some(match self.selection.take() {
+            Some(selection) => ast::Expr::BinaryOp {
+                left: Box::new(selection),
+                op: ast::BinaryOperator::And,
+                right: Box::new(predicate),
+            },
+            None => predicate,
+        });
+    }
+
+    pub fn build(self) -> ast::Select {
+        ast::Select {
+            distinct: self.distinct,
+            top: None,
+            projection: self.projection.unwrap_or_default(),
+            into: None,
+            from: self.from.into_iter().collect(),
+            lateral_views: vec![],
+            selection: self.selection,
+            group_by: self.group_by.unwrap_or_default(),
+            cluster_by: vec![],
+            distribute_by: vec![],
+            sort_by: vec![],
+            having: self.having,
+            qualify: None,
+        }
+    }
+}
+
+pub(super) fn function(
+    name: &str,
+    args: Vec<ast::FunctionArg>,
+    distinct: bool,
+    over: Option<ast::WindowSpec>,
+) -> ast::Function {
+    ast::Function {
+        name: ast::ObjectName(vec![ast::Ident::new(name)]),
+        args,
+        over,
+        distinct,
+        special: false,
+    }
+}
+
+pub(super) fn nested_join(table_with_joins: ast::TableWithJoins) -> ast::TableFactor {
+    ast::TableFactor::NestedJoin(Box::new(table_with_joins))
+}
@@ -0,0 +1,273 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SQL dialects targeted by the [`Unparser`](super::Unparser)
+
+use arrow_schema::DataType;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::Operator;
+use sqlparser::ast;
+
+use super::expr::data_type_to_sql;
+
+/// Describes how the SQL produced by the [`Unparser`](super::Unparser) must
+/// be written for a database.
+///
+/// The [`Unparser`](super::Unparser) returns a
+/// [`DataFusionError::NotImplemented`] for the expressions the database can
+/// not evaluate with the same semantics as DataFusion. The defaults describe
+/// DataFusion itself.
+pub trait Dialect {
+    /// Returns the character used to quote `identifier`, or `None` if it
+    /// can be written as is
+    fn identifier_quote_style(&self, identifier: &str) -> Option<char>;
+
+    /// Returns true if `NULLS FIRST` and `NULLS LAST` can be written in an
+    /// `ORDER BY`
+    fn supports_nulls_first_in_sort(&self) -> bool {
+        true
+    }
+
+    /// Returns true if the binary operator `op` has the same semantics in the
+    /// database as in DataFusion
+    fn supports_operator(&self, _op: &Operator) -> bool {
+        true
+    }
+
+    /// Returns true if the database supports `ILIKE`
+    fn supports_ilike(&self) -> bool {
+        true
+    }
+
+    /// Returns the type written in a `CAST` to `data_type`
+    fn cast_data_type(&self, data_type: &DataType) -> Result<ast::DataType> {
+        data_type_to_sql(data_type)
+    }
+
+    /// Returns true if backslashes start escape sequences in the string
+    /// literals of the database, in which case they are escaped
+    fn escapes_backslashes(&self) -> bool {
+        false
+    }
+
+    /// Returns true if booleans are written `TRUE` and `FALSE`, rather than
+    /// `1` and `0`
+    fn supports_boolean_literals(&self) -> bool {
+        true
+    }
+
+    /// Returns true if dates are written `DATE 'YYYY-MM-DD'`, without which
+    /// date literals are not supported
+    fn supports_date_literals(&self) -> bool {
+        true
+    }
+
+    /// Returns true if string comparisons ignore case by default, in which
+    /// case filters on strings can not be evaluated exactly by the database
+    fn case_insensitive_strings(&self) -> bool {
+        false
+    }
+
+    /// Returns true if `LIKE` ignores case, in which case it can not be
+    /// evaluated exactly by the database
+    fn case_insensitive_like(&self) -> bool {
+        self.case_insensitive_strings()
+    }
+}
+
+/// Returns true if `identifier` is read back as is when it is not quoted,
+/// which is only the case for lowercase identifiers as DataFusion and most
+/// databases normalize unquoted identifiers
+fn is_plain_identifier(identifier: &str) -> bool {
+    let mut chars = identifier.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// The dialect of DataFusion, quoting identifiers with `"` when needed
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultDialect {}
+
+impl Dialect for DefaultDialect {
+    fn identifier_quote_style(&self, identifier: &str) -> Option<char> {
+        (!is_plain_identifier(identifier)).then_some('"')
+    }
+}
+
+/// The dialect of [Postgres](https://www.postgresql.org/)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PostgreSqlDialect {}
+
+impl Dialect for PostgreSqlDialect {
+    fn identifier_quote_style(&self, identifier: &str) -> Option<char> {
+        (!is_plain_identifier(identifier)).then_some('"')
+    }
+
+    fn supports_operator(&self, op: &Operator) -> bool {
+        // the regular expression syntax differs from the one of DataFusion
+        !is_regex_operator(op)
+    }
+
+    fn cast_data_type(&self, data_type: &DataType) -> Result<ast::DataType> {
+        match data_type {
+            DataType::Float64 => Ok(custom_data_type("DOUBLE PRECISION")),
+            DataType::Utf8 | DataType::LargeUtf8 => Ok(ast::DataType::Text),
+            DataType::Int8
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64 => unsupported_cast(data_type),
+            _ => data_type_to_sql(data_type),
+        }
+    }
+}
+
+/// The dialect of [MySQL](https://www.mysql.com/)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MySqlDialect {}
+
+impl Dialect for MySqlDialect {
+    fn identifier_quote_style(&self, identifier: &str) -> Option<char> {
+        (!is_plain_identifier(identifier)).then_some('`')
+    }
+
+    fn supports_nulls_first_in_sort(&self) -> bool {
+        false
+    }
+
+    fn supports_operator(&self, op: &Operator) -> bool {
+        // `/` returns a decimal for integers, `||` is a logical OR and `#`
+        // starts a comment
+        !(is_regex_operator(op)
+            || matches!(
+                op,
+                Operator::Divide
+                    | Operator::StringConcat
+                    | Operator::IsDistinctFrom
+                    | Operator::IsNotDistinctFrom
+                    | Operator::BitwiseXor
+            ))
+    }
+
+    fn supports_ilike(&self) -> bool {
+        false
+    }
+
+    fn cast_data_type(&self, data_type: &DataType) -> Result<ast::DataType> {
+        match data_type {
+            DataType::Int64 => Ok(custom_data_type("SIGNED")),
+            DataType::Utf8 | DataType::LargeUtf8 => Ok(ast::DataType::Char(None)),
+            DataType::Date32 => Ok(ast::DataType::Date),
+            _ => unsupported_cast(data_type),
+        }
+    }
+
+    fn escapes_backslashes(&self) -> bool {
+        true
+    }
+
+    fn case_insensitive_strings(&self) -> bool {
+        true
+    }
+}
+
+/// The dialect of [SQLite](https://www.sqlite.org/)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SqliteDialect {}
+
+impl Dialect for SqliteDialect {
+    fn identifier_quote_style(&self, identifier: &str) -> Option<char> {
+        (!is_plain_identifier(identifier)).then_some('"')
+    }
+
+    fn supports_operator(&self, op: &Operator) -> bool {
+        !(is_regex_operator(op) || *op == Operator::BitwiseXor)
+    }
+
+    fn supports_ilike(&self) -> bool {
+        false
+    }
+
+    fn cast_data_type(&self, data_type: &DataType) -> Result<ast::DataType> {
+        match data_type {
+            DataType::Int64 => Ok(ast::DataType::Integer(None)),
+            DataType::Float64 => Ok(ast::DataType::Real),
+            DataType::Utf8 | DataType::LargeUtf8 => Ok(ast::DataType::Text),
+            _ => unsupported_cast(data_type),
+        }
+    }
+
+    fn supports_boolean_literals(&self) -> bool {
+        false
+    }
+
+    fn supports_date_literals(&self) -> bool {
+        false
+    }
+
+    fn case_insensitive_like(&self) -> bool {
+        true
+    }
+}
+
+fn is_regex_operator(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::RegexMatch
+            | Operator::RegexIMatch
+            | Operator::RegexNotMatch
+            | Operator::RegexNotIMatch
+    )
+}
+
+fn custom_data_type(name: &str) -> ast::DataType {
+    ast::DataType::Custom(ast::ObjectName(vec![ast::Ident::new(name)]), vec![])
+}
+
+fn unsupported_cast(data_type: &DataType) -> Result<ast::DataType> {
+    Err(DataFusionError::NotImplemented(format!(
+        "Unsupported data type in a cast: {data_type:?}"
+    )))
+}
+
+/// A dialect quoting all identifiers with the same character
+#[derive(Debug, Clone, Copy)]
+pub struct CustomDialect {
+    /// The character quoting identifiers, or `None` to never quote them
+    pub identifier_quote_style: Option<char>,
+    /// Whether `NULLS FIRST` and `NULLS LAST` can be written in an `ORDER BY`
+    pub supports_nulls_first_in_sort: bool,
+}
+
+impl Default for CustomDialect {
+    fn default() -> Self {
+        Self {
+            identifier_quote_style: Some('"'),
+            supports_nulls_first_in_sort: true,
+        }
+    }
+}
+
+impl Dialect for CustomDialect {
+    fn identifier_quote_style(&self, _identifier: &str) -> Option<char> {
+        self.identifier_quote_style
+    }
+
+    fn supports_nulls_first_in_sort(&self) -> bool {
+        self.supports_nulls_first_in_sort
+    }
+}
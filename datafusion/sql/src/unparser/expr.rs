@@ -0,0 +1,882 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Unparsing of [`Expr`]s
+
+use arrow_schema::{DataType, TimeUnit};
+use datafusion_common::{Column, DataFusionError, Result, ScalarValue};
+use datafusion_expr::expr::{
+    AggregateFunction, Between, BinaryExpr, Case, Cast, Like, Sort, TryCast,
+    WindowFunction,
+};
+use datafusion_expr::{
+    aggregate_function, window_function, BuiltinScalarFunction, Expr, Operator,
+    WindowFrame, WindowFrameBound, WindowFrameExclude, WindowFrameUnits,
+};
+use sqlparser::ast;
+
+use super::ast::function;
+use super::Unparser;
+
+impl Unparser<'_> {
+    /// Converts `expr` into a SQL expression.
+    ///
+    /// Aliases are dropped, as they can only be written in the `SELECT`
+    /// list, and sort expressions are only supported by [`Self::sort_to_sql`]
+    pub fn expr_to_sql(&self, expr: &Expr) -> Result<ast::Expr> {
+        match expr {
+            Expr::Alias(expr, _) => self.expr_to_sql(expr),
+            Expr::Column(col) => Ok(self.col_to_sql(col)),
+            Expr::Literal(value) => self.scalar_to_sql(value),
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                if !self.dialect.supports_operator(op) {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "Unsupported operator: {op}"
+                    )));
+                }
+                let left = Box::new(nested(self.expr_to_sql(left)?));
+                let right = Box::new(nested(self.expr_to_sql(right)?));
+                Ok(match op {
+                    Operator::IsDistinctFrom => ast::Expr::IsDistinctFrom(left, right),
+                    Operator::IsNotDistinctFrom => {
+                        ast::Expr::IsNotDistinctFrom(left, right)
+                    }
+                    _ => ast::Expr::BinaryOp {
+                        left,
+                        op: op_to_sql(op)?,
+                        right,
+                    },
+                })
+            }
+            Expr::Like(Like {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            }) => Ok(ast::Expr::Like {
+                negated: *negated,
+                expr: self.nested_to_sql(expr)?,
+                pattern: self.nested_to_sql(pattern)?,
+                escape_char: *escape_char,
+            }),
+            Expr::ILike(_) if !self.dialect.supports_ilike() => Err(
+                DataFusionError::NotImplemented("Unsupported ILIKE".to_string()),
+            ),
+            Expr::ILike(Like {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            }) => Ok(ast::Expr::ILike {
+                negated: *negated,
+                expr: self.nested_to_sql(expr)?,
+                pattern: self.nested_to_sql(pattern)?,
+                escape_char: *escape_char,
+            }),
+            Expr::SimilarTo(Like {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            }) => Ok(ast::Expr::SimilarTo {
+                negated: *negated,
+                expr: self.nested_to_sql(expr)?,
+                pattern: self.nested_to_sql(pattern)?,
+                escape_char: *escape_char,
+            }),
+            Expr::Not(expr) => Ok(ast::Expr::UnaryOp {
+                op: ast::UnaryOperator::Not,
+                expr: Box::new(nested(self.expr_to_sql(expr)?)),
+            }),
+            Expr::Negative(expr) => Ok(ast::Expr::UnaryOp {
+                op: ast::UnaryOperator::Minus,
+                expr: Box::new(nested(self.expr_to_sql(expr)?)),
+            }),
+            Expr::IsNull(expr) => Ok(ast::Expr::IsNull(self.nested_to_sql(expr)?)),
+            Expr::IsNotNull(expr) => Ok(ast::Expr::IsNotNull(self.nested_to_sql(expr)?)),
+            Expr::IsTrue(expr) => Ok(ast::Expr::IsTrue(self.nested_to_sql(expr)?)),
+            Expr::IsFalse(expr) => Ok(ast::Expr::IsFalse(self.nested_to_sql(expr)?)),
+            Expr::IsUnknown(expr) => Ok(ast::Expr::IsUnknown(self.nested_to_sql(expr)?)),
+            Expr::IsNotTrue(expr) => Ok(ast::Expr::IsNotTrue(self.nested_to_sql(expr)?)),
+            Expr::IsNotFalse(expr) => {
+                Ok(ast::Expr::IsNotFalse(self.nested_to_sql(expr)?))
+            }
+            Expr::IsNotUnknown(expr) => {
+                Ok(ast::Expr::IsNotUnknown(self.nested_to_sql(expr)?))
+            }
+            Expr::Between(Between {
+                expr,
+                negated,
+                low,
+                high,
+            }) => Ok(ast::Expr::Between {
+                expr: self.nested_to_sql(expr)?,
+                negated: *negated,
+                low: self.nested_to_sql(low)?,
+                high: self.nested_to_sql(high)?,
+            }),
+            Expr::Case(Case {
+                expr,
+                when_then_expr,
+                else_expr,
+            }) => {
+                let (conditions, results) = when_then_expr
+                    .iter()
+                    .map(|(when, then)| {
+                        Ok((self.expr_to_sql(when)?, self.expr_to_sql(then)?))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+                Ok(ast::Expr::Case {
+                    operand: expr
+                        .as_ref()
+                        .map(|expr| self.expr_to_sql(expr).map(Box::new))
+                        .transpose()?,
+                    conditions,
+                    results,
+                    else_result: else_expr
+                        .as_ref()
+                        .map(|expr| self.expr_to_sql(expr).map(Box::new))
+                        .transpose()?,
+                })
+            }
+            Expr::Cast(Cast { expr, data_type }) => Ok(ast::Expr::Cast {
+                expr: Box::new(self.expr_to_sql(expr)?),
+                data_type: self.dialect.cast_data_type(data_type)?,
+            }),
+            Expr::TryCast(TryCast { expr, data_type }) => Ok(ast::Expr::TryCast {
+                expr: Box::new(self.expr_to_sql(expr)?),
+                data_type: data_type_to_sql(data_type)?,
+            }),
+            Expr::ScalarFunction { fun, args } => Ok(ast::Expr::Function(function(
+                &scalar_function_name(fun),
+                self.function_args_to_sql(args)?,
+                false,
+                None,
+            ))),
+            Expr::ScalarUDF { fun, args } => Ok(ast::Expr::Function(function(
+                &fun.name,
+                self.function_args_to_sql(args)?,
+                false,
+                None,
+            ))),
+            Expr::AggregateFunction(AggregateFunction {
+                fun,
+                args,
+                distinct,
+                filter,
+                order_by,
+            }) => {
+                if filter.is_some() {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "Unsupported expression: {expr:?}"
+                    )));
+                }
+                if let Some(order_by) = order_by {
+                    // only ARRAY_AGG has an ORDER BY clause in the syntax of sqlparser
+                    return match (fun, args.as_slice(), order_by.as_slice()) {
+                        (
+                            aggregate_function::AggregateFunction::ArrayAgg,
+                            [arg],
+                            [sort],
+                        ) => Ok(ast::Expr::ArrayAgg(ast::ArrayAgg {
+                            distinct: *distinct,
+                            expr: Box::new(self.expr_to_sql(arg)?),
+                            order_by: Some(Box::new(self.sort_to_sql(sort)?)),
+                            limit: None,
+                            within_group: false,
+                        })),
+                        _ => Err(DataFusionError::NotImplemented(format!(
+                            "Unsupported expression: {expr:?}"
+                        ))),
+                    };
+                }
+                Ok(ast::Expr::Function(function(
+                    aggregate_function_name(fun),
+                    self.function_args_to_sql(args)?,
+                    *distinct,
+                    None,
+                )))
+            }
+            Expr::AggregateUDF { fun, args, filter } => {
+                if filter.is_some() {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "Unsupported expression: {expr:?}"
+                    )));
+                }
+                Ok(ast::Expr::Function(function(
+                    &fun.name,
+                    self.function_args_to_sql(args)?,
+                    false,
+                    None,
+                )))
+            }
+            Expr::WindowFunction(WindowFunction {
+                fun,
+                args,
+                partition_by,
+                order_by,
+                window_frame,
+            }) => {
+                let name = match fun {
+                    window_function::WindowFunction::AggregateFunction(fun) => {
+                        aggregate_function_name(fun).to_string()
+                    }
+                    window_function::WindowFunction::BuiltInWindowFunction(fun) => {
+                        fun.to_string()
+                    }
+                    window_function::WindowFunction::AggregateUDF(fun) => {
+                        fun.name.clone()
+                    }
+                };
+                let over = ast::WindowSpec {
+                    partition_by: partition_by
+                        .iter()
+                        .map(|expr| self.expr_to_sql(expr))
+                        .collect::<Result<_>>()?,
+                    order_by: order_by
+                        .iter()
+                        .map(|expr| self.sort_to_sql(expr))
+                        .collect::<Result<_>>()?,
+                    window_frame: window_frame_to_sql(
+                        window_frame,
+                        !order_by.is_empty(),
+                    )?,
+                };
+                Ok(ast::Expr::Function(function(
+                    &name,
+                    self.function_args_to_sql(args)?,
+                    false,
+                    Some(over),
+                )))
+            }
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => Ok(ast::Expr::InList {
+                expr: self.nested_to_sql(expr)?,
+                list: list
+                    .iter()
+                    .map(|expr| self.expr_to_sql(expr))
+                    .collect::<Result<_>>()?,
+                negated: *negated,
+            }),
+            Expr::Exists { subquery, negated } => Ok(ast::Expr::Exists {
+                subquery: Box::new(self.plan_to_query(&subquery.subquery)?),
+                negated: *negated,
+            }),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => Ok(ast::Expr::InSubquery {
+                expr: self.nested_to_sql(expr)?,
+                subquery: Box::new(self.plan_to_query(&subquery.subquery)?),
+                negated: *negated,
+            }),
+            Expr::ScalarSubquery(subquery) => Ok(ast::Expr::Subquery(Box::new(
+                self.plan_to_query(&subquery.subquery)?,
+            ))),
+            Expr::Placeholder { id, .. } => {
+                Ok(ast::Expr::Value(ast::Value::Placeholder(id.clone())))
+            }
+            Expr::Sort(_)
+            | Expr::ScalarVariable(_, _)
+            | Expr::GetIndexedField(_)
+            | Expr::Wildcard
+            | Expr::QualifiedWildcard { .. }
+            | Expr::GroupingSet(_) => Err(DataFusionError::NotImplemented(format!(
+                "Unsupported expression: {expr:?}"
+            ))),
+        }
+    }
+
+    /// Converts the sort expression `expr` into an `ORDER BY` item
+    pub fn sort_to_sql(&self, expr: &Expr) -> Result<ast::OrderByExpr> {
+        match expr {
+            Expr::Sort(Sort {
+                expr,
+                asc,
+                nulls_first,
+            }) => Ok(ast::OrderByExpr {
+                expr: self.expr_to_sql(expr)?,
+                asc: Some(*asc),
+                nulls_first: self
+                    .dialect
+                    .supports_nulls_first_in_sort()
+                    .then_some(*nulls_first),
+            }),
+            _ => Err(DataFusionError::Internal(format!(
+                "Expected a sort expression, got {expr:?}"
+            ))),
+        }
+    }
+
+    /// Converts `expr` into an item of the `SELECT` list
+    pub(super) fn select_item_to_sql(&self, expr: &Expr) -> Result<ast::SelectItem> {
+        match expr {
+            Expr::Alias(expr, alias) => Ok(ast::SelectItem::ExprWithAlias {
+                expr: self.expr_to_sql(expr)?,
+                alias: self.new_ident(alias),
+            }),
+            Expr::Wildcard => Ok(ast::SelectItem::Wildcard(Default::default())),
+            Expr::QualifiedWildcard { qualifier } => {
+                Ok(ast::SelectItem::QualifiedWildcard(
+                    self.object_name(qualifier),
+                    Default::default(),
+                ))
+            }
+            _ => Ok(ast::SelectItem::UnnamedExpr(self.expr_to_sql(expr)?)),
+        }
+    }
+
+    /// Converts `value` into an identifier, quoted if the dialect requires it
+    pub fn new_ident(&self, value: &str) -> ast::Ident {
+        ast::Ident {
+            value: value.to_string(),
+            quote_style: self.dialect.identifier_quote_style(value),
+        }
+    }
+
+    /// Returns the name of a table or relation, whose parts are separated by `.`
+    pub fn object_name(&self, name: &str) -> ast::ObjectName {
+        ast::ObjectName(name.split('.').map(|part| self.new_ident(part)).collect())
+    }
+
+    fn col_to_sql(&self, col: &Column) -> ast::Expr {
+        match &col.relation {
+            Some(relation) => {
+                let mut idents = self.object_name(relation).0;
+                idents.push(self.new_ident(&col.name));
+                ast::Expr::CompoundIdentifier(idents)
+            }
+            None => ast::Expr::Identifier(self.new_ident(&col.name)),
+        }
+    }
+
+    fn nested_to_sql(&self, expr: &Expr) -> Result<Box<ast::Expr>> {
+        Ok(Box::new(nested(self.expr_to_sql(expr)?)))
+    }
+
+    fn function_args_to_sql(&self, args: &[Expr]) -> Result<Vec<ast::FunctionArg>> {
+        args.iter()
+            .map(|arg| {
+                let arg = match arg {
+                    Expr::Wildcard => ast::FunctionArgExpr::Wildcard,
+                    _ => ast::FunctionArgExpr::Expr(self.expr_to_sql(arg)?),
+                };
+                Ok(ast::FunctionArg::Unnamed(arg))
+            })
+            .collect()
+    }
+
+    fn scalar_to_sql(&self, value: &ScalarValue) -> Result<ast::Expr> {
+        let number =
+            |value: String| Ok(ast::Expr::Value(ast::Value::Number(value, false)));
+        match value {
+            ScalarValue::Boolean(Some(v)) if self.dialect.supports_boolean_literals() => {
+                Ok(ast::Expr::Value(ast::Value::Boolean(*v)))
+            }
+            ScalarValue::Boolean(Some(v)) => number(u8::from(*v).to_string()),
+            ScalarValue::Float32(Some(v)) if v.is_finite() => number(format!("{v:?}")),
+            ScalarValue::Float64(Some(v)) if v.is_finite() => number(format!("{v:?}")),
+            // written as strings, which DataFusion and most databases cast
+            ScalarValue::Float32(Some(v)) => Ok(ast::Expr::Cast {
+                expr: Box::new(ast::Expr::Value(ast::Value::SingleQuotedString(
+                    v.to_string(),
+                ))),
+                data_type: ast::DataType::Real,
+            }),
+            ScalarValue::Float64(Some(v)) => Ok(ast::Expr::Cast {
+                expr: Box::new(ast::Expr::Value(ast::Value::SingleQuotedString(
+                    v.to_string(),
+                ))),
+                data_type: ast::DataType::Double,
+            }),
+            ScalarValue::Int8(Some(v)) => number(v.to_string()),
+            ScalarValue::Int16(Some(v)) => number(v.to_string()),
+            ScalarValue::Int32(Some(v)) => number(v.to_string()),
+            ScalarValue::Int64(Some(v)) => number(v.to_string()),
+            ScalarValue::UInt8(Some(v)) => number(v.to_string()),
+            ScalarValue::UInt16(Some(v)) => number(v.to_string()),
+            ScalarValue::UInt32(Some(v)) => number(v.to_string()),
+            ScalarValue::UInt64(Some(v)) => number(v.to_string()),
+            ScalarValue::Decimal128(Some(v), precision, scale) => {
+                // numbers with a fractional part are read back as floats
+                Ok(ast::Expr::Cast {
+                    expr: Box::new(number(decimal_to_string(*v, *scale))?),
+                    data_type: data_type_to_sql(&DataType::Decimal128(
+                        *precision, *scale,
+                    ))?,
+                })
+            }
+            ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => {
+                let v = if self.dialect.escapes_backslashes() {
+                    v.replace('\\', "\\\\")
+                } else {
+                    v.to_string()
+                };
+                Ok(ast::Expr::Value(ast::Value::SingleQuotedString(v)))
+            }
+            ScalarValue::Date32(Some(days)) if self.dialect.supports_date_literals() => {
+                Ok(ast::Expr::TypedString {
+                    data_type: ast::DataType::Date,
+                    value: date32_to_string(*days),
+                })
+            }
+            value if value.is_null() => Ok(ast::Expr::Value(ast::Value::Null)),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "Unsupported literal: {value:?}"
+            ))),
+        }
+    }
+}
+
+/// Wraps `expr` in parentheses unless it can be an operand of any operator
+fn nested(expr: ast::Expr) -> ast::Expr {
+    match expr {
+        // so that `- -1` is not written as the comment `--1`
+        ast::Expr::Value(ast::Value::Number(ref n, _)) if n.starts_with('-') => {
+            ast::Expr::Nested(Box::new(expr))
+        }
+        ast::Expr::Identifier(_)
+        | ast::Expr::CompoundIdentifier(_)
+        | ast::Expr::Value(_)
+        | ast::Expr::Function(_)
+        | ast::Expr::Cast { .. }
+        | ast::Expr::TryCast { .. }
+        | ast::Expr::Case { .. }
+        | ast::Expr::Exists { .. }
+        | ast::Expr::Subquery(_)
+        | ast::Expr::Nested(_) => expr,
+        _ => ast::Expr::Nested(Box::new(expr)),
+    }
+}
+
+fn op_to_sql(op: &Operator) -> Result<ast::BinaryOperator> {
+    match op {
+        Operator::Eq => Ok(ast::BinaryOperator::Eq),
+        Operator::NotEq => Ok(ast::BinaryOperator::NotEq),
+        Operator::Lt => Ok(ast::BinaryOperator::Lt),
+        Operator::LtEq => Ok(ast::BinaryOperator::LtEq),
+        Operator::Gt => Ok(ast::BinaryOperator::Gt),
+        Operator::GtEq => Ok(ast::BinaryOperator::GtEq),
+        Operator::Plus => Ok(ast::BinaryOperator::Plus),
+        Operator::Minus => Ok(ast::BinaryOperator::Minus),
+        Operator::Multiply => Ok(ast::BinaryOperator::Multiply),
+        Operator::Divide => Ok(ast::BinaryOperator::Divide),
+        Operator::Modulo => Ok(ast::BinaryOperator::Modulo),
+        Operator::And => Ok(ast::BinaryOperator::And),
+        Operator::Or => Ok(ast::BinaryOperator::Or),
+        Operator::RegexMatch => Ok(ast::BinaryOperator::PGRegexMatch),
+        Operator::RegexIMatch => Ok(ast::BinaryOperator::PGRegexIMatch),
+        Operator::RegexNotMatch => Ok(ast::BinaryOperator::PGRegexNotMatch),
+        Operator::RegexNotIMatch => Ok(ast::BinaryOperator::PGRegexNotIMatch),
+        Operator::BitwiseAnd => Ok(ast::BinaryOperator::BitwiseAnd),
+        Operator::BitwiseOr => Ok(ast::BinaryOperator::BitwiseOr),
+        Operator::BitwiseXor => Ok(ast::BinaryOperator::BitwiseXor),
+        Operator::BitwiseShiftRight => Ok(ast::BinaryOperator::PGBitwiseShiftRight),
+        Operator::BitwiseShiftLeft => Ok(ast::BinaryOperator::PGBitwiseShiftLeft),
+        Operator::StringConcat => Ok(ast::BinaryOperator::StringConcat),
+        Operator::IsDistinctFrom | Operator::IsNotDistinctFrom => Err(
+            DataFusionError::Internal(format!("{op} is not a binary operator in SQL")),
+        ),
+    }
+}
+
+pub(super) fn data_type_to_sql(data_type: &DataType) -> Result<ast::DataType> {
+    match data_type {
+        DataType::Boolean => Ok(ast::DataType::Boolean),
+        DataType::Int8 => Ok(ast::DataType::TinyInt(None)),
+        DataType::Int16 => Ok(ast::DataType::SmallInt(None)),
+        DataType::Int32 => Ok(ast::DataType::Int(None)),
+        DataType::Int64 => Ok(ast::DataType::BigInt(None)),
+        DataType::UInt8 => Ok(ast::DataType::UnsignedTinyInt(None)),
+        DataType::UInt16 => Ok(ast::DataType::UnsignedSmallInt(None)),
+        DataType::UInt32 => Ok(ast::DataType::UnsignedInt(None)),
+        DataType::UInt64 => Ok(ast::DataType::UnsignedBigInt(None)),
+        DataType::Float32 => Ok(ast::DataType::Real),
+        DataType::Float64 => Ok(ast::DataType::Double),
+        DataType::Utf8 | DataType::LargeUtf8 => Ok(ast::DataType::Varchar(None)),
+        DataType::Binary | DataType::LargeBinary => Ok(ast::DataType::Bytea),
+        DataType::Date32 => Ok(ast::DataType::Date),
+        DataType::Timestamp(TimeUnit::Nanosecond, None) => {
+            Ok(ast::DataType::Timestamp(None, ast::TimezoneInfo::None))
+        }
+        DataType::Decimal128(precision, scale) => Ok(ast::DataType::Decimal(
+            ast::ExactNumberInfo::PrecisionAndScale(*precision as u64, *scale as u64),
+        )),
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Unsupported data type: {data_type:?}"
+        ))),
+    }
+}
+
+/// Formats the date `days` after the epoch as `YYYY-MM-DD`
+fn date32_to_string(days: i32) -> String {
+    // the proleptic Gregorian calendar, in eras of 400 years starting on
+    // March 1st, see http://howardhinnant.github.io/date_algorithms.html
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn decimal_to_string(value: i128, scale: i8) -> String {
+    if scale <= 0 {
+        return format!("{value}{}", "0".repeat(-scale as usize));
+    }
+    let scale = scale as usize;
+    let digits = format!("{:0>width$}", value.unsigned_abs(), width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{sign}{integer}.{fraction}")
+}
+
+/// Returns the name of `fun` in SQL, which is the snake case of its variant
+/// for most functions
+fn scalar_function_name(fun: &BuiltinScalarFunction) -> String {
+    match fun {
+        BuiltinScalarFunction::ConcatWithSeparator => "concat_ws".to_string(),
+        BuiltinScalarFunction::InitCap => "initcap".to_string(),
+        BuiltinScalarFunction::NullIf => "nullif".to_string(),
+        _ => {
+            let mut name = String::new();
+            let mut previous_lowercase = false;
+            for c in format!("{fun:?}").chars() {
+                if c.is_ascii_uppercase() && previous_lowercase {
+                    name.push('_');
+                }
+                previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+                name.push(c.to_ascii_lowercase());
+            }
+            name
+        }
+    }
+}
+
+fn aggregate_function_name(fun: &aggregate_function::AggregateFunction) -> &'static str {
+    use aggregate_function::AggregateFunction;
+    match fun {
+        AggregateFunction::Count => "COUNT",
+        AggregateFunction::Sum => "SUM",
+        AggregateFunction::Min => "MIN",
+        AggregateFunction::Max => "MAX",
+        AggregateFunction::Avg => "AVG",
+        AggregateFunction::Median => "MEDIAN",
+        AggregateFunction::ApproxDistinct => "APPROX_DISTINCT",
+        AggregateFunction::ArrayAgg => "ARRAY_AGG",
+        AggregateFunction::Variance => "VAR",
+        AggregateFunction::VariancePop => "VAR_POP",
+        AggregateFunction::Stddev => "STDDEV",
+        AggregateFunction::StddevPop => "STDDEV_POP",
+        AggregateFunction::Covariance => "COVAR",
+        AggregateFunction::CovariancePop => "COVAR_POP",
+        AggregateFunction::Correlation => "CORR",
+        AggregateFunction::ApproxPercentileCont => "APPROX_PERCENTILE_CONT",
+        AggregateFunction::ApproxPercentileContWithWeight => {
+            "APPROX_PERCENTILE_CONT_WITH_WEIGHT"
+        }
+        AggregateFunction::ApproxMedian => "APPROX_MEDIAN",
+        AggregateFunction::Grouping => "GROUPING",
+    }
+}
+
+/// Converts `frame` into SQL, or `None` if it is the default frame of a
+/// window with or without `ORDER BY`
+fn window_frame_to_sql(
+    frame: &WindowFrame,
+    has_order_by: bool,
+) -> Result<Option<ast::WindowFrame>> {
+    if *frame == WindowFrame::new(has_order_by) {
+        return Ok(None);
+    }
+    if frame.exclude != WindowFrameExclude::NoOthers {
+        return Err(DataFusionError::NotImplemented(format!(
+            "Unsupported window frame: {frame}"
+        )));
+    }
+    let units = match frame.units {
+        WindowFrameUnits::Rows => ast::WindowFrameUnits::Rows,
+        WindowFrameUnits::Range => ast::WindowFrameUnits::Range,
+        WindowFrameUnits::Groups => ast::WindowFrameUnits::Groups,
+    };
+    Ok(Some(ast::WindowFrame {
+        units,
+        start_bound: window_frame_bound_to_sql(&frame.start_bound)?,
+        end_bound: Some(window_frame_bound_to_sql(&frame.end_bound)?),
+    }))
+}
+
+fn window_frame_bound_to_sql(bound: &WindowFrameBound) -> Result<ast::WindowFrameBound> {
+    let offset = |value: &ScalarValue| -> Result<Option<Box<ast::Expr>>> {
+        let offset = match value {
+            value if value.is_null() => return Ok(None),
+            ScalarValue::Utf8(Some(v)) if v.parse::<u64>().is_ok() => v.to_string(),
+            ScalarValue::UInt64(Some(v)) => v.to_string(),
+            ScalarValue::Int64(Some(v)) if *v >= 0 => v.to_string(),
+            _ => {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "Unsupported window frame bound: {bound}"
+                )))
+            }
+        };
+        Ok(Some(Box::new(ast::Expr::Value(ast::Value::Number(
+            offset, false,
+        )))))
+    };
+    match bound {
+        WindowFrameBound::Preceding(value) => {
+            Ok(ast::WindowFrameBound::Preceding(offset(value)?))
+        }
+        WindowFrameBound::CurrentRow => Ok(ast::WindowFrameBound::CurrentRow),
+        WindowFrameBound::Following(value) => {
+            Ok(ast::WindowFrameBound::Following(offset(value)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion_expr::expr::Cast;
+    use datafusion_expr::{
+        cast, col, lit, BuiltInWindowFunction, Expr, WindowFrame, WindowFrameBound,
+        WindowFrameUnits,
+    };
+
+    use super::*;
+    use crate::unparser::dialect::{
+        CustomDialect, Dialect, MySqlDialect, PostgreSqlDialect, SqliteDialect,
+    };
+    use crate::unparser::expr_to_sql;
+
+    #[test]
+    fn expr_to_sql_ok() -> Result<()> {
+        let tests: Vec<(Expr, &str)> = vec![
+            (col("a").gt(lit(4)), "a > 4"),
+            (
+                col("a").gt(lit(4)).and(col("b").lt(lit(5))),
+                "(a > 4) AND (b < 5)",
+            ),
+            ((col("a") + col("b")) * lit(2), "(a + b) * 2"),
+            (
+                Expr::Column(Column::new(Some("t"), "Mixed Case")).is_null(),
+                r#"t."Mixed Case" IS NULL"#,
+            ),
+            (col("a").not_eq(lit("it's")).not(), "NOT (a <> 'it''s')"),
+            (
+                col("a").in_list(vec![lit(1), lit(2)], true),
+                "a NOT IN (1, 2)",
+            ),
+            (
+                Expr::Between(Between::new(
+                    Box::new(col("a")),
+                    false,
+                    Box::new(lit(1.5)),
+                    Box::new(lit(2)),
+                )),
+                "a BETWEEN 1.5 AND 2",
+            ),
+            (col("a").like(lit("a%")), "a LIKE 'a%'"),
+            (
+                Expr::Cast(Cast::new(Box::new(col("a")), DataType::Int64)),
+                "CAST(a AS BIGINT)",
+            ),
+            (
+                lit(ScalarValue::Decimal128(Some(-1205), 5, 3)),
+                "CAST(-1.205 AS DECIMAL(5,3))",
+            ),
+            (lit(f64::NAN), "CAST('NaN' AS DOUBLE)"),
+            (lit(ScalarValue::Int32(None)), "NULL"),
+            (
+                Expr::ScalarFunction {
+                    fun: BuiltinScalarFunction::DatePart,
+                    args: vec![lit("year"), col("d")],
+                },
+                "date_part('year', d)",
+            ),
+            (
+                Expr::AggregateFunction(AggregateFunction {
+                    fun: aggregate_function::AggregateFunction::ApproxDistinct,
+                    args: vec![col("a")],
+                    distinct: true,
+                    filter: None,
+                    order_by: None,
+                }),
+                "APPROX_DISTINCT(DISTINCT a)",
+            ),
+            (
+                Expr::AggregateFunction(AggregateFunction {
+                    fun: aggregate_function::AggregateFunction::ArrayAgg,
+                    args: vec![col("a")],
+                    distinct: false,
+                    filter: None,
+                    order_by: Some(vec![col("b").sort(true, true)]),
+                }),
+                "ARRAY_AGG(a ORDER BY b ASC NULLS FIRST)",
+            ),
+            (
+                Expr::WindowFunction(WindowFunction {
+                    fun: window_function::WindowFunction::BuiltInWindowFunction(
+                        BuiltInWindowFunction::RowNumber,
+                    ),
+                    args: vec![],
+                    partition_by: vec![col("a")],
+                    order_by: vec![col("b").sort(false, true)],
+                    window_frame: WindowFrame::new(true),
+                }),
+                "ROW_NUMBER() OVER (PARTITION BY a ORDER BY b DESC NULLS FIRST)",
+            ),
+            (
+                Expr::WindowFunction(WindowFunction {
+                    fun: window_function::WindowFunction::AggregateFunction(
+                        aggregate_function::AggregateFunction::Sum,
+                    ),
+                    args: vec![col("a")],
+                    partition_by: vec![],
+                    order_by: vec![col("b").sort(true, false)],
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Utf8(
+                            Some("2".into()),
+                        )),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                        exclude: WindowFrameExclude::NoOthers,
+                    },
+                }),
+                "SUM(a) OVER (ORDER BY b ASC NULLS LAST \
+                 ROWS BETWEEN 2 PRECEDING AND UNBOUNDED FOLLOWING)",
+            ),
+            (col("a").alias("b"), "a"),
+            (Expr::Negative(Box::new(lit(-1))), "-(-1)"),
+        ];
+
+        for (expr, expected) in tests {
+            assert_eq!(expr_to_sql(&expr)?.to_string(), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn expr_to_sql_dialects() -> Result<()> {
+        let mysql = MySqlDialect {};
+        let unparser = Unparser::new(&mysql);
+        assert_eq!(
+            unparser
+                .sort_to_sql(&col("Value").sort(true, true))?
+                .to_string(),
+            "`Value` ASC"
+        );
+        assert!(unparser.expr_to_sql(&col("a").sort(true, true)).is_err());
+
+        let custom = CustomDialect {
+            identifier_quote_style: Some('\''),
+            ..Default::default()
+        };
+        let unparser = Unparser::new(&custom);
+        assert_eq!(
+            unparser.expr_to_sql(&col("a").eq(lit(1)))?.to_string(),
+            "'a' = 1"
+        );
+
+        let expr = col("s")
+            .eq(lit(r"a\b"))
+            .and(cast(col("a"), DataType::Int64).eq(lit(3i64)));
+        assert_eq!(
+            dialect_sql(&PostgreSqlDialect {}, &expr)?,
+            r"(s = 'a\b') AND (CAST(a AS BIGINT) = 3)"
+        );
+        assert_eq!(
+            dialect_sql(&mysql, &expr)?,
+            r"(s = 'a\\b') AND (CAST(a AS SIGNED) = 3)"
+        );
+        let date = lit(ScalarValue::Date32(Some(19000)));
+        assert_eq!(dialect_sql(&mysql, &date)?, "DATE '2022-01-08'");
+        let sqlite = SqliteDialect {};
+        assert_eq!(dialect_sql(&sqlite, &col("b").eq(lit(true)))?, "b = 1");
+        assert!(dialect_sql(&sqlite, &date).is_err());
+
+        // ILIKE and division are not supported by all dialects
+        let expr = col("s").ilike(lit("ab%"));
+        assert_eq!(dialect_sql(&PostgreSqlDialect {}, &expr)?, "s ILIKE 'ab%'");
+        assert!(dialect_sql(&sqlite, &expr).is_err());
+        let expr = col("a") / lit(2);
+        assert!(dialect_sql(&PostgreSqlDialect {}, &expr).is_ok());
+        assert!(dialect_sql(&mysql, &expr).is_err());
+        Ok(())
+    }
+
+    fn dialect_sql(dialect: &dyn Dialect, expr: &Expr) -> Result<String> {
+        Ok(Unparser::new(dialect).expr_to_sql(expr)?.to_string())
+    }
+
+    #[test]
+    fn expr_to_sql_unsupported() {
+        let exprs = vec![
+            Expr::Wildcard,
+            Expr::ScalarVariable(DataType::Utf8, vec!["@a".to_string()]),
+            Expr::Cast(Cast::new(
+                Box::new(col("a")),
+                DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+            )),
+            Expr::AggregateFunction(AggregateFunction {
+                fun: aggregate_function::AggregateFunction::Count,
+                args: vec![col("a")],
+                distinct: false,
+                filter: Some(Box::new(col("b"))),
+                order_by: None,
+            }),
+            Expr::WindowFunction(WindowFunction {
+                fun: window_function::WindowFunction::AggregateFunction(
+                    aggregate_function::AggregateFunction::Sum,
+                ),
+                args: vec![col("a")],
+                partition_by: vec![],
+                order_by: vec![col("b").sort(true, false)],
+                window_frame: WindowFrame {
+                    units: WindowFrameUnits::Range,
+                    start_bound: WindowFrameBound::Preceding(ScalarValue::Utf8(Some(
+                        "1 DAY".into(),
+                    ))),
+                    end_bound: WindowFrameBound::CurrentRow,
+                    exclude: WindowFrameExclude::NoOthers,
+                },
+            }),
+        ];
+        for expr in exprs {
+            assert!(
+                matches!(expr_to_sql(&expr), Err(DataFusionError::NotImplemented(_))),
+                "{expr:?}"
+            );
+        }
+    }
+}
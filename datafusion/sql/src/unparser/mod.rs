@@ -0,0 +1,67 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of [`LogicalPlan`]s and [`Expr`]s back into SQL, for example
+//! to run (parts of) a plan in another database.
+//!
+//! ```
+//! use datafusion_expr::{col, lit};
+//! use datafusion_sql::unparser::expr_to_sql;
+//!
+//! let expr = col("a").gt(lit(4)).and(col("b").is_null());
+//! let sql = expr_to_sql(&expr).unwrap().to_string();
+//! assert_eq!(sql, "(a > 4) AND (b IS NULL)");
+//! ```
+
+mod ast;
+pub mod dialect;
+mod expr;
+mod plan;
+
+use datafusion_common::Result;
+use datafusion_expr::{Expr, LogicalPlan};
+use sqlparser::ast as sql_ast;
+
+use self::dialect::{DefaultDialect, Dialect};
+
+/// Converts [`LogicalPlan`]s and [`Expr`]s into the SQL of a [`Dialect`]
+pub struct Unparser<'a> {
+    dialect: &'a dyn Dialect,
+}
+
+impl<'a> Unparser<'a> {
+    /// Create a new Unparser writing SQL for `dialect`
+    pub fn new(dialect: &'a dyn Dialect) -> Self {
+        Self { dialect }
+    }
+}
+
+impl Default for Unparser<'static> {
+    fn default() -> Self {
+        Self::new(&DefaultDialect {})
+    }
+}
+
+/// Converts `expr` into a SQL expression of the [`DefaultDialect`]
+pub fn expr_to_sql(expr: &Expr) -> Result<sql_ast::Expr> {
+    Unparser::default().expr_to_sql(expr)
+}
+
+/// Converts `plan` into a SQL query of the [`DefaultDialect`]
+pub fn plan_to_sql(plan: &LogicalPlan) -> Result<sql_ast::Statement> {
+    Unparser::default().plan_to_sql(plan)
+}
@@ -0,0 +1,503 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Unparsing of [`LogicalPlan`]s
+//!
+//! A plan is unparsed from its root, filling in the clauses of a `SELECT`
+//! with the operators met. Once an operator can not be written in the
+//! clauses left, such as a projection below another projection, the rest of
+//! the plan is unparsed as a subquery in the `FROM` clause.
+
+use std::collections::HashSet;
+
+use datafusion_common::{DFField, DFSchemaRef, DataFusionError, Result};
+use datafusion_expr::logical_plan::{Join, JoinType, Projection};
+use datafusion_expr::{and, Expr, LogicalPlan};
+use sqlparser::ast;
+
+use super::ast::{nested_join, QueryBuilder, SelectBuilder};
+use super::Unparser;
+use crate::utils::clone_with_replacement;
+
+impl Unparser<'_> {
+    /// Converts `plan` into a SQL query
+    pub fn plan_to_sql(&self, plan: &LogicalPlan) -> Result<ast::Statement> {
+        Ok(ast::Statement::Query(Box::new(self.plan_to_query(plan)?)))
+    }
+
+    pub(super) fn plan_to_query(&self, plan: &LogicalPlan) -> Result<ast::Query> {
+        let mut query = QueryBuilder::default();
+        let mut select = SelectBuilder::default();
+        self.select_to_sql(plan, &mut query, &mut select)?;
+        Ok(query.build(select))
+    }
+
+    fn select_to_sql(
+        &self,
+        plan: &LogicalPlan,
+        query: &mut QueryBuilder,
+        select: &mut SelectBuilder,
+    ) -> Result<()> {
+        match plan {
+            LogicalPlan::TableScan(scan) => {
+                self.default_projection(plan, select)?;
+                for filter in &scan.filters {
+                    select.and_selection(self.expr_to_sql(filter)?);
+                }
+                select.from = Some(ast::TableWithJoins {
+                    relation: self.table_to_sql(&scan.table_name, None),
+                    joins: vec![],
+                });
+                Ok(())
+            }
+            LogicalPlan::Projection(projection) => {
+                if select.already_projected()
+                    || select.selection.is_some()
+                    || select.group_by.is_some()
+                {
+                    return self.derive(plan, select);
+                }
+                // the columns only needed by an `ORDER BY` are computed by
+                // a projection below the sort
+                if let LogicalPlan::Sort(sort) = projection.input.as_ref() {
+                    if let LogicalPlan::Projection(inner) = sort.input.as_ref() {
+                        if query.order_by.is_empty() {
+                            let exprs = inline_projection(&projection.expr, inner)?;
+                            let sort_expr = inline_projection(&sort.expr, inner)?;
+                            query.order_by = unproject(&sort_expr, &inner.input)?
+                                .iter()
+                                .map(|expr| self.sort_to_sql(expr))
+                                .collect::<Result<_>>()?;
+                            if query.limit.is_none() {
+                                query.limit = sort.fetch.map(number);
+                            }
+                            select.projection =
+                                Some(self.select_items(&exprs, &inner.input)?);
+                            return self.select_to_sql(&inner.input, query, select);
+                        }
+                    }
+                }
+                select.projection =
+                    Some(self.select_items(&projection.expr, &projection.input)?);
+                self.select_to_sql(&projection.input, query, select)
+            }
+            LogicalPlan::Filter(filter) => {
+                match filter.input.as_ref() {
+                    LogicalPlan::Aggregate(_) if select.having.is_none() => {
+                        let predicate = unproject(
+                            std::slice::from_ref(&filter.predicate),
+                            &filter.input,
+                        )?;
+                        select.having = Some(self.expr_to_sql(&predicate[0])?);
+                    }
+                    // window functions are evaluated after the `WHERE` clause
+                    LogicalPlan::Aggregate(_) | LogicalPlan::Window(_) => {
+                        return self.derive(plan, select)
+                    }
+                    _ => select.and_selection(self.expr_to_sql(&filter.predicate)?),
+                }
+                self.select_to_sql(&filter.input, query, select)
+            }
+            LogicalPlan::Aggregate(aggregate) => {
+                if select.group_by.is_some() || select.selection.is_some() {
+                    return self.derive(plan, select);
+                }
+                if aggregate
+                    .group_expr
+                    .iter()
+                    .any(|expr| matches!(expr, Expr::GroupingSet(_)))
+                {
+                    return Err(DataFusionError::NotImplemented(
+                        "Unsupported plan: grouping sets can not be unparsed".to_string(),
+                    ));
+                }
+                if !select.already_projected() {
+                    select.projection =
+                        Some(self.select_items(&columns(plan.schema()), plan)?);
+                }
+                select.group_by = Some(
+                    aggregate
+                        .group_expr
+                        .iter()
+                        .map(|expr| self.expr_to_sql(expr))
+                        .collect::<Result<_>>()?,
+                );
+                self.select_to_sql(&aggregate.input, query, select)
+            }
+            LogicalPlan::Window(window) => {
+                if select.group_by.is_some() || select.selection.is_some() {
+                    return self.derive(plan, select);
+                }
+                if !select.already_projected() {
+                    select.projection =
+                        Some(self.select_items(&columns(plan.schema()), plan)?);
+                }
+                self.select_to_sql(&window.input, query, select)
+            }
+            LogicalPlan::Sort(sort) => {
+                if select.already_projected() || !query.order_by.is_empty() {
+                    return self.derive(plan, select);
+                }
+                let sort_expr = match sort.input.as_ref() {
+                    LogicalPlan::Projection(projection) => {
+                        let sort_expr = inline_projection(&sort.expr, projection)?;
+                        unproject(&sort_expr, &projection.input)?
+                    }
+                    input => unproject(&sort.expr, input)?,
+                };
+                query.order_by = sort_expr
+                    .iter()
+                    .map(|expr| self.sort_to_sql(expr))
+                    .collect::<Result<_>>()?;
+                if query.limit.is_none() {
+                    query.limit = sort.fetch.map(number);
+                }
+                self.select_to_sql(&sort.input, query, select)
+            }
+            LogicalPlan::Limit(limit) => {
+                if select.already_projected() || query.has_modifiers() {
+                    return self.derive(plan, select);
+                }
+                query.limit = limit.fetch.map(number);
+                if limit.skip > 0 {
+                    query.offset = Some(ast::Offset {
+                        value: number(limit.skip),
+                        rows: ast::OffsetRows::None,
+                    });
+                }
+                self.select_to_sql(&limit.input, query, select)
+            }
+            LogicalPlan::Distinct(distinct) => {
+                if let LogicalPlan::Union(union) = distinct.input.as_ref() {
+                    if select.is_empty() && query.body.is_none() {
+                        query.body = Some(self.union_to_sql(&union.inputs, true)?);
+                        return Ok(());
+                    }
+                }
+                if select.already_projected() || !select.is_empty() {
+                    return self.derive(plan, select);
+                }
+                select.distinct = true;
+                self.select_to_sql(&distinct.input, query, select)
+            }
+            LogicalPlan::Union(union) => {
+                if !select.is_empty() || query.body.is_some() {
+                    return self.derive(plan, select);
+                }
+                query.body = Some(self.union_to_sql(&union.inputs, false)?);
+                Ok(())
+            }
+            LogicalPlan::SubqueryAlias(_)
+            | LogicalPlan::Join(_)
+            | LogicalPlan::CrossJoin(_) => {
+                self.default_projection(plan, select)?;
+                select.from = Some(self.join_to_sql(plan)?);
+                Ok(())
+            }
+            LogicalPlan::EmptyRelation(empty)
+                if empty.produce_one_row && select.already_projected() =>
+            {
+                Ok(())
+            }
+            LogicalPlan::Subquery(subquery) => {
+                self.select_to_sql(&subquery.subquery, query, select)
+            }
+            LogicalPlan::Repartition(repartition) => {
+                self.select_to_sql(&repartition.input, query, select)
+            }
+            // the plan of a common table expression is unparsed for each reference
+            LogicalPlan::SharedCte(cte) => self.select_to_sql(&cte.input, query, select),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "Unsupported plan: {plan:?}"
+            ))),
+        }
+    }
+
+    /// Unparses `plan` as a subquery in the `FROM` clause of `select`
+    fn derive(&self, plan: &LogicalPlan, select: &mut SelectBuilder) -> Result<()> {
+        if !select.already_projected() {
+            select.projection = Some(
+                columns(plan.schema())
+                    .iter()
+                    .map(|expr| self.select_item_to_sql(expr))
+                    .collect::<Result<_>>()?,
+            );
+        }
+        select.from = Some(ast::TableWithJoins {
+            relation: self.derived_to_sql(plan, derived_alias(plan)?)?,
+            joins: vec![],
+        });
+        Ok(())
+    }
+
+    /// Selects the columns of `plan` if `select` has no projection yet
+    fn default_projection(
+        &self,
+        plan: &LogicalPlan,
+        select: &mut SelectBuilder,
+    ) -> Result<()> {
+        if !select.already_projected() {
+            select.projection = Some(self.select_items(&columns(plan.schema()), plan)?);
+        }
+        Ok(())
+    }
+
+    /// Converts `exprs`, computed from the output of `plan`, into a `SELECT`
+    /// list
+    fn select_items(
+        &self,
+        exprs: &[Expr],
+        plan: &LogicalPlan,
+    ) -> Result<Vec<ast::SelectItem>> {
+        unproject(exprs, plan)?
+            .iter()
+            .map(|expr| self.select_item_to_sql(expr))
+            .collect()
+    }
+
+    fn union_to_sql(
+        &self,
+        inputs: &[std::sync::Arc<LogicalPlan>],
+        distinct: bool,
+    ) -> Result<ast::SetExpr> {
+        let set_quantifier = if distinct {
+            ast::SetQuantifier::None
+        } else {
+            ast::SetQuantifier::All
+        };
+        inputs
+            .iter()
+            .map(|input| {
+                let query = self.plan_to_query(input)?;
+                Ok(match query {
+                    ast::Query {
+                        with: None,
+                        body,
+                        order_by,
+                        limit: None,
+                        offset: None,
+                        fetch: None,
+                        ..
+                    } if order_by.is_empty() => *body,
+                    query => ast::SetExpr::Query(Box::new(query)),
+                })
+            })
+            .reduce(|left, right| {
+                Ok(ast::SetExpr::SetOperation {
+                    op: ast::SetOperator::Union,
+                    set_quantifier,
+                    left: Box::new(left?),
+                    right: Box::new(right?),
+                })
+            })
+            .unwrap_or_else(|| {
+                Err(DataFusionError::Internal(
+                    "A union must have at least one input".to_string(),
+                ))
+            })
+    }
+
+    fn join_to_sql(&self, plan: &LogicalPlan) -> Result<ast::TableWithJoins> {
+        match plan {
+            LogicalPlan::Join(join) => {
+                let mut table_with_joins = self.join_to_sql(&join.left)?;
+                table_with_joins.joins.push(ast::Join {
+                    relation: self.relation_to_sql(&join.right)?,
+                    join_operator: self.join_operator_to_sql(join)?,
+                });
+                Ok(table_with_joins)
+            }
+            LogicalPlan::CrossJoin(cross_join) => {
+                let mut table_with_joins = self.join_to_sql(&cross_join.left)?;
+                table_with_joins.joins.push(ast::Join {
+                    relation: self.relation_to_sql(&cross_join.right)?,
+                    join_operator: ast::JoinOperator::CrossJoin,
+                });
+                Ok(table_with_joins)
+            }
+            _ => Ok(ast::TableWithJoins {
+                relation: self.relation_to_sql(plan)?,
+                joins: vec![],
+            }),
+        }
+    }
+
+    fn join_operator_to_sql(&self, join: &Join) -> Result<ast::JoinOperator> {
+        let condition = join
+            .on
+            .iter()
+            .map(|(left, right)| left.clone().eq(right.clone()))
+            .chain(join.filter.clone())
+            .reduce(and);
+        let constraint = match &condition {
+            Some(condition) => ast::JoinConstraint::On(self.expr_to_sql(condition)?),
+            None => ast::JoinConstraint::On(ast::Expr::Value(ast::Value::Boolean(true))),
+        };
+        match join.join_type {
+            JoinType::Inner if condition.is_none() => Ok(ast::JoinOperator::CrossJoin),
+            JoinType::Inner => Ok(ast::JoinOperator::Inner(constraint)),
+            JoinType::Left => Ok(ast::JoinOperator::LeftOuter(constraint)),
+            JoinType::Right => Ok(ast::JoinOperator::RightOuter(constraint)),
+            JoinType::Full => Ok(ast::JoinOperator::FullOuter(constraint)),
+            JoinType::LeftSemi
+            | JoinType::RightSemi
+            | JoinType::LeftAnti
+            | JoinType::RightAnti
+            | JoinType::LeftMark => Err(DataFusionError::NotImplemented(format!(
+                "Unsupported join type: {}",
+                join.join_type
+            ))),
+        }
+    }
+
+    /// Converts `plan` into a relation of the `FROM` clause
+    fn relation_to_sql(&self, plan: &LogicalPlan) -> Result<ast::TableFactor> {
+        match plan {
+            LogicalPlan::TableScan(scan) if scan.filters.is_empty() => {
+                Ok(self.table_to_sql(&scan.table_name, None))
+            }
+            LogicalPlan::SubqueryAlias(subquery_alias) => {
+                match subquery_alias.input.as_ref() {
+                    LogicalPlan::TableScan(scan) if scan.filters.is_empty() => {
+                        Ok(self
+                            .table_to_sql(&scan.table_name, Some(&subquery_alias.alias)))
+                    }
+                    input => self.derived_to_sql(input, subquery_alias.alias.clone()),
+                }
+            }
+            LogicalPlan::Join(_) | LogicalPlan::CrossJoin(_) => {
+                Ok(nested_join(self.join_to_sql(plan)?))
+            }
+            LogicalPlan::SharedCte(cte) => self.relation_to_sql(&cte.input),
+            _ => self.derived_to_sql(plan, derived_alias(plan)?),
+        }
+    }
+
+    fn table_to_sql(&self, name: &str, alias: Option<&str>) -> ast::TableFactor {
+        ast::TableFactor::Table {
+            name: self.object_name(name),
+            alias: alias.map(|alias| ast::TableAlias {
+                name: self.new_ident(alias),
+                columns: vec![],
+            }),
+            args: None,
+            with_hints: vec![],
+        }
+    }
+
+    fn derived_to_sql(
+        &self,
+        plan: &LogicalPlan,
+        alias: String,
+    ) -> Result<ast::TableFactor> {
+        Ok(ast::TableFactor::Derived {
+            lateral: false,
+            subquery: Box::new(self.plan_to_query(plan)?),
+            alias: Some(ast::TableAlias {
+                name: self.new_ident(&alias),
+                columns: vec![],
+            }),
+        })
+    }
+}
+
+fn number(value: usize) -> ast::Expr {
+    ast::Expr::Value(ast::Value::Number(value.to_string(), false))
+}
+
+fn columns(schema: &DFSchemaRef) -> Vec<Expr> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| Expr::Column(field.qualified_column()))
+        .collect()
+}
+
+/// Returns the alias of the subquery computing `plan`, which must be the
+/// relation its columns are qualified with so that they can be referred to
+fn derived_alias(plan: &LogicalPlan) -> Result<String> {
+    let qualifiers: HashSet<&String> = plan
+        .schema()
+        .fields()
+        .iter()
+        .filter_map(|field| field.qualifier())
+        .collect();
+    match qualifiers.into_iter().collect::<Vec<_>>().as_slice() {
+        [] => Ok("derived".to_string()),
+        [qualifier] if !qualifier.contains('.') => Ok(qualifier.to_string()),
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Unsupported plan: the subquery computing {plan:?} needs an alias"
+        ))),
+    }
+}
+
+/// Replaces the columns of `exprs` computed by `projection` with the
+/// expressions computing them
+fn inline_projection(exprs: &[Expr], projection: &Projection) -> Result<Vec<Expr>> {
+    exprs
+        .iter()
+        .map(|expr| replace_columns(expr, projection.schema.fields(), &projection.expr))
+        .collect()
+}
+
+/// Replaces the columns of `exprs` computed by the aggregations and window
+/// functions of `plan` with the expressions computing them, as they can only
+/// be referred to by their expressions in SQL
+fn unproject(exprs: &[Expr], plan: &LogicalPlan) -> Result<Vec<Expr>> {
+    match plan {
+        LogicalPlan::Aggregate(aggregate) => {
+            let aggregate_expr: Vec<Expr> = aggregate
+                .group_expr
+                .iter()
+                .chain(aggregate.aggr_expr.iter())
+                .cloned()
+                .collect();
+            exprs
+                .iter()
+                .map(|expr| {
+                    replace_columns(expr, aggregate.schema.fields(), &aggregate_expr)
+                })
+                .collect()
+        }
+        LogicalPlan::Window(window) => {
+            let fields = window.schema.fields();
+            let window_fields = &fields[fields.len() - window.window_expr.len()..];
+            let exprs = exprs
+                .iter()
+                .map(|expr| replace_columns(expr, window_fields, &window.window_expr))
+                .collect::<Result<Vec<_>>>()?;
+            unproject(&exprs, &window.input)
+        }
+        LogicalPlan::Filter(filter) => unproject(exprs, &filter.input),
+        _ => Ok(exprs.to_vec()),
+    }
+}
+
+/// Replaces the columns of `expr` for `fields` with the matching `exprs`
+fn replace_columns(expr: &Expr, fields: &[DFField], exprs: &[Expr]) -> Result<Expr> {
+    clone_with_replacement(expr, &|expr| match expr {
+        Expr::Column(col) => Ok(fields
+            .iter()
+            .zip(exprs)
+            .find(|(field, _)| {
+                field.qualified_column() == *col
+                    || (col.relation.is_none() && field.name() == &col.name)
+            })
+            .map(|(_, expr)| expr.clone())),
+        _ => Ok(None),
+    })
+}
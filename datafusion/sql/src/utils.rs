@@ -18,7 +18,7 @@
 //! SQL Utility Functions
 
 use arrow_schema::{DataType, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE};
-use sqlparser::ast::Ident;
+use sqlparser::ast::{Expr as SQLExpr, Ident, ObjectName};
 
 use datafusion_common::{DataFusionError, Result, ScalarValue};
 use datafusion_expr::expr::{
@@ -150,7 +150,7 @@ fn check_column_satisfies_expr(
 ///       cloning/replacement.
 /// * `Err(err)`: Any error returned by the function is returned as-is by
 ///       `clone_with_replacement()`.
-fn clone_with_replacement<F>(expr: &Expr, replacement_fn: &F) -> Result<Expr>
+pub(crate) fn clone_with_replacement<F>(expr: &Expr, replacement_fn: &F) -> Result<Expr>
 where
     F: Fn(&Expr) -> Result<Option<Expr>>,
 {
@@ -168,6 +168,7 @@ where
                 args,
                 distinct,
                 filter,
+                order_by,
             }) => Ok(Expr::AggregateFunction(AggregateFunction::new(
                 fun.clone(),
                 args.iter()
@@ -175,6 +176,15 @@ where
                     .collect::<Result<Vec<Expr>>>()?,
                 *distinct,
                 filter.clone(),
+                order_by
+                    .as_ref()
+                    .map(|order_by| {
+                        order_by
+                            .iter()
+                            .map(|e| clone_with_replacement(e, replacement_fn))
+                            .collect::<Result<Vec<Expr>>>()
+                    })
+                    .transpose()?,
             ))),
             Expr::WindowFunction(WindowFunction {
                 fun,
@@ -537,6 +547,16 @@ pub(crate) fn make_decimal_type(
     }
 }
 
+/// Returns true if `expr` is the `ALL` of `GROUP BY ALL` or `ORDER BY ALL`, that
+/// is parsed as an unquoted identifier
+pub(crate) fn is_all_keyword(expr: &SQLExpr) -> bool {
+    matches!(
+        expr,
+        SQLExpr::Identifier(ident)
+            if ident.quote_style.is_none() && ident.value.eq_ignore_ascii_case("all")
+    )
+}
+
 // Normalize an owned identifier to a lowercase string unless the identifier is quoted.
 pub(crate) fn normalize_ident(id: Ident) -> String {
     match id.quote_style {
@@ -544,3 +564,17 @@ pub(crate) fn normalize_ident(id: Ident) -> String {
         None => id.value.to_ascii_lowercase(),
     }
 }
+
+/// The name a function is registered under, as looked up when planning
+/// function calls: its normalized identifiers joined by `.`, such as
+/// `my_schema.my_func` for a function of the schema `my_schema`.
+///
+/// The names of the functions are case insensitive, whatever the
+/// normalization of the other identifiers
+pub(crate) fn function_name(name: ObjectName) -> String {
+    name.0
+        .into_iter()
+        .map(normalize_ident)
+        .collect::<Vec<_>>()
+        .join(".")
+}
@@ -16,9 +16,9 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use arrow_schema::DataType;
-use datafusion_common::{DFSchema, DataFusionError, Result};
-use datafusion_expr::{lit, Cast, Expr, LogicalPlan, LogicalPlanBuilder};
+use arrow_schema::{DataType, SchemaRef};
+use datafusion_common::{DFSchema, DataFusionError, Result, ScalarValue};
+use datafusion_expr::{lit, values_schema, Cast, Expr, LogicalPlan, LogicalPlanBuilder};
 use sqlparser::ast::{Expr as SQLExpr, Values as SQLValues};
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -77,6 +77,59 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     .collect::<Result<Vec<_>>>()
             })
             .collect::<Result<Vec<_>>>()?;
+
+        let threshold = self
+            .schema_provider
+            .options()
+            .sql_parser
+            .values_table_threshold;
+        if values.len() > threshold {
+            // build the columns of a large list of literals directly, rather
+            // than evaluating one expression per value
+            let rows = values
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|v| match v {
+                            Expr::Literal(value) => Some(value.clone()),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()
+                })
+                .collect::<Option<Vec<_>>>();
+            if let Some(rows) = rows {
+                if let Some(plan) = self.values_table_to_plan(&rows)? {
+                    return Ok(plan);
+                }
+            }
+        }
         LogicalPlanBuilder::values(values)?.build()
     }
+
+    /// Plans a VALUES list of literals as a scan of an in-memory table, if the
+    /// context provider supports it. The values are coerced to the types of
+    /// their columns like by [`LogicalPlanBuilder::values`].
+    fn values_table_to_plan(
+        &self,
+        rows: &[Vec<ScalarValue>],
+    ) -> Result<Option<LogicalPlan>> {
+        let row_types = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| match value {
+                        ScalarValue::Null => None,
+                        value => Some(value.get_datatype()),
+                    })
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let schema: SchemaRef = values_schema(&row_types)?.into();
+        match self.schema_provider.create_values_table(schema, rows)? {
+            Some(source) => Ok(Some(
+                LogicalPlanBuilder::scan("values", source, None)?.build()?,
+            )),
+            None => Ok(None),
+        }
+    }
 }
@@ -33,6 +33,7 @@ use datafusion_expr::TableSource;
 use datafusion_expr::{AggregateUDF, ScalarUDF};
 use datafusion_sql::parser::DFParser;
 use datafusion_sql::planner::{ContextProvider, ParserOptions, SqlToRel};
+use datafusion_sql::unparser::{plan_to_sql, Unparser};
 
 #[test]
 fn parse_decimals() {
@@ -56,11 +57,72 @@ fn parse_decimals() {
             &expected,
             ParserOptions {
                 parse_float_as_decimal: true,
+                enable_lateral_column_alias: false,
+                enable_ident_normalization: true,
             },
         );
     }
 }
 
+#[test]
+fn select_lateral_column_alias() {
+    let options = || ParserOptions {
+        parse_float_as_decimal: false,
+        enable_lateral_column_alias: true,
+        enable_ident_normalization: true,
+    };
+    quick_test_with_options(
+        "SELECT age + 1 AS a1, a1 * 2 AS a2 FROM person WHERE a2 > 10",
+        "Projection: person.age + Int64(1) AS a1, (person.age + Int64(1)) * Int64(2) AS a2\
+             \n  Filter: (person.age + Int64(1)) * Int64(2) > Int64(10)\
+             \n    TableScan: person",
+        options(),
+    );
+
+    // the columns of the input take precedence over the aliases
+    quick_test_with_options(
+        "SELECT age AS id, id + 1 FROM person",
+        "Projection: person.age AS id, person.id + Int64(1)\
+             \n  TableScan: person",
+        options(),
+    );
+
+    quick_test_with_options(
+        "SELECT MAX(age) AS m, m + 1 FROM person",
+        "Projection: MAX(person.age) AS m, MAX(person.age) + Int64(1)\
+             \n  Aggregate: groupBy=[[]], aggr=[[MAX(person.age)]]\
+             \n    TableScan: person",
+        options(),
+    );
+
+    // disabled by default
+    assert!(logical_plan("SELECT age + 1 AS a1, a1 * 2 FROM person").is_err());
+}
+
+#[test]
+fn select_without_ident_normalization() {
+    let options = || ParserOptions {
+        parse_float_as_decimal: false,
+        enable_lateral_column_alias: false,
+        enable_ident_normalization: false,
+    };
+    quick_test_with_options(
+        "SELECT age AS Age_Col, \"first_name\" FROM person",
+        "Projection: person.age AS Age_Col, person.first_name\
+             \n  TableScan: person",
+        options(),
+    );
+
+    // the case of unquoted identifiers is preserved
+    assert!(logical_plan_with_options("SELECT Age FROM person", options()).is_err());
+    assert!(logical_plan_with_options("SELECT age FROM Person", options()).is_err());
+    quick_test(
+        "SELECT Age AS Age_Col FROM Person",
+        "Projection: person.age AS age_col\
+             \n  TableScan: person",
+    );
+}
+
 #[test]
 fn select_no_relation() {
     quick_test(
@@ -310,6 +372,59 @@ fn select_between_negated() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn select_row_value_equality() {
+    let sql = "SELECT id FROM person WHERE (age, state) = (21, 'CO')";
+    let expected = "Projection: person.id\
+            \n  Filter: person.age = Int64(21) AND person.state = Utf8(\"CO\")\
+            \n    TableScan: person";
+
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_row_value_comparison() {
+    let sql = "SELECT id FROM person WHERE (age, state, id) > (21, 'CO', 10)";
+    let expected = "Projection: person.id\
+            \n  Filter: person.age > Int64(21) OR person.age = Int64(21) AND (person.state > Utf8(\"CO\") OR person.state = Utf8(\"CO\") AND person.id > Int64(10))\
+            \n    TableScan: person";
+
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_row_value_is_distinct_from() {
+    let sql = "SELECT id FROM person WHERE (age, state) IS DISTINCT FROM (21, 'CO')";
+    let expected = "Projection: person.id\
+            \n  Filter: (person.age IS DISTINCT FROM Int64(21)) OR (person.state IS DISTINCT FROM Utf8(\"CO\"))\
+            \n    TableScan: person";
+
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_row_value_in_list() {
+    let sql = "SELECT id FROM person WHERE (age, state) IN ((21, 'CO'), (30, 'MA'))";
+    let expected = "Projection: person.id\
+            \n  Filter: person.age = Int64(21) AND person.state = Utf8(\"CO\") OR person.age = Int64(30) AND person.state = Utf8(\"MA\")\
+            \n    TableScan: person";
+
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_row_value_of_different_lengths() {
+    let sql = "SELECT id FROM person WHERE (age, state) = (21, 'CO', 1)";
+    let expected = "Error during planning: Can't compare a row value of 2 fields with a row value of 3 fields";
+    let result = logical_plan(sql).err().unwrap();
+    assert_eq!(result.to_string(), expected);
+
+    let sql = "SELECT id FROM person WHERE (age, state) IN ((21, 'CO'), 30)";
+    let expected = "Error during planning: IN list of a row value can only contain row values, found 30";
+    let result = logical_plan(sql).err().unwrap();
+    assert_eq!(result.to_string(), expected);
+}
+
 #[test]
 fn select_nested() {
     let sql = "SELECT fn2, last_name
@@ -1076,6 +1191,32 @@ fn select_simple_aggregate_with_groupby_position_out_of_range() {
         );
 }
 
+#[test]
+fn select_simple_aggregate_with_groupby_all() {
+    quick_test(
+        "SELECT state AS s, age, MIN(id) FROM person GROUP BY ALL",
+        "Projection: person.state AS s, person.age, MIN(person.id)\
+             \n  Aggregate: groupBy=[[person.state, person.age]], aggr=[[MIN(person.id)]]\
+             \n    TableScan: person",
+    );
+
+    // by the columns referenced outside of the aggregates
+    quick_test(
+        "SELECT age + MAX(age), state, MIN(id) + 1 FROM person GROUP BY ALL",
+        "Projection: person.age + MAX(person.age), person.state, MIN(person.id) + Int64(1)\
+             \n  Aggregate: groupBy=[[person.age, person.state]], aggr=[[MAX(person.age), MIN(person.id)]]\
+             \n    TableScan: person",
+    );
+
+    // without any expression to group by
+    quick_test(
+        "SELECT MIN(id) FROM person GROUP BY ALL",
+        "Projection: MIN(person.id)\
+             \n  Aggregate: groupBy=[[]], aggr=[[MIN(person.id)]]\
+             \n    TableScan: person",
+    );
+}
+
 #[test]
 fn select_simple_aggregate_with_groupby_can_use_alias() {
     quick_test(
@@ -1213,6 +1354,56 @@ fn test_wildcard() {
         );
 }
 
+#[test]
+fn test_wildcard_exclude() {
+    quick_test(
+        "SELECT * EXCLUDE (first_name, \"😀\") from person",
+        "Projection: person.id, person.last_name, person.age, person.state, person.salary, person.birth_date\
+            \n  TableScan: person",
+    );
+    quick_test(
+        "SELECT person.* EXCEPT (age) from person",
+        "Projection: person.id, person.first_name, person.last_name, person.state, person.salary, person.birth_date, person.😀\
+            \n  TableScan: person",
+    );
+}
+
+#[test]
+fn test_wildcard_rename() {
+    quick_test(
+        "SELECT * EXCLUDE (id, last_name, age, salary, birth_date, \"😀\") RENAME (first_name AS name) from person",
+        "Projection: person.first_name AS name, person.state\
+            \n  TableScan: person",
+    );
+}
+
+#[test]
+fn test_wildcard_options_unknown_column() {
+    let err = logical_plan("SELECT * EXCLUDE (nope) from person")
+        .expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"Column nope of the wildcard options is not a column of the wildcard\")",
+        format!("{err:?}")
+    );
+
+    let err = logical_plan("SELECT * EXCLUDE (age) RENAME (age AS a) from person")
+        .expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"Column age is excluded or renamed more than once in the wildcard options\")",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn test_unknown_table_function() {
+    let err =
+        logical_plan("SELECT * FROM nope(1, 'a')").expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"table function 'nope' not found\")",
+        format!("{err:?}")
+    );
+}
+
 #[test]
 fn select_count_one() {
     let sql = "SELECT COUNT(1) FROM person";
@@ -1304,6 +1495,16 @@ fn select_order_by_multiple_index() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn select_order_by_all() {
+    quick_test(
+        "SELECT id, state AS s FROM person ORDER BY ALL DESC",
+        "Sort: person.id DESC NULLS FIRST, s DESC NULLS FIRST\
+            \n  Projection: person.id, person.state AS s\
+            \n    TableScan: person",
+    );
+}
+
 #[test]
 fn select_order_by_index_of_0() {
     let sql = "SELECT id FROM person ORDER BY 0";
@@ -1342,6 +1543,15 @@ fn select_order_by_desc() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn select_array_agg_order_by() {
+    let sql = "SELECT ARRAY_AGG(first_name ORDER BY age DESC) FROM person";
+    let expected = "Projection: ARRAYAGG(person.first_name) ORDER BY [person.age DESC NULLS FIRST]\
+                        \n  Aggregate: groupBy=[[]], aggr=[[ARRAYAGG(person.first_name) ORDER BY [person.age DESC NULLS FIRST]]]\
+                        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
 #[test]
 fn select_order_by_nulls_last() {
     quick_test(
@@ -1489,6 +1699,25 @@ fn create_external_table_parquet_no_schema() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn transaction_statements() {
+    quick_test("BEGIN", "Transaction: BEGIN");
+    quick_test("START TRANSACTION", "Transaction: BEGIN");
+    quick_test("COMMIT", "Transaction: COMMIT");
+    quick_test("ROLLBACK WORK", "Transaction: ROLLBACK");
+    quick_test("SAVEPOINT S1", "Transaction: SAVEPOINT s1");
+    quick_test(
+        "ROLLBACK TO SAVEPOINT \"S1\"",
+        "Transaction: ROLLBACK TO SAVEPOINT S1",
+    );
+    quick_test("RELEASE s1", "Transaction: RELEASE SAVEPOINT s1");
+
+    let err = logical_plan("COMMIT AND CHAIN")
+        .expect_err("query should have failed")
+        .to_string();
+    assert!(err.contains("COMMIT AND CHAIN is not supported"));
+}
+
 #[test]
 fn equijoin_explicit_syntax() {
     let sql = "SELECT id, order_id \
@@ -2188,6 +2417,124 @@ fn select_multibyte_column() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn roundtrip_plan_to_sql() {
+    let tests = [
+        (
+            "SELECT id, first_name FROM person WHERE age > 20 AND state = 'CO'",
+            "SELECT person.id, person.first_name FROM person \
+             WHERE (person.age > 20) AND (person.state = 'CO')",
+        ),
+        (
+            "SELECT first_name FROM person ORDER BY age DESC LIMIT 3",
+            "SELECT person.first_name FROM person \
+             ORDER BY person.age DESC NULLS FIRST LIMIT 3",
+        ),
+        (
+            "SELECT DISTINCT state FROM person",
+            "SELECT DISTINCT person.state FROM person",
+        ),
+        (
+            "SELECT state, MAX(age) + 1 AS m FROM person \
+             GROUP BY state HAVING SUM(salary) > 1000",
+            "SELECT person.state, MAX(person.age) + 1 AS m FROM person \
+             GROUP BY person.state HAVING SUM(person.salary) > 1000",
+        ),
+        (
+            "SELECT p.id, o.order_id FROM person p \
+             JOIN orders o ON p.id = o.customer_id AND o.qty > 1 \
+             LEFT JOIN lineitem l ON o.o_item_id = l.l_description",
+            "SELECT p.id, o.order_id FROM person AS p \
+             JOIN orders AS o ON (p.id = o.customer_id) AND (o.qty > 1) \
+             LEFT JOIN lineitem AS l ON o.o_item_id = l.l_description",
+        ),
+        (
+            "SELECT j1_id FROM j1 CROSS JOIN j2",
+            "SELECT j1.j1_id FROM j1 CROSS JOIN j2",
+        ),
+        (
+            "SELECT id FROM person WHERE id IN (SELECT customer_id FROM orders) \
+             AND EXISTS (SELECT 1 FROM orders WHERE orders.customer_id = person.id)",
+            "SELECT person.id FROM person \
+             WHERE (person.id IN (SELECT orders.customer_id FROM orders)) \
+             AND EXISTS (SELECT 1 FROM orders WHERE orders.customer_id = person.id)",
+        ),
+        (
+            "SELECT id, (SELECT MAX(qty) FROM orders) FROM person",
+            "SELECT person.id, (SELECT MAX(orders.qty) FROM orders) FROM person",
+        ),
+        (
+            "SELECT s.a FROM (SELECT id + 1 AS a FROM person) AS s WHERE s.a > 2",
+            "SELECT s.a FROM (SELECT person.id + 1 AS a FROM person) AS s \
+             WHERE s.a > 2",
+        ),
+        (
+            "SELECT * FROM (SELECT id FROM person LIMIT 5) AS t ORDER BY id",
+            "SELECT t.id FROM (SELECT person.id FROM person LIMIT 5) AS t \
+             ORDER BY t.id ASC NULLS LAST",
+        ),
+        (
+            "SELECT id, ROW_NUMBER() OVER (PARTITION BY state ORDER BY age) AS rn, \
+             SUM(salary) OVER (ORDER BY age ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) \
+             FROM person",
+            "SELECT person.id, \
+             ROW_NUMBER() OVER (PARTITION BY person.state ORDER BY person.age ASC NULLS LAST) AS rn, \
+             SUM(person.salary) OVER (ORDER BY person.age ASC NULLS LAST \
+             ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) FROM person",
+        ),
+        (
+            "SELECT id FROM person UNION SELECT order_id FROM orders ORDER BY id LIMIT 3",
+            "SELECT person.id FROM person UNION SELECT orders.order_id FROM orders \
+             ORDER BY id ASC NULLS LAST LIMIT 3",
+        ),
+        (
+            "SELECT CASE WHEN age > 30 THEN 'old' ELSE 'young' END, \
+             CAST(age AS BIGINT) FROM person",
+            "SELECT CASE WHEN person.age > 30 THEN 'old' ELSE 'young' END, \
+             CAST(person.age AS BIGINT) FROM person",
+        ),
+        ("SELECT 1 + 2", "SELECT 1 + 2"),
+        (
+            "SELECT \"😀\", COUNT(DISTINCT state) FROM person GROUP BY \"😀\"",
+            "SELECT person.\"😀\", COUNT(DISTINCT person.state) FROM person \
+             GROUP BY person.\"😀\"",
+        ),
+    ];
+
+    for (sql, expected) in tests {
+        let plan = logical_plan(sql).unwrap();
+        let unparsed = plan_to_sql(&plan).unwrap().to_string();
+        assert_eq!(unparsed, expected);
+        // the unparsed query has the same plan as the original one
+        let roundtrip = logical_plan(&unparsed).unwrap();
+        assert_eq!(format!("{roundtrip:?}"), format!("{plan:?}"), "{sql}");
+    }
+}
+
+#[test]
+fn plan_to_sql_dialect() {
+    let plan = logical_plan(
+        "SELECT first_name AS \"Name\" FROM person ORDER BY \"Name\" NULLS FIRST",
+    )
+    .unwrap();
+    let dialect = datafusion_sql::unparser::dialect::MySqlDialect {};
+    let unparsed = Unparser::new(&dialect).plan_to_sql(&plan).unwrap();
+    assert_eq!(
+        unparsed.to_string(),
+        "SELECT person.first_name AS `Name` FROM person ORDER BY person.first_name ASC"
+    );
+}
+
+#[test]
+fn plan_to_sql_unsupported() {
+    let plan = logical_plan(
+        "SELECT state, age, COUNT(*) FROM person GROUP BY ROLLUP(state, age)",
+    )
+    .unwrap();
+    let err = plan_to_sql(&plan).unwrap_err();
+    assert!(matches!(err, DataFusionError::NotImplemented(_)), "{err}");
+}
+
 fn logical_plan(sql: &str) -> Result<LogicalPlan> {
     logical_plan_with_options(sql, ParserOptions::default())
 }
@@ -2570,9 +2917,10 @@ fn subquery_references_cte() {
         \n    Subquery:\
         \n      Projection: cte.id, cte.first_name, cte.last_name, cte.age, cte.state, cte.salary, cte.birth_date, cte.😀\
         \n        Filter: cte.id = person.id\
-        \n          SubqueryAlias: cte\
-        \n            Projection: person.id, person.first_name, person.last_name, person.age, person.state, person.salary, person.birth_date, person.😀\
-        \n              TableScan: person\
+        \n          SharedCte: cte\
+        \n            SubqueryAlias: cte\
+        \n              Projection: person.id, person.first_name, person.last_name, person.age, person.state, person.salary, person.birth_date, person.😀\
+        \n                TableScan: person\
         \n    TableScan: person";
 
     quick_test(sql, expected)
@@ -2587,9 +2935,10 @@ fn cte_with_no_column_names() {
         SELECT * FROM numbers;";
 
     let expected = "Projection: numbers.a, numbers.b, numbers.c\
-        \n  SubqueryAlias: numbers\
-        \n    Projection: Int64(1) AS a, Int64(2) AS b, Int64(3) AS c\
-        \n      EmptyRelation";
+        \n  SharedCte: numbers\
+        \n    SubqueryAlias: numbers\
+        \n      Projection: Int64(1) AS a, Int64(2) AS b, Int64(3) AS c\
+        \n        EmptyRelation";
 
     quick_test(sql, expected)
 }
@@ -2603,10 +2952,11 @@ fn cte_with_column_names() {
         SELECT * FROM numbers;";
 
     let expected = "Projection: a, b, c\
-        \n  Projection: numbers.Int64(1) AS a, numbers.Int64(2) AS b, numbers.Int64(3) AS c\
-        \n    SubqueryAlias: numbers\
-        \n      Projection: Int64(1), Int64(2), Int64(3)\
-        \n        EmptyRelation";
+        \n  SharedCte: numbers\
+        \n    Projection: numbers.Int64(1) AS a, numbers.Int64(2) AS b, numbers.Int64(3) AS c\
+        \n      SubqueryAlias: numbers\
+        \n        Projection: Int64(1), Int64(2), Int64(3)\
+        \n          EmptyRelation";
 
     quick_test(sql, expected)
 }
@@ -2621,13 +2971,51 @@ fn cte_with_column_aliases_precedence() {
         SELECT * FROM numbers;";
 
     let expected = "Projection: a, b, c\
-        \n  Projection: numbers.x AS a, numbers.y AS b, numbers.z AS c\
-        \n    SubqueryAlias: numbers\
-        \n      Projection: Int64(1) AS x, Int64(2) AS y, Int64(3) AS z\
-        \n        EmptyRelation";
+        \n  SharedCte: numbers\
+        \n    Projection: numbers.x AS a, numbers.y AS b, numbers.z AS c\
+        \n      SubqueryAlias: numbers\
+        \n        Projection: Int64(1) AS x, Int64(2) AS y, Int64(3) AS z\
+        \n          EmptyRelation";
     quick_test(sql, expected)
 }
 
+#[test]
+fn cte_referenced_twice() {
+    let sql = "WITH \
+        cte AS (SELECT id FROM person) \
+        SELECT a.id FROM cte a JOIN cte b ON a.id = b.id";
+
+    let expected = "Projection: a.id\
+        \n  Inner Join:  Filter: a.id = b.id\
+        \n    SubqueryAlias: a\
+        \n      SharedCte: cte\
+        \n        SubqueryAlias: cte\
+        \n          Projection: person.id\
+        \n            TableScan: person\
+        \n    SubqueryAlias: b\
+        \n      SharedCte: cte\
+        \n        SubqueryAlias: cte\
+        \n          Projection: person.id\
+        \n            TableScan: person";
+    quick_test(sql, expected);
+
+    // both references share the id of the CTE
+    let plan = logical_plan(sql).unwrap();
+    let mut ids = vec![];
+    collect_shared_cte_ids(&plan, &mut ids);
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids[0], ids[1]);
+}
+
+fn collect_shared_cte_ids(plan: &LogicalPlan, ids: &mut Vec<usize>) {
+    if let LogicalPlan::SharedCte(cte) = plan {
+        ids.push(cte.id);
+    }
+    for input in plan.inputs() {
+        collect_shared_cte_ids(input, ids);
+    }
+}
+
 #[test]
 fn cte_unbalanced_number_of_columns() {
     let sql = "WITH \
@@ -3085,7 +3473,7 @@ fn test_prepare_statement_to_plan_panic_prepare_wrong_syntax() {
 
 #[test]
 #[should_panic(
-    expected = "value: SchemaError(FieldNotFound { field: Column { relation: None, name: \"id\" }, valid_fields: Some([]) })"
+    expected = "SchemaError(FieldNotFound { field: Column { relation: None, name: \"id\" }, valid_fields: Some([]) })"
 )]
 fn test_prepare_statement_to_plan_panic_no_relation_and_constant_param() {
     let sql = "PREPARE my_plan(INT) AS SELECT id + $1";
@@ -3316,11 +3704,11 @@ fn test_prepare_statement_to_plan_multi_params() {
     // replace params with values
     let param_values = vec![
         ScalarValue::Int32(Some(10)),
-        ScalarValue::Utf8(Some("abc".to_string())),
+        ScalarValue::Utf8(Some("abc".into())),
         ScalarValue::Float64(Some(100.0)),
         ScalarValue::Int32(Some(20)),
         ScalarValue::Float64(Some(200.0)),
-        ScalarValue::Utf8(Some("xyz".to_string())),
+        ScalarValue::Utf8(Some("xyz".into())),
     ];
     let expected_plan =
             "Projection: person.id, person.age, Utf8(\"xyz\")\
@@ -3386,8 +3774,8 @@ fn test_prepare_statement_to_plan_value_list() {
     ///////////////////
     // replace params with values
     let param_values = vec![
-        ScalarValue::Utf8(Some("a".to_string())),
-        ScalarValue::Utf8(Some("b".to_string())),
+        ScalarValue::Utf8(Some("a".into())),
+        ScalarValue::Utf8(Some("b".into())),
     ];
     let expected_plan = "Projection: num, letter\
         \n  Projection: t.column1 AS num, t.column2 AS letter\
@@ -3450,7 +3838,7 @@ fn test_inner_join_with_cast_key() {
 }
 
 fn assert_field_not_found(err: DataFusionError, name: &str) {
-    match err {
+    match err.find_root() {
         DataFusionError::SchemaError { .. } => {
             let msg = format!("{err}");
             let expected = format!("Schema error: No field named '{name}'.");
@@ -41,7 +41,7 @@ use substrait::protobuf::{
     read_rel::ReadType,
     rel::RelType,
     sort_field::{SortDirection, SortKind::*},
-    AggregateFunction, Expression, Plan, Rel,
+    AggregateFunction, Expression, Plan, Rel, SortField,
 };
 
 use datafusion::logical_expr::expr::Sort;
@@ -193,45 +193,8 @@ pub async fn from_substrait_rel(
                 let input = LogicalPlanBuilder::from(
                     from_substrait_rel(ctx, input, extensions).await?,
                 );
-                let mut sorts: Vec<Expr> = vec![];
-                for s in &sort.sorts {
-                    let expr = from_substrait_rex(
-                        s.expr.as_ref().unwrap(),
-                        input.schema(),
-                        extensions,
-                    )
-                    .await?;
-                    let asc_nullfirst = match &s.sort_kind {
-                        Some(k) => match k {
-                            Direction(d) => {
-                                let direction : SortDirection = unsafe {
-                                    ::std::mem::transmute(*d)
-                                };
-                                match direction {
-                                    SortDirection::AscNullsFirst => Ok((true, true)),
-                                    SortDirection::AscNullsLast => Ok((true, false)),
-                                    SortDirection::DescNullsFirst => Ok((false, true)),
-                                    SortDirection::DescNullsLast => Ok((false, false)),
-                                    SortDirection::Clustered =>
-                                        Err(DataFusionError::NotImplemented("Sort with direction clustered is not yet supported".to_string()))
-                                    ,
-                                    SortDirection::Unspecified =>
-                                        Err(DataFusionError::NotImplemented("Unspecified sort direction is invalid".to_string()))
-                                }
-                            }
-                            ComparisonFunctionReference(_) => {
-                                Err(DataFusionError::NotImplemented("Sort using comparison function reference is not supported".to_string()))
-                            },
-                        },
-                        None => Err(DataFusionError::NotImplemented("Sort without sort kind is invalid".to_string()))
-                    };
-                    let (asc, nulls_first) = asc_nullfirst.unwrap();
-                    sorts.push(Expr::Sort(Sort {
-                        expr: Box::new(expr.as_ref().clone()),
-                        asc,
-                        nulls_first,
-                    }));
-                }
+                let sorts =
+                    from_substrait_sorts(&sort.sorts, input.schema(), extensions).await?;
                 input.sort(sorts)?.build()
             } else {
                 Err(DataFusionError::NotImplemented(
@@ -462,14 +425,69 @@ pub async fn from_substrait_agg_func(
         ))),
     };
 
+    let order_by = if f.sorts.is_empty() {
+        None
+    } else {
+        Some(from_substrait_sorts(&f.sorts, input_schema, extensions).await?)
+    };
+
     Ok(Arc::new(Expr::AggregateFunction(expr::AggregateFunction {
         fun: fun.unwrap(),
         args,
         distinct,
         filter,
+        order_by,
     })))
 }
 
+/// Convert Substrait SortFields to DataFusion sort expressions
+async fn from_substrait_sorts(
+    sorts: &[SortField],
+    input_schema: &DFSchema,
+    extensions: &HashMap<u32, &String>,
+) -> Result<Vec<Expr>> {
+    let mut exprs: Vec<Expr> = vec![];
+    for s in sorts {
+        let expr = from_substrait_rex(s.expr.as_ref().unwrap(), input_schema, extensions)
+            .await?;
+        let (asc, nulls_first) = match &s.sort_kind {
+            Some(k) => match k {
+                Direction(d) => {
+                    let direction: SortDirection = unsafe { ::std::mem::transmute(*d) };
+                    match direction {
+                        SortDirection::AscNullsFirst => Ok((true, true)),
+                        SortDirection::AscNullsLast => Ok((true, false)),
+                        SortDirection::DescNullsFirst => Ok((false, true)),
+                        SortDirection::DescNullsLast => Ok((false, false)),
+                        SortDirection::Clustered => Err(DataFusionError::NotImplemented(
+                            "Sort with direction clustered is not yet supported"
+                                .to_string(),
+                        )),
+                        SortDirection::Unspecified => {
+                            Err(DataFusionError::NotImplemented(
+                                "Unspecified sort direction is invalid".to_string(),
+                            ))
+                        }
+                    }
+                }
+                ComparisonFunctionReference(_) => Err(DataFusionError::NotImplemented(
+                    "Sort using comparison function reference is not supported"
+                        .to_string(),
+                )),
+            },
+            None => Err(DataFusionError::NotImplemented(
+                "Sort without sort kind is invalid".to_string(),
+            )),
+        }?;
+        exprs.push(Expr::Sort(Sort {
+            expr: Box::new(expr.as_ref().clone()),
+            asc,
+            nulls_first,
+        }));
+    }
+    Ok(exprs)
+}
+
 /// Convert Substrait Rex to DataFusion Expr
 #[async_recursion]
 pub async fn from_substrait_rex(
@@ -618,11 +636,11 @@ pub async fn from_substrait_rex(
             Some(LiteralType::Fp64(f)) => {
                 Ok(Arc::new(Expr::Literal(ScalarValue::Float64(Some(*f)))))
             }
-            Some(LiteralType::String(s)) => {
-                Ok(Arc::new(Expr::Literal(ScalarValue::Utf8(Some(s.clone())))))
-            }
+            Some(LiteralType::String(s)) => Ok(Arc::new(Expr::Literal(
+                ScalarValue::Utf8(Some(s.as_str().into())),
+            ))),
             Some(LiteralType::Binary(b)) => Ok(Arc::new(Expr::Literal(
-                ScalarValue::Binary(Some(b.clone())),
+                ScalarValue::Binary(Some(b.as_slice().into())),
             ))),
             _ => {
                 return Err(DataFusionError::NotImplemented(format!(
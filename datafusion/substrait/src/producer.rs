@@ -348,18 +348,25 @@ pub fn to_substrait_agg_measure(
     ),
 ) -> Result<Measure> {
     match expr {
-        Expr::AggregateFunction(expr::AggregateFunction { fun, args, distinct, filter }) => {
+        Expr::AggregateFunction(expr::AggregateFunction { fun, args, distinct, filter, order_by }) => {
             let mut arguments: Vec<FunctionArgument> = vec![];
             for arg in args {
                 arguments.push(FunctionArgument { arg_type: Some(ArgType::Value(to_substrait_rex(arg, schema, extension_info)?)) });
             }
+            let sorts = match order_by {
+                Some(order_by) => order_by
+                    .iter()
+                    .map(|e| substrait_sort_field(e, schema, extension_info))
+                    .collect::<Result<Vec<_>>>()?,
+                None => vec![],
+            };
             let function_name = fun.to_string().to_lowercase();
             let function_anchor = _register_function(function_name, extension_info);
             Ok(Measure {
                 measure: Some(AggregateFunction {
                     function_reference: function_anchor,
                     arguments,
-                    sorts: vec![],
+                    sorts,
                     output_type: None,
                     invocation: match distinct {
                         true => AggregationInvocation::Distinct as i32,
@@ -570,10 +577,14 @@ pub fn to_substrait_rex(
                 ScalarValue::Boolean(Some(b)) => Some(LiteralType::Boolean(*b)),
                 ScalarValue::Float32(Some(f)) => Some(LiteralType::Fp32(*f)),
                 ScalarValue::Float64(Some(f)) => Some(LiteralType::Fp64(*f)),
-                ScalarValue::Utf8(Some(s)) => Some(LiteralType::String(s.clone())),
-                ScalarValue::LargeUtf8(Some(s)) => Some(LiteralType::String(s.clone())),
-                ScalarValue::Binary(Some(b)) => Some(LiteralType::Binary(b.clone())),
-                ScalarValue::LargeBinary(Some(b)) => Some(LiteralType::Binary(b.clone())),
+                ScalarValue::Utf8(Some(s)) => Some(LiteralType::String(s.to_string())),
+                ScalarValue::LargeUtf8(Some(s)) => {
+                    Some(LiteralType::String(s.to_string()))
+                }
+                ScalarValue::Binary(Some(b)) => Some(LiteralType::Binary(b.to_vec())),
+                ScalarValue::LargeBinary(Some(b)) => {
+                    Some(LiteralType::Binary(b.to_vec()))
+                }
                 ScalarValue::Date32(Some(d)) => Some(LiteralType::Date(*d)),
                 _ => {
                     return Err(DataFusionError::NotImplemented(format!(
@@ -33,7 +33,7 @@ use datafusion::physical_expr::execution_props::ExecutionProps;
 use datafusion::physical_plan::file_format::{FileScanConfig, ParquetExec};
 use datafusion::physical_plan::filter::FilterExec;
 use datafusion::physical_plan::metrics::MetricsSet;
-use datafusion::physical_plan::ExecutionPlan;
+use datafusion::physical_plan::{ExecutionPlan, Statistics};
 use datafusion::prelude::{Expr, SessionConfig};
 use object_store::path::Path;
 use object_store::ObjectMeta;
@@ -134,8 +134,9 @@ impl TestParquetFile {
                 partition_values: vec![],
                 range: None,
                 extensions: None,
+                statistics: None,
             }]],
-            statistics: Default::default(),
+            statistics: Statistics::new_unknown(&self.schema),
             projection: None,
             limit: None,
             table_partition_cols: vec![],